@@ -55,7 +55,9 @@
 //!
 //! - [`archive`]: Async archive reader trait ([`AsyncArchiveReader`]) and trait definition
 //! - [`zip`]: Async ZIP implementation ([`AsyncZipArchive`]) using async-zip
-//! - [`loader`]: High-level model loading function ([`load_model_async`])
+//! - [`loader`]: High-level model loading functions ([`load_model_async`], and
+//!   [`load_model_async_limited`] and [`load_model_async_with_budget`] for untrusted input),
+//!   and [`load_package_async`] for models split across several model parts
 //!
 //! ## Runtime Requirements
 //!
@@ -78,6 +80,9 @@
 //! [`AsyncArchiveReader`]: archive::AsyncArchiveReader
 //! [`AsyncZipArchive`]: zip::AsyncZipArchive
 //! [`load_model_async`]: loader::load_model_async
+//! [`load_model_async_limited`]: loader::load_model_async_limited
+//! [`load_model_async_with_budget`]: loader::load_model_async_with_budget
+//! [`load_package_async`]: loader::load_package_async
 
 pub mod archive;
 pub mod loader;
@@ -19,6 +19,12 @@
 //! - **XML parsing**: Runs on blocking thread pool via `tokio::task::spawn_blocking`
 //! - **Memory**: Entire model XML is loaded into memory before parsing
 //!
+//! ## Multi-Part Packages
+//!
+//! [`load_model_async`] loads the main model part only. [`load_package_async`] also loads
+//! the model parts that Production Extension `path` attributes refer to, parsing up to
+//! [`PackageLoadOptions::max_concurrent_parts`] of them at once.
+//!
 //! ## Examples
 //!
 //! ### Basic Usage
@@ -59,12 +65,15 @@
 use crate::archive::AsyncArchiveReader;
 use crate::zip::AsyncZipArchive;
 use lib3mf_core::archive::opc::{Relationship, parse_relationships};
+use lib3mf_core::archive::{normalize_part_name, resolve_part_target};
 use lib3mf_core::error::{Lib3mfError, Result};
-use lib3mf_core::model::Model;
-use lib3mf_core::parser::model_parser::parse_model;
+use lib3mf_core::model::{Model, Package};
+use lib3mf_core::parser::model_parser::{parse_model, parse_model_with_budget};
+use std::collections::{HashSet, VecDeque};
 use std::io::Cursor;
 use std::path::Path;
 use tokio::fs::File;
+use tokio::task::{JoinError, JoinSet};
 
 /// Asynchronously loads a 3MF model from a file path.
 ///
@@ -77,7 +86,8 @@ use tokio::fs::File;
 ///
 /// # Returns
 ///
-/// A fully parsed [`Model`] containing all resources, build items, and metadata.
+/// A fully parsed [`Model`] containing all resources, build items, and metadata of the
+/// main model part. Use [`load_package_async`] to load the model parts it refers to as well.
 ///
 /// # Errors
 ///
@@ -146,15 +156,275 @@ use tokio::fs::File;
 /// [`Lib3mfError::InvalidStructure`]: lib3mf_core::error::Lib3mfError::InvalidStructure
 /// [`parse_model`]: lib3mf_core::parser::model_parser::parse_model
 pub async fn load_model_async<P: AsRef<Path>>(path: P) -> Result<Model> {
+    load_model_impl(path.as_ref(), None, None).await
+}
+
+/// Like [`load_model_async`], but refuses archive parts that decompress to more than
+/// `max_part_bytes`.
+///
+/// Intended for untrusted input such as uploads, where a small ZIP file could otherwise
+/// expand into gigabytes of XML (a "ZIP bomb").
+///
+/// # Errors
+///
+/// Returns [`Lib3mfError::Io`] with [`std::io::ErrorKind::FileTooLarge`] if the relationships
+/// or model part exceed the limit, and the same errors as [`load_model_async`] otherwise.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lib3mf_async::loader::load_model_async_limited;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // Accept model XML of up to 256 MiB.
+///     let model = load_model_async_limited("upload.3mf", 256 << 20).await?;
+///     println!("Loaded {} build items", model.build.items.len());
+///     Ok(())
+/// }
+/// ```
+///
+/// [`Lib3mfError::Io`]: lib3mf_core::error::Lib3mfError::Io
+pub async fn load_model_async_limited<P: AsRef<Path>>(
+    path: P,
+    max_part_bytes: u64,
+) -> Result<Model> {
+    load_model_impl(path.as_ref(), Some(max_part_bytes), None).await
+}
+
+/// Like [`load_model_async_limited`], but also refuses models whose mesh data would take
+/// more than `max_mesh_bytes` of memory once parsed.
+///
+/// The model part is parsed with [`parse_model_with_budget`], which fails as soon as the
+/// vertices, triangles and beams read so far pass the budget, so a multi-tenant server can
+/// bound the memory of each request rather than only the size of its upload.
+///
+/// # Errors
+///
+/// Returns [`Lib3mfError::Io`] with [`std::io::ErrorKind::FileTooLarge`] if a part or the
+/// mesh data exceeds its limit, and the same errors as [`load_model_async`] otherwise.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lib3mf_async::loader::load_model_async_with_budget;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // 256 MiB of XML, and at most 1 GiB of parsed mesh data.
+///     let model = load_model_async_with_budget("upload.3mf", 256 << 20, 1 << 30).await?;
+///     println!("Loaded {} objects", model.resources.iter_objects().count());
+///     Ok(())
+/// }
+/// ```
+///
+/// [`Lib3mfError::Io`]: lib3mf_core::error::Lib3mfError::Io
+/// [`parse_model_with_budget`]: lib3mf_core::parser::parse_model_with_budget
+pub async fn load_model_async_with_budget<P: AsRef<Path>>(
+    path: P,
+    max_part_bytes: u64,
+    max_mesh_bytes: u64,
+) -> Result<Model> {
+    load_model_impl(path.as_ref(), Some(max_part_bytes), Some(max_mesh_bytes)).await
+}
+
+/// Settings for [`load_package_async_with_options`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackageLoadOptions {
+    /// Refuse archive parts that decompress to more than this many bytes, as
+    /// [`load_model_async_limited`] does.
+    pub max_part_bytes: Option<u64>,
+    /// Refuse model parts whose mesh data would take more than this many bytes of memory
+    /// once parsed, as [`load_model_async_with_budget`] does. Each part has its own budget.
+    pub max_mesh_bytes: Option<u64>,
+    /// Most model parts parsed at the same time. Default: the available parallelism.
+    pub max_concurrent_parts: usize,
+}
+
+impl Default for PackageLoadOptions {
+    fn default() -> Self {
+        Self {
+            max_part_bytes: None,
+            max_mesh_bytes: None,
+            max_concurrent_parts: std::thread::available_parallelism().map_or(4, |n| n.get()),
+        }
+    }
+}
+
+/// Asynchronously loads a 3MF package with every model part its main model refers to.
+///
+/// [`load_model_async`] loads the main model part only. Production Extension files, such
+/// as slicer projects, keep their meshes in other model parts, which build items,
+/// components and boolean shapes refer to with `path` attributes. This function follows
+/// those references, including references from one of those parts to another, and parses
+/// the parts concurrently on the blocking thread pool. Every `path` in the returned
+/// [`Package`] resolves with [`Package::part`] and [`Package::resolve_object`], including
+/// paths naming the main model part, whose name is kept in
+/// [`main_model_path`](Package::main_model_path).
+///
+/// # Errors
+///
+/// Returns [`Lib3mfError::InvalidStructure`] if a referenced model part is missing, and
+/// the same errors as [`load_model_async`] otherwise.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lib3mf_async::loader::load_package_async;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let package = load_package_async("project.3mf").await?;
+///     println!("Loaded {} other model part(s)", package.parts.len());
+///
+///     for item in &package.main_model.build.items {
+///         let path = item.path.as_deref();
+///         if let Some((_, object)) = package.resolve_object(item.object_id, path) {
+///             println!("Build item places {:?}", object.name);
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+///
+/// [`Package`]: lib3mf_core::model::Package
+/// [`Package::part`]: lib3mf_core::model::Package::part
+/// [`Package::resolve_object`]: lib3mf_core::model::Package::resolve_object
+/// [`Lib3mfError::InvalidStructure`]: lib3mf_core::error::Lib3mfError::InvalidStructure
+pub async fn load_package_async<P: AsRef<Path>>(path: P) -> Result<Package> {
+    load_package_async_with_options(path, &PackageLoadOptions::default()).await
+}
+
+/// Like [`load_package_async`], with the limits of [`load_model_async_with_budget`] and a
+/// bound on the number of model parts parsed at once.
+///
+/// Parts are read from the archive one at a time, and reading waits while
+/// `max_concurrent_parts` parts are being parsed, so no more than that many parts are held
+/// in memory as XML.
+///
+/// # Errors
+///
+/// As for [`load_package_async`] and [`load_model_async_with_budget`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use lib3mf_async::loader::{PackageLoadOptions, load_package_async_with_options};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let options = PackageLoadOptions {
+///         max_part_bytes: Some(256 << 20),
+///         max_mesh_bytes: Some(1 << 30),
+///         max_concurrent_parts: 2,
+///     };
+///     let package = load_package_async_with_options("upload.3mf", &options).await?;
+///     println!("Loaded {} model part(s)", package.parts.len() + 1);
+///     Ok(())
+/// }
+/// ```
+pub async fn load_package_async_with_options<P: AsRef<Path>>(
+    path: P,
+    options: &PackageLoadOptions,
+) -> Result<Package> {
+    let file = File::open(path.as_ref()).await.map_err(Lib3mfError::Io)?;
+    let mut archive = AsyncZipArchive::new(file).await?;
+    let root_path = find_model_part(&mut archive, options.max_part_bytes).await?;
+    let root_data = read_part(&mut archive, &root_path, options.max_part_bytes).await?;
+    let root = parse_part(root_data, options.max_mesh_bytes).await?;
+
+    let mut discovery = PartDiscovery::new(&root_path);
+    discovery.queue(&root_path, &root);
+    let mut package = Package::new(root);
+    package.main_model_path = normalize_part_name(&root_path);
+    let max_concurrent = options.max_concurrent_parts.max(1);
+    let mut parsing = JoinSet::new();
+    loop {
+        while parsing.len() < max_concurrent
+            && let Some((name, source)) = discovery.pending.pop_front()
+        {
+            if !archive.entry_exists(&name).await {
+                return Err(Lib3mfError::InvalidStructure(format!(
+                    "Model part '{}' referenced by '{}' is missing",
+                    name, source
+                )));
+            }
+            let data = read_part(&mut archive, &name, options.max_part_bytes).await?;
+            let max_mesh_bytes = options.max_mesh_bytes;
+            parsing.spawn_blocking(move || {
+                let model = parse_data(data, max_mesh_bytes);
+                (name, model)
+            });
+        }
+
+        // Parts referenced by the one parsed next are queued in turn
+        let Some(parsed) = parsing.join_next().await else {
+            break;
+        };
+        let (name, model) = parsed.map_err(join_error)?;
+        let model = model?;
+        discovery.queue(&name, &model);
+        package.add_part(name, model);
+    }
+    Ok(package)
+}
+
+/// Model parts seen so far, and those still to load.
+struct PartDiscovery {
+    /// Lowercased names of the parts seen, as part names compare case-insensitively.
+    seen: HashSet<String>,
+    /// Parts to load, with the part that refers to each.
+    pending: VecDeque<(String, String)>,
+}
+
+impl PartDiscovery {
+    fn new(root_path: &str) -> Self {
+        Self {
+            seen: HashSet::from([root_path.to_ascii_lowercase()]),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Queues the parts `model`, loaded from part `source`, refers to that weren't seen.
+    fn queue(&mut self, source: &str, model: &Model) {
+        for name in model.referenced_parts() {
+            if self.seen.insert(name.to_ascii_lowercase()) {
+                self.pending.push_back((name, source.to_string()));
+            }
+        }
+    }
+}
+
+async fn load_model_impl(
+    path: &Path,
+    max_part_bytes: Option<u64>,
+    max_mesh_bytes: Option<u64>,
+) -> Result<Model> {
     let file = File::open(path).await.map_err(Lib3mfError::Io)?;
     let mut archive = AsyncZipArchive::new(file).await?;
+    let model_path = find_model_part(&mut archive, max_part_bytes).await?;
+
+    // 3. Read Model Part
+    let model_data = read_part(&mut archive, &model_path, max_part_bytes).await?;
+
+    // 4. Parse Model (Synchronous - CPU bound but usually fast enough or run via spawn_blocking if needed)
+    // For Buffer & Parse strategy, we stick to current thread for now unless blocking is an issue.
+    // Parsing ~100MB XML might block for seconds. For true async app, spawn_blocking is better.
+    // But keeping it simple for now.
+    parse_part(model_data, max_mesh_bytes).await
+}
 
+/// Returns the name of the main model part, from the package relationships.
+async fn find_model_part(
+    archive: &mut AsyncZipArchive<File>,
+    max_part_bytes: Option<u64>,
+) -> Result<String> {
     // 1. Read [Content_Types].xml (Optional but good for robustness)
     // For simplicity of this Phase, we might follow strict 3MF discovery via _rels
 
     // 2. Read _rels/.rels to find the Start Part (3D Model)
     let rels_path = "_rels/.rels";
-    let rels_data = archive.read_entry(rels_path).await?;
+    let rels_data = read_part(archive, rels_path, max_part_bytes).await?;
     let rels = parse_rels(&rels_data)?;
 
     // Find the 3D Model part (Type = http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel)
@@ -168,32 +438,40 @@ pub async fn load_model_async<P: AsRef<Path>>(path: P) -> Result<Model> {
             "No 3D Model part found in .rels".to_string(),
         ))?;
 
-    let model_path = clean_path(&model_rel.target);
+    Ok(resolve_part_target("", &model_rel.target))
+}
 
-    // 3. Read Model Part
-    let model_data = archive.read_entry(&model_path).await?;
+/// Parses a model part on the blocking thread pool.
+async fn parse_part(data: Vec<u8>, max_mesh_bytes: Option<u64>) -> Result<Model> {
+    // Use spawn_blocking for CPU bound parsing
+    tokio::task::spawn_blocking(move || parse_data(data, max_mesh_bytes))
+        .await
+        .map_err(join_error)? // JoinError + Parse Result
+}
 
-    // 4. Parse Model (Synchronous - CPU bound but usually fast enough or run via spawn_blocking if needed)
-    // For Buffer & Parse strategy, we stick to current thread for now unless blocking is an issue.
-    // Parsing ~100MB XML might block for seconds. For true async app, spawn_blocking is better.
-    // But keeping it simple for now.
+fn parse_data(data: Vec<u8>, max_mesh_bytes: Option<u64>) -> Result<Model> {
+    let cursor = Cursor::new(data);
+    match max_mesh_bytes {
+        Some(max) => parse_model_with_budget(cursor, max),
+        None => parse_model(cursor),
+    }
+}
 
-    // Use spawn_blocking for CPU bound parsing
-    let model = tokio::task::spawn_blocking(move || {
-        let cursor = Cursor::new(model_data);
-        parse_model(cursor)
-    })
-    .await
-    .map_err(|e| Lib3mfError::Validation(format!("Join error: {}", e)))??; // JoinError + Parse Result
+fn join_error(e: JoinError) -> Lib3mfError {
+    Lib3mfError::Validation(format!("Join error: {}", e))
+}
 
-    Ok(model)
+async fn read_part(
+    archive: &mut AsyncZipArchive<File>,
+    name: &str,
+    max_bytes: Option<u64>,
+) -> Result<Vec<u8>> {
+    match max_bytes {
+        Some(max) => archive.read_entry_limited(name, max).await,
+        None => archive.read_entry(name).await,
+    }
 }
 
 fn parse_rels(data: &[u8]) -> Result<Vec<Relationship>> {
     parse_relationships(data)
 }
-
-fn clean_path(path: &str) -> String {
-    let p = path.trim_start_matches('/');
-    p.to_string()
-}
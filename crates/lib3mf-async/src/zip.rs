@@ -37,6 +37,7 @@ use async_trait::async_trait;
 use async_zip::StoredZipEntry;
 use async_zip::tokio::read::seek::ZipFileReader;
 use futures_lite::io::AsyncReadExt;
+use lib3mf_core::archive::part_names_match;
 use lib3mf_core::error::{Lib3mfError, Result};
 use tokio::io::{AsyncRead, AsyncSeek, BufReader};
 use tokio_util::compat::TokioAsyncReadCompatExt;
@@ -121,16 +122,45 @@ impl<R: AsyncRead + AsyncSeek + Unpin> AsyncZipArchive<R> {
     }
 }
 
-#[async_trait]
-impl<R: AsyncRead + AsyncSeek + Unpin + Send + Sync> AsyncArchiveReader for AsyncZipArchive<R> {
-    async fn read_entry(&mut self, name: &str) -> Result<Vec<u8>> {
+impl<R: AsyncRead + AsyncSeek + Unpin> AsyncZipArchive<R> {
+    /// Reads an entry like [`AsyncArchiveReader::read_entry`], but stops decompressing once
+    /// it exceeds `max_bytes`.
+    ///
+    /// Use this for untrusted uploads: the size recorded in a ZIP entry is not trusted, so a
+    /// small, highly compressed entry cannot expand without bound in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Lib3mfError::Io`] with [`std::io::ErrorKind::FileTooLarge`] if the entry
+    /// decompresses to more than `max_bytes`, and the same errors as `read_entry` otherwise.
+    ///
+    /// [`Lib3mfError::Io`]: lib3mf_core::error::Lib3mfError::Io
+    pub async fn read_entry_limited(&mut self, name: &str, max_bytes: u64) -> Result<Vec<u8>> {
+        self.read_entry_impl(name, Some(max_bytes)).await
+    }
+
+    /// Returns the index of the entry for a part name, comparing normalized names when no
+    /// entry matches exactly (see [`lib3mf_core::archive::part_name`]).
+    fn find_entry(&self, name: &str) -> Option<usize> {
         let entries = self.reader.file().entries();
-        let index = entries
+        entries
             .iter()
             .position(|e: &StoredZipEntry| e.filename().as_str().ok() == Some(name))
+            .or_else(|| {
+                entries.iter().position(|e: &StoredZipEntry| {
+                    e.filename()
+                        .as_str()
+                        .is_ok_and(|f| part_names_match(f, name))
+                })
+            })
+    }
+
+    async fn read_entry_impl(&mut self, name: &str, max_bytes: Option<u64>) -> Result<Vec<u8>> {
+        let index = self
+            .find_entry(name)
             .ok_or(Lib3mfError::ResourceNotFound(0))?;
 
-        let mut reader = self
+        let reader = self
             .reader
             .reader_with_entry(index)
             .await
@@ -139,20 +169,33 @@ impl<R: AsyncRead + AsyncSeek + Unpin + Send + Sync> AsyncArchiveReader for Asyn
         let mut buffer = Vec::new();
         // reader implements futures::io::AsyncRead.
         // We imported futures_lite::io::AsyncReadExt so read_to_end should work.
+        // Reading one byte past the limit tells a full entry apart from an oversized one.
         reader
+            .take(max_bytes.map_or(u64::MAX, |max| max.saturating_add(1)))
             .read_to_end(&mut buffer)
             .await
             .map_err(|e| Lib3mfError::Io(std::io::Error::other(e.to_string())))?;
 
+        if let Some(max) = max_bytes
+            && buffer.len() as u64 > max
+        {
+            return Err(Lib3mfError::Io(std::io::Error::new(
+                std::io::ErrorKind::FileTooLarge,
+                format!("Entry '{name}' decompresses to more than {max} bytes"),
+            )));
+        }
         Ok(buffer)
     }
+}
+
+#[async_trait]
+impl<R: AsyncRead + AsyncSeek + Unpin + Send + Sync> AsyncArchiveReader for AsyncZipArchive<R> {
+    async fn read_entry(&mut self, name: &str) -> Result<Vec<u8>> {
+        self.read_entry_impl(name, None).await
+    }
 
     async fn entry_exists(&mut self, name: &str) -> bool {
-        self.reader
-            .file()
-            .entries()
-            .iter()
-            .any(|e: &StoredZipEntry| e.filename().as_str().ok() == Some(name))
+        self.find_entry(name).is_some()
     }
 
     async fn list_entries(&mut self) -> Result<Vec<String>> {
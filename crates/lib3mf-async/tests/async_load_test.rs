@@ -1 +1,209 @@
+use glam::Mat4;
+use lib3mf_async::loader::{
+    PackageLoadOptions, load_model_async, load_model_async_limited, load_package_async,
+    load_package_async_with_options,
+};
+use lib3mf_core::error::Lib3mfError;
+use lib3mf_core::model::{
+    BuildItem, Component, Components, Geometry, Mesh, Model, Object, ObjectType, Package,
+    ResourceId,
+};
+use lib3mf_core::writer::package_writer::PackageWriter;
+use std::path::PathBuf;
+use tempfile::TempDir;
 
+const BENCHY: &str = "../../models/Benchy.3mf";
+
+#[tokio::test]
+async fn test_load_benchy() -> anyhow::Result<()> {
+    let model = load_model_async(BENCHY).await?;
+    assert!(model.resources.iter_objects().count() > 0);
+    assert!(!model.build.items.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_limited_load_accepts_parts_within_limit() -> anyhow::Result<()> {
+    let model = load_model_async_limited(BENCHY, 64 << 20).await?;
+    assert!(!model.build.items.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_limited_load_rejects_oversized_parts() {
+    let err = load_model_async_limited(BENCHY, 16).await.unwrap_err();
+    match err {
+        Lib3mfError::Io(e) => assert_eq!(e.kind(), std::io::ErrorKind::FileTooLarge),
+        other => panic!("expected an I/O error, got {other:?}"),
+    }
+}
+
+/// An object: a triangle if `components` is empty, else an assembly of them as
+/// `(object, path)` pairs.
+fn object(id: u32, components: &[(u32, Option<&str>)]) -> Object {
+    let geometry = if components.is_empty() {
+        let mut mesh = Mesh::new();
+        mesh.add_vertex(0.0, 0.0, 0.0);
+        mesh.add_vertex(1.0, 0.0, 0.0);
+        mesh.add_vertex(0.0, 1.0, 0.0);
+        mesh.add_triangle(0, 1, 2);
+        Geometry::Mesh(mesh)
+    } else {
+        Geometry::Components(Components {
+            components: components
+                .iter()
+                .map(|&(id, path)| Component {
+                    object_id: ResourceId(id),
+                    path: path.map(str::to_string),
+                    uuid: None,
+                    transform: Mat4::IDENTITY,
+                })
+                .collect(),
+        })
+    };
+    Object {
+        id: ResourceId(id),
+        object_type: ObjectType::Model,
+        name: None,
+        part_number: None,
+        uuid: None,
+        pid: None,
+        pindex: None,
+        thumbnail: None,
+        geometry,
+    }
+}
+
+fn model(objects: Vec<Object>) -> Model {
+    let mut model = Model::default();
+    for object in objects {
+        model.resources.add_object(object).unwrap();
+    }
+    model
+}
+
+/// Writes a package whose main model places object 1, assembled from object 1 of
+/// `/3D/a.model` and object 2 of `/3D/b.model`; `a.model` refers to `b.model` in turn.
+fn write_chain(dir: &TempDir, with_b: bool) -> PathBuf {
+    let mut main = model(vec![object(
+        1,
+        &[(1, Some("/3D/a.model")), (2, Some("/3D/b.model"))],
+    )]);
+    main.build
+        .items
+        .push(BuildItem::new(ResourceId(1), Mat4::IDENTITY));
+    let mut package = Package::new(main);
+    package.add_part(
+        "3D/a.model".to_string(),
+        model(vec![object(1, &[(2, Some("/3D/B.model"))])]),
+    );
+    if with_b {
+        package.add_part("3D/b.model".to_string(), model(vec![object(2, &[])]));
+    }
+
+    let path = dir.path().join("parts.3mf");
+    PackageWriter::new(std::fs::File::create(&path).unwrap())
+        .write(&package)
+        .unwrap();
+    path
+}
+
+#[tokio::test]
+async fn test_load_package_resolves_every_part() -> anyhow::Result<()> {
+    let dir = TempDir::new()?;
+    let path = write_chain(&dir, true);
+
+    for max_concurrent_parts in [1, 4] {
+        let options = PackageLoadOptions {
+            max_concurrent_parts,
+            ..Default::default()
+        };
+        let package = load_package_async_with_options(&path, &options).await?;
+        let mut parts: Vec<_> = package.parts.keys().cloned().collect();
+        parts.sort();
+        assert_eq!(parts, ["3D/a.model", "3D/b.model"]);
+
+        let models = std::iter::once(&package.main_model).chain(package.parts.values());
+        for model in models {
+            for object in model.resources.iter_objects() {
+                let Geometry::Components(components) = &object.geometry else {
+                    continue;
+                };
+                for c in &components.components {
+                    assert!(
+                        package
+                            .resolve_object(c.object_id, c.path.as_deref())
+                            .is_some(),
+                        "{:?}",
+                        c.path
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_load_package_keeps_root_part_name() -> anyhow::Result<()> {
+    let dir = TempDir::new()?;
+    let path = dir.path().join("root.3mf");
+    let mut main = model(vec![object(2, &[])]);
+    let mut item = BuildItem::new(ResourceId(1), Mat4::IDENTITY);
+    item.path = Some("/3D/a.model".to_string());
+    main.build.items.push(item);
+    let mut package = Package::new(main);
+    package.main_model_path = "3D/root.model".to_string();
+    // The part refers back to the main model by its name
+    package.add_part(
+        "3D/a.model".to_string(),
+        model(vec![object(1, &[(2, Some("/3D/Root.model"))])]),
+    );
+    PackageWriter::new(std::fs::File::create(&path)?).write(&package)?;
+
+    let package = load_package_async(&path).await?;
+    assert_eq!(package.main_model_path, "3D/root.model");
+    assert_eq!(package.parts.keys().collect::<Vec<_>>(), ["3D/a.model"]);
+    let (model, _) = package
+        .resolve_object(ResourceId(2), Some("/3D/Root.model"))
+        .unwrap();
+    assert!(std::ptr::eq(model, &package.main_model));
+    assert!(package.part(Some("/3D/3dmodel.model")).is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_load_package_reports_missing_part() -> anyhow::Result<()> {
+    let dir = TempDir::new()?;
+    let path = write_chain(&dir, false);
+
+    match load_package_async(&path).await.unwrap_err() {
+        Lib3mfError::InvalidStructure(message) => {
+            assert!(message.contains("3D/b.model"), "{message}")
+        }
+        other => panic!("expected a structure error, got {other:?}"),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_load_package_benchy() -> anyhow::Result<()> {
+    let package = load_package_async(BENCHY).await?;
+    assert_eq!(
+        package.parts.keys().collect::<Vec<_>>(),
+        ["3D/Objects/object_1.model"]
+    );
+    let item = &package.main_model.build.items[0];
+    let (_, object) = package
+        .resolve_object(item.object_id, item.path.as_deref())
+        .unwrap();
+    let Geometry::Components(components) = &object.geometry else {
+        panic!("expected an assembly, got {:?}", object.geometry);
+    };
+    let component = &components.components[0];
+    let (_, mesh) = package
+        .resolve_object(component.object_id, component.path.as_deref())
+        .unwrap();
+    assert!(matches!(&mesh.geometry, Geometry::Mesh(m) if m.triangles.len() > 200_000));
+    Ok(())
+}
@@ -0,0 +1,21 @@
+//! Regenerates `include/lib3mf_capi.h` from the `extern "C"` items in `src/`.
+
+use std::path::PathBuf;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let crate_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR")?);
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))?;
+    let header = crate_dir.join("include").join("lib3mf_capi.h");
+
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=src");
+
+    // `write_to_file` only touches the header when its contents change, so the
+    // checked-in copy stays stable across builds.
+    cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(config)
+        .generate()?
+        .write_to_file(header);
+    Ok(())
+}
@@ -0,0 +1,90 @@
+/*
+ * Prints the objects in a 3MF file and validates it.
+ *
+ * Build (from the workspace root):
+ *   cargo build --release -p lib3mf-capi
+ *   cc crates/lib3mf-capi/examples/inspect.c -Icrates/lib3mf-capi/include \
+ *      -Ltarget/release -llib3mf_capi -o inspect
+ *   LD_LIBRARY_PATH=target/release ./inspect models/Benchy.3mf
+ */
+#include "lib3mf_capi.h"
+
+#include <stdio.h>
+#include <stdlib.h>
+
+static const char *geometry_name(Lib3mfGeometryKind kind) {
+    switch (kind) {
+    case LIB3MF_GEOMETRY_KIND_MESH: return "mesh";
+    case LIB3MF_GEOMETRY_KIND_COMPONENTS: return "components";
+    case LIB3MF_GEOMETRY_KIND_SLICE_STACK: return "slice stack";
+    case LIB3MF_GEOMETRY_KIND_VOLUMETRIC_STACK: return "volumetric stack";
+    case LIB3MF_GEOMETRY_KIND_BOOLEAN_SHAPE: return "boolean shape";
+    case LIB3MF_GEOMETRY_KIND_DISPLACEMENT_MESH: return "displacement mesh";
+    }
+    return "unknown";
+}
+
+int main(int argc, char **argv) {
+    if (argc != 2) {
+        fprintf(stderr, "usage: %s <file.3mf>\n", argv[0]);
+        return 2;
+    }
+
+    printf("lib3mf-capi %s\n", lib3mf_version());
+
+    Lib3mfModel *model = NULL;
+    if (lib3mf_model_open(argv[1], &model) != LIB3MF_STATUS_OK) {
+        fprintf(stderr, "error: %s\n", lib3mf_last_error_message());
+        return 1;
+    }
+
+    size_t count = 0;
+    lib3mf_model_object_count(model, &count);
+    printf("%zu objects\n", count);
+
+    for (size_t i = 0; i < count; i++) {
+        uint32_t id = 0;
+        Lib3mfObjectInfo info;
+        lib3mf_model_get_object_id(model, i, &id);
+        lib3mf_model_get_object_info(model, id, &info);
+
+        size_t name_len = 0;
+        lib3mf_model_get_object_name(model, id, NULL, 0, &name_len);
+        char *name = malloc(name_len);
+        lib3mf_model_get_object_name(model, id, name, name_len, &name_len);
+
+        printf("  [%u] '%s' %s", id, name, geometry_name(info.geometry));
+        if (info.geometry == LIB3MF_GEOMETRY_KIND_MESH && info.vertex_count > 0) {
+            float *xyz = malloc(3 * info.vertex_count * sizeof(float));
+            lib3mf_mesh_copy_vertices(model, id, xyz, 3 * info.vertex_count);
+            float min_z = xyz[2], max_z = xyz[2];
+            for (size_t v = 1; v < info.vertex_count; v++) {
+                float z = xyz[3 * v + 2];
+                min_z = z < min_z ? z : min_z;
+                max_z = z > max_z ? z : max_z;
+            }
+            printf(": %zu vertices, %zu triangles, height %.2f", info.vertex_count,
+                   info.triangle_count, max_z - min_z);
+            free(xyz);
+        }
+        printf("\n");
+        free(name);
+    }
+
+    Lib3mfValidationReport *report = NULL;
+    if (lib3mf_model_validate(model, LIB3MF_VALIDATION_LEVEL_STANDARD, &report) == LIB3MF_STATUS_OK) {
+        size_t items = 0, errors = 0;
+        lib3mf_report_item_count(report, &items);
+        lib3mf_report_error_count(report, &errors);
+        printf("validation: %zu findings, %zu errors\n", items, errors);
+        for (size_t i = 0; i < items; i++) {
+            Lib3mfValidationItem item;
+            lib3mf_report_get_item(report, i, &item);
+            printf("  [%u] %s\n", item.code, item.message);
+        }
+        lib3mf_report_free(report);
+    }
+
+    lib3mf_model_free(model);
+    return 0;
+}
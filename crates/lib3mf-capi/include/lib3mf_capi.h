@@ -0,0 +1,338 @@
+/*
+ * lib3mf-capi: C ABI for lib3mf-rs.
+ *
+ * This file is generated by cbindgen from crates/lib3mf-capi/src; do not edit it by hand.
+ */
+
+#ifndef LIB3MF_CAPI_H
+#define LIB3MF_CAPI_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+// Result code returned by every fallible `lib3mf_*` function.
+typedef enum {
+  // The call succeeded.
+  LIB3MF_STATUS_OK = 0,
+  // A required pointer was NULL, a string was not valid UTF-8, or an index was out of range.
+  LIB3MF_STATUS_INVALID_ARGUMENT = 1,
+  // Reading or writing a file failed.
+  LIB3MF_STATUS_IO = 2,
+  // The package or model XML is malformed or violates the 3MF specification.
+  LIB3MF_STATUS_INVALID_MODEL = 3,
+  // The requested object or resource does not exist.
+  LIB3MF_STATUS_NOT_FOUND = 4,
+  // The caller's buffer is too small; the required size has been written to the size output.
+  LIB3MF_STATUS_BUFFER_TOO_SMALL = 5,
+  // The operation needs a feature that this build of the library does not include, or
+  // the model requires an extension the library doesn't support.
+  LIB3MF_STATUS_UNSUPPORTED = 6,
+  // The model is encrypted, or decryption or signature handling failed.
+  LIB3MF_STATUS_ENCRYPTION = 7,
+  // The library panicked; the handle involved should be considered unusable. Only
+  // reported by libraries built with `panic = "unwind"` (the `release-unwind` profile).
+  LIB3MF_STATUS_PANIC = 8,
+} Lib3mfStatus;
+
+// Unit of measurement declared by the model.
+typedef enum {
+  LIB3MF_UNIT_MICRON = 0,
+  LIB3MF_UNIT_MILLIMETER = 1,
+  LIB3MF_UNIT_CENTIMETER = 2,
+  LIB3MF_UNIT_INCH = 3,
+  LIB3MF_UNIT_FOOT = 4,
+  LIB3MF_UNIT_METER = 5,
+} Lib3mfUnit;
+
+// Value of an object's `type` attribute.
+typedef enum {
+  LIB3MF_OBJECT_TYPE_MODEL = 0,
+  LIB3MF_OBJECT_TYPE_SUPPORT = 1,
+  LIB3MF_OBJECT_TYPE_SOLID_SUPPORT = 2,
+  LIB3MF_OBJECT_TYPE_SURFACE = 3,
+  LIB3MF_OBJECT_TYPE_OTHER = 4,
+} Lib3mfObjectType;
+
+// Kind of geometry an object holds.
+typedef enum {
+  LIB3MF_GEOMETRY_KIND_MESH = 0,
+  LIB3MF_GEOMETRY_KIND_COMPONENTS = 1,
+  LIB3MF_GEOMETRY_KIND_SLICE_STACK = 2,
+  LIB3MF_GEOMETRY_KIND_VOLUMETRIC_STACK = 3,
+  LIB3MF_GEOMETRY_KIND_BOOLEAN_SHAPE = 4,
+  LIB3MF_GEOMETRY_KIND_DISPLACEMENT_MESH = 5,
+  LIB3MF_GEOMETRY_KIND_LEVEL_SET = 6,
+} Lib3mfGeometryKind;
+
+// Validation strictness, matching the core library's levels.
+typedef enum {
+  LIB3MF_VALIDATION_LEVEL_MINIMAL = 0,
+  LIB3MF_VALIDATION_LEVEL_STANDARD = 1,
+  LIB3MF_VALIDATION_LEVEL_STRICT = 2,
+  LIB3MF_VALIDATION_LEVEL_PARANOID = 3,
+} Lib3mfValidationLevel;
+
+// Severity of a validation finding.
+typedef enum {
+  LIB3MF_SEVERITY_ERROR = 0,
+  LIB3MF_SEVERITY_WARNING = 1,
+  LIB3MF_SEVERITY_INFO = 2,
+} Lib3mfSeverity;
+
+// Opaque handle to a parsed or newly created 3MF model.
+//
+// Created by [`lib3mf_model_new`], [`lib3mf_model_open`] or [`lib3mf_model_from_bytes`]
+// and released with [`lib3mf_model_free`]. A handle may be moved between threads but
+// must not be used from two threads at once.
+typedef struct Lib3mfModel Lib3mfModel;
+
+// Opaque handle to the result of [`lib3mf_model_validate`], released with
+// [`lib3mf_report_free`].
+typedef struct Lib3mfValidationReport Lib3mfValidationReport;
+
+// Summary of one object, filled in by [`lib3mf_model_get_object_info`].
+typedef struct {
+  uint32_t id;
+  Lib3mfObjectType object_type;
+  Lib3mfGeometryKind geometry;
+  // Number of vertices; 0 unless `geometry` is a mesh or displacement mesh.
+  size_t vertex_count;
+  // Number of triangles; 0 unless `geometry` is a mesh or displacement mesh.
+  size_t triangle_count;
+  // Number of components; 0 unless `geometry` is `LIB3MF_GEOMETRY_KIND_COMPONENTS`.
+  size_t component_count;
+} Lib3mfObjectInfo;
+
+// One finding, filled in by [`lib3mf_report_get_item`].
+//
+// The string pointers are owned by the report and stay valid until it is freed.
+typedef struct {
+  Lib3mfSeverity severity;
+  uint32_t code;
+  // Human-readable description; never NULL.
+  const char *message;
+  // Suggested fix, or NULL.
+  const char *suggestion;
+  // Affected object or resource (e.g. `"Object 5"`), or NULL.
+  const char *context;
+} Lib3mfValidationItem;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Returns the library version as a static NUL-terminated string (e.g. `"0.4.0"`).
+const char *lib3mf_version(void);
+
+// Returns the message for the most recent failed call on this thread, or NULL.
+//
+// The string is owned by the library and stays valid until the next `lib3mf_*`
+// call on the same thread. Copy it if it must outlive that.
+const char *lib3mf_last_error_message(void);
+
+// Creates an empty model (millimeters, no objects, empty build).
+//
+// # Safety
+//
+// `out_model` must be a valid pointer to writable storage for one handle.
+Lib3mfStatus lib3mf_model_new(Lib3mfModel **out_model);
+
+// Opens and parses a `.3mf` package from a file path (UTF-8, NUL-terminated).
+//
+// # Safety
+//
+// `path` must be a NUL-terminated string and `out_model` a valid pointer to writable
+// storage for one handle.
+Lib3mfStatus lib3mf_model_open(const char *path, Lib3mfModel **out_model);
+
+// Parses a `.3mf` package held in memory. The bytes are copied; the caller keeps ownership.
+//
+// # Safety
+//
+// `data` must point to `len` readable bytes and `out_model` must be a valid pointer to
+// writable storage for one handle.
+Lib3mfStatus lib3mf_model_from_bytes(const uint8_t *data, size_t len, Lib3mfModel **out_model);
+
+// Releases a model handle. Passing NULL is a no-op.
+//
+// # Safety
+//
+// `model` must be NULL or a handle returned by this library that has not been freed.
+void lib3mf_model_free(Lib3mfModel *model);
+
+// Writes the unit of measurement to `out_unit`.
+//
+// # Safety
+//
+// `model` must be a live handle and `out_unit` a valid pointer.
+Lib3mfStatus lib3mf_model_get_unit(const Lib3mfModel *model, Lib3mfUnit *out_unit);
+
+// Writes the number of objects in the model's resources to `out_count`.
+//
+// # Safety
+//
+// `model` must be a live handle and `out_count` a valid pointer.
+Lib3mfStatus lib3mf_model_object_count(const Lib3mfModel *model, size_t *out_count);
+
+// Writes the ID of the object at `index` (0-based, in ascending ID order) to `out_id`.
+//
+// # Safety
+//
+// `model` must be a live handle and `out_id` a valid pointer.
+Lib3mfStatus lib3mf_model_get_object_id(const Lib3mfModel *model, size_t index, uint32_t *out_id);
+
+// Fills `out_info` with a summary of object `id`.
+//
+// # Safety
+//
+// `model` must be a live handle and `out_info` a valid pointer.
+Lib3mfStatus lib3mf_model_get_object_info(const Lib3mfModel *model,
+                                          uint32_t id,
+                                          Lib3mfObjectInfo *out_info);
+
+// Copies the name of object `id` into `buffer` as a NUL-terminated UTF-8 string.
+//
+// The required size, including the terminator, is always written to `out_len`; an
+// unnamed object has an empty name. Pass a NULL `buffer` to query the size only. If
+// `buffer_len` is too small, nothing is copied and `LIB3MF_STATUS_BUFFER_TOO_SMALL`
+// is returned.
+//
+// # Safety
+//
+// `model` must be a live handle, `out_len` a valid pointer, and `buffer` NULL or valid
+// for `buffer_len` bytes of writes.
+Lib3mfStatus lib3mf_model_get_object_name(const Lib3mfModel *model,
+                                          uint32_t id,
+                                          char *buffer,
+                                          size_t buffer_len,
+                                          size_t *out_len);
+
+// Copies the vertices of mesh object `id` into `out_xyz` as packed `x, y, z` floats.
+//
+// `len` is the capacity of `out_xyz` in floats and must be at least
+// `3 * vertex_count` (see [`lib3mf_model_get_object_info`]).
+//
+// # Safety
+//
+// `model` must be a live handle and `out_xyz` valid for `len` float writes.
+Lib3mfStatus lib3mf_mesh_copy_vertices(const Lib3mfModel *model,
+                                       uint32_t id,
+                                       float *out_xyz,
+                                       size_t len);
+
+// Copies the triangles of mesh object `id` into `out_indices` as packed `v1, v2, v3`
+// vertex indices.
+//
+// `len` is the capacity of `out_indices` in elements and must be at least
+// `3 * triangle_count`.
+//
+// # Safety
+//
+// `model` must be a live handle and `out_indices` valid for `len` writes.
+Lib3mfStatus lib3mf_mesh_copy_triangles(const Lib3mfModel *model,
+                                        uint32_t id,
+                                        uint32_t *out_indices,
+                                        size_t len);
+
+// Adds a mesh object built from packed vertex and triangle buffers.
+//
+// `vertices` holds `3 * vertex_count` floats (`x, y, z`) and `indices` holds
+// `3 * triangle_count` vertex indices. The new object gets the lowest unused resource
+// ID, which is written to `out_id`. The object is not placed on the build plate; call
+// [`lib3mf_model_add_build_item`] for that.
+//
+// # Safety
+//
+// `model` must be a live handle, `vertices` and `indices` must be readable for the
+// given lengths, and `out_id` must be a valid pointer.
+Lib3mfStatus lib3mf_model_add_mesh_object(Lib3mfModel *model,
+                                          const float *vertices,
+                                          size_t vertex_count,
+                                          const uint32_t *indices,
+                                          size_t triangle_count,
+                                          uint32_t *out_id);
+
+// Places object `id` on the build plate.
+//
+// `transform` is NULL for identity, or 12 floats in the order of the 3MF `transform`
+// attribute (`m00 m01 m02 m10 m11 m12 m20 m21 m22 m30 m31 m32`).
+//
+// # Safety
+//
+// `model` must be a live handle and `transform` NULL or readable for 12 floats.
+Lib3mfStatus lib3mf_model_add_build_item(Lib3mfModel *model, uint32_t id, const float *transform);
+
+// Writes the model as a `.3mf` package to a file path (UTF-8, NUL-terminated).
+//
+// # Safety
+//
+// `model` must be a live handle and `path` a NUL-terminated string.
+Lib3mfStatus lib3mf_model_write(const Lib3mfModel *model, const char *path);
+
+// Serializes the model as a `.3mf` package into a library-owned buffer.
+//
+// On success `*out_data` points to `*out_len` bytes that must be released with
+// [`lib3mf_bytes_free`].
+//
+// # Safety
+//
+// `model` must be a live handle; `out_data` and `out_len` must be valid pointers.
+Lib3mfStatus lib3mf_model_to_bytes(const Lib3mfModel *model, uint8_t **out_data, size_t *out_len);
+
+// Releases a buffer returned by [`lib3mf_model_to_bytes`]. Passing NULL is a no-op.
+//
+// # Safety
+//
+// `data` and `len` must be exactly the values produced by [`lib3mf_model_to_bytes`],
+// and the buffer must not have been freed already.
+void lib3mf_bytes_free(uint8_t *data, size_t len);
+
+// Validates the model at `level` and returns a new report in `out_report`.
+//
+// A model with findings still returns `LIB3MF_STATUS_OK`; inspect the report with
+// [`lib3mf_report_error_count`] and [`lib3mf_report_get_item`].
+//
+// # Safety
+//
+// `model` must be a live handle and `out_report` a valid pointer to writable storage
+// for one handle.
+Lib3mfStatus lib3mf_model_validate(const Lib3mfModel *model,
+                                   Lib3mfValidationLevel level,
+                                   Lib3mfValidationReport **out_report);
+
+// Writes the total number of findings to `out_count`.
+//
+// # Safety
+//
+// `report` must be a live report handle and `out_count` a valid pointer.
+Lib3mfStatus lib3mf_report_item_count(const Lib3mfValidationReport *report, size_t *out_count);
+
+// Writes the number of error-severity findings to `out_count`.
+//
+// # Safety
+//
+// `report` must be a live report handle and `out_count` a valid pointer.
+Lib3mfStatus lib3mf_report_error_count(const Lib3mfValidationReport *report, size_t *out_count);
+
+// Fills `out_item` with finding `index` (0-based, in the order they were reported).
+//
+// # Safety
+//
+// `report` must be a live report handle and `out_item` a valid pointer.
+Lib3mfStatus lib3mf_report_get_item(const Lib3mfValidationReport *report,
+                                    size_t index,
+                                    Lib3mfValidationItem *out_item);
+
+// Releases a report. Passing NULL is a no-op.
+//
+// # Safety
+//
+// `report` must be NULL or a report handle that has not been freed; the strings of any
+// items read from it become dangling.
+void lib3mf_report_free(Lib3mfValidationReport *report);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* LIB3MF_CAPI_H */
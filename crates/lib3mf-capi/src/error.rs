@@ -0,0 +1,133 @@
+//! Status codes and per-thread error messages.
+//!
+//! Every fallible function returns a [`Lib3mfStatus`]. On failure, a human-readable
+//! message is stored for the calling thread and can be fetched with
+//! [`lib3mf_last_error_message`].
+
+use lib3mf_core::error::Lib3mfError;
+use std::cell::RefCell;
+use std::ffi::{CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+/// Result code returned by every fallible `lib3mf_*` function.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lib3mfStatus {
+    /// The call succeeded.
+    Ok = 0,
+    /// A required pointer was NULL, a string was not valid UTF-8, or an index was out of range.
+    InvalidArgument = 1,
+    /// Reading or writing a file failed.
+    Io = 2,
+    /// The package or model XML is malformed or violates the 3MF specification.
+    InvalidModel = 3,
+    /// The requested object or resource does not exist.
+    NotFound = 4,
+    /// The caller's buffer is too small; the required size has been written to the size output.
+    BufferTooSmall = 5,
+    /// The operation needs a feature that this build of the library does not include, or
+    /// the model requires an extension the library doesn't support.
+    Unsupported = 6,
+    /// The model is encrypted, or decryption or signature handling failed.
+    Encryption = 7,
+    /// The library panicked; the handle involved should be considered unusable. Only
+    /// reported by libraries built with `panic = "unwind"` (the `release-unwind` profile).
+    Panic = 8,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Error carried from an FFI body to [`guard`]: a status code plus a message.
+pub(crate) struct FfiError {
+    status: Lib3mfStatus,
+    message: String,
+}
+
+impl FfiError {
+    pub(crate) fn new(status: Lib3mfStatus, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn invalid_argument(message: impl Into<String>) -> Self {
+        Self::new(Lib3mfStatus::InvalidArgument, message)
+    }
+}
+
+impl From<Lib3mfError> for FfiError {
+    fn from(err: Lib3mfError) -> Self {
+        let status = match &err {
+            Lib3mfError::Io(_) => Lib3mfStatus::Io,
+            Lib3mfError::Validation(_) | Lib3mfError::InvalidStructure(_) => {
+                Lib3mfStatus::InvalidModel
+            }
+            Lib3mfError::ResourceNotFound(_) => Lib3mfStatus::NotFound,
+            Lib3mfError::EncryptionError(_) | Lib3mfError::EncryptedContent(_) => {
+                Lib3mfStatus::Encryption
+            }
+            Lib3mfError::FeatureNotEnabled(..) | Lib3mfError::UnsupportedExtension(_) => {
+                Lib3mfStatus::Unsupported
+            }
+        };
+        Self::new(status, err.to_string())
+    }
+}
+
+impl From<std::io::Error> for FfiError {
+    fn from(err: std::io::Error) -> Self {
+        Self::new(Lib3mfStatus::Io, err.to_string())
+    }
+}
+
+pub(crate) type FfiResult<T = ()> = Result<T, FfiError>;
+
+/// Converts a message to a C string, replacing interior NULs rather than dropping it.
+pub(crate) fn c_string_lossy(s: String) -> CString {
+    CString::new(s.replace('\0', "\u{FFFD}")).unwrap_or_default()
+}
+
+fn set_last_error(message: String) {
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(c_string_lossy(message)));
+}
+
+/// Runs an FFI body, converting errors and panics into a status code.
+///
+/// The last-error message is cleared on success so callers never see a stale one.
+pub(crate) fn guard(body: impl FnOnce() -> FfiResult) -> Lib3mfStatus {
+    match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => {
+            LAST_ERROR.with(|slot| *slot.borrow_mut() = None);
+            Lib3mfStatus::Ok
+        }
+        Ok(Err(err)) => {
+            set_last_error(err.message);
+            err.status
+        }
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            set_last_error(format!("lib3mf panicked: {message}"));
+            Lib3mfStatus::Panic
+        }
+    }
+}
+
+/// Returns the message for the most recent failed call on this thread, or NULL.
+///
+/// The string is owned by the library and stays valid until the next `lib3mf_*`
+/// call on the same thread. Copy it if it must outlive that.
+#[unsafe(no_mangle)]
+pub extern "C" fn lib3mf_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|slot| {
+        slot.borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |message| message.as_ptr())
+    })
+}
@@ -0,0 +1,111 @@
+//! # lib3mf-capi
+//!
+//! A stable C ABI for lib3mf-rs, so C, C++, C# and Python applications can load, inspect,
+//! validate and write 3MF files without a Rust toolchain.
+//!
+//! The crate builds a shared library (`liblib3mf_capi.so`, `lib3mf_capi.dll`,
+//! `liblib3mf_capi.dylib`) and a static library. The matching header,
+//! `include/lib3mf_capi.h`, is generated by cbindgen from this crate on every build.
+//!
+//! ## Conventions
+//!
+//! - Every fallible function returns a [`Lib3mfStatus`]; `LIB3MF_STATUS_OK` is zero. On
+//!   failure, [`lib3mf_last_error_message`] describes what went wrong.
+//! - Results are returned through out-pointers, which are only written on success.
+//! - Objects that the library allocates ([`Lib3mfModel`], [`Lib3mfValidationReport`], byte
+//!   buffers) have a matching `*_free` function. Free functions accept NULL.
+//! - Strings passed in are NUL-terminated UTF-8. Strings handed out are either copied into a
+//!   caller buffer (sized with a NULL-buffer query) or borrowed from a handle and valid
+//!   until that handle is freed.
+//! - Panics never cross the ABI boundary; they are reported as `LIB3MF_STATUS_PANIC`. This
+//!   needs `panic = "unwind"`, so build release libraries with the workspace's
+//!   `release-unwind` profile: with the `release` profile's `panic = "abort"` a panic ends
+//!   the process.
+//!
+//! ## C Usage
+//!
+//! ```c
+//! #include "lib3mf_capi.h"
+//! #include <stdio.h>
+//! #include <stdlib.h>
+//!
+//! int main(void) {
+//!     Lib3mfModel *model = NULL;
+//!     if (lib3mf_model_open("Benchy.3mf", &model) != LIB3MF_STATUS_OK) {
+//!         fprintf(stderr, "open failed: %s\n", lib3mf_last_error_message());
+//!         return 1;
+//!     }
+//!
+//!     size_t count = 0;
+//!     lib3mf_model_object_count(model, &count);
+//!     for (size_t i = 0; i < count; i++) {
+//!         uint32_t id;
+//!         Lib3mfObjectInfo info;
+//!         lib3mf_model_get_object_id(model, i, &id);
+//!         lib3mf_model_get_object_info(model, id, &info);
+//!         if (info.geometry == LIB3MF_GEOMETRY_KIND_MESH) {
+//!             float *xyz = malloc(3 * info.vertex_count * sizeof(float));
+//!             lib3mf_mesh_copy_vertices(model, id, xyz, 3 * info.vertex_count);
+//!             /* ... */
+//!             free(xyz);
+//!         }
+//!     }
+//!
+//!     lib3mf_model_free(model);
+//!     return 0;
+//! }
+//! ```
+//!
+//! See `examples/inspect.c` for a complete program and the README for build instructions.
+
+use std::ffi::{CStr, c_char};
+
+mod error;
+mod model;
+mod validation;
+
+pub use error::*;
+pub use model::*;
+pub use validation::*;
+
+use error::{FfiError, FfiResult};
+
+/// Returns the library version as a static NUL-terminated string (e.g. `"0.4.0"`).
+#[unsafe(no_mangle)]
+pub extern "C" fn lib3mf_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Borrows a NUL-terminated UTF-8 string argument.
+fn c_str<'a>(ptr: *const c_char, name: &str) -> FfiResult<&'a str> {
+    if ptr.is_null() {
+        return Err(FfiError::invalid_argument(format!("{name} is NULL")));
+    }
+    // SAFETY: the caller guarantees a non-NULL `ptr` is NUL-terminated.
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|_| FfiError::invalid_argument(format!("{name} is not valid UTF-8")))
+}
+
+/// Borrows an out-pointer argument.
+fn out_ptr<'a, T>(ptr: *mut T, name: &str) -> FfiResult<&'a mut T> {
+    // SAFETY: the caller guarantees a non-NULL `ptr` is valid for writes.
+    unsafe { ptr.as_mut() }.ok_or_else(|| FfiError::invalid_argument(format!("{name} is NULL")))
+}
+
+/// Borrows an input buffer; NULL is accepted only when `len` is zero.
+fn slice_from_raw<'a, T>(ptr: *const T, len: usize, name: &str) -> FfiResult<&'a [T]> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    if ptr.is_null() {
+        return Err(FfiError::invalid_argument(format!("{name} is NULL")));
+    }
+    if len > isize::MAX as usize / size_of::<T>().max(1) {
+        return Err(FfiError::invalid_argument(format!(
+            "{name} length {len} is too large"
+        )));
+    }
+    // SAFETY: the caller guarantees `ptr` is valid for `len` reads.
+    Ok(unsafe { std::slice::from_raw_parts(ptr, len) })
+}
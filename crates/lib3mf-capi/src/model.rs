@@ -0,0 +1,621 @@
+//! Model handles: loading, object iteration, mesh buffers, and writing.
+
+use crate::error::{FfiError, FfiResult, Lib3mfStatus, guard};
+use crate::{c_str, out_ptr, slice_from_raw};
+use lib3mf_core::Model;
+use lib3mf_core::archive::{
+    ArchiveReader, ZipArchiver, check_model_not_encrypted, find_model_path,
+};
+use lib3mf_core::model::{
+    BuildItem, Geometry, Mesh, Object, ObjectType, ResourceId, Triangle, Unit, Vertex,
+};
+use lib3mf_core::parser::parse_model;
+use std::ffi::c_char;
+use std::fs::File;
+use std::io::{BufWriter, Cursor, Read, Seek};
+
+/// Opaque handle to a parsed or newly created 3MF model.
+///
+/// Created by [`lib3mf_model_new`], [`lib3mf_model_open`] or [`lib3mf_model_from_bytes`]
+/// and released with [`lib3mf_model_free`]. A handle may be moved between threads but
+/// must not be used from two threads at once.
+pub struct Lib3mfModel {
+    pub(crate) model: Model,
+    /// Object IDs in ascending order, so index-based iteration is stable.
+    object_ids: Vec<ResourceId>,
+}
+
+impl Lib3mfModel {
+    fn new(model: Model) -> Self {
+        let mut object_ids: Vec<_> = model.resources.iter_objects().map(|o| o.id).collect();
+        object_ids.sort_by_key(|id| id.0);
+        Self { model, object_ids }
+    }
+
+    fn object(&self, id: u32) -> FfiResult<&Object> {
+        self.model
+            .resources
+            .get_object(ResourceId(id))
+            .ok_or_else(|| FfiError::new(Lib3mfStatus::NotFound, format!("No object with ID {id}")))
+    }
+
+    fn mesh(&self, id: u32) -> FfiResult<&Mesh> {
+        match &self.object(id)?.geometry {
+            Geometry::Mesh(mesh) => Ok(mesh),
+            _ => Err(FfiError::invalid_argument(format!(
+                "Object {id} does not have a triangle mesh"
+            ))),
+        }
+    }
+}
+
+/// Unit of measurement declared by the model.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lib3mfUnit {
+    Micron = 0,
+    Millimeter = 1,
+    Centimeter = 2,
+    Inch = 3,
+    Foot = 4,
+    Meter = 5,
+}
+
+impl From<Unit> for Lib3mfUnit {
+    fn from(unit: Unit) -> Self {
+        match unit {
+            Unit::Micron => Lib3mfUnit::Micron,
+            Unit::Millimeter => Lib3mfUnit::Millimeter,
+            Unit::Centimeter => Lib3mfUnit::Centimeter,
+            Unit::Inch => Lib3mfUnit::Inch,
+            Unit::Foot => Lib3mfUnit::Foot,
+            Unit::Meter => Lib3mfUnit::Meter,
+        }
+    }
+}
+
+/// Value of an object's `type` attribute.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lib3mfObjectType {
+    Model = 0,
+    Support = 1,
+    SolidSupport = 2,
+    Surface = 3,
+    Other = 4,
+}
+
+impl From<ObjectType> for Lib3mfObjectType {
+    fn from(object_type: ObjectType) -> Self {
+        match object_type {
+            ObjectType::Model => Lib3mfObjectType::Model,
+            ObjectType::Support => Lib3mfObjectType::Support,
+            ObjectType::SolidSupport => Lib3mfObjectType::SolidSupport,
+            ObjectType::Surface => Lib3mfObjectType::Surface,
+            ObjectType::Other => Lib3mfObjectType::Other,
+        }
+    }
+}
+
+/// Kind of geometry an object holds.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lib3mfGeometryKind {
+    Mesh = 0,
+    Components = 1,
+    SliceStack = 2,
+    VolumetricStack = 3,
+    BooleanShape = 4,
+    DisplacementMesh = 5,
+    LevelSet = 6,
+}
+
+/// Summary of one object, filled in by [`lib3mf_model_get_object_info`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct Lib3mfObjectInfo {
+    pub id: u32,
+    pub object_type: Lib3mfObjectType,
+    pub geometry: Lib3mfGeometryKind,
+    /// Number of vertices; 0 unless `geometry` is a mesh or displacement mesh.
+    pub vertex_count: usize,
+    /// Number of triangles; 0 unless `geometry` is a mesh or displacement mesh.
+    pub triangle_count: usize,
+    /// Number of components; 0 unless `geometry` is `LIB3MF_GEOMETRY_KIND_COMPONENTS`.
+    pub component_count: usize,
+}
+
+fn load_package<R: Read + Seek>(reader: R) -> FfiResult<Model> {
+    let mut archiver = ZipArchiver::new(reader)?;
+    let model_path = find_model_path(&mut archiver)?;
+    check_model_not_encrypted(&mut archiver, &model_path)?;
+    let data = archiver.read_entry(&model_path)?;
+    Ok(parse_model(Cursor::new(data))?)
+}
+
+fn into_handle(model: Model, out: *mut *mut Lib3mfModel) -> FfiResult {
+    let out = out_ptr(out, "out_model")?;
+    *out = Box::into_raw(Box::new(Lib3mfModel::new(model)));
+    Ok(())
+}
+
+pub(crate) fn model_ref<'a>(model: *const Lib3mfModel) -> FfiResult<&'a Lib3mfModel> {
+    // SAFETY: callers of the public functions guarantee `model` came from this library.
+    unsafe { model.as_ref() }.ok_or_else(|| FfiError::invalid_argument("model is NULL"))
+}
+
+fn model_mut<'a>(model: *mut Lib3mfModel) -> FfiResult<&'a mut Lib3mfModel> {
+    // SAFETY: as for `model_ref`; the caller also guarantees exclusive access.
+    unsafe { model.as_mut() }.ok_or_else(|| FfiError::invalid_argument("model is NULL"))
+}
+
+/// Creates an empty model (millimeters, no objects, empty build).
+///
+/// # Safety
+///
+/// `out_model` must be a valid pointer to writable storage for one handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_model_new(out_model: *mut *mut Lib3mfModel) -> Lib3mfStatus {
+    guard(|| into_handle(Model::default(), out_model))
+}
+
+/// Opens and parses a `.3mf` package from a file path (UTF-8, NUL-terminated).
+///
+/// # Safety
+///
+/// `path` must be a NUL-terminated string and `out_model` a valid pointer to writable
+/// storage for one handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_model_open(
+    path: *const c_char,
+    out_model: *mut *mut Lib3mfModel,
+) -> Lib3mfStatus {
+    guard(|| {
+        let path = c_str(path, "path")?;
+        let file = File::open(path).map_err(|e| {
+            FfiError::new(Lib3mfStatus::Io, format!("Failed to open '{path}': {e}"))
+        })?;
+        into_handle(load_package(file)?, out_model)
+    })
+}
+
+/// Parses a `.3mf` package held in memory. The bytes are copied; the caller keeps ownership.
+///
+/// # Safety
+///
+/// `data` must point to `len` readable bytes and `out_model` must be a valid pointer to
+/// writable storage for one handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_model_from_bytes(
+    data: *const u8,
+    len: usize,
+    out_model: *mut *mut Lib3mfModel,
+) -> Lib3mfStatus {
+    guard(|| {
+        let data = slice_from_raw(data, len, "data")?;
+        into_handle(load_package(Cursor::new(data.to_vec()))?, out_model)
+    })
+}
+
+/// Releases a model handle. Passing NULL is a no-op.
+///
+/// # Safety
+///
+/// `model` must be NULL or a handle returned by this library that has not been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_model_free(model: *mut Lib3mfModel) {
+    if !model.is_null() {
+        // SAFETY: the pointer was produced by `Box::into_raw` in `into_handle`.
+        drop(unsafe { Box::from_raw(model) });
+    }
+}
+
+/// Writes the unit of measurement to `out_unit`.
+///
+/// # Safety
+///
+/// `model` must be a live handle and `out_unit` a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_model_get_unit(
+    model: *const Lib3mfModel,
+    out_unit: *mut Lib3mfUnit,
+) -> Lib3mfStatus {
+    guard(|| {
+        let model = model_ref(model)?;
+        *out_ptr(out_unit, "out_unit")? = model.model.unit.into();
+        Ok(())
+    })
+}
+
+/// Writes the number of objects in the model's resources to `out_count`.
+///
+/// # Safety
+///
+/// `model` must be a live handle and `out_count` a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_model_object_count(
+    model: *const Lib3mfModel,
+    out_count: *mut usize,
+) -> Lib3mfStatus {
+    guard(|| {
+        let model = model_ref(model)?;
+        *out_ptr(out_count, "out_count")? = model.object_ids.len();
+        Ok(())
+    })
+}
+
+/// Writes the ID of the object at `index` (0-based, in ascending ID order) to `out_id`.
+///
+/// # Safety
+///
+/// `model` must be a live handle and `out_id` a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_model_get_object_id(
+    model: *const Lib3mfModel,
+    index: usize,
+    out_id: *mut u32,
+) -> Lib3mfStatus {
+    guard(|| {
+        let model = model_ref(model)?;
+        let id = model.object_ids.get(index).ok_or_else(|| {
+            FfiError::invalid_argument(format!(
+                "Object index {index} out of range ({} objects)",
+                model.object_ids.len()
+            ))
+        })?;
+        *out_ptr(out_id, "out_id")? = id.0;
+        Ok(())
+    })
+}
+
+/// Fills `out_info` with a summary of object `id`.
+///
+/// # Safety
+///
+/// `model` must be a live handle and `out_info` a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_model_get_object_info(
+    model: *const Lib3mfModel,
+    id: u32,
+    out_info: *mut Lib3mfObjectInfo,
+) -> Lib3mfStatus {
+    guard(|| {
+        let object = model_ref(model)?.object(id)?;
+        let (geometry, vertex_count, triangle_count, component_count) = match &object.geometry {
+            Geometry::Mesh(mesh) => (
+                Lib3mfGeometryKind::Mesh,
+                mesh.vertices.len(),
+                mesh.triangles.len(),
+                0,
+            ),
+            Geometry::PreciseMesh(mesh) => (
+                Lib3mfGeometryKind::Mesh,
+                mesh.vertices.len(),
+                mesh.triangles.len(),
+                0,
+            ),
+            Geometry::LazyMesh(lazy) => (
+                Lib3mfGeometryKind::Mesh,
+                lazy.vertex_count,
+                lazy.triangle_count,
+                0,
+            ),
+            Geometry::Components(c) => (Lib3mfGeometryKind::Components, 0, 0, c.components.len()),
+            Geometry::SliceStack(_) => (Lib3mfGeometryKind::SliceStack, 0, 0, 0),
+            Geometry::VolumetricStack(_) => (Lib3mfGeometryKind::VolumetricStack, 0, 0, 0),
+            Geometry::BooleanShape(_) => (Lib3mfGeometryKind::BooleanShape, 0, 0, 0),
+            Geometry::LevelSet(_) => (Lib3mfGeometryKind::LevelSet, 0, 0, 0),
+            Geometry::DisplacementMesh(mesh) => (
+                Lib3mfGeometryKind::DisplacementMesh,
+                mesh.vertices.len(),
+                mesh.triangles.len(),
+                0,
+            ),
+        };
+        *out_ptr(out_info, "out_info")? = Lib3mfObjectInfo {
+            id,
+            object_type: object.object_type.into(),
+            geometry,
+            vertex_count,
+            triangle_count,
+            component_count,
+        };
+        Ok(())
+    })
+}
+
+/// Copies the name of object `id` into `buffer` as a NUL-terminated UTF-8 string.
+///
+/// The required size, including the terminator, is always written to `out_len`; an
+/// unnamed object has an empty name. Pass a NULL `buffer` to query the size only. If
+/// `buffer_len` is too small, nothing is copied and `LIB3MF_STATUS_BUFFER_TOO_SMALL`
+/// is returned.
+///
+/// # Safety
+///
+/// `model` must be a live handle, `out_len` a valid pointer, and `buffer` NULL or valid
+/// for `buffer_len` bytes of writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_model_get_object_name(
+    model: *const Lib3mfModel,
+    id: u32,
+    buffer: *mut c_char,
+    buffer_len: usize,
+    out_len: *mut usize,
+) -> Lib3mfStatus {
+    guard(|| {
+        let name = model_ref(model)?.object(id)?.name.as_deref().unwrap_or("");
+        let needed = name.len() + 1;
+        *out_ptr(out_len, "out_len")? = needed;
+        if buffer.is_null() {
+            return Ok(());
+        }
+        if buffer_len < needed {
+            return Err(FfiError::new(
+                Lib3mfStatus::BufferTooSmall,
+                format!("Name needs {needed} bytes, buffer has {buffer_len}"),
+            ));
+        }
+        // SAFETY: `buffer` is valid for `buffer_len >= needed` bytes per the contract.
+        unsafe {
+            std::ptr::copy_nonoverlapping(name.as_ptr(), buffer.cast::<u8>(), name.len());
+            *buffer.add(name.len()) = 0;
+        }
+        Ok(())
+    })
+}
+
+/// Copies the vertices of mesh object `id` into `out_xyz` as packed `x, y, z` floats.
+///
+/// `len` is the capacity of `out_xyz` in floats and must be at least
+/// `3 * vertex_count` (see [`lib3mf_model_get_object_info`]).
+///
+/// # Safety
+///
+/// `model` must be a live handle and `out_xyz` valid for `len` float writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_mesh_copy_vertices(
+    model: *const Lib3mfModel,
+    id: u32,
+    out_xyz: *mut f32,
+    len: usize,
+) -> Lib3mfStatus {
+    guard(|| {
+        let mesh = model_ref(model)?.mesh(id)?;
+        let out = out_slice(out_xyz, len, mesh.vertices.len() * 3, "out_xyz")?;
+        for (dst, v) in out.chunks_exact_mut(3).zip(&mesh.vertices) {
+            dst.copy_from_slice(&[v.x, v.y, v.z]);
+        }
+        Ok(())
+    })
+}
+
+/// Copies the triangles of mesh object `id` into `out_indices` as packed `v1, v2, v3`
+/// vertex indices.
+///
+/// `len` is the capacity of `out_indices` in elements and must be at least
+/// `3 * triangle_count`.
+///
+/// # Safety
+///
+/// `model` must be a live handle and `out_indices` valid for `len` writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_mesh_copy_triangles(
+    model: *const Lib3mfModel,
+    id: u32,
+    out_indices: *mut u32,
+    len: usize,
+) -> Lib3mfStatus {
+    guard(|| {
+        let mesh = model_ref(model)?.mesh(id)?;
+        let out = out_slice(out_indices, len, mesh.triangles.len() * 3, "out_indices")?;
+        for (dst, t) in out.chunks_exact_mut(3).zip(&mesh.triangles) {
+            dst.copy_from_slice(&[t.v1, t.v2, t.v3]);
+        }
+        Ok(())
+    })
+}
+
+/// Returns the number of buffer elements for `count` items of three, rejecting counts
+/// whose buffer length overflows.
+fn triple(count: usize, name: &str) -> FfiResult<usize> {
+    count
+        .checked_mul(3)
+        .ok_or_else(|| FfiError::invalid_argument(format!("{name} {count} is too large")))
+}
+
+fn out_slice<'a, T>(ptr: *mut T, len: usize, needed: usize, name: &str) -> FfiResult<&'a mut [T]> {
+    if len < needed {
+        return Err(FfiError::new(
+            Lib3mfStatus::BufferTooSmall,
+            format!("{name} needs {needed} elements, buffer has {len}"),
+        ));
+    }
+    if ptr.is_null() {
+        return Err(FfiError::invalid_argument(format!("{name} is NULL")));
+    }
+    // SAFETY: the caller guarantees `ptr` is valid for `len >= needed` writes.
+    Ok(unsafe { std::slice::from_raw_parts_mut(ptr, needed) })
+}
+
+/// Adds a mesh object built from packed vertex and triangle buffers.
+///
+/// `vertices` holds `3 * vertex_count` floats (`x, y, z`) and `indices` holds
+/// `3 * triangle_count` vertex indices. The new object gets the lowest unused resource
+/// ID, which is written to `out_id`. The object is not placed on the build plate; call
+/// [`lib3mf_model_add_build_item`] for that.
+///
+/// # Safety
+///
+/// `model` must be a live handle, `vertices` and `indices` must be readable for the
+/// given lengths, and `out_id` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_model_add_mesh_object(
+    model: *mut Lib3mfModel,
+    vertices: *const f32,
+    vertex_count: usize,
+    indices: *const u32,
+    triangle_count: usize,
+    out_id: *mut u32,
+) -> Lib3mfStatus {
+    guard(|| {
+        let handle = model_mut(model)?;
+        let vertices = slice_from_raw(vertices, triple(vertex_count, "vertex_count")?, "vertices")?;
+        let indices = slice_from_raw(
+            indices,
+            triple(triangle_count, "triangle_count")?,
+            "indices",
+        )?;
+        if let Some(&bad) = indices.iter().find(|&&i| i as usize >= vertex_count) {
+            return Err(FfiError::invalid_argument(format!(
+                "Triangle index {bad} out of range ({vertex_count} vertices)"
+            )));
+        }
+
+        let mesh = Mesh {
+            vertices: vertices
+                .chunks_exact(3)
+                .map(|v| Vertex {
+                    x: v[0],
+                    y: v[1],
+                    z: v[2],
+                })
+                .collect(),
+            triangles: indices
+                .chunks_exact(3)
+                .map(|t| Triangle {
+                    v1: t[0],
+                    v2: t[1],
+                    v3: t[2],
+                    ..Default::default()
+                })
+                .collect(),
+            beam_lattice: None,
+            volume_id: None,
+            triangle_sets: Vec::new(),
+            triangle_attributes: Default::default(),
+            normals: None,
+        };
+
+        let resources = &mut handle.model.resources;
+        let id = (1..=u32::MAX)
+            .map(ResourceId)
+            .find(|id| !resources.exists(*id))
+            .ok_or_else(|| FfiError::invalid_argument("No free resource ID"))?;
+        resources.add_object(Object {
+            id,
+            object_type: ObjectType::Model,
+            name: None,
+            part_number: None,
+            uuid: None,
+            pid: None,
+            pindex: None,
+            thumbnail: None,
+            geometry: Geometry::Mesh(mesh),
+        })?;
+        handle.object_ids.push(id);
+        *out_ptr(out_id, "out_id")? = id.0;
+        Ok(())
+    })
+}
+
+/// Places object `id` on the build plate.
+///
+/// `transform` is NULL for identity, or 12 floats in the order of the 3MF `transform`
+/// attribute (`m00 m01 m02 m10 m11 m12 m20 m21 m22 m30 m31 m32`).
+///
+/// # Safety
+///
+/// `model` must be a live handle and `transform` NULL or readable for 12 floats.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_model_add_build_item(
+    model: *mut Lib3mfModel,
+    id: u32,
+    transform: *const f32,
+) -> Lib3mfStatus {
+    guard(|| {
+        let handle = model_mut(model)?;
+        handle.object(id)?;
+        let transform = if transform.is_null() {
+            glam::Mat4::IDENTITY
+        } else {
+            let p = slice_from_raw(transform, 12, "transform")?;
+            glam::Mat4::from_cols_array(&[
+                p[0], p[1], p[2], 0.0, p[3], p[4], p[5], 0.0, p[6], p[7], p[8], 0.0, p[9], p[10],
+                p[11], 1.0,
+            ])
+        };
+        handle.model.build.items.push(BuildItem {
+            object_id: ResourceId(id),
+            uuid: None,
+            path: None,
+            part_number: None,
+            transform,
+            printable: None,
+            metadata: Default::default(),
+        });
+        Ok(())
+    })
+}
+
+/// Writes the model as a `.3mf` package to a file path (UTF-8, NUL-terminated).
+///
+/// # Safety
+///
+/// `model` must be a live handle and `path` a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_model_write(
+    model: *const Lib3mfModel,
+    path: *const c_char,
+) -> Lib3mfStatus {
+    guard(|| {
+        let model = model_ref(model)?;
+        let path = c_str(path, "path")?;
+        let file = File::create(path).map_err(|e| {
+            FfiError::new(Lib3mfStatus::Io, format!("Failed to create '{path}': {e}"))
+        })?;
+        model.model.write(BufWriter::new(file))?;
+        Ok(())
+    })
+}
+
+/// Serializes the model as a `.3mf` package into a library-owned buffer.
+///
+/// On success `*out_data` points to `*out_len` bytes that must be released with
+/// [`lib3mf_bytes_free`].
+///
+/// # Safety
+///
+/// `model` must be a live handle; `out_data` and `out_len` must be valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_model_to_bytes(
+    model: *const Lib3mfModel,
+    out_data: *mut *mut u8,
+    out_len: *mut usize,
+) -> Lib3mfStatus {
+    guard(|| {
+        let model = model_ref(model)?;
+        let out_data = out_ptr(out_data, "out_data")?;
+        let out_len = out_ptr(out_len, "out_len")?;
+        let mut buffer = Cursor::new(Vec::new());
+        model.model.write(&mut buffer)?;
+        let bytes = buffer.into_inner().into_boxed_slice();
+        *out_len = bytes.len();
+        *out_data = Box::into_raw(bytes).cast::<u8>();
+        Ok(())
+    })
+}
+
+/// Releases a buffer returned by [`lib3mf_model_to_bytes`]. Passing NULL is a no-op.
+///
+/// # Safety
+///
+/// `data` and `len` must be exactly the values produced by [`lib3mf_model_to_bytes`],
+/// and the buffer must not have been freed already.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_bytes_free(data: *mut u8, len: usize) {
+    if !data.is_null() {
+        // SAFETY: reconstructs the boxed slice leaked by `lib3mf_model_to_bytes`.
+        drop(unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(data, len)) });
+    }
+}
@@ -0,0 +1,201 @@
+//! Validation reports.
+
+use crate::error::{FfiError, Lib3mfStatus, c_string_lossy, guard};
+use crate::model::{Lib3mfModel, model_ref};
+use crate::out_ptr;
+use lib3mf_core::validation::{ValidationLevel, ValidationSeverity};
+use std::ffi::{CString, c_char};
+
+/// Validation strictness, matching the core library's levels.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lib3mfValidationLevel {
+    Minimal = 0,
+    Standard = 1,
+    Strict = 2,
+    Paranoid = 3,
+}
+
+impl From<Lib3mfValidationLevel> for ValidationLevel {
+    fn from(level: Lib3mfValidationLevel) -> Self {
+        match level {
+            Lib3mfValidationLevel::Minimal => ValidationLevel::Minimal,
+            Lib3mfValidationLevel::Standard => ValidationLevel::Standard,
+            Lib3mfValidationLevel::Strict => ValidationLevel::Strict,
+            Lib3mfValidationLevel::Paranoid => ValidationLevel::Paranoid,
+        }
+    }
+}
+
+/// Severity of a validation finding.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lib3mfSeverity {
+    Error = 0,
+    Warning = 1,
+    Info = 2,
+}
+
+impl From<ValidationSeverity> for Lib3mfSeverity {
+    fn from(severity: ValidationSeverity) -> Self {
+        match severity {
+            ValidationSeverity::Error => Lib3mfSeverity::Error,
+            ValidationSeverity::Warning => Lib3mfSeverity::Warning,
+            ValidationSeverity::Info => Lib3mfSeverity::Info,
+        }
+    }
+}
+
+/// One finding, filled in by [`lib3mf_report_get_item`].
+///
+/// The string pointers are owned by the report and stay valid until it is freed.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct Lib3mfValidationItem {
+    pub severity: Lib3mfSeverity,
+    pub code: u32,
+    /// Human-readable description; never NULL.
+    pub message: *const c_char,
+    /// Suggested fix, or NULL.
+    pub suggestion: *const c_char,
+    /// Affected object or resource (e.g. `"Object 5"`), or NULL.
+    pub context: *const c_char,
+}
+
+struct ReportItem {
+    severity: Lib3mfSeverity,
+    code: u32,
+    message: CString,
+    suggestion: Option<CString>,
+    context: Option<CString>,
+}
+
+/// Opaque handle to the result of [`lib3mf_model_validate`], released with
+/// [`lib3mf_report_free`].
+pub struct Lib3mfValidationReport {
+    items: Vec<ReportItem>,
+}
+
+/// Validates the model at `level` and returns a new report in `out_report`.
+///
+/// A model with findings still returns `LIB3MF_STATUS_OK`; inspect the report with
+/// [`lib3mf_report_error_count`] and [`lib3mf_report_get_item`].
+///
+/// # Safety
+///
+/// `model` must be a live handle and `out_report` a valid pointer to writable storage
+/// for one handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_model_validate(
+    model: *const Lib3mfModel,
+    level: Lib3mfValidationLevel,
+    out_report: *mut *mut Lib3mfValidationReport,
+) -> Lib3mfStatus {
+    guard(|| {
+        let model = model_ref(model)?;
+        let out_report = out_ptr(out_report, "out_report")?;
+        let report = model.model.validate(level.into());
+        let items = report
+            .items
+            .into_iter()
+            .map(|item| ReportItem {
+                severity: item.severity.into(),
+                code: item.code,
+                message: c_string_lossy(item.message),
+                suggestion: item.suggestion.map(c_string_lossy),
+                context: item.context.map(c_string_lossy),
+            })
+            .collect();
+        *out_report = Box::into_raw(Box::new(Lib3mfValidationReport { items }));
+        Ok(())
+    })
+}
+
+fn report_ref<'a>(
+    report: *const Lib3mfValidationReport,
+) -> Result<&'a Lib3mfValidationReport, FfiError> {
+    // SAFETY: callers guarantee `report` is NULL or a live report handle.
+    unsafe { report.as_ref() }.ok_or_else(|| FfiError::invalid_argument("report is NULL"))
+}
+
+/// Writes the total number of findings to `out_count`.
+///
+/// # Safety
+///
+/// `report` must be a live report handle and `out_count` a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_report_item_count(
+    report: *const Lib3mfValidationReport,
+    out_count: *mut usize,
+) -> Lib3mfStatus {
+    guard(|| {
+        *out_ptr(out_count, "out_count")? = report_ref(report)?.items.len();
+        Ok(())
+    })
+}
+
+/// Writes the number of error-severity findings to `out_count`.
+///
+/// # Safety
+///
+/// `report` must be a live report handle and `out_count` a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_report_error_count(
+    report: *const Lib3mfValidationReport,
+    out_count: *mut usize,
+) -> Lib3mfStatus {
+    guard(|| {
+        let errors = report_ref(report)?
+            .items
+            .iter()
+            .filter(|item| item.severity == Lib3mfSeverity::Error)
+            .count();
+        *out_ptr(out_count, "out_count")? = errors;
+        Ok(())
+    })
+}
+
+/// Fills `out_item` with finding `index` (0-based, in the order they were reported).
+///
+/// # Safety
+///
+/// `report` must be a live report handle and `out_item` a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_report_get_item(
+    report: *const Lib3mfValidationReport,
+    index: usize,
+    out_item: *mut Lib3mfValidationItem,
+) -> Lib3mfStatus {
+    guard(|| {
+        let report = report_ref(report)?;
+        let item = report.items.get(index).ok_or_else(|| {
+            FfiError::invalid_argument(format!(
+                "Item index {index} out of range ({} items)",
+                report.items.len()
+            ))
+        })?;
+        let optional = |s: &Option<CString>| s.as_ref().map_or(std::ptr::null(), |s| s.as_ptr());
+        *out_ptr(out_item, "out_item")? = Lib3mfValidationItem {
+            severity: item.severity,
+            code: item.code,
+            message: item.message.as_ptr(),
+            suggestion: optional(&item.suggestion),
+            context: optional(&item.context),
+        };
+        Ok(())
+    })
+}
+
+/// Releases a report. Passing NULL is a no-op.
+///
+/// # Safety
+///
+/// `report` must be NULL or a report handle that has not been freed; the strings of any
+/// items read from it become dangling.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lib3mf_report_free(report: *mut Lib3mfValidationReport) {
+    if !report.is_null() {
+        // SAFETY: the pointer was produced by `Box::into_raw` in `lib3mf_model_validate`.
+        drop(unsafe { Box::from_raw(report) });
+    }
+}
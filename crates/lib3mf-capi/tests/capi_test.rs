@@ -0,0 +1,314 @@
+use lib3mf_capi::*;
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+fn last_error() -> String {
+    let message = lib3mf_last_error_message();
+    assert!(!message.is_null(), "expected an error message");
+    unsafe { CStr::from_ptr(message) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Builds a model holding one tetrahedron placed twice on the build plate.
+unsafe fn tetrahedron_model() -> (*mut Lib3mfModel, u32) {
+    let vertices: [f32; 12] = [
+        0.0, 0.0, 0.0, 10.0, 0.0, 0.0, 0.0, 10.0, 0.0, 0.0, 0.0, 10.0,
+    ];
+    let indices: [u32; 12] = [0, 2, 1, 0, 1, 3, 1, 2, 3, 0, 3, 2];
+    let translate: [f32; 12] = [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 20.0, 0.0, 0.0];
+
+    let mut model = ptr::null_mut();
+    let mut id = 0;
+    unsafe {
+        assert_eq!(lib3mf_model_new(&mut model), Lib3mfStatus::Ok);
+        assert_eq!(
+            lib3mf_model_add_mesh_object(model, vertices.as_ptr(), 4, indices.as_ptr(), 4, &mut id),
+            Lib3mfStatus::Ok
+        );
+        assert_eq!(
+            lib3mf_model_add_build_item(model, id, ptr::null()),
+            Lib3mfStatus::Ok
+        );
+        assert_eq!(
+            lib3mf_model_add_build_item(model, id, translate.as_ptr()),
+            Lib3mfStatus::Ok
+        );
+    }
+    (model, id)
+}
+
+#[test]
+fn test_version_matches_crate() {
+    let version = unsafe { CStr::from_ptr(lib3mf_version()) };
+    assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn test_roundtrip_through_bytes() {
+    unsafe {
+        let (model, id) = tetrahedron_model();
+        assert_eq!(id, 1);
+
+        let mut data = ptr::null_mut();
+        let mut len = 0;
+        assert_eq!(
+            lib3mf_model_to_bytes(model, &mut data, &mut len),
+            Lib3mfStatus::Ok
+        );
+        lib3mf_model_free(model);
+
+        let mut reloaded = ptr::null_mut();
+        assert_eq!(
+            lib3mf_model_from_bytes(data, len, &mut reloaded),
+            Lib3mfStatus::Ok
+        );
+        lib3mf_bytes_free(data, len);
+
+        let mut unit = Lib3mfUnit::Meter;
+        assert_eq!(lib3mf_model_get_unit(reloaded, &mut unit), Lib3mfStatus::Ok);
+        assert_eq!(unit, Lib3mfUnit::Millimeter);
+
+        let mut count = 0;
+        assert_eq!(
+            lib3mf_model_object_count(reloaded, &mut count),
+            Lib3mfStatus::Ok
+        );
+        assert_eq!(count, 1);
+
+        let mut id = 0;
+        assert_eq!(
+            lib3mf_model_get_object_id(reloaded, 0, &mut id),
+            Lib3mfStatus::Ok
+        );
+        let mut info = std::mem::zeroed::<Lib3mfObjectInfo>();
+        assert_eq!(
+            lib3mf_model_get_object_info(reloaded, id, &mut info),
+            Lib3mfStatus::Ok
+        );
+        assert_eq!(info.geometry, Lib3mfGeometryKind::Mesh);
+        assert_eq!(info.object_type, Lib3mfObjectType::Model);
+        assert_eq!((info.vertex_count, info.triangle_count), (4, 4));
+
+        let mut xyz = vec![0.0f32; info.vertex_count * 3];
+        assert_eq!(
+            lib3mf_mesh_copy_vertices(reloaded, id, xyz.as_mut_ptr(), xyz.len()),
+            Lib3mfStatus::Ok
+        );
+        assert_eq!(&xyz[3..6], &[10.0, 0.0, 0.0]);
+
+        let mut indices = vec![0u32; info.triangle_count * 3];
+        assert_eq!(
+            lib3mf_mesh_copy_triangles(reloaded, id, indices.as_mut_ptr(), indices.len()),
+            Lib3mfStatus::Ok
+        );
+        assert_eq!(indices, [0, 2, 1, 0, 1, 3, 1, 2, 3, 0, 3, 2]);
+
+        lib3mf_model_free(reloaded);
+    }
+}
+
+#[test]
+fn test_open_write_and_validate_file() {
+    let dir = std::env::temp_dir().join(format!("lib3mf-capi-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = CString::new(dir.join("tetra.3mf").to_str().unwrap()).unwrap();
+
+    unsafe {
+        let (model, _) = tetrahedron_model();
+        assert_eq!(lib3mf_model_write(model, path.as_ptr()), Lib3mfStatus::Ok);
+        lib3mf_model_free(model);
+
+        let mut model = ptr::null_mut();
+        assert_eq!(
+            lib3mf_model_open(path.as_ptr(), &mut model),
+            Lib3mfStatus::Ok
+        );
+
+        let mut report = ptr::null_mut();
+        assert_eq!(
+            lib3mf_model_validate(model, Lib3mfValidationLevel::Paranoid, &mut report),
+            Lib3mfStatus::Ok
+        );
+        let mut errors = usize::MAX;
+        assert_eq!(
+            lib3mf_report_error_count(report, &mut errors),
+            Lib3mfStatus::Ok
+        );
+        assert_eq!(errors, 0);
+        lib3mf_report_free(report);
+        lib3mf_model_free(model);
+    }
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_add_mesh_rejects_overflowing_counts() {
+    unsafe {
+        let mut model = ptr::null_mut();
+        assert_eq!(lib3mf_model_new(&mut model), Lib3mfStatus::Ok);
+        let vertices = [0.0f32; 3];
+        let indices = [0u32; 3];
+        let mut id = 0;
+        for (vertex_count, triangle_count) in [(usize::MAX / 2, 1), (1, usize::MAX / 3 + 1)] {
+            assert_eq!(
+                lib3mf_model_add_mesh_object(
+                    model,
+                    vertices.as_ptr(),
+                    vertex_count,
+                    indices.as_ptr(),
+                    triangle_count,
+                    &mut id
+                ),
+                Lib3mfStatus::InvalidArgument
+            );
+        }
+        lib3mf_model_free(model);
+    }
+}
+
+#[test]
+fn test_validation_items_expose_messages() {
+    unsafe {
+        let mut model = ptr::null_mut();
+        assert_eq!(lib3mf_model_new(&mut model), Lib3mfStatus::Ok);
+        // A triangle that references the same vertex three times is degenerate.
+        let vertices = [0.0f32; 3];
+        let indices = [0u32; 3];
+        let mut id = 0;
+        assert_eq!(
+            lib3mf_model_add_mesh_object(model, vertices.as_ptr(), 1, indices.as_ptr(), 1, &mut id),
+            Lib3mfStatus::Ok
+        );
+
+        let mut report = ptr::null_mut();
+        assert_eq!(
+            lib3mf_model_validate(model, Lib3mfValidationLevel::Paranoid, &mut report),
+            Lib3mfStatus::Ok
+        );
+        let mut count = 0;
+        assert_eq!(
+            lib3mf_report_item_count(report, &mut count),
+            Lib3mfStatus::Ok
+        );
+        assert!(count > 0);
+        for index in 0..count {
+            let mut item = std::mem::zeroed::<Lib3mfValidationItem>();
+            assert_eq!(
+                lib3mf_report_get_item(report, index, &mut item),
+                Lib3mfStatus::Ok
+            );
+            assert!(!CStr::from_ptr(item.message).to_bytes().is_empty());
+        }
+        let mut item = std::mem::zeroed::<Lib3mfValidationItem>();
+        assert_eq!(
+            lib3mf_report_get_item(report, count, &mut item),
+            Lib3mfStatus::InvalidArgument
+        );
+        lib3mf_report_free(report);
+        lib3mf_model_free(model);
+    }
+}
+
+#[test]
+fn test_object_name_buffer_protocol() {
+    let xml = r#"<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+        <resources><object id="7" name="Bracket"><mesh><vertices/><triangles/></mesh></object></resources>
+        <build/>
+    </model>"#;
+    let parsed = lib3mf_core::parser::parse_model(std::io::Cursor::new(xml)).unwrap();
+    let mut package = std::io::Cursor::new(Vec::new());
+    parsed.write(&mut package).unwrap();
+    let package = package.into_inner();
+
+    unsafe {
+        let mut model = ptr::null_mut();
+        assert_eq!(
+            lib3mf_model_from_bytes(package.as_ptr(), package.len(), &mut model),
+            Lib3mfStatus::Ok
+        );
+
+        let mut needed = 0;
+        assert_eq!(
+            lib3mf_model_get_object_name(model, 7, ptr::null_mut(), 0, &mut needed),
+            Lib3mfStatus::Ok
+        );
+        assert_eq!(needed, "Bracket".len() + 1);
+
+        let mut small = [0 as std::ffi::c_char; 4];
+        assert_eq!(
+            lib3mf_model_get_object_name(model, 7, small.as_mut_ptr(), small.len(), &mut needed),
+            Lib3mfStatus::BufferTooSmall
+        );
+
+        let mut buffer = vec![0 as std::ffi::c_char; needed];
+        assert_eq!(
+            lib3mf_model_get_object_name(model, 7, buffer.as_mut_ptr(), buffer.len(), &mut needed),
+            Lib3mfStatus::Ok
+        );
+        assert_eq!(CStr::from_ptr(buffer.as_ptr()).to_str().unwrap(), "Bracket");
+        lib3mf_model_free(model);
+    }
+}
+
+#[test]
+fn test_errors_set_status_and_message() {
+    unsafe {
+        let mut model = ptr::null_mut();
+        let garbage = b"not a zip file";
+        assert_eq!(
+            lib3mf_model_from_bytes(garbage.as_ptr(), garbage.len(), &mut model),
+            Lib3mfStatus::Io
+        );
+        assert!(model.is_null());
+        assert!(!last_error().is_empty());
+
+        let missing = CString::new("/nonexistent/model.3mf").unwrap();
+        assert_eq!(
+            lib3mf_model_open(missing.as_ptr(), &mut model),
+            Lib3mfStatus::Io
+        );
+        assert!(last_error().contains("/nonexistent/model.3mf"));
+
+        assert_eq!(
+            lib3mf_model_open(ptr::null(), &mut model),
+            Lib3mfStatus::InvalidArgument
+        );
+        assert_eq!(last_error(), "path is NULL");
+
+        let (model, id) = tetrahedron_model();
+        let mut info = std::mem::zeroed::<Lib3mfObjectInfo>();
+        assert_eq!(
+            lib3mf_model_get_object_info(model, 99, &mut info),
+            Lib3mfStatus::NotFound
+        );
+        let mut out = [0.0f32; 2];
+        assert_eq!(
+            lib3mf_mesh_copy_vertices(model, id, out.as_mut_ptr(), out.len()),
+            Lib3mfStatus::BufferTooSmall
+        );
+        let mut bad_id = 0;
+        assert_eq!(
+            lib3mf_model_add_mesh_object(
+                model,
+                out.as_ptr(),
+                0,
+                [0u32; 3].as_ptr(),
+                1,
+                &mut bad_id
+            ),
+            Lib3mfStatus::InvalidArgument
+        );
+
+        // A successful call clears the message.
+        let mut count = 0;
+        assert_eq!(
+            lib3mf_model_object_count(model, &mut count),
+            Lib3mfStatus::Ok
+        );
+        assert!(lib3mf_last_error_message().is_null());
+
+        lib3mf_model_free(model);
+        lib3mf_model_free(ptr::null_mut());
+    }
+}
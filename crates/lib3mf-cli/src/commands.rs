@@ -4,19 +4,61 @@
 //! corresponds to a CLI subcommand and can be called programmatically.
 
 pub mod batch;
+/// Multi-phase timing of parse, stats, write, repair, and validation.
+pub mod benchmark;
+/// Conformance suite runs with a pass/fail matrix and JUnit report.
+pub mod conformance;
+/// Package copying that keeps signed and encrypted parts intact.
+pub mod copy;
+/// Plane cuts with capped faces and alignment pins.
+pub mod cut;
+/// Model dumps for debugging, filtered by section, resource, depth and mesh data.
+pub mod dump;
+/// Single-object extraction, optionally streamed without loading the model.
+pub mod extract_object;
+/// Bulk extraction of textures, model parts and parts of a content type.
+pub mod extract_parts;
+/// Canonical content digests for comparing models across tools.
+pub mod hash;
+/// Shelling of solid objects with optional drain holes.
+pub mod hollow;
+/// Package summary: extensions declared, required and used, and part sizes.
+pub mod info;
+/// Private key and self-signed certificate generation for signing and encryption tests.
+pub mod keygen;
+/// Beam lattice infill of hollowed objects.
+pub mod lattice_fill;
 pub mod merge;
+/// Package size reduction: pruning, deduplication, image and ZIP recompression.
+pub mod optimize;
+/// Build volumes of common printers, for checking models with `--printer`.
+pub mod printer;
+/// Object selection by type, size and name.
+pub mod query;
 pub mod split;
+/// Splitting of objects into one object per connected shell.
+pub mod split_shells;
+/// Removal of thumbnails, textures, vendor data and unused resources.
+pub mod strip;
 /// Thumbnail extraction, injection, and listing for 3MF files.
 pub mod thumbnails;
 
 use clap::ValueEnum;
-use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path, opc};
+use lib3mf_converters::registry::{ExportOptions, FormatRegistry, ImportOptions};
+use lib3mf_converters::stl::{StlExportOptions, StlExporter, StlFormat};
+use lib3mf_converters::transform::ObjectMode;
+use lib3mf_core::archive::{
+    ArchiveReader, EntryMetadata, ZipArchiver, check_model_not_encrypted, find_model_path,
+    find_model_paths, opc, select_model_path,
+};
+use lib3mf_core::model::{Geometry, MemoryUsage, Model, ModelStats, Unit, read_stats_cache};
 use lib3mf_core::parser::parse_model;
 use serde::Serialize;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs::File;
 use std::io::{Read, Seek, Write};
 use std::path::PathBuf;
+use std::sync::OnceLock;
 
 /// Output format for CLI commands.
 ///
@@ -51,53 +93,139 @@ pub enum RepairType {
 }
 
 enum ModelSource {
-    Archive(ZipArchiver<File>, lib3mf_core::model::Model),
+    Archive(ZipArchiver<PackageData>, lib3mf_core::model::Model),
     Raw(lib3mf_core::model::Model),
 }
 
+/// A 3MF package read from disk, or held in memory after decompressing a `.3mf.gz` file.
+enum PackageData {
+    File(File),
+    Memory(std::io::Cursor<Vec<u8>>),
+}
+
+impl Read for PackageData {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            PackageData::File(f) => f.read(buf),
+            PackageData::Memory(c) => c.read(buf),
+        }
+    }
+}
+
+impl Seek for PackageData {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        match self {
+            PackageData::File(f) => f.seek(pos),
+            PackageData::Memory(c) => c.seek(pos),
+        }
+    }
+}
+
 fn open_model(path: &PathBuf) -> anyhow::Result<ModelSource> {
+    open_model_as(path, false, None)
+}
+
+/// Like [`open_model`], parsing a 3MF package's root model part with `options`.
+fn open_model_with(
+    path: &PathBuf,
+    options: &lib3mf_core::parser::ParseOptions,
+) -> anyhow::Result<ModelSource> {
+    open_model_as(path, false, Some(options))
+}
+
+/// Like [`open_model`], but leaves 3MF meshes unparsed as `Geometry::LazyMesh` handles.
+///
+/// For commands that mostly need the object graph; meshes can still be loaded from the
+/// returned archive on demand.
+fn open_model_lazy(path: &PathBuf) -> anyhow::Result<ModelSource> {
+    open_model_as(path, true, None)
+}
+
+/// Opens `path` as a 3MF package, plain or gzip-compressed (`.3mf.gz`), or through the
+/// importer the format registry finds for it, which includes bare model XML parts such as
+/// `3dmodel.model` and their gzip-compressed form.
+///
+/// A package's root model part is parsed with `options` if given and not `lazy`.
+fn open_model_as(
+    path: &PathBuf,
+    lazy: bool,
+    options: Option<&lib3mf_core::parser::ParseOptions>,
+) -> anyhow::Result<ModelSource> {
+    use lib3mf_core::parser::gzip;
+
     let mut file =
         File::open(path).map_err(|e| anyhow::anyhow!("Failed to open file {:?}: {}", path, e))?;
 
-    let mut magic = [0u8; 4];
-    let is_zip = file.read_exact(&mut magic).is_ok() && &magic == b"PK\x03\x04";
+    let mut head = Vec::new();
+    (&mut file)
+        .take(lib3mf_converters::registry::SNIFF_LEN as u64)
+        .read_to_end(&mut head)?;
     file.rewind()?;
 
-    if is_zip {
-        let mut archiver = ZipArchiver::new(file)
-            .map_err(|e| anyhow::anyhow!("Failed to open zip archive: {}", e))?;
-        let model_path = find_model_path(&mut archiver)
-            .map_err(|e| anyhow::anyhow!("Failed to find model path: {}", e))?;
+    if head.starts_with(b"PK\x03\x04") {
+        return open_package_source(PackageData::File(file), lazy, options);
+    }
+    // A gzip-compressed package is decompressed into memory to be read as a ZIP archive
+    if gzip::peek(&head, 4).starts_with(b"PK\x03\x04") {
+        let data = gzip::decompress(&std::fs::read(path)?)
+            .map_err(|e| anyhow::anyhow!("Failed to decompress {:?}: {}", path, e))?
+            .into_owned();
+        return open_package_source(
+            PackageData::Memory(std::io::Cursor::new(data)),
+            lazy,
+            options,
+        );
+    }
+
+    let registry = FormatRegistry::with_builtins();
+    let importer = registry
+        .importer_for_path(path)
+        .map_err(|e| anyhow::anyhow!("Failed to open file {:?}: {}", path, e))?
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unsupported format: {} (and not a ZIP/3MF archive or model XML)",
+                lowercase_extension(path)
+            )
+        })?;
+    let model = importer
+        .import(path, &ImportOptions::default())
+        .map_err(|e| anyhow::anyhow!("Failed to import {}: {}", importer.name(), e))?;
+    Ok(ModelSource::Raw(model))
+}
+
+/// Opens the root model part of a 3MF package.
+fn open_package_source(
+    data: PackageData,
+    lazy: bool,
+    options: Option<&lib3mf_core::parser::ParseOptions>,
+) -> anyhow::Result<ModelSource> {
+    let mut archiver =
+        ZipArchiver::new(data).map_err(|e| anyhow::anyhow!("Failed to open zip archive: {}", e))?;
+    let model_path = locate_model_part(&mut archiver)
+        .map_err(|e| anyhow::anyhow!("Failed to find model path: {}", e))?;
+    check_model_not_encrypted(&mut archiver, &model_path)?;
+    let model = if lazy {
+        let part = archiver
+            .entry_reader(&model_path)
+            .map_err(|e| anyhow::anyhow!("Failed to read model data: {}", e))?;
+        lib3mf_core::parser::parse_model_lazy(std::io::BufReader::new(part), &model_path)
+    } else if let Some(options) = options {
         let model_data = archiver
             .read_entry(&model_path)
             .map_err(|e| anyhow::anyhow!("Failed to read model data: {}", e))?;
-        let model = parse_model(std::io::Cursor::new(model_data))
-            .map_err(|e| anyhow::anyhow!("Failed to parse model XML: {}", e))?;
-        Ok(ModelSource::Archive(archiver, model))
+        lib3mf_core::parser::parse_model_with_options(std::io::Cursor::new(model_data), options)
     } else {
-        let ext = path
-            .extension()
-            .and_then(|s| s.to_str())
-            .unwrap_or("")
-            .to_lowercase();
-
-        match ext.as_str() {
-            "stl" => {
-                let model = lib3mf_converters::stl::StlImporter::read(file)
-                    .map_err(|e| anyhow::anyhow!("Failed to import STL: {}", e))?;
-                Ok(ModelSource::Raw(model))
-            }
-            "obj" => {
-                let model = lib3mf_converters::obj::ObjImporter::read_from_path(path)
-                    .map_err(|e| anyhow::anyhow!("Failed to import OBJ: {}", e))?;
-                Ok(ModelSource::Raw(model))
-            }
-            _ => Err(anyhow::anyhow!(
-                "Unsupported format: {} (and not a ZIP/3MF archive)",
-                ext
-            )),
-        }
-    }
+        let model_data = archiver
+            .read_entry(&model_path)
+            .map_err(|e| anyhow::anyhow!("Failed to read model data: {}", e))?;
+        #[cfg(feature = "parallel")]
+        let model = lib3mf_core::parser::parse_model_parallel(&model_data);
+        #[cfg(not(feature = "parallel"))]
+        let model = parse_model(std::io::Cursor::new(model_data));
+        model
+    };
+    let model = model.map_err(|e| anyhow::anyhow!("Failed to parse model XML: {}", e))?;
+    Ok(ModelSource::Archive(archiver, model))
 }
 
 /// Generate statistics and metadata for a 3MF file.
@@ -105,10 +233,15 @@ fn open_model(path: &PathBuf) -> anyhow::Result<ModelSource> {
 /// Computes and reports key metrics including unit of measurement, geometry counts,
 /// material groups, metadata, and system information.
 ///
+/// Statistics cached in the package (see `lib3mf_core::model::stats_cache`) are reported
+/// without parsing the model while the package is unchanged, unless a root model part is
+/// selected with [`set_model_part`].
+///
 /// # Arguments
 ///
 /// * `path` - Path to the 3MF file or supported format (STL, OBJ)
 /// * `format` - Output format (Text, Json, or Tree visualization)
+/// * `printer` - Printer whose build volume the model is checked against
 ///
 /// # Errors
 ///
@@ -121,73 +254,203 @@ fn open_model(path: &PathBuf) -> anyhow::Result<ModelSource> {
 /// use std::path::PathBuf;
 ///
 /// # fn main() -> anyhow::Result<()> {
-/// stats(PathBuf::from("model.3mf"), OutputFormat::Text)?;
+/// stats(PathBuf::from("model.3mf"), OutputFormat::Text, None)?;
 /// # Ok(())
 /// # }
 /// ```
-pub fn stats(path: PathBuf, format: OutputFormat) -> anyhow::Result<()> {
-    let mut source = open_model(&path)?;
+pub fn stats(
+    path: PathBuf,
+    format: OutputFormat,
+    printer: Option<&printer::Printer>,
+) -> anyhow::Result<()> {
+    stats_report(path, format, printer, false, true, false)
+}
+
+/// Like [`stats`], but always computes the statistics, ignoring any cached in the package.
+///
+/// # Errors
+///
+/// Returns the same errors as [`stats`].
+pub fn stats_uncached(
+    path: PathBuf,
+    format: OutputFormat,
+    printer: Option<&printer::Printer>,
+) -> anyhow::Result<()> {
+    stats_report(path, format, printer, false, false, false)
+}
+
+/// Like [`stats`], but loads every mesh and adds the model's estimated memory use by
+/// resource kind (see `Model::memory_usage`).
+///
+/// The text report gains a "Memory (estimated)" section and the JSON report a `memory`
+/// object. Tree output is unchanged.
+///
+/// # Errors
+///
+/// Returns the same errors as [`stats`].
+pub fn stats_with_memory(
+    path: PathBuf,
+    format: OutputFormat,
+    printer: Option<&printer::Printer>,
+) -> anyhow::Result<()> {
+    stats_report(path, format, printer, true, false, false)
+}
+
+/// Like [`stats_uncached`], but parses the 3MF package with one
+/// [`MeshInterner`](lib3mf_core::model::MeshInterner) for all its model parts, so that
+/// meshes with identical vertex or triangle arrays share them.
+///
+/// The unique mesh totals then report the shared arrays and the memory they save. Other
+/// formats are read as by [`stats`].
+///
+/// # Errors
+///
+/// Returns the same errors as [`stats`].
+pub fn stats_interned(
+    path: PathBuf,
+    format: OutputFormat,
+    printer: Option<&printer::Printer>,
+) -> anyhow::Result<()> {
+    stats_report(path, format, printer, false, false, true)
+}
+
+fn stats_report(
+    path: PathBuf,
+    format: OutputFormat,
+    printer: Option<&printer::Printer>,
+    memory: bool,
+    use_cache: bool,
+    intern: bool,
+) -> anyhow::Result<()> {
+    // The cache describes the default root model part, and has no hierarchy or memory use
+    if use_cache
+        && !memory
+        && !matches!(format, OutputFormat::Tree)
+        && model_part().is_none()
+        && let Some(stats) = cached_stats(&path)
+    {
+        return print_stats(&path, format, &stats, printer, None);
+    }
+
+    let options = lib3mf_core::parser::ParseOptions {
+        intern_meshes: intern.then(lib3mf_core::model::MeshInterner::new),
+        ..Default::default()
+    };
+    // Lazy meshes take no memory and are never interned, so both need the full model
+    let mut source = if intern {
+        open_model_with(&path, &options)?
+    } else if memory {
+        open_model(&path)?
+    } else {
+        open_model_lazy(&path)?
+    };
+    let memory = if memory {
+        Some(match &mut source {
+            ModelSource::Archive(archiver, model) => {
+                let mut usage = model.memory_usage();
+                // Slicer projects keep their meshes in parts referenced by components
+                let parts: BTreeSet<&str> = model
+                    .resources
+                    .iter_objects()
+                    .filter_map(|o| match &o.geometry {
+                        Geometry::Components(c) => Some(&c.components),
+                        _ => None,
+                    })
+                    .flatten()
+                    .filter_map(|c| c.path.as_deref())
+                    .collect();
+                for part in parts {
+                    let data = archiver.read_entry(part.trim_start_matches('/'))?;
+                    usage += parse_model(std::io::Cursor::new(data))?.memory_usage();
+                }
+                usage
+            }
+            ModelSource::Raw(model) => model.memory_usage(),
+        })
+    } else {
+        None
+    };
     let stats = match source {
         ModelSource::Archive(ref mut archiver, ref model) => model
-            .compute_stats(archiver)
+            .compute_stats_with(archiver, &options)
             .map_err(|e| anyhow::anyhow!("Failed to compute stats: {}", e))?,
-        ModelSource::Raw(ref model) => {
-            struct NoArchive;
-            impl std::io::Read for NoArchive {
-                fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
-                    Ok(0)
-                }
-            }
-            impl std::io::Seek for NoArchive {
-                fn seek(&mut self, _: std::io::SeekFrom) -> std::io::Result<u64> {
-                    Ok(0)
-                }
+        ModelSource::Raw(ref model) => model
+            .compute_stats(&mut NoArchive)
+            .map_err(|e| anyhow::anyhow!("Failed to compute stats: {}", e))?,
+    };
+
+    if let OutputFormat::Tree = format {
+        println!("Model Hierarchy for {:?}", path);
+        match source {
+            ModelSource::Archive(mut archiver, model) => {
+                let mut resolver =
+                    lib3mf_core::model::resolver::PartResolver::new(&mut archiver, model);
+                print_model_hierarchy_resolved(&mut resolver);
             }
-            impl lib3mf_core::archive::ArchiveReader for NoArchive {
-                fn read_entry(&mut self, _: &str) -> lib3mf_core::error::Result<Vec<u8>> {
-                    Err(lib3mf_core::error::Lib3mfError::Io(std::io::Error::new(
-                        std::io::ErrorKind::NotFound,
-                        "Raw format",
-                    )))
-                }
-                fn entry_exists(&mut self, _: &str) -> bool {
-                    false
-                }
-                fn list_entries(&mut self) -> lib3mf_core::error::Result<Vec<String>> {
-                    Ok(vec![])
-                }
+            ModelSource::Raw(model) => {
+                print_model_hierarchy(&model);
             }
-            model
-                .compute_stats(&mut NoArchive)
-                .map_err(|e| anyhow::anyhow!("Failed to compute stats: {}", e))?
         }
-    };
+        return Ok(());
+    }
+    print_stats(&path, format, &stats, printer, memory)
+}
+
+/// Returns the statistics cached in the package at `path`, if it is a 3MF package with a
+/// valid cache.
+fn cached_stats(path: &PathBuf) -> Option<ModelStats> {
+    let mut file = File::open(path).ok()?;
+    let mut magic = [0u8; 4];
+    if file.read_exact(&mut magic).is_err() || &magic != b"PK\x03\x04" {
+        return None;
+    }
+    file.rewind().ok()?;
+    let mut archiver = ZipArchiver::new(file).ok()?;
+    read_stats_cache(&mut archiver).ok().flatten()
+}
 
+/// Prints a text or JSON report of `stats`, with printer fit and memory sections if given.
+fn print_stats(
+    path: &PathBuf,
+    format: OutputFormat,
+    stats: &ModelStats,
+    printer: Option<&printer::Printer>,
+    memory: Option<MemoryUsage>,
+) -> anyhow::Result<()> {
+    let fit = printer.map(|p| p.fit(stats));
     match format {
         OutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(&stats)?);
-        }
-        OutputFormat::Tree => {
-            println!("Model Hierarchy for {:?}", path);
-            match source {
-                ModelSource::Archive(mut archiver, model) => {
-                    let mut resolver =
-                        lib3mf_core::model::resolver::PartResolver::new(&mut archiver, model);
-                    print_model_hierarchy_resolved(&mut resolver);
-                }
-                ModelSource::Raw(model) => {
-                    print_model_hierarchy(&model);
-                }
+            let mut report = serde_json::to_value(stats)?;
+            if let Some(fit) = &fit {
+                report["printer_fit"] = serde_json::to_value(fit)?;
             }
+            if let Some(memory) = memory {
+                let mut value = serde_json::to_value(memory)?;
+                value["total"] = memory.total().into();
+                report["memory"] = value;
+            }
+            println!("{}", serde_json::to_string_pretty(&report)?);
         }
         _ => {
             println!("Stats for {:?}", path);
             println!(
-                "Unit: {:?} (Scale: {} m)",
+                "Unit: {} ({}, scale: {} m)",
                 stats.unit,
+                stats.unit.symbol(),
                 stats.unit.scale_factor()
             );
-            println!("Generator: {:?}", stats.generator.unwrap_or_default());
+            println!(
+                "Generator: {:?}",
+                stats.generator.clone().unwrap_or_default()
+            );
+            let resources: Vec<String> = stats
+                .resource_counts
+                .iter()
+                .map(|(kind, count)| format!("{} {}", count, kind))
+                .collect();
+            if !resources.is_empty() {
+                println!("Resources: {}", resources.join(", "));
+            }
             println!("Geometry:");
 
             // Display object counts by type per CONTEXT.md decision
@@ -216,28 +479,72 @@ pub fn stats(path: PathBuf, format: OutputFormat) -> anyhow::Result<()> {
             }
 
             println!("  Instances: {}", stats.geometry.instance_count);
-            println!("  Vertices: {}", stats.geometry.vertex_count);
-            println!("  Triangles: {}", stats.geometry.triangle_count);
+            println!(
+                "  Vertices: {} (all instances)",
+                stats.geometry.vertex_count
+            );
+            println!(
+                "  Triangles: {} (all instances)",
+                stats.geometry.triangle_count
+            );
             if let Some(bbox) = stats.geometry.bounding_box {
                 println!("  Bounding Box: Min {:?}, Max {:?}", bbox.min, bbox.max);
             }
-            let scale = stats.unit.scale_factor();
+            if let Some([x, y, z]) = stats.size_in(Unit::Millimeter) {
+                println!(
+                    "  Size:         {:.2} x {:.2} x {:.2} mm",
+                    x.value, y.value, z.value
+                );
+            }
             println!(
-                "  Surface Area: {:.2} (native units^2)",
-                stats.geometry.surface_area
+                "  Surface Area: {:.2} {}^2 (all instances)",
+                stats.geometry.surface_area,
+                stats.unit.symbol()
             );
             println!(
                 "                {:.6} m^2",
-                stats.geometry.surface_area * scale * scale
+                stats.surface_area_in(Unit::Meter)
+            );
+            println!(
+                "  Volume:       {:.2} {}^3 (all instances)",
+                stats.geometry.volume,
+                stats.unit.symbol()
             );
+            println!("                {:.6} m^3", stats.volume_in(Unit::Meter));
+            let unique = &stats.geometry.unique;
+            println!("  Unique Meshes: {}", unique.mesh_count);
+            println!("    Vertices:     {}", unique.vertex_count);
+            println!("    Triangles:    {}", unique.triangle_count);
             println!(
-                "  Volume:       {:.2} (native units^3)",
-                stats.geometry.volume
+                "    Surface Area: {:.2} {}^2",
+                unique.surface_area,
+                stats.unit.symbol()
             );
             println!(
-                "                {:.6} m^3",
-                stats.geometry.volume * scale * scale * scale
+                "    Volume:       {:.2} {}^3",
+                unique.volume,
+                stats.unit.symbol()
             );
+            if unique.shared_buffers > 0 {
+                println!(
+                    "    Shared Arrays: {} ({} bytes deduplicated)",
+                    unique.shared_buffers,
+                    group_digits(unique.deduplicated_bytes)
+                );
+            }
+
+            if let Some(fit) = &fit {
+                let [x, y, z] = fit.build_volume;
+                println!("\nPrinter: {} ({} x {} x {} mm)", fit.name, x, y, z);
+                if fit.fits {
+                    println!("  Fits: yes");
+                } else {
+                    println!("  Fits: no");
+                    for problem in fit.oversize.iter().chain(&fit.outside_bed) {
+                        println!("    {}", problem);
+                    }
+                }
+            }
 
             println!("\nSystem Info:");
             println!("  Architecture: {}", stats.system_info.architecture);
@@ -337,6 +644,32 @@ pub fn stats(path: PathBuf, format: OutputFormat) -> anyhow::Result<()> {
                         let name = plate.name.as_deref().unwrap_or("[unnamed]");
                         let locked_str = if plate.locked { " [locked]" } else { "" };
                         println!("    Plate {}: {}{}", plate.id, name, locked_str);
+                        if let Some(geometry) = stats.plates.iter().find(|p| p.plate_id == plate.id)
+                        {
+                            println!(
+                                "      Geometry: {} objects, {} instances, {} triangles, {:.2} {}^3",
+                                geometry.object_count,
+                                geometry.instance_count,
+                                geometry.triangle_count,
+                                geometry.volume,
+                                stats.unit.symbol()
+                            );
+                            if let Some(bbox) = geometry.bounding_box {
+                                let [x, y, z] = bbox.size();
+                                println!(
+                                    "      Size: {:.2} x {:.2} x {:.2} {}",
+                                    x,
+                                    y,
+                                    z,
+                                    stats.unit.symbol()
+                                );
+                            }
+                            let items: Vec<String> =
+                                geometry.build_items.iter().map(|i| i.to_string()).collect();
+                            if !items.is_empty() {
+                                println!("      Build Items: {}", items.join(", "));
+                            }
+                        }
 
                         // Show assigned objects
                         if !plate.items.is_empty() {
@@ -376,6 +709,22 @@ pub fn stats(path: PathBuf, format: OutputFormat) -> anyhow::Result<()> {
                 }
             }
 
+            if !stats.build_items.is_empty() {
+                println!("Build Items:");
+                for item in &stats.build_items {
+                    println!("  Item {} (object {})", item.index, item.object_id);
+                    if let Some(part_number) = &item.part_number {
+                        println!("    Part Number: {}", part_number);
+                    }
+                    if let Some(uuid) = &item.uuid {
+                        println!("    UUID: {}", uuid);
+                    }
+                    for entry in &item.metadata {
+                        println!("    {}: {}", entry.name, entry.value);
+                    }
+                }
+            }
+
             println!("Thumbnails:");
             println!(
                 "  Package Thumbnail: {}",
@@ -412,6 +761,22 @@ pub fn stats(path: PathBuf, format: OutputFormat) -> anyhow::Result<()> {
                     );
                 }
             }
+
+            if let Some(memory) = memory {
+                println!("\nMemory (estimated):");
+                for (name, bytes) in memory.categories() {
+                    if bytes > 0 {
+                        let name = name.replace('_', " ");
+                        let label = format!("{}{}:", name[..1].to_uppercase(), &name[1..]);
+                        println!("  {:<21} {:>15} bytes", label, group_digits(bytes as u64));
+                    }
+                }
+                println!(
+                    "  {:<21} {:>15} bytes",
+                    "Total:",
+                    group_digits(memory.total() as u64)
+                );
+            }
         }
     }
     Ok(())
@@ -425,42 +790,128 @@ pub fn stats(path: PathBuf, format: OutputFormat) -> anyhow::Result<()> {
 ///
 /// * `path` - Path to the 3MF file
 /// * `format` - Output format (Text for flat list, Json for structured, Tree for directory view)
+/// * `sizes` - Show each entry's uncompressed and stored size, its compression ratio and its
+///   share of the stored total
+/// * `sort_by_size` - Order entries by stored size, largest first (implies `sizes`)
 ///
 /// # Errors
 ///
 /// Returns an error if the archive cannot be opened or entries cannot be listed.
-pub fn list(path: PathBuf, format: OutputFormat) -> anyhow::Result<()> {
-    let source = open_model(&path)?;
+pub fn list(
+    path: PathBuf,
+    format: OutputFormat,
+    sizes: bool,
+    sort_by_size: bool,
+) -> anyhow::Result<()> {
+    let source = open_model_lazy(&path)?;
 
-    let entries = match source {
+    let mut entries = match source {
         ModelSource::Archive(mut archiver, _) => archiver
-            .list_entries()
+            .entries_metadata()
             .map_err(|e| anyhow::anyhow!("Failed to list entries: {}", e))?,
-        ModelSource::Raw(_) => vec![
-            path.file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("model")
-                .to_string(),
-        ],
+        ModelSource::Raw(_) => {
+            let data = std::fs::read(&path)?;
+            vec![EntryMetadata {
+                name: path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("model")
+                    .to_string(),
+                size: data.len() as u64,
+                compressed_size: data.len() as u64,
+                compressed: false,
+                crc32: crc32fast::hash(&data),
+            }]
+        }
     };
+    if sort_by_size {
+        entries.sort_by(|a, b| {
+            b.compressed_size
+                .cmp(&a.compressed_size)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+    }
+    let sizes = sizes || sort_by_size;
 
     match format {
+        OutputFormat::Json if sizes => {
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+        }
         OutputFormat::Json => {
-            let tree = build_file_tree(&entries);
+            let names: Vec<String> = entries.into_iter().map(|e| e.name).collect();
+            let tree = build_file_tree(&names);
             println!("{}", serde_json::to_string_pretty(&tree)?);
         }
         OutputFormat::Tree => {
-            print_tree(&entries);
+            let labels: Vec<String> = entries
+                .iter()
+                .map(|e| {
+                    if sizes {
+                        format!("{} ({} bytes)", e.name, group_digits(e.size))
+                    } else {
+                        e.name.clone()
+                    }
+                })
+                .collect();
+            print_tree(&labels);
+        }
+        OutputFormat::Text if sizes => {
+            let stored: u64 = entries.iter().map(|e| e.compressed_size).sum();
+            let total: u64 = entries.iter().map(|e| e.size).sum();
+            println!(
+                "{:>12} {:>12} {:>6} {:>6}  Name",
+                "Size", "Stored", "Ratio", "Share"
+            );
+            for e in &entries {
+                println!(
+                    "{:>12} {:>12} {:>6} {:>6}  {}",
+                    e.size,
+                    e.compressed_size,
+                    format_ratio(e.compressed_size, e.size),
+                    format_ratio(e.compressed_size, stored),
+                    e.name
+                );
+            }
+            println!(
+                "{:>12} {:>12} {:>6} {:>6}  Total ({} entries)",
+                total,
+                stored,
+                format_ratio(stored, total),
+                "100%",
+                entries.len()
+            );
         }
         OutputFormat::Text => {
             for entry in entries {
-                println!("{}", entry);
+                println!("{}", entry.name);
             }
         }
     }
     Ok(())
 }
 
+/// Formats `part` as a whole percentage of `whole`, or `-` if `whole` is zero.
+pub(crate) fn format_ratio(part: u64, whole: u64) -> String {
+    if whole == 0 {
+        "-".to_string()
+    } else {
+        format!("{:.0}%", part as f64 * 100.0 / whole as f64)
+    }
+}
+
+/// Formats a number with `,` thousands separators.
+fn group_digits(n: u64) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
 /// Inspect OPC relationships and content types.
 ///
 /// Dumps the Open Packaging Convention (OPC) relationships from `_rels/.rels` and
@@ -525,39 +976,6 @@ pub fn rels(path: PathBuf, format: OutputFormat) -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Dump the raw parsed Model structure for debugging.
-///
-/// Outputs the in-memory representation of the 3MF model for developer inspection.
-///
-/// # Arguments
-///
-/// * `path` - Path to the 3MF file
-/// * `format` - Output format (Text for debug format, Json for structured)
-///
-/// # Errors
-///
-/// Returns an error if the file cannot be parsed.
-pub fn dump(path: PathBuf, format: OutputFormat) -> anyhow::Result<()> {
-    let mut archiver = open_archive(&path)?;
-    let model_path = find_model_path(&mut archiver)
-        .map_err(|e| anyhow::anyhow!("Failed to find model path: {}", e))?;
-    let model_data = archiver
-        .read_entry(&model_path)
-        .map_err(|e| anyhow::anyhow!("Failed to read model data: {}", e))?;
-    let model = parse_model(std::io::Cursor::new(model_data))
-        .map_err(|e| anyhow::anyhow!("Failed to parse model XML: {}", e))?;
-
-    match format {
-        OutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(&model)?);
-        }
-        _ => {
-            println!("{:#?}", model);
-        }
-    }
-    Ok(())
-}
-
 /// Extract a file from the 3MF archive by path.
 ///
 /// Copies a specific file from inside the ZIP archive to the local filesystem or stdout.
@@ -607,7 +1025,7 @@ pub fn extract_by_resource_id(
     output: Option<PathBuf>,
 ) -> anyhow::Result<()> {
     let mut archiver = open_archive(&path)?;
-    let model_path = find_model_path(&mut archiver)?;
+    let model_path = locate_model_part(&mut archiver)?;
     let model_data = archiver.read_entry(&model_path)?;
     let model = parse_model(std::io::Cursor::new(model_data))?;
 
@@ -640,64 +1058,104 @@ pub fn extract_by_resource_id(
     ))
 }
 
-/// Copy and re-package a 3MF file.
-///
-/// Reads the input file, parses it into memory, and writes it back to a new file.
-/// This verifies that lib3mf can successfully parse and re-serialize the model.
-///
-/// # Arguments
-///
-/// * `input` - Input 3MF file path
-/// * `output` - Output 3MF file path
-///
-/// # Errors
-///
-/// Returns an error if parsing or writing fails.
-pub fn copy(input: PathBuf, output: PathBuf) -> anyhow::Result<()> {
-    let mut archiver = open_archive(&input)?;
-    let model_path = find_model_path(&mut archiver)
-        .map_err(|e| anyhow::anyhow!("Failed to find model path: {}", e))?;
-    let model_data = archiver
-        .read_entry(&model_path)
-        .map_err(|e| anyhow::anyhow!("Failed to read model data: {}", e))?;
-    let mut model = parse_model(std::io::Cursor::new(model_data))
-        .map_err(|e| anyhow::anyhow!("Failed to parse model XML: {}", e))?;
+/// Root model part chosen with the global `--model-part` option.
+static MODEL_PART: OnceLock<Option<String>> = OnceLock::new();
 
-    // Load all existing files to preserve multi-part relationships and attachments
-    let all_files = archiver.list_entries()?;
-    for entry_path in all_files {
-        // Skip files that PackageWriter regenerates
-        if entry_path == model_path
-            || entry_path == "_rels/.rels"
-            || entry_path == "[Content_Types].xml"
-        {
-            continue;
-        }
+/// Sets the root model part that commands load from 3MF packages, for packages with
+/// several 3D model parts. `None` keeps the default of the package's single model
+/// relationship. Only the first call has an effect.
+pub fn set_model_part(part: Option<String>) {
+    let _ = MODEL_PART.set(part);
+}
 
-        // Load .rels files to preserve relationships
-        if entry_path.ends_with(".rels") {
-            if let Ok(data) = archiver.read_entry(&entry_path)
-                && let Ok(rels) = lib3mf_core::archive::opc::parse_relationships(&data)
-            {
-                model.existing_relationships.insert(entry_path, rels);
-            }
-            continue;
+/// Returns the root model part set with [`set_model_part`], if any.
+pub(crate) fn model_part() -> Option<&'static str> {
+    MODEL_PART.get().and_then(|p| p.as_deref())
+}
+
+/// Finds the root model part: the one set with [`set_model_part`], or else the single
+/// model relationship target. Packages with several model relationships are an error
+/// that lists them, so the user can pick one with `--model-part`.
+pub(crate) fn locate_model_part(archiver: &mut impl ArchiveReader) -> anyhow::Result<String> {
+    if let Some(part) = model_part() {
+        return Ok(select_model_path(archiver, Some(part))?);
+    }
+    find_model_path(archiver).map_err(|e| {
+        let candidates = find_model_paths(archiver).unwrap_or_default();
+        if candidates.len() > 1 {
+            let paths: Vec<&str> = candidates.iter().map(|c| c.path.as_str()).collect();
+            anyhow::anyhow!(
+                "Package has {} 3D model parts ({}); choose one with --model-part",
+                candidates.len(),
+                paths.join(", ")
+            )
+        } else {
+            e.into()
         }
+    })
+}
 
-        // Load other data as attachments
-        if let Ok(data) = archiver.read_entry(&entry_path) {
-            model.attachments.insert(entry_path, data);
+/// Reads the package's core properties into `model`, which writes them back with their
+/// relationship. Returns the part they were read from, which commands copying the package's
+/// other parts as attachments skip. Unreadable core properties are left to be copied as
+/// they are, with a warning.
+pub(crate) fn load_core_properties(
+    archiver: &mut impl ArchiveReader,
+    model: &mut Model,
+) -> Option<String> {
+    let path = match lib3mf_core::model::find_core_properties_path(archiver) {
+        Ok(path) => path?,
+        Err(e) => {
+            eprintln!("Warning: ignoring unreadable package relationships: {}", e);
+            return None;
+        }
+    };
+    match lib3mf_core::model::read_core_properties(archiver) {
+        Ok(properties) => {
+            model.core_properties = properties;
+            model.core_properties.as_ref().map(|_| path)
+        }
+        Err(e) => {
+            eprintln!(
+                "Warning: copying unreadable core properties {} as is: {}",
+                path, e
+            );
+            None
         }
     }
+}
 
-    let file = File::create(&output)
-        .map_err(|e| anyhow::anyhow!("Failed to create output file: {}", e))?;
-    model
-        .write(file)
-        .map_err(|e| anyhow::anyhow!("Failed to write 3MF: {}", e))?;
+/// An empty archive, for models read from formats without parts.
+struct NoArchive;
 
-    println!("Copied {:?} to {:?}", input, output);
-    Ok(())
+impl std::io::Read for NoArchive {
+    fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+        Ok(0)
+    }
+}
+
+impl std::io::Seek for NoArchive {
+    fn seek(&mut self, _: std::io::SeekFrom) -> std::io::Result<u64> {
+        Ok(0)
+    }
+}
+
+impl ArchiveReader for NoArchive {
+    fn read_entry(&mut self, name: &str) -> lib3mf_core::error::Result<Vec<u8>> {
+        Err(lib3mf_core::error::Lib3mfError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!(
+                "Part {} is not available: the input is not a 3MF package",
+                name
+            ),
+        )))
+    }
+    fn entry_exists(&mut self, _: &str) -> bool {
+        false
+    }
+    fn list_entries(&mut self) -> lib3mf_core::error::Result<Vec<String>> {
+        Ok(vec![])
+    }
 }
 
 fn open_archive(path: &PathBuf) -> anyhow::Result<ZipArchiver<File>> {
@@ -766,6 +1224,7 @@ fn print_model_hierarchy(model: &lib3mf_core::model::Model) {
             item.object_id.0
         );
         let node = tree.entry(name).or_insert_with(node::Node::new);
+        add_item_details(item, node);
 
         // Recurse into objects
         add_object_to_tree(model, item.object_id, node);
@@ -774,6 +1233,20 @@ fn print_model_hierarchy(model: &lib3mf_core::model::Model) {
     node::print_nodes(&tree, "");
 }
 
+/// Adds a build item's part number and metadata entries as leaves of its tree node.
+fn add_item_details(item: &lib3mf_core::model::BuildItem, node: &mut node::Node) {
+    if let Some(part_number) = &item.part_number {
+        node.children
+            .insert(format!("Part Number: {}", part_number), node::Node::new());
+    }
+    for entry in &item.metadata {
+        node.children.insert(
+            format!("Metadata: {} = {}", entry.name, entry.value),
+            node::Node::new(),
+        );
+    }
+}
+
 fn add_object_to_tree(
     model: &lib3mf_core::model::Model,
     id: lib3mf_core::model::ResourceId,
@@ -781,14 +1254,6 @@ fn add_object_to_tree(
 ) {
     if let Some(obj) = model.resources.get_object(id) {
         match &obj.geometry {
-            lib3mf_core::model::Geometry::Mesh(mesh) => {
-                let info = format!(
-                    "Mesh: {} vertices, {} triangles",
-                    mesh.vertices.len(),
-                    mesh.triangles.len()
-                );
-                parent.children.insert(info, node::Node::new());
-            }
             lib3mf_core::model::Geometry::Components(comps) => {
                 for (i, comp) in comps.components.iter().enumerate() {
                     let child_obj_name = model
@@ -807,21 +1272,137 @@ fn add_object_to_tree(
                     add_object_to_tree(model, comp.object_id, node);
                 }
             }
-            _ => {
-                parent
+            lib3mf_core::model::Geometry::BooleanShape(shape) => {
+                let node = parent
                     .children
-                    .insert("Unknown Geometry".to_string(), node::Node::new());
+                    .entry("Boolean Shape".to_string())
+                    .or_insert_with(node::Node::new);
+                for (label, object_id, _path) in boolean_operands(shape) {
+                    let name = format!(
+                        "{} [{}] (ID: {})",
+                        label,
+                        object_name(model.resources.get_object(object_id), object_id),
+                        object_id.0
+                    );
+                    let child = node.children.entry(name).or_insert_with(node::Node::new);
+                    add_object_to_tree(model, object_id, child);
+                }
             }
+            geometry => add_geometry_leaf(&model.resources, geometry, parent),
         }
     }
 }
 
-fn print_model_hierarchy_resolved<A: ArchiveReader>(
-    resolver: &mut lib3mf_core::model::resolver::PartResolver<A>,
-) {
-    let mut tree: BTreeMap<String, node::Node> = BTreeMap::new();
+/// Returns the display name of an object, falling back to its ID.
+fn object_name(
+    obj: Option<&lib3mf_core::model::Object>,
+    id: lib3mf_core::model::ResourceId,
+) -> String {
+    obj.and_then(|obj| obj.name.clone())
+        .unwrap_or_else(|| format!("Object {}", id.0))
+}
 
-    let build_items = resolver.get_root_model().build.items.clone();
+/// Lists the base object and operands of a boolean shape as `(label, object ID, path)`, in
+/// the order they are applied.
+fn boolean_operands(
+    shape: &lib3mf_core::model::BooleanShape,
+) -> Vec<(String, lib3mf_core::model::ResourceId, Option<String>)> {
+    let mut operands = vec![(
+        "Base".to_string(),
+        shape.base_object_id,
+        shape.base_path.clone(),
+    )];
+    for (i, op) in shape.operations.iter().enumerate() {
+        let kind = match op.operation_type {
+            lib3mf_core::model::BooleanOperationType::Union => "Union",
+            lib3mf_core::model::BooleanOperationType::Difference => "Difference",
+            lib3mf_core::model::BooleanOperationType::Intersection => "Intersection",
+        };
+        operands.push((
+            format!("Operation {}: {}", i + 1, kind),
+            op.object_id,
+            op.path.clone(),
+        ));
+    }
+    operands
+}
+
+/// Adds a one-line summary of geometry without child objects (meshes and the slice,
+/// volumetric and displacement extension geometries) to `parent`.
+///
+/// `resources` is the collection of the part holding the object, where referenced slice
+/// and volumetric stacks are looked up.
+fn add_geometry_leaf(
+    resources: &lib3mf_core::model::ResourceCollection,
+    geometry: &lib3mf_core::model::Geometry,
+    parent: &mut node::Node,
+) {
+    use lib3mf_core::model::Geometry;
+
+    let info = match geometry {
+        Geometry::Mesh(mesh) => {
+            let mut info = format!(
+                "Mesh: {} vertices, {} triangles",
+                mesh.vertices.len(),
+                mesh.triangles.len()
+            );
+            if let Some(lattice) = &mesh.beam_lattice {
+                info.push_str(&format!(", {} beams", lattice.beams.len()));
+            }
+            info
+        }
+        Geometry::LazyMesh(lazy) => format!(
+            "Mesh: {} vertices, {} triangles",
+            lazy.vertex_count, lazy.triangle_count
+        ),
+        Geometry::PreciseMesh(mesh) => format!(
+            "Mesh: {} vertices, {} triangles (f64)",
+            mesh.vertices.len(),
+            mesh.triangles.len()
+        ),
+        Geometry::SliceStack(id) => match resources.get_slice_stack(*id) {
+            Some(stack) => {
+                let mut info = format!("Slice Stack {}: {} layers", id.0, stack.slices.len());
+                if let Some(last) = stack.slices.last() {
+                    info.push_str(&format!(", z {} to {}", stack.z_bottom, last.z_top));
+                }
+                if !stack.refs.is_empty() {
+                    info.push_str(&format!(", {} referenced stacks", stack.refs.len()));
+                }
+                info
+            }
+            None => format!("Slice Stack {}: missing", id.0),
+        },
+        Geometry::VolumetricStack(id) => match resources.get_volumetric_stack(*id) {
+            Some(stack) => {
+                let mut info = format!("Volumetric Stack {}: {} layers", id.0, stack.layers.len());
+                if !stack.refs.is_empty() {
+                    info.push_str(&format!(", {} referenced stacks", stack.refs.len()));
+                }
+                info
+            }
+            None => format!("Volumetric Stack {}: missing", id.0),
+        },
+        Geometry::LevelSet(level_set) => format!(
+            "Level Set: function {} channel '{}', bounded by mesh {}",
+            level_set.function_id.0, level_set.channel, level_set.mesh_id.0
+        ),
+        Geometry::DisplacementMesh(mesh) => format!(
+            "Displacement Mesh: {} vertices, {} triangles",
+            mesh.vertices.len(),
+            mesh.triangles.len()
+        ),
+        Geometry::Components(_) | Geometry::BooleanShape(_) => return,
+    };
+    parent.children.insert(info, node::Node::new());
+}
+
+fn print_model_hierarchy_resolved<A: ArchiveReader>(
+    resolver: &mut lib3mf_core::model::resolver::PartResolver<A>,
+) {
+    let mut tree: BTreeMap<String, node::Node> = BTreeMap::new();
+
+    let build_items = resolver.get_root_model().build.items.clone();
 
     for (i, item) in build_items.iter().enumerate() {
         let (obj_name, obj_id, obj_type) = {
@@ -852,6 +1433,7 @@ fn print_model_hierarchy_resolved<A: ArchiveReader>(
             obj_id.0
         );
         let node = tree.entry(name).or_insert_with(node::Node::new);
+        add_item_details(item, node);
 
         // Recurse into objects
         add_object_to_tree_resolved(resolver, obj_id, None, node);
@@ -866,32 +1448,53 @@ fn add_object_to_tree_resolved<A: ArchiveReader>(
     path: Option<&str>,
     parent: &mut node::Node,
 ) {
-    let components = {
+    let (components, operands) = {
         let resolved = resolver.resolve_object(id, path).unwrap_or(None);
-        if let Some((_model, obj)) = resolved {
+        if let Some((model, obj)) = resolved {
             match &obj.geometry {
-                lib3mf_core::model::Geometry::Mesh(mesh) => {
-                    let info = format!(
-                        "Mesh: {} vertices, {} triangles",
-                        mesh.vertices.len(),
-                        mesh.triangles.len()
-                    );
-                    parent.children.insert(info, node::Node::new());
-                    None
+                lib3mf_core::model::Geometry::Components(comps) => {
+                    (Some(comps.components.clone()), None)
+                }
+                lib3mf_core::model::Geometry::BooleanShape(shape) => {
+                    (None, Some(boolean_operands(shape)))
                 }
-                lib3mf_core::model::Geometry::Components(comps) => Some(comps.components.clone()),
-                _ => {
-                    parent
-                        .children
-                        .insert("Unknown Geometry".to_string(), node::Node::new());
-                    None
+                geometry => {
+                    add_geometry_leaf(&model.resources, geometry, parent);
+                    (None, None)
                 }
             }
         } else {
-            None
+            (None, None)
         }
     };
 
+    if let Some(operands) = operands {
+        let node = parent
+            .children
+            .entry("Boolean Shape".to_string())
+            .or_insert_with(node::Node::new);
+        for (label, object_id, operand_path) in operands {
+            let next_path = operand_path.as_deref().or(path);
+            let name = match resolver
+                .resolve_object(object_id, next_path)
+                .unwrap_or(None)
+            {
+                Some((_model, obj)) => format!(
+                    "{} [{}] (ID: {})",
+                    label,
+                    object_name(Some(obj), object_id),
+                    object_id.0
+                ),
+                None => format!(
+                    "{} [Missing Object {}] (ID: {})",
+                    label, object_id.0, object_id.0
+                ),
+            };
+            let child = node.children.entry(name).or_insert_with(node::Node::new);
+            add_object_to_tree_resolved(resolver, object_id, next_path, child);
+        }
+    }
+
     if let Some(comps) = components {
         for (i, comp) in comps.iter().enumerate() {
             let next_path = comp.path.as_deref().or(path);
@@ -1004,7 +1607,7 @@ mod node {
     }
 }
 
-/// Convert between 3D formats (3MF, STL, OBJ).
+/// Convert between 3D formats (3MF, STL, OBJ, JSON).
 ///
 /// Auto-detects formats based on file extensions and performs the appropriate conversion.
 ///
@@ -1013,6 +1616,7 @@ mod node {
 /// - OBJ → 3MF
 /// - 3MF → STL (binary by default, ASCII with `ascii = true`)
 /// - 3MF → OBJ
+/// - 3MF ↔ JSON (the versioned document from [`Model::to_json`](lib3mf_core::model::Model::to_json))
 ///
 /// # Arguments
 ///
@@ -1039,102 +1643,453 @@ mod node {
 /// # }
 /// ```
 pub fn convert(input: PathBuf, output: PathBuf, ascii: bool) -> anyhow::Result<()> {
-    let output_ext = output
-        .extension()
-        .and_then(|e| e.to_str())
-        .unwrap_or("")
-        .to_lowercase();
+    let export = if ascii {
+        ExportOptions::ascii()
+    } else {
+        ExportOptions::default()
+    };
+    convert_with_registry(
+        input,
+        output,
+        &ImportOptions::default(),
+        &export,
+        &FormatRegistry::with_builtins(),
+    )
+}
 
-    // Special handling for STL/OBJ export from 3MF to support components
-    if output_ext == "stl" || output_ext == "obj" {
-        // We need to keep the archive open for resolving components
-        // Try opening as archive (zip)
-        let file_res = File::open(&input);
+/// Convert between formats using the importers and exporters in `registry`.
+///
+/// The input format is detected by [`FormatRegistry::importer_for_path`] (signature first,
+/// then extension) and the output format by the output file's extension, so formats
+/// registered by the caller take part in conversion. `import` and `export` adjust units,
+/// orientation, scale and object grouping on the way through.
+///
+/// 3MF packages converted to STL or OBJ have their components resolved across model
+/// parts first.
+///
+/// # Errors
+///
+/// Returns an error if no importer or exporter matches, or if conversion fails.
+pub fn convert_with_registry(
+    input: PathBuf,
+    output: PathBuf,
+    import: &ImportOptions,
+    export: &ExportOptions,
+    registry: &FormatRegistry,
+) -> anyhow::Result<()> {
+    let output_ext = lowercase_extension(&output);
+    let exporter = registry
+        .exporter_for_path(&output)
+        .ok_or_else(|| anyhow::anyhow!("Unsupported output format: {}", output_ext))?;
+
+    // STL and OBJ cannot express multi-part packages, so resolve components first.
+    let flatten = is_zip(&input) && (output_ext == "stl" || output_ext == "obj");
+
+    let mut plain = ExportOptions::default();
+    plain.ascii = export.ascii;
+    if flatten && *import == ImportOptions::default() && *export == plain {
+        // Without adjustments, stream straight from the resolver.
+        let (mut archiver, model) = open_package(&input)?;
+        let resolver = lib3mf_core::model::resolver::PartResolver::new(&mut archiver, model);
+        let root_model = resolver.get_root_model().clone();
+        let file = File::create(&output)
+            .map_err(|e| anyhow::anyhow!("Failed to create output file: {}", e))?;
+        if output_ext == "obj" {
+            lib3mf_converters::obj::ObjExporter::write_with_resolver(&root_model, resolver, file)
+                .map_err(|e| anyhow::anyhow!("Failed to export OBJ: {}", e))?;
+        } else {
+            let format = if export.ascii {
+                StlFormat::Ascii
+            } else {
+                StlFormat::Binary
+            };
+            StlExporter::new(StlExportOptions { format })
+                .write_with_resolver(&root_model, resolver, file)
+                .map_err(|e| anyhow::anyhow!("Failed to export STL: {}", e))?;
+        }
+        println!("Converted {:?} to {:?}", input, output);
+        return Ok(());
+    }
+
+    let group_items = export.item_groups && output_ext == "obj";
+    let model = import_model(&input, flatten, group_items, import, registry)?;
+    exporter
+        .export(&model, &output, export)
+        .map_err(|e| anyhow::anyhow!("Failed to export {}: {}", exporter.name(), e))?;
+
+    println!("Converted {:?} to {:?}", input, output);
+    Ok(())
+}
 
-        let should_use_resolver = if let Ok(mut f) = file_res {
-            let mut magic = [0u8; 4];
-            f.read_exact(&mut magic).is_ok() && &magic == b"PK\x03\x04"
+/// Convert several files into one model, each input's objects kept as separate objects.
+///
+/// Inputs are read as by [`convert_with_registry`] and combined in order: resource IDs of
+/// later inputs are shifted past those already taken, and each model is converted to the
+/// unit of the first. Objects and build items keep their positions, so parts exported
+/// together from a CAD assembly stay aligned. Unnamed objects are named after their input
+/// file.
+///
+/// # Errors
+///
+/// Returns an error if any input can't be read, if no exporter matches `output`, or if
+/// conversion fails. Nothing is written in that case.
+pub fn convert_merged(
+    inputs: &[PathBuf],
+    output: PathBuf,
+    import: &ImportOptions,
+    export: &ExportOptions,
+    registry: &FormatRegistry,
+) -> anyhow::Result<()> {
+    let output_ext = lowercase_extension(&output);
+    let exporter = registry
+        .exporter_for_path(&output)
+        .ok_or_else(|| anyhow::anyhow!("Unsupported output format: {}", output_ext))?;
+    let flatten_packages = output_ext == "stl" || output_ext == "obj";
+    let group_items = export.item_groups && output_ext == "obj";
+
+    let mut merged: Option<Model> = None;
+    for (index, input) in inputs.iter().enumerate() {
+        let mut model = import_model(
+            input,
+            flatten_packages && is_zip(input),
+            group_items,
+            import,
+            registry,
+        )?;
+        let stem = input
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("object");
+        for object in model.resources.iter_objects_mut() {
+            object.name.get_or_insert_with(|| stem.to_string());
+        }
+        match merged.as_mut() {
+            None => merged = Some(model),
+            Some(merged) => {
+                if model.unit != merged.unit {
+                    lib3mf_converters::transform::convert_unit(&mut model, merged.unit);
+                }
+                merge::append_model(merged, model, index)?;
+            }
+        }
+    }
+    let merged = merged.ok_or_else(|| anyhow::anyhow!("No input files"))?;
+
+    exporter
+        .export(&merged, &output, export)
+        .map_err(|e| anyhow::anyhow!("Failed to export {}: {}", exporter.name(), e))?;
+
+    println!(
+        "Converted {} files ({} object(s)) to {:?}",
+        inputs.len(),
+        merged.build.items.len(),
+        output
+    );
+    Ok(())
+}
+
+/// Convert every object in the build of each input to its own file in `dir`.
+///
+/// Each printable build item is resolved across model parts, like a 3MF converted to STL,
+/// and written with the exporter for `extension` (e.g. `"stl"`). With
+/// [`ObjectMode::Split`](lib3mf_converters::transform::ObjectMode::Split) in `export`, each
+/// disconnected piece of an object gets its own file. Files are named after the object, or
+/// `<input file name>_<object id>` if it has none; a numeric suffix keeps names unique.
+/// Objects keep their position in the build. Materials are not carried over. `dir` is
+/// created if needed.
+///
+/// # Errors
+///
+/// Returns an error if an input can't be read, if no exporter matches `extension`, or if
+/// a file can't be written.
+pub fn convert_to_directory(
+    inputs: &[PathBuf],
+    dir: PathBuf,
+    extension: &str,
+    import: &ImportOptions,
+    export: &ExportOptions,
+    registry: &FormatRegistry,
+) -> anyhow::Result<()> {
+    let extension = extension.trim_start_matches('.').to_lowercase();
+    let exporter = registry
+        .exporter_for_extension(&extension)
+        .ok_or_else(|| anyhow::anyhow!("Unsupported output format: {}", extension))?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| anyhow::anyhow!("Failed to create directory {:?}: {}", dir, e))?;
+
+    let mut names = BTreeSet::new();
+    let mut written = 0;
+    for input in inputs {
+        let stem = input
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("object");
+        let mut target = SplitTarget {
+            exporter: exporter.as_ref(),
+            export,
+            dir: &dir,
+            extension: &extension,
+            stem,
+            names: &mut names,
+        };
+        written += if is_zip(input) {
+            let (mut archiver, model) = open_package(input)?;
+            let mut resolver =
+                lib3mf_core::model::resolver::PartResolver::new(&mut archiver, model);
+            target.write_items(&mut resolver, Some(import))?
         } else {
-            false
+            // Importers apply the import options themselves
+            let model = import_model(input, false, false, import, registry)?;
+            let mut archive = NoArchive;
+            let mut resolver = lib3mf_core::model::resolver::PartResolver::new(&mut archive, model);
+            target.write_items(&mut resolver, None)?
         };
+    }
 
-        if should_use_resolver {
-            let mut archiver = open_archive(&input)?;
-            let model_path = find_model_path(&mut archiver)
-                .map_err(|e| anyhow::anyhow!("Failed to find model path: {}", e))?;
-            let model_data = archiver
-                .read_entry(&model_path)
-                .map_err(|e| anyhow::anyhow!("Failed to read model data: {}", e))?;
-            let model = parse_model(std::io::Cursor::new(model_data))
-                .map_err(|e| anyhow::anyhow!("Failed to parse model XML: {}", e))?;
-
-            let resolver = lib3mf_core::model::resolver::PartResolver::new(&mut archiver, model);
-            let file = File::create(&output)
-                .map_err(|e| anyhow::anyhow!("Failed to create output file: {}", e))?;
-
-            // Access the root model via resolver for export
-            let root_model = resolver.get_root_model().clone(); // Clone to pass to export, or export takes ref
-
-            if output_ext == "obj" {
-                lib3mf_converters::obj::ObjExporter::write_with_resolver(
-                    &root_model,
-                    resolver,
-                    file,
-                )
-                .map_err(|e| anyhow::anyhow!("Failed to export OBJ: {}", e))?;
-            } else if ascii {
-                lib3mf_converters::stl::AsciiStlExporter::write_with_resolver(
-                    &root_model,
-                    resolver,
-                    file,
-                )
-                .map_err(|e| anyhow::anyhow!("Failed to export ASCII STL: {}", e))?;
-            } else {
-                lib3mf_converters::stl::BinaryStlExporter::write_with_resolver(
-                    &root_model,
-                    resolver,
-                    file,
-                )
-                .map_err(|e| anyhow::anyhow!("Failed to export STL: {}", e))?;
+    println!("Converted {} object(s) to {:?}", written, dir);
+    Ok(())
+}
+
+/// Where and how [`convert_to_directory`] writes objects.
+struct SplitTarget<'a> {
+    exporter: &'a dyn lib3mf_converters::registry::ModelExporter,
+    export: &'a ExportOptions,
+    dir: &'a std::path::Path,
+    extension: &'a str,
+    /// File name of the input, for objects without a name.
+    stem: &'a str,
+    /// File names used so far, across inputs.
+    names: &'a mut BTreeSet<String>,
+}
+
+impl SplitTarget<'_> {
+    /// Writes each printable build item of the resolver's root model to its own file,
+    /// applying `import` to the resolved meshes if given. Returns the number of files.
+    fn write_items<A: ArchiveReader>(
+        &mut self,
+        resolver: &mut lib3mf_core::model::resolver::PartResolver<A>,
+        import: Option<&ImportOptions>,
+    ) -> anyhow::Result<usize> {
+        let options = lib3mf_core::model::ResolveOptions::default();
+        let root = resolver.get_root_model();
+        let unit = root.unit;
+        let items = root.build.items.clone();
+
+        let mut written = 0;
+        for item in items.iter().filter(|i| i.printable != Some(false)) {
+            let name = resolver
+                .resolve_object(item.object_id, item.path.as_deref())
+                .map_err(|e| {
+                    anyhow::anyhow!("Failed to resolve object {}: {}", item.object_id.0, e)
+                })?
+                .and_then(|(_, object)| object.name.clone())
+                .unwrap_or_else(|| format!("{}_{}", self.stem, item.object_id.0));
+            let meshes = resolver
+                .resolve_item(item, &options)
+                .map_err(|e| anyhow::anyhow!("Failed to resolve components: {}", e))?;
+            if meshes.is_empty() {
+                continue;
+            }
+            let mut model = lib3mf_converters::transform::model_from_resolved(meshes, unit)
+                .map_err(|e| anyhow::anyhow!("Failed to flatten model: {}", e))?;
+            if let Some(import) = import {
+                import
+                    .apply(&mut model)
+                    .map_err(|e| anyhow::anyhow!("Failed to import 3MF: {}", e))?;
             }
 
-            println!("Converted {:?} to {:?}", input, output);
-            return Ok(());
+            if self.export.objects != ObjectMode::Split {
+                self.write(&model, &name, self.export)?;
+                written += 1;
+                continue;
+            }
+            // One file per piece, each named after the object and numbered
+            lib3mf_converters::transform::split_objects(&mut model)
+                .map_err(|e| anyhow::anyhow!("Failed to split objects: {}", e))?;
+            let mut export = self.export.clone();
+            export.objects = ObjectMode::Keep;
+            for item in std::mem::take(&mut model.build.items) {
+                let Some(object) = model.resources.get_object(item.object_id) else {
+                    continue;
+                };
+                let piece_name = object.name.clone().unwrap_or_else(|| name.clone());
+                let mut piece = Model {
+                    unit: model.unit,
+                    ..Default::default()
+                };
+                piece
+                    .resources
+                    .add_object(object.clone())
+                    .map_err(|e| anyhow::anyhow!("Failed to split objects: {}", e))?;
+                piece.build.items.push(item);
+                self.write(&piece, &piece_name, &export)?;
+                written += 1;
+            }
         }
+        Ok(written)
     }
 
-    // Fallback to legacy conversion (or non-archive)
-    // 1. Load Model
-    let model = load_model(&input)?;
-
-    // 2. Export Model
-    let file = File::create(&output)
-        .map_err(|e| anyhow::anyhow!("Failed to create output file: {}", e))?;
+    fn write(&mut self, model: &Model, name: &str, export: &ExportOptions) -> anyhow::Result<()> {
+        let path = self.dir.join(self.file_name(name));
+        self.exporter
+            .export(model, &path, export)
+            .map_err(|e| anyhow::anyhow!("Failed to export {:?}: {}", path, e))
+    }
 
-    match output_ext.as_str() {
-        "3mf" => {
-            model
-                .write(file)
-                .map_err(|e| anyhow::anyhow!("Failed to write 3MF: {}", e))?;
+    /// Returns an unused file name for an object, with unsafe characters replaced.
+    fn file_name(&mut self, object_name: &str) -> String {
+        let base: String = object_name
+            .trim()
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | ' ' | '(' | ')') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let base = if base.is_empty() {
+            self.stem.to_string()
+        } else {
+            base
+        };
+        let mut name = format!("{base}.{}", self.extension);
+        let mut n = 2;
+        while !self.names.insert(name.to_lowercase()) {
+            name = format!("{base}_{n}.{}", self.extension);
+            n += 1;
         }
-        "stl" => {
-            if ascii {
-                lib3mf_converters::stl::AsciiStlExporter::write(&model, file)
-                    .map_err(|e| anyhow::anyhow!("Failed to export ASCII STL: {}", e))?;
-            } else {
-                lib3mf_converters::stl::BinaryStlExporter::write(&model, file)
-                    .map_err(|e| anyhow::anyhow!("Failed to export STL: {}", e))?;
-            }
+        name
+    }
+}
+
+/// Expands glob patterns (`*`, `?`, `[...]`) among `inputs`, in sorted order per pattern.
+///
+/// Other paths are passed through unchanged, so shells that already expanded the pattern
+/// and files whose names contain these characters both work.
+///
+/// # Errors
+///
+/// Returns an error if a pattern is invalid or matches no files.
+pub fn expand_globs(inputs: Vec<PathBuf>) -> anyhow::Result<Vec<PathBuf>> {
+    let mut expanded = Vec::new();
+    for input in inputs {
+        let pattern = input.to_string_lossy();
+        if input.exists() || !pattern.contains(['*', '?', '[']) {
+            expanded.push(input);
+            continue;
         }
-        "obj" => {
-            lib3mf_converters::obj::ObjExporter::write(&model, file)
-                .map_err(|e| anyhow::anyhow!("Failed to export OBJ: {}", e))?;
+        let mut matches: Vec<PathBuf> = glob::glob(&pattern)
+            .map_err(|e| anyhow::anyhow!("Invalid glob pattern {:?}: {}", input, e))?
+            .filter_map(|r| r.ok())
+            .filter(|p| p.is_file())
+            .collect();
+        if matches.is_empty() {
+            anyhow::bail!("Glob pattern {:?} matched no files", input);
         }
-        _ => return Err(anyhow::anyhow!("Unsupported output format: {}", output_ext)),
+        matches.sort();
+        expanded.extend(matches);
     }
+    Ok(expanded)
+}
 
-    println!("Converted {:?} to {:?}", input, output);
-    Ok(())
+/// Returns true if `path` names a directory to write into: an existing directory, or a
+/// path ending in a separator.
+pub fn is_output_directory(path: &std::path::Path) -> bool {
+    path.is_dir() || path.to_string_lossy().ends_with(std::path::is_separator)
+}
+
+/// Reads `input` into a model. With `flatten`, `input` must be a 3MF package, whose
+/// build is resolved across model parts into one mesh object per instance; otherwise the
+/// importer `registry` detects for the file is used.
+/// Reads `input` through `registry`, or with `flatten` resolves the components of a 3MF
+/// package into a single-part model, keeping each build item's meshes together as one
+/// object if `group_items` is set.
+fn import_model(
+    input: &PathBuf,
+    flatten: bool,
+    group_items: bool,
+    import: &ImportOptions,
+    registry: &FormatRegistry,
+) -> anyhow::Result<Model> {
+    if flatten {
+        let (mut archiver, model) = open_package(input)?;
+        let mut resolver = lib3mf_core::model::resolver::PartResolver::new(&mut archiver, model);
+        let unit = resolver.get_root_model().unit;
+        let options = lib3mf_core::model::ResolveOptions::default();
+        let flattened = if group_items {
+            let root = resolver.get_root_model().clone();
+            let items = root
+                .build
+                .items
+                .iter()
+                .filter(|item| item.printable != Some(false))
+                .map(|item| {
+                    let name = root
+                        .resources
+                        .get_object(item.object_id)
+                        .and_then(|o| o.name.clone());
+                    Ok((name, resolver.resolve_item(item, &options)?))
+                })
+                .collect::<lib3mf_core::error::Result<Vec<_>>>()
+                .map_err(|e| anyhow::anyhow!("Failed to resolve components: {}", e))?;
+            lib3mf_converters::transform::model_from_resolved_items(items, unit)
+        } else {
+            let meshes = resolver
+                .resolve_meshes(&options)
+                .map_err(|e| anyhow::anyhow!("Failed to resolve components: {}", e))?;
+            lib3mf_converters::transform::model_from_resolved(meshes, unit)
+        };
+        let mut model = flattened.map_err(|e| anyhow::anyhow!("Failed to flatten model: {}", e))?;
+        import
+            .apply(&mut model)
+            .map_err(|e| anyhow::anyhow!("Failed to import 3MF: {}", e))?;
+        return Ok(model);
+    }
+
+    let importer = registry
+        .importer_for_path(input)
+        .map_err(|e| anyhow::anyhow!("Failed to open file {:?}: {}", input, e))?
+        .ok_or_else(|| anyhow::anyhow!("Unsupported format: {}", lowercase_extension(input)))?;
+    importer
+        .import(input, import)
+        .map_err(|e| anyhow::anyhow!("Failed to import {}: {}", importer.name(), e))
+}
+
+/// Opens a 3MF package and parses its root model part.
+fn open_package(input: &PathBuf) -> anyhow::Result<(ZipArchiver<File>, Model)> {
+    open_package_with(input, &lib3mf_core::parser::ParseOptions::default())
+}
+
+fn open_package_with(
+    input: &PathBuf,
+    options: &lib3mf_core::parser::ParseOptions,
+) -> anyhow::Result<(ZipArchiver<File>, Model)> {
+    let mut archiver = open_archive(input)?;
+    let model_path = locate_model_part(&mut archiver)
+        .map_err(|e| anyhow::anyhow!("Failed to find model path: {}", e))?;
+    let model_data = archiver
+        .read_entry(&model_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read model data: {}", e))?;
+    let model =
+        lib3mf_core::parser::parse_model_with_options(std::io::Cursor::new(model_data), options)
+            .map_err(|e| anyhow::anyhow!("Failed to parse model XML: {}", e))?;
+    Ok((archiver, model))
+}
+
+fn is_zip(path: &PathBuf) -> bool {
+    File::open(path).is_ok_and(|mut f| {
+        let mut magic = [0u8; 4];
+        f.read_exact(&mut magic).is_ok() && &magic == b"PK\x03\x04"
+    })
+}
+
+fn lowercase_extension(path: &std::path::Path) -> String {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase()
 }
 
 /// Validate a 3MF file against the specification.
@@ -1149,6 +2104,9 @@ pub fn convert(input: PathBuf, output: PathBuf, ascii: bool) -> anyhow::Result<(
 ///
 /// * `path` - Path to the 3MF file
 /// * `level` - Validation level string (minimal, standard, strict, paranoid)
+/// * `printer` - Printer whose build volume the model must fit: a model larger than it is
+///   an error (3020), and one placed off its bed a warning (3021)
+/// * `options` - Limits such as the maximum nesting depth of boolean shapes
 ///
 /// # Errors
 ///
@@ -1157,7 +2115,12 @@ pub fn convert(input: PathBuf, output: PathBuf, ascii: bool) -> anyhow::Result<(
 /// # Exit Code
 ///
 /// Exits with code 1 if validation errors are found, 0 if passed.
-pub fn validate(path: PathBuf, level: String) -> anyhow::Result<()> {
+pub fn validate(
+    path: PathBuf,
+    level: String,
+    printer: Option<&printer::Printer>,
+    options: &lib3mf_core::validation::ValidationOptions,
+) -> anyhow::Result<()> {
     use lib3mf_core::validation::{ValidationLevel, ValidationSeverity};
 
     let level_enum = match level.to_lowercase().as_str() {
@@ -1170,10 +2133,44 @@ pub fn validate(path: PathBuf, level: String) -> anyhow::Result<()> {
 
     println!("Validating {:?} at {:?} level...", path, level_enum);
 
-    let model = load_model(&path)?;
+    // Strict reports unknown elements and attributes, which the parser only records on request
+    let mut model = if level_enum >= ValidationLevel::Strict && is_zip(&path) {
+        let options = lib3mf_core::parser::ParseOptions {
+            track_unknown_names: true,
+            ..Default::default()
+        };
+        open_package_with(&path, &options)?.1
+    } else {
+        load_model(&path)?
+    };
+
+    // Geometry checksums cover external model parts and textures, so load the whole package
+    if model.has_geometry_checksums()
+        && let Ok(full) = merge::load_full(&path)
+    {
+        let unknown_names = std::mem::take(&mut model.unknown_names);
+        model = full;
+        model.unknown_names = unknown_names;
+    }
 
     // Run comprehensive validation
-    let report = model.validate(level_enum);
+    let mut report = model.validate_with_options(level_enum, options);
+    if level_enum >= ValidationLevel::Standard && is_zip(&path) {
+        let mut archiver = open_archive(&path)?;
+        let model_path = locate_model_part(&mut archiver)
+            .map_err(|e| anyhow::anyhow!("Failed to find model path: {}", e))?;
+        lib3mf_core::validation::validate_package_with_options(
+            &mut archiver,
+            &model_path,
+            &model,
+            level_enum,
+            options,
+            &mut report,
+        );
+    }
+    if let Some(printer) = printer {
+        check_printer_fit(&path, &model, printer, &mut report)?;
+    }
 
     let errors: Vec<_> = report
         .items
@@ -1190,12 +2187,133 @@ pub fn validate(path: PathBuf, level: String) -> anyhow::Result<()> {
         println!("Validation Failed with {} error(s):", errors.len());
         for item in &errors {
             println!("  [ERROR {}] {}", item.code, item.message);
+            if let Some(suggestion) = &item.suggestion {
+                println!("      Suggestion: {}", suggestion);
+            }
         }
         std::process::exit(1);
     } else if !warnings.is_empty() {
         println!("Validation Passed with {} warning(s):", warnings.len());
         for item in &warnings {
             println!("  [WARN {}] {}", item.code, item.message);
+            if let Some(suggestion) = &item.suggestion {
+                println!("      Suggestion: {}", suggestion);
+            }
+        }
+    } else {
+        println!("Validation Passed.");
+    }
+
+    Ok(())
+}
+
+/// Reports the parts of `model` that do not fit `printer` in `report`.
+fn check_printer_fit(
+    path: &PathBuf,
+    model: &Model,
+    printer: &printer::Printer,
+    report: &mut lib3mf_core::validation::ValidationReport,
+) -> anyhow::Result<()> {
+    let stats = if is_zip(path) {
+        model.compute_stats(&mut open_archive(path)?)
+    } else {
+        model.compute_stats(&mut NoArchive)
+    }
+    .map_err(|e| anyhow::anyhow!("Failed to compute stats: {}", e))?;
+    let fit = printer.fit(&stats);
+    for message in fit.oversize {
+        report.add_error(3020, message);
+    }
+    for message in fit.outside_bed {
+        report.add_warning(3021, message);
+    }
+    Ok(())
+}
+
+/// Validate a 3MF file with the streaming parser.
+///
+/// The model part, and every model part its build items and components reference, is
+/// decompressed and parsed incrementally, so memory stays bounded regardless of file size.
+/// Reference integrity, index bounds and duplicate IDs are
+/// checked; geometry checks that need mesh adjacency are skipped.
+///
+/// # Arguments
+///
+/// * `path` - Path to the 3MF file
+/// * `level` - Validation level string: "minimal", "standard", "strict", or "paranoid"
+///
+/// # Errors
+///
+/// Returns an error if the archive cannot be opened or the model XML is malformed.
+/// Exits the process with code 1 if validation errors are found.
+pub fn validate_streaming(path: PathBuf, level: String) -> anyhow::Result<()> {
+    use lib3mf_core::validation::{ValidationLevel, ValidationSeverity};
+
+    let level_enum = match level.to_lowercase().as_str() {
+        "minimal" => ValidationLevel::Minimal,
+        "standard" => ValidationLevel::Standard,
+        "strict" => ValidationLevel::Strict,
+        "paranoid" => ValidationLevel::Paranoid,
+        _ => ValidationLevel::Standard,
+    };
+
+    println!(
+        "Validating {:?} at {:?} level (streaming)...",
+        path, level_enum
+    );
+
+    let mut archiver = open_archive(&path)?;
+    let model_path = locate_model_part(&mut archiver)
+        .map_err(|e| anyhow::anyhow!("Failed to find model path: {}", e))?;
+    // Model parts referenced through the Production extension are streamed as well
+    let result =
+        lib3mf_core::validation::validate_streaming_package(&mut archiver, &model_path, level_enum)
+            .map_err(|e| anyhow::anyhow!("Failed to parse model XML: {}", e))?;
+
+    let summary = &result.summary;
+    let parts: BTreeSet<String> = summary
+        .part_references
+        .iter()
+        .map(|r| r.path.to_ascii_lowercase())
+        .collect();
+    println!(
+        "Scanned {} object(s), {} vertices, {} triangles, {} build item(s) in {} model part(s)",
+        summary.object_count,
+        summary.vertex_count,
+        summary.triangle_count,
+        summary.build_item_count,
+        parts.len() + 1
+    );
+
+    let errors: Vec<_> = result
+        .report
+        .items
+        .iter()
+        .filter(|i| i.severity == ValidationSeverity::Error)
+        .collect();
+    let warnings: Vec<_> = result
+        .report
+        .items
+        .iter()
+        .filter(|i| i.severity == ValidationSeverity::Warning)
+        .collect();
+
+    if !errors.is_empty() {
+        println!("Validation Failed with {} error(s):", errors.len());
+        for item in &errors {
+            match &item.context {
+                Some(part) => println!("  [ERROR {}] {}: {}", item.code, part, item.message),
+                None => println!("  [ERROR {}] {}", item.code, item.message),
+            }
+        }
+        std::process::exit(1);
+    } else if !warnings.is_empty() {
+        println!("Validation Passed with {} warning(s):", warnings.len());
+        for item in &warnings {
+            match &item.context {
+                Some(part) => println!("  [WARN {}] {}: {}", item.code, part, item.message),
+                None => println!("  [WARN {}] {}", item.code, item.message),
+            }
         }
     } else {
         println!("Validation Passed.");
@@ -1220,22 +2338,35 @@ pub fn validate(path: PathBuf, level: String) -> anyhow::Result<()> {
 /// * `output` - Output 3MF file path
 /// * `epsilon` - Vertex merge tolerance for stitching
 /// * `fixes` - List of repair types to perform
+/// * `object_ids` - Objects to repair; all mesh objects when empty
+/// * `degenerate_area` - Area in mm² at or below which triangles are degenerate; relative to
+///   each mesh's bounding box when `None`
+///
+/// Island removal and hole filling are skipped for objects whose type need not be
+/// manifold (support, surface, other), see [`RepairOptions::for_object_type`].
+///
+/// [`RepairOptions::for_object_type`]: lib3mf_core::model::RepairOptions::for_object_type
 ///
 /// # Errors
 ///
-/// Returns an error if parsing or writing fails.
+/// Returns an error if parsing or writing fails, or if an object ID does not name a mesh
+/// object.
 pub fn repair(
     input: PathBuf,
     output: PathBuf,
     epsilon: f32,
     fixes: Vec<RepairType>,
+    object_ids: Vec<u32>,
+    degenerate_area: Option<f64>,
 ) -> anyhow::Result<()> {
-    use lib3mf_core::model::{Geometry, MeshRepair, RepairOptions};
+    use lib3mf_core::model::{
+        AreaDistribution, DegenerateArea, Geometry, MeshRepair, RepairOptions, ResourceId,
+    };
 
     println!("Repairing {:?} -> {:?}", input, output);
 
     let mut archiver = open_archive(&input)?;
-    let model_path = find_model_path(&mut archiver)
+    let model_path = locate_model_part(&mut archiver)
         .map_err(|e| anyhow::anyhow!("Failed to find model path: {}", e))?;
     let model_data = archiver
         .read_entry(&model_path)
@@ -1250,6 +2381,10 @@ pub fn repair(
         harmonize_orientations: false,
         remove_islands: false,
         fill_holes: false,
+        degenerate_area: degenerate_area
+            .map(DegenerateArea::SquareMillimeters)
+            .unwrap_or_default(),
+        unit: model.unit,
     };
 
     let has_all = fixes.contains(&RepairType::All);
@@ -1280,32 +2415,51 @@ pub fn repair(
 
     println!("Repair Options: {:?}", options);
 
+    for &id in &object_ids {
+        match model.resources.get_object(ResourceId(id)) {
+            Some(object) if matches!(object.geometry, Geometry::Mesh(_)) => {}
+            Some(_) => anyhow::bail!("Object {} is not a mesh object", id),
+            None => anyhow::bail!("Object {} not found", id),
+        }
+    }
+
     let mut total_vertices_removed = 0;
     let mut total_triangles_removed = 0;
     let mut total_triangles_flipped = 0;
     let mut total_triangles_added = 0;
+    let mut degenerate_areas = AreaDistribution::default();
 
     for object in model.resources.iter_objects_mut() {
-        if let Geometry::Mesh(mesh) = &mut object.geometry {
-            let stats = mesh.repair(options);
-            if stats.vertices_removed > 0
-                || stats.triangles_removed > 0
-                || stats.triangles_flipped > 0
-                || stats.triangles_added > 0
-            {
-                println!(
-                    "Repaired Object {}: Removed {} vertices, {} triangles. Flipped {}. Added {}.",
-                    object.id.0,
-                    stats.vertices_removed,
-                    stats.triangles_removed,
-                    stats.triangles_flipped,
-                    stats.triangles_added
-                );
-                total_vertices_removed += stats.vertices_removed;
-                total_triangles_removed += stats.triangles_removed;
-                total_triangles_flipped += stats.triangles_flipped;
-                total_triangles_added += stats.triangles_added;
-            }
+        if !matches!(object.geometry, Geometry::Mesh(_))
+            || (!object_ids.is_empty() && !object_ids.contains(&object.id.0))
+        {
+            continue;
+        }
+        if options.for_object_type(object.object_type) != options {
+            println!(
+                "Object {} is of type {}: skipping island removal and hole filling",
+                object.id.0, object.object_type
+            );
+        }
+        let stats = object.repair(options);
+        if stats.vertices_removed > 0
+            || stats.triangles_removed > 0
+            || stats.triangles_flipped > 0
+            || stats.triangles_added > 0
+        {
+            println!(
+                "Repaired Object {}: Removed {} vertices, {} triangles. Flipped {}. Added {}.",
+                object.id.0,
+                stats.vertices_removed,
+                stats.triangles_removed,
+                stats.triangles_flipped,
+                stats.triangles_added
+            );
+            total_vertices_removed += stats.vertices_removed;
+            total_triangles_removed += stats.triangles_removed;
+            total_triangles_flipped += stats.triangles_flipped;
+            total_triangles_added += stats.triangles_added;
+            degenerate_areas.merge(&stats.degenerate_areas);
         }
     }
 
@@ -1314,6 +2468,16 @@ pub fn repair(
     println!("  Triangles Removed: {}", total_triangles_removed);
     println!("  Triangles Flipped: {}", total_triangles_flipped);
     println!("  Triangles Added:   {}", total_triangles_added);
+    if degenerate_areas.count() > 0 {
+        println!("Degenerate Triangle Areas (mm²):");
+        if degenerate_areas.zero > 0 {
+            println!("  zero:              {}", degenerate_areas.zero);
+        }
+        for (decade, count) in &degenerate_areas.decades {
+            let range = format!("1e{}..1e{}", decade, decade + 1);
+            println!("  {:<18} {}", range, count);
+        }
+    }
 
     // Write output
     let file = File::create(&output)
@@ -1325,65 +2489,6 @@ pub fn repair(
     Ok(())
 }
 
-/// Benchmark loading and parsing performance.
-///
-/// Measures time taken for ZIP archive opening, XML parsing, and statistics calculation.
-/// Useful for performance profiling and identifying bottlenecks.
-///
-/// # Arguments
-///
-/// * `path` - Path to the 3MF file
-///
-/// # Errors
-///
-/// Returns an error if the file cannot be opened or parsed.
-pub fn benchmark(path: PathBuf) -> anyhow::Result<()> {
-    use std::time::Instant;
-
-    println!("Benchmarking {:?}...", path);
-
-    let start = Instant::now();
-    let mut archiver = open_archive(&path)?;
-    let t_zip = start.elapsed();
-
-    let start_parse = Instant::now();
-    let model_path = find_model_path(&mut archiver)
-        .map_err(|e| anyhow::anyhow!("Failed to find model path: {}", e))?;
-    let model_data = archiver
-        .read_entry(&model_path)
-        .map_err(|e| anyhow::anyhow!("Failed to read model data: {}", e))?;
-    let model = parse_model(std::io::Cursor::new(model_data))
-        .map_err(|e| anyhow::anyhow!("Failed to parse model XML: {}", e))?;
-    let t_parse = start_parse.elapsed();
-
-    let start_stats = Instant::now();
-    let stats = model
-        .compute_stats(&mut archiver)
-        .map_err(|e| anyhow::anyhow!("Failed to compute stats: {}", e))?;
-    let t_stats = start_stats.elapsed();
-
-    let total = start.elapsed();
-
-    println!("Results:");
-    println!(
-        "  System: {} ({} CPUs), SIMD: {}",
-        stats.system_info.architecture,
-        stats.system_info.num_cpus,
-        stats.system_info.simd_features.join(", ")
-    );
-    println!("  Zip Open: {:?}", t_zip);
-    println!("  XML Parse: {:?}", t_parse);
-    println!("  Stats Calc: {:?}", t_stats);
-    println!("  Total: {:?}", total);
-    println!("  Triangles: {}", stats.geometry.triangle_count);
-    println!(
-        "  Area: {:.2}, Volume: {:.2}",
-        stats.geometry.surface_area, stats.geometry.volume
-    );
-
-    Ok(())
-}
-
 /// Compare two 3MF files structurally.
 ///
 /// Performs a detailed comparison detecting differences in metadata, resource counts,
@@ -1394,22 +2499,48 @@ pub fn benchmark(path: PathBuf) -> anyhow::Result<()> {
 /// * `file1` - First 3MF file path
 /// * `file2` - Second 3MF file path
 /// * `format` - Output format ("text" or "json")
+/// * `deviation` - Also measure the geometric deviation of mesh objects present in both files
+/// * `tolerance` - Fail if any deviation exceeds this length; implies `deviation`
 ///
 /// # Errors
 ///
-/// Returns an error if either file cannot be parsed.
-pub fn diff(file1: PathBuf, file2: PathBuf, format: &str) -> anyhow::Result<()> {
+/// Returns an error if either file cannot be parsed, or if a deviation exceeds `tolerance`.
+pub fn diff(
+    file1: PathBuf,
+    file2: PathBuf,
+    format: &str,
+    deviation: bool,
+    tolerance: Option<&hollow::LengthArg>,
+) -> anyhow::Result<()> {
     println!("Comparing {:?} and {:?}...", file1, file2);
 
     let model_a = load_model(&file1)?;
     let model_b = load_model(&file2)?;
 
     let diff = lib3mf_core::utils::diff::compare_models(&model_a, &model_b);
+    let deviations = if deviation || tolerance.is_some() {
+        object_deviations(&model_a, &model_b)?
+    } else {
+        Vec::new()
+    };
 
     if format == "json" {
-        println!("{}", serde_json::to_string_pretty(&diff)?);
-    } else if diff.is_empty() {
+        #[derive(Serialize)]
+        struct Report<'a> {
+            #[serde(flatten)]
+            diff: &'a lib3mf_core::utils::diff::ModelDiff,
+            #[serde(skip_serializing_if = "Vec::is_empty")]
+            deviations: &'a Vec<ObjectDeviation>,
+        }
+        let report = Report {
+            diff: &diff,
+            deviations: &deviations,
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else if diff.is_empty() && deviations.is_empty() {
         println!("Models are identical.");
+    } else if diff.is_empty() {
+        println!("Models are structurally identical.");
     } else {
         println!("Differences found:");
         if !diff.metadata_diffs.is_empty() {
@@ -1445,9 +2576,90 @@ pub fn diff(file1: PathBuf, file2: PathBuf, format: &str) -> anyhow::Result<()>
         }
     }
 
+    let unit = model_a.unit.symbol();
+    if format != "json" && !deviations.is_empty() {
+        println!("  Geometric deviation ({}):", unit);
+        for d in &deviations {
+            println!(
+                "    Object {}: max {:.4}, RMS {:.4}, mean {:.4}",
+                d.object_id, d.deviation.hausdorff, d.deviation.rms, d.deviation.mean
+            );
+        }
+    }
+    let worst = deviations
+        .iter()
+        .max_by(|a, b| a.deviation.hausdorff.total_cmp(&b.deviation.hausdorff));
+    if format != "json"
+        && let Some(worst) = worst
+    {
+        let at = worst.deviation.max_location;
+        println!(
+            "  Maximum deviation: {:.4} {} on object {} near ({:.3}, {:.3}, {:.3})",
+            worst.deviation.hausdorff, unit, worst.object_id, at.x, at.y, at.z
+        );
+    }
+    if let (Some(tolerance), Some(worst)) = (tolerance, worst) {
+        let limit = tolerance.in_units(model_a.unit);
+        if worst.deviation.hausdorff > limit {
+            anyhow::bail!(
+                "Object {} deviates by {:.4} {}, more than the tolerance of {} {}",
+                worst.object_id,
+                worst.deviation.hausdorff,
+                unit,
+                limit,
+                unit
+            );
+        }
+    }
+
     Ok(())
 }
 
+/// Deviation of one mesh object between the two files.
+#[derive(Serialize)]
+struct ObjectDeviation {
+    object_id: u32,
+    #[serde(flatten)]
+    deviation: lib3mf_core::utils::diff::MeshDeviation,
+}
+
+/// Compares every object that is a mesh in both models, in the units of `a`.
+fn object_deviations(
+    a: &lib3mf_core::model::Model,
+    b: &lib3mf_core::model::Model,
+) -> anyhow::Result<Vec<ObjectDeviation>> {
+    use lib3mf_core::model::Geometry;
+
+    let scale = b.unit.convert(1.0, a.unit) as f32;
+    let mut deviations = Vec::new();
+    for object in a.resources.iter_objects() {
+        let (Geometry::Mesh(mesh_a), Some(Geometry::Mesh(mesh_b))) = (
+            &object.geometry,
+            b.resources.get_object(object.id).map(|o| &o.geometry),
+        ) else {
+            continue;
+        };
+        if mesh_a.triangles.is_empty() || mesh_b.triangles.is_empty() {
+            continue;
+        }
+        let mut mesh_b = mesh_b.clone();
+        if scale != 1.0 {
+            for v in &mut mesh_b.vertices {
+                v.x *= scale;
+                v.y *= scale;
+                v.z *= scale;
+            }
+        }
+        let deviation = lib3mf_core::utils::diff::compare_meshes(mesh_a, &mesh_b)
+            .map_err(|e| anyhow::anyhow!("Failed to compare object {}: {}", object.id.0, e))?;
+        deviations.push(ObjectDeviation {
+            object_id: object.id.0,
+            deviation,
+        });
+    }
+    Ok(deviations)
+}
+
 fn load_model(path: &PathBuf) -> anyhow::Result<lib3mf_core::model::Model> {
     match open_model(path)? {
         ModelSource::Archive(_, model) => Ok(model),
@@ -1632,6 +2844,8 @@ pub fn verify(file: PathBuf) -> anyhow::Result<()> {
             })
         };
 
+        let coverage = signature.coverage(&mut archiver)?;
+
         match lib3mf_core::crypto::verification::verify_signature_extended(
             &signature,
             resolver,
@@ -1640,6 +2854,7 @@ pub fn verify(file: PathBuf) -> anyhow::Result<()> {
             Ok(valid) => {
                 if valid {
                     println!("  [PASS] Signature is VALID.");
+                    print_signature_coverage(&coverage);
                     // Check certificate trust if present
                     if let Some(mut ki) = signature.key_info {
                         if let Some(x509) = ki.x509_data.take() {
@@ -1684,6 +2899,30 @@ pub fn verify(file: PathBuf) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Reports the parts a valid signature covers and warns about unprotected model parts
+/// and textures.
+#[cfg(feature = "crypto")]
+fn print_signature_coverage(coverage: &lib3mf_core::model::SignatureCoverage) {
+    println!("  [INFO] Covers {} part(s):", coverage.covered.len());
+    for part in &coverage.covered {
+        println!("           {}", part);
+    }
+    for uri in &coverage.missing {
+        println!("  [WARNING] Referenced part {} is not in the package", uri);
+    }
+    if !coverage.protects_geometry() {
+        println!(
+            "  [WARNING] Signature does not protect the geometry; these parts can be modified without invalidating it:"
+        );
+        for part in &coverage.uncovered_critical {
+            println!("           {}", part);
+        }
+    }
+    if !coverage.uncovered.is_empty() {
+        println!("  [INFO] Not covered: {}", coverage.uncovered.join(", "));
+    }
+}
+
 /// Verify digital signatures (crypto feature disabled).
 ///
 /// This is a stub function that returns an error when the `crypto` feature is not enabled.
@@ -24,6 +24,7 @@
 use crate::commands::OutputFormat;
 use crate::commands::merge::Verbosity;
 use glob::glob;
+use lib3mf_converters::stl::{StlExportOptions, StlExporter, StlFormat};
 use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
 use lib3mf_core::model::{Geometry, Model};
 use lib3mf_core::parser::parse_model;
@@ -464,6 +465,7 @@ fn run_stats_op(result: &mut FileResult, model: &Model, archiver: &mut ZipArchiv
                     "instance_count": stats.geometry.instance_count,
                     "surface_area": stats.geometry.surface_area,
                     "volume": stats.geometry.volume,
+                    "unique": stats.geometry.unique,
                     "is_manifold": stats.geometry.is_manifold,
                 },
                 "materials": {
@@ -549,11 +551,12 @@ fn run_convert_3mf_op(
         }
     };
 
-    let write_result = if ops.convert_ascii {
-        lib3mf_converters::stl::AsciiStlExporter::write(model, out_file)
+    let format = if ops.convert_ascii {
+        StlFormat::Ascii
     } else {
-        lib3mf_converters::stl::BinaryStlExporter::write(model, out_file)
+        StlFormat::Binary
     };
+    let write_result = StlExporter::new(StlExportOptions { format }).write(model, out_file);
 
     match write_result {
         Ok(()) => {
@@ -1027,7 +1030,7 @@ mod tests {
     fn test_discover_files_single() {
         let dir = TempDir::new().unwrap();
         let p = make_zip_file(dir.path(), "a.3mf");
-        let discovered = discover_files(&[p.clone()], false).unwrap();
+        let discovered = discover_files(std::slice::from_ref(&p), false).unwrap();
         assert_eq!(discovered.len(), 1);
         assert_eq!(discovered[0], p);
     }
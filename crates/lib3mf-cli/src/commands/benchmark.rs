@@ -0,0 +1,291 @@
+//! Benchmark command — time each stage of the load/process/write pipeline.
+//!
+//! Every phase is run `warmup + iterations` times; warmup samples are discarded and the
+//! remaining samples are summarized as min / median / p95 / mean. JSON output is stable
+//! and self-describing so it can be archived by CI and plotted over time.
+//!
+//! Samples are the durations the library itself records in its timing spans (see
+//! [`lib3mf_core::trace`]), so they cover exactly the library stage and match what an
+//! application collecting the same spans would see.
+
+use crate::commands::{OutputFormat, locate_model_part};
+use lib3mf_core::archive::ArchiveReader;
+use lib3mf_core::model::{Geometry, MeshRepair, Model, RepairOptions};
+use lib3mf_core::parser::parse_model;
+use lib3mf_core::trace::{TimingCollector, span};
+use lib3mf_core::utils::hardware::{HardwareCapabilities, detect_capabilities};
+use lib3mf_core::validation::ValidationLevel;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Which optional phases to include and how many samples to take.
+#[derive(Debug, Clone)]
+pub struct BenchmarkOptions {
+    /// Measured iterations per phase (at least 1).
+    pub iterations: usize,
+    /// Unmeasured iterations run before sampling.
+    pub warmup: usize,
+    /// Benchmark serializing the model to an in-memory 3MF package.
+    pub write: bool,
+    /// Benchmark `MeshRepair::repair` with the default `RepairOptions`.
+    pub repair: bool,
+    /// Benchmark `Model::validate` at each validation level.
+    pub validate: bool,
+    /// Output format (text or json).
+    pub format: OutputFormat,
+}
+
+impl Default for BenchmarkOptions {
+    fn default() -> Self {
+        Self {
+            iterations: 1,
+            warmup: 0,
+            write: false,
+            repair: false,
+            validate: false,
+            format: OutputFormat::Text,
+        }
+    }
+}
+
+/// Timing summary for one phase, in milliseconds.
+#[derive(Debug, Clone, Serialize)]
+pub struct PhaseTiming {
+    /// Phase name, e.g. `"parse"` or `"validate_standard"`.
+    pub name: String,
+    /// Number of measured samples.
+    pub samples: usize,
+    /// Fastest sample.
+    pub min_ms: f64,
+    /// Median sample.
+    pub median_ms: f64,
+    /// 95th percentile (nearest-rank).
+    pub p95_ms: f64,
+    /// Arithmetic mean.
+    pub mean_ms: f64,
+}
+
+impl PhaseTiming {
+    fn from_samples(name: &str, samples: &[Duration]) -> Self {
+        let mut ms: Vec<f64> = samples.iter().map(|d| d.as_secs_f64() * 1e3).collect();
+        ms.sort_by(|a, b| a.total_cmp(b));
+        let n = ms.len();
+        let rank = |p: f64| ms[((p * n as f64).ceil() as usize).clamp(1, n) - 1];
+        Self {
+            name: name.to_string(),
+            samples: n,
+            min_ms: ms[0],
+            median_ms: if n % 2 == 1 {
+                ms[n / 2]
+            } else {
+                (ms[n / 2 - 1] + ms[n / 2]) / 2.0
+            },
+            p95_ms: rank(0.95),
+            mean_ms: ms.iter().sum::<f64>() / n as f64,
+        }
+    }
+}
+
+/// Complete benchmark report, serialized as-is for `--format json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchmarkReport {
+    /// Input file.
+    pub file: PathBuf,
+    /// `lib3mf-cli` version that produced the report.
+    pub version: String,
+    /// Measured iterations per phase.
+    pub iterations: usize,
+    /// Discarded warmup iterations per phase.
+    pub warmup: usize,
+    /// Host hardware summary.
+    pub system: HardwareCapabilities,
+    /// Number of object resources in the model.
+    pub object_count: usize,
+    /// Total vertices across all meshes.
+    pub vertex_count: u64,
+    /// Total triangles across all meshes.
+    pub triangle_count: u64,
+    /// One entry per benchmarked phase, in execution order.
+    pub phases: Vec<PhaseTiming>,
+}
+
+/// Runs `f` for each warmup and measured iteration. A sample is the total duration of the
+/// library spans named `stage` opened by one run, which is zero if there were none (such
+/// as repairing a model without meshes).
+fn measure<T>(
+    opts: &BenchmarkOptions,
+    stage: &str,
+    mut f: impl FnMut() -> anyhow::Result<T>,
+) -> anyhow::Result<(Vec<Duration>, T)> {
+    let collector = TimingCollector::new();
+    tracing::subscriber::with_default(collector.clone(), || {
+        for _ in 0..opts.warmup {
+            f()?;
+        }
+        let mut samples = Vec::with_capacity(opts.iterations);
+        let mut last = None;
+        for _ in 0..opts.iterations.max(1) {
+            collector.clear();
+            let value = f()?;
+            samples.push(Duration::from_secs_f64(collector.total_ms(stage) / 1e3));
+            last = Some(value);
+        }
+        // iterations.max(1) guarantees at least one value
+        Ok((samples, last.expect("at least one iteration")))
+    })
+}
+
+fn load(path: &PathBuf) -> anyhow::Result<Model> {
+    let mut archiver = crate::commands::open_archive(path)?;
+    let model_path = locate_model_part(&mut archiver)
+        .map_err(|e| anyhow::anyhow!("Failed to find model path: {}", e))?;
+    let model_data = archiver
+        .read_entry(&model_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read model data: {}", e))?;
+    parse_model(std::io::Cursor::new(model_data))
+        .map_err(|e| anyhow::anyhow!("Failed to parse model XML: {}", e))
+}
+
+/// Runs the benchmark and returns the report without printing it.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened, parsed, or written.
+pub fn measure_file(path: &PathBuf, opts: &BenchmarkOptions) -> anyhow::Result<BenchmarkReport> {
+    let mut phases = Vec::new();
+
+    let (samples, _) = measure(opts, span::ARCHIVE_OPEN, || {
+        crate::commands::open_archive(path)
+    })?;
+    phases.push(PhaseTiming::from_samples("zip_open", &samples));
+
+    let (samples, model) = measure(opts, span::PARSE_MODEL, || load(path))?;
+    phases.push(PhaseTiming::from_samples("parse", &samples));
+
+    let (samples, stats) = measure(opts, span::COMPUTE_STATS, || {
+        let mut archiver = crate::commands::open_archive(path)?;
+        model
+            .compute_stats(&mut archiver)
+            .map_err(|e| anyhow::anyhow!("Failed to compute stats: {}", e))
+    })?;
+    phases.push(PhaseTiming::from_samples("stats", &samples));
+
+    if opts.write {
+        let (samples, _) = measure(opts, span::WRITE_PACKAGE, || {
+            let mut buf = std::io::Cursor::new(Vec::new());
+            model
+                .write(&mut buf)
+                .map_err(|e| anyhow::anyhow!("Failed to write 3MF: {}", e))
+        })?;
+        phases.push(PhaseTiming::from_samples("write", &samples));
+    }
+
+    if opts.repair {
+        let options = RepairOptions::default();
+        // Repair mutates in place, so each run works on a fresh clone; only the
+        // per-mesh repair spans are counted, not the clone.
+        let (samples, _) = measure(opts, span::REPAIR_MESH, || {
+            repair_all(&mut model.clone(), options);
+            Ok(())
+        })?;
+        phases.push(PhaseTiming::from_samples("repair", &samples));
+    }
+
+    if opts.validate {
+        for (name, level) in [
+            ("validate_minimal", ValidationLevel::Minimal),
+            ("validate_standard", ValidationLevel::Standard),
+            ("validate_strict", ValidationLevel::Strict),
+            ("validate_paranoid", ValidationLevel::Paranoid),
+        ] {
+            let (samples, _) = measure(opts, span::VALIDATE, || Ok(model.validate(level)))?;
+            phases.push(PhaseTiming::from_samples(name, &samples));
+        }
+    }
+
+    Ok(BenchmarkReport {
+        file: path.clone(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        iterations: opts.iterations.max(1),
+        warmup: opts.warmup,
+        system: detect_capabilities(),
+        object_count: model.resources.iter_objects().count(),
+        vertex_count: stats.geometry.vertex_count,
+        triangle_count: stats.geometry.triangle_count,
+        phases,
+    })
+}
+
+fn repair_all(model: &mut Model, options: RepairOptions) {
+    for object in model.resources.iter_objects_mut() {
+        if let Geometry::Mesh(mesh) = &mut object.geometry {
+            mesh.repair(options);
+        }
+    }
+}
+
+/// Entry point for the `benchmark` subcommand.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened, parsed, or written.
+pub fn run(path: PathBuf, opts: BenchmarkOptions) -> anyhow::Result<()> {
+    if opts.format != OutputFormat::Json {
+        println!("Benchmarking {:?}...", path);
+    }
+
+    let report = measure_file(&path, &opts)?;
+
+    if opts.format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!("Results:");
+    println!(
+        "  System: {} ({} CPUs), SIMD: {}",
+        report.system.architecture,
+        report.system.num_cpus,
+        report.system.simd_features.join(", ")
+    );
+    println!(
+        "  Iterations: {} (warmup {})",
+        report.iterations, report.warmup
+    );
+    println!(
+        "  Model: {} objects, {} vertices, {} triangles",
+        report.object_count, report.vertex_count, report.triangle_count
+    );
+    println!(
+        "  {:<20} {:>12} {:>12} {:>12}",
+        "Phase", "Min (ms)", "Median (ms)", "P95 (ms)"
+    );
+    for phase in &report.phases {
+        println!(
+            "  {:<20} {:>12.3} {:>12.3} {:>12.3}",
+            phase.name, phase.min_ms, phase.median_ms, phase.p95_ms
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phase_timing_percentiles() {
+        let samples: Vec<Duration> = (1..=20).map(Duration::from_millis).collect();
+        let t = PhaseTiming::from_samples("x", &samples);
+        assert_eq!(t.samples, 20);
+        assert!((t.min_ms - 1.0).abs() < 1e-9);
+        assert!((t.median_ms - 10.5).abs() < 1e-9);
+        assert!((t.p95_ms - 19.0).abs() < 1e-9);
+        assert!((t.mean_ms - 10.5).abs() < 1e-9);
+
+        let t = PhaseTiming::from_samples("single", &[Duration::from_millis(3)]);
+        assert!((t.median_ms - 3.0).abs() < 1e-9);
+        assert!((t.p95_ms - 3.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,119 @@
+use crate::commands::OutputFormat;
+use anyhow::Result;
+use lib3mf_core::validation::ValidationLevel;
+use lib3mf_core::validation::conformance::{
+    ConformanceOptions, ConformanceReport, Expectation, run_conformance_suite,
+};
+use std::path::PathBuf;
+
+/// Entry point for the `conformance` subcommand.
+///
+/// Runs every `.3mf` file below `suite` through
+/// [`run_conformance_suite`], prints the pass/fail matrix and, if `report` is given,
+/// writes it as JUnit XML. `level` overrides the default levels (Standard for samples
+/// that must pass, Strict for samples that must fail). Fails if any sample did not
+/// behave as expected.
+pub fn run(
+    suite: PathBuf,
+    report: Option<PathBuf>,
+    level: Option<String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let options = match level.as_deref().map(str::to_lowercase).as_deref() {
+        None => ConformanceOptions::default(),
+        Some("minimal") => ConformanceOptions::with_level(ValidationLevel::Minimal),
+        Some("standard") => ConformanceOptions::with_level(ValidationLevel::Standard),
+        Some("strict") => ConformanceOptions::with_level(ValidationLevel::Strict),
+        Some("paranoid") => ConformanceOptions::with_level(ValidationLevel::Paranoid),
+        Some(other) => anyhow::bail!(
+            "Unknown validation level '{}' (expected minimal, standard, strict or paranoid)",
+            other
+        ),
+    };
+
+    let results = run_conformance_suite(&suite, &options)
+        .map_err(|e| anyhow::anyhow!("Failed to read suite {}: {}", suite.display(), e))?;
+    if results.results.is_empty() {
+        anyhow::bail!("No .3mf samples found in {}", suite.display());
+    }
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&results)?),
+        _ => print_matrix(&results),
+    }
+
+    if let Some(report) = report {
+        std::fs::write(&report, results.to_junit_xml())?;
+        if format != OutputFormat::Json {
+            println!("Wrote JUnit report to {}", report.display());
+        }
+    }
+
+    let failed = results.results.len() - results.passed();
+    if failed > 0 {
+        anyhow::bail!(
+            "{} of {} sample(s) did not behave as expected",
+            failed,
+            results.results.len()
+        );
+    }
+    Ok(())
+}
+
+fn print_matrix(report: &ConformanceReport) {
+    let width = report
+        .results
+        .iter()
+        .map(|r| r.path.display().to_string().len())
+        .max()
+        .unwrap_or(0)
+        .max("Sample".len());
+
+    println!(
+        "{:<width$}  {:<8}  {:<16}  Verdict",
+        "Sample", "Expected", "Result"
+    );
+    for result in &report.results {
+        let expected = match result.expectation {
+            Expectation::MustPass => "pass",
+            Expectation::MustFail => "fail",
+        };
+        let outcome = match &result.rejection {
+            None => "accepted".to_string(),
+            Some(rejection) => format!("{} error", rejection.stage),
+        };
+        let verdict = if result.passed() { "PASS" } else { "FAIL" };
+        println!(
+            "{:<width$}  {:<8}  {:<16}  {}",
+            result.path.display().to_string(),
+            expected,
+            outcome,
+            verdict
+        );
+        if !result.passed()
+            && let Some(rejection) = &result.rejection
+        {
+            println!("{:<width$}    {}", "", rejection.message);
+        }
+    }
+
+    let by_expectation = |expectation: Expectation| {
+        let results: Vec<_> = report
+            .results
+            .iter()
+            .filter(|r| r.expectation == expectation)
+            .collect();
+        let passed = results.iter().filter(|r| r.passed()).count();
+        (passed, results.len())
+    };
+    let (pass_ok, pass_total) = by_expectation(Expectation::MustPass);
+    let (fail_ok, fail_total) = by_expectation(Expectation::MustFail);
+    println!();
+    println!("MUSTPASS: {}/{} accepted", pass_ok, pass_total);
+    println!("MUSTFAIL: {}/{} rejected", fail_ok, fail_total);
+    println!(
+        "Total:    {}/{} as expected",
+        report.passed(),
+        report.results.len()
+    );
+}
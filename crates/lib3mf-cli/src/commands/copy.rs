@@ -0,0 +1,359 @@
+use crate::commands::{locate_model_part, open_archive};
+use anyhow::Result;
+use lib3mf_core::archive::opc::{self, ContentType, Relationship};
+use lib3mf_core::archive::{ArchiveReader, rels_source_part, resolve_part_target};
+use lib3mf_core::model::{Model, Package};
+use lib3mf_core::parser::parse_model;
+use lib3mf_core::writer::package_writer::PackageWriter;
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+/// Relationship type suffixes of OPC digital signatures: the signature parts themselves and
+/// the signature origin part that anchors them.
+const SIGNATURE_REL_SUFFIXES: &[&str] = &["/signature", "/digital-signature/origin"];
+
+/// Relationship type suffixes of the 3MF Secure Content extension.
+const SECURE_CONTENT_REL_SUFFIXES: &[&str] = &["/keystore", "/encryptedfile"];
+
+/// Package parts that re-serializing the package would invalidate.
+#[derive(Debug, Default)]
+pub struct ProtectedParts {
+    /// Signature parts (XML-DSig documents).
+    pub signatures: BTreeSet<String>,
+    /// Signature origin parts, which exist only to anchor the signatures.
+    pub signature_origins: BTreeSet<String>,
+    /// Secure Content keystores and encrypted parts.
+    pub encrypted: BTreeSet<String>,
+}
+
+impl ProtectedParts {
+    /// Finds signed and encrypted parts by scanning every relationship file in the archive.
+    pub fn scan<A: ArchiveReader>(archiver: &mut A) -> Result<Self> {
+        let mut parts = Self::default();
+        for rels_path in archiver.list_entries()? {
+            if !rels_path.ends_with(".rels") {
+                continue;
+            }
+            let Ok(data) = archiver.read_entry(&rels_path) else {
+                continue;
+            };
+            let Ok(rels) = opc::parse_relationships(&data) else {
+                continue;
+            };
+            for rel in rels {
+                if rel.target_mode.eq_ignore_ascii_case("External") {
+                    continue;
+                }
+                let target = resolve_target(&rels_path, &rel.target);
+                if rel.rel_type.ends_with("/digital-signature/origin") {
+                    parts.signature_origins.insert(target);
+                } else if is_signature_rel(&rel) {
+                    parts.signatures.insert(target);
+                } else if SECURE_CONTENT_REL_SUFFIXES
+                    .iter()
+                    .any(|s| rel.rel_type.ends_with(s))
+                {
+                    parts.encrypted.insert(target);
+                }
+            }
+        }
+        Ok(parts)
+    }
+
+    /// Removes signature parts and relationships from a model loaded with its attachments.
+    pub(crate) fn remove_signatures(&self, model: &mut Model) {
+        model
+            .attachments
+            .retain(|path, _| !self.is_signature_part(path));
+        model
+            .existing_relationships
+            .retain(|path, _| !self.is_signature_part(path));
+        for rels in model.existing_relationships.values_mut() {
+            rels.retain(|r| !is_signature_rel(r));
+        }
+    }
+
+    /// Returns true if `path` is a signature or signature origin part, or the relationship
+    /// file of one.
+    fn is_signature_part(&self, path: &str) -> bool {
+        let part = rels_source(path);
+        let part = part.as_deref().unwrap_or(path);
+        self.signatures.contains(part) || self.signature_origins.contains(part)
+    }
+}
+
+/// Entry point for the `copy` subcommand.
+///
+/// Parses the model to verify it can be read, then writes the package to `output`.
+/// Unprotected packages are re-serialized, exercising the full read/write cycle.
+///
+/// Re-serializing would invalidate digital signatures and could corrupt Secure Content, so:
+///
+/// - A signed package is copied entry by entry with each part's compressed bytes unchanged,
+///   keeping the signatures valid. With `strip_signatures`, the signature parts and their
+///   relationships are removed instead, with a warning.
+/// - Keystores and encrypted parts are always copied unmodified, as are all other parts of
+///   a package that contains them.
+///
+/// With `checksums`, a geometry checksum for every object is embedded in the model's
+/// metadata. With `stats_cache`, the model statistics are stored in the package (see
+/// `lib3mf_core::model::stats_cache`). Both require re-serializing, so they are ignored for
+/// packages copied unmodified.
+pub fn run(
+    input: PathBuf,
+    output: PathBuf,
+    strip_signatures: bool,
+    checksums: bool,
+    stats_cache: bool,
+) -> Result<()> {
+    let mut archiver = open_archive(&input)?;
+    let model_path = locate_model_part(&mut archiver)
+        .map_err(|e| anyhow::anyhow!("Failed to find model path: {}", e))?;
+    let model_data = archiver
+        .read_entry(&model_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read model data: {}", e))?;
+    let mut model = parse_model(std::io::Cursor::new(model_data))
+        .map_err(|e| anyhow::anyhow!("Failed to parse model XML: {}", e))?;
+
+    let protected = ProtectedParts::scan(&mut archiver)?;
+    if !protected.signatures.is_empty() {
+        if strip_signatures {
+            eprintln!(
+                "Warning: removing {} digital signature(s); the copy will not be signed",
+                protected.signatures.len()
+            );
+        } else {
+            println!(
+                "Package has {} digital signature(s); copying parts unmodified to keep them valid \
+                (use --strip-signatures to remove them and re-serialize)",
+                protected.signatures.len()
+            );
+        }
+    }
+    if !protected.encrypted.is_empty() {
+        println!(
+            "Preserving {} keystore/encrypted part(s) unmodified",
+            protected.encrypted.len()
+        );
+    }
+
+    let has_signatures =
+        !protected.signatures.is_empty() || !protected.signature_origins.is_empty();
+    if protected.encrypted.is_empty() && (!has_signatures || strip_signatures) {
+        // Load all existing files to preserve multi-part relationships and attachments
+        let core_properties = crate::commands::load_core_properties(&mut archiver, &mut model);
+        // Skip the parts PackageWriter regenerates, and stripped signatures
+        model.load_attachments(&mut archiver, |path| {
+            path == model_path
+                || Some(path) == core_properties.as_deref()
+                || protected.is_signature_part(path)
+        })?;
+        for rels in model.existing_relationships.values_mut() {
+            rels.retain(|r| !is_signature_rel(r));
+        }
+
+        if checksums {
+            let count = model.embed_geometry_checksums();
+            println!("Embedded {} geometry checksum(s)", count);
+        }
+
+        let file = File::create(&output)
+            .map_err(|e| anyhow::anyhow!("Failed to create output file: {}", e))?;
+        PackageWriter::new(file)
+            .with_stats_cache(stats_cache)
+            .write(&Package::new(model))
+            .map_err(|e| anyhow::anyhow!("Failed to write 3MF: {}", e))?;
+    } else {
+        if checksums {
+            eprintln!(
+                "Warning: not embedding geometry checksums; the package is copied unmodified"
+            );
+        }
+        if stats_cache {
+            eprintln!("Warning: not storing statistics; the package is copied unmodified");
+        }
+        let strip = if strip_signatures {
+            Some(&protected)
+        } else {
+            None
+        };
+        passthrough(&input, &output, strip)?;
+    }
+
+    println!("Copied {:?} to {:?}", input, output);
+    Ok(())
+}
+
+/// Copies every entry of `input` to `output` without recompressing it.
+///
+/// When `strip` is given, its signature parts are dropped, and relationship files and
+/// `[Content_Types].xml` are rewritten without references to them.
+fn passthrough(input: &PathBuf, output: &PathBuf, strip: Option<&ProtectedParts>) -> Result<()> {
+    let file =
+        File::open(input).map_err(|e| anyhow::anyhow!("Failed to open file {:?}: {}", input, e))?;
+    let mut archive =
+        ZipArchive::new(file).map_err(|e| anyhow::anyhow!("Failed to open zip archive: {}", e))?;
+    let out =
+        File::create(output).map_err(|e| anyhow::anyhow!("Failed to create output file: {}", e))?;
+    let mut writer = ZipWriter::new(out);
+    let options = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .unix_permissions(0o644);
+
+    for i in 0..archive.len() {
+        let name = archive.by_index_raw(i)?.name().to_string();
+        let rewritten = match strip {
+            Some(parts) if parts.is_signature_part(&name) => continue,
+            Some(parts) => strip_references(&mut archive, i, &name, parts)?,
+            None => None,
+        };
+        match rewritten {
+            Some(data) => {
+                writer.start_file(name.as_str(), options)?;
+                writer.write_all(&data)?;
+            }
+            None => writer.raw_copy_file(archive.by_index_raw(i)?)?,
+        }
+    }
+
+    writer.finish()?;
+    Ok(())
+}
+
+/// Returns the content of entry `index` without references to stripped signature parts, or
+/// `None` if it has none and can be copied as is.
+fn strip_references(
+    archive: &mut ZipArchive<File>,
+    index: usize,
+    name: &str,
+    parts: &ProtectedParts,
+) -> Result<Option<Vec<u8>>> {
+    let is_rels = name.ends_with(".rels");
+    if !is_rels && name != "[Content_Types].xml" {
+        return Ok(None);
+    }
+    let mut data = Vec::new();
+    archive.by_index(index)?.read_to_end(&mut data)?;
+
+    if is_rels {
+        let mut rels = opc::parse_relationships(&data)?;
+        let before = rels.len();
+        rels.retain(|r| !is_signature_rel(r));
+        return Ok((rels.len() != before).then(|| relationships_xml(&rels).into_bytes()));
+    }
+
+    let mut types = opc::parse_content_types(&data)?;
+    let before = types.len();
+    types.retain(|t| match t {
+        ContentType::Override { part_name, .. } => {
+            !parts.is_signature_part(part_name.trim_start_matches('/'))
+        }
+        ContentType::Default { .. } => true,
+    });
+    Ok((types.len() != before).then(|| content_types_xml(&types).into_bytes()))
+}
+
+fn is_signature_rel(rel: &Relationship) -> bool {
+    SIGNATURE_REL_SUFFIXES
+        .iter()
+        .any(|s| rel.rel_type.ends_with(s))
+}
+
+/// Returns the part whose relationships `rels_path` holds, e.g. `3D/3dmodel.model` for
+/// `3D/_rels/3dmodel.model.rels`.
+fn rels_source(rels_path: &str) -> Option<String> {
+    let (base, name) = rels_path.rsplit_once("_rels/")?;
+    let part = name.strip_suffix(".rels")?;
+    (!part.is_empty()).then(|| format!("{base}{part}"))
+}
+
+/// Resolves a relationship target to an archive entry name.
+///
+/// Absolute targets are package-rooted; relative ones are relative to the source part's
+/// directory, which is the parent of the `_rels` directory holding the relationship file.
+fn resolve_target(rels_path: &str, target: &str) -> String {
+    resolve_part_target(&rels_source_part(rels_path), target)
+}
+
+fn relationships_xml(rels: &[Relationship]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(
+        "<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\n",
+    );
+    for rel in rels {
+        xml.push_str(&format!(
+            "  <Relationship Target=\"{}\" Id=\"{}\" Type=\"{}\"",
+            rel.target, rel.id, rel.rel_type
+        ));
+        if rel.target_mode != "Internal" {
+            xml.push_str(&format!(" TargetMode=\"{}\"", rel.target_mode));
+        }
+        xml.push_str(" />\n");
+    }
+    xml.push_str("</Relationships>");
+    xml
+}
+
+fn content_types_xml(types: &[ContentType]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(
+        "<Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\n",
+    );
+    for t in types {
+        match t {
+            ContentType::Default {
+                extension,
+                content_type,
+            } => xml.push_str(&format!(
+                "  <Default Extension=\"{}\" ContentType=\"{}\" />\n",
+                extension, content_type
+            )),
+            ContentType::Override {
+                part_name,
+                content_type,
+            } => xml.push_str(&format!(
+                "  <Override PartName=\"{}\" ContentType=\"{}\" />\n",
+                part_name, content_type
+            )),
+        }
+    }
+    xml.push_str("</Types>");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_target() {
+        assert_eq!(
+            resolve_target("_rels/.rels", "/3D/3dmodel.model"),
+            "3D/3dmodel.model"
+        );
+        assert_eq!(
+            resolve_target("_rels/.rels", "Metadata/sig.xml"),
+            "Metadata/sig.xml"
+        );
+        assert_eq!(
+            resolve_target(
+                "package/services/digital-signature/_rels/origin.psdsor.rels",
+                "xml-signature/a.psdsxs"
+            ),
+            "package/services/digital-signature/xml-signature/a.psdsxs"
+        );
+        assert_eq!(
+            resolve_target("3D/_rels/3dmodel.model.rels", "../Secure/keystore.xml"),
+            "Secure/keystore.xml"
+        );
+        assert_eq!(
+            rels_source("3D/_rels/3dmodel.model.rels").as_deref(),
+            Some("3D/3dmodel.model")
+        );
+        assert_eq!(rels_source("_rels/.rels"), None);
+    }
+}
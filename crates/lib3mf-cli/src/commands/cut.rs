@@ -0,0 +1,106 @@
+use crate::commands::copy::ProtectedParts;
+use crate::commands::hollow::LengthArg;
+use crate::commands::merge::load_full;
+use crate::commands::open_archive;
+use anyhow::Result;
+use lib3mf_core::model::{CutOptions, Geometry, PinOptions, Plane, ResourceId, Side};
+use std::fs::File;
+use std::path::PathBuf;
+
+/// Alignment pin settings for the `cut` subcommand, with lengths as given on the command
+/// line.
+#[derive(Debug, Clone)]
+pub struct PinArgs {
+    /// Pins per cut face.
+    pub count: usize,
+    /// Peg diameter.
+    pub diameter: LengthArg,
+    /// Peg height above the cut.
+    pub length: LengthArg,
+    /// Gap between peg and socket.
+    pub clearance: LengthArg,
+}
+
+/// Entry point for the `cut` subcommand.
+///
+/// Cuts the objects of every build item in `input` (or those of `object_ids`) along
+/// `plane`, given in build coordinates and model units, using
+/// [`lib3mf_core::model::Model::cut_with`], then writes the result to `output`.
+///
+/// Rewriting the geometry invalidates digital signatures, so they are removed with a
+/// warning. Packages with encrypted parts are rejected.
+pub fn run(
+    input: PathBuf,
+    output: PathBuf,
+    plane: Plane,
+    keep: Side,
+    pins: Option<&PinArgs>,
+    object_ids: &[u32],
+) -> Result<()> {
+    let protected = ProtectedParts::scan(&mut open_archive(&input)?)?;
+    if !protected.encrypted.is_empty() {
+        anyhow::bail!(
+            "Cannot cut {:?}: it contains {} encrypted part(s)",
+            input,
+            protected.encrypted.len()
+        );
+    }
+
+    let mut model = load_full(&input)?;
+    for &id in object_ids {
+        match model.resources.get_object(ResourceId(id)) {
+            Some(object) if matches!(object.geometry, Geometry::Mesh(_)) => {}
+            Some(_) => anyhow::bail!("Object {} is not a mesh object", id),
+            None => anyhow::bail!("Object {} not found", id),
+        }
+    }
+
+    let unit = model.unit;
+    let options = CutOptions {
+        keep,
+        pins: pins.map(|pins| PinOptions {
+            count: pins.count,
+            diameter: pins.diameter.in_units(unit),
+            length: pins.length.in_units(unit),
+            clearance: pins.clearance.in_units(unit),
+            ..Default::default()
+        }),
+        objects: object_ids.iter().map(|&id| ResourceId(id)).collect(),
+    };
+    let report = model
+        .cut_with(&plane, &options)
+        .map_err(|e| anyhow::anyhow!("Failed to cut: {}", e))?;
+    if report.items_cut == 0 {
+        anyhow::bail!("The plane does not pass through any object in {:?}", input);
+    }
+    println!(
+        "Cut {} build item(s), keeping {}: {} new object(s), {} item(s) removed",
+        report.items_cut,
+        keep,
+        report.new_objects.len(),
+        report.items_removed
+    );
+    if pins.is_some() {
+        if report.pins == 0 {
+            eprintln!("Warning: no cut face has room for an alignment pin");
+        } else {
+            println!("Added {} alignment pin(s)", report.pins);
+        }
+    }
+
+    if !protected.signatures.is_empty() {
+        eprintln!(
+            "Warning: removing {} digital signature(s) invalidated by cutting",
+            protected.signatures.len()
+        );
+    }
+    protected.remove_signatures(&mut model);
+
+    let file = File::create(&output)
+        .map_err(|e| anyhow::anyhow!("Failed to create output file: {}", e))?;
+    model
+        .write(file)
+        .map_err(|e| anyhow::anyhow!("Failed to write 3MF: {}", e))?;
+    println!("Written to {:?}", output);
+    Ok(())
+}
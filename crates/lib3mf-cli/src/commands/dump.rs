@@ -0,0 +1,214 @@
+//! Dump command — print the parsed model, or a part of it, for debugging.
+//!
+//! Without filters the whole model is printed, which is unusable for large files. A
+//! section or a single resource can be selected, mesh data left out and nesting cut off,
+//! and `--ndjson` prints one resource (or build item, or metadata entry) per line for
+//! piping into tools like `jq`.
+
+use crate::commands::{OutputFormat, locate_model_part};
+use anyhow::{Context, bail};
+use clap::ValueEnum;
+use lib3mf_core::archive::ArchiveReader;
+use lib3mf_core::model::{Model, ResourceId};
+use lib3mf_core::parser::parse_model;
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+/// Part of the model to dump.
+#[derive(Clone, Copy, ValueEnum, Debug, PartialEq, Eq)]
+pub enum DumpSection {
+    /// Every resource (objects, materials, textures and extension resources)
+    Resources,
+    /// The build items
+    Build,
+    /// Model-level metadata entries
+    Metadata,
+}
+
+/// What to dump and how.
+#[derive(Debug, Clone)]
+pub struct DumpOptions {
+    /// Output format (text or json). Text is the Rust debug view.
+    pub format: OutputFormat,
+    /// Dump only this section.
+    pub section: Option<DumpSection>,
+    /// Dump only the resource with this ID.
+    pub object_id: Option<u32>,
+    /// Show this many levels of nesting; deeper objects and arrays are summarized.
+    pub max_depth: Option<usize>,
+    /// Replace vertex, triangle, beam and other mesh arrays with their lengths.
+    pub no_geometry: bool,
+    /// Print one compact JSON object per line: resources unless another section is chosen.
+    pub ndjson: bool,
+}
+
+impl Default for DumpOptions {
+    fn default() -> Self {
+        Self {
+            format: OutputFormat::Text,
+            section: None,
+            object_id: None,
+            max_depth: None,
+            no_geometry: false,
+            ndjson: false,
+        }
+    }
+}
+
+/// Arrays that hold mesh data, dropped by `--no-geometry`.
+const GEOMETRY_ARRAYS: &[&str] = &[
+    "vertices",
+    "triangles",
+    "beams",
+    "balls",
+    "normals",
+    "gradients",
+    "polygons",
+];
+
+/// Parses the model part of `path` and prints it as selected by `options`.
+pub fn run(path: PathBuf, options: &DumpOptions) -> anyhow::Result<()> {
+    let mut archiver = crate::commands::open_archive(&path)?;
+    let model_path = locate_model_part(&mut archiver)
+        .map_err(|e| anyhow::anyhow!("Failed to find model path: {}", e))?;
+    let model_data = archiver
+        .read_entry(&model_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read model data: {}", e))?;
+    let model = parse_model(std::io::Cursor::new(model_data))
+        .map_err(|e| anyhow::anyhow!("Failed to parse model XML: {}", e))?;
+
+    print!("{}", render(&model, options)?);
+    Ok(())
+}
+
+/// Renders the dump of `model` selected by `options`, ending with a newline.
+///
+/// The text format prints the Rust debug view of the selection; since `max_depth` and
+/// `no_geometry` work on the JSON view, setting either switches to JSON.
+///
+/// # Errors
+///
+/// Returns an error if `object_id` names no resource.
+pub fn render(model: &Model, options: &DumpOptions) -> anyhow::Result<String> {
+    let mut out = String::new();
+
+    if options.ndjson {
+        let lines: Vec<Value> = match (options.section, options.object_id) {
+            (_, Some(id)) => {
+                let resource = get_resource(model, id)?;
+                vec![resource_line(resource, options)?]
+            }
+            (None | Some(DumpSection::Resources), None) => model
+                .resources
+                .iter_all()
+                .map(|resource| resource_line(resource, options))
+                .collect::<anyhow::Result<_>>()?,
+            (Some(DumpSection::Build), None) => model
+                .build
+                .items
+                .iter()
+                .map(|item| to_value(item, options))
+                .collect::<anyhow::Result<_>>()?,
+            (Some(DumpSection::Metadata), None) => model
+                .metadata
+                .iter()
+                .map(|entry| to_value(entry, options))
+                .collect::<anyhow::Result<_>>()?,
+        };
+        for line in lines {
+            writeln!(out, "{}", serde_json::to_string(&line)?)?;
+        }
+        return Ok(out);
+    }
+
+    let json =
+        options.format == OutputFormat::Json || options.max_depth.is_some() || options.no_geometry;
+    let rendered = match (options.section, options.object_id) {
+        (_, Some(id)) => render_one(&get_resource(model, id)?, json, options)?,
+        (Some(DumpSection::Resources), None) => render_one(&model.resources, json, options)?,
+        (Some(DumpSection::Build), None) => render_one(&model.build, json, options)?,
+        (Some(DumpSection::Metadata), None) => render_one(&model.metadata, json, options)?,
+        (None, None) => render_one(model, json, options)?,
+    };
+    writeln!(out, "{}", rendered)?;
+    Ok(out)
+}
+
+fn get_resource(model: &Model, id: u32) -> anyhow::Result<lib3mf_core::model::ResourceRef<'_>> {
+    match model.resources.get(ResourceId(id)) {
+        Some(resource) => Ok(resource),
+        None => bail!("No resource with ID {}", id),
+    }
+}
+
+/// Renders one selection as pretty JSON, or as its debug view.
+fn render_one<T: Serialize + std::fmt::Debug>(
+    value: &T,
+    json: bool,
+    options: &DumpOptions,
+) -> anyhow::Result<String> {
+    if json {
+        Ok(serde_json::to_string_pretty(&to_value(value, options)?)?)
+    } else {
+        Ok(format!("{:#?}", value))
+    }
+}
+
+/// An NDJSON line for a resource: its ID and kind, and the resource itself.
+fn resource_line(
+    resource: lib3mf_core::model::ResourceRef<'_>,
+    options: &DumpOptions,
+) -> anyhow::Result<Value> {
+    Ok(json!({
+        "id": resource.id().0,
+        "kind": resource.kind().name(),
+        "resource": to_value(&resource, options)?,
+    }))
+}
+
+/// Serializes `value` and applies the `no_geometry` and `max_depth` filters.
+fn to_value<T: Serialize>(value: &T, options: &DumpOptions) -> anyhow::Result<Value> {
+    let mut value = serde_json::to_value(value).context("Failed to serialize model")?;
+    if options.no_geometry {
+        strip_geometry(&mut value);
+    }
+    if let Some(depth) = options.max_depth {
+        truncate(&mut value, depth);
+    }
+    Ok(value)
+}
+
+/// Replaces mesh data arrays with their lengths.
+fn strip_geometry(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                match child {
+                    Value::Array(items) if GEOMETRY_ARRAYS.contains(&key.as_str()) => {
+                        *child = Value::String(format!("[{} items]", items.len()));
+                    }
+                    _ => strip_geometry(child),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_geometry),
+        _ => {}
+    }
+}
+
+/// Replaces objects and arrays more than `depth` levels down with a summary.
+fn truncate(value: &mut Value, depth: usize) {
+    match value {
+        Value::Object(map) if depth == 0 => {
+            *value = Value::String(format!("{{{} fields}}", map.len()));
+        }
+        Value::Array(items) if depth == 0 => {
+            *value = Value::String(format!("[{} items]", items.len()));
+        }
+        Value::Object(map) => map.values_mut().for_each(|v| truncate(v, depth - 1)),
+        Value::Array(items) => items.iter_mut().for_each(|v| truncate(v, depth - 1)),
+        _ => {}
+    }
+}
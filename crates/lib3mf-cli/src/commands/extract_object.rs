@@ -0,0 +1,78 @@
+use crate::commands::merge::{check_secure_content, load_full};
+use anyhow::Result;
+use lib3mf_core::archive::ZipArchiver;
+use lib3mf_core::model::ResourceId;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+
+/// Entry point for the `extract-object` subcommand.
+///
+/// Writes object `id` of `input` and everything it references to `output`. By default the
+/// model is loaded and the output gets compact renumbered IDs, like `split`. With
+/// `streaming`, the needed resources are copied from the XML without loading the model,
+/// keeping their original IDs.
+pub fn run(input: PathBuf, id: u32, output: PathBuf, streaming: bool) -> Result<()> {
+    let id = ResourceId(id);
+
+    if streaming {
+        let file = File::open(&input)
+            .map_err(|e| anyhow::anyhow!("Failed to open file {:?}: {}", input, e))?;
+        let mut archive = ZipArchiver::new(file)
+            .map_err(|e| anyhow::anyhow!("Failed to open archive {:?}: {}", input, e))?;
+        let out = File::create(&output)
+            .map_err(|e| anyhow::anyhow!("Failed to create output file {:?}: {}", output, e))?;
+        let summary =
+            lib3mf_core::parser::extract::extract_object(&mut archive, id, BufWriter::new(out))
+                .map_err(|e| anyhow::anyhow!("Failed to extract object {}: {}", id.0, e))?;
+
+        println!(
+            "Extracted object {} from {} -> {} ({} resource{} in {} part{}, {} attachment{})",
+            id.0,
+            input.display(),
+            output.display(),
+            summary.resource_count(),
+            if summary.resource_count() == 1 {
+                ""
+            } else {
+                "s"
+            },
+            summary.parts.len(),
+            if summary.parts.len() == 1 { "" } else { "s" },
+            summary.attachments.len(),
+            if summary.attachments.len() == 1 {
+                ""
+            } else {
+                "s"
+            },
+        );
+        return Ok(());
+    }
+
+    let source = load_full(&input)?;
+    check_secure_content(&source).map_err(|_| {
+        anyhow::anyhow!(
+            "Cannot extract from signed/encrypted 3MF files. Strip signatures first using the decrypt command."
+        )
+    })?;
+    let model = crate::commands::split::extract_object(&source, id)?;
+    let out = File::create(&output)
+        .map_err(|e| anyhow::anyhow!("Failed to create output file {:?}: {}", output, e))?;
+    model
+        .write(BufWriter::new(out))
+        .map_err(|e| anyhow::anyhow!("Failed to write {:?}: {}", output, e))?;
+
+    println!(
+        "Extracted object {} from {} -> {} ({} object{})",
+        id.0,
+        input.display(),
+        output.display(),
+        model.resources.iter_objects().count(),
+        if model.resources.iter_objects().count() == 1 {
+            ""
+        } else {
+            "s"
+        },
+    );
+    Ok(())
+}
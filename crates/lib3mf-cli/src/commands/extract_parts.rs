@@ -0,0 +1,160 @@
+//! Bulk extraction of archive parts by kind or content type.
+//!
+//! `extract` copies one part by its internal path. The options here select parts without
+//! knowing those paths: every texture the model parts reference, every 3D model part (a
+//! package using the Production extension may have several), or every part with a given
+//! content type. Selected parts keep their package paths below the output directory.
+
+use crate::commands::open_archive;
+use anyhow::{Context, bail};
+use lib3mf_core::archive::opc::{ContentType, content_type_of, parse_content_types};
+use lib3mf_core::archive::{ArchiveReader, normalize_part_name, part_names_match};
+use lib3mf_core::parser::parse_model;
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+
+/// Content type of 3D model parts.
+const MODEL_CONTENT_TYPE: &str = "application/vnd.ms-package.3dmanufacturing-3dmodel+xml";
+
+/// Which parts to extract, and where to.
+#[derive(Debug, Clone, Default)]
+pub struct PartSelection {
+    /// Extract every texture referenced by a model part into this directory.
+    pub textures: Option<PathBuf>,
+    /// Extract every 3D model part into this directory.
+    pub models: Option<PathBuf>,
+    /// Only extract parts with one of these content types. Without `textures` or `models`,
+    /// every part with one of them is extracted into `output`.
+    pub content_types: Vec<String>,
+    /// Directory for parts selected by content type alone.
+    pub output: Option<PathBuf>,
+}
+
+/// Extracts the parts selected by `selection` from the package at `path`, printing each
+/// part written.
+///
+/// # Errors
+///
+/// Returns an error if nothing is selected, a part cannot be read or written, or a part
+/// name would leave the output directory. Referenced textures missing from the package
+/// are skipped with a warning.
+pub fn run(path: PathBuf, selection: &PartSelection) -> anyhow::Result<()> {
+    let mut archiver = open_archive(&path)?;
+    let plan = plan(&mut archiver, selection)?;
+    for (part, dest) in &plan {
+        let data = archiver
+            .read_entry(part)
+            .map_err(|e| anyhow::anyhow!("Failed to read entry '{}': {}", part, e))?;
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(dest, data).with_context(|| format!("Failed to write {:?}", dest))?;
+        println!("Extracted '{}' to {:?}", part, dest);
+    }
+    println!("Extracted {} parts", plan.len());
+    Ok(())
+}
+
+/// Lists the parts selected by `selection` with the files they are written to, in package
+/// path order for each directory.
+pub fn plan<A: ArchiveReader>(
+    archiver: &mut A,
+    selection: &PartSelection,
+) -> anyhow::Result<Vec<(String, PathBuf)>> {
+    let types = match archiver.read_entry("[Content_Types].xml") {
+        Ok(data) => parse_content_types(&data)?,
+        Err(_) => Vec::new(),
+    };
+    let entries = archiver.list_entries()?;
+    let type_matches = |part: &str| {
+        selection.content_types.is_empty()
+            || content_type_of(&types, part).is_some_and(|t| {
+                selection
+                    .content_types
+                    .iter()
+                    .any(|wanted| wanted.eq_ignore_ascii_case(t))
+            })
+    };
+
+    let mut groups: Vec<(BTreeSet<String>, &Path)> = Vec::new();
+    let models: BTreeSet<String> = entries
+        .iter()
+        .filter(|e| is_model_part(&types, e))
+        .cloned()
+        .collect();
+    if let Some(dir) = &selection.textures {
+        let mut textures = BTreeSet::new();
+        for part in &models {
+            let model = parse_model(std::io::Cursor::new(archiver.read_entry(part)?))
+                .map_err(|e| anyhow::anyhow!("Failed to parse '{}': {}", part, e))?;
+            let resources = &model.resources;
+            let paths = resources
+                .iter_texture_2d()
+                .map(|t| t.path.as_str())
+                .chain(resources.iter_displacement_2d().map(|d| d.path.as_str()));
+            textures.extend(paths.map(normalize_part_name));
+        }
+        groups.push((textures, dir));
+    }
+    if let Some(dir) = &selection.models {
+        groups.push((models, dir));
+    }
+    if groups.is_empty() {
+        if selection.content_types.is_empty() {
+            bail!("Nothing selected: use --all-textures, --all-models or --content-type");
+        }
+        let Some(dir) = &selection.output else {
+            bail!("--content-type needs --output <DIR>, --all-textures or --all-models");
+        };
+        let all = entries
+            .iter()
+            .filter(|e| *e != "[Content_Types].xml")
+            .cloned()
+            .collect();
+        groups.push((all, dir));
+    }
+
+    let mut plan = Vec::new();
+    for (parts, dir) in groups {
+        for part in parts {
+            // References may differ from the entry name in case, escapes or `..` segments
+            let Some(entry) = entries.iter().find(|e| part_names_match(e, &part)) else {
+                eprintln!(
+                    "Warning: referenced part '{}' is missing from the package",
+                    part
+                );
+                continue;
+            };
+            if !type_matches(entry) {
+                continue;
+            }
+            let dest = destination(dir, entry)?;
+            plan.push((entry.clone(), dest));
+        }
+    }
+    Ok(plan)
+}
+
+/// Whether an entry is a 3D model part, by content type or else by extension.
+fn is_model_part(types: &[ContentType], entry: &str) -> bool {
+    match content_type_of(types, entry) {
+        Some(t) => t.eq_ignore_ascii_case(MODEL_CONTENT_TYPE),
+        None => entry.ends_with(".model"),
+    }
+}
+
+/// Maps a part name below `dir`, refusing names that would leave it.
+fn destination(dir: &Path, part: &str) -> anyhow::Result<PathBuf> {
+    let name = normalize_part_name(part);
+    let relative = Path::new(&name);
+    if !relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)))
+    {
+        bail!(
+            "Refusing to extract '{}' outside the output directory",
+            part
+        );
+    }
+    Ok(dir.join(relative))
+}
@@ -0,0 +1,45 @@
+use crate::commands::OutputFormat;
+use crate::commands::merge::load_full;
+use anyhow::Result;
+use std::path::PathBuf;
+
+/// Entry point for the `hash` subcommand.
+///
+/// Prints [`Model::content_hash`](lib3mf_core::model::Model::content_hash) for each file,
+/// one `<digest>  <file>` line per file like `sha256sum`. With more than one file, also
+/// reports whether they all describe the same content.
+pub fn run(files: &[PathBuf], format: OutputFormat) -> Result<()> {
+    let mut hashes = Vec::with_capacity(files.len());
+    for file in files {
+        let model = load_full(file)?;
+        hashes.push((file, model.content_hash()));
+    }
+
+    match format {
+        OutputFormat::Json => {
+            let entries: Vec<_> = hashes
+                .iter()
+                .map(|(file, hash)| {
+                    serde_json::json!({
+                        "file": file,
+                        "content_hash": hash.to_hex(),
+                    })
+                })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+        }
+        _ => {
+            for (file, hash) in &hashes {
+                println!("{}  {}", hash, file.display());
+            }
+            if hashes.len() > 1 {
+                if hashes.iter().all(|(_, h)| *h == hashes[0].1) {
+                    println!("All {} files have the same content.", hashes.len());
+                } else {
+                    println!("Files differ.");
+                }
+            }
+        }
+    }
+    Ok(())
+}
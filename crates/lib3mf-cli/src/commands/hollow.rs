@@ -0,0 +1,151 @@
+use crate::commands::copy::ProtectedParts;
+use crate::commands::merge::load_full;
+use crate::commands::open_archive;
+use anyhow::Result;
+use lib3mf_core::model::{
+    DrainHole, Geometry, ObjectType, OffsetOptions, ResourceId, ShellOptions, Unit,
+};
+use std::fs::File;
+use std::path::PathBuf;
+
+/// A length given on the command line, such as `2`, `3mm` or `0.1in`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LengthArg {
+    /// Magnitude, in `unit` or in model units when there is no suffix.
+    pub value: f64,
+    /// Unit from the suffix, if any.
+    pub unit: Option<Unit>,
+}
+
+impl LengthArg {
+    /// The length in `model_unit`, the unit of the model it applies to.
+    pub fn in_units(&self, model_unit: Unit) -> f32 {
+        match self.unit {
+            Some(unit) => unit.convert(self.value, model_unit) as f32,
+            None => self.value as f32,
+        }
+    }
+}
+
+/// Parses a positive length with an optional `um`, `mm`, `cm`, `in`, `ft` or `m` suffix.
+pub fn parse_length(s: &str) -> std::result::Result<LengthArg, String> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| c.is_alphabetic() || c == 'µ')
+        .unwrap_or(s.len());
+    let (number, suffix) = s.split_at(split);
+    let value = match number.trim().parse::<f64>() {
+        Ok(v) if v > 0.0 && v.is_finite() => v,
+        _ => return Err(format!("invalid length '{}'", s)),
+    };
+    let unit = match suffix {
+        "" => None,
+        "um" | "µm" => Some(Unit::Micron),
+        "mm" => Some(Unit::Millimeter),
+        "cm" => Some(Unit::Centimeter),
+        "in" => Some(Unit::Inch),
+        "ft" => Some(Unit::Foot),
+        "m" => Some(Unit::Meter),
+        other => return Err(format!("unknown unit '{}' in '{}'", other, s)),
+    };
+    Ok(LengthArg { value, unit })
+}
+
+/// Entry point for the `hollow` subcommand.
+///
+/// Replaces the mesh of every model object in `input` (or of the objects in `object_ids`)
+/// with a shell of `thickness` from [`lib3mf_core::model::Mesh::shell_with`], adding a
+/// drain hole through the bottom of each cavity when `drain_hole` is given, and writes the
+/// result to `output`. Support, surface and other non-model objects are left alone unless
+/// selected explicitly.
+///
+/// Rewriting the geometry invalidates digital signatures, so they are removed with a
+/// warning. Packages with encrypted parts are rejected.
+pub fn run(
+    input: PathBuf,
+    output: PathBuf,
+    thickness: LengthArg,
+    drain_hole: Option<LengthArg>,
+    voxel_size: Option<LengthArg>,
+    object_ids: &[u32],
+) -> Result<()> {
+    let protected = ProtectedParts::scan(&mut open_archive(&input)?)?;
+    if !protected.encrypted.is_empty() {
+        anyhow::bail!(
+            "Cannot hollow {:?}: it contains {} encrypted part(s)",
+            input,
+            protected.encrypted.len()
+        );
+    }
+
+    let mut model = load_full(&input)?;
+    for &id in object_ids {
+        match model.resources.get_object(ResourceId(id)) {
+            Some(object) if matches!(object.geometry, Geometry::Mesh(_)) => {}
+            Some(_) => anyhow::bail!("Object {} is not a mesh object", id),
+            None => anyhow::bail!("Object {} not found", id),
+        }
+    }
+
+    let unit = model.unit;
+    let options = ShellOptions {
+        thickness: thickness.in_units(unit),
+        drain_holes: drain_hole
+            .map(|d| DrainHole::new(d.in_units(unit)))
+            .into_iter()
+            .collect(),
+        grid: OffsetOptions {
+            voxel_size: voxel_size.map(|v| v.in_units(unit)),
+            ..Default::default()
+        },
+    };
+
+    let mut hollowed = 0;
+    for object in model.resources.iter_objects_mut() {
+        let selected = if object_ids.is_empty() {
+            object.object_type == ObjectType::Model
+        } else {
+            object_ids.contains(&object.id.0)
+        };
+        let Geometry::Mesh(mesh) = &mut object.geometry else {
+            continue;
+        };
+        if !selected {
+            continue;
+        }
+        let before = mesh.compute_area_and_volume().1;
+        let shell = mesh
+            .shell_with(&options)
+            .map_err(|e| anyhow::anyhow!("Failed to hollow object {}: {}", object.id.0, e))?;
+        let after = shell.compute_area_and_volume().1;
+        println!(
+            "Hollowed object {}: volume {:.2} -> {:.2} {}³ ({} triangles)",
+            object.id.0,
+            before,
+            after,
+            unit.symbol(),
+            shell.triangles.len()
+        );
+        *mesh = shell;
+        hollowed += 1;
+    }
+    if hollowed == 0 {
+        anyhow::bail!("No mesh objects to hollow in {:?}", input);
+    }
+
+    if !protected.signatures.is_empty() {
+        eprintln!(
+            "Warning: removing {} digital signature(s) invalidated by hollowing",
+            protected.signatures.len()
+        );
+    }
+    protected.remove_signatures(&mut model);
+
+    let file = File::create(&output)
+        .map_err(|e| anyhow::anyhow!("Failed to create output file: {}", e))?;
+    model
+        .write(file)
+        .map_err(|e| anyhow::anyhow!("Failed to write 3MF: {}", e))?;
+    println!("Hollowed {} object(s) into {:?}", hollowed, output);
+    Ok(())
+}
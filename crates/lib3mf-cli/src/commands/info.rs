@@ -0,0 +1,314 @@
+use crate::commands::copy::ProtectedParts;
+use crate::commands::{OutputFormat, format_ratio};
+use anyhow::Result;
+use lib3mf_core::archive::{
+    ArchiveReader, EntryMetadata, ModelPartCandidate, find_model_paths, select_model_path,
+};
+use lib3mf_core::model::{CORE_NAMESPACE, CoreProperties, Extension, read_core_properties};
+use lib3mf_core::parser::{
+    ParseOptions, RequiredExtensionPolicy, parse_model, parse_model_header,
+    parse_model_with_options,
+};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+/// Package summary reported by the `info` subcommand.
+#[derive(Debug, Serialize)]
+pub struct PackageInfo {
+    /// Size of the file in bytes.
+    pub file_size: u64,
+    /// Path of the root model part.
+    pub model_path: String,
+    /// Targets of the package's 3D model relationships; the core spec allows one.
+    pub model_parts: Vec<ModelPartCandidate>,
+    /// Application that wrote the file, from the `Application` metadata.
+    pub generator: Option<String>,
+    /// Default namespace of the root model part.
+    pub core_namespace: Option<String>,
+    /// Extensions that are declared, required or used.
+    pub extensions: Vec<ExtensionInfo>,
+    /// Declared namespaces that are neither core nor a known extension, by prefix.
+    pub vendor_namespaces: BTreeMap<String, String>,
+    /// Violations of the `requiredextensions` rules.
+    pub issues: Vec<String>,
+    /// Observations that don't make the file invalid.
+    pub notes: Vec<String>,
+    /// Archive entries, largest first.
+    pub parts: Vec<EntryMetadata>,
+    /// Whether the package has digital signatures.
+    pub signed: bool,
+    /// Whether the package has Secure Content keystores or encrypted parts.
+    pub encrypted: bool,
+    /// Document properties from the package's core properties part.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub core_properties: Option<CoreProperties>,
+}
+
+/// How one extension appears in a package.
+#[derive(Debug, Serialize)]
+pub struct ExtensionInfo {
+    pub extension: Extension,
+    /// Specification version this tool implements.
+    pub spec_version: &'static str,
+    /// Namespace URI declared for the extension in the root model part.
+    pub namespace: Option<String>,
+    pub declared: bool,
+    pub required: bool,
+    pub used: bool,
+}
+
+/// Entry point for the `info` subcommand.
+pub fn run(file: PathBuf, format: OutputFormat) -> Result<()> {
+    let info = inspect(&file)?;
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&info)?),
+        _ => print_text(&file, &info),
+    }
+    Ok(())
+}
+
+/// Collects the [`PackageInfo`] of a 3MF file.
+///
+/// Declared namespaces and `requiredextensions` come from the root model part; extension
+/// use is gathered from every model part in the package.
+pub fn inspect(file: &Path) -> Result<PackageInfo> {
+    let file_size = std::fs::metadata(file)?.len();
+    let mut archiver = crate::commands::open_archive(&file.to_path_buf())?;
+    // Inspect packages with several model parts too, reporting them as an issue
+    let model_parts = find_model_paths(&mut archiver)?;
+    let model_path = select_model_path(&mut archiver, crate::commands::model_part())?;
+    let data = archiver.read_entry(&model_path)?;
+    let header = parse_model_header(Cursor::new(&data))?;
+    // Unsupported required extensions are reported below rather than failing the command
+    let options = ParseOptions {
+        unsupported_extensions: RequiredExtensionPolicy::Warn,
+        ..Default::default()
+    };
+    let model = parse_model_with_options(Cursor::new(&data), &options)?;
+
+    let mut used = model.used_extensions();
+    for entry in archiver.list_entries()? {
+        if entry.ends_with(".model") && entry.trim_start_matches('/') != model_path {
+            // Models spread over several parts are a Production extension feature
+            used.insert(Extension::Production);
+            if let Ok(part) = parse_model(Cursor::new(archiver.read_entry(&entry)?)) {
+                used.extend(part.used_extensions());
+            }
+        }
+    }
+
+    let mut issues = Vec::new();
+    let mut notes = Vec::new();
+
+    if model_parts.len() > 1 {
+        issues.push(format!(
+            "Package has {} 3D model relationships, the core spec allows one; showing {}",
+            model_parts.len(),
+            model_path
+        ));
+    }
+
+    let core_namespace = header.namespace("").map(str::to_string);
+    if core_namespace.as_deref() != Some(CORE_NAMESPACE) {
+        issues.push(format!(
+            "Default namespace is {}, expected the 3MF core namespace {}",
+            core_namespace.as_deref().unwrap_or("missing"),
+            CORE_NAMESPACE
+        ));
+    }
+
+    let mut required = BTreeSet::new();
+    for prefix in &header.required_extensions {
+        match header.namespace(prefix) {
+            None => issues.push(format!(
+                "requiredextensions lists '{}', but no namespace is declared for it",
+                prefix
+            )),
+            Some(uri) => match Extension::from_namespace(uri) {
+                Some(ext) => {
+                    required.insert(ext);
+                }
+                None => issues.push(format!(
+                    "Requires unsupported extension '{}' ({}); consumers without it must reject the file",
+                    prefix, uri
+                )),
+            },
+        }
+    }
+
+    let mut vendor_namespaces = BTreeMap::new();
+    let mut declared = BTreeMap::new();
+    for (prefix, uri) in &header.namespaces {
+        match Extension::from_namespace(uri) {
+            Some(ext) => {
+                if uri != ext.namespace() {
+                    notes.push(format!(
+                        "{} is declared with the older namespace {}",
+                        ext.name(),
+                        uri
+                    ));
+                }
+                declared.insert(ext, uri.clone());
+            }
+            None if !prefix.is_empty() => {
+                vendor_namespaces.insert(prefix.clone(), uri.clone());
+            }
+            None => {}
+        }
+    }
+
+    let mut extensions = Vec::new();
+    for ext in Extension::ALL {
+        let info = ExtensionInfo {
+            extension: ext,
+            spec_version: ext.spec_version(),
+            namespace: declared.get(&ext).cloned(),
+            declared: declared.contains_key(&ext),
+            required: required.contains(&ext),
+            used: used.contains(&ext),
+        };
+        if info.used && !info.declared {
+            issues.push(format!(
+                "{} features are used but its namespace is not declared",
+                ext.name()
+            ));
+        }
+        if info.used && !info.required {
+            notes.push(format!(
+                "{} is used but not required; consumers without it may ignore that content",
+                ext.name()
+            ));
+        }
+        if info.required && !info.used {
+            notes.push(format!(
+                "{} is required but not used; consumers without it reject the file needlessly",
+                ext.name()
+            ));
+        }
+        if info.declared || info.required || info.used {
+            extensions.push(info);
+        }
+    }
+
+    let core_properties = read_core_properties(&mut archiver).unwrap_or_else(|e| {
+        notes.push(format!("Core properties could not be read: {}", e));
+        None
+    });
+
+    let protected = ProtectedParts::scan(&mut archiver)?;
+
+    let mut parts = archiver.entries_metadata()?;
+    parts.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+
+    Ok(PackageInfo {
+        file_size,
+        model_path,
+        model_parts,
+        generator: model.metadata.get("Application").map(str::to_string),
+        core_namespace,
+        extensions,
+        vendor_namespaces,
+        issues,
+        notes,
+        parts,
+        signed: !protected.signatures.is_empty(),
+        encrypted: !protected.encrypted.is_empty(),
+        core_properties,
+    })
+}
+
+fn print_text(file: &Path, info: &PackageInfo) {
+    let yes_no = |b: bool| if b { "yes" } else { "no" };
+
+    println!("File:        {} ({} bytes)", file.display(), info.file_size);
+    println!("Model part:  {}", info.model_path);
+    if info.model_parts.len() > 1 {
+        for candidate in &info.model_parts {
+            println!(
+                "             {} ({}{})",
+                candidate.path,
+                candidate.relationship_id,
+                if candidate.exists { "" } else { ", missing" }
+            );
+        }
+    }
+    println!(
+        "Generator:   {}",
+        info.generator.as_deref().unwrap_or("unknown")
+    );
+    println!(
+        "Core:        {}",
+        info.core_namespace.as_deref().unwrap_or("missing")
+    );
+    println!("Signed:      {}", yes_no(info.signed));
+    println!("Encrypted:   {}", yes_no(info.encrypted));
+
+    if let Some(properties) = info.core_properties.as_ref().filter(|p| !p.is_empty()) {
+        println!("\nDocument properties:");
+        for (name, value) in properties.entries() {
+            let label = name.split_once(':').map_or(name, |(_, local)| local);
+            println!("  {:<16} {}", label, value);
+        }
+    }
+
+    println!("\nExtensions:");
+    if info.extensions.is_empty() {
+        println!("  None");
+    } else {
+        println!(
+            "  {:<26} {:<9} {:<9} {:<9} {:<5}",
+            "Extension", "Version", "Declared", "Required", "Used"
+        );
+        for ext in &info.extensions {
+            println!(
+                "  {:<26} {:<9} {:<9} {:<9} {:<5}",
+                ext.extension.name(),
+                ext.spec_version,
+                yes_no(ext.declared),
+                yes_no(ext.required),
+                yes_no(ext.used)
+            );
+        }
+    }
+    if !info.vendor_namespaces.is_empty() {
+        println!("\nVendor namespaces:");
+        for (prefix, uri) in &info.vendor_namespaces {
+            println!("  {:<16} {}", prefix, uri);
+        }
+    }
+
+    println!("\nRequired extensions compliance:");
+    if info.issues.is_empty() {
+        println!("  [OK] No issues");
+    }
+    for issue in &info.issues {
+        println!("  [ERROR] {}", issue);
+    }
+    for note in &info.notes {
+        println!("  [INFO] {}", note);
+    }
+
+    println!("\nParts:");
+    println!("  {:>12} {:>12} {:>6}  Name", "Size", "Compressed", "Ratio");
+    let (mut total, mut total_compressed) = (0, 0);
+    for part in &info.parts {
+        total += part.size;
+        total_compressed += part.compressed_size;
+        println!(
+            "  {:>12} {:>12} {:>6}  {}",
+            part.size,
+            part.compressed_size,
+            format_ratio(part.compressed_size, part.size),
+            part.name
+        );
+    }
+    println!(
+        "  {:>12} {:>12} {:>6}  Total ({} parts)",
+        total,
+        total_compressed,
+        format_ratio(total_compressed, total),
+        info.parts.len()
+    );
+}
@@ -0,0 +1,75 @@
+use anyhow::Result;
+use std::path::PathBuf;
+
+/// Key type generated by the `keygen` subcommand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum KeyType {
+    /// RSA, usable for signing and as an encryption recipient
+    #[default]
+    Rsa,
+    /// ECDSA on NIST P-256, signing only
+    EcdsaP256,
+    /// ECDSA on NIST P-384, signing only
+    EcdsaP384,
+    /// Ed25519, signing only
+    Ed25519,
+}
+
+/// Entry point for the `keygen` subcommand.
+///
+/// Writes a new PKCS#8 private key to `out` and, if `cert` is given, a self-signed
+/// certificate for it. Existing files are not overwritten.
+#[cfg(feature = "crypto")]
+pub fn run(
+    out: PathBuf,
+    cert: Option<PathBuf>,
+    key_type: KeyType,
+    bits: usize,
+    subject: &str,
+    days: u32,
+) -> Result<()> {
+    use lib3mf_core::crypto::keygen::{KeyAlgorithm, KeyPair};
+
+    for path in std::iter::once(&out).chain(cert.as_ref()) {
+        if path.exists() {
+            anyhow::bail!("{} already exists; refusing to overwrite", path.display());
+        }
+    }
+
+    let algorithm = match key_type {
+        KeyType::Rsa => KeyAlgorithm::Rsa(bits),
+        KeyType::EcdsaP256 => KeyAlgorithm::EcdsaP256,
+        KeyType::EcdsaP384 => KeyAlgorithm::EcdsaP384,
+        KeyType::Ed25519 => KeyAlgorithm::Ed25519,
+    };
+    let key = KeyPair::generate(algorithm)?;
+    std::fs::write(&out, key.to_pem()?)?;
+    println!("Wrote {:?} private key to {}", algorithm, out.display());
+
+    if let Some(cert) = cert {
+        std::fs::write(&cert, key.self_signed_certificate(subject, days)?)?;
+        println!(
+            "Wrote self-signed certificate for '{}' ({} days) to {}",
+            subject,
+            days,
+            cert.display()
+        );
+    }
+    Ok(())
+}
+
+/// Entry point for the `keygen` subcommand (crypto feature disabled).
+#[cfg(not(feature = "crypto"))]
+pub fn run(
+    _out: PathBuf,
+    _cert: Option<PathBuf>,
+    _key_type: KeyType,
+    _bits: usize,
+    _subject: &str,
+    _days: u32,
+) -> Result<()> {
+    anyhow::bail!(
+        "Key generation requires the 'crypto' feature to be enabled.\n\
+        The CLI was built without cryptographic support."
+    )
+}
@@ -0,0 +1,121 @@
+use crate::commands::copy::ProtectedParts;
+use crate::commands::hollow::LengthArg;
+use crate::commands::merge::load_full;
+use crate::commands::open_archive;
+use anyhow::Result;
+use lib3mf_core::model::{
+    Geometry, LatticeOptions, LatticePattern, ObjectType, OffsetOptions, ResourceId,
+};
+use std::fs::File;
+use std::path::PathBuf;
+
+/// Lattice settings for the `lattice-fill` subcommand, with lengths as given on the
+/// command line.
+#[derive(Debug, Clone)]
+pub struct LatticeArgs {
+    /// Beam arrangement.
+    pub pattern: LatticePattern,
+    /// Cell edge length.
+    pub cell_size: LengthArg,
+    /// Beam radius, unless `density` is set.
+    pub beam_radius: LengthArg,
+    /// Target fraction of the interior filled by beams.
+    pub density: Option<f32>,
+    /// Wall thickness to hollow to; `None` keeps objects solid.
+    pub shell: Option<LengthArg>,
+    /// Distance field voxel size.
+    pub voxel_size: Option<LengthArg>,
+}
+
+/// Entry point for the `lattice-fill` subcommand.
+///
+/// Hollows every model object in `input` (or the objects in `object_ids`) and fills it
+/// with a beam lattice using [`lib3mf_core::model::Mesh::lattice_fill`], then writes the
+/// result to `output`. Lengths without a unit are in the model's unit.
+///
+/// Rewriting the geometry invalidates digital signatures, so they are removed with a
+/// warning. Packages with encrypted parts are rejected.
+pub fn run(input: PathBuf, output: PathBuf, args: &LatticeArgs, object_ids: &[u32]) -> Result<()> {
+    let protected = ProtectedParts::scan(&mut open_archive(&input)?)?;
+    if !protected.encrypted.is_empty() {
+        anyhow::bail!(
+            "Cannot fill {:?}: it contains {} encrypted part(s)",
+            input,
+            protected.encrypted.len()
+        );
+    }
+
+    let mut model = load_full(&input)?;
+    for &id in object_ids {
+        match model.resources.get_object(ResourceId(id)) {
+            Some(object) if matches!(object.geometry, Geometry::Mesh(_)) => {}
+            Some(_) => anyhow::bail!("Object {} is not a mesh object", id),
+            None => anyhow::bail!("Object {} not found", id),
+        }
+    }
+
+    let unit = model.unit;
+    let options = LatticeOptions {
+        pattern: args.pattern,
+        cell_size: args.cell_size.in_units(unit),
+        beam_radius: args.beam_radius.in_units(unit),
+        density: args.density,
+        shell: args.shell.map(|s| s.in_units(unit)),
+        grid: OffsetOptions {
+            voxel_size: args.voxel_size.map(|v| v.in_units(unit)),
+            ..Default::default()
+        },
+    };
+
+    let mut filled = 0;
+    for object in model.resources.iter_objects_mut() {
+        let selected = if object_ids.is_empty() {
+            object.object_type == ObjectType::Model
+        } else {
+            object_ids.contains(&object.id.0)
+        };
+        let Geometry::Mesh(mesh) = &mut object.geometry else {
+            continue;
+        };
+        if !selected {
+            continue;
+        }
+        let before = mesh.compute_area_and_volume().1;
+        let result = mesh
+            .lattice_fill(&options)
+            .map_err(|e| anyhow::anyhow!("Failed to fill object {}: {}", object.id.0, e))?;
+        let beams = result.beam_lattice.as_ref().map_or(0, |l| l.beams.len());
+        println!(
+            "Filled object {}: {} {} beams of radius {:.3} {}, skin volume {:.2} -> {:.2} {}³",
+            object.id.0,
+            beams,
+            options.pattern,
+            options.effective_radius(),
+            unit.symbol(),
+            before,
+            result.compute_area_and_volume().1,
+            unit.symbol()
+        );
+        *mesh = result;
+        filled += 1;
+    }
+    if filled == 0 {
+        anyhow::bail!("No mesh objects to fill in {:?}", input);
+    }
+
+    if !protected.signatures.is_empty() {
+        eprintln!(
+            "Warning: removing {} digital signature(s) invalidated by lattice filling",
+            protected.signatures.len()
+        );
+    }
+    protected.remove_signatures(&mut model);
+
+    let file = File::create(&output)
+        .map_err(|e| anyhow::anyhow!("Failed to create output file: {}", e))?;
+    model
+        .write(file)
+        .map_err(|e| anyhow::anyhow!("Failed to write 3MF: {}", e))?;
+    println!("Filled {} object(s) into {:?}", filled, output);
+    Ok(())
+}
@@ -13,11 +13,11 @@
 //! 9. Apply placement (plate-per-file or single-plate)
 //! 10. Write merged model to output
 
+use crate::commands::printer::Printer;
 use glob::glob;
-use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path, opc};
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
 use lib3mf_core::model::{
-    BaseMaterialsGroup, ColorGroup, CompositeMaterials, Displacement2D, Geometry, Model,
-    MultiProperties, Object, ResourceId, SliceStack, Texture2D, Texture2DGroup, VolumetricStack,
+    Displacement2D, Geometry, Metadata, Model, Object, ResourceId, Texture2D, Texture2DGroup,
     stats::BoundingBox,
 };
 use lib3mf_core::parser::parse_model;
@@ -46,12 +46,16 @@ pub enum Verbosity {
 }
 
 /// Entry point for the merge command.
+///
+/// In single-plate mode with a `printer`, the arrangement is centered on its bed, with a
+/// warning if it does not fit.
 pub fn run(
     inputs: Vec<PathBuf>,
     output: PathBuf,
     force: bool,
     single_plate: bool,
     pack: PackAlgorithm,
+    printer: Option<&Printer>,
     verbosity: Verbosity,
 ) -> anyhow::Result<()> {
     // Step 1: Expand glob patterns
@@ -85,50 +89,23 @@ pub fn run(
     let mut total_objects = count_objects(&merged);
     let mut total_materials = count_materials(&merged);
 
-    for (file_index, mut source) in loaded.into_iter().enumerate() {
+    for (file_index, source) in loaded.into_iter().enumerate() {
         // file_index 0 = second input file (index 1 overall)
         let actual_file_index = file_index + 1;
 
-        // Step 4a: Compute ID offset (max resource ID in merged so far + 1)
-        let offset = max_resource_id(&merged) + 1;
-
         if matches!(verbosity, Verbosity::Verbose) {
             eprintln!(
                 "  Merging file {} with ID offset {}",
                 actual_file_index + 1,
-                offset
+                merged.resources.allocate_id().0
             );
         }
 
-        // Step 4b: Remap all IDs in source model
-        remap_model(&mut source, offset);
-
-        // Step 4c: Merge attachments with path deduplication
-        // Take attachments out of source first so we can still borrow source mutably after.
-        let src_attachments = std::mem::take(&mut source.attachments);
-        let path_remap =
-            merge_attachments(&mut merged.attachments, src_attachments, actual_file_index);
-
-        // Step 4d: Update texture/displacement paths after attachment remap
-        update_texture_paths(&mut source, &path_remap);
-
         // Count before transfer (resources will be moved)
         let src_objects = count_objects_resources(&source.resources);
         let src_materials = count_materials_resources(&source.resources);
 
-        // Step 4e: Transfer all resources from source into merged
-        transfer_resources(&mut merged, source.resources)?;
-
-        // Step 4f: Transfer build items
-        merged.build.items.extend(source.build.items);
-
-        // Step 4g: Merge metadata, relationships, namespaces
-        merge_metadata(&mut merged.metadata, &source.metadata);
-        merge_relationships(
-            &mut merged.existing_relationships,
-            source.existing_relationships,
-        );
-        merge_extra_namespaces(&mut merged.extra_namespaces, &source.extra_namespaces);
+        append_model(&mut merged, source, actual_file_index)?;
 
         total_objects += src_objects;
         total_materials += src_materials;
@@ -136,7 +113,7 @@ pub fn run(
 
     // Step 5: Apply placement
     if single_plate {
-        apply_single_plate_placement(&mut merged, pack, verbosity)?;
+        apply_single_plate_placement(&mut merged, pack, printer, verbosity)?;
     } else {
         check_build_item_overlaps(&merged, verbosity);
     }
@@ -175,6 +152,47 @@ pub fn run(
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// Append one model to the merged model
+// ---------------------------------------------------------------------------
+
+/// Moves the resources, build items, attachments and metadata of `source` into `merged`.
+///
+/// Resource IDs of `source` are shifted past those of `merged`, and attachments whose path
+/// is taken by different content are renamed with `file_index` (see [`merge_attachments`]).
+pub(crate) fn append_model(
+    merged: &mut Model,
+    mut source: Model,
+    file_index: usize,
+) -> anyhow::Result<()> {
+    // Compute ID offset (max resource ID in merged so far + 1)
+    let offset = merged.resources.allocate_id().0;
+
+    // Remap all IDs in source model
+    remap_model(&mut source, offset)?;
+
+    // Merge attachments with path deduplication
+    // Take attachments out of source first so we can still borrow source mutably after.
+    let src_attachments = std::mem::take(&mut source.attachments);
+    let path_remap = merge_attachments(&mut merged.attachments, src_attachments, file_index);
+
+    // Update texture/displacement paths after attachment remap
+    update_texture_paths(&mut source, &path_remap);
+
+    // Transfer all resources and build items from source into merged
+    transfer_resources(merged, source.resources)?;
+    merged.build.items.extend(source.build.items);
+
+    // Merge metadata, relationships, namespaces
+    merge_metadata(&mut merged.metadata, &source.metadata);
+    merge_relationships(
+        &mut merged.existing_relationships,
+        source.existing_relationships,
+    );
+    merge_extra_namespaces(&mut merged.extra_namespaces, &source.extra_namespaces);
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Resolve output path — auto-increment if exists and force=false
 // ---------------------------------------------------------------------------
@@ -422,9 +440,12 @@ fn aabbs_overlap(a: &BoundingBox, b: &BoundingBox) -> bool {
 // ---------------------------------------------------------------------------
 
 /// In single-plate mode, replace ALL build item transforms with grid-computed ones.
+///
+/// The grid starts at the origin, or is centered on `printer`'s bed if given.
 pub(crate) fn apply_single_plate_placement(
     model: &mut Model,
     _pack: PackAlgorithm,
+    printer: Option<&Printer>,
     verbosity: Verbosity,
 ) -> anyhow::Result<()> {
     const SPACING_MM: f32 = 10.0;
@@ -434,6 +455,7 @@ pub(crate) fn apply_single_plate_placement(
     struct ItemInfo {
         size_x: f32,
         size_y: f32,
+        size_z: f32,
     }
 
     let item_infos: Vec<ItemInfo> = model
@@ -450,10 +472,12 @@ pub(crate) fn apply_single_plate_placement(
                 Some(bb) => ItemInfo {
                     size_x: (bb.max[0] - bb.min[0]).max(0.0),
                     size_y: (bb.max[1] - bb.min[1]).max(0.0),
+                    size_z: (bb.max[2] - bb.min[2]).max(0.0),
                 },
                 None => ItemInfo {
                     size_x: 0.0,
                     size_y: 0.0,
+                    size_z: 0.0,
                 },
             }
         })
@@ -495,12 +519,34 @@ pub(crate) fn apply_single_plate_placement(
         y_offsets[r + 1] = y_offsets[r] + row_heights[r] + SPACING_MM;
     }
 
+    // Center the grid on the printer's bed, and warn if it does not fit there
+    let mut origin = [0.0_f32; 2];
+    if let Some(printer) = printer {
+        let width = x_offsets[cols] - SPACING_MM;
+        let depth = y_offsets[row_heights.len()] - SPACING_MM;
+        let height = item_infos.iter().map(|i| i.size_z).fold(0.0_f32, f32::max);
+        let bed = printer.bed();
+        origin = [
+            bed.min[0] + (printer.build_volume[0] - width) / 2.0,
+            bed.min[1] + (printer.build_volume[1] - depth) / 2.0,
+        ];
+        let arrangement = BoundingBox {
+            min: [origin[0], origin[1], 0.0],
+            max: [origin[0] + width, origin[1] + depth, height],
+        };
+        if !matches!(verbosity, Verbosity::Quiet) {
+            for problem in printer.oversize(&arrangement, "Warning: arrangement ") {
+                eprintln!("{}", problem);
+            }
+        }
+    }
+
     // Apply transforms: place each item at its grid cell's (x_offset, y_offset, 0)
     for (idx, item) in model.build.items.iter_mut().enumerate() {
         let col = idx % cols;
         let row = idx / cols;
-        let tx = x_offsets[col];
-        let ty = y_offsets[row];
+        let tx = origin[0] + x_offsets[col];
+        let ty = origin[1] + y_offsets[row];
         item.transform = glam::Mat4::from_translation(glam::Vec3::new(tx, ty, 0.0));
     }
 
@@ -533,34 +579,12 @@ pub(crate) fn load_full(path: &Path) -> anyhow::Result<Model> {
         .map_err(|e| anyhow::anyhow!("Failed to parse model XML from {:?}: {}", path, e))?;
 
     // Load all entries — preserve attachments and relationships, like the copy command.
-    let all_files = archiver
-        .list_entries()
-        .map_err(|e| anyhow::anyhow!("Failed to list archive entries in {:?}: {}", path, e))?;
-
-    for entry_path in all_files {
-        // Skip files that PackageWriter regenerates
-        if entry_path == model_path
-            || entry_path == "_rels/.rels"
-            || entry_path == "[Content_Types].xml"
-        {
-            continue;
-        }
-
-        // Load .rels files to preserve relationships
-        if entry_path.ends_with(".rels") {
-            if let Ok(data) = archiver.read_entry(&entry_path)
-                && let Ok(rels) = opc::parse_relationships(&data)
-            {
-                model.existing_relationships.insert(entry_path, rels);
-            }
-            continue;
-        }
-
-        // Load other data as attachments
-        if let Ok(data) = archiver.read_entry(&entry_path) {
-            model.attachments.insert(entry_path, data);
-        }
-    }
+    let core_properties = crate::commands::load_core_properties(&mut archiver, &mut model);
+    model
+        .load_attachments(&mut archiver, |entry| {
+            entry == model_path || Some(entry) == core_properties.as_deref()
+        })
+        .map_err(|e| anyhow::anyhow!("Failed to load attachments from {:?}: {}", path, e))?;
 
     Ok(model)
 }
@@ -579,254 +603,25 @@ pub(crate) fn check_secure_content(model: &Model) -> anyhow::Result<()> {
 }
 
 // ---------------------------------------------------------------------------
-// Internal helper: compute maximum ResourceId across all resource types
-// ---------------------------------------------------------------------------
-
-pub(crate) fn max_resource_id(model: &Model) -> u32 {
-    let r = &model.resources;
-    let mut max = 0u32;
-    for obj in r.iter_objects() {
-        max = max.max(obj.id.0);
-    }
-    for mat in r.iter_base_materials() {
-        max = max.max(mat.id.0);
-    }
-    for col in r.iter_color_groups() {
-        max = max.max(col.id.0);
-    }
-    for tex in r.iter_texture_2d() {
-        max = max.max(tex.id.0);
-    }
-    for grp in r.iter_textures() {
-        max = max.max(grp.id.0);
-    }
-    for comp in r.iter_composite_materials() {
-        max = max.max(comp.id.0);
-    }
-    for mp in r.iter_multi_properties() {
-        max = max.max(mp.id.0);
-    }
-    for ss in r.iter_slice_stacks() {
-        max = max.max(ss.id.0);
-    }
-    for vs in r.iter_volumetric_stacks() {
-        max = max.max(vs.id.0);
-    }
-    for d in r.iter_displacement_2d() {
-        max = max.max(d.id.0);
-    }
-    max
-}
-
-// ---------------------------------------------------------------------------
-// ID remap helpers
-// ---------------------------------------------------------------------------
-
-#[inline]
-fn remap_id(id: &mut ResourceId, offset: u32) {
-    id.0 += offset;
-}
-
-#[inline]
-fn remap_opt_id(id: &mut Option<ResourceId>, offset: u32) {
-    if let Some(inner) = id {
-        inner.0 += offset;
-    }
-}
-
-#[inline]
-fn remap_opt_u32_pid(pid: &mut Option<u32>, offset: u32) {
-    if let Some(p) = pid {
-        *p += offset;
-    }
-}
-
-// ---------------------------------------------------------------------------
-// Internal helper: remap all ResourceId cross-references in a model
+// Internal helper: shift all ResourceIds in a model
 // ---------------------------------------------------------------------------
 //
 // After calling this function, every resource in the model has IDs shifted
 // by `offset`, and every cross-reference is updated to match. This allows
 // models with overlapping ID namespaces to be merged without collisions.
 
-pub(crate) fn remap_model(model: &mut Model, offset: u32) {
+pub(crate) fn remap_model(model: &mut Model, offset: u32) -> anyhow::Result<()> {
     if offset == 0 {
-        return;
-    }
-
-    // --- Collect all resources out of the collection so we can mutate them ---
-    // Since ResourceCollection uses private HashMap fields, we collect resources
-    // via iterators (cloning), remap their IDs, and then rebuild the collection
-    // using add_* methods.
-
-    let old_resources = std::mem::take(&mut model.resources);
-
-    // Collect and remap objects
-    let mut objects: Vec<Object> = old_resources.iter_objects().cloned().collect();
-    for obj in &mut objects {
-        remap_id(&mut obj.id, offset);
-        remap_opt_id(&mut obj.pid, offset);
-        match &mut obj.geometry {
-            Geometry::Mesh(mesh) => {
-                for tri in &mut mesh.triangles {
-                    remap_opt_u32_pid(&mut tri.pid, offset);
-                }
-            }
-            Geometry::Components(comps) => {
-                for comp in &mut comps.components {
-                    remap_id(&mut comp.object_id, offset);
-                }
-            }
-            Geometry::BooleanShape(shape) => {
-                remap_id(&mut shape.base_object_id, offset);
-                for op in &mut shape.operations {
-                    remap_id(&mut op.object_id, offset);
-                }
-            }
-            Geometry::SliceStack(id) => {
-                remap_id(&mut *id, offset);
-            }
-            Geometry::VolumetricStack(id) => {
-                remap_id(&mut *id, offset);
-            }
-            Geometry::DisplacementMesh(dm) => {
-                for tri in &mut dm.triangles {
-                    remap_opt_u32_pid(&mut tri.pid, offset);
-                }
-            }
-        }
-    }
-
-    // Collect and remap base material groups
-    let mut base_materials: Vec<BaseMaterialsGroup> =
-        old_resources.iter_base_materials().cloned().collect();
-    for mat in &mut base_materials {
-        remap_id(&mut mat.id, offset);
-    }
-
-    // Collect and remap color groups
-    let mut color_groups: Vec<ColorGroup> = old_resources.iter_color_groups().cloned().collect();
-    for col in &mut color_groups {
-        remap_id(&mut col.id, offset);
-    }
-
-    // Collect and remap Texture2D resources
-    let mut texture_2d: Vec<Texture2D> = old_resources.iter_texture_2d().cloned().collect();
-    for tex in &mut texture_2d {
-        remap_id(&mut tex.id, offset);
-    }
-
-    // Collect and remap Texture2DGroup resources
-    let mut texture_2d_groups: Vec<Texture2DGroup> =
-        old_resources.iter_textures().cloned().collect();
-    for grp in &mut texture_2d_groups {
-        remap_id(&mut grp.id, offset);
-        remap_id(&mut grp.texture_id, offset);
-    }
-
-    // Collect and remap composite materials
-    let mut composite_materials: Vec<CompositeMaterials> =
-        old_resources.iter_composite_materials().cloned().collect();
-    for comp in &mut composite_materials {
-        remap_id(&mut comp.id, offset);
-        remap_id(&mut comp.base_material_id, offset);
-    }
-
-    // Collect and remap multi-properties
-    let mut multi_properties: Vec<MultiProperties> =
-        old_resources.iter_multi_properties().cloned().collect();
-    for mp in &mut multi_properties {
-        remap_id(&mut mp.id, offset);
-        for pid in &mut mp.pids {
-            remap_id(pid, offset);
-        }
-    }
-
-    // Collect and remap slice stacks
-    let mut slice_stacks: Vec<SliceStack> = old_resources.iter_slice_stacks().cloned().collect();
-    for ss in &mut slice_stacks {
-        remap_id(&mut ss.id, offset);
-    }
-
-    // Collect and remap volumetric stacks
-    let mut volumetric_stacks: Vec<VolumetricStack> =
-        old_resources.iter_volumetric_stacks().cloned().collect();
-    for vs in &mut volumetric_stacks {
-        remap_id(&mut vs.id, offset);
-    }
-
-    // Collect and remap Displacement2D resources
-    let mut displacement_2d: Vec<Displacement2D> =
-        old_resources.iter_displacement_2d().cloned().collect();
-    for d in &mut displacement_2d {
-        remap_id(&mut d.id, offset);
-    }
-
-    // Preserve key_store as-is (secure content check already done before remap)
-    let key_store = old_resources.key_store;
-
-    // Rebuild ResourceCollection with remapped resources
-    let mut new_resources = lib3mf_core::model::ResourceCollection::new();
-    for obj in objects {
-        new_resources
-            .add_object(obj)
-            .expect("Remapped IDs should not collide within the same model");
-    }
-    for mat in base_materials {
-        new_resources
-            .add_base_materials(mat)
-            .expect("Remapped IDs should not collide within the same model");
-    }
-    for col in color_groups {
-        new_resources
-            .add_color_group(col)
-            .expect("Remapped IDs should not collide within the same model");
-    }
-    for tex in texture_2d {
-        new_resources
-            .add_texture_2d(tex)
-            .expect("Remapped IDs should not collide within the same model");
-    }
-    for grp in texture_2d_groups {
-        new_resources
-            .add_texture_2d_group(grp)
-            .expect("Remapped IDs should not collide within the same model");
-    }
-    for comp in composite_materials {
-        new_resources
-            .add_composite_materials(comp)
-            .expect("Remapped IDs should not collide within the same model");
-    }
-    for mp in multi_properties {
-        new_resources
-            .add_multi_properties(mp)
-            .expect("Remapped IDs should not collide within the same model");
-    }
-    for ss in slice_stacks {
-        new_resources
-            .add_slice_stack(ss)
-            .expect("Remapped IDs should not collide within the same model");
-    }
-    for vs in volumetric_stacks {
-        new_resources
-            .add_volumetric_stack(vs)
-            .expect("Remapped IDs should not collide within the same model");
-    }
-    for d in displacement_2d {
-        new_resources
-            .add_displacement_2d(d)
-            .expect("Remapped IDs should not collide within the same model");
-    }
-    if let Some(ks) = key_store {
-        new_resources.set_key_store(ks);
-    }
-
-    model.resources = new_resources;
-
-    // Remap build items
-    for item in &mut model.build.items {
-        remap_id(&mut item.object_id, offset);
+        return Ok(());
     }
+    let mapping: HashMap<ResourceId, ResourceId> = model
+        .resources
+        .iter_ids()
+        .map(|id| (id, ResourceId(id.0 + offset)))
+        .collect();
+    model
+        .remap_ids(&mapping)
+        .map_err(|e| anyhow::anyhow!("Failed to remap resource IDs: {}", e))
 }
 
 // ---------------------------------------------------------------------------
@@ -872,18 +667,18 @@ pub(crate) fn merge_attachments(
 // Internal helper: merge metadata with semicolon concatenation
 // ---------------------------------------------------------------------------
 
-pub(crate) fn merge_metadata(
-    merged: &mut HashMap<String, String>,
-    source: &HashMap<String, String>,
-) {
-    for (key, value) in source {
-        merged
-            .entry(key.clone())
-            .and_modify(|existing| {
-                existing.push_str("; ");
-                existing.push_str(value);
-            })
-            .or_insert_with(|| value.clone());
+pub(crate) fn merge_metadata(merged: &mut Metadata, source: &Metadata) {
+    for entry in source {
+        match merged
+            .iter_mut()
+            .find(|e| e.name == entry.name && e.lang == entry.lang)
+        {
+            Some(existing) => {
+                existing.value.push_str("; ");
+                existing.value.push_str(&entry.value);
+            }
+            None => merged.push(entry.clone()),
+        }
     }
 }
 
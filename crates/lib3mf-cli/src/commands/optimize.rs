@@ -0,0 +1,352 @@
+use crate::commands::copy::ProtectedParts;
+use crate::commands::merge::load_full;
+use crate::commands::open_archive;
+use anyhow::Result;
+use lib3mf_core::model::{Model, Package, PackageFilter};
+use lib3mf_core::writer::package_writer::PackageWriter;
+use lib3mf_core::writer::{FloatPrecision, WriteOptions};
+use std::io::Cursor;
+use std::path::PathBuf;
+
+/// Deflate level the optimized package is compressed with.
+pub const COMPRESSION_LEVEL: i64 = 9;
+
+/// Settings of the `optimize` subcommand.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OptimizeOptions {
+    /// Downscale PNG images wider or taller than this many pixels to fit, keeping their
+    /// aspect ratio.
+    pub max_image_size: Option<u32>,
+    /// Round vertex coordinates to this many decimal places.
+    pub decimals: Option<u8>,
+}
+
+/// Bytes saved by one step of [`optimize`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Saving {
+    /// What the step optimizes, such as `unused resources`.
+    pub category: &'static str,
+    /// What the step changed.
+    pub detail: String,
+    /// Package size before the step minus after it; negative if the package grew.
+    pub bytes: i64,
+}
+
+/// Result of [`optimize`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptimizeReport {
+    /// Size of the input package in bytes.
+    pub original_size: u64,
+    /// Size of the optimized package in bytes.
+    pub optimized_size: u64,
+    /// Savings of each step, in the order they ran.
+    pub savings: Vec<Saving>,
+}
+
+/// How the package is written after the steps so far.
+#[derive(Default)]
+struct Encoding {
+    write_options: WriteOptions,
+    compression_level: Option<i64>,
+}
+
+impl Encoding {
+    fn write(&self, model: &Model) -> Result<Vec<u8>> {
+        let mut out = Cursor::new(Vec::new());
+        PackageWriter::new(&mut out)
+            .with_write_options(self.write_options.clone())
+            .with_compression_level(self.compression_level)
+            .write(&Package::new(model.clone()))
+            .map_err(|e| anyhow::anyhow!("Failed to write 3MF: {}", e))?;
+        Ok(out.into_inner())
+    }
+}
+
+/// Package written after the last step, and the savings recorded so far.
+struct Progress {
+    package: Vec<u8>,
+    size: u64,
+    savings: Vec<Saving>,
+}
+
+impl Progress {
+    /// Records a step, writing the package again unless the step changed nothing.
+    fn step(
+        &mut self,
+        category: &'static str,
+        detail: String,
+        changed: bool,
+        model: &Model,
+        encoding: &Encoding,
+    ) -> Result<()> {
+        if changed {
+            self.package = encoding.write(model)?;
+        }
+        let size = self.package.len() as u64;
+        self.savings.push(Saving {
+            category,
+            detail,
+            bytes: self.size as i64 - size as i64,
+        });
+        self.size = size;
+        Ok(())
+    }
+}
+
+/// Optimizes `model`, loaded from a package of `original_size` bytes, and returns the
+/// optimized package with a report of what each step saved.
+///
+/// The steps run in order, each measured by writing the package again:
+///
+/// 1. `repackaging`: writing the model as loaded, which drops unknown XML and the stats
+///    cache and normalizes formatting;
+/// 2. `unused resources`: removing resources no build item reaches, and attachments only
+///    they used;
+/// 3. `duplicates`: merging identical images, textures, property groups and objects;
+/// 4. `images`: re-encoding PNG attachments at maximum compression, keeping the result
+///    only if it is smaller, and downscaling those beyond
+///    [`max_image_size`](OptimizeOptions::max_image_size);
+/// 5. `coordinates`: rounding vertex coordinates to
+///    [`decimals`](OptimizeOptions::decimals) places, if set;
+/// 6. `compression`: compressing every entry at [`COMPRESSION_LEVEL`].
+///
+/// Attachments are written in path order, so the same input always gives the same bytes.
+pub fn optimize(
+    model: &mut Model,
+    original_size: u64,
+    options: OptimizeOptions,
+) -> Result<(Vec<u8>, OptimizeReport)> {
+    let mut encoding = Encoding::default();
+    let mut progress = Progress {
+        package: Vec::new(),
+        size: original_size,
+        savings: Vec::new(),
+    };
+    progress.step(
+        "repackaging",
+        "written as loaded".to_string(),
+        true,
+        model,
+        &encoding,
+    )?;
+
+    let report = PackageFilter {
+        unused_resources: true,
+        ..Default::default()
+    }
+    .apply(model);
+    progress.step(
+        "unused resources",
+        format!(
+            "{} resource(s) and {} part(s) removed",
+            report.resources_removed.len(),
+            report.attachments_removed.len()
+        ),
+        !report.is_empty(),
+        model,
+        &encoding,
+    )?;
+
+    let report = PackageFilter {
+        duplicates: true,
+        ..Default::default()
+    }
+    .apply(model);
+    progress.step(
+        "duplicates",
+        format!(
+            "{} resource(s) and {} part(s) merged",
+            report.resources_removed.len(),
+            report.attachments_removed.len()
+        ),
+        !report.is_empty(),
+        model,
+        &encoding,
+    )?;
+
+    let images = recompress_images(model, options.max_image_size)?;
+    progress.step(
+        "images",
+        images.detail(),
+        images.reencoded > 0,
+        model,
+        &encoding,
+    )?;
+
+    if let Some(places) = options.decimals {
+        encoding.write_options.float_precision = FloatPrecision::Decimals(places);
+        progress.step(
+            "coordinates",
+            format!("rounded to {} decimal place(s)", places),
+            true,
+            model,
+            &encoding,
+        )?;
+    }
+
+    encoding.compression_level = Some(COMPRESSION_LEVEL);
+    progress.step(
+        "compression",
+        format!("Deflate level {}", COMPRESSION_LEVEL),
+        true,
+        model,
+        &encoding,
+    )?;
+
+    let report = OptimizeReport {
+        original_size,
+        optimized_size: progress.size,
+        savings: progress.savings,
+    };
+    Ok((progress.package, report))
+}
+
+/// PNG attachments the `images` step looked at and changed.
+#[derive(Default)]
+struct ImageReport {
+    found: usize,
+    reencoded: usize,
+    downscaled: usize,
+}
+
+impl ImageReport {
+    fn detail(&self) -> String {
+        if cfg!(not(feature = "image-processing")) && self.found > 0 {
+            return format!(
+                "{} PNG image(s) skipped: built without the 'image-processing' feature",
+                self.found
+            );
+        }
+        format!(
+            "{} of {} PNG image(s) re-encoded, {} downscaled",
+            self.reencoded, self.found, self.downscaled
+        )
+    }
+}
+
+/// Re-encodes the PNG attachments of `model`, replacing those that get smaller or are
+/// downscaled to fit `max_size`. Images that fail to decode are left as they are.
+fn recompress_images(model: &mut Model, max_size: Option<u32>) -> Result<ImageReport> {
+    const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+    let mut paths: Vec<String> = model
+        .attachments
+        .iter()
+        .filter(|(_, data)| data.starts_with(PNG_SIGNATURE))
+        .map(|(path, _)| path.clone())
+        .collect();
+    paths.sort();
+
+    let mut report = ImageReport {
+        found: paths.len(),
+        ..Default::default()
+    };
+    if max_size.is_some() && report.found > 0 && cfg!(not(feature = "image-processing")) {
+        anyhow::bail!(
+            "--max-image-size requires the 'image-processing' feature.\n\
+            The CLI was built without image support."
+        );
+    }
+    for path in paths {
+        let data = &model.attachments[&path];
+        let Ok((png, downscaled)) = imaging::recompress_png(data, max_size) else {
+            continue;
+        };
+        if downscaled || png.len() < data.len() {
+            report.reencoded += 1;
+            report.downscaled += usize::from(downscaled);
+            model.attachments.insert(path, png);
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(feature = "image-processing")]
+mod imaging {
+    use anyhow::Result;
+    use image::ImageFormat;
+    use image::codecs::png::{CompressionType, FilterType, PngEncoder};
+    use image::imageops::FilterType as Resample;
+
+    /// Decodes the PNG `data`, fits it within `max_size` pixels if larger, and encodes it
+    /// at maximum compression. Returns the image and whether it was downscaled.
+    pub(super) fn recompress_png(data: &[u8], max_size: Option<u32>) -> Result<(Vec<u8>, bool)> {
+        let mut img = image::load_from_memory_with_format(data, ImageFormat::Png)?;
+        let downscaled = match max_size {
+            Some(max) if img.width() > max || img.height() > max => {
+                img = img.resize(max, max, Resample::Lanczos3);
+                true
+            }
+            _ => false,
+        };
+        let mut png = Vec::new();
+        img.write_with_encoder(PngEncoder::new_with_quality(
+            &mut png,
+            CompressionType::Best,
+            FilterType::Adaptive,
+        ))?;
+        Ok((png, downscaled))
+    }
+}
+
+#[cfg(not(feature = "image-processing"))]
+mod imaging {
+    use anyhow::Result;
+
+    pub(super) fn recompress_png(_data: &[u8], _max_size: Option<u32>) -> Result<(Vec<u8>, bool)> {
+        anyhow::bail!("built without image support")
+    }
+}
+
+/// Entry point for the `optimize` subcommand.
+///
+/// Loads `input` with all of its attachments, [optimizes](optimize) it and writes the
+/// result to `output`, printing the bytes each step saved. Nothing the build uses is
+/// removed unless `options` asks for lossy steps; see `strip` for removing thumbnails,
+/// textures and vendor data.
+///
+/// Rewriting the package would invalidate digital signatures, so they are removed with a
+/// warning. Packages with encrypted parts are rejected, since re-serializing them would
+/// lose the Secure Content relationships.
+pub fn run(input: PathBuf, output: PathBuf, options: OptimizeOptions) -> Result<OptimizeReport> {
+    let protected = ProtectedParts::scan(&mut open_archive(&input)?)?;
+    if !protected.encrypted.is_empty() {
+        anyhow::bail!(
+            "Cannot optimize {:?}: it contains {} encrypted part(s). Use `copy` to duplicate it unmodified.",
+            input,
+            protected.encrypted.len()
+        );
+    }
+
+    let original_size = std::fs::metadata(&input)
+        .map_err(|e| anyhow::anyhow!("Failed to read {:?}: {}", input, e))?
+        .len();
+    let mut model = load_full(&input)?;
+    if !protected.signatures.is_empty() {
+        eprintln!(
+            "Warning: removing {} digital signature(s) invalidated by optimizing",
+            protected.signatures.len()
+        );
+    }
+    protected.remove_signatures(&mut model);
+
+    let (package, report) = optimize(&mut model, original_size, options)?;
+    std::fs::write(&output, package)
+        .map_err(|e| anyhow::anyhow!("Failed to create output file: {}", e))?;
+
+    for saving in &report.savings {
+        println!(
+            "  {:<18} {:>10} bytes  {}",
+            saving.category, saving.bytes, saving.detail
+        );
+    }
+    let saved = report.original_size as i64 - report.optimized_size as i64;
+    println!(
+        "Optimized {} bytes to {} bytes ({:.1}% saved) into {:?}",
+        report.original_size,
+        report.optimized_size,
+        saved as f64 * 100.0 / report.original_size.max(1) as f64,
+        output
+    );
+    Ok(report)
+}
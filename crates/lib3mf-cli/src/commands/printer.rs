@@ -0,0 +1,284 @@
+//! Printer presets for `--printer`: build volumes and bed origins of common printers.
+//!
+//! The built-in presets ship with the tool. A user printers file in the same TOML format
+//! adds printers or replaces built-in ones by id:
+//!
+//! ```toml
+//! [printers.my-printer]
+//! name = "Workshop printer"
+//! build_volume = [300, 300, 400]
+//! origin = "corner"
+//! ```
+//!
+//! The file is `--printers-file` when given, else `$LIB3MF_PRINTERS`, else
+//! `lib3mf/printers.toml` in the user's config directory if it exists.
+
+use crate::commands::OutputFormat;
+use anyhow::Context;
+use lib3mf_core::model::stats::BoundingBox;
+use lib3mf_core::model::{ModelStats, Unit};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming a user printers file.
+pub const PRINTERS_FILE_ENV: &str = "LIB3MF_PRINTERS";
+
+const BUILTIN_PRINTERS: &str = include_str!("printers.toml");
+
+/// Differences smaller than this, in millimeters, count as fitting.
+const TOLERANCE_MM: f32 = 1e-3;
+
+/// Where a printer's coordinate origin sits on its bed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BedOrigin {
+    /// The bed spans 0 to the build volume along X and Y.
+    #[default]
+    Corner,
+    /// The origin is the middle of the bed, as on most resin printers.
+    Center,
+}
+
+/// A printer's build volume.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Printer {
+    /// Id selected with `--printer`, such as `bambu-x1c`.
+    pub id: String,
+    /// Display name.
+    pub name: String,
+    /// Printable X, Y and Z extents in millimeters.
+    pub build_volume: [f32; 3],
+    /// Where the coordinate origin sits on the bed.
+    pub origin: BedOrigin,
+}
+
+#[derive(Deserialize)]
+struct PrintersFile {
+    #[serde(default)]
+    printers: BTreeMap<String, PrinterEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PrinterEntry {
+    name: Option<String>,
+    build_volume: [f32; 3],
+    #[serde(default)]
+    origin: BedOrigin,
+}
+
+impl Printer {
+    /// Returns the printable space in millimeters, in the printer's coordinates.
+    pub fn bed(&self) -> BoundingBox {
+        let [x, y, z] = self.build_volume;
+        match self.origin {
+            BedOrigin::Corner => BoundingBox {
+                min: [0.0; 3],
+                max: [x, y, z],
+            },
+            BedOrigin::Center => BoundingBox {
+                min: [-x / 2.0, -y / 2.0, 0.0],
+                max: [x / 2.0, y / 2.0, z],
+            },
+        }
+    }
+
+    /// Checks the model described by `stats` against this printer.
+    ///
+    /// Slicer projects with plates place each plate at its own offset, so only the size of
+    /// each plate's contents is checked. Other models are checked for size, then for lying
+    /// on the bed.
+    pub fn fit(&self, stats: &ModelStats) -> PrinterFit {
+        let mut fit = PrinterFit {
+            printer: self.id.clone(),
+            name: self.name.clone(),
+            build_volume: self.build_volume,
+            origin: self.origin,
+            fits: true,
+            oversize: Vec::new(),
+            outside_bed: Vec::new(),
+        };
+        if stats.plates.is_empty() {
+            if let Some(bbox) = stats.extents_in(Unit::Millimeter) {
+                fit.oversize = self.oversize(&bbox, "");
+                if fit.oversize.is_empty() {
+                    fit.outside_bed = self.outside_bed(&bbox);
+                }
+            }
+        } else {
+            for plate in &stats.plates {
+                if let Some(bbox) = plate.bounding_box {
+                    let bbox = bbox.convert(stats.unit, Unit::Millimeter);
+                    let prefix = format!("Plate {}: ", plate.plate_id);
+                    fit.oversize.extend(self.oversize(&bbox, &prefix));
+                }
+            }
+        }
+        fit.fits = fit.oversize.is_empty() && fit.outside_bed.is_empty();
+        fit
+    }
+
+    /// Describes each axis along which `bbox` is larger than the build volume.
+    pub fn oversize(&self, bbox: &BoundingBox, prefix: &str) -> Vec<String> {
+        let size = bbox.size();
+        (0..3)
+            .filter(|&i| size[i] > self.build_volume[i] + TOLERANCE_MM)
+            .map(|i| {
+                format!(
+                    "{}{} size {:.2} mm exceeds the {:.2} mm build volume of {}",
+                    prefix, AXES[i], size[i], self.build_volume[i], self.name
+                )
+            })
+            .collect()
+    }
+
+    /// Describes each axis along which `bbox` extends past the bed.
+    pub fn outside_bed(&self, bbox: &BoundingBox) -> Vec<String> {
+        let bed = self.bed();
+        (0..3)
+            .filter(|&i| {
+                bbox.min[i] < bed.min[i] - TOLERANCE_MM || bbox.max[i] > bed.max[i] + TOLERANCE_MM
+            })
+            .map(|i| {
+                format!(
+                    "{} spans {:.2} to {:.2} mm, outside the bed's {:.2} to {:.2} mm",
+                    AXES[i], bbox.min[i], bbox.max[i], bed.min[i], bed.max[i]
+                )
+            })
+            .collect()
+    }
+}
+
+const AXES: [char; 3] = ['X', 'Y', 'Z'];
+
+/// How a model fits a printer, reported by `stats` and `validate` with `--printer`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PrinterFit {
+    /// Id of the printer checked against.
+    pub printer: String,
+    /// Display name of the printer.
+    pub name: String,
+    /// Printable X, Y and Z extents in millimeters.
+    pub build_volume: [f32; 3],
+    /// Where the printer's coordinate origin sits on the bed.
+    pub origin: BedOrigin,
+    /// Whether the model fits and lies on the bed.
+    pub fits: bool,
+    /// Axes along which the model, or a plate, is larger than the build volume.
+    pub oversize: Vec<String>,
+    /// Axes along which the model fits but has been placed off the bed.
+    pub outside_bed: Vec<String>,
+}
+
+/// Returns the built-in printer presets, ordered by id.
+pub fn builtin_printers() -> Vec<Printer> {
+    parse_printers(BUILTIN_PRINTERS).expect("built-in printer presets are valid")
+}
+
+/// Returns the built-in presets merged with the user printers file, ordered by id.
+///
+/// `file` overrides the default printers file (see the module docs).
+///
+/// # Errors
+///
+/// Returns an error if an explicitly chosen printers file cannot be read, or if the
+/// printers file is malformed.
+pub fn load_printers(file: Option<&Path>) -> anyhow::Result<Vec<Printer>> {
+    let mut printers: BTreeMap<String, Printer> = builtin_printers()
+        .into_iter()
+        .map(|p| (p.id.clone(), p))
+        .collect();
+    if let Some(path) = file.map(Path::to_path_buf).or_else(user_printers_file) {
+        let text = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read printers file {:?}", path))?;
+        let user =
+            parse_printers(&text).with_context(|| format!("Invalid printers file {:?}", path))?;
+        printers.extend(user.into_iter().map(|p| (p.id.clone(), p)));
+    }
+    Ok(printers.into_values().collect())
+}
+
+/// Finds the printer with the given id (case-insensitive) among [`load_printers`].
+///
+/// # Errors
+///
+/// Returns an error listing the known ids if there is no such printer, or any error of
+/// [`load_printers`].
+pub fn find_printer(id: &str, file: Option<&Path>) -> anyhow::Result<Printer> {
+    let printers = load_printers(file)?;
+    if let Some(printer) = printers.iter().find(|p| p.id.eq_ignore_ascii_case(id)) {
+        return Ok(printer.clone());
+    }
+    let ids: Vec<&str> = printers.iter().map(|p| p.id.as_str()).collect();
+    anyhow::bail!(
+        "Unknown printer '{}'; known printers: {}",
+        id,
+        ids.join(", ")
+    )
+}
+
+/// Returns the user printers file: `$LIB3MF_PRINTERS`, or `lib3mf/printers.toml` in the
+/// config directory if that file exists.
+pub fn user_printers_file() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(PRINTERS_FILE_ENV) {
+        return Some(PathBuf::from(path));
+    }
+    let config = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))?;
+    let path = config.join("lib3mf").join("printers.toml");
+    path.is_file().then_some(path)
+}
+
+fn parse_printers(text: &str) -> anyhow::Result<Vec<Printer>> {
+    let file: PrintersFile = toml::from_str(text)?;
+    file.printers
+        .into_iter()
+        .map(|(id, entry)| {
+            if entry
+                .build_volume
+                .iter()
+                .any(|&v| !(v > 0.0 && v.is_finite()))
+            {
+                anyhow::bail!("Printer '{}' has a non-positive build volume", id);
+            }
+            Ok(Printer {
+                name: entry.name.unwrap_or_else(|| id.clone()),
+                id,
+                build_volume: entry.build_volume,
+                origin: entry.origin,
+            })
+        })
+        .collect()
+}
+
+/// Entry point for the `printers` subcommand: lists the presets usable with `--printer`.
+pub fn run(file: Option<PathBuf>, format: OutputFormat) -> anyhow::Result<()> {
+    let printers = load_printers(file.as_deref())?;
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&printers)?),
+        _ => {
+            println!(
+                "{:<18} {:<30} {:>22}  Origin",
+                "ID", "Name", "Build Volume (mm)"
+            );
+            for p in &printers {
+                let [x, y, z] = p.build_volume;
+                let origin = match p.origin {
+                    BedOrigin::Corner => "corner",
+                    BedOrigin::Center => "center",
+                };
+                println!(
+                    "{:<18} {:<30} {:>22}  {}",
+                    p.id,
+                    p.name,
+                    format!("{} x {} x {}", x, y, z),
+                    origin
+                );
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use crate::commands::OutputFormat;
+use crate::commands::merge::load_full;
+use anyhow::Result;
+use lib3mf_core::model::ObjectFilter;
+use std::path::PathBuf;
+
+/// Entry point for the `query` subcommand.
+///
+/// Lists the objects matching `filter` (all objects if `None`), ordered by ID, or the
+/// `largest` matching objects by volume, largest first.
+pub fn run(
+    path: PathBuf,
+    filter: Option<&str>,
+    largest: Option<usize>,
+    format: OutputFormat,
+) -> Result<()> {
+    let filter = filter
+        .map(ObjectFilter::parse)
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("Failed to parse --where expression: {}", e))?;
+    let model = load_full(&path)?;
+
+    let mut query = model.query().objects();
+    if let Some(filter) = &filter {
+        query = query.matching(filter);
+    }
+    if let Some(n) = largest {
+        query = query.largest_by_volume(n);
+    }
+    let results = query.with_metrics();
+
+    match format {
+        OutputFormat::Json => {
+            let entries: Vec<_> = results
+                .iter()
+                .map(|(obj, metrics)| {
+                    serde_json::json!({
+                        "id": obj.id.0,
+                        "type": obj.object_type.to_string(),
+                        "name": obj.name,
+                        "part_number": obj.part_number,
+                        "triangles": metrics.triangles,
+                        "vertices": metrics.vertices,
+                        "volume": metrics.volume,
+                    })
+                })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+        }
+        _ => {
+            println!(
+                "{:>6}  {:<12}  {:>10}  {:>10}  {:>14}  Name",
+                "ID", "Type", "Triangles", "Vertices", "Volume"
+            );
+            for (obj, metrics) in &results {
+                println!(
+                    "{:>6}  {:<12}  {:>10}  {:>10}  {:>14.3}  {}",
+                    obj.id.0,
+                    obj.object_type.to_string(),
+                    metrics.triangles,
+                    metrics.vertices,
+                    metrics.volume,
+                    obj.name.as_deref().unwrap_or("-")
+                );
+            }
+            println!(
+                "{} object(s) matched (volume in {}³)",
+                results.len(),
+                model.unit.as_str()
+            );
+        }
+    }
+    Ok(())
+}
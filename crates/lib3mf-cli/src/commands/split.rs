@@ -13,9 +13,9 @@
 
 use crate::commands::merge::{Verbosity, check_secure_content, load_full};
 use lib3mf_core::model::{
-    BaseMaterialsGroup, Build, BuildItem, ColorGroup, CompositeMaterials, Displacement2D, Geometry,
-    Model, MultiProperties, Object, ResourceCollection, ResourceId, SliceStack, Texture2D,
-    Texture2DGroup, VolumetricStack,
+    BaseMaterialsGroup, Build, BuildItem, ColorGroup, CompositeMaterials, Displacement2D, Function,
+    Geometry, Image3D, Model, MultiProperties, Object, ResourceCollection, ResourceId, SliceStack,
+    Texture2D, Texture2DGroup, VolumeData, VolumetricStack,
 };
 use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
@@ -114,12 +114,32 @@ impl<'a> DependencyCollector<'a> {
                         self.collect_property(ResourceId(pid));
                     }
                 }
+                if let Some(volume_id) = mesh.volume_id {
+                    self.collect_volume_data(volume_id);
+                }
+            }
+            Geometry::PreciseMesh(mesh) => {
+                for tri in &mesh.triangles {
+                    if let Some(pid) = tri.pid {
+                        self.collect_property(ResourceId(pid));
+                    }
+                }
+                if let Some(volume_id) = mesh.volume_id {
+                    self.collect_volume_data(volume_id);
+                }
             }
             Geometry::Components(comps) => {
                 for comp in &comps.components {
                     self.collect_object(comp.object_id); // recurse into child objects
                 }
             }
+            Geometry::LevelSet(level_set) => {
+                self.collect_object(level_set.mesh_id);
+                self.collect_function(level_set.function_id);
+                if let Some(volume_id) = level_set.volume_id {
+                    self.collect_volume_data(volume_id);
+                }
+            }
             Geometry::BooleanShape(shape) => {
                 self.collect_object(shape.base_object_id);
                 for op in &shape.operations {
@@ -139,6 +159,7 @@ impl<'a> DependencyCollector<'a> {
                     }
                 }
             }
+            Geometry::LazyMesh(_) => unreachable!("load_full parses meshes eagerly"),
         }
     }
 
@@ -179,6 +200,57 @@ impl<'a> DependencyCollector<'a> {
 
         // BaseMaterialsGroup and ColorGroup: no further dependencies (leaves)
     }
+
+    /// Collect a function and what it samples or references.
+    fn collect_function(&mut self, id: ResourceId) {
+        if !self.needed_ids.insert(id) {
+            return; // Already visited
+        }
+        match self.resources.get_function(id) {
+            // FunctionFromImage3D -> Image3D -> sheet attachments
+            Some(Function::FromImage3D(f)) => {
+                self.needed_ids.insert(f.image3d_id);
+                if let Some(image) = self.resources.get_image_3d(f.image3d_id) {
+                    self.needed_attachment_paths
+                        .extend(image.stack.sheets.iter().cloned());
+                }
+            }
+            // Resource ID constants name meshes or other functions
+            Some(Function::Implicit(f)) => {
+                let ids: Vec<ResourceId> = f
+                    .nodes
+                    .iter()
+                    .filter(|n| n.kind == "constresourceid")
+                    .filter_map(|n| n.attribute("value")?.parse().ok())
+                    .map(ResourceId)
+                    .collect();
+                for id in ids {
+                    if self.resources.get_function(id).is_some() {
+                        self.collect_function(id);
+                    } else {
+                        self.collect_object(id);
+                    }
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Collect a volume data resource, its functions and its base materials.
+    fn collect_volume_data(&mut self, id: ResourceId) {
+        if !self.needed_ids.insert(id) {
+            return; // Already visited
+        }
+        let Some(data) = self.resources.get_volume_data(id) else {
+            return;
+        };
+        if let Some(composite) = &data.composite {
+            self.collect_property(composite.base_material_id);
+        }
+        for function_ref in data.function_refs() {
+            self.collect_function(function_ref.function_id);
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -241,11 +313,8 @@ fn build_split_model(
 ) -> anyhow::Result<Model> {
     // Copy model-level fields; metadata is annotated with source provenance (Pattern 8)
     let mut metadata = source.metadata.clone();
-    metadata.insert(
-        "Source".to_string(),
-        source.metadata.get("Source").cloned().unwrap_or_default(),
-    );
-    metadata.insert("SourceObject".to_string(), target.name.clone());
+    metadata.insert("Source", source.metadata.get("Source").unwrap_or_default());
+    metadata.insert("SourceObject", target.name.clone());
 
     let mut out = Model {
         unit: source.unit,
@@ -256,6 +325,10 @@ fn build_split_model(
         attachments: std::collections::HashMap::new(),
         existing_relationships: std::collections::HashMap::new(),
         extra_namespaces: source.extra_namespaces.clone(),
+        required_extensions: source.required_extensions.clone(),
+        production_namespace: source.production_namespace.clone(),
+        unknown_names: Vec::new(),
+        core_properties: source.core_properties.clone(),
     };
 
     // --- Add only needed objects, with remapped IDs ---
@@ -273,6 +346,18 @@ fn build_split_model(
                 for tri in &mut mesh.triangles {
                     remap_opt_u32_pid(&mut tri.pid, id_remap);
                 }
+                remap_opt_id(&mut mesh.volume_id, id_remap);
+            }
+            Geometry::PreciseMesh(mesh) => {
+                for tri in &mut mesh.triangles {
+                    remap_opt_u32_pid(&mut tri.pid, id_remap);
+                }
+                remap_opt_id(&mut mesh.volume_id, id_remap);
+            }
+            Geometry::LevelSet(level_set) => {
+                remap_id(&mut level_set.function_id, id_remap);
+                remap_id(&mut level_set.mesh_id, id_remap);
+                remap_opt_id(&mut level_set.volume_id, id_remap);
             }
             Geometry::Components(comps) => {
                 for comp in &mut comps.components {
@@ -296,6 +381,7 @@ fn build_split_model(
                     remap_opt_u32_pid(&mut tri.pid, id_remap);
                 }
             }
+            Geometry::LazyMesh(_) => unreachable!("load_full parses meshes eagerly"),
         }
         out.resources
             .add_object(obj)
@@ -433,6 +519,80 @@ fn build_split_model(
             .map_err(|e| anyhow::anyhow!("Failed to add displacement 2D to split model: {}", e))?;
     }
 
+    // --- Image3D ---
+    let images_3d: Vec<Image3D> = source
+        .resources
+        .iter_images_3d()
+        .filter(|i| needed_ids.contains(&i.id))
+        .cloned()
+        .collect();
+    for mut image in images_3d {
+        remap_id(&mut image.id, id_remap);
+        out.resources
+            .add_image_3d(image)
+            .map_err(|e| anyhow::anyhow!("Failed to add 3D image to split model: {}", e))?;
+    }
+
+    // --- Functions ---
+    let functions: Vec<Function> = source
+        .resources
+        .iter_functions()
+        .filter(|f| needed_ids.contains(&f.id()))
+        .cloned()
+        .collect();
+    for mut function in functions {
+        match &mut function {
+            Function::Implicit(f) => {
+                remap_id(&mut f.id, id_remap);
+                let values = f
+                    .nodes
+                    .iter_mut()
+                    .filter(|n| n.kind == "constresourceid")
+                    .flat_map(|n| n.attributes.iter_mut())
+                    .filter(|(key, _)| key == "value");
+                for (_, value) in values {
+                    if let Ok(old) = value.parse() {
+                        let mut id = ResourceId(old);
+                        remap_id(&mut id, id_remap);
+                        *value = id.0.to_string();
+                    }
+                }
+            }
+            Function::FromImage3D(f) => {
+                remap_id(&mut f.id, id_remap);
+                remap_id(&mut f.image3d_id, id_remap);
+            }
+        }
+        out.resources
+            .add_function(function)
+            .map_err(|e| anyhow::anyhow!("Failed to add function to split model: {}", e))?;
+    }
+
+    // --- VolumeData ---
+    let volume_data: Vec<VolumeData> = source
+        .resources
+        .iter_volume_data()
+        .filter(|v| needed_ids.contains(&v.id))
+        .cloned()
+        .collect();
+    for mut data in volume_data {
+        remap_id(&mut data.id, id_remap);
+        if let Some(composite) = &mut data.composite {
+            remap_id(&mut composite.base_material_id, id_remap);
+        }
+        let refs = data
+            .color
+            .iter_mut()
+            .chain(data.composite.iter_mut().flat_map(|c| &mut c.mappings))
+            .chain(data.properties.iter_mut().map(|p| &mut p.function));
+        for function_ref in refs {
+            remap_id(&mut function_ref.function_id, id_remap);
+        }
+        out.resources
+            .add_volume_data(data)
+            .map_err(|e| anyhow::anyhow!("Failed to add volume data to split model: {}", e))?;
+    }
+
     // --- Build section: one item for the root object ---
     let new_root_id = id_remap
         .get(&target.root_object_id)
@@ -454,6 +614,7 @@ fn build_split_model(
         path: None,
         part_number: None,
         printable: None,
+        metadata: Default::default(),
     });
 
     // --- Attachments: only needed ones ---
@@ -466,6 +627,42 @@ fn build_split_model(
     Ok(out)
 }
 
+/// Construct a model holding object `id` and the resources it references, with compact
+/// renumbered IDs. The transform of the object's first build item is kept.
+pub(crate) fn extract_object(source: &Model, id: ResourceId) -> anyhow::Result<Model> {
+    let obj = source
+        .resources
+        .get_object(id)
+        .ok_or_else(|| anyhow::anyhow!("Object {} not found", id.0))?;
+    let target = SplitTarget {
+        root_object_id: id,
+        build_item: source
+            .build
+            .items
+            .iter()
+            .find(|item| item.object_id == id)
+            .cloned(),
+        name: obj
+            .name
+            .clone()
+            .filter(|n| !n.is_empty())
+            .unwrap_or_else(|| format!("object_{}", id.0)),
+        index: 0,
+    };
+
+    let mut collector = DependencyCollector::new(&source.resources);
+    collector.collect_object(id);
+    let id_remap = build_compact_remap(&collector.needed_ids);
+    build_split_model(
+        source,
+        &target,
+        true,
+        &id_remap,
+        &collector.needed_ids,
+        &collector.needed_attachment_paths,
+    )
+}
+
 // ---------------------------------------------------------------------------
 // Output naming helpers
 // ---------------------------------------------------------------------------
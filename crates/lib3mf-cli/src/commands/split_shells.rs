@@ -0,0 +1,102 @@
+use crate::commands::copy::ProtectedParts;
+use crate::commands::merge::load_full;
+use crate::commands::open_archive;
+use anyhow::Result;
+use lib3mf_core::model::{Geometry, Model, ObjectType, ResourceId};
+use std::fs::File;
+use std::path::PathBuf;
+
+/// Entry point for the `split-shells` subcommand.
+///
+/// Splits every model object in `input` (or the objects in `object_ids`) into one object
+/// per connected shell using [`lib3mf_core::model::Model::split_object_components`], then
+/// writes the result to `output`. Build items and components are duplicated for the new
+/// objects, so the parts stay where they were.
+///
+/// Adding objects invalidates digital signatures, so they are removed with a warning.
+/// Packages with encrypted parts are rejected. Only meshes in the root model part are
+/// split; meshes in external model parts (Production extension) are not supported.
+pub fn run(input: PathBuf, output: PathBuf, object_ids: &[u32]) -> Result<()> {
+    let protected = ProtectedParts::scan(&mut open_archive(&input)?)?;
+    if !protected.encrypted.is_empty() {
+        anyhow::bail!(
+            "Cannot split {:?}: it contains {} encrypted part(s)",
+            input,
+            protected.encrypted.len()
+        );
+    }
+
+    let mut model = load_full(&input)?;
+    for &id in object_ids {
+        match model.resources.get_object(ResourceId(id)) {
+            Some(object) if matches!(object.geometry, Geometry::Mesh(_)) => {}
+            Some(_) => anyhow::bail!("Object {} is not a mesh object", id),
+            None => anyhow::bail!("Object {} not found", id),
+        }
+    }
+
+    let selected: Vec<ResourceId> = if object_ids.is_empty() {
+        model
+            .resources
+            .iter_objects()
+            .filter(|o| {
+                o.object_type == ObjectType::Model && matches!(o.geometry, Geometry::Mesh(_))
+            })
+            .map(|o| o.id)
+            .collect()
+    } else {
+        object_ids.iter().map(|&id| ResourceId(id)).collect()
+    };
+    if selected.is_empty() {
+        if references_external_parts(&model) {
+            anyhow::bail!(
+                "No mesh objects to split in the root model part of {:?}; meshes in external model parts are not supported",
+                input
+            );
+        }
+        anyhow::bail!("No mesh objects to split in {:?}", input);
+    }
+
+    let mut added = 0;
+    for id in selected {
+        let ids = model
+            .split_object_components(id)
+            .map_err(|e| anyhow::anyhow!("Failed to split object {}: {}", id.0, e))?;
+        if ids.len() > 1 {
+            let new: Vec<String> = ids[1..].iter().map(|id| id.0.to_string()).collect();
+            println!(
+                "Split object {} into {} shells (new objects: {})",
+                id.0,
+                ids.len(),
+                new.join(", ")
+            );
+            added += ids.len() - 1;
+        }
+    }
+    if added == 0 {
+        println!("Every object is a single shell; nothing to split");
+    } else if !protected.signatures.is_empty() {
+        eprintln!(
+            "Warning: removing {} digital signature(s) invalidated by splitting",
+            protected.signatures.len()
+        );
+    }
+    protected.remove_signatures(&mut model);
+
+    let file = File::create(&output)
+        .map_err(|e| anyhow::anyhow!("Failed to create output file: {}", e))?;
+    model
+        .write(file)
+        .map_err(|e| anyhow::anyhow!("Failed to write 3MF: {}", e))?;
+    println!("Added {} object(s), written to {:?}", added, output);
+    Ok(())
+}
+
+/// Whether any build item or component refers to an object in another model part.
+fn references_external_parts(model: &Model) -> bool {
+    model.build.items.iter().any(|item| item.path.is_some())
+        || model.resources.iter_objects().any(|o| match &o.geometry {
+            Geometry::Components(c) => c.components.iter().any(|c| c.path.is_some()),
+            _ => false,
+        })
+}
@@ -0,0 +1,108 @@
+use crate::commands::copy::ProtectedParts;
+use crate::commands::merge::load_full;
+use crate::commands::open_archive;
+use anyhow::Result;
+use lib3mf_core::model::PackageFilter;
+use std::fs::File;
+use std::path::PathBuf;
+
+/// Kind of content removed by the `strip` subcommand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum StripTarget {
+    /// Package, object and vendor plate thumbnails
+    Thumbnails,
+    /// Textures, texture coordinates and their images
+    Textures,
+    /// Namespaced metadata and slicer files under Metadata/ and Auxiliaries/
+    VendorMetadata,
+    /// Resources not reachable from the build
+    UnusedResources,
+    /// Duplicate images, textures, material groups and objects, merged into one copy
+    Duplicates,
+}
+
+impl StripTarget {
+    /// All targets, the default when none are given.
+    pub const ALL: [StripTarget; 5] = [
+        StripTarget::Thumbnails,
+        StripTarget::Textures,
+        StripTarget::VendorMetadata,
+        StripTarget::UnusedResources,
+        StripTarget::Duplicates,
+    ];
+}
+
+/// Builds the [`PackageFilter`] selecting `targets`.
+pub fn filter_for(targets: &[StripTarget]) -> PackageFilter {
+    PackageFilter {
+        thumbnails: targets.contains(&StripTarget::Thumbnails),
+        textures: targets.contains(&StripTarget::Textures),
+        vendor_metadata: targets.contains(&StripTarget::VendorMetadata),
+        unused_resources: targets.contains(&StripTarget::UnusedResources),
+        duplicates: targets.contains(&StripTarget::Duplicates),
+    }
+}
+
+/// Entry point for the `strip` subcommand.
+///
+/// Loads `input` with all of its attachments, removes the content selected by `targets`
+/// using [`PackageFilter`], and writes the result to `output`.
+///
+/// Rewriting the package would invalidate digital signatures, so they are removed with a
+/// warning. Packages with encrypted parts are rejected, since re-serializing them would
+/// lose the Secure Content relationships.
+pub fn run(input: PathBuf, output: PathBuf, targets: &[StripTarget]) -> Result<()> {
+    let protected = ProtectedParts::scan(&mut open_archive(&input)?)?;
+    if !protected.encrypted.is_empty() {
+        anyhow::bail!(
+            "Cannot strip {:?}: it contains {} encrypted part(s). Use `copy` to duplicate it unmodified.",
+            input,
+            protected.encrypted.len()
+        );
+    }
+
+    let mut model = load_full(&input)?;
+    if !protected.signatures.is_empty() {
+        eprintln!(
+            "Warning: removing {} digital signature(s) invalidated by stripping",
+            protected.signatures.len()
+        );
+    }
+    protected.remove_signatures(&mut model);
+
+    let report = filter_for(targets).apply(&mut model);
+
+    let file = File::create(&output)
+        .map_err(|e| anyhow::anyhow!("Failed to create output file: {}", e))?;
+    model
+        .write(file)
+        .map_err(|e| anyhow::anyhow!("Failed to write 3MF: {}", e))?;
+
+    if report.is_empty() {
+        println!("Nothing to strip; wrote {:?}", output);
+        return Ok(());
+    }
+    for path in &report.attachments_removed {
+        println!("  removed part      {}", path);
+    }
+    for id in &report.resources_removed {
+        println!("  removed resource  {}", id.0);
+    }
+    for name in &report.metadata_removed {
+        println!("  removed metadata  {}", name);
+    }
+    println!(
+        "Stripped {} part(s) ({} bytes), {} resource(s) and {} metadata entr{} into {:?}",
+        report.attachments_removed.len(),
+        report.bytes_removed,
+        report.resources_removed.len(),
+        report.metadata_removed.len(),
+        if report.metadata_removed.len() == 1 {
+            "y"
+        } else {
+            "ies"
+        },
+        output
+    );
+    Ok(())
+}
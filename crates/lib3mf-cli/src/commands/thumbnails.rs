@@ -1,45 +1,92 @@
 use anyhow::Result;
-use lib3mf_core::archive::ArchiveReader; // Trait must be in scope
+use clap::ValueEnum;
+use lib3mf_core::archive::{ArchiveReader, normalize_part_name, resolve_part_target};
 use lib3mf_core::model::ResourceId;
 use std::fs::{self, File};
 use std::io::Write; // Removed Read
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Image format thumbnails are converted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ThumbnailFormat {
+    Png,
+    Jpeg,
+}
+
+impl ThumbnailFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ThumbnailFormat::Png => "png",
+            ThumbnailFormat::Jpeg => "jpg",
+        }
+    }
+}
+
+/// Processing applied to extracted and injected thumbnails. Requires the
+/// `image-processing` feature when anything is set.
+#[derive(Debug, Clone, Default)]
+pub struct ImageOptions {
+    /// Fit images within this width and height, keeping their aspect ratio.
+    pub resize: Option<(u32, u32)>,
+    /// Re-encode images in this format instead of their own.
+    pub convert: Option<ThumbnailFormat>,
+}
+
+/// Parses a `WxH` size such as `256x256`.
+pub fn parse_size(s: &str) -> std::result::Result<(u32, u32), String> {
+    let (width, height) = s
+        .split_once(['x', 'X'])
+        .ok_or_else(|| format!("expected WIDTHxHEIGHT, got '{}'", s))?;
+    let parse = |v: &str| match v.trim().parse::<u32>() {
+        Ok(0) | Err(_) => Err(format!("invalid size '{}'", s)),
+        Ok(n) => Ok(n),
+    };
+    Ok((parse(width)?, parse(height)?))
+}
 
 /// Entry point for the `thumbnails` subcommand.
 ///
 /// Depending on the flags provided, this function either lists all thumbnail
-/// attachments in the 3MF archive, extracts them to a directory, or injects
-/// a new thumbnail image into the archive.
+/// attachments in the 3MF archive, extracts them to a directory, writes a contact
+/// sheet of the object thumbnails, or injects a new thumbnail image into the archive.
+/// Extracted and injected images are resized and converted as `image` asks.
 pub fn run(
     file: PathBuf,
     list: bool,
     extract: Option<PathBuf>,
     inject: Option<PathBuf>,
     oid: Option<u32>,
+    contact_sheet: Option<PathBuf>,
+    image: &ImageOptions,
 ) -> Result<()> {
     if list {
         run_list(&file)?;
         return Ok(());
     }
 
-    if let Some(dir) = extract {
-        run_extract(&file, dir)?;
+    if extract.is_some() || contact_sheet.is_some() {
+        if let Some(dir) = extract {
+            run_extract(&file, dir, image)?;
+        }
+        if let Some(out) = contact_sheet {
+            run_contact_sheet(&file, &out, image)?;
+        }
         return Ok(());
     }
 
     if let Some(img_path) = inject {
-        run_inject(&file, img_path, oid)?;
+        run_inject(&file, img_path, oid, image)?;
         return Ok(());
     }
 
     // Default or help usage if no flags?
-    println!("Please specify --list, --extract <DIR>, or --inject <IMG>.");
+    println!("Please specify --list, --extract <DIR>, --contact-sheet <FILE>, or --inject <IMG>.");
     Ok(())
 }
 
 fn run_list(file: &PathBuf) -> Result<()> {
     let mut archiver = crate::commands::open_archive(file)?;
-    let model_path = lib3mf_core::archive::find_model_path(&mut archiver)?;
+    let model_path = crate::commands::locate_model_part(&mut archiver)?;
     let model_data = archiver.read_entry(&model_path)?;
     let model = lib3mf_core::parser::parse_model(std::io::Cursor::new(model_data))?;
 
@@ -78,7 +125,7 @@ fn run_list(file: &PathBuf) -> Result<()> {
     // Build Map: Relationship ID -> Target Path
     let mut rel_map = std::collections::HashMap::new();
     for rel in model_rels {
-        rel_map.insert(rel.id, rel.target);
+        rel_map.insert(rel.id, resolve_part_target(&model_path, &rel.target));
     }
 
     // Objects
@@ -122,68 +169,61 @@ fn run_list(file: &PathBuf) -> Result<()> {
 // I'll view the file to get current state.
 // Better safe than sorry.
 
-fn run_inject(file: &PathBuf, img_path: PathBuf, oid: Option<u32>) -> Result<()> {
+fn run_inject(
+    file: &PathBuf,
+    img_path: PathBuf,
+    oid: Option<u32>,
+    image: &ImageOptions,
+) -> Result<()> {
     // We need to preserve existing attachments!
     let mut archiver = crate::commands::open_archive(file)?;
-    let model_path = lib3mf_core::archive::find_model_path(&mut archiver)?;
+    let model_path = crate::commands::locate_model_part(&mut archiver)?;
     let model_data = archiver.read_entry(&model_path)?;
     let mut model = lib3mf_core::parser::parse_model(std::io::Cursor::new(model_data))?;
 
-    // Load ALL existing files as attachments (excluding system files)
-    // Also load .rels files to preserve multi-part relationships
-    let all_files = archiver.list_entries()?;
-    for entry_path in all_files {
-        // Skip files that PackageWriter regenerates
-        if entry_path == model_path
-            || entry_path == "_rels/.rels"
-            || entry_path == "[Content_Types].xml"
-        {
-            continue;
-        }
-
-        // Load .rels files separately to preserve relationships
-        if entry_path.ends_with(".rels") {
-            if let Ok(data) = archiver.read_entry(&entry_path)
-                && let Ok(rels) = lib3mf_core::archive::opc::parse_relationships(&data)
-            {
-                model.existing_relationships.insert(entry_path, rels);
-            }
-            continue;
-        }
-
-        // Load other data as attachments
-        if let Ok(data) = archiver.read_entry(&entry_path) {
-            model.attachments.insert(entry_path, data);
-        }
-    }
+    // Load all other parts and their relationships, to preserve them
+    let core_properties = crate::commands::load_core_properties(&mut archiver, &mut model);
+    model.load_attachments(&mut archiver, |path| {
+        path == model_path || Some(path) == core_properties.as_deref()
+    })?;
 
     println!("Injecting {:?} into {:?}", img_path, file);
 
     let img_data = fs::read(&img_path)?;
 
     if let Some(id) = oid {
-        // Object Injection
-        let rid = ResourceId(id);
-
-        let mut found = false;
-        for obj in model.resources.iter_objects_mut() {
-            if obj.id == rid {
-                // Set path
-                let path = format!("3D/Textures/thumb_{}.png", id);
-                obj.thumbnail = Some(path.clone());
-
-                // Add attachment
-                model.attachments.insert(path, img_data.clone());
-                println!("Updated Object {} thumbnail.", id);
-                found = true;
-                break;
-            }
-        }
-        if !found {
-            anyhow::bail!("Object ID {} not found.", id);
+        // Object Injection. The writer links the image from the model part with a
+        // thumbnail relationship and registers its content type.
+        if image_extension(&img_data).is_none() {
+            anyhow::bail!("{:?} is not a PNG or JPEG image", img_path);
         }
+        let (img_data, ext) = process_image(img_data, image)?;
+        let obj = model
+            .resources
+            .iter_objects_mut()
+            .find(|obj| obj.id == ResourceId(id))
+            .ok_or_else(|| anyhow::anyhow!("Object ID {} not found.", id))?;
+
+        let path = format!("/3D/Thumbnails/object_{}.{}", id, ext);
+        obj.thumbnail = Some(path.clone());
+        model
+            .attachments
+            .insert(path.trim_start_matches('/').to_string(), img_data);
+        println!("Updated Object {} thumbnail.", id);
     } else {
-        // Package Injection
+        // Package Injection. The package thumbnail part is always PNG.
+        if image.convert == Some(ThumbnailFormat::Jpeg) {
+            anyhow::bail!("The package thumbnail must be PNG; use --oid to inject a JPEG");
+        }
+        let img_data = if image.resize.is_some() || image.convert.is_some() {
+            let options = ImageOptions {
+                convert: Some(ThumbnailFormat::Png),
+                ..image.clone()
+            };
+            process_image(img_data, &options)?.0
+        } else {
+            img_data
+        };
         let path = "Metadata/thumbnail.png".to_string();
         model.attachments.insert(path, img_data);
         println!("Updated Package Thumbnail.");
@@ -199,13 +239,25 @@ fn run_inject(file: &PathBuf, img_path: PathBuf, oid: Option<u32>) -> Result<()>
     Ok(())
 }
 
-fn run_extract(file: &PathBuf, dir: PathBuf) -> Result<()> {
+/// Returns the file extension for PNG or JPEG image data, the formats 3MF allows for
+/// thumbnails.
+fn image_extension(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("png")
+    } else if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("jpg")
+    } else {
+        None
+    }
+}
+
+fn run_extract(file: &PathBuf, dir: PathBuf, image: &ImageOptions) -> Result<()> {
     // We need the archiver to read relationships
     let mut archiver = crate::commands::open_archive(file)?;
 
     // Parse Model (to get objects)
     // Note: open_archive returns ZipArchiver. We need to find model path.
-    let model_path_str = lib3mf_core::archive::find_model_path(&mut archiver)?;
+    let model_path_str = crate::commands::locate_model_part(&mut archiver)?;
     let model_data = archiver.read_entry(&model_path_str)?;
     let model = lib3mf_core::parser::parse_model(std::io::Cursor::new(model_data))?;
 
@@ -257,7 +309,7 @@ fn run_extract(file: &PathBuf, dir: PathBuf) -> Result<()> {
     let mut pkg_thumb_path = None;
     for rel in global_rels {
         if rel.rel_type.ends_with("metadata/thumbnail") {
-            pkg_thumb_path = Some(rel.target);
+            pkg_thumb_path = Some(resolve_part_target("", &rel.target));
             break;
         }
     }
@@ -269,19 +321,35 @@ fn run_extract(file: &PathBuf, dir: PathBuf) -> Result<()> {
     if let Some(path) = pkg_thumb_path
         && let Ok(data) = archiver.read_entry(&path)
     {
-        let out = dir.join("package_thumbnail.png");
+        let (data, ext) = process_image(data, image)?;
+        let out = dir.join(format!("package_thumbnail.{}", ext));
         let mut f = File::create(&out)?;
         f.write_all(&data)?;
         println!("  Extracted Package Thumbnail: {:?}", out);
     }
 
     // 2. Object Thumbnails
+    for (id, bytes) in object_thumbnails(&mut archiver, &model_path_str, &model) {
+        let (bytes, ext) = process_image(bytes, image)?;
+        let out = dir.join(format!("obj_{}_thumbnail.{}", id, ext));
+        let mut f = File::create(&out)?;
+        f.write_all(&bytes)?;
+        println!("  Extracted Object {} Thumbnail: {:?}", id, out);
+    }
+
+    Ok(())
+}
+
+/// Reads the thumbnail image of every object that has one, by object ID.
+fn object_thumbnails(
+    archiver: &mut impl ArchiveReader,
+    model_path: &str,
+    model: &lib3mf_core::model::Model,
+) -> Vec<(u32, Vec<u8>)> {
     // Parse Model Relationships
     // Path is e.g. "3D/_rels/3dmodel.model.rels" (if main model is "3D/3dmodel.model")
-    // We need to construct the rels path from `model_path_str`.
-    // e.g. "3D/3dmodel.model" -> "3D/_rels/3dmodel.model.rels"
     let model_rels_path = {
-        let path = std::path::Path::new(&model_path_str);
+        let path = std::path::Path::new(model_path);
         if let Some(parent) = path.parent() {
             let fname = path.file_name().unwrap_or_default().to_string_lossy();
             parent
@@ -290,7 +358,7 @@ fn run_extract(file: &PathBuf, dir: PathBuf) -> Result<()> {
                 .to_string_lossy()
                 .replace("\\", "/")
         } else {
-            format!("_rels/{}.rels", model_path_str) // Unlikely for root file but possible
+            format!("_rels/{}.rels", model_path) // Unlikely for root file but possible
         }
     };
 
@@ -304,35 +372,165 @@ fn run_extract(file: &PathBuf, dir: PathBuf) -> Result<()> {
     // Build Map ID -> Target
     let mut rel_map = std::collections::HashMap::new();
     for rel in model_rels {
-        rel_map.insert(rel.id, rel.target);
+        rel_map.insert(rel.id, resolve_part_target(model_path, &rel.target));
     }
 
+    let mut thumbnails = Vec::new();
     for obj in model.resources.iter_objects() {
         if let Some(thumb_ref) = &obj.thumbnail {
-            // Resolve ref
-            let target = rel_map.get(thumb_ref).cloned().or_else(|| {
-                // Maybe it IS a path (legacy or incorrectly written)?
-                Some(thumb_ref.clone())
-            });
-
-            if let Some(path) = target {
-                // Read from archiver
-                let lookup_path = path.trim_start_matches('/');
-                if let Ok(bytes) = archiver.read_entry(lookup_path) {
-                    let fname = format!("obj_{}_thumbnail.png", obj.id.0);
-                    let out = dir.join(fname);
-                    let mut f = File::create(&out)?;
-                    f.write_all(&bytes)?;
-                    println!("  Extracted Object {} Thumbnail: {:?}", obj.id.0, out);
-                } else {
-                    println!(
-                        "  Warning: Object {} thumbnail target '{}' not found in archive.",
-                        obj.id.0, lookup_path
-                    );
-                }
+            // Resolve ref. Fall back to the raw value, which may be a path (legacy or
+            // incorrectly written).
+            let lookup_path = rel_map
+                .get(thumb_ref)
+                .cloned()
+                .unwrap_or_else(|| normalize_part_name(thumb_ref));
+            if let Ok(bytes) = archiver.read_entry(&lookup_path) {
+                thumbnails.push((obj.id.0, bytes));
+            } else {
+                println!(
+                    "  Warning: Object {} thumbnail target '{}' not found in archive.",
+                    obj.id.0, lookup_path
+                );
             }
         }
     }
+    thumbnails.sort_by_key(|(id, _)| *id);
+    thumbnails
+}
 
+/// Writes a grid of all object thumbnails, each fit into a tile of `image.resize`
+/// (256x256 by default), ordered by object ID.
+fn run_contact_sheet(file: &PathBuf, out: &Path, image: &ImageOptions) -> Result<()> {
+    let mut archiver = crate::commands::open_archive(file)?;
+    let model_path = crate::commands::locate_model_part(&mut archiver)?;
+    let model_data = archiver.read_entry(&model_path)?;
+    let model = lib3mf_core::parser::parse_model(std::io::Cursor::new(model_data))?;
+
+    let thumbnails = object_thumbnails(&mut archiver, &model_path, &model);
+    if thumbnails.is_empty() {
+        anyhow::bail!("{:?} has no object thumbnails", file);
+    }
+    let images: Vec<Vec<u8>> = thumbnails.into_iter().map(|(_, data)| data).collect();
+
+    let format = image
+        .convert
+        .unwrap_or_else(|| match out.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("jpg") || ext.eq_ignore_ascii_case("jpeg") => {
+                ThumbnailFormat::Jpeg
+            }
+            _ => ThumbnailFormat::Png,
+        });
+    let sheet = imaging::contact_sheet(&images, image.resize.unwrap_or((256, 256)), format)?;
+    fs::write(out, sheet)?;
+    println!(
+        "Wrote contact sheet of {} thumbnails to {:?}",
+        images.len(),
+        out
+    );
     Ok(())
 }
+
+/// Applies `options` to image data, returning the new data and its file extension.
+/// Data is returned unchanged when no processing is requested.
+fn process_image(data: Vec<u8>, options: &ImageOptions) -> Result<(Vec<u8>, &'static str)> {
+    if options.resize.is_none() && options.convert.is_none() {
+        let ext = image_extension(&data).unwrap_or("png");
+        return Ok((data, ext));
+    }
+    let format = options.convert.unwrap_or(match image_extension(&data) {
+        Some("jpg") => ThumbnailFormat::Jpeg,
+        _ => ThumbnailFormat::Png,
+    });
+    let data = imaging::process(&data, options.resize, format)?;
+    Ok((data, format.extension()))
+}
+
+#[cfg(feature = "image-processing")]
+mod imaging {
+    use super::ThumbnailFormat;
+    use anyhow::Result;
+    use image::imageops::{self, FilterType};
+    use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+    use std::io::Cursor;
+
+    /// Decodes `data`, fits it within `resize` keeping its aspect ratio, and encodes it
+    /// as `format`.
+    pub(super) fn process(
+        data: &[u8],
+        resize: Option<(u32, u32)>,
+        format: ThumbnailFormat,
+    ) -> Result<Vec<u8>> {
+        let mut img = image::load_from_memory(data)?;
+        if let Some((width, height)) = resize {
+            img = img.resize(width, height, FilterType::Lanczos3);
+        }
+        encode(img, format)
+    }
+
+    /// Lays out `images` in a square grid of `tile`-sized cells on a white background,
+    /// each image fit and centered in its cell.
+    pub(super) fn contact_sheet(
+        images: &[Vec<u8>],
+        (tile_width, tile_height): (u32, u32),
+        format: ThumbnailFormat,
+    ) -> Result<Vec<u8>> {
+        let columns = (images.len() as f64).sqrt().ceil() as u32;
+        let rows = (images.len() as u32).div_ceil(columns);
+        let mut sheet = RgbaImage::from_pixel(
+            columns * tile_width,
+            rows * tile_height,
+            Rgba([255, 255, 255, 255]),
+        );
+        for (i, data) in images.iter().enumerate() {
+            let img = image::load_from_memory(data)?
+                .resize(tile_width, tile_height, FilterType::Lanczos3)
+                .to_rgba8();
+            let (column, row) = (i as u32 % columns, i as u32 / columns);
+            let x = column * tile_width + (tile_width - img.width()) / 2;
+            let y = row * tile_height + (tile_height - img.height()) / 2;
+            imageops::overlay(&mut sheet, &img, x.into(), y.into());
+        }
+        encode(DynamicImage::ImageRgba8(sheet), format)
+    }
+
+    fn encode(img: DynamicImage, format: ThumbnailFormat) -> Result<Vec<u8>> {
+        let mut out = Cursor::new(Vec::new());
+        match format {
+            ThumbnailFormat::Png => img.write_to(&mut out, ImageFormat::Png)?,
+            // JPEG has no alpha channel
+            ThumbnailFormat::Jpeg => {
+                DynamicImage::ImageRgb8(img.to_rgb8()).write_to(&mut out, ImageFormat::Jpeg)?
+            }
+        }
+        Ok(out.into_inner())
+    }
+}
+
+#[cfg(not(feature = "image-processing"))]
+mod imaging {
+    use super::ThumbnailFormat;
+    use anyhow::Result;
+
+    pub(super) fn process(
+        _data: &[u8],
+        _resize: Option<(u32, u32)>,
+        _format: ThumbnailFormat,
+    ) -> Result<Vec<u8>> {
+        disabled()
+    }
+
+    pub(super) fn contact_sheet(
+        _images: &[Vec<u8>],
+        _tile: (u32, u32),
+        _format: ThumbnailFormat,
+    ) -> Result<Vec<u8>> {
+        disabled()
+    }
+
+    fn disabled() -> Result<Vec<u8>> {
+        anyhow::bail!(
+            "Resizing, converting and contact sheets require the 'image-processing' feature.\n\
+            The CLI was built without image support."
+        )
+    }
+}
@@ -20,6 +20,7 @@
 //! - **`copy`**: Copy and re-package a 3MF file (verifies read/write cycle)
 //! - **`convert`**: Convert between 3MF, STL, and OBJ formats
 //! - **`validate`**: Validate a 3MF file at various strictness levels
+//! - **`printers`**: List the printer build volumes usable with `--printer`
 //! - **`repair`**: Repair mesh geometry (stitch vertices, remove degenerates, harmonize orientations)
 //! - **`sign`**: Sign a 3MF file using an RSA key (not yet implemented)
 //! - **`verify`**: Verify digital signatures in a 3MF file (requires `crypto` feature)
@@ -57,14 +58,20 @@
 //!
 //! ```no_run
 //! use lib3mf_cli::commands::{OutputFormat, stats, validate};
+//! use lib3mf_core::validation::ValidationOptions;
 //! use std::path::PathBuf;
 //!
 //! # fn main() -> anyhow::Result<()> {
 //! // Generate statistics programmatically
-//! stats(PathBuf::from("model.3mf"), OutputFormat::Json)?;
+//! stats(PathBuf::from("model.3mf"), OutputFormat::Json, None)?;
 //!
 //! // Validate a model
-//! validate(PathBuf::from("model.3mf"), "standard".to_string())?;
+//! validate(
+//!     PathBuf::from("model.3mf"),
+//!     "standard".to_string(),
+//!     None,
+//!     &ValidationOptions::default(),
+//! )?;
 //! # Ok(())
 //! # }
 //! ```
@@ -6,6 +6,9 @@
 use clap::{Parser, Subcommand};
 use lib3mf_cli::commands;
 use lib3mf_cli::commands::{OutputFormat, RepairType};
+use lib3mf_converters::registry::{ExportOptions, FormatRegistry, ImportOptions};
+use lib3mf_converters::transform::{CoordinateSystem, Handedness, ObjectMode, UpAxis};
+use lib3mf_core::model::Unit;
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -21,6 +24,11 @@ use std::path::PathBuf;
 )]
 #[cfg_attr(not(debug_assertions), command(version = env!("CARGO_PKG_VERSION")))]
 struct Cli {
+    /// Root model part to load from packages with several 3D model parts
+    /// (e.g. `3D/plate_2.model`)
+    #[arg(long, global = true, value_name = "PATH")]
+    model_part: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -48,6 +56,21 @@ enum Commands {
     /// # Generate a JSON report for machine parsing
     ///
     /// $ lib3mf stats model.3mf --format json
+    ///
+    /// # Add the estimated memory taken by meshes, materials and other resources
+    ///
+    /// $ lib3mf stats model.3mf --memory
+    ///
+    /// # Report how much memory sharing identical meshes saves
+    ///
+    /// $ lib3mf stats model.3mf --intern-meshes
+    ///
+    /// # Check whether the model fits a printer's build volume
+    ///
+    /// $ lib3mf stats model.3mf --printer bambu-x1c
+    ///
+    /// Statistics stored in the package by `copy --stats-cache` are reported without parsing
+    /// the model, as long as the package is unchanged; --no-cache always computes them.
     Stats {
         /// Path to the 3MF file
         file: PathBuf,
@@ -57,8 +80,69 @@ enum Commands {
         format: OutputFormat,
 
         /// Shortcut for --format tree
-        #[arg(long, short)]
+        #[arg(long, short, conflicts_with = "memory")]
         tree: bool,
+
+        /// Load every mesh and report estimated memory use per resource kind
+        #[arg(long)]
+        memory: bool,
+
+        /// Compute the statistics even if the package has a valid cached copy
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Share identical vertex and triangle arrays between meshes while parsing, and
+        /// report the memory saved with the unique meshes
+        #[arg(long, conflicts_with = "memory")]
+        intern_meshes: bool,
+
+        /// Report whether the model fits this printer's build volume (see `printers`)
+        #[arg(long, value_name = "ID")]
+        printer: Option<String>,
+
+        /// Printers file adding to or replacing the built-in printer presets
+        #[arg(long, value_name = "PATH", requires = "printer")]
+        printers_file: Option<PathBuf>,
+    },
+    /// List the printer presets usable with --printer
+    ///
+    /// Shows the build volume and bed origin of each built-in preset, merged with the
+    /// user printers file: --printers-file, else $LIB3MF_PRINTERS, else
+    /// lib3mf/printers.toml in the user's config directory.
+    ///
+    /// Examples:
+    ///
+    /// $ lib3mf printers
+    ///
+    /// $ lib3mf printers --printers-file my-printers.toml --format json
+    Printers {
+        /// Printers file adding to or replacing the built-in presets
+        #[arg(long, value_name = "PATH")]
+        printers_file: Option<PathBuf>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Summarize a package's extensions, conformance, generator and part sizes
+    ///
+    /// Reports which extensions are declared, listed in requiredextensions and actually
+    /// used, flags requiredextensions problems, and lists every archive entry with its
+    /// compressed and uncompressed size. Also shows whether the file is signed or
+    /// encrypted.
+    ///
+    /// Examples:
+    ///
+    /// $ lib3mf info model.3mf
+    ///
+    /// $ lib3mf info model.3mf --format json
+    Info {
+        /// Path to the 3MF file
+        file: PathBuf,
+
+        /// Output format (text, json)
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
     },
     /// List all entries in the 3MF archive
     ///
@@ -74,6 +158,10 @@ enum Commands {
     /// # Show directory structure as a tree
     ///
     /// $ lib3mf list model.3mf --format tree
+    ///
+    /// # Find what takes up space
+    ///
+    /// $ lib3mf list model.3mf --sort-by-size
     List {
         /// Path to the 3MF file
         file: PathBuf,
@@ -85,6 +173,14 @@ enum Commands {
         /// Shortcut for --format tree
         #[arg(long, short)]
         tree: bool,
+
+        /// Show uncompressed and stored sizes and compression ratios
+        #[arg(long)]
+        sizes: bool,
+
+        /// Sort by stored size, largest first (implies --sizes)
+        #[arg(long)]
+        sort_by_size: bool,
     },
     /// Inspect OPC Relationships and Content Types
     ///
@@ -117,6 +213,14 @@ enum Commands {
     /// # Dump debug view to stdout
     ///
     /// $ lib3mf dump model.3mf
+    ///
+    /// # Dump one resource without its vertices and triangles
+    ///
+    /// $ lib3mf dump model.3mf --object-id 5 --no-geometry --format json
+    ///
+    /// # One JSON line per resource, for jq
+    ///
+    /// $ lib3mf dump model.3mf --ndjson --no-geometry | jq 'select(.kind == "object")'
     Dump {
         /// Path to the 3MF file
         file: PathBuf,
@@ -124,6 +228,26 @@ enum Commands {
         /// Output format (text, json)
         #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
         format: OutputFormat,
+
+        /// Dump only this section of the model
+        #[arg(long, value_enum)]
+        section: Option<commands::dump::DumpSection>,
+
+        /// Dump only the resource with this ID
+        #[arg(long, conflicts_with = "section")]
+        object_id: Option<u32>,
+
+        /// Show this many levels of nesting, summarizing deeper objects and arrays (implies JSON)
+        #[arg(long)]
+        max_depth: Option<usize>,
+
+        /// Replace vertex, triangle and other mesh arrays with their lengths (implies JSON)
+        #[arg(long)]
+        no_geometry: bool,
+
+        /// Print one JSON object per line: each resource, or each entry of --section
+        #[arg(long, conflicts_with = "format")]
+        ndjson: bool,
     },
     /// Extract a file from the archive
     ///
@@ -143,6 +267,14 @@ enum Commands {
     /// # Extract displacement texture by resource ID
     ///
     /// $ lib3mf extract model.3mf --resource-id 100 --output height.png
+    ///
+    /// # Extract every texture and every model part, keeping their package paths
+    ///
+    /// $ lib3mf extract model.3mf --all-textures textures/ --all-models models/
+    ///
+    /// # Extract every JPEG part
+    ///
+    /// $ lib3mf extract model.3mf --content-type image/jpeg --output jpegs/
     Extract {
         /// Path to the 3MF file
         file: PathBuf,
@@ -155,27 +287,198 @@ enum Commands {
         #[arg(long, conflicts_with = "inner_path")]
         resource_id: Option<u32>,
 
-        /// Output path (defaults to stdout)
+        /// Output path (defaults to stdout); the output directory with --content-type alone
         #[arg(long, short)]
         output: Option<PathBuf>,
+
+        /// Extract every texture referenced by the model parts into this directory
+        #[arg(long, value_name = "DIR", conflicts_with_all = ["inner_path", "resource_id"])]
+        all_textures: Option<PathBuf>,
+
+        /// Extract every 3D model part into this directory
+        #[arg(long, value_name = "DIR", conflicts_with_all = ["inner_path", "resource_id"])]
+        all_models: Option<PathBuf>,
+
+        /// Only extract parts with this content type (repeatable). Without --all-textures or
+        /// --all-models, extracts every part of the type into the --output directory
+        #[arg(
+            long = "content-type",
+            value_name = "TYPE",
+            conflicts_with_all = ["inner_path", "resource_id"]
+        )]
+        content_types: Vec<String>,
+    },
+    /// Extract one object and the resources it references into a new 3MF file
+    ///
+    /// Components, materials, textures and thumbnails the object uses come along. With
+    /// --streaming the model is never loaded: the XML of the needed resources is copied
+    /// as is (keeping resource IDs), so single parts can be pulled out of multi-gigabyte
+    /// plates with bounded memory.
+    ///
+    /// Examples:
+    ///
+    /// $ lib3mf extract-object plate.3mf --id 42 -o part.3mf
+    ///
+    /// # Without loading the model
+    ///
+    /// $ lib3mf extract-object huge_plate.3mf --id 42 -o part.3mf --streaming
+    ExtractObject {
+        /// Input 3MF file
+        input: PathBuf,
+
+        /// Resource ID of the object to extract
+        #[arg(long)]
+        id: u32,
+
+        /// Output 3MF file
+        #[arg(long, short)]
+        output: PathBuf,
+
+        /// Copy the resources from the XML without loading the model
+        #[arg(long)]
+        streaming: bool,
+    },
+    /// List objects matching a filter expression
+    ///
+    /// Conditions are <field><op><value>, combined with && and ||. Fields: id, triangles,
+    /// vertices, volume (numbers; = != > >= < <=), name, partnumber (text; = != ~ for
+    /// contains), type (model, support, solidsupport, surface, other) and build (true if
+    /// placed by a build item). Counts and volumes of assemblies include their components.
+    ///
+    /// Examples:
+    ///
+    /// # Model objects with more than a million triangles
+    ///
+    /// $ lib3mf query model.3mf --where "type=model && triangles>1e6"
+    ///
+    /// # The five largest objects by volume, as JSON
+    ///
+    /// $ lib3mf query model.3mf --largest 5 --format json
+    Query {
+        /// Input 3MF file
+        file: PathBuf,
+        /// Filter expression, e.g. "type=model && triangles>1e6"
+        #[arg(long = "where", value_name = "EXPR")]
+        filter: Option<String>,
+        /// Keep only the N largest matching objects by volume
+        #[arg(long, value_name = "N")]
+        largest: Option<usize>,
+        /// Output format (text or json)
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Print a canonical content hash of each file
+    ///
+    /// The digest covers geometry, build placement and materials, and ignores XML
+    /// formatting, resource IDs, metadata and ZIP layout, so files re-saved by different
+    /// tools hash the same when they describe the same part.
+    ///
+    /// Examples:
+    ///
+    /// $ lib3mf hash original.3mf resaved.3mf
+    Hash {
+        /// Input 3MF files
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// Output format (text or json)
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
     },
     /// Copy and re-package a 3MF file (verifies read/write cycle)
     ///
     /// Reads the input file into memory and writes it back to a new file.
     /// This effectively verifies that lib3mf can parse and re-serialize the model without errors.
     ///
+    /// Signed packages are copied part by part without modification so their signatures stay
+    /// valid, unless --strip-signatures is given. Keystores and encrypted parts are always
+    /// preserved unmodified.
+    ///
     /// Examples:
     ///
     /// # Read, parse, and write back to a new file
     ///
     /// $ lib3mf copy source.3mf destination.3mf
+    ///
+    /// # Embed per-object geometry checksums for corruption detection
+    ///
+    /// $ lib3mf copy source.3mf destination.3mf --checksums
+    ///
+    /// # Store the model statistics in the copy so `stats` can skip parsing it
+    ///
+    /// $ lib3mf copy source.3mf destination.3mf --stats-cache
     Copy {
         /// Input 3MF file
         input: PathBuf,
         /// Output 3MF file
         output: PathBuf,
+        /// Remove digital signatures and re-serialize the package instead of copying it as is
+        #[arg(long)]
+        strip_signatures: bool,
+        /// Embed a SHA-256 geometry checksum for every object as metadata, verified by `validate`
+        #[arg(long)]
+        checksums: bool,
+        /// Store the model statistics in the package, reported by `stats` while it is unchanged
+        #[arg(long)]
+        stats_cache: bool,
     },
-    /// Convert between 3D formats (3MF, STL, OBJ)
+    /// Remove optional content to produce a smaller, geometry-focused file
+    ///
+    /// Removes thumbnails, textures, vendor metadata (slicer settings, embedded G-code,
+    /// namespaced metadata) and resources not used by the build, and merges duplicate
+    /// images, textures, material groups and objects. All are applied unless --remove
+    /// selects a subset.
+    ///
+    /// Examples:
+    ///
+    /// # Geometry-only copy for sharing
+    ///
+    /// $ lib3mf strip model.3mf minimal.3mf
+    ///
+    /// # Drop only thumbnails and slicer data
+    ///
+    /// $ lib3mf strip model.3mf out.3mf --remove thumbnails,vendor-metadata
+    ///
+    /// # Merge repeated materials and objects in a bloated export
+    ///
+    /// $ lib3mf strip export.3mf out.3mf --remove duplicates
+    Strip {
+        /// Input 3MF file
+        input: PathBuf,
+        /// Output 3MF file
+        output: PathBuf,
+        /// Content to remove (comma-separated; default: all)
+        #[arg(long, value_enum, value_delimiter = ',')]
+        remove: Vec<commands::strip::StripTarget>,
+    },
+    /// Shrink a package without removing anything the build uses
+    ///
+    /// Removes resources not used by the build, merges duplicate images, textures,
+    /// material groups and objects, re-encodes PNG images at maximum compression and
+    /// compresses the package at the highest Deflate level. Prints the bytes each step
+    /// saved. The output is the same for the same input.
+    ///
+    /// Examples:
+    ///
+    /// # Make a file smaller
+    ///
+    /// $ lib3mf optimize model.3mf small.3mf
+    ///
+    /// # Also shrink large textures and round coordinates to 1 µm
+    ///
+    /// $ lib3mf optimize model.3mf small.3mf --max-image-size 1024 --decimals 3
+    Optimize {
+        /// Input 3MF file
+        input: PathBuf,
+        /// Output 3MF file
+        output: PathBuf,
+        /// Downscale PNG images wider or taller than this to fit (lossy)
+        #[arg(long, value_name = "PIXELS")]
+        max_image_size: Option<u32>,
+        /// Round vertex coordinates to this many decimal places (lossy)
+        #[arg(long, value_name = "N")]
+        decimals: Option<u8>,
+    },
+    /// Convert between 3D formats (3MF, STL, OBJ, JSON)
     ///
     /// Auto-detects the format based on file extensions.
     ///
@@ -189,6 +492,13 @@ enum Commands {
     ///
     /// * 3MF -> OBJ
     ///
+    /// * 3MF <-> JSON (versioned lib3mf model document)
+    ///
+    /// Several inputs, or glob patterns such as "*.stl", are merged into one model with
+    /// each input's objects kept separate and in place. An output directory (an existing
+    /// one, or a path ending in "/") receives one file per object of the build instead, or
+    /// one per disconnected piece with --split-objects.
+    ///
     /// Examples:
     ///
     /// # Import STL to 3MF
@@ -202,14 +512,83 @@ enum Commands {
     /// # Export 3MF to OBJ
     ///
     /// $ lib3mf convert model.3mf mesh.obj
+    ///
+    /// # Export 3MF to JSON for diffing, then back
+    ///
+    /// $ lib3mf convert model.3mf model.json
+    ///
+    /// $ lib3mf convert model.json model.3mf
+    ///
+    /// # Import a Y-up OBJ modelled in inches, welding its seams
+    ///
+    /// $ lib3mf convert part.obj part.3mf --y-up --unit inch --weld-epsilon 0.0001
+    ///
+    /// # Export each build item as one merged STL mesh in centimeters
+    ///
+    /// $ lib3mf convert model.3mf mesh.stl --merge-objects --unit centimeter
+    ///
+    /// # Export OBJ with smooth normals, keeping edges sharper than 45° hard
+    ///
+    /// $ lib3mf convert model.3mf mesh.obj --normals 45
+    ///
+    /// # Export OBJ with one object per build item
+    ///
+    /// $ lib3mf convert assembly.3mf assembly.obj --item-groups
+    ///
+    /// # Merge STL parts into one 3MF plate, one object per file
+    ///
+    /// $ lib3mf convert *.stl plate.3mf
+    ///
+    /// # Write each object of an assembly to its own STL, one per disconnected piece
+    ///
+    /// $ lib3mf convert assembly.3mf --split-objects out_dir/
     Convert {
-        /// Input file
-        input: PathBuf,
-        /// Output file
-        output: PathBuf,
+        /// Input files or glob patterns, then the output file, or a directory to write one
+        /// file per object into
+        #[arg(value_name = "PATH", required = true, num_args = 1..)]
+        paths: Vec<PathBuf>,
+        /// Format of the files written to an output directory
+        #[arg(long, value_name = "EXT", default_value = "stl")]
+        extension: String,
         /// Write ASCII STL instead of binary (only applies when output is .stl)
         #[arg(long, default_value_t = false)]
         ascii: bool,
+        /// Multiply all coordinates by this factor
+        #[arg(long, default_value_t = 1.0)]
+        scale: f32,
+        /// Output unit (micron, millimeter, centimeter, inch, foot, meter). STL and OBJ
+        /// input is read in this unit; 3MF input is converted to it
+        #[arg(long)]
+        unit: Option<Unit>,
+        /// Merge input vertices closer than this distance
+        #[arg(long, default_value_t = 0.0)]
+        weld_epsilon: f32,
+        /// STL and OBJ files are Y-up: rotate to Z-up on import and back on export
+        #[arg(long, conflicts_with = "z_up")]
+        y_up: bool,
+        /// STL and OBJ files are Z-up, like 3MF (default)
+        #[arg(long)]
+        z_up: bool,
+        /// STL and OBJ files use left-handed axes (Unity, Unreal): mirror and fix winding
+        #[arg(long)]
+        left_handed: bool,
+        /// Combine everything in the build into a single object
+        #[arg(long, conflicts_with = "split_objects")]
+        merge_objects: bool,
+        /// Give each disconnected piece of a mesh its own object
+        #[arg(long)]
+        split_objects: bool,
+        /// Bake build item transforms into vertex positions
+        #[arg(long)]
+        apply_transforms: bool,
+        /// Write vertex normals to OBJ output, smooth across edges flatter than this
+        /// crease angle in degrees (default when given without a value: 30)
+        #[arg(long, value_name = "ANGLE", num_args = 0..=1, default_missing_value = "30")]
+        normals: Option<f32>,
+        /// Write each build item as an OBJ object (`o`) holding its meshes, so the parts of
+        /// assemblies stay together
+        #[arg(long)]
+        item_groups: bool,
     },
     /// Validate a 3MF file
     ///
@@ -232,12 +611,60 @@ enum Commands {
     /// # Strict validation
     ///
     /// $ lib3mf validate model.3mf --level strict
+    ///
+    /// # Bounded-memory validation of a multi-gigabyte file
+    ///
+    /// $ lib3mf validate huge.3mf --streaming
     Validate {
         /// Path to the 3MF file
         file: PathBuf,
         /// Validation level (minimal, standard, strict, paranoid)
         #[arg(long, default_value = "standard")]
         level: String,
+        /// Validate with the streaming parser without loading meshes into memory.
+        /// Checks references and index bounds only; skips adjacency-based geometry checks.
+        #[arg(long, default_value_t = false)]
+        streaming: bool,
+        /// Require the model to fit this printer's build volume (see `printers`)
+        #[arg(long, value_name = "ID", conflicts_with = "streaming")]
+        printer: Option<String>,
+        /// Printers file adding to or replacing the built-in printer presets
+        #[arg(long, value_name = "PATH", requires = "printer")]
+        printers_file: Option<PathBuf>,
+        /// Maximum nesting depth of boolean shapes before validation reports an error
+        #[arg(long, value_name = "N", default_value_t = lib3mf_core::validation::DEFAULT_MAX_BOOLEAN_DEPTH, conflicts_with = "streaming")]
+        max_boolean_depth: u32,
+    },
+    /// Run a directory of samples as a conformance suite
+    ///
+    /// Loads and validates every .3mf file below the suite directory and prints a pass/fail
+    /// matrix. Samples named or filed under MUSTFAIL (or prefixed N_) must be rejected;
+    /// all others must load without validation errors. By default samples that must pass
+    /// are validated at standard level and samples that must fail at strict level.
+    /// Exits with an error if any sample did not behave as expected.
+    ///
+    /// Examples:
+    ///
+    /// # Run the 3MF Consortium samples
+    ///
+    /// $ lib3mf conformance --suite 3mf-samples/
+    ///
+    /// # Write a JUnit report for CI
+    ///
+    /// $ lib3mf conformance --suite samples/ --report junit.xml
+    Conformance {
+        /// Directory containing the samples (searched recursively)
+        #[arg(long)]
+        suite: PathBuf,
+        /// Write a JUnit XML report to this path
+        #[arg(long)]
+        report: Option<PathBuf>,
+        /// Validation level for every sample (minimal, standard, strict, paranoid)
+        #[arg(long)]
+        level: Option<String>,
+        /// Output format (text or json)
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
     },
     /// Repair a 3MF mesh
     ///
@@ -250,6 +677,17 @@ enum Commands {
     /// # Repair a mesh
     ///
     /// $ lib3mf repair broken.3mf fixed.3mf
+    ///
+    /// # Fill holes in objects 2 and 5 only
+    ///
+    /// $ lib3mf repair broken.3mf fixed.3mf --fix holes --object-id 2,5
+    ///
+    /// # Treat triangles up to 0.0001 mm² as degenerate
+    ///
+    /// $ lib3mf repair scan.3mf fixed.3mf --degenerate-area 1e-4
+    ///
+    /// Island removal and hole filling are skipped for support, surface and other objects,
+    /// which need not be manifold.
     Repair {
         /// Input file
         input: PathBuf,
@@ -268,6 +706,189 @@ enum Commands {
             default_value = "degenerate,duplicates,harmonize"
         )]
         fixes: Vec<RepairType>,
+
+        /// Repair only these objects (comma-separated IDs); all mesh objects by default
+        #[arg(long = "object-id", value_delimiter = ',')]
+        object_ids: Vec<u32>,
+
+        /// Area in square millimeters at or below which a triangle is degenerate; by
+        /// default a tiny fraction of each mesh's squared bounding-box diagonal
+        #[arg(long)]
+        degenerate_area: Option<f64>,
+    },
+    /// Cut objects along a plane to fit a build volume
+    ///
+    /// Splits every object in the build along a plane given in build coordinates and
+    /// model units, and closes the cut faces so each half is watertight. With `--pins`,
+    /// the lower half gets alignment pegs and the upper half matching sockets.
+    ///
+    /// Examples:
+    ///
+    /// # Cut at 100 above the build plate, keeping both halves
+    ///
+    /// $ lib3mf cut model.3mf out.3mf --plane z=100
+    ///
+    /// # Keep only the part below a tilted plane
+    ///
+    /// $ lib3mf cut model.3mf out.3mf --plane 0,1,1,80 --keep below
+    ///
+    /// # Two 4 mm pins per cut face
+    ///
+    /// $ lib3mf cut model.3mf out.3mf --plane z=100 --pins --pin-diameter 4mm
+    Cut {
+        /// Input 3MF file
+        input: PathBuf,
+        /// Output 3MF file
+        output: PathBuf,
+
+        /// Cut plane: x=<value>, y=<value>, z=<value>, or a,b,c,d for ax+by+cz=d
+        #[arg(long, allow_hyphen_values = true)]
+        plane: lib3mf_core::model::Plane,
+
+        /// Halves to keep (above, below, both)
+        #[arg(long, default_value = "both")]
+        keep: lib3mf_core::model::Side,
+
+        /// Add alignment pins between the halves (requires --keep both)
+        #[arg(long)]
+        pins: bool,
+
+        /// Pins per cut face
+        #[arg(long, default_value_t = 2)]
+        pin_count: usize,
+
+        /// Pin diameter
+        #[arg(long, default_value = "5mm", value_parser = commands::hollow::parse_length)]
+        pin_diameter: commands::hollow::LengthArg,
+
+        /// Pin height above the cut
+        #[arg(long, default_value = "6mm", value_parser = commands::hollow::parse_length)]
+        pin_length: commands::hollow::LengthArg,
+
+        /// Gap between pin and socket
+        #[arg(long, default_value = "0.2mm", value_parser = commands::hollow::parse_length)]
+        pin_clearance: commands::hollow::LengthArg,
+
+        /// Cut only build items of these objects (comma-separated IDs); all by default
+        #[arg(long = "object-id", value_delimiter = ',')]
+        object_ids: Vec<u32>,
+    },
+    /// Hollow solid objects into shells for resin printing
+    ///
+    /// Replaces each model object's mesh with a closed shell of the given wall thickness,
+    /// remeshed from a signed distance field. Lengths without a unit suffix are in the
+    /// model's unit; `mm`, `cm`, `in`, `ft`, `m` and `um` suffixes are converted.
+    ///
+    /// Examples:
+    ///
+    /// # 2 mm walls with a 3 mm drain hole through the bottom
+    ///
+    /// $ lib3mf hollow model.3mf out.3mf --thickness 2 --drain-hole 3mm
+    ///
+    /// # Hollow only object 5, on a finer grid
+    ///
+    /// $ lib3mf hollow model.3mf out.3mf --thickness 1.5mm --object-id 5 --voxel-size 0.2mm
+    ///
+    /// Per-triangle properties and triangle sets of hollowed objects are not kept.
+    Hollow {
+        /// Input 3MF file
+        input: PathBuf,
+        /// Output 3MF file
+        output: PathBuf,
+
+        /// Wall thickness
+        #[arg(long, value_parser = commands::hollow::parse_length)]
+        thickness: commands::hollow::LengthArg,
+
+        /// Diameter of a drain hole through the bottom of each cavity
+        #[arg(long, value_parser = commands::hollow::parse_length)]
+        drain_hole: Option<commands::hollow::LengthArg>,
+
+        /// Remeshing voxel size (default: 1/128 of the object size, finer for thin walls)
+        #[arg(long, value_parser = commands::hollow::parse_length)]
+        voxel_size: Option<commands::hollow::LengthArg>,
+
+        /// Hollow only these objects (comma-separated IDs); all model objects by default
+        #[arg(long = "object-id", value_delimiter = ',')]
+        object_ids: Vec<u32>,
+    },
+    /// Fill solid objects with a beam lattice for lightweighting
+    ///
+    /// Hollows each model object and fills the cavity with a periodic lattice of beams,
+    /// written as Beam Lattice extension data. Lengths take the same unit suffixes as
+    /// `hollow`.
+    ///
+    /// Examples:
+    ///
+    /// # Octet truss in 8 mm cells with 0.6 mm beams
+    ///
+    /// $ lib3mf lattice-fill model.3mf out.3mf --pattern octet --cell-size 8mm --beam-radius 0.6mm
+    ///
+    /// # Gyroid-like lattice at 15% density inside 1.5 mm walls
+    ///
+    /// $ lib3mf lattice-fill model.3mf out.3mf --pattern gyroid --density 0.15 --shell 1.5mm
+    LatticeFill {
+        /// Input 3MF file
+        input: PathBuf,
+        /// Output 3MF file
+        output: PathBuf,
+
+        /// Lattice pattern (grid, octet, gyroid)
+        #[arg(long, default_value = "grid")]
+        pattern: lib3mf_core::model::LatticePattern,
+
+        /// Edge length of a lattice cell
+        #[arg(long, default_value = "5mm", value_parser = commands::hollow::parse_length)]
+        cell_size: commands::hollow::LengthArg,
+
+        /// Beam radius
+        #[arg(long, default_value = "0.5mm", value_parser = commands::hollow::parse_length)]
+        beam_radius: commands::hollow::LengthArg,
+
+        /// Fraction of the interior filled by beams (0-1); sets the beam radius
+        #[arg(long, conflicts_with = "beam_radius")]
+        density: Option<f32>,
+
+        /// Wall thickness of the hollowed skin
+        #[arg(long, default_value = "2mm", value_parser = commands::hollow::parse_length)]
+        shell: commands::hollow::LengthArg,
+
+        /// Keep objects solid and only add the lattice
+        #[arg(long, conflicts_with = "shell")]
+        no_shell: bool,
+
+        /// Distance field voxel size (default: 1/128 of the object size, finer for thin beams)
+        #[arg(long, value_parser = commands::hollow::parse_length)]
+        voxel_size: Option<commands::hollow::LengthArg>,
+
+        /// Fill only these objects (comma-separated IDs); all model objects by default
+        #[arg(long = "object-id", value_delimiter = ',')]
+        object_ids: Vec<u32>,
+    },
+    /// Split objects into one object per disconnected shell
+    ///
+    /// Each separate piece of a model object's mesh becomes its own object with the same
+    /// type and material. Cavity surfaces stay with the shell around them. Build items and
+    /// components referencing a split object are repeated for every new object.
+    ///
+    /// Examples:
+    ///
+    /// # Split every model object
+    ///
+    /// $ lib3mf split-shells plate.3mf parts.3mf
+    ///
+    /// # Split only object 3
+    ///
+    /// $ lib3mf split-shells plate.3mf parts.3mf --object-id 3
+    SplitShells {
+        /// Input 3MF file
+        input: PathBuf,
+        /// Output 3MF file
+        output: PathBuf,
+
+        /// Split only these objects (comma-separated IDs); all model objects by default
+        #[arg(long = "object-id", value_delimiter = ',')]
+        object_ids: Vec<u32>,
     },
     /// Sign a 3MF file using an RSA key
     ///
@@ -342,6 +963,41 @@ enum Commands {
         #[arg(long)]
         key: PathBuf,
     },
+    /// Generate a private key and self-signed certificate
+    ///
+    /// Creates keys for trying out signing and encryption without OpenSSL. RSA keys can
+    /// also receive encrypted content; ECDSA and Ed25519 keys can only sign. Self-signed certificates
+    /// are for testing only.
+    ///
+    /// Examples:
+    ///
+    /// # RSA key and certificate
+    ///
+    /// $ lib3mf keygen --out key.pem --cert cert.pem
+    ///
+    /// # ECDSA P-256 key with a custom subject, valid for 30 days
+    ///
+    /// $ lib3mf keygen --out key.pem --cert cert.pem --type ecdsa-p256 --subject "CN=Alice,O=Example" --days 30
+    Keygen {
+        /// Output path of the PEM private key (PKCS#8)
+        #[arg(long)]
+        out: PathBuf,
+        /// Output path of the PEM self-signed certificate
+        #[arg(long)]
+        cert: Option<PathBuf>,
+        /// Key type
+        #[arg(long = "type", value_enum, default_value_t)]
+        key_type: commands::keygen::KeyType,
+        /// RSA modulus size in bits
+        #[arg(long, default_value_t = 2048)]
+        bits: usize,
+        /// Certificate subject distinguished name
+        #[arg(long, default_value = "CN=lib3mf test key")]
+        subject: String,
+        /// Certificate validity in days
+        #[arg(long, default_value_t = 365)]
+        days: u32,
+    },
     /// Benchmark loading and parsing speed
     ///
     /// measures the time taken for various stages of the loading process:
@@ -352,6 +1008,11 @@ enum Commands {
     ///
     /// - Statistics calculation
     ///
+    /// Optionally also serialization (--write), mesh repair (--repair), and every
+    /// validation level (--validate). Each phase reports min/median/p95 over
+    /// --iterations samples after --warmup discarded runs. Times are those the library
+    /// records in its timing spans, so they exclude the surrounding file I/O.
+    ///
     /// Useful for performance profiling.
     ///
     /// Examples:
@@ -359,9 +1020,31 @@ enum Commands {
     /// # Benchmark a model
     ///
     /// $ lib3mf benchmark massive_model.3mf
+    ///
+    /// # All phases, 10 samples after 2 warmups, JSON for CI dashboards
+    ///
+    /// $ lib3mf benchmark model.3mf --write --repair --validate -n 10 --warmup 2 --format json
     Benchmark {
         /// Path to the 3MF file
         file: PathBuf,
+        /// Number of measured iterations per phase
+        #[arg(short = 'n', long, default_value_t = 1)]
+        iterations: usize,
+        /// Number of unmeasured warmup iterations per phase
+        #[arg(long, default_value_t = 0)]
+        warmup: usize,
+        /// Also benchmark writing the model to a 3MF package (in memory)
+        #[arg(long, default_value_t = false)]
+        write: bool,
+        /// Also benchmark mesh repair with default options
+        #[arg(long, default_value_t = false)]
+        repair: bool,
+        /// Also benchmark validation at every level
+        #[arg(long, default_value_t = false)]
+        validate: bool,
+        /// Output format (text or json)
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
     },
     /// Compare two 3MF files
     ///
@@ -374,11 +1057,22 @@ enum Commands {
     ///
     /// - Build item counts
     ///
+    /// With --deviation, also measures how far the surfaces of mesh objects
+    /// present in both files moved (Hausdorff and RMS distance).
+    ///
     /// Examples:
     ///
     /// # Diff two files
     ///
     /// $ lib3mf diff v1.3mf v2.3mf
+    ///
+    /// # Report the maximum geometric deviation between two versions
+    ///
+    /// $ lib3mf diff v1.3mf v2.3mf --deviation
+    ///
+    /// # Fail if any part moved by more than 0.05 mm
+    ///
+    /// $ lib3mf diff v1.3mf v2.3mf --tolerance 0.05mm
     Diff {
         /// First file
         file1: PathBuf,
@@ -387,6 +1081,12 @@ enum Commands {
         /// Output format (text, json)
         #[arg(long, default_value = "text")]
         format: String,
+        /// Measure the geometric deviation of mesh objects present in both files
+        #[arg(long)]
+        deviation: bool,
+        /// Fail if any deviation exceeds this length (e.g. 0.05mm); implies --deviation
+        #[arg(long, value_parser = commands::hollow::parse_length)]
+        tolerance: Option<commands::hollow::LengthArg>,
     },
     /// Manage thumbnails (extract, inject, list)
     ///
@@ -400,6 +1100,12 @@ enum Commands {
     ///
     /// # Inject object thumbnail
     /// $ lib3mf thumbnails input.3mf --inject thumb.png --oid 1
+    ///
+    /// # Extract thumbnails as 128x128 JPEGs
+    /// $ lib3mf thumbnails input.3mf --extract thumbs/ --resize 128x128 --convert jpeg
+    ///
+    /// # Grid of all object thumbnails for a catalog
+    /// $ lib3mf thumbnails input.3mf --contact-sheet sheet.png
     Thumbnails {
         /// Input 3MF file
         file: PathBuf,
@@ -412,9 +1118,18 @@ enum Commands {
         /// Inject image file
         #[arg(long)]
         inject: Option<PathBuf>,
-        /// Target Object ID (for injection)
+        /// Target Object ID (for injection; PNG or JPEG, stored under 3D/Thumbnails/)
         #[arg(long)]
         oid: Option<u32>,
+        /// Write a grid of all object thumbnails to this image file
+        #[arg(long)]
+        contact_sheet: Option<PathBuf>,
+        /// Fit extracted or injected images within WxH (contact sheet tile size, default 256x256)
+        #[arg(long, value_name = "WxH", value_parser = commands::thumbnails::parse_size)]
+        resize: Option<(u32, u32)>,
+        /// Re-encode extracted or injected images as png or jpeg
+        #[arg(long, value_enum)]
+        convert: Option<commands::thumbnails::ThumbnailFormat>,
     },
     /// Merge multiple 3MF files into one
     ///
@@ -460,6 +1175,14 @@ enum Commands {
         #[arg(long, value_enum, default_value_t = commands::merge::PackAlgorithm::Grid)]
         pack: commands::merge::PackAlgorithm,
 
+        /// Center the --single-plate arrangement on this printer's bed (see `printers`)
+        #[arg(long, value_name = "ID", requires = "single_plate")]
+        printer: Option<String>,
+
+        /// Printers file adding to or replacing the built-in printer presets
+        #[arg(long, value_name = "PATH", requires = "printer")]
+        printers_file: Option<PathBuf>,
+
         /// Suppress all output
         #[arg(long, conflicts_with = "verbose")]
         quiet: bool,
@@ -619,56 +1342,314 @@ enum Commands {
 
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    commands::set_model_part(cli.model_part);
 
     match cli.command {
-        Commands::Stats { file, format, tree } => {
+        Commands::Stats {
+            file,
+            format,
+            tree,
+            memory,
+            no_cache,
+            intern_meshes,
+            printer,
+            printers_file,
+        } => {
             let format = if tree { OutputFormat::Tree } else { format };
-            commands::stats(file, format)?;
+            let printer = find_printer(printer, printers_file)?;
+            if memory {
+                commands::stats_with_memory(file, format, printer.as_ref())?;
+            } else if intern_meshes {
+                commands::stats_interned(file, format, printer.as_ref())?;
+            } else if no_cache {
+                commands::stats_uncached(file, format, printer.as_ref())?;
+            } else {
+                commands::stats(file, format, printer.as_ref())?;
+            }
+        }
+        Commands::Printers {
+            printers_file,
+            format,
+        } => {
+            commands::printer::run(printers_file, format)?;
+        }
+        Commands::Info { file, format } => {
+            commands::info::run(file, format)?;
         }
-        Commands::List { file, format, tree } => {
+        Commands::List {
+            file,
+            format,
+            tree,
+            sizes,
+            sort_by_size,
+        } => {
             let format = if tree { OutputFormat::Tree } else { format };
-            commands::list(file, format)?;
+            commands::list(file, format, sizes, sort_by_size)?;
         }
         Commands::Rels { file, format } => {
             commands::rels(file, format)?;
         }
-        Commands::Dump { file, format } => {
-            commands::dump(file, format)?;
+        Commands::Dump {
+            file,
+            format,
+            section,
+            object_id,
+            max_depth,
+            no_geometry,
+            ndjson,
+        } => {
+            commands::dump::run(
+                file,
+                &commands::dump::DumpOptions {
+                    format,
+                    section,
+                    object_id,
+                    max_depth,
+                    no_geometry,
+                    ndjson,
+                },
+            )?;
         }
         Commands::Extract {
             file,
             inner_path,
             resource_id,
             output,
+            all_textures,
+            all_models,
+            content_types,
         } => {
-            if let Some(rid) = resource_id {
+            if all_textures.is_some() || all_models.is_some() || !content_types.is_empty() {
+                commands::extract_parts::run(
+                    file,
+                    &commands::extract_parts::PartSelection {
+                        textures: all_textures,
+                        models: all_models,
+                        content_types,
+                        output,
+                    },
+                )?;
+            } else if let Some(rid) = resource_id {
                 commands::extract_by_resource_id(file, rid, output)?;
             } else if let Some(path) = inner_path {
                 commands::extract(file, path, output)?;
             } else {
-                anyhow::bail!("Either inner_path or --resource-id must be provided");
+                anyhow::bail!(
+                    "Either inner_path, --resource-id, --all-textures, --all-models or --content-type must be provided"
+                );
             }
         }
-        Commands::Copy { input, output } => {
-            commands::copy(input, output)?;
+        Commands::ExtractObject {
+            input,
+            id,
+            output,
+            streaming,
+        } => {
+            commands::extract_object::run(input, id, output, streaming)?;
         }
-        Commands::Convert {
+        Commands::Copy {
             input,
             output,
+            strip_signatures,
+            checksums,
+            stats_cache,
+        } => {
+            commands::copy::run(input, output, strip_signatures, checksums, stats_cache)?;
+        }
+        Commands::Query {
+            file,
+            filter,
+            largest,
+            format,
+        } => {
+            commands::query::run(file, filter.as_deref(), largest, format)?;
+        }
+        Commands::Hash { files, format } => {
+            commands::hash::run(&files, format)?;
+        }
+        Commands::Strip {
+            input,
+            output,
+            remove,
+        } => {
+            let targets = if remove.is_empty() {
+                commands::strip::StripTarget::ALL.to_vec()
+            } else {
+                remove
+            };
+            commands::strip::run(input, output, &targets)?;
+        }
+        Commands::Optimize {
+            input,
+            output,
+            max_image_size,
+            decimals,
+        } => {
+            let options = commands::optimize::OptimizeOptions {
+                max_image_size,
+                decimals,
+            };
+            commands::optimize::run(input, output, options)?;
+        }
+        Commands::Convert {
+            mut paths,
+            extension,
             ascii,
+            scale,
+            unit,
+            weld_epsilon,
+            y_up,
+            z_up: _,
+            left_handed,
+            merge_objects,
+            split_objects,
+            apply_transforms,
+            normals,
+            item_groups,
         } => {
-            commands::convert(input, output, ascii)?;
+            let axes = CoordinateSystem::new(
+                if y_up { UpAxis::Y } else { UpAxis::Z },
+                if left_handed {
+                    Handedness::Left
+                } else {
+                    Handedness::Right
+                },
+            );
+            let mut import = ImportOptions::default();
+            import.scale = scale;
+            import.weld_epsilon = weld_epsilon;
+            import.unit = unit.unwrap_or(Unit::Millimeter);
+            import.axes = axes;
+            let mut export = ExportOptions::default();
+            export.ascii = ascii;
+            export.unit = unit;
+            export.axes = axes;
+            export.apply_transforms = apply_transforms;
+            export.normals = normals;
+            export.item_groups = item_groups;
+            export.objects = if merge_objects {
+                ObjectMode::Merge
+            } else if split_objects {
+                ObjectMode::Split
+            } else {
+                ObjectMode::Keep
+            };
+            let registry = FormatRegistry::with_builtins();
+            if paths.len() < 2 {
+                anyhow::bail!("convert needs at least one input and an output");
+            }
+            let output = paths.pop().expect("checked above");
+            let mut inputs = commands::expand_globs(paths)?;
+            if commands::is_output_directory(&output) {
+                commands::convert_to_directory(
+                    &inputs, output, &extension, &import, &export, &registry,
+                )?;
+            } else if inputs.len() > 1 {
+                commands::convert_merged(&inputs, output, &import, &export, &registry)?;
+            } else {
+                let input = inputs.remove(0);
+                commands::convert_with_registry(input, output, &import, &export, &registry)?;
+            }
         }
-        Commands::Validate { file, level } => {
-            commands::validate(file, level)?;
+        Commands::Validate {
+            file,
+            level,
+            streaming,
+            printer,
+            printers_file,
+            max_boolean_depth,
+        } => {
+            if streaming {
+                commands::validate_streaming(file, level)?;
+            } else {
+                let printer = find_printer(printer, printers_file)?;
+                let options = lib3mf_core::validation::ValidationOptions { max_boolean_depth };
+                commands::validate(file, level, printer.as_ref(), &options)?;
+            }
+        }
+        Commands::Conformance {
+            suite,
+            report,
+            level,
+            format,
+        } => {
+            commands::conformance::run(suite, report, level, format)?;
         }
         Commands::Repair {
             input,
             output,
             epsilon,
             fixes,
+            object_ids,
+            degenerate_area,
+        } => {
+            commands::repair(input, output, epsilon, fixes, object_ids, degenerate_area)?;
+        }
+        Commands::Cut {
+            input,
+            output,
+            plane,
+            keep,
+            pins,
+            pin_count,
+            pin_diameter,
+            pin_length,
+            pin_clearance,
+            object_ids,
+        } => {
+            let pins = pins.then_some(commands::cut::PinArgs {
+                count: pin_count,
+                diameter: pin_diameter,
+                length: pin_length,
+                clearance: pin_clearance,
+            });
+            commands::cut::run(input, output, plane, keep, pins.as_ref(), &object_ids)?;
+        }
+        Commands::Hollow {
+            input,
+            output,
+            thickness,
+            drain_hole,
+            voxel_size,
+            object_ids,
+        } => {
+            commands::hollow::run(
+                input,
+                output,
+                thickness,
+                drain_hole,
+                voxel_size,
+                &object_ids,
+            )?;
+        }
+        Commands::LatticeFill {
+            input,
+            output,
+            pattern,
+            cell_size,
+            beam_radius,
+            density,
+            shell,
+            no_shell,
+            voxel_size,
+            object_ids,
+        } => {
+            let args = commands::lattice_fill::LatticeArgs {
+                pattern,
+                cell_size,
+                beam_radius,
+                density,
+                shell: (!no_shell).then_some(shell),
+                voxel_size,
+            };
+            commands::lattice_fill::run(input, output, &args, &object_ids)?;
+        }
+        Commands::SplitShells {
+            input,
+            output,
+            object_ids,
         } => {
-            commands::repair(input, output, epsilon, fixes)?;
+            commands::split_shells::run(input, output, &object_ids)?;
         }
         Commands::Sign {
             input,
@@ -691,15 +1672,45 @@ fn main() -> anyhow::Result<()> {
         Commands::Decrypt { input, output, key } => {
             commands::decrypt(input, output, key)?;
         }
-        Commands::Benchmark { file } => {
-            commands::benchmark(file)?;
+        Commands::Keygen {
+            out,
+            cert,
+            key_type,
+            bits,
+            subject,
+            days,
+        } => {
+            commands::keygen::run(out, cert, key_type, bits, &subject, days)?;
+        }
+        Commands::Benchmark {
+            file,
+            iterations,
+            warmup,
+            write,
+            repair,
+            validate,
+            format,
+        } => {
+            commands::benchmark::run(
+                file,
+                commands::benchmark::BenchmarkOptions {
+                    iterations,
+                    warmup,
+                    write,
+                    repair,
+                    validate,
+                    format,
+                },
+            )?;
         }
         Commands::Diff {
             file1,
             file2,
             format,
+            deviation,
+            tolerance,
         } => {
-            commands::diff(file1, file2, &format)?;
+            commands::diff(file1, file2, &format, deviation, tolerance.as_ref())?;
         }
         Commands::Thumbnails {
             file,
@@ -707,8 +1718,12 @@ fn main() -> anyhow::Result<()> {
             extract,
             inject,
             oid,
+            contact_sheet,
+            resize,
+            convert,
         } => {
-            commands::thumbnails::run(file, list, extract, inject, oid)?;
+            let image = commands::thumbnails::ImageOptions { resize, convert };
+            commands::thumbnails::run(file, list, extract, inject, oid, contact_sheet, &image)?;
         }
         Commands::Merge {
             inputs,
@@ -717,6 +1732,8 @@ fn main() -> anyhow::Result<()> {
             single_plate,
             plate_per_file: _,
             pack,
+            printer,
+            printers_file,
             quiet,
             verbose,
         } => {
@@ -727,7 +1744,16 @@ fn main() -> anyhow::Result<()> {
             } else {
                 commands::merge::Verbosity::Normal
             };
-            commands::merge::run(inputs, output, force, single_plate, pack, verbosity)?;
+            let printer = find_printer(printer, printers_file)?;
+            commands::merge::run(
+                inputs,
+                output,
+                force,
+                single_plate,
+                pack,
+                printer.as_ref(),
+                verbosity,
+            )?;
         }
         Commands::Split {
             input,
@@ -815,3 +1841,12 @@ fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Looks up the printer chosen with `--printer`, if any.
+fn find_printer(
+    id: Option<String>,
+    printers_file: Option<PathBuf>,
+) -> anyhow::Result<Option<commands::printer::Printer>> {
+    id.map(|id| commands::printer::find_printer(&id, printers_file.as_deref()))
+        .transpose()
+}
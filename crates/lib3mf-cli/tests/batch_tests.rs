@@ -58,6 +58,7 @@ fn create_test_3mf(dir: &Path, name: &str) -> PathBuf {
         part_number: None,
         transform: glam::Mat4::IDENTITY,
         printable: None,
+        metadata: Default::default(),
     });
 
     let model = Model {
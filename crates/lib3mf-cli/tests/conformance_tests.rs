@@ -0,0 +1,95 @@
+//! Integration tests for `3mf conformance`.
+
+use lib3mf_cli::commands::OutputFormat;
+use lib3mf_cli::commands::conformance;
+use std::io::Write;
+use std::path::Path;
+use tempfile::TempDir;
+use zip::write::SimpleFileOptions;
+
+const MODEL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+  <resources>
+    <object id="1" type="model">
+      <mesh>
+        <vertices>
+          <vertex x="0" y="0" z="0"/><vertex x="1" y="0" z="0"/><vertex x="0" y="1" z="0"/>
+        </vertices>
+        <triangles><triangle v1="0" v2="1" v3="2"/></triangles>
+      </mesh>
+    </object>
+  </resources>
+  <build><item objectid="1"/></build>
+</model>"#;
+
+const RELS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rel0" Target="/3D/3dmodel.model" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel"/>
+</Relationships>"#;
+
+fn write_sample(path: &Path, model: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let mut writer = zip::ZipWriter::new(std::fs::File::create(path).unwrap());
+    let options = SimpleFileOptions::default();
+    writer.start_file("_rels/.rels", options).unwrap();
+    writer.write_all(RELS.as_bytes()).unwrap();
+    writer.start_file("3D/3dmodel.model", options).unwrap();
+    writer.write_all(model.as_bytes()).unwrap();
+    writer.finish().unwrap();
+}
+
+#[test]
+fn test_conformance_suite_passes_and_writes_junit() {
+    let dir = TempDir::new().unwrap();
+    let suite = dir.path().join("suite");
+    write_sample(&suite.join("MUSTPASS/good.3mf"), MODEL);
+    write_sample(&suite.join("MUSTFAIL/bad.3mf"), "<model");
+    let junit = dir.path().join("junit.xml");
+
+    conformance::run(suite, Some(junit.clone()), None, OutputFormat::Text).unwrap();
+
+    let xml = std::fs::read_to_string(&junit).unwrap();
+    assert!(xml.contains("tests=\"2\" failures=\"0\""), "{xml}");
+}
+
+#[test]
+fn test_conformance_fails_on_unexpected_result() {
+    let dir = TempDir::new().unwrap();
+    write_sample(&dir.path().join("MUSTFAIL/accepted.3mf"), MODEL);
+    let junit = dir.path().join("junit.xml");
+
+    let err = conformance::run(
+        dir.path().to_path_buf(),
+        Some(junit.clone()),
+        Some("paranoid".to_string()),
+        OutputFormat::Json,
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("1 of 1 sample(s)"), "{err}");
+    // The report is still written so CI can show the failure
+    assert!(
+        std::fs::read_to_string(&junit)
+            .unwrap()
+            .contains("<failure ")
+    );
+}
+
+#[test]
+fn test_conformance_rejects_empty_suite_and_bad_level() {
+    let dir = TempDir::new().unwrap();
+    let err =
+        conformance::run(dir.path().to_path_buf(), None, None, OutputFormat::Text).unwrap_err();
+    assert!(err.to_string().contains("No .3mf samples"), "{err}");
+
+    let err = conformance::run(
+        dir.path().to_path_buf(),
+        None,
+        Some("extreme".to_string()),
+        OutputFormat::Text,
+    )
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("Unknown validation level"),
+        "{err}"
+    );
+}
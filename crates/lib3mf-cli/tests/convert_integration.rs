@@ -1,9 +1,15 @@
-use lib3mf_cli::commands::convert;
+use lib3mf_cli::commands::{
+    convert, convert_merged, convert_to_directory, convert_with_registry, expand_globs,
+};
+use lib3mf_converters::registry::{ExportOptions, FormatRegistry, ImportOptions, ModelExporter};
+use lib3mf_converters::transform::{CoordinateSystem, ObjectMode};
 use lib3mf_core::model::{
-    Build, BuildItem, Geometry, Mesh, Model, Object, ObjectType, ResourceCollection, ResourceId,
+    Build, BuildItem, Component, Components, Geometry, Mesh, Model, Object, ObjectType,
+    ResourceCollection, ResourceId,
 };
 use std::fs::{self, File};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Create a minimal single-triangle 3MF file at the given path.
 ///
@@ -40,6 +46,7 @@ fn write_minimal_3mf(path: &Path) {
         part_number: None,
         transform: glam::Mat4::IDENTITY,
         printable: None,
+        metadata: Default::default(),
     });
 
     let model = Model {
@@ -99,7 +106,7 @@ fn test_convert_3mf_to_binary_stl_default() {
 
     // Binary STL format: 80-byte header + 4-byte triangle count + N * 50 bytes per triangle
     // For 1 triangle: 80 + 4 + 1*50 = 134 bytes
-    let expected_size = 80 + 4 + 1 * 50;
+    let expected_size = 80 + 4 + 50;
     assert_eq!(
         bytes.len(),
         expected_size,
@@ -145,3 +152,424 @@ fn test_convert_benchy_to_ascii_stl() {
 
     let _ = fs::remove_file(&output);
 }
+
+#[test]
+fn test_convert_3mf_to_json_and_back() {
+    let tmp = std::env::temp_dir();
+    let input = tmp.join("lib3mf_test_json_in.3mf");
+    let json = tmp.join("lib3mf_test_json.json");
+    let output = tmp.join("lib3mf_test_json_out.3mf");
+
+    write_minimal_3mf(&input);
+    convert(input.clone(), json.clone(), false).expect("3MF to JSON conversion failed");
+
+    let text = fs::read_to_string(&json).expect("Failed to read JSON output");
+    let model = Model::from_json(&text).expect("Output must be a model document");
+    assert_eq!(model.build.items.len(), 1);
+
+    convert(json.clone(), output.clone(), false).expect("JSON to 3MF conversion failed");
+    let file = File::open(&output).expect("Failed to open converted 3MF");
+    let mut archiver =
+        lib3mf_core::archive::ZipArchiver::new(file).expect("Converted 3MF must be a ZIP");
+    let model_path = lib3mf_core::archive::find_model_path(&mut archiver).unwrap();
+    let data = lib3mf_core::archive::ArchiveReader::read_entry(&mut archiver, &model_path).unwrap();
+    let model = lib3mf_core::parser::parse_model(std::io::Cursor::new(data)).unwrap();
+    let Geometry::Mesh(mesh) = &model.resources.get_object(ResourceId(1)).unwrap().geometry else {
+        panic!("Object 1 must be a mesh");
+    };
+    assert_eq!((mesh.vertices.len(), mesh.triangles.len()), (3, 1));
+
+    let _ = fs::remove_file(&input);
+    let _ = fs::remove_file(&json);
+    let _ = fs::remove_file(&output);
+}
+
+/// Writes the triangle count of each object, one per line.
+struct TriangleCounts;
+
+impl ModelExporter for TriangleCounts {
+    fn name(&self) -> &str {
+        "Triangle counts"
+    }
+    fn extensions(&self) -> &[&str] {
+        &["tris"]
+    }
+    fn export(
+        &self,
+        model: &Model,
+        path: &Path,
+        _options: &ExportOptions,
+    ) -> lib3mf_core::error::Result<()> {
+        let lines: Vec<String> = model
+            .resources
+            .iter_objects()
+            .filter_map(|o| match &o.geometry {
+                Geometry::Mesh(mesh) => Some(mesh.triangles.len().to_string()),
+                _ => None,
+            })
+            .collect();
+        fs::write(path, lines.join("\n"))?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_convert_with_custom_exporter() {
+    let tmp = std::env::temp_dir();
+    let input = tmp.join("lib3mf_test_registry_in.3mf");
+    let output = tmp.join("lib3mf_test_registry_out.tris");
+
+    write_minimal_3mf(&input);
+    assert!(convert(input.clone(), output.clone(), false).is_err());
+
+    let mut registry = FormatRegistry::with_builtins();
+    registry.register_exporter(Arc::new(TriangleCounts));
+    convert_with_registry(
+        input.clone(),
+        output.clone(),
+        &ImportOptions::default(),
+        &ExportOptions::default(),
+        &registry,
+    )
+    .expect("Custom format conversion failed");
+    assert_eq!(fs::read_to_string(&output).unwrap(), "1");
+
+    let _ = fs::remove_file(&input);
+    let _ = fs::remove_file(&output);
+}
+
+#[test]
+fn test_convert_with_unit_and_axis_options() {
+    let tmp = std::env::temp_dir();
+    let input = tmp.join("lib3mf_test_options_in.3mf");
+    let output = tmp.join("lib3mf_test_options_out.obj");
+    let roundtrip = tmp.join("lib3mf_test_options_back.3mf");
+
+    write_minimal_3mf(&input);
+    let mut export = ExportOptions::default();
+    export.unit = Some(lib3mf_core::model::Unit::Centimeter);
+    export.axes = CoordinateSystem::Y_UP_RIGHT;
+    convert_with_registry(
+        input.clone(),
+        output.clone(),
+        &ImportOptions::default(),
+        &export,
+        &FormatRegistry::with_builtins(),
+    )
+    .expect("Conversion with options failed");
+
+    // Millimeters to centimeters, then Z-up to Y-up: (0, 1, 0) mm becomes (0, 0, -0.1) cm.
+    let text = fs::read_to_string(&output).unwrap();
+    let vertices: Vec<Vec<f32>> = text
+        .lines()
+        .filter_map(|l| l.strip_prefix("v "))
+        .map(|l| l.split_whitespace().map(|c| c.parse().unwrap()).collect())
+        .collect();
+    assert_eq!(vertices.len(), 3);
+    let expected = [[0.0, 0.0, 0.0], [0.1, 0.0, 0.0], [0.0, 0.0, -0.1]];
+    for (v, e) in vertices.iter().zip(expected) {
+        for (a, b) in v.iter().zip(e) {
+            assert!((a - b).abs() < 1e-6, "{:?} != {:?}", v, e);
+        }
+    }
+
+    // Reading it back as Y-up centimeters restores the original geometry.
+    let mut import = ImportOptions::default();
+    import.unit = lib3mf_core::model::Unit::Centimeter;
+    import.axes = CoordinateSystem::Y_UP_RIGHT;
+    convert_with_registry(
+        output.clone(),
+        roundtrip.clone(),
+        &import,
+        &ExportOptions::default(),
+        &FormatRegistry::with_builtins(),
+    )
+    .expect("Import with options failed");
+    let file = File::open(&roundtrip).unwrap();
+    let mut archiver = lib3mf_core::archive::ZipArchiver::new(file).unwrap();
+    let model_path = lib3mf_core::archive::find_model_path(&mut archiver).unwrap();
+    let data = lib3mf_core::archive::ArchiveReader::read_entry(&mut archiver, &model_path).unwrap();
+    let model = lib3mf_core::parser::parse_model(std::io::Cursor::new(data)).unwrap();
+    assert_eq!(model.unit, lib3mf_core::model::Unit::Centimeter);
+    let mesh = model
+        .resources
+        .iter_objects()
+        .find_map(|o| match &o.geometry {
+            Geometry::Mesh(mesh) => Some(mesh),
+            _ => None,
+        })
+        .unwrap();
+    assert!((mesh.vertices[2].y - 0.1).abs() < 1e-6);
+    assert!(mesh.vertices[2].z.abs() < 1e-6);
+
+    let _ = fs::remove_file(&input);
+    let _ = fs::remove_file(&output);
+    let _ = fs::remove_file(&roundtrip);
+}
+
+#[test]
+fn test_convert_obj_with_normals() {
+    let tmp = std::env::temp_dir();
+    let input = tmp.join("lib3mf_test_normals_in.3mf");
+    let output = tmp.join("lib3mf_test_normals_out.obj");
+    write_minimal_3mf(&input);
+
+    let result = std::process::Command::new("cargo")
+        .args(["run", "--quiet", "-p", "lib3mf-cli", "--", "convert"])
+        .arg(&input)
+        .arg(&output)
+        .arg("--normals")
+        .output()
+        .expect("Failed to run convert command");
+    assert!(result.status.success(), "{result:?}");
+
+    let text = fs::read_to_string(&output).unwrap();
+    assert!(text.contains("vn 0 0 1"), "{text}");
+    assert!(text.contains("f 1//1 2//1 3//1"), "{text}");
+
+    let _ = fs::remove_file(&input);
+    let _ = fs::remove_file(&output);
+}
+
+/// Writes an ASCII STL of a tetrahedron with a 10 mm edge at each of `origins`, with the
+/// file name as the solid's name.
+fn write_tetrahedra_stl(path: &Path, origins: &[[f32; 3]]) {
+    let name = path.file_stem().unwrap().to_str().unwrap();
+    let mut text = format!("solid {name}\n");
+    for [x, y, z] in origins {
+        let v = [
+            [*x, *y, *z],
+            [x + 10.0, *y, *z],
+            [*x, y + 10.0, *z],
+            [*x, *y, z + 10.0],
+        ];
+        for [a, b, c] in [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]] {
+            text.push_str("facet normal 0 0 0\nouter loop\n");
+            for i in [a, b, c] {
+                text.push_str(&format!("vertex {} {} {}\n", v[i][0], v[i][1], v[i][2]));
+            }
+            text.push_str("endloop\nendfacet\n");
+        }
+    }
+    text.push_str(&format!("endsolid {name}\n"));
+    fs::write(path, text).unwrap();
+}
+
+fn read_3mf(path: &Path) -> Model {
+    let mut archiver = lib3mf_core::archive::ZipArchiver::new(File::open(path).unwrap()).unwrap();
+    let model_path = lib3mf_core::archive::find_model_path(&mut archiver).unwrap();
+    let data = lib3mf_core::archive::ArchiveReader::read_entry(&mut archiver, &model_path).unwrap();
+    lib3mf_core::parser::parse_model(std::io::Cursor::new(data)).unwrap()
+}
+
+fn file_names(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_convert_merges_inputs_into_separate_objects() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let left = tmp.path().join("left.stl");
+    let right = tmp.path().join("right.stl");
+    let output = tmp.path().join("plate.3mf");
+    write_tetrahedra_stl(&left, &[[0.0, 0.0, 0.0]]);
+    write_tetrahedra_stl(&right, &[[50.0, 0.0, 0.0]]);
+
+    convert_merged(
+        &[left, right],
+        output.clone(),
+        &ImportOptions::default(),
+        &ExportOptions::default(),
+        &FormatRegistry::with_builtins(),
+    )
+    .unwrap();
+
+    let model = read_3mf(&output);
+    assert_eq!(model.build.items.len(), 2);
+    let ids: Vec<ResourceId> = model.build.items.iter().map(|i| i.object_id).collect();
+    assert_ne!(ids[0], ids[1]);
+
+    // Objects keep their positions, in input order
+    let min_x: Vec<f32> = ids
+        .iter()
+        .map(
+            |id| match &model.resources.get_object(*id).unwrap().geometry {
+                Geometry::Mesh(mesh) => mesh.vertices.iter().map(|v| v.x).fold(f32::MAX, f32::min),
+                _ => panic!("expected a mesh"),
+            },
+        )
+        .collect();
+    assert_eq!(min_x, [0.0, 50.0]);
+}
+
+#[test]
+fn test_convert_to_directory_writes_one_file_per_object() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let shells = tmp.path().join("shells.stl");
+    let single = tmp.path().join("single.stl");
+    let plate = tmp.path().join("plate.3mf");
+    write_tetrahedra_stl(&shells, &[[0.0, 0.0, 0.0], [20.0, 0.0, 0.0]]);
+    write_tetrahedra_stl(&single, &[[0.0, 40.0, 0.0]]);
+    let registry = FormatRegistry::with_builtins();
+    convert_merged(
+        &[shells, single],
+        plate.clone(),
+        &ImportOptions::default(),
+        &ExportOptions::default(),
+        &registry,
+    )
+    .unwrap();
+    let names: Vec<String> = read_3mf(&plate)
+        .resources
+        .iter_objects()
+        .map(|o| o.name.clone().unwrap())
+        .collect();
+
+    let objects = tmp.path().join("objects");
+    convert_to_directory(
+        std::slice::from_ref(&plate),
+        objects.clone(),
+        "stl",
+        &ImportOptions::default(),
+        &ExportOptions::default(),
+        &registry,
+    )
+    .unwrap();
+    assert_eq!(names, ["shells", "single"]);
+    assert_eq!(file_names(&objects), ["shells.stl", "single.stl"]);
+
+    // Splitting gives each disconnected piece its own file
+    let pieces = tmp.path().join("pieces");
+    let mut export = ExportOptions::default();
+    export.objects = ObjectMode::Split;
+    convert_to_directory(
+        &[plate],
+        pieces.clone(),
+        "obj",
+        &ImportOptions::default(),
+        &export,
+        &registry,
+    )
+    .unwrap();
+    let files = file_names(&pieces);
+    assert_eq!(files.len(), 3, "{files:?}");
+    assert!(files.iter().all(|f| f.ends_with(".obj")), "{files:?}");
+}
+
+#[test]
+fn test_expand_globs() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    for name in ["b.stl", "a.stl", "c.obj"] {
+        fs::write(tmp.path().join(name), "").unwrap();
+    }
+    let pattern = tmp.path().join("*.stl");
+    let literal = tmp.path().join("c.obj");
+    let expanded = expand_globs(vec![pattern, literal.clone()]).unwrap();
+    assert_eq!(
+        expanded,
+        [tmp.path().join("a.stl"), tmp.path().join("b.stl"), literal]
+    );
+
+    assert!(expand_globs(vec![tmp.path().join("*.3mf")]).is_err());
+}
+
+#[test]
+fn test_convert_cli_split_objects_into_directory() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let input = tmp.path().join("shells.stl");
+    write_tetrahedra_stl(&input, &[[0.0, 0.0, 0.0], [20.0, 0.0, 0.0]]);
+    let out_dir = format!("{}/", tmp.path().join("out").display());
+
+    // Flags may come between the inputs and the output
+    let result = std::process::Command::new("cargo")
+        .args(["run", "--quiet", "-p", "lib3mf-cli", "--", "convert"])
+        .arg(&input)
+        .arg("--split-objects")
+        .arg(&out_dir)
+        .output()
+        .expect("Failed to run convert command");
+    assert!(result.status.success(), "{result:?}");
+    assert_eq!(file_names(&tmp.path().join("out")).len(), 2);
+}
+
+#[test]
+fn test_convert_assembly_to_obj_item_groups() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let input = tmp.path().join("assembly.3mf");
+    let output = tmp.path().join("assembly.obj");
+
+    // An assembly of the same triangle twice, side by side
+    let mut mesh = Mesh::new();
+    let v0 = mesh.add_vertex(0.0, 0.0, 0.0);
+    let v1 = mesh.add_vertex(1.0, 0.0, 0.0);
+    let v2 = mesh.add_vertex(0.0, 1.0, 0.0);
+    mesh.add_triangle(v0, v1, v2);
+    let object = |id, name: &str, geometry| Object {
+        id: ResourceId(id),
+        object_type: ObjectType::Model,
+        name: Some(name.to_string()),
+        part_number: None,
+        uuid: None,
+        pid: None,
+        pindex: None,
+        thumbnail: None,
+        geometry,
+    };
+    let component = |x| Component {
+        object_id: ResourceId(1),
+        path: None,
+        uuid: None,
+        transform: glam::Mat4::from_translation(glam::Vec3::new(x, 0.0, 0.0)),
+    };
+    let mut resources = ResourceCollection::new();
+    resources
+        .add_object(object(1, "Leaf", Geometry::Mesh(mesh)))
+        .unwrap();
+    resources
+        .add_object(object(
+            2,
+            "Pair",
+            Geometry::Components(Components {
+                components: vec![component(0.0), component(5.0)],
+            }),
+        ))
+        .unwrap();
+    let mut build = Build::default();
+    build.items.push(BuildItem {
+        object_id: ResourceId(2),
+        uuid: None,
+        path: None,
+        part_number: None,
+        transform: glam::Mat4::IDENTITY,
+        printable: None,
+        metadata: Default::default(),
+    });
+    let model = Model {
+        resources,
+        build,
+        ..Default::default()
+    };
+    model.write(File::create(&input).unwrap()).unwrap();
+
+    let mut export = ExportOptions::default();
+    export.item_groups = true;
+    convert_with_registry(
+        input,
+        output.clone(),
+        &ImportOptions::default(),
+        &export,
+        &FormatRegistry::with_builtins(),
+    )
+    .expect("Conversion with item groups failed");
+
+    let text = fs::read_to_string(&output).unwrap();
+    let objects = text.lines().filter(|l| l.starts_with("o ")).count();
+    let groups = text.lines().filter(|l| l.starts_with("g ")).count();
+    assert_eq!((objects, groups), (1, 2), "{text}");
+    assert!(text.lines().any(|l| l == "v 6 0 0"), "{text}");
+}
@@ -0,0 +1,210 @@
+//! Integration tests for `3mf copy` on signed and encrypted packages.
+
+use lib3mf_cli::commands::copy;
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, opc};
+use std::fs::File;
+use std::io::{Cursor, Write};
+use std::path::Path;
+use tempfile::TempDir;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+/// Hand-formatted, so a re-serialized copy differs from it byte-wise.
+const MODEL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+  <resources>
+    <object id="1" type="model">
+      <mesh>
+        <vertices>
+          <vertex x="0" y="0" z="0" /><vertex x="1" y="0" z="0" /><vertex x="0" y="1" z="0" />
+        </vertices>
+        <triangles><triangle v1="0" v2="1" v3="2" /></triangles>
+      </mesh>
+    </object>
+  </resources>
+  <build><item objectid="1" /></build>
+</model>"#;
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+  <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml" />
+  <Default Extension="model" ContentType="application/vnd.ms-package.3dmanufacturing-3dmodel+xml" />
+  <Default Extension="xml" ContentType="application/xml" />
+  <Override PartName="/package/services/digital-signature/origin.psdsor" ContentType="application/vnd.openxmlformats-package.digital-signature-origin" />
+  <Override PartName="/package/services/digital-signature/xml-signature/sig1.psdsxs" ContentType="application/vnd.openxmlformats-package.digital-signature-xmlsignature+xml" />
+</Types>"#;
+
+const ORIGIN: &str = "package/services/digital-signature/origin.psdsor";
+const SIGNATURE: &str = "package/services/digital-signature/xml-signature/sig1.psdsxs";
+const KEYSTORE: &str = "Secure/keystore.xml";
+const ENCRYPTED: &str = "3D/secret.model";
+
+fn root_rels(signed: bool, encrypted: bool) -> String {
+    let mut xml = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Target="/3D/3dmodel.model" Id="rel0" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel" />
+"#,
+    );
+    if signed {
+        xml.push_str(r#"  <Relationship Target="/package/services/digital-signature/origin.psdsor" Id="rel1" Type="http://schemas.openxmlformats.org/package/2006/relationships/digital-signature/origin" />
+"#);
+    }
+    if encrypted {
+        xml.push_str(r#"  <Relationship Target="/Secure/keystore.xml" Id="rel2" Type="http://schemas.microsoft.com/3dmanufacturing/2019/04/keystore" />
+  <Relationship Target="/3D/secret.model" Id="rel3" Type="http://schemas.openxmlformats.org/package/2006/relationships/encryptedfile" />
+"#);
+    }
+    xml.push_str("</Relationships>");
+    xml
+}
+
+/// Writes a package with an (unverifiable) OPC signature and/or Secure Content parts.
+fn write_package(path: &Path, signed: bool, encrypted: bool) {
+    let mut zip = ZipWriter::new(File::create(path).unwrap());
+    let options = SimpleFileOptions::default();
+    let mut add = |name: &str, data: &[u8]| {
+        zip.start_file(name, options).unwrap();
+        zip.write_all(data).unwrap();
+    };
+    add("[Content_Types].xml", CONTENT_TYPES.as_bytes());
+    add("_rels/.rels", root_rels(signed, encrypted).as_bytes());
+    add("3D/3dmodel.model", MODEL.as_bytes());
+    if signed {
+        add(ORIGIN, b"");
+        add(
+            "package/services/digital-signature/_rels/origin.psdsor.rels",
+            br#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Target="xml-signature/sig1.psdsxs" Id="sig1" Type="http://schemas.openxmlformats.org/package/2006/relationships/digital-signature/signature" />
+</Relationships>"#,
+        );
+        add(
+            SIGNATURE,
+            b"<Signature xmlns=\"http://www.w3.org/2000/09/xmldsig#\" />",
+        );
+    }
+    if encrypted {
+        add(KEYSTORE, b"<keystore />");
+        add(ENCRYPTED, &[0xde, 0xad, 0xbe, 0xef, 0x00, 0x17]);
+    }
+    zip.finish().unwrap();
+}
+
+fn read(path: &Path, entry: &str) -> Vec<u8> {
+    ZipArchiver::new(File::open(path).unwrap())
+        .unwrap()
+        .read_entry(entry)
+        .unwrap()
+}
+
+fn entries(path: &Path) -> Vec<String> {
+    let mut names = ZipArchiver::new(File::open(path).unwrap())
+        .unwrap()
+        .list_entries()
+        .unwrap();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_copy_signed_package_is_passed_through() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("signed.3mf");
+    let output = dir.path().join("copy.3mf");
+    write_package(&input, true, false);
+
+    copy::run(input.clone(), output.clone(), false, false, false).unwrap();
+
+    assert_eq!(entries(&input), entries(&output));
+    for entry in entries(&input) {
+        assert_eq!(
+            read(&input, &entry),
+            read(&output, &entry),
+            "{entry} changed"
+        );
+    }
+}
+
+#[test]
+fn test_copy_strip_signatures() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("signed.3mf");
+    let output = dir.path().join("stripped.3mf");
+    write_package(&input, true, false);
+
+    copy::run(input, output.clone(), true, false, false).unwrap();
+
+    let names = entries(&output);
+    assert!(
+        !names.iter().any(|n| n.contains("digital-signature")),
+        "{names:?}"
+    );
+    let rels = opc::parse_relationships(&read(&output, "_rels/.rels")).unwrap();
+    assert!(
+        !rels
+            .iter()
+            .any(|r| r.rel_type.contains("digital-signature"))
+    );
+    // Re-serialized: the model still parses but is no longer byte-identical.
+    let model = read(&output, "3D/3dmodel.model");
+    assert_ne!(model, MODEL.as_bytes());
+    lib3mf_core::parser::parse_model(Cursor::new(model)).unwrap();
+}
+
+#[test]
+fn test_copy_preserves_encrypted_parts() {
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("secure.3mf");
+    let output = dir.path().join("copy.3mf");
+    write_package(&input, true, true);
+
+    copy::run(input.clone(), output.clone(), true, false, false).unwrap();
+
+    assert_eq!(read(&output, ENCRYPTED), read(&input, ENCRYPTED));
+    assert_eq!(read(&output, KEYSTORE), read(&input, KEYSTORE));
+    assert_eq!(read(&output, "3D/3dmodel.model"), MODEL.as_bytes());
+    assert!(
+        !entries(&output)
+            .iter()
+            .any(|n| n.contains("digital-signature"))
+    );
+
+    let rels = opc::parse_relationships(&read(&output, "_rels/.rels")).unwrap();
+    let types: Vec<_> = rels.iter().map(|r| r.rel_type.as_str()).collect();
+    assert!(types.iter().any(|t| t.ends_with("/keystore")));
+    assert!(types.iter().any(|t| t.ends_with("/encryptedfile")));
+    assert!(!types.iter().any(|t| t.contains("digital-signature")));
+
+    let content_types = opc::parse_content_types(&read(&output, "[Content_Types].xml")).unwrap();
+    assert_eq!(content_types.len(), 3);
+}
+
+#[test]
+fn test_copy_preserves_print_ticket() {
+    use lib3mf_core::model::{Model, PRINT_TICKET_REL_TYPE};
+
+    let dir = TempDir::new().unwrap();
+    let input = dir.path().join("ticket.3mf");
+    let output = dir.path().join("copy.3mf");
+    let mut model = Model::default();
+    model.add_attachment(
+        "3D/3dmodel.model",
+        PRINT_TICKET_REL_TYPE,
+        "Metadata/print_ticket.xml",
+        b"<PrintTicket />".to_vec(),
+    );
+    model.write(File::create(&input).unwrap()).unwrap();
+
+    copy::run(input.clone(), output.clone(), false, false, false).unwrap();
+
+    let mut archive = ZipArchiver::new(File::open(&output).unwrap()).unwrap();
+    let mut copied = Model::default();
+    copied
+        .load_attachments(&mut archive, |path| path == "3D/3dmodel.model")
+        .unwrap();
+    let registry = copied.attachment_registry();
+    let tickets = registry.of_type(PRINT_TICKET_REL_TYPE);
+    assert_eq!(tickets.len(), 1);
+    assert_eq!(tickets[0].data, Some(&b"<PrintTicket />"[..]));
+}
@@ -0,0 +1,157 @@
+//! Integration tests for `3mf cut`.
+
+use lib3mf_cli::commands::cut::{self, PinArgs};
+use lib3mf_cli::commands::hollow::parse_length;
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::model::{
+    Build, BuildItem, Geometry, Mesh, Model, Object, ObjectType, ResourceCollection, ResourceId,
+    Side,
+};
+use lib3mf_core::parser::parse_model;
+use std::fs::File;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// A 30 mm cube standing on the plate at x = 10.
+fn write_cube(dir: &Path) -> PathBuf {
+    let mut mesh = Mesh::new();
+    for i in 0..8 {
+        let bit = |b: u32| if i >> b & 1 == 1 { 30.0 } else { 0.0 };
+        mesh.add_vertex(bit(0), bit(1), bit(2));
+    }
+    for [a, b, c, d] in [
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+    ] {
+        mesh.add_triangle(a, b, c);
+        mesh.add_triangle(a, c, d);
+    }
+    let mut resources = ResourceCollection::new();
+    resources
+        .add_object(Object {
+            id: ResourceId(1),
+            object_type: ObjectType::Model,
+            name: Some("Cube".into()),
+            part_number: None,
+            uuid: None,
+            pid: None,
+            pindex: None,
+            thumbnail: None,
+            geometry: Geometry::Mesh(mesh),
+        })
+        .unwrap();
+    let mut build = Build::default();
+    build.items.push(BuildItem::new(
+        ResourceId(1),
+        glam::Mat4::from_translation(glam::Vec3::new(10.0, 0.0, 0.0)),
+    ));
+    let model = Model {
+        resources,
+        build,
+        ..Default::default()
+    };
+    let path = dir.join("cube.3mf");
+    model.write(File::create(&path).unwrap()).unwrap();
+    path
+}
+
+fn read_model(path: &Path) -> Model {
+    let mut archiver = ZipArchiver::new(File::open(path).unwrap()).unwrap();
+    let model_path = find_model_path(&mut archiver).unwrap();
+    parse_model(Cursor::new(archiver.read_entry(&model_path).unwrap())).unwrap()
+}
+
+fn volume(model: &Model, id: ResourceId) -> f64 {
+    match &model.resources.get_object(id).unwrap().geometry {
+        Geometry::Mesh(mesh) => mesh.compute_area_and_volume().1,
+        _ => panic!("object {} is not a mesh", id.0),
+    }
+}
+
+/// Run the cut CLI command with the given arguments.
+/// Uses `cargo run` so the binary is always up-to-date.
+fn run_cut(input: &Path, output: &Path, args: &[&str]) -> std::process::Output {
+    std::process::Command::new("cargo")
+        .args(["run", "--quiet", "-p", "lib3mf-cli", "--"])
+        .arg("cut")
+        .arg(input)
+        .arg(output)
+        .args(args)
+        .output()
+        .expect("Failed to run cut command")
+}
+
+#[test]
+fn test_cut_both_halves_with_pins() {
+    let dir = TempDir::new().unwrap();
+    let input = write_cube(dir.path());
+    let output = dir.path().join("cut.3mf");
+
+    let pins = PinArgs {
+        count: 2,
+        diameter: parse_length("4mm").unwrap(),
+        length: parse_length("5mm").unwrap(),
+        clearance: parse_length("0.2mm").unwrap(),
+    };
+    cut::run(
+        input,
+        output.clone(),
+        "z=10".parse().unwrap(),
+        Side::Both,
+        Some(&pins),
+        &[],
+    )
+    .unwrap();
+
+    let model = read_model(&output);
+    let ids: Vec<ResourceId> = model.build.items.iter().map(|i| i.object_id).collect();
+    assert_eq!(ids, vec![ResourceId(1), ResourceId(2)]);
+    assert_eq!(
+        model
+            .resources
+            .get_object(ResourceId(2))
+            .unwrap()
+            .name
+            .as_deref(),
+        Some("Cube (below)")
+    );
+    // Pegs add to the lower half what the wider sockets take from the upper one
+    let (above, below) = (volume(&model, ids[0]), volume(&model, ids[1]));
+    assert!(below > 9000.0 && below < 9000.0 + 200.0, "{below}");
+    assert!(above < 18000.0 && above > 18000.0 - 200.0, "{above}");
+}
+
+#[test]
+fn test_cut_cli_keep_and_errors() {
+    let dir = TempDir::new().unwrap();
+    let input = write_cube(dir.path());
+    let output = dir.path().join("cut.3mf");
+
+    // The plane is in build coordinates: x=25 is 15 into the cube
+    let result = run_cut(&input, &output, &["--plane", "x=25", "--keep", "below"]);
+    assert!(result.status.success(), "{result:?}");
+    let model = read_model(&output);
+    assert_eq!(model.build.items.len(), 1);
+    assert!((volume(&model, ResourceId(1)) - 13500.0).abs() < 1e-2);
+
+    let result = run_cut(&input, &output, &["--plane", "z=100"]);
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("does not pass through"));
+
+    let result = run_cut(&input, &output, &["--plane", "w=3"]);
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("Invalid cut plane"));
+
+    let result = run_cut(
+        &input,
+        &output,
+        &["--plane", "z=10", "--keep", "above", "--pins"],
+    );
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("both halves"));
+}
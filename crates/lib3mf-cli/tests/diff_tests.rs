@@ -0,0 +1,107 @@
+//! Integration tests for `3mf diff --deviation`.
+
+use lib3mf_core::model::{
+    Build, BuildItem, Geometry, Mesh, Model, Object, ObjectType, ResourceCollection, ResourceId,
+};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// A 10 mm cube whose top face is raised by `lift`.
+fn write_cube(dir: &Path, name: &str, lift: f32) -> PathBuf {
+    let mut mesh = Mesh::new();
+    for i in 0..8 {
+        let bit = |b: u32| if i >> b & 1 == 1 { 10.0 } else { 0.0 };
+        let z = if i >> 2 & 1 == 1 { 10.0 + lift } else { 0.0 };
+        mesh.add_vertex(bit(0), bit(1), z);
+    }
+    for [a, b, c, d] in [
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+    ] {
+        mesh.add_triangle(a, b, c);
+        mesh.add_triangle(a, c, d);
+    }
+    let mut resources = ResourceCollection::new();
+    resources
+        .add_object(Object {
+            id: ResourceId(1),
+            object_type: ObjectType::Model,
+            name: Some("Cube".into()),
+            part_number: None,
+            uuid: None,
+            pid: None,
+            pindex: None,
+            thumbnail: None,
+            geometry: Geometry::Mesh(mesh),
+        })
+        .unwrap();
+    let mut build = Build::default();
+    build
+        .items
+        .push(BuildItem::new(ResourceId(1), glam::Mat4::IDENTITY));
+    let model = Model {
+        resources,
+        build,
+        ..Default::default()
+    };
+    let path = dir.join(name);
+    model.write(File::create(&path).unwrap()).unwrap();
+    path
+}
+
+/// Run the diff CLI command with the given arguments.
+/// Uses `cargo run` so the binary is always up-to-date.
+fn run_diff(file1: &Path, file2: &Path, args: &[&str]) -> std::process::Output {
+    std::process::Command::new("cargo")
+        .args(["run", "--quiet", "-p", "lib3mf-cli", "--"])
+        .arg("diff")
+        .arg(file1)
+        .arg(file2)
+        .args(args)
+        .output()
+        .expect("Failed to run diff command")
+}
+
+#[test]
+fn test_diff_deviation_reports_maximum() {
+    let dir = TempDir::new().unwrap();
+    let v1 = write_cube(dir.path(), "v1.3mf", 0.0);
+    let v2 = write_cube(dir.path(), "v2.3mf", 0.25);
+
+    let result = run_diff(&v1, &v2, &["--deviation"]);
+    assert!(result.status.success(), "{result:?}");
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert!(
+        stdout.contains("Maximum deviation: 0.2500 mm on object 1"),
+        "{stdout}"
+    );
+
+    let result = run_diff(&v1, &v2, &["--deviation", "--format", "json"]);
+    assert!(result.status.success(), "{result:?}");
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout[stdout.find('{').unwrap()..]).unwrap();
+    let deviation = &json["deviations"][0];
+    assert_eq!(deviation["object_id"], 1);
+    assert!((deviation["hausdorff"].as_f64().unwrap() - 0.25).abs() < 1e-4);
+    assert!(json["resource_diffs"].is_array());
+}
+
+#[test]
+fn test_diff_tolerance() {
+    let dir = TempDir::new().unwrap();
+    let v1 = write_cube(dir.path(), "v1.3mf", 0.0);
+    let v2 = write_cube(dir.path(), "v2.3mf", 0.25);
+
+    let result = run_diff(&v1, &v2, &["--tolerance", "0.5mm"]);
+    assert!(result.status.success(), "{result:?}");
+
+    let result = run_diff(&v1, &v2, &["--tolerance", "100um"]);
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("more than the tolerance"));
+}
@@ -0,0 +1,132 @@
+//! Tests for `3mf dump` filters and NDJSON output.
+
+use lib3mf_cli::commands::OutputFormat;
+use lib3mf_cli::commands::dump::{DumpOptions, DumpSection, render};
+use lib3mf_core::model::{
+    BaseMaterial, BaseMaterialsGroup, BuildItem, Color, Geometry, Mesh, Model, Object, ObjectType,
+    ResourceId,
+};
+
+fn model() -> Model {
+    let mut mesh = Mesh::new();
+    mesh.add_vertex(0.0, 0.0, 0.0);
+    mesh.add_vertex(10.0, 0.0, 0.0);
+    mesh.add_vertex(0.0, 10.0, 0.0);
+    mesh.add_triangle(0, 1, 2);
+
+    let mut model = Model::default();
+    model.metadata.insert("Title", "Dump test".to_string());
+    model
+        .resources
+        .add_base_materials(BaseMaterialsGroup {
+            id: ResourceId(1),
+            materials: vec![BaseMaterial {
+                name: "PLA".to_string(),
+                display_color: Color::new(255, 0, 0, 255),
+            }],
+        })
+        .unwrap();
+    model
+        .resources
+        .add_object(Object {
+            id: ResourceId(2),
+            object_type: ObjectType::Model,
+            name: Some("Plate".to_string()),
+            part_number: None,
+            uuid: None,
+            pid: None,
+            pindex: None,
+            thumbnail: None,
+            geometry: Geometry::Mesh(mesh),
+        })
+        .unwrap();
+    model
+        .build
+        .items
+        .push(BuildItem::new(ResourceId(2), glam::Mat4::IDENTITY));
+    model
+}
+
+#[test]
+fn test_dump_single_resource_without_geometry() {
+    let options = DumpOptions {
+        object_id: Some(2),
+        no_geometry: true,
+        ..Default::default()
+    };
+    let value: serde_json::Value =
+        serde_json::from_str(&render(&model(), &options).unwrap()).unwrap();
+    assert_eq!(value["name"], "Plate");
+    let mesh = &value["geometry"]["Mesh"];
+    assert_eq!(mesh["vertices"], "[3 items]");
+    assert_eq!(mesh["triangles"], "[1 items]");
+
+    let options = DumpOptions {
+        object_id: Some(9),
+        ..Default::default()
+    };
+    assert!(render(&model(), &options).is_err());
+}
+
+#[test]
+fn test_dump_section_and_depth() {
+    let options = DumpOptions {
+        section: Some(DumpSection::Build),
+        format: OutputFormat::Json,
+        ..Default::default()
+    };
+    let build: serde_json::Value =
+        serde_json::from_str(&render(&model(), &options).unwrap()).unwrap();
+    assert_eq!(build["items"][0]["object_id"], 2);
+
+    // Text output is the debug view of the section alone
+    let options = DumpOptions {
+        section: Some(DumpSection::Metadata),
+        ..Default::default()
+    };
+    let text = render(&model(), &options).unwrap();
+    assert!(text.contains("Dump test"), "{text}");
+    assert!(!text.contains("Plate"), "{text}");
+
+    let depth = |max_depth| {
+        let options = DumpOptions {
+            max_depth: Some(max_depth),
+            ..Default::default()
+        };
+        serde_json::from_str::<serde_json::Value>(&render(&model(), &options).unwrap()).unwrap()
+    };
+    let whole = depth(1);
+    assert_eq!(whole["unit"], "millimeter");
+    assert_eq!(whole["build"], "{1 fields}");
+    assert_eq!(depth(2)["build"]["items"], "[1 items]");
+}
+
+#[test]
+fn test_dump_ndjson_resources() {
+    let options = DumpOptions {
+        ndjson: true,
+        no_geometry: true,
+        ..Default::default()
+    };
+    let out = render(&model(), &options).unwrap();
+    let lines: Vec<serde_json::Value> = out
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["id"], 2);
+    assert_eq!(lines[0]["kind"], "object");
+    assert_eq!(lines[1]["kind"], "basematerials");
+    assert_eq!(
+        lines[0]["resource"]["geometry"]["Mesh"]["vertices"],
+        "[3 items]"
+    );
+
+    let options = DumpOptions {
+        ndjson: true,
+        section: Some(DumpSection::Build),
+        ..Default::default()
+    };
+    let out = render(&model(), &options).unwrap();
+    assert_eq!(out.lines().count(), 1);
+}
@@ -0,0 +1,179 @@
+//! Integration tests for the `3mf extract-object` command.
+//!
+//! Test files are written with lib3mf_core's Model API; the command is run as a CLI
+//! subprocess and its output parsed back.
+
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::model::{
+    BaseMaterial, BaseMaterialsGroup, Build, BuildItem, Color, Geometry, Mesh, Model, Object,
+    ObjectType, ResourceCollection, ResourceId,
+};
+use lib3mf_core::parser::parse_model;
+use std::fs::File;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Writes a 3MF with a "Gear" (5, material 1) and a "Bolt" (7, material 2), each placed
+/// by a build item.
+fn create_test_3mf(dir: &Path) -> PathBuf {
+    let mut resources = ResourceCollection::new();
+    let mut build = Build::default();
+
+    for (object_id, name, material_id) in [(5, "Gear", 1), (7, "Bolt", 2)] {
+        let mut mesh = Mesh::new();
+        let v0 = mesh.add_vertex(0.0, 0.0, 0.0);
+        let v1 = mesh.add_vertex(10.0, 0.0, 0.0);
+        let v2 = mesh.add_vertex(5.0, 10.0, 0.0);
+        let v3 = mesh.add_vertex(5.0, 5.0, 10.0);
+        mesh.add_triangle(v0, v2, v1);
+        mesh.add_triangle(v0, v1, v3);
+        mesh.add_triangle(v1, v2, v3);
+        mesh.add_triangle(v0, v3, v2);
+
+        resources
+            .add_base_materials(BaseMaterialsGroup {
+                id: ResourceId(material_id),
+                materials: vec![BaseMaterial {
+                    name: format!("Mat_{}", name),
+                    display_color: Color::new(128, 128, 128, 255),
+                }],
+            })
+            .expect("Failed to add base materials");
+        resources
+            .add_object(Object {
+                id: ResourceId(object_id),
+                object_type: ObjectType::Model,
+                name: Some(name.to_string()),
+                part_number: None,
+                uuid: None,
+                pid: Some(ResourceId(material_id)),
+                pindex: Some(0),
+                thumbnail: None,
+                geometry: Geometry::Mesh(mesh),
+            })
+            .expect("Failed to add object");
+        build.items.push(BuildItem {
+            object_id: ResourceId(object_id),
+            uuid: None,
+            path: None,
+            part_number: None,
+            transform: glam::Mat4::from_translation(glam::Vec3::new(object_id as f32, 0.0, 0.0)),
+            printable: None,
+            metadata: Default::default(),
+        });
+    }
+
+    let model = Model {
+        resources,
+        build,
+        ..Default::default()
+    };
+    let path = dir.join("plate.3mf");
+    let file = File::create(&path).expect("Failed to create test 3MF file");
+    model.write(file).expect("Failed to write test 3MF model");
+    path
+}
+
+/// Parse a 3MF file and return the Model.
+fn load_3mf(path: &Path) -> Model {
+    let file = File::open(path).expect("Failed to open 3MF file");
+    let mut archiver = ZipArchiver::new(file).expect("Failed to open ZIP archive");
+    let model_path = find_model_path(&mut archiver).expect("Failed to find model path");
+    let model_data = archiver
+        .read_entry(&model_path)
+        .expect("Failed to read model XML");
+    parse_model(Cursor::new(model_data)).expect("Failed to parse model XML")
+}
+
+fn run_extract_object(args: &[&str]) -> std::process::Output {
+    std::process::Command::new("cargo")
+        .args(["run", "--quiet", "-p", "lib3mf-cli", "--"])
+        .arg("extract-object")
+        .args(args)
+        .output()
+        .expect("Failed to run extract-object command")
+}
+
+#[test]
+fn test_extract_object_renumbers_ids() {
+    let dir = TempDir::new().unwrap();
+    let input = create_test_3mf(dir.path());
+    let output = dir.path().join("bolt.3mf");
+
+    let result = run_extract_object(&[
+        input.to_str().unwrap(),
+        "--id",
+        "7",
+        "-o",
+        output.to_str().unwrap(),
+    ]);
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    let model = load_3mf(&output);
+    let objects: Vec<_> = model.resources.iter_objects().collect();
+    assert_eq!(objects.len(), 1);
+    assert_eq!(objects[0].name.as_deref(), Some("Bolt"));
+    assert_eq!(model.resources.iter_base_materials().count(), 1);
+    assert_eq!(model.build.items.len(), 1);
+    assert_eq!(model.build.items[0].object_id, objects[0].id);
+    assert_eq!(model.build.items[0].transform.w_axis.x, 7.0);
+}
+
+#[test]
+fn test_extract_object_streaming_keeps_ids() {
+    let dir = TempDir::new().unwrap();
+    let input = create_test_3mf(dir.path());
+    let output = dir.path().join("bolt.3mf");
+
+    let result = run_extract_object(&[
+        input.to_str().unwrap(),
+        "--id",
+        "7",
+        "-o",
+        output.to_str().unwrap(),
+        "--streaming",
+    ]);
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert!(stdout.contains("2 resources in 1 part"), "{}", stdout);
+
+    let model = load_3mf(&output);
+    let ids: Vec<u32> = model.resources.iter_objects().map(|o| o.id.0).collect();
+    assert_eq!(ids, [7]);
+    assert!(model.resources.get_base_materials(ResourceId(2)).is_some());
+    assert!(model.resources.get_base_materials(ResourceId(1)).is_none());
+    assert_eq!(model.build.items.len(), 1);
+    assert_eq!(model.build.items[0].transform.w_axis.x, 7.0);
+}
+
+#[test]
+fn test_extract_object_missing_id_fails() {
+    let dir = TempDir::new().unwrap();
+    let input = create_test_3mf(dir.path());
+    let output = dir.path().join("out.3mf");
+
+    for streaming in [false, true] {
+        let mut args = vec![
+            input.to_str().unwrap(),
+            "--id",
+            "42",
+            "-o",
+            output.to_str().unwrap(),
+        ];
+        if streaming {
+            args.push("--streaming");
+        }
+        let result = run_extract_object(&args);
+        assert!(!result.status.success());
+        assert!(String::from_utf8_lossy(&result.stderr).contains("42"));
+    }
+}
@@ -0,0 +1,140 @@
+//! Tests for bulk `3mf extract` by part kind and content type.
+
+use lib3mf_cli::commands::extract_parts::{PartSelection, run};
+use lib3mf_core::model::{Model, ResourceId, Texture2D, TileStyle};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\nfake texture";
+const JPEG: &[u8] = b"\xff\xd8\xfffake photo";
+
+/// A package with a referenced PNG texture and an unreferenced JPEG attachment.
+fn write_package(dir: &Path) -> PathBuf {
+    let mut model = Model::default();
+    model
+        .resources
+        .add_texture_2d(Texture2D {
+            id: ResourceId(1),
+            path: "/3D/Textures/wood.png".to_string(),
+            contenttype: "image/png".to_string(),
+            tile_style_u: TileStyle::Wrap,
+            tile_style_v: TileStyle::Wrap,
+            filter: None,
+        })
+        .unwrap();
+    model
+        .attachments
+        .insert("3D/Textures/wood.png".to_string(), PNG.to_vec());
+    model
+        .attachments
+        .insert("Metadata/photo.jpg".to_string(), JPEG.to_vec());
+    let path = dir.join("textured.3mf");
+    model.write(File::create(&path).unwrap()).unwrap();
+    path
+}
+
+#[test]
+fn test_extract_all_textures_and_models() {
+    let dir = TempDir::new().unwrap();
+    let package = write_package(dir.path());
+    let textures = dir.path().join("textures");
+    let models = dir.path().join("models");
+
+    run(
+        package,
+        &PartSelection {
+            textures: Some(textures.clone()),
+            models: Some(models.clone()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        std::fs::read(textures.join("3D/Textures/wood.png")).unwrap(),
+        PNG
+    );
+    assert!(!textures.join("Metadata/photo.jpg").exists());
+    assert!(models.join("3D/3dmodel.model").exists());
+}
+
+#[test]
+fn test_extract_textures_with_unnormalized_references() {
+    let dir = TempDir::new().unwrap();
+    let mut model = Model::default();
+    model
+        .resources
+        .add_texture_2d(Texture2D {
+            id: ResourceId(1),
+            path: "3D\\Textures\\..\\Textures\\Dark%20Wood.PNG".to_string(),
+            contenttype: "image/png".to_string(),
+            tile_style_u: TileStyle::Wrap,
+            tile_style_v: TileStyle::Wrap,
+            filter: None,
+        })
+        .unwrap();
+    model
+        .attachments
+        .insert("3D/Textures/dark wood.png".to_string(), PNG.to_vec());
+    let package = dir.path().join("escaped.3mf");
+    model.write(File::create(&package).unwrap()).unwrap();
+    let textures = dir.path().join("textures");
+
+    run(
+        package,
+        &PartSelection {
+            textures: Some(textures.clone()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        std::fs::read(textures.join("3D/Textures/dark wood.png")).unwrap(),
+        PNG
+    );
+}
+
+#[test]
+fn test_extract_by_content_type() {
+    let dir = TempDir::new().unwrap();
+    let package = write_package(dir.path());
+    let out = dir.path().join("jpegs");
+
+    run(
+        package.clone(),
+        &PartSelection {
+            content_types: vec!["IMAGE/JPEG".to_string()],
+            output: Some(out.clone()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(std::fs::read(out.join("Metadata/photo.jpg")).unwrap(), JPEG);
+    assert!(!out.join("3D").exists());
+
+    // A content type narrows the textures down
+    let textures = dir.path().join("textures");
+    run(
+        package.clone(),
+        &PartSelection {
+            textures: Some(textures.clone()),
+            content_types: vec!["image/jpeg".to_string()],
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert!(!textures.exists());
+
+    // Selecting by content type needs somewhere to write to
+    let err = run(
+        package,
+        &PartSelection {
+            content_types: vec!["image/png".to_string()],
+            ..Default::default()
+        },
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("--output"), "{err}");
+}
@@ -0,0 +1,134 @@
+//! Integration tests for `3mf hollow`.
+
+use lib3mf_cli::commands::hollow::{self, LengthArg, parse_length};
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::model::{
+    Build, BuildItem, Geometry, Model, Object, ObjectType, ResourceCollection, ResourceId, Unit,
+};
+use lib3mf_core::parser::parse_model;
+use lib3mf_core::testutil::meshes::cuboid;
+use std::fs::File;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// A 2 cm model cube (object 1) and a support cube (object 2), in centimeters.
+fn write_cubes(dir: &Path) -> PathBuf {
+    let mut resources = ResourceCollection::new();
+    let mut build = Build::default();
+    for (id, object_type) in [(1, ObjectType::Model), (2, ObjectType::Support)] {
+        resources
+            .add_object(Object {
+                id: ResourceId(id),
+                object_type,
+                name: None,
+                part_number: None,
+                uuid: None,
+                pid: None,
+                pindex: None,
+                thumbnail: None,
+                geometry: Geometry::Mesh(cuboid([2.0; 3], 1)),
+            })
+            .unwrap();
+        build.items.push(BuildItem {
+            object_id: ResourceId(id),
+            uuid: None,
+            path: None,
+            part_number: None,
+            transform: glam::Mat4::IDENTITY,
+            printable: None,
+            metadata: Default::default(),
+        });
+    }
+    let model = Model {
+        unit: Unit::Centimeter,
+        resources,
+        build,
+        ..Default::default()
+    };
+    let path = dir.join("cubes.3mf");
+    model.write(File::create(&path).unwrap()).unwrap();
+    path
+}
+
+fn read_model(path: &Path) -> Model {
+    let mut archiver = ZipArchiver::new(File::open(path).unwrap()).unwrap();
+    let model_path = find_model_path(&mut archiver).unwrap();
+    parse_model(Cursor::new(archiver.read_entry(&model_path).unwrap())).unwrap()
+}
+
+fn volume(model: &Model, id: u32) -> f64 {
+    match &model.resources.get_object(ResourceId(id)).unwrap().geometry {
+        Geometry::Mesh(mesh) => mesh.compute_area_and_volume().1,
+        _ => panic!("object {id} is not a mesh"),
+    }
+}
+
+#[test]
+fn test_hollow_converts_units_and_skips_supports() {
+    let dir = TempDir::new().unwrap();
+    let input = write_cubes(dir.path());
+    let output = dir.path().join("hollow.3mf");
+
+    hollow::run(
+        input,
+        output.clone(),
+        parse_length("2mm").unwrap(),
+        Some(parse_length("4mm").unwrap()),
+        Some(parse_length("0.5mm").unwrap()),
+        &[],
+    )
+    .unwrap();
+
+    let model = read_model(&output);
+    // 2 cm cube with 0.2 cm walls, less a small drain hole
+    let hollowed = volume(&model, 1);
+    let shell = 8.0 - 1.6f64.powi(3);
+    assert!(hollowed < shell && hollowed > shell * 0.95, "{hollowed}");
+    assert!((volume(&model, 2) - 8.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_hollow_selected_object() {
+    let dir = TempDir::new().unwrap();
+    let input = write_cubes(dir.path());
+    let output = dir.path().join("hollow.3mf");
+
+    let thickness = LengthArg {
+        value: 0.3,
+        unit: None,
+    };
+    hollow::run(
+        input.clone(),
+        output.clone(),
+        thickness,
+        None,
+        Some(parse_length("0.1").unwrap()),
+        &[2],
+    )
+    .unwrap();
+    let model = read_model(&output);
+    assert!((volume(&model, 1) - 8.0).abs() < 1e-6);
+    let expected = 8.0 - 1.4f64.powi(3);
+    assert!((volume(&model, 2) - expected).abs() < expected * 0.03);
+
+    assert!(hollow::run(input, output, thickness, None, None, &[9]).is_err());
+}
+
+#[test]
+fn test_parse_length() {
+    assert_eq!(
+        parse_length("3mm").unwrap(),
+        LengthArg {
+            value: 3.0,
+            unit: Some(Unit::Millimeter)
+        }
+    );
+    assert_eq!(parse_length("2.5").unwrap().unit, None);
+    assert_eq!(parse_length("0.1 in").unwrap().unit, Some(Unit::Inch));
+    assert_eq!(parse_length("40um").unwrap().unit, Some(Unit::Micron));
+    assert!((parse_length("3mm").unwrap().in_units(Unit::Centimeter) - 0.3).abs() < 1e-6);
+    assert!(parse_length("3furlongs").is_err());
+    assert!(parse_length("-1mm").is_err());
+    assert!(parse_length("mm").is_err());
+}
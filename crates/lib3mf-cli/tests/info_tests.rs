@@ -0,0 +1,177 @@
+//! Integration tests for `3mf info`.
+
+use lib3mf_cli::commands::info::{ExtensionInfo, PackageInfo, inspect};
+use lib3mf_core::model::{
+    BaseMaterial, BaseMaterialsGroup, BuildItem, Color, CoreProperties, Extension, Geometry, Mesh,
+    Model, Object, ObjectType, ResourceId,
+};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+const BENCHY: &str = "../../models/Benchy.3mf";
+
+/// Writes a one-triangle model colored with a base material.
+fn create_material_3mf(dir: &Path) -> PathBuf {
+    let mut model = Model::default();
+    model
+        .resources
+        .add_base_materials(BaseMaterialsGroup {
+            id: ResourceId(1),
+            materials: vec![BaseMaterial {
+                name: "Red".to_string(),
+                display_color: Color::new(255, 0, 0, 255),
+            }],
+        })
+        .unwrap();
+    let mut mesh = Mesh::new();
+    let v0 = mesh.add_vertex(0.0, 0.0, 0.0);
+    let v1 = mesh.add_vertex(1.0, 0.0, 0.0);
+    let v2 = mesh.add_vertex(0.0, 1.0, 0.0);
+    mesh.add_triangle(v0, v1, v2);
+    model
+        .resources
+        .add_object(Object {
+            id: ResourceId(2),
+            object_type: ObjectType::Model,
+            name: None,
+            part_number: None,
+            uuid: None,
+            pid: Some(ResourceId(1)),
+            pindex: Some(0),
+            thumbnail: None,
+            geometry: Geometry::Mesh(mesh),
+        })
+        .unwrap();
+    model.build.items.push(BuildItem {
+        object_id: ResourceId(2),
+        uuid: None,
+        path: None,
+        part_number: None,
+        transform: glam::Mat4::IDENTITY,
+        printable: None,
+        metadata: Default::default(),
+    });
+
+    let path = dir.join("material.3mf");
+    model.write(File::create(&path).unwrap()).unwrap();
+    path
+}
+
+/// Copies the package at `src` to `dst`, applying `edit` to the root model part's XML.
+fn rewrite_model(src: &Path, dst: &Path, edit: impl Fn(String) -> String) {
+    let mut input = ZipArchive::new(File::open(src).unwrap()).unwrap();
+    let mut output = ZipWriter::new(File::create(dst).unwrap());
+    for i in 0..input.len() {
+        let mut entry = input.by_index(i).unwrap();
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data).unwrap();
+        if entry.name() == "3D/3dmodel.model" {
+            data = edit(String::from_utf8(data).unwrap()).into_bytes();
+        }
+        output
+            .start_file(entry.name(), SimpleFileOptions::default())
+            .unwrap();
+        output.write_all(&data).unwrap();
+    }
+    output.finish().unwrap();
+}
+
+fn extension(info: &PackageInfo, ext: Extension) -> Option<&ExtensionInfo> {
+    info.extensions.iter().find(|e| e.extension == ext)
+}
+
+#[test]
+fn test_info_benchy() {
+    let info = inspect(Path::new(BENCHY)).unwrap();
+
+    assert_eq!(info.model_path, "3D/3dmodel.model");
+    assert!(
+        info.generator
+            .as_deref()
+            .unwrap()
+            .starts_with("BambuStudio")
+    );
+    assert!(info.issues.is_empty(), "{:?}", info.issues);
+    assert!(!info.signed && !info.encrypted);
+    assert!(info.vendor_namespaces.contains_key("BambuStudio"));
+
+    let production = extension(&info, Extension::Production).unwrap();
+    assert!(production.declared && production.required && production.used);
+
+    // Largest first, and every entry accounted for
+    let model = &info.parts[0];
+    assert_eq!(model.name, "3D/Objects/object_1.model");
+    assert!(model.compressed_size < model.size);
+    assert!(info.parts.windows(2).all(|w| w[0].size >= w[1].size));
+    let stored: u64 = info.parts.iter().map(|p| p.compressed_size).sum();
+    assert!(stored < info.file_size);
+}
+
+#[test]
+fn test_info_declared_vs_used() {
+    let dir = TempDir::new().unwrap();
+    let file = create_material_3mf(dir.path());
+    let info = inspect(&file).unwrap();
+
+    let materials = extension(&info, Extension::Materials).unwrap();
+    assert!(materials.declared && materials.used && !materials.required);
+    let slice = extension(&info, Extension::Slice).unwrap();
+    assert!(slice.declared && !slice.used);
+    assert!(info.issues.is_empty(), "{:?}", info.issues);
+    assert!(
+        info.notes
+            .iter()
+            .any(|n| n.starts_with("Materials and Properties is used but not required"))
+    );
+}
+
+#[test]
+fn test_info_required_extension_issues() {
+    let dir = TempDir::new().unwrap();
+    let file = create_material_3mf(dir.path());
+    let edited = dir.path().join("edited.3mf");
+    rewrite_model(&file, &edited, |xml| {
+        xml.replacen(
+            "<model ",
+            r#"<model requiredextensions="m q x" xmlns:x="http://example.com/unknown" "#,
+            1,
+        )
+    });
+
+    let info = inspect(&edited).unwrap();
+    assert!(extension(&info, Extension::Materials).unwrap().required);
+    assert_eq!(info.issues.len(), 2, "{:?}", info.issues);
+    assert!(info.issues[0].contains("'q'"));
+    assert!(info.issues[1].contains("http://example.com/unknown"));
+    assert_eq!(
+        info.vendor_namespaces.get("x").map(String::as_str),
+        Some("http://example.com/unknown")
+    );
+}
+
+#[test]
+fn test_info_core_properties_survive_copy() {
+    let dir = TempDir::new().unwrap();
+    let model = Model {
+        core_properties: Some(CoreProperties {
+            title: Some("Bracket".to_string()),
+            creator: Some("Jane Doe".to_string()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let input = dir.path().join("props.3mf");
+    model.write(File::create(&input).unwrap()).unwrap();
+    let output = dir.path().join("copy.3mf");
+    lib3mf_cli::commands::copy::run(input.clone(), output.clone(), false, false, false).unwrap();
+
+    for file in [input, output] {
+        let info = inspect(&file).unwrap();
+        assert_eq!(info.core_properties, model.core_properties);
+        assert!(!info.notes.iter().any(|n| n.starts_with("Core properties")));
+    }
+}
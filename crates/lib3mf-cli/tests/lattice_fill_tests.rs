@@ -0,0 +1,150 @@
+//! Integration tests for `3mf lattice-fill`.
+
+use lib3mf_cli::commands::hollow::parse_length;
+use lib3mf_cli::commands::lattice_fill::{self, LatticeArgs};
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::model::{
+    Build, BuildItem, Geometry, LatticePattern, Mesh, Model, Object, ObjectType,
+    ResourceCollection, ResourceId,
+};
+use lib3mf_core::parser::parse_model;
+use std::fs::File;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn write_cube(dir: &Path) -> PathBuf {
+    let mut mesh = Mesh::new();
+    for i in 0..8 {
+        let bit = |b: u32| if i >> b & 1 == 1 { 24.0 } else { 0.0 };
+        mesh.add_vertex(bit(0), bit(1), bit(2));
+    }
+    for [a, b, c, d] in [
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+    ] {
+        mesh.add_triangle(a, b, c);
+        mesh.add_triangle(a, c, d);
+    }
+    let mut resources = ResourceCollection::new();
+    resources
+        .add_object(Object {
+            id: ResourceId(1),
+            object_type: ObjectType::Model,
+            name: Some("Cube".into()),
+            part_number: None,
+            uuid: None,
+            pid: None,
+            pindex: None,
+            thumbnail: None,
+            geometry: Geometry::Mesh(mesh),
+        })
+        .unwrap();
+    let mut build = Build::default();
+    build.items.push(BuildItem {
+        object_id: ResourceId(1),
+        uuid: None,
+        path: None,
+        part_number: None,
+        transform: glam::Mat4::IDENTITY,
+        printable: None,
+        metadata: Default::default(),
+    });
+    let model = Model {
+        resources,
+        build,
+        ..Default::default()
+    };
+    let path = dir.join("cube.3mf");
+    model.write(File::create(&path).unwrap()).unwrap();
+    path
+}
+
+fn read_mesh(path: &Path) -> Mesh {
+    let mut archiver = ZipArchiver::new(File::open(path).unwrap()).unwrap();
+    let model_path = find_model_path(&mut archiver).unwrap();
+    let model = parse_model(Cursor::new(archiver.read_entry(&model_path).unwrap())).unwrap();
+    match &model.resources.get_object(ResourceId(1)).unwrap().geometry {
+        Geometry::Mesh(mesh) => mesh.clone(),
+        _ => panic!("object 1 is not a mesh"),
+    }
+}
+
+/// Run the lattice-fill CLI command with the given arguments.
+/// Uses `cargo run` so the binary is always up-to-date.
+fn run_lattice_fill(input: &Path, output: &Path, args: &[&str]) -> std::process::Output {
+    std::process::Command::new("cargo")
+        .args(["run", "--quiet", "-p", "lib3mf-cli", "--"])
+        .arg("lattice-fill")
+        .arg(input)
+        .arg(output)
+        .args(args)
+        .output()
+        .expect("Failed to run lattice-fill command")
+}
+
+#[test]
+fn test_lattice_fill_writes_beam_lattice() {
+    let dir = TempDir::new().unwrap();
+    let input = write_cube(dir.path());
+    let output = dir.path().join("filled.3mf");
+
+    let args = LatticeArgs {
+        pattern: LatticePattern::Octet,
+        cell_size: parse_length("6mm").unwrap(),
+        beam_radius: parse_length("0.5").unwrap(),
+        density: None,
+        shell: Some(parse_length("2mm").unwrap()),
+        voxel_size: Some(parse_length("0.5mm").unwrap()),
+    };
+    lattice_fill::run(input, output.clone(), &args, &[]).unwrap();
+
+    let mesh = read_mesh(&output);
+    let lattice = mesh.beam_lattice.as_ref().expect("beam lattice written");
+    assert!(!lattice.beams.is_empty());
+    assert_eq!(lattice.radius, Some(0.5));
+    for beam in &lattice.beams {
+        assert!(
+            (beam.v1 as usize) < mesh.vertices.len() && (beam.v2 as usize) < mesh.vertices.len()
+        );
+    }
+    // Hollowed skin: outer surface and cavity
+    let (_, volume) = mesh.compute_area_and_volume();
+    assert!(volume < 24.0f64.powi(3) * 0.5, "{volume}");
+}
+
+#[test]
+fn test_lattice_fill_cli_options() {
+    let dir = TempDir::new().unwrap();
+    let input = write_cube(dir.path());
+    let output = dir.path().join("filled.3mf");
+
+    let result = run_lattice_fill(
+        &input,
+        &output,
+        &[
+            "--pattern",
+            "gyroid",
+            "--cell-size",
+            "8",
+            "--density",
+            "0.2",
+            "--no-shell",
+            "--voxel-size",
+            "0.5",
+        ],
+    );
+    assert!(result.status.success(), "{result:?}");
+    let mesh = read_mesh(&output);
+    // Solid: the original triangles only
+    assert_eq!(mesh.triangles.len(), 12);
+    assert!(!mesh.beam_lattice.unwrap().beams.is_empty());
+
+    let result = run_lattice_fill(&input, &output, &["--pattern", "honeycomb"]);
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("honeycomb"));
+}
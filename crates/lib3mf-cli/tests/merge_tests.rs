@@ -75,6 +75,7 @@ fn create_test_3mf(dir: &Path, name: &str, object_id: u32, material_id: Option<u
         part_number: None,
         transform: glam::Mat4::IDENTITY,
         printable: None,
+        metadata: Default::default(),
     });
 
     let mut model = Model {
@@ -515,6 +516,50 @@ fn test_single_plate_grid_layout() {
     );
 }
 
+/// Single-plate mode with --printer centers the grid on the printer's bed.
+#[test]
+fn test_single_plate_centered_on_printer_bed() {
+    let tmp = TempDir::new().unwrap();
+    let a = create_test_3mf(tmp.path(), "bed_a", 1, None);
+    let b = create_test_3mf(tmp.path(), "bed_b", 1, None);
+
+    // Two 10 mm cells with 10 mm spacing: a 30 x 10 mm arrangement
+    for (printer, first) in [("bambu-x1c", [113.0, 123.0]), ("resin-6in", [-15.0, -5.0])] {
+        let out = tmp.path().join(format!("{printer}.3mf"));
+        let result = run_merge(&[
+            a.to_str().unwrap(),
+            b.to_str().unwrap(),
+            "--output",
+            out.to_str().unwrap(),
+            "--single-plate",
+            "--printer",
+            printer,
+        ]);
+        assert!(
+            result.status.success(),
+            "{}",
+            String::from_utf8_lossy(&result.stderr)
+        );
+        let model = load_3mf(&out);
+        let t0 = model.build.items[0].transform.w_axis;
+        let t1 = model.build.items[1].transform.w_axis;
+        assert_eq!([t0.x, t0.y], first, "{printer}");
+        assert_eq!([t1.x, t1.y], [first[0] + 20.0, first[1]], "{printer}");
+    }
+
+    // Arranging for a printer only makes sense on a single plate
+    let out = tmp.path().join("per_file.3mf");
+    let result = run_merge(&[
+        a.to_str().unwrap(),
+        b.to_str().unwrap(),
+        "--output",
+        out.to_str().unwrap(),
+        "--printer",
+        "bambu-x1c",
+    ]);
+    assert!(!result.status.success());
+}
+
 /// Test 11: --quiet mode produces no stdout output.
 #[test]
 fn test_quiet_mode() {
@@ -0,0 +1,139 @@
+//! Integration tests for the global `--model-part` option.
+
+use lib3mf_cli::commands::info::inspect;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+/// A model part with `triangles` disjoint triangles.
+fn model_xml(triangles: usize) -> String {
+    let mut vertices = String::new();
+    let mut tris = String::new();
+    for i in 0..triangles {
+        let x = i as f32 * 2.0;
+        vertices.push_str(&format!(
+            "<vertex x=\"{x}\" y=\"0\" z=\"0\" /><vertex x=\"{}\" y=\"0\" z=\"0\" /><vertex x=\"{x}\" y=\"1\" z=\"0\" />",
+            x + 1.0
+        ));
+        tris.push_str(&format!(
+            "<triangle v1=\"{}\" v2=\"{}\" v3=\"{}\" />",
+            i * 3,
+            i * 3 + 1,
+            i * 3 + 2
+        ));
+    }
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    <resources>
+        <object id="1" type="model">
+            <mesh><vertices>{vertices}</vertices><triangles>{tris}</triangles></mesh>
+        </object>
+    </resources>
+    <build><item objectid="1" /></build>
+</model>"#
+    )
+}
+
+/// Writes a package with two model relationships, one triangle on plate 1 and two on plate 2.
+fn create_multi_plate_3mf(dir: &Path) -> PathBuf {
+    let path = dir.join("plates.3mf");
+    let mut writer = ZipWriter::new(File::create(&path).unwrap());
+    let options = SimpleFileOptions::default();
+    let files = [
+        (
+            "[Content_Types].xml",
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+    <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml" />
+    <Default Extension="model" ContentType="application/vnd.ms-package.3dmanufacturing-3dmodel+xml" />
+</Types>"#
+                .to_string(),
+        ),
+        (
+            "_rels/.rels",
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+    <Relationship Id="rel0" Target="/3D/plate_1.model" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel" />
+    <Relationship Id="rel1" Target="/3D/plate_2.model" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel" />
+</Relationships>"#
+                .to_string(),
+        ),
+        ("3D/plate_1.model", model_xml(1)),
+        ("3D/plate_2.model", model_xml(2)),
+    ];
+    for (name, data) in files {
+        writer.start_file(name, options).unwrap();
+        writer.write_all(data.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap();
+    path
+}
+
+fn run_3mf(args: &[&str]) -> std::process::Output {
+    std::process::Command::new("cargo")
+        .args(["run", "--quiet", "-p", "lib3mf-cli", "--"])
+        .args(args)
+        .output()
+        .expect("Failed to run 3mf")
+}
+
+#[test]
+fn test_ambiguous_package_requires_model_part() {
+    let dir = TempDir::new().unwrap();
+    let file = create_multi_plate_3mf(dir.path());
+
+    let result = run_3mf(&["stats", file.to_str().unwrap()]);
+    assert!(!result.status.success());
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert!(stderr.contains("--model-part"), "{}", stderr);
+}
+
+#[test]
+fn test_model_part_selects_plate() {
+    let dir = TempDir::new().unwrap();
+    let file = create_multi_plate_3mf(dir.path());
+
+    let result = run_3mf(&[
+        "stats",
+        file.to_str().unwrap(),
+        "--model-part",
+        "3D/plate_2.model",
+        "--format",
+        "json",
+    ]);
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+    let stats: serde_json::Value = serde_json::from_slice(&result.stdout).unwrap();
+    assert_eq!(stats["geometry"]["triangle_count"], 2);
+
+    let result = run_3mf(&[
+        "stats",
+        file.to_str().unwrap(),
+        "--model-part",
+        "3D/plate_3.model",
+    ]);
+    assert!(!result.status.success());
+}
+
+#[test]
+fn test_info_lists_model_parts() {
+    let dir = TempDir::new().unwrap();
+    let file = create_multi_plate_3mf(dir.path());
+
+    let info = inspect(&file).unwrap();
+    assert_eq!(info.model_path, "3D/plate_1.model");
+    assert_eq!(info.model_parts.len(), 2);
+    assert!(info.model_parts.iter().all(|c| c.exists));
+    assert!(
+        info.issues
+            .iter()
+            .any(|i| i.contains("2 3D model relationships"))
+    );
+}
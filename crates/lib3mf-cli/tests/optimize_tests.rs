@@ -0,0 +1,86 @@
+//! Integration tests for `3mf optimize`.
+
+use lib3mf_cli::commands::optimize::{self, OptimizeOptions};
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::model::{Geometry, Model};
+use lib3mf_core::parser::parse_model;
+use std::fs::File;
+use std::io::Cursor;
+use std::path::Path;
+use tempfile::TempDir;
+
+const BENCHY: &str = "../../models/Benchy.3mf";
+
+fn parse_output(path: &Path) -> Model {
+    let mut archiver = ZipArchiver::new(File::open(path).unwrap()).unwrap();
+    let model_path = find_model_path(&mut archiver).unwrap();
+    parse_model(Cursor::new(archiver.read_entry(&model_path).unwrap())).unwrap()
+}
+
+/// Triangles of the meshes the build items place directly.
+fn build_triangles(model: &Model) -> usize {
+    model
+        .build
+        .items
+        .iter()
+        .filter_map(
+            |item| match &model.resources.get_object(item.object_id)?.geometry {
+                Geometry::Mesh(mesh) => Some(mesh.triangles.len()),
+                _ => None,
+            },
+        )
+        .sum()
+}
+
+#[test]
+fn test_optimize_benchy() {
+    let dir = TempDir::new().unwrap();
+    let output = dir.path().join("optimized.3mf");
+
+    let report = optimize::run(BENCHY.into(), output.clone(), OptimizeOptions::default()).unwrap();
+
+    let before = std::fs::metadata(BENCHY).unwrap().len();
+    let after = std::fs::metadata(&output).unwrap().len();
+    assert_eq!(report.original_size, before);
+    assert_eq!(report.optimized_size, after);
+    assert!(after < before, "{after} >= {before}");
+
+    let categories: Vec<_> = report.savings.iter().map(|s| s.category).collect();
+    assert_eq!(
+        categories,
+        [
+            "repackaging",
+            "unused resources",
+            "duplicates",
+            "images",
+            "compression"
+        ]
+    );
+    let total: i64 = report.savings.iter().map(|s| s.bytes).sum();
+    assert_eq!(total, before as i64 - after as i64);
+
+    // Nothing the build uses is lost.
+    let original = parse_output(BENCHY.as_ref());
+    let optimized = parse_output(&output);
+    assert_eq!(optimized.build.items.len(), original.build.items.len());
+    assert_eq!(build_triangles(&optimized), build_triangles(&original));
+}
+
+#[test]
+fn test_optimize_is_deterministic() {
+    let dir = TempDir::new().unwrap();
+    let first = dir.path().join("first.3mf");
+    let second = dir.path().join("second.3mf");
+    let options = OptimizeOptions {
+        decimals: Some(3),
+        ..Default::default()
+    };
+
+    let report = optimize::run(BENCHY.into(), first.clone(), options).unwrap();
+    optimize::run(BENCHY.into(), second.clone(), options).unwrap();
+    assert!(report.savings.iter().any(|s| s.category == "coordinates"));
+    assert_eq!(
+        std::fs::read(&first).unwrap(),
+        std::fs::read(&second).unwrap()
+    );
+}
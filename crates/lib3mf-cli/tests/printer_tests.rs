@@ -0,0 +1,291 @@
+//! Integration tests for printer presets and `--printer`.
+
+use lib3mf_cli::commands::printer::{BedOrigin, builtin_printers, find_printer, load_printers};
+use lib3mf_core::model::{
+    BoundingBox, BuildItem, Geometry, Mesh, Model, ModelStats, Object, ObjectType,
+    PlateGeometryStats, ResourceId, Unit,
+};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn run_3mf(args: &[&str]) -> std::process::Output {
+    std::process::Command::new("cargo")
+        .args(["run", "--quiet", "-p", "lib3mf-cli", "--"])
+        .args(args)
+        .output()
+        .expect("Failed to run 3mf")
+}
+
+/// Writes a 10 mm tetrahedron placed by `transform`.
+fn write_tetrahedron(dir: &Path, transform: glam::Mat4) -> PathBuf {
+    let mut mesh = Mesh::new();
+    let v0 = mesh.add_vertex(0.0, 0.0, 0.0);
+    let v1 = mesh.add_vertex(10.0, 0.0, 0.0);
+    let v2 = mesh.add_vertex(0.0, 10.0, 0.0);
+    let v3 = mesh.add_vertex(0.0, 0.0, 10.0);
+    mesh.add_triangle(v0, v2, v1);
+    mesh.add_triangle(v0, v1, v3);
+    mesh.add_triangle(v0, v3, v2);
+    mesh.add_triangle(v1, v2, v3);
+
+    let mut model = Model::default();
+    model
+        .resources
+        .add_object(Object {
+            id: ResourceId(1),
+            object_type: ObjectType::Model,
+            name: None,
+            part_number: None,
+            uuid: None,
+            pid: None,
+            pindex: None,
+            thumbnail: None,
+            geometry: Geometry::Mesh(mesh),
+        })
+        .unwrap();
+    model.build.items.push(BuildItem {
+        object_id: ResourceId(1),
+        uuid: None,
+        path: None,
+        part_number: None,
+        transform,
+        printable: None,
+        metadata: Default::default(),
+    });
+    let path = dir.join("tetrahedron.3mf");
+    model.write(std::fs::File::create(&path).unwrap()).unwrap();
+    path
+}
+
+fn stats_with_extents(min: [f32; 3], max: [f32; 3]) -> ModelStats {
+    let mut stats = ModelStats::default();
+    stats.geometry.bounding_box = Some(BoundingBox { min, max });
+    stats
+}
+
+#[test]
+fn test_builtin_presets() {
+    let printers = builtin_printers();
+    let mk4 = printers.iter().find(|p| p.id == "prusa-mk4").unwrap();
+    assert_eq!(mk4.build_volume, [250.0, 210.0, 220.0]);
+    assert_eq!(mk4.origin, BedOrigin::Corner);
+    let x1c = printers.iter().find(|p| p.id == "bambu-x1c").unwrap();
+    assert_eq!(x1c.build_volume, [256.0; 3]);
+    assert!(
+        printers
+            .iter()
+            .any(|p| p.id.starts_with("resin-") && p.origin == BedOrigin::Center)
+    );
+}
+
+#[test]
+fn test_find_printer_ignores_case_and_lists_known_ids() {
+    let tmp = TempDir::new().unwrap();
+    let empty = tmp.path().join("printers.toml");
+    std::fs::write(&empty, "").unwrap();
+
+    let printer = find_printer("Bambu-X1C", Some(&empty)).unwrap();
+    assert_eq!(printer.name, "Bambu Lab X1 Carbon");
+
+    let err = find_printer("no-such-printer", Some(&empty))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("no-such-printer"), "{err}");
+    assert!(err.contains("prusa-mk4"), "{err}");
+}
+
+#[test]
+fn test_printers_file_adds_and_replaces_presets() {
+    let tmp = TempDir::new().unwrap();
+    let file = tmp.path().join("printers.toml");
+    std::fs::write(
+        &file,
+        r#"
+[printers.bambu-x1c]
+name = "Shrunk X1C"
+build_volume = [200, 200, 200]
+
+[printers.workshop]
+build_volume = [300.5, 300, 400]
+origin = "center"
+"#,
+    )
+    .unwrap();
+
+    let printers = load_printers(Some(&file)).unwrap();
+    assert_eq!(
+        printers.len(),
+        builtin_printers().len() + 1,
+        "replaced preset counted twice"
+    );
+    let x1c = printers.iter().find(|p| p.id == "bambu-x1c").unwrap();
+    assert_eq!(x1c.name, "Shrunk X1C");
+    assert_eq!(x1c.build_volume, [200.0; 3]);
+    let workshop = printers.iter().find(|p| p.id == "workshop").unwrap();
+    assert_eq!(workshop.name, "workshop");
+    assert_eq!(workshop.build_volume, [300.5, 300.0, 400.0]);
+    assert_eq!(workshop.origin, BedOrigin::Center);
+}
+
+#[test]
+fn test_invalid_printers_file_is_an_error() {
+    let tmp = TempDir::new().unwrap();
+    let file = tmp.path().join("printers.toml");
+
+    std::fs::write(
+        &file,
+        "[printers.a]\nbuild_volume = [100, 100, 100]\nsize = 3\n",
+    )
+    .unwrap();
+    assert!(load_printers(Some(&file)).is_err());
+
+    std::fs::write(&file, "[printers.a]\nbuild_volume = [100, 0, 100]\n").unwrap();
+    assert!(load_printers(Some(&file)).is_err());
+
+    assert!(load_printers(Some(&tmp.path().join("missing.toml"))).is_err());
+}
+
+#[test]
+fn test_fit_checks_size_then_bed_position() {
+    let mk4 = builtin_printers()
+        .into_iter()
+        .find(|p| p.id == "prusa-mk4")
+        .unwrap();
+
+    let fit = mk4.fit(&stats_with_extents([10.0, 10.0, 0.0], [110.0, 110.0, 50.0]));
+    assert!(fit.fits);
+
+    // Too deep for the 210 mm Y axis: moving it would not help
+    let fit = mk4.fit(&stats_with_extents([0.0, 0.0, 0.0], [100.0, 230.0, 50.0]));
+    assert!(!fit.fits);
+    assert_eq!(fit.oversize.len(), 1);
+    assert!(fit.oversize[0].starts_with("Y size 230.00 mm"), "{:?}", fit);
+    assert!(fit.outside_bed.is_empty());
+
+    // Small enough, but placed around the origin
+    let fit = mk4.fit(&stats_with_extents([-50.0, -50.0, 0.0], [50.0, 50.0, 50.0]));
+    assert!(!fit.fits);
+    assert!(fit.oversize.is_empty());
+    assert_eq!(fit.outside_bed.len(), 2);
+
+    // Inches are converted before comparing: 8.5 in is 215.9 mm, only too deep
+    let mut stats = stats_with_extents([0.0; 3], [8.5, 8.5, 8.5]);
+    stats.unit = Unit::Inch;
+    let fit = mk4.fit(&stats);
+    assert!(!fit.fits);
+    assert_eq!(fit.oversize.len(), 1, "{:?}", fit);
+}
+
+#[test]
+fn test_fit_centered_bed() {
+    let resin = builtin_printers()
+        .into_iter()
+        .find(|p| p.id == "resin-6in")
+        .unwrap();
+    assert_eq!(resin.bed().min, [-65.0, -40.0, 0.0]);
+
+    assert!(
+        resin
+            .fit(&stats_with_extents([-20.0, -20.0, 0.0], [20.0, 20.0, 30.0]))
+            .fits
+    );
+    let fit = resin.fit(&stats_with_extents([0.0; 3], [70.0, 20.0, 30.0]));
+    assert_eq!(fit.outside_bed.len(), 1, "{:?}", fit);
+}
+
+#[test]
+fn test_fit_checks_only_plate_sizes() {
+    let x1c = builtin_printers()
+        .into_iter()
+        .find(|p| p.id == "bambu-x1c")
+        .unwrap();
+    let mut stats = stats_with_extents([0.0; 3], [600.0, 100.0, 100.0]);
+    for (plate_id, min_x) in [(1, 0.0), (2, 300.0)] {
+        stats.plates.push(PlateGeometryStats {
+            plate_id,
+            bounding_box: Some(BoundingBox {
+                min: [min_x, 0.0, 0.0],
+                max: [min_x + 100.0, 100.0, 100.0],
+            }),
+            ..Default::default()
+        });
+    }
+    assert!(x1c.fit(&stats).fits);
+
+    stats.plates[1].bounding_box.as_mut().unwrap().max[2] = 300.0;
+    let fit = x1c.fit(&stats);
+    assert_eq!(fit.oversize.len(), 1);
+    assert!(fit.oversize[0].starts_with("Plate 2: Z size"), "{:?}", fit);
+}
+
+#[test]
+fn test_cli_printer_checks() {
+    let tmp = TempDir::new().unwrap();
+
+    let large = write_tetrahedron(tmp.path(), glam::Mat4::from_scale(glam::Vec3::splat(30.0)));
+    let result = run_3mf(&[
+        "validate",
+        large.to_str().unwrap(),
+        "--printer",
+        "bambu-x1c",
+    ]);
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert!(!result.status.success(), "{stdout}");
+    assert_eq!(stdout.matches("[ERROR 3020]").count(), 3, "{stdout}");
+    assert!(
+        run_3mf(&["validate", large.to_str().unwrap()])
+            .status
+            .success()
+    );
+
+    let result = run_3mf(&["stats", large.to_str().unwrap(), "--printer", "prusa-xl"]);
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert!(
+        stdout.contains("Printer: Original Prusa XL (360 x 360 x 360 mm)\n  Fits: yes"),
+        "{stdout}"
+    );
+
+    let offset = write_tetrahedron(
+        tmp.path(),
+        glam::Mat4::from_translation(glam::Vec3::new(-50.0, 0.0, 0.0)),
+    );
+    let result = run_3mf(&[
+        "validate",
+        offset.to_str().unwrap(),
+        "--printer",
+        "prusa-mk4",
+    ]);
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert!(result.status.success(), "{stdout}");
+    assert!(stdout.contains("[WARN 3021] X spans -50.00"), "{stdout}");
+
+    let result = run_3mf(&[
+        "stats",
+        offset.to_str().unwrap(),
+        "--printer",
+        "prusa-mk4",
+        "--format",
+        "json",
+    ]);
+    let stats: serde_json::Value = serde_json::from_slice(&result.stdout).unwrap();
+    assert_eq!(stats["printer_fit"]["printer"], "prusa-mk4");
+    assert_eq!(stats["printer_fit"]["fits"], false);
+
+    let result = run_3mf(&["stats", offset.to_str().unwrap(), "--printer", "unknown"]);
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("known printers: "));
+}
+
+#[test]
+fn test_cli_lists_printers() {
+    let result = run_3mf(&["printers", "--format", "json"]);
+    assert!(result.status.success());
+    let printers: serde_json::Value = serde_json::from_slice(&result.stdout).unwrap();
+    assert!(
+        printers
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|p| p["id"] == "prusa-mk4" && p["origin"] == "corner")
+    );
+}
@@ -0,0 +1,140 @@
+//! Integration tests for `3mf split-shells`.
+
+use lib3mf_cli::commands::split_shells;
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::model::{
+    Build, BuildItem, Geometry, Mesh, Model, Object, ObjectType, ResourceCollection, ResourceId,
+};
+use lib3mf_core::parser::parse_model;
+use std::fs::File;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn add_cube(mesh: &mut Mesh, min: f32) {
+    let base = mesh.vertices.len() as u32;
+    for i in 0..8 {
+        let bit = |b: u32| if i >> b & 1 == 1 { min + 4.0 } else { min };
+        mesh.add_vertex(bit(0), bit(1), bit(2));
+    }
+    for [a, b, c, d] in [
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+    ] {
+        mesh.add_triangle(base + a, base + b, base + c);
+        mesh.add_triangle(base + a, base + c, base + d);
+    }
+}
+
+/// Object 1 holds two separate cubes, object 2 a single cube.
+fn write_plate(dir: &Path) -> PathBuf {
+    let mut resources = ResourceCollection::new();
+    let mut build = Build::default();
+    for (id, cubes) in [(1, 2), (2, 1)] {
+        let mut mesh = Mesh::new();
+        for i in 0..cubes {
+            add_cube(&mut mesh, i as f32 * 10.0);
+        }
+        resources
+            .add_object(Object {
+                id: ResourceId(id),
+                object_type: ObjectType::Model,
+                name: Some(format!("Object {id}")),
+                part_number: None,
+                uuid: None,
+                pid: None,
+                pindex: None,
+                thumbnail: None,
+                geometry: Geometry::Mesh(mesh),
+            })
+            .unwrap();
+        build.items.push(BuildItem::new(
+            ResourceId(id),
+            glam::Mat4::from_translation(glam::Vec3::new(0.0, id as f32 * 20.0, 0.0)),
+        ));
+    }
+    let model = Model {
+        resources,
+        build,
+        ..Default::default()
+    };
+    let path = dir.join("plate.3mf");
+    model.write(File::create(&path).unwrap()).unwrap();
+    path
+}
+
+fn read_model(path: &Path) -> Model {
+    let mut archiver = ZipArchiver::new(File::open(path).unwrap()).unwrap();
+    let model_path = find_model_path(&mut archiver).unwrap();
+    parse_model(Cursor::new(archiver.read_entry(&model_path).unwrap())).unwrap()
+}
+
+/// Run the split-shells CLI command with the given arguments.
+/// Uses `cargo run` so the binary is always up-to-date.
+fn run_split_shells(input: &Path, output: &Path, args: &[&str]) -> std::process::Output {
+    std::process::Command::new("cargo")
+        .args(["run", "--quiet", "-p", "lib3mf-cli", "--"])
+        .arg("split-shells")
+        .arg(input)
+        .arg(output)
+        .args(args)
+        .output()
+        .expect("Failed to run split-shells command")
+}
+
+#[test]
+fn test_split_shells_adds_objects_and_build_items() {
+    let dir = TempDir::new().unwrap();
+    let input = write_plate(dir.path());
+    let output = dir.path().join("parts.3mf");
+
+    split_shells::run(input, output.clone(), &[]).unwrap();
+
+    let model = read_model(&output);
+    assert_eq!(model.resources.iter_objects().count(), 3);
+    let new = model.resources.get_object(ResourceId(3)).unwrap();
+    assert_eq!(new.name.as_deref(), Some("Object 1 (2)"));
+    match &new.geometry {
+        Geometry::Mesh(mesh) => {
+            assert_eq!(mesh.triangles.len(), 12);
+            assert_eq!(mesh.vertices[0].x, 10.0);
+        }
+        _ => panic!("object 3 is not a mesh"),
+    }
+    let built: Vec<(u32, f32)> = model
+        .build
+        .items
+        .iter()
+        .map(|i| (i.object_id.0, i.transform.w_axis.y))
+        .collect();
+    assert_eq!(built, vec![(1, 20.0), (3, 20.0), (2, 40.0)]);
+}
+
+#[test]
+fn test_split_shells_cli_selected_object() {
+    let dir = TempDir::new().unwrap();
+    let input = write_plate(dir.path());
+    let output = dir.path().join("parts.3mf");
+
+    let result = run_split_shells(&input, &output, &["--object-id", "2"]);
+    assert!(result.status.success(), "{result:?}");
+    assert!(String::from_utf8_lossy(&result.stdout).contains("nothing to split"));
+    assert_eq!(read_model(&output).resources.iter_objects().count(), 2);
+
+    let result = run_split_shells(&input, &output, &["--object-id", "9"]);
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("Object 9 not found"));
+}
+
+#[test]
+fn test_split_shells_rejects_external_parts() {
+    let dir = TempDir::new().unwrap();
+    let input = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../models/Benchy.3mf");
+
+    let err = split_shells::run(input, dir.path().join("parts.3mf"), &[]).unwrap_err();
+    assert!(err.to_string().contains("external model parts"), "{err}");
+}
@@ -81,6 +81,7 @@ fn create_test_3mf(
         part_number: None,
         transform: glam::Mat4::IDENTITY,
         printable: None,
+        metadata: Default::default(),
     });
 
     let mut model = Model {
@@ -154,6 +155,7 @@ fn create_multi_object_3mf(
             part_number: None,
             transform: glam::Mat4::IDENTITY,
             printable: None,
+            metadata: Default::default(),
         });
     }
 
@@ -576,6 +578,7 @@ fn test_split_preserve_transforms() {
         path: None,
         part_number: None,
         printable: None,
+        metadata: Default::default(),
     });
 
     let model = Model {
@@ -0,0 +1,300 @@
+//! Integration tests for `3mf stats --memory`, `--intern-meshes` and cached statistics.
+
+use lib3mf_core::model::{
+    BooleanOperation, BooleanOperationType, BooleanShape, BuildItem, Geometry, Mesh, Model, Object,
+    ObjectType, ResourceId, Slice, SliceStack, Triangle,
+};
+
+const BENCHY: &str = "../../models/Benchy.3mf";
+
+fn run_3mf(args: &[&str]) -> std::process::Output {
+    std::process::Command::new("cargo")
+        .args(["run", "--quiet", "-p", "lib3mf-cli", "--"])
+        .args(args)
+        .output()
+        .expect("Failed to run 3mf")
+}
+
+#[test]
+fn test_stats_memory_counts_referenced_parts() {
+    let result = run_3mf(&["stats", BENCHY, "--memory", "--format", "json"]);
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+    let stats: serde_json::Value = serde_json::from_slice(&result.stdout).unwrap();
+    let memory = &stats["memory"];
+    // The root part only has components; the meshes live in the parts they point at
+    assert!(memory["meshes"].as_u64().unwrap() > 0, "{memory}");
+    assert!(memory["components"].as_u64().unwrap() > 0, "{memory}");
+    let sum: u64 = memory
+        .as_object()
+        .unwrap()
+        .iter()
+        .filter(|(k, _)| *k != "total")
+        .map(|(_, v)| v.as_u64().unwrap())
+        .sum();
+    assert_eq!(memory["total"].as_u64(), Some(sum));
+
+    let result = run_3mf(&["stats", BENCHY]);
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert!(!stdout.contains("Memory (estimated)"), "{stdout}");
+    assert!(
+        stdout.contains("Unit: millimeter (mm, scale: 0.001 m)"),
+        "{stdout}"
+    );
+    assert!(stdout.contains(" mm^3\n"), "{stdout}");
+    assert!(stdout.contains(" (all instances)\n"), "{stdout}");
+    assert!(stdout.contains("  Unique Meshes: "), "{stdout}");
+
+    let result = run_3mf(&["stats", BENCHY, "--memory"]);
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert!(stdout.contains("Memory (estimated):"), "{stdout}");
+    assert!(stdout.contains("  Meshes:"), "{stdout}");
+
+    // Tree output has nowhere to show the estimate
+    assert!(
+        !run_3mf(&["stats", BENCHY, "--memory", "--tree"])
+            .status
+            .success()
+    );
+}
+
+/// JSON stats report without `system_info`, which is detected at report time.
+fn stats_json(args: &[&str]) -> serde_json::Value {
+    let result = run_3mf(args);
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+    let mut stats: serde_json::Value = serde_json::from_slice(&result.stdout).unwrap();
+    stats.as_object_mut().unwrap().remove("system_info");
+    stats
+}
+
+#[test]
+fn test_stats_from_cache_match_computed() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let output = dir.path().join("cached.3mf");
+    let output = output.to_str().unwrap();
+
+    let result = run_3mf(&["copy", BENCHY, output, "--stats-cache"]);
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+    let listing = run_3mf(&["list", output]);
+    let listing = String::from_utf8_lossy(&listing.stdout);
+    assert!(listing.contains("lib3mf-stats.json"), "{listing}");
+
+    let cached = stats_json(&["stats", output, "--format", "json"]);
+    let computed = stats_json(&["stats", output, "--format", "json", "--no-cache"]);
+    assert!(cached["geometry"]["triangle_count"].as_u64().unwrap() > 0);
+    assert_eq!(cached, computed);
+}
+
+fn object(id: u32, name: &str, geometry: Geometry) -> Object {
+    Object {
+        id: ResourceId(id),
+        object_type: ObjectType::Model,
+        name: Some(name.to_string()),
+        part_number: None,
+        uuid: None,
+        pid: None,
+        pindex: None,
+        thumbnail: None,
+        geometry,
+    }
+}
+
+#[test]
+fn test_stats_tree_shows_extension_geometry() {
+    let mut mesh = Mesh::new();
+    mesh.add_vertex(0.0, 0.0, 0.0);
+    mesh.add_vertex(10.0, 0.0, 0.0);
+    mesh.add_vertex(0.0, 10.0, 0.0);
+    mesh.add_triangle(0, 1, 2);
+
+    let mut model = Model::default();
+    let resources = &mut model.resources;
+    resources
+        .add_object(object(1, "Block", Geometry::Mesh(mesh.clone())))
+        .unwrap();
+    resources
+        .add_object(object(2, "Hole", Geometry::Mesh(mesh)))
+        .unwrap();
+    resources
+        .add_object(object(
+            3,
+            "Drilled",
+            Geometry::BooleanShape(BooleanShape {
+                base_object_id: ResourceId(1),
+                base_transform: glam::Mat4::IDENTITY,
+                base_path: None,
+                operations: vec![BooleanOperation {
+                    operation_type: BooleanOperationType::Difference,
+                    object_id: ResourceId(2),
+                    transform: glam::Mat4::IDENTITY,
+                    path: None,
+                }],
+            }),
+        ))
+        .unwrap();
+    resources
+        .add_slice_stack(SliceStack {
+            id: ResourceId(4),
+            z_bottom: 0.0,
+            slices: vec![
+                Slice {
+                    z_top: 0.5,
+                    ..Default::default()
+                },
+                Slice {
+                    z_top: 1.0,
+                    ..Default::default()
+                },
+            ],
+            refs: Vec::new(),
+        })
+        .unwrap();
+    resources
+        .add_object(object(5, "Sliced", Geometry::SliceStack(ResourceId(4))))
+        .unwrap();
+    model
+        .build
+        .items
+        .push(BuildItem::new(ResourceId(3), glam::Mat4::IDENTITY));
+    model
+        .build
+        .items
+        .push(BuildItem::new(ResourceId(5), glam::Mat4::IDENTITY));
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("extensions.3mf");
+    model.write(std::fs::File::create(&path).unwrap()).unwrap();
+
+    let result = run_3mf(&["stats", path.to_str().unwrap(), "--tree"]);
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert!(result.status.success(), "{stdout}");
+    for line in [
+        "Boolean Shape",
+        "Base [Block] (ID: 1)",
+        "Operation 1: Difference [Hole] (ID: 2)",
+        "Mesh: 3 vertices, 1 triangles",
+        "Slice Stack 4: 2 layers, z 0 to 1",
+    ] {
+        assert!(stdout.contains(line), "missing {line:?} in\n{stdout}");
+    }
+    assert!(!stdout.contains("Unknown Geometry"), "{stdout}");
+}
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    use std::io::Write;
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn test_stats_reads_gzip_and_bare_model_xml() {
+    let dir = tempfile::TempDir::new().unwrap();
+
+    let benchy_gz = dir.path().join("Benchy.3mf.gz");
+    std::fs::write(&benchy_gz, gzip(&std::fs::read(BENCHY).unwrap())).unwrap();
+    let plain = stats_json(&["stats", BENCHY, "--format", "json"]);
+    let compressed = stats_json(&["stats", benchy_gz.to_str().unwrap(), "--format", "json"]);
+    assert_eq!(compressed["geometry"], plain["geometry"]);
+
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    <resources>
+        <object id="1" type="model">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="10" y="0" z="0" />
+                    <vertex x="0" y="10" z="0" />
+                    <vertex x="0" y="0" z="10" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="2" v3="1" />
+                    <triangle v1="0" v2="1" v3="3" />
+                    <triangle v1="0" v2="3" v3="2" />
+                    <triangle v1="1" v2="2" v3="3" />
+                </triangles>
+            </mesh>
+        </object>
+    </resources>
+    <build>
+        <item objectid="1" />
+    </build>
+</model>"#;
+    let model = dir.path().join("3dmodel.model");
+    std::fs::write(&model, xml).unwrap();
+    // The extension does not matter: the contents are sniffed
+    let model_gz = dir.path().join("part.gz");
+    std::fs::write(&model_gz, gzip(xml.as_bytes())).unwrap();
+    for path in [&model, &model_gz] {
+        let stats = stats_json(&["stats", path.to_str().unwrap(), "--format", "json"]);
+        assert_eq!(stats["geometry"]["triangle_count"], 4, "{stats}");
+    }
+
+    // A bare part lists as the only entry
+    let result = run_3mf(&["list", model.to_str().unwrap()]);
+    assert!(result.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&result.stdout).trim(),
+        "3dmodel.model"
+    );
+}
+
+#[test]
+fn test_stats_intern_meshes_reports_shared_arrays() {
+    let mut mesh = Mesh::new();
+    mesh.add_vertex(0.0, 0.0, 0.0);
+    mesh.add_vertex(10.0, 0.0, 0.0);
+    mesh.add_vertex(0.0, 10.0, 0.0);
+    mesh.add_triangle(0, 1, 2);
+
+    // The copies are written out separately and parsed into separate arrays
+    let mut model = Model::default();
+    for (id, name) in [(1, "Left"), (2, "Right")] {
+        model
+            .resources
+            .add_object(object(id, name, Geometry::Mesh(mesh.clone())))
+            .unwrap();
+        model.build.items.push(BuildItem {
+            object_id: ResourceId(id),
+            uuid: None,
+            path: None,
+            part_number: None,
+            transform: glam::Mat4::IDENTITY,
+            printable: None,
+            metadata: Default::default(),
+        });
+    }
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("copies.3mf");
+    model.write(std::fs::File::create(&path).unwrap()).unwrap();
+    let path = path.to_str().unwrap();
+
+    let plain = stats_json(&["stats", path, "--format", "json"]);
+    assert_eq!(plain["geometry"]["unique"]["mesh_count"], 2);
+    assert_eq!(plain["geometry"]["unique"]["shared_buffers"], 0);
+
+    let interned = stats_json(&["stats", path, "--format", "json", "--intern-meshes"]);
+    assert_eq!(interned["geometry"]["unique"]["mesh_count"], 2);
+    assert_eq!(interned["geometry"]["unique"]["shared_buffers"], 2);
+    let bytes = 3 * 12 + std::mem::size_of::<Triangle>();
+    assert_eq!(interned["geometry"]["unique"]["deduplicated_bytes"], bytes);
+
+    let text = run_3mf(&["stats", path, "--intern-meshes"]);
+    let text = String::from_utf8_lossy(&text.stdout);
+    assert!(
+        text.contains(&format!("Shared Arrays: 2 ({bytes} bytes deduplicated)")),
+        "{text}"
+    );
+}
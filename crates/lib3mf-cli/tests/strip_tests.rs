@@ -0,0 +1,41 @@
+//! Integration tests for `3mf strip`.
+
+use lib3mf_cli::commands::strip::{self, StripTarget};
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::parser::parse_model;
+use std::fs::File;
+use std::io::Cursor;
+use tempfile::TempDir;
+
+const BENCHY: &str = "../../models/Benchy.3mf";
+
+#[test]
+fn test_strip_benchy() {
+    let dir = TempDir::new().unwrap();
+    let output = dir.path().join("stripped.3mf");
+
+    strip::run(
+        BENCHY.into(),
+        output.clone(),
+        &[StripTarget::Thumbnails, StripTarget::VendorMetadata],
+    )
+    .unwrap();
+
+    let before = std::fs::metadata(BENCHY).unwrap().len();
+    let after = std::fs::metadata(&output).unwrap().len();
+    assert!(after < before, "{after} >= {before}");
+
+    let mut archiver = ZipArchiver::new(File::open(&output).unwrap()).unwrap();
+    let entries = archiver.list_entries().unwrap();
+    assert!(
+        !entries
+            .iter()
+            .any(|e| e.starts_with("Metadata/") || e.starts_with("Auxiliaries/")),
+        "{entries:?}"
+    );
+
+    let model_path = find_model_path(&mut archiver).unwrap();
+    let model = parse_model(Cursor::new(archiver.read_entry(&model_path).unwrap())).unwrap();
+    assert!(model.metadata.names().all(|k| !k.contains(':')));
+    assert!(!model.build.items.is_empty());
+}
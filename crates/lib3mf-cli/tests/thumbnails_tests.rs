@@ -0,0 +1,181 @@
+//! Integration tests for `3mf thumbnails` image processing.
+
+#![cfg(feature = "image-processing")]
+
+use image::{ImageFormat, Rgba, RgbaImage};
+use lib3mf_cli::commands::thumbnails::{self, ImageOptions, ThumbnailFormat, parse_size};
+use lib3mf_core::model::{BuildItem, Geometry, Mesh, Model, Object, ObjectType, ResourceId};
+use std::fs::File;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Encodes a solid `width` x `height` PNG.
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let img = RgbaImage::from_pixel(width, height, Rgba([200, 30, 30, 255]));
+    let mut data = Cursor::new(Vec::new());
+    img.write_to(&mut data, ImageFormat::Png).unwrap();
+    data.into_inner()
+}
+
+/// Writes a 3MF with three objects (1-3), each with a 64x32 PNG thumbnail.
+fn create_test_3mf(dir: &Path) -> PathBuf {
+    create_3mf_with_thumbnails(dir, |_| png(64, 32))
+}
+
+/// Writes a 3MF with three objects (1-3) whose thumbnail data `thumbnail` returns by ID.
+fn create_3mf_with_thumbnails(dir: &Path, thumbnail: impl Fn(u32) -> Vec<u8>) -> PathBuf {
+    let mut model = Model::default();
+    for id in 1..=3 {
+        let mut mesh = Mesh::new();
+        let v0 = mesh.add_vertex(0.0, 0.0, 0.0);
+        let v1 = mesh.add_vertex(10.0, 0.0, 0.0);
+        let v2 = mesh.add_vertex(0.0, 10.0, 0.0);
+        mesh.add_triangle(v0, v1, v2);
+
+        let path = format!("/3D/Thumbnails/object_{}.png", id);
+        model
+            .attachments
+            .insert(path.trim_start_matches('/').to_string(), thumbnail(id));
+        model
+            .resources
+            .add_object(Object {
+                id: ResourceId(id),
+                object_type: ObjectType::Model,
+                name: None,
+                part_number: None,
+                uuid: None,
+                pid: None,
+                pindex: None,
+                thumbnail: Some(path),
+                geometry: Geometry::Mesh(mesh),
+            })
+            .unwrap();
+    }
+    for id in 1..=3 {
+        model.build.items.push(BuildItem {
+            object_id: ResourceId(id),
+            uuid: None,
+            path: None,
+            part_number: None,
+            transform: glam::Mat4::IDENTITY,
+            printable: None,
+            metadata: Default::default(),
+        });
+    }
+
+    let path = dir.join("thumbs.3mf");
+    model.write(File::create(&path).unwrap()).unwrap();
+    path
+}
+
+#[test]
+fn test_extract_resized_and_converted() {
+    let dir = TempDir::new().unwrap();
+    let file = create_test_3mf(dir.path());
+    let out = dir.path().join("out");
+
+    let image = ImageOptions {
+        resize: Some((16, 16)),
+        convert: Some(ThumbnailFormat::Jpeg),
+    };
+    thumbnails::run(file, false, Some(out.clone()), None, None, None, &image).unwrap();
+
+    for id in 1..=3 {
+        let data = std::fs::read(out.join(format!("obj_{}_thumbnail.jpg", id))).unwrap();
+        assert_eq!(image::guess_format(&data).unwrap(), ImageFormat::Jpeg);
+        let img = image::load_from_memory(&data).unwrap();
+        // Aspect ratio is kept
+        assert_eq!((img.width(), img.height()), (16, 8));
+    }
+}
+
+#[test]
+fn test_contact_sheet_grid() {
+    let dir = TempDir::new().unwrap();
+    let file = create_test_3mf(dir.path());
+    let sheet = dir.path().join("sheet.png");
+
+    let image = ImageOptions {
+        resize: Some((32, 32)),
+        convert: None,
+    };
+    thumbnails::run(file, false, None, None, None, Some(sheet.clone()), &image).unwrap();
+
+    // Three thumbnails make a 2x2 grid of 32x32 tiles
+    let img = image::open(&sheet).unwrap();
+    assert_eq!((img.width(), img.height()), (64, 64));
+    let img = img.to_rgba8();
+    // Thumbnails are centered in their tiles; the fourth tile is background
+    assert_eq!(img.get_pixel(16, 16), &Rgba([200, 30, 30, 255]));
+    assert_eq!(img.get_pixel(16, 2), &Rgba([255, 255, 255, 255]));
+    assert_eq!(img.get_pixel(48, 48), &Rgba([255, 255, 255, 255]));
+}
+
+#[test]
+fn test_inject_resized() {
+    let dir = TempDir::new().unwrap();
+    let file = create_test_3mf(dir.path());
+    let img_path = dir.path().join("big.png");
+    std::fs::write(&img_path, png(512, 512)).unwrap();
+
+    let image = ImageOptions {
+        resize: Some((128, 128)),
+        convert: None,
+    };
+    thumbnails::run(
+        file.clone(),
+        false,
+        None,
+        Some(img_path),
+        Some(2),
+        None,
+        &image,
+    )
+    .unwrap();
+
+    let out = dir.path().join("out");
+    thumbnails::run(
+        file,
+        false,
+        Some(out.clone()),
+        None,
+        None,
+        None,
+        &ImageOptions::default(),
+    )
+    .unwrap();
+    let img = image::open(out.join("obj_2_thumbnail.png")).unwrap();
+    assert_eq!((img.width(), img.height()), (128, 128));
+}
+
+#[test]
+fn test_parse_size() {
+    assert_eq!(parse_size("256x128"), Ok((256, 128)));
+    assert_eq!(parse_size("64X64"), Ok((64, 64)));
+    assert!(parse_size("256").is_err());
+    assert!(parse_size("0x10").is_err());
+    assert!(parse_size("axb").is_err());
+}
+
+#[test]
+fn test_validate_strict_checks_thumbnails() {
+    let dir = TempDir::new().unwrap();
+    let path = create_3mf_with_thumbnails(dir.path(), |id| match id {
+        2 => b"\xff\xd8\xff\xe0not a png".to_vec(),
+        _ => png(64, 32),
+    });
+
+    let output = std::process::Command::new("cargo")
+        .args(["run", "--quiet", "-p", "lib3mf-cli", "--", "validate"])
+        .arg(&path)
+        .args(["--level", "strict"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("[ERROR 1012] Thumbnail '3D/Thumbnails/object_2.png'"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("object_1.png"), "{stdout}");
+}
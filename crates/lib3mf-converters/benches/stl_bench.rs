@@ -0,0 +1,75 @@
+//! STL import/export throughput over the shared generated fixtures.
+//!
+//! Set `LIB3MF_BENCH_HUGE=1` to include the 5M-triangle mesh.
+
+#[path = "../../lib3mf-core/benches/fixtures/mod.rs"]
+mod fixtures;
+
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use lib3mf_converters::stl::{StlExportOptions, StlExporter, StlFormat, StlImporter};
+use std::io::Cursor;
+
+const ASCII: StlExporter = StlExporter {
+    options: StlExportOptions {
+        format: StlFormat::Ascii,
+    },
+};
+
+fn bench_stl_export(c: &mut Criterion) {
+    let mut group = c.benchmark_group("stl/export");
+    group.sample_size(10);
+
+    for (label, tris) in fixtures::mesh_sizes() {
+        let model = fixtures::mesh_model(tris);
+        group.throughput(Throughput::Elements(tris as u64));
+        group.bench_with_input(BenchmarkId::new("binary", label), &model, |b, model| {
+            b.iter(|| {
+                let mut out = Vec::new();
+                StlExporter::default()
+                    .write(black_box(model), &mut out)
+                    .expect("export failed");
+                black_box(out);
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("ascii", label), &model, |b, model| {
+            b.iter(|| {
+                let mut out = Vec::new();
+                ASCII
+                    .write(black_box(model), &mut out)
+                    .expect("export failed");
+                black_box(out);
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_stl_import(c: &mut Criterion) {
+    let mut group = c.benchmark_group("stl/import");
+    group.sample_size(10);
+
+    for (label, tris) in fixtures::mesh_sizes() {
+        let model = fixtures::mesh_model(tris);
+        let mut binary = Vec::new();
+        StlExporter::default()
+            .write(&model, &mut binary)
+            .expect("export failed");
+        let mut ascii = Vec::new();
+        ASCII.write(&model, &mut ascii).expect("export failed");
+
+        group.throughput(Throughput::Bytes(binary.len() as u64));
+        group.bench_with_input(BenchmarkId::new("binary", label), &binary, |b, data| {
+            b.iter(|| black_box(StlImporter::read(Cursor::new(black_box(data))).unwrap()));
+        });
+        group.throughput(Throughput::Bytes(ascii.len() as u64));
+        group.bench_with_input(BenchmarkId::new("ascii", label), &ascii, |b, data| {
+            b.iter(|| black_box(StlImporter::read(Cursor::new(black_box(data))).unwrap()));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_stl_export, bench_stl_import);
+criterion_main!(benches);
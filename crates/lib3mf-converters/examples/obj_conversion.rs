@@ -41,6 +41,7 @@ fn main() -> anyhow::Result<()> {
         part_number: None,
         transform: glam::Mat4::IDENTITY,
         printable: None,
+        metadata: Default::default(),
     });
 
     // 2. Export to OBJ
@@ -1,4 +1,4 @@
-use lib3mf_converters::stl::{BinaryStlExporter, StlImporter};
+use lib3mf_converters::stl::{StlExporter, StlImporter};
 use lib3mf_core::model::{Model, Unit};
 use std::fs::File;
 use std::io::{BufReader, BufWriter};
@@ -39,13 +39,14 @@ fn main() -> anyhow::Result<()> {
         part_number: None,
         transform: glam::Mat4::IDENTITY,
         printable: None,
+        metadata: Default::default(),
     });
 
     // 2. Export to STL
     let stl_path = "triangle.stl";
     println!("Exporting to {}...", stl_path);
     let stl_file = File::create(stl_path)?;
-    BinaryStlExporter::write(&model, BufWriter::new(stl_file))?;
+    StlExporter::default().write(&model, BufWriter::new(stl_file))?;
 
     // 3. Import back from STL
     println!("Importing back from {}...", stl_path);
@@ -0,0 +1,263 @@
+//! File input for the importers: buffered or memory-mapped reading with progress reporting.
+//!
+//! The STL and OBJ importers parse their input as a stream, so a multi-gigabyte scan export
+//! never has to be held in memory as a whole; only the resulting mesh is. [`InputFile`]
+//! is the reader they use when importing from a path:
+//!
+//! - By default the file is read through a [`CHUNK_SIZE`] buffer.
+//! - With the `mmap` feature and [`ImportOptions::memory_map`] set, the file is mapped into
+//!   memory instead, leaving paging to the operating system.
+//! - A [`Progress`] callback receives the number of bytes consumed so far and the file size,
+//!   at most once per [`CHUNK_SIZE`] bytes and once at the end.
+//!
+//! ```no_run
+//! use lib3mf_converters::input::Progress;
+//! use lib3mf_converters::registry::{ImportOptions, ModelImporter};
+//! use lib3mf_converters::stl::StlImporter;
+//! use std::path::Path;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut options = ImportOptions::default();
+//! options.progress = Some(Progress::new(|done, total| {
+//!     eprint!("\r{:3}%", done * 100 / total.max(1));
+//! }));
+//! let model = StlImporter::new().import(Path::new("scan.stl"), &options)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`ImportOptions::memory_map`]: crate::registry::ImportOptions::memory_map
+
+use crate::registry::ImportOptions;
+use lib3mf_core::error::{Lib3mfError, Result};
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Size of the read buffer, and the interval in bytes between [`Progress`] reports.
+pub const CHUNK_SIZE: usize = 1 << 20;
+
+/// Callback receiving `(bytes_read, total_bytes)` while a file is imported.
+///
+/// Cloning shares the callback. Two values are equal if they share the same callback.
+#[derive(Clone)]
+pub struct Progress(Arc<dyn Fn(u64, u64) + Send + Sync>);
+
+impl Progress {
+    /// Wraps `callback`.
+    pub fn new(callback: impl Fn(u64, u64) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+
+    /// Calls the callback.
+    pub fn report(&self, bytes_read: u64, total_bytes: u64) {
+        (self.0)(bytes_read, total_bytes)
+    }
+}
+
+impl fmt::Debug for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Progress(..)")
+    }
+}
+
+impl PartialEq for Progress {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+enum Source {
+    Buffered(BufReader<File>),
+    #[cfg(feature = "mmap")]
+    Mapped(std::io::Cursor<memmap2::Mmap>),
+}
+
+/// A file opened for import, implementing [`Read`], [`BufRead`] and [`Seek`].
+pub struct InputFile {
+    source: Source,
+    position: u64,
+    len: u64,
+    reported: u64,
+    progress: Option<Progress>,
+}
+
+impl InputFile {
+    /// Opens `path` as [`ImportOptions::memory_map`] and [`ImportOptions::progress`] ask.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Lib3mfError::Io`] if the file cannot be opened or mapped.
+    pub fn open(path: &Path, options: &ImportOptions) -> Result<Self> {
+        let file = File::open(path).map_err(Lib3mfError::Io)?;
+        let len = file.metadata().map_err(Lib3mfError::Io)?.len();
+        Ok(Self {
+            source: Self::source(file, options.memory_map)?,
+            position: 0,
+            len,
+            reported: 0,
+            progress: options.progress.clone(),
+        })
+    }
+
+    #[cfg(feature = "mmap")]
+    fn source(file: File, memory_map: bool) -> Result<Source> {
+        if !memory_map {
+            return Ok(Source::Buffered(BufReader::with_capacity(CHUNK_SIZE, file)));
+        }
+        // SAFETY: the map is only read, and only while the file is open. Another process
+        // truncating the file meanwhile is undefined behavior, which callers opt into by
+        // setting `memory_map`.
+        let map = unsafe { memmap2::Mmap::map(&file) }.map_err(Lib3mfError::Io)?;
+        Ok(Source::Mapped(std::io::Cursor::new(map)))
+    }
+
+    #[cfg(not(feature = "mmap"))]
+    fn source(file: File, _memory_map: bool) -> Result<Source> {
+        Ok(Source::Buffered(BufReader::with_capacity(CHUNK_SIZE, file)))
+    }
+
+    /// Size of the file in bytes.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns true for an empty file.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns true if the file is memory-mapped rather than read through a buffer.
+    pub fn is_mapped(&self) -> bool {
+        !matches!(self.source, Source::Buffered(_))
+    }
+
+    fn advance(&mut self, bytes: usize) {
+        self.position += bytes as u64;
+        if let Some(progress) = &self.progress
+            && (self.position >= self.reported + CHUNK_SIZE as u64
+                || (self.position >= self.len && self.reported < self.len))
+        {
+            self.reported = self.position;
+            progress.report(self.position.min(self.len), self.len);
+        }
+    }
+}
+
+impl Read for InputFile {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = match &mut self.source {
+            Source::Buffered(reader) => reader.read(buf)?,
+            #[cfg(feature = "mmap")]
+            Source::Mapped(reader) => reader.read(buf)?,
+        };
+        self.advance(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for InputFile {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        match &mut self.source {
+            Source::Buffered(reader) => reader.fill_buf(),
+            #[cfg(feature = "mmap")]
+            Source::Mapped(reader) => reader.fill_buf(),
+        }
+    }
+
+    fn consume(&mut self, amount: usize) {
+        match &mut self.source {
+            Source::Buffered(reader) => reader.consume(amount),
+            #[cfg(feature = "mmap")]
+            Source::Mapped(reader) => reader.consume(amount),
+        }
+        self.advance(amount);
+    }
+}
+
+impl Seek for InputFile {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.position = match &mut self.source {
+            Source::Buffered(reader) => reader.seek(pos)?,
+            #[cfg(feature = "mmap")]
+            Source::Mapped(reader) => reader.seek(pos)?,
+        };
+        // Format detection reads the head and rewinds; only count progress past it
+        self.reported = self.reported.min(self.position);
+        Ok(self.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn recorded(options: &mut ImportOptions) -> Arc<Mutex<Vec<(u64, u64)>>> {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let sink = reports.clone();
+        options.progress = Some(Progress::new(move |done, total| {
+            sink.lock().unwrap().push((done, total))
+        }));
+        reports
+    }
+
+    fn read_all(path: &Path, options: &ImportOptions) -> Vec<u8> {
+        let mut input = InputFile::open(path, options).unwrap();
+        let mut head = [0u8; 16];
+        input.read_exact(&mut head).unwrap();
+        input.seek(SeekFrom::Start(0)).unwrap();
+        let mut data = Vec::new();
+        input.read_to_end(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_progress_reports_up_to_file_size() {
+        let path = std::env::temp_dir().join("lib3mf_input_progress.bin");
+        let content: Vec<u8> = (0..CHUNK_SIZE * 3 + 17).map(|i| i as u8).collect();
+        std::fs::write(&path, &content).unwrap();
+
+        let mut options = ImportOptions::default();
+        let reports = recorded(&mut options);
+        let data = read_all(&path, &options);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(data, content);
+        let reports = reports.lock().unwrap();
+        assert!(reports.len() >= 3, "{reports:?}");
+        assert!(reports.windows(2).all(|w| w[0].0 < w[1].0), "{reports:?}");
+        let total = content.len() as u64;
+        assert_eq!(reports.last(), Some(&(total, total)));
+    }
+
+    #[test]
+    fn test_progress_eq_shares_callback() {
+        let progress = Progress::new(|_, _| {});
+        assert_eq!(progress, progress.clone());
+        assert_ne!(progress, Progress::new(|_, _| {}));
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_memory_mapped_input_reads_file() {
+        let path = std::env::temp_dir().join("lib3mf_input_mmap.bin");
+        let content: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&path, &content).unwrap();
+
+        let mut options = ImportOptions {
+            memory_map: true,
+            ..Default::default()
+        };
+        assert!(InputFile::open(&path, &options).unwrap().is_mapped());
+        let reports = recorded(&mut options);
+        let data = read_all(&path, &options);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(data, content);
+        let total = content.len() as u64;
+        assert_eq!(reports.lock().unwrap().last(), Some(&(total, total)));
+    }
+}
@@ -39,12 +39,21 @@
 //! ## Modules
 //!
 //! - [`stl`]: Binary and ASCII STL import and export
+//! - [`input`]: Buffered or memory-mapped (`mmap` feature) file input with progress callbacks,
+//!   used by the streaming STL and OBJ parsers
 //! - [`obj`]: Wavefront OBJ import and export
+//! - [`registry`]: Format lookup by extension or file signature, with runtime registration of
+//!   custom importers and exporters
+//! - [`transform`]: Scaling, unit and up-axis conversion, vertex welding, transform baking,
+//!   and merging or splitting objects
+//! - `render`: Position/normal/index buffers and instance transforms for graphics engines
+//!   (requires the `render` feature)
 //!
 //! ## Limitations
 //!
 //! - **STL**: No color or material attribute support in STL format
-//! - **OBJ**: Materials (mtllib/usemtl), texture coordinates (vt), and normals (vn) are ignored during import
+//! - **OBJ**: Texture coordinates (vt) are ignored during import; normals (vn) are kept only
+//!   when every face of an object has them
 //! - **OBJ**: Export does not include materials or textures, only geometry
 //! - Vertex deduplication in STL import uses bitwise float comparison (exact match required)
 //!
@@ -61,6 +70,11 @@
 //! [`lib3mf_core::model::Mesh`]: https://docs.rs/lib3mf-core/latest/lib3mf_core/model/struct.Mesh.html
 //! [`lib3mf_core::error::Lib3mfError`]: https://docs.rs/lib3mf-core/latest/lib3mf_core/error/enum.Lib3mfError.html
 
+pub mod input;
 pub mod mtl;
 pub mod obj;
+pub mod registry;
+#[cfg(feature = "render")]
+pub mod render;
 pub mod stl;
+pub mod transform;
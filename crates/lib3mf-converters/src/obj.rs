@@ -8,14 +8,17 @@
 //!
 //! **Supported features:**
 //! - `v` - Vertex positions (x, y, z)
-//! - `f` - Faces (vertex indices, with automatic fan triangulation for polygons)
-//! - `g` / `o` - Group/object directives (each creates a separate 3MF Object)
+//! - `vn` - Vertex normals, kept as [`Mesh::normals`] when every face of an object has them
+//! - `f` - Faces (vertex indices, negative ones relative to the last vertex; polygons are
+//!   triangulated by ear clipping). Faces with invalid indices fail the import, listing all
+//!   of them, unless [`ObjImportOptions::skip_invalid_faces`] leaves them out
+//! - `g` / `o` - Group/object directives (each creates a separate 3MF Object; after the
+//!   first `o`, groups are parts of the enclosing object)
 //! - `usemtl` - Material assignment (maps to per-triangle `pid`/`p1`/`p2`/`p3`)
 //! - `mtllib` - Material library file reference (parsed via [`mtl`] module)
 //!
 //! **Ignored features:**
 //! - `vt` - Texture coordinates
-//! - `vn` - Vertex normals
 //!
 //! ## Material Import
 //!
@@ -74,18 +77,26 @@
 //! [`Model`]: lib3mf_core::model::Model
 //! [`BaseMaterial`]: lib3mf_core::model::BaseMaterial
 //! [`BaseMaterialsGroup`]: lib3mf_core::model::BaseMaterialsGroup
+//! [`Mesh::normals`]: lib3mf_core::model::Mesh::normals
 
+use crate::input::{InputFile, Progress};
 use crate::mtl;
+use crate::registry::{ExportOptions, ImportOptions, ModelExporter, ModelImporter};
+use crate::transform::CoordinateSystem;
 use lib3mf_core::error::{Lib3mfError, Result};
 use lib3mf_core::model::resources::ResourceId;
 use lib3mf_core::model::{
-    BaseMaterial, BaseMaterialsGroup, BuildItem, Color, Mesh, Model, Object, ObjectType, Triangle,
-    Vertex,
+    BaseMaterial, BaseMaterialsGroup, BuildItem, Color, Mesh, MeshNormals, Model, Object,
+    ObjectType, Triangle, Unit, Vertex,
 };
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::io::{BufRead, BufReader, Read, Write};
 use std::path::Path;
 
+/// Marks a global vertex not yet added to the mesh being built.
+const UNMAPPED: u32 = u32::MAX;
+
 /// Default gray color for undefined materials.
 const DEFAULT_GRAY: Color = Color {
     r: 128,
@@ -98,10 +109,13 @@ const DEFAULT_GRAY: Color = Color {
 // Intermediate representation for OBJ parsing
 // ---------------------------------------------------------------------------
 
-/// A face parsed from OBJ, storing global 0-based vertex indices.
+/// A triangle parsed from OBJ, storing global 0-based vertex indices.
 struct ObjFace {
-    indices: Vec<u32>,
-    material_name: Option<String>,
+    indices: [u32; 3],
+    /// Global 0-based normal indices, if every corner has a valid one.
+    normals: Option<[u32; 3]>,
+    /// Index into [`ObjIntermediate::material_names`].
+    material: Option<u32>,
 }
 
 /// A group/object parsed from OBJ.
@@ -112,10 +126,88 @@ struct ObjGroup {
 
 /// Complete intermediate representation of a parsed OBJ file.
 struct ObjIntermediate {
-    global_vertices: Vec<(f32, f32, f32)>,
+    global_vertices: Vec<Vertex>,
+    global_normals: Vec<glam::Vec3>,
     groups: Vec<ObjGroup>,
+    /// Names given to `usemtl`, each stored once.
+    material_names: Vec<String>,
     mtllib: Option<String>,
     had_explicit_group: bool,
+    /// Faces left out because of invalid indices.
+    face_errors: Vec<ObjFaceError>,
+}
+
+/// An OBJ face left out of the import because of an invalid vertex index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjFaceError {
+    /// 1-based line number of the face in the file.
+    pub line: usize,
+    /// What is wrong with the face.
+    pub message: String,
+}
+
+impl std::fmt::Display for ObjFaceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Options for [`ObjImporter::read_from_path_with_options`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjImportOptions {
+    /// Load `.mtl` material libraries referenced by the file (default: true).
+    pub materials: bool,
+    /// Unit of the file's coordinates, which OBJ does not record (default: millimeter).
+    pub unit: Unit,
+    /// Factor applied to all coordinates (default: 1.0).
+    pub scale: f32,
+    /// Axis convention of the file (default: Z-up, right-handed). Other conventions are
+    /// converted to that of 3MF.
+    pub axes: CoordinateSystem,
+    /// Distance below which vertices are merged (default: 0.0, off).
+    pub weld_epsilon: f32,
+    /// Called with the bytes read so far and the file size while parsing (default: none).
+    pub progress: Option<Progress>,
+    /// Memory-map the file instead of reading it through a buffer (default: false). Has no
+    /// effect without the `mmap` feature.
+    pub memory_map: bool,
+    /// Leave out faces with invalid vertex indices, with a warning, instead of failing the
+    /// import (default: false).
+    pub skip_invalid_faces: bool,
+}
+
+impl Default for ObjImportOptions {
+    fn default() -> Self {
+        Self::from(&ImportOptions::default())
+    }
+}
+
+impl From<&ImportOptions> for ObjImportOptions {
+    fn from(options: &ImportOptions) -> Self {
+        Self {
+            materials: true,
+            unit: options.unit,
+            scale: options.scale,
+            axes: options.axes,
+            weld_epsilon: options.weld_epsilon,
+            progress: options.progress.clone(),
+            memory_map: options.memory_map,
+            skip_invalid_faces: false,
+        }
+    }
+}
+
+impl From<&ObjImportOptions> for ImportOptions {
+    fn from(options: &ObjImportOptions) -> Self {
+        Self {
+            scale: options.scale,
+            weld_epsilon: options.weld_epsilon,
+            unit: options.unit,
+            axes: options.axes,
+            progress: options.progress.clone(),
+            memory_map: options.memory_map,
+        }
+    }
 }
 
 /// Imports Wavefront OBJ files into 3MF [`Model`] structures.
@@ -153,9 +245,18 @@ impl ObjImporter {
     ///
     /// [`Model`]: lib3mf_core::model::Model
     pub fn read_from_path(path: &Path) -> Result<Model> {
-        let dir = path.parent().unwrap_or(Path::new("."));
         let file = std::fs::File::open(path).map_err(Lib3mfError::Io)?;
-        let intermediate = Self::parse_obj(BufReader::new(file))?;
+        Self::read_with_materials(path, BufReader::new(file), false)
+    }
+
+    /// Parses OBJ text read from `path`, resolving its MTL files.
+    fn read_with_materials<R: BufRead>(
+        path: &Path,
+        reader: R,
+        skip_invalid_faces: bool,
+    ) -> Result<Model> {
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let intermediate = Self::parse_checked(reader, skip_invalid_faces)?;
 
         // Resolve MTL file
         let materials = if let Some(ref mtl_filename) = intermediate.mtllib {
@@ -168,6 +269,27 @@ impl ObjImporter {
         Self::build_model(intermediate, &materials)
     }
 
+    /// Reads an OBJ file from a path with unit, orientation, scale and welding adjustments.
+    ///
+    /// With [`ObjImportOptions::materials`] set this behaves like
+    /// [`read_from_path`](Self::read_from_path), otherwise like [`read`](Self::read).
+    ///
+    /// # Errors
+    ///
+    /// As for [`read_from_path`](Self::read_from_path), plus [`Lib3mfError::Validation`] for
+    /// an invalid scale. Invalid faces are not an error with
+    /// [`ObjImportOptions::skip_invalid_faces`] set.
+    pub fn read_from_path_with_options(path: &Path, options: &ObjImportOptions) -> Result<Model> {
+        let input = InputFile::open(path, &ImportOptions::from(options))?;
+        let mut model = if options.materials {
+            Self::read_with_materials(path, input, options.skip_invalid_faces)?
+        } else {
+            Self::build_model_compat(Self::parse_checked(input, options.skip_invalid_faces)?)?
+        };
+        ImportOptions::from(options).apply_unitless(&mut model)?;
+        Ok(model)
+    }
+
     /// Reads an OBJ file and converts it to a 3MF [`Model`].
     ///
     /// This is the backward-compatible entry point. No MTL file resolution is
@@ -184,18 +306,19 @@ impl ObjImporter {
     ///
     /// A [`Model`] containing:
     /// - Single mesh object with ResourceId(1) named "OBJ Import"
-    /// - All triangles from the OBJ file (polygons triangulated via fan method)
+    /// - All triangles from the OBJ file (polygons triangulated by ear clipping)
     /// - All vertices from the OBJ file
     /// - Single build item referencing the mesh object
     ///
+    /// Face indices may be negative, counting back from the last vertex defined.
+    ///
     /// # Errors
     ///
     /// Returns [`Lib3mfError::Validation`] if:
     /// - Vertex line has fewer than 4 fields (v x y z)
-    /// - Face line has fewer than 4 fields (f v1 v2 v3...)
     /// - Float parsing fails for vertex coordinates
-    /// - Integer parsing fails for face indices
-    /// - Relative indices (negative values) are used (not supported)
+    /// - Any face has an index that is not an integer, is 0, or refers to a vertex not yet
+    ///   defined; the error lists the offending lines (see [`face_errors`](Self::face_errors))
     ///
     /// Returns [`Lib3mfError::Io`] if reading from the input fails.
     ///
@@ -224,119 +347,209 @@ impl ObjImporter {
     /// [`Lib3mfError::Validation`]: lib3mf_core::error::Lib3mfError::Validation
     /// [`Lib3mfError::Io`]: lib3mf_core::error::Lib3mfError::Io
     pub fn read<R: Read>(reader: R) -> Result<Model> {
-        let intermediate = Self::parse_obj(BufReader::new(reader))?;
+        let intermediate = Self::parse_checked(BufReader::new(reader), false)?;
         // Backward-compatible: no materials, collapse all groups into one object
         Self::build_model_compat(intermediate)
     }
 
+    /// Lists the faces of an OBJ file that import would reject or, with
+    /// [`ObjImportOptions::skip_invalid_faces`], leave out.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`read`](Self::read) other than those for invalid faces.
+    pub fn face_errors<R: Read>(reader: R) -> Result<Vec<ObjFaceError>> {
+        Ok(Self::parse_obj(BufReader::new(reader))?.face_errors)
+    }
+
+    /// [`parse_obj`](Self::parse_obj), then reports the faces with invalid indices: as an
+    /// error listing them, or as a warning if they are skipped.
+    fn parse_checked<R: BufRead>(reader: R, skip_invalid_faces: bool) -> Result<ObjIntermediate> {
+        const LISTED: usize = 10;
+        let intermediate = Self::parse_obj(reader)?;
+        let errors = &intermediate.face_errors;
+        if errors.is_empty() {
+            return Ok(intermediate);
+        }
+        let mut list = errors
+            .iter()
+            .take(LISTED)
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ");
+        if errors.len() > LISTED {
+            list += &format!("; and {} more", errors.len() - LISTED);
+        }
+        if !skip_invalid_faces {
+            return Err(Lib3mfError::Validation(format!(
+                "{} invalid OBJ face(s): {list}",
+                errors.len()
+            )));
+        }
+        eprintln!(
+            "Warning: skipped {} invalid OBJ face(s): {list}",
+            errors.len()
+        );
+        Ok(intermediate)
+    }
+
     /// Parse OBJ text into the intermediate representation.
+    ///
+    /// Faces with invalid indices are left out and recorded in
+    /// [`ObjIntermediate::face_errors`].
+    ///
+    /// Lines are read into one reused buffer and faces are stored as fixed-size triangles,
+    /// so memory grows with the geometry only.
     fn parse_obj<R: BufRead>(mut reader: R) -> Result<ObjIntermediate> {
-        let mut global_vertices: Vec<(f32, f32, f32)> = Vec::new();
+        let mut global_vertices: Vec<Vertex> = Vec::new();
+        let mut global_normals: Vec<glam::Vec3> = Vec::new();
         let mut groups: Vec<ObjGroup> = Vec::new();
         let mut current_group = ObjGroup {
             name: None,
             faces: Vec::new(),
         };
-        let mut current_material: Option<String> = None;
+        let mut material_names: Vec<String> = Vec::new();
+        let mut material_lookup: HashMap<String, u32> = HashMap::new();
+        let mut current_material: Option<u32> = None;
         let mut mtllib: Option<String> = None;
         let mut had_explicit_group = false;
+        let mut in_object = false;
+
+        let mut face_errors: Vec<ObjFaceError> = Vec::new();
 
         let mut line = String::new();
+        let mut line_number = 0;
+        // Vertex and normal indices, and positions, of the current polygon
+        let mut corners: Vec<(u32, Option<u32>)> = Vec::new();
+        let mut polygon: Vec<glam::Vec3> = Vec::new();
 
-        while reader.read_line(&mut line).map_err(Lib3mfError::Io)? > 0 {
-            let trimmed = line.trim();
-            if trimmed.is_empty() || trimmed.starts_with('#') {
-                line.clear();
-                continue;
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).map_err(Lib3mfError::Io)? == 0 {
+                break;
             }
-
-            let parts: Vec<&str> = trimmed.split_whitespace().collect();
-            if parts.is_empty() {
-                line.clear();
+            line_number += 1;
+            let mut parts = line.split_whitespace();
+            let Some(keyword) = parts.next() else {
+                continue;
+            };
+            if keyword.starts_with('#') {
                 continue;
             }
 
-            match parts[0] {
+            match keyword {
                 "v" => {
-                    if parts.len() < 4 {
+                    if parts.clone().count() < 3 {
                         return Err(Lib3mfError::Validation("Invalid OBJ vertex".to_string()));
                     }
-                    let x = parts[1]
-                        .parse::<f32>()
-                        .map_err(|_| Lib3mfError::Validation("Invalid float".to_string()))?;
-                    let y = parts[2]
-                        .parse::<f32>()
-                        .map_err(|_| Lib3mfError::Validation("Invalid float".to_string()))?;
-                    let z = parts[3]
-                        .parse::<f32>()
-                        .map_err(|_| Lib3mfError::Validation("Invalid float".to_string()))?;
-                    global_vertices.push((x, y, z));
+                    let mut coord = || {
+                        parts
+                            .next()
+                            .unwrap_or_default()
+                            .parse::<f32>()
+                            .map_err(|_| Lib3mfError::Validation("Invalid float".to_string()))
+                    };
+                    global_vertices.push(Vertex {
+                        x: coord()?,
+                        y: coord()?,
+                        z: coord()?,
+                    });
+                }
+                "vn" => {
+                    let mut normal = [0f32; 3];
+                    let mut found = 0;
+                    for (c, part) in normal.iter_mut().zip(parts) {
+                        match part.parse::<f32>() {
+                            Ok(value) => *c = value,
+                            Err(_) => break,
+                        }
+                        found += 1;
+                    }
+                    if found < 3 {
+                        return Err(Lib3mfError::Validation("Invalid OBJ normal".to_string()));
+                    }
+                    // Referenced by position, so unusable normals are kept as zero
+                    global_normals.push(
+                        glam::Vec3::from_array(normal)
+                            .try_normalize()
+                            .unwrap_or_default(),
+                    );
                 }
                 "f" => {
-                    if parts.len() < 4 {
+                    if parts.clone().count() < 3 {
                         // Skip point/line elements
-                        line.clear();
                         continue;
                     }
 
-                    let mut indices = Vec::new();
-                    for part in &parts[1..] {
-                        // Format: v, v/vt, v/vt/vn, v//vn
-                        let subparts: Vec<&str> = part.split('/').collect();
-                        let v_idx = subparts[0]
-                            .parse::<i32>()
-                            .map_err(|_| Lib3mfError::Validation("Invalid index".to_string()))?;
-
-                        let idx = if v_idx > 0 {
-                            (v_idx - 1) as u32
-                        } else {
-                            return Err(Lib3mfError::Validation(
-                                "Relative OBJ indices not supported yet".to_string(),
-                            ));
-                        };
-                        indices.push(idx);
+                    corners.clear();
+                    let resolved = parts.try_for_each(|part| {
+                        corners.push(resolve_corner(
+                            part,
+                            global_vertices.len(),
+                            global_normals.len(),
+                        )?);
+                        Ok(())
+                    });
+                    if let Err(message) = resolved {
+                        face_errors.push(ObjFaceError {
+                            line: line_number,
+                            message,
+                        });
+                        continue;
                     }
 
-                    // Fan-triangulate
-                    if indices.len() >= 3 {
-                        for i in 1..indices.len() - 1 {
-                            current_group.faces.push(ObjFace {
-                                indices: vec![indices[0], indices[i], indices[i + 1]],
-                                material_name: current_material.clone(),
-                            });
-                        }
+                    // A face without a usable normal on every corner has none
+                    let has_normals = corners.iter().all(|(_, n)| n.is_some());
+                    polygon.clear();
+                    polygon.extend(corners.iter().map(|&(v, _)| {
+                        let v = global_vertices[v as usize];
+                        glam::Vec3::new(v.x, v.y, v.z)
+                    }));
+                    for [a, b, c] in triangulate(&polygon) {
+                        let [(va, na), (vb, nb), (vc, nc)] = [corners[a], corners[b], corners[c]];
+                        current_group.faces.push(ObjFace {
+                            indices: [va, vb, vc],
+                            normals: has_normals
+                                .then(|| [na, nb, nc].map(Option::unwrap_or_default)),
+                            material: current_material,
+                        });
                     }
                 }
+                // Groups within an object are parts of it, as written for build items with
+                // several meshes
+                "g" if in_object => {}
                 "g" | "o" => {
                     had_explicit_group = true;
+                    in_object |= keyword == "o";
                     // Flush current group if it has faces
                     if !current_group.faces.is_empty() {
                         groups.push(current_group);
                     }
-                    let name = if parts.len() >= 2 {
-                        Some(parts[1..].join(" "))
-                    } else {
-                        None
-                    };
+                    let name = parts.collect::<Vec<_>>().join(" ");
                     current_group = ObjGroup {
-                        name,
+                        name: (!name.is_empty()).then_some(name),
                         faces: Vec::new(),
                     };
                 }
                 "usemtl" => {
-                    if parts.len() >= 2 {
-                        current_material = Some(parts[1..].join(" "));
+                    let name = parts.collect::<Vec<_>>().join(" ");
+                    if !name.is_empty() {
+                        let next = material_names.len() as u32;
+                        let index = *material_lookup.entry(name).or_insert_with_key(|name| {
+                            material_names.push(name.clone());
+                            next
+                        });
+                        current_material = Some(index);
                     }
                 }
                 "mtllib" => {
-                    if parts.len() >= 2 {
-                        mtllib = Some(parts[1..].join(" "));
+                    let name = parts.collect::<Vec<_>>().join(" ");
+                    if !name.is_empty() {
+                        mtllib = Some(name);
                     }
                 }
-                _ => {} // Ignore vt, vn, comments, etc.
+                _ => {} // Ignore vt, etc.
             }
-
-            line.clear();
         }
 
         // Flush last group
@@ -346,9 +559,12 @@ impl ObjImporter {
 
         Ok(ObjIntermediate {
             global_vertices,
+            global_normals,
             groups,
+            material_names,
             mtllib,
             had_explicit_group,
+            face_errors,
         })
     }
 
@@ -363,17 +579,17 @@ impl ObjImporter {
             return Ok(model);
         }
 
-        // Collect all referenced material names across all groups
-        let mut referenced_materials: Vec<String> = Vec::new();
-        let mut material_seen: HashMap<String, u32> = HashMap::new();
+        // Collect all referenced materials across all groups, in order of first use
+        let mut referenced_materials: Vec<&str> = Vec::new();
+        let mut material_seen: HashMap<u32, u32> = HashMap::new();
         for group in &intermediate.groups {
             for face in &group.faces {
-                if let Some(ref mat_name) = face.material_name
-                    && !material_seen.contains_key(mat_name)
+                if let Some(material) = face.material
+                    && !material_seen.contains_key(&material)
                 {
                     let idx = referenced_materials.len() as u32;
-                    material_seen.insert(mat_name.clone(), idx);
-                    referenced_materials.push(mat_name.clone());
+                    material_seen.insert(material, idx);
+                    referenced_materials.push(&intermediate.material_names[material as usize]);
                 }
             }
         }
@@ -387,7 +603,7 @@ impl ObjImporter {
             next_id += 1;
 
             let mut base_materials = Vec::new();
-            for mat_name in &referenced_materials {
+            for &mat_name in &referenced_materials {
                 let base_mat = if let Some(mtl_mat) = materials_map.get(mat_name) {
                     BaseMaterial {
                         name: mtl_mat.name.clone(),
@@ -400,7 +616,7 @@ impl ObjImporter {
                         mat_name
                     );
                     BaseMaterial {
-                        name: mat_name.clone(),
+                        name: mat_name.to_string(),
                         display_color: DEFAULT_GRAY,
                     }
                 };
@@ -417,13 +633,23 @@ impl ObjImporter {
             None
         };
 
+        // Local index of each global vertex in the mesh being built, shared by all groups
+        let mut local_map = vec![UNMAPPED; intermediate.global_vertices.len()];
+
         // Determine if we're in single-object backward-compat mode
         let single_object_mode = !intermediate.had_explicit_group && intermediate.groups.len() == 1;
 
         if single_object_mode && !has_materials {
             // Full backward compatibility: single object with ResourceId(1), no materials
             let group = &intermediate.groups[0];
-            let mesh = Self::build_mesh_full(&intermediate.global_vertices, group, None, None);
+            let mesh = Self::build_mesh_full(
+                &intermediate.global_vertices,
+                &intermediate.global_normals,
+                group,
+                None,
+                None,
+                &mut local_map,
+            );
 
             let resource_id = ResourceId(next_id);
             let object = Object {
@@ -445,6 +671,7 @@ impl ObjImporter {
                 uuid: None,
                 path: None,
                 printable: None,
+                metadata: Default::default(),
             });
         } else {
             // Multi-object or single-object-with-materials path
@@ -454,9 +681,11 @@ impl ObjImporter {
 
                 let mesh = Self::build_mesh_full(
                     &intermediate.global_vertices,
+                    &intermediate.global_normals,
                     group,
                     materials_group_id,
                     Some(&material_seen),
+                    &mut local_map,
                 );
 
                 let name = group
@@ -483,6 +712,7 @@ impl ObjImporter {
                     uuid: None,
                     path: None,
                     printable: None,
+                    metadata: Default::default(),
                 });
             }
         }
@@ -496,19 +726,20 @@ impl ObjImporter {
 
         // Collapse all groups into a single mesh
         let mut mesh = Mesh::default();
-        for &(x, y, z) in &intermediate.global_vertices {
-            mesh.add_vertex(x, y, z);
-        }
-        for group in &intermediate.groups {
-            for face in &group.faces {
-                if face.indices.len() == 3 {
-                    mesh.triangles.push(Triangle {
-                        v1: face.indices[0],
-                        v2: face.indices[1],
-                        v3: face.indices[2],
-                        ..Default::default()
-                    });
-                }
+        let faces = intermediate.groups.iter().flat_map(|g| &g.faces);
+        mesh.normals = collect_normals(&intermediate.global_normals, faces);
+
+        mesh.vertices = intermediate.global_vertices.into();
+        mesh.triangles
+            .reserve_exact(intermediate.groups.iter().map(|g| g.faces.len()).sum());
+        for group in intermediate.groups {
+            for face in group.faces {
+                mesh.triangles.push(Triangle {
+                    v1: face.indices[0],
+                    v2: face.indices[1],
+                    v3: face.indices[2],
+                    ..Default::default()
+                });
             }
         }
 
@@ -536,72 +767,206 @@ impl ObjImporter {
             uuid: None,
             path: None,
             printable: None,
+            metadata: Default::default(),
         });
 
         Ok(model)
     }
 
     /// Build a Mesh for a single OBJ group, remapping vertices to local indices.
+    ///
+    /// `local_map` holds [`UNMAPPED`] for every global vertex and is left that way.
     fn build_mesh_full(
-        global_vertices: &[(f32, f32, f32)],
+        global_vertices: &[Vertex],
+        global_normals: &[glam::Vec3],
         group: &ObjGroup,
         materials_group_id: Option<ResourceId>,
-        material_index_map: Option<&HashMap<String, u32>>,
+        material_index_map: Option<&HashMap<u32, u32>>,
+        local_map: &mut [u32],
     ) -> Mesh {
         let mut mesh = Mesh::default();
-        let mut local_map: HashMap<u32, u32> = HashMap::new();
+        mesh.triangles.reserve_exact(group.faces.len());
 
         for face in &group.faces {
             // Remap vertex indices to local
-            let mut local_indices = Vec::with_capacity(face.indices.len());
-            for &global_idx in &face.indices {
-                let local_idx = if let Some(&li) = local_map.get(&global_idx) {
-                    li
-                } else {
-                    let li = mesh.vertices.len() as u32;
-                    local_map.insert(global_idx, li);
-                    let (x, y, z) = global_vertices[global_idx as usize];
-                    mesh.vertices.push(Vertex { x, y, z });
-                    li
-                };
-                local_indices.push(local_idx);
-            }
+            let local_indices = face.indices.map(|global_idx| {
+                let local_idx = &mut local_map[global_idx as usize];
+                if *local_idx == UNMAPPED {
+                    *local_idx = mesh.vertices.len() as u32;
+                    mesh.vertices.push(global_vertices[global_idx as usize]);
+                }
+                *local_idx
+            });
 
             // Build triangle with material assignment
-            if local_indices.len() == 3 {
-                let (pid, p1, p2, p3) = if let (Some(group_id), Some(index_map), Some(mat_name)) =
-                    (materials_group_id, material_index_map, &face.material_name)
-                {
-                    if let Some(&mat_idx) = index_map.get(mat_name.as_str()) {
-                        (
-                            Some(group_id.0),
-                            Some(mat_idx),
-                            Some(mat_idx),
-                            Some(mat_idx),
-                        )
-                    } else {
-                        (None, None, None, None)
-                    }
+            let (pid, p1, p2, p3) = if let (Some(group_id), Some(index_map), Some(material)) =
+                (materials_group_id, material_index_map, face.material)
+            {
+                if let Some(&mat_idx) = index_map.get(&material) {
+                    (
+                        Some(group_id.0),
+                        Some(mat_idx),
+                        Some(mat_idx),
+                        Some(mat_idx),
+                    )
                 } else {
                     (None, None, None, None)
-                };
+                }
+            } else {
+                (None, None, None, None)
+            };
 
-                mesh.triangles.push(Triangle {
-                    v1: local_indices[0],
-                    v2: local_indices[1],
-                    v3: local_indices[2],
-                    pid,
-                    p1,
-                    p2,
-                    p3,
-                });
+            mesh.triangles.push(Triangle {
+                v1: local_indices[0],
+                v2: local_indices[1],
+                v3: local_indices[2],
+                pid,
+                p1,
+                p2,
+                p3,
+            });
+        }
+
+        for face in &group.faces {
+            for global_idx in face.indices {
+                local_map[global_idx as usize] = UNMAPPED;
             }
         }
 
+        mesh.normals = collect_normals(global_normals, &group.faces);
         mesh
     }
 }
 
+/// Resolves one `v`, `v/vt`, `v/vt/vn` or `v//vn` face corner to 0-based vertex and normal
+/// indices, given the number of vertices and normals defined so far.
+///
+/// Negative indices count back from the last definition. An unusable normal index only
+/// drops the normal.
+fn resolve_corner(
+    corner: &str,
+    vertex_count: usize,
+    normal_count: usize,
+) -> std::result::Result<(u32, Option<u32>), String> {
+    let mut subparts = corner.split('/');
+    let vertex = subparts.next().unwrap_or_default();
+    let index = vertex
+        .parse::<i64>()
+        .map_err(|_| format!("invalid vertex index '{vertex}'"))?;
+    let Some(resolved) = resolve_index(index, vertex_count) else {
+        return Err(format!(
+            "vertex index {index} out of range ({vertex_count} vertices defined)"
+        ));
+    };
+    let normal = subparts
+        .nth(1)
+        .and_then(|n| n.parse::<i64>().ok())
+        .and_then(|n| resolve_index(n, normal_count));
+    Ok((resolved, normal))
+}
+
+/// Converts a 1-based, or negative relative, OBJ index to a 0-based one below `count`.
+fn resolve_index(index: i64, count: usize) -> Option<u32> {
+    let count = count as i64;
+    let resolved = if index < 0 { count + index } else { index - 1 };
+    if (0..count).contains(&resolved) {
+        u32::try_from(resolved).ok()
+    } else {
+        None
+    }
+}
+
+/// Splits a polygon into triangles of corner indices by ear clipping, so that concave
+/// polygons are covered correctly. Polygons that are degenerate or self-intersecting fall
+/// back to a fan from the first corner.
+fn triangulate(polygon: &[glam::Vec3]) -> Vec<[usize; 3]> {
+    let n = polygon.len();
+    let fan = || (1..n - 1).map(|i| [0, i, i + 1]).collect();
+    if n == 3 {
+        return fan();
+    }
+
+    // Newell's method gives the normal of a non-planar or concave polygon
+    let normal: glam::Vec3 = (0..n)
+        .map(|i| {
+            let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+            glam::Vec3::new(
+                (a.y - b.y) * (a.z + b.z),
+                (a.z - b.z) * (a.x + b.x),
+                (a.x - b.x) * (a.y + b.y),
+            )
+        })
+        .sum();
+    let Some(normal) = normal.try_normalize() else {
+        return fan();
+    };
+    // Project onto the polygon's plane, keeping its counterclockwise winding
+    let u = normal.any_orthonormal_vector();
+    let v = normal.cross(u);
+    let flat: Vec<glam::Vec2> = polygon
+        .iter()
+        .map(|p| glam::Vec2::new(p.dot(u), p.dot(v)))
+        .collect();
+
+    let mut remaining: Vec<usize> = (0..n).collect();
+    let mut triangles = Vec::with_capacity(n - 2);
+    while remaining.len() > 3 {
+        let m = remaining.len();
+        // Starting from the second corner gives a convex polygon the same triangles as a fan
+        let ear = (1..=m).map(|i| i % m).find(|&i| {
+            let [a, b, c] = [
+                remaining[(i + m - 1) % m],
+                remaining[i],
+                remaining[(i + 1) % m],
+            ];
+            let (pa, pb, pc) = (flat[a], flat[b], flat[c]);
+            (pb - pa).perp_dot(pc - pb) > 0.0
+                && !remaining.iter().any(|&k| {
+                    let p = flat[k];
+                    k != a
+                        && k != b
+                        && k != c
+                        && (pb - pa).perp_dot(p - pa) >= 0.0
+                        && (pc - pb).perp_dot(p - pb) >= 0.0
+                        && (pa - pc).perp_dot(p - pc) >= 0.0
+                })
+        });
+        let Some(i) = ear else {
+            return fan();
+        };
+        triangles.push([
+            remaining[(i + m - 1) % m],
+            remaining[i],
+            remaining[(i + 1) % m],
+        ]);
+        remaining.remove(i);
+    }
+    triangles.push([remaining[0], remaining[1], remaining[2]]);
+    triangles
+}
+
+/// Normals for the triangles made from `faces`, if every one of them has normals, keeping
+/// only the normals they use.
+fn collect_normals<'a>(
+    global_normals: &[glam::Vec3],
+    faces: impl IntoIterator<Item = &'a ObjFace>,
+) -> Option<MeshNormals> {
+    let mut out = MeshNormals::default();
+    let mut local_map: HashMap<u32, u32> = HashMap::new();
+    for face in faces {
+        let normals = face.normals.as_ref()?;
+        let mut corners = [0; 3];
+        for (corner, &global_idx) in corners.iter_mut().zip(normals) {
+            *corner = *local_map.entry(global_idx).or_insert_with(|| {
+                out.normals.push(global_normals[global_idx as usize]);
+                out.normals.len() as u32 - 1
+            });
+        }
+        out.indices.push(corners);
+    }
+    (!out.indices.is_empty()).then_some(out)
+}
+
 /// Exports 3MF [`Model`] structures to Wavefront OBJ files.
 ///
 /// The exporter writes all mesh objects from build items to OBJ format, creating
@@ -630,14 +995,19 @@ impl ObjExporter {
     ///
     /// - **Groups**: Each mesh object creates an OBJ group (`g`) with the object's name or "Object"
     /// - **Vertex indices**: Written as 1-based indices (OBJ convention)
-    /// - **Transformations**: Build item transforms are applied to vertex coordinates
+    /// - **Transformations**: Build item and component transforms are applied to vertex
+    ///   coordinates
     /// - **Materials**: Not exported (OBJ output is geometry-only)
-    /// - **Normals/UVs**: Not exported
+    /// - **Normals**: Stored [`Mesh::normals`] are written as `vn` lines, transformed with
+    ///   the vertices, and referenced from faces as `v//vn`
+    /// - **UVs**: Not exported
     ///
     /// # Behavior
     ///
-    /// - Only mesh objects from `model.build.items` are exported
-    /// - Non-mesh geometries (Components, BooleanShape, etc.) are skipped
+    /// - Only meshes placed by `model.build.items` are exported, through components of the
+    ///   same model part; use [`write_with_resolver`](Self::write_with_resolver) for
+    ///   components in other parts
+    /// - Other geometries (BooleanShape, etc.) are skipped
     /// - Vertex indices are offset correctly across multiple objects
     /// - Each object's vertices and faces are written in sequence
     ///
@@ -659,40 +1029,35 @@ impl ObjExporter {
     ///
     /// [`Model`]: lib3mf_core::model::Model
     /// [`Lib3mfError::Io`]: lib3mf_core::error::Lib3mfError::Io
-    pub fn write<W: Write>(model: &Model, mut writer: W) -> Result<()> {
-        let mut vertex_offset = 1;
-
-        for item in &model.build.items {
-            if let Some(object) = model.resources.get_object(item.object_id)
-                && let lib3mf_core::model::Geometry::Mesh(mesh) = &object.geometry
-            {
-                let transform = item.transform;
-
-                writeln!(writer, "g {}", object.name.as_deref().unwrap_or("Object"))
-                    .map_err(Lib3mfError::Io)?;
-
-                // Write vertices
-                for v in &mesh.vertices {
-                    let p = transform.transform_point3(glam::Vec3::new(v.x, v.y, v.z));
-                    writeln!(writer, "v {} {} {}", p.x, p.y, p.z).map_err(Lib3mfError::Io)?;
-                }
-
-                // Write faces
-                for tri in &mesh.triangles {
-                    writeln!(
-                        writer,
-                        "f {} {} {}",
-                        tri.v1 + vertex_offset,
-                        tri.v2 + vertex_offset,
-                        tri.v3 + vertex_offset
-                    )
-                    .map_err(Lib3mfError::Io)?;
-                }
+    /// [`Mesh::normals`]: lib3mf_core::model::Mesh::normals
+    pub fn write<W: Write>(model: &Model, writer: W) -> Result<()> {
+        write_items(writer, &local_items(model)?, false)
+    }
 
-                vertex_offset += mesh.vertices.len() as u32;
-            }
+    /// Writes a 3MF [`Model`] to OBJ text format after applying `options`.
+    ///
+    /// OBJ is conventionally Y-up; set [`ExportOptions::axes`] to
+    /// [`CoordinateSystem::Y_UP_RIGHT`] for viewers that expect it. A change of handedness
+    /// also reverses face winding, so implied normals still point outward. With
+    /// [`ExportOptions::normals`] set, meshes without stored normals get computed ones. With
+    /// [`ExportOptions::item_groups`] set, each build item is written as an object (`o`)
+    /// holding a group per mesh, so the parts of an assembly stay together and apart from
+    /// other items.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`ExportOptions::prepare`], [`Lib3mfError::InvalidStructure`]
+    /// for a component cycle, and [`Lib3mfError::Io`] if any write operation fails.
+    pub fn write_with_options<W: Write>(
+        model: &Model,
+        writer: W,
+        options: &ExportOptions,
+    ) -> Result<()> {
+        let mut model = options.prepare_unitless(model)?;
+        if let Some(crease_angle) = options.normals {
+            crate::transform::update_normals(model.to_mut(), crease_angle);
         }
-        Ok(())
+        write_items(writer, &local_items(&model)?, options.item_groups)
     }
 
     /// Writes a 3MF [`Model`] to OBJ text format, resolving Production Extension
@@ -706,49 +1071,173 @@ impl ObjExporter {
     pub fn write_with_resolver<W: Write, A: lib3mf_core::archive::ArchiveReader>(
         model: &Model,
         mut resolver: lib3mf_core::model::resolver::PartResolver<A>,
-        mut writer: W,
+        writer: W,
     ) -> Result<()> {
-        let mut objects: Vec<(String, glam::Mat4, Mesh)> = Vec::new();
-
+        let mut items = Vec::new();
         for item in &model.build.items {
             let name = model
                 .resources
                 .get_object(item.object_id)
                 .and_then(|o| o.name.clone());
+            let mut objects = Vec::new();
             collect_obj_objects(
                 &mut resolver,
                 item.object_id,
                 item.transform,
                 None,
-                name,
+                name.clone(),
                 &mut objects,
             )?;
+            items.push(PlacedItem {
+                name: name.unwrap_or_else(|| "Object".to_string()),
+                meshes: objects
+                    .into_iter()
+                    .map(|(name, transform, mesh)| PlacedMesh {
+                        name,
+                        transform,
+                        mesh: Cow::Owned(mesh),
+                    })
+                    .collect(),
+            });
         }
+        write_items(writer, &items, false)
+    }
+}
 
-        let mut vertex_offset: u32 = 1;
-        for (name, transform, mesh) in &objects {
-            writeln!(writer, "g {name}").map_err(Lib3mfError::Io)?;
+/// A mesh placed by the build, written as an OBJ group.
+struct PlacedMesh<'a> {
+    name: String,
+    transform: glam::Mat4,
+    mesh: Cow<'a, Mesh>,
+}
 
-            for v in &mesh.vertices {
-                let p = transform.transform_point3(glam::Vec3::new(v.x, v.y, v.z));
-                writeln!(writer, "v {} {} {}", p.x, p.y, p.z).map_err(Lib3mfError::Io)?;
+/// The meshes placed by one build item.
+struct PlacedItem<'a> {
+    name: String,
+    meshes: Vec<PlacedMesh<'a>>,
+}
+
+/// The meshes of each build item, found through components within `model`.
+///
+/// Items and components referring to other model parts (`path`) are skipped.
+fn local_items(model: &Model) -> Result<Vec<PlacedItem<'_>>> {
+    let mut items = Vec::new();
+    for item in model.build.items.iter().filter(|item| item.path.is_none()) {
+        let Some(object) = model.resources.get_object(item.object_id) else {
+            continue;
+        };
+        let mut meshes = Vec::new();
+        collect_local_meshes(model, object, item.transform, &mut Vec::new(), &mut meshes)?;
+        items.push(PlacedItem {
+            name: object.name.clone().unwrap_or_else(|| "Object".to_string()),
+            meshes,
+        });
+    }
+    Ok(items)
+}
+
+fn collect_local_meshes<'a>(
+    model: &'a Model,
+    object: &'a Object,
+    transform: glam::Mat4,
+    ancestry: &mut Vec<ResourceId>,
+    out: &mut Vec<PlacedMesh<'a>>,
+) -> Result<()> {
+    match &object.geometry {
+        lib3mf_core::model::Geometry::Mesh(mesh) => out.push(PlacedMesh {
+            name: object.name.clone().unwrap_or_else(|| "Object".to_string()),
+            transform,
+            mesh: Cow::Borrowed(mesh),
+        }),
+        lib3mf_core::model::Geometry::Components(comps) => {
+            if ancestry.contains(&object.id) {
+                return Err(Lib3mfError::InvalidStructure(format!(
+                    "Cycle detected: object {} appears in current ancestry",
+                    object.id.0
+                )));
+            }
+            ancestry.push(object.id);
+            for comp in comps.components.iter().filter(|c| c.path.is_none()) {
+                if let Some(child) = model.resources.get_object(comp.object_id) {
+                    collect_local_meshes(model, child, transform * comp.transform, ancestry, out)?;
+                }
             }
+            ancestry.pop();
+        }
+        _ => {}
+    }
+    Ok(())
+}
 
-            for tri in &mesh.triangles {
-                writeln!(
-                    writer,
-                    "f {} {} {}",
-                    tri.v1 + vertex_offset,
-                    tri.v2 + vertex_offset,
-                    tri.v3 + vertex_offset
-                )
-                .map_err(Lib3mfError::Io)?;
+/// Writes `items`, each mesh as a group (`g`), and with `item_groups` each item as an
+/// object (`o`) around its groups. Object names are made unique with a `_2`, `_3`... suffix.
+fn write_items<W: Write>(mut writer: W, items: &[PlacedItem], item_groups: bool) -> Result<()> {
+    let mut offsets = [1, 1];
+    let mut names: HashMap<&str, usize> = HashMap::new();
+    for item in items.iter().filter(|item| !item.meshes.is_empty()) {
+        if item_groups {
+            let count = names.entry(&item.name).or_default();
+            *count += 1;
+            if *count == 1 {
+                writeln!(writer, "o {}", item.name)
+            } else {
+                writeln!(writer, "o {}_{count}", item.name)
             }
+            .map_err(Lib3mfError::Io)?;
+        }
+        for placed in &item.meshes {
+            writeln!(writer, "g {}", placed.name).map_err(Lib3mfError::Io)?;
+            write_mesh(&mut writer, &placed.mesh, placed.transform, &mut offsets)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes a mesh's `v`, `vn` and `f` lines placed by `transform`, and advances the 1-based
+/// vertex and normal `offsets` past them.
+///
+/// The winding is reversed when `transform` mirrors the mesh so that faces keep pointing
+/// outward.
+fn write_mesh<W: Write>(
+    writer: &mut W,
+    mesh: &Mesh,
+    transform: glam::Mat4,
+    offsets: &mut [u32; 2],
+) -> Result<()> {
+    for v in &mesh.vertices {
+        let p = transform.transform_point3(glam::Vec3::new(v.x, v.y, v.z));
+        writeln!(writer, "v {} {} {}", p.x, p.y, p.z).map_err(Lib3mfError::Io)?;
+    }
+    let normals = mesh.stored_normals().map(|normals| {
+        let mut normals = normals.clone();
+        normals.transform(transform);
+        normals
+    });
+    if let Some(normals) = &normals {
+        for n in &normals.normals {
+            writeln!(writer, "vn {} {} {}", n.x, n.y, n.z).map_err(Lib3mfError::Io)?;
+        }
+    }
 
-            vertex_offset += mesh.vertices.len() as u32;
+    let [vertex_offset, normal_offset] = *offsets;
+    for (i, tri) in mesh.triangles.iter().enumerate() {
+        let mut corners = [tri.v1, tri.v2, tri.v3].map(|v| (v + vertex_offset).to_string());
+        // Mirroring already swapped the normals' last two corners
+        if transform.determinant() < 0.0 {
+            corners.swap(1, 2);
+        }
+        if let Some(normals) = &normals {
+            for (corner, n) in corners.iter_mut().zip(normals.indices[i]) {
+                corner.push_str(&format!("//{}", n + normal_offset));
+            }
         }
-        Ok(())
+        writeln!(writer, "f {} {} {}", corners[0], corners[1], corners[2])
+            .map_err(Lib3mfError::Io)?;
     }
+
+    offsets[0] += mesh.vertices.len() as u32;
+    offsets[1] += normals.map_or(0, |n| n.normals.len() as u32);
+    Ok(())
 }
 
 fn collect_obj_objects<A: lib3mf_core::archive::ArchiveReader>(
@@ -799,6 +1288,35 @@ fn collect_obj_objects<A: lib3mf_core::archive::ArchiveReader>(
     Ok(())
 }
 
+impl ModelImporter for ObjImporter {
+    fn name(&self) -> &str {
+        "Wavefront OBJ"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["obj"]
+    }
+
+    fn import(&self, path: &Path, options: &ImportOptions) -> Result<Model> {
+        Self::read_from_path_with_options(path, &options.into())
+    }
+}
+
+impl ModelExporter for ObjExporter {
+    fn name(&self) -> &str {
+        "Wavefront OBJ"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["obj"]
+    }
+
+    fn export(&self, model: &Model, path: &Path, options: &ExportOptions) -> Result<()> {
+        let file = std::fs::File::create(path).map_err(Lib3mfError::Io)?;
+        Self::write_with_options(model, std::io::BufWriter::new(file), options)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -809,12 +1327,54 @@ mod tests {
         b"v 0.0 0.0 0.0\nv 1.0 0.0 0.0\nv 0.0 1.0 0.0\nf 1 2 3\n"
     }
 
+    #[test]
+    fn test_axes_round_trip() {
+        let model = ObjImporter::read(bare_triangle_obj()).unwrap();
+        for axes in [CoordinateSystem::Y_UP_RIGHT, CoordinateSystem::Y_UP_LEFT] {
+            let options = ExportOptions {
+                axes,
+                ..Default::default()
+            };
+            let mut buf = Vec::new();
+            ObjExporter::write_with_options(&model, &mut buf, &options).unwrap();
+            let text = String::from_utf8(buf.clone()).unwrap();
+            // The Z-up triangle's +Y vertex lies along the file's depth axis.
+            assert!(
+                text.contains("v 0 0 -1") || text.contains("v 0 0 1"),
+                "{text}"
+            );
+
+            let path =
+                std::env::temp_dir().join(format!("lib3mf_obj_axes_{:?}.obj", axes.handedness));
+            std::fs::write(&path, &buf).unwrap();
+            let import = ObjImportOptions {
+                materials: false,
+                axes,
+                ..Default::default()
+            };
+            let back = ObjImporter::read_from_path_with_options(&path, &import).unwrap();
+            let _ = std::fs::remove_file(&path);
+
+            let Geometry::Mesh(original) =
+                &model.resources.get_object(ResourceId(1)).unwrap().geometry
+            else {
+                panic!("expected mesh");
+            };
+            let Geometry::Mesh(mesh) = &back.resources.get_object(ResourceId(1)).unwrap().geometry
+            else {
+                panic!("expected mesh");
+            };
+            assert_eq!(mesh.vertices, original.vertices);
+            assert_eq!(mesh.triangles, original.triangles);
+        }
+    }
+
     #[test]
     fn test_backward_compat_bare_obj() {
         // A bare OBJ (no groups, no materials) must produce identical output to the
         // original importer: single object with ResourceId(1), name "OBJ Import",
         // no BaseMaterialsGroup, no pid/p1/p2/p3.
-        let model = ObjImporter::read(&bare_triangle_obj()[..]).unwrap();
+        let model = ObjImporter::read(bare_triangle_obj()).unwrap();
 
         // Single object with ID 1
         assert_eq!(model.build.items.len(), 1);
@@ -1008,6 +1568,74 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_concave_polygon_ear_clipping() {
+        // A dart whose fan from the first corner would cover area outside it
+        let obj_data = b"v 0 2 0\nv 1 1 0\nv 0 0 0\nv 2 1 0\nf 1 2 3 4\n";
+        let model = ObjImporter::read(&obj_data[..]).unwrap();
+        let obj = model.resources.get_object(ResourceId(1)).unwrap();
+        let Geometry::Mesh(mesh) = &obj.geometry else {
+            panic!("Expected mesh");
+        };
+        assert_eq!(mesh.triangles.len(), 2);
+        let mut area = 0.0;
+        for t in &mesh.triangles {
+            let [a, b, c] = [t.v1, t.v2, t.v3].map(|v| {
+                let v = mesh.vertices[v as usize];
+                glam::Vec3::new(v.x, v.y, v.z)
+            });
+            let normal = (b - a).cross(c - a);
+            assert!(normal.z > 0.0, "triangle flipped: {t:?}");
+            area += normal.length() / 2.0;
+        }
+        assert!((area - 1.0).abs() < 1e-6, "area {area}");
+    }
+
+    #[test]
+    fn test_relative_indices() {
+        let obj_data = b"v 9 9 9\nv 0 0 0\nv 1 0 0\nv 0 1 0\nf -3 -2 -1\nv 1 1 0\nf 3/1/-1 -1 4\n";
+        let model = ObjImporter::read(&obj_data[..]).unwrap();
+        let obj = model.resources.get_object(ResourceId(1)).unwrap();
+        let Geometry::Mesh(mesh) = &obj.geometry else {
+            panic!("Expected mesh");
+        };
+        let triangles: Vec<_> = mesh.triangles.iter().map(|t| [t.v1, t.v2, t.v3]).collect();
+        assert_eq!(triangles, [[1, 2, 3], [2, 4, 3]]);
+    }
+
+    #[test]
+    fn test_invalid_faces_are_collected() {
+        let obj_data = b"v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 9\nf 1 2 3\nf 0 1 2\nf -4 1 2\nf 1 x 3\n";
+        let errors = ObjImporter::face_errors(&obj_data[..]).unwrap();
+        let lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, [4, 6, 7, 8]);
+        assert_eq!(
+            errors[0].to_string(),
+            "line 4: vertex index 9 out of range (3 vertices defined)"
+        );
+
+        // All are reported at once
+        let err = ObjImporter::read(&obj_data[..]).unwrap_err().to_string();
+        assert!(err.contains("4 invalid OBJ face(s)"), "{err}");
+        assert!(err.contains("line 8: invalid vertex index 'x'"), "{err}");
+
+        // Or left out, keeping the valid face
+        let path = std::env::temp_dir().join("lib3mf_obj_invalid_faces.obj");
+        std::fs::write(&path, obj_data).unwrap();
+        let options = ObjImportOptions {
+            skip_invalid_faces: true,
+            ..Default::default()
+        };
+        let model = ObjImporter::read_from_path_with_options(&path, &options);
+        let _ = std::fs::remove_file(&path);
+        let model = model.unwrap();
+        let obj = model.resources.get_object(ResourceId(1)).unwrap();
+        let Geometry::Mesh(mesh) = &obj.geometry else {
+            panic!("Expected mesh");
+        };
+        assert_eq!(mesh.triangles.len(), 1);
+    }
+
     #[test]
     fn test_face_with_vt_vn_format() {
         // v/vt/vn format -- should extract vertex index only
@@ -1072,6 +1700,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_reused_and_unused_materials() {
+        // Only materials with faces are kept, in order of first use
+        let obj_data = b"v 0 0 0\nv 1 0 0\nv 0 1 0\nusemtl Unused\nusemtl Red\nf 1 2 3\nusemtl Blue\nf 1 3 2\nusemtl Red\nf 2 3 1\n";
+        let intermediate = ObjImporter::parse_obj(BufReader::new(&obj_data[..])).unwrap();
+        let model = ObjImporter::build_model(intermediate, &HashMap::new()).unwrap();
+
+        let bmg = model.resources.get_base_materials(ResourceId(1)).unwrap();
+        let names: Vec<&str> = bmg.materials.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["Red", "Blue"]);
+        let obj = model
+            .resources
+            .get_object(model.build.items[0].object_id)
+            .unwrap();
+        let Geometry::Mesh(mesh) = &obj.geometry else {
+            panic!("Expected mesh");
+        };
+        let p1: Vec<_> = mesh.triangles.iter().map(|t| t.p1).collect();
+        assert_eq!(p1, [Some(0), Some(1), Some(0)]);
+    }
+
+    #[test]
+    fn test_read_from_path_reports_progress() {
+        use std::sync::{Arc, Mutex};
+
+        let path = std::env::temp_dir().join("lib3mf_obj_progress.obj");
+        let mut obj_data = String::new();
+        for i in 0..1000 {
+            obj_data += &format!("v {i} 0 0\nv {i} 1 0\nv {i} 0 1\n");
+            obj_data += &format!("f {} {} {}\n", 3 * i + 1, 3 * i + 2, 3 * i + 3);
+        }
+        std::fs::write(&path, &obj_data).unwrap();
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let sink = reports.clone();
+        let options = ObjImportOptions {
+            progress: Some(Progress::new(move |done, total| {
+                sink.lock().unwrap().push((done, total))
+            })),
+            ..Default::default()
+        };
+        let model = ObjImporter::read_from_path_with_options(&path, &options);
+        let _ = std::fs::remove_file(&path);
+
+        let model = model.unwrap();
+        let Geometry::Mesh(mesh) = &model.resources.get_object(ResourceId(1)).unwrap().geometry
+        else {
+            panic!("Expected mesh");
+        };
+        assert_eq!(mesh.triangles.len(), 1000);
+        assert_eq!(mesh.vertices.len(), 3000);
+        let len = obj_data.len() as u64;
+        assert_eq!(reports.lock().unwrap().as_slice(), [(len, len)]);
+    }
+
     #[test]
     fn test_faces_before_first_group() {
         // Faces before any g/o directive go into default group
@@ -1109,4 +1792,165 @@ mod tests {
             .unwrap();
         assert_eq!(obj.name.as_deref(), Some("MyObject"));
     }
+
+    #[test]
+    fn test_write_mirrored_item_reverses_faces() {
+        let obj_data = b"v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n";
+        let intermediate = ObjImporter::parse_obj(BufReader::new(&obj_data[..])).unwrap();
+        let mut model = ObjImporter::build_model(intermediate, &HashMap::new()).unwrap();
+        model.build.items[0].transform = glam::Mat4::from_scale(glam::Vec3::new(1.0, 1.0, -1.0));
+
+        let mut output = Vec::new();
+        ObjExporter::write(&model, &mut output).unwrap();
+        let text = String::from_utf8(output).unwrap();
+        assert!(
+            text.contains("f 1 3 2"),
+            "winding should be reversed:\n{text}"
+        );
+    }
+
+    /// A triangle "Part", placed directly and twice by the components of "Assembly".
+    fn assembly_model() -> Model {
+        use lib3mf_core::model::{Component, Components};
+
+        let mut model = ObjImporter::read(bare_triangle_obj()).unwrap();
+        let mut part = model.resources.remove_object(ResourceId(1)).unwrap();
+        part.name = Some("Part".to_string());
+        model.resources.add_object(part).unwrap();
+        let components = [10.0, 20.0]
+            .map(|x| Component {
+                object_id: ResourceId(1),
+                path: None,
+                uuid: None,
+                transform: glam::Mat4::from_translation(glam::Vec3::new(x, 0.0, 0.0)),
+            })
+            .to_vec();
+        model
+            .resources
+            .add_object(Object {
+                id: ResourceId(2),
+                object_type: ObjectType::Model,
+                name: Some("Assembly".to_string()),
+                part_number: None,
+                uuid: None,
+                pid: None,
+                pindex: None,
+                thumbnail: None,
+                geometry: Geometry::Components(Components { components }),
+            })
+            .unwrap();
+        let mut item = model.build.items[0].clone();
+        item.object_id = ResourceId(2);
+        item.transform = glam::Mat4::from_translation(glam::Vec3::new(0.0, 5.0, 0.0));
+        model.build.items.push(item);
+        model
+    }
+
+    #[test]
+    fn test_write_follows_components() {
+        let model = assembly_model();
+        let mut output = Vec::new();
+        ObjExporter::write(&model, &mut output).unwrap();
+        let text = String::from_utf8(output).unwrap();
+        assert_eq!(text.matches("g Part").count(), 3, "{text}");
+        assert!(!text.contains("o "), "{text}");
+        // Item and component transforms are both applied
+        assert!(
+            text.contains("v 20 5 0") && text.contains("v 21 5 0"),
+            "{text}"
+        );
+
+        let mut cyclic = model.clone();
+        let mut assembly = cyclic.resources.remove_object(ResourceId(2)).unwrap();
+        let Geometry::Components(components) = &mut assembly.geometry else {
+            unreachable!();
+        };
+        components.components[1].object_id = ResourceId(2);
+        cyclic.resources.add_object(assembly).unwrap();
+        assert!(ObjExporter::write(&cyclic, &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_item_groups_round_trip() {
+        let options = ExportOptions {
+            item_groups: true,
+            ..Default::default()
+        };
+        let mut output = Vec::new();
+        ObjExporter::write_with_options(&assembly_model(), &mut output, &options).unwrap();
+        let text = String::from_utf8(output).unwrap();
+        let objects: Vec<&str> = text.lines().filter(|l| l.starts_with("o ")).collect();
+        assert_eq!(objects, ["o Part", "o Assembly"], "{text}");
+
+        // Each item comes back as one object, its parts in place
+        let intermediate = ObjImporter::parse_obj(BufReader::new(text.as_bytes())).unwrap();
+        let model = ObjImporter::build_model(intermediate, &HashMap::new()).unwrap();
+        assert_eq!(model.build.items.len(), 2);
+        let assembly = model
+            .resources
+            .get_object(model.build.items[1].object_id)
+            .unwrap();
+        assert_eq!(assembly.name.as_deref(), Some("Assembly"));
+        let Geometry::Mesh(mesh) = &assembly.geometry else {
+            panic!("Expected mesh");
+        };
+        assert_eq!(mesh.triangles.len(), 2);
+        assert!(mesh.vertices.iter().any(|v| (v.x, v.y) == (21.0, 5.0)));
+    }
+
+    #[test]
+    fn test_vn_round_trip() {
+        // A quad with tilted normals, plus a face without normals in another group
+        let obj_data = b"v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nvn 0 0 2\nvn 0 1 1\n\
+            g Smooth\nf 1//1 2//2 3//2 4//1\ng Plain\nf 1 2 3\n";
+        let intermediate = ObjImporter::parse_obj(BufReader::new(&obj_data[..])).unwrap();
+        let model = ObjImporter::build_model(intermediate, &HashMap::new()).unwrap();
+        let mesh = |id| match &model.resources.get_object(ResourceId(id)).unwrap().geometry {
+            Geometry::Mesh(mesh) => mesh.clone(),
+            _ => panic!("Expected mesh"),
+        };
+        let smooth = mesh(1);
+        let normals = smooth.stored_normals().unwrap();
+        assert_eq!(normals.normals[0], glam::Vec3::Z);
+        assert_eq!(normals.indices, vec![[0, 1, 1], [0, 1, 0]]);
+        assert!(mesh(2).normals.is_none());
+
+        let mut model = model;
+        model.build.items[0].transform = glam::Mat4::from_scale(glam::Vec3::new(1.0, 1.0, -1.0));
+        let mut output = Vec::new();
+        ObjExporter::write(&model, &mut output).unwrap();
+        let text = String::from_utf8(output).unwrap();
+        assert!(text.contains("vn 0 0 -1\n"), "{text}");
+        // Mirroring reverses the winding of vertices and normals alike
+        assert!(text.contains("f 1//1 3//2 2//2\n"), "{text}");
+        // The second group continues the vertex numbering but has no normals
+        assert!(text.contains("f 5 6 7\n"), "{text}");
+
+        // Bad references drop a face's normals rather than failing
+        let bad = b"v 0 0 0\nv 1 0 0\nv 0 1 0\nvn 0 0 1\nf 1//1 2//1 3//4\n";
+        let model = ObjImporter::read(&bad[..]).unwrap();
+        let Geometry::Mesh(mesh) = &model.resources.get_object(ResourceId(1)).unwrap().geometry
+        else {
+            panic!("Expected mesh");
+        };
+        assert!(mesh.normals.is_none());
+        assert!(ObjImporter::read(&b"vn 0 x 1\n"[..]).is_err());
+    }
+
+    #[test]
+    fn test_export_computed_normals() {
+        let model = ObjImporter::read(bare_triangle_obj()).unwrap();
+        let mut plain = Vec::new();
+        ObjExporter::write_with_options(&model, &mut plain, &ExportOptions::default()).unwrap();
+        assert!(!String::from_utf8(plain).unwrap().contains("vn"));
+
+        let options = ExportOptions {
+            normals: Some(30.0),
+            ..Default::default()
+        };
+        let mut output = Vec::new();
+        ObjExporter::write_with_options(&model, &mut output, &options).unwrap();
+        let text = String::from_utf8(output).unwrap();
+        assert!(text.contains("vn 0 0 1\nf 1//1 2//1 3//1\n"), "{text}");
+    }
 }
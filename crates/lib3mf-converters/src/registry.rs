@@ -0,0 +1,567 @@
+//! Pluggable import/export formats.
+//!
+//! A [`FormatRegistry`] maps file formats to [`ModelImporter`] and [`ModelExporter`]
+//! implementations. Importers are found by sniffing the first bytes of a file, falling back
+//! to its extension; exporters are found by extension. The CLI `convert` command resolves
+//! formats through a registry, and applications can register their own formats at runtime:
+//!
+//! ```
+//! use lib3mf_converters::registry::{ExportOptions, FormatRegistry, ModelExporter};
+//! use lib3mf_core::error::Result;
+//! use lib3mf_core::model::Model;
+//! use std::io::Write;
+//! use std::sync::Arc;
+//!
+//! /// Writes one line per object: `<id> <name>`.
+//! struct ObjectList;
+//!
+//! impl ModelExporter for ObjectList {
+//!     fn name(&self) -> &str {
+//!         "Object list"
+//!     }
+//!     fn extensions(&self) -> &[&str] {
+//!         &["objects"]
+//!     }
+//!     fn export(&self, model: &Model, path: &std::path::Path, _: &ExportOptions) -> Result<()> {
+//!         let mut out = std::fs::File::create(path)?;
+//!         for object in model.resources.iter_objects() {
+//!             writeln!(out, "{} {}", object.id.0, object.name.as_deref().unwrap_or(""))?;
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let mut registry = FormatRegistry::with_builtins();
+//! registry.register_exporter(Arc::new(ObjectList));
+//! assert!(registry.exporter_for_extension("objects").is_some());
+//! ```
+//!
+//! Importers and exporters work on paths rather than streams because several formats need
+//! more than one file (OBJ reads its `.mtl` libraries) or a seekable output (3MF).
+//! Formats registered later take precedence over earlier ones, so a custom implementation
+//! can replace a built-in one.
+//!
+//! The STL and OBJ converters ([`StlImporter`], [`StlExporter`], [`ObjImporter`],
+//! [`ObjExporter`]) implement these traits themselves, so they can be used directly or
+//! registered again with different options.
+
+use crate::input::Progress;
+use crate::obj::{ObjExporter, ObjImporter};
+use crate::stl::{StlExporter, StlImporter};
+use crate::transform::{self, CoordinateSystem, ObjectMode};
+use lib3mf_core::archive::{
+    ArchiveReader, ZipArchiver, check_model_not_encrypted, find_model_path,
+};
+use lib3mf_core::error::Result;
+use lib3mf_core::model::{Model, Unit};
+use lib3mf_core::parser::{encoding, gzip, parse_model};
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Cursor, Read, Seek};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Number of leading bytes passed to [`ModelImporter::sniff`].
+pub const SNIFF_LEN: usize = 512;
+
+/// Reads a file format into a [`Model`].
+pub trait ModelImporter: Send + Sync {
+    /// Human-readable format name, e.g. `"STL"`.
+    fn name(&self) -> &str;
+
+    /// Lowercase file extensions without the dot, e.g. `["stl"]`.
+    fn extensions(&self) -> &[&str];
+
+    /// Returns true if `head` (up to [`SNIFF_LEN`] leading bytes) identifies this format.
+    ///
+    /// Only formats with a reliable signature should claim a file here; the default
+    /// implementation leaves detection to the extension.
+    fn sniff(&self, head: &[u8]) -> bool {
+        let _ = head;
+        false
+    }
+
+    /// Reads the file at `path`, applying `options`.
+    fn import(&self, path: &Path, options: &ImportOptions) -> Result<Model>;
+}
+
+/// Adjustments applied to a model as it is imported.
+///
+/// Formats that record their unit and orientation (3MF, JSON) only use `scale` and
+/// `weld_epsilon`; [`ImportOptions::apply_unitless`] covers formats that do not.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ImportOptions {
+    /// Factor applied to all coordinates (default: 1.0).
+    pub scale: f32,
+    /// Distance below which vertices are merged, in file coordinates (default: 0.0, off).
+    pub weld_epsilon: f32,
+    /// Unit assumed for formats without one (default: millimeter).
+    pub unit: Unit,
+    /// Axis convention assumed for formats without one (default: Z-up, right-handed).
+    pub axes: CoordinateSystem,
+    /// Called with the bytes read so far and the file size while STL and OBJ files are
+    /// parsed (default: none).
+    pub progress: Option<Progress>,
+    /// Memory-map STL and OBJ files instead of reading them through a buffer (default:
+    /// false). Has no effect without the `mmap` feature.
+    pub memory_map: bool,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            weld_epsilon: 0.0,
+            unit: Unit::Millimeter,
+            axes: CoordinateSystem::Z_UP_RIGHT,
+            progress: None,
+            memory_map: false,
+        }
+    }
+}
+
+impl ImportOptions {
+    /// Welds vertices, then scales the model.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Lib3mfError::Validation` if `scale` is not a positive, finite number.
+    pub fn apply(&self, model: &mut Model) -> Result<()> {
+        transform::weld_vertices(model, self.weld_epsilon);
+        transform::scale_model(model, self.scale)
+    }
+
+    /// Like [`apply`](Self::apply), but first sets `model.unit` and converts the model from
+    /// [`axes`](Self::axes) to the Z-up, right-handed system of 3MF. For formats without unit
+    /// or orientation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Lib3mfError::Validation` if `scale` is not a positive, finite number.
+    pub fn apply_unitless(&self, model: &mut Model) -> Result<()> {
+        model.unit = self.unit;
+        transform::weld_vertices(model, self.weld_epsilon);
+        transform::convert_coordinate_system(model, self.axes, CoordinateSystem::Z_UP_RIGHT);
+        transform::scale_model(model, self.scale)
+    }
+}
+
+/// Options understood by the built-in exporters; custom exporters may ignore them.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct ExportOptions {
+    /// Prefer a text variant of the format where one exists (ASCII STL).
+    pub ascii: bool,
+    /// Convert coordinates to this unit before writing.
+    pub unit: Option<Unit>,
+    /// Axis convention to write for formats without one (default: Z-up, right-handed).
+    pub axes: CoordinateSystem,
+    /// Merge or split objects before writing.
+    pub objects: ObjectMode,
+    /// Bake build item transforms into vertex positions.
+    pub apply_transforms: bool,
+    /// Compute vertex normals, smooth up to this crease angle in degrees, for meshes without
+    /// stored ones in formats that carry normals (OBJ `vn`). Stored normals are always
+    /// written.
+    pub normals: Option<f32>,
+    /// Write each build item as a named object holding its meshes, in formats with object
+    /// grouping (OBJ `o`), so assemblies stay apart.
+    pub item_groups: bool,
+}
+
+impl ExportOptions {
+    /// Options requesting the text variant of the format.
+    pub fn ascii() -> Self {
+        Self {
+            ascii: true,
+            ..Default::default()
+        }
+    }
+
+    /// Returns `model` with unit conversion, object grouping and transform baking applied,
+    /// borrowing it unchanged when there is nothing to do.
+    ///
+    /// Exporters for formats without an axis convention also call
+    /// [`transform::convert_coordinate_system`] with [`axes`](Self::axes).
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`transform::merge_objects`] and [`transform::split_objects`].
+    pub fn prepare<'a>(&self, model: &'a Model) -> Result<Cow<'a, Model>> {
+        let mut model = Cow::Borrowed(model);
+        if let Some(unit) = self.unit
+            && unit != model.unit
+        {
+            transform::convert_unit(model.to_mut(), unit);
+        }
+        match self.objects {
+            ObjectMode::Keep => {}
+            ObjectMode::Merge => transform::merge_objects(model.to_mut())?,
+            ObjectMode::Split => {
+                transform::split_objects(model.to_mut())?;
+            }
+        }
+        if self.apply_transforms {
+            transform::apply_build_transforms(model.to_mut())?;
+        }
+        Ok(model)
+    }
+
+    /// [`prepare`](Self::prepare), then conversion from the Z-up, right-handed system of 3MF
+    /// to [`axes`](Self::axes). For formats without a unit or orientation.
+    ///
+    /// # Errors
+    ///
+    /// As for [`prepare`](Self::prepare).
+    pub fn prepare_unitless<'a>(&self, model: &'a Model) -> Result<Cow<'a, Model>> {
+        let mut model = self.prepare(model)?;
+        if self.axes != CoordinateSystem::Z_UP_RIGHT {
+            transform::convert_coordinate_system(
+                model.to_mut(),
+                CoordinateSystem::Z_UP_RIGHT,
+                self.axes,
+            );
+        }
+        Ok(model)
+    }
+}
+
+/// Writes a [`Model`] in some file format.
+pub trait ModelExporter: Send + Sync {
+    /// Human-readable format name, e.g. `"Wavefront OBJ"`.
+    fn name(&self) -> &str;
+
+    /// Lowercase file extensions without the dot, e.g. `["obj"]`.
+    fn extensions(&self) -> &[&str];
+
+    /// Writes `model` to a new file at `path`.
+    fn export(&self, model: &Model, path: &Path, options: &ExportOptions) -> Result<()>;
+}
+
+/// The set of known import and export formats.
+#[derive(Clone, Default)]
+pub struct FormatRegistry {
+    importers: Vec<Arc<dyn ModelImporter>>,
+    exporters: Vec<Arc<dyn ModelExporter>>,
+}
+
+impl std::fmt::Debug for FormatRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FormatRegistry")
+            .field(
+                "importers",
+                &self.importers.iter().map(|i| i.name()).collect::<Vec<_>>(),
+            )
+            .field(
+                "exporters",
+                &self.exporters.iter().map(|e| e.name()).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl FormatRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry with the built-in formats: 3MF, STL, OBJ and lib3mf JSON.
+    ///
+    /// 3MF packages may be gzip-compressed (`.3mf.gz`), and bare model XML parts such as
+    /// `3dmodel.model`, plain or gzip-compressed, import as well.
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry.register_importer(Arc::new(ModelXmlFormat));
+        registry.register_importer(Arc::new(ThreeMfFormat));
+        registry.register_importer(Arc::new(StlImporter));
+        registry.register_importer(Arc::new(ObjImporter));
+        registry.register_importer(Arc::new(JsonFormat));
+        registry.register_exporter(Arc::new(ThreeMfFormat));
+        registry.register_exporter(Arc::new(StlExporter::default()));
+        registry.register_exporter(Arc::new(ObjExporter));
+        registry.register_exporter(Arc::new(JsonFormat));
+        registry
+    }
+
+    /// Adds an importer. It takes precedence over importers registered before it.
+    pub fn register_importer(&mut self, importer: Arc<dyn ModelImporter>) {
+        self.importers.push(importer);
+    }
+
+    /// Adds an exporter. It takes precedence over exporters registered before it.
+    pub fn register_exporter(&mut self, exporter: Arc<dyn ModelExporter>) {
+        self.exporters.push(exporter);
+    }
+
+    /// Registered importers, most recently registered first.
+    pub fn importers(&self) -> impl Iterator<Item = &Arc<dyn ModelImporter>> {
+        self.importers.iter().rev()
+    }
+
+    /// Registered exporters, most recently registered first.
+    pub fn exporters(&self) -> impl Iterator<Item = &Arc<dyn ModelExporter>> {
+        self.exporters.iter().rev()
+    }
+
+    /// Finds the importer whose signature matches `head`, if any.
+    pub fn importer_for_bytes(&self, head: &[u8]) -> Option<&Arc<dyn ModelImporter>> {
+        self.importers().find(|i| i.sniff(head))
+    }
+
+    /// Finds the importer registered for `extension` (case-insensitive, without the dot).
+    pub fn importer_for_extension(&self, extension: &str) -> Option<&Arc<dyn ModelImporter>> {
+        let extension = extension.to_lowercase();
+        self.importers()
+            .find(|i| i.extensions().contains(&extension.as_str()))
+    }
+
+    /// Finds the exporter registered for `extension` (case-insensitive, without the dot).
+    pub fn exporter_for_extension(&self, extension: &str) -> Option<&Arc<dyn ModelExporter>> {
+        let extension = extension.to_lowercase();
+        self.exporters()
+            .find(|e| e.extensions().contains(&extension.as_str()))
+    }
+
+    /// Finds the importer for the file at `path`: by its leading bytes first, then by its
+    /// extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Lib3mfError::Io` if the file cannot be read.
+    pub fn importer_for_path(&self, path: &Path) -> Result<Option<&Arc<dyn ModelImporter>>> {
+        let mut head = Vec::with_capacity(SNIFF_LEN);
+        File::open(path)?
+            .take(SNIFF_LEN as u64)
+            .read_to_end(&mut head)?;
+        Ok(self
+            .importer_for_bytes(&head)
+            .or_else(|| self.importer_for_extension(extension(path)?)))
+    }
+
+    /// Finds the exporter for `path` by its extension.
+    pub fn exporter_for_path(&self, path: &Path) -> Option<&Arc<dyn ModelExporter>> {
+        self.exporter_for_extension(extension(path)?)
+    }
+}
+
+fn extension(path: &Path) -> Option<&str> {
+    path.extension().and_then(|e| e.to_str())
+}
+
+/// 3MF packages, plain or gzip-compressed. Imports the root model part only.
+struct ThreeMfFormat;
+
+impl ThreeMfFormat {
+    fn import_package<R: Read + Seek>(archive: R, options: &ImportOptions) -> Result<Model> {
+        let mut archiver = ZipArchiver::new(archive)?;
+        let model_path = find_model_path(&mut archiver)?;
+        check_model_not_encrypted(&mut archiver, &model_path)?;
+        let data = archiver.read_entry(&model_path)?;
+        let mut model = parse_model(Cursor::new(data))?;
+        options.apply(&mut model)?;
+        Ok(model)
+    }
+}
+
+impl ModelImporter for ThreeMfFormat {
+    fn name(&self) -> &str {
+        "3MF"
+    }
+    fn extensions(&self) -> &[&str] {
+        &["3mf"]
+    }
+    fn sniff(&self, head: &[u8]) -> bool {
+        gzip::peek(head, 4).starts_with(b"PK\x03\x04")
+    }
+    fn import(&self, path: &Path, options: &ImportOptions) -> Result<Model> {
+        let mut file = File::open(path)?;
+        let mut magic = Vec::new();
+        (&mut file).take(2).read_to_end(&mut magic)?;
+        if gzip::is_gzip(&magic) {
+            let data = gzip::decompress(&std::fs::read(path)?)?.into_owned();
+            return Self::import_package(Cursor::new(data), options);
+        }
+        file.rewind()?;
+        Self::import_package(file, options)
+    }
+}
+
+impl ModelExporter for ThreeMfFormat {
+    fn name(&self) -> &str {
+        "3MF"
+    }
+    fn extensions(&self) -> &[&str] {
+        &["3mf"]
+    }
+    fn export(&self, model: &Model, path: &Path, options: &ExportOptions) -> Result<()> {
+        options.prepare(model)?.write(File::create(path)?)
+    }
+}
+
+/// A bare model XML part such as `3dmodel.model`, plain or gzip-compressed.
+///
+/// Components that reference other parts of a package cannot be resolved.
+struct ModelXmlFormat;
+
+impl ModelImporter for ModelXmlFormat {
+    fn name(&self) -> &str {
+        "3MF model XML"
+    }
+    fn extensions(&self) -> &[&str] {
+        &["model"]
+    }
+    fn sniff(&self, head: &[u8]) -> bool {
+        encoding::is_xml(&gzip::peek(head, 64))
+    }
+    fn import(&self, path: &Path, options: &ImportOptions) -> Result<Model> {
+        // parse_model decompresses gzip input itself
+        let mut model = parse_model(BufReader::new(File::open(path)?))?;
+        options.apply(&mut model)?;
+        Ok(model)
+    }
+}
+
+/// The versioned document from [`Model::to_json`].
+struct JsonFormat;
+
+impl ModelImporter for JsonFormat {
+    fn name(&self) -> &str {
+        "lib3mf JSON"
+    }
+    fn extensions(&self) -> &[&str] {
+        &["json"]
+    }
+    fn import(&self, path: &Path, options: &ImportOptions) -> Result<Model> {
+        let mut model = Model::read_json(BufReader::new(File::open(path)?))?;
+        options.apply(&mut model)?;
+        Ok(model)
+    }
+}
+
+impl ModelExporter for JsonFormat {
+    fn name(&self) -> &str {
+        "lib3mf JSON"
+    }
+    fn extensions(&self) -> &[&str] {
+        &["json"]
+    }
+    fn export(&self, model: &Model, path: &Path, options: &ExportOptions) -> Result<()> {
+        options
+            .prepare(model)?
+            .write_json(BufWriter::new(File::create(path)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fake(&'static str);
+
+    impl ModelImporter for Fake {
+        fn name(&self) -> &str {
+            self.0
+        }
+        fn extensions(&self) -> &[&str] {
+            &["stl", "fake"]
+        }
+        fn sniff(&self, head: &[u8]) -> bool {
+            head.starts_with(b"FAKE")
+        }
+        fn import(&self, _path: &Path, _options: &ImportOptions) -> Result<Model> {
+            Ok(Model::default())
+        }
+    }
+
+    #[test]
+    fn test_builtin_lookup() {
+        let registry = FormatRegistry::with_builtins();
+        assert_eq!(
+            registry
+                .importer_for_bytes(b"PK\x03\x04rest")
+                .unwrap()
+                .name(),
+            "3MF"
+        );
+        assert!(registry.importer_for_bytes(b"solid cube").is_none());
+        assert_eq!(
+            registry.importer_for_extension("OBJ").unwrap().name(),
+            "Wavefront OBJ"
+        );
+        assert_eq!(
+            registry.exporter_for_extension("json").unwrap().name(),
+            "lib3mf JSON"
+        );
+        assert!(
+            registry
+                .exporter_for_path(Path::new("model.step"))
+                .is_none()
+        );
+        assert!(
+            FormatRegistry::new()
+                .importer_for_extension("stl")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_later_registrations_take_precedence() {
+        let mut registry = FormatRegistry::with_builtins();
+        registry.register_importer(Arc::new(Fake("Custom")));
+
+        assert_eq!(
+            registry.importer_for_extension("stl").unwrap().name(),
+            "Custom"
+        );
+        assert_eq!(
+            registry.importer_for_extension("fake").unwrap().name(),
+            "Custom"
+        );
+        assert_eq!(
+            registry.importer_for_bytes(b"FAKE data").unwrap().name(),
+            "Custom"
+        );
+        assert_eq!(registry.importers().next().unwrap().name(), "Custom");
+    }
+
+    #[test]
+    fn test_importer_for_path_prefers_signature() {
+        let path = std::env::temp_dir().join(format!("lib3mf_registry_{}.stl", std::process::id()));
+        std::fs::write(&path, b"FAKE contents").unwrap();
+
+        let mut registry = FormatRegistry::with_builtins();
+        assert_eq!(
+            registry.importer_for_path(&path).unwrap().unwrap().name(),
+            "STL"
+        );
+        registry.register_importer(Arc::new(Fake("Custom")));
+        assert_eq!(
+            registry.importer_for_path(&path).unwrap().unwrap().name(),
+            "Custom"
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_model_xml_is_sniffed_before_extension() {
+        let registry = FormatRegistry::with_builtins();
+        assert_eq!(
+            registry
+                .importer_for_bytes(b"\xEF\xBB\xBF<?xml version=\"1.0\"?><model>")
+                .unwrap()
+                .name(),
+            "3MF model XML"
+        );
+        assert_eq!(
+            registry.importer_for_extension("model").unwrap().name(),
+            "3MF model XML"
+        );
+        assert_eq!(
+            registry.importer_for_bytes(b"PK\x03\x04").unwrap().name(),
+            "3MF"
+        );
+    }
+}
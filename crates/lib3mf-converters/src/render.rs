@@ -0,0 +1,436 @@
+//! Render-ready mesh buffers for Rust graphics engines.
+//!
+//! Viewers built on Bevy, three-d, wgpu and similar engines want flat attribute buffers
+//! rather than 3MF's object graph. [`RenderScene::from_model`] walks the build, flattens
+//! component trees, and produces one [`RenderMesh`] per distinct mesh object plus one
+//! [`RenderInstance`] per placement, so an object printed eight times is uploaded once.
+//!
+//! Each mesh carries positions, normals and `u32` triangle indices, plus per-vertex colors
+//! when the object uses color groups or base materials. Colors stay in sRGB bytes, which
+//! three-d's `Srgba` takes as is; for Bevy's `ATTRIBUTE_COLOR` convert them to linear with
+//! `Color::srgba_u8(r, g, b, a).to_linear().to_f32_array()`.
+//!
+//! This module requires the `render` feature.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use lib3mf_converters::render::{RenderOptions, RenderScene};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let model = lib3mf_core::model::Model::default();
+//! let scene = RenderScene::from_model(&model, &RenderOptions::default())?;
+//! for instance in &scene.instances {
+//!     let mesh = &scene.meshes[instance.mesh];
+//!     // Upload `mesh.positions`, `mesh.normals`, `mesh.indices` (and `mesh.colors`) once,
+//!     // then draw it with `instance.transform.to_cols_array()` as the model matrix.
+//!     println!("{:?}: {} triangles", mesh.name, mesh.indices.len() / 3);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Components that point into other model parts (Production Extension `p:path`) are not
+//! followed by [`RenderScene::from_model`]; flatten those with
+//! [`PartResolver::resolve_meshes`] and pass the result to [`RenderScene::from_resolved`].
+//!
+//! [`PartResolver::resolve_meshes`]: lib3mf_core::model::resolver::PartResolver::resolve_meshes
+
+use glam::{Mat4, Vec3};
+use lib3mf_core::error::{Lib3mfError, Result};
+use lib3mf_core::model::{
+    Geometry, Mesh, Model, Object, ResolvedMesh, ResourceCollection, ResourceId, Triangle,
+};
+use std::collections::HashMap;
+
+/// Component nesting depth at which [`RenderScene::from_model`] gives up.
+const MAX_DEPTH: usize = 16;
+
+/// Options for building render buffers.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RenderOptions {
+    /// Share vertices between triangles and average their normals.
+    ///
+    /// By default every triangle gets its own three vertices with the face normal, which
+    /// gives the faceted look expected for CAD parts and keeps per-triangle colors exact.
+    /// Smooth normals suit scanned or organic meshes; a shared vertex then takes the color
+    /// of the first triangle corner that uses it.
+    pub smooth_normals: bool,
+}
+
+/// Vertex and index buffers for one mesh object.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RenderMesh {
+    /// Resource ID of the source object, if it came from a model's resources.
+    pub object_id: Option<ResourceId>,
+    /// Name of the source object, if set.
+    pub name: Option<String>,
+    /// Vertex positions in model units.
+    pub positions: Vec<[f32; 3]>,
+    /// Unit-length vertex normals, one per position.
+    pub normals: Vec<[f32; 3]>,
+    /// Triangle list indices into `positions`, three per triangle, counter-clockwise.
+    pub indices: Vec<u32>,
+    /// sRGB vertex colors, one per position, if any triangle of the object is colored.
+    /// Uncolored vertices of a partly colored mesh are opaque white.
+    pub colors: Option<Vec<[u8; 4]>>,
+}
+
+/// One placement of a [`RenderMesh`] in the scene.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderInstance {
+    /// Index into [`RenderScene::meshes`].
+    pub mesh: usize,
+    /// Model matrix: the build item transform times any component transforms.
+    pub transform: Mat4,
+}
+
+/// Flattened, instanced view of a model's build.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RenderScene {
+    /// Distinct meshes, each referenced by one or more instances.
+    pub meshes: Vec<RenderMesh>,
+    /// Placements in build order.
+    pub instances: Vec<RenderInstance>,
+}
+
+impl RenderMesh {
+    /// Builds buffers for a mesh without colors.
+    pub fn from_mesh(mesh: &Mesh, options: &RenderOptions) -> Self {
+        build_buffers(mesh, options, |_| None)
+    }
+
+    /// Builds buffers for a mesh object, resolving vertex colors from the color groups and
+    /// base materials in `resources`.
+    ///
+    /// Returns `None` if the object does not hold a triangle mesh.
+    pub fn from_object(
+        object: &Object,
+        resources: &ResourceCollection,
+        options: &RenderOptions,
+    ) -> Option<Self> {
+        let Geometry::Mesh(mesh) = &object.geometry else {
+            return None;
+        };
+        let mut buffers = build_buffers(mesh, options, |tri: &Triangle| {
+            // Triangle properties override the object default; p2/p3 default to p1.
+            let (pid, p1) = match tri.pid {
+                Some(pid) => (ResourceId(pid), tri.p1?),
+                None => (object.pid?, tri.p1.or(object.pindex)?),
+            };
+            let corners = [p1, tri.p2.unwrap_or(p1), tri.p3.unwrap_or(p1)];
+            let mut out = [[0u8; 4]; 3];
+            for (slot, index) in out.iter_mut().zip(corners) {
+                *slot = property_color(resources, pid, index)?;
+            }
+            Some(out)
+        });
+        buffers.object_id = Some(object.id);
+        buffers.name = object.name.clone();
+        Some(buffers)
+    }
+}
+
+impl RenderScene {
+    /// Flattens the build of `model` into instanced render meshes.
+    ///
+    /// Build items marked non-printable are included; filter `model.build.items` first to
+    /// hide them. Non-mesh geometry (slices, volumetric, boolean shapes) is skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Lib3mfError::InvalidStructure` if a component tree is cyclic or nested more
+    /// than 16 levels deep, and `Lib3mfError::ResourceNotFound` if a build item or component
+    /// references a missing object.
+    pub fn from_model(model: &Model, options: &RenderOptions) -> Result<Self> {
+        let mut scene = Self::default();
+        let mut mesh_indices = HashMap::new();
+        let mut ancestry = Vec::new();
+        for item in &model.build.items {
+            if item.path.is_some() {
+                continue;
+            }
+            scene.add_object(
+                &model.resources,
+                item.object_id,
+                item.transform,
+                options,
+                &mut mesh_indices,
+                &mut ancestry,
+            )?;
+        }
+        Ok(scene)
+    }
+
+    /// Builds a scene from meshes flattened by
+    /// [`PartResolver::resolve_meshes`](lib3mf_core::model::resolver::PartResolver::resolve_meshes).
+    ///
+    /// Each resolved mesh becomes its own [`RenderMesh`] (without colors) and instance.
+    pub fn from_resolved(resolved: &[ResolvedMesh], options: &RenderOptions) -> Self {
+        let mut scene = Self::default();
+        for entry in resolved {
+            let mut mesh = RenderMesh::from_mesh(&entry.mesh, options);
+            mesh.name = entry.name.clone();
+            scene.instances.push(RenderInstance {
+                mesh: scene.meshes.len(),
+                transform: entry.transform,
+            });
+            scene.meshes.push(mesh);
+        }
+        scene
+    }
+
+    fn add_object(
+        &mut self,
+        resources: &ResourceCollection,
+        id: ResourceId,
+        transform: Mat4,
+        options: &RenderOptions,
+        mesh_indices: &mut HashMap<ResourceId, usize>,
+        ancestry: &mut Vec<ResourceId>,
+    ) -> Result<()> {
+        if ancestry.len() > MAX_DEPTH {
+            return Err(Lib3mfError::InvalidStructure(format!(
+                "Component tree depth exceeds maximum of {MAX_DEPTH}"
+            )));
+        }
+        if ancestry.contains(&id) {
+            return Err(Lib3mfError::InvalidStructure(format!(
+                "Component cycle detected at object {}",
+                id.0
+            )));
+        }
+        let object = resources
+            .get_object(id)
+            .ok_or(Lib3mfError::ResourceNotFound(id.0))?;
+
+        match &object.geometry {
+            Geometry::Mesh(_) => {
+                let mesh = match mesh_indices.get(&id) {
+                    Some(&index) => index,
+                    None => {
+                        let index = self.meshes.len();
+                        self.meshes
+                            .extend(RenderMesh::from_object(object, resources, options));
+                        mesh_indices.insert(id, index);
+                        index
+                    }
+                };
+                self.instances.push(RenderInstance { mesh, transform });
+            }
+            Geometry::Components(components) => {
+                ancestry.push(id);
+                for component in &components.components {
+                    if component.path.is_some() {
+                        continue;
+                    }
+                    self.add_object(
+                        resources,
+                        component.object_id,
+                        transform * component.transform,
+                        options,
+                        mesh_indices,
+                        ancestry,
+                    )?;
+                }
+                ancestry.pop();
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Looks up entry `index` of property group `pid` as an sRGB color.
+fn property_color(resources: &ResourceCollection, pid: ResourceId, index: u32) -> Option<[u8; 4]> {
+    let color = if let Some(group) = resources.get_color_group(pid) {
+        *group.colors.get(index as usize)?
+    } else {
+        resources
+            .get_base_materials(pid)?
+            .materials
+            .get(index as usize)?
+            .display_color
+    };
+    Some([color.r, color.g, color.b, color.a])
+}
+
+fn build_buffers(
+    mesh: &Mesh,
+    options: &RenderOptions,
+    corner_colors: impl Fn(&Triangle) -> Option<[[u8; 4]; 3]>,
+) -> RenderMesh {
+    let position = |i: u32| {
+        let v = &mesh.vertices[i as usize];
+        Vec3::new(v.x, v.y, v.z)
+    };
+    // Triangles with out-of-range indices are dropped rather than drawn garbled.
+    let in_range = |tri: &&Triangle| {
+        [tri.v1, tri.v2, tri.v3]
+            .iter()
+            .all(|&i| (i as usize) < mesh.vertices.len())
+    };
+    let mut out = RenderMesh::default();
+    let mut colors = Vec::new();
+    let mut any_color = false;
+
+    if options.smooth_normals {
+        out.positions = mesh.vertices.iter().map(|v| [v.x, v.y, v.z]).collect();
+        let mut normals = vec![Vec3::ZERO; mesh.vertices.len()];
+        colors = vec![None; mesh.vertices.len()];
+        for tri in mesh.triangles.iter().filter(in_range) {
+            let corners = [tri.v1, tri.v2, tri.v3];
+            // The unnormalized cross product weights each face by its area.
+            let [a, b, c] = corners.map(position);
+            let face = (b - a).cross(c - a);
+            let tri_colors = corner_colors(tri);
+            for (k, &i) in corners.iter().enumerate() {
+                normals[i as usize] += face;
+                if let Some(tri_colors) = tri_colors
+                    && colors[i as usize].is_none()
+                {
+                    colors[i as usize] = Some(tri_colors[k]);
+                    any_color = true;
+                }
+            }
+            out.indices.extend(corners);
+        }
+        out.normals = normals
+            .into_iter()
+            .map(|n| n.normalize_or_zero().to_array())
+            .collect();
+    } else {
+        for tri in mesh.triangles.iter().filter(in_range) {
+            let [a, b, c] = [tri.v1, tri.v2, tri.v3].map(position);
+            let normal = (b - a).cross(c - a).normalize_or_zero().to_array();
+            let tri_colors = corner_colors(tri);
+            any_color |= tri_colors.is_some();
+            for (k, p) in [a, b, c].into_iter().enumerate() {
+                out.indices.push(out.positions.len() as u32);
+                out.positions.push(p.to_array());
+                out.normals.push(normal);
+                colors.push(tri_colors.map(|c| c[k]));
+            }
+        }
+    }
+
+    if any_color {
+        out.colors = Some(colors.into_iter().map(|c| c.unwrap_or([255; 4])).collect());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lib3mf_core::parser::parse_model;
+    use std::io::Cursor;
+
+    const XML: &str = r##"<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    <resources>
+        <colorgroup id="1">
+            <color color="#FF0000FF" />
+            <color color="#00FF00FF" />
+        </colorgroup>
+        <object id="2" name="Tetra" pid="1" pindex="0">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="1" y="0" z="0" />
+                    <vertex x="0" y="1" z="0" />
+                    <vertex x="0" y="0" z="1" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="2" v3="1" />
+                    <triangle v1="0" v2="1" v3="3" p1="1" />
+                    <triangle v1="1" v2="2" v3="3" />
+                    <triangle v1="0" v2="3" v3="2" />
+                </triangles>
+            </mesh>
+        </object>
+        <object id="3">
+            <components>
+                <component objectid="2" />
+                <component objectid="2" transform="1 0 0 0 1 0 0 0 1 5 0 0" />
+            </components>
+        </object>
+    </resources>
+    <build>
+        <item objectid="3" transform="1 0 0 0 1 0 0 0 1 0 0 2" />
+        <item objectid="2" />
+    </build>
+</model>"##;
+
+    #[test]
+    fn test_instances_share_one_mesh() {
+        let model = parse_model(Cursor::new(XML)).unwrap();
+        let scene = RenderScene::from_model(&model, &RenderOptions::default()).unwrap();
+
+        assert_eq!(scene.meshes.len(), 1);
+        assert_eq!(scene.instances.len(), 3);
+        assert!(scene.instances.iter().all(|i| i.mesh == 0));
+        assert_eq!(
+            scene.instances[1].transform.transform_point3(Vec3::ZERO),
+            Vec3::new(5.0, 0.0, 2.0)
+        );
+        assert_eq!(scene.instances[2].transform, Mat4::IDENTITY);
+
+        let mesh = &scene.meshes[0];
+        assert_eq!(mesh.object_id, Some(ResourceId(2)));
+        assert_eq!(mesh.name.as_deref(), Some("Tetra"));
+    }
+
+    #[test]
+    fn test_flat_buffers_with_colors() {
+        let model = parse_model(Cursor::new(XML)).unwrap();
+        let object = model.resources.get_object(ResourceId(2)).unwrap();
+        let mesh =
+            RenderMesh::from_object(object, &model.resources, &RenderOptions::default()).unwrap();
+
+        assert_eq!(mesh.positions.len(), 12);
+        assert_eq!(mesh.normals.len(), 12);
+        assert_eq!(mesh.indices, (0..12).collect::<Vec<u32>>());
+        // First face lies in z = 0 and winds to face -z.
+        assert_eq!(mesh.normals[0], [0.0, 0.0, -1.0]);
+
+        let colors = mesh.colors.unwrap();
+        assert_eq!(colors[0], [255, 0, 0, 255]);
+        assert_eq!(colors[3], [0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn test_smooth_buffers_share_vertices() {
+        let model = parse_model(Cursor::new(XML)).unwrap();
+        let Geometry::Mesh(source) = &model.resources.get_object(ResourceId(2)).unwrap().geometry
+        else {
+            unreachable!()
+        };
+        let options = RenderOptions {
+            smooth_normals: true,
+        };
+        let mesh = RenderMesh::from_mesh(source, &options);
+
+        assert_eq!(mesh.positions.len(), 4);
+        assert_eq!(mesh.indices.len(), 12);
+        assert!(mesh.colors.is_none());
+        for normal in &mesh.normals {
+            assert!((Vec3::from_array(*normal).length() - 1.0).abs() < 1e-5);
+        }
+        // The corner at the origin averages three axis-aligned faces.
+        let n = Vec3::from_array(mesh.normals[0]);
+        assert!(n.x < 0.0 && n.y < 0.0 && n.z < 0.0);
+    }
+
+    #[test]
+    fn test_component_cycle_is_an_error() {
+        let xml = r#"<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    <resources>
+        <object id="1"><components><component objectid="2" /></components></object>
+        <object id="2"><components><component objectid="1" /></components></object>
+    </resources>
+    <build><item objectid="1" /></build>
+</model>"#;
+        let model = parse_model(Cursor::new(xml)).unwrap();
+        let err = RenderScene::from_model(&model, &RenderOptions::default()).unwrap_err();
+        assert!(err.to_string().contains("cycle"), "{err}");
+    }
+}
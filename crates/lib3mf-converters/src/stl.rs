@@ -45,46 +45,53 @@
 //! # }
 //! ```
 //!
-//! ### Exporting Binary STL
+//! ### Exporting STL
 //!
-//! ```no_run
-//! use lib3mf_converters::stl::BinaryStlExporter;
-//! use lib3mf_core::model::Model;
-//! use std::fs::File;
-//!
-//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
-//! # let model = Model::default();
-//! let file = File::create("output.stl")?;
-//! BinaryStlExporter::write(&model, file)?;
-//! # Ok(())
-//! # }
-//! ```
-//!
-//! ### Exporting ASCII STL
+//! [`StlExporter`] writes binary STL by default; [`StlExportOptions`] selects ASCII.
 //!
 //! ```no_run
-//! use lib3mf_converters::stl::AsciiStlExporter;
+//! use lib3mf_converters::stl::{StlExportOptions, StlExporter, StlFormat};
 //! use lib3mf_core::model::Model;
 //! use std::fs::File;
 //!
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! # let model = Model::default();
-//! let file = File::create("output.stl")?;
-//! AsciiStlExporter::write(&model, file)?;
+//! StlExporter::default().write(&model, File::create("output.stl")?)?;
+//!
+//! let ascii = StlExporter::new(StlExportOptions {
+//!     format: StlFormat::Ascii,
+//! });
+//! ascii.write(&model, File::create("output_ascii.stl")?)?;
 //! # Ok(())
 //! # }
 //! ```
 //!
+//! `BinaryStlExporter` and `AsciiStlExporter` are deprecated in favor of [`StlExporter`].
+//!
 //! [`Model`]: lib3mf_core::model::Model
 
+use crate::input::InputFile;
+use crate::registry::{ExportOptions, ImportOptions, ModelExporter, ModelImporter};
+use crate::transform::CoordinateSystem;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use lib3mf_core::error::{Lib3mfError, Result};
 use lib3mf_core::model::resources::ResourceId;
-use lib3mf_core::model::{BuildItem, Mesh, Model, Triangle, Vertex};
+use lib3mf_core::model::{BuildItem, Mesh, Model, Triangle, Unit, Vertex};
 use std::collections::HashMap;
-use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Triangles read from a binary STL file at a time.
+const TRIANGLES_PER_CHUNK: usize = 4096;
 
-/// Detected STL file format.
+/// Size of one binary STL triangle record: normal, three vertices and attribute byte count.
+const TRIANGLE_SIZE: usize = 50;
+
+/// Most triangles reserved for ahead of reading them, from the count in a binary STL header.
+const MAX_RESERVED_TRIANGLES: usize = 1 << 22;
+
+/// STL encoding, as detected on import or chosen for export.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StlFormat {
     /// Binary STL — compact 80-byte header followed by packed triangle records.
@@ -138,6 +145,52 @@ pub fn detect_stl_format<R: Read + Seek>(reader: &mut R) -> Result<StlFormat> {
     Ok(StlFormat::Ascii)
 }
 
+/// Options for [`StlImporter::read_with_options`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct StlImportOptions {
+    /// Force binary or ASCII parsing instead of detecting the encoding.
+    pub format: Option<StlFormat>,
+    /// Unit of the file's coordinates, which STL does not record (default: millimeter).
+    pub unit: Unit,
+    /// Factor applied to all coordinates (default: 1.0).
+    pub scale: f32,
+    /// Axis convention of the file (default: Z-up, right-handed). Other conventions are
+    /// converted to that of 3MF.
+    pub axes: CoordinateSystem,
+    /// Distance below which vertices are merged (default: 0.0, exact matches only).
+    pub weld_epsilon: f32,
+}
+
+impl Default for StlImportOptions {
+    fn default() -> Self {
+        Self::from(&ImportOptions::default())
+    }
+}
+
+impl From<&ImportOptions> for StlImportOptions {
+    fn from(options: &ImportOptions) -> Self {
+        Self {
+            format: None,
+            unit: options.unit,
+            scale: options.scale,
+            axes: options.axes,
+            weld_epsilon: options.weld_epsilon,
+        }
+    }
+}
+
+impl From<&StlImportOptions> for ImportOptions {
+    fn from(options: &StlImportOptions) -> Self {
+        Self {
+            scale: options.scale,
+            weld_epsilon: options.weld_epsilon,
+            unit: options.unit,
+            axes: options.axes,
+            ..Default::default()
+        }
+    }
+}
+
 /// Imports STL files (binary or ASCII) into 3MF [`Model`] structures.
 ///
 /// The importer supports both binary and ASCII STL formats:
@@ -196,6 +249,30 @@ impl StlImporter {
         }
     }
 
+    /// Reads an STL file with unit, orientation, scale and welding adjustments.
+    ///
+    /// The encoding is detected as in [`read()`](Self::read) unless
+    /// [`StlImportOptions::format`] forces one.
+    ///
+    /// # Errors
+    ///
+    /// As for [`read()`](Self::read), plus [`Lib3mfError::Validation`] for an invalid scale.
+    pub fn read_with_options<R: Read + Seek>(
+        mut reader: R,
+        options: &StlImportOptions,
+    ) -> Result<Model> {
+        let format = match options.format {
+            Some(format) => format,
+            None => detect_stl_format(&mut reader)?,
+        };
+        let mut model = match format {
+            StlFormat::Binary => Self::read_binary(reader)?,
+            StlFormat::Ascii => Self::read_ascii(reader)?,
+        };
+        ImportOptions::from(options).apply_unitless(&mut model)?;
+        Ok(model)
+    }
+
     /// Reads a binary STL file and converts it to a 3MF [`Model`].
     ///
     /// # Arguments
@@ -225,6 +302,8 @@ impl StlImporter {
     ///   as key. Only exactly identical vertices (bitwise) are merged.
     /// - **Normal vectors**: Read from STL but ignored (not stored in Model).
     /// - **Attribute bytes**: Read but ignored (2-byte field after each triangle).
+    /// - **Chunked reading**: Triangle records are read a few thousand at a time, so an
+    ///   unbuffered reader is fine.
     ///
     /// # Examples
     ///
@@ -259,44 +338,52 @@ impl StlImporter {
         let mut header = [0u8; 80];
         reader.read_exact(&mut header).map_err(Lib3mfError::Io)?;
 
-        let triangle_count = reader.read_u32::<LittleEndian>().map_err(|_| {
-            Lib3mfError::Validation("Failed to read STL triangle count".to_string())
-        })?;
+        let triangle_count = reader
+            .read_u32::<LittleEndian>()
+            .map_err(|_| Lib3mfError::Validation("Failed to read STL triangle count".to_string()))?
+            as usize;
 
         let mut mesh = Mesh::default();
-        let mut vert_map: HashMap<[u32; 3], u32> = HashMap::new();
-
-        for _ in 0..triangle_count {
-            // Normal (3 floats) - Ignored
-            let _nx = reader.read_f32::<LittleEndian>().map_err(Lib3mfError::Io)?;
-            let _ny = reader.read_f32::<LittleEndian>().map_err(Lib3mfError::Io)?;
-            let _nz = reader.read_f32::<LittleEndian>().map_err(Lib3mfError::Io)?;
-
-            let mut indices = [0u32; 3];
-
-            for index in &mut indices {
-                let x = reader.read_f32::<LittleEndian>().map_err(Lib3mfError::Io)?;
-                let y = reader.read_f32::<LittleEndian>().map_err(Lib3mfError::Io)?;
-                let z = reader.read_f32::<LittleEndian>().map_err(Lib3mfError::Io)?;
-
-                let key = [x.to_bits(), y.to_bits(), z.to_bits()];
+        // Reserve up front so a large mesh does not overshoot its size by growing, but not
+        // so much that a corrupt count allocates before any triangle is read
+        let reserve = triangle_count.min(MAX_RESERVED_TRIANGLES);
+        mesh.triangles.reserve_exact(reserve);
+        mesh.vertices.reserve_exact(reserve / 2);
+        let mut vert_map: HashMap<[u32; 3], u32> = HashMap::with_capacity(reserve / 2);
+
+        // Triangles are read a chunk at a time rather than field by field
+        let mut chunk = vec![0u8; TRIANGLES_PER_CHUNK.min(triangle_count) * TRIANGLE_SIZE];
+        let mut remaining = triangle_count;
+        while remaining > 0 {
+            let count = remaining.min(TRIANGLES_PER_CHUNK);
+            let records = &mut chunk[..count * TRIANGLE_SIZE];
+            reader.read_exact(records).map_err(Lib3mfError::Io)?;
+            remaining -= count;
+
+            for record in records.chunks_exact(TRIANGLE_SIZE) {
+                // Normal (first 12 bytes) and attribute byte count (last 2) are ignored
+                let mut indices = [0u32; 3];
+                for (index, vertex) in indices.iter_mut().zip(record[12..48].chunks_exact(12)) {
+                    let coord = |i: usize| {
+                        f32::from_le_bytes([vertex[i], vertex[i + 1], vertex[i + 2], vertex[i + 3]])
+                    };
+                    let (x, y, z) = (coord(0), coord(4), coord(8));
+                    let key = [x.to_bits(), y.to_bits(), z.to_bits()];
+
+                    *index = *vert_map.entry(key).or_insert_with(|| {
+                        let new_idx = mesh.vertices.len() as u32;
+                        mesh.vertices.push(Vertex { x, y, z });
+                        new_idx
+                    });
+                }
 
-                let idx = *vert_map.entry(key).or_insert_with(|| {
-                    let new_idx = mesh.vertices.len() as u32;
-                    mesh.vertices.push(Vertex { x, y, z });
-                    new_idx
+                mesh.triangles.push(Triangle {
+                    v1: indices[0],
+                    v2: indices[1],
+                    v3: indices[2],
+                    ..Default::default()
                 });
-                *index = idx;
             }
-
-            let _attr_byte_count = reader.read_u16::<LittleEndian>().map_err(Lib3mfError::Io)?;
-
-            mesh.triangles.push(Triangle {
-                v1: indices[0],
-                v2: indices[1],
-                v3: indices[2],
-                ..Default::default()
-            });
         }
 
         let mut model = Model::default();
@@ -323,6 +410,7 @@ impl StlImporter {
             uuid: None,
             path: None,
             printable: None,
+            metadata: Default::default(),
         });
 
         Ok(model)
@@ -363,7 +451,7 @@ impl StlImporter {
     /// [`ResourceId`]: lib3mf_core::model::resources::ResourceId
     /// [`BuildItem`]: lib3mf_core::model::BuildItem
     pub fn read_ascii<R: Read>(reader: R) -> Result<Model> {
-        let buf_reader = BufReader::new(reader);
+        let mut buf_reader = BufReader::new(reader);
         let mut model = Model::default();
         let mut next_id = 1u32;
 
@@ -371,27 +459,27 @@ impl StlImporter {
         let mut vert_map: HashMap<[u32; 3], u32> = HashMap::new();
         // Buffer for the 3 vertices of the current facet
         let mut facet_verts: Vec<(f32, f32, f32)> = Vec::with_capacity(3);
-
-        for line_res in buf_reader.lines() {
-            let line = line_res.map_err(Lib3mfError::Io)?;
-            let trimmed = line.trim();
-            if trimmed.is_empty() {
-                continue;
+        // Reused for every line, so parsing does not allocate per line
+        let mut line = String::new();
+        let mut keyword = String::new();
+
+        loop {
+            line.clear();
+            if buf_reader.read_line(&mut line).map_err(Lib3mfError::Io)? == 0 {
+                break;
             }
-
-            let parts: Vec<&str> = trimmed.split_whitespace().collect();
-            if parts.is_empty() {
+            let mut parts = line.split_whitespace();
+            let Some(first) = parts.next() else {
                 continue;
-            }
+            };
+            keyword.clear();
+            keyword.push_str(first);
+            keyword.make_ascii_lowercase();
 
-            match parts[0].to_ascii_lowercase().as_str() {
+            match keyword.as_str() {
                 "solid" => {
                     // Name is everything after "solid" (joined with spaces)
-                    let name = if parts.len() > 1 {
-                        parts[1..].join(" ")
-                    } else {
-                        String::new()
-                    };
+                    let name = parts.collect::<Vec<_>>().join(" ");
                     current_mesh = Some((Mesh::default(), name));
                     vert_map.clear();
                     facet_verts.clear();
@@ -401,17 +489,19 @@ impl StlImporter {
                     facet_verts.clear();
                 }
                 "vertex" => {
-                    if parts.len() >= 4 {
-                        let x = parts[1].parse::<f32>().map_err(|_| {
-                            Lib3mfError::InvalidStructure("Invalid STL vertex x coordinate".into())
-                        })?;
-                        let y = parts[2].parse::<f32>().map_err(|_| {
-                            Lib3mfError::InvalidStructure("Invalid STL vertex y coordinate".into())
-                        })?;
-                        let z = parts[3].parse::<f32>().map_err(|_| {
-                            Lib3mfError::InvalidStructure("Invalid STL vertex z coordinate".into())
-                        })?;
-                        facet_verts.push((x, y, z));
+                    if parts.clone().count() >= 3 {
+                        let mut coord = |axis: char| {
+                            parts
+                                .next()
+                                .unwrap_or_default()
+                                .parse::<f32>()
+                                .map_err(|_| {
+                                    Lib3mfError::InvalidStructure(format!(
+                                        "Invalid STL vertex {axis} coordinate"
+                                    ))
+                                })
+                        };
+                        facet_verts.push((coord('x')?, coord('y')?, coord('z')?));
                     }
                 }
                 "endfacet" => {
@@ -487,9 +577,23 @@ fn finalize_solid(model: &mut Model, mesh: Mesh, name: String, id: u32) {
         uuid: None,
         path: None,
         printable: None,
+        metadata: Default::default(),
     });
 }
 
+/// Returns a transformed triangle's corners in outward-facing order.
+///
+/// A mirroring transform (negative determinant) reverses the winding of every triangle,
+/// which would turn the exported part inside out.
+fn oriented(
+    v1: glam::Vec3,
+    v2: glam::Vec3,
+    v3: glam::Vec3,
+    mirrored: bool,
+) -> (glam::Vec3, glam::Vec3, glam::Vec3) {
+    if mirrored { (v1, v3, v2) } else { (v1, v2, v3) }
+}
+
 /// Computes a face normal from three vertices using the cross product.
 ///
 /// Returns a zero vector for degenerate (zero-area) triangles.
@@ -501,459 +605,479 @@ fn compute_face_normal(v1: glam::Vec3, v2: glam::Vec3, v3: glam::Vec3) -> glam::
 
 /// Exports 3MF [`Model`] structures to binary STL files.
 ///
-/// The exporter flattens all mesh objects referenced in build items into a single STL file,
-/// applying build item transformations to vertex coordinates.
-///
-/// This was previously named `StlExporter`. If you were using `StlExporter`, update your
-/// code to use `BinaryStlExporter`.
-///
 /// [`Model`]: lib3mf_core::model::Model
+#[deprecated(note = "use StlExporter with StlExportOptions")]
 pub struct BinaryStlExporter;
 
+#[allow(deprecated)]
 impl BinaryStlExporter {
-    /// Writes a 3MF [`Model`] to binary STL format.
-    ///
-    /// # Arguments
-    ///
-    /// * `model` - The 3MF model to export
-    /// * `writer` - Any type implementing [`Write`] to receive STL data
-    ///
-    /// # Returns
-    ///
-    /// `Ok(())` on successful export.
-    ///
-    /// # Errors
-    ///
-    /// Returns [`Lib3mfError::Io`] if any write operation fails.
-    ///
-    /// # Format Details
-    ///
-    /// - **Header**: 80 zero bytes (standard for most STL files)
-    /// - **Normals**: Written as (0, 0, 0) - viewers must compute face normals
-    /// - **Transformations**: Build item transforms are applied to vertex coordinates
-    /// - **Attribute bytes**: Written as 0 (no extended attributes)
-    ///
-    /// # Behavior
-    ///
-    /// - Only mesh objects from `model.build.items` are exported
-    /// - Non-mesh geometries (Components, BooleanShape, etc.) are skipped
-    /// - Each build item's transformation matrix is applied to its mesh vertices
-    /// - All triangles from all build items are combined into a single STL file
-    ///
-    /// # Examples
-    ///
-    /// ```no_run
-    /// use lib3mf_converters::stl::BinaryStlExporter;
-    /// use lib3mf_core::model::Model;
-    /// use std::fs::File;
-    ///
-    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let model = Model::default();
-    /// let output = File::create("exported.stl")?;
-    /// BinaryStlExporter::write(&model, output)?;
-    /// println!("Model exported successfully");
-    /// # Ok(())
-    /// # }
-    /// ```
+    /// Writes the build items of `model` as binary STL.
     ///
     /// [`Model`]: lib3mf_core::model::Model
-    /// [`Lib3mfError::Io`]: lib3mf_core::error::Lib3mfError::Io
-    pub fn write<W: Write>(model: &Model, mut writer: W) -> Result<()> {
-        // 1. Collect all triangles from all build items
-        let mut triangles: Vec<(glam::Vec3, glam::Vec3, glam::Vec3)> = Vec::new(); // v1, v2, v3
-
-        for item in &model.build.items {
-            #[allow(clippy::collapsible_if)]
-            if let Some(object) = model.resources.get_object(item.object_id) {
-                if let lib3mf_core::model::Geometry::Mesh(mesh) = &object.geometry {
-                    let transform = item.transform;
-
-                    for tri in &mesh.triangles {
-                        let v1_local = mesh.vertices[tri.v1 as usize];
-                        let v2_local = mesh.vertices[tri.v2 as usize];
-                        let v3_local = mesh.vertices[tri.v3 as usize];
-
-                        let v1 = transform
-                            .transform_point3(glam::Vec3::new(v1_local.x, v1_local.y, v1_local.z));
-                        let v2 = transform
-                            .transform_point3(glam::Vec3::new(v2_local.x, v2_local.y, v2_local.z));
-                        let v3 = transform
-                            .transform_point3(glam::Vec3::new(v3_local.x, v3_local.y, v3_local.z));
-
-                        triangles.push((v1, v2, v3));
-                    }
-                }
-            }
-        }
+    #[deprecated(note = "use StlExporter with StlExportOptions")]
+    pub fn write<W: Write>(model: &Model, writer: W) -> Result<()> {
+        write_binary(model, writer)
+    }
 
-        // 2. Write Header (80 bytes)
-        let header = [0u8; 80];
-        writer.write_all(&header).map_err(Lib3mfError::Io)?;
+    /// Writes the build items of `model` as binary STL, resolving components across
+    /// model parts.
+    #[deprecated(note = "use StlExporter with StlExportOptions")]
+    pub fn write_with_resolver<W: Write, A: lib3mf_core::archive::ArchiveReader>(
+        model: &Model,
+        resolver: lib3mf_core::model::resolver::PartResolver<A>,
+        writer: W,
+    ) -> Result<()> {
+        write_binary_with_resolver(model, resolver, writer)
+    }
+}
 
-        // 3. Write Count
-        writer
-            .write_u32::<LittleEndian>(triangles.len() as u32)
-            .map_err(Lib3mfError::Io)?;
+/// Exports 3MF [`Model`] structures to ASCII STL files.
+///
+/// [`Model`]: lib3mf_core::model::Model
+#[deprecated(note = "use StlExporter with StlExportOptions")]
+pub struct AsciiStlExporter;
 
-        // 4. Write Triangles
-        for (v1, v2, v3) in triangles {
-            // Normal (0,0,0) - let viewer calculate
-            writer
-                .write_f32::<LittleEndian>(0.0)
-                .map_err(Lib3mfError::Io)?;
-            writer
-                .write_f32::<LittleEndian>(0.0)
-                .map_err(Lib3mfError::Io)?;
-            writer
-                .write_f32::<LittleEndian>(0.0)
-                .map_err(Lib3mfError::Io)?;
+#[allow(deprecated)]
+impl AsciiStlExporter {
+    /// Writes the build items of `model` as ASCII STL.
+    #[deprecated(note = "use StlExporter with StlExportOptions")]
+    pub fn write<W: Write>(model: &Model, writer: W) -> Result<()> {
+        write_ascii(model, writer)
+    }
 
-            // v1
-            writer
-                .write_f32::<LittleEndian>(v1.x)
-                .map_err(Lib3mfError::Io)?;
-            writer
-                .write_f32::<LittleEndian>(v1.y)
-                .map_err(Lib3mfError::Io)?;
-            writer
-                .write_f32::<LittleEndian>(v1.z)
-                .map_err(Lib3mfError::Io)?;
+    /// Writes the build items of `model` as ASCII STL, resolving components across model
+    /// parts.
+    #[deprecated(note = "use StlExporter with StlExportOptions")]
+    pub fn write_with_resolver<W: Write, A: lib3mf_core::archive::ArchiveReader>(
+        model: &Model,
+        resolver: lib3mf_core::model::resolver::PartResolver<A>,
+        writer: W,
+    ) -> Result<()> {
+        write_ascii_with_resolver(model, resolver, writer)
+    }
+}
 
-            // v2
-            writer
-                .write_f32::<LittleEndian>(v2.x)
-                .map_err(Lib3mfError::Io)?;
-            writer
-                .write_f32::<LittleEndian>(v2.y)
-                .map_err(Lib3mfError::Io)?;
-            writer
-                .write_f32::<LittleEndian>(v2.z)
-                .map_err(Lib3mfError::Io)?;
+/// Options for [`StlExporter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StlExportOptions {
+    /// Output encoding. Defaults to [`StlFormat::Binary`].
+    pub format: StlFormat,
+}
 
-            // v3
-            writer
-                .write_f32::<LittleEndian>(v3.x)
-                .map_err(Lib3mfError::Io)?;
-            writer
-                .write_f32::<LittleEndian>(v3.y)
-                .map_err(Lib3mfError::Io)?;
-            writer
-                .write_f32::<LittleEndian>(v3.z)
-                .map_err(Lib3mfError::Io)?;
-
-            // Attribute byte count (0)
-            writer
-                .write_u16::<LittleEndian>(0)
-                .map_err(Lib3mfError::Io)?;
+impl Default for StlExportOptions {
+    fn default() -> Self {
+        Self {
+            format: StlFormat::Binary,
         }
+    }
+}
 
-        Ok(())
+/// Exports 3MF [`Model`] structures to STL, in the encoding chosen by [`StlExportOptions`].
+///
+/// The exporter flattens the mesh objects placed by build items, applying their
+/// transforms to vertex coordinates and reversing the winding of mirrored placements.
+/// Face normals are computed from the triangle edges, and are zero for degenerate
+/// triangles, which are still written.
+///
+/// - **Binary** output has an 80-byte zero header and zero attribute byte counts, and
+///   combines all triangles into one file.
+/// - **ASCII** output writes each placed object as a `solid ... endsolid` block named
+///   after the object, with normals in scientific notation and vertices to 6 decimal
+///   places.
+///
+/// This is the STL exporter used by [`FormatRegistry`](crate::registry::FormatRegistry); the registry's
+/// [`ExportOptions::ascii`](crate::registry::ExportOptions::ascii) flag selects ASCII output
+/// regardless of the configured format.
+///
+/// [`Model`]: lib3mf_core::model::Model
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StlExporter {
+    /// Export options.
+    pub options: StlExportOptions,
+}
+
+impl StlExporter {
+    /// Creates an exporter with the given options.
+    pub fn new(options: StlExportOptions) -> Self {
+        Self { options }
     }
 
-    /// Writes a 3MF [`Model`] to binary STL format with support for multi-part 3MF files.
-    ///
-    /// This method extends [`write`] by recursively resolving component references and external
-    /// model parts using a [`PartResolver`]. This is necessary for 3MF files with the Production
-    /// Extension that reference objects from external model parts.
-    ///
-    /// # Arguments
+    /// Writes the mesh objects placed by the build items of `model`. Objects that aren't
+    /// meshes, such as components, are skipped; use
+    /// [`write_with_resolver`](Self::write_with_resolver) to flatten them.
     ///
-    /// * `model` - The root 3MF model to export
-    /// * `resolver` - A [`PartResolver`] for loading external model parts from the 3MF archive
-    /// * `writer` - Any type implementing [`Write`] to receive STL data
+    /// # Examples
     ///
-    /// # Returns
+    /// ```no_run
+    /// use lib3mf_converters::stl::{StlExportOptions, StlExporter, StlFormat};
+    /// use lib3mf_core::model::Model;
+    /// use std::fs::File;
     ///
-    /// `Ok(())` on successful export.
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let model = Model::default();
+    /// let exporter = StlExporter::new(StlExportOptions {
+    ///     format: StlFormat::Ascii,
+    /// });
+    /// exporter.write(&model, File::create("exported.stl")?)?;
+    /// # Ok(())
+    /// # }
+    /// ```
     ///
     /// # Errors
     ///
     /// Returns [`Lib3mfError::Io`] if any write operation fails.
-    ///
-    /// Returns errors from the resolver if external parts cannot be loaded.
-    ///
-    /// # Behavior
-    ///
-    /// - Recursively resolves component hierarchies using the PartResolver
-    /// - Follows external references via component `path` attributes
-    /// - Applies accumulated transformations through the component tree
-    /// - Flattens all resolved meshes into a single STL file
+    pub fn write<W: Write>(&self, model: &Model, writer: W) -> Result<()> {
+        match self.options.format {
+            StlFormat::Binary => write_binary(model, writer),
+            StlFormat::Ascii => write_ascii(model, writer),
+        }
+    }
+
+    /// Writes the build items of `model`, resolving components and external model parts
+    /// (Production Extension) through `resolver` and applying the accumulated transforms.
+    /// ASCII output is written as a single solid.
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// use lib3mf_converters::stl::BinaryStlExporter;
+    /// use lib3mf_converters::stl::StlExporter;
     /// use lib3mf_core::archive::ZipArchiver;
     /// use lib3mf_core::model::resolver::PartResolver;
     /// use std::fs::File;
     ///
     /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// # let model = lib3mf_core::model::Model::default();
-    /// let archive_file = File::open("multipart.3mf")?;
-    /// let mut archiver = ZipArchiver::new(archive_file)?;
+    /// let mut archiver = ZipArchiver::new(File::open("multipart.3mf")?)?;
     /// let resolver = PartResolver::new(&mut archiver, model.clone());
-    ///
-    /// let output = File::create("output.stl")?;
-    /// BinaryStlExporter::write_with_resolver(&model, resolver, output)?;
+    /// StlExporter::default().write_with_resolver(&model, resolver, File::create("output.stl")?)?;
     /// # Ok(())
     /// # }
     /// ```
     ///
-    /// [`write`]: BinaryStlExporter::write
-    /// [`Model`]: lib3mf_core::model::Model
-    /// [`PartResolver`]: lib3mf_core::model::resolver::PartResolver
-    /// [`Lib3mfError::Io`]: lib3mf_core::error::Lib3mfError::Io
+    /// # Errors
+    ///
+    /// Returns [`Lib3mfError::Io`] if any write operation fails, or errors from the resolver
+    /// if external parts cannot be loaded.
     pub fn write_with_resolver<W: Write, A: lib3mf_core::archive::ArchiveReader>(
+        &self,
         model: &Model,
-        mut resolver: lib3mf_core::model::resolver::PartResolver<A>,
-        mut writer: W,
+        resolver: lib3mf_core::model::resolver::PartResolver<A>,
+        writer: W,
     ) -> Result<()> {
-        // 1. Collect all triangles from all build items (recursively)
-        let mut triangles: Vec<(glam::Vec3, glam::Vec3, glam::Vec3)> = Vec::new();
-
-        for item in &model.build.items {
-            collect_triangles(
-                &mut resolver,
-                item.object_id,
-                item.transform,
-                None, // Start with root path (None)
-                &mut triangles,
-            )?;
+        match self.options.format {
+            StlFormat::Binary => write_binary_with_resolver(model, resolver, writer),
+            StlFormat::Ascii => write_ascii_with_resolver(model, resolver, writer),
         }
+    }
+}
 
-        // 2. Write Header (80 bytes)
-        let header = [0u8; 80];
-        writer.write_all(&header).map_err(Lib3mfError::Io)?;
+fn write_binary<W: Write>(model: &Model, mut writer: W) -> Result<()> {
+    // 1. Collect all triangles from all build items
+    let mut triangles: Vec<(glam::Vec3, glam::Vec3, glam::Vec3)> = Vec::new(); // v1, v2, v3
+
+    for item in &model.build.items {
+        #[allow(clippy::collapsible_if)]
+        if let Some(object) = model.resources.get_object(item.object_id) {
+            if let lib3mf_core::model::Geometry::Mesh(mesh) = &object.geometry {
+                let transform = item.transform;
+                let mirrored = transform.determinant() < 0.0;
+
+                for tri in &mesh.triangles {
+                    let v1_local = mesh.vertices[tri.v1 as usize];
+                    let v2_local = mesh.vertices[tri.v2 as usize];
+                    let v3_local = mesh.vertices[tri.v3 as usize];
+
+                    let v1 = transform
+                        .transform_point3(glam::Vec3::new(v1_local.x, v1_local.y, v1_local.z));
+                    let v2 = transform
+                        .transform_point3(glam::Vec3::new(v2_local.x, v2_local.y, v2_local.z));
+                    let v3 = transform
+                        .transform_point3(glam::Vec3::new(v3_local.x, v3_local.y, v3_local.z));
 
-        // 3. Write Count
+                    triangles.push(oriented(v1, v2, v3, mirrored));
+                }
+            }
+        }
+    }
+
+    // 2. Write Header (80 bytes)
+    let header = [0u8; 80];
+    writer.write_all(&header).map_err(Lib3mfError::Io)?;
+
+    // 3. Write Count
+    writer
+        .write_u32::<LittleEndian>(triangles.len() as u32)
+        .map_err(Lib3mfError::Io)?;
+
+    // 4. Write Triangles
+    for (v1, v2, v3) in triangles {
+        // Normal
+        let normal = compute_face_normal(v1, v2, v3);
+        writer
+            .write_f32::<LittleEndian>(normal.x)
+            .map_err(Lib3mfError::Io)?;
         writer
-            .write_u32::<LittleEndian>(triangles.len() as u32)
+            .write_f32::<LittleEndian>(normal.y)
+            .map_err(Lib3mfError::Io)?;
+        writer
+            .write_f32::<LittleEndian>(normal.z)
             .map_err(Lib3mfError::Io)?;
 
-        // 4. Write Triangles
-        for (v1, v2, v3) in triangles {
-            // Normal (0,0,0) - let viewer calculate
-            writer
-                .write_f32::<LittleEndian>(0.0)
-                .map_err(Lib3mfError::Io)?;
-            writer
-                .write_f32::<LittleEndian>(0.0)
-                .map_err(Lib3mfError::Io)?;
-            writer
-                .write_f32::<LittleEndian>(0.0)
-                .map_err(Lib3mfError::Io)?;
+        // v1
+        writer
+            .write_f32::<LittleEndian>(v1.x)
+            .map_err(Lib3mfError::Io)?;
+        writer
+            .write_f32::<LittleEndian>(v1.y)
+            .map_err(Lib3mfError::Io)?;
+        writer
+            .write_f32::<LittleEndian>(v1.z)
+            .map_err(Lib3mfError::Io)?;
 
-            // v1
-            writer
-                .write_f32::<LittleEndian>(v1.x)
-                .map_err(Lib3mfError::Io)?;
-            writer
-                .write_f32::<LittleEndian>(v1.y)
-                .map_err(Lib3mfError::Io)?;
-            writer
-                .write_f32::<LittleEndian>(v1.z)
-                .map_err(Lib3mfError::Io)?;
+        // v2
+        writer
+            .write_f32::<LittleEndian>(v2.x)
+            .map_err(Lib3mfError::Io)?;
+        writer
+            .write_f32::<LittleEndian>(v2.y)
+            .map_err(Lib3mfError::Io)?;
+        writer
+            .write_f32::<LittleEndian>(v2.z)
+            .map_err(Lib3mfError::Io)?;
 
-            // v2
-            writer
-                .write_f32::<LittleEndian>(v2.x)
-                .map_err(Lib3mfError::Io)?;
-            writer
-                .write_f32::<LittleEndian>(v2.y)
-                .map_err(Lib3mfError::Io)?;
-            writer
-                .write_f32::<LittleEndian>(v2.z)
-                .map_err(Lib3mfError::Io)?;
+        // v3
+        writer
+            .write_f32::<LittleEndian>(v3.x)
+            .map_err(Lib3mfError::Io)?;
+        writer
+            .write_f32::<LittleEndian>(v3.y)
+            .map_err(Lib3mfError::Io)?;
+        writer
+            .write_f32::<LittleEndian>(v3.z)
+            .map_err(Lib3mfError::Io)?;
 
-            // v3
-            writer
-                .write_f32::<LittleEndian>(v3.x)
-                .map_err(Lib3mfError::Io)?;
-            writer
-                .write_f32::<LittleEndian>(v3.y)
-                .map_err(Lib3mfError::Io)?;
-            writer
-                .write_f32::<LittleEndian>(v3.z)
-                .map_err(Lib3mfError::Io)?;
-
-            // Attribute byte count (0)
-            writer
-                .write_u16::<LittleEndian>(0)
-                .map_err(Lib3mfError::Io)?;
-        }
+        // Attribute byte count (0)
+        writer
+            .write_u16::<LittleEndian>(0)
+            .map_err(Lib3mfError::Io)?;
+    }
+
+    Ok(())
+}
+
+fn write_binary_with_resolver<W: Write, A: lib3mf_core::archive::ArchiveReader>(
+    model: &Model,
+    mut resolver: lib3mf_core::model::resolver::PartResolver<A>,
+    mut writer: W,
+) -> Result<()> {
+    // 1. Collect all triangles from all build items (recursively)
+    let mut triangles: Vec<(glam::Vec3, glam::Vec3, glam::Vec3)> = Vec::new();
+
+    for item in &model.build.items {
+        collect_triangles(
+            &mut resolver,
+            item.object_id,
+            item.transform,
+            None, // Start with root path (None)
+            &mut triangles,
+        )?;
+    }
+
+    // 2. Write Header (80 bytes)
+    let header = [0u8; 80];
+    writer.write_all(&header).map_err(Lib3mfError::Io)?;
+
+    // 3. Write Count
+    writer
+        .write_u32::<LittleEndian>(triangles.len() as u32)
+        .map_err(Lib3mfError::Io)?;
+
+    // 4. Write Triangles
+    for (v1, v2, v3) in triangles {
+        // Normal
+        let normal = compute_face_normal(v1, v2, v3);
+        writer
+            .write_f32::<LittleEndian>(normal.x)
+            .map_err(Lib3mfError::Io)?;
+        writer
+            .write_f32::<LittleEndian>(normal.y)
+            .map_err(Lib3mfError::Io)?;
+        writer
+            .write_f32::<LittleEndian>(normal.z)
+            .map_err(Lib3mfError::Io)?;
+
+        // v1
+        writer
+            .write_f32::<LittleEndian>(v1.x)
+            .map_err(Lib3mfError::Io)?;
+        writer
+            .write_f32::<LittleEndian>(v1.y)
+            .map_err(Lib3mfError::Io)?;
+        writer
+            .write_f32::<LittleEndian>(v1.z)
+            .map_err(Lib3mfError::Io)?;
+
+        // v2
+        writer
+            .write_f32::<LittleEndian>(v2.x)
+            .map_err(Lib3mfError::Io)?;
+        writer
+            .write_f32::<LittleEndian>(v2.y)
+            .map_err(Lib3mfError::Io)?;
+        writer
+            .write_f32::<LittleEndian>(v2.z)
+            .map_err(Lib3mfError::Io)?;
+
+        // v3
+        writer
+            .write_f32::<LittleEndian>(v3.x)
+            .map_err(Lib3mfError::Io)?;
+        writer
+            .write_f32::<LittleEndian>(v3.y)
+            .map_err(Lib3mfError::Io)?;
+        writer
+            .write_f32::<LittleEndian>(v3.z)
+            .map_err(Lib3mfError::Io)?;
 
-        Ok(())
+        // Attribute byte count (0)
+        writer
+            .write_u16::<LittleEndian>(0)
+            .map_err(Lib3mfError::Io)?;
     }
+
+    Ok(())
 }
 
-/// Exports 3MF [`Model`] structures to ASCII STL files.
-///
-/// Each mesh object in the model's build items is written as a separate ASCII STL solid.
-/// Face normals are computed from the triangle edges using the cross product.
-///
-/// [`Model`]: lib3mf_core::model::Model
-pub struct AsciiStlExporter;
+fn write_ascii<W: Write>(model: &Model, mut writer: W) -> Result<()> {
+    for item in &model.build.items {
+        #[allow(clippy::collapsible_if)]
+        if let Some(object) = model.resources.get_object(item.object_id) {
+            if let lib3mf_core::model::Geometry::Mesh(mesh) = &object.geometry {
+                let name = object.name.as_deref().unwrap_or("");
+                let transform = item.transform;
+                let mirrored = transform.determinant() < 0.0;
 
-impl AsciiStlExporter {
-    /// Writes a 3MF [`Model`] to ASCII STL format.
-    ///
-    /// Each mesh object referenced in the model's build items is written as a separate
-    /// `solid ... endsolid` block. The solid name is taken from the object's name field,
-    /// or left empty if the object has no name.
-    ///
-    /// # Arguments
-    ///
-    /// * `model` - The 3MF model to export
-    /// * `writer` - Any type implementing [`Write`] to receive ASCII STL text
-    ///
-    /// # Returns
-    ///
-    /// `Ok(())` on successful export.
-    ///
-    /// # Errors
-    ///
-    /// Returns [`Lib3mfError::Io`] if any write operation fails.
-    ///
-    /// # Format Details
-    ///
-    /// - **Normals**: Computed via cross product of triangle edges (`normalize_or_zero()`)
-    /// - **Degenerate triangles**: Emit zero normal `(0 0 0)`, triangle is not skipped
-    /// - **Normal format**: Scientific notation with 6 decimal places (`{:.6e}`)
-    /// - **Vertex format**: Fixed-point with 6 decimal places (`{:.6}`)
-    /// - **Transformations**: Build item transforms are applied to vertex coordinates
-    /// - **Solid names**: Taken from `object.name`, empty string if `None`
-    ///
-    /// # Examples
-    ///
-    /// ```no_run
-    /// use lib3mf_converters::stl::AsciiStlExporter;
-    /// use lib3mf_core::model::Model;
-    /// use std::fs::File;
-    ///
-    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let model = Model::default();
-    /// let output = File::create("exported.stl")?;
-    /// AsciiStlExporter::write(&model, output)?;
-    /// # Ok(())
-    /// # }
-    /// ```
-    ///
-    /// [`Model`]: lib3mf_core::model::Model
-    /// [`Lib3mfError::Io`]: lib3mf_core::error::Lib3mfError::Io
-    pub fn write<W: Write>(model: &Model, mut writer: W) -> Result<()> {
-        for item in &model.build.items {
-            #[allow(clippy::collapsible_if)]
-            if let Some(object) = model.resources.get_object(item.object_id) {
-                if let lib3mf_core::model::Geometry::Mesh(mesh) = &object.geometry {
-                    let name = object.name.as_deref().unwrap_or("");
-                    let transform = item.transform;
-
-                    writeln!(writer, "solid {name}").map_err(Lib3mfError::Io)?;
-
-                    for tri in &mesh.triangles {
-                        let v1_local = mesh.vertices[tri.v1 as usize];
-                        let v2_local = mesh.vertices[tri.v2 as usize];
-                        let v3_local = mesh.vertices[tri.v3 as usize];
-
-                        let v1 = transform
-                            .transform_point3(glam::Vec3::new(v1_local.x, v1_local.y, v1_local.z));
-                        let v2 = transform
-                            .transform_point3(glam::Vec3::new(v2_local.x, v2_local.y, v2_local.z));
-                        let v3 = transform
-                            .transform_point3(glam::Vec3::new(v3_local.x, v3_local.y, v3_local.z));
-
-                        let normal = compute_face_normal(v1, v2, v3);
-
-                        writeln!(
-                            writer,
-                            "  facet normal {:.6e} {:.6e} {:.6e}",
-                            normal.x, normal.y, normal.z
-                        )
-                        .map_err(Lib3mfError::Io)?;
-                        writeln!(writer, "    outer loop").map_err(Lib3mfError::Io)?;
-                        writeln!(writer, "      vertex {:.6} {:.6} {:.6}", v1.x, v1.y, v1.z)
-                            .map_err(Lib3mfError::Io)?;
-                        writeln!(writer, "      vertex {:.6} {:.6} {:.6}", v2.x, v2.y, v2.z)
-                            .map_err(Lib3mfError::Io)?;
-                        writeln!(writer, "      vertex {:.6} {:.6} {:.6}", v3.x, v3.y, v3.z)
-                            .map_err(Lib3mfError::Io)?;
-                        writeln!(writer, "    endloop").map_err(Lib3mfError::Io)?;
-                        writeln!(writer, "  endfacet").map_err(Lib3mfError::Io)?;
-                    }
+                writeln!(writer, "solid {name}").map_err(Lib3mfError::Io)?;
+
+                for tri in &mesh.triangles {
+                    let v1_local = mesh.vertices[tri.v1 as usize];
+                    let v2_local = mesh.vertices[tri.v2 as usize];
+                    let v3_local = mesh.vertices[tri.v3 as usize];
 
-                    writeln!(writer, "endsolid {name}").map_err(Lib3mfError::Io)?;
+                    let v1 = transform
+                        .transform_point3(glam::Vec3::new(v1_local.x, v1_local.y, v1_local.z));
+                    let v2 = transform
+                        .transform_point3(glam::Vec3::new(v2_local.x, v2_local.y, v2_local.z));
+                    let v3 = transform
+                        .transform_point3(glam::Vec3::new(v3_local.x, v3_local.y, v3_local.z));
+                    let (v1, v2, v3) = oriented(v1, v2, v3, mirrored);
+
+                    let normal = compute_face_normal(v1, v2, v3);
+
+                    writeln!(
+                        writer,
+                        "  facet normal {:.6e} {:.6e} {:.6e}",
+                        normal.x, normal.y, normal.z
+                    )
+                    .map_err(Lib3mfError::Io)?;
+                    writeln!(writer, "    outer loop").map_err(Lib3mfError::Io)?;
+                    writeln!(writer, "      vertex {:.6} {:.6} {:.6}", v1.x, v1.y, v1.z)
+                        .map_err(Lib3mfError::Io)?;
+                    writeln!(writer, "      vertex {:.6} {:.6} {:.6}", v2.x, v2.y, v2.z)
+                        .map_err(Lib3mfError::Io)?;
+                    writeln!(writer, "      vertex {:.6} {:.6} {:.6}", v3.x, v3.y, v3.z)
+                        .map_err(Lib3mfError::Io)?;
+                    writeln!(writer, "    endloop").map_err(Lib3mfError::Io)?;
+                    writeln!(writer, "  endfacet").map_err(Lib3mfError::Io)?;
                 }
+
+                writeln!(writer, "endsolid {name}").map_err(Lib3mfError::Io)?;
             }
         }
-        Ok(())
     }
+    Ok(())
+}
 
-    /// Writes a 3MF [`Model`] to ASCII STL format with support for multi-part 3MF files.
-    ///
-    /// Resolves component references and external model parts using a [`PartResolver`],
-    /// then writes all collected triangles as a single ASCII STL solid.
-    ///
-    /// # Arguments
-    ///
-    /// * `model` - The root 3MF model to export
-    /// * `resolver` - A [`PartResolver`] for loading external model parts from the 3MF archive
-    /// * `writer` - Any type implementing [`Write`] to receive ASCII STL text
-    ///
-    /// # Returns
-    ///
-    /// `Ok(())` on successful export.
-    ///
-    /// # Errors
-    ///
-    /// Returns [`Lib3mfError::Io`] if any write operation fails.
-    /// Returns errors from the resolver if external parts cannot be loaded.
-    ///
-    /// [`Model`]: lib3mf_core::model::Model
-    /// [`PartResolver`]: lib3mf_core::model::resolver::PartResolver
-    pub fn write_with_resolver<W: Write, A: lib3mf_core::archive::ArchiveReader>(
-        model: &Model,
-        mut resolver: lib3mf_core::model::resolver::PartResolver<A>,
-        mut writer: W,
-    ) -> Result<()> {
-        // Collect all triangles from all build items (recursively)
-        let mut triangles: Vec<(glam::Vec3, glam::Vec3, glam::Vec3)> = Vec::new();
-
-        for item in &model.build.items {
-            collect_triangles(
-                &mut resolver,
-                item.object_id,
-                item.transform,
-                None,
-                &mut triangles,
-            )?;
-        }
+fn write_ascii_with_resolver<W: Write, A: lib3mf_core::archive::ArchiveReader>(
+    model: &Model,
+    mut resolver: lib3mf_core::model::resolver::PartResolver<A>,
+    mut writer: W,
+) -> Result<()> {
+    // Collect all triangles from all build items (recursively)
+    let mut triangles: Vec<(glam::Vec3, glam::Vec3, glam::Vec3)> = Vec::new();
+
+    for item in &model.build.items {
+        collect_triangles(
+            &mut resolver,
+            item.object_id,
+            item.transform,
+            None,
+            &mut triangles,
+        )?;
+    }
 
-        // Write as a single solid (resolver flattens all objects)
-        writeln!(writer, "solid ").map_err(Lib3mfError::Io)?;
+    // Write as a single solid (resolver flattens all objects)
+    writeln!(writer, "solid ").map_err(Lib3mfError::Io)?;
 
-        for (v1, v2, v3) in triangles {
-            let normal = compute_face_normal(v1, v2, v3);
+    for (v1, v2, v3) in triangles {
+        let normal = compute_face_normal(v1, v2, v3);
 
-            writeln!(
-                writer,
-                "  facet normal {:.6e} {:.6e} {:.6e}",
-                normal.x, normal.y, normal.z
-            )
+        writeln!(
+            writer,
+            "  facet normal {:.6e} {:.6e} {:.6e}",
+            normal.x, normal.y, normal.z
+        )
+        .map_err(Lib3mfError::Io)?;
+        writeln!(writer, "    outer loop").map_err(Lib3mfError::Io)?;
+        writeln!(writer, "      vertex {:.6} {:.6} {:.6}", v1.x, v1.y, v1.z)
             .map_err(Lib3mfError::Io)?;
-            writeln!(writer, "    outer loop").map_err(Lib3mfError::Io)?;
-            writeln!(writer, "      vertex {:.6} {:.6} {:.6}", v1.x, v1.y, v1.z)
-                .map_err(Lib3mfError::Io)?;
-            writeln!(writer, "      vertex {:.6} {:.6} {:.6}", v2.x, v2.y, v2.z)
-                .map_err(Lib3mfError::Io)?;
-            writeln!(writer, "      vertex {:.6} {:.6} {:.6}", v3.x, v3.y, v3.z)
-                .map_err(Lib3mfError::Io)?;
-            writeln!(writer, "    endloop").map_err(Lib3mfError::Io)?;
-            writeln!(writer, "  endfacet").map_err(Lib3mfError::Io)?;
-        }
+        writeln!(writer, "      vertex {:.6} {:.6} {:.6}", v2.x, v2.y, v2.z)
+            .map_err(Lib3mfError::Io)?;
+        writeln!(writer, "      vertex {:.6} {:.6} {:.6}", v3.x, v3.y, v3.z)
+            .map_err(Lib3mfError::Io)?;
+        writeln!(writer, "    endloop").map_err(Lib3mfError::Io)?;
+        writeln!(writer, "  endfacet").map_err(Lib3mfError::Io)?;
+    }
+
+    writeln!(writer, "endsolid ").map_err(Lib3mfError::Io)?;
+
+    Ok(())
+}
+
+impl ModelImporter for StlImporter {
+    fn name(&self) -> &str {
+        "STL"
+    }
 
-        writeln!(writer, "endsolid ").map_err(Lib3mfError::Io)?;
+    fn extensions(&self) -> &[&str] {
+        &["stl"]
+    }
 
-        Ok(())
+    // No signature: binary STL headers are free-form and often start with "solid" too.
+    fn import(&self, path: &Path, options: &ImportOptions) -> Result<Model> {
+        Self::read_with_options(InputFile::open(path, options)?, &options.into())
+    }
+}
+
+impl ModelExporter for StlExporter {
+    fn name(&self) -> &str {
+        "STL"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["stl"]
+    }
+
+    fn export(&self, model: &Model, path: &Path, options: &ExportOptions) -> Result<()> {
+        let exporter = if options.ascii {
+            Self::new(StlExportOptions {
+                format: StlFormat::Ascii,
+            })
+        } else {
+            *self
+        };
+        let model = options.prepare_unitless(model)?;
+        exporter.write(&model, BufWriter::new(File::create(path)?))
     }
 }
 
@@ -984,6 +1108,7 @@ fn collect_triangles<A: lib3mf_core::archive::ArchiveReader>(
     if let Some(geo) = geometry {
         match geo {
             lib3mf_core::model::Geometry::Mesh(mesh) => {
+                let mirrored = transform.determinant() < 0.0;
                 for tri in &mesh.triangles {
                     let v1_local = mesh.vertices[tri.v1 as usize];
                     let v2_local = mesh.vertices[tri.v2 as usize];
@@ -996,7 +1121,7 @@ fn collect_triangles<A: lib3mf_core::archive::ArchiveReader>(
                     let v3 = transform
                         .transform_point3(glam::Vec3::new(v3_local.x, v3_local.y, v3_local.z));
 
-                    triangles.push((v1, v2, v3));
+                    triangles.push(oriented(v1, v2, v3, mirrored));
                 }
             }
             lib3mf_core::model::Geometry::Components(comps) => {
@@ -1028,11 +1153,15 @@ mod tests {
 
     // ===== Helper functions =====
 
+    const ASCII: StlExportOptions = StlExportOptions {
+        format: StlFormat::Ascii,
+    };
+
+    /// Triangle corners as (v1x, v1y, v1z, v2x, v2y, v2z, v3x, v3y, v3z).
+    type TriCoords = (f32, f32, f32, f32, f32, f32, f32, f32, f32);
+
     /// Build a minimal binary STL with the given triangles.
-    fn make_binary_stl(
-        header: &[u8; 80],
-        triangles: &[(f32, f32, f32, f32, f32, f32, f32, f32, f32)],
-    ) -> Vec<u8> {
+    fn make_binary_stl(header: &[u8; 80], triangles: &[TriCoords]) -> Vec<u8> {
         // Each element: (v1x, v1y, v1z, v2x, v2y, v2z, v3x, v3y, v3z)
         use byteorder::{LittleEndian, WriteBytesExt};
         let mut buf = Vec::new();
@@ -1105,6 +1234,7 @@ mod tests {
             uuid: None,
             path: None,
             printable: None,
+            metadata: Default::default(),
         });
         model
     }
@@ -1371,7 +1501,9 @@ solid truncated
         );
 
         let mut output = Vec::new();
-        AsciiStlExporter::write(&model, &mut output).expect("write should succeed");
+        StlExporter::new(ASCII)
+            .write(&model, &mut output)
+            .expect("write should succeed");
         let text = String::from_utf8(output).expect("valid UTF-8");
 
         assert!(
@@ -1420,7 +1552,9 @@ solid truncated
         );
 
         let mut output = Vec::new();
-        AsciiStlExporter::write(&model, &mut output).expect("write should succeed");
+        StlExporter::new(ASCII)
+            .write(&model, &mut output)
+            .expect("write should succeed");
         let text = String::from_utf8(output).expect("valid UTF-8");
 
         let normal_line = text
@@ -1450,7 +1584,9 @@ solid truncated
         );
 
         let mut output = Vec::new();
-        AsciiStlExporter::write(&model, &mut output).expect("write should succeed");
+        StlExporter::new(ASCII)
+            .write(&model, &mut output)
+            .expect("write should succeed");
         let text = String::from_utf8(output).expect("valid UTF-8");
 
         let first_line = text.lines().next().expect("should have lines");
@@ -1461,8 +1597,7 @@ solid truncated
 
         let last_line = text
             .lines()
-            .filter(|l| !l.is_empty())
-            .last()
+            .rfind(|l| !l.is_empty())
             .expect("should have lines");
         assert_eq!(
             last_line, "endsolid MyPart",
@@ -1488,7 +1623,9 @@ solid truncated
 
         // Write to ASCII
         let mut buf1 = Vec::new();
-        AsciiStlExporter::write(&model, &mut buf1).expect("first write should succeed");
+        StlExporter::new(ASCII)
+            .write(&model, &mut buf1)
+            .expect("first write should succeed");
 
         // Parse back
         let model2 =
@@ -1496,7 +1633,9 @@ solid truncated
 
         // Write again
         let mut buf2 = Vec::new();
-        AsciiStlExporter::write(&model2, &mut buf2).expect("second write should succeed");
+        StlExporter::new(ASCII)
+            .write(&model2, &mut buf2)
+            .expect("second write should succeed");
 
         // Parse again
         let model3 =
@@ -1539,7 +1678,7 @@ solid truncated
         assert_eq!(t_count2, 2, "should have 2 triangles");
     }
 
-    // ===== Test 15: BinaryStlExporter::write produces correct binary output =====
+    // ===== Test 15: binary StlExporter::write produces correct binary output =====
 
     #[test]
     fn test_write_binary_simple() {
@@ -1553,7 +1692,9 @@ solid truncated
         );
 
         let mut buf = Vec::new();
-        BinaryStlExporter::write(&model, Cursor::new(&mut buf)).expect("write should succeed");
+        StlExporter::default()
+            .write(&model, Cursor::new(&mut buf))
+            .expect("write should succeed");
 
         // Total: 80-byte header + 4-byte count + 1 * 50-byte triangle = 134
         assert_eq!(
@@ -1573,7 +1714,13 @@ solid truncated
         //   bytes 108..120: vertex 2 (3 x f32 = 12 bytes)
         //   bytes 120..132: vertex 3 (3 x f32 = 12 bytes)
         //   bytes 132..134: attribute byte count (u16 = 2 bytes)
-        let mut tri_cursor = Cursor::new(&buf[96..]);
+        let mut tri_cursor = Cursor::new(&buf[84..]);
+
+        // Face normal: +Z for the counter-clockwise XY-plane triangle
+        let normal: Vec<f32> = (0..3)
+            .map(|_| tri_cursor.read_f32::<LittleEndian>().unwrap())
+            .collect();
+        assert_eq!(normal, vec![0.0, 0.0, 1.0], "normal should be +Z");
 
         // Vertex 1: (0,0,0)
         let v1x = tri_cursor.read_f32::<LittleEndian>().unwrap();
@@ -1600,7 +1747,7 @@ solid truncated
         assert!((v3z - 0.0).abs() < 1e-6, "v3.z should be 0.0, got {v3z}");
     }
 
-    // ===== Test 16: BinaryStlExporter::write roundtrip preserves triangle count =====
+    // ===== Test 16: binary StlExporter::write roundtrip preserves triangle count =====
 
     #[test]
     fn test_roundtrip_binary() {
@@ -1618,7 +1765,9 @@ solid truncated
 
         // Write to binary STL
         let mut buf = Vec::new();
-        BinaryStlExporter::write(&model, Cursor::new(&mut buf)).expect("write should succeed");
+        StlExporter::default()
+            .write(&model, Cursor::new(&mut buf))
+            .expect("write should succeed");
 
         // Read back using StlImporter
         let model2 =
@@ -1640,7 +1789,106 @@ solid truncated
         }
     }
 
-    // ===== Test 17: BinaryStlExporter::write combines triangles from multiple build items =====
+    #[test]
+    fn test_import_binary_across_chunks_reports_progress() {
+        use crate::input::Progress;
+        use std::sync::{Arc, Mutex};
+
+        // A strip of triangles sharing edges, spanning several read chunks
+        let count = TRIANGLES_PER_CHUNK * 2 + 5;
+        let triangles: Vec<TriCoords> = (0..count)
+            .map(|i| {
+                let x = i as f32;
+                (x, 0.0, 0.0, x + 1.0, 0.0, 0.0, x, 1.0, 0.0)
+            })
+            .collect();
+        let data = make_binary_stl(&[0u8; 80], &triangles);
+        let path = std::env::temp_dir().join("lib3mf_stl_chunks.stl");
+        std::fs::write(&path, &data).unwrap();
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let sink = reports.clone();
+        let options = ImportOptions {
+            progress: Some(Progress::new(move |done, total| {
+                sink.lock().unwrap().push((done, total))
+            })),
+            ..Default::default()
+        };
+        let model = StlImporter::new().import(&path, &options);
+        std::fs::write(&path, &data[..data.len() - 10]).unwrap();
+        let truncated = StlImporter::read_binary(File::open(&path).unwrap());
+        let _ = std::fs::remove_file(&path);
+
+        let model = model.unwrap();
+        let lib3mf_core::model::Geometry::Mesh(mesh) =
+            &model.resources.get_object(ResourceId(1)).unwrap().geometry
+        else {
+            panic!("expected mesh");
+        };
+        assert_eq!(mesh.triangles.len(), count);
+        assert_eq!(mesh.vertices.len(), 2 * count + 1);
+        assert_eq!(mesh.triangles[count - 1].v3, mesh.vertices.len() as u32 - 1);
+        let len = data.len() as u64;
+        assert_eq!(reports.lock().unwrap().last(), Some(&(len, len)));
+        assert!(truncated.is_err());
+    }
+
+    // ===== Binary roundtrip of generated solids =====
+
+    mod roundtrip_props {
+        use super::*;
+        use lib3mf_core::model::{Geometry, Object, ObjectType};
+        use lib3mf_core::testutil::manifold_mesh;
+        use lib3mf_core::testutil::proptest::prelude::*;
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(32))]
+
+            #[test]
+            fn prop_binary_roundtrip_keeps_solid(mesh in manifold_mesh()) {
+                let (_, volume) = mesh.compute_area_and_volume();
+                let triangles = mesh.triangles.len();
+                let mut model = Model::default();
+                model
+                    .resources
+                    .add_object(Object {
+                        id: ResourceId(1),
+                        object_type: ObjectType::Model,
+                        name: None,
+                        part_number: None,
+                        uuid: None,
+                        pid: None,
+                        pindex: None,
+                        thumbnail: None,
+                        geometry: Geometry::Mesh(mesh),
+                    })
+                    .unwrap();
+                model.build.items.push(lib3mf_core::model::BuildItem {
+                    object_id: ResourceId(1),
+                    uuid: None,
+                    path: None,
+                    part_number: None,
+                    transform: glam::Mat4::IDENTITY,
+                    printable: None,
+                    metadata: Default::default(),
+                });
+
+                let mut buf = Vec::new();
+                StlExporter::default().write(&model, Cursor::new(&mut buf)).unwrap();
+                let model2 = StlImporter::read(Cursor::new(buf)).unwrap();
+                let Geometry::Mesh(mesh2) = &model2.resources.get_object(ResourceId(1)).unwrap().geometry
+                else {
+                    panic!("expected Mesh geometry");
+                };
+
+                prop_assert_eq!(mesh2.triangles.len(), triangles);
+                let (_, volume2) = mesh2.compute_area_and_volume();
+                prop_assert!((volume2 - volume).abs() <= volume.abs() * 1e-3, "{} vs {}", volume2, volume);
+            }
+        }
+    }
+
+    // ===== Test 17: binary StlExporter::write combines triangles from multiple build items =====
 
     #[test]
     fn test_write_binary_multi_object() {
@@ -1740,6 +1988,7 @@ solid truncated
             uuid: None,
             path: None,
             printable: None,
+            metadata: Default::default(),
         });
         model.build.items.push(BuildItem {
             object_id: ResourceId(2),
@@ -1748,10 +1997,13 @@ solid truncated
             uuid: None,
             path: None,
             printable: None,
+            metadata: Default::default(),
         });
 
         let mut buf = Vec::new();
-        BinaryStlExporter::write(&model, Cursor::new(&mut buf)).expect("write should succeed");
+        StlExporter::default()
+            .write(&model, Cursor::new(&mut buf))
+            .expect("write should succeed");
 
         // Total: 80 + 4 + 3 * 50 = 234 bytes
         assert_eq!(
@@ -1812,4 +2064,79 @@ endsolid autotest
             panic!("expected Mesh");
         }
     }
+
+    // ===== Test 20: StlExporter dispatches on the configured format =====
+
+    #[test]
+    fn test_stl_exporter_format_option() {
+        let ascii = "solid t\nfacet normal 0 0 1\nouter loop\nvertex 0 0 0\nvertex 1 0 0\nvertex 0 1 0\nendloop\nendfacet\nendsolid t\n";
+        let model = StlImporter::read_ascii(ascii.as_bytes()).unwrap();
+
+        let mut binary = Vec::new();
+        StlExporter::default().write(&model, &mut binary).unwrap();
+        assert_eq!(binary.len(), 84 + 50);
+
+        let mut text = Vec::new();
+        StlExporter::new(StlExportOptions {
+            format: StlFormat::Ascii,
+        })
+        .write(&model, &mut text)
+        .unwrap();
+        assert!(String::from_utf8(text).unwrap().contains("facet normal"));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_exporters_match_stl_exporter() {
+        let model = make_simple_model(
+            vec![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0)],
+            vec![(0, 1, 2)],
+            Some("shim"),
+        );
+        let mut expected = Vec::new();
+        let mut actual = Vec::new();
+        StlExporter::default().write(&model, &mut expected).unwrap();
+        BinaryStlExporter::write(&model, &mut actual).unwrap();
+        assert_eq!(actual, expected);
+
+        expected.clear();
+        actual.clear();
+        StlExporter::new(ASCII)
+            .write(&model, &mut expected)
+            .unwrap();
+        AsciiStlExporter::write(&model, &mut actual).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    // ===== Test 21: mirrored build items keep outward normals =====
+
+    #[test]
+    fn test_write_ascii_mirrored_item_keeps_winding() {
+        let mut model = make_simple_model(
+            vec![(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0)],
+            vec![(0, 1, 2)],
+            None,
+        );
+        model.build.items[0].transform = glam::Mat4::from_scale(glam::Vec3::new(-1.0, 1.0, 1.0));
+
+        let mut output = Vec::new();
+        StlExporter::new(ASCII)
+            .write(&model, &mut output)
+            .expect("write should succeed");
+        let text = String::from_utf8(output).expect("valid UTF-8");
+
+        // Mirroring in X maps the +Z normal to itself; without reversing the winding
+        // the face would point down
+        let normal_line = text
+            .lines()
+            .find(|l| l.trim().starts_with("facet normal"))
+            .expect("should have facet normal line");
+        let nz: f64 = normal_line
+            .split_whitespace()
+            .nth(4)
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(nz > 0.5, "normal should still point +Z, got {normal_line}");
+    }
 }
@@ -0,0 +1,994 @@
+//! Whole-model geometry adjustments used by import and export options.
+//!
+//! These functions back [`ImportOptions`](crate::registry::ImportOptions) and
+//! [`ExportOptions`](crate::registry::ExportOptions), and can also be called directly on any
+//! [`Model`]:
+//!
+//! - [`scale_model`], [`convert_unit`] and [`convert_up_axis`] change the coordinate system
+//! - [`weld_vertices`] merges nearly coincident vertices
+//! - [`apply_build_transforms`] bakes build item placements into vertex positions
+//! - [`merge_objects`] and [`split_objects`] change how geometry is grouped into objects
+//!
+//! Only triangle meshes and component/build transforms are rewritten. Other geometry
+//! (slices, volumes, boolean shapes, displacement meshes) is left untouched.
+//!
+//! [`Model`]: lib3mf_core::model::Model
+
+use glam::{Mat4, Vec3, Vec4};
+use lib3mf_core::error::{Lib3mfError, Result};
+use lib3mf_core::model::repair::{MeshRepair, RepairOptions};
+use lib3mf_core::model::resources::{ResourceCollection, ResourceId};
+use lib3mf_core::model::{
+    BuildItem, Component, Components, Geometry, Mesh, Model, Object, ObjectType, ResolvedMesh,
+    Triangle, TriangleSet, Unit, Vertex,
+};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+/// Component nesting deeper than this is reported as a cycle by [`merge_objects`].
+const MAX_DEPTH: usize = 16;
+
+/// The axis pointing up in a model's coordinate system.
+///
+/// 3MF is always Z-up. STL and OBJ carry no convention, and files from many modelling and
+/// game tools are Y-up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UpAxis {
+    /// Y points up.
+    Y,
+    /// Z points up, as in 3MF (default).
+    #[default]
+    Z,
+}
+
+impl FromStr for UpAxis {
+    type Err = Lib3mfError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "y" => Ok(UpAxis::Y),
+            "z" => Ok(UpAxis::Z),
+            _ => Err(Lib3mfError::Validation(format!(
+                "Invalid up axis '{}'. Expected y or z",
+                s
+            ))),
+        }
+    }
+}
+
+/// Whether a coordinate system's axes are right- or left-handed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Handedness {
+    /// Right-handed, as in 3MF, OBJ and glTF (default).
+    #[default]
+    Right,
+    /// Left-handed, as in Unity and Unreal Engine.
+    Left,
+}
+
+impl FromStr for Handedness {
+    type Err = Lib3mfError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "right" | "rh" => Ok(Handedness::Right),
+            "left" | "lh" => Ok(Handedness::Left),
+            _ => Err(Lib3mfError::Validation(format!(
+                "Invalid handedness '{}'. Expected right or left",
+                s
+            ))),
+        }
+    }
+}
+
+/// An axis convention: which axis points up, and the handedness of the axes.
+///
+/// 3MF is Z-up and right-handed. OBJ files are conventionally Y-up and right-handed, as is
+/// glTF by specification; importing those as-is leaves models lying on their side.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CoordinateSystem {
+    /// The axis pointing up.
+    pub up: UpAxis,
+    /// Handedness of the axes.
+    pub handedness: Handedness,
+}
+
+impl CoordinateSystem {
+    /// Z-up, right-handed: 3MF, Blender, most CAD and slicer software.
+    pub const Z_UP_RIGHT: Self = Self::new(UpAxis::Z, Handedness::Right);
+    /// Y-up, right-handed: conventional OBJ, glTF, Maya.
+    pub const Y_UP_RIGHT: Self = Self::new(UpAxis::Y, Handedness::Right);
+    /// Y-up, left-handed: Unity, DirectX.
+    pub const Y_UP_LEFT: Self = Self::new(UpAxis::Y, Handedness::Left);
+    /// Z-up, left-handed: Unreal Engine.
+    pub const Z_UP_LEFT: Self = Self::new(UpAxis::Z, Handedness::Left);
+
+    /// Creates a coordinate system.
+    pub const fn new(up: UpAxis, handedness: Handedness) -> Self {
+        Self { up, handedness }
+    }
+
+    /// Returns the matrix mapping coordinates in this system to `target`.
+    ///
+    /// The matrix is a signed axis permutation. Its determinant is negative exactly when the
+    /// handedness changes, in which case [`transform_model`] also reverses triangle winding
+    /// so that normals derived from it keep pointing outward.
+    pub fn matrix_to(self, target: Self) -> Mat4 {
+        target.to_z_up_right().inverse() * self.to_z_up_right()
+    }
+
+    fn to_z_up_right(self) -> Mat4 {
+        match (self.up, self.handedness) {
+            (UpAxis::Z, Handedness::Right) => Mat4::IDENTITY,
+            // (x, y, z) -> (x, -z, y)
+            (UpAxis::Y, Handedness::Right) => Mat4::from_cols(Vec4::X, Vec4::Z, -Vec4::Y, Vec4::W),
+            // (x, y, z) -> (x, z, y): flip z to reach right-handed Y-up, then rotate.
+            (UpAxis::Y, Handedness::Left) => Mat4::from_cols(Vec4::X, Vec4::Z, Vec4::Y, Vec4::W),
+            // (x, y, z) -> (x, -y, z)
+            (UpAxis::Z, Handedness::Left) => Mat4::from_cols(Vec4::X, -Vec4::Y, Vec4::Z, Vec4::W),
+        }
+    }
+}
+
+/// How geometry is grouped into objects on export.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ObjectMode {
+    /// Leave objects as they are (default).
+    #[default]
+    Keep,
+    /// Combine everything placed by the build into one object (see [`merge_objects`]).
+    Merge,
+    /// Give each connected piece of a mesh its own object (see [`split_objects`]).
+    Split,
+}
+
+/// Applies the linear map `matrix` to the whole model.
+///
+/// Mesh vertices are transformed directly, while component and build item transforms are
+/// conjugated (`matrix * t * matrix⁻¹`) so that placements stay consistent with the
+/// transformed meshes. Mirroring maps reverse triangle winding so normals keep pointing
+/// outward, and beam radii scale with the map's average scale factor.
+///
+/// `matrix` must be invertible and should not contain a translation.
+pub fn transform_model(model: &mut Model, matrix: Mat4) {
+    let inverse = matrix.inverse();
+    for object in model.resources.iter_objects_mut() {
+        match &mut object.geometry {
+            Geometry::Mesh(mesh) => transform_mesh(mesh, matrix),
+            Geometry::Components(components) => {
+                for component in &mut components.components {
+                    component.transform = matrix * component.transform * inverse;
+                }
+            }
+            _ => {}
+        }
+    }
+    for item in &mut model.build.items {
+        item.transform = matrix * item.transform * inverse;
+    }
+}
+
+/// Scales all geometry uniformly by `factor`.
+///
+/// # Errors
+///
+/// Returns `Lib3mfError::Validation` if `factor` is not a positive, finite number.
+pub fn scale_model(model: &mut Model, factor: f32) -> Result<()> {
+    if !(factor.is_finite() && factor > 0.0) {
+        return Err(Lib3mfError::Validation(format!(
+            "Scale factor must be a positive number, got {}",
+            factor
+        )));
+    }
+    if factor != 1.0 {
+        transform_model(model, Mat4::from_scale(Vec3::splat(factor)));
+    }
+    Ok(())
+}
+
+/// Rescales coordinates from the model's unit to `unit` and sets `model.unit`.
+pub fn convert_unit(model: &mut Model, unit: Unit) {
+    let factor = (model.unit.scale_factor() / unit.scale_factor()) as f32;
+    if factor != 1.0 {
+        transform_model(model, Mat4::from_scale(Vec3::splat(factor)));
+    }
+    model.unit = unit;
+}
+
+/// Rotates the model from the `from` up-axis convention to `to`, both right-handed.
+///
+/// Y-up to Z-up maps `(x, y, z)` to `(x, -z, y)`; Z-up to Y-up is the inverse.
+pub fn convert_up_axis(model: &mut Model, from: UpAxis, to: UpAxis) {
+    convert_coordinate_system(
+        model,
+        CoordinateSystem::new(from, Handedness::Right),
+        CoordinateSystem::new(to, Handedness::Right),
+    );
+}
+
+/// Re-expresses the model, given in `from`, in the `to` coordinate system.
+///
+/// Changing handedness mirrors one axis, so triangle winding is reversed to keep faces
+/// pointing outward.
+pub fn convert_coordinate_system(model: &mut Model, from: CoordinateSystem, to: CoordinateSystem) {
+    if from != to {
+        transform_model(model, from.matrix_to(to));
+    }
+}
+
+/// Merges vertices closer than `epsilon` in every mesh, dropping triangles that collapse.
+///
+/// Meshes with a beam lattice are skipped, since beams reference vertices that triangles
+/// may not. Returns the number of vertices removed.
+pub fn weld_vertices(model: &mut Model, epsilon: f32) -> usize {
+    if epsilon <= 0.0 {
+        return 0;
+    }
+    let options = RepairOptions {
+        stitch_epsilon: epsilon,
+        remove_degenerate: true,
+        remove_duplicate_faces: false,
+        harmonize_orientations: false,
+        remove_islands: false,
+        fill_holes: false,
+        unit: model.unit,
+        ..Default::default()
+    };
+    let mut removed = 0;
+    for object in model.resources.iter_objects_mut() {
+        if let Geometry::Mesh(mesh) = &mut object.geometry
+            && mesh.beam_lattice.is_none()
+        {
+            removed += mesh.repair(options).vertices_removed;
+        }
+    }
+    removed
+}
+
+/// Stores normals computed with [`Mesh::compute_normals`] on every mesh without valid
+/// stored ones. Returns the number of meshes updated.
+pub fn update_normals(model: &mut Model, crease_angle: f32) -> usize {
+    let mut updated = 0;
+    for object in model.resources.iter_objects_mut() {
+        if let Geometry::Mesh(mesh) = &mut object.geometry
+            && mesh.stored_normals().is_none()
+            && !mesh.triangles.is_empty()
+        {
+            mesh.update_normals(crease_angle);
+            updated += 1;
+        }
+    }
+    updated
+}
+
+/// Bakes build item transforms into mesh vertices and resets the items to identity.
+///
+/// A mesh placed by a single build item is rewritten in place; a mesh that is placed
+/// several times, or also used as a component, is copied to a new object first. Items
+/// referencing component assemblies or other model parts keep their transforms.
+pub fn apply_build_transforms(model: &mut Model) -> Result<()> {
+    let uses = object_uses(model);
+    for index in 0..model.build.items.len() {
+        let item = &model.build.items[index];
+        if item.path.is_some() || item.transform == Mat4::IDENTITY {
+            continue;
+        }
+        let Some(object) = model.resources.get_object(item.object_id) else {
+            continue;
+        };
+        let Geometry::Mesh(mesh) = &object.geometry else {
+            continue;
+        };
+
+        let mut mesh = mesh.clone();
+        transform_mesh(&mut mesh, item.transform);
+        if uses.get(&object.id) == Some(&1) {
+            let id = object.id;
+            let mut object = model
+                .resources
+                .remove_object(id)
+                .ok_or(Lib3mfError::ResourceNotFound(id.0))?;
+            object.geometry = Geometry::Mesh(mesh);
+            model.resources.add_object(object)?;
+        } else {
+            let copy = Object {
+                id: next_free_id(&model.resources),
+                uuid: None,
+                geometry: Geometry::Mesh(mesh),
+                ..object.clone()
+            };
+            model.build.items[index].object_id = copy.id;
+            model.resources.add_object(copy)?;
+        }
+        model.build.items[index].transform = Mat4::IDENTITY;
+    }
+    Ok(())
+}
+
+/// Combines every mesh placed by the build into a single object with one build item.
+///
+/// Component assemblies are flattened and all transforms are baked into the vertices.
+/// Object-level materials are copied onto the triangles so that colors survive. Build
+/// items referencing other model parts, or geometry other than meshes and components, are
+/// kept as they are. Objects that are no longer referenced are removed. Beam lattices are
+/// not carried over.
+///
+/// # Errors
+///
+/// Returns `Lib3mfError::ResourceNotFound` for dangling references, and
+/// `Lib3mfError::InvalidStructure` for components in other model parts or nesting
+/// deeper than 16 levels.
+pub fn merge_objects(model: &mut Model) -> Result<()> {
+    let mut merged = Mesh::new();
+    let mut visited = HashSet::new();
+    let mut kept = Vec::new();
+    let mut first_item = None;
+
+    for item in std::mem::take(&mut model.build.items) {
+        let mergeable = item.path.is_none()
+            && model
+                .resources
+                .get_object(item.object_id)
+                .is_some_and(|o| matches!(o.geometry, Geometry::Mesh(_) | Geometry::Components(_)));
+        if !mergeable {
+            kept.push(item);
+            continue;
+        }
+        append_object(
+            &model.resources,
+            item.object_id,
+            item.transform,
+            0,
+            &mut merged,
+            &mut visited,
+        )?;
+        first_item.get_or_insert(item);
+    }
+
+    let Some(first_item) = first_item else {
+        model.build.items = kept;
+        return Ok(());
+    };
+
+    // Drop merged objects that nothing else still references.
+    let mut referenced: HashSet<ResourceId> = kept.iter().map(|i| i.object_id).collect();
+    for object in model.resources.iter_objects() {
+        if let Geometry::Components(components) = &object.geometry
+            && !visited.contains(&object.id)
+        {
+            referenced.extend(components.components.iter().map(|c| c.object_id));
+        }
+    }
+    for id in visited.difference(&referenced) {
+        model.resources.remove_object(*id);
+    }
+
+    let id = next_free_id(&model.resources);
+    model.resources.add_object(Object {
+        id,
+        object_type: ObjectType::Model,
+        name: None,
+        part_number: None,
+        uuid: None,
+        pid: None,
+        pindex: None,
+        thumbnail: None,
+        geometry: Geometry::Mesh(merged),
+    })?;
+    kept.insert(
+        0,
+        BuildItem {
+            object_id: id,
+            uuid: None,
+            transform: Mat4::IDENTITY,
+            ..first_item
+        },
+    );
+    model.build.items = kept;
+    Ok(())
+}
+
+/// Gives each connected piece of a mesh its own object and build item.
+///
+/// Triangles are connected when they share a vertex. Each piece keeps the source object's
+/// type, materials and name (suffixed with ` (n)`), and each build item is replaced by one
+/// item per piece with the same transform. Meshes used as components or carrying a beam
+/// lattice are left whole. Returns the number of objects created.
+pub fn split_objects(model: &mut Model) -> Result<usize> {
+    let component_refs: HashSet<ResourceId> = model
+        .resources
+        .iter_objects()
+        .filter_map(|o| match &o.geometry {
+            Geometry::Components(c) => Some(c.components.iter().map(|c| c.object_id)),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+
+    let mut pieces: HashMap<ResourceId, Vec<ResourceId>> = HashMap::new();
+    let mut created = 0;
+    let mut items = Vec::with_capacity(model.build.items.len());
+
+    for item in std::mem::take(&mut model.build.items) {
+        let id = item.object_id;
+        if item.path.is_some() || component_refs.contains(&id) {
+            items.push(item);
+            continue;
+        }
+        let parts = match pieces.entry(id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => match split_object(&mut model.resources, id)? {
+                Some(parts) => {
+                    created += parts.len();
+                    entry.insert(parts)
+                }
+                None => {
+                    items.push(item);
+                    continue;
+                }
+            },
+        };
+        for part in parts.iter() {
+            items.push(BuildItem {
+                object_id: *part,
+                uuid: None,
+                ..item.clone()
+            });
+        }
+    }
+
+    // Split objects are only placed by build items, which now reference the pieces.
+    for id in pieces.keys() {
+        model.resources.remove_object(*id);
+    }
+    model.build.items = items;
+    Ok(created)
+}
+
+/// Builds a single-part model from meshes resolved across a multi-part package.
+///
+/// Each resolved mesh becomes an object placed by its accumulated transform. Meshes from
+/// parts with a different unit are rescaled to `unit`.
+pub fn model_from_resolved(meshes: Vec<ResolvedMesh>, unit: Unit) -> Result<Model> {
+    model_from_resolved_items(meshes.into_iter().map(|m| (None, vec![m])).collect(), unit)
+}
+
+/// Like [`model_from_resolved`], keeping together the meshes resolved for each build item
+/// (see [`PartResolver::resolve_item`]): an item with several meshes becomes an object,
+/// with the given name, made of components placing them.
+///
+/// [`PartResolver::resolve_item`]: lib3mf_core::model::resolver::PartResolver::resolve_item
+pub fn model_from_resolved_items(
+    items: Vec<(Option<String>, Vec<ResolvedMesh>)>,
+    unit: Unit,
+) -> Result<Model> {
+    let mut model = Model {
+        unit,
+        ..Default::default()
+    };
+    let mut next_id = 1;
+    for (name, meshes) in items {
+        let mut placed = Vec::with_capacity(meshes.len());
+        for resolved in meshes {
+            let mut mesh = resolved.mesh;
+            let factor = (resolved.unit.scale_factor() / unit.scale_factor()) as f32;
+            if factor != 1.0 {
+                transform_mesh(&mut mesh, Mat4::from_scale(Vec3::splat(factor)));
+            }
+            let id = ResourceId(next_id);
+            next_id += 1;
+            model.resources.add_object(Object {
+                id,
+                object_type: resolved.object_type,
+                name: resolved.name,
+                part_number: None,
+                uuid: None,
+                pid: None,
+                pindex: None,
+                thumbnail: None,
+                geometry: Geometry::Mesh(mesh),
+            })?;
+            placed.push((id, resolved.transform));
+        }
+        let (object_id, transform) = match placed.as_slice() {
+            [] => continue,
+            [single] => *single,
+            _ => {
+                let id = ResourceId(next_id);
+                next_id += 1;
+                let components = placed
+                    .iter()
+                    .map(|&(object_id, transform)| Component {
+                        object_id,
+                        path: None,
+                        uuid: None,
+                        transform,
+                    })
+                    .collect();
+                model.resources.add_object(Object {
+                    id,
+                    object_type: ObjectType::Model,
+                    name,
+                    part_number: None,
+                    uuid: None,
+                    pid: None,
+                    pindex: None,
+                    thumbnail: None,
+                    geometry: Geometry::Components(Components { components }),
+                })?;
+                (id, Mat4::IDENTITY)
+            }
+        };
+        model.build.items.push(BuildItem {
+            object_id,
+            uuid: None,
+            path: None,
+            part_number: None,
+            transform,
+            printable: None,
+            metadata: Default::default(),
+        });
+    }
+    Ok(model)
+}
+
+/// Applies an affine transform to a mesh's vertices, fixing winding and beam radii.
+fn transform_mesh(mesh: &mut Mesh, transform: Mat4) {
+    for vertex in &mut mesh.vertices {
+        *vertex = transform.transform_point3(Vec3::from(*vertex)).into();
+    }
+    let determinant = transform.determinant();
+    if determinant < 0.0 {
+        mesh.triangles.iter_mut().for_each(flip_winding);
+    }
+    if let Some(normals) = &mut mesh.normals {
+        normals.transform(transform);
+    }
+    if let Some(lattice) = &mut mesh.beam_lattice {
+        let scale = determinant.abs().cbrt();
+        lattice.radius = lattice.radius.map(|r| r * scale);
+        lattice.min_length *= scale;
+        for beam in &mut lattice.beams {
+            beam.r1 *= scale;
+            beam.r2 *= scale;
+        }
+    }
+}
+
+fn flip_winding(triangle: &mut Triangle) {
+    std::mem::swap(&mut triangle.v2, &mut triangle.v3);
+    std::mem::swap(&mut triangle.p2, &mut triangle.p3);
+}
+
+/// Counts build item and component references per object.
+fn object_uses(model: &Model) -> HashMap<ResourceId, usize> {
+    let mut uses = HashMap::new();
+    for item in model.build.items.iter().filter(|i| i.path.is_none()) {
+        *uses.entry(item.object_id).or_insert(0) += 1;
+    }
+    for object in model.resources.iter_objects() {
+        if let Geometry::Components(components) = &object.geometry {
+            for component in components.components.iter().filter(|c| c.path.is_none()) {
+                *uses.entry(component.object_id).or_insert(0) += 1;
+            }
+        }
+    }
+    uses
+}
+
+/// Returns the lowest ID above every object ID that no resource uses.
+fn next_free_id(resources: &ResourceCollection) -> ResourceId {
+    let mut id = resources.iter_objects().map(|o| o.id.0).max().unwrap_or(0) + 1;
+    while resources.exists(ResourceId(id)) {
+        id += 1;
+    }
+    ResourceId(id)
+}
+
+/// Appends an object's triangles to `target`, flattening local components.
+fn append_object(
+    resources: &ResourceCollection,
+    id: ResourceId,
+    transform: Mat4,
+    depth: usize,
+    target: &mut Mesh,
+    visited: &mut HashSet<ResourceId>,
+) -> Result<()> {
+    if depth > MAX_DEPTH {
+        return Err(Lib3mfError::InvalidStructure(format!(
+            "Component nesting exceeds {} levels at object {} (possible cycle)",
+            MAX_DEPTH, id.0
+        )));
+    }
+    let object = resources
+        .get_object(id)
+        .ok_or(Lib3mfError::ResourceNotFound(id.0))?;
+    visited.insert(id);
+
+    match &object.geometry {
+        Geometry::Mesh(mesh) => {
+            let offset = target.vertices.len() as u32;
+            let triangle_offset = target.triangles.len() as u32;
+            let flip = transform.determinant() < 0.0;
+            target.vertices.extend(
+                mesh.vertices
+                    .iter()
+                    .map(|v| Vertex::from(transform.transform_point3(Vec3::from(*v)))),
+            );
+            target.triangles.extend(mesh.triangles.iter().map(|tri| {
+                let mut tri = *tri;
+                tri.v1 += offset;
+                tri.v2 += offset;
+                tri.v3 += offset;
+                // Triangles inherit the object's default property.
+                if tri.pid.is_none()
+                    && let Some(pid) = object.pid
+                {
+                    tri.pid = Some(pid.0);
+                    if tri.p1.is_none() {
+                        let index = object.pindex.unwrap_or(0);
+                        (tri.p1, tri.p2, tri.p3) = (Some(index), Some(index), Some(index));
+                    }
+                }
+                if flip {
+                    flip_winding(&mut tri);
+                }
+                tri
+            }));
+            // Sets with the same identifier in different objects are combined.
+            for set in &mesh.triangle_sets {
+                let index = match target
+                    .triangle_sets
+                    .iter()
+                    .position(|s| s.identifier == set.identifier)
+                {
+                    Some(index) => index,
+                    None => {
+                        target
+                            .triangle_sets
+                            .push(TriangleSet::new(&set.name, &set.identifier));
+                        target.triangle_sets.len() - 1
+                    }
+                };
+                target.triangle_sets[index]
+                    .triangles
+                    .extend(set.triangles.iter().map(|t| t + triangle_offset));
+            }
+            for (name, values) in mesh.triangle_attributes.channels() {
+                for (t, value) in values {
+                    target
+                        .triangle_attributes
+                        .set(name, t + triangle_offset, value.as_str());
+                }
+            }
+        }
+        Geometry::Components(components) => {
+            for component in &components.components {
+                if component.path.is_some() {
+                    return Err(Lib3mfError::InvalidStructure(format!(
+                        "Object {} references a component in another model part; resolve \
+                         the package before merging",
+                        id.0
+                    )));
+                }
+                append_object(
+                    resources,
+                    component.object_id,
+                    transform * component.transform,
+                    depth + 1,
+                    target,
+                    visited,
+                )?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Splits a mesh object into new objects, one per connected piece.
+///
+/// Returns `None` if the object is not a splittable mesh or has only one piece.
+fn split_object(
+    resources: &mut ResourceCollection,
+    id: ResourceId,
+) -> Result<Option<Vec<ResourceId>>> {
+    let Some(object) = resources.get_object(id) else {
+        return Ok(None);
+    };
+    let Geometry::Mesh(mesh) = &object.geometry else {
+        return Ok(None);
+    };
+    if mesh.beam_lattice.is_some() {
+        return Ok(None);
+    }
+
+    // Union-find over vertices, joined along triangle edges.
+    let mut parent: Vec<usize> = (0..mesh.vertices.len()).collect();
+    fn find(parent: &mut [usize], mut v: usize) -> usize {
+        while parent[v] != v {
+            parent[v] = parent[parent[v]];
+            v = parent[v];
+        }
+        v
+    }
+    let in_range = |tri: &Triangle| {
+        [tri.v1, tri.v2, tri.v3]
+            .iter()
+            .all(|&v| (v as usize) < parent.len())
+    };
+    let triangles: Vec<(usize, Triangle)> = mesh
+        .triangles
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|(_, tri)| in_range(tri))
+        .collect();
+    for (_, tri) in &triangles {
+        let a = find(&mut parent, tri.v1 as usize);
+        for v in [tri.v2, tri.v3] {
+            let b = find(&mut parent, v as usize);
+            parent[b] = a;
+        }
+    }
+
+    // Group triangles by root, in order of first appearance.
+    let mut piece_of_root: HashMap<usize, usize> = HashMap::new();
+    let mut pieces: Vec<(Mesh, HashMap<u32, u32>)> = Vec::new();
+    // Piece and index within it of each source triangle, for carrying triangle sets over.
+    let mut placement = vec![None; mesh.triangles.len()];
+    for &(source_index, tri) in &triangles {
+        let root = find(&mut parent, tri.v1 as usize);
+        let index = *piece_of_root.entry(root).or_insert_with(|| {
+            pieces.push((Mesh::new(), HashMap::new()));
+            pieces.len() - 1
+        });
+        let (piece, remap) = &mut pieces[index];
+        let mut local = |v: u32| {
+            *remap.entry(v).or_insert_with(|| {
+                piece.vertices.push(mesh.vertices[v as usize]);
+                piece.vertices.len() as u32 - 1
+            })
+        };
+        let (v1, v2, v3) = (local(tri.v1), local(tri.v2), local(tri.v3));
+        placement[source_index] = Some((index, piece.triangles.len() as u32));
+        piece.triangles.push(Triangle { v1, v2, v3, ..tri });
+    }
+    if pieces.len() < 2 {
+        return Ok(None);
+    }
+    for set in &mesh.triangle_sets {
+        let mut parts: Vec<TriangleSet> = (0..pieces.len())
+            .map(|_| TriangleSet::new(&set.name, &set.identifier))
+            .collect();
+        for &t in &set.triangles {
+            if let Some(Some((piece, local))) = placement.get(t as usize) {
+                parts[*piece].triangles.push(*local);
+            }
+        }
+        for ((piece, _), part) in pieces.iter_mut().zip(parts) {
+            if !part.triangles.is_empty() {
+                piece.triangle_sets.push(part);
+            }
+        }
+    }
+    for (name, values) in mesh.triangle_attributes.channels() {
+        for (&t, value) in values {
+            if let Some(Some((piece, local))) = placement.get(t as usize) {
+                pieces[*piece]
+                    .0
+                    .triangle_attributes
+                    .set(name, *local, value.as_str());
+            }
+        }
+    }
+
+    let source = object.clone();
+    let mut ids = Vec::with_capacity(pieces.len());
+    for (n, (piece, _)) in pieces.into_iter().enumerate() {
+        let part = Object {
+            id: next_free_id(resources),
+            name: source
+                .name
+                .as_ref()
+                .map(|name| format!("{} ({})", name, n + 1)),
+            uuid: None,
+            geometry: Geometry::Mesh(piece),
+            ..source.clone()
+        };
+        ids.push(part.id);
+        resources.add_object(part)?;
+    }
+    Ok(Some(ids))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two disjoint unit triangles, placed by one build item at `transform`.
+    fn two_triangles(transform: Mat4) -> Model {
+        let mut mesh = Mesh::new();
+        for offset in [0.0, 10.0] {
+            let a = mesh.add_vertex(offset, 0.0, 0.0);
+            let b = mesh.add_vertex(offset + 1.0, 0.0, 0.0);
+            let c = mesh.add_vertex(offset, 1.0, 0.0);
+            mesh.add_triangle(a, b, c);
+        }
+        let mut model = Model::default();
+        model
+            .resources
+            .add_object(Object {
+                id: ResourceId(1),
+                object_type: ObjectType::Model,
+                name: Some("pair".to_string()),
+                part_number: None,
+                uuid: None,
+                pid: None,
+                pindex: None,
+                thumbnail: None,
+                geometry: Geometry::Mesh(mesh),
+            })
+            .unwrap();
+        model.build.items.push(BuildItem {
+            object_id: ResourceId(1),
+            uuid: None,
+            path: None,
+            part_number: None,
+            transform,
+            printable: None,
+            metadata: Default::default(),
+        });
+        model
+    }
+
+    fn mesh_of(model: &Model, id: ResourceId) -> &Mesh {
+        match &model.resources.get_object(id).unwrap().geometry {
+            Geometry::Mesh(mesh) => mesh,
+            _ => panic!("expected a mesh"),
+        }
+    }
+
+    #[test]
+    fn test_unit_axis_and_scale() {
+        let mut model = two_triangles(Mat4::from_translation(Vec3::new(0.0, 5.0, 0.0)));
+        convert_up_axis(&mut model, UpAxis::Y, UpAxis::Z);
+        let mesh = mesh_of(&model, ResourceId(1));
+        assert_eq!(Vec3::from(mesh.vertices[2]), Vec3::new(0.0, 0.0, 1.0));
+        // The item's +Y offset is now +Z.
+        let t = model.build.items[0].transform.w_axis;
+        assert_eq!((t.x, t.y, t.z), (0.0, 0.0, 5.0));
+
+        convert_unit(&mut model, Unit::Centimeter);
+        assert_eq!(model.unit, Unit::Centimeter);
+        assert!((mesh_of(&model, ResourceId(1)).vertices[1].x - 0.1).abs() < 1e-6);
+
+        assert!(scale_model(&mut model, 0.0).is_err());
+        scale_model(&mut model, 10.0).unwrap();
+        assert!((mesh_of(&model, ResourceId(1)).vertices[1].x - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_coordinate_systems() {
+        use CoordinateSystem as C;
+        let all = [C::Z_UP_RIGHT, C::Y_UP_RIGHT, C::Y_UP_LEFT, C::Z_UP_LEFT];
+        for from in all {
+            for to in all {
+                let m = from.matrix_to(to);
+                assert_eq!(
+                    m.determinant() < 0.0,
+                    from.handedness != to.handedness,
+                    "{from:?} -> {to:?}"
+                );
+                assert_eq!(to.matrix_to(from) * m, Mat4::IDENTITY);
+            }
+            // Up always maps to up.
+            let up = match from.up {
+                UpAxis::Y => Vec3::Y,
+                UpAxis::Z => Vec3::Z,
+            };
+            assert_eq!(from.matrix_to(C::Z_UP_RIGHT).transform_vector3(up), Vec3::Z);
+        }
+
+        let mut model = two_triangles(Mat4::IDENTITY);
+        convert_coordinate_system(&mut model, C::Y_UP_LEFT, C::Z_UP_RIGHT);
+        let mesh = mesh_of(&model, ResourceId(1));
+        assert_eq!(Vec3::from(mesh.vertices[2]), Vec3::Z);
+        let tri = mesh.triangles[0];
+        assert_eq!((tri.v1, tri.v2, tri.v3), (0, 2, 1));
+    }
+
+    #[test]
+    fn test_mirroring_flips_winding() {
+        let mut model = two_triangles(Mat4::IDENTITY);
+        transform_model(&mut model, Mat4::from_scale(Vec3::new(-1.0, 1.0, 1.0)));
+        let tri = mesh_of(&model, ResourceId(1)).triangles[0];
+        assert_eq!((tri.v1, tri.v2, tri.v3), (0, 2, 1));
+    }
+
+    #[test]
+    fn test_apply_build_transforms() {
+        let mut model = two_triangles(Mat4::from_translation(Vec3::new(0.0, 0.0, 2.0)));
+        apply_build_transforms(&mut model).unwrap();
+        assert_eq!(model.build.items[0].transform, Mat4::IDENTITY);
+        assert_eq!(mesh_of(&model, ResourceId(1)).vertices[0].z, 2.0);
+    }
+
+    #[test]
+    fn test_split_then_merge() {
+        let mut model = two_triangles(Mat4::from_translation(Vec3::X));
+        assert_eq!(split_objects(&mut model).unwrap(), 2);
+        assert_eq!(model.build.items.len(), 2);
+        assert!(model.resources.get_object(ResourceId(1)).is_none());
+        let names: Vec<_> = model
+            .build
+            .items
+            .iter()
+            .map(|i| {
+                model
+                    .resources
+                    .get_object(i.object_id)
+                    .unwrap()
+                    .name
+                    .clone()
+            })
+            .collect();
+        assert_eq!(
+            names,
+            [Some("pair (1)".to_string()), Some("pair (2)".to_string())]
+        );
+
+        merge_objects(&mut model).unwrap();
+        assert_eq!(model.build.items.len(), 1);
+        assert_eq!(model.resources.iter_objects().count(), 1);
+        let merged = mesh_of(&model, model.build.items[0].object_id);
+        assert_eq!(merged.triangles.len(), 2);
+        // The item translation was baked in.
+        assert_eq!(merged.vertices[0].x, 1.0);
+    }
+
+    #[test]
+    fn test_split_and_merge_keep_triangle_sets() {
+        let mut model = two_triangles(Mat4::IDENTITY);
+        if let Geometry::Mesh(mesh) =
+            &mut model.resources.iter_objects_mut().next().unwrap().geometry
+        {
+            let mut seam = TriangleSet::new("Seam", "seam");
+            seam.triangles = vec![1];
+            mesh.triangle_sets.push(seam);
+        }
+
+        split_objects(&mut model).unwrap();
+        let sets: Vec<usize> = model
+            .build
+            .items
+            .iter()
+            .map(|i| mesh_of(&model, i.object_id).triangle_sets.len())
+            .collect();
+        assert_eq!(sets, [0, 1]);
+        let second = mesh_of(&model, model.build.items[1].object_id);
+        assert_eq!(second.triangle_sets[0].triangles, [0]);
+
+        merge_objects(&mut model).unwrap();
+        let merged = mesh_of(&model, model.build.items[0].object_id);
+        assert_eq!(merged.triangle_set("seam").unwrap().triangles, [1]);
+    }
+
+    #[test]
+    fn test_weld_vertices() {
+        let mut model = two_triangles(Mat4::IDENTITY);
+        let Geometry::Mesh(mesh) = &mut model.resources.iter_objects_mut().next().unwrap().geometry
+        else {
+            unreachable!()
+        };
+        // A third triangle sharing an edge through nearly coincident copies.
+        let a = mesh.add_vertex(1.0 + 1e-6, 0.0, 0.0);
+        let b = mesh.add_vertex(0.0, 1.0 - 1e-6, 0.0);
+        let c = mesh.add_vertex(1.0, 1.0, 0.0);
+        mesh.add_triangle(a, c, b);
+
+        assert_eq!(weld_vertices(&mut model, 1e-4), 2);
+        assert_eq!(mesh_of(&model, ResourceId(1)).vertices.len(), 7);
+    }
+}
@@ -0,0 +1,208 @@
+//! Deterministic synthetic models for the regression benchmarks.
+//!
+//! Everything here is generated in memory so the suite runs without the conformance
+//! submodule and produces identical inputs on every machine. Shared with
+//! `lib3mf-converters/benches` via `#[path]`, so it may only depend on `lib3mf_core`.
+
+#![allow(dead_code)]
+
+use lib3mf_core::Model;
+use lib3mf_core::model::{
+    BaseMaterial, BaseMaterialsGroup, BuildItem, Color, ColorGroup, Component, Components,
+    Geometry, Mesh, Object, ObjectType, ResourceId,
+};
+use std::io::Cursor;
+
+/// Set this environment variable to include the 5M-triangle fixture.
+/// It needs several GB of RAM and minutes per sample, so it is opt-in.
+pub const HUGE_ENV: &str = "LIB3MF_BENCH_HUGE";
+
+/// Mesh sizes exercised by default, as `(label, approximate triangle count)`.
+pub fn mesh_sizes() -> Vec<(&'static str, usize)> {
+    let mut sizes = vec![("1k", 1_000), ("100k", 100_000)];
+    if std::env::var_os(HUGE_ENV).is_some() {
+        sizes.push(("5m", 5_000_000));
+    }
+    sizes
+}
+
+/// Closed, consistently oriented UV sphere with roughly `approx_triangles` faces.
+///
+/// With `n` latitude bands and `2n` segments the sphere has `4n(n-1)` triangles, so the
+/// mesh is manifold and realistic for repair and Paranoid validation.
+pub fn sphere_mesh(approx_triangles: usize) -> Mesh {
+    let bands = (((approx_triangles / 4) as f64).sqrt().round() as usize).max(2);
+    let segments = bands * 2;
+    let mut mesh = Mesh::new();
+
+    let north = mesh.add_vertex(0.0, 0.0, 50.0);
+    for band in 1..bands {
+        let theta = std::f32::consts::PI * band as f32 / bands as f32;
+        for seg in 0..segments {
+            let phi = std::f32::consts::TAU * seg as f32 / segments as f32;
+            mesh.add_vertex(
+                50.0 * theta.sin() * phi.cos(),
+                50.0 * theta.sin() * phi.sin(),
+                50.0 * theta.cos(),
+            );
+        }
+    }
+    let south = mesh.add_vertex(0.0, 0.0, -50.0);
+
+    let ring = |band: usize, seg: usize| (1 + (band - 1) * segments + seg % segments) as u32;
+    for seg in 0..segments {
+        mesh.add_triangle(north, ring(1, seg), ring(1, seg + 1));
+    }
+    for band in 1..bands - 1 {
+        for seg in 0..segments {
+            let (a, b) = (ring(band, seg), ring(band, seg + 1));
+            let (c, d) = (ring(band + 1, seg), ring(band + 1, seg + 1));
+            mesh.add_triangle(a, c, d);
+            mesh.add_triangle(a, d, b);
+        }
+    }
+    for seg in 0..segments {
+        mesh.add_triangle(south, ring(bands - 1, seg + 1), ring(bands - 1, seg));
+    }
+    mesh
+}
+
+fn object(id: u32, object_type: ObjectType, geometry: Geometry) -> Object {
+    Object {
+        id: ResourceId(id),
+        object_type,
+        name: None,
+        part_number: None,
+        uuid: None,
+        pid: None,
+        pindex: None,
+        thumbnail: None,
+        geometry,
+    }
+}
+
+fn build_item(object_id: u32) -> BuildItem {
+    BuildItem {
+        object_id: ResourceId(object_id),
+        uuid: None,
+        path: None,
+        part_number: None,
+        transform: glam::Mat4::IDENTITY,
+        printable: None,
+        metadata: Default::default(),
+    }
+}
+
+/// Single sphere object in the build.
+pub fn mesh_model(approx_triangles: usize) -> Model {
+    let mut model = Model::default();
+    model
+        .resources
+        .add_object(object(
+            1,
+            ObjectType::Model,
+            Geometry::Mesh(sphere_mesh(approx_triangles)),
+        ))
+        .expect("unique id");
+    model.build.items.push(build_item(1));
+    model
+}
+
+/// Build plate of `objects` separate spheres, each with roughly `approx_triangles` faces.
+pub fn plate_model(objects: u32, approx_triangles: usize) -> Model {
+    let mut model = Model::default();
+    let mesh = sphere_mesh(approx_triangles);
+    for id in 1..=objects {
+        model
+            .resources
+            .add_object(object(id, ObjectType::Model, Geometry::Mesh(mesh.clone())))
+            .expect("unique id");
+        model.build.items.push(build_item(id));
+    }
+    model
+}
+
+/// Chain of `depth` component objects, each translating the next, ending in a small mesh.
+pub fn component_tree_model(depth: u32) -> Model {
+    let mut model = Model::default();
+    model
+        .resources
+        .add_object(object(
+            1,
+            ObjectType::Model,
+            Geometry::Mesh(sphere_mesh(1_000)),
+        ))
+        .expect("unique id");
+    for id in 2..=depth + 1 {
+        let component = Component {
+            object_id: ResourceId(id - 1),
+            path: None,
+            uuid: None,
+            transform: glam::Mat4::from_translation(glam::Vec3::new(1.0, 0.0, 0.0)),
+        };
+        let geometry = Geometry::Components(Components {
+            components: vec![component],
+        });
+        model
+            .resources
+            .add_object(object(id, ObjectType::Model, geometry))
+            .expect("unique id");
+    }
+    model.build.items.push(build_item(depth + 1));
+    model
+}
+
+/// Sphere whose triangles cycle through `groups` base-material groups and one color
+/// group, each with `per_group` entries, exercising property parsing and writing.
+pub fn materials_model(approx_triangles: usize, groups: u32, per_group: u32) -> Model {
+    let mut model = Model::default();
+    for g in 0..groups {
+        let materials = (0..per_group)
+            .map(|i| BaseMaterial {
+                name: format!("Material {g}-{i}"),
+                display_color: Color::new((i % 256) as u8, (g % 256) as u8, 128, 255),
+            })
+            .collect();
+        model
+            .resources
+            .add_base_materials(BaseMaterialsGroup {
+                id: ResourceId(g + 1),
+                materials,
+            })
+            .expect("unique id");
+    }
+    let color_id = groups + 1;
+    model
+        .resources
+        .add_color_group(ColorGroup {
+            id: ResourceId(color_id),
+            colors: (0..per_group)
+                .map(|i| Color::new(255, (i % 256) as u8, 0, 255))
+                .collect(),
+        })
+        .expect("unique id");
+
+    let mut mesh = sphere_mesh(approx_triangles);
+    let property_groups = groups + 1;
+    for (i, tri) in mesh.triangles.iter_mut().enumerate() {
+        let i = i as u32;
+        let pindex = i % per_group;
+        tri.pid = Some(i % property_groups + 1);
+        tri.p1 = Some(pindex);
+        tri.p2 = Some(pindex);
+        tri.p3 = Some(pindex);
+    }
+    let mut obj = object(color_id + 1, ObjectType::Model, Geometry::Mesh(mesh));
+    obj.pid = Some(ResourceId(1));
+    obj.pindex = Some(0);
+    model.resources.add_object(obj).expect("unique id");
+    model.build.items.push(build_item(color_id + 1));
+    model
+}
+
+/// Serializes `model` to an in-memory 3MF package.
+pub fn package_bytes(model: &Model) -> Vec<u8> {
+    let mut out = Cursor::new(Vec::new());
+    model.write(&mut out).expect("fixture should serialize");
+    out.into_inner()
+}
@@ -0,0 +1,213 @@
+//! Performance regression suite over generated fixtures.
+//!
+//! Unlike `core_bench`, every input is synthesized by `fixtures` so results are
+//! comparable across machines and do not depend on the conformance submodule.
+//! Save a baseline before a change and compare after it:
+//!
+//! ```text
+//! cargo bench -p lib3mf-core --bench regression_bench -- --save-baseline main
+//! cargo bench -p lib3mf-core --bench regression_bench -- --baseline main
+//! ```
+//!
+//! Set `LIB3MF_BENCH_HUGE=1` to include the 5M-triangle mesh.
+
+mod fixtures;
+
+use criterion::{
+    BatchSize, BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main,
+};
+use lib3mf_core::Model;
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::model::{Geometry, MeshRepair, RepairOptions};
+use lib3mf_core::parser::parse_model;
+use lib3mf_core::validation::ValidationLevel;
+use lib3mf_core::validation::bvh::BvhNode;
+use std::io::Cursor;
+
+/// Depth of the component chain fixture.
+const TREE_DEPTH: u32 = 32;
+
+/// Number of objects on the build plate fixture.
+#[cfg(feature = "parallel")]
+const PLATE_OBJECTS: u32 = 200;
+
+fn parse_complete(data: &[u8]) -> Model {
+    let mut archiver = ZipArchiver::new(Cursor::new(data)).expect("Failed to open archive");
+    let model_path = find_model_path(&mut archiver).expect("Failed to find model path");
+    let model_data = archiver
+        .read_entry(&model_path)
+        .expect("Failed to read model entry");
+    parse_model(Cursor::new(model_data)).expect("Failed to parse model")
+}
+
+/// All fixtures as `(label, model)`, in increasing cost.
+fn all_fixtures() -> Vec<(String, Model)> {
+    let mut out: Vec<(String, Model)> = fixtures::mesh_sizes()
+        .into_iter()
+        .map(|(label, tris)| (format!("mesh_{label}"), fixtures::mesh_model(tris)))
+        .collect();
+    out.push((
+        format!("components_depth{TREE_DEPTH}"),
+        fixtures::component_tree_model(TREE_DEPTH),
+    ));
+    out.push((
+        "materials_16x256".to_string(),
+        fixtures::materials_model(10_000, 16, 256),
+    ));
+    // Fixtures must be valid, otherwise we would be timing error paths.
+    for (label, model) in &out {
+        let report = model.validate(ValidationLevel::Paranoid);
+        assert!(!report.has_errors(), "{label}: {:?}", report.items);
+    }
+    out
+}
+
+fn triangle_count(model: &Model) -> u64 {
+    model
+        .resources
+        .iter_objects()
+        .map(|o| match &o.geometry {
+            Geometry::Mesh(m) => m.triangles.len() as u64,
+            _ => 0,
+        })
+        .sum()
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("regression/parse");
+    group.sample_size(10);
+
+    for (label, model) in all_fixtures() {
+        let data = fixtures::package_bytes(&model);
+        group.throughput(Throughput::Bytes(data.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(&label), &data, |b, data| {
+            b.iter(|| black_box(parse_complete(black_box(data))));
+        });
+    }
+
+    group.finish();
+}
+
+/// Sequential versus two-phase parsing of a plate with many independent objects.
+#[cfg(feature = "parallel")]
+fn bench_parse_plate(c: &mut Criterion) {
+    use lib3mf_core::parser::parse_model_parallel;
+
+    let mut group = c.benchmark_group("regression/parse_plate");
+    group.sample_size(10);
+
+    let model = fixtures::plate_model(PLATE_OBJECTS, 2_000);
+    let data = fixtures::package_bytes(&model);
+    let mut archiver = ZipArchiver::new(Cursor::new(data)).expect("Failed to open archive");
+    let model_path = find_model_path(&mut archiver).expect("Failed to find model path");
+    let xml = archiver
+        .read_entry(&model_path)
+        .expect("Failed to read model entry");
+
+    group.throughput(Throughput::Bytes(xml.len() as u64));
+    group.bench_with_input("sequential", &xml, |b, xml| {
+        b.iter(|| black_box(parse_model(Cursor::new(black_box(xml))).expect("parse failed")));
+    });
+    group.bench_with_input("parallel", &xml, |b, xml| {
+        b.iter(|| black_box(parse_model_parallel(black_box(xml)).expect("parse failed")));
+    });
+
+    group.finish();
+}
+
+#[cfg(not(feature = "parallel"))]
+fn bench_parse_plate(_: &mut Criterion) {}
+
+fn bench_write(c: &mut Criterion) {
+    let mut group = c.benchmark_group("regression/write");
+    group.sample_size(10);
+
+    for (label, model) in all_fixtures() {
+        group.throughput(Throughput::Elements(triangle_count(&model)));
+        group.bench_with_input(BenchmarkId::from_parameter(&label), &model, |b, model| {
+            b.iter(|| {
+                let mut out = Cursor::new(Vec::new());
+                black_box(model).write(&mut out).expect("write failed");
+                black_box(out);
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_validate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("regression/validate");
+    group.sample_size(10);
+
+    for (label, model) in all_fixtures() {
+        group.throughput(Throughput::Elements(triangle_count(&model)));
+        for (level_name, level) in [
+            ("standard", ValidationLevel::Standard),
+            ("paranoid", ValidationLevel::Paranoid),
+        ] {
+            group.bench_with_input(BenchmarkId::new(level_name, &label), &model, |b, model| {
+                b.iter(|| black_box(black_box(model).validate(level)));
+            });
+        }
+    }
+
+    group.finish();
+}
+
+/// Mesh statistics and BVH construction. Compare `--features simd` runs with and
+/// without `LIB3MF_DISABLE_SIMD=1` to measure the accelerated kernels.
+fn bench_mesh_kernels(c: &mut Criterion) {
+    let mut group = c.benchmark_group("regression/mesh_kernels");
+    group.sample_size(10);
+
+    for (label, tris) in fixtures::mesh_sizes() {
+        let mesh = fixtures::sphere_mesh(tris);
+        group.throughput(Throughput::Elements(mesh.triangles.len() as u64));
+        group.bench_with_input(BenchmarkId::new("aabb", label), &mesh, |b, mesh| {
+            b.iter(|| black_box(black_box(mesh).compute_aabb()));
+        });
+        group.bench_with_input(BenchmarkId::new("area_volume", label), &mesh, |b, mesh| {
+            b.iter(|| black_box(black_box(mesh).compute_area_and_volume()));
+        });
+        group.bench_with_input(BenchmarkId::new("bvh_build", label), &mesh, |b, mesh| {
+            b.iter(|| {
+                let indices = (0..mesh.triangles.len()).collect();
+                black_box(BvhNode::build(black_box(mesh), indices))
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_repair(c: &mut Criterion) {
+    let mut group = c.benchmark_group("regression/repair");
+    group.sample_size(10);
+
+    for (label, tris) in fixtures::mesh_sizes() {
+        let mesh = fixtures::sphere_mesh(tris);
+        group.throughput(Throughput::Elements(mesh.triangles.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(label), &mesh, |b, mesh| {
+            // Repair mutates in place; clone outside the timed section.
+            b.iter_batched(
+                || mesh.clone(),
+                |mut m| black_box(m.repair(RepairOptions::default())),
+                BatchSize::LargeInput,
+            );
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_parse,
+    bench_parse_plate,
+    bench_write,
+    bench_validate,
+    bench_mesh_kernels,
+    bench_repair,
+);
+criterion_main!(benches);
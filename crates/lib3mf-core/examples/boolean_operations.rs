@@ -50,17 +50,17 @@ fn main() -> anyhow::Result<()> {
     let parsed = lib3mf_core::parser::parse_model(Cursor::new(&buffer))?;
 
     // Inspect the boolean shape
-    if let Some(obj) = parsed.resources.get_object(ResourceId(100)) {
-        if let Geometry::BooleanShape(bs) = &obj.geometry {
-            println!("Parsed BooleanShape:");
-            println!("  Base object ID: {}", bs.base_object_id.0);
-            println!("  Operations: {}", bs.operations.len());
-            for (i, op) in bs.operations.iter().enumerate() {
-                println!(
-                    "    [{}] {:?} with object {}",
-                    i, op.operation_type, op.object_id.0
-                );
-            }
+    if let Some(obj) = parsed.resources.get_object(ResourceId(100))
+        && let Geometry::BooleanShape(bs) = &obj.geometry
+    {
+        println!("Parsed BooleanShape:");
+        println!("  Base object ID: {}", bs.base_object_id.0);
+        println!("  Operations: {}", bs.operations.len());
+        for (i, op) in bs.operations.iter().enumerate() {
+            println!(
+                "    [{}] {:?} with object {}",
+                i, op.operation_type, op.object_id.0
+            );
         }
     }
 
@@ -168,7 +168,9 @@ fn create_boolean_model() -> Model {
             part_number: None,
             path: None,
             printable: None,
+            metadata: Default::default(),
         }],
+        uuid: None,
     };
 
     model
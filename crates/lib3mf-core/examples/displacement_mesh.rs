@@ -46,17 +46,17 @@ fn main() -> anyhow::Result<()> {
     let parsed = lib3mf_core::parser::parse_model(Cursor::new(&buffer))?;
 
     // Inspect displacement mesh
-    if let Some(obj) = parsed.resources.get_object(ResourceId(1)) {
-        if let Geometry::DisplacementMesh(dmesh) = &obj.geometry {
-            println!("Parsed DisplacementMesh:");
-            println!("  Vertices: {}", dmesh.vertices.len());
-            println!("  Triangles: {}", dmesh.triangles.len());
-            println!("  Normals: {}", dmesh.normals.len());
-            println!(
-                "  Gradients: {}",
-                dmesh.gradients.as_ref().map_or(0, |g| g.len())
-            );
-        }
+    if let Some(obj) = parsed.resources.get_object(ResourceId(1))
+        && let Geometry::DisplacementMesh(dmesh) = &obj.geometry
+    {
+        println!("Parsed DisplacementMesh:");
+        println!("  Vertices: {}", dmesh.vertices.len());
+        println!("  Triangles: {}", dmesh.triangles.len());
+        println!("  Normals: {}", dmesh.normals.len());
+        println!(
+            "  Gradients: {}",
+            dmesh.gradients.as_ref().map_or(0, |g| g.len())
+        );
     }
 
     println!("\nExample complete!");
@@ -150,7 +150,9 @@ fn create_displacement_model() -> Model {
             part_number: None,
             path: None,
             printable: None,
+            metadata: Default::default(),
         }],
+        uuid: None,
     };
 
     model
@@ -36,6 +36,7 @@ fn main() {
         harmonize_orientations: true,
         remove_islands: false,
         fill_holes: false,
+        ..Default::default()
     };
 
     let stats = mesh.repair(options);
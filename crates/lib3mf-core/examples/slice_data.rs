@@ -120,6 +120,7 @@ fn main() -> anyhow::Result<()> {
         uuid: None,
         path: None,
         printable: None,
+        metadata: Default::default(),
     };
     model.build.items.push(item);
 
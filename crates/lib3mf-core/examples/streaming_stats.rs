@@ -33,7 +33,12 @@ impl ModelVisitor for StatsVisitor {
         Ok(())
     }
 
-    fn on_metadata(&mut self, name: &str, value: &str) -> lib3mf_core::error::Result<()> {
+    fn on_metadata(
+        &mut self,
+        name: &str,
+        value: &str,
+        _lang: Option<&str>,
+    ) -> lib3mf_core::error::Result<()> {
         println!("Metadata: {} = {}", name, value);
         Ok(())
     }
@@ -55,7 +55,8 @@
 //! 1. Read `_rels/.rels` (package-level relationships)
 //! 2. Find relationship with type `http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel`
 //! 3. Extract target path (e.g., `/3D/3dmodel.model`)
-//! 4. Normalize path (remove leading `/`)
+//! 4. Normalize path with [`resolve_part_target`] (drop the leading `/`, resolve `./` and
+//!    `..`, backslashes and percent-escapes)
 //!
 //! This allows 3MF files to have different internal structures while remaining conformant to the spec.
 //!
@@ -81,16 +82,51 @@
 pub mod model_locator;
 /// OPC relationship and content type parsers.
 pub mod opc;
+/// OPC part name normalization and relationship target resolution.
+pub mod part_name;
+/// Secure Content detection: key store lookup and encrypted part listing.
+pub mod secure_content;
 /// ZIP-based `ArchiveReader` implementation using the `zip` crate.
 pub mod zip_archive;
 
 pub use model_locator::*;
+pub use part_name::*;
+pub use secure_content::*;
 // pub use opc::*; // Clippy says unused
 pub use zip_archive::*;
 
 use crate::error::Result;
+use serde::{Deserialize, Serialize};
 use std::io::{Read, Seek};
 
+/// Size, compression and CRC-32 of an archive entry, as returned by
+/// [`ArchiveReader::entry_metadata`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryMetadata {
+    /// Path of the entry within the archive.
+    pub name: String,
+    /// Uncompressed size in bytes.
+    pub size: u64,
+    /// Size in bytes as stored in the archive.
+    pub compressed_size: u64,
+    /// Whether the entry is compressed rather than stored as is.
+    pub compressed: bool,
+    /// CRC-32 of the uncompressed data.
+    pub crc32: u32,
+}
+
+impl EntryMetadata {
+    /// Returns the stored size as a fraction of the uncompressed size, `1.0` for empty
+    /// entries. Lower is better; already-compressed data such as PNG stays near `1.0`.
+    pub fn compression_ratio(&self) -> f64 {
+        if self.size == 0 {
+            1.0
+        } else {
+            self.compressed_size as f64 / self.size as f64
+        }
+    }
+}
+
 /// Trait for reading entries from an archive (ZIP).
 ///
 /// This trait abstracts over different ZIP backend implementations, allowing the parser to work
@@ -160,6 +196,53 @@ pub trait ArchiveReader: Read + Seek {
     ///
     /// Returns [`Lib3mfError::Io`](crate::error::Lib3mfError::Io) if the archive can't be read.
     fn list_entries(&mut self) -> Result<Vec<String>>;
+
+    /// Returns the size, compression and checksum of an entry.
+    ///
+    /// The default implementation reads the entry and reports it as stored uncompressed;
+    /// [`ZipArchiver`] reads the sizes and CRC-32 from the ZIP directory without
+    /// decompressing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Lib3mfError::Io`](crate::error::Lib3mfError::Io) if the entry doesn't exist.
+    fn entry_metadata(&mut self, name: &str) -> Result<EntryMetadata> {
+        let data = self.read_entry(name)?;
+        Ok(EntryMetadata {
+            name: name.trim_start_matches('/').to_string(),
+            size: data.len() as u64,
+            compressed_size: data.len() as u64,
+            compressed: false,
+            crc32: crc32fast::hash(&data),
+        })
+    }
+
+    /// Returns the size and compression of every entry, directories excluded, in archive
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lib3mf_core::archive::{ArchiveReader, ZipArchiver};
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut archive = ZipArchiver::new(File::open("model.3mf")?)?;
+    /// let mut entries = archive.entries_metadata()?;
+    /// entries.sort_by_key(|e| std::cmp::Reverse(e.compressed_size));
+    /// for entry in entries.iter().take(3) {
+    ///     println!("{}: {} bytes stored", entry.name, entry.compressed_size);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn entries_metadata(&mut self) -> Result<Vec<EntryMetadata>> {
+        self.list_entries()?
+            .iter()
+            .filter(|name| !name.ends_with('/'))
+            .map(|name| self.entry_metadata(name))
+            .collect()
+    }
 }
 
 /// Trait for writing entries to an archive.
@@ -1,7 +1,105 @@
-use crate::archive::{ArchiveReader, opc::parse_relationships};
+use crate::archive::{
+    ArchiveReader, normalize_part_name, opc::parse_relationships, part_names_match,
+    resolve_part_target,
+};
 use crate::error::{Lib3mfError, Result};
+use crate::model::PRINT_TICKET_REL_TYPE;
+use serde::{Deserialize, Serialize};
+
+/// Relationship type of the root 3D model part.
+pub const MODEL_REL_TYPE: &str = "http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel";
+
+/// A 3D model relationship of the package, as listed by [`find_model_paths`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelPartCandidate {
+    /// Archive entry name of the target part.
+    pub path: String,
+    /// `Id` of the relationship in `_rels/.rels`.
+    pub relationship_id: String,
+    /// Whether the target part exists in the archive.
+    pub exists: bool,
+}
+
+/// Lists every 3D model relationship in `_rels/.rels`, in document order.
+///
+/// Unlike [`find_model_path`], this accepts packages with several model relationships,
+/// as written by some vendors with one plate per part.
+pub fn find_model_paths<R: ArchiveReader>(archive: &mut R) -> Result<Vec<ModelPartCandidate>> {
+    if !archive.entry_exists("_rels/.rels") {
+        return Err(Lib3mfError::InvalidStructure(
+            "Missing _rels/.rels".to_string(),
+        ));
+    }
+    let rels = parse_relationships(&archive.read_entry("_rels/.rels")?)?;
+    Ok(rels
+        .into_iter()
+        .filter(|rel| {
+            rel.rel_type == MODEL_REL_TYPE && !rel.target_mode.eq_ignore_ascii_case("external")
+        })
+        .map(|rel| {
+            let path = resolve_part_target("", &rel.target);
+            ModelPartCandidate {
+                exists: archive.entry_exists(&path),
+                path,
+                relationship_id: rel.id,
+            }
+        })
+        .collect())
+}
+
+/// Chooses the root model part to load.
+///
+/// The selection policy is:
+///
+/// 1. If `requested` is given, that part is loaded. It must exist in the archive but need
+///    not be the target of a model relationship, so any model part can be opened.
+/// 2. Otherwise, if the package has exactly one model relationship, its target is loaded.
+/// 3. Otherwise the first model relationship in document order whose target exists is
+///    loaded. Such packages violate the core specification, which
+///    [`find_model_path`] and validation report.
+///
+/// Returns the archive entry name of the chosen part.
+///
+/// # Errors
+///
+/// Returns `Lib3mfError::Validation` if `requested` does not exist or no model
+/// relationship has an existing target, and `Lib3mfError::InvalidStructure` if
+/// `_rels/.rels` is missing.
+pub fn select_model_path<R: ArchiveReader>(
+    archive: &mut R,
+    requested: Option<&str>,
+) -> Result<String> {
+    if let Some(requested) = requested {
+        if !archive.entry_exists(requested) {
+            return Err(Lib3mfError::Validation(format!(
+                "Model part '{}' not found in package",
+                requested
+            )));
+        }
+        // Prefer the spelling of the relationship target, then the entry name
+        let candidates = find_model_paths(archive).unwrap_or_default();
+        return Ok(candidates
+            .into_iter()
+            .find(|c| part_names_match(&c.path, requested))
+            .map(|c| c.path)
+            .unwrap_or_else(|| normalize_part_name(requested)));
+    }
+
+    let candidates = find_model_paths(archive)?;
+    if let [only] = candidates.as_slice() {
+        return Ok(only.path.clone());
+    }
+    candidates
+        .into_iter()
+        .find(|c| c.exists)
+        .map(|c| c.path)
+        .ok_or_else(|| Lib3mfError::Validation("No 3D model relationship found".to_string()))
+}
 
 /// Locates the path of the 3D model file within the archive.
+///
+/// This is the strict lookup: packages with more than one model relationship are rejected,
+/// as the core specification requires. Use [`select_model_path`] to open them anyway.
 pub fn find_model_path<R: ArchiveReader>(archive: &mut R) -> Result<String> {
     // 1. Read _rels/.rels
     if !archive.entry_exists("_rels/.rels") {
@@ -15,10 +113,8 @@ pub fn find_model_path<R: ArchiveReader>(archive: &mut R) -> Result<String> {
 
     // 2. Validate relationships and find the 3D Model
     // 3MF Core Spec: http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel
-    let model_rel_type = "http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel";
     let thumbnail_rel_type =
         "http://schemas.openxmlformats.org/package/2006/relationships/metadata/thumbnail";
-    let print_ticket_rel_type = "http://schemas.microsoft.com/3dmanufacturing/2013/01/printticket";
 
     let mut model_path: Option<String> = None;
     let mut model_count = 0;
@@ -33,7 +129,7 @@ pub fn find_model_path<R: ArchiveReader>(archive: &mut R) -> Result<String> {
             )));
         }
 
-        if rel.rel_type == model_rel_type {
+        if rel.rel_type == MODEL_REL_TYPE {
             model_count += 1;
             if model_count > 1 {
                 return Err(Lib3mfError::Validation(
@@ -41,29 +137,18 @@ pub fn find_model_path<R: ArchiveReader>(archive: &mut R) -> Result<String> {
                 ));
             }
 
-            // Target paths in OPC are often relative to the root if they start with /
-            // or relative to the relations file location.
-            // For root relationships, they are usually relative to root.
-            let path = rel.target.clone();
-            model_path = Some(if path.starts_with('/') {
-                path.trim_start_matches('/').to_string()
-            } else {
-                path
-            });
+            // Package relationships resolve against the package root
+            model_path = Some(resolve_part_target("", &rel.target));
         } else if rel.rel_type == thumbnail_rel_type {
             // Validate that thumbnail file exists
-            let thumb_path = if rel.target.starts_with('/') {
-                rel.target.trim_start_matches('/').to_string()
-            } else {
-                rel.target.clone()
-            };
+            let thumb_path = resolve_part_target("", &rel.target);
             if !archive.entry_exists(&thumb_path) {
                 return Err(Lib3mfError::Validation(format!(
                     "Thumbnail file '{}' referenced in relationships does not exist in package",
                     thumb_path
                 )));
             }
-        } else if rel.rel_type == print_ticket_rel_type {
+        } else if rel.rel_type == PRINT_TICKET_REL_TYPE {
             print_ticket_count += 1;
             if print_ticket_count > 1 {
                 return Err(Lib3mfError::Validation(
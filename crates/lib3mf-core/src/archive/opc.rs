@@ -36,6 +36,10 @@ pub mod bambu_rel_types {
     pub const GCODE: &str = "http://schemas.bambulab.com/package/2021/gcode";
 }
 
+/// Relationship type of package and object thumbnail images.
+pub const THUMBNAIL_REL_TYPE: &str =
+    "http://schemas.openxmlformats.org/package/2006/relationships/metadata/thumbnail";
+
 /// Represents an OPC Relationship.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Relationship {
@@ -177,3 +181,31 @@ pub fn parse_content_types(xml_content: &[u8]) -> Result<Vec<ContentType>> {
 
     Ok(types)
 }
+
+/// Returns the content type of a part: its `Override` entry if there is one, otherwise the
+/// `Default` entry for its extension.
+///
+/// Part names and extensions are compared ASCII case-insensitively, as OPC requires, and
+/// `part_name` may be given with or without its leading `/`.
+pub fn content_type_of<'a>(types: &'a [ContentType], part_name: &str) -> Option<&'a str> {
+    let part_name = part_name.trim_start_matches('/');
+    let overridden = types.iter().find_map(|t| match t {
+        ContentType::Override {
+            part_name: name,
+            content_type,
+        } if name.trim_start_matches('/').eq_ignore_ascii_case(part_name) => {
+            Some(content_type.as_str())
+        }
+        _ => None,
+    });
+    overridden.or_else(|| {
+        let (_, extension) = part_name.rsplit_once('.')?;
+        types.iter().find_map(|t| match t {
+            ContentType::Default {
+                extension: ext,
+                content_type,
+            } if ext.eq_ignore_ascii_case(extension) => Some(content_type.as_str()),
+            _ => None,
+        })
+    })
+}
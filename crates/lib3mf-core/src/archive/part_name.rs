@@ -0,0 +1,114 @@
+//! OPC part name normalization and relationship target resolution.
+//!
+//! Relationship targets and part references written by some producers use `./` and `..`
+//! segments, Windows backslashes, or percent-encoded characters (`3D/my%20part.model`).
+//! These helpers turn any such reference into the archive entry name it denotes: forward
+//! slashes, no leading `/`, no empty, `.` or `..` segments, and percent-escapes decoded.
+//!
+//! ```
+//! use lib3mf_core::archive::{normalize_part_name, resolve_part_target};
+//!
+//! assert_eq!(normalize_part_name("/3D/./Textures/../3dmodel.model"), "3D/3dmodel.model");
+//! assert_eq!(normalize_part_name("3D\\my%20part.model"), "3D/my part.model");
+//!
+//! // Relative targets resolve against the directory of their source part
+//! assert_eq!(
+//!     resolve_part_target("3D/3dmodel.model", "../Metadata/thumbnail.png"),
+//!     "Metadata/thumbnail.png"
+//! );
+//! // Package relationships (`_rels/.rels`) have the package root as their source
+//! assert_eq!(resolve_part_target("", "./3D/3dmodel.model"), "3D/3dmodel.model");
+//! ```
+//!
+//! OPC compares part names case-insensitively; [`part_names_match`] does so after
+//! normalizing both sides.
+
+/// Normalizes a part name or package-rooted reference to an archive entry name.
+///
+/// `..` segments above the package root are dropped.
+pub fn normalize_part_name(name: &str) -> String {
+    resolve_part_target("", name)
+}
+
+/// Resolves a relationship target or part reference against `source_part`, the part the
+/// reference appears in (the empty string for the package root), and normalizes the result.
+///
+/// Targets starting with `/` (or `\`) are package-rooted and ignore `source_part`.
+pub fn resolve_part_target(source_part: &str, target: &str) -> String {
+    let target = target.replace('\\', "/");
+    let mut segments: Vec<String> = Vec::new();
+    if !target.starts_with('/') {
+        push_segments(&mut segments, &source_part.replace('\\', "/"));
+        // The reference is relative to the source part's directory
+        segments.pop();
+    }
+    push_segments(&mut segments, &target);
+    segments.join("/")
+}
+
+/// Returns the source part of a relationships part: `3D/3dmodel.model` for
+/// `3D/_rels/3dmodel.model.rels`, and the empty string (the package root) for `_rels/.rels`.
+pub fn rels_source_part(rels_path: &str) -> String {
+    let rels_path = normalize_part_name(rels_path);
+    let (dir, file) = match rels_path.rsplit_once("_rels/") {
+        Some((dir, file)) => (dir, file),
+        None => ("", rels_path.as_str()),
+    };
+    let file = file.strip_suffix(".rels").unwrap_or(file);
+    format!("{}{}", dir, file)
+}
+
+/// Returns the relationships part of a part: `3D/_rels/3dmodel.model.rels` for
+/// `3D/3dmodel.model`. The inverse of [`rels_source_part`].
+pub fn part_rels_path(part: &str) -> String {
+    match part.rsplit_once('/') {
+        Some((dir, name)) => format!("{}/_rels/{}.rels", dir, name),
+        None => format!("_rels/{}.rels", part),
+    }
+}
+
+/// Returns true if two part references name the same part, comparing their normalized
+/// forms case-insensitively as OPC requires.
+pub fn part_names_match(a: &str, b: &str) -> bool {
+    normalize_part_name(a).eq_ignore_ascii_case(&normalize_part_name(b))
+}
+
+fn push_segments(segments: &mut Vec<String>, path: &str) {
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            s => segments.push(percent_decode(s)),
+        }
+    }
+}
+
+/// Decodes `%XX` escapes. Escapes that don't form valid UTF-8 leave the segment unchanged.
+fn percent_decode(segment: &str) -> String {
+    if !segment.contains('%') {
+        return segment.to_string();
+    }
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).unwrap_or_else(|_| segment.to_string())
+}
@@ -0,0 +1,137 @@
+use crate::archive::opc::parse_relationships;
+use crate::archive::{
+    ArchiveReader, normalize_part_name, part_names_match, rels_source_part, resolve_part_target,
+};
+use crate::error::{EncryptedContentInfo, Lib3mfError, Result};
+use quick_xml::events::Event;
+use quick_xml::reader::Reader;
+
+/// Relationship type of the Secure Content key store part.
+pub const KEYSTORE_REL_TYPE: &str = "http://schemas.microsoft.com/3dmanufacturing/2019/04/keystore";
+
+/// Relationship type marking a part as encrypted.
+pub const ENCRYPTED_FILE_REL_TYPE: &str =
+    "http://schemas.microsoft.com/3dmanufacturing/2019/04/encryptedfile";
+
+/// Returns the key store part named by the package relationships, if any.
+pub fn find_keystore_path<R: ArchiveReader>(archive: &mut R) -> Result<Option<String>> {
+    if !archive.entry_exists("_rels/.rels") {
+        return Ok(None);
+    }
+    let rels = parse_relationships(&archive.read_entry("_rels/.rels")?)?;
+    Ok(rels
+        .into_iter()
+        .find(|rel| rel.rel_type == KEYSTORE_REL_TYPE)
+        .map(|rel| resolve_part_target("", &rel.target)))
+}
+
+/// Inspects the key store of a package without decrypting anything.
+///
+/// Encrypted parts are those listed by `<resourcedata>` in the key store and the targets of
+/// `encryptedfile` relationships anywhere in the package. Returns `None` if the package
+/// has no key store.
+pub fn inspect_secure_content<R: ArchiveReader>(
+    archive: &mut R,
+) -> Result<Option<EncryptedContentInfo>> {
+    let Some(keystore_path) = find_keystore_path(archive)? else {
+        return Ok(None);
+    };
+    let mut info = EncryptedContentInfo {
+        keystore_path,
+        ..Default::default()
+    };
+    if archive.entry_exists(&info.keystore_path) {
+        let data = archive.read_entry(&info.keystore_path)?;
+        scan_keystore(&data, &mut info)?;
+    }
+
+    for rels_path in archive.list_entries()? {
+        if !rels_path.ends_with(".rels") {
+            continue;
+        }
+        let Ok(rels) = parse_relationships(&archive.read_entry(&rels_path)?) else {
+            continue;
+        };
+        let source = rels_source_part(&rels_path);
+        for rel in rels {
+            if rel.rel_type == ENCRYPTED_FILE_REL_TYPE {
+                add_part(&mut info, resolve_part_target(&source, &rel.target));
+            }
+        }
+    }
+    Ok(Some(info))
+}
+
+/// Fails with [`Lib3mfError::EncryptedContent`] if `model_path` is encrypted.
+///
+/// Call this before parsing a model part read from the archive, so encrypted packages
+/// report their key store instead of an XML syntax error.
+pub fn check_model_not_encrypted<R: ArchiveReader>(
+    archive: &mut R,
+    model_path: &str,
+) -> Result<()> {
+    match inspect_secure_content(archive)? {
+        Some(info)
+            if info
+                .encrypted_parts
+                .iter()
+                .any(|p| part_names_match(p, model_path)) =>
+        {
+            Err(Lib3mfError::EncryptedContent(Box::new(info)))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn add_part(info: &mut EncryptedContentInfo, path: String) {
+    if !info
+        .encrypted_parts
+        .iter()
+        .any(|p| part_names_match(p, &path))
+    {
+        info.encrypted_parts.push(path);
+    }
+}
+
+/// Collects the UUID, consumer IDs and resource paths of a key store with a plain XML scan,
+/// so detection works without the `crypto` feature.
+fn scan_keystore(data: &[u8], info: &mut EncryptedContentInfo) -> Result<()> {
+    let mut reader = Reader::from_reader(data);
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                let attr = |name: &[u8]| {
+                    e.attributes()
+                        .flatten()
+                        .find(|a| a.key.local_name().as_ref() == name)
+                        .and_then(|a| a.unescape_value().ok().map(|v| v.into_owned()))
+                };
+                match e.local_name().as_ref() {
+                    b"keystore" => info.keystore_uuid = attr(b"UUID"),
+                    b"consumer" => {
+                        if let Some(id) = attr(b"consumerid") {
+                            info.consumers.push(id);
+                        }
+                    }
+                    b"resourcedata" => {
+                        if let Some(path) = attr(b"path") {
+                            add_part(info, normalize_part_name(&path));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                return Err(Lib3mfError::InvalidStructure(format!(
+                    "Invalid key store XML: {}",
+                    e
+                )));
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+    Ok(())
+}
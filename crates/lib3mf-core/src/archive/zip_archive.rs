@@ -1,5 +1,6 @@
-use crate::archive::ArchiveReader;
+use crate::archive::{ArchiveReader, EntryMetadata, part_names_match};
 use crate::error::{Lib3mfError, Result};
+use crate::trace::{phase, span};
 use std::io::{Read, Seek};
 use zip::ZipArchive;
 
@@ -7,6 +8,9 @@ use zip::ZipArchive;
 ///
 /// Wraps a [`zip::ZipArchive`] and implements [`ArchiveReader`] for use with the 3MF parser.
 /// This is the standard entry point for reading `.3mf` files from disk or memory.
+///
+/// Zip64 archives are supported, so packages may exceed 4 GiB, hold entries of 4 GiB or
+/// more, and have more than 65,535 entries.
 #[derive(Debug)]
 pub struct ZipArchiver<R> {
     archive: ZipArchive<R>,
@@ -15,9 +19,39 @@ pub struct ZipArchiver<R> {
 impl<R: Read + Seek> ZipArchiver<R> {
     /// Creates a new `ZipArchiver` by reading a ZIP archive from the given reader.
     pub fn new(reader: R) -> Result<Self> {
-        Ok(Self {
-            archive: ZipArchive::new(reader).map_err(|e| Lib3mfError::Io(e.into()))?,
-        })
+        let phase = phase!(span::ARCHIVE_OPEN, entries = tracing::field::Empty);
+        let archive = ZipArchive::new(reader).map_err(|e| Lib3mfError::Io(e.into()))?;
+        phase.record("entries", archive.len());
+        Ok(Self { archive })
+    }
+
+    /// Opens an entry for incremental reading without buffering it in memory.
+    ///
+    /// Unlike [`ArchiveReader::read_entry`], the returned reader decompresses on demand,
+    /// so multi-gigabyte model parts can be fed to the streaming parser with bounded memory.
+    pub fn entry_reader(&mut self, name: &str) -> Result<impl Read + '_> {
+        let index = self.find_entry(name)?;
+        self.archive
+            .by_index(index)
+            .map_err(|e| Lib3mfError::Io(e.into()))
+    }
+
+    /// Returns the index of the entry for a part name. Names that don't match an entry
+    /// exactly are compared in normalized form, so `./`, `..`, backslashes,
+    /// percent-escapes and case differences still find the part.
+    fn find_entry(&self, name: &str) -> Result<usize> {
+        let trimmed = name.trim_start_matches('/');
+        self.archive
+            .index_for_name(trimmed)
+            .or_else(|| {
+                self.archive
+                    .file_names()
+                    .find(|entry| part_names_match(entry, name))
+                    .and_then(|entry| self.archive.index_for_name(entry))
+            })
+            .ok_or_else(|| {
+                Lib3mfError::Io(std::io::Error::new(std::io::ErrorKind::NotFound, trimmed))
+            })
     }
 }
 
@@ -45,10 +79,11 @@ impl<R: Read + Seek> Seek for ZipArchiver<R> {
 
 impl<R: Read + Seek> ArchiveReader for ZipArchiver<R> {
     fn read_entry(&mut self, name: &str) -> Result<Vec<u8>> {
-        let name = name.trim_start_matches('/');
-        let mut file = self.archive.by_name(name).map_err(|_| {
-            Lib3mfError::Io(std::io::Error::new(std::io::ErrorKind::NotFound, name))
-        })?;
+        let index = self.find_entry(name)?;
+        let mut file = self
+            .archive
+            .by_index(index)
+            .map_err(|e| Lib3mfError::Io(e.into()))?;
 
         let mut buffer = Vec::new();
         file.read_to_end(&mut buffer)?;
@@ -56,11 +91,43 @@ impl<R: Read + Seek> ArchiveReader for ZipArchiver<R> {
     }
 
     fn entry_exists(&mut self, name: &str) -> bool {
-        let name = name.trim_start_matches('/');
-        self.archive.by_name(name).is_ok()
+        self.find_entry(name).is_ok()
     }
 
     fn list_entries(&mut self) -> Result<Vec<String>> {
         Ok(self.archive.file_names().map(|s| s.to_string()).collect())
     }
+
+    fn entry_metadata(&mut self, name: &str) -> Result<EntryMetadata> {
+        let index = self.find_entry(name)?;
+        let file = self
+            .archive
+            .by_index_raw(index)
+            .map_err(|e| Lib3mfError::Io(e.into()))?;
+        Ok(entry_metadata(&file))
+    }
+
+    fn entries_metadata(&mut self) -> Result<Vec<EntryMetadata>> {
+        let mut entries = Vec::with_capacity(self.archive.len());
+        for i in 0..self.archive.len() {
+            let file = self
+                .archive
+                .by_index_raw(i)
+                .map_err(|e| Lib3mfError::Io(e.into()))?;
+            if !file.is_dir() {
+                entries.push(entry_metadata(&file));
+            }
+        }
+        Ok(entries)
+    }
+}
+
+fn entry_metadata(file: &zip::read::ZipFile<'_>) -> EntryMetadata {
+    EntryMetadata {
+        name: file.name().to_string(),
+        size: file.size(),
+        compressed_size: file.compressed_size(),
+        compressed: file.compression() != zip::CompressionMethod::Stored,
+        crc32: file.crc32(),
+    }
 }
@@ -1,4 +1,4 @@
-use crate::archive::ArchiveReader;
+use crate::archive::{ArchiveReader, normalize_part_name};
 use crate::crypto::encryption::decrypt_aes256gcm;
 use crate::crypto::keys::KeyManager;
 use crate::error::{Lib3mfError, Result};
@@ -33,11 +33,52 @@ impl SecureContext {
             keystore,
             private_key,
             cek_cache: HashMap::new(),
-            resource_key_map,
+            resource_key_map: resource_key_map
+                .into_iter()
+                .map(|(path, key_uuid)| (normalize_part_name(&path), key_uuid))
+                .collect(),
             consumer_id,
         }
     }
 
+    /// Creates a context for the consumer whose access right `private_key` unwraps.
+    ///
+    /// Parts are mapped to content keys by the `<resourcedata>` paths of the key store's
+    /// groups. Fails if the key unwraps no access right in any group.
+    pub fn for_private_key(keystore: KeyStore, private_key: RsaPrivateKey) -> Result<Self> {
+        let mut resource_key_map = HashMap::new();
+        for group in &keystore.resource_data_groups {
+            for path in &group.resource_paths {
+                resource_key_map.insert(path.clone(), group.key_uuid);
+            }
+        }
+
+        // RSA-OAEP unwrapping fails for a key the CEK was not wrapped with
+        let (consumer_id, key_uuid, cek) = keystore
+            .resource_data_groups
+            .iter()
+            .flat_map(|g| g.access_rights.iter().map(move |ar| (g.key_uuid, ar)))
+            .find_map(|(key_uuid, ar)| {
+                KeyManager::unwrap_key(&private_key, &ar.wrapped_key)
+                    .ok()
+                    .map(|cek| (ar.consumer_id.clone(), key_uuid, cek))
+            })
+            .ok_or_else(|| {
+                Lib3mfError::EncryptionError(
+                    "Private key does not match any access right in the KeyStore".to_string(),
+                )
+            })?;
+
+        let mut context = Self::new(keystore, private_key, resource_key_map, consumer_id);
+        context.cek_cache.insert(key_uuid.to_string(), cek);
+        Ok(context)
+    }
+
+    /// ID of the consumer this context decrypts for.
+    pub fn consumer_id(&self) -> &str {
+        &self.consumer_id
+    }
+
     /// Reads and decrypts an entry from the archive if it is covered by the KeyStore.
     /// If the path is not encrypted (not in map), returns None (or error if strictly checking?).
     /// Recommended: Return `Ok(Some(data))` if decrypted, `Ok(None)` if not encrypted, `Err` if failure.
@@ -46,7 +87,7 @@ impl SecureContext {
         archiver: &mut impl ArchiveReader,
         path: &str,
     ) -> Result<Option<Vec<u8>>> {
-        let key_uuid = match self.resource_key_map.get(path) {
+        let key_uuid = match self.resource_key_map.get(&normalize_part_name(path)) {
             Some(u) => u,
             None => return Ok(None), // Not encrypted
         };
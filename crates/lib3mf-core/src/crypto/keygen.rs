@@ -0,0 +1,271 @@
+//! Key pair and self-signed certificate generation.
+//!
+//! Signing and encrypting a 3MF package needs a private key and, for the recipient or
+//! verifier, an X.509 certificate. [`KeyPair`] generates RSA, ECDSA and Ed25519 keys and
+//! issues self-signed certificates for them, so test workflows don't need OpenSSL:
+//!
+//! ```no_run
+//! use lib3mf_core::crypto::keygen::{KeyAlgorithm, KeyPair};
+//!
+//! # fn main() -> lib3mf_core::Result<()> {
+//! let key = KeyPair::generate(KeyAlgorithm::Rsa(2048))?;
+//! std::fs::write("key.pem", key.to_pem()?)?;
+//! std::fs::write("cert.pem", key.self_signed_certificate("CN=Test Signer", 365)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Self-signed certificates prove nothing about who holds the key; use them only for
+//! testing.
+
+use crate::crypto::verification::VerificationKey;
+use crate::error::{Lib3mfError, Result};
+use ed25519_dalek::Signer;
+use rand::RngCore;
+use rand::rngs::OsRng;
+use rsa::pkcs1::DecodeRsaPrivateKey;
+use rsa::pkcs8::{DecodePrivateKey, EncodePrivateKey, EncodePublicKey, LineEnding};
+use rsa::traits::PublicKeyParts;
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use sha2::Sha256;
+use std::str::FromStr;
+use std::time::Duration;
+use x509_cert::builder::{Builder, CertificateBuilder, Profile};
+use x509_cert::der::EncodePem;
+use x509_cert::der::asn1::BitString;
+use x509_cert::name::Name;
+use x509_cert::serial_number::SerialNumber;
+use x509_cert::spki::SubjectPublicKeyInfoOwned;
+use x509_cert::time::Validity;
+
+/// Algorithm and size of a generated key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyAlgorithm {
+    /// RSA with the given modulus size in bits (at least 2048).
+    Rsa(usize),
+    /// ECDSA on the NIST P-256 curve, signing with SHA-256.
+    EcdsaP256,
+    /// ECDSA on the NIST P-384 curve, signing with SHA-384.
+    EcdsaP384,
+    /// Ed25519 (EdDSA on Curve25519).
+    Ed25519,
+}
+
+impl Default for KeyAlgorithm {
+    fn default() -> Self {
+        Self::Rsa(2048)
+    }
+}
+
+/// A private key together with its public half.
+///
+/// Only RSA keys can wrap content keys for encryption; ECDSA and Ed25519 keys are for
+/// signing.
+#[derive(Debug, Clone)]
+pub enum KeyPair {
+    /// RSA key pair.
+    Rsa(RsaPrivateKey),
+    /// ECDSA P-256 key pair.
+    EcdsaP256(p256::ecdsa::SigningKey),
+    /// ECDSA P-384 key pair.
+    EcdsaP384(p384::ecdsa::SigningKey),
+    /// Ed25519 key pair.
+    Ed25519(ed25519_dalek::SigningKey),
+}
+
+impl KeyPair {
+    /// Generates a new random key pair.
+    ///
+    /// RSA keys smaller than 2048 bits are rejected.
+    pub fn generate(algorithm: KeyAlgorithm) -> Result<Self> {
+        let mut rng = OsRng;
+        match algorithm {
+            KeyAlgorithm::Rsa(bits) if bits < 2048 => Err(Lib3mfError::Validation(format!(
+                "RSA keys must be at least 2048 bits, got {}",
+                bits
+            ))),
+            KeyAlgorithm::Rsa(bits) => RsaPrivateKey::new(&mut rng, bits)
+                .map(Self::Rsa)
+                .map_err(|e| Lib3mfError::EncryptionError(format!("Key generation failed: {}", e))),
+            KeyAlgorithm::EcdsaP256 => {
+                Ok(Self::EcdsaP256(p256::ecdsa::SigningKey::random(&mut rng)))
+            }
+            KeyAlgorithm::EcdsaP384 => {
+                Ok(Self::EcdsaP384(p384::ecdsa::SigningKey::random(&mut rng)))
+            }
+            KeyAlgorithm::Ed25519 => {
+                Ok(Self::Ed25519(ed25519_dalek::SigningKey::generate(&mut rng)))
+            }
+        }
+    }
+
+    /// Reads a PEM private key: PKCS#8 (RSA, P-256, P-384 or Ed25519) or PKCS#1 RSA.
+    pub fn from_pem(pem: &str) -> Result<Self> {
+        if let Ok(key) = RsaPrivateKey::from_pkcs8_pem(pem) {
+            return Ok(Self::Rsa(key));
+        }
+        if let Ok(key) = RsaPrivateKey::from_pkcs1_pem(pem) {
+            return Ok(Self::Rsa(key));
+        }
+        if let Ok(key) = p256::ecdsa::SigningKey::from_pkcs8_pem(pem) {
+            return Ok(Self::EcdsaP256(key));
+        }
+        if let Ok(key) = p384::ecdsa::SigningKey::from_pkcs8_pem(pem) {
+            return Ok(Self::EcdsaP384(key));
+        }
+        ed25519_dalek::SigningKey::from_pkcs8_pem(pem)
+            .map(Self::Ed25519)
+            .map_err(|_| {
+                Lib3mfError::Validation(
+                    "Invalid private key format: expected an RSA, P-256, P-384 or Ed25519 key"
+                        .to_string(),
+                )
+            })
+    }
+
+    /// Returns the algorithm of this key.
+    pub fn algorithm(&self) -> KeyAlgorithm {
+        match self {
+            Self::Rsa(key) => KeyAlgorithm::Rsa(key.size() * 8),
+            Self::EcdsaP256(_) => KeyAlgorithm::EcdsaP256,
+            Self::EcdsaP384(_) => KeyAlgorithm::EcdsaP384,
+            Self::Ed25519(_) => KeyAlgorithm::Ed25519,
+        }
+    }
+
+    /// Returns the public half, for [`verify_signature_with_key`](crate::crypto::verification::verify_signature_with_key).
+    pub fn verification_key(&self) -> VerificationKey {
+        match self {
+            Self::Rsa(key) => VerificationKey::Rsa(RsaPublicKey::from(key)),
+            Self::EcdsaP256(key) => VerificationKey::EcdsaP256(*key.verifying_key()),
+            Self::EcdsaP384(key) => VerificationKey::EcdsaP384(*key.verifying_key()),
+            Self::Ed25519(key) => VerificationKey::Ed25519(key.verifying_key()),
+        }
+    }
+
+    /// Returns the XML-DSig `SignatureMethod` algorithm URI this key signs with.
+    pub fn signature_method(&self) -> &'static str {
+        self.verification_key().signature_method()
+    }
+
+    /// Signs `data` (the canonicalized `<SignedInfo>`) and returns the raw `SignatureValue`.
+    ///
+    /// The algorithm is [`signature_method`](Self::signature_method); ECDSA signatures use
+    /// the XML-DSig fixed-size `r || s` encoding.
+    pub fn sign(&self, data: &[u8]) -> Result<Vec<u8>> {
+        use rsa::signature::{SignatureEncoding, Signer};
+
+        let signature = match self {
+            Self::Rsa(key) => rsa::pkcs1v15::SigningKey::<Sha256>::new(key.clone())
+                .try_sign(data)
+                .map(|s| s.to_vec()),
+            Self::EcdsaP256(key) => {
+                Signer::<p256::ecdsa::Signature>::try_sign(key, data).map(|s| s.to_vec())
+            }
+            Self::EcdsaP384(key) => {
+                Signer::<p384::ecdsa::Signature>::try_sign(key, data).map(|s| s.to_vec())
+            }
+            Self::Ed25519(key) => key.try_sign(data).map(|s| s.to_vec()),
+        };
+        signature.map_err(|e| Lib3mfError::EncryptionError(format!("Signing failed: {}", e)))
+    }
+
+    /// Returns the RSA key, for key wrapping with [`KeyManager`](crate::crypto::keys::KeyManager).
+    pub fn as_rsa(&self) -> Option<&RsaPrivateKey> {
+        match self {
+            Self::Rsa(key) => Some(key),
+            _ => None,
+        }
+    }
+
+    /// Encodes the private key as PKCS#8 PEM.
+    pub fn to_pem(&self) -> Result<String> {
+        let pem = match self {
+            Self::Rsa(key) => key.to_pkcs8_pem(LineEnding::LF),
+            Self::EcdsaP256(key) => key.to_pkcs8_pem(LineEnding::LF),
+            Self::EcdsaP384(key) => key.to_pkcs8_pem(LineEnding::LF),
+            Self::Ed25519(key) => key.to_pkcs8_pem(LineEnding::LF),
+        }
+        .map_err(|e| Lib3mfError::EncryptionError(format!("Key encoding failed: {}", e)))?;
+        Ok(pem.to_string())
+    }
+
+    /// Encodes the public key as SubjectPublicKeyInfo PEM.
+    pub fn public_key_pem(&self) -> Result<String> {
+        match self {
+            Self::Rsa(key) => RsaPublicKey::from(key).to_public_key_pem(LineEnding::LF),
+            Self::EcdsaP256(key) => key.verifying_key().to_public_key_pem(LineEnding::LF),
+            Self::EcdsaP384(key) => key.verifying_key().to_public_key_pem(LineEnding::LF),
+            Self::Ed25519(key) => key.verifying_key().to_public_key_pem(LineEnding::LF),
+        }
+        .map_err(|e| Lib3mfError::EncryptionError(format!("Key encoding failed: {}", e)))
+    }
+
+    /// Issues a self-signed X.509 end-entity certificate for this key, as PEM.
+    ///
+    /// `subject` is an RFC 4514 distinguished name such as `"CN=Test,O=Example"`. The
+    /// certificate is valid from now for `validity_days` days and has a random serial
+    /// number. RSA certificates also allow key encipherment, for Secure Content recipients.
+    pub fn self_signed_certificate(&self, subject: &str, validity_days: u32) -> Result<String> {
+        let subject = Name::from_str(subject).map_err(|e| {
+            Lib3mfError::Validation(format!("Invalid certificate subject '{}': {}", subject, e))
+        })?;
+        let validity = Validity::from_now(Duration::from_secs(u64::from(validity_days) * 86_400))
+            .map_err(cert_error)?;
+        let profile = Profile::Leaf {
+            issuer: subject.clone(),
+            enable_key_agreement: false,
+            enable_key_encipherment: matches!(self, Self::Rsa(_)),
+        };
+
+        // Positive 128-bit serial: clear the sign bit of the DER integer
+        let mut serial = [0u8; 16];
+        OsRng.fill_bytes(&mut serial);
+        serial[0] &= 0x7f;
+        let serial = SerialNumber::new(&serial).map_err(cert_error)?;
+
+        let certificate = match self {
+            Self::Rsa(key) => {
+                let signer = rsa::pkcs1v15::SigningKey::<Sha256>::new(key.clone());
+                let spki = SubjectPublicKeyInfoOwned::from_key(RsaPublicKey::from(key))
+                    .map_err(cert_error)?;
+                CertificateBuilder::new(profile, serial, validity, subject, spki, &signer)
+                    .map_err(cert_error)?
+                    .build::<rsa::pkcs1v15::Signature>()
+            }
+            Self::EcdsaP256(key) => {
+                let spki = SubjectPublicKeyInfoOwned::from_key(*key.verifying_key())
+                    .map_err(cert_error)?;
+                CertificateBuilder::new(profile, serial, validity, subject, spki, key)
+                    .map_err(cert_error)?
+                    .build::<p256::ecdsa::DerSignature>()
+            }
+            Self::EcdsaP384(key) => {
+                let spki = SubjectPublicKeyInfoOwned::from_key(*key.verifying_key())
+                    .map_err(cert_error)?;
+                CertificateBuilder::new(profile, serial, validity, subject, spki, key)
+                    .map_err(cert_error)?
+                    .build::<p384::ecdsa::DerSignature>()
+            }
+            Self::Ed25519(key) => {
+                let spki =
+                    SubjectPublicKeyInfoOwned::from_key(key.verifying_key()).map_err(cert_error)?;
+                // ed25519 signatures lack a BIT STRING encoding, so sign the TBS by hand
+                let mut builder =
+                    CertificateBuilder::new(profile, serial, validity, subject, spki, key)
+                        .map_err(cert_error)?;
+                let tbs = builder.finalize().map_err(cert_error)?;
+                let signature =
+                    BitString::from_bytes(&key.sign(&tbs).to_bytes()).map_err(cert_error)?;
+                builder.assemble(signature)
+            }
+        }
+        .map_err(cert_error)?;
+
+        certificate.to_pem(LineEnding::LF).map_err(cert_error)
+    }
+}
+
+fn cert_error(e: impl std::fmt::Display) -> Lib3mfError {
+    Lib3mfError::EncryptionError(format!("Certificate generation failed: {}", e))
+}
@@ -0,0 +1,103 @@
+//! Access-right management for Secure Content key stores.
+//!
+//! Each [`ResourceDataGroup`] protects its resources with one content encryption key (CEK).
+//! A consumer may decrypt them if the group holds an [`AccessRight`] with the CEK wrapped
+//! by that consumer's RSA public key. Granting access to a new consumer therefore requires
+//! the plain CEK, which an existing consumer recovers with its private key.
+
+use crate::crypto::keys::KeyManager;
+use crate::error::{Lib3mfError, Result};
+use crate::model::secure_content::*;
+use rand::RngCore;
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use uuid::Uuid;
+
+/// Key wrapping algorithm recorded in access rights created by this module.
+pub const RSA_OAEP: &str = "http://www.w3.org/2001/04/xmlenc#rsa-oaep-mgf1p";
+
+impl KeyStore {
+    /// Adds a resource data group with a freshly generated 256-bit content key.
+    ///
+    /// Returns the group's key UUID and the plain CEK, which is needed to encrypt the
+    /// group's resources and to [`grant_access`](KeyStore::grant_access).
+    pub fn create_resource_data_group(&mut self) -> Result<(Uuid, Vec<u8>)> {
+        let key_uuid = Uuid::new_v4();
+        let mut cek = vec![0u8; 32];
+        rand::rngs::OsRng.fill_bytes(&mut cek);
+        self.add_resource_data_group(key_uuid)?;
+        Ok((key_uuid, cek))
+    }
+
+    /// Recovers the content key of group `key_uuid` using `consumer_id`'s private key.
+    pub fn unwrap_content_key(
+        &self,
+        key_uuid: Uuid,
+        consumer_id: &str,
+        private_key: &RsaPrivateKey,
+    ) -> Result<Vec<u8>> {
+        let right = self
+            .group(key_uuid)?
+            .access_right(consumer_id)
+            .ok_or_else(|| {
+                Lib3mfError::Validation(format!(
+                    "No access right for consumer {} in group {}",
+                    consumer_id, key_uuid
+                ))
+            })?;
+        KeyManager::unwrap_key(private_key, &right.wrapped_key)
+    }
+
+    /// Grants `consumer_id` access to group `key_uuid` by wrapping `cek` with its public key.
+    ///
+    /// The consumer must already be registered. An existing access right for the consumer
+    /// is replaced.
+    pub fn grant_access(
+        &mut self,
+        key_uuid: Uuid,
+        consumer_id: &str,
+        cek: &[u8],
+        public_key: &RsaPublicKey,
+    ) -> Result<()> {
+        if self.consumer(consumer_id).is_none() {
+            return Err(Lib3mfError::Validation(format!(
+                "Consumer {} not found in KeyStore",
+                consumer_id
+            )));
+        }
+        let wrapped_key = KeyManager::wrap_key(public_key, cek)?;
+        self.group_mut(key_uuid)?.set_access_right(AccessRight {
+            consumer_id: consumer_id.to_string(),
+            algorithm: RSA_OAEP.to_string(),
+            wrapped_key,
+        });
+        Ok(())
+    }
+
+    /// Re-wraps the content key of group `key_uuid` for `consumer_id`.
+    ///
+    /// `grantor_id` must hold an access right to the group; its `grantor_key` unwraps the
+    /// content key, which is then wrapped with `public_key` for the new consumer.
+    pub fn share_access(
+        &mut self,
+        key_uuid: Uuid,
+        grantor_id: &str,
+        grantor_key: &RsaPrivateKey,
+        consumer_id: &str,
+        public_key: &RsaPublicKey,
+    ) -> Result<()> {
+        let cek = self.unwrap_content_key(key_uuid, grantor_id, grantor_key)?;
+        self.grant_access(key_uuid, consumer_id, &cek, public_key)
+    }
+
+    fn group(&self, key_uuid: Uuid) -> Result<&ResourceDataGroup> {
+        self.resource_data_group(key_uuid).ok_or_else(|| {
+            Lib3mfError::Validation(format!("KeyUUID {} not found in KeyStore", key_uuid))
+        })
+    }
+
+    fn group_mut(&mut self, key_uuid: Uuid) -> Result<&mut ResourceDataGroup> {
+        self.resource_data_group_mut(key_uuid).ok_or_else(|| {
+            Lib3mfError::Validation(format!("KeyUUID {} not found in KeyStore", key_uuid))
+        })
+    }
+}
@@ -1,4 +1,6 @@
 pub mod context;
 pub mod encryption;
+pub mod keygen;
 pub mod keys;
+pub mod keystore;
 pub mod verification;
@@ -2,14 +2,140 @@ use crate::error::{Lib3mfError, Result};
 use crate::model::crypto::*;
 use base64::prelude::*;
 use rsa::RsaPublicKey;
+use rsa::pkcs8::DecodePublicKey;
 use rsa::signature::Verifier;
 use sha1::Sha1;
-use sha2::{Digest, Sha256};
+use sha2::{Digest, Sha256, Sha384, Sha512};
 use x509_parser::prelude::FromDer;
 
+/// XML-DSig `SignatureMethod` for RSA PKCS#1 v1.5 with SHA-256.
+pub const RSA_SHA256: &str = "http://www.w3.org/2001/04/xmldsig-more#rsa-sha256";
+/// XML-DSig `SignatureMethod` for ECDSA with SHA-256.
+pub const ECDSA_SHA256: &str = "http://www.w3.org/2001/04/xmldsig-more#ecdsa-sha256";
+/// XML-DSig `SignatureMethod` for ECDSA with SHA-384.
+pub const ECDSA_SHA384: &str = "http://www.w3.org/2001/04/xmldsig-more#ecdsa-sha384";
+/// XML-DSig `SignatureMethod` for Ed25519 (RFC 9231).
+pub const ED25519: &str = "http://www.w3.org/2021/04/xmldsig-more#eddsa-ed25519";
+
+/// A public key that can check XML digital signatures.
+///
+/// The signature algorithm follows from the key type; see [`VerificationKey::signature_method`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationKey {
+    /// RSA key, verifying `rsa-sha256`.
+    Rsa(RsaPublicKey),
+    /// ECDSA P-256 key, verifying `ecdsa-sha256`.
+    EcdsaP256(p256::ecdsa::VerifyingKey),
+    /// ECDSA P-384 key, verifying `ecdsa-sha384`.
+    EcdsaP384(p384::ecdsa::VerifyingKey),
+    /// Ed25519 key, verifying `eddsa-ed25519`.
+    Ed25519(ed25519_dalek::VerifyingKey),
+}
+
+impl VerificationKey {
+    /// Reads a DER-encoded SubjectPublicKeyInfo holding an RSA, P-256, P-384 or Ed25519 key.
+    pub fn from_spki_der(der: &[u8]) -> Result<Self> {
+        if let Ok(key) = RsaPublicKey::from_public_key_der(der) {
+            return Ok(Self::Rsa(key));
+        }
+        if let Ok(key) = p256::ecdsa::VerifyingKey::from_public_key_der(der) {
+            return Ok(Self::EcdsaP256(key));
+        }
+        if let Ok(key) = p384::ecdsa::VerifyingKey::from_public_key_der(der) {
+            return Ok(Self::EcdsaP384(key));
+        }
+        ed25519_dalek::VerifyingKey::from_public_key_der(der)
+            .map(Self::Ed25519)
+            .map_err(|_| {
+                Lib3mfError::Validation(
+                    "Unsupported public key: expected an RSA, P-256, P-384 or Ed25519 key"
+                        .to_string(),
+                )
+            })
+    }
+
+    /// Reads the public key of a DER-encoded X.509 certificate.
+    pub fn from_certificate_der(der: &[u8]) -> Result<Self> {
+        let (_, cert) = x509_parser::certificate::X509Certificate::from_der(der)
+            .map_err(|e| Lib3mfError::Validation(format!("Invalid X509 certificate: {}", e)))?;
+        Self::from_spki_der(cert.tbs_certificate.subject_pki.raw)
+    }
+
+    /// Returns the XML-DSig `SignatureMethod` algorithm URI used with this key.
+    pub fn signature_method(&self) -> &'static str {
+        match self {
+            Self::Rsa(_) => RSA_SHA256,
+            Self::EcdsaP256(_) => ECDSA_SHA256,
+            Self::EcdsaP384(_) => ECDSA_SHA384,
+            Self::Ed25519(_) => ED25519,
+        }
+    }
+
+    /// Checks a raw `SignatureValue` over `data`.
+    ///
+    /// `algorithm` must be the method belonging to this key type. ECDSA signatures use the
+    /// XML-DSig fixed-size `r || s` encoding; DER-encoded signatures are accepted as well.
+    pub fn verify(&self, algorithm: &str, data: &[u8], signature: &[u8]) -> Result<()> {
+        if algorithm != self.signature_method() {
+            return Err(Lib3mfError::Validation(format!(
+                "Signature method {} does not match the {} signing key",
+                algorithm,
+                self.key_type()
+            )));
+        }
+        let invalid = |e: rsa::signature::Error| {
+            Lib3mfError::Validation(format!(
+                "Invalid {} signature format: {}",
+                self.key_type(),
+                e
+            ))
+        };
+        let verified = match self {
+            Self::Rsa(key) => {
+                let signature = rsa::pkcs1v15::Signature::try_from(signature).map_err(invalid)?;
+                rsa::pkcs1v15::VerifyingKey::<Sha256>::new(key.clone()).verify(data, &signature)
+            }
+            Self::EcdsaP256(key) => {
+                let signature = p256::ecdsa::Signature::from_slice(signature)
+                    .or_else(|_| p256::ecdsa::Signature::from_der(signature))
+                    .map_err(invalid)?;
+                key.verify(data, &signature)
+            }
+            Self::EcdsaP384(key) => {
+                let signature = p384::ecdsa::Signature::from_slice(signature)
+                    .or_else(|_| p384::ecdsa::Signature::from_der(signature))
+                    .map_err(invalid)?;
+                key.verify(data, &signature)
+            }
+            Self::Ed25519(key) => {
+                let signature = ed25519_dalek::Signature::from_slice(signature).map_err(invalid)?;
+                key.verify_strict(data, &signature)
+            }
+        };
+        verified
+            .map_err(|e| Lib3mfError::Validation(format!("Signature verification failed: {}", e)))
+    }
+
+    fn key_type(&self) -> &'static str {
+        match self {
+            Self::Rsa(_) => "RSA",
+            Self::EcdsaP256(_) => "ECDSA P-256",
+            Self::EcdsaP384(_) => "ECDSA P-384",
+            Self::Ed25519(_) => "Ed25519",
+        }
+    }
+}
+
+impl From<RsaPublicKey> for VerificationKey {
+    fn from(key: RsaPublicKey) -> Self {
+        Self::Rsa(key)
+    }
+}
+
 /// Verifies a 3MF XML digital signature.
 ///
-/// `public_key`: The RSA public key to use for verification.
+/// `public_key`: The RSA public key to use for verification. Use
+///               [`verify_signature_with_key`] for ECDSA and Ed25519 keys.
 /// `content_resolver`: A closure that takes a URI and returns the content bytes.
 /// `signed_info_bytes`: The RAW canonicalized bytes of the `<SignedInfo>` element.
 ///                      Note: This is critical. The parser must extract the exact bytes used for signing.
@@ -22,6 +148,26 @@ pub fn verify_signature<F>(
     content_resolver: F,
     signed_info_bytes: &[u8],
 ) -> Result<bool>
+where
+    F: Fn(&str) -> Result<Vec<u8>>,
+{
+    verify_signature_with_key(
+        signature,
+        &VerificationKey::Rsa(public_key.clone()),
+        content_resolver,
+        signed_info_bytes,
+    )
+}
+
+/// Verifies a 3MF XML digital signature with an RSA, ECDSA or Ed25519 key.
+///
+/// Works like [`verify_signature`]; the `SignatureMethod` must match the key type.
+pub fn verify_signature_with_key<F>(
+    signature: &Signature,
+    key: &VerificationKey,
+    content_resolver: F,
+    signed_info_bytes: &[u8],
+) -> Result<bool>
 where
     F: Fn(&str) -> Result<Vec<u8>>,
 {
@@ -32,33 +178,24 @@ where
 
     // 2. Verify SignatureValue
     let sig_value = BASE64_STANDARD
-        .decode(&signature.signature_value.value)
+        .decode(
+            signature
+                .signature_value
+                .value
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .collect::<String>(),
+        )
         .map_err(|e| Lib3mfError::Validation(format!("Invalid base64 signature: {}", e)))?;
 
-    // 3MF uses RSA-SHA256 usually for SignatureMethod.
-    // Check algo.
-    match signature.signed_info.signature_method.algorithm.as_str() {
-        "http://www.w3.org/2001/04/xmldsig-more#rsa-sha256" => {
-            // RSA-SHA256
-            let verifying_key = rsa::pkcs1v15::VerifyingKey::<Sha256>::new(public_key.clone());
-            let rsa_signature =
-                rsa::pkcs1v15::Signature::try_from(sig_value.as_slice()).map_err(|e| {
-                    Lib3mfError::Validation(format!("Invalid RSA signature format: {}", e))
-                })?;
-
-            verifying_key
-                .verify(signed_info_bytes, &rsa_signature)
-                .map_err(|e| {
-                    Lib3mfError::Validation(format!("Signature verification failed: {}", e))
-                })?;
-        }
-        _ => {
-            return Err(Lib3mfError::Validation(format!(
-                "Unsupported signature method: {}",
-                signature.signed_info.signature_method.algorithm
-            )));
-        }
+    let algorithm = signature.signed_info.signature_method.algorithm.as_str();
+    if ![RSA_SHA256, ECDSA_SHA256, ECDSA_SHA384, ED25519].contains(&algorithm) {
+        return Err(Lib3mfError::Validation(format!(
+            "Unsupported signature method: {}",
+            algorithm
+        )));
     }
+    key.verify(algorithm, signed_info_bytes, &sig_value)?;
 
     Ok(true)
 }
@@ -77,6 +214,8 @@ where
             hasher.update(&content);
             hasher.finalize().to_vec()
         }
+        "http://www.w3.org/2001/04/xmldsig-more#sha384" => Sha384::digest(&content).to_vec(),
+        "http://www.w3.org/2001/04/xmlenc#sha512" => Sha512::digest(&content).to_vec(),
         "http://www.w3.org/2000/09/xmldsig#sha1" => {
             let mut hasher = Sha1::new();
             hasher.update(&content);
@@ -114,11 +253,25 @@ pub fn verify_signature_extended<F>(
 where
     F: Fn(&str) -> Result<Vec<u8>>,
 {
-    let key = extract_key_from_signature(signature)?;
-    verify_signature(signature, &key, content_resolver, signed_info_bytes)
+    let key = extract_verification_key(signature)?;
+    verify_signature_with_key(signature, &key, content_resolver, signed_info_bytes)
 }
 
+/// Extracts an RSA public key from KeyInfo.
+///
+/// Fails if the signature carries a non-RSA key; use [`extract_verification_key`] for those.
 pub fn extract_key_from_signature(signature: &Signature) -> Result<RsaPublicKey> {
+    match extract_verification_key(signature)? {
+        VerificationKey::Rsa(key) => Ok(key),
+        other => Err(Lib3mfError::Validation(format!(
+            "Expected an RSA key in KeyInfo, found {}",
+            other.key_type()
+        ))),
+    }
+}
+
+/// Extracts the signing key from KeyInfo: an `RSAKeyValue` or the key of an X.509 certificate.
+pub fn extract_verification_key(signature: &Signature) -> Result<VerificationKey> {
     if let Some(info) = &signature.key_info {
         // 1. Try KeyValue (RSA)
         if let Some(kv) = &info.key_value
@@ -134,9 +287,11 @@ pub fn extract_key_from_signature(signature: &Signature) -> Result<RsaPublicKey>
             let n = rsa::BigUint::from_bytes_be(&n_bytes);
             let e = rsa::BigUint::from_bytes_be(&e_bytes);
 
-            return RsaPublicKey::new(n, e).map_err(|e| {
-                Lib3mfError::Validation(format!("Invalid RSA key components: {}", e))
-            });
+            return RsaPublicKey::new(n, e)
+                .map(VerificationKey::Rsa)
+                .map_err(|e| {
+                    Lib3mfError::Validation(format!("Invalid RSA key components: {}", e))
+                });
         }
 
         // 2. Try X509Data
@@ -149,15 +304,7 @@ pub fn extract_key_from_signature(signature: &Signature) -> Result<RsaPublicKey>
                 .decode(&clean_b64)
                 .map_err(|e| Lib3mfError::Validation(format!("Invalid X509 base64: {}", e)))?;
 
-            let (_, cert) = x509_parser::certificate::X509Certificate::from_der(&cert_der)
-                .map_err(|e| Lib3mfError::Validation(format!("Invalid X509 certificate: {}", e)))?;
-
-            // Extract SPKI
-            // rsa crate can parse PKCS#1 or PKCS#8. SPKI is usually PKCS#8 compatible (public key info).
-            // cert.tbs_certificate.subject_pki contains the SPKI
-            use rsa::pkcs8::DecodePublicKey;
-            return RsaPublicKey::from_public_key_der(cert.tbs_certificate.subject_pki.raw)
-                .map_err(|e| Lib3mfError::Validation(format!("Invalid RSA key in cert: {}", e)));
+            return VerificationKey::from_certificate_der(&cert_der);
         }
     }
     Err(Lib3mfError::Validation(
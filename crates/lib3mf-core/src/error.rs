@@ -21,6 +21,8 @@
 //! - **ResourceNotFound**: Referenced resource ID doesn't exist in the model
 //! - **InvalidStructure**: Malformed XML, missing required elements, spec violations
 //! - **EncryptionError**: Cryptographic operations failed (wrong key, tampered data)
+//! - **EncryptedContent**: The model part is encrypted with the Secure Content extension and
+//!   must be read with a private key
 //! - **FeatureNotEnabled**: Operation requires a cargo feature that wasn't enabled
 //!
 //! ## Usage
@@ -54,6 +56,7 @@
 //! );
 //! ```
 
+use std::fmt;
 use thiserror::Error;
 
 /// The main error type for all fallible operations in lib3mf-core.
@@ -79,10 +82,54 @@ pub enum Lib3mfError {
     #[error("Encryption error: {0}")]
     EncryptionError(String),
 
+    /// The model part is encrypted (Secure Content extension) and cannot be parsed as is.
+    ///
+    /// Read such packages with `parse_encrypted_model` (`crypto` feature) and the private
+    /// key of one of the listed consumers.
+    #[error("Encrypted content: {0}")]
+    EncryptedContent(Box<EncryptedContentInfo>),
+
+    /// The model lists an extension in `requiredextensions` that this library doesn't support.
+    ///
+    /// The core specification requires consumers to reject such models. Parse with
+    /// [`RequiredExtensionPolicy::Warn`](crate::parser::RequiredExtensionPolicy::Warn) to
+    /// read them anyway.
+    #[error("Unsupported required extension: {0}")]
+    UnsupportedExtension(String),
+
     /// The requested operation requires a cargo feature that was not enabled at compile time.
     #[error("Feature not enabled: {0}. Rebuild with `cargo build --features {1}`")]
     FeatureNotEnabled(String, String),
 }
 
+/// Key store details of a package whose model part is encrypted, carried by
+/// [`Lib3mfError::EncryptedContent`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EncryptedContentInfo {
+    /// Archive entry name of the key store part.
+    pub keystore_path: String,
+    /// `UUID` attribute of the key store, if present.
+    pub keystore_uuid: Option<String>,
+    /// IDs of the consumers that hold an access right.
+    pub consumers: Vec<String>,
+    /// Archive entry names of the encrypted parts.
+    pub encrypted_parts: Vec<String>,
+}
+
+impl fmt::Display for EncryptedContentInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} part(s) encrypted, key store '{}'",
+            self.encrypted_parts.len(),
+            self.keystore_path
+        )?;
+        if !self.consumers.is_empty() {
+            write!(f, ", consumers: {}", self.consumers.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
 /// Convenience type alias for `Result<T, Lib3mfError>`.
 pub type Result<T> = std::result::Result<T, Lib3mfError>;
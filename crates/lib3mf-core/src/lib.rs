@@ -57,17 +57,23 @@
 //!
 //! ## Feature Flags
 //!
-//! By default, `lib3mf-core` is built with minimal dependencies (`default = []`). Optional features can be enabled
-//! to add functionality at the cost of additional dependencies:
+//! By default, `lib3mf-core` is built with minimal dependencies (`default = ["vendor", "writer"]`, neither of
+//! which adds a dependency). Optional features can be enabled to add functionality at the cost of additional
+//! dependencies:
 //!
 //! | Feature | Description | Dependency Impact |
 //! |---------|-------------|-------------------|
-//! | `crypto` | Enables Secure Content Extension (digital signatures, encryption) | ~300 crates (rsa, aes-gcm, sha1, sha2, x509-parser, base64) |
+//! | `crypto` | Enables Secure Content Extension (digital signatures, encryption, key generation) | ~300 crates (rsa, p256, p384, ed25519-dalek, aes-gcm, sha1, x509-parser, x509-cert, base64) |
 //! | `parallel` | Enables multi-threaded mesh processing using Rayon | +1 crate |
-//! | `png-validation` | Enables PNG texture validation | +1 crate |
-//! | `full` | Enables all features: `crypto`, `parallel`, `png-validation` | All of the above |
-//!
-//! **Minimal build** (no features): ~154 crates
+//! | `png-validation` | Decodes PNG thumbnails during Strict package validation | +1 crate |
+//! | `simd` | Runtime-detected AVX kernels for bounding boxes, area/volume, and BVH builds | None |
+//! | `tracing` | Timing spans around archive open, parsing, validation and writing ([`trace`]) | +tracing |
+//! | `writer` | Package writing ([`writer`], `Model::write`) and object extraction; on by default | None |
+//! | `vendor` | Bambu Studio project data (plates, filaments, print settings) in [`parser`] and statistics; on by default | None |
+//! | `testutil` | Mesh and model generators (proptest strategies) and writer snapshots for tests; not part of `full` | +proptest |
+//! | `full` | Enables all features: `crypto`, `parallel`, `png-validation`, `simd`, `tracing`, `vendor`, `writer` | All of the above |
+//!
+//! **Minimal build** (default features): ~154 crates
 //! **Full build** (`--all-features`): ~300 crates
 //!
 //! ```toml
@@ -79,6 +85,10 @@
 //! [dependencies]
 //! lib3mf-core = { version = "0.4", features = ["crypto"] }
 //!
+//! # Cargo.toml - read-only build for size-sensitive targets such as WebAssembly
+//! [dependencies]
+//! lib3mf-core = { version = "0.4", default-features = false }
+//!
 //! # Cargo.toml - full-featured build
 //! [dependencies]
 //! lib3mf-core = { version = "0.4", features = ["full"] }
@@ -94,9 +104,14 @@
 //!   Follows an immutable-by-default design philosophy for thread safety and predictability.
 //! - [`validation`]: Progressive validation system with four levels ([`ValidationLevel`]): Minimal (structure),
 //!   Standard (reference integrity), Strict (spec compliance), and Paranoid (geometry analysis with BVH acceleration).
-//! - [`writer`]: Model-to-XML-to-ZIP serialization pipeline. Mirrors the parser module structure but in reverse.
+//! - [`writer`] (feature gated, on by default): Model-to-XML-to-ZIP serialization pipeline. Mirrors the parser
+//!   module structure but in reverse. Requires the `writer` feature.
 //! - [`crypto`] (feature gated): Secure Content Extension support for digital signatures and encryption. Requires
 //!   `features = ["crypto"]` to enable.
+//! - [`testutil`] (feature gated): Generators of valid and deliberately broken meshes and models, and
+//!   golden-file snapshots of writer output, for tests.
+//!   Requires `features = ["testutil"]`.
+//! - [`trace`]: Names of the timing spans emitted with the `tracing` feature.
 //! - [`error`]: Error handling types. All library functions return [`Result<T>`][`crate::Result`] with [`Lib3mfError`]
 //!   for failures. The library never panics on user input.
 //!
@@ -117,7 +132,8 @@
 //!
 //! ## Design Principles
 //!
-//! - **Immutable-by-default**: Model structures use Clone semantics. Mutation happens via explicit repair operations.
+//! - **Immutable-by-default**: Model structures use Clone semantics. Clones share mesh data until one of them
+//!   modifies it ([`model::SharedVec`]). Mutation happens via explicit repair operations.
 //! - **No panics**: All errors are returned as `Result<T, Lib3mfError>`. Invalid input never panics.
 //! - **Trait-based abstraction**: [`ArchiveReader`], [`ArchiveWriter`], and other traits decouple implementation
 //!   from interface.
@@ -131,6 +147,7 @@
 //! [`validation`]: crate::validation
 //! [`writer`]: crate::writer
 //! [`crypto`]: crate::crypto
+//! [`testutil`]: crate::testutil
 //! [`error`]: crate::error
 //! [`ArchiveReader`]: crate::archive::ArchiveReader
 //! [`ArchiveWriter`]: crate::archive::ArchiveWriter
@@ -152,9 +169,13 @@ pub mod crypto;
 pub mod error;
 pub mod model;
 pub mod parser;
+#[cfg(feature = "testutil")]
+pub mod testutil;
+pub mod trace;
 /// Utility types and functions (model diffing, hardware detection, XML canonicalization).
 pub mod utils;
 pub mod validation;
+#[cfg(feature = "writer")]
 pub mod writer;
 
 pub use error::{Lib3mfError, Result};
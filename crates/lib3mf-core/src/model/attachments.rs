@@ -0,0 +1,231 @@
+//! Package parts bound by relationships: print tickets, thumbnails, textures and vendor
+//! parts such as embedded G-code.
+//!
+//! [`Model::attachments`] holds the bytes of each part by archive path, and
+//! [`Model::existing_relationships`] the relationships that bind them to the package or to
+//! a model part. The [`AttachmentRegistry`] returned by [`Model::attachment_registry`]
+//! joins the two, listing every bound part under its relationship type.
+//! [`Model::load_attachments`] fills both from a package, and [`Model::add_attachment`] adds
+//! a part together with its relationship. The package writer writes them back, keeping
+//! package relationships (`_rels/.rels`) of types it doesn't generate itself.
+//!
+//! ```
+//! # #[cfg(feature = "writer")] {
+//! use lib3mf_core::archive::ZipArchiver;
+//! use lib3mf_core::model::{Model, PRINT_TICKET_REL_TYPE};
+//! use std::io::Cursor;
+//!
+//! let mut model = Model::default();
+//! model.add_attachment(
+//!     "3D/3dmodel.model",
+//!     PRINT_TICKET_REL_TYPE,
+//!     "Metadata/print_ticket.xml",
+//!     b"<PrintTicket />".to_vec(),
+//! );
+//! let mut buffer = Cursor::new(Vec::new());
+//! model.write(&mut buffer)?;
+//!
+//! let mut archive = ZipArchiver::new(Cursor::new(buffer.into_inner()))?;
+//! let mut copy = Model::default();
+//! copy.load_attachments(&mut archive, |path| path == "3D/3dmodel.model")?;
+//! let registry = copy.attachment_registry();
+//! let ticket = &registry.of_type(PRINT_TICKET_REL_TYPE)[0];
+//! assert_eq!(ticket.source, "3D/3dmodel.model");
+//! assert_eq!(ticket.path, "Metadata/print_ticket.xml");
+//! assert_eq!(ticket.data, Some(&b"<PrintTicket />"[..]));
+//! # }
+//! # Ok::<(), lib3mf_core::error::Lib3mfError>(())
+//! ```
+//!
+//! [`Model::attachments`]: crate::model::Model::attachments
+//! [`Model::existing_relationships`]: crate::model::Model::existing_relationships
+
+use crate::archive::opc::{Relationship, parse_relationships};
+use crate::archive::{
+    ArchiveReader, MODEL_REL_TYPE, normalize_part_name, part_rels_path, rels_source_part,
+    resolve_part_target,
+};
+use crate::error::Result;
+use crate::model::Model;
+use std::collections::BTreeMap;
+
+/// Relationship type of a print ticket, binding print settings to a model part.
+pub const PRINT_TICKET_REL_TYPE: &str =
+    "http://schemas.microsoft.com/3dmanufacturing/2013/01/printticket";
+
+/// Content type of a print ticket part.
+pub const PRINT_TICKET_CONTENT_TYPE: &str = "application/vnd.ms-printing.printticket+xml";
+
+/// A package part bound to the package or a model part by a relationship.
+#[derive(Debug, Clone)]
+pub struct Attachment<'a> {
+    /// Part the relationship belongs to, or the empty string for a package relationship
+    pub source: String,
+    /// The relationship binding the part
+    pub relationship: &'a Relationship,
+    /// Archive path of the part the relationship targets
+    pub path: String,
+    /// Content of the part, or `None` if it isn't among the model's attachments
+    pub data: Option<&'a [u8]>,
+}
+
+/// The attachments of a model by relationship type, from [`Model::attachment_registry`].
+///
+/// Relationships to model parts and external targets aren't attachments and aren't listed.
+#[derive(Debug, Clone, Default)]
+pub struct AttachmentRegistry<'a> {
+    by_type: BTreeMap<&'a str, Vec<Attachment<'a>>>,
+}
+
+impl<'a> AttachmentRegistry<'a> {
+    /// Returns the relationship types with at least one attachment, in sorted order.
+    pub fn rel_types(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.by_type.keys().copied()
+    }
+
+    /// Returns the attachments bound by relationships of `rel_type`.
+    pub fn of_type(&self, rel_type: &str) -> &[Attachment<'a>] {
+        self.by_type.get(rel_type).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns every attachment, grouped by relationship type.
+    pub fn iter(&self) -> impl Iterator<Item = &Attachment<'a>> {
+        self.by_type.values().flatten()
+    }
+
+    /// Returns the number of attachments.
+    pub fn len(&self) -> usize {
+        self.by_type.values().map(Vec::len).sum()
+    }
+
+    /// Returns whether there are no attachments.
+    pub fn is_empty(&self) -> bool {
+        self.by_type.is_empty()
+    }
+}
+
+impl Model {
+    /// Returns the attachments bound by the model's relationships, keyed by relationship
+    /// type.
+    ///
+    /// Relationships are listed by relationships part, then in document order.
+    pub fn attachment_registry(&self) -> AttachmentRegistry<'_> {
+        let mut rels_paths: Vec<&String> = self.existing_relationships.keys().collect();
+        rels_paths.sort();
+
+        let mut registry = AttachmentRegistry::default();
+        for rels_path in rels_paths {
+            let source = rels_source_part(rels_path);
+            for rel in &self.existing_relationships[rels_path] {
+                if rel.rel_type == MODEL_REL_TYPE
+                    || rel.target_mode.eq_ignore_ascii_case("external")
+                {
+                    continue;
+                }
+                let path = resolve_part_target(&source, &rel.target);
+                registry
+                    .by_type
+                    .entry(rel.rel_type.as_str())
+                    .or_default()
+                    .push(Attachment {
+                        source: source.clone(),
+                        relationship: rel,
+                        data: self.attachment(&path),
+                        path,
+                    });
+            }
+        }
+        registry
+    }
+
+    /// Returns the content of the attachment at `path`, however either side spells the
+    /// part name.
+    pub fn attachment(&self, path: &str) -> Option<&[u8]> {
+        let path = normalize_part_name(path);
+        self.attachments
+            .get(&path)
+            .or_else(|| {
+                self.attachments
+                    .iter()
+                    .find(|(name, _)| normalize_part_name(name) == path)
+                    .map(|(_, data)| data)
+            })
+            .map(Vec::as_slice)
+    }
+
+    /// Adds a part at `path` bound to `source` by a relationship of `rel_type`, and returns
+    /// the relationship's ID.
+    ///
+    /// `source` is the model part the relationship belongs to (e.g. `3D/3dmodel.model` for
+    /// a print ticket), or the empty string for a package relationship. A part already at
+    /// `path` is replaced; a relationship of the same type to it is kept.
+    pub fn add_attachment(
+        &mut self,
+        source: &str,
+        rel_type: &str,
+        path: &str,
+        data: Vec<u8>,
+    ) -> String {
+        let path = normalize_part_name(path);
+        self.attachments
+            .retain(|name, _| normalize_part_name(name) != path);
+        self.attachments.insert(path.clone(), data);
+
+        let source = normalize_part_name(source);
+        let rels_path = if source.is_empty() {
+            "_rels/.rels".to_string()
+        } else {
+            part_rels_path(&source)
+        };
+        let rels = self.existing_relationships.entry(rels_path).or_default();
+        if let Some(rel) = rels
+            .iter()
+            .find(|r| r.rel_type == rel_type && resolve_part_target(&source, &r.target) == path)
+        {
+            return rel.id.clone();
+        }
+        let id = (0..)
+            .map(|n| format!("rel_attachment_{}", n))
+            .find(|id| !rels.iter().any(|r| &r.id == id))
+            .unwrap_or_default();
+        rels.push(Relationship {
+            id: id.clone(),
+            rel_type: rel_type.to_string(),
+            target: format!("/{}", path),
+            target_mode: "Internal".to_string(),
+        });
+        id
+    }
+
+    /// Loads the parts of a package into [`attachments`](Model::attachments) and its
+    /// relationships parts into [`existing_relationships`](Model::existing_relationships),
+    /// so that writing the model preserves them.
+    ///
+    /// `[Content_Types].xml`, which the package writer regenerates, and the entries `skip`
+    /// returns true for (typically the model part being edited) are left out. Relationships
+    /// parts that can't be parsed are left out too, rather than copied as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an entry can't be read.
+    pub fn load_attachments<R: ArchiveReader>(
+        &mut self,
+        archive: &mut R,
+        skip: impl Fn(&str) -> bool,
+    ) -> Result<()> {
+        for entry in archive.list_entries()? {
+            if entry == "[Content_Types].xml" || skip(&entry) {
+                continue;
+            }
+            let data = archive.read_entry(&entry)?;
+            if entry.ends_with(".rels") {
+                if let Ok(rels) = parse_relationships(&data) {
+                    self.existing_relationships.insert(entry, rels);
+                }
+                continue;
+            }
+            self.attachments.insert(entry, data);
+        }
+        Ok(())
+    }
+}
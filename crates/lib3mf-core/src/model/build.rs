@@ -1,5 +1,6 @@
-use crate::model::ResourceId;
-use glam::Mat4;
+use crate::error::{Lib3mfError, Result};
+use crate::model::{Metadata, Model, ResourceId};
+use glam::{Mat4, Vec3};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -24,6 +25,7 @@ use uuid::Uuid;
 ///     part_number: None,
 ///     transform: glam::Mat4::IDENTITY,
 ///     printable: None,
+///     metadata: Default::default(),
 /// });
 /// assert_eq!(build.items.len(), 1);
 /// ```
@@ -31,6 +33,10 @@ use uuid::Uuid;
 pub struct Build {
     /// The list of objects to manufacture.
     pub items: Vec<BuildItem>,
+
+    /// Production Extension UUID identifying the build (optional).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<Uuid>,
 }
 
 /// A reference to an object that should be manufactured.
@@ -68,6 +74,172 @@ pub struct BuildItem {
     /// `Some(true)` means printable, `Some(false)` means display-only, `None` means unspecified.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub printable: Option<bool>,
+
+    /// Item-level `<metadata>` entries, from the item's `<metadatagroup>`.
+    #[serde(default, skip_serializing_if = "Metadata::is_empty")]
+    pub metadata: Metadata,
+}
+
+impl Build {
+    /// Returns the number of build items.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if the build has no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Iterates over the build items in document order.
+    pub fn iter(&self) -> std::slice::Iter<'_, BuildItem> {
+        self.items.iter()
+    }
+
+    /// Iterates mutably over the build items in document order.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, BuildItem> {
+        self.items.iter_mut()
+    }
+
+    /// Returns the build item at `index`.
+    pub fn get(&self, index: usize) -> Option<&BuildItem> {
+        self.items.get(index)
+    }
+
+    /// Returns the build item at `index` mutably.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut BuildItem> {
+        self.items.get_mut(index)
+    }
+
+    /// Removes and returns the build item at `index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Lib3mfError::Validation` if `index` is out of range.
+    pub fn remove_item(&mut self, index: usize) -> Result<BuildItem> {
+        self.check_index(index)?;
+        Ok(self.items.remove(index))
+    }
+
+    /// Appends a copy of the build item at `index`, translated by `offset`, and returns the
+    /// index of the copy. The copy gets no UUID, since UUIDs must be unique across the build.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Lib3mfError::Validation` if `index` is out of range.
+    pub fn duplicate_item(&mut self, index: usize, offset: Vec3) -> Result<usize> {
+        self.check_index(index)?;
+        let mut item = self.items[index].clone();
+        item.uuid = None;
+        item.transform = Mat4::from_translation(offset) * item.transform;
+        self.items.push(item);
+        Ok(self.items.len() - 1)
+    }
+
+    /// Moves the build item at `from` so that it ends up at index `to`, shifting the items
+    /// in between.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Lib3mfError::Validation` if either index is out of range.
+    pub fn move_item(&mut self, from: usize, to: usize) -> Result<()> {
+        self.check_index(from)?;
+        self.check_index(to)?;
+        let item = self.items.remove(from);
+        self.items.insert(to, item);
+        Ok(())
+    }
+
+    fn check_index(&self, index: usize) -> Result<()> {
+        if index >= self.items.len() {
+            return Err(Lib3mfError::Validation(format!(
+                "Build item index {} out of range (build has {} items)",
+                index,
+                self.items.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl BuildItem {
+    /// Creates a build item for `object_id` with the given transform and no optional
+    /// attributes.
+    pub fn new(object_id: ResourceId, transform: Mat4) -> Self {
+        Self {
+            object_id,
+            uuid: None,
+            path: None,
+            part_number: None,
+            transform,
+            printable: None,
+            metadata: Metadata::new(),
+        }
+    }
+}
+
+impl Model {
+    /// Appends a build item for `object_id` and returns its index.
+    ///
+    /// # Errors
+    ///
+    /// Fails the same checks the validator applies to build items:
+    ///
+    /// - `Lib3mfError::ResourceNotFound` if the object does not exist (validation code 3002)
+    /// - `Lib3mfError::Validation` if its type cannot be in the build (validation code 3010)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib3mf_core::model::{Geometry, Mesh, Model, Object, ObjectType, ResourceId};
+    ///
+    /// let mut model = Model::default();
+    /// let mut object = Object {
+    ///     id: ResourceId(1),
+    ///     object_type: ObjectType::Model,
+    ///     name: None,
+    ///     part_number: None,
+    ///     uuid: None,
+    ///     pid: None,
+    ///     pindex: None,
+    ///     thumbnail: None,
+    ///     geometry: Geometry::Mesh(Mesh::new()),
+    /// };
+    /// model.resources.add_object(object.clone()).unwrap();
+    /// object.id = ResourceId(2);
+    /// object.object_type = ObjectType::Other;
+    /// model.resources.add_object(object).unwrap();
+    ///
+    /// assert_eq!(model.add_build_item(ResourceId(1), glam::Mat4::IDENTITY).unwrap(), 0);
+    /// assert!(model.add_build_item(ResourceId(2), glam::Mat4::IDENTITY).is_err());
+    /// assert!(model.add_build_item(ResourceId(3), glam::Mat4::IDENTITY).is_err());
+    /// ```
+    pub fn add_build_item(&mut self, object_id: ResourceId, transform: Mat4) -> Result<usize> {
+        let index = self.build.items.len();
+        check_build_reference(self, index, object_id)?;
+        self.build.items.push(BuildItem::new(object_id, transform));
+        Ok(index)
+    }
+}
+
+/// Checks that a build item at `index` may reference `object_id`, mirroring the
+/// validator's build reference checks.
+pub(crate) fn check_build_reference(
+    model: &Model,
+    index: usize,
+    object_id: ResourceId,
+) -> Result<()> {
+    let object = model
+        .resources
+        .get_object(object_id)
+        .ok_or(Lib3mfError::ResourceNotFound(object_id.0))?;
+    if !object.object_type.can_be_in_build() {
+        return Err(Lib3mfError::Validation(format!(
+            "Build item {} references object {} with type '{}' which cannot be in build",
+            index, object_id.0, object.object_type
+        )));
+    }
+    Ok(())
 }
 
 fn default_transform() -> Mat4 {
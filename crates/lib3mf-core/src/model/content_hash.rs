@@ -0,0 +1,955 @@
+//! Canonical content digests for detecting equivalent models.
+//!
+//! [`Model::content_hash`] digests what gets printed — geometry, build placement and
+//! resolved material properties — and nothing about how it was serialized. Two packages
+//! re-saved by different tools hash the same as long as they describe the same parts:
+//!
+//! - XML formatting, attribute order, number formatting and ZIP layout are irrelevant,
+//!   since the digest is computed from the parsed model.
+//! - Resource IDs are not hashed; objects and properties are hashed by content, so
+//!   renumbered resources don't change the digest.
+//! - Metadata, names, part numbers, UUIDs, thumbnails and vendor data are excluded.
+//! - Build items and components are hashed as unordered sets.
+//! - Coordinates are converted to millimetres and quantized to 0.1 µm, so differences
+//!   in unit or float printing precision don't matter.
+//!
+//! Vertex and triangle order are significant: a tool that reorders the mesh produces a
+//! different digest.
+//!
+//! ```
+//! use lib3mf_core::parser::parse_model;
+//!
+//! let model = |unit: &str, size: &str, id: &str| {
+//!     let xml = format!(
+//!         r#"<model unit="{unit}" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+//!         <resources><object id="{id}" type="model"><mesh>
+//!             <vertices><vertex x="0" y="0" z="0" /><vertex x="{size}" y="0" z="0" />
+//!             <vertex x="0" y="{size}" z="0" /></vertices>
+//!             <triangles><triangle v1="0" v2="1" v3="2" /></triangles>
+//!         </mesh></object></resources>
+//!         <build><item objectid="{id}" /></build></model>"#
+//!     );
+//!     parse_model(std::io::Cursor::new(xml)).unwrap()
+//! };
+//!
+//! // 10 mm with resource ID 1 is the same part as 1 cm with resource ID 7.
+//! let a = model("millimeter", "10", "1");
+//! let b = model("centimeter", "1.0", "7");
+//! assert_eq!(a.content_hash(), b.content_hash());
+//! assert_ne!(a.content_hash(), model("millimeter", "12", "1").content_hash());
+//! ```
+
+use crate::archive::{normalize_part_name, part_names_match};
+use crate::model::{
+    BeamLattice, DisplacementMesh, Function, FunctionPort, Geometry, Mesh, Model, Object,
+    ResourceId, Texture2D, TileStyle, Triangle, Unit, VolumeFunctionRef,
+};
+use crate::parser::parse_model;
+use glam::Mat4;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::Cursor;
+
+/// Version tag mixed into every digest; bump when the canonical form changes.
+const VERSION: &[u8] = b"lib3mf-content-hash/2";
+
+/// Quantization step for lengths, in millimetres.
+const LENGTH_STEP: f64 = 1e-4;
+
+/// Quantization step for unitless values (rotation, texture coordinates, mix ratios).
+const RATIO_STEP: f64 = 1e-6;
+
+/// A SHA-256 digest of a model's printable content. See the [module docs](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ContentHash(pub [u8; 32]);
+
+impl ContentHash {
+    /// Returns the digest as a lowercase hex string.
+    pub fn to_hex(&self) -> String {
+        self.to_string()
+    }
+
+    /// Parses a 64-character hex string, as produced by [`to_hex`](Self::to_hex).
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        if hex.len() != 64 || !hex.is_ascii() {
+            return None;
+        }
+        let mut bytes = [0u8; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+        }
+        Some(Self(bytes))
+    }
+}
+
+impl fmt::Display for ContentHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl Model {
+    /// Computes a canonical digest of the model's geometry and materials.
+    ///
+    /// Components and build items referencing other model parts are followed when the
+    /// part is present in [`attachments`](Model::attachments); otherwise only the
+    /// reference itself is hashed. Meshes that are still [lazy](Geometry::LazyMesh) hash
+    /// the same as when parsed eagerly if their part's bytes are in the attachments;
+    /// otherwise only their location is hashed, so call
+    /// [`materialize_meshes`](Model::materialize_meshes) first.
+    pub fn content_hash(&self) -> ContentHash {
+        let parts = load_parts(self);
+        let mut hasher = ContentHasher::new(self, &parts);
+
+        let mut items: Vec<[u8; 32]> = self
+            .build
+            .items
+            .iter()
+            .map(|item| {
+                let part = item.path.as_deref().map(normalize).unwrap_or_default();
+                let mut h = Sha256::new();
+                h.update(hasher.object_digest(&part, item.object_id));
+                hash_transform(&mut h, &item.transform, self.unit);
+                h.update([item.printable.map_or(2, u8::from)]);
+                h.finalize().into()
+            })
+            .collect();
+        items.sort_unstable();
+
+        let mut h = Sha256::new();
+        h.update(VERSION);
+        h.update((items.len() as u64).to_le_bytes());
+        for item in items {
+            h.update(item);
+        }
+        ContentHash(h.finalize().into())
+    }
+
+    /// Computes the canonical digest of a single object, including the components and
+    /// properties it references.
+    ///
+    /// Like [`content_hash`](Self::content_hash), the digest ignores resource IDs, names
+    /// and serialization details, so it survives a write/parse round trip. Returns `None`
+    /// if no object has this ID, or if the object depends on a model part, image or lazy
+    /// mesh whose data is missing from [`attachments`](Model::attachments), since the
+    /// digest would then depend on what happened to be loaded.
+    pub fn object_hash(&self, id: ResourceId) -> Option<ContentHash> {
+        self.resources.get_object(id)?;
+        self.object_hashes().remove(&id)
+    }
+
+    /// Computes [`object_hash`](Self::object_hash) for every object in the model, sharing
+    /// work between objects that reference the same components and properties. Objects
+    /// that depend on unavailable parts or images are omitted.
+    pub fn object_hashes(&self) -> HashMap<ResourceId, ContentHash> {
+        let parts = load_parts(self);
+        let mut hasher = ContentHasher::new(self, &parts);
+        self.resources
+            .iter_objects()
+            .filter_map(|obj| {
+                let missing = hasher.missing;
+                let digest = hasher.object_digest("", obj.id);
+                if hasher.missing > missing {
+                    return None;
+                }
+                let mut h = Sha256::new();
+                h.update(VERSION);
+                h.update(digest);
+                Some((obj.id, ContentHash(h.finalize().into())))
+            })
+            .collect()
+    }
+}
+
+/// Parses the model parts reachable from `root` through `path` references.
+pub(crate) fn load_parts(root: &Model) -> HashMap<String, Model> {
+    let mut parts = HashMap::new();
+    let mut pending: Vec<String> = external_paths(root);
+    while let Some(path) = pending.pop() {
+        if parts.contains_key(&path) {
+            continue;
+        }
+        let data = root
+            .attachments
+            .iter()
+            .find(|(k, _)| normalize(k) == path)
+            .map(|(_, v)| v);
+        if let Some(model) = data.and_then(|d| parse_model(Cursor::new(d)).ok()) {
+            pending.extend(external_paths(&model));
+            parts.insert(path, model);
+        }
+    }
+    parts
+}
+
+fn external_paths(model: &Model) -> Vec<String> {
+    let mut paths: Vec<&str> = model
+        .build
+        .items
+        .iter()
+        .filter_map(|i| i.path.as_deref())
+        .collect();
+    for obj in model.resources.iter_objects() {
+        match &obj.geometry {
+            Geometry::Components(c) => {
+                paths.extend(c.components.iter().filter_map(|c| c.path.as_deref()));
+            }
+            Geometry::BooleanShape(shape) => {
+                paths.extend(shape.base_path.as_deref());
+                paths.extend(shape.operations.iter().filter_map(|o| o.path.as_deref()));
+            }
+            _ => {}
+        }
+    }
+    for stack in model.resources.iter_slice_stacks() {
+        paths.extend(stack.refs.iter().map(|r| r.slice_path.as_str()));
+    }
+    for stack in model.resources.iter_volumetric_stacks() {
+        paths.extend(stack.refs.iter().map(|r| r.path.as_str()));
+    }
+    paths.into_iter().map(normalize).collect()
+}
+
+/// Canonical part name: no leading `/`; the main model part is the empty string.
+pub(crate) fn normalize(path: &str) -> String {
+    let path = normalize_part_name(path);
+    if path.eq_ignore_ascii_case("3D/3dmodel.model") {
+        String::new()
+    } else {
+        path
+    }
+}
+
+fn quantize(value: f64, step: f64) -> i64 {
+    (value / step).round() as i64
+}
+
+fn hash_length(h: &mut Sha256, value: f32, to_mm: f64) {
+    h.update(quantize(value as f64 * to_mm, LENGTH_STEP).to_le_bytes());
+}
+
+fn hash_ratio(h: &mut Sha256, value: f32) {
+    h.update(quantize(value as f64, RATIO_STEP).to_le_bytes());
+}
+
+fn hash_str(h: &mut Sha256, s: &str) {
+    h.update((s.len() as u64).to_le_bytes());
+    h.update(s.as_bytes());
+}
+
+fn hash_transform(h: &mut Sha256, m: &Mat4, unit: Unit) {
+    let to_mm = unit.convert(1.0, Unit::Millimeter);
+    for axis in [m.x_axis, m.y_axis, m.z_axis] {
+        for v in axis.truncate().to_array() {
+            hash_ratio(h, v);
+        }
+    }
+    for v in m.w_axis.truncate().to_array() {
+        hash_length(h, v, to_mm);
+    }
+}
+
+/// Hashes values that have no canonical form of their own via their JSON serialization.
+fn hash_ports(h: &mut Sha256, ports: &[FunctionPort]) {
+    h.update((ports.len() as u64).to_le_bytes());
+    for port in ports {
+        hash_str(h, &port.identifier);
+        hash_str(h, port.port_type.element_name());
+        hash_str(h, port.reference.as_deref().unwrap_or_default());
+    }
+}
+
+fn hash_json<T: Serialize>(h: &mut Sha256, value: &T) {
+    hash_str(h, &serde_json::to_string(value).unwrap_or_default());
+}
+
+struct ContentHasher<'a> {
+    root: &'a Model,
+    parts: &'a HashMap<String, Model>,
+    objects: HashMap<(String, ResourceId), [u8; 32]>,
+    visiting: HashSet<(String, ResourceId)>,
+    /// Slice stacks, volumetric stacks and functions being hashed, to break cycles.
+    references: HashSet<(String, ResourceId)>,
+    textures: HashMap<(String, ResourceId), [u8; 32]>,
+    /// Objects and textures whose digest depends on a part or image that isn't loaded.
+    incomplete: HashSet<(String, ResourceId, bool)>,
+    /// Number of unavailable parts and images encountered so far, including through
+    /// cached digests; a digest is incomplete if hashing it raised the count.
+    missing: usize,
+}
+
+impl<'a> ContentHasher<'a> {
+    fn new(root: &'a Model, parts: &'a HashMap<String, Model>) -> Self {
+        Self {
+            root,
+            parts,
+            objects: HashMap::new(),
+            visiting: HashSet::new(),
+            references: HashSet::new(),
+            textures: HashMap::new(),
+            incomplete: HashSet::new(),
+            missing: 0,
+        }
+    }
+
+    fn model(&self, part: &str) -> Option<&'a Model> {
+        if part.is_empty() {
+            Some(self.root)
+        } else {
+            self.parts.get(part)
+        }
+    }
+
+    fn object_digest(&mut self, part: &str, id: ResourceId) -> [u8; 32] {
+        let key = (part.to_string(), id);
+        if let Some(digest) = self.objects.get(&key) {
+            if self.incomplete.contains(&(key.0, id, false)) {
+                self.missing += 1;
+            }
+            return *digest;
+        }
+        let missing = self.missing;
+        let mut h = Sha256::new();
+        if !self.visiting.insert(key.clone()) {
+            h.update(b"cycle");
+            return h.finalize().into();
+        }
+        match self
+            .model(part)
+            .and_then(|m| m.resources.get_object(id).map(|o| (m, o)))
+        {
+            Some((model, obj)) => self.hash_object(&mut h, part, model, obj),
+            None => {
+                // Unresolvable reference: all we know is where it points.
+                if self.model(part).is_none() {
+                    self.missing += 1;
+                }
+                h.update(b"unresolved");
+                hash_str(&mut h, part);
+                h.update(id.0.to_le_bytes());
+            }
+        }
+        self.visiting.remove(&key);
+        if self.missing > missing {
+            self.incomplete.insert((key.0.clone(), id, false));
+        }
+        let digest = h.finalize().into();
+        self.objects.insert(key, digest);
+        digest
+    }
+
+    fn hash_object(&mut self, h: &mut Sha256, part: &str, model: &'a Model, obj: &'a Object) {
+        hash_str(h, &format!("{:?}", obj.object_type));
+        match &obj.geometry {
+            Geometry::Mesh(mesh) => {
+                h.update(b"mesh");
+                self.hash_mesh(h, part, model, obj, mesh);
+            }
+            Geometry::Components(comps) => {
+                h.update(b"components");
+                let mut children: Vec<[u8; 32]> = comps
+                    .components
+                    .iter()
+                    .map(|c| {
+                        let child_part = c.path.as_deref().map(normalize);
+                        let child =
+                            self.object_digest(child_part.as_deref().unwrap_or(part), c.object_id);
+                        let mut ch = Sha256::new();
+                        ch.update(child);
+                        hash_transform(&mut ch, &c.transform, model.unit);
+                        ch.finalize().into()
+                    })
+                    .collect();
+                children.sort_unstable();
+                h.update((children.len() as u64).to_le_bytes());
+                for child in children {
+                    h.update(child);
+                }
+            }
+            Geometry::BooleanShape(shape) => {
+                h.update(b"boolean");
+                let base_part = shape.base_path.as_deref().map(normalize);
+                let base =
+                    self.object_digest(base_part.as_deref().unwrap_or(part), shape.base_object_id);
+                h.update(base);
+                hash_transform(h, &shape.base_transform, model.unit);
+                for op in &shape.operations {
+                    let op_part = op.path.as_deref().map(normalize);
+                    let operand =
+                        self.object_digest(op_part.as_deref().unwrap_or(part), op.object_id);
+                    hash_str(h, &format!("{:?}", op.operation_type));
+                    h.update(operand);
+                    hash_transform(h, &op.transform, model.unit);
+                }
+            }
+            Geometry::SliceStack(id) => {
+                h.update(b"slices");
+                h.update(self.slice_stack_digest(part, *id));
+            }
+            Geometry::VolumetricStack(id) => {
+                h.update(b"volumetric");
+                h.update(self.volumetric_stack_digest(part, *id));
+            }
+            Geometry::DisplacementMesh(mesh) => {
+                h.update(b"displacement");
+                self.hash_displacement_mesh(h, part, model, obj, mesh);
+            }
+            Geometry::PreciseMesh(mesh) => {
+                // Hashed like the f32 mesh it rounds to; lengths are quantized far more
+                // coarsely than f32 resolves them, so the digest doesn't depend on the
+                // precision the model was parsed with.
+                h.update(b"mesh");
+                self.hash_mesh(h, part, model, obj, &Mesh::from(mesh));
+            }
+            Geometry::LazyMesh(lazy) => {
+                // Hashed like the eager mesh it parses to, so the digest doesn't depend on
+                // how the model was loaded.
+                let data = self
+                    .root
+                    .attachments
+                    .iter()
+                    .find(|(k, _)| part_names_match(k, &lazy.part))
+                    .map(|(_, v)| v);
+                match data.and_then(|d| lazy.load_from(d).ok()) {
+                    Some(mesh) => {
+                        h.update(b"mesh");
+                        self.hash_mesh(h, part, model, obj, &mesh);
+                    }
+                    None => {
+                        self.missing += 1;
+                        h.update(b"lazy");
+                        hash_str(h, &lazy.part);
+                        h.update((lazy.span.start as u64).to_le_bytes());
+                    }
+                }
+            }
+            Geometry::LevelSet(level_set) => {
+                h.update(b"levelset");
+                let to_mm = model.unit.convert(1.0, Unit::Millimeter);
+                let bounds = self.object_digest(part, level_set.mesh_id);
+                h.update(bounds);
+                h.update(self.function_digest(part, model, level_set.function_id));
+                hash_str(h, &level_set.channel);
+                hash_transform(h, &level_set.transform, model.unit);
+                hash_length(h, level_set.min_feature_size, to_mm);
+                h.update([u8::from(level_set.mesh_bbox_only)]);
+                hash_ratio(h, level_set.fallback_value);
+                match level_set.volume_id {
+                    Some(id) => self.hash_volume_data(h, part, model, id),
+                    None => h.update([0]),
+                }
+            }
+        }
+    }
+
+    fn hash_mesh(
+        &mut self,
+        h: &mut Sha256,
+        part: &str,
+        model: &'a Model,
+        obj: &Object,
+        mesh: &Mesh,
+    ) {
+        let to_mm = model.unit.convert(1.0, Unit::Millimeter);
+        h.update((mesh.vertices.len() as u64).to_le_bytes());
+        for v in &mesh.vertices {
+            hash_length(h, v.x, to_mm);
+            hash_length(h, v.y, to_mm);
+            hash_length(h, v.z, to_mm);
+        }
+
+        let mut properties: HashMap<(ResourceId, u32), [u8; 32]> = HashMap::new();
+        h.update((mesh.triangles.len() as u64).to_le_bytes());
+        for tri in &mesh.triangles {
+            h.update(tri.v1.to_le_bytes());
+            h.update(tri.v2.to_le_bytes());
+            h.update(tri.v3.to_le_bytes());
+            match triangle_properties(tri, obj) {
+                Some((pid, indices)) => {
+                    h.update([1]);
+                    for index in indices {
+                        let digest = *properties
+                            .entry((pid, index))
+                            .or_insert_with(|| self.property_digest(part, model, pid, index));
+                        h.update(digest);
+                    }
+                }
+                None => h.update([0]),
+            }
+        }
+
+        match &mesh.beam_lattice {
+            Some(lattice) => {
+                h.update(b"beamlattice");
+                self.hash_beam_lattice(h, part, model, obj, lattice);
+            }
+            None => h.update([0]),
+        }
+    }
+
+    /// Hashes a beam lattice. Beam set names and identifiers are excluded like other names.
+    fn hash_beam_lattice(
+        &mut self,
+        h: &mut Sha256,
+        part: &str,
+        model: &'a Model,
+        obj: &Object,
+        lattice: &BeamLattice,
+    ) {
+        let to_mm = model.unit.convert(1.0, Unit::Millimeter);
+        match lattice.radius {
+            Some(radius) => hash_length(h, radius, to_mm),
+            None => h.update([0]),
+        }
+        hash_length(h, lattice.min_length, to_mm);
+        hash_length(h, lattice.precision, to_mm);
+        hash_str(h, &format!("{:?}", lattice.clipping_mode));
+
+        h.update((lattice.beams.len() as u64).to_le_bytes());
+        for beam in &lattice.beams {
+            h.update(beam.v1.to_le_bytes());
+            h.update(beam.v2.to_le_bytes());
+            hash_length(h, beam.r1, to_mm);
+            hash_length(h, beam.r2, to_mm);
+            hash_str(h, &format!("{:?}", beam.cap_mode));
+            // Beams take their properties from the object's property group.
+            match (obj.pid, beam.p1.or(obj.pindex)) {
+                (Some(pid), Some(p1)) => {
+                    h.update(self.property_digest(part, model, pid, p1));
+                    h.update(self.property_digest(part, model, pid, beam.p2.unwrap_or(p1)));
+                }
+                _ => h.update([0]),
+            }
+        }
+
+        h.update((lattice.beam_sets.len() as u64).to_le_bytes());
+        for set in &lattice.beam_sets {
+            h.update((set.refs.len() as u64).to_le_bytes());
+            for index in &set.refs {
+                h.update(index.to_le_bytes());
+            }
+        }
+    }
+
+    fn hash_displacement_mesh(
+        &mut self,
+        h: &mut Sha256,
+        part: &str,
+        model: &'a Model,
+        obj: &Object,
+        mesh: &DisplacementMesh,
+    ) {
+        let to_mm = model.unit.convert(1.0, Unit::Millimeter);
+        h.update((mesh.vertices.len() as u64).to_le_bytes());
+        for v in &mesh.vertices {
+            hash_length(h, v.x, to_mm);
+            hash_length(h, v.y, to_mm);
+            hash_length(h, v.z, to_mm);
+        }
+        h.update((mesh.normals.len() as u64).to_le_bytes());
+        for n in &mesh.normals {
+            hash_ratio(h, n.nx);
+            hash_ratio(h, n.ny);
+            hash_ratio(h, n.nz);
+        }
+        match &mesh.gradients {
+            Some(gradients) => {
+                h.update((gradients.len() as u64 + 1).to_le_bytes());
+                for g in gradients {
+                    hash_ratio(h, g.gu);
+                    hash_ratio(h, g.gv);
+                }
+            }
+            None => h.update(0u64.to_le_bytes()),
+        }
+
+        let mut properties: HashMap<(ResourceId, u32), [u8; 32]> = HashMap::new();
+        h.update((mesh.triangles.len() as u64).to_le_bytes());
+        for tri in &mesh.triangles {
+            h.update(tri.v1.to_le_bytes());
+            h.update(tri.v2.to_le_bytes());
+            h.update(tri.v3.to_le_bytes());
+            for d in [tri.d1, tri.d2, tri.d3] {
+                h.update(d.map_or(0, |d| u64::from(d) + 1).to_le_bytes());
+            }
+            let props = Triangle {
+                v1: tri.v1,
+                v2: tri.v2,
+                v3: tri.v3,
+                p1: tri.p1,
+                p2: tri.p2,
+                p3: tri.p3,
+                pid: tri.pid,
+            };
+            match triangle_properties(&props, obj) {
+                Some((pid, indices)) => {
+                    h.update([1]);
+                    for index in indices {
+                        let digest = *properties
+                            .entry((pid, index))
+                            .or_insert_with(|| self.property_digest(part, model, pid, index));
+                        h.update(digest);
+                    }
+                }
+                None => h.update([0]),
+            }
+        }
+    }
+
+    /// Digest of slice stack `id` in `part`, following references into other parts.
+    fn slice_stack_digest(&mut self, part: &str, id: ResourceId) -> [u8; 32] {
+        let mut h = Sha256::new();
+        let key = (part.to_string(), id);
+        let Some(model) = self.model(part) else {
+            self.missing += 1;
+            h.update(b"unresolved");
+            hash_str(&mut h, part);
+            return h.finalize().into();
+        };
+        let Some(stack) = model.resources.get_slice_stack(id) else {
+            h.update(b"unresolved");
+            return h.finalize().into();
+        };
+        if !self.references.insert(key.clone()) {
+            h.update(b"cycle");
+            return h.finalize().into();
+        }
+        let to_mm = model.unit.convert(1.0, Unit::Millimeter);
+        hash_length(&mut h, stack.z_bottom, to_mm);
+        h.update((stack.slices.len() as u64).to_le_bytes());
+        for slice in &stack.slices {
+            hash_length(&mut h, slice.z_top, to_mm);
+            h.update((slice.vertices.len() as u64).to_le_bytes());
+            for v in &slice.vertices {
+                hash_length(&mut h, v.x, to_mm);
+                hash_length(&mut h, v.y, to_mm);
+            }
+            h.update((slice.polygons.len() as u64).to_le_bytes());
+            for polygon in &slice.polygons {
+                h.update(polygon.start_segment.to_le_bytes());
+                h.update((polygon.segments.len() as u64).to_le_bytes());
+                for segment in &polygon.segments {
+                    h.update(segment.v2.to_le_bytes());
+                    match (segment.pid, segment.p1) {
+                        (Some(pid), Some(p1)) => {
+                            h.update(self.property_digest(part, model, pid, p1));
+                            let p2 = segment.p2.unwrap_or(p1);
+                            h.update(self.property_digest(part, model, pid, p2));
+                        }
+                        _ => h.update([0]),
+                    }
+                }
+            }
+        }
+        h.update((stack.refs.len() as u64).to_le_bytes());
+        for r in &stack.refs {
+            let ref_part = normalize(&r.slice_path);
+            h.update(self.slice_stack_digest(&ref_part, r.slice_stack_id));
+        }
+        self.references.remove(&key);
+        h.finalize().into()
+    }
+
+    /// Digest of volumetric stack `id` in `part`, following references into other parts.
+    fn volumetric_stack_digest(&mut self, part: &str, id: ResourceId) -> [u8; 32] {
+        let mut h = Sha256::new();
+        let key = (part.to_string(), id);
+        let Some(model) = self.model(part) else {
+            self.missing += 1;
+            h.update(b"unresolved");
+            hash_str(&mut h, part);
+            return h.finalize().into();
+        };
+        let Some(stack) = model.resources.get_volumetric_stack(id) else {
+            h.update(b"unresolved");
+            return h.finalize().into();
+        };
+        if !self.references.insert(key.clone()) {
+            h.update(b"cycle");
+            return h.finalize().into();
+        }
+        let to_mm = model.unit.convert(1.0, Unit::Millimeter);
+        hash_str(&mut h, &stack.version);
+        h.update((stack.layers.len() as u64).to_le_bytes());
+        for layer in &stack.layers {
+            hash_length(&mut h, layer.z_height, to_mm);
+            self.hash_attachment(&mut h, &layer.content_path);
+        }
+        h.update((stack.refs.len() as u64).to_le_bytes());
+        for r in &stack.refs {
+            let ref_part = normalize(&r.path);
+            h.update(self.volumetric_stack_digest(&ref_part, r.stack_id));
+        }
+        self.references.remove(&key);
+        h.finalize().into()
+    }
+
+    /// Hashes the contents of the attachment at `path`, or its name if it isn't loaded.
+    fn hash_attachment(&mut self, h: &mut Sha256, path: &str) {
+        let path = normalize_part_name(path);
+        match self
+            .root
+            .attachments
+            .iter()
+            .find(|(k, _)| part_names_match(k, &path))
+        {
+            Some((_, data)) => h.update(Sha256::digest(data)),
+            None => {
+                self.missing += 1;
+                hash_str(h, &path);
+            }
+        }
+    }
+
+    /// Digest of function `id`. Names are excluded; node and port identifiers are kept,
+    /// since they define how the graph is wired.
+    fn function_digest(&mut self, part: &str, model: &'a Model, id: ResourceId) -> [u8; 32] {
+        let mut h = Sha256::new();
+        let key = (part.to_string(), id);
+        let Some(function) = model.resources.get_function(id) else {
+            h.update(b"unresolved");
+            return h.finalize().into();
+        };
+        if !self.references.insert(key.clone()) {
+            h.update(b"cycle");
+            return h.finalize().into();
+        }
+        match function {
+            Function::Implicit(f) => {
+                h.update(b"implicit");
+                hash_ports(&mut h, &f.inputs);
+                hash_ports(&mut h, &f.outputs);
+                h.update((f.nodes.len() as u64).to_le_bytes());
+                for node in &f.nodes {
+                    hash_str(&mut h, &node.kind);
+                    hash_str(&mut h, &node.identifier);
+                    hash_ports(&mut h, &node.inputs);
+                    hash_ports(&mut h, &node.outputs);
+                    h.update((node.attributes.len() as u64).to_le_bytes());
+                    for (name, value) in &node.attributes {
+                        hash_str(&mut h, name);
+                        // Resource IDs are followed by content, like everywhere else.
+                        let target = value.parse().ok().map(ResourceId);
+                        match target.filter(|_| node.kind == "resourceid" && name == "value") {
+                            Some(target) if model.resources.get_function(target).is_some() => {
+                                h.update(self.function_digest(part, model, target));
+                            }
+                            Some(target) => h.update(self.object_digest(part, target)),
+                            None => hash_str(&mut h, value),
+                        }
+                    }
+                }
+            }
+            Function::FromImage3D(f) => {
+                h.update(b"image3d");
+                match model.resources.get_image_3d(f.image3d_id) {
+                    Some(image) => {
+                        let stack = &image.stack;
+                        h.update(stack.row_count.to_le_bytes());
+                        h.update(stack.column_count.to_le_bytes());
+                        h.update(stack.sheet_count.to_le_bytes());
+                        h.update((stack.sheets.len() as u64).to_le_bytes());
+                        for sheet in &stack.sheets {
+                            self.hash_attachment(&mut h, sheet);
+                        }
+                    }
+                    None => h.update(b"unresolved"),
+                }
+                hash_ratio(&mut h, f.value_offset);
+                hash_ratio(&mut h, f.value_scale);
+                hash_str(
+                    &mut h,
+                    &format!(
+                        "{:?} {:?} {:?} {:?}",
+                        f.filter, f.tile_style_u, f.tile_style_v, f.tile_style_w
+                    ),
+                );
+            }
+        }
+        self.references.remove(&key);
+        h.finalize().into()
+    }
+
+    fn hash_function_ref(
+        &mut self,
+        h: &mut Sha256,
+        part: &str,
+        model: &'a Model,
+        function: &VolumeFunctionRef,
+    ) {
+        let to_mm = model.unit.convert(1.0, Unit::Millimeter);
+        h.update(self.function_digest(part, model, function.function_id));
+        hash_str(h, &function.channel);
+        hash_transform(h, &function.transform, model.unit);
+        hash_length(h, function.min_feature_size, to_mm);
+        hash_ratio(h, function.fallback_value);
+    }
+
+    fn hash_volume_data(&mut self, h: &mut Sha256, part: &str, model: &'a Model, id: ResourceId) {
+        let Some(data) = model.resources.get_volume_data(id) else {
+            h.update(b"unresolved");
+            return;
+        };
+        h.update(b"volumedata");
+        match &data.color {
+            Some(color) => {
+                h.update([1]);
+                self.hash_function_ref(h, part, model, color);
+            }
+            None => h.update([0]),
+        }
+        match &data.composite {
+            Some(composite) => {
+                h.update([1]);
+                h.update((composite.mappings.len() as u64).to_le_bytes());
+                for (index, mapping) in composite.mappings.iter().enumerate() {
+                    let material = index as u32;
+                    h.update(self.property_digest(
+                        part,
+                        model,
+                        composite.base_material_id,
+                        material,
+                    ));
+                    self.hash_function_ref(h, part, model, mapping);
+                }
+            }
+            None => h.update([0]),
+        }
+        // Properties are unique by name, so they are hashed as a set.
+        let mut properties: Vec<[u8; 32]> = data
+            .properties
+            .iter()
+            .map(|property| {
+                let mut ph = Sha256::new();
+                hash_str(&mut ph, &property.name);
+                ph.update([u8::from(property.required)]);
+                self.hash_function_ref(&mut ph, part, model, &property.function);
+                ph.finalize().into()
+            })
+            .collect();
+        properties.sort_unstable();
+        h.update((properties.len() as u64).to_le_bytes());
+        for property in properties {
+            h.update(property);
+        }
+    }
+
+    /// Digest of the property value at `index` of property resource `pid`.
+    fn property_digest(
+        &mut self,
+        part: &str,
+        model: &'a Model,
+        pid: ResourceId,
+        index: u32,
+    ) -> [u8; 32] {
+        let res = &model.resources;
+        let i = index as usize;
+        let mut h = Sha256::new();
+        if let Some(group) = res.get_base_materials(pid) {
+            h.update(b"base");
+            if let Some(m) = group.materials.get(i) {
+                hash_str(&mut h, &m.name);
+                hash_json(&mut h, &m.display_color);
+            }
+        } else if let Some(group) = res.get_color_group(pid) {
+            h.update(b"color");
+            hash_json(&mut h, &group.colors.get(i));
+        } else if let Some(group) = res.get_texture_2d_group(pid) {
+            h.update(b"texture");
+            if let Some(coord) = group.coords.get(i) {
+                hash_ratio(&mut h, coord.u);
+                hash_ratio(&mut h, coord.v);
+            }
+            h.update(self.texture_digest(part, model, group.texture_id));
+        } else if let Some(composite) = res.get_composite_materials(pid) {
+            h.update(b"composite");
+            if let Some(mix) = composite.composites.get(i) {
+                for (value, base) in mix.values.iter().zip(&composite.indices) {
+                    hash_ratio(&mut h, *value);
+                    h.update(self.property_digest(part, model, composite.base_material_id, *base));
+                }
+            }
+        } else if let Some(multi) = res.get_multi_properties(pid) {
+            h.update(b"multi");
+            hash_json(&mut h, &multi.blend_methods);
+            if let Some(layers) = multi.multis.get(i) {
+                for (layer_pid, layer_index) in multi.pids.iter().zip(&layers.pindices) {
+                    h.update(self.property_digest(part, model, *layer_pid, *layer_index));
+                }
+            }
+        } else {
+            h.update(b"unresolved");
+        }
+        h.finalize().into()
+    }
+
+    fn texture_digest(&mut self, part: &str, model: &'a Model, id: ResourceId) -> [u8; 32] {
+        let key = (part.to_string(), id);
+        if let Some(digest) = self.textures.get(&key) {
+            if self.incomplete.contains(&(key.0, id, true)) {
+                self.missing += 1;
+            }
+            return *digest;
+        }
+        let texture: Option<&Texture2D> = model.resources.iter_texture_2d().find(|t| t.id == id);
+        let mut h = Sha256::new();
+        if let Some(texture) = texture {
+            hash_str(&mut h, &texture.contenttype);
+            // Sampling settings only join the digest when set, so textures using the
+            // defaults keep their earlier hashes.
+            if texture.tile_style_u != TileStyle::Wrap
+                || texture.tile_style_v != TileStyle::Wrap
+                || texture.filter.is_some()
+            {
+                hash_str(
+                    &mut h,
+                    &format!(
+                        "{:?} {:?} {:?}",
+                        texture.tile_style_u, texture.tile_style_v, texture.filter
+                    ),
+                );
+            }
+            let path = normalize_part_name(&texture.path);
+            match self
+                .root
+                .attachments
+                .iter()
+                .find(|(k, _)| part_names_match(k, &path))
+            {
+                Some((_, data)) => h.update(Sha256::digest(data)),
+                None => {
+                    self.missing += 1;
+                    self.incomplete.insert((key.0.clone(), id, true));
+                    hash_str(&mut h, &path)
+                }
+            }
+        }
+        let digest = h.finalize().into();
+        self.textures.insert(key, digest);
+        digest
+    }
+}
+
+/// The property resource and per-vertex indices applying to a triangle, falling back to
+/// the object's default property.
+fn triangle_properties(tri: &Triangle, obj: &Object) -> Option<(ResourceId, [u32; 3])> {
+    match tri.pid {
+        Some(pid) => {
+            let p1 = tri.p1.or(obj.pindex)?;
+            Some((
+                ResourceId(pid),
+                [p1, tri.p2.unwrap_or(p1), tri.p3.unwrap_or(p1)],
+            ))
+        }
+        None => {
+            let pid = obj.pid?;
+            let index = tri.p1.or(obj.pindex).unwrap_or(0);
+            Some((pid, [index; 3]))
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use super::units::Unit;
-use crate::model::{Build, ResourceCollection};
+use crate::model::{Build, CoreProperties, Metadata, ResourceCollection};
+use crate::trace::{phase, span};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -19,9 +20,9 @@ pub struct Model {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub language: Option<String>,
 
-    /// Arbitrary metadata key-value pairs.
+    /// Model-level metadata entries, in document order.
     #[serde(default)]
-    pub metadata: HashMap<String, String>,
+    pub metadata: Metadata,
 
     /// Collection of all resources (objects, materials) used in the build.
     #[serde(default)]
@@ -34,6 +35,10 @@ pub struct Model {
     /// Binary attachments (Textures, Thumbnails, etc.) stored by package path.
     /// Key: Path in archive (e.g., "Metadata/thumbnail.png", "3D/Textures/diffuse.png")
     /// Value: Binary content
+    ///
+    /// Loaded with [`Model::load_attachments`]; the
+    /// [`attachment_registry`](Model::attachment_registry) lists those bound by relationships,
+    /// such as print tickets, by relationship type.
     #[serde(skip)]
     pub attachments: HashMap<String, Vec<u8>>,
 
@@ -47,6 +52,60 @@ pub struct Model {
     /// Key: prefix (e.g., `"BambuStudio"`), Value: URI (e.g., `"http://schemas.bambulab.com/..."`).
     #[serde(default)]
     pub extra_namespaces: HashMap<String, String>,
+
+    /// Namespace URIs listed in the model's `requiredextensions` attribute, in document order.
+    ///
+    /// An entry whose prefix has no namespace declaration is kept as the bare prefix. The
+    /// writer does not copy this list; it emits [`Model::extensions_to_require`] instead,
+    /// plus the entries this library doesn't support whose namespaces are kept in
+    /// `extra_namespaces`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_extensions: Vec<String>,
+
+    /// Production Extension namespace URI the model element declares or lists in
+    /// `requiredextensions`, if any. A model that declares the namespace uses the
+    /// extension even without UUIDs, so Strict validation requires them (see
+    /// [`Model::uses_production_extension`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub production_namespace: Option<String>,
+
+    /// Elements and attributes the 3MF schemas don't define, in document order.
+    ///
+    /// Only recorded when parsing with
+    /// [`ParseOptions::track_unknown_names`](crate::parser::ParseOptions::track_unknown_names);
+    /// reported as warnings by Strict validation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unknown_names: Vec<UnknownName>,
+
+    /// Document properties of the package, such as its title, creator and dates.
+    ///
+    /// Read from the package with [`read_core_properties`](crate::model::read_core_properties);
+    /// when set, the package writer stores them in
+    /// [`CORE_PROPERTIES_PATH`](crate::model::CORE_PROPERTIES_PATH).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub core_properties: Option<CoreProperties>,
+}
+
+/// Whether an [`UnknownName`] is an element or an attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnknownNameKind {
+    /// An element the schema of its namespace doesn't define.
+    Element,
+    /// An attribute the element doesn't accept.
+    Attribute,
+}
+
+/// An element or attribute found while parsing that the 3MF schemas don't define, such as
+/// a misspelled `transfrom=`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnknownName {
+    /// Path of the element, e.g. `/model/build/item[2]`. For an unknown element this is the
+    /// path of the element itself.
+    pub path: String,
+    /// The name as written, including any namespace prefix.
+    pub name: String,
+    /// Whether the name is an element or an attribute.
+    pub kind: UnknownNameKind,
 }
 
 impl Model {
@@ -103,43 +162,119 @@ impl Model {
         &self,
         level: crate::validation::ValidationLevel,
     ) -> crate::validation::ValidationReport {
-        use crate::validation::{ValidationLevel, displacement, geometry, schema, semantic};
+        self.validate_with_options(level, &crate::validation::ValidationOptions::default())
+    }
 
+    /// Validates the model at the given level, with the limits of `options`.
+    ///
+    /// Like [`validate`](Self::validate), which uses the default
+    /// [`ValidationOptions`](crate::validation::ValidationOptions).
+    ///
+    /// ```
+    /// use lib3mf_core::Model;
+    /// use lib3mf_core::validation::{ValidationLevel, ValidationOptions};
+    ///
+    /// let options = ValidationOptions {
+    ///     max_boolean_depth: 4,
+    /// };
+    /// let report = Model::default().validate_with_options(ValidationLevel::Standard, &options);
+    /// assert!(!report.has_errors());
+    /// ```
+    pub fn validate_with_options(
+        &self,
+        level: crate::validation::ValidationLevel,
+        options: &crate::validation::ValidationOptions,
+    ) -> crate::validation::ValidationReport {
+        use crate::validation::{
+            ValidationLevel, displacement, geometry, production, properties, schema, semantic,
+        };
+
+        let phase = phase!(
+            span::VALIDATE,
+            level = ?level,
+            issues = tracing::field::Empty
+        );
         let mut report = crate::validation::ValidationReport::new();
 
         // Minimal: Schema validation (placeholders usually checked by parser, but explicit invariants here)
         if level >= ValidationLevel::Minimal {
-            schema::validate_schema(self, &mut report);
+            run_check(&mut report, "schema", |r| {
+                schema::validate_schema(self, level, r)
+            });
         }
 
         // Standard: Semantic validation (integrity)
         if level >= ValidationLevel::Standard {
-            semantic::validate_semantic(self, &mut report);
+            run_check(&mut report, "semantic", |r| {
+                semantic::validate_semantic_with_options(self, options, r)
+            });
+            run_check(&mut report, "properties", |r| {
+                properties::validate_properties(self, level, r)
+            });
+            run_check(&mut report, "production", |r| {
+                production::validate_production(self, level, r)
+            });
+            run_check(&mut report, "coordinate_range", |r| {
+                geometry::validate_coordinate_range(self, r)
+            });
         }
 
         // All levels: Displacement validation (progressive checks)
-        displacement::validate_displacement(self, level, &mut report);
+        run_check(&mut report, "displacement", |r| {
+            displacement::validate_displacement(self, level, r)
+        });
+
+        // Strict: Solid objects enclose positive volume
+        if level >= ValidationLevel::Strict {
+            run_check(&mut report, "solid_orientation", |r| {
+                geometry::validate_solid_orientation(self, r)
+            });
+        }
 
         // Paranoid: Geometry validation
         if level >= ValidationLevel::Paranoid {
-            geometry::validate_geometry(self, level, &mut report);
+            run_check(&mut report, "geometry", |r| {
+                geometry::validate_geometry(self, level, r)
+            });
         }
 
+        phase.record("issues", report.items.len());
         report
     }
 }
 
+/// Runs one validation check, instrumented as a [`span::VALIDATION_CHECK`] span.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+fn run_check(
+    report: &mut crate::validation::ValidationReport,
+    check: &'static str,
+    run: impl FnOnce(&mut crate::validation::ValidationReport),
+) {
+    let phase = phase!(
+        span::VALIDATION_CHECK,
+        check,
+        issues = tracing::field::Empty
+    );
+    let before = report.items.len();
+    run(report);
+    phase.record("issues", report.items.len() - before);
+}
+
 impl Default for Model {
     fn default() -> Self {
         Self {
             unit: Unit::Millimeter,
             language: None,
-            metadata: HashMap::new(),
+            metadata: Metadata::new(),
             resources: ResourceCollection::default(),
             build: Build::default(),
             attachments: HashMap::new(),
             existing_relationships: HashMap::new(),
             extra_namespaces: HashMap::new(),
+            required_extensions: Vec::new(),
+            production_namespace: None,
+            unknown_names: Vec::new(),
+            core_properties: None,
         }
     }
 }
@@ -0,0 +1,199 @@
+//! OPC core properties: the package's title, creator, dates and similar document
+//! properties.
+//!
+//! Packages written by Office-style tooling carry a core properties part, usually
+//! `docProps/core.xml`, named by a package relationship of type
+//! [`CORE_PROPERTIES_REL_TYPE`]. [`read_core_properties`] reads it into
+//! [`CoreProperties`], which loaders store in [`Model::core_properties`]. The package
+//! writer writes them back to [`CORE_PROPERTIES_PATH`] with their relationship and content
+//! type.
+//!
+//! Core properties describe the package, while the model's `<metadata>` entries
+//! ([`Model::metadata`]) describe the model; the two are kept apart.
+//!
+//! ```
+//! # #[cfg(feature = "writer")] {
+//! use lib3mf_core::archive::ZipArchiver;
+//! use lib3mf_core::model::{CoreProperties, Model, read_core_properties};
+//! use std::io::Cursor;
+//!
+//! let mut model = Model::default();
+//! model.core_properties = Some(CoreProperties {
+//!     title: Some("Bracket".to_string()),
+//!     creator: Some("Jane Doe".to_string()),
+//!     created: Some("2024-05-01T09:30:00Z".to_string()),
+//!     ..Default::default()
+//! });
+//! let mut buffer = Cursor::new(Vec::new());
+//! model.write(&mut buffer)?;
+//!
+//! let mut archive = ZipArchiver::new(Cursor::new(buffer.into_inner()))?;
+//! let properties = read_core_properties(&mut archive)?.expect("core properties part");
+//! assert_eq!(properties.title.as_deref(), Some("Bracket"));
+//! # }
+//! # Ok::<(), lib3mf_core::error::Lib3mfError>(())
+//! ```
+//!
+//! [`Model::core_properties`]: crate::model::Model::core_properties
+//! [`Model::metadata`]: crate::model::Model::metadata
+
+use crate::archive::opc::parse_relationships;
+use crate::archive::{ArchiveReader, resolve_part_target};
+use crate::error::Result;
+use crate::parser::core_properties_parser::parse_core_properties;
+use serde::{Deserialize, Serialize};
+
+/// Relationship type of the core properties part.
+pub const CORE_PROPERTIES_REL_TYPE: &str =
+    "http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties";
+
+/// Relationship type of the core properties part used by older Office documents, also
+/// accepted when reading.
+pub const LEGACY_CORE_PROPERTIES_REL_TYPE: &str =
+    "http://schemas.openxmlformats.org/officedocument/2006/relationships/metadata/core-properties";
+
+/// Archive path the package writer stores core properties at.
+pub const CORE_PROPERTIES_PATH: &str = "docProps/core.xml";
+
+/// Content type of the core properties part.
+pub const CORE_PROPERTIES_CONTENT_TYPE: &str =
+    "application/vnd.openxmlformats-package.core-properties+xml";
+
+/// Document properties of a package, from its core properties part.
+///
+/// Every property is optional and kept as written. Dates (`created`, `modified` and
+/// `last_printed`) are W3C date-time strings such as `2024-05-01T09:30:00Z`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoreProperties {
+    /// Name given to the package (`dc:title`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Topic of the package (`dc:subject`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    /// Who created the package (`dc:creator`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creator: Option<String>,
+    /// Keywords for searching, as one string (`cp:keywords`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<String>,
+    /// Description of the content (`dc:description`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Who last modified the package (`cp:lastModifiedBy`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified_by: Option<String>,
+    /// Revision number (`cp:revision`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+    /// When the package was last printed (`cp:lastPrinted`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_printed: Option<String>,
+    /// When the package was created (`dcterms:created`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    /// When the package was last modified (`dcterms:modified`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<String>,
+    /// Category of the content (`cp:category`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Status such as "Draft" or "Final" (`cp:contentStatus`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_status: Option<String>,
+    /// Unambiguous reference to the package (`dc:identifier`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+    /// Language of the content (`dc:language`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Version number (`cp:version`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+impl CoreProperties {
+    /// Returns whether no property is set.
+    pub fn is_empty(&self) -> bool {
+        self.entries().next().is_none()
+    }
+
+    /// Returns the properties that are set, by element name (e.g. `dc:title`), in the
+    /// order the part lists them.
+    pub fn entries(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        [
+            ("dc:title", &self.title),
+            ("dc:subject", &self.subject),
+            ("dc:creator", &self.creator),
+            ("cp:keywords", &self.keywords),
+            ("dc:description", &self.description),
+            ("cp:lastModifiedBy", &self.last_modified_by),
+            ("cp:revision", &self.revision),
+            ("cp:lastPrinted", &self.last_printed),
+            ("dcterms:created", &self.created),
+            ("dcterms:modified", &self.modified),
+            ("cp:category", &self.category),
+            ("cp:contentStatus", &self.content_status),
+            ("dc:identifier", &self.identifier),
+            ("dc:language", &self.language),
+            ("cp:version", &self.version),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name, value.as_deref()?)))
+    }
+
+    /// Returns the property stored in the element with local name `name` (e.g. `title`).
+    pub(crate) fn property_mut(&mut self, name: &[u8]) -> Option<&mut Option<String>> {
+        Some(match name {
+            b"title" => &mut self.title,
+            b"subject" => &mut self.subject,
+            b"creator" => &mut self.creator,
+            b"keywords" => &mut self.keywords,
+            b"description" => &mut self.description,
+            b"lastModifiedBy" => &mut self.last_modified_by,
+            b"revision" => &mut self.revision,
+            b"lastPrinted" => &mut self.last_printed,
+            b"created" => &mut self.created,
+            b"modified" => &mut self.modified,
+            b"category" => &mut self.category,
+            b"contentStatus" => &mut self.content_status,
+            b"identifier" => &mut self.identifier,
+            b"language" => &mut self.language,
+            b"version" => &mut self.version,
+            _ => return None,
+        })
+    }
+}
+
+/// Returns the core properties part named by the package relationships, if any.
+pub fn find_core_properties_path<R: ArchiveReader>(archive: &mut R) -> Result<Option<String>> {
+    if !archive.entry_exists("_rels/.rels") {
+        return Ok(None);
+    }
+    let rels = parse_relationships(&archive.read_entry("_rels/.rels")?)?;
+    Ok(rels
+        .into_iter()
+        .find(|rel| {
+            rel.rel_type == CORE_PROPERTIES_REL_TYPE
+                || rel.rel_type == LEGACY_CORE_PROPERTIES_REL_TYPE
+        })
+        .map(|rel| resolve_part_target("", &rel.target)))
+}
+
+/// Reads the package's core properties.
+///
+/// Returns `None` if the package relationships name no core properties part, or the part
+/// they name is missing.
+///
+/// # Errors
+///
+/// Returns an error if the relationships or the part can't be read or parsed.
+pub fn read_core_properties<R: ArchiveReader>(archive: &mut R) -> Result<Option<CoreProperties>> {
+    let Some(path) = find_core_properties_path(archive)? else {
+        return Ok(None);
+    };
+    if !archive.entry_exists(&path) {
+        return Ok(None);
+    }
+    parse_core_properties(&archive.read_entry(&path)?).map(Some)
+}
@@ -1,4 +1,8 @@
+use crate::archive::opc::parse_relationships;
+use crate::archive::{ArchiveReader, rels_source_part, resolve_part_target};
+use crate::error::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 
 /// Represents an XML-DSIG Signature element.
 /// Namespace: <http://www.w3.org/2000/09/xmldsig#>
@@ -132,3 +136,108 @@ pub struct CertificateInfo {
     // Real parsed data could be stored if we hold the X509Certificate object,
     // but usually we just parse on demand from the PEM/DER bytes.
 }
+
+/// Which package parts a [`Signature`] protects, as computed by [`Signature::coverage`].
+///
+/// A signature can verify correctly and still leave parts of the package open to
+/// tampering if its references don't include them. Parts are archive entry names without
+/// a leading `/`, sorted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SignatureCoverage {
+    /// Parts referenced by the signature.
+    pub covered: Vec<String>,
+    /// Reference URIs that name no part in the package.
+    pub missing: Vec<String>,
+    /// Model parts and textures that are not referenced. Modifying these changes the
+    /// printed geometry or appearance without invalidating the signature.
+    pub uncovered_critical: Vec<String>,
+    /// Other unreferenced parts, such as thumbnails, metadata and relationship files.
+    pub uncovered: Vec<String>,
+}
+
+impl SignatureCoverage {
+    /// Returns true if every model part and texture is covered.
+    pub fn protects_geometry(&self) -> bool {
+        self.uncovered_critical.is_empty()
+    }
+}
+
+impl Signature {
+    /// Reports which parts of `archive` this signature's references cover.
+    ///
+    /// Reference URIs are package-rooted part names, optionally followed by an OPC
+    /// `?ContentType=` query; same-document references (`#id`) are ignored.
+    /// `[Content_Types].xml` and the digital signature parts themselves cannot be signed
+    /// and are never reported as uncovered.
+    pub fn coverage<A: ArchiveReader>(&self, archive: &mut A) -> Result<SignatureCoverage> {
+        let entries: BTreeSet<String> = archive
+            .list_entries()?
+            .into_iter()
+            .filter(|e| !e.ends_with('/'))
+            .collect();
+
+        let mut report = SignatureCoverage::default();
+        let mut covered = BTreeSet::new();
+        for reference in &self.signed_info.references {
+            let Some(part) = reference_part(&reference.uri) else {
+                continue;
+            };
+            if entries.contains(part) {
+                covered.insert(part.to_string());
+            } else if !report.missing.iter().any(|m| m == part) {
+                report.missing.push(part.to_string());
+            }
+        }
+        report.missing.sort();
+
+        let textures = texture_parts(archive, &entries);
+        for entry in &entries {
+            if covered.contains(entry) || !is_signable(entry) {
+                continue;
+            }
+            if entry.to_ascii_lowercase().ends_with(".model") || textures.contains(entry) {
+                report.uncovered_critical.push(entry.clone());
+            } else {
+                report.uncovered.push(entry.clone());
+            }
+        }
+        report.covered = covered.into_iter().collect();
+        Ok(report)
+    }
+}
+
+/// Returns the part named by a reference URI, or `None` for same-document references.
+fn reference_part(uri: &str) -> Option<&str> {
+    let part = uri.split(['?', '#']).next().unwrap_or_default();
+    let part = part.trim_start_matches('/');
+    (!part.is_empty()).then_some(part)
+}
+
+fn is_signable(entry: &str) -> bool {
+    entry != "[Content_Types].xml" && !entry.to_ascii_lowercase().contains("digital-signature")
+}
+
+/// Entries under `3D/Textures/` or targeted by a texture relationship.
+fn texture_parts<A: ArchiveReader>(
+    archive: &mut A,
+    entries: &BTreeSet<String>,
+) -> BTreeSet<String> {
+    let mut textures: BTreeSet<String> = entries
+        .iter()
+        .filter(|e| e.to_ascii_lowercase().starts_with("3d/textures/"))
+        .cloned()
+        .collect();
+    for rels_path in entries.iter().filter(|e| e.ends_with(".rels")) {
+        let Ok(rels) = archive
+            .read_entry(rels_path)
+            .and_then(|data| parse_relationships(&data))
+        else {
+            continue;
+        };
+        let source = rels_source_part(rels_path);
+        for rel in rels.iter().filter(|r| r.rel_type.contains("texture")) {
+            textures.insert(resolve_part_target(&source, &rel.target));
+        }
+    }
+    textures
+}
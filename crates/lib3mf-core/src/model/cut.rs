@@ -0,0 +1,1025 @@
+//! Plane cuts of meshes and models with watertight caps.
+//!
+//! [`Mesh::cut`] splits a closed mesh along a [`Plane`] into the part above it (on the side
+//! its normal points to) and the part below. Triangles crossing the plane are clipped, and
+//! the openings left on each half are closed with planar caps, so both halves are closed
+//! again. Cut faces with holes, such as a section through a hollow part, are capped as
+//! rings.
+//!
+//! [`Model::cut`] applies a cut given in build coordinates to every object in the build,
+//! keeping one or both halves ([`Side`]). With [`CutOptions::pins`], the lower half gets
+//! alignment pegs on its cap and the upper half matching sockets, so printed halves can be
+//! glued back together in register.
+//!
+//! ```
+//! use lib3mf_core::model::{Mesh, Plane};
+//!
+//! let mut mesh = Mesh::new();
+//! for i in 0..8 {
+//!     let bit = |b: u32| if i >> b & 1 == 1 { 10.0 } else { 0.0 };
+//!     mesh.add_vertex(bit(0), bit(1), bit(2));
+//! }
+//! for [a, b, c, d] in [[0, 2, 3, 1], [4, 5, 7, 6], [0, 1, 5, 4], [2, 6, 7, 3], [0, 4, 6, 2], [1, 3, 7, 5]] {
+//!     mesh.add_triangle(a, b, c);
+//!     mesh.add_triangle(a, c, d);
+//! }
+//!
+//! let plane: Plane = "z=4".parse().unwrap();
+//! let cut = mesh.cut(&plane).unwrap();
+//! let (_, above) = cut.above.compute_area_and_volume();
+//! let (_, below) = cut.below.compute_area_and_volume();
+//! assert!((above - 600.0).abs() < 1e-3 && (below - 400.0).abs() < 1e-3);
+//! ```
+
+use crate::error::{Lib3mfError, Result};
+use crate::model::{
+    Geometry, Mesh, Model, Object, ResourceId, SpatialIndex, Triangle, TriangleSet,
+};
+use glam::{Mat4, Vec2, Vec3};
+use std::collections::{HashMap, HashSet};
+use std::f32::consts::TAU;
+use uuid::Uuid;
+
+/// The plane of points `p` with `normal · p = offset`.
+///
+/// Parses from `x=<value>`, `y=<value>` or `z=<value>` for planes normal to an axis, or from
+/// `a,b,c,d` for the plane `ax + by + cz = d`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane {
+    /// Normal vector, pointing to the [`Side::Above`] half space. Need not be unit length.
+    pub normal: Vec3,
+    /// Value of `normal · p` for points on the plane.
+    pub offset: f32,
+}
+
+impl Plane {
+    /// Creates the plane through `point` with the given normal.
+    pub fn through(point: Vec3, normal: Vec3) -> Self {
+        Self {
+            normal,
+            offset: normal.dot(point),
+        }
+    }
+
+    /// Signed distance of `point` from the plane, positive above it, in units of the
+    /// normal's length.
+    pub fn signed_distance(&self, point: Vec3) -> f32 {
+        self.normal.dot(point) - self.offset
+    }
+
+    /// This plane expressed in the coordinates of an object placed with `transform`, such
+    /// as a build item transform.
+    pub fn to_local(&self, transform: &Mat4) -> Plane {
+        Self {
+            normal: transform.transpose().transform_vector3(self.normal),
+            offset: self.offset - self.normal.dot(transform.w_axis.truncate()),
+        }
+    }
+
+    /// The same plane with a unit normal.
+    fn unit(&self) -> Result<Plane> {
+        let length = self.normal.length();
+        if !(length > 0.0 && length.is_finite() && self.offset.is_finite()) {
+            return Err(Lib3mfError::Validation(format!(
+                "Cut plane normal must be non-zero and finite, got {} with offset {}",
+                self.normal, self.offset
+            )));
+        }
+        Ok(Self {
+            normal: self.normal / length,
+            offset: self.offset / length,
+        })
+    }
+}
+
+impl std::str::FromStr for Plane {
+    type Err = Lib3mfError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            Lib3mfError::Validation(format!(
+                "Invalid cut plane '{}'. Use x=<value>, y=<value>, z=<value> or a,b,c,d for ax+by+cz=d",
+                s
+            ))
+        };
+        let number = |v: &str| v.trim().parse::<f32>().map_err(|_| invalid());
+        if let Some((axis, value)) = s.split_once('=') {
+            let normal = match axis.trim() {
+                "x" | "X" => Vec3::X,
+                "y" | "Y" => Vec3::Y,
+                "z" | "Z" => Vec3::Z,
+                _ => return Err(invalid()),
+            };
+            return Ok(Plane {
+                normal,
+                offset: number(value)?,
+            });
+        }
+        let values = s.split(',').map(number).collect::<Result<Vec<f32>>>()?;
+        match values[..] {
+            [a, b, c, d] => {
+                let plane = Plane {
+                    normal: Vec3::new(a, b, c),
+                    offset: d,
+                };
+                plane.unit()?;
+                Ok(plane)
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Which halves of a cut to keep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Side {
+    /// The half on the side the plane normal points to.
+    Above,
+    /// The half on the other side.
+    Below,
+    /// Both halves, as separate objects.
+    #[default]
+    Both,
+}
+
+impl Side {
+    fn keeps_above(self) -> bool {
+        self != Side::Below
+    }
+
+    fn keeps_below(self) -> bool {
+        self != Side::Above
+    }
+}
+
+impl std::fmt::Display for Side {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Side::Above => write!(f, "above"),
+            Side::Below => write!(f, "below"),
+            Side::Both => write!(f, "both"),
+        }
+    }
+}
+
+impl std::str::FromStr for Side {
+    type Err = Lib3mfError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "above" => Ok(Side::Above),
+            "below" => Ok(Side::Below),
+            "both" => Ok(Side::Both),
+            unknown => Err(Lib3mfError::Validation(format!(
+                "Invalid cut side '{}'. Valid sides are: above, below, both",
+                unknown
+            ))),
+        }
+    }
+}
+
+/// Alignment pins added across a cut by [`CutOptions::pins`].
+///
+/// Each pin is a peg on the lower half's cap with a matching socket in the upper half,
+/// widened by the clearance on every side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PinOptions {
+    /// Pins per cut face, where they fit. Default: `2`.
+    pub count: usize,
+    /// Peg diameter. Default: `5.0`.
+    pub diameter: f32,
+    /// Peg height above the cut. Default: `6.0`.
+    pub length: f32,
+    /// Gap between peg and socket. Default: `0.2`.
+    pub clearance: f32,
+    /// Sides of the polygon approximating the pin section. Default: `16`.
+    pub segments: u32,
+}
+
+impl Default for PinOptions {
+    fn default() -> Self {
+        Self {
+            count: 2,
+            diameter: 5.0,
+            length: 6.0,
+            clearance: 0.2,
+            segments: 16,
+        }
+    }
+}
+
+/// Options for [`Mesh::cut_with`] and [`Model::cut_with`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CutOptions {
+    /// Halves to keep. Default: [`Side::Both`].
+    pub keep: Side,
+    /// Alignment pins between the halves; requires [`Side::Both`]. Default: `None`.
+    pub pins: Option<PinOptions>,
+    /// Objects whose build items are cut by [`Model::cut_with`]; all when empty.
+    /// Default: empty.
+    pub objects: Vec<ResourceId>,
+}
+
+/// The halves of a mesh cut by [`Mesh::cut`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeshCut {
+    /// Part above the plane; empty if not kept or if the mesh lies below the plane.
+    pub above: Mesh,
+    /// Part below the plane; empty if not kept or if the mesh lies above the plane.
+    pub below: Mesh,
+    /// Whether the plane passes through the mesh; if not, the half kept holds all of it.
+    pub crossed: bool,
+    /// Centres of the alignment pins, on the plane.
+    pub pins: Vec<Vec3>,
+}
+
+/// Summary of a [`Model::cut`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CutReport {
+    /// Build items whose object crossed the plane.
+    pub items_cut: usize,
+    /// Build items removed because nothing of their object was kept.
+    pub items_removed: usize,
+    /// Objects added for second halves and for copies of shared objects.
+    pub new_objects: Vec<ResourceId>,
+    /// Alignment pins added.
+    pub pins: usize,
+}
+
+impl Mesh {
+    /// Cuts this closed mesh along `plane`, keeping both halves. See [`cut_with`](Self::cut_with).
+    pub fn cut(&self, plane: &Plane) -> Result<MeshCut> {
+        self.cut_with(plane, &CutOptions::default())
+    }
+
+    /// Cuts this closed mesh along `plane` and caps the cut faces of the halves kept.
+    ///
+    /// Triangles keep their properties, triangle sets and attributes; the parts of a
+    /// clipped triangle inherit them. Caps have no triangle properties. Triangles lying in
+    /// the plane go to the half they face away from. Boundary loops of an open mesh that do
+    /// not lie in the plane are left open.
+    ///
+    /// Pins are placed on the cap regions with the most room, at least two diameters apart,
+    /// where the socket stays a radius inside the mesh; faces too small for a pin get none.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Lib3mfError::Validation`] if the plane or pin options are invalid, pins are
+    /// requested without keeping both halves, or the mesh has no triangles or has a beam
+    /// lattice.
+    pub fn cut_with(&self, plane: &Plane, options: &CutOptions) -> Result<MeshCut> {
+        let plane = plane.unit()?;
+        if let Some(pins) = &options.pins {
+            if options.keep != Side::Both {
+                return Err(Lib3mfError::Validation(
+                    "Alignment pins need both halves of the cut".into(),
+                ));
+            }
+            if !(pins.diameter > 0.0
+                && pins.length > 0.0
+                && pins.clearance >= 0.0
+                && pins.segments >= 3
+                && (pins.diameter + pins.length + pins.clearance).is_finite())
+            {
+                return Err(Lib3mfError::Validation(format!(
+                    "Invalid alignment pins: {pins:?}"
+                )));
+            }
+        }
+        if self.beam_lattice.is_some() {
+            return Err(Lib3mfError::Validation(
+                "Cannot cut a mesh with a beam lattice".into(),
+            ));
+        }
+        if self.triangles.is_empty() {
+            return Err(Lib3mfError::Validation(
+                "Cannot cut a mesh without triangles".into(),
+            ));
+        }
+
+        let mut pool: Vec<Vec3> = self
+            .vertices
+            .iter()
+            .map(|v| Vec3::new(v.x, v.y, v.z))
+            .collect();
+        let extent = pool
+            .iter()
+            .fold(1.0f32, |m, p| m.max(p.abs().max_element()));
+        let epsilon = extent * 1e-6;
+        let distance: Vec<f32> = pool
+            .iter()
+            .map(|&p| {
+                let s = plane.signed_distance(p);
+                if s.abs() <= epsilon { 0.0 } else { s }
+            })
+            .collect();
+        let mut on_plane: Vec<bool> = distance.iter().map(|&s| s == 0.0).collect();
+
+        let mut cut = MeshCut {
+            above: Mesh::new(),
+            below: Mesh::new(),
+            crossed: false,
+            pins: Vec::new(),
+        };
+        // Mesh entirely on one side
+        if distance.iter().all(|&s| s >= 0.0) {
+            if options.keep.keeps_above() {
+                cut.above = self.clone();
+            }
+            return Ok(cut);
+        }
+        if distance.iter().all(|&s| s <= 0.0) {
+            if options.keep.keeps_below() {
+                cut.below = self.clone();
+            }
+            return Ok(cut);
+        }
+
+        cut.crossed = true;
+
+        // Clip triangles into the halves: 0 above, 1 below
+        let keep = [options.keep.keeps_above(), options.keep.keeps_below()];
+        let mut halves: [Vec<(Triangle, Option<usize>)>; 2] = [Vec::new(), Vec::new()];
+        let mut edge_points: HashMap<(u32, u32), u32> = HashMap::new();
+        for (index, t) in self.triangles.iter().enumerate() {
+            let corners = [(t.v1, t.p1), (t.v2, t.p2), (t.v3, t.p3)];
+            let s = corners.map(|(v, _)| distance[v as usize]);
+            if s == [0.0; 3] {
+                let [a, b, c] = corners.map(|(v, _)| pool[v as usize]);
+                let half = usize::from((b - a).cross(c - a).dot(plane.normal) > 0.0);
+                if keep[half] {
+                    halves[half].push((*t, Some(index)));
+                }
+                continue;
+            }
+            for (half, sign) in [(0, 1.0f32), (1, -1.0)] {
+                if !keep[half] {
+                    continue;
+                }
+                if s.iter().all(|&s| s * sign >= 0.0) {
+                    halves[half].push((*t, Some(index)));
+                    continue;
+                }
+                let mut polygon: Vec<(u32, Option<u32>)> = Vec::with_capacity(4);
+                for k in 0..3 {
+                    let (a, b) = (corners[k], corners[(k + 1) % 3]);
+                    let (sa, sb) = (s[k], s[(k + 1) % 3]);
+                    if sa * sign >= 0.0 {
+                        polygon.push(a);
+                    }
+                    if sa * sb < 0.0 {
+                        let key = (a.0.min(b.0), a.0.max(b.0));
+                        let point = *edge_points.entry(key).or_insert_with(|| {
+                            let (p, q) = (key.0 as usize, key.1 as usize);
+                            let f = distance[p] / (distance[p] - distance[q]);
+                            pool.push(pool[p].lerp(pool[q], f));
+                            on_plane.push(true);
+                            pool.len() as u32 - 1
+                        });
+                        let property = if sa * sign > 0.0 { a.1 } else { b.1 };
+                        polygon.push((point, property));
+                    }
+                }
+                for j in 1..polygon.len().saturating_sub(1) {
+                    let (p1, p2, p3) = (polygon[0], polygon[j], polygon[j + 1]);
+                    halves[half].push((
+                        Triangle {
+                            v1: p1.0,
+                            v2: p2.0,
+                            v3: p3.0,
+                            p1: p1.1,
+                            p2: p2.1,
+                            p3: p3.1,
+                            pid: t.pid,
+                        },
+                        Some(index),
+                    ));
+                }
+            }
+        }
+
+        let loops = [0, 1].map(|half| boundary_loops(&halves[half], &on_plane));
+        if let Some(pins) = &options.pins {
+            cut.pins = place_pins(self, &plane, &pool, &loops[1], pins);
+        }
+        for (half, sign) in [(0, -1.0f32), (1, 1.0)] {
+            if !keep[half] {
+                continue;
+            }
+            // Caps face out of the half: down for the upper half, up for the lower one
+            let facing = plane.normal * sign;
+            let mut cap_loops = loops[half].clone();
+            if let Some(pins) = &options.pins {
+                for &centre in &cut.pins {
+                    let (radius, height) = if half == 1 {
+                        (pins.diameter / 2.0, pins.length)
+                    } else {
+                        // Socket reaching into the upper half
+                        (
+                            pins.diameter / 2.0 + pins.clearance,
+                            -(pins.length + pins.clearance),
+                        )
+                    };
+                    let (ring, walls) =
+                        prism(&mut pool, centre, facing, radius, height, pins.segments);
+                    cap_loops.push(ring);
+                    halves[half].extend(walls.into_iter().map(|t| (t, None)));
+                }
+            }
+            let (u, v) = basis(facing);
+            let project = |id: u32| {
+                let p = pool[id as usize];
+                Vec2::new(p.dot(u), p.dot(v))
+            };
+            for [a, b, c] in triangulate_cap(&cap_loops, &project) {
+                halves[half].push((
+                    Triangle {
+                        v1: a,
+                        v2: b,
+                        v3: c,
+                        ..Default::default()
+                    },
+                    None,
+                ));
+            }
+        }
+
+        cut.above = compact(&pool, &halves[0], self);
+        cut.below = compact(&pool, &halves[1], self);
+        Ok(cut)
+    }
+}
+
+impl Model {
+    /// Cuts every object in the build along `plane`, given in build coordinates, keeping
+    /// the halves selected by `keep`. See [`cut_with`](Self::cut_with).
+    pub fn cut(&mut self, plane: &Plane, keep: Side) -> Result<CutReport> {
+        self.cut_with(
+            plane,
+            &CutOptions {
+                keep,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Cuts the objects of build items along `plane`, given in build coordinates, with
+    /// [`Mesh::cut_with`].
+    ///
+    /// Each build item's object is cut in its own coordinates. The first half kept stays in
+    /// the object; with [`Side::Both`] the lower half becomes a new object named with a
+    /// ` (below)` suffix, placed by a new build item after the original. Objects used as
+    /// components, or by build items not yet cut, are copied before cutting, so other
+    /// references keep the whole object. Build items with nothing left are removed, along
+    /// with their object if nothing else uses it.
+    ///
+    /// Pin sizes are in object units; build item scaling is not taken into account.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Lib3mfError::Validation`] if a build item to cut references an object that
+    /// is not a loaded mesh, or if cutting fails (see [`Mesh::cut_with`]). The model may be
+    /// partly cut when an error is returned.
+    pub fn cut_with(&mut self, plane: &Plane, options: &CutOptions) -> Result<CutReport> {
+        plane.unit()?;
+        let mut report = CutReport::default();
+        let components: HashSet<ResourceId> = self
+            .resources
+            .iter_objects()
+            .filter_map(|o| match &o.geometry {
+                Geometry::Components(c) => Some(c.components.iter().map(|c| c.object_id)),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        let mut uses: HashMap<ResourceId, usize> = HashMap::new();
+        for item in self.build.items.iter().filter(|i| i.path.is_none()) {
+            *uses.entry(item.object_id).or_default() += 1;
+        }
+
+        let mut items = Vec::with_capacity(self.build.items.len());
+        for mut item in std::mem::take(&mut self.build.items) {
+            if item.path.is_some()
+                || !(options.objects.is_empty() || options.objects.contains(&item.object_id))
+            {
+                items.push(item);
+                continue;
+            }
+            let id = item.object_id;
+            let object = self
+                .resources
+                .get_object(id)
+                .ok_or(Lib3mfError::ResourceNotFound(id.0))?;
+            let Geometry::Mesh(mesh) = &object.geometry else {
+                return Err(Lib3mfError::Validation(format!(
+                    "Object {} is not a mesh object and cannot be cut",
+                    id.0
+                )));
+            };
+            let cut = mesh.cut_with(&plane.to_local(&item.transform), options)?;
+            let template = object.clone();
+            if cut.crossed {
+                report.items_cut += 1;
+            }
+            report.pins += cut.pins.len();
+
+            // The last build item using an object cuts it in place
+            let owned = uses.get_mut(&id).is_some_and(|n| {
+                *n -= 1;
+                *n == 0
+            }) && !components.contains(&id);
+            let mut halves = [cut.above, cut.below]
+                .into_iter()
+                .filter(|m| !m.triangles.is_empty());
+            let Some(first) = halves.next() else {
+                report.items_removed += 1;
+                if owned {
+                    self.resources.remove_object(id);
+                }
+                continue;
+            };
+            if owned {
+                if let Some(object) = self.resources.iter_objects_mut().find(|o| o.id == id) {
+                    object.geometry = Geometry::Mesh(first);
+                }
+            } else {
+                item.object_id = self.add_cut_object(&template, first, None)?;
+                report.new_objects.push(item.object_id);
+            }
+            let second = halves
+                .next()
+                .map(|below| self.add_cut_object(&template, below, Some("below")))
+                .transpose()?;
+            items.push(item.clone());
+            if let Some(below) = second {
+                report.new_objects.push(below);
+                items.push(crate::model::BuildItem {
+                    object_id: below,
+                    uuid: item.uuid.map(|_| Uuid::new_v4()),
+                    ..item
+                });
+            }
+        }
+        self.build.items = items;
+        Ok(report)
+    }
+
+    fn add_cut_object(
+        &mut self,
+        template: &Object,
+        mesh: Mesh,
+        suffix: Option<&str>,
+    ) -> Result<ResourceId> {
+        let id = self.resources.allocate_id();
+        self.resources.add_object(Object {
+            id,
+            name: match (&template.name, suffix) {
+                (Some(name), Some(suffix)) => Some(format!("{} ({})", name, suffix)),
+                (name, _) => name.clone(),
+            },
+            uuid: template.uuid.map(|_| Uuid::new_v4()),
+            geometry: Geometry::Mesh(mesh),
+            ..template.clone()
+        })?;
+        Ok(id)
+    }
+}
+
+/// Right-handed in-plane axes for a plane facing `normal`.
+fn basis(normal: Vec3) -> (Vec3, Vec3) {
+    let u = normal.any_orthonormal_vector();
+    (u, normal.cross(u))
+}
+
+/// Closed loops of cap edges for a clipped half: its boundary edges in the plane, reversed,
+/// so outer loops run counter-clockwise seen from outside the half.
+fn boundary_loops(triangles: &[(Triangle, Option<usize>)], on_plane: &[bool]) -> Vec<Vec<u32>> {
+    // Net use of each undirected edge, positive for the low-to-high direction
+    let mut net: HashMap<(u32, u32), i32> = HashMap::new();
+    for (t, _) in triangles {
+        for (a, b) in [(t.v1, t.v2), (t.v2, t.v3), (t.v3, t.v1)] {
+            let (key, d) = if a < b { ((a, b), 1) } else { ((b, a), -1) };
+            *net.entry(key).or_default() += d;
+        }
+    }
+    let mut edges: Vec<(u32, u32)> = Vec::new();
+    for (&(lo, hi), &n) in &net {
+        if !(on_plane[lo as usize] && on_plane[hi as usize]) {
+            continue;
+        }
+        // Cap edges run against the boundary
+        let edge = if n > 0 { (hi, lo) } else { (lo, hi) };
+        edges.extend(std::iter::repeat_n(edge, n.unsigned_abs() as usize));
+    }
+    edges.sort_unstable();
+
+    let mut next: HashMap<u32, Vec<u32>> = HashMap::new();
+    for &(a, b) in edges.iter().rev() {
+        next.entry(a).or_default().push(b);
+    }
+    let mut loops = Vec::new();
+    for &(start, _) in &edges {
+        let mut chain = vec![start];
+        loop {
+            let current = *chain.last().unwrap();
+            let Some(to) = next.get_mut(&current).and_then(Vec::pop) else {
+                break;
+            };
+            if to == start {
+                loops.push(std::mem::take(&mut chain));
+                break;
+            }
+            chain.push(to);
+        }
+        // Open chains (a mesh that was not closed) stay uncapped
+    }
+    loops.retain(|l| l.len() >= 3);
+    loops
+}
+
+/// Picks pin centres on the lower half's cap, where `loops` are its cap loops.
+fn place_pins(
+    mesh: &Mesh,
+    plane: &Plane,
+    pool: &[Vec3],
+    loops: &[Vec<u32>],
+    pins: &PinOptions,
+) -> Vec<Vec3> {
+    let (u, v) = basis(plane.normal);
+    let origin = plane.normal * plane.offset;
+    let project = |id: &u32| {
+        let p = pool[*id as usize];
+        Vec2::new(p.dot(u), p.dot(v))
+    };
+    let rings: Vec<Vec<Vec2>> = loops
+        .iter()
+        .map(|l| l.iter().map(project).collect())
+        .collect();
+    let socket = pins.diameter / 2.0 + pins.clearance;
+    let depth = pins.length + pins.clearance;
+    let index = SpatialIndex::new(mesh);
+
+    let mut placed = Vec::new();
+    for (outer, holes) in regions(&rings) {
+        let mut region = vec![&rings[outer]];
+        region.extend(holes.iter().map(|&h| &rings[h]));
+        let (min, max) = rings[outer]
+            .iter()
+            .fold((Vec2::MAX, Vec2::MIN), |(lo, hi), &p| {
+                (lo.min(p), hi.max(p))
+            });
+        let step = (max - min).max_element() / 48.0;
+        let mut candidates: Vec<(f32, Vec2)> = Vec::new();
+        for i in 0..=((max.x - min.x) / step) as usize {
+            for j in 0..=((max.y - min.y) / step) as usize {
+                let p = min + Vec2::new(i as f32 + 0.5, j as f32 + 0.5) * step;
+                let inside = region.iter().filter(|ring| winds_around(ring, p)).count() % 2 == 1;
+                if !inside {
+                    continue;
+                }
+                let room = region
+                    .iter()
+                    .flat_map(|ring| (0..ring.len()).map(|k| (ring[k], ring[(k + 1) % ring.len()])))
+                    .map(|(a, b)| segment_distance(p, a, b))
+                    .fold(f32::MAX, f32::min);
+                if room < socket + pins.diameter / 2.0 {
+                    continue;
+                }
+                // The socket must stay inside the upper half
+                let centre = origin + u * p.x + v * p.y;
+                let clear = (1..=4).all(|k| {
+                    let q = centre + plane.normal * (depth * k as f32 / 4.0);
+                    index.contains(q)
+                        && index
+                            .closest_point(q)
+                            .is_some_and(|c| c.distance >= socket + pins.diameter / 2.0 - step)
+                });
+                if clear {
+                    candidates.push((room, p));
+                }
+            }
+        }
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+        let mut chosen: Vec<Vec2> = Vec::new();
+        while chosen.len() < pins.count {
+            let spread = |p: Vec2| {
+                chosen
+                    .iter()
+                    .map(|c| c.distance(p))
+                    .fold(f32::MAX, f32::min)
+            };
+            let best = candidates
+                .iter()
+                .filter(|(_, p)| spread(*p) >= 2.0 * pins.diameter)
+                .max_by(|a, b| {
+                    if chosen.is_empty() {
+                        a.0.total_cmp(&b.0)
+                    } else {
+                        spread(a.1).total_cmp(&spread(b.1))
+                    }
+                });
+            match best {
+                Some(&(_, p)) => chosen.push(p),
+                None => break,
+            }
+        }
+        placed.extend(chosen.into_iter().map(|p| origin + u * p.x + v * p.y));
+    }
+    placed
+}
+
+/// A prism of `radius` standing on `centre` along `facing * height`: the hole loop for the
+/// cap it stands on, and its side and end triangles.
+fn prism(
+    pool: &mut Vec<Vec3>,
+    centre: Vec3,
+    facing: Vec3,
+    radius: f32,
+    height: f32,
+    segments: u32,
+) -> (Vec<u32>, Vec<Triangle>) {
+    let (u, v) = basis(facing);
+    let start = pool.len() as u32;
+    // Clockwise seen from the facing side, as holes in the cap are
+    for j in 0..segments {
+        let angle = -TAU * j as f32 / segments as f32;
+        pool.push(centre + (u * angle.cos() + v * angle.sin()) * radius);
+    }
+    for j in 0..segments as usize {
+        pool.push(pool[start as usize + j] + facing * height);
+    }
+    let hole: Vec<u32> = (start..start + segments).collect();
+    let end = |j: u32| start + segments + j % segments;
+    let side = |j: u32| start + j % segments;
+    let triangle = |v1, v2, v3| Triangle {
+        v1,
+        v2,
+        v3,
+        ..Default::default()
+    };
+    let mut triangles = Vec::new();
+    for j in 0..segments {
+        triangles.push(triangle(side(j + 1), side(j), end(j)));
+        triangles.push(triangle(side(j + 1), end(j), end(j + 1)));
+    }
+    for j in 1..segments - 1 {
+        triangles.push(triangle(end(0), end(j + 1), end(j)));
+    }
+    (hole, triangles)
+}
+
+/// Groups counter-clockwise outer loops with the clockwise hole loops inside them.
+fn regions(rings: &[Vec<Vec2>]) -> Vec<(usize, Vec<usize>)> {
+    let areas: Vec<f32> = rings.iter().map(|r| signed_area(r)).collect();
+    let mut regions: Vec<(usize, Vec<usize>)> = (0..rings.len())
+        .filter(|&i| areas[i] > 0.0)
+        .map(|i| (i, Vec::new()))
+        .collect();
+    for hole in (0..rings.len()).filter(|&i| areas[i] < 0.0) {
+        let point = rings[hole][0];
+        let owner = regions
+            .iter_mut()
+            .filter(|(outer, _)| winds_around(&rings[*outer], point))
+            .min_by(|a, b| areas[a.0].total_cmp(&areas[b.0]));
+        if let Some((_, holes)) = owner {
+            holes.push(hole);
+        }
+    }
+    regions
+}
+
+/// Triangulates cap loops by ear clipping, after bridging holes into their outer loops.
+fn triangulate_cap(loops: &[Vec<u32>], project: &dyn Fn(u32) -> Vec2) -> Vec<[u32; 3]> {
+    let rings: Vec<Vec<Vec2>> = loops
+        .iter()
+        .map(|l| l.iter().map(|&id| project(id)).collect())
+        .collect();
+    let mut triangles = Vec::new();
+    for (outer, mut holes) in regions(&rings) {
+        let mut polygon: Vec<u32> = loops[outer].clone();
+        // Rightmost holes first, so later bridges can reach past earlier ones
+        let rightmost = |h: usize| {
+            (0..rings[h].len())
+                .max_by(|&a, &b| rings[h][a].x.total_cmp(&rings[h][b].x))
+                .unwrap_or(0)
+        };
+        holes.sort_by(|&a, &b| {
+            rings[b][rightmost(b)]
+                .x
+                .total_cmp(&rings[a][rightmost(a)].x)
+        });
+        for hole in holes {
+            let m = rightmost(hole);
+            let Some(p) = bridge(&polygon, rings[hole][m], project) else {
+                continue;
+            };
+            let ring = &loops[hole];
+            let mut merged = Vec::with_capacity(polygon.len() + ring.len() + 2);
+            merged.extend_from_slice(&polygon[..=p]);
+            merged.extend(ring[m..].iter().chain(&ring[..=m]));
+            merged.extend_from_slice(&polygon[p..]);
+            polygon = merged;
+        }
+        ear_clip(&polygon, project, &mut triangles);
+    }
+    triangles
+}
+
+/// Index of a vertex of `polygon` visible from hole vertex `m`, found by casting a ray in
+/// the +x direction.
+fn bridge(polygon: &[u32], m: Vec2, project: &dyn Fn(u32) -> Vec2) -> Option<usize> {
+    let points: Vec<Vec2> = polygon.iter().map(|&id| project(id)).collect();
+    let n = points.len();
+    let mut nearest = f32::MAX;
+    let mut candidate = None;
+    for i in 0..n {
+        let (a, b) = (points[i], points[(i + 1) % n]);
+        if a.y == b.y || (a.y - m.y) * (b.y - m.y) > 0.0 {
+            continue;
+        }
+        let x = a.x + (m.y - a.y) * (b.x - a.x) / (b.y - a.y);
+        if x >= m.x && x < nearest {
+            nearest = x;
+            candidate = Some(if a.x > b.x { i } else { (i + 1) % n });
+        }
+    }
+    let p = candidate?;
+    // A vertex inside the triangle from M to the hit and the candidate blocks the view;
+    // take the one closest in angle to the ray instead
+    let hit = Vec2::new(nearest, m.y);
+    let target = points[p];
+    let mut best = p;
+    let mut best_key = (f32::MAX, f32::MAX);
+    for (i, &q) in points.iter().enumerate() {
+        if i == p || q == target || q.x < m.x || !in_triangle_any(q, m, hit, target) {
+            continue;
+        }
+        let key = (
+            (q.y - m.y).abs() / (q.x - m.x).max(f32::MIN_POSITIVE),
+            m.distance(q),
+        );
+        if key < best_key {
+            best_key = key;
+            best = i;
+        }
+    }
+    Some(best)
+}
+
+/// Ear clipping of a counter-clockwise polygon that may touch itself at bridge vertices.
+fn ear_clip(polygon: &[u32], project: &dyn Fn(u32) -> Vec2, out: &mut Vec<[u32; 3]>) {
+    let points: Vec<Vec2> = polygon.iter().map(|&id| project(id)).collect();
+    let n = points.len();
+    if n < 3 {
+        return;
+    }
+    let mut prev: Vec<usize> = (0..n).map(|i| (i + n - 1) % n).collect();
+    let mut next: Vec<usize> = (0..n).map(|i| (i + 1) % n).collect();
+    let cross =
+        |a: usize, b: usize, c: usize| (points[b] - points[a]).perp_dot(points[c] - points[b]);
+    let mut remaining = n;
+    let mut current = 0;
+    let mut misses = 0;
+    let mut relaxed = false;
+    while remaining > 3 {
+        let (a, b, c) = (prev[current], current, next[current]);
+        let is_ear = if relaxed {
+            cross(a, b, c) >= 0.0 || misses >= 2 * remaining
+        } else {
+            cross(a, b, c) > 0.0 && {
+                let (pa, pb, pc) = (points[a], points[b], points[c]);
+                let mut blocked = false;
+                let mut k = next[c];
+                while k != a {
+                    let p = points[k];
+                    if p != pa
+                        && p != pb
+                        && p != pc
+                        && cross(prev[k], k, next[k]) <= 0.0
+                        && in_triangle(p, pa, pb, pc)
+                    {
+                        blocked = true;
+                        break;
+                    }
+                    k = next[k];
+                }
+                !blocked
+            }
+        };
+        if is_ear {
+            out.push([polygon[a], polygon[b], polygon[c]]);
+            next[a] = c;
+            prev[c] = a;
+            remaining -= 1;
+            current = c;
+            misses = 0;
+            relaxed = false;
+        } else {
+            current = next[current];
+            misses += 1;
+            if misses >= remaining {
+                // No clean ear left (degenerate input): accept flat or blocked ones
+                relaxed = true;
+            }
+        }
+    }
+    let a = prev[current];
+    out.push([polygon[a], polygon[current], polygon[next[current]]]);
+}
+
+fn signed_area(ring: &[Vec2]) -> f32 {
+    (0..ring.len())
+        .map(|i| ring[i].perp_dot(ring[(i + 1) % ring.len()]))
+        .sum::<f32>()
+        / 2.0
+}
+
+/// Whether a ray from `p` in the +x direction crosses `ring` an odd number of times.
+fn winds_around(ring: &[Vec2], p: Vec2) -> bool {
+    let mut inside = false;
+    for i in 0..ring.len() {
+        let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+        if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) * (b.x - a.x) / (b.y - a.y) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Whether `p` lies in or on the counter-clockwise triangle `a b c`.
+fn in_triangle(p: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    (b - a).perp_dot(p - a) >= 0.0
+        && (c - b).perp_dot(p - b) >= 0.0
+        && (a - c).perp_dot(p - c) >= 0.0
+}
+
+/// [`in_triangle`] for either winding.
+fn in_triangle_any(p: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    in_triangle(p, a, b, c) || in_triangle(p, a, c, b)
+}
+
+fn segment_distance(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let t = ((p - a).dot(ab) / ab.length_squared().max(f32::MIN_POSITIVE)).clamp(0.0, 1.0);
+    p.distance(a + ab * t)
+}
+
+/// Builds a half from its triangles, keeping only the vertices they use (in their original
+/// order) and carrying triangle sets and attributes over from `source`.
+fn compact(pool: &[Vec3], triangles: &[(Triangle, Option<usize>)], source: &Mesh) -> Mesh {
+    let mut mesh = Mesh {
+        volume_id: source.volume_id,
+        ..Mesh::new()
+    };
+    if triangles.is_empty() {
+        return mesh;
+    }
+    let mut used = vec![false; pool.len()];
+    for (t, _) in triangles {
+        for v in [t.v1, t.v2, t.v3] {
+            used[v as usize] = true;
+        }
+    }
+    let mut remap = vec![0u32; pool.len()];
+    for (i, p) in pool.iter().enumerate() {
+        if used[i] {
+            remap[i] = mesh.add_vertex(p.x, p.y, p.z);
+        }
+    }
+    let mut derived: Vec<Vec<u32>> = vec![Vec::new(); source.triangles.len()];
+    for (t, origin) in triangles {
+        if let Some(origin) = origin {
+            derived[*origin].push(mesh.triangles.len() as u32);
+        }
+        mesh.triangles.push(Triangle {
+            v1: remap[t.v1 as usize],
+            v2: remap[t.v2 as usize],
+            v3: remap[t.v3 as usize],
+            ..*t
+        });
+    }
+    for set in &source.triangle_sets {
+        let mut triangles: Vec<u32> = set
+            .triangles
+            .iter()
+            .filter_map(|&t| derived.get(t as usize))
+            .flatten()
+            .copied()
+            .collect();
+        if !triangles.is_empty() {
+            triangles.sort_unstable();
+            mesh.triangle_sets.push(TriangleSet {
+                triangles,
+                ..set.clone()
+            });
+        }
+    }
+    for (name, values) in source.triangle_attributes.channels() {
+        for (&t, value) in values {
+            for &new in derived.get(t as usize).into_iter().flatten() {
+                mesh.triangle_attributes.set(name, new, value.as_str());
+            }
+        }
+    }
+    mesh
+}
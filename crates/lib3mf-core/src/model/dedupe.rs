@@ -0,0 +1,483 @@
+//! Detection and merging of duplicate resources.
+//!
+//! Exporters often repeat themselves: a material group per object holding the same
+//! material, the same image stored once per texture, or a copy of an object per build item
+//! where one object placed several times would do. [`Model::find_duplicate_resources`]
+//! reports such repeats and what merging them would save, and
+//! [`Model::deduplicate_resources`] merges them, pointing every reference to a duplicate
+//! at the resource kept in its place.
+//!
+//! What counts as a duplicate:
+//!
+//! - Texture images: images of textures and displacement maps with identical bytes,
+//!   stored under different paths.
+//! - Textures: `texture2d` resources with the same image, content type, tile styles and filter.
+//! - Property groups (base materials, colors, texture coordinates, composite materials and
+//!   multi-properties): the same entries, referring to the same or duplicate resources.
+//! - Objects: the same [`object_hash`](Model::object_hash), that is the same type,
+//!   geometry to within 0.1 µm, components and resolved properties. Names, part numbers,
+//!   UUIDs and thumbnails are ignored; the kept object keeps its own.
+//!
+//! In each group the resource with the lowest ID is kept.
+//!
+//! ```
+//! use lib3mf_core::parser::parse_model;
+//!
+//! let xml = r##"<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+//!     xmlns:m="http://schemas.microsoft.com/3dmanufacturing/material/2015/02">
+//!     <resources>
+//!         <m:colorgroup id="1"><m:color color="#FF0000" /></m:colorgroup>
+//!         <m:colorgroup id="2"><m:color color="#FF0000" /></m:colorgroup>
+//!         <object id="3" pid="1" pindex="0"><mesh>
+//!             <vertices><vertex x="0" y="0" z="0" /><vertex x="1" y="0" z="0" />
+//!             <vertex x="0" y="1" z="0" /></vertices>
+//!             <triangles><triangle v1="0" v2="1" v3="2" /></triangles>
+//!         </mesh></object>
+//!         <object id="4" pid="2" pindex="0"><mesh>
+//!             <vertices><vertex x="0" y="0" z="0" /><vertex x="1" y="0" z="0" />
+//!             <vertex x="0" y="1" z="0" /></vertices>
+//!             <triangles><triangle v1="0" v2="1" v3="2" /></triangles>
+//!         </mesh></object>
+//!     </resources>
+//!     <build><item objectid="3" /><item objectid="4" /></build>
+//! </model>"##;
+//! let mut model = parse_model(std::io::Cursor::new(xml)).unwrap();
+//!
+//! let report = model.deduplicate_resources().unwrap();
+//! assert_eq!(report.groups.len(), 2);
+//! assert_eq!(report.triangles_saved, 1);
+//! assert_eq!(model.resources.iter_objects().count(), 1);
+//! assert!(model.build.items.iter().all(|item| item.object_id.0 == 3));
+//! ```
+
+use crate::archive::{
+    normalize_part_name, part_names_match, rels_source_part, resolve_part_target,
+};
+use crate::error::{Lib3mfError, Result};
+use crate::model::{Geometry, Model, Object, ResourceId};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Kind of resource in a [`DuplicateGroup`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DuplicateKind {
+    /// Mesh, components or other geometry objects.
+    Object,
+    /// `texture2d` image references.
+    Texture,
+    /// Base material groups.
+    BaseMaterials,
+    /// Color groups.
+    ColorGroup,
+    /// Texture coordinate groups.
+    TextureGroup,
+    /// Composite material groups.
+    CompositeMaterials,
+    /// Multi-property groups.
+    MultiProperties,
+}
+
+/// Resources that duplicate one another.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+    /// Kind of the resources in the group.
+    pub kind: DuplicateKind,
+    /// The resource kept, the lowest ID in the group.
+    pub kept: ResourceId,
+    /// The resources merged into `kept`, sorted by ID.
+    pub duplicates: Vec<ResourceId>,
+    /// Whether the duplicates are copies of `kept` apart from their IDs (and, for objects,
+    /// names and UUIDs). Otherwise they are equivalent: they refer to different but
+    /// duplicate resources, or differ below the hashing precision.
+    pub exact: bool,
+}
+
+/// An image stored under more than one path.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DuplicateImage {
+    /// The path kept, the first in sorted order.
+    pub kept: String,
+    /// Paths holding the same bytes, sorted.
+    pub duplicates: Vec<String>,
+    /// Size of one copy of the image in bytes.
+    pub bytes: usize,
+}
+
+/// Duplicates found by [`Model::find_duplicate_resources`] and merged by
+/// [`Model::deduplicate_resources`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DuplicateReport {
+    /// Duplicate resources, sorted by kept ID.
+    pub groups: Vec<DuplicateGroup>,
+    /// Duplicate texture and displacement images, sorted by kept path.
+    pub images: Vec<DuplicateImage>,
+    /// Vertices in duplicate mesh objects.
+    pub vertices_saved: usize,
+    /// Triangles in duplicate mesh objects.
+    pub triangles_saved: usize,
+    /// Size of the duplicate images in bytes.
+    pub bytes_saved: usize,
+}
+
+impl DuplicateReport {
+    /// Returns true if no duplicates were found.
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty() && self.images.is_empty()
+    }
+
+    /// IDs of every duplicate resource, sorted.
+    pub fn duplicate_ids(&self) -> Vec<ResourceId> {
+        let mut ids: Vec<ResourceId> = self
+            .groups
+            .iter()
+            .flat_map(|g| g.duplicates.iter().copied())
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+}
+
+impl Model {
+    /// Finds duplicate images, textures, property groups and objects. See the
+    /// [module docs](self) for what counts as a duplicate.
+    pub fn find_duplicate_resources(&self) -> DuplicateReport {
+        let res = &self.resources;
+        let images = duplicate_images(self);
+        let image_path: HashMap<String, String> = images
+            .iter()
+            .flat_map(|image| {
+                image
+                    .duplicates
+                    .iter()
+                    .map(|path| (part_key(path), part_key(&image.kept)))
+            })
+            .collect();
+        let canonical_path = |path: &str| {
+            let key = part_key(path);
+            image_path.get(&key).cloned().unwrap_or(key)
+        };
+
+        let mut groups = Vec::new();
+        let mut mapping: HashMap<ResourceId, ResourceId> = HashMap::new();
+        let textures = res
+            .iter_texture_2d()
+            .map(|t| {
+                let key = format!(
+                    "{}\n{}\n{:?}\n{:?}\n{:?}",
+                    canonical_path(&t.path),
+                    t.contenttype,
+                    t.tile_style_u,
+                    t.tile_style_v,
+                    t.filter
+                );
+                (t.id, key, format!("{}\n{}", t.path, t.contenttype))
+            })
+            .collect();
+        merge_keys(DuplicateKind::Texture, textures, &mut mapping, &mut groups);
+        let base_materials = res
+            .iter_base_materials()
+            .map(|g| (g.id, json(&g.materials), String::new()))
+            .collect();
+        merge_keys(
+            DuplicateKind::BaseMaterials,
+            base_materials,
+            &mut mapping,
+            &mut groups,
+        );
+        let colors = res
+            .iter_color_groups()
+            .map(|g| (g.id, json(&g.colors), String::new()))
+            .collect();
+        merge_keys(DuplicateKind::ColorGroup, colors, &mut mapping, &mut groups);
+
+        let canonical = |mapping: &HashMap<ResourceId, ResourceId>, id: ResourceId| {
+            mapping.get(&id).copied().unwrap_or(id)
+        };
+        let texture_groups = res
+            .iter_textures()
+            .map(|g| {
+                let texture = canonical(&mapping, g.texture_id);
+                (
+                    g.id,
+                    json(&(texture, &g.coords)),
+                    json(&(g.texture_id, &g.coords)),
+                )
+            })
+            .collect();
+        merge_keys(
+            DuplicateKind::TextureGroup,
+            texture_groups,
+            &mut mapping,
+            &mut groups,
+        );
+        let composites = res
+            .iter_composite_materials()
+            .map(|c| {
+                let base = canonical(&mapping, c.base_material_id);
+                (
+                    c.id,
+                    json(&(base, &c.indices, &c.composites)),
+                    json(&(c.base_material_id, &c.indices, &c.composites)),
+                )
+            })
+            .collect();
+        merge_keys(
+            DuplicateKind::CompositeMaterials,
+            composites,
+            &mut mapping,
+            &mut groups,
+        );
+        let multis = res
+            .iter_multi_properties()
+            .map(|m| {
+                let pids: Vec<ResourceId> =
+                    m.pids.iter().map(|&p| canonical(&mapping, p)).collect();
+                (
+                    m.id,
+                    json(&(pids, &m.blend_methods, &m.multis)),
+                    json(&(&m.pids, &m.blend_methods, &m.multis)),
+                )
+            })
+            .collect();
+        merge_keys(
+            DuplicateKind::MultiProperties,
+            multis,
+            &mut mapping,
+            &mut groups,
+        );
+
+        // Objects hash by content, so property references need no canonicalizing here
+        let mut by_hash: BTreeMap<[u8; 32], Vec<ResourceId>> = BTreeMap::new();
+        for (id, hash) in self.object_hashes() {
+            by_hash.entry(hash.0).or_default().push(id);
+        }
+        let (mut vertices_saved, mut triangles_saved) = (0, 0);
+        for mut ids in by_hash.into_values().filter(|ids| ids.len() > 1) {
+            ids.sort_unstable();
+            let kept = ids.remove(0);
+            let first = res.get_object(kept);
+            let mut exact = true;
+            for id in &ids {
+                let object = res.get_object(*id);
+                exact &= first.zip(object).is_some_and(|(a, b)| same_object(a, b));
+                if let Some(Geometry::Mesh(mesh)) = object.map(|o| &o.geometry) {
+                    vertices_saved += mesh.vertices.len();
+                    triangles_saved += mesh.triangles.len();
+                }
+            }
+            groups.push(DuplicateGroup {
+                kind: DuplicateKind::Object,
+                kept,
+                duplicates: ids,
+                exact,
+            });
+        }
+        groups.sort_by_key(|g| g.kept);
+
+        DuplicateReport {
+            bytes_saved: images.iter().map(|i| i.bytes * i.duplicates.len()).sum(),
+            groups,
+            images,
+            vertices_saved,
+            triangles_saved,
+        }
+    }
+
+    /// Merges the duplicates found by
+    /// [`find_duplicate_resources`](Self::find_duplicate_resources) and returns them.
+    ///
+    /// References to duplicate resources, from objects, property groups and build items,
+    /// are rewritten as by [`remap_ids`](Self::remap_ids) to point at the resource kept.
+    /// Textures and displacement maps using a duplicate image are pointed at the kept path
+    /// and the duplicate images and relationships to them are removed. References from
+    /// other model parts are left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Lib3mfError::InvalidStructure`, leaving the model unchanged, if an
+    /// object's mesh has not been loaded yet ([`Geometry::LazyMesh`]).
+    pub fn deduplicate_resources(&mut self) -> Result<DuplicateReport> {
+        if let Some(obj) = self
+            .resources
+            .iter_objects()
+            .find(|o| matches!(o.geometry, Geometry::LazyMesh(_)))
+        {
+            return Err(Lib3mfError::InvalidStructure(format!(
+                "Cannot deduplicate resources: mesh of object {} is not loaded",
+                obj.id.0
+            )));
+        }
+        let report = self.find_duplicate_resources();
+        if report.is_empty() {
+            return Ok(report);
+        }
+
+        self.repoint_images(&report.images)?;
+        let mut mapping = HashMap::new();
+        for group in &report.groups {
+            for &id in &group.duplicates {
+                self.resources.remove(id);
+                mapping.insert(id, group.kept);
+            }
+        }
+        self.remap_ids(&mapping)?;
+
+        let removed: Vec<&String> = report.images.iter().flat_map(|i| &i.duplicates).collect();
+        self.attachments
+            .retain(|path, _| !removed.iter().any(|r| part_names_match(path, r)));
+        for (rels_path, rels) in self.existing_relationships.iter_mut() {
+            let source = rels_source_part(rels_path);
+            rels.retain(|r| {
+                let target = resolve_part_target(&source, &r.target);
+                !removed.iter().any(|path| part_names_match(path, &target))
+            });
+        }
+        Ok(report)
+    }
+
+    /// Points textures and displacement maps using a duplicate image at the kept path.
+    fn repoint_images(&mut self, images: &[DuplicateImage]) -> Result<()> {
+        let kept_path = |path: &str| {
+            images
+                .iter()
+                .find(|i| i.duplicates.iter().any(|d| part_names_match(d, path)))
+                .map(|i| format!("/{}", i.kept))
+        };
+        let textures: Vec<_> = self
+            .resources
+            .iter_texture_2d()
+            .filter_map(|t| kept_path(&t.path).map(|path| (t.clone(), path)))
+            .collect();
+        for (mut texture, path) in textures {
+            self.resources.remove(texture.id);
+            texture.path = path;
+            self.resources.add_texture_2d(texture)?;
+        }
+        let maps: Vec<_> = self
+            .resources
+            .iter_displacement_2d()
+            .filter_map(|d| kept_path(&d.path).map(|path| (d.clone(), path)))
+            .collect();
+        for (mut map, path) in maps {
+            self.resources.remove(map.id);
+            map.path = path;
+            self.resources.add_displacement_2d(map)?;
+        }
+        Ok(())
+    }
+}
+
+/// Case-insensitive key for comparing part names.
+fn part_key(path: &str) -> String {
+    normalize_part_name(path).to_ascii_lowercase()
+}
+
+fn json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+/// Groups resources by their canonical key, records each duplicate's kept resource in
+/// `mapping` and appends the groups. `entries` are `(id, key, exact key)` in ID order; an
+/// empty exact key means the key is exact.
+fn merge_keys(
+    kind: DuplicateKind,
+    entries: Vec<(ResourceId, String, String)>,
+    mapping: &mut HashMap<ResourceId, ResourceId>,
+    groups: &mut Vec<DuplicateGroup>,
+) {
+    let mut found: Vec<(String, DuplicateGroup)> = Vec::new();
+    let mut by_key: HashMap<String, usize> = HashMap::new();
+    for (id, key, exact_key) in entries {
+        let exact_key = if exact_key.is_empty() {
+            key.clone()
+        } else {
+            exact_key
+        };
+        match by_key.get(&key) {
+            Some(&i) => {
+                let (kept_exact, group) = &mut found[i];
+                group.exact &= *kept_exact == exact_key;
+                group.duplicates.push(id);
+                mapping.insert(id, group.kept);
+            }
+            None => {
+                by_key.insert(key, found.len());
+                found.push((
+                    exact_key,
+                    DuplicateGroup {
+                        kind,
+                        kept: id,
+                        duplicates: Vec::new(),
+                        exact: true,
+                    },
+                ));
+            }
+        }
+    }
+    groups.extend(
+        found
+            .into_iter()
+            .map(|(_, group)| group)
+            .filter(|g| !g.duplicates.is_empty()),
+    );
+}
+
+/// Whether two objects are the same apart from ID, name, part number, UUID and thumbnail.
+fn same_object(a: &Object, b: &Object) -> bool {
+    a.object_type == b.object_type
+        && a.pid == b.pid
+        && a.pindex == b.pindex
+        && a.geometry == b.geometry
+}
+
+/// Images of textures and displacement maps stored more than once.
+fn duplicate_images(model: &Model) -> Vec<DuplicateImage> {
+    let mut paths: Vec<String> = model
+        .resources
+        .iter_texture_2d()
+        .map(|t| t.path.as_str())
+        .chain(
+            model
+                .resources
+                .iter_displacement_2d()
+                .map(|d| d.path.as_str()),
+        )
+        .map(normalize_part_name)
+        .collect();
+    paths.sort_unstable();
+    paths.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+
+    let mut seen = HashSet::new();
+    let mut by_digest: BTreeMap<[u8; 32], (usize, Vec<String>)> = BTreeMap::new();
+    for path in paths {
+        let Some((name, data)) = model
+            .attachments
+            .iter()
+            .find(|(name, _)| part_names_match(name, &path))
+        else {
+            continue;
+        };
+        if !seen.insert(name.clone()) {
+            continue;
+        }
+        let digest: [u8; 32] = Sha256::digest(data).into();
+        by_digest
+            .entry(digest)
+            .or_insert_with(|| (data.len(), Vec::new()))
+            .1
+            .push(path);
+    }
+
+    let mut images: Vec<DuplicateImage> = by_digest
+        .into_values()
+        .filter(|(_, paths)| paths.len() > 1)
+        .map(|(bytes, mut paths)| DuplicateImage {
+            kept: paths.remove(0),
+            duplicates: paths,
+            bytes,
+        })
+        .collect();
+    images.sort_by(|a, b| a.kept.cmp(&b.kept));
+    images
+}
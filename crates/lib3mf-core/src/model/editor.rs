@@ -0,0 +1,473 @@
+//! Transactional model editing with undo and redo.
+//!
+//! [`ModelEditor`] works on a copy of a model and records every change as an [`Edit`] in a
+//! command log. Each applied edit keeps its inverse, so changes can be undone and redone in
+//! any order, which is what interactive editors need. [`ModelEditor::commit`] returns the
+//! edited model; dropping the editor discards the changes and leaves the original untouched.
+//!
+//! Edits are checked when applied: an edit that would leave dangling references (a build
+//! item for a missing object, removing an object that is still in use) fails without
+//! changing anything.
+//!
+//! ```
+//! use lib3mf_core::model::{Geometry, Mesh, Model, Object, ObjectType, ResourceId};
+//! use glam::Mat4;
+//!
+//! let original = Model::default();
+//! let mut editor = original.edit();
+//!
+//! let id = editor.next_resource_id();
+//! editor.add_object(Object {
+//!     id,
+//!     object_type: ObjectType::Model,
+//!     name: Some("Part".to_string()),
+//!     part_number: None,
+//!     uuid: None,
+//!     pid: None,
+//!     pindex: None,
+//!     thumbnail: None,
+//!     geometry: Geometry::Mesh(Mesh::new()),
+//! })?;
+//! let item = editor.add_item(id)?;
+//! editor.set_item_transform(item, Mat4::from_translation(glam::Vec3::new(10.0, 0.0, 0.0)))?;
+//! editor.set_metadata("Title", "Bracket");
+//!
+//! editor.undo()?; // drop the title
+//! assert!(editor.model().metadata.get("Title").is_none());
+//! editor.redo()?;
+//!
+//! let edited = editor.commit();
+//! assert_eq!(edited.build.items.len(), 1);
+//! assert!(original.build.items.is_empty());
+//! # Ok::<(), lib3mf_core::error::Lib3mfError>(())
+//! ```
+
+use crate::error::{Lib3mfError, Result};
+use crate::model::build::check_build_reference;
+use crate::model::{BuildItem, Geometry, Metadata, MetadataEntry, Model, Object, ResourceId};
+use glam::Mat4;
+use std::fmt;
+
+/// A single reversible change to a model.
+#[derive(Debug, Clone)]
+pub enum Edit {
+    /// Adds an object resource. Its ID must not be in use.
+    AddObject(Object),
+    /// Removes an object that no build item, component or boolean shape references.
+    RemoveObject(ResourceId),
+    /// Replaces the object with the same ID, e.g. after editing its mesh.
+    ReplaceObject(Object),
+    /// Inserts a build item at `index` (`index == items.len()` appends).
+    InsertItem {
+        /// Position in the build item list.
+        index: usize,
+        /// The item to insert; its object must exist and be allowed in the build.
+        item: BuildItem,
+    },
+    /// Removes the build item at the given index.
+    RemoveItem(usize),
+    /// Sets the transform of the build item at `index`.
+    SetItemTransform {
+        /// Position in the build item list.
+        index: usize,
+        /// The new transform.
+        transform: Mat4,
+    },
+    /// Replaces all metadata entries named `name` with `entries`; an empty list removes
+    /// them.
+    SetMetadata {
+        /// Metadata name.
+        name: String,
+        /// The new entries, each named `name`.
+        entries: Vec<MetadataEntry>,
+    },
+    /// Replaces all metadata, keeping entry order exactly. Used to revert metadata edits.
+    ReplaceMetadata(Metadata),
+    /// Replaces the metadata entries named `name` of the build item at `index`; an empty
+    /// list removes them.
+    SetItemMetadata {
+        /// Position in the build item list.
+        index: usize,
+        /// Metadata name.
+        name: String,
+        /// The new entries, each named `name`.
+        entries: Vec<MetadataEntry>,
+    },
+    /// Replaces all metadata of the build item at `index`. Used to revert item metadata
+    /// edits.
+    ReplaceItemMetadata {
+        /// Position in the build item list.
+        index: usize,
+        /// The item's new metadata.
+        metadata: Metadata,
+    },
+}
+
+impl fmt::Display for Edit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Edit::AddObject(obj) => write!(f, "Add object {}", obj.id.0),
+            Edit::RemoveObject(id) => write!(f, "Remove object {}", id.0),
+            Edit::ReplaceObject(obj) => write!(f, "Modify object {}", obj.id.0),
+            Edit::InsertItem { item, .. } => {
+                write!(f, "Add build item for object {}", item.object_id.0)
+            }
+            Edit::RemoveItem(index) => write!(f, "Remove build item {}", index),
+            Edit::SetItemTransform { index, .. } => write!(f, "Transform build item {}", index),
+            Edit::SetMetadata { name, entries } if entries.is_empty() => {
+                write!(f, "Remove metadata '{}'", name)
+            }
+            Edit::SetMetadata { name, .. } => write!(f, "Set metadata '{}'", name),
+            Edit::ReplaceMetadata(_) => write!(f, "Replace metadata"),
+            Edit::SetItemMetadata {
+                index,
+                name,
+                entries,
+            } if entries.is_empty() => {
+                write!(f, "Remove metadata '{}' of build item {}", name, index)
+            }
+            Edit::SetItemMetadata { index, name, .. } => {
+                write!(f, "Set metadata '{}' of build item {}", name, index)
+            }
+            Edit::ReplaceItemMetadata { index, .. } => {
+                write!(f, "Replace metadata of build item {}", index)
+            }
+        }
+    }
+}
+
+/// An applied edit together with the edit that reverts it.
+#[derive(Debug, Clone)]
+struct Applied {
+    edit: Edit,
+    inverse: Edit,
+}
+
+/// Records edits to a model as an undoable command log. See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct ModelEditor {
+    model: Model,
+    done: Vec<Applied>,
+    undone: Vec<Edit>,
+}
+
+impl Model {
+    /// Starts editing a copy of this model.
+    pub fn edit(&self) -> ModelEditor {
+        ModelEditor::new(self.clone())
+    }
+}
+
+impl ModelEditor {
+    /// Creates an editor that takes ownership of `model`.
+    pub fn new(model: Model) -> Self {
+        Self {
+            model,
+            done: Vec::new(),
+            undone: Vec::new(),
+        }
+    }
+
+    /// Returns the model with all applied edits.
+    pub fn model(&self) -> &Model {
+        &self.model
+    }
+
+    /// Finishes editing and returns the edited model.
+    pub fn commit(self) -> Model {
+        self.model
+    }
+
+    /// Returns the applied edits, oldest first.
+    pub fn history(&self) -> impl Iterator<Item = &Edit> {
+        self.done.iter().map(|a| &a.edit)
+    }
+
+    /// Returns the edit that [`undo`](Self::undo) would revert.
+    pub fn undo_edit(&self) -> Option<&Edit> {
+        self.done.last().map(|a| &a.edit)
+    }
+
+    /// Returns the edit that [`redo`](Self::redo) would reapply.
+    pub fn redo_edit(&self) -> Option<&Edit> {
+        self.undone.last()
+    }
+
+    /// Returns true if there is an edit to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.done.is_empty()
+    }
+
+    /// Returns true if there is an undone edit to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// Returns an ID not used by any resource, for adding new resources.
+    pub fn next_resource_id(&self) -> ResourceId {
+        self.model.resources.allocate_id()
+    }
+
+    /// Applies an edit and records it. Clears the redo stack.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, leaving the model unchanged, if the edit doesn't apply to the
+    /// current model.
+    pub fn apply(&mut self, edit: Edit) -> Result<()> {
+        let inverse = apply_edit(&mut self.model, edit.clone())?;
+        self.done.push(Applied { edit, inverse });
+        self.undone.clear();
+        Ok(())
+    }
+
+    /// Reverts the most recent edit. Returns `false` if there was nothing to undo.
+    pub fn undo(&mut self) -> Result<bool> {
+        let Some(applied) = self.done.pop() else {
+            return Ok(false);
+        };
+        if let Err(e) = apply_edit(&mut self.model, applied.inverse.clone()) {
+            self.done.push(applied);
+            return Err(e);
+        }
+        self.undone.push(applied.edit);
+        Ok(true)
+    }
+
+    /// Reapplies the most recently undone edit. Returns `false` if there was nothing to redo.
+    pub fn redo(&mut self) -> Result<bool> {
+        let Some(edit) = self.undone.pop() else {
+            return Ok(false);
+        };
+        match apply_edit(&mut self.model, edit.clone()) {
+            Ok(inverse) => {
+                self.done.push(Applied { edit, inverse });
+                Ok(true)
+            }
+            Err(e) => {
+                self.undone.push(edit);
+                Err(e)
+            }
+        }
+    }
+
+    /// Adds an object resource.
+    pub fn add_object(&mut self, object: Object) -> Result<()> {
+        self.apply(Edit::AddObject(object))
+    }
+
+    /// Removes an object that nothing references.
+    pub fn remove_object(&mut self, id: ResourceId) -> Result<()> {
+        self.apply(Edit::RemoveObject(id))
+    }
+
+    /// Replaces the object with the same ID.
+    pub fn replace_object(&mut self, object: Object) -> Result<()> {
+        self.apply(Edit::ReplaceObject(object))
+    }
+
+    /// Appends an untransformed build item for `object_id` and returns its index.
+    pub fn add_item(&mut self, object_id: ResourceId) -> Result<usize> {
+        self.push_item(BuildItem::new(object_id, Mat4::IDENTITY))
+    }
+
+    /// Appends a build item and returns its index.
+    pub fn push_item(&mut self, item: BuildItem) -> Result<usize> {
+        let index = self.model.build.items.len();
+        self.apply(Edit::InsertItem { index, item })?;
+        Ok(index)
+    }
+
+    /// Removes the build item at `index`.
+    pub fn remove_item(&mut self, index: usize) -> Result<()> {
+        self.apply(Edit::RemoveItem(index))
+    }
+
+    /// Sets the transform of the build item at `index`.
+    pub fn set_item_transform(&mut self, index: usize, transform: Mat4) -> Result<()> {
+        self.apply(Edit::SetItemTransform { index, transform })
+    }
+
+    /// Sets a metadata entry, replacing any entries of the same name.
+    pub fn set_metadata(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into();
+        // Metadata edits can't fail.
+        let _ = self.apply(Edit::SetMetadata {
+            entries: vec![MetadataEntry::new(name.clone(), value)],
+            name,
+        });
+    }
+
+    /// Removes all metadata entries with the given name.
+    pub fn remove_metadata(&mut self, name: impl Into<String>) {
+        let _ = self.apply(Edit::SetMetadata {
+            name: name.into(),
+            entries: Vec::new(),
+        });
+    }
+
+    /// Sets a metadata entry of the build item at `index`, replacing any entries of the
+    /// same name.
+    pub fn set_item_metadata(
+        &mut self,
+        index: usize,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Result<()> {
+        let name = name.into();
+        self.apply(Edit::SetItemMetadata {
+            index,
+            entries: vec![MetadataEntry::new(name.clone(), value)],
+            name,
+        })
+    }
+
+    /// Removes all metadata entries with the given name from the build item at `index`.
+    pub fn remove_item_metadata(&mut self, index: usize, name: impl Into<String>) -> Result<()> {
+        self.apply(Edit::SetItemMetadata {
+            index,
+            name: name.into(),
+            entries: Vec::new(),
+        })
+    }
+}
+
+/// Applies `edit` to `model` and returns the edit that reverts it. The model is unchanged
+/// on error.
+fn apply_edit(model: &mut Model, edit: Edit) -> Result<Edit> {
+    match edit {
+        Edit::AddObject(object) => {
+            let id = object.id;
+            model.resources.add_object(object)?;
+            Ok(Edit::RemoveObject(id))
+        }
+        Edit::RemoveObject(id) => {
+            if model.resources.get_object(id).is_none() {
+                return Err(Lib3mfError::ResourceNotFound(id.0));
+            }
+            if let Some(user) = referenced_by(model, id) {
+                return Err(Lib3mfError::Validation(format!(
+                    "Object {} is still referenced by {}",
+                    id.0, user
+                )));
+            }
+            let object = model
+                .resources
+                .remove_object(id)
+                .ok_or(Lib3mfError::ResourceNotFound(id.0))?;
+            Ok(Edit::AddObject(object))
+        }
+        Edit::ReplaceObject(object) => {
+            let id = object.id;
+            let old = model
+                .resources
+                .remove_object(id)
+                .ok_or(Lib3mfError::ResourceNotFound(id.0))?;
+            model.resources.add_object(object)?;
+            Ok(Edit::ReplaceObject(old))
+        }
+        Edit::InsertItem { index, item } => {
+            if index > model.build.items.len() {
+                return Err(item_out_of_range(model, index));
+            }
+            if item.path.is_none() {
+                check_build_reference(model, index, item.object_id)?;
+            }
+            model.build.items.insert(index, item);
+            Ok(Edit::RemoveItem(index))
+        }
+        Edit::RemoveItem(index) => {
+            if index >= model.build.items.len() {
+                return Err(item_out_of_range(model, index));
+            }
+            let item = model.build.items.remove(index);
+            Ok(Edit::InsertItem { index, item })
+        }
+        Edit::SetItemTransform { index, transform } => {
+            let item = model
+                .build
+                .items
+                .get_mut(index)
+                .ok_or_else(|| Lib3mfError::Validation(format!("No build item {}", index)))?;
+            let old = std::mem::replace(&mut item.transform, transform);
+            Ok(Edit::SetItemTransform {
+                index,
+                transform: old,
+            })
+        }
+        Edit::SetMetadata { name, entries } => {
+            let old = model.metadata.clone();
+            model.metadata.replace(&name, entries);
+            Ok(Edit::ReplaceMetadata(old))
+        }
+        Edit::ReplaceMetadata(metadata) => Ok(Edit::ReplaceMetadata(std::mem::replace(
+            &mut model.metadata,
+            metadata,
+        ))),
+        Edit::SetItemMetadata {
+            index,
+            name,
+            entries,
+        } => {
+            let item = model
+                .build
+                .items
+                .get_mut(index)
+                .ok_or_else(|| Lib3mfError::Validation(format!("No build item {}", index)))?;
+            let old = item.metadata.clone();
+            item.metadata.replace(&name, entries);
+            Ok(Edit::ReplaceItemMetadata {
+                index,
+                metadata: old,
+            })
+        }
+        Edit::ReplaceItemMetadata { index, metadata } => {
+            let item = model
+                .build
+                .items
+                .get_mut(index)
+                .ok_or_else(|| Lib3mfError::Validation(format!("No build item {}", index)))?;
+            Ok(Edit::ReplaceItemMetadata {
+                index,
+                metadata: std::mem::replace(&mut item.metadata, metadata),
+            })
+        }
+    }
+}
+
+fn item_out_of_range(model: &Model, index: usize) -> Lib3mfError {
+    Lib3mfError::Validation(format!(
+        "Build item index {} out of range (build has {} items)",
+        index,
+        model.build.items.len()
+    ))
+}
+
+/// Describes the first local reference to object `id`, if any.
+fn referenced_by(model: &Model, id: ResourceId) -> Option<String> {
+    if let Some(index) = model
+        .build
+        .items
+        .iter()
+        .position(|item| item.path.is_none() && item.object_id == id)
+    {
+        return Some(format!("build item {}", index));
+    }
+    model.resources.iter_objects().find_map(|obj| {
+        let uses = match &obj.geometry {
+            Geometry::Components(comps) => comps
+                .components
+                .iter()
+                .any(|c| c.path.is_none() && c.object_id == id),
+            Geometry::BooleanShape(shape) => {
+                (shape.base_path.is_none() && shape.base_object_id == id)
+                    || shape
+                        .operations
+                        .iter()
+                        .any(|op| op.path.is_none() && op.object_id == id)
+            }
+            _ => false,
+        };
+        uses.then(|| format!("object {}", obj.id.0))
+    })
+}
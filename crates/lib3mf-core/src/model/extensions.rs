@@ -0,0 +1,277 @@
+//! 3MF extensions: their namespaces, and which ones a model uses.
+//!
+//! A producer declares an extension by binding its namespace on the `<model>` element, and
+//! lists the ones a consumer must understand in `requiredextensions`. Files often declare
+//! more than they use (this crate's writer declares every namespace it knows, except
+//! Production, which it declares only for models that use it), so
+//! [`Model::used_extensions`] looks at the content instead.
+//!
+//! # Examples
+//!
+//! ```
+//! use lib3mf_core::model::{Extension, Model};
+//!
+//! let model = Model::default();
+//! assert!(model.used_extensions().is_empty());
+//!
+//! let uri = "http://schemas.microsoft.com/3dmanufacturing/material/2015/02";
+//! assert_eq!(Extension::from_namespace(uri), Some(Extension::Materials));
+//! ```
+
+use crate::model::{Function, Geometry, Model};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Namespace of the 3MF core specification.
+pub const CORE_NAMESPACE: &str = "http://schemas.microsoft.com/3dmanufacturing/core/2015/02";
+
+/// A 3MF extension specification supported by this crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Extension {
+    /// Materials and Properties.
+    Materials,
+    /// Production (UUIDs and multi-part packages).
+    Production,
+    /// Beam Lattice.
+    BeamLattice,
+    /// Triangle Sets.
+    TriangleSets,
+    /// Slice.
+    Slice,
+    /// Volumetric.
+    Volumetric,
+    /// Implicit functions.
+    Implicit,
+    /// Secure Content.
+    SecureContent,
+    /// Boolean Operations.
+    BooleanOperations,
+    /// Displacement.
+    Displacement,
+}
+
+impl Extension {
+    /// All extensions.
+    pub const ALL: [Extension; 10] = [
+        Extension::Materials,
+        Extension::Production,
+        Extension::BeamLattice,
+        Extension::TriangleSets,
+        Extension::Slice,
+        Extension::Volumetric,
+        Extension::Implicit,
+        Extension::SecureContent,
+        Extension::BooleanOperations,
+        Extension::Displacement,
+    ];
+
+    /// Returns the human-readable name of the extension.
+    pub fn name(self) -> &'static str {
+        match self {
+            Extension::Materials => "Materials and Properties",
+            Extension::Production => "Production",
+            Extension::BeamLattice => "Beam Lattice",
+            Extension::TriangleSets => "Triangle Sets",
+            Extension::Slice => "Slice",
+            Extension::Volumetric => "Volumetric",
+            Extension::Implicit => "Implicit",
+            Extension::SecureContent => "Secure Content",
+            Extension::BooleanOperations => "Boolean Operations",
+            Extension::Displacement => "Displacement",
+        }
+    }
+
+    /// Returns the specification version this crate implements.
+    pub fn spec_version(self) -> &'static str {
+        match self {
+            Extension::Materials => "1.2.1",
+            Extension::Production => "1.1.2",
+            Extension::BeamLattice => "1.2.0",
+            Extension::TriangleSets => "2021/07",
+            Extension::Slice => "1.0.2",
+            Extension::Volumetric => "0.8.0",
+            Extension::Implicit => "2023/12",
+            Extension::SecureContent => "1.0.2",
+            Extension::BooleanOperations => "1.1.1",
+            Extension::Displacement => "1.0.0",
+        }
+    }
+
+    /// Returns the namespace URI the writer uses for the extension.
+    pub fn namespace(self) -> &'static str {
+        self.namespaces()[0]
+    }
+
+    /// Returns every namespace URI that identifies the extension, current one first.
+    pub fn namespaces(self) -> &'static [&'static str] {
+        match self {
+            Extension::Materials => {
+                &["http://schemas.microsoft.com/3dmanufacturing/material/2015/02"]
+            }
+            Extension::Production => {
+                &["http://schemas.microsoft.com/3dmanufacturing/production/2015/06"]
+            }
+            Extension::BeamLattice => &[
+                "http://schemas.microsoft.com/3dmanufacturing/beamlattice/2017/02",
+                "http://schemas.microsoft.com/3dmanufacturing/beamlattice/balls/2020/07",
+            ],
+            Extension::TriangleSets => {
+                &["http://schemas.microsoft.com/3dmanufacturing/trianglesets/2021/07"]
+            }
+            Extension::Slice => &["http://schemas.microsoft.com/3dmanufacturing/slice/2015/07"],
+            Extension::Volumetric => &[
+                "http://schemas.3mf.io/3dmanufacturing/volumetric/2022/01",
+                "http://schemas.microsoft.com/3dmanufacturing/volumetric/2018/11",
+            ],
+            Extension::Implicit => &["http://schemas.3mf.io/3dmanufacturing/implicit/2023/12"],
+            Extension::SecureContent => &[
+                "http://schemas.microsoft.com/3dmanufacturing/securecontent/2019/07",
+                "http://schemas.microsoft.com/3dmanufacturing/securecontent/2019/04",
+            ],
+            Extension::BooleanOperations => {
+                &["http://schemas.3mf.io/3dmanufacturing/booleanoperations/2023/07"]
+            }
+            Extension::Displacement => {
+                &["http://schemas.microsoft.com/3dmanufacturing/displacement/2024/01"]
+            }
+        }
+    }
+
+    /// Returns the extension identified by a namespace URI.
+    pub fn from_namespace(uri: &str) -> Option<Extension> {
+        Extension::ALL
+            .into_iter()
+            .find(|ext| ext.namespaces().contains(&uri))
+    }
+}
+
+impl Model {
+    /// Returns the used extensions a consumer must support to read this model correctly.
+    ///
+    /// The writer lists these in `requiredextensions`. Extensions that only add to
+    /// geometry a consumer can still print are left out: Materials (appearance), Triangle
+    /// Sets (grouping) and Production unless a build item or component references another
+    /// model part. Secure Content is left out too, as its key store lives outside the
+    /// model part.
+    pub fn extensions_to_require(&self) -> BTreeSet<Extension> {
+        let references_parts = self.build.items.iter().any(|item| item.path.is_some())
+            || self
+                .resources
+                .iter_objects()
+                .any(|object| match &object.geometry {
+                    Geometry::Components(components) => {
+                        components.components.iter().any(|c| c.path.is_some())
+                    }
+                    _ => false,
+                });
+        self.used_extensions()
+            .into_iter()
+            .filter(|ext| match ext {
+                Extension::Materials | Extension::TriangleSets | Extension::SecureContent => false,
+                Extension::Production => references_parts,
+                _ => true,
+            })
+            .collect()
+    }
+
+    /// Returns the extensions whose features this model's content uses.
+    ///
+    /// Only this model part is inspected; components in other parts are not followed.
+    pub fn used_extensions(&self) -> BTreeSet<Extension> {
+        let mut used = BTreeSet::new();
+        let resources = &self.resources;
+
+        if resources.base_material_groups_count() > 0
+            || resources.color_groups_count() > 0
+            || resources.texture_2d_groups_count() > 0
+            || resources.composite_materials_count() > 0
+            || resources.multi_properties_count() > 0
+            || resources.iter_texture_2d().next().is_some()
+        {
+            used.insert(Extension::Materials);
+        }
+        if resources.iter_slice_stacks().next().is_some() {
+            used.insert(Extension::Slice);
+        }
+        if resources.volumetric_stacks_count() > 0
+            || resources.iter_images_3d().next().is_some()
+            || resources.iter_volume_data().next().is_some()
+        {
+            used.insert(Extension::Volumetric);
+        }
+        for function in resources.iter_functions() {
+            used.insert(match function {
+                Function::Implicit(_) => Extension::Implicit,
+                Function::FromImage3D(_) => Extension::Volumetric,
+            });
+        }
+        if resources.displacement_2d_count() > 0 {
+            used.insert(Extension::Displacement);
+        }
+        if resources.key_store.is_some() {
+            used.insert(Extension::SecureContent);
+        }
+        if self.build.uuid.is_some()
+            || self
+                .build
+                .items
+                .iter()
+                .any(|item| item.uuid.is_some() || item.path.is_some())
+        {
+            used.insert(Extension::Production);
+        }
+
+        for object in resources.iter_objects() {
+            if object.uuid.is_some() {
+                used.insert(Extension::Production);
+            }
+            match &object.geometry {
+                Geometry::Mesh(mesh) => {
+                    if mesh.beam_lattice.is_some() {
+                        used.insert(Extension::BeamLattice);
+                    }
+                    if !mesh.triangle_sets.is_empty() {
+                        used.insert(Extension::TriangleSets);
+                    }
+                    if mesh.volume_id.is_some() {
+                        used.insert(Extension::Volumetric);
+                    }
+                }
+                Geometry::Components(components) => {
+                    if components
+                        .components
+                        .iter()
+                        .any(|c| c.uuid.is_some() || c.path.is_some())
+                    {
+                        used.insert(Extension::Production);
+                    }
+                }
+                Geometry::SliceStack(_) => {
+                    used.insert(Extension::Slice);
+                }
+                Geometry::VolumetricStack(_) | Geometry::LevelSet(_) => {
+                    used.insert(Extension::Volumetric);
+                }
+                Geometry::BooleanShape(_) => {
+                    used.insert(Extension::BooleanOperations);
+                }
+                Geometry::DisplacementMesh(_) => {
+                    used.insert(Extension::Displacement);
+                }
+                Geometry::PreciseMesh(mesh) => {
+                    if mesh.beam_lattice.is_some() {
+                        used.insert(Extension::BeamLattice);
+                    }
+                    if !mesh.triangle_sets.is_empty() {
+                        used.insert(Extension::TriangleSets);
+                    }
+                    if mesh.volume_id.is_some() {
+                        used.insert(Extension::Volumetric);
+                    }
+                }
+                Geometry::LazyMesh(_) => {}
+            }
+        }
+        used
+    }
+}
@@ -0,0 +1,422 @@
+//! Removal of optional package content: thumbnails, textures, vendor data, unused and
+//! duplicate resources.
+//!
+//! [`PackageFilter`] trims a [`Model`] loaded with its attachments down to what is needed
+//! for the geometry, e.g. to share a minimal file or shrink it before uploading to a
+//! quoting service.
+//!
+//! ```
+//! use lib3mf_core::model::{Model, PackageFilter};
+//!
+//! let mut model = Model::default();
+//! model
+//!     .attachments
+//!     .insert("Metadata/thumbnail.png".to_string(), vec![0x89, b'P', b'N', b'G']);
+//!
+//! let filter = PackageFilter {
+//!     thumbnails: true,
+//!     ..Default::default()
+//! };
+//! let report = filter.apply(&mut model);
+//! assert_eq!(report.attachments_removed, ["Metadata/thumbnail.png"]);
+//! assert!(model.attachments.is_empty());
+//! ```
+
+use crate::archive::{
+    normalize_part_name, part_names_match, rels_source_part, resolve_part_target,
+};
+use crate::model::{Function, Geometry, Model, ResourceId, ResourceKind};
+use std::collections::{BTreeSet, HashSet};
+
+/// Selects the kinds of content [`PackageFilter::apply`] removes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PackageFilter {
+    /// Package and object thumbnails: images under `Metadata/` (including vendor plate
+    /// previews), object `thumbnail` attributes and thumbnail relationships.
+    pub thumbnails: bool,
+    /// 2D textures and texture coordinate groups, their images, and multi-property groups
+    /// that layer them. Faces that used them lose their property assignment.
+    pub textures: bool,
+    /// Namespaced metadata entries (e.g. `BambuStudio:...`), their namespace declarations,
+    /// and non-image vendor files under `Metadata/` and `Auxiliaries/` such as slicer
+    /// configurations and embedded G-code.
+    pub vendor_metadata: bool,
+    /// Resources that no build item reaches through components, boolean shapes or
+    /// property references.
+    pub unused_resources: bool,
+    /// Duplicate images, textures, property groups and objects, merged into the copy kept
+    /// by [`Model::deduplicate_resources`]. Skipped if a mesh is not loaded.
+    pub duplicates: bool,
+}
+
+/// What [`PackageFilter::apply`] removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FilterReport {
+    /// Attachment paths removed, sorted.
+    pub attachments_removed: Vec<String>,
+    /// Resources removed, sorted by ID.
+    pub resources_removed: Vec<ResourceId>,
+    /// Metadata names removed, sorted.
+    pub metadata_removed: Vec<String>,
+    /// Total size of the removed attachments in bytes.
+    pub bytes_removed: usize,
+}
+
+impl FilterReport {
+    /// Returns true if nothing was removed.
+    pub fn is_empty(&self) -> bool {
+        self.attachments_removed.is_empty()
+            && self.resources_removed.is_empty()
+            && self.metadata_removed.is_empty()
+    }
+}
+
+impl PackageFilter {
+    /// A filter removing every kind of optional content.
+    pub fn all() -> Self {
+        Self {
+            thumbnails: true,
+            textures: true,
+            vendor_metadata: true,
+            unused_resources: true,
+            duplicates: true,
+        }
+    }
+
+    /// Removes the selected content from `model` and its attachments.
+    ///
+    /// Relationships pointing at removed attachments are dropped as well. Only the root
+    /// model is filtered; model parts stored as attachments are kept as they are.
+    pub fn apply(&self, model: &mut Model) -> FilterReport {
+        let mut attachments = BTreeSet::new();
+        let mut resources = BTreeSet::new();
+        let mut metadata = BTreeSet::new();
+        let mut bytes_removed = 0;
+
+        if self.thumbnails {
+            strip_thumbnails(model, &mut attachments);
+        }
+        if self.textures {
+            strip_textures(model, &mut attachments, &mut resources);
+        }
+        if self.vendor_metadata {
+            strip_vendor_metadata(model, &mut attachments, &mut metadata);
+        }
+        if self.duplicates
+            && let Ok(report) = model.deduplicate_resources()
+        {
+            resources.extend(report.duplicate_ids());
+            for image in &report.images {
+                attachments.extend(image.duplicates.iter().cloned());
+                bytes_removed += image.bytes * image.duplicates.len();
+            }
+        }
+        if self.unused_resources {
+            strip_unused_resources(model, &mut attachments, &mut resources);
+        }
+
+        for path in &attachments {
+            if let Some(data) = model.attachments.remove(path) {
+                bytes_removed += data.len();
+            }
+        }
+        for (rels_path, rels) in model.existing_relationships.iter_mut() {
+            let source = rels_source_part(rels_path);
+            rels.retain(|r| {
+                let target = resolve_part_target(&source, &r.target);
+                !attachments.iter().any(|a| part_names_match(a, &target))
+            });
+        }
+
+        FilterReport {
+            attachments_removed: attachments.into_iter().collect(),
+            resources_removed: resources.into_iter().collect(),
+            metadata_removed: metadata.into_iter().collect(),
+            bytes_removed,
+        }
+    }
+}
+
+fn is_image(path: &str) -> bool {
+    let lower = path.to_ascii_lowercase();
+    [".png", ".jpg", ".jpeg", ".gif", ".bmp", ".webp"]
+        .iter()
+        .any(|ext| lower.ends_with(ext))
+}
+
+fn in_dir(path: &str, dir: &str) -> bool {
+    normalize_part_name(path)
+        .get(..dir.len())
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(dir))
+}
+
+/// Attachment paths of images still referenced by texture or displacement resources.
+fn texture_paths(model: &Model) -> HashSet<String> {
+    model
+        .resources
+        .iter_texture_2d()
+        .map(|t| t.path.as_str())
+        .chain(
+            model
+                .resources
+                .iter_displacement_2d()
+                .map(|d| d.path.as_str()),
+        )
+        .map(normalize_part_name)
+        .collect()
+}
+
+fn strip_thumbnails(model: &mut Model, attachments: &mut BTreeSet<String>) {
+    let mut thumbnails: HashSet<String> = HashSet::new();
+    for object in model.resources.iter_objects_mut() {
+        if let Some(path) = object.thumbnail.take() {
+            thumbnails.insert(normalize_part_name(&path));
+        }
+    }
+    let textures = texture_paths(model);
+    for path in model.attachments.keys() {
+        let normalized = normalize_part_name(path);
+        if is_image(path)
+            && (in_dir(path, "Metadata/") || thumbnails.contains(&normalized))
+            && !textures.contains(&normalized)
+        {
+            attachments.insert(path.clone());
+        }
+    }
+    for rels in model.existing_relationships.values_mut() {
+        rels.retain(|r| !r.rel_type.contains("thumbnail"));
+    }
+}
+
+fn strip_textures(
+    model: &mut Model,
+    attachments: &mut BTreeSet<String>,
+    resources: &mut BTreeSet<ResourceId>,
+) {
+    let groups: HashSet<ResourceId> = model.resources.iter_textures().map(|g| g.id).collect();
+    let mut removed: HashSet<ResourceId> = groups.clone();
+    removed.extend(model.resources.iter_texture_2d().map(|t| t.id));
+    removed.extend(
+        model
+            .resources
+            .iter_multi_properties()
+            .filter(|m| m.pids.iter().any(|pid| groups.contains(pid)))
+            .map(|m| m.id),
+    );
+
+    let image_paths: Vec<String> = model
+        .resources
+        .iter_texture_2d()
+        .map(|t| normalize_part_name(&t.path))
+        .collect();
+    for &id in &removed {
+        model.resources.remove(id);
+    }
+    // Images shared with a displacement map stay.
+    let still_used = texture_paths(model);
+    for path in model.attachments.keys() {
+        let normalized = normalize_part_name(path);
+        if image_paths.contains(&normalized) && !still_used.contains(&normalized) {
+            attachments.insert(path.clone());
+        }
+    }
+
+    clear_property_references(model, &removed);
+    resources.extend(removed);
+}
+
+/// Unassigns object, triangle and vertex properties that point at `removed` resources.
+fn clear_property_references(model: &mut Model, removed: &HashSet<ResourceId>) {
+    let is_removed = |pid: Option<u32>| pid.is_some_and(|p| removed.contains(&ResourceId(p)));
+    for object in model.resources.iter_objects_mut() {
+        let object_removed = object.pid.is_some_and(|p| removed.contains(&p));
+        if object_removed {
+            object.pid = None;
+            object.pindex = None;
+        }
+        match &mut object.geometry {
+            Geometry::Mesh(mesh) => {
+                for tri in &mut mesh.triangles {
+                    if is_removed(tri.pid) || (tri.pid.is_none() && object_removed) {
+                        tri.pid = None;
+                        (tri.p1, tri.p2, tri.p3) = (None, None, None);
+                    }
+                }
+                if object_removed && let Some(lattice) = &mut mesh.beam_lattice {
+                    for beam in &mut lattice.beams {
+                        (beam.p1, beam.p2) = (None, None);
+                    }
+                }
+            }
+            Geometry::DisplacementMesh(mesh) => {
+                for tri in &mut mesh.triangles {
+                    if is_removed(tri.pid) || (tri.pid.is_none() && object_removed) {
+                        tri.pid = None;
+                        (tri.p1, tri.p2, tri.p3) = (None, None, None);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn strip_vendor_metadata(
+    model: &mut Model,
+    attachments: &mut BTreeSet<String>,
+    metadata: &mut BTreeSet<String>,
+) {
+    model.metadata.retain(|entry| {
+        let vendor = entry.name.contains(':');
+        if vendor {
+            metadata.insert(entry.name.clone());
+        }
+        !vendor
+    });
+    let prefixes: HashSet<&str> = model
+        .metadata
+        .names()
+        .filter_map(|name| name.split_once(':').map(|(prefix, _)| prefix))
+        .collect();
+    model
+        .extra_namespaces
+        .retain(|prefix, _| prefixes.contains(prefix.as_str()));
+
+    for path in model.attachments.keys() {
+        if (in_dir(path, "Metadata/") && !is_image(path)) || in_dir(path, "Auxiliaries/") {
+            attachments.insert(path.clone());
+        }
+    }
+}
+
+fn strip_unused_resources(
+    model: &mut Model,
+    attachments: &mut BTreeSet<String>,
+    resources: &mut BTreeSet<ResourceId>,
+) {
+    let used = used_resources(model);
+    let unused: Vec<ResourceId> = model
+        .resources
+        .iter_ids()
+        .filter(|id| !used.contains(id))
+        .collect();
+    if unused.is_empty() {
+        return;
+    }
+
+    let before = texture_paths(model);
+    for &id in &unused {
+        model.resources.remove(id);
+    }
+    let after = texture_paths(model);
+    for path in model.attachments.keys() {
+        let normalized = normalize_part_name(path);
+        if before.contains(&normalized) && !after.contains(&normalized) {
+            attachments.insert(path.clone());
+        }
+    }
+    resources.extend(unused);
+}
+
+/// Resources reachable from the build, following components, boolean operands, slice and
+/// volumetric stacks, and property references.
+fn used_resources(model: &Model) -> HashSet<ResourceId> {
+    let res = &model.resources;
+    let mut used = HashSet::new();
+    let mut pending: Vec<ResourceId> = model
+        .build
+        .items
+        .iter()
+        .filter(|item| item.path.is_none())
+        .map(|item| item.object_id)
+        .collect();
+
+    while let Some(id) = pending.pop() {
+        if !used.insert(id) {
+            continue;
+        }
+        if let Some(object) = res.get_object(id) {
+            pending.extend(object.pid);
+            match &object.geometry {
+                Geometry::Mesh(mesh) => {
+                    pending.extend(mesh.triangles.iter().filter_map(|t| t.pid).map(ResourceId));
+                    pending.extend(mesh.volume_id);
+                }
+                Geometry::PreciseMesh(mesh) => {
+                    pending.extend(mesh.triangles.iter().filter_map(|t| t.pid).map(ResourceId));
+                    pending.extend(mesh.volume_id);
+                }
+                Geometry::Components(components) => pending.extend(
+                    components
+                        .components
+                        .iter()
+                        .filter(|c| c.path.is_none())
+                        .map(|c| c.object_id),
+                ),
+                Geometry::SliceStack(stack) | Geometry::VolumetricStack(stack) => {
+                    pending.push(*stack);
+                }
+                Geometry::BooleanShape(shape) => {
+                    if shape.base_path.is_none() {
+                        pending.push(shape.base_object_id);
+                    }
+                    pending.extend(
+                        shape
+                            .operations
+                            .iter()
+                            .filter(|op| op.path.is_none())
+                            .map(|op| op.object_id),
+                    );
+                }
+                Geometry::DisplacementMesh(mesh) => {
+                    pending.extend(mesh.triangles.iter().filter_map(|t| t.pid).map(ResourceId));
+                    // Displacement triangles do not name their map, so keep them all.
+                    pending.extend(res.iter_displacement_2d().map(|d| d.id));
+                }
+                Geometry::LevelSet(level_set) => {
+                    pending.push(level_set.function_id);
+                    pending.push(level_set.mesh_id);
+                    pending.extend(level_set.volume_id);
+                }
+                Geometry::LazyMesh(_) => {
+                    // Triangle properties are unknown until the mesh is loaded.
+                    pending.extend(
+                        res.iter_all()
+                            .filter(|r| r.kind() != ResourceKind::Object)
+                            .map(|r| r.id()),
+                    );
+                }
+            }
+        } else if let Some(multi) = res.get_multi_properties(id) {
+            pending.extend(multi.pids.iter().copied());
+        } else if let Some(composite) = res.get_composite_materials(id) {
+            pending.push(composite.base_material_id);
+        } else if let Some(group) = res.get_texture_2d_group(id) {
+            pending.push(group.texture_id);
+        } else if let Some(function) = res.get_function(id) {
+            match function {
+                Function::Implicit(f) => pending.extend(
+                    f.nodes
+                        .iter()
+                        .filter(|n| n.kind == "constresourceid")
+                        .filter_map(|n| n.attribute("value")?.parse().ok())
+                        .map(ResourceId),
+                ),
+                Function::FromImage3D(f) => pending.push(f.image3d_id),
+            }
+        } else if let Some(data) = res.get_volume_data(id) {
+            pending.extend(data.composite.iter().map(|c| c.base_material_id));
+            pending.extend(data.function_refs().map(|r| r.function_id));
+        } else if let Some(stack) = res.get_slice_stack(id) {
+            pending.extend(
+                stack
+                    .slices
+                    .iter()
+                    .flat_map(|s| &s.polygons)
+                    .flat_map(|p| &p.segments)
+                    .filter_map(|s| s.pid),
+            );
+        }
+    }
+    used
+}
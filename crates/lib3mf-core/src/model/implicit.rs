@@ -0,0 +1,146 @@
+//! Implicit Extension types: function graphs that define scalar and vector fields.
+//!
+//! An [`ImplicitFunction`] is a resource with named inputs (for example the sampling
+//! position `pos`), a list of [`ImplicitNode`]s and named outputs. Each node reads other
+//! nodes' outputs or the function inputs through `ref` strings of the form
+//! `nodeidentifier.portidentifier` (or `inputs.identifier`). Level sets and volume data
+//! (see [`volumetric`](crate::model::volumetric)) evaluate functions by resource ID.
+//!
+//! Nodes are kept generically: the node element name is stored as [`ImplicitNode::kind`]
+//! and attributes other than `identifier`, `displayname` and `tag` are kept in order, so
+//! files using node types this crate doesn't know about round-trip unchanged.
+
+use crate::model::ResourceId;
+use serde::{Deserialize, Serialize};
+
+/// Type of value carried by a function or node port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PortType {
+    /// A single number.
+    #[default]
+    Scalar,
+    /// A 3D vector.
+    Vector,
+    /// A 4x4 matrix.
+    Matrix,
+    /// A resource ID (e.g. a mesh or another function).
+    ResourceId,
+}
+
+impl PortType {
+    /// Returns the element name declaring a port of this type (`scalar`, `vector`, ...).
+    pub fn element_name(self) -> &'static str {
+        match self {
+            PortType::Scalar => "scalar",
+            PortType::Vector => "vector",
+            PortType::Matrix => "matrix",
+            PortType::ResourceId => "resourceid",
+        }
+    }
+
+    /// Returns the element name referencing a port of this type (`scalarref`, ...).
+    pub fn reference_element_name(self) -> &'static str {
+        match self {
+            PortType::Scalar => "scalarref",
+            PortType::Vector => "vectorref",
+            PortType::Matrix => "matrixref",
+            PortType::ResourceId => "resourceref",
+        }
+    }
+
+    /// Parses a port element name, returning the type and whether it is a reference.
+    pub fn from_element_name(name: &str) -> Option<(Self, bool)> {
+        let (base, is_ref) = match name.strip_suffix("ref") {
+            Some(base) => (base, true),
+            None => (name, false),
+        };
+        let port_type = match base {
+            "scalar" => PortType::Scalar,
+            "vector" => PortType::Vector,
+            "matrix" => PortType::Matrix,
+            "resourceid" | "resource" => PortType::ResourceId,
+            _ => return None,
+        };
+        Some((port_type, is_ref))
+    }
+}
+
+/// An input or output of a function or node.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionPort {
+    /// Port name, unique within its `<in>` or `<out>` list.
+    pub identifier: String,
+    /// Human-readable name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// Value type.
+    #[serde(default)]
+    pub port_type: PortType,
+    /// Source of the value (`node.port` or `inputs.name`) for reference ports.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+}
+
+/// A single operation in an implicit function graph.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImplicitNode {
+    /// Node element name, e.g. `addition`, `constant`, `functioncall`.
+    pub kind: String,
+    /// Node name, unique within the function.
+    pub identifier: String,
+    /// Human-readable name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// Free-form grouping tag.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Other attributes in document order, e.g. `value` of a `constant`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<(String, String)>,
+    /// Input ports (usually references).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inputs: Vec<FunctionPort>,
+    /// Output ports.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outputs: Vec<FunctionPort>,
+}
+
+impl ImplicitNode {
+    /// Returns the value of attribute `name`, if present.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// An `<implicitfunction>` resource.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImplicitFunction {
+    /// Unique resource ID.
+    pub id: ResourceId,
+    /// Function name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+    /// Human-readable name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// Function inputs, e.g. the sampling position `pos`.
+    #[serde(default)]
+    pub inputs: Vec<FunctionPort>,
+    /// Function outputs, referencing node outputs.
+    #[serde(default)]
+    pub outputs: Vec<FunctionPort>,
+    /// Nodes in document order.
+    #[serde(default)]
+    pub nodes: Vec<ImplicitNode>,
+}
+
+impl ImplicitFunction {
+    /// Returns the node named `identifier`, if any.
+    pub fn node(&self, identifier: &str) -> Option<&ImplicitNode> {
+        self.nodes.iter().find(|n| n.identifier == identifier)
+    }
+}
@@ -0,0 +1,159 @@
+//! Per-object geometry checksums stored in model metadata.
+//!
+//! A lightweight alternative to XML digital signatures for catching corruption or
+//! accidental edits: [`Model::embed_geometry_checksums`] records the
+//! [`object_hash`](Model::object_hash) of every object as a namespaced metadata entry,
+//! and [`Model::verify_geometry_checksums`] recomputes and compares them. Validation at
+//! [`Standard`](crate::validation::ValidationLevel::Standard) level and above reports
+//! mismatches.
+//!
+//! Checksums of objects that reference other model parts or texture images can only be
+//! computed and verified when those parts are loaded into [`Model::attachments`].
+//!
+//! Entries are named `lib3mf:sha256.<object id>` with the hex digest as the value. They
+//! offer no protection against deliberate tampering, since anyone editing the geometry
+//! can recompute them; use signatures for that.
+//!
+//! ```
+//! use lib3mf_core::parser::parse_model;
+//! use lib3mf_core::model::Geometry;
+//!
+//! let xml = r#"<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+//!     <resources><object id="1" type="model"><mesh>
+//!         <vertices><vertex x="0" y="0" z="0" /><vertex x="10" y="0" z="0" />
+//!         <vertex x="0" y="10" z="0" /></vertices>
+//!         <triangles><triangle v1="0" v2="1" v3="2" /></triangles>
+//!     </mesh></object></resources>
+//!     <build><item objectid="1" /></build></model>"#;
+//! let mut model = parse_model(std::io::Cursor::new(xml)).unwrap();
+//!
+//! assert_eq!(model.embed_geometry_checksums(), 1);
+//! assert!(model.verify_geometry_checksums().is_empty());
+//!
+//! for obj in model.resources.iter_objects_mut() {
+//!     if let Geometry::Mesh(mesh) = &mut obj.geometry {
+//!         mesh.vertices[1].x = 11.0;
+//!     }
+//! }
+//! assert_eq!(model.verify_geometry_checksums().len(), 1);
+//! ```
+
+use crate::model::{ContentHash, MetadataEntry, Model, ResourceId};
+
+/// Namespace URI declared for the checksum metadata prefix.
+pub const INTEGRITY_NS: &str = "https://github.com/sscargal/lib3mf-rs/integrity/2026";
+
+/// Namespace prefix of checksum metadata names.
+pub const INTEGRITY_PREFIX: &str = "lib3mf";
+
+/// Metadata name prefix shared by all checksum entries.
+const CHECKSUM_NAME_PREFIX: &str = "lib3mf:sha256.";
+
+/// Returns the metadata name holding the checksum of object `id`.
+pub fn checksum_name(id: ResourceId) -> String {
+    format!("{}{}", CHECKSUM_NAME_PREFIX, id.0)
+}
+
+/// Why a stored geometry checksum failed verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChecksumFailure {
+    /// The object's geometry no longer matches the stored digest.
+    Mismatch {
+        /// Digest stored in the metadata.
+        expected: String,
+        /// Digest computed from the current geometry.
+        actual: ContentHash,
+    },
+    /// The checksum names an object that doesn't exist.
+    MissingObject,
+    /// The object references a model part or texture image that isn't loaded, so its
+    /// checksum can't be recomputed.
+    Unverifiable,
+    /// The metadata name or value is not a valid checksum entry.
+    Malformed,
+}
+
+/// A stored checksum that failed verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecksumError {
+    /// Metadata name of the checksum entry.
+    pub name: String,
+    /// Object the entry refers to, if the name could be parsed.
+    pub object_id: Option<ResourceId>,
+    /// What went wrong.
+    pub failure: ChecksumFailure,
+}
+
+impl Model {
+    /// Stores the checksum of every object as metadata, replacing any existing checksum
+    /// entries, and declares the checksum namespace. Returns the number of checksums stored.
+    ///
+    /// Objects referencing other model parts or textures need those parts in
+    /// [`attachments`](Model::attachments); objects whose checksum can't be computed are
+    /// skipped.
+    pub fn embed_geometry_checksums(&mut self) -> usize {
+        self.metadata
+            .retain(|entry| !entry.name.starts_with(CHECKSUM_NAME_PREFIX));
+
+        let mut checksums: Vec<(ResourceId, ContentHash)> =
+            self.object_hashes().into_iter().collect();
+        checksums.sort_by_key(|(id, _)| *id);
+        let checksums: Vec<MetadataEntry> = checksums
+            .into_iter()
+            .map(|(id, hash)| MetadataEntry::new(checksum_name(id), hash.to_hex()))
+            .collect();
+        let count = checksums.len();
+        self.metadata.extend(checksums);
+        if count > 0 {
+            self.extra_namespaces
+                .insert(INTEGRITY_PREFIX.to_string(), INTEGRITY_NS.to_string());
+        }
+        count
+    }
+
+    /// Returns true if the model carries any geometry checksum entries.
+    pub fn has_geometry_checksums(&self) -> bool {
+        self.metadata
+            .names()
+            .any(|name| name.starts_with(CHECKSUM_NAME_PREFIX))
+    }
+
+    /// Recomputes every stored geometry checksum and returns the entries that fail,
+    /// sorted by metadata name. An empty result means all checksums match.
+    pub fn verify_geometry_checksums(&self) -> Vec<ChecksumError> {
+        if !self.has_geometry_checksums() {
+            return Vec::new();
+        }
+        let hashes = self.object_hashes();
+        let mut errors: Vec<ChecksumError> = self
+            .metadata
+            .iter()
+            .filter_map(|entry| {
+                let (name, value) = (&entry.name, &entry.value);
+                let id = name.strip_prefix(CHECKSUM_NAME_PREFIX)?;
+                let object_id = id.parse().ok().map(ResourceId);
+                let failure = match (object_id, ContentHash::from_hex(value.trim())) {
+                    (Some(id), Some(expected)) => match hashes.get(&id).copied() {
+                        Some(actual) if actual == expected => return None,
+                        Some(actual) => ChecksumFailure::Mismatch {
+                            expected: value.trim().to_string(),
+                            actual,
+                        },
+                        None if self.resources.get_object(id).is_some() => {
+                            ChecksumFailure::Unverifiable
+                        }
+                        None => ChecksumFailure::MissingObject,
+                    },
+                    _ => ChecksumFailure::Malformed,
+                };
+                Some(ChecksumError {
+                    name: name.clone(),
+                    object_id,
+                    failure,
+                })
+            })
+            .collect();
+        errors.sort_by(|a, b| a.name.cmp(&b.name));
+        errors
+    }
+}
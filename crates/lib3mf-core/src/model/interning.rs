@@ -0,0 +1,196 @@
+//! Content-hash interning of identical mesh arrays.
+//!
+//! Exporters often write the same mesh several times: once per copy of a part, or once per
+//! model part of a multi-part package. A [`MeshInterner`] passed to the parser in
+//! [`ParseOptions::intern_meshes`](crate::parser::ParseOptions::intern_meshes) hashes the
+//! vertex and triangle arrays of each mesh as it is parsed. An array identical to one
+//! interned earlier is replaced by a [`SharedVec`] sharing that array's buffer, so the
+//! duplicate is freed as soon as it is read. Vertex and triangle arrays are interned
+//! separately, so meshes with the same vertices but different triangles still share their
+//! vertices.
+//!
+//! Arrays match when they hold the same values bit for bit, so `0.0` and `-0.0` differ.
+//! The interner doesn't keep arrays alive: once every mesh holding an array is dropped, a
+//! later identical array is kept as is. Shared arrays are copied when a mesh sharing them
+//! is first modified (see [`shared`](crate::model::shared)).
+//!
+//! ```
+//! use lib3mf_core::model::MeshInterner;
+//! use lib3mf_core::parser::{ParseOptions, parse_model_with_options};
+//!
+//! let mesh = r#"<mesh><vertices><vertex x="0" y="0" z="0" /><vertex x="1" y="0" z="0" />
+//!     <vertex x="0" y="1" z="0" /></vertices>
+//!     <triangles><triangle v1="0" v2="1" v3="2" /></triangles></mesh>"#;
+//! let xml = format!(
+//!     r#"<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+//!     <resources><object id="1">{mesh}</object><object id="2">{mesh}</object></resources>
+//!     <build><item objectid="1" /><item objectid="2" /></build>
+//! </model>"#
+//! );
+//! let options = ParseOptions {
+//!     intern_meshes: Some(MeshInterner::new()),
+//!     ..Default::default()
+//! };
+//! let model = parse_model_with_options(xml.as_bytes(), &options).unwrap();
+//!
+//! let stats = options.intern_meshes.unwrap().stats();
+//! assert_eq!(stats.buffers, 4);
+//! assert_eq!(stats.shared_buffers, 2);
+//! assert!(stats.deduplicated_bytes > 0);
+//! # let _ = model;
+//! ```
+//!
+//! [`Model::compute_stats_with`](crate::model::Model::compute_stats_with) reports the arrays
+//! shared between the distinct meshes of a build in
+//! [`UniqueGeometryStats`](crate::model::UniqueGeometryStats).
+
+use crate::model::{Mesh, SharedVec, Triangle, Vertex};
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+
+/// Shares identical vertex and triangle arrays between meshes.
+///
+/// Clones share one table, so a single interner can be handed to the parser of each model
+/// part of a package, or used from several threads. Two interners compare equal when they
+/// are clones of each other.
+#[derive(Clone, Default)]
+pub struct MeshInterner {
+    inner: Arc<Mutex<Interned>>,
+}
+
+/// What a [`MeshInterner`] has shared so far.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InternStats {
+    /// Non-empty vertex and triangle arrays interned
+    pub buffers: usize,
+    /// Arrays replaced by an identical array interned earlier
+    pub shared_buffers: usize,
+    /// Memory of the replaced arrays, freed by sharing them
+    pub deduplicated_bytes: u64,
+}
+
+#[derive(Default)]
+struct Interned {
+    vertices: Table<Vertex>,
+    triangles: Table<Triangle>,
+    stats: InternStats,
+}
+
+/// Interned arrays by content hash. A bucket holds every array with that hash.
+type Table<T> = HashMap<u64, Vec<Weak<Vec<T>>>>;
+
+impl MeshInterner {
+    /// Creates an interner with no arrays.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the vertex and triangle arrays of `mesh` with identical arrays interned
+    /// earlier, and interns those it doesn't find.
+    pub fn intern(&self, mesh: &mut Mesh) {
+        let mut interned = self.lock();
+        let Interned {
+            vertices,
+            triangles,
+            stats,
+        } = &mut *interned;
+        intern_buffer(vertices, &mut mesh.vertices, stats);
+        intern_buffer(triangles, &mut mesh.triangles, stats);
+    }
+
+    /// Returns what has been shared so far.
+    pub fn stats(&self) -> InternStats {
+        self.lock().stats
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Interned> {
+        // A panic while holding the lock leaves the table consistent
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl fmt::Debug for MeshInterner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MeshInterner")
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+impl PartialEq for MeshInterner {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for MeshInterner {}
+
+/// Array elements compared by their bits.
+trait InternKey {
+    type Key: Hash + Eq;
+
+    fn key(&self) -> Self::Key;
+}
+
+impl InternKey for Vertex {
+    type Key = [u32; 3];
+
+    fn key(&self) -> [u32; 3] {
+        [self.x.to_bits(), self.y.to_bits(), self.z.to_bits()]
+    }
+}
+
+impl InternKey for Triangle {
+    type Key = ([u32; 3], [Option<u32>; 4]);
+
+    fn key(&self) -> Self::Key {
+        (
+            [self.v1, self.v2, self.v3],
+            [self.p1, self.p2, self.p3, self.pid],
+        )
+    }
+}
+
+/// Shares `buffer` with an identical array interned earlier, or interns it.
+fn intern_buffer<T: InternKey>(
+    table: &mut Table<T>,
+    buffer: &mut SharedVec<T>,
+    stats: &mut InternStats,
+) {
+    if buffer.is_empty() {
+        return;
+    }
+    stats.buffers += 1;
+
+    let mut hasher = DefaultHasher::new();
+    buffer.len().hash(&mut hasher);
+    for item in buffer.iter() {
+        item.key().hash(&mut hasher);
+    }
+    let bucket = table.entry(hasher.finish()).or_default();
+    bucket.retain(|weak| weak.strong_count() > 0);
+
+    for weak in bucket.iter() {
+        let Some(existing) = SharedVec::upgrade(weak) else {
+            continue;
+        };
+        if SharedVec::ptr_eq(&existing, buffer) {
+            return;
+        }
+        let same = existing.len() == buffer.len()
+            && existing
+                .iter()
+                .zip(buffer.iter())
+                .all(|(a, b)| a.key() == b.key());
+        if same {
+            stats.shared_buffers += 1;
+            stats.deduplicated_bytes += std::mem::size_of_val(buffer.as_slice()) as u64;
+            *buffer = existing;
+            return;
+        }
+    }
+    bucket.push(buffer.downgrade());
+}
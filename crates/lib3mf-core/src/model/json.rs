@@ -0,0 +1,169 @@
+//! JSON import/export of a [`Model`].
+//!
+//! The model types derive `Serialize`/`Deserialize`; this module wraps them in a versioned
+//! document so JSON files can be diffed, patched, and stored in document databases, then
+//! turned back into a model:
+//!
+//! ```json
+//! {
+//!   "schema": "lib3mf-model",
+//!   "version": 2,
+//!   "model": { "unit": "millimeter", "metadata": [], "resources": {}, "build": {} }
+//! }
+//! ```
+//!
+//! Object keys are written in sorted order, so the same model always produces the same
+//! text. [`JSON_SCHEMA_VERSION`] is bumped whenever a change to the model types alters the
+//! JSON layout; [`Model::from_json`] rejects documents from other schema versions instead
+//! of guessing.
+//!
+//! Package-level data (attachments such as textures and thumbnails, and OPC relationships)
+//! is not part of the document. Meshes must be fully parsed: a model holding
+//! [`Geometry::LazyMesh`] objects is rejected, as it is by [`Model::write`].
+//!
+//! # Examples
+//!
+//! ```
+//! use lib3mf_core::Model;
+//!
+//! # fn main() -> lib3mf_core::error::Result<()> {
+//! let mut model = Model::default();
+//! model.metadata.insert("Title", "Bracket");
+//!
+//! let json = model.to_json()?;
+//! let restored = Model::from_json(&json)?;
+//! assert_eq!(restored.metadata["Title"], "Bracket");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Lib3mfError, Result};
+use crate::model::{Geometry, Model};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+/// Value of the `schema` field in every model document.
+pub const JSON_SCHEMA: &str = "lib3mf-model";
+
+/// Current version of the model document layout.
+pub const JSON_SCHEMA_VERSION: u32 = 2;
+
+#[derive(Serialize)]
+struct DocumentRef<'a> {
+    schema: &'static str,
+    version: u32,
+    model: &'a Model,
+}
+
+#[derive(Deserialize)]
+struct Document {
+    schema: String,
+    version: u32,
+    model: Model,
+}
+
+/// The envelope fields alone, used to explain why a document failed to parse.
+#[derive(Deserialize)]
+struct Header {
+    schema: String,
+    version: u32,
+}
+
+fn check_header(schema: &str, version: u32) -> Result<()> {
+    if schema != JSON_SCHEMA {
+        return Err(Lib3mfError::InvalidStructure(format!(
+            "Not a lib3mf model document: schema is '{schema}', expected '{JSON_SCHEMA}'"
+        )));
+    }
+    if version != JSON_SCHEMA_VERSION {
+        return Err(Lib3mfError::InvalidStructure(format!(
+            "Unsupported model document version {version} (this build reads version {JSON_SCHEMA_VERSION})"
+        )));
+    }
+    Ok(())
+}
+
+fn json_error(err: serde_json::Error) -> Lib3mfError {
+    if err.is_io() {
+        Lib3mfError::Io(err.into())
+    } else {
+        Lib3mfError::InvalidStructure(format!("Invalid model JSON: {err}"))
+    }
+}
+
+impl Model {
+    /// Serializes the model as a pretty-printed, versioned JSON document.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Lib3mfError::InvalidStructure` if an object still holds a lazy mesh.
+    pub fn to_json(&self) -> Result<String> {
+        let mut out = Vec::new();
+        self.write_json(&mut out)?;
+        Ok(String::from_utf8(out).expect("serde_json emits UTF-8"))
+    }
+
+    /// Writes the model as a pretty-printed, versioned JSON document.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Lib3mfError::InvalidStructure` if an object still holds a lazy mesh, or
+    /// `Lib3mfError::Io` if writing fails.
+    pub fn write_json<W: Write>(&self, writer: W) -> Result<()> {
+        if let Some(object) = self
+            .resources
+            .iter_objects()
+            .find(|o| matches!(o.geometry, Geometry::LazyMesh(_)))
+        {
+            return Err(Lib3mfError::InvalidStructure(format!(
+                "Object {} has a lazy mesh; call Model::materialize_meshes before exporting JSON",
+                object.id.0
+            )));
+        }
+
+        // Going through `Value` sorts map keys, so `HashMap` iteration order never leaks
+        // into the output.
+        let mut value = serde_json::to_value(DocumentRef {
+            schema: JSON_SCHEMA,
+            version: JSON_SCHEMA_VERSION,
+            model: self,
+        })
+        .map_err(json_error)?;
+        value.sort_all_objects();
+        serde_json::to_writer_pretty(writer, &value).map_err(json_error)
+    }
+
+    /// Parses a model from a JSON document produced by [`Model::to_json`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Lib3mfError::InvalidStructure` if the text is not a model document, comes
+    /// from another schema version, or does not match the model layout.
+    pub fn from_json(json: &str) -> Result<Model> {
+        match serde_json::from_str::<Document>(json) {
+            Ok(document) => {
+                check_header(&document.schema, document.version)?;
+                Ok(document.model)
+            }
+            Err(err) => {
+                // A version mismatch usually surfaces as a shape error inside `model`;
+                // report the version instead when the header says so.
+                if let Ok(header) = serde_json::from_str::<Header>(json) {
+                    check_header(&header.schema, header.version)?;
+                }
+                Err(json_error(err))
+            }
+        }
+    }
+
+    /// Reads a model from a JSON document produced by [`Model::write_json`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Model::from_json`], plus `Lib3mfError::Io` if reading fails.
+    pub fn read_json<R: Read>(mut reader: R) -> Result<Model> {
+        let mut json = String::new();
+        reader.read_to_string(&mut json)?;
+        Self::from_json(&json)
+    }
+}
@@ -0,0 +1,316 @@
+//! Lattice infill generated as Beam Lattice extension data.
+//!
+//! [`Mesh::lattice_fill`] hollows a closed mesh (see [`Mesh::shell_with`]) and fills the
+//! interior with a periodic [`LatticePattern`] of beams, emitted as a [`BeamLattice`] on the
+//! returned mesh. Lattice nodes lie on a cubic grid of [`LatticeOptions::cell_size`]
+//! centred on the mesh; beams that would come closer than their radius to the outer
+//! surface are dropped, and the rest end inside the wall, which anchors them.
+//!
+//! Beam thickness is set directly with [`LatticeOptions::beam_radius`] or derived from a
+//! target [`LatticeOptions::density`], the fraction of the interior volume the beams fill.
+//!
+//! ```
+//! use lib3mf_core::model::{LatticeOptions, LatticePattern, Mesh, OffsetOptions};
+//!
+//! let mut mesh = Mesh::new();
+//! for i in 0..8 {
+//!     let bit = |b: u32| if i >> b & 1 == 1 { 30.0 } else { 0.0 };
+//!     mesh.add_vertex(bit(0), bit(1), bit(2));
+//! }
+//! for [a, b, c, d] in [[0, 2, 3, 1], [4, 5, 7, 6], [0, 1, 5, 4], [2, 6, 7, 3], [0, 4, 6, 2], [1, 3, 7, 5]] {
+//!     mesh.add_triangle(a, b, c);
+//!     mesh.add_triangle(a, c, d);
+//! }
+//!
+//! let options = LatticeOptions {
+//!     pattern: LatticePattern::Octet,
+//!     cell_size: 6.0,
+//!     density: Some(0.1),
+//!     grid: OffsetOptions { voxel_size: Some(1.0), ..Default::default() },
+//!     ..Default::default()
+//! };
+//! let filled = mesh.lattice_fill(&options).unwrap();
+//! assert!(!filled.beam_lattice.unwrap().beams.is_empty());
+//! ```
+
+use crate::error::{Lib3mfError, Result};
+use crate::model::{Beam, BeamLattice, Mesh, OffsetOptions, ShellOptions, SpatialIndex};
+use glam::{IVec3, Vec3};
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+/// Repeating beam arrangement of a lattice infill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LatticePattern {
+    /// Cubic grid: beams along the cell edges, six per node.
+    #[default]
+    Grid,
+    /// Octet truss: face-centred cubic nodes joined to their twelve nearest neighbours.
+    Octet,
+    /// Laves (srs) net, the skeleton of one gyroid labyrinth: three beams per node.
+    Gyroid,
+}
+
+impl std::fmt::Display for LatticePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LatticePattern::Grid => write!(f, "grid"),
+            LatticePattern::Octet => write!(f, "octet"),
+            LatticePattern::Gyroid => write!(f, "gyroid"),
+        }
+    }
+}
+
+impl std::str::FromStr for LatticePattern {
+    type Err = Lib3mfError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "grid" => Ok(LatticePattern::Grid),
+            "octet" => Ok(LatticePattern::Octet),
+            "gyroid" => Ok(LatticePattern::Gyroid),
+            unknown => Err(Lib3mfError::Validation(format!(
+                "Invalid lattice pattern '{}'. Valid patterns are: grid, octet, gyroid",
+                unknown
+            ))),
+        }
+    }
+}
+
+impl LatticePattern {
+    /// Node positions within one cell, in eighths of the cell size.
+    fn unit_nodes(self) -> &'static [IVec3] {
+        const GRID: [IVec3; 1] = [IVec3::ZERO];
+        const OCTET: [IVec3; 4] = [
+            IVec3::ZERO,
+            IVec3::new(4, 4, 0),
+            IVec3::new(4, 0, 4),
+            IVec3::new(0, 4, 4),
+        ];
+        // Wyckoff 8a positions of space group I4₁32
+        const GYROID: [IVec3; 8] = [
+            IVec3::new(1, 1, 1),
+            IVec3::new(3, 7, 5),
+            IVec3::new(7, 5, 3),
+            IVec3::new(5, 3, 7),
+            IVec3::new(5, 5, 5),
+            IVec3::new(7, 3, 1),
+            IVec3::new(3, 1, 7),
+            IVec3::new(1, 7, 3),
+        ];
+        match self {
+            LatticePattern::Grid => &GRID,
+            LatticePattern::Octet => &OCTET,
+            LatticePattern::Gyroid => &GYROID,
+        }
+    }
+
+    /// Squared beam length, in eighths of the cell size.
+    fn beam_length_squared(self) -> i32 {
+        match self {
+            LatticePattern::Grid => 64,
+            LatticePattern::Octet => 32,
+            LatticePattern::Gyroid => 8,
+        }
+    }
+
+    /// Offsets from each unit node to its neighbours, in eighths.
+    fn neighbour_offsets(self) -> Vec<Vec<IVec3>> {
+        let nodes = self.unit_nodes();
+        nodes
+            .iter()
+            .map(|&from| {
+                let mut offsets = Vec::new();
+                for &to in nodes {
+                    for shift in (0..27).map(|i| IVec3::new(i % 3 - 1, i / 3 % 3 - 1, i / 9 - 1)) {
+                        let offset = to + shift * 8 - from;
+                        if offset.length_squared() == self.beam_length_squared() {
+                            offsets.push(offset);
+                        }
+                    }
+                }
+                offsets
+            })
+            .collect()
+    }
+
+    /// Total beam length in one cell, in cell sizes.
+    fn length_per_cell(self) -> f32 {
+        let beams: usize = self.neighbour_offsets().iter().map(Vec::len).sum::<usize>() / 2;
+        beams as f32 * (self.beam_length_squared() as f32).sqrt() / 8.0
+    }
+}
+
+/// Options for [`Mesh::lattice_fill`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatticeOptions {
+    /// Beam arrangement. Default: [`LatticePattern::Grid`].
+    pub pattern: LatticePattern,
+    /// Edge length of the repeating cubic cell. Default: `5.0`.
+    pub cell_size: f32,
+    /// Radius of every beam, unless [`density`](Self::density) is set. Default: `0.5`.
+    pub beam_radius: f32,
+    /// Fraction of the interior volume to fill with beams, between 0 and 1. When set, the
+    /// beam radius is derived from it. Default: `None`.
+    pub density: Option<f32>,
+    /// Wall thickness the mesh is hollowed to before filling. Default: `Some(2.0)`.
+    ///
+    /// `None` keeps the input triangles unchanged, for meshes whose interior is already
+    /// handled by the caller.
+    pub shell: Option<f32>,
+    /// Distance field grid settings for hollowing and clipping. Default:
+    /// `OffsetOptions::default()`.
+    pub grid: OffsetOptions,
+}
+
+impl Default for LatticeOptions {
+    fn default() -> Self {
+        Self {
+            pattern: LatticePattern::Grid,
+            cell_size: 5.0,
+            beam_radius: 0.5,
+            density: None,
+            shell: Some(2.0),
+            grid: OffsetOptions::default(),
+        }
+    }
+}
+
+impl LatticeOptions {
+    /// Beam radius in effect: derived from [`density`](Self::density) when set, otherwise
+    /// [`beam_radius`](Self::beam_radius).
+    ///
+    /// The density estimate treats beams as cylinders and ignores their overlap at nodes.
+    pub fn effective_radius(&self) -> f32 {
+        match self.density {
+            Some(density) => {
+                self.cell_size * (density / (PI * self.pattern.length_per_cell())).sqrt()
+            }
+            None => self.beam_radius,
+        }
+    }
+}
+
+impl Mesh {
+    /// Returns a copy of this closed mesh, hollowed per [`LatticeOptions::shell`], with its
+    /// interior filled by a lattice of beams.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Lib3mfError::Validation`] if the mesh has no triangles or already has a
+    /// beam lattice, an option is out of range, hollowing fails (see
+    /// [`shell_with`](Self::shell_with)), or no beam fits inside the mesh.
+    pub fn lattice_fill(&self, options: &LatticeOptions) -> Result<Mesh> {
+        if self.beam_lattice.is_some() {
+            return Err(Lib3mfError::Validation(
+                "Mesh already has a beam lattice".into(),
+            ));
+        }
+        let radius = options.effective_radius();
+        if !(options.cell_size > 0.0 && options.cell_size.is_finite()) {
+            return Err(Lib3mfError::Validation(format!(
+                "Lattice cell size must be positive, got {}",
+                options.cell_size
+            )));
+        }
+        if let Some(density) = options.density
+            && !(density > 0.0 && density < 1.0)
+        {
+            return Err(Lib3mfError::Validation(format!(
+                "Lattice density must be between 0 and 1, got {density}"
+            )));
+        }
+        if !(radius > 0.0 && radius.is_finite()) {
+            return Err(Lib3mfError::Validation(format!(
+                "Beam radius must be positive, got {radius}"
+            )));
+        }
+
+        let index = SpatialIndex::new(self);
+        let sdf = options.grid.sample(&index, radius, Some(4.0 * radius))?;
+        let bounds = index.bounds().ok_or_else(|| {
+            Lib3mfError::Validation("Cannot fill a mesh without triangles".into())
+        })?;
+        // Beams keep a radius, plus interpolation slack, from the outer surface
+        let clearance = -(radius + 0.5 * sdf.voxel_size());
+        let inside = |p: Vec3| sdf.sample(p) < clearance;
+
+        // Lattice centred on the mesh, in eighths of a cell
+        let cell = options.cell_size;
+        let cells = ((bounds.max - bounds.min) / cell).ceil().as_ivec3() + 1;
+        let origin = (bounds.min + bounds.max) * 0.5 - cells.as_vec3() * cell * 0.5;
+        let position = |g: IVec3| origin + g.as_vec3() * (cell / 8.0);
+
+        let unit_nodes = options.pattern.unit_nodes();
+        let offsets = options.pattern.neighbour_offsets();
+        let mut nodes: HashMap<IVec3, usize> = HashMap::new();
+        for z in 0..=cells.z {
+            for y in 0..=cells.y {
+                for x in 0..=cells.x {
+                    for (kind, unit) in unit_nodes.iter().enumerate() {
+                        let g = IVec3::new(x, y, z) * 8 + *unit;
+                        if inside(position(g)) {
+                            nodes.insert(g, kind);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut beams: Vec<(IVec3, IVec3)> = Vec::new();
+        let step = sdf.voxel_size();
+        for (&g, &kind) in &nodes {
+            for &offset in &offsets[kind] {
+                let h = g + offset;
+                // Each beam once, from its lexicographically smaller end
+                if h.to_array() < g.to_array() || !nodes.contains_key(&h) {
+                    continue;
+                }
+                let (a, b) = (position(g), position(h));
+                let samples = (a.distance(b) / step).ceil().max(2.0) as usize;
+                if (1..samples).all(|i| inside(a.lerp(b, i as f32 / samples as f32))) {
+                    beams.push((g, h));
+                }
+            }
+        }
+        if beams.is_empty() {
+            return Err(Lib3mfError::Validation(format!(
+                "No lattice beams of radius {radius} fit in cells of {cell}"
+            )));
+        }
+        // Stable output regardless of hash order
+        beams.sort_unstable_by_key(|&(a, b)| (a.to_array(), b.to_array()));
+
+        let mut mesh = match options.shell {
+            Some(thickness) => self.shell_with(&ShellOptions {
+                thickness,
+                drain_holes: Vec::new(),
+                grid: options.grid.clone(),
+            })?,
+            None => self.clone(),
+        };
+        let mut vertices: HashMap<IVec3, u32> = HashMap::new();
+        let mut vertex = |mesh: &mut Mesh, g: IVec3| {
+            *vertices.entry(g).or_insert_with(|| {
+                let p = position(g);
+                mesh.add_vertex(p.x, p.y, p.z)
+            })
+        };
+        let beams = beams
+            .into_iter()
+            .map(|(a, b)| Beam {
+                v1: vertex(&mut mesh, a),
+                v2: vertex(&mut mesh, b),
+                r1: radius,
+                r2: radius,
+                ..Default::default()
+            })
+            .collect();
+        mesh.beam_lattice = Some(BeamLattice {
+            radius: Some(radius),
+            beams,
+            ..Default::default()
+        });
+        Ok(mesh)
+    }
+}
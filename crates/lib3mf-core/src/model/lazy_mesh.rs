@@ -0,0 +1,142 @@
+//! Deferred mesh geometry for fast, low-memory model loading.
+//!
+//! [`parse_model_lazy`] indexes a model part without building its meshes: each mesh object
+//! gets a [`Geometry::LazyMesh`] holding the byte range of its `<mesh>` element plus vertex
+//! and triangle counts gathered while skipping it. Commands that only need the object
+//! graph, names, or counts can work on that model directly; geometry is parsed on demand
+//! with [`LazyMesh::load`], or for the whole model with [`Model::materialize_meshes`].
+//!
+//! The byte ranges refer to the decompressed part, so the archive the model came from must
+//! stay available (and unchanged) until every lazy mesh has been loaded. Validation skips
+//! triangle checks for meshes that are still lazy, and writing such a model is an error.
+//!
+//! [`parse_model_lazy`]: crate::parser::parse_model_lazy
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+//! use lib3mf_core::model::Geometry;
+//! use lib3mf_core::parser::parse_model_lazy;
+//! use std::fs::File;
+//! use std::io::BufReader;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut archive = ZipArchiver::new(File::open("plate.3mf")?)?;
+//! let model_path = find_model_path(&mut archive)?;
+//! // The part is decompressed as it is indexed, so it is never held in memory in full.
+//! let part = BufReader::new(archive.entry_reader(&model_path)?);
+//! let mut model = parse_model_lazy(part, &model_path)?;
+//!
+//! for object in model.resources.iter_objects() {
+//!     if let Geometry::LazyMesh(lazy) = &object.geometry {
+//!         println!("object {}: {} triangles", object.id.0, lazy.triangle_count);
+//!     }
+//! }
+//!
+//! // Parse every deferred mesh when full geometry is needed.
+//! model.materialize_meshes(&mut archive)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::archive::ArchiveReader;
+use crate::error::{Lib3mfError, Result};
+use crate::model::{Geometry, Mesh, Model};
+use crate::parser::encoding::decode_xml;
+use crate::parser::mesh_parser::parse_mesh_element;
+use crate::parser::xml_parser::XmlParser;
+use quick_xml::events::Event;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Handle to a `<mesh>` element that has not been parsed yet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LazyMesh {
+    /// Archive path of the model part containing the mesh (e.g. `"3D/3dmodel.model"`).
+    pub part: String,
+    /// Byte range of the `<mesh>` element within the decompressed part.
+    pub span: Range<usize>,
+    /// Number of `<vertex>` elements in the mesh.
+    pub vertex_count: usize,
+    /// Number of `<triangle>` elements in the mesh.
+    pub triangle_count: usize,
+}
+
+impl LazyMesh {
+    /// Parses the mesh from the archive part it was indexed from.
+    ///
+    /// Reads and decompresses the whole part; when loading several meshes from the same
+    /// part, read it once and use [`load_from`](Self::load_from) instead.
+    pub fn load(&self, archive: &mut impl ArchiveReader) -> Result<Mesh> {
+        let data = archive.read_entry(&self.part)?;
+        self.load_from(&data)
+    }
+
+    /// Parses the mesh from the already-decompressed contents of its part.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Lib3mfError::InvalidStructure`] if the span does not lie within `part_data`
+    /// or does not contain a `<mesh>` element, and any error from the mesh parser.
+    pub fn load_from(&self, part_data: &[u8]) -> Result<Mesh> {
+        // The span indexes the part as decoded to UTF-8, which is a no-op for UTF-8 parts.
+        let part_data = &*decode_xml(part_data)?;
+        let fragment = part_data.get(self.span.clone()).ok_or_else(|| {
+            Lib3mfError::InvalidStructure(format!(
+                "Mesh span {:?} is outside part '{}' ({} bytes)",
+                self.span,
+                self.part,
+                part_data.len()
+            ))
+        })?;
+
+        let mut parser = XmlParser::new(fragment);
+        loop {
+            match parser.read_next_event()? {
+                Event::Start(e) if e.local_name().as_ref() == b"mesh" => {
+                    let start = e.into_owned();
+                    return parse_mesh_element(&mut parser, &start);
+                }
+                Event::Eof => {
+                    return Err(Lib3mfError::InvalidStructure(format!(
+                        "No <mesh> element at {:?} in part '{}'",
+                        self.span, self.part
+                    )));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Model {
+    /// Replaces every [`Geometry::LazyMesh`] with its parsed [`Mesh`].
+    ///
+    /// Each referenced part is read from `archive` once. Returns the number of meshes
+    /// that were materialized.
+    pub fn materialize_meshes(&mut self, archive: &mut impl ArchiveReader) -> Result<usize> {
+        let mut parts: HashMap<String, Vec<u8>> = HashMap::new();
+        let mut count = 0;
+        for object in self.resources.iter_objects_mut() {
+            let Geometry::LazyMesh(lazy) = &object.geometry else {
+                continue;
+            };
+            if !parts.contains_key(&lazy.part) {
+                parts.insert(lazy.part.clone(), archive.read_entry(&lazy.part)?);
+            }
+            let mesh = lazy.load_from(&parts[&lazy.part])?;
+            object.geometry = Geometry::Mesh(mesh);
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Returns true if any object still holds a [`Geometry::LazyMesh`].
+    pub fn has_lazy_meshes(&self) -> bool {
+        self.resources
+            .iter_objects()
+            .any(|o| matches!(o.geometry, Geometry::LazyMesh(_)))
+    }
+}
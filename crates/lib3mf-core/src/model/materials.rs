@@ -23,12 +23,19 @@ impl Color {
         Self { r, g, b, a }
     }
 
-    /// Parses a hex color string in `#RRGGBB` or `#RRGGBBAA` format.
+    /// Parses a hex color string in `#RRGGBB` or `#RRGGBBAA` format, or the short
+    /// `#RGB`/`#RGBA` forms where each digit is doubled (`#F80` is `#FF8800FF`).
     pub fn from_hex(hex: &str) -> Option<Self> {
         let hex = hex.trim_start_matches('#');
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
         let val = u32::from_str_radix(hex, 16).ok()?;
+        let nibble = |shift: u32| ((val >> shift) & 0xF) as u8 * 0x11;
 
         match hex.len() {
+            3 => Some(Self::new(nibble(8), nibble(4), nibble(0), 255)),
+            4 => Some(Self::new(nibble(12), nibble(8), nibble(4), nibble(0))),
             6 => Some(Self {
                 r: ((val >> 16) & 0xFF) as u8,
                 g: ((val >> 8) & 0xFF) as u8,
@@ -45,12 +52,105 @@ impl Color {
         }
     }
 
+    /// Looks up a named color, case-insensitively.
+    ///
+    /// Covers the CSS basic color keywords plus `orange`, `grey` and `transparent`, which
+    /// some exporters write instead of a hex value.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let rgb = match name.to_ascii_lowercase().as_str() {
+            "black" => 0x000000,
+            "silver" => 0xC0C0C0,
+            "gray" | "grey" => 0x808080,
+            "white" => 0xFFFFFF,
+            "maroon" => 0x800000,
+            "red" => 0xFF0000,
+            "purple" => 0x800080,
+            "fuchsia" | "magenta" => 0xFF00FF,
+            "green" => 0x008000,
+            "lime" => 0x00FF00,
+            "olive" => 0x808000,
+            "yellow" => 0xFFFF00,
+            "navy" => 0x000080,
+            "blue" => 0x0000FF,
+            "teal" => 0x008080,
+            "aqua" | "cyan" => 0x00FFFF,
+            "orange" => 0xFFA500,
+            "transparent" => return Some(Self::new(0, 0, 0, 0)),
+            _ => return None,
+        };
+        Some(Self::new(
+            (rgb >> 16) as u8,
+            (rgb >> 8) as u8,
+            rgb as u8,
+            255,
+        ))
+    }
+
+    /// Parses a color attribute value: any form accepted by [`Color::from_hex`], or a
+    /// name accepted by [`Color::from_name`].
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.starts_with('#') {
+            Self::from_hex(value)
+        } else {
+            Self::from_name(value).or_else(|| Self::from_hex(value))
+        }
+    }
+
+    /// Returns the color as linear-light RGBA components in `0.0..=1.0`.
+    ///
+    /// RGB is decoded from sRGB; alpha is already linear and only rescaled.
+    pub fn to_linear(&self) -> [f32; 4] {
+        [
+            srgb_to_linear(f32::from(self.r) / 255.0),
+            srgb_to_linear(f32::from(self.g) / 255.0),
+            srgb_to_linear(f32::from(self.b) / 255.0),
+            f32::from(self.a) / 255.0,
+        ]
+    }
+
+    /// Builds a color from linear-light RGBA components, encoding RGB as sRGB.
+    ///
+    /// Components are clamped to `0.0..=1.0` and rounded to the nearest 8-bit value.
+    pub fn from_linear(rgba: [f32; 4]) -> Self {
+        let quantize = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+        Self::new(
+            quantize(linear_to_srgb(rgba[0])),
+            quantize(linear_to_srgb(rgba[1])),
+            quantize(linear_to_srgb(rgba[2])),
+            quantize(rgba[3]),
+        )
+    }
+
+    /// Whether the color is fully transparent (alpha 0).
+    pub fn is_transparent(&self) -> bool {
+        self.a == 0
+    }
+
     /// Convert color to hex string #RRGGBBAA
     pub fn to_hex(&self) -> String {
         format!("#{:02X}{:02X}{:02X}{:02X}", self.r, self.g, self.b, self.a)
     }
 }
 
+/// Decodes an sRGB-encoded component in `0.0..=1.0` to linear light (IEC 61966-2-1).
+pub fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Encodes a linear-light component in `0.0..=1.0` as sRGB (IEC 61966-2-1).
+pub fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
 /// A base material with a name and display color.
 ///
 /// Base materials represent named material types (e.g., "PLA", "ABS", "Steel")
@@ -123,7 +223,7 @@ pub struct Texture2DCoord {
 /// Texture2D defines a reference to an image file within the 3MF package
 /// that can be applied to mesh surfaces. The actual image data is stored
 /// as an attachment and referenced by the path.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Texture2D {
     /// Unique resource ID for this texture
     pub id: ResourceId,
@@ -131,6 +231,15 @@ pub struct Texture2D {
     pub path: String,
     /// MIME content type of the texture (e.g., "image/png", "image/jpeg")
     pub contenttype: String,
+    /// How the texture repeats along u (`tilestyleu`)
+    #[serde(default)]
+    pub tile_style_u: TileStyle,
+    /// How the texture repeats along v (`tilestylev`)
+    #[serde(default)]
+    pub tile_style_v: TileStyle,
+    /// Texture filtering (`filter`); `None` is `auto`, leaving the choice to the consumer
+    #[serde(default)]
+    pub filter: Option<FilterMode>,
 }
 
 /// A resource group for composite/mixed materials.
@@ -138,7 +247,7 @@ pub struct Texture2D {
 /// Composite materials allow blending multiple materials together with
 /// specified mixing ratios. This enables gradient materials, multi-material
 /// prints, and material transitions.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CompositeMaterials {
     /// Unique resource ID for this composite materials group
     pub id: ResourceId,
@@ -154,7 +263,7 @@ pub struct CompositeMaterials {
 ///
 /// The values specify mixing ratios for the materials referenced by
 /// the parent `CompositeMaterials`' indices. Values typically sum to 1.0.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Composite {
     /// Mixing ratios for each material (typically summing to 1.0)
     pub values: Vec<f32>,
@@ -165,7 +274,7 @@ pub struct Composite {
 /// Multi-properties allow applying multiple different property types
 /// (materials, colors, textures) to the same geometry, with specified
 /// blending methods to combine them.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MultiProperties {
     /// Unique resource ID for this multi-properties group
     pub id: ResourceId,
@@ -178,7 +287,7 @@ pub struct MultiProperties {
 }
 
 /// A single multi-property combination specifying indices into each property group.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Multi {
     /// Property indices for each property group (parallel to parent's pids)
     pub pindices: Vec<u32>,
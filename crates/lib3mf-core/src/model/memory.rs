@@ -0,0 +1,208 @@
+//! Estimated memory taken by a loaded model.
+//!
+//! [`Model::memory_usage`] adds up the heap allocations behind each kind of resource:
+//! vector capacities times element sizes, plus the text of names and attribute values. It
+//! does not follow allocator overhead or small fixed-size fields, so the total is a lower
+//! bound that is close for mesh-heavy models. Lazy meshes count as empty until they are
+//! loaded. Mesh arrays shared with clones of the model ([`SharedVec`]) are counted in full
+//! by each clone.
+//!
+//! [`SharedVec`]: crate::model::SharedVec
+//!
+//! To refuse models that would not fit before they are built, parse with
+//! [`parse_model_with_budget`](crate::parser::parse_model_with_budget).
+//!
+//! ```
+//! use lib3mf_core::model::{Geometry, Mesh, Model, Object, ObjectType, ResourceId};
+//!
+//! let mut mesh = Mesh::new();
+//! let a = mesh.add_vertex(0.0, 0.0, 0.0);
+//! let b = mesh.add_vertex(1.0, 0.0, 0.0);
+//! let c = mesh.add_vertex(0.0, 1.0, 0.0);
+//! mesh.add_triangle(a, b, c);
+//! mesh.vertices.shrink_to_fit();
+//!
+//! let mut model = Model::default();
+//! model.resources.add_object(Object {
+//!     id: ResourceId(1),
+//!     object_type: ObjectType::Model,
+//!     name: None,
+//!     part_number: None,
+//!     uuid: None,
+//!     pid: None,
+//!     pindex: None,
+//!     thumbnail: None,
+//!     geometry: Geometry::Mesh(mesh),
+//! })?;
+//!
+//! let usage = model.memory_usage();
+//! assert!(usage.meshes >= 3 * 12);
+//! assert_eq!(usage.total(), usage.categories().iter().map(|(_, b)| b).sum::<usize>());
+//! # Ok::<(), lib3mf_core::error::Lib3mfError>(())
+//! ```
+
+use crate::model::{Geometry, Mesh, Model};
+use serde::{Deserialize, Serialize};
+
+/// Estimated heap bytes of a [`Model`], by kind of resource. See the [module docs](self).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryUsage {
+    /// Mesh vertices and triangles.
+    pub meshes: usize,
+    /// Stored normals, triangle sets and non-standard triangle attributes of meshes.
+    pub mesh_annotations: usize,
+    /// Beams and beam sets of Beam Lattice meshes.
+    pub beam_lattices: usize,
+    /// Vertices, triangles and vectors of Displacement Extension meshes.
+    pub displacement_meshes: usize,
+    /// Components of assembly objects.
+    pub components: usize,
+    /// Base materials, colors, texture coordinates, composites and multi-properties.
+    pub materials: usize,
+    /// Slice Extension polygons.
+    pub slices: usize,
+    /// Raw package parts kept alongside the model, such as textures and thumbnails.
+    pub attachments: usize,
+}
+
+impl MemoryUsage {
+    /// Sum of all categories.
+    pub fn total(&self) -> usize {
+        self.categories().iter().map(|(_, bytes)| bytes).sum()
+    }
+
+    /// The categories as `(name, bytes)` pairs, in field order, for display.
+    pub fn categories(&self) -> [(&'static str, usize); 8] {
+        [
+            ("meshes", self.meshes),
+            ("mesh_annotations", self.mesh_annotations),
+            ("beam_lattices", self.beam_lattices),
+            ("displacement_meshes", self.displacement_meshes),
+            ("components", self.components),
+            ("materials", self.materials),
+            ("slices", self.slices),
+            ("attachments", self.attachments),
+        ]
+    }
+}
+
+impl std::ops::AddAssign for MemoryUsage {
+    /// Adds the usage of another model, e.g. one of a package's external model parts.
+    fn add_assign(&mut self, other: Self) {
+        self.meshes += other.meshes;
+        self.mesh_annotations += other.mesh_annotations;
+        self.beam_lattices += other.beam_lattices;
+        self.displacement_meshes += other.displacement_meshes;
+        self.components += other.components;
+        self.materials += other.materials;
+        self.slices += other.slices;
+        self.attachments += other.attachments;
+    }
+}
+
+impl Model {
+    /// Estimates the heap memory held by the model's resources and attachments.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let mut usage = MemoryUsage::default();
+        for object in self.resources.iter_objects() {
+            match &object.geometry {
+                Geometry::Mesh(mesh) => add_mesh(&mut usage, mesh),
+                Geometry::PreciseMesh(mesh) => {
+                    usage.meshes += vec_bytes(&mesh.vertices) + vec_bytes(&mesh.triangles);
+                    usage.mesh_annotations +=
+                        vec_bytes(&mesh.triangle_sets) + mesh.triangle_attributes.heap_bytes();
+                }
+                Geometry::Components(c) => {
+                    usage.components += vec_bytes(&c.components)
+                        + c.components
+                            .iter()
+                            .map(|c| c.path.as_ref().map_or(0, String::capacity))
+                            .sum::<usize>();
+                }
+                Geometry::DisplacementMesh(d) => {
+                    usage.displacement_meshes += vec_bytes(&d.vertices)
+                        + vec_bytes(&d.triangles)
+                        + vec_bytes(&d.normals)
+                        + d.gradients.as_ref().map_or(0, vec_bytes);
+                }
+                _ => {}
+            }
+        }
+
+        for group in self.resources.iter_base_materials() {
+            usage.materials += vec_bytes(&group.materials)
+                + group
+                    .materials
+                    .iter()
+                    .map(|m| m.name.capacity())
+                    .sum::<usize>();
+        }
+        for group in self.resources.iter_color_groups() {
+            usage.materials += vec_bytes(&group.colors);
+        }
+        for group in self.resources.iter_textures() {
+            usage.materials += vec_bytes(&group.coords);
+        }
+        for group in self.resources.iter_composite_materials() {
+            usage.materials += vec_bytes(&group.indices)
+                + vec_bytes(&group.composites)
+                + group
+                    .composites
+                    .iter()
+                    .map(|c| vec_bytes(&c.values))
+                    .sum::<usize>();
+        }
+        for group in self.resources.iter_multi_properties() {
+            usage.materials += vec_bytes(&group.pids)
+                + vec_bytes(&group.blend_methods)
+                + vec_bytes(&group.multis)
+                + group
+                    .multis
+                    .iter()
+                    .map(|m| vec_bytes(&m.pindices))
+                    .sum::<usize>();
+        }
+
+        for stack in self.resources.iter_slice_stacks() {
+            usage.slices += vec_bytes(&stack.slices) + vec_bytes(&stack.refs);
+            for slice in &stack.slices {
+                usage.slices += vec_bytes(&slice.vertices)
+                    + vec_bytes(&slice.polygons)
+                    + slice
+                        .polygons
+                        .iter()
+                        .map(|p| vec_bytes(&p.segments))
+                        .sum::<usize>();
+            }
+        }
+
+        usage.attachments = self.attachments.values().map(Vec::capacity).sum();
+        usage
+    }
+}
+
+fn add_mesh(usage: &mut MemoryUsage, mesh: &Mesh) {
+    usage.meshes += vec_bytes(&mesh.vertices) + vec_bytes(&mesh.triangles);
+    if let Some(normals) = &mesh.normals {
+        usage.mesh_annotations += vec_bytes(&normals.normals) + vec_bytes(&normals.indices);
+    }
+    for set in &mesh.triangle_sets {
+        usage.mesh_annotations +=
+            set.name.capacity() + set.identifier.capacity() + vec_bytes(&set.triangles);
+    }
+    usage.mesh_annotations +=
+        vec_bytes(&mesh.triangle_sets) + mesh.triangle_attributes.heap_bytes();
+    if let Some(lattice) = &mesh.beam_lattice {
+        usage.beam_lattices += vec_bytes(&lattice.beams)
+            + vec_bytes(&lattice.beam_sets)
+            + lattice
+                .beam_sets
+                .iter()
+                .map(|s| vec_bytes(&s.refs))
+                .sum::<usize>();
+    }
+}
+
+fn vec_bytes<T>(v: &Vec<T>) -> usize {
+    v.capacity() * size_of::<T>()
+}
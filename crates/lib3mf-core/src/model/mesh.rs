@@ -1,4 +1,4 @@
-use crate::model::ResourceId;
+use crate::model::{MeshNormals, ResourceId, SharedVec, TriangleAttributes};
 use glam::Vec3;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -54,6 +54,25 @@ impl std::fmt::Display for ObjectType {
     }
 }
 
+impl std::str::FromStr for ObjectType {
+    type Err = crate::error::Lib3mfError;
+
+    /// Parses the value of an object's `type` attribute.
+    fn from_str(s: &str) -> crate::error::Result<Self> {
+        match s {
+            "model" => Ok(ObjectType::Model),
+            "support" => Ok(ObjectType::Support),
+            "solidsupport" => Ok(ObjectType::SolidSupport),
+            "surface" => Ok(ObjectType::Surface),
+            "other" => Ok(ObjectType::Other),
+            unknown => Err(crate::error::Lib3mfError::Validation(format!(
+                "Invalid object type '{}'. Valid types are: model, support, solidsupport, surface, other",
+                unknown
+            ))),
+        }
+    }
+}
+
 impl ObjectType {
     /// Returns true if this type requires manifold mesh validation
     pub fn requires_manifold(&self) -> bool {
@@ -133,11 +152,20 @@ pub enum Geometry {
     BooleanShape(BooleanShape),
     /// A mesh with displacement mapping for fine surface detail (Displacement Extension).
     DisplacementMesh(DisplacementMesh),
+    /// A triangle mesh that has not been parsed yet (see [`LazyMesh`](crate::model::LazyMesh)).
+    /// Produced only by [`parse_model_lazy`](crate::parser::parse_model_lazy).
+    LazyMesh(crate::model::LazyMesh),
+    /// A shape defined by a function's zero level set (Volumetric Extension).
+    LevelSet(crate::model::LevelSet),
+    /// A triangle mesh with `f64` coordinates (see [`PreciseMesh`](crate::model::PreciseMesh)).
+    /// Produced only when parsing with
+    /// [`ParseOptions::precise_coordinates`](crate::parser::ParseOptions::precise_coordinates).
+    PreciseMesh(crate::model::PreciseMesh),
 }
 
 impl Geometry {
     /// Returns true if this geometry contains actual content (non-empty mesh,
-    /// components, boolean shapes, or displacement meshes).
+    /// components, boolean shapes, displacement meshes or level sets).
     ///
     /// A default-constructed `Geometry::Mesh(Mesh::default())` has no content
     /// (no vertices, no triangles). This is the default return from
@@ -149,6 +177,9 @@ impl Geometry {
             Geometry::Components(c) => !c.components.is_empty(),
             Geometry::BooleanShape(_) => true,
             Geometry::DisplacementMesh(_) => true,
+            Geometry::LevelSet(_) => true,
+            Geometry::LazyMesh(lazy) => lazy.vertex_count > 0 || lazy.triangle_count > 0,
+            Geometry::PreciseMesh(mesh) => !mesh.vertices.is_empty() || !mesh.triangles.is_empty(),
             // SliceStack and VolumetricStack are references, not inline content
             Geometry::SliceStack(_) | Geometry::VolumetricStack(_) => false,
         }
@@ -261,7 +292,8 @@ pub struct BooleanShape {
 ///
 /// A mesh is the fundamental geometry container in 3MF, consisting of vertices
 /// (3D points) and triangles that connect those vertices. Meshes can optionally
-/// include beam lattice structures for lightweight, high-strength geometry.
+/// include beam lattice structures for lightweight, high-strength geometry, and named
+/// [`TriangleSet`]s grouping triangles for per-region settings.
 ///
 /// # Examples
 ///
@@ -277,13 +309,56 @@ pub struct BooleanShape {
 /// ```
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Mesh {
-    /// List of vertices (points in 3D space).
-    pub vertices: Vec<Vertex>,
-    /// List of triangles connecting vertices by their indices.
-    pub triangles: Vec<Triangle>,
+    /// List of vertices (points in 3D space), shared with clones until modified.
+    pub vertices: SharedVec<Vertex>,
+    /// List of triangles connecting vertices by their indices, shared with clones until
+    /// modified.
+    pub triangles: SharedVec<Triangle>,
     /// Beam Lattice extension data for structural lattice geometry (optional).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub beam_lattice: Option<BeamLattice>,
+    /// Volumetric Extension [`VolumeData`](crate::model::VolumeData) applied inside the mesh.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume_id: Option<ResourceId>,
+    /// Triangle Sets extension groups, in document order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub triangle_sets: Vec<TriangleSet>,
+    /// Non-standard triangle attributes, such as slicer paint data.
+    #[serde(default, skip_serializing_if = "TriangleAttributes::is_empty")]
+    pub triangle_attributes: TriangleAttributes,
+    /// Shading normals from an import or [`Mesh::update_normals`]; not written to 3MF.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normals: Option<MeshNormals>,
+}
+
+/// A named group of triangles (Triangle Sets extension, `t:triangleset`).
+///
+/// Slicers use triangle sets to attach per-region settings, such as painted seams or
+/// support enforcers, to parts of a mesh. A triangle may belong to several sets.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TriangleSet {
+    /// Human-readable name.
+    pub name: String,
+    /// Machine-readable identifier, unique within the mesh.
+    pub identifier: String,
+    /// Indices of triangles in this set (references into the triangles array).
+    pub triangles: Vec<u32>,
+}
+
+impl TriangleSet {
+    /// Creates an empty triangle set.
+    pub fn new(name: impl Into<String>, identifier: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            identifier: identifier.into(),
+            triangles: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if the set contains the triangle at `index`.
+    pub fn contains(&self, index: u32) -> bool {
+        self.triangles.contains(&index)
+    }
 }
 
 /// Beam lattice structure for lightweight, high-strength geometry.
@@ -426,6 +501,35 @@ impl Mesh {
         });
     }
 
+    /// Returns the triangle set with the given identifier, if any.
+    pub fn triangle_set(&self, identifier: &str) -> Option<&TriangleSet> {
+        self.triangle_sets
+            .iter()
+            .find(|set| set.identifier == identifier)
+    }
+
+    /// Returns the triangle sets containing the triangle at `index`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib3mf_core::model::{Mesh, TriangleSet};
+    ///
+    /// let mut mesh = Mesh::new();
+    /// let mut seam = TriangleSet::new("Seam", "seam");
+    /// seam.triangles = vec![0, 4, 5];
+    /// mesh.triangle_sets.push(seam);
+    ///
+    /// let names: Vec<&str> = mesh.triangle_sets_of(4).map(|s| s.name.as_str()).collect();
+    /// assert_eq!(names, ["Seam"]);
+    /// assert_eq!(mesh.triangle_sets_of(1).count(), 0);
+    /// ```
+    pub fn triangle_sets_of(&self, index: u32) -> impl Iterator<Item = &TriangleSet> {
+        self.triangle_sets
+            .iter()
+            .filter(move |set| set.contains(index))
+    }
+
     /// Computes the axis-aligned bounding box (AABB) of the mesh.
     ///
     /// Returns `None` if the mesh has no vertices.
@@ -434,6 +538,11 @@ impl Mesh {
             return None;
         }
 
+        #[cfg(feature = "simd")]
+        if let Some((min, max)) = crate::utils::simd::bounds(&self.vertices) {
+            return Some(crate::model::stats::BoundingBox { min, max });
+        }
+
         let initial = (
             f32::INFINITY,
             f32::INFINITY,
@@ -508,6 +617,11 @@ impl Mesh {
             return (0.0, 0.0);
         }
 
+        #[cfg(feature = "simd")]
+        if let Some(result) = crate::utils::simd::area_and_volume(self) {
+            return result;
+        }
+
         #[cfg(feature = "parallel")]
         let (area, volume) = {
             use rayon::prelude::*;
@@ -598,7 +712,11 @@ impl Mesh {
 ///
 /// Represents a vertex position in the mesh coordinate system.
 /// Coordinates are in model units (see [`Unit`](crate::model::Unit)).
+///
+/// The layout is `#[repr(C)]` so a `&[Vertex]` can be read as a flat `x, y, z` stream
+/// by the SIMD kernels.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[repr(C)]
 pub struct Vertex {
     /// X coordinate in model units
     pub x: f32,
@@ -618,6 +736,12 @@ impl From<Vec3> for Vertex {
     }
 }
 
+impl From<Vertex> for Vec3 {
+    fn from(v: Vertex) -> Self {
+        Vec3::new(v.x, v.y, v.z)
+    }
+}
+
 /// A triangle face defined by three vertex indices.
 ///
 /// Triangles are the fundamental building blocks of 3MF meshes. They reference
@@ -0,0 +1,489 @@
+//! Storage abstraction for triangle meshes.
+//!
+//! [`Mesh`] stores vertices and triangles as arrays of structs, with four `Option<u32>`
+//! property fields on every triangle (44 bytes each). That is convenient for editing but
+//! wasteful for very large, mostly property-free meshes. [`CompactMesh`] stores the same
+//! data as structure-of-arrays: coordinates in three `f32` columns, indices as
+//! `[u32; 3]`, and property columns that are only allocated once a triangle actually
+//! carries properties. For a typical mesh with about half as many vertices as triangles
+//! this cuts memory from roughly 50 to 18 bytes per triangle.
+//!
+//! The parser ([`parse_mesh_into`]), writer ([`write_mesh`]), and geometry validation
+//! ([`validate_mesh`]) are generic over [`MeshStorage`], so either representation can be
+//! used end to end. So can [`PreciseMesh`](crate::model::PreciseMesh), which keeps
+//! coordinates as `f64`.
+//!
+//! [`parse_mesh_into`]: crate::parser::mesh_parser::parse_mesh_into
+//! [`write_mesh`]: crate::writer::mesh_writer::write_mesh
+//! [`validate_mesh`]: crate::validation::geometry::validate_mesh
+//!
+//! # Examples
+//!
+//! ```
+//! use lib3mf_core::model::{CompactMesh, Mesh, MeshStorage};
+//!
+//! let mut mesh = Mesh::new();
+//! let a = mesh.add_vertex(0.0, 0.0, 0.0);
+//! let b = mesh.add_vertex(1.0, 0.0, 0.0);
+//! let c = mesh.add_vertex(0.0, 1.0, 0.0);
+//! mesh.add_triangle(a, b, c);
+//!
+//! let compact = CompactMesh::from(&mesh);
+//! assert_eq!(compact.triangle_count(), 1);
+//! assert!(compact.heap_bytes() < mesh.heap_bytes());
+//! assert_eq!(Mesh::from(&compact), mesh);
+//! ```
+
+use crate::model::{
+    BeamLattice, Mesh, ResourceId, Triangle, TriangleAttributes, TriangleSet, Vertex,
+};
+use glam::{DVec3, Vec3};
+
+/// Read/append access to mesh vertices and triangles, independent of memory layout.
+///
+/// Indices passed to [`vertex`](Self::vertex) and [`triangle`](Self::triangle) must be
+/// in bounds; both panic otherwise, like slice indexing.
+pub trait MeshStorage {
+    /// Number of vertices.
+    fn vertex_count(&self) -> usize;
+
+    /// Number of triangles.
+    fn triangle_count(&self) -> usize;
+
+    /// Vertex at `index`.
+    fn vertex(&self, index: usize) -> Vertex;
+
+    /// Triangle at `index`, including its property indices.
+    fn triangle(&self, index: usize) -> Triangle;
+
+    /// Appends a vertex.
+    fn push_vertex(&mut self, vertex: Vertex);
+
+    /// Appends a triangle.
+    fn push_triangle(&mut self, triangle: Triangle);
+
+    /// Beam lattice attached to this mesh, if any.
+    fn beam_lattice(&self) -> Option<&BeamLattice>;
+
+    /// Attaches a beam lattice, replacing any existing one.
+    fn set_beam_lattice(&mut self, lattice: BeamLattice);
+
+    /// Triangle Sets extension groups of this mesh.
+    fn triangle_sets(&self) -> &[TriangleSet];
+
+    /// Replaces the triangle sets of this mesh.
+    fn set_triangle_sets(&mut self, sets: Vec<TriangleSet>);
+
+    /// Non-standard triangle attributes, such as slicer paint data.
+    fn triangle_attributes(&self) -> &TriangleAttributes;
+
+    /// Mutable access to the non-standard triangle attributes.
+    fn triangle_attributes_mut(&mut self) -> &mut TriangleAttributes;
+
+    /// Bytes allocated on the heap for vertex and triangle data (by capacity).
+    fn heap_bytes(&self) -> usize;
+
+    /// Volumetric Extension volume data applied inside the mesh, if any.
+    fn volume_id(&self) -> Option<ResourceId> {
+        None
+    }
+
+    /// Returns true if the storage keeps coordinates as `f64`, so the parser and writer
+    /// should go through [`push_vertex_f64`](Self::push_vertex_f64) and
+    /// [`vertex_f64`](Self::vertex_f64) instead of rounding to `f32`.
+    fn has_f64_coordinates(&self) -> bool {
+        false
+    }
+
+    /// Vertex at `index` in `f64`. Storages with `f32` coordinates widen them exactly.
+    fn vertex_f64(&self, index: usize) -> DVec3 {
+        Vec3::from(self.vertex(index)).as_dvec3()
+    }
+
+    /// Appends a vertex given in `f64`. Storages with `f32` coordinates round it.
+    fn push_vertex_f64(&mut self, vertex: DVec3) {
+        self.push_vertex(Vertex::from(vertex.as_vec3()));
+    }
+
+    /// Iterates over all vertices in order.
+    fn iter_vertices(&self) -> impl Iterator<Item = Vertex> + '_ {
+        (0..self.vertex_count()).map(|i| self.vertex(i))
+    }
+
+    /// Iterates over all triangles in order.
+    fn iter_triangles(&self) -> impl Iterator<Item = Triangle> + '_ {
+        (0..self.triangle_count()).map(|i| self.triangle(i))
+    }
+}
+
+impl MeshStorage for Mesh {
+    fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn triangle_count(&self) -> usize {
+        self.triangles.len()
+    }
+
+    fn vertex(&self, index: usize) -> Vertex {
+        self.vertices[index]
+    }
+
+    fn triangle(&self, index: usize) -> Triangle {
+        self.triangles[index]
+    }
+
+    fn push_vertex(&mut self, vertex: Vertex) {
+        self.vertices.push(vertex);
+    }
+
+    fn push_triangle(&mut self, triangle: Triangle) {
+        self.triangles.push(triangle);
+    }
+
+    fn beam_lattice(&self) -> Option<&BeamLattice> {
+        self.beam_lattice.as_ref()
+    }
+
+    fn set_beam_lattice(&mut self, lattice: BeamLattice) {
+        self.beam_lattice = Some(lattice);
+    }
+
+    fn triangle_sets(&self) -> &[TriangleSet] {
+        &self.triangle_sets
+    }
+
+    fn set_triangle_sets(&mut self, sets: Vec<TriangleSet>) {
+        self.triangle_sets = sets;
+    }
+
+    fn triangle_attributes(&self) -> &TriangleAttributes {
+        &self.triangle_attributes
+    }
+
+    fn triangle_attributes_mut(&mut self) -> &mut TriangleAttributes {
+        &mut self.triangle_attributes
+    }
+
+    fn heap_bytes(&self) -> usize {
+        self.vertices.capacity() * size_of::<Vertex>()
+            + self.triangles.capacity() * size_of::<Triangle>()
+    }
+
+    fn volume_id(&self) -> Option<ResourceId> {
+        self.volume_id
+    }
+
+    fn iter_vertices(&self) -> impl Iterator<Item = Vertex> + '_ {
+        self.vertices.iter().copied()
+    }
+
+    fn iter_triangles(&self) -> impl Iterator<Item = Triangle> + '_ {
+        self.triangles.iter().copied()
+    }
+}
+
+/// Per-triangle property columns, allocated on first use.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct PropertyColumns {
+    /// Bit 0: `pid`, bits 1-3: `p1`..`p3` present.
+    present: Vec<u8>,
+    pid: Vec<u32>,
+    p: Vec<[u32; 3]>,
+}
+
+impl PropertyColumns {
+    fn with_absent(len: usize) -> Self {
+        Self {
+            present: vec![0; len],
+            pid: vec![0; len],
+            p: vec![[0; 3]; len],
+        }
+    }
+
+    fn push(&mut self, t: &Triangle) {
+        let bit = |o: Option<u32>, shift: u8| if o.is_some() { 1u8 << shift } else { 0 };
+        self.present
+            .push(bit(t.pid, 0) | bit(t.p1, 1) | bit(t.p2, 2) | bit(t.p3, 3));
+        self.pid.push(t.pid.unwrap_or(0));
+        self.p
+            .push([t.p1.unwrap_or(0), t.p2.unwrap_or(0), t.p3.unwrap_or(0)]);
+    }
+
+    fn apply(&self, index: usize, t: &mut Triangle) {
+        let flags = self.present[index];
+        let get = |shift: u8, value: u32| (flags & (1 << shift) != 0).then_some(value);
+        let [p1, p2, p3] = self.p[index];
+        t.pid = get(0, self.pid[index]);
+        t.p1 = get(1, p1);
+        t.p2 = get(2, p2);
+        t.p3 = get(3, p3);
+    }
+
+    fn heap_bytes(&self) -> usize {
+        self.present.capacity()
+            + self.pid.capacity() * size_of::<u32>()
+            + self.p.capacity() * size_of::<[u32; 3]>()
+    }
+}
+
+/// Structure-of-arrays mesh for large models. See the [module docs](self).
+///
+/// Converts losslessly to and from [`Mesh`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompactMesh {
+    x: Vec<f32>,
+    y: Vec<f32>,
+    z: Vec<f32>,
+    indices: Vec<[u32; 3]>,
+    properties: Option<PropertyColumns>,
+    beam_lattice: Option<BeamLattice>,
+    triangle_sets: Vec<TriangleSet>,
+    triangle_attributes: TriangleAttributes,
+}
+
+impl CompactMesh {
+    /// Creates an empty compact mesh.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty compact mesh with room for the given counts.
+    pub fn with_capacity(vertices: usize, triangles: usize) -> Self {
+        Self {
+            x: Vec::with_capacity(vertices),
+            y: Vec::with_capacity(vertices),
+            z: Vec::with_capacity(vertices),
+            indices: Vec::with_capacity(triangles),
+            ..Self::default()
+        }
+    }
+
+    /// X coordinates of all vertices.
+    pub fn xs(&self) -> &[f32] {
+        &self.x
+    }
+
+    /// Y coordinates of all vertices.
+    pub fn ys(&self) -> &[f32] {
+        &self.y
+    }
+
+    /// Z coordinates of all vertices.
+    pub fn zs(&self) -> &[f32] {
+        &self.z
+    }
+
+    /// Vertex indices of all triangles.
+    pub fn indices(&self) -> &[[u32; 3]] {
+        &self.indices
+    }
+
+    /// Returns `true` if any triangle carries property indices.
+    pub fn has_properties(&self) -> bool {
+        self.properties.is_some()
+    }
+
+    /// Releases excess capacity, e.g. after parsing.
+    pub fn shrink_to_fit(&mut self) {
+        self.x.shrink_to_fit();
+        self.y.shrink_to_fit();
+        self.z.shrink_to_fit();
+        self.indices.shrink_to_fit();
+        if let Some(props) = &mut self.properties {
+            props.present.shrink_to_fit();
+            props.pid.shrink_to_fit();
+            props.p.shrink_to_fit();
+        }
+    }
+}
+
+impl MeshStorage for CompactMesh {
+    fn vertex_count(&self) -> usize {
+        self.x.len()
+    }
+
+    fn triangle_count(&self) -> usize {
+        self.indices.len()
+    }
+
+    fn vertex(&self, index: usize) -> Vertex {
+        Vertex {
+            x: self.x[index],
+            y: self.y[index],
+            z: self.z[index],
+        }
+    }
+
+    fn triangle(&self, index: usize) -> Triangle {
+        let [v1, v2, v3] = self.indices[index];
+        let mut t = Triangle {
+            v1,
+            v2,
+            v3,
+            ..Default::default()
+        };
+        if let Some(props) = &self.properties {
+            props.apply(index, &mut t);
+        }
+        t
+    }
+
+    fn push_vertex(&mut self, vertex: Vertex) {
+        self.x.push(vertex.x);
+        self.y.push(vertex.y);
+        self.z.push(vertex.z);
+    }
+
+    fn push_triangle(&mut self, triangle: Triangle) {
+        let has_props = triangle.pid.is_some()
+            || triangle.p1.is_some()
+            || triangle.p2.is_some()
+            || triangle.p3.is_some();
+        if has_props && self.properties.is_none() {
+            self.properties = Some(PropertyColumns::with_absent(self.indices.len()));
+        }
+        if let Some(props) = &mut self.properties {
+            props.push(&triangle);
+        }
+        self.indices.push([triangle.v1, triangle.v2, triangle.v3]);
+    }
+
+    fn beam_lattice(&self) -> Option<&BeamLattice> {
+        self.beam_lattice.as_ref()
+    }
+
+    fn set_beam_lattice(&mut self, lattice: BeamLattice) {
+        self.beam_lattice = Some(lattice);
+    }
+
+    fn triangle_sets(&self) -> &[TriangleSet] {
+        &self.triangle_sets
+    }
+
+    fn set_triangle_sets(&mut self, sets: Vec<TriangleSet>) {
+        self.triangle_sets = sets;
+    }
+
+    fn triangle_attributes(&self) -> &TriangleAttributes {
+        &self.triangle_attributes
+    }
+
+    fn triangle_attributes_mut(&mut self) -> &mut TriangleAttributes {
+        &mut self.triangle_attributes
+    }
+
+    fn heap_bytes(&self) -> usize {
+        (self.x.capacity() + self.y.capacity() + self.z.capacity()) * size_of::<f32>()
+            + self.indices.capacity() * size_of::<[u32; 3]>()
+            + self
+                .properties
+                .as_ref()
+                .map_or(0, PropertyColumns::heap_bytes)
+    }
+}
+
+impl From<&Mesh> for CompactMesh {
+    fn from(mesh: &Mesh) -> Self {
+        let mut out = CompactMesh::with_capacity(mesh.vertices.len(), mesh.triangles.len());
+        for v in &mesh.vertices {
+            out.push_vertex(*v);
+        }
+        for t in &mesh.triangles {
+            out.push_triangle(*t);
+        }
+        out.beam_lattice = mesh.beam_lattice.clone();
+        out.triangle_sets = mesh.triangle_sets.clone();
+        out.triangle_attributes = mesh.triangle_attributes.clone();
+        out
+    }
+}
+
+impl From<&CompactMesh> for Mesh {
+    fn from(compact: &CompactMesh) -> Self {
+        Mesh {
+            vertices: compact.iter_vertices().collect(),
+            triangles: compact.iter_triangles().collect(),
+            beam_lattice: compact.beam_lattice.clone(),
+            volume_id: None,
+            triangle_sets: compact.triangle_sets.clone(),
+            triangle_attributes: compact.triangle_attributes.clone(),
+            normals: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(n: u32, with_props: bool) -> Mesh {
+        let mut mesh = Mesh::new();
+        for i in 0..=n {
+            for j in 0..=n {
+                mesh.add_vertex(i as f32, j as f32, 0.0);
+            }
+        }
+        let at = |i: u32, j: u32| i * (n + 1) + j;
+        for i in 0..n {
+            for j in 0..n {
+                mesh.add_triangle(at(i, j), at(i + 1, j), at(i + 1, j + 1));
+                mesh.add_triangle(at(i, j), at(i + 1, j + 1), at(i, j + 1));
+            }
+        }
+        if with_props {
+            for (k, t) in mesh.triangles.iter_mut().enumerate() {
+                if k % 3 == 0 {
+                    t.pid = Some(7);
+                    t.p1 = Some(k as u32);
+                }
+                if k % 5 == 0 {
+                    t.p3 = Some(u32::MAX);
+                }
+            }
+        }
+        mesh
+    }
+
+    #[test]
+    fn test_round_trip_without_properties() {
+        let mesh = grid(20, false);
+        let compact = CompactMesh::from(&mesh);
+        assert!(!compact.has_properties());
+        assert_eq!(compact.vertex_count(), mesh.vertices.len());
+        assert_eq!(compact.triangle_count(), mesh.triangles.len());
+        assert_eq!(Mesh::from(&compact), mesh);
+    }
+
+    #[test]
+    fn test_round_trip_with_sparse_properties() {
+        let mut mesh = grid(10, false);
+        // First triangle with properties appears late: earlier rows must read back as None.
+        mesh.triangles[150].pid = Some(2);
+        mesh.triangles[150].p2 = Some(0);
+        let compact = CompactMesh::from(&mesh);
+        assert!(compact.has_properties());
+        assert_eq!(compact.triangle(0), mesh.triangles[0]);
+        assert_eq!(compact.triangle(150), mesh.triangles[150]);
+        assert_eq!(Mesh::from(&compact), mesh);
+
+        let mesh = grid(10, true);
+        assert_eq!(Mesh::from(&CompactMesh::from(&mesh)), mesh);
+    }
+
+    #[test]
+    fn test_compact_memory_savings() {
+        let mesh = grid(100, false);
+        let mut compact = CompactMesh::from(&mesh);
+        compact.shrink_to_fit();
+        let (aos, soa) = (mesh.heap_bytes(), compact.heap_bytes());
+        assert!(
+            soa * 10 <= aos * 6,
+            "expected >=40% savings: {aos} -> {soa}"
+        );
+
+        let mesh = grid(100, true);
+        let compact = CompactMesh::from(&mesh);
+        assert!(compact.heap_bytes() < mesh.heap_bytes());
+    }
+}
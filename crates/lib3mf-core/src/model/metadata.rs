@@ -0,0 +1,243 @@
+//! Model-level `<metadata>` entries.
+//!
+//! [`Metadata`] keeps entries in document order together with their `type`, `preserve`
+//! and `xml:lang` attributes, so a model round-trips without losing them. A name may
+//! appear more than once when the entries differ in language:
+//!
+//! ```
+//! use lib3mf_core::model::{Metadata, MetadataEntry};
+//!
+//! let mut metadata = Metadata::new();
+//! metadata.insert("Title", "Bracket");
+//! metadata.push(MetadataEntry::new("Title", "Halterung").with_lang("de"));
+//!
+//! assert_eq!(metadata.get("Title"), Some("Bracket"));
+//! assert_eq!(metadata.get_lang("Title", "de"), Some("Halterung"));
+//! assert_eq!(metadata.get_all("Title").count(), 2);
+//!
+//! // `insert` replaces every entry of that name
+//! metadata.insert("Title", "Clip");
+//! assert_eq!(metadata.len(), 1);
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::ops::Index;
+
+/// A single `<metadata>` element.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetadataEntry {
+    /// Metadata name, optionally namespace-qualified (`Vendor:Key`).
+    pub name: String,
+    /// Text content.
+    pub value: String,
+    /// The `type` attribute (e.g. `xs:string`), if given.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub value_type: Option<String>,
+    /// Whether consumers should keep this entry when modifying the file (`preserve="1"`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub preserve: bool,
+    /// The `xml:lang` language tag, if given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+}
+
+impl MetadataEntry {
+    /// Creates an entry with no type, language or preserve flag.
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+            value_type: None,
+            preserve: false,
+            lang: None,
+        }
+    }
+
+    /// Sets the `xml:lang` language tag.
+    pub fn with_lang(mut self, lang: impl Into<String>) -> Self {
+        self.lang = Some(lang.into());
+        self
+    }
+
+    /// Sets the `type` attribute.
+    pub fn with_type(mut self, value_type: impl Into<String>) -> Self {
+        self.value_type = Some(value_type.into());
+        self
+    }
+
+    /// Sets the `preserve` flag.
+    pub fn with_preserve(mut self, preserve: bool) -> Self {
+        self.preserve = preserve;
+        self
+    }
+}
+
+/// Ordered collection of metadata entries. See the [module docs](self).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Metadata {
+    entries: Vec<MetadataEntry>,
+}
+
+impl Metadata {
+    /// Creates an empty collection.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over the entries in document order.
+    pub fn iter(&self) -> std::slice::Iter<'_, MetadataEntry> {
+        self.entries.iter()
+    }
+
+    /// Returns a mutable iterator over the entries in document order.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, MetadataEntry> {
+        self.entries.iter_mut()
+    }
+
+    /// Returns each distinct name once, in order of first appearance.
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        let mut seen = HashSet::new();
+        self.entries
+            .iter()
+            .map(|e| e.name.as_str())
+            .filter(move |name| seen.insert(*name))
+    }
+
+    /// Returns true if an entry named `name` exists.
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.entries.iter().any(|e| e.name == name)
+    }
+
+    /// Returns the value of `name`, preferring an entry without a language tag.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.get_entry(name).map(|e| e.value.as_str())
+    }
+
+    /// Returns the entry for `name`, preferring one without a language tag.
+    pub fn get_entry(&self, name: &str) -> Option<&MetadataEntry> {
+        let mut all = self.entries.iter().filter(|e| e.name == name);
+        let first = all.next()?;
+        if first.lang.is_none() {
+            return Some(first);
+        }
+        Some(all.find(|e| e.lang.is_none()).unwrap_or(first))
+    }
+
+    /// Returns the value of `name` in language `lang`.
+    pub fn get_lang(&self, name: &str, lang: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.name == name && e.lang.as_deref() == Some(lang))
+            .map(|e| e.value.as_str())
+    }
+
+    /// Returns all entries named `name`, in document order.
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a MetadataEntry> + 'a {
+        self.entries.iter().filter(move |e| e.name == name)
+    }
+
+    /// Sets `name` to a single plain entry, replacing all existing entries of that name in
+    /// place. Returns the previous value (see [`get`](Self::get)).
+    pub fn insert(&mut self, name: impl Into<String>, value: impl Into<String>) -> Option<String> {
+        let name = name.into();
+        let old = self.replace(&name, vec![MetadataEntry::new(name.clone(), value)]);
+        first_value(old)
+    }
+
+    /// Appends an entry, keeping any existing entries of the same name.
+    pub fn push(&mut self, entry: MetadataEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Removes all entries named `name`. Returns the previous value (see [`get`](Self::get)).
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        first_value(self.replace(name, Vec::new()))
+    }
+
+    /// Replaces all entries named `name` with `entries`, placed where the first of them
+    /// was (or at the end), and returns the removed entries.
+    pub fn replace(&mut self, name: &str, entries: Vec<MetadataEntry>) -> Vec<MetadataEntry> {
+        let position = self
+            .entries
+            .iter()
+            .position(|e| e.name == name)
+            .unwrap_or(self.entries.len());
+        let (removed, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.entries)
+            .into_iter()
+            .partition(|e| e.name == name);
+        self.entries = kept;
+        self.entries.splice(position..position, entries);
+        removed
+    }
+
+    /// Keeps only the entries for which `keep` returns true.
+    pub fn retain(&mut self, keep: impl FnMut(&MetadataEntry) -> bool) {
+        self.entries.retain(keep);
+    }
+}
+
+/// The value [`Metadata::get`] would have returned for `entries`.
+fn first_value(entries: Vec<MetadataEntry>) -> Option<String> {
+    let index = entries.iter().position(|e| e.lang.is_none()).unwrap_or(0);
+    entries.into_iter().nth(index).map(|e| e.value)
+}
+
+impl Index<&str> for Metadata {
+    type Output = String;
+
+    /// Returns the value of `name` (see [`get`](Metadata::get)).
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no entry named `name`.
+    fn index(&self, name: &str) -> &String {
+        match self.get_entry(name) {
+            Some(entry) => &entry.value,
+            None => panic!("no metadata entry named '{}'", name),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Metadata {
+    type Item = &'a MetadataEntry;
+    type IntoIter = std::slice::Iter<'a, MetadataEntry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
+
+impl IntoIterator for Metadata {
+    type Item = MetadataEntry;
+    type IntoIter = std::vec::IntoIter<MetadataEntry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl FromIterator<MetadataEntry> for Metadata {
+    fn from_iter<I: IntoIterator<Item = MetadataEntry>>(iter: I) -> Self {
+        Self {
+            entries: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<MetadataEntry> for Metadata {
+    fn extend<I: IntoIterator<Item = MetadataEntry>>(&mut self, iter: I) {
+        self.entries.extend(iter);
+    }
+}
@@ -34,12 +34,19 @@
 //! The model includes first-class support for 3MF extensions:
 //!
 //! - **Beam Lattice**: [`BeamLattice`] and [`BeamSet`] for structural lattices
+//! - **Triangle Sets**: [`TriangleSet`] for named triangle groups within a [`Mesh`]
+//! - **Slicer paint data**: [`TriangleAttributes`] for vendor per-triangle attributes
 //! - **Slice**: [`SliceStack`] for layer-based geometry
-//! - **Volumetric**: [`VolumetricStack`] for voxel data
+//! - **Volumetric**: [`VolumetricStack`] for voxel data; [`LevelSet`], [`VolumeData`] and
+//!   [`Image3D`] for function-based volumes
+//! - **Implicit**: [`ImplicitFunction`] node graphs evaluated by level sets and volume data
 //! - **Boolean Operations**: [`BooleanShape`] for CSG operations
 //! - **Displacement**: [`DisplacementMesh`] for texture-driven surface modification
 //! - **Secure Content**: Cryptographic features (see [`secure_content`] module)
 //!
+//! [`Extension`] identifies each extension by namespace, and [`Model::used_extensions`]
+//! reports which ones a model's content uses.
+//!
 //! ## Design Philosophy
 //!
 //! The model follows an **immutable-by-default** design:
@@ -54,50 +61,140 @@
 //! For convenience, all public types are re-exported at the crate root via `pub use model::*`.
 //! You can use `lib3mf_core::Model` instead of `lib3mf_core::model::Model`.
 
+/// Package parts bound by relationships, such as print tickets (`AttachmentRegistry`).
+pub mod attachments;
 /// Build instructions — `Build` and `BuildItem` types.
 pub mod build;
+/// Canonical geometry and material digests (`Model::content_hash`, `ContentHash`).
+pub mod content_hash;
 /// Root `Model` struct and its `validate` / `compute_stats` methods.
 pub mod core;
+/// OPC core properties of the package (`CoreProperties`, `read_core_properties`).
+pub mod core_properties;
 /// XML-DSIG crypto data structures used by the Secure Content Extension.
 pub mod crypto;
+/// Plane cuts with capped faces and alignment pins (`Model::cut`, `Mesh::cut`).
+pub mod cut;
+/// Duplicate resource detection and merging (`Model::deduplicate_resources`).
+pub mod dedupe;
+/// Transactional editing with undo/redo (`ModelEditor`, `Edit`).
+pub mod editor;
+/// Extension identification (`Extension`) and detection of the extensions a model uses.
+pub mod extensions;
+/// Removal of thumbnails, textures, vendor data and unused resources (`PackageFilter`).
+pub mod filter;
+/// Implicit Extension function graphs (`ImplicitFunction`, `ImplicitNode`).
+pub mod implicit;
+/// Per-object geometry checksums stored as metadata (`Model::embed_geometry_checksums`).
+pub mod integrity;
+/// Content-hash interning of identical mesh arrays at parse time (`MeshInterner`).
+pub mod interning;
+/// Versioned JSON import/export (`Model::to_json` / `Model::from_json`).
+pub mod json;
+/// Lattice infill emitted as beam lattices (`Mesh::lattice_fill`, `LatticeOptions`).
+pub mod lattice;
+/// Deferred `<mesh>` geometry (`LazyMesh`) loaded on demand from the archive.
+pub mod lazy_mesh;
 /// Material and texture types (colors, base materials, composites, etc.).
 pub mod materials;
+/// Estimated heap memory of a loaded model by resource kind (`Model::memory_usage`).
+pub mod memory;
 /// Mesh geometry types (`Mesh`, `Triangle`, `Vertex`, `BeamLattice`, etc.).
 pub mod mesh;
+/// `MeshStorage` trait and the structure-of-arrays `CompactMesh` for large models.
+pub mod mesh_storage;
+/// Ordered model metadata with `type`, `preserve` and `xml:lang` (`Metadata`, `MetadataEntry`).
+pub mod metadata;
+/// Face, vertex and crease-aware corner normals (`Mesh::compute_normals`, `MeshNormals`).
+pub mod normals;
+/// Mesh offsetting and hollowing with drain holes (`Mesh::offset`, `Mesh::shell`).
+pub mod offset;
 /// Multi-part `Package` type for Production Extension multi-model files.
 pub mod package;
-/// Mesh repair operations (`MeshRepair` trait and `RepairStats`).
+/// World-space build bounds and recentering of far-out builds (`Model::recenter`).
+pub mod placement;
+/// Double-precision mesh storage (`PreciseMesh`) for metrology-grade models.
+pub mod precise_mesh;
+/// Production Extension UUID helpers (`Model::assign_missing_uuids`).
+pub mod production;
+/// Fluent object selection (`Model::query`) and textual filters (`ObjectFilter`).
+pub mod query;
+/// Mesh repair operations (`MeshRepair` trait, `RepairStats` and ordered `RepairPipeline`s).
 pub mod repair;
 /// Cross-file component resolver (`PartResolver`, `ResolvedMesh`, `ResolveOptions`).
 pub mod resolver;
 /// `ResourceCollection` — central registry for all model resources.
 pub mod resources;
+/// Signed distance fields sampled from meshes (`SdfGrid`, `SdfOptions`).
+pub mod sdf;
 /// Secure Content Extension key store types (`KeyStore`, `Consumer`, etc.).
 pub mod secure_content;
+/// Copy-on-write vectors shared between model clones (`SharedVec`).
+pub mod shared;
+/// Connected-shell splitting (`Mesh::split_components`, `Model::split_object_components`).
+pub mod shells;
 /// Slice Extension types (`SliceStack`, `Slice`, `Polygon`, etc.).
 pub mod slice;
+/// Ray casting, closest-point, containment and box queries on meshes (`SpatialIndex`).
+pub mod spatial;
 /// Model statistics types returned by `Model::compute_stats()`.
 pub mod stats;
+/// Model statistics stored in the package and reused while it is unchanged.
+pub mod stats_cache;
 /// Internal implementation of `compute_stats()` — not part of the public API surface.
 pub mod stats_impl;
 
+/// Per-triangle attribute channels (`TriangleAttributes`) and slicer paint adapters.
+pub mod triangle_attributes;
 /// Unit of measurement enum and conversion utilities.
 pub mod units;
-/// Volumetric Extension types (`VolumetricStack`, `VolumetricLayer`, etc.).
+/// Volumetric Extension types (`VolumetricStack`, `Image3D`, `VolumeData`, `LevelSet`, etc.).
 pub mod volumetric;
 
+pub use attachments::{
+    Attachment, AttachmentRegistry, PRINT_TICKET_CONTENT_TYPE, PRINT_TICKET_REL_TYPE,
+};
 pub use build::*;
+pub use content_hash::ContentHash;
 pub use core::*;
+pub use core_properties::{
+    CORE_PROPERTIES_CONTENT_TYPE, CORE_PROPERTIES_PATH, CORE_PROPERTIES_REL_TYPE, CoreProperties,
+    LEGACY_CORE_PROPERTIES_REL_TYPE, find_core_properties_path, read_core_properties,
+};
 pub use crypto::*;
+pub use cut::{CutOptions, CutReport, MeshCut, PinOptions, Plane, Side};
+pub use dedupe::{DuplicateGroup, DuplicateImage, DuplicateKind, DuplicateReport};
+pub use editor::{Edit, ModelEditor};
+pub use extensions::{CORE_NAMESPACE, Extension};
+pub use filter::{FilterReport, PackageFilter};
+pub use implicit::*;
+pub use integrity::{ChecksumError, ChecksumFailure};
+pub use interning::{InternStats, MeshInterner};
+pub use json::{JSON_SCHEMA, JSON_SCHEMA_VERSION};
+pub use lattice::{LatticeOptions, LatticePattern};
+pub use lazy_mesh::*;
 pub use materials::*;
+pub use memory::MemoryUsage;
 pub use mesh::*;
+pub use mesh_storage::*;
+pub use metadata::{Metadata, MetadataEntry};
+pub use normals::{DEFAULT_CREASE_ANGLE, MeshNormals};
+pub use offset::{DrainHole, OffsetOptions, ShellOptions};
 pub use package::*;
+pub use placement::{RECENTER_NS, RECENTER_OFFSET_NAME, RECENTER_PREFIX, SAFE_COORDINATE_LIMIT_MM};
+pub use precise_mesh::PreciseMesh;
+pub use query::{ObjectFilter, ObjectMetrics};
 pub use repair::*;
 pub use resolver::{ResolveOptions, ResolvedMesh};
 pub use resources::*;
+pub use sdf::{SdfGrid, SdfOptions};
 pub use secure_content::*;
+pub use shared::SharedVec;
 pub use slice::*;
+pub use spatial::{ClosestPoint, RayHit, SpatialIndex};
 pub use stats::*;
+pub use stats_cache::{STATS_CACHE_PATH, package_fingerprint, read_stats_cache};
+pub use triangle_attributes::*;
 
 pub use units::*;
 pub use volumetric::*;
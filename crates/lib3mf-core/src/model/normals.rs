@@ -0,0 +1,220 @@
+//! Face and vertex normals for shading.
+//!
+//! 3MF stores no normals: faces point outward by their winding and viewers shade them
+//! flat. Formats such as OBJ carry per-corner normals so that curved surfaces look smooth.
+//! [`Mesh::compute_normals`] derives them, averaging the normals of the faces around each
+//! vertex but keeping edges sharper than a crease angle hard. The result can be stored in
+//! [`Mesh::normals`], which exporters write and importers fill, and which the 3MF writer
+//! ignores.
+//!
+//! ```
+//! use lib3mf_core::model::Mesh;
+//!
+//! let mut mesh = Mesh::new();
+//! let a = mesh.add_vertex(0.0, 0.0, 0.0);
+//! let b = mesh.add_vertex(1.0, 0.0, 0.0);
+//! let c = mesh.add_vertex(0.0, 1.0, 0.0);
+//! let d = mesh.add_vertex(0.0, 0.0, 1.0);
+//! for [x, y, z] in [[a, c, b], [a, b, d], [a, d, c], [b, c, d]] {
+//!     mesh.add_triangle(x, y, z);
+//! }
+//!
+//! // Every edge of a tetrahedron is sharper than 30°: one normal per face
+//! let normals = mesh.compute_normals(30.0);
+//! assert_eq!(normals.normals.len(), 4);
+//! assert_eq!(normals.normals[normals.indices[0][0] as usize], glam::Vec3::NEG_Z);
+//!
+//! mesh.update_normals(180.0);
+//! assert_eq!(mesh.stored_normals().unwrap().normals.len(), 4);
+//! ```
+
+use crate::model::Mesh;
+use glam::{Mat3, Mat4, Vec3};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Crease angle, in degrees, used when a caller asks for normals without choosing one.
+pub const DEFAULT_CREASE_ANGLE: f32 = 30.0;
+
+/// Per-corner shading normals of a mesh.
+///
+/// Each triangle corner refers to one of a shared list of unit normals, as OBJ `vn`
+/// references do. Corners meeting at a smooth vertex share a normal; corners on either
+/// side of a crease do not.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MeshNormals {
+    /// Unit-length normals.
+    pub normals: Vec<Vec3>,
+    /// Indices into `normals` for the three corners of each triangle, in triangle order.
+    pub indices: Vec<[u32; 3]>,
+}
+
+impl MeshNormals {
+    /// The normals at the corners of triangle `index`, if it and its normals exist.
+    pub fn corners(&self, index: usize) -> Option<[Vec3; 3]> {
+        let [a, b, c] = *self.indices.get(index)?;
+        Some([
+            *self.normals.get(a as usize)?,
+            *self.normals.get(b as usize)?,
+            *self.normals.get(c as usize)?,
+        ])
+    }
+
+    /// Applies the linear part of `transform` to the normals.
+    ///
+    /// Normals are mapped by the inverse transpose and renormalized. When `transform`
+    /// mirrors, the last two corners of each triangle swap, matching the reversed winding
+    /// that keeps the mesh's faces pointing outward.
+    pub fn transform(&mut self, transform: Mat4) {
+        let linear = Mat3::from_mat4(transform);
+        let normal_matrix = linear.inverse().transpose();
+        for normal in &mut self.normals {
+            *normal = (normal_matrix * *normal).normalize_or_zero();
+        }
+        if linear.determinant() < 0.0 {
+            for corners in &mut self.indices {
+                corners.swap(1, 2);
+            }
+        }
+    }
+}
+
+impl Mesh {
+    /// Unit normals of every triangle, following the winding; zero for degenerate triangles
+    /// and those with out-of-range vertices.
+    pub fn face_normals(&self) -> Vec<Vec3> {
+        self.triangles
+            .iter()
+            .map(|t| match self.corner_positions([t.v1, t.v2, t.v3]) {
+                Some([a, b, c]) => (b - a).cross(c - a).normalize_or_zero(),
+                None => Vec3::ZERO,
+            })
+            .collect()
+    }
+
+    /// Smooth normals, one per vertex: the average of the surrounding face normals, each
+    /// weighted by the angle of its corner at the vertex. Vertices used by no triangle get
+    /// a zero normal.
+    pub fn vertex_normals(&self) -> Vec<Vec3> {
+        let faces = self.face_normals();
+        let mut normals = vec![Vec3::ZERO; self.vertices.len()];
+        for (t, face) in self.triangles.iter().zip(&faces) {
+            let corners = [t.v1, t.v2, t.v3];
+            let Some(angles) = self.corner_angles(corners) else {
+                continue;
+            };
+            for (v, angle) in corners.into_iter().zip(angles) {
+                normals[v as usize] += *face * angle;
+            }
+        }
+        normals.iter_mut().for_each(|n| *n = n.normalize_or_zero());
+        normals
+    }
+
+    /// Per-corner normals that are smooth across edges flatter than `crease_angle` degrees.
+    ///
+    /// The normal at a corner averages the normals of the faces around its vertex that lie
+    /// within `crease_angle` of the corner's own face, weighted by corner angle. An angle of
+    /// `0` gives flat shading and `180` or more smooths everything, like
+    /// [`vertex_normals`](Self::vertex_normals). Equal normals are stored once.
+    pub fn compute_normals(&self, crease_angle: f32) -> MeshNormals {
+        let faces = self.face_normals();
+        let angles: Vec<Option<[f32; 3]>> = self
+            .triangles
+            .iter()
+            .map(|t| self.corner_angles([t.v1, t.v2, t.v3]))
+            .collect();
+
+        // Triangles around each vertex, as (triangle, corner) pairs in CSR layout
+        let mut start = vec![0usize; self.vertices.len() + 1];
+        for (t, corner_angles) in self.triangles.iter().zip(&angles) {
+            if corner_angles.is_some() {
+                for v in [t.v1, t.v2, t.v3] {
+                    start[v as usize + 1] += 1;
+                }
+            }
+        }
+        for i in 0..self.vertices.len() {
+            start[i + 1] += start[i];
+        }
+        let mut fill = start.clone();
+        let mut incident = vec![(0usize, 0usize); start[self.vertices.len()]];
+        for (i, (t, corner_angles)) in self.triangles.iter().zip(&angles).enumerate() {
+            if corner_angles.is_some() {
+                for (k, v) in [t.v1, t.v2, t.v3].into_iter().enumerate() {
+                    incident[fill[v as usize]] = (i, k);
+                    fill[v as usize] += 1;
+                }
+            }
+        }
+
+        let threshold = crease_angle.clamp(0.0, 180.0).to_radians().cos() - 1e-6;
+        let mut out = MeshNormals {
+            normals: Vec::new(),
+            indices: vec![[0; 3]; self.triangles.len()],
+        };
+        // Normals equal to within rounding are shared, across vertices too
+        let mut shared: HashMap<[i32; 3], u32> = HashMap::new();
+        for v in 0..self.vertices.len() {
+            for &(i, k) in &incident[start[v]..start[v + 1]] {
+                let own = faces[i];
+                let mut sum = Vec3::ZERO;
+                for &(j, l) in &incident[start[v]..start[v + 1]] {
+                    if j == i || faces[j].dot(own) >= threshold {
+                        sum += faces[j] * angles[j].map_or(0.0, |a| a[l]);
+                    }
+                }
+                let normal = sum.try_normalize().unwrap_or(own);
+                let key = (normal * 1e5).round().as_ivec3().to_array();
+                out.indices[i][k] = *shared.entry(key).or_insert_with(|| {
+                    out.normals.push(normal);
+                    out.normals.len() as u32 - 1
+                });
+            }
+        }
+
+        // Triangles with out-of-range vertices still need valid indices
+        for (t, corner_angles) in out.indices.iter_mut().zip(&angles) {
+            if corner_angles.is_none() {
+                out.normals.push(Vec3::ZERO);
+                *t = [out.normals.len() as u32 - 1; 3];
+            }
+        }
+        out
+    }
+
+    /// Computes normals with [`compute_normals`](Self::compute_normals) and stores them in
+    /// [`normals`](Self::normals).
+    pub fn update_normals(&mut self, crease_angle: f32) {
+        self.normals = Some(self.compute_normals(crease_angle));
+    }
+
+    /// The stored normals, if there are any and they still match the triangles.
+    ///
+    /// Editing the triangles leaves stored normals behind; normals whose count differs
+    /// from the triangles' or that refer past their list are ignored.
+    pub fn stored_normals(&self) -> Option<&MeshNormals> {
+        self.normals.as_ref().filter(|n| {
+            n.indices.len() == self.triangles.len()
+                && n.indices
+                    .iter()
+                    .flatten()
+                    .all(|&i| (i as usize) < n.normals.len())
+        })
+    }
+
+    fn corner_positions(&self, corners: [u32; 3]) -> Option<[Vec3; 3]> {
+        let [a, b, c] = corners.map(|v| self.vertices.get(v as usize));
+        Some([Vec3::from(*a?), Vec3::from(*b?), Vec3::from(*c?)])
+    }
+
+    /// Interior angles at the three corners, or `None` for out-of-range vertices.
+    fn corner_angles(&self, corners: [u32; 3]) -> Option<[f32; 3]> {
+        let [a, b, c] = self.corner_positions(corners)?;
+        let angle = |p: Vec3, q: Vec3, r: Vec3| {
+            let (u, w) = ((q - p).normalize_or_zero(), (r - p).normalize_or_zero());
+            u.dot(w).clamp(-1.0, 1.0).acos()
+        };
+        Some([angle(a, b, c), angle(b, c, a), angle(c, a, b)])
+    }
+}
@@ -0,0 +1,350 @@
+//! Mesh offsetting and hollowing.
+//!
+//! [`Mesh::offset`] moves the surface of a closed mesh outward (positive distance) or
+//! inward (negative distance), and [`Mesh::shell`] hollows it into a wall of fixed
+//! thickness, optionally with [`DrainHole`]s so resin can escape the cavity.
+//!
+//! Both sample the mesh into a narrow-band [`SdfGrid`] and remesh the resulting level set
+//! with [`SdfGrid::isosurface`]. As a consequence:
+//!
+//! - the result is a new closed mesh whose triangle size follows the voxel size, and sharp
+//!   edges are rounded by up to about one voxel;
+//! - per-triangle properties, triangle sets and beam lattices are not carried over;
+//! - the input must be closed and consistently oriented (see [`MeshRepair`]).
+//!
+//! ```
+//! use lib3mf_core::model::{DrainHole, Mesh, OffsetOptions, ShellOptions};
+//!
+//! let mut mesh = Mesh::new();
+//! for i in 0..8 {
+//!     let bit = |b: u32| if i >> b & 1 == 1 { 20.0 } else { 0.0 };
+//!     mesh.add_vertex(bit(0), bit(1), bit(2));
+//! }
+//! for [a, b, c, d] in [[0, 2, 3, 1], [4, 5, 7, 6], [0, 1, 5, 4], [2, 6, 7, 3], [0, 4, 6, 2], [1, 3, 7, 5]] {
+//!     mesh.add_triangle(a, b, c);
+//!     mesh.add_triangle(a, c, d);
+//! }
+//!
+//! let grid = OffsetOptions { voxel_size: Some(0.5), ..Default::default() };
+//! let grown = mesh.offset_with(1.0, &grid).unwrap();
+//! assert!(grown.compute_area_and_volume().1 > mesh.compute_area_and_volume().1);
+//!
+//! let hollow = mesh
+//!     .shell_with(&ShellOptions {
+//!         thickness: 2.0,
+//!         drain_holes: vec![DrainHole::new(3.0)],
+//!         grid,
+//!     })
+//!     .unwrap();
+//! assert!(hollow.compute_area_and_volume().1 < mesh.compute_area_and_volume().1);
+//! ```
+//!
+//! [`MeshRepair`]: crate::model::MeshRepair
+
+use crate::error::{Lib3mfError, Result};
+use crate::model::sdf::{SdfGrid, SdfOptions, marching_tetrahedra};
+use crate::model::{Mesh, MeshStorage, SpatialIndex};
+use glam::{Vec2, Vec3};
+
+/// Voxels along the longest side of the grid when no voxel size is given.
+pub const DEFAULT_RESOLUTION: usize = 128;
+
+/// Remeshing grid settings for [`Mesh::offset_with`] and [`ShellOptions`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OffsetOptions {
+    /// Voxel edge length in model units. Default: `None`.
+    ///
+    /// When unset, the grid has [`DEFAULT_RESOLUTION`] voxels along its longest side, made
+    /// finer to resolve shell walls and drain holes and coarser to stay within
+    /// [`max_voxels`](Self::max_voxels).
+    pub voxel_size: Option<f32>,
+    /// Largest number of voxels to allocate before failing. Default: 2²⁴ (256³).
+    pub max_voxels: usize,
+}
+
+impl Default for OffsetOptions {
+    fn default() -> Self {
+        Self {
+            voxel_size: None,
+            max_voxels: 1 << 24,
+        }
+    }
+}
+
+/// Options for [`Mesh::shell_with`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShellOptions {
+    /// Wall thickness, measured inward from the surface. Default: `2.0`.
+    pub thickness: f32,
+    /// Holes cut through the wall from the cavity to the outside. Default: none.
+    pub drain_holes: Vec<DrainHole>,
+    /// Remeshing grid settings. Default: `OffsetOptions::default()`.
+    pub grid: OffsetOptions,
+}
+
+impl Default for ShellOptions {
+    fn default() -> Self {
+        Self {
+            thickness: 2.0,
+            drain_holes: Vec::new(),
+            grid: OffsetOptions::default(),
+        }
+    }
+}
+
+/// A cylindrical hole from inside the cavity of a shell through its wall.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrainHole {
+    /// Hole diameter in model units.
+    pub diameter: f32,
+    /// Point inside the cavity where the hole starts.
+    ///
+    /// `None` places it at the point of the cavity farthest along
+    /// [`direction`](Self::direction) that leaves room for the hole.
+    pub origin: Option<Vec3>,
+    /// Direction the hole runs from its origin through the wall.
+    pub direction: Vec3,
+}
+
+impl DrainHole {
+    /// A hole of `diameter` through the bottom of the cavity, running down (`-Z`).
+    pub fn new(diameter: f32) -> Self {
+        Self {
+            diameter,
+            origin: None,
+            direction: Vec3::NEG_Z,
+        }
+    }
+}
+
+impl Mesh {
+    /// Offsets the surface by `distance` with default [`OffsetOptions`].
+    ///
+    /// See [`offset_with`](Self::offset_with).
+    pub fn offset(&self, distance: f32) -> Result<Mesh> {
+        self.offset_with(distance, &OffsetOptions::default())
+    }
+
+    /// Returns the surface at signed `distance` from this closed mesh: inflated when
+    /// positive, deflated when negative.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Lib3mfError::Validation`] if the mesh has no triangles, `distance` is not
+    /// finite, the grid would exceed [`OffsetOptions::max_voxels`], or deflating removes
+    /// the whole mesh.
+    pub fn offset_with(&self, distance: f32, options: &OffsetOptions) -> Result<Mesh> {
+        if !distance.is_finite() {
+            return Err(Lib3mfError::Validation(format!(
+                "Offset distance must be finite, got {distance}"
+            )));
+        }
+        let index = SpatialIndex::new(self);
+        let sdf = options.sample(&index, distance.abs(), None)?;
+        let mesh = sdf.isosurface(distance);
+        if mesh.triangles.is_empty() {
+            return Err(Lib3mfError::Validation(format!(
+                "Offsetting by {distance} leaves no surface"
+            )));
+        }
+        Ok(mesh)
+    }
+
+    /// Hollows the mesh into a wall of `thickness` with default [`ShellOptions`].
+    ///
+    /// See [`shell_with`](Self::shell_with).
+    pub fn shell(&self, thickness: f32) -> Result<Mesh> {
+        self.shell_with(&ShellOptions {
+            thickness,
+            ..Default::default()
+        })
+    }
+
+    /// Returns a hollow version of this closed mesh: the outer surface plus an inward-facing
+    /// cavity surface [`ShellOptions::thickness`] below it, joined through any drain holes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Lib3mfError::Validation`] if the mesh has no triangles, the thickness or a
+    /// drain hole is invalid, the grid would exceed [`OffsetOptions::max_voxels`], the wall
+    /// leaves no cavity, or an automatically placed drain hole does not fit in the cavity.
+    pub fn shell_with(&self, options: &ShellOptions) -> Result<Mesh> {
+        let thickness = options.thickness;
+        if !(thickness > 0.0 && thickness.is_finite()) {
+            return Err(Lib3mfError::Validation(format!(
+                "Shell thickness must be positive, got {thickness}"
+            )));
+        }
+        for hole in &options.drain_holes {
+            if !(hole.diameter > 0.0 && hole.diameter.is_finite())
+                || hole.direction.length_squared() == 0.0
+                || !hole.direction.is_finite()
+            {
+                return Err(Lib3mfError::Validation(format!(
+                    "Invalid drain hole: diameter {} along {}",
+                    hole.diameter, hole.direction
+                )));
+            }
+        }
+
+        // The wall and every hole need a couple of voxels to be resolved
+        let feature = options
+            .drain_holes
+            .iter()
+            .map(|h| h.diameter)
+            .fold(thickness, f32::min);
+        // Placing a hole needs distances a radius deeper than the cavity wall
+        let reach = options
+            .drain_holes
+            .iter()
+            .filter(|h| h.origin.is_none())
+            .map(|h| thickness + h.diameter / 2.0)
+            .fold(thickness, f32::max);
+        let index = SpatialIndex::new(self);
+        let sdf = options.grid.sample(&index, reach, Some(feature))?;
+        let distances = sdf.to_dense();
+        if !distances.iter().any(|&d| d < -thickness) {
+            return Err(Lib3mfError::Validation(format!(
+                "Wall thickness {thickness} leaves no cavity inside the mesh"
+            )));
+        }
+
+        let holes = options
+            .drain_holes
+            .iter()
+            .map(|hole| Cylinder::place(hole, &sdf, &distances, thickness))
+            .collect::<Result<Vec<_>>>()?;
+        let [nx, ny, _] = sdf.dims();
+        let values: Vec<f32> = distances
+            .iter()
+            .enumerate()
+            .map(|(i, &d)| {
+                // Negative between the surface and the cavity
+                let wall = d.max(-(d + thickness));
+                let p = sdf.position(i % nx, (i / nx) % ny, i / (nx * ny));
+                holes.iter().fold(wall, |v, hole| v.max(-hole.distance(p)))
+            })
+            .collect();
+        Ok(marching_tetrahedra(
+            sdf.origin(),
+            sdf.voxel_size(),
+            sdf.dims(),
+            &values,
+        ))
+    }
+}
+
+impl OffsetOptions {
+    /// Narrow-band SDF of the mesh reaching `reach` beyond the surface on both sides.
+    pub(crate) fn sample<S: MeshStorage + Sync>(
+        &self,
+        index: &SpatialIndex<'_, S>,
+        reach: f32,
+        feature: Option<f32>,
+    ) -> Result<SdfGrid> {
+        let bounds = index.bounds().ok_or_else(|| {
+            Lib3mfError::Validation("Cannot offset a mesh without triangles".into())
+        })?;
+        let size = bounds.max - bounds.min;
+        let voxel_size = self.voxel_size.unwrap_or_else(|| {
+            let mut voxel = (size.max_element() + 2.0 * reach) / DEFAULT_RESOLUTION as f32;
+            if let Some(feature) = feature {
+                voxel = voxel.min(feature / 2.0);
+            }
+            while voxel > 0.0 && grid_voxels(size, reach, voxel) > self.max_voxels as f64 {
+                voxel *= 1.25;
+            }
+            voxel
+        });
+        SdfGrid::from_index(
+            index,
+            &SdfOptions {
+                voxel_size,
+                padding: padding(reach, voxel_size),
+                narrow_band: Some(reach + 2.0 * voxel_size),
+                max_voxels: self.max_voxels,
+            },
+        )
+    }
+}
+
+/// Voxels of padding that keep the level set at `reach` away from the grid edge.
+fn padding(reach: f32, voxel: f32) -> usize {
+    (reach / voxel).ceil() as usize + 2
+}
+
+/// Approximate voxel count of the grid [`OffsetOptions::sample`] builds.
+fn grid_voxels(size: Vec3, reach: f32, voxel: f32) -> f64 {
+    let extent = size + 2.0 * padding(reach, voxel) as f32 * voxel;
+    ((extent / voxel).ceil() + 1.0)
+        .to_array()
+        .iter()
+        .map(|&d| d as f64)
+        .product()
+}
+
+/// A drain hole with a resolved origin: a cylinder capped at the origin and unbounded
+/// along its axis.
+struct Cylinder {
+    origin: Vec3,
+    axis: Vec3,
+    radius: f32,
+}
+
+impl Cylinder {
+    fn place(hole: &DrainHole, sdf: &SdfGrid, distances: &[f32], thickness: f32) -> Result<Self> {
+        let axis = hole.direction.normalize();
+        let radius = hole.diameter / 2.0;
+        let origin = match hole.origin {
+            Some(origin) => origin,
+            None => {
+                // Cavity voxels with room for the hole, deepest along the axis
+                let [nx, ny, _] = sdf.dims();
+                let candidates: Vec<Vec3> = distances
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &d)| d < -(thickness + radius))
+                    .map(|(i, _)| sdf.position(i % nx, (i / nx) % ny, i / (nx * ny)))
+                    .collect();
+                let deepest = candidates
+                    .iter()
+                    .map(|p| p.dot(axis))
+                    .fold(f32::NEG_INFINITY, f32::max);
+                let tie = sdf.voxel_size() * 0.5;
+                let floor: Vec<Vec3> = candidates
+                    .into_iter()
+                    .filter(|p| p.dot(axis) > deepest - tie)
+                    .collect();
+                if floor.is_empty() {
+                    return Err(Lib3mfError::Validation(format!(
+                        "No room for a drain hole of diameter {} inside the cavity",
+                        hole.diameter
+                    )));
+                }
+                // A flat floor ties; take the middle of it
+                let center = floor.iter().sum::<Vec3>() / floor.len() as f32;
+                floor
+                    .into_iter()
+                    .min_by(|a, b| {
+                        a.distance_squared(center)
+                            .total_cmp(&b.distance_squared(center))
+                    })
+                    .unwrap_or(center)
+            }
+        };
+        Ok(Self {
+            origin,
+            axis,
+            radius,
+        })
+    }
+
+    /// Signed distance to the cylinder, negative inside.
+    fn distance(&self, p: Vec3) -> f32 {
+        let d = p - self.origin;
+        let along = d.dot(self.axis);
+        let radial = (d - self.axis * along).length() - self.radius;
+        let (a, b) = (radial, -along);
+        a.max(b).min(0.0) + Vec2::new(a.max(0.0), b.max(0.0)).length()
+    }
+}
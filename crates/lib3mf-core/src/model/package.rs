@@ -1,21 +1,41 @@
-use crate::model::Model;
-use std::collections::HashMap;
+use crate::archive::normalize_part_name;
+use crate::model::{Geometry, Model, Object, ResourceId};
+use std::collections::{BTreeSet, HashMap};
+
+/// Part name of the main model part in packages this crate writes.
+const MAIN_MODEL_PART: &str = "3D/3dmodel.model";
 
 /// Represents a 3MF Package, which can contain multiple model parts.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct Package {
     /// The main model part (usually /3D/3dmodel.model).
     pub main_model: Model,
 
+    /// Part name of the main model, without a leading `/`.
+    ///
+    /// `3D/3dmodel.model` unless the package was loaded from an archive whose root
+    /// relationship names another part. `path` attributes naming it refer to
+    /// [`main_model`](Self::main_model), and
+    /// [`PackageWriter`](crate::writer::package_writer::PackageWriter) writes the main
+    /// model there.
+    pub main_model_path: String,
+
     /// Additional model parts keyed by their package path.
     pub parts: HashMap<String, Model>,
 }
 
+impl Default for Package {
+    fn default() -> Self {
+        Self::new(Model::default())
+    }
+}
+
 impl Package {
     /// Creates a new `Package` with the given main model and no additional parts.
     pub fn new(main_model: Model) -> Self {
         Self {
             main_model,
+            main_model_path: MAIN_MODEL_PART.to_string(),
             parts: HashMap::new(),
         }
     }
@@ -24,4 +44,63 @@ impl Package {
     pub fn add_part(&mut self, path: String, model: Model) {
         self.parts.insert(path, model);
     }
+
+    /// Returns the model part a Production Extension `path` attribute refers to.
+    ///
+    /// `None` and [`main_model_path`](Self::main_model_path) refer to the main model.
+    /// Paths are compared as part names, ignoring a leading `/` and case.
+    pub fn part(&self, path: Option<&str>) -> Option<&Model> {
+        let Some(path) = path else {
+            return Some(&self.main_model);
+        };
+        let name = normalize_part_name(path);
+        if name.eq_ignore_ascii_case(&normalize_part_name(&self.main_model_path)) {
+            return Some(&self.main_model);
+        }
+        self.parts
+            .iter()
+            .find(|(key, _)| normalize_part_name(key).eq_ignore_ascii_case(&name))
+            .map(|(_, model)| model)
+    }
+
+    /// Resolves an object by ID and optional model part path, like
+    /// [`PartResolver::resolve_object`](crate::model::resolver::PartResolver::resolve_object)
+    /// but from the parts already in the package.
+    pub fn resolve_object(&self, id: ResourceId, path: Option<&str>) -> Option<(&Model, &Object)> {
+        let model = self.part(path)?;
+        model.resources.get_object(id).map(|object| (model, object))
+    }
+}
+
+impl Model {
+    /// Returns the model parts this model refers to through the Production Extension `path`
+    /// attributes of build items, components and boolean shapes, as sorted, deduplicated
+    /// part names.
+    pub fn referenced_parts(&self) -> Vec<String> {
+        let mut paths: Vec<&str> = self
+            .build
+            .items
+            .iter()
+            .filter_map(|item| item.path.as_deref())
+            .collect();
+        for object in self.resources.iter_objects() {
+            match &object.geometry {
+                Geometry::Components(components) => {
+                    paths.extend(
+                        components
+                            .components
+                            .iter()
+                            .filter_map(|c| c.path.as_deref()),
+                    );
+                }
+                Geometry::BooleanShape(shape) => {
+                    paths.extend(shape.base_path.as_deref());
+                    paths.extend(shape.operations.iter().filter_map(|o| o.path.as_deref()));
+                }
+                _ => {}
+            }
+        }
+        let names: BTreeSet<String> = paths.into_iter().map(normalize_part_name).collect();
+        names.into_iter().collect()
+    }
 }
@@ -0,0 +1,228 @@
+//! World-space bounds of the build, and recentering of builds placed far from the origin.
+//!
+//! `f32` coordinates get coarser with distance from the origin: beyond
+//! [`SAFE_COORDINATE_LIMIT_MM`] adjacent values are more than a micrometre apart, and every
+//! transform a slicer applies on top rounds again. CAD exports placed in site or machine
+//! coordinates can sit millions of units out. [`Model::recenter`] moves such a build back to
+//! the origin by translating its build items, leaving the meshes untouched, and records the
+//! translation in metadata so the original placement can be restored.
+//!
+//! ```
+//! use glam::{Mat4, Vec3};
+//! use lib3mf_core::model::{BuildItem, Geometry, Mesh, Model, Object, ObjectType, ResourceId};
+//!
+//! let mut mesh = Mesh::new();
+//! mesh.add_vertex(0.0, 0.0, 0.0);
+//! mesh.add_vertex(10.0, 0.0, 0.0);
+//! mesh.add_vertex(0.0, 10.0, 10.0);
+//! mesh.add_triangle(0, 1, 2);
+//!
+//! let mut model = Model::default();
+//! model.resources.add_object(Object {
+//!     id: ResourceId(1),
+//!     object_type: ObjectType::Model,
+//!     name: None,
+//!     part_number: None,
+//!     uuid: None,
+//!     pid: None,
+//!     pindex: None,
+//!     thumbnail: None,
+//!     geometry: Geometry::Mesh(mesh),
+//! })?;
+//! let far = Mat4::from_translation(Vec3::new(2.0e6, 0.0, 0.0));
+//! model.build.items.push(BuildItem::new(ResourceId(1), far));
+//!
+//! let offset = model.recenter();
+//! assert_eq!(offset, Vec3::new(-2_000_005.0, -5.0, -5.0));
+//! assert_eq!(model.build_bounds().unwrap().max, [5.0, 5.0, 5.0]);
+//! assert_eq!(model.recenter_offset(), Some(offset));
+//! # Ok::<(), lib3mf_core::error::Lib3mfError>(())
+//! ```
+
+use crate::model::{BoundingBox, Geometry, Model, ResourceId, Vertex};
+use glam::{Mat4, Vec3};
+use std::collections::{HashMap, HashSet};
+
+/// Largest coordinate magnitude, in millimetres, at which adjacent `f32` values are at most
+/// 1 µm apart (2²³ µm).
+pub const SAFE_COORDINATE_LIMIT_MM: f64 = 8388.608;
+
+/// Namespace URI declared for the recenter metadata prefix.
+pub const RECENTER_NS: &str = "https://github.com/sscargal/lib3mf-rs/recenter/2026";
+
+/// Namespace prefix of the recenter metadata name.
+pub const RECENTER_PREFIX: &str = "recenter";
+
+/// Metadata name holding the total translation [`Model::recenter`] applied to the build,
+/// written as `x y z` in model units.
+pub const RECENTER_OFFSET_NAME: &str = "recenter:offset";
+
+impl Model {
+    /// Returns the world-space bounds of the geometry build item `index` places.
+    ///
+    /// Meshes and displacement meshes count, including those reached through components.
+    /// Items and components that reference other model parts, and geometry without
+    /// vertices (boolean shapes, slices, volumes), are left out. Returns `None` if the
+    /// index is out of range or nothing is left.
+    pub fn build_item_bounds(&self, index: usize) -> Option<BoundingBox> {
+        let item = self.build.items.get(index)?;
+        if item.path.is_some() {
+            return None;
+        }
+        BoundsCache::new(self)
+            .object_bounds(item.object_id)
+            .map(|bounds| bounds.transform(item.transform))
+    }
+
+    /// Returns the world-space bounds of everything the build places, as
+    /// [`build_item_bounds`](Self::build_item_bounds) counts it.
+    pub fn build_bounds(&self) -> Option<BoundingBox> {
+        let mut total = None;
+        for bounds in item_bounds(self).iter().flatten() {
+            union(&mut total, bounds);
+        }
+        total
+    }
+
+    /// Translates every build item so that the [`build_bounds`](Self::build_bounds) are
+    /// centered on the origin, and returns the translation.
+    ///
+    /// Only the build item transforms change. The translation is added to the
+    /// [`RECENTER_OFFSET_NAME`] metadata entry, so subtracting that entry from the item
+    /// transforms restores the original placement however often the model was recentered.
+    /// A build without bounds is left as it is, and the result is zero.
+    pub fn recenter(&mut self) -> Vec3 {
+        let Some(bounds) = self.build_bounds() else {
+            return Vec3::ZERO;
+        };
+        let center = (Vec3::from(bounds.min).as_dvec3() + Vec3::from(bounds.max).as_dvec3()) / 2.0;
+        let offset = -center.as_vec3();
+        if offset == Vec3::ZERO {
+            return offset;
+        }
+
+        let translation = Mat4::from_translation(offset);
+        for item in self.build.items.iter_mut() {
+            item.transform = translation * item.transform;
+        }
+        let total = self.recenter_offset().unwrap_or(Vec3::ZERO) + offset;
+        self.metadata.insert(
+            RECENTER_OFFSET_NAME,
+            format!("{} {} {}", total.x, total.y, total.z),
+        );
+        self.extra_namespaces
+            .insert(RECENTER_PREFIX.to_string(), RECENTER_NS.to_string());
+        offset
+    }
+
+    /// Returns the total translation [`recenter`](Self::recenter) applied, if the model
+    /// records one.
+    pub fn recenter_offset(&self) -> Option<Vec3> {
+        let value = self.metadata.get(RECENTER_OFFSET_NAME)?;
+        let components: Vec<f32> = value
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .ok()?;
+        <[f32; 3]>::try_from(components).ok().map(Vec3::from)
+    }
+}
+
+/// Object bounds in object coordinates, computed once per object.
+struct BoundsCache<'a> {
+    model: &'a Model,
+    bounds: HashMap<ResourceId, Option<BoundingBox>>,
+    /// Objects whose bounds are being computed, so component cycles end.
+    active: HashSet<ResourceId>,
+}
+
+impl<'a> BoundsCache<'a> {
+    fn new(model: &'a Model) -> Self {
+        Self {
+            model,
+            bounds: HashMap::new(),
+            active: HashSet::new(),
+        }
+    }
+
+    fn object_bounds(&mut self, id: ResourceId) -> Option<BoundingBox> {
+        if let Some(bounds) = self.bounds.get(&id) {
+            return *bounds;
+        }
+        if !self.active.insert(id) {
+            return None;
+        }
+        let model = self.model;
+        let bounds = match model
+            .resources
+            .get_object(id)
+            .map(|object| &object.geometry)
+        {
+            Some(Geometry::Mesh(mesh)) => mesh.compute_aabb(),
+            Some(Geometry::PreciseMesh(mesh)) => mesh.compute_aabb(),
+            Some(Geometry::DisplacementMesh(mesh)) => vertex_bounds(&mesh.vertices),
+            Some(Geometry::Components(components)) => {
+                let mut total = None;
+                for component in components.components.iter().filter(|c| c.path.is_none()) {
+                    if let Some(bounds) = self.object_bounds(component.object_id) {
+                        union(&mut total, &bounds.transform(component.transform));
+                    }
+                }
+                total
+            }
+            _ => None,
+        };
+        self.active.remove(&id);
+        self.bounds.insert(id, bounds);
+        bounds
+    }
+}
+
+/// Returns the world-space bounds of every build item, in build order.
+pub(crate) fn item_bounds(model: &Model) -> Vec<Option<BoundingBox>> {
+    let mut cache = BoundsCache::new(model);
+    model
+        .build
+        .items
+        .iter()
+        .map(|item| {
+            if item.path.is_some() {
+                return None;
+            }
+            cache
+                .object_bounds(item.object_id)
+                .map(|bounds| bounds.transform(item.transform))
+        })
+        .collect()
+}
+
+pub(crate) fn vertex_bounds(vertices: &[Vertex]) -> Option<BoundingBox> {
+    let mut total = None;
+    for v in vertices {
+        let point = [v.x, v.y, v.z];
+        union(
+            &mut total,
+            &BoundingBox {
+                min: point,
+                max: point,
+            },
+        );
+    }
+    total
+}
+
+fn union(total: &mut Option<BoundingBox>, bounds: &BoundingBox) {
+    match total {
+        Some(total) => total.union(bounds),
+        None => *total = Some(*bounds),
+    }
+}
+
+/// Returns the largest absolute coordinate of `bounds`.
+pub(crate) fn max_abs_coordinate(bounds: &BoundingBox) -> f32 {
+    bounds
+        .min
+        .iter()
+        .chain(&bounds.max)
+        .fold(0.0, |max, v| max.max(v.abs()))
+}
@@ -0,0 +1,283 @@
+//! Double-precision mesh storage for metrology-grade meshes.
+//!
+//! [`Mesh`] stores coordinates as `f32`, which keeps 24 significant bits: a part measured
+//! in millimetres a few metres from the origin is only resolved to a fraction of a
+//! micrometre, and fine features of large scans visibly snap to that grid. [`PreciseMesh`]
+//! stores coordinates as `f64` instead and implements [`MeshStorage`], so it goes through
+//! the same code as `Mesh`:
+//!
+//! - [`parse_mesh_into`] reads coordinates as `f64` for storages that
+//!   [keep them](MeshStorage::has_f64_coordinates);
+//! - [`write_mesh`] writes them back in the shortest form that reads back exactly;
+//! - [`PreciseMesh::compute_aabb`] and [`PreciseMesh::compute_area_and_volume`] compute
+//!   statistics in `f64`;
+//! - [`MeshRepair`] repairs it without rounding the vertices it keeps.
+//!
+//! Geometry validation ([`validate_mesh`]) checks the `f32` view of the coordinates.
+//!
+//! # Scope
+//!
+//! Models store `PreciseMesh` as [`Geometry::PreciseMesh`], which
+//! [`parse_model_with_options`] produces when [`ParseOptions::precise_coordinates`] is set.
+//! [`Model::compute_stats`], [`PackageWriter`] and [`MeshRepair`] on an [`Object`] use its
+//! `f64` coordinates. Operations that only take [`Geometry::Mesh`], such as cutting and
+//! shell splitting, skip it; converting it into a `Mesh` for them rounds its coordinates.
+//!
+//! [`parse_mesh_into`]: crate::parser::mesh_parser::parse_mesh_into
+//! [`write_mesh`]: crate::writer::mesh_writer::write_mesh
+//! [`validate_mesh`]: crate::validation::geometry::validate_mesh
+//! [`Geometry::Mesh`]: crate::model::Geometry::Mesh
+//! [`Geometry::PreciseMesh`]: crate::model::Geometry::PreciseMesh
+//! [`parse_model_with_options`]: crate::parser::parse_model_with_options
+//! [`ParseOptions::precise_coordinates`]: crate::parser::ParseOptions::precise_coordinates
+//! [`Object`]: crate::model::Object
+//! [`PackageWriter`]: crate::writer::package_writer::PackageWriter
+//! [`Model::compute_stats`]: crate::model::Model::compute_stats
+//!
+//! # Examples
+//!
+//! ```
+//! use glam::DVec3;
+//! use lib3mf_core::model::{Mesh, MeshStorage, PreciseMesh};
+//!
+//! let mut mesh = PreciseMesh::new();
+//! mesh.push_vertex_f64(DVec3::new(4_000_000.000_001, 0.0, 0.0));
+//! assert_eq!(mesh.vertex_f64(0).x, 4_000_000.000_001);
+//!
+//! // The f32 view rounds to the nearest representable value.
+//! assert_eq!(Mesh::from(&mesh).vertices[0].x, 4_000_000.0);
+//! ```
+
+use crate::model::repair::{MeshRepair, RepairOptions, RepairStats};
+use crate::model::stats::BoundingBox;
+use crate::model::{
+    BeamLattice, Mesh, MeshStorage, ResourceId, Triangle, TriangleAttributes, TriangleSet, Vertex,
+};
+use glam::{DVec3, Vec3};
+use serde::{Deserialize, Serialize};
+
+/// A triangle mesh with `f64` vertex coordinates.
+///
+/// Mirrors [`Mesh`] apart from the coordinate type; convert with `From` in either
+/// direction.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PreciseMesh {
+    /// Vertex coordinates in model units.
+    pub vertices: Vec<DVec3>,
+    /// Triangles referencing `vertices` by index.
+    pub triangles: Vec<Triangle>,
+    /// Beam Lattice extension data, if any.
+    pub beam_lattice: Option<BeamLattice>,
+    /// Volumetric Extension volume data applied inside the mesh, if any.
+    pub volume_id: Option<ResourceId>,
+    /// Triangle Sets extension groups.
+    pub triangle_sets: Vec<TriangleSet>,
+    /// Non-standard triangle attributes, such as slicer paint data.
+    pub triangle_attributes: TriangleAttributes,
+}
+
+impl PreciseMesh {
+    /// Creates an empty mesh.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Computes the axis-aligned bounding box of the mesh, rounded outward to `f32`.
+    ///
+    /// Returns `None` if the mesh has no vertices.
+    pub fn compute_aabb(&self) -> Option<BoundingBox> {
+        let (min, max) = self.bounds()?;
+        let down = |v: f64| match v as f32 {
+            rounded if f64::from(rounded) > v => rounded.next_down(),
+            rounded => rounded,
+        };
+        let up = |v: f64| match v as f32 {
+            rounded if f64::from(rounded) < v => rounded.next_up(),
+            rounded => rounded,
+        };
+        Some(BoundingBox {
+            min: min.to_array().map(down),
+            max: max.to_array().map(up),
+        })
+    }
+
+    /// Computes the surface area and signed volume of the mesh in `f64`.
+    ///
+    /// Both are measured relative to the center of the mesh, so they stay accurate for
+    /// meshes far from the origin. Returns `(0.0, 0.0)` for a mesh without triangles.
+    pub fn compute_area_and_volume(&self) -> (f64, f64) {
+        let Some((min, max)) = self.bounds() else {
+            return (0.0, 0.0);
+        };
+        let center = (min + max) / 2.0;
+        self.triangles.iter().fold((0.0, 0.0), |(area, volume), t| {
+            let [a, b, c] = [t.v1, t.v2, t.v3].map(|v| self.vertices[v as usize] - center);
+            (
+                area + (b - a).cross(c - a).length() / 2.0,
+                volume + a.dot(b.cross(c)) / 6.0,
+            )
+        })
+    }
+
+    fn bounds(&self) -> Option<(DVec3, DVec3)> {
+        let first = *self.vertices.first()?;
+        Some(
+            self.vertices
+                .iter()
+                .fold((first, first), |(min, max), v| (min.min(*v), max.max(*v))),
+        )
+    }
+
+    /// Returns the mesh with coordinates relative to `origin`, rounded to `f32`.
+    fn local_mesh(&self, origin: DVec3) -> Mesh {
+        Mesh {
+            vertices: self
+                .vertices
+                .iter()
+                .map(|v| Vertex::from((*v - origin).as_vec3()))
+                .collect(),
+            triangles: self.triangles.clone().into(),
+            beam_lattice: self.beam_lattice.clone(),
+            volume_id: self.volume_id,
+            triangle_sets: self.triangle_sets.clone(),
+            triangle_attributes: self.triangle_attributes.clone(),
+            normals: None,
+        }
+    }
+}
+
+impl MeshRepair for PreciseMesh {
+    /// Repairs the mesh as [`Mesh`] does.
+    ///
+    /// The repair passes run on `f32` coordinates relative to the center of the mesh, which
+    /// is where `f32` is most precise. None of them computes new coordinates: they keep,
+    /// drop or merge existing vertices in order, so each vertex kept gets its `f64`
+    /// coordinates back.
+    fn repair(&mut self, options: RepairOptions) -> RepairStats {
+        let origin = self
+            .bounds()
+            .map_or(DVec3::ZERO, |(min, max)| (min + max) / 2.0);
+        let mut local = self.local_mesh(origin);
+        let originals = local.vertices.to_vec();
+        let stats = local.repair(options);
+
+        let mut candidates = originals.iter().zip(&self.vertices);
+        self.vertices = local
+            .vertices
+            .iter()
+            .map(|kept| {
+                candidates
+                    .find(|(original, _)| *original == kept)
+                    .map_or_else(|| Vec3::from(*kept).as_dvec3() + origin, |(_, v)| *v)
+            })
+            .collect();
+        self.triangles = local.triangles.to_vec();
+        self.beam_lattice = local.beam_lattice;
+        self.triangle_sets = local.triangle_sets;
+        self.triangle_attributes = local.triangle_attributes;
+        stats
+    }
+}
+
+impl MeshStorage for PreciseMesh {
+    fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn triangle_count(&self) -> usize {
+        self.triangles.len()
+    }
+
+    fn vertex(&self, index: usize) -> Vertex {
+        Vertex::from(self.vertices[index].as_vec3())
+    }
+
+    fn triangle(&self, index: usize) -> Triangle {
+        self.triangles[index]
+    }
+
+    fn push_vertex(&mut self, vertex: Vertex) {
+        self.vertices.push(Vec3::from(vertex).as_dvec3());
+    }
+
+    fn push_triangle(&mut self, triangle: Triangle) {
+        self.triangles.push(triangle);
+    }
+
+    fn beam_lattice(&self) -> Option<&BeamLattice> {
+        self.beam_lattice.as_ref()
+    }
+
+    fn set_beam_lattice(&mut self, lattice: BeamLattice) {
+        self.beam_lattice = Some(lattice);
+    }
+
+    fn triangle_sets(&self) -> &[TriangleSet] {
+        &self.triangle_sets
+    }
+
+    fn set_triangle_sets(&mut self, sets: Vec<TriangleSet>) {
+        self.triangle_sets = sets;
+    }
+
+    fn triangle_attributes(&self) -> &TriangleAttributes {
+        &self.triangle_attributes
+    }
+
+    fn triangle_attributes_mut(&mut self) -> &mut TriangleAttributes {
+        &mut self.triangle_attributes
+    }
+
+    fn heap_bytes(&self) -> usize {
+        self.vertices.capacity() * size_of::<DVec3>()
+            + self.triangles.capacity() * size_of::<Triangle>()
+    }
+
+    fn volume_id(&self) -> Option<ResourceId> {
+        self.volume_id
+    }
+
+    fn has_f64_coordinates(&self) -> bool {
+        true
+    }
+
+    fn vertex_f64(&self, index: usize) -> DVec3 {
+        self.vertices[index]
+    }
+
+    fn push_vertex_f64(&mut self, vertex: DVec3) {
+        self.vertices.push(vertex);
+    }
+}
+
+impl From<&Mesh> for PreciseMesh {
+    fn from(mesh: &Mesh) -> Self {
+        PreciseMesh {
+            vertices: mesh
+                .vertices
+                .iter()
+                .map(|v| Vec3::from(*v).as_dvec3())
+                .collect(),
+            triangles: mesh.triangles.to_vec(),
+            beam_lattice: mesh.beam_lattice.clone(),
+            volume_id: mesh.volume_id,
+            triangle_sets: mesh.triangle_sets.clone(),
+            triangle_attributes: mesh.triangle_attributes.clone(),
+        }
+    }
+}
+
+impl From<&PreciseMesh> for Mesh {
+    /// Converts to a [`Mesh`], rounding coordinates to the nearest `f32`.
+    fn from(mesh: &PreciseMesh) -> Self {
+        Mesh {
+            vertices: mesh.iter_vertices().collect(),
+            triangles: mesh.triangles.clone().into(),
+            beam_lattice: mesh.beam_lattice.clone(),
+            volume_id: mesh.volume_id,
+            triangle_sets: mesh.triangle_sets.clone(),
+            triangle_attributes: mesh.triangle_attributes.clone(),
+            normals: None,
+        }
+    }
+}
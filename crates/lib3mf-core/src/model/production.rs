@@ -0,0 +1,84 @@
+//! Production Extension identifiers (`p:UUID`).
+//!
+//! When a file uses the Production Extension, the build, every build item, every object
+//! and every component must carry a unique `p:UUID`. Validation at
+//! [`Strict`](crate::validation::ValidationLevel::Strict) level reports missing ones, and
+//! [`Model::assign_missing_uuids`] fills them in with random (version 4) UUIDs:
+//!
+//! ```
+//! use lib3mf_core::model::{BuildItem, Model, ResourceId};
+//!
+//! let mut model = Model::default();
+//! model.build.items.push(BuildItem {
+//!     object_id: ResourceId(1),
+//!     uuid: None,
+//!     path: None,
+//!     part_number: None,
+//!     transform: glam::Mat4::IDENTITY,
+//!     printable: None,
+//!     metadata: Default::default(),
+//! });
+//!
+//! // The build and its item
+//! assert_eq!(model.assign_missing_uuids(), 2);
+//! assert_eq!(model.assign_missing_uuids(), 0);
+//! ```
+
+use crate::model::{Geometry, Model};
+use uuid::Uuid;
+
+impl Model {
+    /// Returns true if the model uses the Production Extension: it declares or requires
+    /// the namespace ([`production_namespace`](Model::production_namespace)), or the build,
+    /// a build item, an object, a component or a boolean operand carries a UUID or an
+    /// external `path`.
+    pub fn uses_production_extension(&self) -> bool {
+        self.production_namespace.is_some()
+            || self.build.uuid.is_some()
+            || self
+                .build
+                .items
+                .iter()
+                .any(|i| i.uuid.is_some() || i.path.is_some())
+            || self.resources.iter_objects().any(|o| {
+                o.uuid.is_some()
+                    || match &o.geometry {
+                        Geometry::Components(comps) => comps
+                            .components
+                            .iter()
+                            .any(|c| c.uuid.is_some() || c.path.is_some()),
+                        Geometry::BooleanShape(shape) => {
+                            shape.base_path.is_some()
+                                || shape.operations.iter().any(|op| op.path.is_some())
+                        }
+                        _ => false,
+                    }
+            })
+    }
+
+    /// Gives the build, build items, objects and components that lack a UUID a new random
+    /// one. Existing UUIDs are kept. Returns the number of UUIDs assigned.
+    pub fn assign_missing_uuids(&mut self) -> usize {
+        fn assign(uuid: &mut Option<Uuid>, assigned: &mut usize) {
+            if uuid.is_none() {
+                *uuid = Some(Uuid::new_v4());
+                *assigned += 1;
+            }
+        }
+
+        let mut assigned = 0;
+        assign(&mut self.build.uuid, &mut assigned);
+        for item in &mut self.build.items {
+            assign(&mut item.uuid, &mut assigned);
+        }
+        for object in self.resources.iter_objects_mut() {
+            assign(&mut object.uuid, &mut assigned);
+            if let Geometry::Components(comps) = &mut object.geometry {
+                for component in &mut comps.components {
+                    assign(&mut component.uuid, &mut assigned);
+                }
+            }
+        }
+        assigned
+    }
+}
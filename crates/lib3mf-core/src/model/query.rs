@@ -0,0 +1,509 @@
+//! Selecting objects by type, size and other properties.
+//!
+//! [`Model::query`] starts a fluent query; each step narrows the selection and the result
+//! is a list of object references, ordered by ID unless a step sorts them:
+//!
+//! ```
+//! use lib3mf_core::model::{Model, ObjectType};
+//!
+//! let model = Model::default();
+//! let big: Vec<_> = model
+//!     .query()
+//!     .objects()
+//!     .of_type(ObjectType::Model)
+//!     .with_triangle_count_gt(1_000_000)
+//!     .largest_by_volume(5)
+//!     .to_vec();
+//! assert!(big.is_empty());
+//! ```
+//!
+//! The same conditions can be written as text with [`ObjectFilter`], which is what the
+//! CLI `query --where` option uses:
+//!
+//! ```
+//! use lib3mf_core::model::{Model, query::ObjectFilter};
+//!
+//! let filter = ObjectFilter::parse("type=model && triangles>1e6 || name~bracket").unwrap();
+//! let model = Model::default();
+//! assert_eq!(model.query().objects().matching(&filter).count(), 0);
+//! ```
+//!
+//! Triangle and vertex counts and volumes of component assemblies include the objects
+//! they reference, once per reference. Objects in other model parts are followed when the
+//! part is loaded in [`Model::attachments`]. Volumes are in model units cubed.
+
+use crate::error::{Lib3mfError, Result};
+use crate::model::content_hash::{load_parts, normalize};
+use crate::model::{Geometry, Model, Object, ObjectType, ResourceId};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Size measures of an object, used for filtering and sorting.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ObjectMetrics {
+    /// Number of triangles, including referenced components.
+    pub triangles: u64,
+    /// Number of vertices, including referenced components.
+    pub vertices: u64,
+    /// Enclosed volume in model units cubed, including referenced components.
+    pub volume: f64,
+}
+
+/// Entry point for queries over a model. Created by [`Model::query`].
+#[derive(Debug, Clone, Copy)]
+pub struct ModelQuery<'a> {
+    model: &'a Model,
+}
+
+impl Model {
+    /// Starts a query over the model's resources. See the [module docs](crate::model::query).
+    pub fn query(&self) -> ModelQuery<'_> {
+        ModelQuery { model: self }
+    }
+}
+
+impl<'a> ModelQuery<'a> {
+    /// Selects all objects, ordered by ID.
+    pub fn objects(self) -> ObjectQuery<'a> {
+        let mut selected: Vec<&Object> = self.model.resources.iter_objects().collect();
+        selected.sort_by_key(|o| o.id);
+        ObjectQuery {
+            model: self.model,
+            selected,
+            metrics: MetricsCache::default(),
+        }
+    }
+}
+
+/// A selection of objects, narrowed step by step.
+#[derive(Debug, Clone)]
+pub struct ObjectQuery<'a> {
+    model: &'a Model,
+    selected: Vec<&'a Object>,
+    metrics: MetricsCache,
+}
+
+impl<'a> ObjectQuery<'a> {
+    /// Keeps objects for which `predicate` returns true.
+    pub fn filter(mut self, mut predicate: impl FnMut(&Object) -> bool) -> Self {
+        self.selected.retain(|o| predicate(o));
+        self
+    }
+
+    /// Keeps objects of the given type.
+    pub fn of_type(self, object_type: ObjectType) -> Self {
+        self.filter(|o| o.object_type == object_type)
+    }
+
+    /// Keeps objects whose name contains `text`, ignoring case.
+    pub fn name_contains(self, text: &str) -> Self {
+        let text = text.to_lowercase();
+        self.filter(|o| {
+            o.name
+                .as_deref()
+                .is_some_and(|n| n.to_lowercase().contains(&text))
+        })
+    }
+
+    /// Keeps objects placed directly by a build item.
+    pub fn in_build(self) -> Self {
+        let placed = build_objects(self.model);
+        self.filter(|o| placed.contains(&o.id))
+    }
+
+    /// Keeps objects with more than `count` triangles.
+    pub fn with_triangle_count_gt(self, count: u64) -> Self {
+        self.retain_metrics(|m| m.triangles > count)
+    }
+
+    /// Keeps objects with fewer than `count` triangles.
+    pub fn with_triangle_count_lt(self, count: u64) -> Self {
+        self.retain_metrics(|m| m.triangles < count)
+    }
+
+    /// Keeps objects matching a parsed filter expression.
+    pub fn matching(mut self, filter: &ObjectFilter) -> Self {
+        let placed = build_objects(self.model);
+        let selected = std::mem::take(&mut self.selected);
+        self.selected = selected
+            .into_iter()
+            .filter(|o| {
+                let metrics = self.metrics_of(o);
+                filter.matches(o, &metrics, placed.contains(&o.id))
+            })
+            .collect();
+        self
+    }
+
+    /// Keeps the `n` objects with the largest volume, largest first.
+    pub fn largest_by_volume(mut self, n: usize) -> Self {
+        let mut selected = std::mem::take(&mut self.selected);
+        let volumes: HashMap<ResourceId, f64> = selected
+            .iter()
+            .map(|o| (o.id, self.metrics_of(o).volume))
+            .collect();
+        selected.sort_by(|a, b| volumes[&b.id].total_cmp(&volumes[&a.id]));
+        selected.truncate(n);
+        self.selected = selected;
+        self
+    }
+
+    /// Returns the number of selected objects.
+    pub fn count(&self) -> usize {
+        self.selected.len()
+    }
+
+    /// Returns true if no objects are selected.
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    /// Returns the first selected object.
+    pub fn first(&self) -> Option<&'a Object> {
+        self.selected.first().copied()
+    }
+
+    /// Returns the IDs of the selected objects.
+    pub fn ids(&self) -> Vec<ResourceId> {
+        self.selected.iter().map(|o| o.id).collect()
+    }
+
+    /// Returns an iterator over the selected objects.
+    pub fn iter(&self) -> impl Iterator<Item = &'a Object> + '_ {
+        self.selected.iter().copied()
+    }
+
+    /// Returns the selected objects.
+    pub fn to_vec(self) -> Vec<&'a Object> {
+        self.selected
+    }
+
+    /// Returns the selected objects with their metrics.
+    pub fn with_metrics(mut self) -> Vec<(&'a Object, ObjectMetrics)> {
+        let selected = std::mem::take(&mut self.selected);
+        selected
+            .into_iter()
+            .map(|o| (o, self.metrics_of(o)))
+            .collect()
+    }
+
+    fn retain_metrics(mut self, mut keep: impl FnMut(&ObjectMetrics) -> bool) -> Self {
+        let selected = std::mem::take(&mut self.selected);
+        self.selected = selected
+            .into_iter()
+            .filter(|o| keep(&self.metrics_of(o)))
+            .collect();
+        self
+    }
+
+    fn metrics_of(&mut self, obj: &Object) -> ObjectMetrics {
+        self.metrics.get(self.model, "", obj.id)
+    }
+}
+
+/// Object IDs referenced by build items in this model part.
+fn build_objects(model: &Model) -> HashSet<ResourceId> {
+    model
+        .build
+        .items
+        .iter()
+        .filter(|i| i.path.is_none())
+        .map(|i| i.object_id)
+        .collect()
+}
+
+/// Memoized object metrics, keyed by model part (empty for the root) and object ID.
+#[derive(Debug, Clone, Default)]
+struct MetricsCache {
+    /// Model parts referenced by `path`, parsed on first use.
+    parts: Option<HashMap<String, Model>>,
+    metrics: HashMap<(String, ResourceId), ObjectMetrics>,
+    visiting: HashSet<(String, ResourceId)>,
+}
+
+impl MetricsCache {
+    fn get(&mut self, root: &Model, part: &str, id: ResourceId) -> ObjectMetrics {
+        let key = (part.to_string(), id);
+        if let Some(metrics) = self.metrics.get(&key) {
+            return *metrics;
+        }
+        if !self.visiting.insert(key.clone()) {
+            return ObjectMetrics::default();
+        }
+        let metrics = self.compute(root, part, id);
+        self.visiting.remove(&key);
+        self.metrics.insert(key, metrics);
+        metrics
+    }
+
+    fn compute(&mut self, root: &Model, part: &str, id: ResourceId) -> ObjectMetrics {
+        let model = if part.is_empty() {
+            root
+        } else {
+            match self.parts.get_or_insert_with(|| load_parts(root)).get(part) {
+                Some(model) => model,
+                None => return ObjectMetrics::default(),
+            }
+        };
+        let Some(obj) = model.resources.get_object(id) else {
+            return ObjectMetrics::default();
+        };
+        match &obj.geometry {
+            Geometry::Mesh(mesh) => ObjectMetrics {
+                triangles: mesh.triangles.len() as u64,
+                vertices: mesh.vertices.len() as u64,
+                volume: mesh.compute_area_and_volume().1.abs(),
+            },
+            Geometry::PreciseMesh(mesh) => ObjectMetrics {
+                triangles: mesh.triangles.len() as u64,
+                vertices: mesh.vertices.len() as u64,
+                volume: mesh.compute_area_and_volume().1.abs(),
+            },
+            Geometry::DisplacementMesh(mesh) => ObjectMetrics {
+                triangles: mesh.triangles.len() as u64,
+                vertices: mesh.vertices.len() as u64,
+                volume: 0.0,
+            },
+            Geometry::LazyMesh(lazy) => ObjectMetrics {
+                triangles: lazy.triangle_count as u64,
+                vertices: lazy.vertex_count as u64,
+                volume: 0.0,
+            },
+            Geometry::Components(comps) => {
+                // Cloned so the cache can be borrowed mutably while walking the children.
+                let children: Vec<_> = comps
+                    .components
+                    .iter()
+                    .map(|c| (c.path.as_deref().map(normalize), c.object_id, c.transform))
+                    .collect();
+                let mut total = ObjectMetrics::default();
+                for (path, child_id, transform) in children {
+                    let child = self.get(root, path.as_deref().unwrap_or(part), child_id);
+                    total.triangles += child.triangles;
+                    total.vertices += child.vertices;
+                    total.volume += child.volume * (transform.determinant() as f64).abs();
+                }
+                total
+            }
+            Geometry::BooleanShape(_)
+            | Geometry::SliceStack(_)
+            | Geometry::VolumetricStack(_)
+            | Geometry::LevelSet(_) => ObjectMetrics::default(),
+        }
+    }
+}
+
+/// A textual object filter, e.g. `type=model && triangles>1e6 || name~bracket`.
+///
+/// Conditions are `<field><op><value>`, combined with `&&` (binds tighter) and `||`.
+///
+/// | Field | Values | Operators |
+/// |-------|--------|-----------|
+/// | `id`, `triangles`, `vertices`, `volume` | numbers (`1e6` allowed) | `=` `!=` `>` `>=` `<` `<=` |
+/// | `name`, `partnumber` | text, optionally quoted | `=` `!=` `~` (contains, ignoring case) |
+/// | `type` | `model`, `support`, `solidsupport`, `surface`, `other` | `=` `!=` |
+/// | `build` | `true`, `false` (placed by a build item) | `=` `!=` |
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectFilter {
+    /// Alternatives (`||`), each a list of conditions that must all hold (`&&`).
+    any_of: Vec<Vec<Condition>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Condition {
+    field: Field,
+    op: Op,
+    value: Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Id,
+    Triangles,
+    Vertices,
+    Volume,
+    Name,
+    PartNumber,
+    Type,
+    Build,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Contains,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Number(f64),
+    Text(String),
+    Type(ObjectType),
+    Bool(bool),
+}
+
+/// Operators in match order: two-character operators before their one-character prefixes.
+const OPERATORS: &[(&str, Op)] = &[
+    ("!=", Op::Ne),
+    (">=", Op::Ge),
+    ("<=", Op::Le),
+    ("==", Op::Eq),
+    ("=", Op::Eq),
+    (">", Op::Gt),
+    ("<", Op::Lt),
+    ("~", Op::Contains),
+];
+
+impl ObjectFilter {
+    /// Parses a filter expression. See the [type docs](Self) for the syntax.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Lib3mfError::Validation` describing the first invalid condition.
+    pub fn parse(expr: &str) -> Result<Self> {
+        let any_of = expr
+            .split("||")
+            .map(|alt| alt.split("&&").map(parse_condition).collect())
+            .collect::<Result<Vec<Vec<Condition>>>>()?;
+        Ok(Self { any_of })
+    }
+
+    /// Returns true if an object with the given metrics satisfies the filter.
+    pub fn matches(&self, obj: &Object, metrics: &ObjectMetrics, in_build: bool) -> bool {
+        self.any_of
+            .iter()
+            .any(|all| all.iter().all(|c| c.matches(obj, metrics, in_build)))
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Field::Id => "id",
+            Field::Triangles => "triangles",
+            Field::Vertices => "vertices",
+            Field::Volume => "volume",
+            Field::Name => "name",
+            Field::PartNumber => "partnumber",
+            Field::Type => "type",
+            Field::Build => "build",
+        };
+        f.write_str(name)
+    }
+}
+
+fn parse_condition(text: &str) -> Result<Condition> {
+    let invalid = |reason: String| {
+        Lib3mfError::Validation(format!(
+            "Invalid query condition '{}': {}",
+            text.trim(),
+            reason
+        ))
+    };
+
+    let (pos, symbol, op) = OPERATORS
+        .iter()
+        .filter_map(|(symbol, op)| text.find(symbol).map(|pos| (pos, *symbol, *op)))
+        .min_by_key(|(pos, _, _)| *pos)
+        .ok_or_else(|| invalid("expected <field><op><value>".to_string()))?;
+    let field_name = text[..pos].trim().to_lowercase();
+    let raw = text[pos + symbol.len()..].trim();
+    let raw = raw
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| raw.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(raw);
+
+    let field = match field_name.as_str() {
+        "id" => Field::Id,
+        "triangles" => Field::Triangles,
+        "vertices" => Field::Vertices,
+        "volume" => Field::Volume,
+        "name" => Field::Name,
+        "partnumber" => Field::PartNumber,
+        "type" => Field::Type,
+        "build" => Field::Build,
+        "" => return Err(invalid("missing field name".to_string())),
+        other => return Err(invalid(format!("unknown field '{}'", other))),
+    };
+
+    let value = match field {
+        Field::Id | Field::Triangles | Field::Vertices | Field::Volume => {
+            if op == Op::Contains {
+                return Err(invalid(format!("'~' does not apply to {}", field)));
+            }
+            Value::Number(
+                raw.parse()
+                    .map_err(|_| invalid(format!("'{}' is not a number", raw)))?,
+            )
+        }
+        Field::Name | Field::PartNumber => {
+            if !matches!(op, Op::Eq | Op::Ne | Op::Contains) {
+                return Err(invalid(format!("{} supports only =, != and ~", field)));
+            }
+            Value::Text(raw.to_string())
+        }
+        Field::Type | Field::Build => {
+            if !matches!(op, Op::Eq | Op::Ne) {
+                return Err(invalid(format!("{} supports only = and !=", field)));
+            }
+            if field == Field::Type {
+                Value::Type(
+                    raw.to_lowercase()
+                        .parse()
+                        .map_err(|_| invalid(format!("unknown object type '{}'", raw)))?,
+                )
+            } else {
+                Value::Bool(match raw.to_lowercase().as_str() {
+                    "true" | "yes" | "1" => true,
+                    "false" | "no" | "0" => false,
+                    _ => return Err(invalid(format!("'{}' is not true or false", raw))),
+                })
+            }
+        }
+    };
+    Ok(Condition { field, op, value })
+}
+
+impl Condition {
+    fn matches(&self, obj: &Object, metrics: &ObjectMetrics, in_build: bool) -> bool {
+        match &self.value {
+            Value::Number(expected) => {
+                let actual = match self.field {
+                    Field::Id => obj.id.0 as f64,
+                    Field::Triangles => metrics.triangles as f64,
+                    Field::Vertices => metrics.vertices as f64,
+                    _ => metrics.volume,
+                };
+                match self.op {
+                    Op::Eq => actual == *expected,
+                    Op::Ne => actual != *expected,
+                    Op::Gt => actual > *expected,
+                    Op::Ge => actual >= *expected,
+                    Op::Lt => actual < *expected,
+                    Op::Le => actual <= *expected,
+                    Op::Contains => false,
+                }
+            }
+            Value::Text(expected) => {
+                let actual = match self.field {
+                    Field::Name => obj.name.as_deref(),
+                    _ => obj.part_number.as_deref(),
+                }
+                .unwrap_or("");
+                match self.op {
+                    Op::Eq => actual == expected,
+                    Op::Ne => actual != expected,
+                    _ => actual.to_lowercase().contains(&expected.to_lowercase()),
+                }
+            }
+            Value::Type(expected) => (obj.object_type == *expected) == (self.op == Op::Eq),
+            Value::Bool(expected) => (in_build == *expected) == (self.op == Op::Eq),
+        }
+    }
+}
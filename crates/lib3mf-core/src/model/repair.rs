@@ -1,5 +1,60 @@
-use crate::model::Mesh;
-use std::collections::HashMap;
+use crate::model::{Geometry, Mesh, Object, ObjectType, ResolvedMesh, Unit};
+use crate::trace::{phase, span};
+use std::collections::{BTreeMap, HashMap};
+
+/// Default [`DegenerateArea::Relative`] fraction of the squared bounding-box diagonal.
+///
+/// For a 100 mm part this is about 3e-8 mm², well above the rounding noise of `f32`
+/// coordinates yet far below any printable feature.
+pub const DEFAULT_RELATIVE_DEGENERATE_AREA: f64 = 1e-12;
+
+/// Area at or below which a triangle counts as degenerate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DegenerateArea {
+    /// A fraction of the squared bounding-box diagonal of the mesh, so one setting fits
+    /// micron-scale and meter-scale models alike.
+    Relative(f64),
+    /// An area in square millimeters, converted to the unit of the mesh coordinates.
+    SquareMillimeters(f64),
+}
+
+impl Default for DegenerateArea {
+    fn default() -> Self {
+        DegenerateArea::Relative(DEFAULT_RELATIVE_DEGENERATE_AREA)
+    }
+}
+
+impl DegenerateArea {
+    /// Returns the threshold in square units of the coordinates of `mesh`, which are in `unit`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib3mf_core::model::{DegenerateArea, Mesh, Unit};
+    ///
+    /// let mut mesh = Mesh::new();
+    /// mesh.add_vertex(0.0, 0.0, 0.0);
+    /// mesh.add_vertex(3.0, 4.0, 0.0);
+    /// assert_eq!(DegenerateArea::Relative(0.01).threshold(&mesh, Unit::Meter), 0.25);
+    /// assert_eq!(DegenerateArea::SquareMillimeters(1.0).threshold(&mesh, Unit::Centimeter), 0.01);
+    /// ```
+    pub fn threshold(&self, mesh: &Mesh, unit: Unit) -> f64 {
+        match *self {
+            DegenerateArea::Relative(fraction) => mesh.compute_aabb().map_or(0.0, |aabb| {
+                let diagonal =
+                    glam::Vec3::from(aabb.max).as_dvec3() - glam::Vec3::from(aabb.min).as_dvec3();
+                fraction * diagonal.length_squared()
+            }),
+            DegenerateArea::SquareMillimeters(area) => area / square_millimeters(unit),
+        }
+    }
+}
+
+/// Square millimeters in one square `unit`.
+fn square_millimeters(unit: Unit) -> f64 {
+    let millimeters = unit.scale_factor() * 1000.0;
+    millimeters * millimeters
+}
 
 /// Configuration options for mesh repair operations.
 ///
@@ -14,10 +69,14 @@ pub struct RepairOptions {
     pub stitch_epsilon: f32,
     /// Whether to remove triangles with zero or near-zero area (default: true).
     pub remove_degenerate: bool,
+    /// Area at or below which `remove_degenerate` removes a triangle (default:
+    /// [`DEFAULT_RELATIVE_DEGENERATE_AREA`] of the squared bounding-box diagonal).
+    pub degenerate_area: DegenerateArea,
     /// Whether to remove duplicate triangles sharing the same vertices (default: true).
     pub remove_duplicate_faces: bool,
     /// Whether to harmonize triangle winding for consistent normals (default: true).
-    /// Uses BFS to propagate consistent orientation through connected components.
+    /// Uses BFS to propagate consistent orientation through connected components, then turns
+    /// closed meshes that enclose negative volume (inside out) outward.
     pub harmonize_orientations: bool,
     /// Whether to remove disconnected islands, keeping only the largest component (default: false).
     /// Useful for removing unwanted floating geometry.
@@ -25,6 +84,9 @@ pub struct RepairOptions {
     /// Whether to attempt to fill holes using simple fan triangulation (default: false).
     /// Detects boundary loops and caps them with triangles.
     pub fill_holes: bool,
+    /// Unit of the mesh coordinates (default: millimeters), for
+    /// [`DegenerateArea::SquareMillimeters`] and [`RepairStats::degenerate_areas`].
+    pub unit: Unit,
 }
 
 impl Default for RepairOptions {
@@ -32,14 +94,43 @@ impl Default for RepairOptions {
         Self {
             stitch_epsilon: 1e-4, // 0.1 microns effectively
             remove_degenerate: true,
+            degenerate_area: DegenerateArea::default(),
             remove_duplicate_faces: true,
             harmonize_orientations: true,
             remove_islands: false,
             fill_holes: false,
+            unit: Unit::Millimeter,
         }
     }
 }
 
+impl RepairOptions {
+    /// Returns these options adjusted for an object of the given type.
+    ///
+    /// Repairs that make a mesh manifold (island removal and hole filling) are turned off
+    /// for types that need not be manifold ([`ObjectType::requires_manifold`]): support
+    /// structures are often many disconnected pieces, and the boundary of a surface is
+    /// part of its shape. Clean-up repairs (stitching, degenerate and duplicate removal,
+    /// orientation) apply to every type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib3mf_core::model::{ObjectType, RepairOptions};
+    ///
+    /// let options = RepairOptions { fill_holes: true, ..Default::default() };
+    /// assert!(options.for_object_type(ObjectType::Model).fill_holes);
+    /// assert!(!options.for_object_type(ObjectType::Surface).fill_holes);
+    /// ```
+    pub fn for_object_type(mut self, object_type: ObjectType) -> Self {
+        if !object_type.requires_manifold() {
+            self.remove_islands = false;
+            self.fill_holes = false;
+        }
+        self
+    }
+}
+
 /// Trait for mesh repair operations.
 ///
 /// Provides automatic mesh repair functionality to fix common geometry issues
@@ -67,7 +158,9 @@ pub trait MeshRepair {
     /// 3. Remove unused vertices
     /// 4. Remove disconnected islands (keep largest component)
     /// 5. Fill holes (simple fan triangulation)
-    /// 6. Harmonize orientation (consistent winding)
+    /// 6. Harmonize orientation (consistent winding, inside-out closed meshes turned outward)
+    ///
+    /// Use a [`RepairPipeline`] to choose the order, repeat passes or make them conditional.
     ///
     /// # Arguments
     ///
@@ -83,7 +176,7 @@ pub trait MeshRepair {
 ///
 /// Records what changes were made during repair so users can understand
 /// what was fixed and verify the results are acceptable.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct RepairStats {
     /// Number of vertices removed (merged duplicates or unused)
     pub vertices_removed: usize,
@@ -93,48 +186,573 @@ pub struct RepairStats {
     pub triangles_flipped: usize,
     /// Number of triangles added (hole filling)
     pub triangles_added: usize,
+    /// Areas of the triangles removed as degenerate
+    pub degenerate_areas: AreaDistribution,
+}
+
+impl RepairStats {
+    fn add(&mut self, other: &RepairStats) {
+        self.vertices_removed += other.vertices_removed;
+        self.triangles_removed += other.triangles_removed;
+        self.triangles_flipped += other.triangles_flipped;
+        self.triangles_added += other.triangles_added;
+        self.degenerate_areas.merge(&other.degenerate_areas);
+    }
+
+    fn is_empty(&self) -> bool {
+        *self == RepairStats::default()
+    }
+}
+
+/// Distribution of triangle areas in square millimeters, by order of magnitude.
+///
+/// Shows whether removed degenerate triangles were slivers of zero area or small but real
+/// faces, which would suggest a lower [`DegenerateArea`] threshold.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AreaDistribution {
+    /// Triangles with a repeated vertex or an area of exactly zero.
+    pub zero: usize,
+    /// Other triangles by decade: key `k` counts areas in `[10^k, 10^(k+1))` mm².
+    pub decades: BTreeMap<i32, usize>,
+    /// Smallest nonzero area, if any.
+    pub min: Option<f64>,
+    /// Largest area, if any is nonzero.
+    pub max: Option<f64>,
+}
+
+impl AreaDistribution {
+    /// Total number of triangles recorded.
+    pub fn count(&self) -> usize {
+        self.zero + self.decades.values().sum::<usize>()
+    }
+
+    /// Records a triangle of `area` mm².
+    pub fn record(&mut self, area: f64) {
+        if area <= 0.0 {
+            self.zero += 1;
+            return;
+        }
+        *self.decades.entry(area.log10().floor() as i32).or_default() += 1;
+        self.min = Some(self.min.map_or(area, |min| min.min(area)));
+        self.max = Some(self.max.map_or(area, |max| max.max(area)));
+    }
+
+    /// Adds the triangles recorded in `other`.
+    pub fn merge(&mut self, other: &AreaDistribution) {
+        self.zero += other.zero;
+        for (&decade, &count) in &other.decades {
+            *self.decades.entry(decade).or_default() += count;
+        }
+        for area in other.min.into_iter().chain(other.max) {
+            self.min = Some(self.min.map_or(area, |min| min.min(area)));
+            self.max = Some(self.max.map_or(area, |max| max.max(area)));
+        }
+    }
 }
 
 impl MeshRepair for Mesh {
     fn repair(&mut self, options: RepairOptions) -> RepairStats {
+        let phase = phase!(
+            span::REPAIR_MESH,
+            vertices_removed = tracing::field::Empty,
+            triangles_removed = tracing::field::Empty
+        );
+        let pipeline = RepairPipeline::from(options);
         let mut stats = RepairStats::default();
+        for step in pipeline.steps() {
+            stats.add(&apply_pass(self, step.pass, pipeline.unit));
+        }
+        phase.record("vertices_removed", stats.vertices_removed);
+        phase.record("triangles_removed", stats.triangles_removed);
+        stats
+    }
+}
 
-        if options.stitch_epsilon > 0.0 {
-            let removed = stitch_vertices(self, options.stitch_epsilon);
-            stats.vertices_removed += removed;
+/// Repairs the object's mesh with options adjusted for its type, see
+/// [`RepairOptions::for_object_type`]. Objects without mesh geometry are left unchanged.
+impl MeshRepair for Object {
+    fn repair(&mut self, options: RepairOptions) -> RepairStats {
+        let options = options.for_object_type(self.object_type);
+        match &mut self.geometry {
+            Geometry::Mesh(mesh) => mesh.repair(options),
+            Geometry::PreciseMesh(mesh) => mesh.repair(options),
+            _ => RepairStats::default(),
         }
+    }
+}
+
+/// One repair operation in a [`RepairPipeline`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RepairPass {
+    /// Merges vertices closer than `epsilon`. Does nothing for an `epsilon` of 0 or less.
+    Stitch {
+        /// Distance below which vertices are merged.
+        epsilon: f32,
+    },
+    /// Removes triangles with a repeated vertex or an area at or below the threshold.
+    RemoveDegenerate {
+        /// Area at or below which a triangle is removed.
+        area: DegenerateArea,
+    },
+    /// Removes triangles using the same three vertices as an earlier triangle.
+    RemoveDuplicateFaces,
+    /// Removes vertices no triangle uses.
+    RemoveUnusedVertices,
+    /// Keeps only the largest edge-connected piece.
+    RemoveIslands,
+    /// Caps boundary loops with fan triangulation.
+    FillHoles,
+    /// Makes winding consistent, then turns closed meshes that are inside out outward.
+    HarmonizeOrientations,
+    /// Leaves the mesh unchanged. Its report records a snapshot for later conditions and
+    /// for the caller to inspect.
+    Validate,
+}
 
-        if options.remove_degenerate || options.remove_duplicate_faces {
-            let removed_tris = clean_triangles(
-                self,
-                options.remove_degenerate,
-                options.remove_duplicate_faces,
-            );
-            stats.triangles_removed += removed_tris;
+/// When a [`RepairStep`] runs, judged on the mesh as the previous steps left it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepairCondition {
+    /// Always run.
+    #[default]
+    Always,
+    /// Run if the mesh has boundary edges.
+    IfOpen,
+    /// Run if the mesh has boundary or non-manifold edges.
+    IfNotManifold,
+    /// Run if neighboring triangles are wound inconsistently.
+    IfInconsistentOrientation,
+}
+
+impl RepairCondition {
+    /// Returns whether the condition holds for a mesh with this snapshot.
+    pub fn holds(&self, snapshot: &MeshSnapshot) -> bool {
+        match self {
+            RepairCondition::Always => true,
+            RepairCondition::IfOpen => !snapshot.is_closed(),
+            RepairCondition::IfNotManifold => !snapshot.is_manifold(),
+            RepairCondition::IfInconsistentOrientation => !snapshot.is_consistently_oriented(),
         }
+    }
+}
 
-        // Always clean up unused vertices after operations if any changes occurred or if explicitly requested
-        // For now, we do it if we stitched or removed triangles, or just always for a "repair"
-        let removed_verts = remove_unused_vertices(self);
-        stats.vertices_removed += removed_verts;
+/// A [`RepairPass`] with the condition under which it runs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RepairStep {
+    /// The operation.
+    pub pass: RepairPass,
+    /// When the operation runs.
+    pub condition: RepairCondition,
+}
 
-        if options.remove_islands {
-            let removed = remove_islands(self);
-            stats.triangles_removed += removed;
+/// Topology counts of a mesh, taken before and after each step of a [`RepairPipeline`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MeshSnapshot {
+    /// Number of vertices.
+    pub vertices: usize,
+    /// Number of triangles.
+    pub triangles: usize,
+    /// Triangles with a repeated vertex or an area at or below [`DegenerateArea::default`].
+    pub degenerate_triangles: usize,
+    /// Edges used by exactly one triangle.
+    pub boundary_edges: usize,
+    /// Edges used by more than two triangles.
+    pub non_manifold_edges: usize,
+    /// Directed edges traversed by more than one triangle, where neighbors disagree on
+    /// winding (or faces are duplicated).
+    pub inconsistent_edges: usize,
+}
+
+impl MeshSnapshot {
+    /// Takes a snapshot of `mesh`.
+    pub fn of(mesh: &Mesh) -> Self {
+        let mut edges: HashMap<(u32, u32), usize> = HashMap::new();
+        let mut directed: HashMap<(u32, u32), usize> = HashMap::new();
+        let threshold = DegenerateArea::default().threshold(mesh, Unit::Millimeter);
+        let mut degenerate_triangles = 0;
+        for tri in &mesh.triangles {
+            if tri.v1 == tri.v2
+                || tri.v2 == tri.v3
+                || tri.v3 == tri.v1
+                || mesh.compute_triangle_area(tri) <= threshold
+            {
+                degenerate_triangles += 1;
+            }
+            for (a, b) in [(tri.v1, tri.v2), (tri.v2, tri.v3), (tri.v3, tri.v1)] {
+                *edges.entry(sort_unord_edge(a, b)).or_default() += 1;
+                *directed.entry((a, b)).or_default() += 1;
+            }
+        }
+        Self {
+            vertices: mesh.vertices.len(),
+            triangles: mesh.triangles.len(),
+            degenerate_triangles,
+            boundary_edges: edges.values().filter(|&&n| n == 1).count(),
+            non_manifold_edges: edges.values().filter(|&&n| n > 2).count(),
+            inconsistent_edges: directed.values().filter(|&&n| n > 1).count(),
         }
+    }
 
-        if options.fill_holes {
-            let added = fill_holes(self);
-            stats.triangles_added += added;
+    /// Returns true if every edge is shared by at least two triangles.
+    pub fn is_closed(&self) -> bool {
+        self.boundary_edges == 0
+    }
+
+    /// Returns true if every edge is shared by exactly two triangles.
+    pub fn is_manifold(&self) -> bool {
+        self.boundary_edges == 0 && self.non_manifold_edges == 0
+    }
+
+    /// Returns true if neighboring triangles agree on winding.
+    pub fn is_consistently_oriented(&self) -> bool {
+        self.inconsistent_edges == 0
+    }
+}
+
+/// What one step of a [`RepairPipeline`] did.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassReport {
+    /// The step that ran or was skipped.
+    pub step: RepairStep,
+    /// True if the step's condition did not hold, so the mesh was left unchanged.
+    pub skipped: bool,
+    /// The mesh before the step.
+    pub before: MeshSnapshot,
+    /// The mesh after the step.
+    pub after: MeshSnapshot,
+    /// Changes made by the step.
+    pub stats: RepairStats,
+}
+
+/// Result of [`RepairPipeline::run`]: one [`PassReport`] per step, in order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PipelineReport {
+    /// Reports of the steps in the order they ran.
+    pub passes: Vec<PassReport>,
+    /// Changes made by all steps together.
+    pub stats: RepairStats,
+}
+
+impl PipelineReport {
+    /// The mesh before the first step, if there were any steps.
+    pub fn initial(&self) -> Option<&MeshSnapshot> {
+        self.passes.first().map(|pass| &pass.before)
+    }
+
+    /// The mesh after the last step, if there were any steps.
+    pub fn last(&self) -> Option<&MeshSnapshot> {
+        self.passes.last().map(|pass| &pass.after)
+    }
+}
+
+/// An ordered sequence of repair passes, each with its own parameters and condition.
+///
+/// Where [`RepairOptions`] turns a fixed sequence of repairs on and off, a pipeline states
+/// the order itself, may repeat passes, and may make a pass depend on the state of the
+/// mesh when it is reached. Every step is reported with snapshots of the mesh before and
+/// after it. `RepairPipeline::from(options)` gives the sequence [`MeshRepair::repair`] runs.
+///
+/// # Examples
+///
+/// Stitch, then harmonize, then check manifoldness, then fill holes only if still open:
+///
+/// ```
+/// use lib3mf_core::model::{Mesh, RepairCondition, RepairPass, RepairPipeline};
+///
+/// let mut mesh = Mesh::new();
+/// mesh.add_vertex(0.0, 0.0, 0.0);
+/// mesh.add_vertex(1.0, 0.0, 0.0);
+/// mesh.add_vertex(0.0, 1.0, 0.0);
+/// mesh.add_triangle(0, 1, 2);
+///
+/// let pipeline = RepairPipeline::new()
+///     .then(RepairPass::Stitch { epsilon: 1e-4 })
+///     .then(RepairPass::HarmonizeOrientations)
+///     .then(RepairPass::Validate)
+///     .then_if(RepairCondition::IfOpen, RepairPass::FillHoles);
+/// let report = pipeline.run(&mut mesh);
+///
+/// assert!(!report.passes[2].after.is_manifold());
+/// assert!(!report.passes[3].skipped);
+/// assert!(report.last().unwrap().is_closed());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RepairPipeline {
+    steps: Vec<RepairStep>,
+    unit: Unit,
+}
+
+impl RepairPipeline {
+    /// Creates a pipeline without steps.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a step that always runs.
+    pub fn then(self, pass: RepairPass) -> Self {
+        self.then_if(RepairCondition::Always, pass)
+    }
+
+    /// Appends a step that runs only if `condition` holds when it is reached.
+    pub fn then_if(mut self, condition: RepairCondition, pass: RepairPass) -> Self {
+        self.steps.push(RepairStep { pass, condition });
+        self
+    }
+
+    /// Sets the unit of the mesh coordinates (default: millimeters), for
+    /// [`DegenerateArea::SquareMillimeters`] and [`RepairStats::degenerate_areas`].
+    pub fn in_unit(mut self, unit: Unit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// The steps in order.
+    pub fn steps(&self) -> &[RepairStep] {
+        &self.steps
+    }
+
+    /// Returns this pipeline without the steps that make a mesh manifold (island removal
+    /// and hole filling) if `object_type` need not be manifold, as
+    /// [`RepairOptions::for_object_type`] does.
+    pub fn for_object_type(mut self, object_type: ObjectType) -> Self {
+        if !object_type.requires_manifold() {
+            self.steps.retain(|step| {
+                !matches!(step.pass, RepairPass::RemoveIslands | RepairPass::FillHoles)
+            });
+        }
+        self
+    }
+
+    /// Runs the steps on `mesh` in order.
+    ///
+    /// Stored normals are dropped once a step changes the mesh, as they follow the old
+    /// triangles.
+    pub fn run(&self, mesh: &mut Mesh) -> PipelineReport {
+        let mut report = PipelineReport::default();
+        let mut snapshot = MeshSnapshot::of(mesh);
+        for step in &self.steps {
+            let before = snapshot;
+            let skipped = !step.condition.holds(&before);
+            let stats = if skipped {
+                RepairStats::default()
+            } else {
+                apply_pass(mesh, step.pass, self.unit)
+            };
+            if !stats.is_empty() {
+                snapshot = MeshSnapshot::of(mesh);
+            }
+            report.stats.add(&stats);
+            report.passes.push(PassReport {
+                step: *step,
+                skipped,
+                before,
+                after: snapshot,
+                stats,
+            });
         }
+        report
+    }
+}
 
+impl From<RepairOptions> for RepairPipeline {
+    fn from(options: RepairOptions) -> Self {
+        let mut pipeline = RepairPipeline::new().in_unit(options.unit);
+        if options.stitch_epsilon > 0.0 {
+            pipeline = pipeline.then(RepairPass::Stitch {
+                epsilon: options.stitch_epsilon,
+            });
+        }
+        if options.remove_degenerate {
+            pipeline = pipeline.then(RepairPass::RemoveDegenerate {
+                area: options.degenerate_area,
+            });
+        }
+        if options.remove_duplicate_faces {
+            pipeline = pipeline.then(RepairPass::RemoveDuplicateFaces);
+        }
+        pipeline = pipeline.then(RepairPass::RemoveUnusedVertices);
+        if options.remove_islands {
+            pipeline = pipeline.then(RepairPass::RemoveIslands);
+        }
+        if options.fill_holes {
+            pipeline = pipeline.then(RepairPass::FillHoles);
+        }
         if options.harmonize_orientations {
-            let flipped = harmonize_orientations(self);
-            stats.triangles_flipped += flipped;
+            pipeline = pipeline.then(RepairPass::HarmonizeOrientations);
         }
+        pipeline
+    }
+}
 
-        stats
+/// Applies one pass, dropping stored normals if it changed the mesh.
+fn apply_pass(mesh: &mut Mesh, pass: RepairPass, unit: Unit) -> RepairStats {
+    let mut stats = RepairStats::default();
+    match pass {
+        RepairPass::Stitch { epsilon } => {
+            if epsilon > 0.0 {
+                stats.vertices_removed = stitch_vertices(mesh, epsilon);
+            }
+        }
+        RepairPass::RemoveDegenerate { area } => {
+            let degenerate = Degenerate {
+                threshold: area.threshold(mesh, unit),
+                to_square_mm: square_millimeters(unit),
+                areas: &mut stats.degenerate_areas,
+            };
+            stats.triangles_removed = clean_triangles(mesh, Some(degenerate), false)
+        }
+        RepairPass::RemoveDuplicateFaces => {
+            stats.triangles_removed = clean_triangles(mesh, None, true)
+        }
+        RepairPass::RemoveUnusedVertices => stats.vertices_removed = remove_unused_vertices(mesh),
+        RepairPass::RemoveIslands => stats.triangles_removed = remove_islands(mesh),
+        RepairPass::FillHoles => stats.triangles_added = fill_holes(mesh),
+        RepairPass::HarmonizeOrientations => {
+            let flipped = harmonize_orientations(mesh);
+            // Triangles flipped by harmonizing are back as they were after turning outward
+            stats.triangles_flipped = if turn_outward(mesh) {
+                mesh.triangles.len() - flipped
+            } else {
+                flipped
+            };
+        }
+        RepairPass::Validate => {}
+    }
+    // Stored normals follow the old triangles
+    if !stats.is_empty() {
+        mesh.normals = None;
+    }
+    stats
+}
+
+/// Flattens resolved instances into one world-space mesh with consistent winding.
+///
+/// Each instance is placed with [`ResolvedMesh::world_mesh`], which reverses the winding
+/// of mirrored instances, and the results are concatenated and repaired with `options`.
+/// Stitching joins instances that share a boundary, so orientation harmonization then
+/// works across instances rather than within each one. Finally, every closed connected
+/// piece whose triangles face inward (negative signed volume) is flipped as a whole.
+///
+/// Only vertices and triangles are carried over; triangle property indices are kept as
+/// they are in the source parts.
+///
+/// # Examples
+///
+/// ```
+/// use lib3mf_core::model::{Mesh, ObjectType, ResolvedMesh, Unit};
+/// use lib3mf_core::model::repair::{RepairOptions, flatten_instances};
+///
+/// let mut mesh = Mesh::new();
+/// mesh.add_vertex(0.0, 0.0, 0.0);
+/// mesh.add_vertex(1.0, 0.0, 0.0);
+/// mesh.add_vertex(0.0, 1.0, 0.0);
+/// mesh.add_triangle(0, 1, 2);
+/// let instance = |transform| ResolvedMesh {
+///     mesh: mesh.clone(),
+///     transform,
+///     object_type: ObjectType::Model,
+///     name: None,
+///     unit: Unit::Millimeter,
+/// };
+///
+/// let mirror = glam::Mat4::from_scale(glam::Vec3::new(-1.0, 1.0, 1.0));
+/// let (flat, _) = flatten_instances(
+///     &[instance(glam::Mat4::IDENTITY), instance(mirror)],
+///     RepairOptions::default(),
+/// );
+/// assert_eq!(flat.triangles.len(), 2);
+/// ```
+pub fn flatten_instances(
+    instances: &[ResolvedMesh],
+    options: RepairOptions,
+) -> (Mesh, RepairStats) {
+    let mut flat = Mesh::new();
+    for instance in instances {
+        let world = instance.world_mesh();
+        let offset = flat.vertices.len() as u32;
+        flat.vertices.extend(world.vertices);
+        flat.triangles
+            .extend(world.triangles.into_iter().map(|mut tri| {
+                tri.v1 += offset;
+                tri.v2 += offset;
+                tri.v3 += offset;
+                tri
+            }));
+    }
+
+    let mut stats = flat.repair(RepairOptions {
+        harmonize_orientations: false,
+        ..options
+    });
+    if options.harmonize_orientations {
+        stats.triangles_flipped += harmonize_orientations(&mut flat);
+        stats.triangles_flipped += orient_outward(&mut flat);
+    }
+    (flat, stats)
+}
+
+/// Flips every closed edge-connected piece with a negative signed volume.
+fn orient_outward(mesh: &mut Mesh) -> usize {
+    let mut edge_to_tris: HashMap<(u32, u32), Vec<usize>> = HashMap::new();
+    for (i, tri) in mesh.triangles.iter().enumerate() {
+        for e in [
+            sort_unord_edge(tri.v1, tri.v2),
+            sort_unord_edge(tri.v2, tri.v3),
+            sort_unord_edge(tri.v3, tri.v1),
+        ] {
+            edge_to_tris.entry(e).or_default().push(i);
+        }
     }
+
+    let mut flipped = 0;
+    let mut visited = vec![false; mesh.triangles.len()];
+    for start_idx in 0..mesh.triangles.len() {
+        if visited[start_idx] {
+            continue;
+        }
+
+        let mut piece = Vec::new();
+        let mut closed = true;
+        let mut stack = vec![start_idx];
+        visited[start_idx] = true;
+        while let Some(curr_idx) = stack.pop() {
+            piece.push(curr_idx);
+            let tri = mesh.triangles[curr_idx];
+            for e in [
+                sort_unord_edge(tri.v1, tri.v2),
+                sort_unord_edge(tri.v2, tri.v3),
+                sort_unord_edge(tri.v3, tri.v1),
+            ] {
+                let neighbors = &edge_to_tris[&e];
+                closed &= neighbors.len() == 2;
+                for &neigh_idx in neighbors {
+                    if !visited[neigh_idx] {
+                        visited[neigh_idx] = true;
+                        stack.push(neigh_idx);
+                    }
+                }
+            }
+        }
+
+        let volume: f64 = piece
+            .iter()
+            .map(|&i| {
+                let tri = &mesh.triangles[i];
+                let v1 = glam::DVec3::from(glam::Vec3::from(mesh.vertices[tri.v1 as usize]));
+                let v2 = glam::DVec3::from(glam::Vec3::from(mesh.vertices[tri.v2 as usize]));
+                let v3 = glam::DVec3::from(glam::Vec3::from(mesh.vertices[tri.v3 as usize]));
+                v1.dot(v2.cross(v3))
+            })
+            .sum();
+        if closed && volume < 0.0 {
+            for &i in &piece {
+                let tri = &mut mesh.triangles[i];
+                std::mem::swap(&mut tri.v2, &mut tri.v3);
+                std::mem::swap(&mut tri.p2, &mut tri.p3);
+            }
+            flipped += piece.len();
+        }
+    }
+    flipped
 }
 
 fn remove_islands(mesh: &mut Mesh) -> usize {
@@ -200,14 +818,32 @@ fn remove_islands(mesh: &mut Mesh) -> usize {
     let initial_count = mesh.triangles.len();
     let largest_comp = &component_tris[0];
     let mut new_triangles = Vec::with_capacity(largest_comp.len());
+    let mut remap = vec![None; initial_count];
     for &idx in largest_comp {
+        remap[idx] = Some(new_triangles.len() as u32);
         new_triangles.push(mesh.triangles[idx]);
     }
 
-    mesh.triangles = new_triangles;
+    mesh.triangles = new_triangles.into();
+    remap_triangle_data(mesh, &remap);
     initial_count - mesh.triangles.len()
 }
 
+/// Updates triangle sets and attributes after triangles were removed or reordered.
+///
+/// `remap[old]` is the new index of triangle `old`, or `None` if it was removed.
+fn remap_triangle_data(mesh: &mut Mesh, remap: &[Option<u32>]) {
+    mesh.triangle_attributes.remap(remap);
+    for set in &mut mesh.triangle_sets {
+        set.triangles = set
+            .triangles
+            .iter()
+            .filter_map(|&t| remap.get(t as usize).copied().flatten())
+            .collect();
+        set.triangles.sort_unstable();
+    }
+}
+
 fn sort_unord_edge(v1: u32, v2: u32) -> (u32, u32) {
     if v1 < v2 { (v1, v2) } else { (v2, v1) }
 }
@@ -371,6 +1007,33 @@ fn harmonize_orientations(mesh: &mut Mesh) -> usize {
     flipped_count
 }
 
+/// Flips every triangle of a closed mesh that encloses negative volume (inside out).
+///
+/// Returns whether the mesh was flipped. Unlike [`orient_outward`] this looks at the mesh
+/// as a whole, so inward-facing cavity shells of a hollow solid are left alone.
+fn turn_outward(mesh: &mut Mesh) -> bool {
+    let mut edge_counts: HashMap<(u32, u32), usize> = HashMap::new();
+    for tri in &mesh.triangles {
+        for e in [
+            sort_unord_edge(tri.v1, tri.v2),
+            sort_unord_edge(tri.v2, tri.v3),
+            sort_unord_edge(tri.v3, tri.v1),
+        ] {
+            *edge_counts.entry(e).or_insert(0) += 1;
+        }
+    }
+    let closed = !edge_counts.is_empty() && edge_counts.values().all(|&count| count == 2);
+    if !closed || mesh.compute_area_and_volume().1 >= 0.0 {
+        return false;
+    }
+
+    for tri in &mut mesh.triangles {
+        std::mem::swap(&mut tri.v2, &mut tri.v3);
+        std::mem::swap(&mut tri.p2, &mut tri.p3);
+    }
+    true
+}
+
 fn remove_unused_vertices(mesh: &mut Mesh) -> usize {
     let initial_count = mesh.vertices.len();
     if initial_count == 0 {
@@ -399,7 +1062,7 @@ fn remove_unused_vertices(mesh: &mut Mesh) -> usize {
     let removed = initial_count - new_vertices.len();
 
     // 3. Update mesh
-    mesh.vertices = new_vertices;
+    mesh.vertices = new_vertices.into();
     for tri in &mut mesh.triangles {
         tri.v1 = old_to_new[tri.v1 as usize];
         tri.v2 = old_to_new[tri.v2 as usize];
@@ -447,7 +1110,7 @@ fn stitch_vertices(mesh: &mut Mesh, epsilon: f32) -> usize {
     let merged_count = initial_count - new_vertices.len();
 
     // Update mesh
-    mesh.vertices = new_vertices;
+    mesh.vertices = new_vertices.into();
 
     // Remap triangles
     for tri in &mut mesh.triangles {
@@ -459,23 +1122,41 @@ fn stitch_vertices(mesh: &mut Mesh, epsilon: f32) -> usize {
     merged_count
 }
 
-fn clean_triangles(mesh: &mut Mesh, remove_degenerate: bool, remove_duplicates: bool) -> usize {
+/// Degenerate triangle removal for [`clean_triangles`].
+struct Degenerate<'a> {
+    /// Area at or below which a triangle is removed, in square coordinate units.
+    threshold: f64,
+    /// Square millimeters per square coordinate unit.
+    to_square_mm: f64,
+    /// Records the areas of removed triangles.
+    areas: &'a mut AreaDistribution,
+}
+
+fn clean_triangles(
+    mesh: &mut Mesh,
+    mut degenerate: Option<Degenerate<'_>>,
+    remove_duplicates: bool,
+) -> usize {
     let initial_count = mesh.triangles.len();
     let mut valid_triangles = Vec::with_capacity(initial_count);
 
     // For duplicate check
     let mut seen_faces = std::collections::HashSet::new();
 
-    for tri in &mesh.triangles {
-        // 1. Check Index degeneracy
-        if (tri.v1 == tri.v2 || tri.v2 == tri.v3 || tri.v3 == tri.v1) && remove_degenerate {
-            continue;
-        }
+    let mut remap = vec![None; initial_count];
+
+    for (i, tri) in mesh.triangles.iter().enumerate() {
+        if let Some(degenerate) = &mut degenerate {
+            // 1. Check Index degeneracy
+            if tri.v1 == tri.v2 || tri.v2 == tri.v3 || tri.v3 == tri.v1 {
+                degenerate.areas.record(0.0);
+                continue;
+            }
 
-        // 2. Check Area degeneracy
-        if remove_degenerate {
+            // 2. Check Area degeneracy
             let area = mesh.compute_triangle_area(tri);
-            if area <= 1e-9 {
+            if area <= degenerate.threshold {
+                degenerate.areas.record(area * degenerate.to_square_mm);
                 continue;
             }
         }
@@ -492,10 +1173,14 @@ fn clean_triangles(mesh: &mut Mesh, remove_degenerate: bool, remove_duplicates:
             }
         }
 
+        remap[i] = Some(valid_triangles.len() as u32);
         valid_triangles.push(*tri);
     }
 
     let removed = initial_count - valid_triangles.len();
-    mesh.triangles = valid_triangles;
+    mesh.triangles = valid_triangles.into();
+    if removed > 0 {
+        remap_triangle_data(mesh, &remap);
+    }
     removed
 }
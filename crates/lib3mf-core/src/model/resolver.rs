@@ -1,25 +1,35 @@
-use crate::archive::ArchiveReader;
+use crate::archive::{ArchiveReader, normalize_part_name};
 use crate::error::{Lib3mfError, Result};
-use crate::model::{Geometry, Mesh, Model, Object, ObjectType, ResourceId, Unit};
-use crate::parser::model_parser::parse_model;
+use crate::model::{BuildItem, Geometry, Mesh, Model, Object, ObjectType, ResourceId, Unit};
+use crate::parser::model_parser::{ParseOptions, parse_model_with_options};
 use std::collections::HashMap;
 use std::io::Cursor;
 
-const ROOT_PATH: &str = "ROOT";
+pub(crate) const ROOT_PATH: &str = "ROOT";
 const MAIN_MODEL_PART: &str = "3D/3dmodel.model";
 
 /// Resolves resources across multiple model parts in a 3MF package.
 pub struct PartResolver<'a, A: ArchiveReader> {
     archive: &'a mut A,
     models: HashMap<String, Model>,
+    options: ParseOptions,
 }
 
 impl<'a, A: ArchiveReader> PartResolver<'a, A> {
     /// Creates a new `PartResolver` with the given archive and root model.
     pub fn new(archive: &'a mut A, root_model: Model) -> Self {
+        Self::with_options(archive, root_model, ParseOptions::default())
+    }
+
+    /// Like [`new`](Self::new), parsing the other model parts with `options`.
+    pub fn with_options(archive: &'a mut A, root_model: Model, options: ParseOptions) -> Self {
         let mut models = HashMap::new();
         models.insert(ROOT_PATH.to_string(), root_model);
-        Self { archive, models }
+        Self {
+            archive,
+            models,
+            options,
+        }
     }
 
     /// Resolves an object by ID and optional model part path.
@@ -53,29 +63,19 @@ impl<'a, A: ArchiveReader> PartResolver<'a, A> {
     }
 
     fn get_or_load_model(&mut self, path: Option<&str>) -> Result<&Model> {
-        let part_path = match path {
-            Some(p) => {
-                let p = p.trim_start_matches('/');
-                if p.is_empty() || p.eq_ignore_ascii_case(MAIN_MODEL_PART) {
-                    ROOT_PATH
-                } else {
-                    p
-                }
-            }
-            None => ROOT_PATH,
-        };
+        let part_path = canonical_path(path);
 
-        if !self.models.contains_key(part_path) {
-            let data = self.archive.read_entry(part_path).or_else(|_| {
+        if !self.models.contains_key(&part_path) {
+            let data = self.archive.read_entry(&part_path).or_else(|_| {
                 let alt = format!("/{}", part_path);
                 self.archive.read_entry(&alt)
             })?;
 
-            let model = parse_model(Cursor::new(data))?;
-            self.models.insert(part_path.to_string(), model);
+            let model = parse_model_with_options(Cursor::new(data), &self.options)?;
+            self.models.insert(part_path.clone(), model);
         }
 
-        Ok(self.models.get(part_path).unwrap())
+        Ok(self.models.get(&part_path).unwrap())
     }
 
     /// Returns a reference to the root model.
@@ -118,27 +118,43 @@ impl<'a, A: ArchiveReader> PartResolver<'a, A> {
         let build_items = self.get_root_model().build.items.clone();
 
         let mut out = Vec::new();
-        let mut ancestry: Vec<(u32, String)> = Vec::new();
-
         for item in &build_items {
             if options.filter_non_printable && item.printable == Some(false) {
                 continue;
             }
-
-            resolve_recursive(
-                item.object_id,
-                item.path.as_deref(),
-                item.transform,
-                0,
-                &mut ancestry,
-                options,
-                self,
-                &mut out,
-            )?;
+            out.extend(self.resolve_item(item, options)?);
         }
 
         Ok(out)
     }
+
+    /// Resolves the meshes placed by a single build item of the root model.
+    ///
+    /// Like [`resolve_meshes`](Self::resolve_meshes) for one item, e.g. to export each
+    /// object of the build on its own. `options.filter_non_printable` is not applied, as
+    /// the caller chose the item.
+    ///
+    /// # Errors
+    ///
+    /// As for [`resolve_meshes`](Self::resolve_meshes).
+    pub fn resolve_item(
+        &mut self,
+        item: &BuildItem,
+        options: &ResolveOptions,
+    ) -> Result<Vec<ResolvedMesh>> {
+        let mut out = Vec::new();
+        resolve_recursive(
+            item.object_id,
+            item.path.as_deref(),
+            item.transform,
+            0,
+            &mut Vec::new(),
+            options,
+            self,
+            &mut out,
+        )?;
+        Ok(out)
+    }
 }
 
 /// A single resolved mesh instance with its accumulated world transform.
@@ -171,6 +187,38 @@ pub struct ResolvedMesh {
     pub unit: Unit,
 }
 
+impl ResolvedMesh {
+    /// Returns true if the accumulated transform mirrors the mesh (negative determinant).
+    ///
+    /// A mirrored instance turns inside out when its vertices are transformed unless the
+    /// winding of every triangle is reversed as well; [`world_mesh`](Self::world_mesh)
+    /// does this.
+    pub fn is_mirrored(&self) -> bool {
+        self.transform.determinant() < 0.0
+    }
+
+    /// Returns a copy of the mesh with the transform applied to its vertices.
+    ///
+    /// Triangle winding (and per-vertex property indices) are reversed for mirrored
+    /// instances so that faces keep pointing outward. Units are not converted.
+    pub fn world_mesh(&self) -> Mesh {
+        let mut mesh = self.mesh.clone();
+        for vertex in &mut mesh.vertices {
+            *vertex = self
+                .transform
+                .transform_point3(glam::Vec3::from(*vertex))
+                .into();
+        }
+        if self.is_mirrored() {
+            for triangle in &mut mesh.triangles {
+                std::mem::swap(&mut triangle.v2, &mut triangle.v3);
+                std::mem::swap(&mut triangle.p2, &mut triangle.p3);
+            }
+        }
+        mesh
+    }
+}
+
 /// Options controlling the behavior of [`PartResolver::resolve_meshes`].
 #[derive(Debug, Clone)]
 pub struct ResolveOptions {
@@ -199,20 +247,20 @@ impl Default for ResolveOptions {
     }
 }
 
-/// Normalizes a component path to a canonical string for cycle detection and path inheritance.
+/// Normalizes a part path to the key used for loaded parts, cycle detection and path
+/// inheritance.
 ///
-/// Mirrors the normalization logic in `PartResolver::get_or_load_model()` (lines 52–62).
 /// - `None`, `"ROOT"`, `"3D/3dmodel.model"`, and `"/3D/3dmodel.model"` all map to `"ROOT"`.
-/// - All other paths have leading `/` stripped.
-fn canonical_path(path: Option<&str>) -> String {
+/// - All other paths are normalized with [`normalize_part_name`].
+pub(crate) fn canonical_path(path: Option<&str>) -> String {
     match path {
         None | Some(ROOT_PATH) => ROOT_PATH.to_string(),
         Some(p) => {
-            let p = p.trim_start_matches('/');
+            let p = normalize_part_name(p);
             if p.is_empty() || p.eq_ignore_ascii_case(MAIN_MODEL_PART) {
                 ROOT_PATH.to_string()
             } else {
-                p.to_string()
+                p
             }
         }
     }
@@ -344,7 +392,7 @@ fn resolve_recursive(
     Ok(())
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "writer"))]
 mod tests {
     use super::*;
     use crate::model::{
@@ -497,6 +545,7 @@ mod tests {
             part_number: None,
             transform: glam::Mat4::IDENTITY,
             printable: None,
+            metadata: Default::default(),
         }
     }
 
@@ -509,6 +558,7 @@ mod tests {
             part_number: None,
             transform,
             printable: None,
+            metadata: Default::default(),
         }
     }
 
@@ -521,6 +571,7 @@ mod tests {
             part_number: None,
             transform: glam::Mat4::IDENTITY,
             printable,
+            metadata: Default::default(),
         }
     }
 
@@ -638,6 +689,43 @@ mod tests {
         assert_eq!(meshes.len(), 2);
     }
 
+    #[test]
+    fn test_resolve_item() {
+        // Object id=2: assembly of two meshes (ids 1 and 3); build items for 2 and 3
+        let mut model = Model::default();
+        model
+            .resources
+            .add_object(mesh_object(1, ObjectType::Model, None))
+            .unwrap();
+        model
+            .resources
+            .add_object(mesh_object(3, ObjectType::Model, Some("loose")))
+            .unwrap();
+        model
+            .resources
+            .add_object(components_object(2, vec![component(1), component(3)]))
+            .unwrap();
+        model.build.items.push(build_item(2));
+        model.build.items.push(build_item_printable(3, Some(false)));
+
+        let mut archive = MockArchive::new();
+        let mut resolver = PartResolver::new(&mut archive, model.clone());
+        let options = ResolveOptions::default();
+        assert_eq!(
+            resolver
+                .resolve_item(&model.build.items[0], &options)
+                .unwrap()
+                .len(),
+            2
+        );
+        // The caller picked the item, so it resolves even though it is not printable
+        let meshes = resolver
+            .resolve_item(&model.build.items[1], &options)
+            .unwrap();
+        assert_eq!(meshes.len(), 1);
+        assert_eq!(meshes[0].name.as_deref(), Some("loose"));
+    }
+
     #[test]
     fn test_resolve_cycle_detection() {
         // Object id=1: Components referencing id=2
@@ -746,8 +834,10 @@ mod tests {
     fn test_resolve_unit_carried() {
         // Model with unit=Inch containing a mesh.
         // Assert: ResolvedMesh.unit == Unit::Inch.
-        let mut model = Model::default();
-        model.unit = Unit::Inch;
+        let mut model = Model {
+            unit: Unit::Inch,
+            ..Default::default()
+        };
         let obj1 = mesh_object(1, ObjectType::Model, None);
         model.resources.add_object(obj1).unwrap();
         model.build.items.push(build_item(1));
@@ -1,10 +1,11 @@
 use crate::error::{Lib3mfError, Result};
 use crate::model::{
-    BaseMaterialsGroup, ColorGroup, CompositeMaterials, Displacement2D, KeyStore, MultiProperties,
-    Object, SliceStack, Texture2D, Texture2DGroup, VolumetricStack,
+    BaseMaterialsGroup, ColorGroup, CompositeMaterials, Displacement2D, Function, Geometry,
+    Image3D, KeyStore, Model, MultiProperties, Object, SliceStack, Texture2D, Texture2DGroup,
+    VolumeData, VolumetricStack,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// Unique identifier for a resource within the model.
 ///
@@ -21,7 +22,9 @@ use std::collections::HashMap;
 /// let id = ResourceId(42);
 /// assert_eq!(id.0, 42);
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
 pub struct ResourceId(pub u32);
 
 /// Central registry for all resources in a 3MF model.
@@ -31,8 +34,8 @@ pub struct ResourceId(pub u32);
 /// ID namespace requirement: each [`ResourceId`] can only be used once across
 /// all resource types.
 ///
-/// Resources are stored in separate `HashMap<ResourceId, T>` collections internally,
-/// allowing efficient lookup by ID.
+/// Resources are stored in separate `BTreeMap<ResourceId, T>` collections internally,
+/// allowing lookup by ID and iteration (and therefore writing) in ascending ID order.
 ///
 /// # Examples
 ///
@@ -58,16 +61,22 @@ pub struct ResourceId(pub u32);
 /// ```
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ResourceCollection {
-    objects: HashMap<ResourceId, Object>,
-    base_materials: HashMap<ResourceId, BaseMaterialsGroup>,
-    color_groups: HashMap<ResourceId, ColorGroup>,
-    slice_stacks: HashMap<ResourceId, SliceStack>,
-    volumetric_stacks: HashMap<ResourceId, VolumetricStack>,
-    texture_2d: HashMap<ResourceId, Texture2D>,
-    texture_2d_groups: HashMap<ResourceId, Texture2DGroup>,
-    composite_materials: HashMap<ResourceId, CompositeMaterials>,
-    multi_properties: HashMap<ResourceId, MultiProperties>,
-    displacement_2d: HashMap<ResourceId, Displacement2D>,
+    objects: BTreeMap<ResourceId, Object>,
+    base_materials: BTreeMap<ResourceId, BaseMaterialsGroup>,
+    color_groups: BTreeMap<ResourceId, ColorGroup>,
+    slice_stacks: BTreeMap<ResourceId, SliceStack>,
+    volumetric_stacks: BTreeMap<ResourceId, VolumetricStack>,
+    texture_2d: BTreeMap<ResourceId, Texture2D>,
+    texture_2d_groups: BTreeMap<ResourceId, Texture2DGroup>,
+    composite_materials: BTreeMap<ResourceId, CompositeMaterials>,
+    multi_properties: BTreeMap<ResourceId, MultiProperties>,
+    displacement_2d: BTreeMap<ResourceId, Displacement2D>,
+    #[serde(default)]
+    images_3d: BTreeMap<ResourceId, Image3D>,
+    #[serde(default)]
+    functions: BTreeMap<ResourceId, Function>,
+    #[serde(default)]
+    volume_data: BTreeMap<ResourceId, VolumeData>,
     /// Optional Secure Content key store for this model (at most one per model).
     pub key_store: Option<KeyStore>, // Usually one KeyStore per model/part
 }
@@ -92,6 +101,9 @@ impl ResourceCollection {
             || self.composite_materials.contains_key(&id)
             || self.multi_properties.contains_key(&id)
             || self.displacement_2d.contains_key(&id)
+            || self.images_3d.contains_key(&id)
+            || self.functions.contains_key(&id)
+            || self.volume_data.contains_key(&id)
     }
 
     /// Adds an object to the collection.
@@ -186,6 +198,161 @@ impl ResourceCollection {
         self.objects.get(&id)
     }
 
+    /// Removes an object and returns it.
+    ///
+    /// Returns `None` if no object with the given ID exists. Build items and components
+    /// that reference the object are not updated.
+    pub fn remove_object(&mut self, id: ResourceId) -> Option<Object> {
+        self.objects.remove(&id)
+    }
+
+    /// Removes the resource with the given ID, whatever its type.
+    ///
+    /// Returns `false` if no resource with the given ID exists. References to the resource
+    /// are not updated.
+    pub fn remove(&mut self, id: ResourceId) -> bool {
+        self.objects.remove(&id).is_some()
+            || self.base_materials.remove(&id).is_some()
+            || self.color_groups.remove(&id).is_some()
+            || self.slice_stacks.remove(&id).is_some()
+            || self.volumetric_stacks.remove(&id).is_some()
+            || self.texture_2d.remove(&id).is_some()
+            || self.texture_2d_groups.remove(&id).is_some()
+            || self.composite_materials.remove(&id).is_some()
+            || self.multi_properties.remove(&id).is_some()
+            || self.displacement_2d.remove(&id).is_some()
+            || self.images_3d.remove(&id).is_some()
+            || self.functions.remove(&id).is_some()
+            || self.volume_data.remove(&id).is_some()
+    }
+
+    /// Returns an iterator over the IDs of all resources, of every type.
+    pub fn iter_ids(&self) -> impl Iterator<Item = ResourceId> + '_ {
+        self.objects
+            .keys()
+            .chain(self.base_materials.keys())
+            .chain(self.color_groups.keys())
+            .chain(self.slice_stacks.keys())
+            .chain(self.volumetric_stacks.keys())
+            .chain(self.texture_2d.keys())
+            .chain(self.texture_2d_groups.keys())
+            .chain(self.composite_materials.keys())
+            .chain(self.multi_properties.keys())
+            .chain(self.displacement_2d.keys())
+            .chain(self.images_3d.keys())
+            .chain(self.functions.keys())
+            .chain(self.volume_data.keys())
+            .copied()
+    }
+
+    /// Returns an iterator over all resources, of every type.
+    ///
+    /// Resources are yielded grouped by [`ResourceKind`], in ascending ID order within each
+    /// kind, matching [`iter_ids`](Self::iter_ids).
+    pub fn iter_all(&self) -> impl Iterator<Item = ResourceRef<'_>> + '_ {
+        self.objects
+            .values()
+            .map(ResourceRef::Object)
+            .chain(self.base_materials.values().map(ResourceRef::BaseMaterials))
+            .chain(self.color_groups.values().map(ResourceRef::ColorGroup))
+            .chain(self.slice_stacks.values().map(ResourceRef::SliceStack))
+            .chain(
+                self.volumetric_stacks
+                    .values()
+                    .map(ResourceRef::VolumetricStack),
+            )
+            .chain(self.texture_2d.values().map(ResourceRef::Texture2D))
+            .chain(
+                self.texture_2d_groups
+                    .values()
+                    .map(ResourceRef::Texture2DGroup),
+            )
+            .chain(
+                self.composite_materials
+                    .values()
+                    .map(ResourceRef::CompositeMaterials),
+            )
+            .chain(
+                self.multi_properties
+                    .values()
+                    .map(ResourceRef::MultiProperties),
+            )
+            .chain(
+                self.displacement_2d
+                    .values()
+                    .map(ResourceRef::Displacement2D),
+            )
+            .chain(self.images_3d.values().map(ResourceRef::Image3D))
+            .chain(self.functions.values().map(ResourceRef::Function))
+            .chain(self.volume_data.values().map(ResourceRef::VolumeData))
+    }
+
+    /// Retrieves the resource with the given ID, whatever its type.
+    ///
+    /// Returns `None` if no resource with the given ID exists.
+    pub fn get(&self, id: ResourceId) -> Option<ResourceRef<'_>> {
+        self.objects
+            .get(&id)
+            .map(ResourceRef::Object)
+            .or_else(|| self.base_materials.get(&id).map(ResourceRef::BaseMaterials))
+            .or_else(|| self.color_groups.get(&id).map(ResourceRef::ColorGroup))
+            .or_else(|| self.slice_stacks.get(&id).map(ResourceRef::SliceStack))
+            .or_else(|| {
+                self.volumetric_stacks
+                    .get(&id)
+                    .map(ResourceRef::VolumetricStack)
+            })
+            .or_else(|| self.texture_2d.get(&id).map(ResourceRef::Texture2D))
+            .or_else(|| {
+                self.texture_2d_groups
+                    .get(&id)
+                    .map(ResourceRef::Texture2DGroup)
+            })
+            .or_else(|| {
+                self.composite_materials
+                    .get(&id)
+                    .map(ResourceRef::CompositeMaterials)
+            })
+            .or_else(|| {
+                self.multi_properties
+                    .get(&id)
+                    .map(ResourceRef::MultiProperties)
+            })
+            .or_else(|| {
+                self.displacement_2d
+                    .get(&id)
+                    .map(ResourceRef::Displacement2D)
+            })
+            .or_else(|| self.images_3d.get(&id).map(ResourceRef::Image3D))
+            .or_else(|| self.functions.get(&id).map(ResourceRef::Function))
+            .or_else(|| self.volume_data.get(&id).map(ResourceRef::VolumeData))
+    }
+
+    /// Returns the number of resources of each kind. Kinds without resources are omitted.
+    pub fn counts(&self) -> BTreeMap<ResourceKind, usize> {
+        [
+            (ResourceKind::Object, self.objects.len()),
+            (ResourceKind::BaseMaterials, self.base_materials.len()),
+            (ResourceKind::ColorGroup, self.color_groups.len()),
+            (ResourceKind::SliceStack, self.slice_stacks.len()),
+            (ResourceKind::VolumetricStack, self.volumetric_stacks.len()),
+            (ResourceKind::Texture2D, self.texture_2d.len()),
+            (ResourceKind::Texture2DGroup, self.texture_2d_groups.len()),
+            (
+                ResourceKind::CompositeMaterials,
+                self.composite_materials.len(),
+            ),
+            (ResourceKind::MultiProperties, self.multi_properties.len()),
+            (ResourceKind::Displacement2D, self.displacement_2d.len()),
+            (ResourceKind::Image3D, self.images_3d.len()),
+            (ResourceKind::Function, self.functions.len()),
+            (ResourceKind::VolumeData, self.volume_data.len()),
+        ]
+        .into_iter()
+        .filter(|&(_, count)| count > 0)
+        .collect()
+    }
+
     /// Retrieves a base materials group by its ID.
     ///
     /// Returns `None` if no base materials group with the given ID exists.
@@ -411,4 +578,444 @@ impl ResourceCollection {
     pub fn iter_volumetric_stacks(&self) -> impl Iterator<Item = &VolumetricStack> {
         self.volumetric_stacks.values()
     }
+
+    /// Adds a 3D image resource to the collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Lib3mfError::Validation` if a resource with the same ID already exists.
+    pub fn add_image_3d(&mut self, image: Image3D) -> Result<()> {
+        if self.exists(image.id) {
+            return Err(Lib3mfError::Validation(format!(
+                "Duplicate resource ID: {}",
+                image.id.0
+            )));
+        }
+        self.images_3d.insert(image.id, image);
+        Ok(())
+    }
+
+    /// Retrieves a 3D image by its ID.
+    ///
+    /// Returns `None` if no 3D image with the given ID exists.
+    pub fn get_image_3d(&self, id: ResourceId) -> Option<&Image3D> {
+        self.images_3d.get(&id)
+    }
+
+    /// Returns an iterator over all 3D images in the collection.
+    pub fn iter_images_3d(&self) -> impl Iterator<Item = &Image3D> {
+        self.images_3d.values()
+    }
+
+    /// Adds a function resource (implicit or from a 3D image) to the collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Lib3mfError::Validation` if a resource with the same ID already exists.
+    pub fn add_function(&mut self, function: Function) -> Result<()> {
+        let id = function.id();
+        if self.exists(id) {
+            return Err(Lib3mfError::Validation(format!(
+                "Duplicate resource ID: {}",
+                id.0
+            )));
+        }
+        self.functions.insert(id, function);
+        Ok(())
+    }
+
+    /// Retrieves a function by its ID.
+    ///
+    /// Returns `None` if no function with the given ID exists.
+    pub fn get_function(&self, id: ResourceId) -> Option<&Function> {
+        self.functions.get(&id)
+    }
+
+    /// Returns the number of functions in the collection.
+    pub fn functions_count(&self) -> usize {
+        self.functions.len()
+    }
+
+    /// Returns an iterator over all functions in the collection.
+    pub fn iter_functions(&self) -> impl Iterator<Item = &Function> {
+        self.functions.values()
+    }
+
+    /// Adds a volume data resource to the collection.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Lib3mfError::Validation` if a resource with the same ID already exists.
+    pub fn add_volume_data(&mut self, data: VolumeData) -> Result<()> {
+        if self.exists(data.id) {
+            return Err(Lib3mfError::Validation(format!(
+                "Duplicate resource ID: {}",
+                data.id.0
+            )));
+        }
+        self.volume_data.insert(data.id, data);
+        Ok(())
+    }
+
+    /// Retrieves a volume data resource by its ID.
+    ///
+    /// Returns `None` if no volume data with the given ID exists.
+    pub fn get_volume_data(&self, id: ResourceId) -> Option<&VolumeData> {
+        self.volume_data.get(&id)
+    }
+
+    /// Returns an iterator over all volume data resources in the collection.
+    pub fn iter_volume_data(&self) -> impl Iterator<Item = &VolumeData> {
+        self.volume_data.values()
+    }
+
+    /// Returns an unused ID: one more than the highest ID in use, or 1 if the collection
+    /// is empty.
+    ///
+    /// The ID is not reserved, so add a resource with it before allocating another.
+    pub fn allocate_id(&self) -> ResourceId {
+        ResourceId(
+            self.iter_ids()
+                .map(|id| id.0)
+                .max()
+                .map_or(1, |max| max + 1),
+        )
+    }
+
+    /// Renumbers resources according to `mapping` and rewrites every reference to them:
+    /// object property IDs, triangle and slice segment `pid`s, components, boolean operands,
+    /// slice and volumetric stack geometry, level sets, texture groups, composite materials,
+    /// multi-properties, functions and volume data. IDs missing from `mapping` are kept.
+    ///
+    /// References into other model parts (with a `path`) are left unchanged. Build items
+    /// belong to the [`Model`]; use [`Model::remap_ids`] to update them as well.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Lib3mfError::InvalidStructure`, leaving the collection unchanged, if two
+    /// resources would end up with the same ID, or if an object's mesh has not been
+    /// loaded yet ([`Geometry::LazyMesh`]), since its triangle properties can't be rewritten.
+    pub fn remap_ids(&mut self, mapping: &HashMap<ResourceId, ResourceId>) -> Result<()> {
+        if let Some(obj) = self
+            .objects
+            .values()
+            .find(|o| matches!(o.geometry, Geometry::LazyMesh(_)))
+        {
+            return Err(Lib3mfError::InvalidStructure(format!(
+                "Cannot remap IDs: mesh of object {} is not loaded",
+                obj.id.0
+            )));
+        }
+        let mut seen = HashSet::new();
+        for id in self.iter_ids() {
+            let new_id = remapped(mapping, id);
+            if !seen.insert(new_id) {
+                return Err(Lib3mfError::InvalidStructure(format!(
+                    "Cannot remap IDs: more than one resource would use ID {}",
+                    new_id.0
+                )));
+            }
+        }
+
+        let map = |id: &mut ResourceId| *id = remapped(mapping, *id);
+        let map_pid = |pid: &mut Option<u32>| {
+            if let Some(p) = pid {
+                *p = remapped(mapping, ResourceId(*p)).0;
+            }
+        };
+
+        rekey(&mut self.objects, mapping, |obj| {
+            map(&mut obj.id);
+            obj.pid.as_mut().map(map);
+            match &mut obj.geometry {
+                Geometry::Mesh(mesh) => {
+                    mesh.triangles.iter_mut().for_each(|t| map_pid(&mut t.pid));
+                    mesh.volume_id.as_mut().map(map);
+                }
+                Geometry::PreciseMesh(mesh) => {
+                    mesh.triangles.iter_mut().for_each(|t| map_pid(&mut t.pid));
+                    mesh.volume_id.as_mut().map(map);
+                }
+                Geometry::DisplacementMesh(mesh) => {
+                    mesh.triangles.iter_mut().for_each(|t| map_pid(&mut t.pid))
+                }
+                Geometry::Components(comps) => {
+                    for c in comps.components.iter_mut().filter(|c| c.path.is_none()) {
+                        map(&mut c.object_id);
+                    }
+                }
+                Geometry::BooleanShape(shape) => {
+                    if shape.base_path.is_none() {
+                        map(&mut shape.base_object_id);
+                    }
+                    for op in shape.operations.iter_mut().filter(|op| op.path.is_none()) {
+                        map(&mut op.object_id);
+                    }
+                }
+                Geometry::SliceStack(id) | Geometry::VolumetricStack(id) => map(id),
+                Geometry::LevelSet(level_set) => {
+                    map(&mut level_set.function_id);
+                    map(&mut level_set.mesh_id);
+                    level_set.volume_id.as_mut().map(map);
+                }
+                Geometry::LazyMesh(_) => {}
+            }
+        });
+        rekey(&mut self.base_materials, mapping, |m| map(&mut m.id));
+        rekey(&mut self.color_groups, mapping, |g| map(&mut g.id));
+        rekey(&mut self.slice_stacks, mapping, |stack| {
+            map(&mut stack.id);
+            let segments = stack
+                .slices
+                .iter_mut()
+                .flat_map(|s| &mut s.polygons)
+                .flat_map(|p| &mut p.segments);
+            for segment in segments {
+                segment.pid.as_mut().map(map);
+            }
+        });
+        rekey(&mut self.volumetric_stacks, mapping, |v| map(&mut v.id));
+        rekey(&mut self.texture_2d, mapping, |t| map(&mut t.id));
+        rekey(&mut self.texture_2d_groups, mapping, |g| {
+            map(&mut g.id);
+            map(&mut g.texture_id);
+        });
+        rekey(&mut self.composite_materials, mapping, |c| {
+            map(&mut c.id);
+            map(&mut c.base_material_id);
+        });
+        rekey(&mut self.multi_properties, mapping, |m| {
+            map(&mut m.id);
+            m.pids.iter_mut().for_each(map);
+        });
+        rekey(&mut self.displacement_2d, mapping, |d| map(&mut d.id));
+        rekey(&mut self.images_3d, mapping, |i| map(&mut i.id));
+        rekey(&mut self.functions, mapping, |f| match f {
+            Function::Implicit(f) => {
+                map(&mut f.id);
+                // Resource ID constants name other resources, e.g. a mesh for signed distance
+                let values = f
+                    .nodes
+                    .iter_mut()
+                    .filter(|n| n.kind == "constresourceid")
+                    .flat_map(|n| n.attributes.iter_mut())
+                    .filter(|(key, _)| key == "value");
+                for (_, value) in values {
+                    if let Ok(id) = value.parse() {
+                        *value = remapped(mapping, ResourceId(id)).0.to_string();
+                    }
+                }
+            }
+            Function::FromImage3D(f) => {
+                map(&mut f.id);
+                map(&mut f.image3d_id);
+            }
+        });
+        rekey(&mut self.volume_data, mapping, |v| {
+            map(&mut v.id);
+            if let Some(composite) = &mut v.composite {
+                map(&mut composite.base_material_id);
+            }
+            let refs = v
+                .color
+                .iter_mut()
+                .chain(v.composite.iter_mut().flat_map(|c| &mut c.mappings))
+                .chain(v.properties.iter_mut().map(|p| &mut p.function));
+            for function_ref in refs {
+                map(&mut function_ref.function_id);
+            }
+        });
+        Ok(())
+    }
+
+    /// Renumbers resources to consecutive IDs starting at 1, keeping their relative order,
+    /// and returns the mapping from old to new IDs. See [`remap_ids`](Self::remap_ids) for
+    /// what gets rewritten; use [`Model::normalize_ids`] to update build items as well.
+    pub fn normalize_ids(&mut self) -> Result<HashMap<ResourceId, ResourceId>> {
+        let mut ids: Vec<ResourceId> = self.iter_ids().collect();
+        ids.sort_unstable();
+        let mapping: HashMap<ResourceId, ResourceId> = ids
+            .into_iter()
+            .zip(1..)
+            .map(|(old, new)| (old, ResourceId(new)))
+            .collect();
+        self.remap_ids(&mapping)?;
+        Ok(mapping)
+    }
+}
+
+/// The type of a resource in a [`ResourceCollection`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ResourceKind {
+    /// An object (`<object>`), whatever its geometry.
+    Object,
+    /// A base materials group (`<basematerials>`).
+    BaseMaterials,
+    /// A color group (`<colorgroup>`).
+    ColorGroup,
+    /// A slice stack (`<slicestack>`).
+    SliceStack,
+    /// A volumetric stack (`<volumetricstack>`).
+    VolumetricStack,
+    /// A 2D texture image (`<texture2d>`).
+    Texture2D,
+    /// A texture coordinate group (`<texture2dgroup>`).
+    Texture2DGroup,
+    /// A composite materials group (`<compositematerials>`).
+    CompositeMaterials,
+    /// A multiproperties group (`<multiproperties>`).
+    MultiProperties,
+    /// A displacement texture (`<displacement2d>`).
+    Displacement2D,
+    /// A 3D image (`<image3d>`).
+    Image3D,
+    /// An implicit function or function from a 3D image.
+    Function,
+    /// Volumetric property data (`<volumedata>`).
+    VolumeData,
+}
+
+impl ResourceKind {
+    /// Returns the XML element name of the kind, e.g. `"basematerials"`.
+    ///
+    /// Functions are reported as `"function"`, covering both function elements.
+    pub fn name(self) -> &'static str {
+        match self {
+            ResourceKind::Object => "object",
+            ResourceKind::BaseMaterials => "basematerials",
+            ResourceKind::ColorGroup => "colorgroup",
+            ResourceKind::SliceStack => "slicestack",
+            ResourceKind::VolumetricStack => "volumetricstack",
+            ResourceKind::Texture2D => "texture2d",
+            ResourceKind::Texture2DGroup => "texture2dgroup",
+            ResourceKind::CompositeMaterials => "compositematerials",
+            ResourceKind::MultiProperties => "multiproperties",
+            ResourceKind::Displacement2D => "displacement2d",
+            ResourceKind::Image3D => "image3d",
+            ResourceKind::Function => "function",
+            ResourceKind::VolumeData => "volumedata",
+        }
+    }
+}
+
+impl std::fmt::Display for ResourceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A borrowed resource of any kind, as returned by [`ResourceCollection::iter_all`] and
+/// [`ResourceCollection::get`].
+///
+/// Serializes as the resource itself.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(untagged)]
+pub enum ResourceRef<'a> {
+    /// An object.
+    Object(&'a Object),
+    /// A base materials group.
+    BaseMaterials(&'a BaseMaterialsGroup),
+    /// A color group.
+    ColorGroup(&'a ColorGroup),
+    /// A slice stack.
+    SliceStack(&'a SliceStack),
+    /// A volumetric stack.
+    VolumetricStack(&'a VolumetricStack),
+    /// A 2D texture image.
+    Texture2D(&'a Texture2D),
+    /// A texture coordinate group.
+    Texture2DGroup(&'a Texture2DGroup),
+    /// A composite materials group.
+    CompositeMaterials(&'a CompositeMaterials),
+    /// A multiproperties group.
+    MultiProperties(&'a MultiProperties),
+    /// A displacement texture.
+    Displacement2D(&'a Displacement2D),
+    /// A 3D image.
+    Image3D(&'a Image3D),
+    /// A function.
+    Function(&'a Function),
+    /// Volumetric property data.
+    VolumeData(&'a VolumeData),
+}
+
+impl ResourceRef<'_> {
+    /// Returns the ID of the resource.
+    pub fn id(&self) -> ResourceId {
+        match self {
+            ResourceRef::Object(r) => r.id,
+            ResourceRef::BaseMaterials(r) => r.id,
+            ResourceRef::ColorGroup(r) => r.id,
+            ResourceRef::SliceStack(r) => r.id,
+            ResourceRef::VolumetricStack(r) => r.id,
+            ResourceRef::Texture2D(r) => r.id,
+            ResourceRef::Texture2DGroup(r) => r.id,
+            ResourceRef::CompositeMaterials(r) => r.id,
+            ResourceRef::MultiProperties(r) => r.id,
+            ResourceRef::Displacement2D(r) => r.id,
+            ResourceRef::Image3D(r) => r.id,
+            ResourceRef::Function(r) => r.id(),
+            ResourceRef::VolumeData(r) => r.id,
+        }
+    }
+
+    /// Returns the kind of the resource.
+    pub fn kind(&self) -> ResourceKind {
+        match self {
+            ResourceRef::Object(_) => ResourceKind::Object,
+            ResourceRef::BaseMaterials(_) => ResourceKind::BaseMaterials,
+            ResourceRef::ColorGroup(_) => ResourceKind::ColorGroup,
+            ResourceRef::SliceStack(_) => ResourceKind::SliceStack,
+            ResourceRef::VolumetricStack(_) => ResourceKind::VolumetricStack,
+            ResourceRef::Texture2D(_) => ResourceKind::Texture2D,
+            ResourceRef::Texture2DGroup(_) => ResourceKind::Texture2DGroup,
+            ResourceRef::CompositeMaterials(_) => ResourceKind::CompositeMaterials,
+            ResourceRef::MultiProperties(_) => ResourceKind::MultiProperties,
+            ResourceRef::Displacement2D(_) => ResourceKind::Displacement2D,
+            ResourceRef::Image3D(_) => ResourceKind::Image3D,
+            ResourceRef::Function(_) => ResourceKind::Function,
+            ResourceRef::VolumeData(_) => ResourceKind::VolumeData,
+        }
+    }
+}
+
+impl Model {
+    /// Renumbers resources and updates build items to match. See
+    /// [`ResourceCollection::remap_ids`].
+    pub fn remap_ids(&mut self, mapping: &HashMap<ResourceId, ResourceId>) -> Result<()> {
+        self.resources.remap_ids(mapping)?;
+        for item in self.build.items.iter_mut().filter(|i| i.path.is_none()) {
+            item.object_id = remapped(mapping, item.object_id);
+        }
+        Ok(())
+    }
+
+    /// Renumbers resources to consecutive IDs starting at 1 and updates build items to
+    /// match. See [`ResourceCollection::normalize_ids`].
+    pub fn normalize_ids(&mut self) -> Result<HashMap<ResourceId, ResourceId>> {
+        let mapping = self.resources.normalize_ids()?;
+        for item in self.build.items.iter_mut().filter(|i| i.path.is_none()) {
+            item.object_id = remapped(&mapping, item.object_id);
+        }
+        Ok(mapping)
+    }
+}
+
+fn remapped(mapping: &HashMap<ResourceId, ResourceId>, id: ResourceId) -> ResourceId {
+    mapping.get(&id).copied().unwrap_or(id)
+}
+
+/// Applies `update` to every resource in `map` and re-inserts it under its new ID.
+fn rekey<T>(
+    map: &mut BTreeMap<ResourceId, T>,
+    mapping: &HashMap<ResourceId, ResourceId>,
+    mut update: impl FnMut(&mut T),
+) {
+    *map = std::mem::take(map)
+        .into_iter()
+        .map(|(id, mut resource)| {
+            update(&mut resource);
+            (remapped(mapping, id), resource)
+        })
+        .collect();
 }
@@ -0,0 +1,477 @@
+//! Signed distance fields sampled from meshes.
+//!
+//! [`SdfGrid`] stores the signed distance to a mesh surface at the centres of a regular
+//! voxel grid: negative inside, positive outside. Distances come from
+//! [`SpatialIndex::closest_point`] and signs from crossings along grid rows, so building
+//! a grid costs about one BVH query per voxel. With the `parallel` feature, voxels are
+//! evaluated on all cores.
+//!
+//! The grid is split into bricks of [`BRICK_SIZE`]³ voxels. Setting
+//! [`SdfOptions::narrow_band`] makes it sparse: bricks entirely farther than the band from
+//! the surface store one clamped value instead of a distance per voxel, which is what
+//! offsetting and shelling need.
+//!
+//! [`SdfGrid::isosurface`] turns a grid back into a closed triangle mesh.
+//!
+//! ```
+//! use glam::Vec3;
+//! use lib3mf_core::model::Mesh;
+//! use lib3mf_core::model::sdf::SdfOptions;
+//!
+//! let mut mesh = Mesh::new();
+//! let o = mesh.add_vertex(0.0, 0.0, 0.0);
+//! let x = mesh.add_vertex(10.0, 0.0, 0.0);
+//! let y = mesh.add_vertex(0.0, 10.0, 0.0);
+//! let z = mesh.add_vertex(0.0, 0.0, 10.0);
+//! mesh.add_triangle(o, y, x);
+//! mesh.add_triangle(o, x, z);
+//! mesh.add_triangle(o, z, y);
+//! mesh.add_triangle(x, y, z);
+//!
+//! let options = SdfOptions { voxel_size: 0.5, ..Default::default() };
+//! let sdf = mesh.signed_distance_field(&options).unwrap();
+//! assert!(sdf.sample(Vec3::new(1.0, 1.0, 1.0)) < 0.0);
+//! assert!(sdf.sample(Vec3::new(8.0, 8.0, 8.0)) > 0.0);
+//! ```
+
+use crate::error::{Lib3mfError, Result};
+use crate::model::{Mesh, MeshStorage, SpatialIndex};
+use glam::Vec3;
+use std::collections::HashMap;
+
+/// Edge length, in voxels, of the bricks an [`SdfGrid`] is stored in.
+pub const BRICK_SIZE: usize = 8;
+
+const BRICK_VOXELS: usize = BRICK_SIZE * BRICK_SIZE * BRICK_SIZE;
+
+/// Options for [`SdfGrid::from_mesh`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SdfOptions {
+    /// Edge length of a voxel in model units. Default: `1.0`.
+    pub voxel_size: f32,
+    /// Voxels added on every side of the mesh bounds. Default: `2`.
+    ///
+    /// At least one voxel of padding keeps the zero level set inside the grid.
+    pub padding: usize,
+    /// Half-width of the band around the surface where exact distances are kept.
+    /// Default: `None` (dense: every voxel exact).
+    ///
+    /// Bricks entirely outside the band store `±narrow_band` for all their voxels.
+    pub narrow_band: Option<f32>,
+    /// Largest number of voxels to allocate before failing. Default: 2²⁴ (256³).
+    pub max_voxels: usize,
+}
+
+impl Default for SdfOptions {
+    fn default() -> Self {
+        Self {
+            voxel_size: 1.0,
+            padding: 2,
+            narrow_band: None,
+            max_voxels: 1 << 24,
+        }
+    }
+}
+
+/// One brick of voxels.
+#[derive(Debug, Clone, PartialEq)]
+enum Brick {
+    /// Every voxel has this value (a brick outside the narrow band).
+    Uniform(f32),
+    /// Per-voxel values, x fastest.
+    Dense(Box<[f32; BRICK_VOXELS]>),
+}
+
+/// Signed distances to a mesh surface at voxel centres. Negative inside.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SdfGrid {
+    origin: Vec3,
+    voxel_size: f32,
+    dims: [usize; 3],
+    bricks_per_axis: [usize; 3],
+    bricks: Vec<Brick>,
+    narrow_band: Option<f32>,
+}
+
+impl Mesh {
+    /// Samples the mesh into a signed distance field. See [`SdfGrid::from_mesh`].
+    pub fn signed_distance_field(&self, options: &SdfOptions) -> Result<SdfGrid> {
+        SdfGrid::from_mesh(self, options)
+    }
+}
+
+impl SdfGrid {
+    /// Samples `mesh` into a grid covering its bounds plus padding.
+    ///
+    /// The sign is only meaningful for closed, consistently oriented meshes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Lib3mfError::Validation`] if the mesh has no triangles, the voxel size is
+    /// not positive, or the grid would exceed [`SdfOptions::max_voxels`].
+    pub fn from_mesh(mesh: &Mesh, options: &SdfOptions) -> Result<Self> {
+        Self::from_index(&SpatialIndex::new(mesh), options)
+    }
+
+    /// Like [`from_mesh`](Self::from_mesh), reusing an existing [`SpatialIndex`].
+    pub fn from_index<S: MeshStorage + Sync>(
+        index: &SpatialIndex<'_, S>,
+        options: &SdfOptions,
+    ) -> Result<Self> {
+        if !(options.voxel_size > 0.0 && options.voxel_size.is_finite()) {
+            return Err(Lib3mfError::Validation(format!(
+                "SDF voxel size must be positive, got {}",
+                options.voxel_size
+            )));
+        }
+        let bounds = index.bounds().ok_or_else(|| {
+            Lib3mfError::Validation("Cannot build an SDF of a mesh without triangles".into())
+        })?;
+
+        let voxel = options.voxel_size;
+        let pad = options.padding as f32 * voxel;
+        let extent = bounds.max - bounds.min + 2.0 * pad;
+        let dims = (extent / voxel)
+            .ceil()
+            .max(Vec3::ONE)
+            .to_array()
+            .map(|d| d as usize + 1);
+        let total = dims.iter().try_fold(1usize, |acc, &d| acc.checked_mul(d));
+        if total.is_none_or(|total| total > options.max_voxels) {
+            return Err(Lib3mfError::Validation(format!(
+                "SDF grid of {}x{}x{} voxels exceeds the limit of {} voxels",
+                dims[0], dims[1], dims[2], options.max_voxels
+            )));
+        }
+        // Centre the grid on the mesh
+        let origin = (bounds.min + bounds.max) * 0.5
+            - Vec3::new(dims[0] as f32, dims[1] as f32, dims[2] as f32) * voxel * 0.5
+            + voxel * 0.5;
+
+        let mut grid = Self {
+            origin,
+            voxel_size: voxel,
+            dims,
+            bricks_per_axis: dims.map(|d| d.div_ceil(BRICK_SIZE)),
+            bricks: Vec::new(),
+            narrow_band: options.narrow_band.map(f32::abs),
+        };
+        let inside = grid.inside_mask(index);
+        let brick_count = grid.bricks_per_axis.iter().product();
+
+        let build = |b: usize| grid.build_brick(index, &inside, b);
+        #[cfg(feature = "parallel")]
+        let bricks = {
+            use rayon::prelude::*;
+            (0..brick_count).into_par_iter().map(build).collect()
+        };
+        #[cfg(not(feature = "parallel"))]
+        let bricks = (0..brick_count).map(build).collect();
+
+        grid.bricks = bricks;
+        Ok(grid)
+    }
+
+    /// Number of voxels along x, y and z.
+    pub fn dims(&self) -> [usize; 3] {
+        self.dims
+    }
+
+    /// Edge length of a voxel.
+    pub fn voxel_size(&self) -> f32 {
+        self.voxel_size
+    }
+
+    /// Centre of voxel `(0, 0, 0)`.
+    pub fn origin(&self) -> Vec3 {
+        self.origin
+    }
+
+    /// Narrow band half-width the grid was built with, if sparse.
+    pub fn narrow_band(&self) -> Option<f32> {
+        self.narrow_band
+    }
+
+    /// Centre of voxel `(x, y, z)`.
+    pub fn position(&self, x: usize, y: usize, z: usize) -> Vec3 {
+        self.origin + Vec3::new(x as f32, y as f32, z as f32) * self.voxel_size
+    }
+
+    /// Signed distance stored at voxel `(x, y, z)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the voxel is outside [`dims`](Self::dims).
+    pub fn get(&self, x: usize, y: usize, z: usize) -> f32 {
+        assert!(
+            x < self.dims[0] && y < self.dims[1] && z < self.dims[2],
+            "voxel ({x}, {y}, {z}) outside {:?}",
+            self.dims
+        );
+        let b = self.brick_index(x / BRICK_SIZE, y / BRICK_SIZE, z / BRICK_SIZE);
+        match &self.bricks[b] {
+            Brick::Uniform(value) => *value,
+            Brick::Dense(values) => {
+                values[local_index(x % BRICK_SIZE, y % BRICK_SIZE, z % BRICK_SIZE)]
+            }
+        }
+    }
+
+    /// Trilinearly interpolated signed distance at `point`, clamped to the grid.
+    pub fn sample(&self, point: Vec3) -> f32 {
+        let max = Vec3::new(
+            (self.dims[0] - 1) as f32,
+            (self.dims[1] - 1) as f32,
+            (self.dims[2] - 1) as f32,
+        );
+        let g = ((point - self.origin) / self.voxel_size).clamp(Vec3::ZERO, max);
+        let base = g.floor().min((max - 1.0).max(Vec3::ZERO));
+        let f = (g - base).min(Vec3::ONE);
+        let [x, y, z] = base.to_array().map(|v| v as usize);
+        let next = |i: usize, axis: usize| (i + 1).min(self.dims[axis] - 1);
+        let (x1, y1, z1) = (next(x, 0), next(y, 1), next(z, 2));
+
+        let lerp = |a: f32, b: f32, t: f32| a + (b - a) * t;
+        let c00 = lerp(self.get(x, y, z), self.get(x1, y, z), f.x);
+        let c10 = lerp(self.get(x, y1, z), self.get(x1, y1, z), f.x);
+        let c01 = lerp(self.get(x, y, z1), self.get(x1, y, z1), f.x);
+        let c11 = lerp(self.get(x, y1, z1), self.get(x1, y1, z1), f.x);
+        lerp(lerp(c00, c10, f.y), lerp(c01, c11, f.y), f.z)
+    }
+
+    /// Number of bricks holding per-voxel values; the rest store one value.
+    pub fn dense_bricks(&self) -> usize {
+        self.bricks
+            .iter()
+            .filter(|b| matches!(b, Brick::Dense(_)))
+            .count()
+    }
+
+    /// All values in x-fastest order, expanding uniform bricks.
+    pub fn to_dense(&self) -> Vec<f32> {
+        let [nx, ny, nz] = self.dims;
+        let mut values = Vec::with_capacity(nx * ny * nz);
+        for z in 0..nz {
+            for y in 0..ny {
+                for x in 0..nx {
+                    values.push(self.get(x, y, z));
+                }
+            }
+        }
+        values
+    }
+
+    /// Closed triangle mesh of the surface where the field equals `level`.
+    ///
+    /// Triangles face toward larger values, so `isosurface(0.0)` is oriented outward like
+    /// the source mesh. The surface is extracted by marching tetrahedra and is watertight
+    /// wherever the level set does not touch the grid boundary. Returns an empty mesh if
+    /// no voxel pair straddles `level`.
+    pub fn isosurface(&self, level: f32) -> Mesh {
+        let mut values = self.to_dense();
+        values.iter_mut().for_each(|v| *v -= level);
+        marching_tetrahedra(self.origin, self.voxel_size, self.dims, &values)
+    }
+
+    fn brick_index(&self, bx: usize, by: usize, bz: usize) -> usize {
+        let [nbx, nby, _] = self.bricks_per_axis;
+        bx + nbx * (by + nby * bz)
+    }
+
+    /// Inside flags for every voxel, x fastest, from signed crossings along x rows.
+    fn inside_mask<S: MeshStorage + Sync>(&self, index: &SpatialIndex<'_, S>) -> Vec<bool> {
+        let [nx, ny, nz] = self.dims;
+        // Rays start outside the mesh even without padding
+        let start_x = index
+            .bounds()
+            .map_or(self.origin.x, |b| b.min.x.min(self.origin.x))
+            - self.voxel_size;
+        let row = |r: usize| {
+            let (y, z) = (r % ny, r / ny);
+            let mut start = self.position(0, y, z);
+            start.x = start_x;
+            let mut hits = index.raycast_all(start, Vec3::X);
+            // A row through a shared edge or vertex hits each adjacent triangle
+            let tolerance = self.voxel_size * 1e-4;
+            hits.dedup_by(|b, a| {
+                b.front_face == a.front_face && (b.distance - a.distance).abs() <= tolerance
+            });
+
+            let mut flags = vec![false; nx];
+            let (mut winding, mut next_hit) = (0i32, 0);
+            for (x, flag) in flags.iter_mut().enumerate() {
+                let distance = self.position(x, y, z).x - start_x;
+                while next_hit < hits.len() && hits[next_hit].distance <= distance {
+                    // Entering through a front face, leaving through a back face
+                    winding += if hits[next_hit].front_face { 1 } else { -1 };
+                    next_hit += 1;
+                }
+                *flag = winding > 0;
+            }
+            flags
+        };
+        #[cfg(feature = "parallel")]
+        let rows: Vec<Vec<bool>> = {
+            use rayon::prelude::*;
+            (0..ny * nz).into_par_iter().map(row).collect()
+        };
+        #[cfg(not(feature = "parallel"))]
+        let rows: Vec<Vec<bool>> = (0..ny * nz).map(row).collect();
+        rows.concat()
+    }
+
+    fn build_brick<S: MeshStorage + Sync>(
+        &self,
+        index: &SpatialIndex<'_, S>,
+        inside: &[bool],
+        brick: usize,
+    ) -> Brick {
+        let [nbx, nby, _] = self.bricks_per_axis;
+        let (bx, by, bz) = (brick % nbx, (brick / nbx) % nby, brick / (nbx * nby));
+        let [nx, ny, nz] = self.dims;
+        let voxel = |x: usize, y: usize, z: usize| {
+            // Voxels past the grid edge in partial bricks repeat the last one
+            let (x, y, z) = (x.min(nx - 1), y.min(ny - 1), z.min(nz - 1));
+            (self.position(x, y, z), inside[x + nx * (y + ny * z)])
+        };
+        let first = [bx, by, bz].map(|b| b * BRICK_SIZE);
+
+        if let Some(band) = self.narrow_band {
+            let half = (BRICK_SIZE - 1) as f32 * 0.5;
+            let center = self.position(first[0], first[1], first[2]) + half * self.voxel_size;
+            let radius = half * self.voxel_size * 3f32.sqrt();
+            let far = index
+                .closest_point(center)
+                .is_none_or(|c| c.distance > band + radius);
+            if far {
+                let (_, inside) = voxel(
+                    first[0] + BRICK_SIZE / 2,
+                    first[1] + BRICK_SIZE / 2,
+                    first[2] + BRICK_SIZE / 2,
+                );
+                return Brick::Uniform(if inside { -band } else { band });
+            }
+        }
+
+        let mut values = Box::new([0.0f32; BRICK_VOXELS]);
+        for lz in 0..BRICK_SIZE {
+            for ly in 0..BRICK_SIZE {
+                for lx in 0..BRICK_SIZE {
+                    let (p, inside) = voxel(first[0] + lx, first[1] + ly, first[2] + lz);
+                    let distance = index.closest_point(p).map_or(f32::MAX, |c| c.distance);
+                    let distance = match self.narrow_band {
+                        Some(band) => distance.min(band),
+                        None => distance,
+                    };
+                    values[local_index(lx, ly, lz)] = if inside { -distance } else { distance };
+                }
+            }
+        }
+        Brick::Dense(values)
+    }
+}
+
+fn local_index(x: usize, y: usize, z: usize) -> usize {
+    x + BRICK_SIZE * (y + BRICK_SIZE * z)
+}
+
+/// Kuhn decomposition of a cell into six tetrahedra around its 0–7 diagonal. Corner bit 0
+/// is +x, bit 1 is +y and bit 2 is +z. Neighbouring cells split shared faces the same way,
+/// which keeps the extracted surface watertight.
+const TETRAHEDRA: [[usize; 4]; 6] = [
+    [0, 1, 3, 7],
+    [0, 1, 5, 7],
+    [0, 2, 3, 7],
+    [0, 2, 6, 7],
+    [0, 4, 5, 7],
+    [0, 4, 6, 7],
+];
+
+/// Extracts the zero level set of `values` (x fastest, sampled at `origin + index * voxel`).
+///
+/// Triangles face toward positive values.
+pub(crate) fn marching_tetrahedra(
+    origin: Vec3,
+    voxel: f32,
+    dims: [usize; 3],
+    values: &[f32],
+) -> Mesh {
+    let [nx, ny, nz] = dims;
+    // Samples exactly on the level would put vertices on grid points and create
+    // zero-area triangles; treat them as just outside
+    let epsilon = voxel * 1e-5;
+    let value = |i: usize| {
+        let v = values[i];
+        if v.abs() < epsilon { epsilon } else { v }
+    };
+    let position = |i: usize| {
+        let (x, y, z) = (i % nx, (i / nx) % ny, i / (nx * ny));
+        origin + Vec3::new(x as f32, y as f32, z as f32) * voxel
+    };
+    let corner_offset = |c: usize| Vec3::new((c & 1) as f32, (c >> 1 & 1) as f32, (c >> 2) as f32);
+
+    let mut mesh = Mesh::new();
+    let mut edge_vertices: HashMap<(usize, usize), u32> = HashMap::new();
+    let mut vertex = |mesh: &mut Mesh, a: usize, b: usize| {
+        let key = (a.min(b), a.max(b));
+        *edge_vertices.entry(key).or_insert_with(|| {
+            let (va, vb) = (value(key.0), value(key.1));
+            let p = position(key.0).lerp(position(key.1), va / (va - vb));
+            mesh.add_vertex(p.x, p.y, p.z)
+        })
+    };
+
+    for z in 0..nz.saturating_sub(1) {
+        for y in 0..ny.saturating_sub(1) {
+            for x in 0..nx.saturating_sub(1) {
+                let base = x + nx * (y + ny * z);
+                let corners: [usize; 8] = std::array::from_fn(|c| {
+                    base + (c & 1) + nx * (c >> 1 & 1) + nx * ny * (c >> 2)
+                });
+                let inside = corners.map(|c| value(c) < 0.0);
+                if inside.iter().all(|&i| i) || inside.iter().all(|&i| !i) {
+                    continue;
+                }
+
+                for tet in TETRAHEDRA {
+                    let (ins, outs): (Vec<usize>, Vec<usize>) =
+                        tet.iter().partition(|&&c| inside[c]);
+                    // Edges crossing the level, in cyclic order around the cut
+                    let cut: Vec<(usize, usize)> = match (ins.as_slice(), outs.as_slice()) {
+                        ([i], [a, b, c]) | ([a, b, c], [i]) => vec![(*i, *a), (*i, *b), (*i, *c)],
+                        ([i0, i1], [o0, o1]) => {
+                            vec![(*i0, *o0), (*i0, *o1), (*i1, *o1), (*i1, *o0)]
+                        }
+                        _ => continue,
+                    };
+
+                    // Orientation from edge midpoints in cell coordinates: the cut through a
+                    // tetrahedron never degenerates, so this matches the interpolated one
+                    let midpoint =
+                        |(a, b): (usize, usize)| (corner_offset(a) + corner_offset(b)) * 0.5;
+                    let centroid = |cs: &[usize]| {
+                        cs.iter().map(|&c| corner_offset(c)).sum::<Vec3>() / cs.len() as f32
+                    };
+                    let outward = centroid(&outs) - centroid(&ins);
+                    let normal = (midpoint(cut[1]) - midpoint(cut[0]))
+                        .cross(midpoint(cut[2]) - midpoint(cut[0]));
+                    let flip = normal.dot(outward) < 0.0;
+
+                    let ids: Vec<u32> = cut
+                        .iter()
+                        .map(|&(a, b)| vertex(&mut mesh, corners[a], corners[b]))
+                        .collect();
+                    for k in 1..ids.len() - 1 {
+                        let (b, c) = if flip {
+                            (ids[k + 1], ids[k])
+                        } else {
+                            (ids[k], ids[k + 1])
+                        };
+                        mesh.add_triangle(ids[0], b, c);
+                    }
+                }
+            }
+        }
+    }
+    mesh
+}
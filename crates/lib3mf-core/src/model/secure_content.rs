@@ -1,3 +1,5 @@
+use crate::archive::part_names_match;
+use crate::error::{Lib3mfError, Result};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -35,6 +37,9 @@ pub struct ResourceDataGroup {
     pub key_uuid: Uuid, // UUID of the content encryption key
     /// Per-consumer access rights specifying how each consumer's wrapped key is provided.
     pub access_rights: Vec<AccessRight>,
+    /// Package paths of the parts encrypted with this group's key (`<resourcedata path>`).
+    #[serde(default)]
+    pub resource_paths: Vec<String>,
     // This group logically contains resources. The resources themselves (Objects, Textures)
     // refer to this group or are implicitly part of it via relationships.
 }
@@ -50,5 +55,144 @@ pub struct AccessRight {
     pub wrapped_key: Vec<u8>, // The encrypted content key for this consumer
 }
 
+impl KeyStore {
+    /// Creates an empty key store with the given UUID.
+    pub fn new(uuid: Uuid) -> Self {
+        Self {
+            uuid,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the consumer with the given ID.
+    pub fn consumer(&self, id: &str) -> Option<&Consumer> {
+        self.consumers.iter().find(|c| c.id == id)
+    }
+
+    /// Adds a consumer. Fails if a consumer with the same ID already exists.
+    pub fn add_consumer(&mut self, consumer: Consumer) -> Result<()> {
+        if self.consumer(&consumer.id).is_some() {
+            return Err(Lib3mfError::Validation(format!(
+                "Consumer {} already exists in KeyStore",
+                consumer.id
+            )));
+        }
+        self.consumers.push(consumer);
+        Ok(())
+    }
+
+    /// Removes a consumer and all of its access rights, returning it if it existed.
+    pub fn remove_consumer(&mut self, id: &str) -> Option<Consumer> {
+        let index = self.consumers.iter().position(|c| c.id == id)?;
+        for group in &mut self.resource_data_groups {
+            group.access_rights.retain(|ar| ar.consumer_id != id);
+        }
+        Some(self.consumers.remove(index))
+    }
+
+    /// Returns the resource data group protected by the content key `key_uuid`.
+    pub fn resource_data_group(&self, key_uuid: Uuid) -> Option<&ResourceDataGroup> {
+        self.resource_data_groups
+            .iter()
+            .find(|g| g.key_uuid == key_uuid)
+    }
+
+    /// Mutable variant of [`KeyStore::resource_data_group`].
+    pub fn resource_data_group_mut(&mut self, key_uuid: Uuid) -> Option<&mut ResourceDataGroup> {
+        self.resource_data_groups
+            .iter_mut()
+            .find(|g| g.key_uuid == key_uuid)
+    }
+
+    /// Adds an empty resource data group for the content key `key_uuid`.
+    ///
+    /// Fails if a group for that key already exists.
+    pub fn add_resource_data_group(&mut self, key_uuid: Uuid) -> Result<&mut ResourceDataGroup> {
+        if self.resource_data_group(key_uuid).is_some() {
+            return Err(Lib3mfError::Validation(format!(
+                "Resource data group {} already exists in KeyStore",
+                key_uuid
+            )));
+        }
+        self.resource_data_groups.push(ResourceDataGroup {
+            key_uuid,
+            ..Default::default()
+        });
+        Ok(self.resource_data_groups.last_mut().expect("just pushed"))
+    }
+
+    /// Removes the resource data group for `key_uuid`, returning it if it existed.
+    pub fn remove_resource_data_group(&mut self, key_uuid: Uuid) -> Option<ResourceDataGroup> {
+        let index = self
+            .resource_data_groups
+            .iter()
+            .position(|g| g.key_uuid == key_uuid)?;
+        Some(self.resource_data_groups.remove(index))
+    }
+
+    /// Revokes a consumer's access to the resources of group `key_uuid`.
+    ///
+    /// Returns `true` if an access right was removed. The content key itself is unchanged,
+    /// so a consumer that already unwrapped it can still decrypt the current ciphertext.
+    pub fn revoke_access(&mut self, key_uuid: Uuid, consumer_id: &str) -> bool {
+        self.resource_data_group_mut(key_uuid)
+            .is_some_and(|group| group.remove_access_right(consumer_id).is_some())
+    }
+
+    /// Returns the resource data group whose key encrypts the part at `path`.
+    pub fn group_for_path(&self, path: &str) -> Option<&ResourceDataGroup> {
+        self.resource_data_groups.iter().find(|g| g.protects(path))
+    }
+
+    /// Returns the IDs of the consumers holding an access right for group `key_uuid`.
+    pub fn consumers_with_access(&self, key_uuid: Uuid) -> Vec<&str> {
+        self.resource_data_group(key_uuid)
+            .map(|g| {
+                g.access_rights
+                    .iter()
+                    .map(|ar| ar.consumer_id.as_str())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl ResourceDataGroup {
+    /// Returns true if the part at `path` is encrypted with this group's key.
+    pub fn protects(&self, path: &str) -> bool {
+        self.resource_paths
+            .iter()
+            .any(|p| part_names_match(p, path))
+    }
+
+    /// Returns the access right of the given consumer.
+    pub fn access_right(&self, consumer_id: &str) -> Option<&AccessRight> {
+        self.access_rights
+            .iter()
+            .find(|ar| ar.consumer_id == consumer_id)
+    }
+
+    /// Adds an access right, replacing an existing one for the same consumer.
+    pub fn set_access_right(&mut self, right: AccessRight) {
+        match self
+            .access_rights
+            .iter_mut()
+            .find(|ar| ar.consumer_id == right.consumer_id)
+        {
+            Some(existing) => *existing = right,
+            None => self.access_rights.push(right),
+        }
+    }
+
+    /// Removes the access right of the given consumer, returning it if it existed.
+    pub fn remove_access_right(&mut self, consumer_id: &str) -> Option<AccessRight> {
+        let index = self
+            .access_rights
+            .iter()
+            .position(|ar| ar.consumer_id == consumer_id)?;
+        Some(self.access_rights.remove(index))
+    }
+}
+
 // Note: In 3MF Secure Content, the actual resources are encrypted in the OPC (ZIP) container.
 // The XML metadata describes HOW to decrypt them.
@@ -0,0 +1,179 @@
+//! Copy-on-write storage shared between clones of a model.
+//!
+//! The vertex and triangle arrays of a [`Mesh`](crate::model::Mesh) are [`SharedVec`]s:
+//! vectors behind an [`Arc`] that cloning shares instead of copying. Cloning a [`Model`]
+//! therefore costs memory for its structure, not for its meshes, and the clones can be
+//! handed to worker threads that validate, export or compute statistics concurrently.
+//! A `SharedVec` is copied only when a clone that shares it is first modified, so the other
+//! clones never see the change.
+//!
+//! `SharedVec` dereferences to [`Vec`], so meshes are read and edited as before:
+//!
+//! ```
+//! use lib3mf_core::model::Mesh;
+//!
+//! let mut mesh = Mesh::new();
+//! mesh.add_vertex(0.0, 0.0, 0.0);
+//! let copy = mesh.clone();
+//! assert!(mesh.vertices.is_shared());
+//!
+//! // The first edit copies the buffer for this mesh only
+//! mesh.vertices[0].x = 1.0;
+//! assert!(!mesh.vertices.is_shared());
+//! assert_eq!(copy.vertices[0].x, 0.0);
+//! ```
+//!
+//! Every clone still counts shared buffers in its own
+//! [`memory_usage`](crate::model::Model::memory_usage), so the estimates of several clones
+//! overstate what they take together.
+//!
+//! [`Model`]: crate::model::Model
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Weak};
+
+/// A vector shared between clones until one of them modifies it.
+///
+/// Reading goes through [`Deref`] to the shared [`Vec`]. Mutable access through
+/// [`DerefMut`] first copies the vector if another clone still shares it. Serialized like
+/// a `Vec`.
+pub struct SharedVec<T>(Arc<Vec<T>>);
+
+impl<T> SharedVec<T> {
+    /// Creates an empty vector.
+    pub fn new() -> Self {
+        Self(Arc::new(Vec::new()))
+    }
+
+    /// Returns whether another clone shares this vector's buffer.
+    pub fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.0) > 1
+    }
+
+    /// Returns whether `a` and `b` share one buffer.
+    pub fn ptr_eq(a: &Self, b: &Self) -> bool {
+        Arc::ptr_eq(&a.0, &b.0)
+    }
+
+    /// Returns a reference to the buffer that doesn't keep it alive.
+    pub(crate) fn downgrade(&self) -> Weak<Vec<T>> {
+        Arc::downgrade(&self.0)
+    }
+
+    /// Shares the buffer behind `weak`, if a vector still holds it.
+    pub(crate) fn upgrade(weak: &Weak<Vec<T>>) -> Option<Self> {
+        weak.upgrade().map(Self)
+    }
+}
+
+impl<T: Clone> SharedVec<T> {
+    /// Returns the vector, copying it if another clone shares it.
+    pub fn into_vec(self) -> Vec<T> {
+        Arc::try_unwrap(self.0).unwrap_or_else(|shared| (*shared).clone())
+    }
+}
+
+impl<T> Clone for SharedVec<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> Default for SharedVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Deref for SharedVec<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Vec<T> {
+        &self.0
+    }
+}
+
+impl<T: Clone> DerefMut for SharedVec<T> {
+    fn deref_mut(&mut self) -> &mut Vec<T> {
+        Arc::make_mut(&mut self.0)
+    }
+}
+
+impl<T> From<Vec<T>> for SharedVec<T> {
+    fn from(vec: Vec<T>) -> Self {
+        Self(Arc::new(vec))
+    }
+}
+
+impl<T: Clone> From<SharedVec<T>> for Vec<T> {
+    fn from(shared: SharedVec<T>) -> Self {
+        shared.into_vec()
+    }
+}
+
+impl<T> FromIterator<T> for SharedVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<T>>())
+    }
+}
+
+impl<T: Clone> IntoIterator for SharedVec<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_vec().into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SharedVec<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'a, T: Clone> IntoIterator for &'a mut SharedVec<T> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.deref_mut().iter_mut()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SharedVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: PartialEq> PartialEq for SharedVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: PartialEq> PartialEq<Vec<T>> for SharedVec<T> {
+    fn eq(&self, other: &Vec<T>) -> bool {
+        *self.0 == *other
+    }
+}
+
+impl<T: Eq> Eq for SharedVec<T> {}
+
+impl<T: Serialize> Serialize for SharedVec<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for SharedVec<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Self::from)
+    }
+}
@@ -0,0 +1,359 @@
+//! Splitting meshes into their connected shells.
+//!
+//! [`Mesh::split_components`] separates a mesh into the groups of triangles and beams that
+//! share no vertices. Inward-facing shells, such as the cavity surface of a hollowed part,
+//! stay with the shell that encloses them, so each returned mesh is a solid on its own.
+//!
+//! [`Model::split_object_components`] applies this to a mesh object: the first shell stays
+//! in the object and every further shell becomes a new object with the same type and
+//! properties. Build items and components that referenced the object get an extra entry
+//! for each new object, with the same transform.
+//!
+//! ```
+//! use lib3mf_core::model::Mesh;
+//!
+//! let mut mesh = Mesh::new();
+//! for offset in [0.0, 10.0] {
+//!     let a = mesh.add_vertex(offset, 0.0, 0.0);
+//!     let b = mesh.add_vertex(offset + 1.0, 0.0, 0.0);
+//!     let c = mesh.add_vertex(offset, 1.0, 0.0);
+//!     let d = mesh.add_vertex(offset, 0.0, 1.0);
+//!     for [x, y, z] in [[a, c, b], [a, b, d], [a, d, c], [b, c, d]] {
+//!         mesh.add_triangle(x, y, z);
+//!     }
+//! }
+//!
+//! let parts = mesh.split_components();
+//! assert_eq!(parts.len(), 2);
+//! assert_eq!(parts[1].vertices[0].x, 10.0);
+//! ```
+
+use crate::error::{Lib3mfError, Result};
+use crate::model::{
+    Beam, BeamLattice, BeamSet, BuildItem, Component, Geometry, Mesh, Model, Object, ResourceId,
+    SpatialIndex, Triangle, TriangleSet,
+};
+use glam::Vec3;
+use uuid::Uuid;
+
+impl Mesh {
+    /// Splits the mesh into its connected components, in the order their first triangle
+    /// (or, for beam-only components, first beam) appears.
+    ///
+    /// Triangles and beams are connected when they share a vertex. A component with
+    /// negative signed volume is merged into the smallest outward-facing component that
+    /// contains it. Each part keeps its triangle sets, triangle attributes, beam sets and
+    /// volume reference, reindexed. Vertices keep their relative order; those used by no
+    /// triangle or beam are dropped.
+    ///
+    /// Returns a single copy of the compacted mesh when it is connected, and no meshes when
+    /// it has neither triangles nor beams.
+    pub fn split_components(&self) -> Vec<Mesh> {
+        let mut labels = self.component_labels();
+        let count = labels.iter().flatten().max().map_or(0, |&l| l as usize + 1);
+        if count > 1 {
+            let parts = self.partition(&labels, count);
+            let volumes: Vec<f64> = parts
+                .iter()
+                .map(|p| p.compute_area_and_volume().1)
+                .collect();
+            let mut target: Vec<u32> = (0..count as u32).collect();
+            let mut indexes: Vec<Option<SpatialIndex>> = (0..count).map(|_| None).collect();
+            for (inner, part) in parts.iter().enumerate() {
+                if volumes[inner] >= 0.0 || part.triangles.is_empty() {
+                    continue;
+                }
+                let v = part.vertices[part.triangles[0].v1 as usize];
+                let point = Vec3::new(v.x, v.y, v.z);
+                let mut best: Option<usize> = None;
+                for outer in 0..count {
+                    if volumes[outer] <= 0.0 || best.is_some_and(|b| volumes[b] <= volumes[outer]) {
+                        continue;
+                    }
+                    let index =
+                        indexes[outer].get_or_insert_with(|| SpatialIndex::new(&parts[outer]));
+                    if index.contains(point) {
+                        best = Some(outer);
+                    }
+                }
+                if let Some(outer) = best {
+                    target[inner] = outer as u32;
+                }
+            }
+            drop(indexes);
+            if target.iter().enumerate().any(|(i, &t)| t as usize != i) {
+                // Renumber the remaining components in order of first appearance
+                let mut order = vec![None; count];
+                let mut next = 0;
+                for label in labels.iter_mut().flatten() {
+                    let merged = target[*label as usize] as usize;
+                    *label = *order[merged].get_or_insert_with(|| {
+                        next += 1;
+                        next - 1
+                    });
+                }
+                return self.partition(&labels, next as usize);
+            }
+            return parts;
+        }
+        self.partition(&labels, count)
+    }
+
+    /// Component of each vertex, numbered in order of first use; `None` for unused vertices.
+    fn component_labels(&self) -> Vec<Option<u32>> {
+        let mut parent: Vec<u32> = (0..self.vertices.len() as u32).collect();
+        fn find(parent: &mut [u32], mut v: u32) -> u32 {
+            while parent[v as usize] != v {
+                parent[v as usize] = parent[parent[v as usize] as usize];
+                v = parent[v as usize];
+            }
+            v
+        }
+        let mut union = |a: u32, b: u32| {
+            let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+            if ra != rb {
+                parent[ra.max(rb) as usize] = ra.min(rb);
+            }
+        };
+        let beams = self.beam_lattice.iter().flat_map(|l| &l.beams);
+        for t in &self.triangles {
+            union(t.v1, t.v2);
+            union(t.v2, t.v3);
+        }
+        for beam in beams.clone() {
+            union(beam.v1, beam.v2);
+        }
+
+        let mut roots: Vec<Option<u32>> = vec![None; self.vertices.len()];
+        let mut labels: Vec<Option<u32>> = vec![None; self.vertices.len()];
+        let mut next = 0;
+        let used = self
+            .triangles
+            .iter()
+            .flat_map(|t| [t.v1, t.v2, t.v3])
+            .chain(beams.flat_map(|b| [b.v1, b.v2]));
+        for v in used {
+            let root = find(&mut parent, v) as usize;
+            let label = *roots[root].get_or_insert_with(|| {
+                next += 1;
+                next - 1
+            });
+            labels[v as usize] = Some(label);
+        }
+        labels
+    }
+
+    /// Builds one mesh per component label.
+    fn partition(&self, labels: &[Option<u32>], count: usize) -> Vec<Mesh> {
+        let mut parts: Vec<Mesh> = (0..count)
+            .map(|_| Mesh {
+                volume_id: self.volume_id,
+                ..Mesh::new()
+            })
+            .collect();
+        // Vertices keep their relative order within each part
+        let mut vertex_map: Vec<u32> = vec![0; self.vertices.len()];
+        for (v, label) in labels.iter().enumerate() {
+            if let Some(label) = label {
+                let part = &mut parts[*label as usize];
+                vertex_map[v] = part.vertices.len() as u32;
+                part.vertices.push(self.vertices[v]);
+            }
+        }
+
+        // Triangle sets and attributes are remapped per part: `None` for other parts
+        let mut triangle_part: Vec<u32> = Vec::with_capacity(self.triangles.len());
+        let mut triangle_index: Vec<u32> = Vec::with_capacity(self.triangles.len());
+        for t in &self.triangles {
+            let label = labels[t.v1 as usize].unwrap_or_default();
+            let [v1, v2, v3] = [t.v1, t.v2, t.v3].map(|v| vertex_map[v as usize]);
+            let part = &mut parts[label as usize];
+            triangle_part.push(label);
+            triangle_index.push(part.triangles.len() as u32);
+            part.triangles.push(Triangle { v1, v2, v3, ..*t });
+        }
+        for (label, part) in parts.iter_mut().enumerate() {
+            let remap: Vec<Option<u32>> = triangle_part
+                .iter()
+                .zip(&triangle_index)
+                .map(|(&p, &i)| (p as usize == label).then_some(i))
+                .collect();
+            let mut attributes = self.triangle_attributes.clone();
+            attributes.remap(&remap);
+            part.triangle_attributes = attributes;
+            for set in &self.triangle_sets {
+                let mut triangles: Vec<u32> = set
+                    .triangles
+                    .iter()
+                    .filter_map(|&t| remap.get(t as usize).copied().flatten())
+                    .collect();
+                if !triangles.is_empty() {
+                    triangles.sort_unstable();
+                    part.triangle_sets.push(TriangleSet {
+                        triangles,
+                        ..set.clone()
+                    });
+                }
+            }
+        }
+
+        if let Some(lattice) = &self.beam_lattice {
+            let mut beam_part: Vec<(usize, u32)> = Vec::with_capacity(lattice.beams.len());
+            for beam in &lattice.beams {
+                let label = labels[beam.v1 as usize].unwrap_or_default() as usize;
+                let [v1, v2] = [beam.v1, beam.v2].map(|v| vertex_map[v as usize]);
+                let part = parts[label]
+                    .beam_lattice
+                    .get_or_insert_with(|| BeamLattice {
+                        radius: lattice.radius,
+                        min_length: lattice.min_length,
+                        precision: lattice.precision,
+                        clipping_mode: lattice.clipping_mode,
+                        beams: Vec::new(),
+                        beam_sets: Vec::new(),
+                    });
+                beam_part.push((label, part.beams.len() as u32));
+                part.beams.push(Beam {
+                    v1,
+                    v2,
+                    ..beam.clone()
+                });
+            }
+            for set in &lattice.beam_sets {
+                for (label, part) in parts.iter_mut().enumerate() {
+                    let Some(part_lattice) = &mut part.beam_lattice else {
+                        continue;
+                    };
+                    let refs: Vec<u32> = set
+                        .refs
+                        .iter()
+                        .filter_map(|&r| beam_part.get(r as usize))
+                        .filter(|&&(p, _)| p == label)
+                        .map(|&(_, i)| i)
+                        .collect();
+                    if !refs.is_empty() {
+                        part_lattice.beam_sets.push(BeamSet {
+                            refs,
+                            ..set.clone()
+                        });
+                    }
+                }
+            }
+        }
+        parts
+    }
+}
+
+impl Model {
+    /// Splits mesh object `id` into one object per connected shell, as found by
+    /// [`Mesh::split_components`], and returns the IDs of the resulting objects.
+    ///
+    /// The first shell stays in object `id`. Each further shell becomes a new object with
+    /// the next free ID, copying the object's type, part number, thumbnail and material
+    /// (`pid`/`pindex`); its name gets a ` (n)` suffix and it gets a fresh UUID if the
+    /// original had one. Every build item and component referencing `id` is followed by
+    /// one for each new object, with the same transform and fresh UUIDs where set.
+    ///
+    /// A connected mesh is left unchanged and `vec![id]` is returned.
+    ///
+    /// # Errors
+    ///
+    /// - `Lib3mfError::ResourceNotFound` if object `id` does not exist
+    /// - `Lib3mfError::Validation` if it is not a mesh object, or is referenced by a
+    ///   boolean shape or level set, which can only name a single object
+    pub fn split_object_components(&mut self, id: ResourceId) -> Result<Vec<ResourceId>> {
+        let object = self
+            .resources
+            .get_object(id)
+            .ok_or(Lib3mfError::ResourceNotFound(id.0))?;
+        let Geometry::Mesh(mesh) = &object.geometry else {
+            return Err(Lib3mfError::Validation(format!(
+                "Object {} is not a mesh object",
+                id.0
+            )));
+        };
+        let mut parts = mesh.split_components();
+        if parts.len() <= 1 {
+            return Ok(vec![id]);
+        }
+        let single_reference = self.resources.iter_objects().any(|o| match &o.geometry {
+            Geometry::BooleanShape(shape) => {
+                (shape.base_object_id == id && shape.base_path.is_none())
+                    || shape
+                        .operations
+                        .iter()
+                        .any(|op| op.object_id == id && op.path.is_none())
+            }
+            Geometry::LevelSet(level_set) => level_set.mesh_id == id,
+            _ => false,
+        });
+        if single_reference {
+            return Err(Lib3mfError::Validation(format!(
+                "Object {} is referenced by a boolean shape or level set and cannot be split",
+                id.0
+            )));
+        }
+
+        let template = object.clone();
+        let rest = parts.split_off(1);
+        let mut ids = vec![id];
+        for (n, mesh) in rest.into_iter().enumerate() {
+            let new_id = self.resources.allocate_id();
+            self.resources.add_object(Object {
+                id: new_id,
+                name: template
+                    .name
+                    .as_ref()
+                    .map(|name| format!("{} ({})", name, n + 2)),
+                uuid: template.uuid.map(|_| Uuid::new_v4()),
+                geometry: Geometry::Mesh(mesh),
+                ..template.clone()
+            })?;
+            ids.push(new_id);
+        }
+        if let Some(object) = self.resources.iter_objects_mut().find(|o| o.id == id) {
+            object.geometry = Geometry::Mesh(parts.remove(0));
+        }
+
+        let mut items = Vec::with_capacity(self.build.items.len());
+        for item in self.build.items.drain(..) {
+            let split = item.object_id == id && item.path.is_none();
+            items.push(item.clone());
+            if split {
+                items.extend(ids[1..].iter().map(|&new_id| BuildItem {
+                    object_id: new_id,
+                    uuid: item.uuid.map(|_| Uuid::new_v4()),
+                    ..item.clone()
+                }));
+            }
+        }
+        self.build.items = items;
+
+        for object in self.resources.iter_objects_mut() {
+            let Geometry::Components(components) = &mut object.geometry else {
+                continue;
+            };
+            if !components
+                .components
+                .iter()
+                .any(|c| c.object_id == id && c.path.is_none())
+            {
+                continue;
+            }
+            let mut expanded = Vec::with_capacity(components.components.len() + ids.len());
+            for component in components.components.drain(..) {
+                let split = component.object_id == id && component.path.is_none();
+                expanded.push(component.clone());
+                if split {
+                    expanded.extend(ids[1..].iter().map(|&new_id| Component {
+                        object_id: new_id,
+                        uuid: component.uuid.map(|_| Uuid::new_v4()),
+                        ..component.clone()
+                    }));
+                }
+            }
+            components.components = expanded;
+        }
+        Ok(ids)
+    }
+}
@@ -0,0 +1,336 @@
+//! Spatial queries on meshes: ray casting, closest points, containment and box queries.
+//!
+//! [`Mesh::spatial_index`] builds the same bounding volume hierarchy that Paranoid
+//! validation uses for self-intersection checks. Building is O(n log n); keep the
+//! [`SpatialIndex`] around and reuse it for many queries against an unchanged mesh:
+//!
+//! ```
+//! use glam::Vec3;
+//! use lib3mf_core::model::Mesh;
+//!
+//! let mut mesh = Mesh::new();
+//! let o = mesh.add_vertex(0.0, 0.0, 0.0);
+//! let x = mesh.add_vertex(10.0, 0.0, 0.0);
+//! let y = mesh.add_vertex(0.0, 10.0, 0.0);
+//! let z = mesh.add_vertex(0.0, 0.0, 10.0);
+//! mesh.add_triangle(o, y, x);
+//! mesh.add_triangle(o, x, z);
+//! mesh.add_triangle(o, z, y);
+//! mesh.add_triangle(x, y, z);
+//!
+//! let index = mesh.spatial_index();
+//! let hit = index.raycast(Vec3::new(1.0, 1.0, 20.0), Vec3::NEG_Z).unwrap();
+//! assert!((hit.distance - 12.0).abs() < 1e-4);
+//! assert!(index.contains(Vec3::new(1.0, 1.0, 1.0)));
+//! assert!(!index.contains(Vec3::new(8.0, 8.0, 8.0)));
+//! ```
+//!
+//! Queries work on any [`MeshStorage`] through [`SpatialIndex::new`]. Positions are in
+//! the mesh's own coordinate system, before any build item or component transform.
+
+use crate::model::{Mesh, MeshStorage};
+use crate::validation::bvh::{AABB, BvhContent, BvhNode};
+use glam::Vec3;
+
+/// A ray intersection found by [`SpatialIndex::raycast`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    /// Index of the triangle hit.
+    pub triangle: usize,
+    /// Distance along the ray, in units of the direction's length.
+    pub distance: f32,
+    /// Intersection point.
+    pub point: Vec3,
+    /// Barycentric coordinates of the point with respect to the triangle's `v1`, `v2`
+    /// and `v3`.
+    pub barycentric: Vec3,
+    /// Whether the ray hit the triangle's front (counter-clockwise, outward) side.
+    pub front_face: bool,
+}
+
+/// The point on a mesh nearest to a query point, found by [`SpatialIndex::closest_point`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClosestPoint {
+    /// Index of the triangle the point lies on.
+    pub triangle: usize,
+    /// Closest point on the mesh surface.
+    pub point: Vec3,
+    /// Distance from the query point.
+    pub distance: f32,
+}
+
+/// Bounding volume hierarchy over a mesh's triangles for repeated spatial queries.
+///
+/// Borrows the mesh, so it cannot go stale: rebuild it after editing the mesh.
+pub struct SpatialIndex<'a, S: MeshStorage = Mesh> {
+    mesh: &'a S,
+    bvh: Option<BvhNode>,
+}
+
+impl Mesh {
+    /// Builds a [`SpatialIndex`] for ray casting, closest-point, containment and box
+    /// queries. See the [module docs](crate::model::spatial).
+    pub fn spatial_index(&self) -> SpatialIndex<'_> {
+        SpatialIndex::new(self)
+    }
+}
+
+impl<'a, S: MeshStorage> SpatialIndex<'a, S> {
+    /// Builds the hierarchy over all triangles of `mesh`.
+    pub fn new(mesh: &'a S) -> Self {
+        let count = mesh.triangle_count();
+        let bvh = (count > 0).then(|| BvhNode::build(mesh, (0..count).collect()));
+        Self { mesh, bvh }
+    }
+
+    /// The indexed mesh.
+    pub fn mesh(&self) -> &'a S {
+        self.mesh
+    }
+
+    /// Bounding box of all triangles, or `None` for a mesh without triangles.
+    pub fn bounds(&self) -> Option<AABB> {
+        self.bvh.as_ref().map(|node| node.aabb)
+    }
+
+    /// Nearest intersection of the ray `origin + t * direction` (for `t >= 0`) with the
+    /// mesh, hitting triangles from either side.
+    pub fn raycast(&self, origin: Vec3, direction: Vec3) -> Option<RayHit> {
+        let root = self.bvh.as_ref()?;
+        let inv_dir = direction.recip();
+        let mut best: Option<RayHit> = None;
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            let limit = best.map_or(f32::INFINITY, |hit| hit.distance);
+            match node.aabb.ray_entry(origin, inv_dir) {
+                Some(entry) if entry <= limit => {}
+                _ => continue,
+            }
+            match &node.content {
+                BvhContent::Leaf(indices) => {
+                    for &i in indices {
+                        if let Some(hit) = self.ray_triangle(i, origin, direction)
+                            && best.is_none_or(|best| hit.distance < best.distance)
+                        {
+                            best = Some(hit);
+                        }
+                    }
+                }
+                BvhContent::Branch(left, right) => {
+                    // Visit the nearer child first so its hits prune the other
+                    let entry = |n: &BvhNode| n.aabb.ray_entry(origin, inv_dir);
+                    let (near, far) = match (entry(left), entry(right)) {
+                        (Some(l), Some(r)) if r < l => (right, left),
+                        _ => (left, right),
+                    };
+                    stack.push(far);
+                    stack.push(near);
+                }
+            }
+        }
+        best
+    }
+
+    /// All intersections of the ray `origin + t * direction` (for `t >= 0`) with the
+    /// mesh, nearest first.
+    pub fn raycast_all(&self, origin: Vec3, direction: Vec3) -> Vec<RayHit> {
+        let Some(root) = &self.bvh else {
+            return Vec::new();
+        };
+        let inv_dir = direction.recip();
+        let mut hits = Vec::new();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            if node.aabb.ray_entry(origin, inv_dir).is_none() {
+                continue;
+            }
+            match &node.content {
+                BvhContent::Leaf(indices) => hits.extend(
+                    indices
+                        .iter()
+                        .filter_map(|&i| self.ray_triangle(i, origin, direction)),
+                ),
+                BvhContent::Branch(left, right) => {
+                    stack.push(left);
+                    stack.push(right);
+                }
+            }
+        }
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+
+    /// The point on the mesh surface nearest to `point`, or `None` for a mesh without
+    /// triangles.
+    pub fn closest_point(&self, point: Vec3) -> Option<ClosestPoint> {
+        let root = self.bvh.as_ref()?;
+        let mut best: Option<ClosestPoint> = None;
+        let mut best_sq = f32::INFINITY;
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            if node.aabb.distance_squared(point) > best_sq {
+                continue;
+            }
+            match &node.content {
+                BvhContent::Leaf(indices) => {
+                    for &i in indices {
+                        let [a, b, c] = self.corners(i);
+                        let q = closest_on_triangle(point, a, b, c);
+                        let d_sq = q.distance_squared(point);
+                        if d_sq < best_sq {
+                            best_sq = d_sq;
+                            best = Some(ClosestPoint {
+                                triangle: i,
+                                point: q,
+                                distance: d_sq.sqrt(),
+                            });
+                        }
+                    }
+                }
+                BvhContent::Branch(left, right) => {
+                    let (near, far) =
+                        if right.aabb.distance_squared(point) < left.aabb.distance_squared(point) {
+                            (right, left)
+                        } else {
+                            (left, right)
+                        };
+                    stack.push(far);
+                    stack.push(near);
+                }
+            }
+        }
+        best
+    }
+
+    /// Generalized winding number of the mesh around `point`.
+    ///
+    /// About 1 inside and 0 outside a closed, outward-oriented mesh (-1 inside an
+    /// inverted one). For meshes with holes the value degrades smoothly, which makes it a
+    /// more robust inside test than ray parity.
+    pub fn winding_number(&self, point: Vec3) -> f32 {
+        let mut total = 0.0f64;
+        for i in 0..self.mesh.triangle_count() {
+            let [a, b, c] = self.corners(i).map(|v| v - point);
+            let (la, lb, lc) = (a.length(), b.length(), c.length());
+            // Van Oosterom–Strackee solid angle of the triangle seen from `point`
+            let numerator = a.dot(b.cross(c));
+            let denominator = la * lb * lc + a.dot(b) * lc + b.dot(c) * la + c.dot(a) * lb;
+            total += 2.0 * f64::from(numerator).atan2(f64::from(denominator));
+        }
+        (total / (4.0 * std::f64::consts::PI)) as f32
+    }
+
+    /// Whether `point` is inside the mesh, judged by a [winding number](Self::winding_number)
+    /// of at least one half in magnitude.
+    pub fn contains(&self, point: Vec3) -> bool {
+        if self
+            .bounds()
+            .is_none_or(|bounds| bounds.distance_squared(point) > 0.0)
+        {
+            return false;
+        }
+        self.winding_number(point).abs() >= 0.5
+    }
+
+    /// Indices of the triangles whose bounding boxes overlap `aabb`, in ascending order.
+    pub fn triangles_in_aabb(&self, aabb: &AABB) -> Vec<usize> {
+        let Some(root) = &self.bvh else {
+            return Vec::new();
+        };
+        let mut found = Vec::new();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            if !node.aabb.intersects(aabb) {
+                continue;
+            }
+            match &node.content {
+                BvhContent::Leaf(indices) => found.extend(indices.iter().copied().filter(|&i| {
+                    AABB::from_triangle(self.mesh, &self.mesh.triangle(i)).intersects(aabb)
+                })),
+                BvhContent::Branch(left, right) => {
+                    stack.push(left);
+                    stack.push(right);
+                }
+            }
+        }
+        found.sort_unstable();
+        found
+    }
+
+    fn corners(&self, triangle: usize) -> [Vec3; 3] {
+        let t = self.mesh.triangle(triangle);
+        [t.v1, t.v2, t.v3].map(|i| {
+            let v = self.mesh.vertex(i as usize);
+            Vec3::new(v.x, v.y, v.z)
+        })
+    }
+
+    /// Möller–Trumbore intersection with triangle `index`, without back-face culling.
+    fn ray_triangle(&self, index: usize, origin: Vec3, direction: Vec3) -> Option<RayHit> {
+        let [a, b, c] = self.corners(index);
+        let (e1, e2) = (b - a, c - a);
+        let p = direction.cross(e2);
+        let det = e1.dot(p);
+        if det.abs() <= f32::EPSILON * e1.length() * e2.length() * direction.length() {
+            return None; // Parallel to the triangle's plane, or degenerate
+        }
+        let inv_det = 1.0 / det;
+        let s = origin - a;
+        let u = s.dot(p) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = s.cross(e1);
+        let v = direction.dot(q) * inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let t = e2.dot(q) * inv_det;
+        if t < 0.0 {
+            return None;
+        }
+        Some(RayHit {
+            triangle: index,
+            distance: t,
+            point: origin + direction * t,
+            barycentric: Vec3::new(1.0 - u - v, u, v),
+            front_face: det > 0.0,
+        })
+    }
+}
+
+/// Closest point to `p` on triangle `abc` (Ericson, Real-Time Collision Detection 5.1.5).
+fn closest_on_triangle(p: Vec3, a: Vec3, b: Vec3, c: Vec3) -> Vec3 {
+    let (ab, ac, ap) = (b - a, c - a, p - a);
+    let (d1, d2) = (ab.dot(ap), ac.dot(ap));
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+    let bp = p - b;
+    let (d3, d4) = (ab.dot(bp), ac.dot(bp));
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + ab * (d1 / (d1 - d3));
+    }
+    let cp = p - c;
+    let (d5, d6) = (ab.dot(cp), ac.dot(cp));
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + ac * (d2 / (d2 - d6));
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+    let denom = va + vb + vc;
+    if denom.abs() <= f32::MIN_POSITIVE {
+        return a; // Degenerate triangle
+    }
+    a + ab * (vb / denom) + ac * (vc / denom)
+}
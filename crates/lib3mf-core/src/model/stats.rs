@@ -1,7 +1,7 @@
-use crate::model::Unit;
+use crate::model::{Length, Metadata, ResourceKind, Unit};
 use crate::utils::hardware::HardwareCapabilities;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Comprehensive statistics and metadata for a 3MF model.
 ///
@@ -14,8 +14,12 @@ pub struct ModelStats {
     pub unit: Unit,
     /// Software that generated the model (from metadata)
     pub generator: Option<String>,
-    /// Custom metadata key-value pairs from the model
+    /// Custom metadata key-value pairs from the model (the untagged value for names given
+    /// in several languages)
     pub metadata: HashMap<String, String>,
+    /// Number of resources of each kind (kinds without resources are omitted)
+    #[serde(default)]
+    pub resource_counts: BTreeMap<ResourceKind, usize>,
     /// Geometric statistics (vertices, triangles, volume, etc.)
     pub geometry: GeometryStats,
     /// Material and property statistics
@@ -30,6 +34,60 @@ pub struct ModelStats {
     pub system_info: HardwareCapabilities,
     /// Thumbnail statistics
     pub thumbnails: ThumbnailStats,
+    /// Build items that carry a part number, UUID or item-level metadata
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub build_items: Vec<BuildItemStats>,
+    /// Geometry on each Bambu Studio plate, counting every instance placed on it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub plates: Vec<PlateGeometryStats>,
+}
+
+/// Geometry placed on one Bambu Studio plate.
+///
+/// Like the totals of [`GeometryStats`], these count a mesh once per instance. Lets a print
+/// farm schedule each plate of a multi-plate project separately.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlateGeometryStats {
+    /// The plate's ID.
+    pub plate_id: u32,
+    /// The plate's name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Positions in the build, starting at 1, of the build items on the plate.
+    #[serde(default)]
+    pub build_items: Vec<usize>,
+    /// Number of distinct objects on the plate.
+    #[serde(default)]
+    pub object_count: usize,
+    /// Number of object instances on the plate.
+    pub instance_count: usize,
+    /// Vertices of every instance on the plate.
+    pub vertex_count: u64,
+    /// Triangles of every instance on the plate.
+    pub triangle_count: u64,
+    /// Volume of every instance on the plate, in cubic model units.
+    pub volume: f64,
+    /// Bounding box of the instances on the plate, in model units.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bounding_box: Option<BoundingBox>,
+}
+
+/// Identification and metadata of one build item.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BuildItemStats {
+    /// Position of the item in the build, starting at 1.
+    pub index: usize,
+    /// ID of the object the item places.
+    pub object_id: u32,
+    /// The item's `partnumber` attribute.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_number: Option<String>,
+    /// The item's Production Extension UUID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+    /// The item's `<metadatagroup>` entries.
+    #[serde(default, skip_serializing_if = "Metadata::is_empty")]
+    pub metadata: Metadata,
 }
 
 /// Statistics about thumbnails in a 3MF package.
@@ -60,22 +118,29 @@ pub struct MaterialsStats {
 ///
 /// Aggregates counts and measurements of the model's geometry including
 /// vertices, triangles, bounding box, surface area, and volume.
+///
+/// The totals describe what gets printed: a mesh placed several times, by several build
+/// items or components, is counted once per placement, with its transform applied.
+/// [`unique`](Self::unique) counts each mesh resource once instead.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GeometryStats {
-    /// Total number of object resources
+    /// Number of mesh placements reached from the build
     pub object_count: usize,
     /// Number of build items (instances to print)
     pub instance_count: usize,
-    /// Total number of triangles across all meshes
+    /// Triangles of every mesh placement
     pub triangle_count: u64,
-    /// Total number of vertices across all meshes
+    /// Vertices of every mesh placement
     pub vertex_count: u64,
     /// Axis-aligned bounding box of the entire model
     pub bounding_box: Option<BoundingBox>,
-    /// Total surface area in square model units
+    /// Surface area of every mesh placement in square model units
     pub surface_area: f64,
-    /// Total volume in cubic model units
+    /// Volume of every mesh placement (the printed volume) in cubic model units
     pub volume: f64,
+    /// The same measurements with each mesh resource counted once
+    #[serde(default)]
+    pub unique: UniqueGeometryStats,
     /// Whether all meshes are manifold (watertight)
     pub is_manifold: bool,
     /// Count of objects by type (e.g., {"model": 5, "support": 2})
@@ -83,6 +148,30 @@ pub struct GeometryStats {
     pub type_counts: HashMap<String, usize>,
 }
 
+/// Geometry of the distinct meshes reached from the build, each counted once however often
+/// it is placed, and measured untransformed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UniqueGeometryStats {
+    /// Number of distinct mesh resources
+    pub mesh_count: usize,
+    /// Vertices of the distinct meshes
+    pub vertex_count: u64,
+    /// Triangles of the distinct meshes
+    pub triangle_count: u64,
+    /// Surface area of the distinct meshes in square model units
+    pub surface_area: f64,
+    /// Volume of the distinct meshes in cubic model units
+    pub volume: f64,
+    /// Vertex and triangle arrays of distinct meshes that share their storage with another
+    /// distinct mesh's, e.g. after parsing with
+    /// [`ParseOptions::intern_meshes`](crate::parser::ParseOptions::intern_meshes)
+    #[serde(default)]
+    pub shared_buffers: usize,
+    /// Memory the shared arrays would take if each mesh held its own copy, in bytes
+    #[serde(default)]
+    pub deduplicated_bytes: u64,
+}
+
 /// An axis-aligned bounding box in 3D space.
 ///
 /// Represents the smallest box (aligned with coordinate axes) that
@@ -124,6 +213,70 @@ impl BoundingBox {
             max: [transformed_max.x, transformed_max.y, transformed_max.z],
         }
     }
+
+    /// Returns the edge lengths of the box along X, Y and Z.
+    pub fn size(&self) -> [f32; 3] {
+        [
+            self.max[0] - self.min[0],
+            self.max[1] - self.min[1],
+            self.max[2] - self.min[2],
+        ]
+    }
+
+    /// Merges another box into this one so that it covers both.
+    pub fn union(&mut self, other: &BoundingBox) {
+        for i in 0..3 {
+            self.min[i] = self.min[i].min(other.min[i]);
+            self.max[i] = self.max[i].max(other.max[i]);
+        }
+    }
+
+    /// Returns this box with its coordinates converted from `from` units to `to` units.
+    pub fn convert(&self, from: Unit, to: Unit) -> Self {
+        let f = |v: f32| from.convert(v as f64, to) as f32;
+        Self {
+            min: self.min.map(f),
+            max: self.max.map(f),
+        }
+    }
+}
+
+impl ModelStats {
+    /// Returns the model bounding box converted to the requested unit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib3mf_core::model::{BoundingBox, ModelStats, Unit};
+    ///
+    /// let mut stats = ModelStats::default();
+    /// stats.geometry.bounding_box = Some(BoundingBox { min: [0.0; 3], max: [10.0, 20.0, 30.0] });
+    ///
+    /// let bbox = stats.extents_in(Unit::Centimeter).unwrap();
+    /// assert_eq!(bbox.max, [1.0, 2.0, 3.0]);
+    /// ```
+    pub fn extents_in(&self, unit: Unit) -> Option<BoundingBox> {
+        self.geometry
+            .bounding_box
+            .map(|b| b.convert(self.unit, unit))
+    }
+
+    /// Returns the model's X/Y/Z dimensions as lengths in the requested unit.
+    pub fn size_in(&self, unit: Unit) -> Option<[Length; 3]> {
+        self.geometry
+            .bounding_box
+            .map(|b| b.size().map(|v| Length::new(v as f64, self.unit).to(unit)))
+    }
+
+    /// Returns the total surface area converted to square `unit`.
+    pub fn surface_area_in(&self, unit: Unit) -> f64 {
+        self.unit.convert_area(self.geometry.surface_area, unit)
+    }
+
+    /// Returns the total volume converted to cubic `unit`.
+    pub fn volume_in(&self, unit: Unit) -> f64 {
+        self.unit.convert_volume(self.geometry.volume, unit)
+    }
 }
 
 /// Statistics from the Production Extension (UUIDs).
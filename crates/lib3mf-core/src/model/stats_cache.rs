@@ -0,0 +1,122 @@
+//! Precomputed [`ModelStats`] stored in the package.
+//!
+//! Computing statistics means parsing every model part, which takes seconds to minutes for
+//! the largest files. A package written with
+//! [`PackageWriter::with_stats_cache`](crate::writer::package_writer::PackageWriter::with_stats_cache)
+//! carries its statistics as JSON in [`STATS_CACHE_PATH`], and [`read_stats_cache`] returns
+//! them without parsing anything.
+//!
+//! The cache records a fingerprint of the package: a SHA-256 over the name, size and CRC-32
+//! of every other entry, all of which the ZIP directory holds. Checking it decompresses no
+//! data, yet any edit to a part, whether by this crate or another tool, changes the
+//! fingerprint and the cache is ignored. `[Content_Types].xml` is left out, as it does not
+//! affect the statistics. Caches written by another version of this crate are ignored too.
+//!
+//! [`ModelStats::system_info`] describes the machine reading the statistics, so it is
+//! detected again rather than taken from the cache.
+//!
+//! ```
+//! # #[cfg(feature = "writer")] {
+//! use lib3mf_core::archive::ZipArchiver;
+//! use lib3mf_core::model::{Model, read_stats_cache};
+//! use lib3mf_core::writer::package_writer::PackageWriter;
+//! use lib3mf_core::model::Package;
+//! use std::io::Cursor;
+//!
+//! let mut buffer = Cursor::new(Vec::new());
+//! PackageWriter::new(&mut buffer)
+//!     .with_stats_cache(true)
+//!     .write(&Package::new(Model::default()))?;
+//!
+//! let mut archive = ZipArchiver::new(Cursor::new(buffer.into_inner()))?;
+//! let stats = read_stats_cache(&mut archive)?.expect("fresh cache");
+//! assert_eq!(stats.geometry.triangle_count, 0);
+//! # }
+//! # Ok::<(), lib3mf_core::error::Lib3mfError>(())
+//! ```
+
+use crate::archive::{ArchiveReader, EntryMetadata};
+use crate::error::Result;
+use crate::model::{ContentHash, ModelStats};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Archive path of the statistics cache.
+pub const STATS_CACHE_PATH: &str = "Metadata/lib3mf-stats.json";
+
+/// Entries the fingerprint leaves out: the cache itself, and the content types, which the
+/// writer may only know once the cache exists.
+const UNFINGERPRINTED: [&str; 2] = [STATS_CACHE_PATH, "[Content_Types].xml"];
+
+/// JSON layout of [`STATS_CACHE_PATH`].
+#[derive(Serialize, Deserialize)]
+struct StatsCache {
+    /// Version of lib3mf-core that computed the statistics.
+    version: String,
+    /// Hex [`package_fingerprint`] of the package the statistics describe.
+    fingerprint: String,
+    stats: ModelStats,
+}
+
+/// Computes the fingerprint the cache is checked against from the package's entries.
+///
+/// Entries may be given in any order and with or without a leading `/`.
+pub fn package_fingerprint(entries: &[EntryMetadata]) -> ContentHash {
+    let mut entries: Vec<(&str, &EntryMetadata)> = entries
+        .iter()
+        .map(|e| (e.name.trim_start_matches('/'), e))
+        .filter(|(name, _)| !UNFINGERPRINTED.contains(name))
+        .collect();
+    entries.sort_unstable_by_key(|(name, _)| *name);
+
+    let mut h = Sha256::new();
+    h.update((entries.len() as u64).to_le_bytes());
+    for (name, entry) in entries {
+        h.update((name.len() as u64).to_le_bytes());
+        h.update(name.as_bytes());
+        h.update(entry.size.to_le_bytes());
+        h.update(entry.crc32.to_le_bytes());
+    }
+    ContentHash(h.finalize().into())
+}
+
+/// Serializes `stats` as the cache of a package with the given fingerprint.
+#[cfg(feature = "writer")]
+pub(crate) fn stats_cache_json(stats: &ModelStats, fingerprint: ContentHash) -> Result<Vec<u8>> {
+    let cache = StatsCache {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        fingerprint: fingerprint.to_hex(),
+        stats: stats.clone(),
+    };
+    serde_json::to_vec_pretty(&cache).map_err(|e| {
+        crate::error::Lib3mfError::InvalidStructure(format!("Failed to encode stats cache: {e}"))
+    })
+}
+
+/// Returns the statistics cached in the package, if it has a cache that is still valid.
+///
+/// Returns `None` when there is no cache, when it is malformed or from another version of
+/// this crate, or when the package changed since it was written.
+///
+/// # Errors
+///
+/// Returns [`Lib3mfError::Io`](crate::error::Lib3mfError::Io) if the archive can't be read.
+pub fn read_stats_cache(archive: &mut impl ArchiveReader) -> Result<Option<ModelStats>> {
+    if !archive.entry_exists(STATS_CACHE_PATH) {
+        return Ok(None);
+    }
+    let data = archive.read_entry(STATS_CACHE_PATH)?;
+    let Ok(cache) = serde_json::from_slice::<StatsCache>(&data) else {
+        return Ok(None);
+    };
+    if cache.version != env!("CARGO_PKG_VERSION") {
+        return Ok(None);
+    }
+    let fingerprint = package_fingerprint(&archive.entries_metadata()?);
+    if ContentHash::from_hex(&cache.fingerprint) != Some(fingerprint) {
+        return Ok(None);
+    }
+    let mut stats = cache.stats;
+    stats.system_info = crate::utils::hardware::detect_capabilities();
+    Ok(Some(stats))
+}
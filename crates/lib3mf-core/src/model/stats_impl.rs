@@ -1,27 +1,93 @@
 use crate::archive::ArchiveReader;
 use crate::error::Result;
 use crate::model::stats::{
-    DisplacementStats, FilamentInfo, GeometryStats, MaterialsStats, ModelStats, ProductionStats,
-    VendorData,
+    BoundingBox, DisplacementStats, GeometryStats, MaterialsStats, ModelStats, PlateGeometryStats,
+    ProductionStats, UniqueGeometryStats, VendorData,
 };
-use crate::model::{Geometry, Model};
+use crate::model::{Geometry, Mesh, Model, PreciseMesh, ResourceId, Unit};
+use crate::parser::ParseOptions;
+use crate::trace::{phase, span};
+use std::collections::{HashMap, HashSet};
+
+/// State carried through one walk of the build by [`Model::compute_stats`].
+#[derive(Default)]
+struct StatsWalk {
+    /// Decompressed parts holding lazily loaded meshes
+    lazy_parts: HashMap<String, Vec<u8>>,
+    /// Meshes already added to the unique totals, by part (`None` for the root) and ID
+    seen_meshes: HashSet<(Option<String>, ResourceId)>,
+    /// Addresses of the vertex and triangle arrays of the meshes counted as unique
+    unique_buffers: HashSet<usize>,
+}
+
+impl StatsWalk {
+    /// Counts the arrays of a mesh placed for the first time that another unique mesh
+    /// already holds. Only meshes owned by the resolver are tracked, since the buffers of
+    /// lazily loaded meshes are freed, and their addresses reused, once they are counted.
+    fn count_shared_buffers(&mut self, mesh: &Mesh, unique: &mut UniqueGeometryStats) {
+        let buffers = [
+            (
+                mesh.vertices.as_ptr() as usize,
+                size_of_val(mesh.vertices.as_slice()),
+            ),
+            (
+                mesh.triangles.as_ptr() as usize,
+                size_of_val(mesh.triangles.as_slice()),
+            ),
+        ];
+        for (address, bytes) in buffers {
+            if bytes > 0 && !self.unique_buffers.insert(address) {
+                unique.shared_buffers += 1;
+                unique.deduplicated_bytes += bytes as u64;
+            }
+        }
+    }
+}
+
+/// Component nesting depth beyond which [`Model::extents_in`] stops descending.
+const MAX_EXTENTS_DEPTH: u32 = 64;
 
 impl Model {
     /// Computes comprehensive statistics about the model, including geometry, materials, and vendor data.
     pub fn compute_stats(&self, archiver: &mut impl ArchiveReader) -> Result<ModelStats> {
-        let mut resolver = crate::model::resolver::PartResolver::new(archiver, self.clone());
+        self.compute_stats_with(archiver, &ParseOptions::default())
+    }
+
+    /// Like [`compute_stats`](Self::compute_stats), parsing the model parts referenced by
+    /// components with `options`.
+    ///
+    /// Pass the options the model was parsed with: with
+    /// [`intern_meshes`](ParseOptions::intern_meshes), meshes in other parts share arrays
+    /// with the model's own, and [`UniqueGeometryStats`] reports the shared arrays.
+    pub fn compute_stats_with(
+        &self,
+        archiver: &mut impl ArchiveReader,
+        options: &ParseOptions,
+    ) -> Result<ModelStats> {
+        let _phase = phase!(span::COMPUTE_STATS);
+        let mut resolver = crate::model::resolver::PartResolver::with_options(
+            archiver,
+            self.clone(),
+            options.clone(),
+        );
         let mut geom_stats = GeometryStats::default();
+        let mut walk = StatsWalk::default();
 
-        // 1. Process Build Items (Entry points)
+        // 1. Process Build Items (Entry points), keeping each item's geometry for the plates
+        let mut item_geometry: Vec<GeometryStats> = Vec::new();
         for item in &self.build.items {
-            geom_stats.instance_count += 1;
+            let mut item_stats = GeometryStats::default();
             self.accumulate_object_stats(
                 item.object_id,
                 item.path.as_deref(),
                 item.transform,
                 &mut resolver,
-                &mut geom_stats,
+                &mut walk,
+                &mut item_stats,
             )?;
+            geom_stats.instance_count += 1;
+            add_item_stats(&mut geom_stats, &item_stats);
+            item_geometry.push(item_stats);
         }
 
         // 2. Production Stats
@@ -30,137 +96,16 @@ impl Model {
         };
 
         // 3. Vendor Data (Bambu Studio / OrcaSlicer)
-        let mut vendor_data = VendorData::default();
-        let generator = self.metadata.get("Application").cloned();
-
+        let generator = self.metadata.get("Application").map(str::to_string);
         let is_bambu = generator
             .as_ref()
             .is_some_and(|app| app.contains("Bambu") || app.contains("Orca"));
-
-        if is_bambu {
-            let archive = resolver.archive_mut();
-
-            // 3a. Parse slice_info.config (slicer version, printer model, filaments, print time, warnings)
-            if archive.entry_exists("Metadata/slice_info.config")
-                && let Ok(content) = archive.read_entry("Metadata/slice_info.config")
-                && let Ok(slice_info) = crate::parser::bambu_config::parse_slice_info(&content)
-            {
-                vendor_data.slicer_version = slice_info.client_version.as_ref().map(|v| {
-                    let client = slice_info.client_type.as_deref().unwrap_or("BambuStudio");
-                    format!("{}-{}", client.replace(' ', ""), v)
-                });
-
-                // Aggregate print time and filaments across all plates
-                let mut total_time_secs: u32 = 0;
-                for plate in &slice_info.plates {
-                    if let Some(pred) = plate.prediction {
-                        total_time_secs += pred;
-                    }
-                    // Collect slicer warnings
-                    for w in &plate.warnings {
-                        vendor_data.slicer_warnings.push(w.clone());
-                    }
-                }
-
-                if total_time_secs > 0 {
-                    vendor_data.print_time_estimate = Some(format_duration(total_time_secs));
-                }
-
-                // Filaments from first plate (they are per-plate but typically same)
-                if let Some(first_plate) = slice_info.plates.first() {
-                    for f in &first_plate.filaments {
-                        vendor_data.filaments.push(FilamentInfo {
-                            id: f.id,
-                            tray_info_idx: f.tray_info_idx.clone(),
-                            type_: f.type_.clone().unwrap_or_default(),
-                            color: f.color.clone(),
-                            used_m: f.used_m,
-                            used_g: f.used_g,
-                        });
-                    }
-                }
-            }
-
-            // 3b. Parse model_settings.config (plates, objects, assembly)
-            if archive.entry_exists("Metadata/model_settings.config")
-                && let Ok(content) = archive.read_entry("Metadata/model_settings.config")
-                && let Ok(data) = crate::parser::bambu_config::parse_model_settings(&content)
-            {
-                vendor_data.plates = data.plates;
-                vendor_data.object_metadata = data.objects;
-                vendor_data.assembly_info = data.assembly;
-            }
-
-            // 3c. Parse project_settings.config (printer model, bed type, layer height, etc.)
-            if archive.entry_exists("Metadata/project_settings.config")
-                && let Ok(content) = archive.read_entry("Metadata/project_settings.config")
-                && let Ok(settings) = crate::parser::bambu_config::parse_project_settings(&content)
-            {
-                // Use project settings for printer model if not already set from slice_info
-                if vendor_data.printer_model.is_none() {
-                    vendor_data.printer_model = settings
-                        .printer_inherits
-                        .clone()
-                        .or_else(|| settings.printer_model.clone());
-                }
-                if vendor_data.nozzle_diameter.is_none() {
-                    vendor_data.nozzle_diameter = settings.nozzle_diameter.first().copied();
-                }
-                vendor_data.project_settings = Some(settings);
-            }
-
-            // 3d. Parse per-profile configs (filament_settings_N.config, machine_settings_N.config, process_settings_N.config)
-            for config_type in &["filament", "machine", "process"] {
-                for n in 0u32..16 {
-                    let path = format!("Metadata/{}_settings_{}.config", config_type, n);
-                    if archive.entry_exists(&path)
-                        && let Ok(content) = archive.read_entry(&path)
-                        && let Ok(config) = crate::parser::bambu_config::parse_profile_config(
-                            &content,
-                            config_type,
-                            n,
-                        )
-                    {
-                        vendor_data.profile_configs.push(config);
-                    }
-                }
-            }
-
-            // Try to get printer model from machine profile if still not set
-            if vendor_data.printer_model.is_none()
-                && let Some(machine_config) = vendor_data
-                    .profile_configs
-                    .iter()
-                    .find(|c| c.config_type == "machine")
-            {
-                vendor_data.printer_model = machine_config
-                    .inherits
-                    .clone()
-                    .or_else(|| machine_config.name.clone());
-            }
-
-            // 3e. Read OPC relationships and identify Bambu-specific entries
-            if archive.entry_exists("_rels/.rels")
-                && let Ok(rels_data) = archive.read_entry("_rels/.rels")
-                && let Ok(rels) = crate::archive::opc::parse_relationships(&rels_data)
-            {
-                use crate::archive::opc::bambu_rel_types;
-                for rel in &rels {
-                    match rel.rel_type.as_str() {
-                        bambu_rel_types::COVER_THUMBNAIL_MIDDLE
-                        | bambu_rel_types::COVER_THUMBNAIL_SMALL => {
-                            if vendor_data.bambu_cover_thumbnail.is_none() {
-                                vendor_data.bambu_cover_thumbnail = Some(rel.target.clone());
-                            }
-                        }
-                        bambu_rel_types::GCODE => {
-                            vendor_data.bambu_gcode = Some(rel.target.clone());
-                        }
-                        _ => {}
-                    }
-                }
-            }
-        }
+        let vendor_data = if is_bambu {
+            read_vendor_data(resolver.archive_mut())
+        } else {
+            VendorData::default()
+        };
+        let plates = self.plate_geometry(&vendor_data, &item_geometry);
 
         // 4. Material Stats
         let materials_stats = MaterialsStats {
@@ -187,7 +132,12 @@ impl Model {
         Ok(ModelStats {
             unit: self.unit,
             generator,
-            metadata: self.metadata.clone(),
+            metadata: self
+                .metadata
+                .names()
+                .filter_map(|name| Some((name.to_string(), self.metadata.get(name)?.to_string())))
+                .collect(),
+            resource_counts: self.resources.counts(),
             geometry: geom_stats,
             materials: materials_stats,
             production: prod_stats,
@@ -198,9 +148,172 @@ impl Model {
                 package_thumbnail_present: pkg_thumb,
                 object_thumbnail_count: obj_thumb_count,
             },
+            build_items: self
+                .build
+                .items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| {
+                    item.part_number.is_some() || item.uuid.is_some() || !item.metadata.is_empty()
+                })
+                .map(|(i, item)| crate::model::stats::BuildItemStats {
+                    index: i + 1,
+                    object_id: item.object_id.0,
+                    part_number: item.part_number.clone(),
+                    uuid: item.uuid.map(|u| u.to_string()),
+                    metadata: item.metadata.clone(),
+                })
+                .collect(),
+            plates,
         })
     }
 
+    /// Groups the build items by the Bambu Studio plate they are placed on, and sums their
+    /// geometry.
+    ///
+    /// A plate lists object instances; the `instance_id`-th build item placing that object is
+    /// the instance. `item_geometry` holds the geometry of each build item.
+    fn plate_geometry(
+        &self,
+        vendor: &VendorData,
+        item_geometry: &[GeometryStats],
+    ) -> Vec<PlateGeometryStats> {
+        vendor
+            .plates
+            .iter()
+            .map(|plate| {
+                let mut stats = PlateGeometryStats {
+                    plate_id: plate.id,
+                    name: plate.name.clone(),
+                    ..Default::default()
+                };
+                for instance in &plate.items {
+                    let item = self
+                        .build
+                        .items
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, item)| item.object_id.0 == instance.object_id)
+                        .nth(instance.instance_id as usize);
+                    let Some((index, _)) = item else {
+                        continue;
+                    };
+                    let geometry = &item_geometry[index];
+                    stats.build_items.push(index + 1);
+                    stats.instance_count += 1;
+                    stats.vertex_count += geometry.vertex_count;
+                    stats.triangle_count += geometry.triangle_count;
+                    stats.volume += geometry.volume;
+                    if let Some(bbox) = &geometry.bounding_box {
+                        match &mut stats.bounding_box {
+                            Some(total) => total.union(bbox),
+                            None => stats.bounding_box = Some(*bbox),
+                        }
+                    }
+                }
+                stats.build_items.sort_unstable();
+                stats.build_items.dedup();
+                let mut objects: Vec<u32> = stats
+                    .build_items
+                    .iter()
+                    .map(|i| self.build.items[i - 1].object_id.0)
+                    .collect();
+                objects.sort_unstable();
+                objects.dedup();
+                stats.object_count = objects.len();
+                stats
+            })
+            .collect()
+    }
+
+    /// Computes the bounding box of all build items in the requested unit.
+    ///
+    /// Unlike [`compute_stats`](Self::compute_stats) this needs no archive: only
+    /// objects in this model are visited, and build items or components that
+    /// reference another package part (`path`) are skipped. Returns `None` if no
+    /// mesh geometry is reachable from the build.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib3mf_core::model::*;
+    ///
+    /// let mut mesh = Mesh::new();
+    /// mesh.add_vertex(0.0, 0.0, 0.0);
+    /// mesh.add_vertex(10.0, 10.0, 10.0);
+    ///
+    /// let mut model = Model::default();
+    /// model.resources.add_object(Object {
+    ///     id: ResourceId(1),
+    ///     object_type: ObjectType::Model,
+    ///     name: None,
+    ///     part_number: None,
+    ///     uuid: None,
+    ///     pid: None,
+    ///     pindex: None,
+    ///     thumbnail: None,
+    ///     geometry: Geometry::Mesh(mesh),
+    /// }).unwrap();
+    /// model.build.items.push(BuildItem {
+    ///     object_id: ResourceId(1),
+    ///     uuid: None,
+    ///     path: None,
+    ///     part_number: None,
+    ///     transform: glam::Mat4::IDENTITY,
+    ///     printable: None,
+    ///     metadata: Default::default(),
+    /// });
+    ///
+    /// let bbox = model.extents_in(Unit::Centimeter).unwrap();
+    /// assert_eq!(bbox.max, [1.0, 1.0, 1.0]);
+    /// ```
+    pub fn extents_in(&self, unit: Unit) -> Option<BoundingBox> {
+        let mut total: Option<BoundingBox> = None;
+        for item in self.build.items.iter().filter(|i| i.path.is_none()) {
+            self.accumulate_local_extents(item.object_id, item.transform, 0, &mut total);
+        }
+        total.map(|b| b.convert(self.unit, unit))
+    }
+
+    fn accumulate_local_extents(
+        &self,
+        id: ResourceId,
+        transform: glam::Mat4,
+        depth: u32,
+        total: &mut Option<BoundingBox>,
+    ) {
+        // Guards against component cycles, which validation reports separately.
+        if depth > MAX_EXTENTS_DEPTH {
+            return;
+        }
+        let Some(object) = self.resources.get_object(id) else {
+            return;
+        };
+        let aabb = match &object.geometry {
+            Geometry::Mesh(mesh) => mesh.compute_aabb(),
+            Geometry::PreciseMesh(mesh) => mesh.compute_aabb(),
+            Geometry::Components(comps) => {
+                for comp in comps.components.iter().filter(|c| c.path.is_none()) {
+                    self.accumulate_local_extents(
+                        comp.object_id,
+                        transform * comp.transform,
+                        depth + 1,
+                        total,
+                    );
+                }
+                None
+            }
+            _ => None,
+        };
+        if let Some(aabb) = aabb {
+            let aabb = aabb.transform(transform);
+            match total {
+                Some(t) => t.union(&aabb),
+                None => *total = Some(aabb),
+            }
+        }
+    }
+
     fn compute_displacement_stats(&self) -> DisplacementStats {
         let mut stats = DisplacementStats {
             texture_count: self.resources.displacement_2d_count(),
@@ -233,6 +346,7 @@ impl Model {
         path: Option<&str>,
         transform: glam::Mat4,
         resolver: &mut crate::model::resolver::PartResolver<impl ArchiveReader>,
+        walk: &mut StatsWalk,
         stats: &mut GeometryStats,
     ) -> Result<()> {
         let (geom, path_to_use, obj_type) = {
@@ -266,31 +380,29 @@ impl Model {
                 *stats.type_counts.entry(ot.to_string()).or_insert(0) += 1;
             }
 
+            let first_placement = matches!(
+                geometry,
+                Geometry::Mesh(_) | Geometry::LazyMesh(_) | Geometry::PreciseMesh(_)
+            ) && walk.seen_meshes.insert((path_to_use.clone(), id));
             match geometry {
                 Geometry::Mesh(mesh) => {
-                    stats.object_count += 1;
-                    stats.vertex_count += mesh.vertices.len() as u64;
-                    stats.triangle_count += mesh.triangles.len() as u64;
-
-                    if let Some(mesh_aabb) = mesh.compute_aabb() {
-                        let transformed_aabb = mesh_aabb.transform(transform);
-                        if let Some(total_aabb) = &mut stats.bounding_box {
-                            total_aabb.min[0] = total_aabb.min[0].min(transformed_aabb.min[0]);
-                            total_aabb.min[1] = total_aabb.min[1].min(transformed_aabb.min[1]);
-                            total_aabb.min[2] = total_aabb.min[2].min(transformed_aabb.min[2]);
-                            total_aabb.max[0] = total_aabb.max[0].max(transformed_aabb.max[0]);
-                            total_aabb.max[1] = total_aabb.max[1].max(transformed_aabb.max[1]);
-                            total_aabb.max[2] = total_aabb.max[2].max(transformed_aabb.max[2]);
-                        } else {
-                            stats.bounding_box = Some(transformed_aabb);
-                        }
+                    if first_placement {
+                        walk.count_shared_buffers(&mesh, &mut stats.unique);
                     }
-
-                    let (area, volume) = mesh.compute_area_and_volume();
-                    let scale_det = transform.determinant().abs() as f64;
-                    let area_scale = scale_det.powf(2.0 / 3.0);
-                    stats.surface_area += area * area_scale;
-                    stats.volume += volume * scale_det;
+                    accumulate_mesh_stats(&mesh, transform, first_placement, stats)
+                }
+                Geometry::PreciseMesh(mesh) => {
+                    accumulate_mesh_stats(&mesh, transform, first_placement, stats)
+                }
+                Geometry::LazyMesh(lazy) => {
+                    // Parse one mesh at a time so peak memory stays near the largest mesh;
+                    // each part is decompressed only once.
+                    if !walk.lazy_parts.contains_key(&lazy.part) {
+                        let data = resolver.archive_mut().read_entry(&lazy.part)?;
+                        walk.lazy_parts.insert(lazy.part.clone(), data);
+                    }
+                    let mesh = lazy.load_from(&walk.lazy_parts[&lazy.part])?;
+                    accumulate_mesh_stats(&mesh, transform, first_placement, stats);
                 }
                 Geometry::Components(comps) => {
                     for comp in comps.components {
@@ -304,6 +416,7 @@ impl Model {
                             next_path,
                             transform * comp.transform,
                             resolver,
+                            walk,
                             stats,
                         )?;
                     }
@@ -315,6 +428,240 @@ impl Model {
     }
 }
 
+/// Reads Bambu Studio project data from the package's `Metadata/*.config` parts and
+/// relationships.
+#[cfg(feature = "vendor")]
+fn read_vendor_data(archive: &mut impl ArchiveReader) -> VendorData {
+    use crate::model::stats::FilamentInfo;
+
+    let mut vendor_data = VendorData::default();
+
+    // 3a. Parse slice_info.config (slicer version, printer model, filaments, print time, warnings)
+    if archive.entry_exists("Metadata/slice_info.config")
+        && let Ok(content) = archive.read_entry("Metadata/slice_info.config")
+        && let Ok(slice_info) = crate::parser::bambu_config::parse_slice_info(&content)
+    {
+        vendor_data.slicer_version = slice_info.client_version.as_ref().map(|v| {
+            let client = slice_info.client_type.as_deref().unwrap_or("BambuStudio");
+            format!("{}-{}", client.replace(' ', ""), v)
+        });
+
+        // Aggregate print time and filaments across all plates
+        let mut total_time_secs: u32 = 0;
+        for plate in &slice_info.plates {
+            if let Some(pred) = plate.prediction {
+                total_time_secs += pred;
+            }
+            // Collect slicer warnings
+            for w in &plate.warnings {
+                vendor_data.slicer_warnings.push(w.clone());
+            }
+        }
+
+        if total_time_secs > 0 {
+            vendor_data.print_time_estimate = Some(format_duration(total_time_secs));
+        }
+
+        // Filaments from first plate (they are per-plate but typically same)
+        if let Some(first_plate) = slice_info.plates.first() {
+            for f in &first_plate.filaments {
+                vendor_data.filaments.push(FilamentInfo {
+                    id: f.id,
+                    tray_info_idx: f.tray_info_idx.clone(),
+                    type_: f.type_.clone().unwrap_or_default(),
+                    color: f.color.clone(),
+                    used_m: f.used_m,
+                    used_g: f.used_g,
+                });
+            }
+        }
+    }
+
+    // 3b. Parse model_settings.config (plates, objects, assembly)
+    if archive.entry_exists("Metadata/model_settings.config")
+        && let Ok(content) = archive.read_entry("Metadata/model_settings.config")
+        && let Ok(data) = crate::parser::bambu_config::parse_model_settings(&content)
+    {
+        vendor_data.plates = data.plates;
+        vendor_data.object_metadata = data.objects;
+        vendor_data.assembly_info = data.assembly;
+    }
+
+    // 3c. Parse project_settings.config (printer model, bed type, layer height, etc.)
+    if archive.entry_exists("Metadata/project_settings.config")
+        && let Ok(content) = archive.read_entry("Metadata/project_settings.config")
+        && let Ok(settings) = crate::parser::bambu_config::parse_project_settings(&content)
+    {
+        // Use project settings for printer model if not already set from slice_info
+        if vendor_data.printer_model.is_none() {
+            vendor_data.printer_model = settings
+                .printer_inherits
+                .clone()
+                .or_else(|| settings.printer_model.clone());
+        }
+        if vendor_data.nozzle_diameter.is_none() {
+            vendor_data.nozzle_diameter = settings.nozzle_diameter.first().copied();
+        }
+        vendor_data.project_settings = Some(settings);
+    }
+
+    // 3d. Parse per-profile configs (filament_settings_N.config, machine_settings_N.config, process_settings_N.config)
+    for config_type in &["filament", "machine", "process"] {
+        for n in 0u32..16 {
+            let path = format!("Metadata/{}_settings_{}.config", config_type, n);
+            if archive.entry_exists(&path)
+                && let Ok(content) = archive.read_entry(&path)
+                && let Ok(config) =
+                    crate::parser::bambu_config::parse_profile_config(&content, config_type, n)
+            {
+                vendor_data.profile_configs.push(config);
+            }
+        }
+    }
+
+    // Try to get printer model from machine profile if still not set
+    if vendor_data.printer_model.is_none()
+        && let Some(machine_config) = vendor_data
+            .profile_configs
+            .iter()
+            .find(|c| c.config_type == "machine")
+    {
+        vendor_data.printer_model = machine_config
+            .inherits
+            .clone()
+            .or_else(|| machine_config.name.clone());
+    }
+
+    // 3e. Read OPC relationships and identify Bambu-specific entries
+    if archive.entry_exists("_rels/.rels")
+        && let Ok(rels_data) = archive.read_entry("_rels/.rels")
+        && let Ok(rels) = crate::archive::opc::parse_relationships(&rels_data)
+    {
+        use crate::archive::opc::bambu_rel_types;
+        for rel in &rels {
+            match rel.rel_type.as_str() {
+                bambu_rel_types::COVER_THUMBNAIL_MIDDLE
+                | bambu_rel_types::COVER_THUMBNAIL_SMALL => {
+                    if vendor_data.bambu_cover_thumbnail.is_none() {
+                        vendor_data.bambu_cover_thumbnail = Some(rel.target.clone());
+                    }
+                }
+                bambu_rel_types::GCODE => {
+                    vendor_data.bambu_gcode = Some(rel.target.clone());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    vendor_data
+}
+
+/// Without the `vendor` feature, vendor project data is not read.
+#[cfg(not(feature = "vendor"))]
+fn read_vendor_data(_archive: &mut impl ArchiveReader) -> VendorData {
+    VendorData::default()
+}
+
+/// Adds the geometry of one build item to the model totals.
+fn add_item_stats(total: &mut GeometryStats, item: &GeometryStats) {
+    total.object_count += item.object_count;
+    total.vertex_count += item.vertex_count;
+    total.triangle_count += item.triangle_count;
+    total.surface_area += item.surface_area;
+    total.volume += item.volume;
+    if let Some(bbox) = &item.bounding_box {
+        match &mut total.bounding_box {
+            Some(t) => t.union(bbox),
+            None => total.bounding_box = Some(*bbox),
+        }
+    }
+    for (object_type, count) in &item.type_counts {
+        *total.type_counts.entry(object_type.clone()).or_insert(0) += count;
+    }
+
+    let unique = &mut total.unique;
+    unique.mesh_count += item.unique.mesh_count;
+    unique.vertex_count += item.unique.vertex_count;
+    unique.triangle_count += item.unique.triangle_count;
+    unique.surface_area += item.unique.surface_area;
+    unique.volume += item.unique.volume;
+    unique.shared_buffers += item.unique.shared_buffers;
+    unique.deduplicated_bytes += item.unique.deduplicated_bytes;
+}
+
+/// Adds one mesh instance, placed by `transform`, to the geometry totals, and to the unique
+/// totals if it is the mesh's first placement.
+/// Counts, bounds and measures of a mesh, whichever precision its coordinates have.
+trait MeasuredMesh {
+    fn counts(&self) -> (usize, usize);
+    fn aabb(&self) -> Option<BoundingBox>;
+    fn area_and_volume(&self) -> (f64, f64);
+}
+
+impl MeasuredMesh for Mesh {
+    fn counts(&self) -> (usize, usize) {
+        (self.vertices.len(), self.triangles.len())
+    }
+
+    fn aabb(&self) -> Option<BoundingBox> {
+        self.compute_aabb()
+    }
+
+    fn area_and_volume(&self) -> (f64, f64) {
+        self.compute_area_and_volume()
+    }
+}
+
+impl MeasuredMesh for PreciseMesh {
+    fn counts(&self) -> (usize, usize) {
+        (self.vertices.len(), self.triangles.len())
+    }
+
+    fn aabb(&self) -> Option<BoundingBox> {
+        self.compute_aabb()
+    }
+
+    fn area_and_volume(&self) -> (f64, f64) {
+        self.compute_area_and_volume()
+    }
+}
+
+fn accumulate_mesh_stats(
+    mesh: &impl MeasuredMesh,
+    transform: glam::Mat4,
+    first_placement: bool,
+    stats: &mut GeometryStats,
+) {
+    let (vertex_count, triangle_count) = mesh.counts();
+    stats.object_count += 1;
+    stats.vertex_count += vertex_count as u64;
+    stats.triangle_count += triangle_count as u64;
+
+    if let Some(mesh_aabb) = mesh.aabb() {
+        let transformed_aabb = mesh_aabb.transform(transform);
+        if let Some(total_aabb) = &mut stats.bounding_box {
+            total_aabb.union(&transformed_aabb);
+        } else {
+            stats.bounding_box = Some(transformed_aabb);
+        }
+    }
+
+    let (area, volume) = mesh.area_and_volume();
+    if first_placement {
+        let unique = &mut stats.unique;
+        unique.mesh_count += 1;
+        unique.vertex_count += vertex_count as u64;
+        unique.triangle_count += triangle_count as u64;
+        unique.surface_area += area;
+        unique.volume += volume;
+    }
+    let scale_det = transform.determinant().abs() as f64;
+    let area_scale = scale_det.powf(2.0 / 3.0);
+    stats.surface_area += area * area_scale;
+    stats.volume += volume * scale_det;
+}
+
 /// Format seconds as human-readable duration (e.g., "31m 35s", "2h 15m 3s").
 pub fn format_duration(total_secs: u32) -> String {
     let hours = total_secs / 3600;
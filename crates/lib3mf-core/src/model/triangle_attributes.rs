@@ -0,0 +1,289 @@
+//! Per-triangle attribute channels, such as slicer paint data.
+//!
+//! Slicers store painted regions as extra attributes on `<triangle>` elements. PrusaSlicer
+//! writes `slic3rpe:custom_supports`, `slic3rpe:custom_seam` and `slic3rpe:mmu_segmentation`;
+//! Bambu Studio writes `paint_supports`, `paint_seam` and `paint_color`.
+//! [`TriangleAttributes`] keeps every triangle attribute the core specification doesn't
+//! define, under its name as written, so this data survives a parse/write round trip.
+//!
+//! [`PaintVendor`] and [`PaintChannel`] map the vendor attribute names onto common channels.
+//! Both vendors encode a value as a hex string of a bit stream describing how the triangle
+//! was subdivided for painting and the state of each piece; [`decode_paint_state`] and
+//! [`encode_paint_state`] handle triangles painted as a whole.
+//!
+//! # Examples
+//!
+//! ```
+//! use lib3mf_core::model::{PaintChannel, PaintVendor, TriangleAttributes};
+//!
+//! let mut attributes = TriangleAttributes::new();
+//! attributes.set("slic3rpe:custom_supports", 7, "4");
+//! assert_eq!(
+//!     attributes.paint_state(PaintVendor::Prusa, PaintChannel::Supports, 7),
+//!     Some(1) // support enforcer
+//! );
+//!
+//! attributes.convert_paint(PaintVendor::Prusa, PaintVendor::Bambu);
+//! assert_eq!(attributes.get("paint_supports", 7), Some("4"));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Values of non-standard triangle attributes, by attribute name and triangle index.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TriangleAttributes {
+    channels: BTreeMap<String, BTreeMap<u32, String>>,
+}
+
+impl TriangleAttributes {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if no triangle has an attribute.
+    pub fn is_empty(&self) -> bool {
+        self.channels.is_empty()
+    }
+
+    /// Approximate heap bytes held by names, keys and values, ignoring tree node overhead.
+    pub fn heap_bytes(&self) -> usize {
+        self.channels
+            .iter()
+            .map(|(name, values)| {
+                name.capacity()
+                    + values
+                        .values()
+                        .map(|v| size_of::<(u32, String)>() + v.capacity())
+                        .sum::<usize>()
+            })
+            .sum()
+    }
+
+    /// Returns the value of attribute `name` on the triangle at `triangle`.
+    pub fn get(&self, name: &str, triangle: u32) -> Option<&str> {
+        self.channels
+            .get(name)
+            .and_then(|values| values.get(&triangle))
+            .map(String::as_str)
+    }
+
+    /// Sets attribute `name` on the triangle at `triangle`.
+    pub fn set(&mut self, name: impl Into<String>, triangle: u32, value: impl Into<String>) {
+        self.channels
+            .entry(name.into())
+            .or_default()
+            .insert(triangle, value.into());
+    }
+
+    /// Removes attribute `name` from the triangle at `triangle`, returning its value.
+    pub fn remove(&mut self, name: &str, triangle: u32) -> Option<String> {
+        let values = self.channels.get_mut(name)?;
+        let value = values.remove(&triangle);
+        if values.is_empty() {
+            self.channels.remove(name);
+        }
+        value
+    }
+
+    /// Returns all values of attribute `name`, by triangle index.
+    pub fn channel(&self, name: &str) -> Option<&BTreeMap<u32, String>> {
+        self.channels.get(name)
+    }
+
+    /// Iterates over attribute names and their values, sorted by name.
+    pub fn channels(&self) -> impl Iterator<Item = (&str, &BTreeMap<u32, String>)> {
+        self.channels
+            .iter()
+            .map(|(name, values)| (name.as_str(), values))
+    }
+
+    /// Removes attribute `name` from all triangles, returning its values.
+    pub fn remove_channel(&mut self, name: &str) -> Option<BTreeMap<u32, String>> {
+        self.channels.remove(name)
+    }
+
+    /// Renames attribute `from` to `to`. Values already stored under `to` are replaced
+    /// for triangles that have both.
+    pub fn rename_channel(&mut self, from: &str, to: &str) {
+        if from == to {
+            return;
+        }
+        if let Some(values) = self.channels.remove(from) {
+            self.channels
+                .entry(to.to_string())
+                .or_default()
+                .extend(values);
+        }
+    }
+
+    /// Iterates over the attributes of the triangle at `triangle`, sorted by name.
+    pub fn of_triangle(&self, triangle: u32) -> impl Iterator<Item = (&str, &str)> {
+        self.channels.iter().filter_map(move |(name, values)| {
+            values
+                .get(&triangle)
+                .map(|value| (name.as_str(), value.as_str()))
+        })
+    }
+
+    /// Updates triangle indices after triangles were removed or reordered.
+    ///
+    /// `remap[old]` is the new index of triangle `old`, or `None` if it was removed.
+    pub fn remap(&mut self, remap: &[Option<u32>]) {
+        for values in self.channels.values_mut() {
+            *values = std::mem::take(values)
+                .into_iter()
+                .filter_map(|(t, value)| Some((remap.get(t as usize).copied().flatten()?, value)))
+                .collect();
+        }
+        self.channels.retain(|_, values| !values.is_empty());
+    }
+
+    /// Returns the decoded paint state of a triangle painted as a whole. See
+    /// [`decode_paint_state`].
+    pub fn paint_state(
+        &self,
+        vendor: PaintVendor,
+        channel: PaintChannel,
+        triangle: u32,
+    ) -> Option<u8> {
+        let name = vendor.attribute_name(channel)?;
+        decode_paint_state(self.get(name, triangle)?)
+    }
+
+    /// Paints the whole triangle at `triangle` with `state`, or clears its paint for
+    /// state 0. Does nothing if `vendor` has no attribute for `channel`.
+    pub fn set_paint_state(
+        &mut self,
+        vendor: PaintVendor,
+        channel: PaintChannel,
+        triangle: u32,
+        state: u8,
+    ) {
+        let Some(name) = vendor.attribute_name(channel) else {
+            return;
+        };
+        if state == 0 {
+            self.remove(name, triangle);
+        } else {
+            self.set(name, triangle, encode_paint_state(state));
+        }
+    }
+
+    /// Renames the paint attributes of `from` to those of `to`, e.g. to open a PrusaSlicer
+    /// project's painting in Bambu Studio. Channels `to` has no attribute for are kept.
+    pub fn convert_paint(&mut self, from: PaintVendor, to: PaintVendor) {
+        for channel in PaintChannel::ALL {
+            if let (Some(source), Some(target)) =
+                (from.attribute_name(channel), to.attribute_name(channel))
+            {
+                self.rename_channel(source, target);
+            }
+        }
+    }
+}
+
+/// Kind of slicer paint data.
+///
+/// Paint states are 0 for unpainted. For supports and seams 1 is an enforcer and 2 a
+/// blocker; for multi-material painting the state is the extruder number; for fuzzy skin
+/// 1 means painted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PaintChannel {
+    /// Support enforcers and blockers.
+    Supports,
+    /// Seam enforcers and blockers.
+    Seam,
+    /// Multi-material (color) painting.
+    MultiMaterial,
+    /// Fuzzy skin painting.
+    FuzzySkin,
+}
+
+impl PaintChannel {
+    /// All channels.
+    pub const ALL: [PaintChannel; 4] = [
+        PaintChannel::Supports,
+        PaintChannel::Seam,
+        PaintChannel::MultiMaterial,
+        PaintChannel::FuzzySkin,
+    ];
+}
+
+/// Slicer whose triangle attribute names are used for paint data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PaintVendor {
+    /// PrusaSlicer (`slic3rpe:` attributes).
+    Prusa,
+    /// Bambu Studio and its forks (`paint_` attributes).
+    Bambu,
+}
+
+impl PaintVendor {
+    /// Returns the triangle attribute holding `channel`, if this vendor stores it.
+    pub fn attribute_name(self, channel: PaintChannel) -> Option<&'static str> {
+        match (self, channel) {
+            (PaintVendor::Prusa, PaintChannel::Supports) => Some("slic3rpe:custom_supports"),
+            (PaintVendor::Prusa, PaintChannel::Seam) => Some("slic3rpe:custom_seam"),
+            (PaintVendor::Prusa, PaintChannel::MultiMaterial) => Some("slic3rpe:mmu_segmentation"),
+            (PaintVendor::Prusa, PaintChannel::FuzzySkin) => Some("slic3rpe:fuzzy_skin"),
+            (PaintVendor::Bambu, PaintChannel::Supports) => Some("paint_supports"),
+            (PaintVendor::Bambu, PaintChannel::Seam) => Some("paint_seam"),
+            (PaintVendor::Bambu, PaintChannel::MultiMaterial) => Some("paint_color"),
+            (PaintVendor::Bambu, PaintChannel::FuzzySkin) => None,
+        }
+    }
+}
+
+/// Highest state [`encode_paint_state`] can represent.
+pub const MAX_PAINT_STATE: u8 = 18;
+
+/// Decodes a paint value for a triangle painted as a whole.
+///
+/// The value is a hex string read from the last character to the first, each digit giving
+/// four bits least significant first. A triangle starts with two bits of split count
+/// followed, when unsplit, by two bits of state, where `3` means four more bits hold
+/// `state - 3`. Returns `None` for subdivided triangles (painted in parts) and malformed
+/// values.
+pub fn decode_paint_state(value: &str) -> Option<u8> {
+    let mut bits = Vec::with_capacity(value.len() * 4);
+    for c in value.chars().rev() {
+        let digit = c.to_digit(16)?;
+        bits.extend((0..4).map(|i| digit & (1 << i) != 0));
+    }
+    let mut bits = bits.into_iter();
+    let mut read = |n: u32| -> Option<u8> {
+        (0..n).try_fold(0u8, |acc, i| Some(acc | (u8::from(bits.next()?) << i)))
+    };
+    if read(2)? != 0 {
+        return None;
+    }
+    let mut state = read(2)?;
+    if state == 3 {
+        state += read(4)?;
+    }
+    // Anything after the leaf is padding
+    (!bits.any(|bit| bit)).then_some(state)
+}
+
+/// Encodes the paint value for a triangle painted as a whole with `state`.
+///
+/// # Panics
+///
+/// Panics if `state` is greater than [`MAX_PAINT_STATE`].
+pub fn encode_paint_state(state: u8) -> String {
+    assert!(
+        state <= MAX_PAINT_STATE,
+        "paint state {} exceeds {}",
+        state,
+        MAX_PAINT_STATE
+    );
+    if state < 3 {
+        // Bits: split count 00, then the state
+        format!("{:X}", state << 2)
+    } else {
+        // Bits: split count 00, state 11, then four bits of state - 3
+        format!("{:X}C", state - 3)
+    }
+}
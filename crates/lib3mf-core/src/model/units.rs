@@ -1,4 +1,7 @@
+use crate::error::{Lib3mfError, Result};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 
 /// Units of measurement for the 3MF model.
 ///
@@ -7,6 +10,8 @@ use serde::{Deserialize, Serialize};
 /// (vertices, transformations, radii, etc.).
 ///
 /// Per the 3MF specification, the default unit is Millimeter if not specified.
+/// The spec spells micrometers as `"micron"`; that is the only accepted
+/// spelling on parse and the one always written (see [`Unit::as_str`]).
 ///
 /// # Examples
 ///
@@ -27,7 +32,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum Unit {
-    /// Micrometers - 0.000001 meters (1 μm)
+    /// Micrometers - 0.000001 meters (1 μm), spelled `"micron"` in 3MF XML
     Micron,
     /// Millimeters - 0.001 meters (1 mm) - Default per 3MF spec
     #[default]
@@ -43,6 +48,49 @@ pub enum Unit {
 }
 
 impl Unit {
+    /// All units defined by the 3MF Core Specification, smallest first.
+    pub const ALL: [Unit; 6] = [
+        Unit::Micron,
+        Unit::Millimeter,
+        Unit::Centimeter,
+        Unit::Inch,
+        Unit::Foot,
+        Unit::Meter,
+    ];
+
+    /// Returns the exact spelling of this unit in the 3MF `unit` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib3mf_core::model::Unit;
+    ///
+    /// assert_eq!(Unit::Micron.as_str(), "micron");
+    /// assert_eq!("micron".parse::<Unit>().unwrap(), Unit::Micron);
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Unit::Micron => "micron",
+            Unit::Millimeter => "millimeter",
+            Unit::Centimeter => "centimeter",
+            Unit::Inch => "inch",
+            Unit::Foot => "foot",
+            Unit::Meter => "meter",
+        }
+    }
+
+    /// Returns the conventional short symbol for this unit (e.g. `"mm"`, `"µm"`).
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Unit::Micron => "µm",
+            Unit::Millimeter => "mm",
+            Unit::Centimeter => "cm",
+            Unit::Inch => "in",
+            Unit::Foot => "ft",
+            Unit::Meter => "m",
+        }
+    }
+
     /// Returns the scale factor to convert this unit to meters.
     ///
     /// # Examples
@@ -95,6 +143,126 @@ impl Unit {
         let meters = value * self.scale_factor();
         meters / target.scale_factor()
     }
+
+    /// Converts an area (square units) from this unit to another target unit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib3mf_core::model::Unit;
+    ///
+    /// // 1 cm² = 100 mm²
+    /// let mm2 = Unit::Centimeter.convert_area(1.0, Unit::Millimeter);
+    /// assert!((mm2 - 100.0).abs() < 1e-9);
+    /// ```
+    pub fn convert_area(&self, value: f64, target: Unit) -> f64 {
+        if *self == target {
+            return value;
+        }
+        let ratio = self.scale_factor() / target.scale_factor();
+        value * ratio * ratio
+    }
+
+    /// Converts a volume (cubic units) from this unit to another target unit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib3mf_core::model::Unit;
+    ///
+    /// // 1 cm³ = 1000 mm³
+    /// let mm3 = Unit::Centimeter.convert_volume(1.0, Unit::Millimeter);
+    /// assert!((mm3 - 1000.0).abs() < 1e-9);
+    /// ```
+    pub fn convert_volume(&self, value: f64, target: Unit) -> f64 {
+        if *self == target {
+            return value;
+        }
+        let ratio = self.scale_factor() / target.scale_factor();
+        value * ratio * ratio * ratio
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Unit {
+    type Err = Lib3mfError;
+
+    /// Parses a 3MF `unit` attribute value.
+    ///
+    /// Only the exact spellings from the specification are accepted
+    /// (`micron`, `millimeter`, `centimeter`, `inch`, `foot`, `meter`).
+    fn from_str(s: &str) -> Result<Self> {
+        Unit::ALL
+            .into_iter()
+            .find(|u| u.as_str() == s)
+            .ok_or_else(|| {
+                Lib3mfError::Validation(format!(
+                    "Invalid unit '{}'. Expected one of: micron, millimeter, centimeter, inch, foot, meter",
+                    s
+                ))
+            })
+    }
+}
+
+/// A scalar length tagged with the unit it is expressed in.
+///
+/// Raw `f32`/`f64` coordinates are only meaningful together with the model's
+/// [`Unit`]. `Length` keeps the two together so conversions are explicit.
+///
+/// # Examples
+///
+/// ```
+/// use lib3mf_core::model::{Length, Unit};
+///
+/// let width = Length::new(25.4, Unit::Millimeter);
+/// assert!((width.to(Unit::Inch).value - 1.0).abs() < 1e-9);
+/// assert_eq!(width.to_string(), "25.4 mm");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Length {
+    /// Magnitude in `unit`.
+    pub value: f64,
+    /// Unit the magnitude is expressed in.
+    pub unit: Unit,
+}
+
+impl Length {
+    /// Creates a new length.
+    pub fn new(value: f64, unit: Unit) -> Self {
+        Self { value, unit }
+    }
+
+    /// Returns this length expressed in `target` units.
+    pub fn to(self, target: Unit) -> Self {
+        Self {
+            value: self.unit.convert(self.value, target),
+            unit: target,
+        }
+    }
+
+    /// Returns the magnitude of this length in `target` units.
+    pub fn value_in(self, target: Unit) -> f64 {
+        self.unit.convert(self.value, target)
+    }
+
+    /// Returns the magnitude of this length in meters.
+    pub fn meters(self) -> f64 {
+        self.value * self.unit.scale_factor()
+    }
+}
+
+impl fmt::Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.precision() {
+            Some(p) => write!(f, "{:.*} {}", p, self.value, self.unit.symbol()),
+            None => write!(f, "{} {}", self.value, self.unit.symbol()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -117,4 +285,55 @@ mod tests {
         let val = Unit::Millimeter.convert(1000.0, Unit::Meter);
         assert!((val - 1.0).abs() < 1e-5);
     }
+
+    #[test]
+    fn test_unit_string_roundtrip() {
+        for unit in Unit::ALL {
+            assert_eq!(unit.as_str().parse::<Unit>().unwrap(), unit);
+        }
+        assert!("MicroMeter".parse::<Unit>().is_err());
+        assert!("micrometer".parse::<Unit>().is_err());
+        assert!("Millimeter".parse::<Unit>().is_err());
+    }
+
+    #[test]
+    fn test_serde_uses_attribute_spelling() {
+        for unit in Unit::ALL {
+            let json = serde_json::to_string(&unit).unwrap();
+            assert_eq!(json, format!("\"{}\"", unit.as_str()));
+            assert_eq!(serde_json::from_str::<Unit>(&json).unwrap(), unit);
+        }
+    }
+
+    #[test]
+    fn test_conversion_between_all_units() {
+        // One meter expressed in each unit
+        let per_meter = [1e6, 1000.0, 100.0, 1.0 / 0.0254, 1.0 / 0.3048, 1.0];
+        for (unit, count) in Unit::ALL.into_iter().zip(per_meter) {
+            assert!((Unit::Meter.convert(1.0, unit) - count).abs() < count * 1e-12);
+            assert!((unit.convert(count, Unit::Meter) - 1.0).abs() < 1e-12);
+            assert!(
+                (Unit::Meter.convert_area(1.0, unit) - count * count).abs() < count * count * 1e-12
+            );
+            let cubed = count * count * count;
+            assert!((Unit::Meter.convert_volume(1.0, unit) - cubed).abs() < cubed * 1e-12);
+        }
+
+        for from in Unit::ALL {
+            for to in Unit::ALL {
+                let back = to.convert(from.convert(12.5, to), from);
+                assert!((back - 12.5).abs() < 1e-9, "{from} -> {to} -> {from}");
+            }
+        }
+        assert!((Unit::Micron.convert(25_400.0, Unit::Inch) - 1.0).abs() < 1e-12);
+        assert!((Unit::Foot.convert(1.0, Unit::Inch) - 12.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_length_conversion() {
+        let l = Length::new(1500.0, Unit::Micron);
+        assert!((l.value_in(Unit::Millimeter) - 1.5).abs() < 1e-9);
+        assert!((l.meters() - 0.0015).abs() < 1e-12);
+        assert_eq!(format!("{:.1}", l.to(Unit::Millimeter)), "1.5 mm");
+    }
 }
@@ -1,4 +1,13 @@
-use crate::model::ResourceId;
+//! Volumetric Extension types.
+//!
+//! The original layer-stack model ([`VolumetricStack`]) is kept for older files. Current
+//! files describe volumes with functions: an [`Image3D`] sampled by a
+//! [`FunctionFromImage3D`], or an [`ImplicitFunction`] node graph. A [`LevelSet`] object
+//! turns a function into a shape, and [`VolumeData`] assigns color, materials and
+//! properties inside a mesh or level set.
+
+use crate::model::{FilterMode, ImplicitFunction, ResourceId, TileStyle};
+use glam::Mat4;
 use serde::{Deserialize, Serialize};
 
 /// Represents a stack of volumetric layers, defining a 3D volume via slices.
@@ -35,3 +44,231 @@ pub struct VolumetricRef {
     /// Package path to the model part containing the referenced volumetric stack.
     pub path: String, // Path to the other model file
 }
+
+/// A `<vol:image3d>` resource: a 3D image stored as a stack of 2D sheets (PNG parts).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Image3D {
+    /// Unique resource ID.
+    pub id: ResourceId,
+    /// Optional name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The image stack holding the voxel data.
+    pub stack: ImageStack,
+}
+
+/// The `<vol:imagestack>` of an [`Image3D`]: `sheet_count` images of equal size.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ImageStack {
+    /// Number of pixel rows per sheet.
+    pub row_count: u32,
+    /// Number of pixel columns per sheet.
+    pub column_count: u32,
+    /// Number of sheets as declared by `sheetcount`.
+    pub sheet_count: u32,
+    /// Package paths of the sheet images, bottom to top.
+    pub sheets: Vec<String>,
+}
+
+/// A `<vol:functionfromimage3d>` resource: samples an [`Image3D`] as a function of
+/// normalized `uvw` coordinates, with outputs `color` and `red`/`green`/`blue`/`alpha`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FunctionFromImage3D {
+    /// Unique resource ID.
+    pub id: ResourceId,
+    /// Human-readable name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// ID of the sampled [`Image3D`].
+    pub image3d_id: ResourceId,
+    /// Offset added to sampled values (default 0).
+    #[serde(default)]
+    pub value_offset: f32,
+    /// Factor applied to sampled values (default 1).
+    pub value_scale: f32,
+    /// Sampling filter.
+    #[serde(default)]
+    pub filter: FilterMode,
+    /// Tiling along u.
+    #[serde(default)]
+    pub tile_style_u: TileStyle,
+    /// Tiling along v.
+    #[serde(default)]
+    pub tile_style_v: TileStyle,
+    /// Tiling along w.
+    #[serde(default)]
+    pub tile_style_w: TileStyle,
+}
+
+impl FunctionFromImage3D {
+    /// Creates a function sampling `image3d_id` with the spec defaults.
+    pub fn new(id: ResourceId, image3d_id: ResourceId) -> Self {
+        Self {
+            id,
+            display_name: None,
+            image3d_id,
+            value_offset: 0.0,
+            value_scale: 1.0,
+            filter: FilterMode::Linear,
+            tile_style_u: TileStyle::Wrap,
+            tile_style_v: TileStyle::Wrap,
+            tile_style_w: TileStyle::Wrap,
+        }
+    }
+}
+
+/// A function resource that level sets and volume data can evaluate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Function {
+    /// A node graph (`<i:implicitfunction>`).
+    Implicit(ImplicitFunction),
+    /// A sampled 3D image (`<vol:functionfromimage3d>`).
+    FromImage3D(FunctionFromImage3D),
+}
+
+impl Function {
+    /// Returns the resource ID of the function.
+    pub fn id(&self) -> ResourceId {
+        match self {
+            Function::Implicit(f) => f.id,
+            Function::FromImage3D(f) => f.id,
+        }
+    }
+
+    /// Returns true if the function has an output named `channel`.
+    pub fn has_output(&self, channel: &str) -> bool {
+        match self {
+            Function::Implicit(f) => f.outputs.iter().any(|o| o.identifier == channel),
+            Function::FromImage3D(_) => {
+                matches!(channel, "color" | "red" | "green" | "blue" | "alpha")
+            }
+        }
+    }
+}
+
+/// A reference to one output channel of a [`Function`], evaluated in the space given by
+/// `transform`. Used by volume data elements.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VolumeFunctionRef {
+    /// ID of the evaluated function.
+    pub function_id: ResourceId,
+    /// Name of the function output to use.
+    pub channel: String,
+    /// Maps object coordinates into function coordinates (default identity).
+    #[serde(default = "identity")]
+    pub transform: Mat4,
+    /// Smallest feature the producer expects consumers to resolve (default 0).
+    #[serde(default)]
+    pub min_feature_size: f32,
+    /// Value used where the function cannot be evaluated (default 0).
+    #[serde(default)]
+    pub fallback_value: f32,
+}
+
+impl VolumeFunctionRef {
+    /// Creates a reference to `channel` of `function_id` with default transform and values.
+    pub fn new(function_id: ResourceId, channel: impl Into<String>) -> Self {
+        Self {
+            function_id,
+            channel: channel.into(),
+            transform: Mat4::IDENTITY,
+            min_feature_size: 0.0,
+            fallback_value: 0.0,
+        }
+    }
+}
+
+/// A `<vol:composite>` element mixing the materials of a base materials group.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VolumeComposite {
+    /// ID of the base materials group being mixed.
+    pub base_material_id: ResourceId,
+    /// One `<vol:materialmapping>` per base material, giving its weight.
+    pub mappings: Vec<VolumeFunctionRef>,
+}
+
+/// A named `<vol:property>` (e.g. density) defined by a function.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VolumeProperty {
+    /// Property name, unique within the volume data.
+    pub name: String,
+    /// Whether consumers must support the property to print the object.
+    #[serde(default)]
+    pub required: bool,
+    /// The function defining the property.
+    pub function: VolumeFunctionRef,
+}
+
+/// A `<vol:volumedata>` resource: color, material mix and properties inside a mesh or
+/// level set, referenced through the `volumeid` attribute.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VolumeData {
+    /// Unique resource ID.
+    pub id: ResourceId,
+    /// Color field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<VolumeFunctionRef>,
+    /// Material mix.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub composite: Option<VolumeComposite>,
+    /// Additional named properties.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub properties: Vec<VolumeProperty>,
+}
+
+impl VolumeData {
+    /// Returns every function reference in the volume data.
+    pub fn function_refs(&self) -> impl Iterator<Item = &VolumeFunctionRef> {
+        self.color
+            .iter()
+            .chain(self.composite.iter().flat_map(|c| &c.mappings))
+            .chain(self.properties.iter().map(|p| &p.function))
+    }
+}
+
+/// Level set geometry (`<vol:levelset>`): the object is the region where the function
+/// channel is negative, clipped to the bounding box (or the interior) of `mesh_id`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelSet {
+    /// ID of the evaluated function.
+    pub function_id: ResourceId,
+    /// Name of the function output holding the signed distance.
+    pub channel: String,
+    /// Maps object coordinates into function coordinates (default identity).
+    #[serde(default = "identity")]
+    pub transform: Mat4,
+    /// Smallest feature the producer expects consumers to resolve (default 0).
+    #[serde(default)]
+    pub min_feature_size: f32,
+    /// Clip to the mesh bounding box instead of the mesh itself (default false).
+    #[serde(default)]
+    pub mesh_bbox_only: bool,
+    /// Value used where the function cannot be evaluated (default 0).
+    #[serde(default)]
+    pub fallback_value: f32,
+    /// ID of the mesh object bounding the level set.
+    pub mesh_id: ResourceId,
+    /// Optional [`VolumeData`] applied inside the level set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume_id: Option<ResourceId>,
+}
+
+impl LevelSet {
+    /// Creates a level set on `channel` of `function_id`, bounded by `mesh_id`.
+    pub fn new(function_id: ResourceId, channel: impl Into<String>, mesh_id: ResourceId) -> Self {
+        Self {
+            function_id,
+            channel: channel.into(),
+            transform: Mat4::IDENTITY,
+            min_feature_size: 0.0,
+            mesh_bbox_only: false,
+            fallback_value: 0.0,
+            mesh_id,
+            volume_id: None,
+        }
+    }
+}
+
+fn identity() -> Mat4 {
+    Mat4::IDENTITY
+}
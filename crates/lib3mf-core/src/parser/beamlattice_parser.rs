@@ -75,6 +75,7 @@ fn parse_beams<R: BufRead>(parser: &mut XmlParser<R>, default_radius: f32) -> Re
                     CapMode::Sphere
                 };
 
+                parser.charge_mesh_bytes(size_of::<Beam>())?;
                 beams.push(Beam {
                     v1,
                     v2,
@@ -1,6 +1,7 @@
 use crate::error::{Lib3mfError, Result};
-use crate::model::{Build, BuildItem};
+use crate::model::{Build, BuildItem, Metadata};
 use crate::parser::component_parser::parse_transform;
+use crate::parser::model_parser::parse_metadata_entry;
 use crate::parser::xml_parser::{XmlParser, get_attribute, get_attribute_u32};
 
 use glam::Mat4;
@@ -36,14 +37,17 @@ pub fn parse_build<R: BufRead>(parser: &mut XmlParser<R>) -> Result<Build> {
                     _ => None,
                 });
 
-                build.items.push(BuildItem {
+                let mut item = BuildItem {
                     object_id,
                     part_number,
                     uuid,
                     path,
                     transform,
                     printable,
-                });
+                    metadata: Metadata::new(),
+                };
+                parse_item_children(parser, &mut item)?;
+                build.items.push(item);
             }
             Event::End(e) if e.name().as_ref() == b"build" => break,
             Event::Eof => {
@@ -57,3 +61,23 @@ pub fn parse_build<R: BufRead>(parser: &mut XmlParser<R>) -> Result<Build> {
 
     Ok(build)
 }
+
+/// Reads the children of an `<item>` up to its end tag, keeping `<metadatagroup>` entries.
+fn parse_item_children<R: BufRead>(parser: &mut XmlParser<R>, item: &mut BuildItem) -> Result<()> {
+    loop {
+        match parser.read_next_event()? {
+            Event::Start(e) if e.name().as_ref() == b"metadata" => {
+                let mut entry = parse_metadata_entry(&e)?;
+                entry.value = parser.read_text_content()?;
+                item.metadata.push(entry);
+            }
+            Event::End(e) if e.name().as_ref() == b"item" => return Ok(()),
+            Event::Eof => {
+                return Err(Lib3mfError::Validation(
+                    "Unexpected EOF in build item".to_string(),
+                ));
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use crate::error::{Lib3mfError, Result};
+use crate::model::CoreProperties;
+use crate::parser::xml_parser::XmlParser;
+use quick_xml::events::Event;
+
+/// Parses an OPC core properties part (`docProps/core.xml`).
+///
+/// Properties are matched by local name, whatever prefix the part binds their namespaces
+/// to. Empty elements and elements that aren't core properties are skipped.
+pub fn parse_core_properties(xml: &[u8]) -> Result<CoreProperties> {
+    let mut parser = XmlParser::new(xml);
+    let mut properties = CoreProperties::default();
+    let mut in_root = false;
+
+    loop {
+        let start = match parser.read_next_event()? {
+            Event::Start(e) => Some((e.local_name().as_ref().to_vec(), e.name().as_ref().to_vec())),
+            Event::Eof => break,
+            _ => None,
+        };
+        let Some((local_name, raw_name)) = start else {
+            continue;
+        };
+        if !in_root {
+            if local_name != b"coreProperties" {
+                return Err(Lib3mfError::InvalidStructure(format!(
+                    "Core properties part has root element <{}>, expected <coreProperties>",
+                    String::from_utf8_lossy(&raw_name)
+                )));
+            }
+            in_root = true;
+            continue;
+        }
+        match properties.property_mut(&local_name) {
+            Some(property) => {
+                let text = parser.read_text_content()?;
+                let text = text.trim();
+                *property = (!text.is_empty()).then(|| text.to_string());
+            }
+            None => parser.read_to_end(&raw_name)?,
+        }
+    }
+    Ok(properties)
+}
@@ -65,6 +65,7 @@ fn parse_displacement_vertices<R: BufRead>(parser: &mut XmlParser<R>) -> Result<
                 let x = get_attribute_f32(&e, b"x")?;
                 let y = get_attribute_f32(&e, b"y")?;
                 let z = get_attribute_f32(&e, b"z")?;
+                parser.charge_mesh_bytes(size_of::<Vertex>())?;
                 vertices.push(Vertex { x, y, z });
             }
             Event::End(e) if e.local_name().as_ref() == b"vertices" => break,
@@ -97,6 +98,7 @@ fn parse_displacement_triangles<R: BufRead>(
                 let p3 = get_attribute_u32(&e, b"p3").ok();
                 let pid = get_attribute_u32(&e, b"pid").ok();
 
+                parser.charge_mesh_bytes(size_of::<DisplacementTriangle>())?;
                 triangles.push(DisplacementTriangle {
                     v1,
                     v2,
@@ -150,11 +152,15 @@ fn parse_disp2d_groups<R: BufRead>(parser: &mut XmlParser<R>) -> Result<Vec<Grad
         match parser.read_next_event()? {
             Event::Start(e) => {
                 if e.local_name().as_ref() == b"disp2dgroup" {
-                    // Parse gradient vectors within the group
+                    // Parse gradient vectors within the group. Older versions of this
+                    // crate wrote them as `tex2dcoord` elements.
                     loop {
                         match parser.read_next_event()? {
                             Event::Start(grad_e) | Event::Empty(grad_e)
-                                if grad_e.local_name().as_ref() == b"gradient" =>
+                                if matches!(
+                                    grad_e.local_name().as_ref(),
+                                    b"gradient" | b"tex2dcoord"
+                                ) =>
                             {
                                 let gu = get_attribute_f32(&grad_e, b"gu")?;
                                 let gv = get_attribute_f32(&grad_e, b"gv")?;
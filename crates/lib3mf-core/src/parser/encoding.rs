@@ -0,0 +1,281 @@
+//! Input encoding detection and transcoding for model XML.
+//!
+//! The parsers work on UTF-8 bytes.
+//! [`DecodingReader`](crate::parser::encoding::DecodingReader) sits between the raw input
+//! and the XML reader and detects the document encoding the way XML 1.0 Appendix F
+//! describes:
+//!
+//! 1. A byte order mark selects UTF-8, UTF-16LE or UTF-16BE and is skipped.
+//! 2. Without a BOM, the byte pattern of `<?` identifies UTF-16 in either byte order.
+//! 3. Otherwise the input is UTF-8. The 3MF core specification allows only UTF-8 and
+//!    UTF-16, so an XML declaration naming any other encoding is rejected.
+//!
+//! UTF-8 input is passed through untouched, so byte offsets reported by the parser still
+//! index the original document. UTF-16 is transcoded to UTF-8 on the fly.
+//!
+//! ```
+//! use lib3mf_core::parser::parse_model;
+//! use std::io::Cursor;
+//!
+//! let xml = r#"<?xml version="1.0" encoding="UTF-16"?>
+//! <model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+//!     <resources />
+//!     <build />
+//! </model>"#;
+//!
+//! // UTF-16LE with a byte order mark
+//! let mut data = vec![0xFF, 0xFE];
+//! data.extend(xml.encode_utf16().flat_map(|u| u.to_le_bytes()));
+//!
+//! let model = parse_model(Cursor::new(data)).unwrap();
+//! assert_eq!(model.unit.as_str(), "millimeter");
+//! ```
+
+use crate::error::{Lib3mfError, Result};
+use std::borrow::Cow;
+use std::io::{self, BufRead, Read};
+
+/// Character encodings accepted for model XML.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XmlEncoding {
+    /// UTF-8 (also used for US-ASCII).
+    Utf8,
+    /// UTF-16, little-endian.
+    Utf16Le,
+    /// UTF-16, big-endian.
+    Utf16Be,
+}
+
+impl XmlEncoding {
+    /// Detects the encoding from the first bytes of a document.
+    ///
+    /// Returns the encoding and the length of the byte order mark to skip, or an error if
+    /// the XML declaration names an unsupported encoding.
+    pub fn detect(prefix: &[u8]) -> Result<(Self, usize)> {
+        match prefix {
+            [0xEF, 0xBB, 0xBF, ..] => Ok((Self::Utf8, 3)),
+            [0xFF, 0xFE, ..] => Ok((Self::Utf16Le, 2)),
+            [0xFE, 0xFF, ..] => Ok((Self::Utf16Be, 2)),
+            [b'<', 0, b'?', 0, ..] => Ok((Self::Utf16Le, 0)),
+            [0, b'<', 0, b'?', ..] => Ok((Self::Utf16Be, 0)),
+            _ => match declared_encoding(prefix) {
+                // A UTF-16 label on 8-bit content is a producer bug; the bytes win.
+                Some(label) => Self::from_label(&label)
+                    .map(|_| (Self::Utf8, 0))
+                    .ok_or_else(|| {
+                        Lib3mfError::Validation(format!(
+                            "Unsupported XML encoding '{}': 3MF requires UTF-8 or UTF-16",
+                            label
+                        ))
+                    }),
+                None => Ok((Self::Utf8, 0)),
+            },
+        }
+    }
+
+    /// Maps an XML declaration `encoding` label to an encoding, ignoring case.
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label.to_ascii_lowercase().as_str() {
+            "utf-8" | "utf8" | "us-ascii" | "ascii" => Some(Self::Utf8),
+            "utf-16" | "utf-16le" => Some(Self::Utf16Le),
+            "utf-16be" => Some(Self::Utf16Be),
+            _ => None,
+        }
+    }
+}
+
+/// Returns true if `prefix` looks like the start of an XML document in a supported
+/// encoding: a byte order mark or UTF-16 `<`, or `<` after optional whitespace.
+///
+/// Used to recognize bare model parts such as `3dmodel.model` outside a package.
+pub fn is_xml(prefix: &[u8]) -> bool {
+    match prefix {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => is_xml(rest),
+        [0xFF, 0xFE, ..] | [0xFE, 0xFF, ..] | [b'<', 0, ..] | [0, b'<', ..] => true,
+        _ => prefix.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'<'),
+    }
+}
+
+/// Returns the `encoding` label of the XML declaration at the start of `prefix`, if any.
+fn declared_encoding(prefix: &[u8]) -> Option<String> {
+    let rest = prefix.strip_prefix(b"<?xml")?;
+    let end = rest.windows(2).position(|w| w == b"?>")?;
+    let decl = std::str::from_utf8(&rest[..end]).ok()?;
+    let value = decl
+        .split_once("encoding")?
+        .1
+        .trim_start()
+        .strip_prefix('=')?;
+    let value = value.trim_start();
+    let quote = value.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+    let value = &value[1..];
+    Some(value[..value.find(quote)?].to_string())
+}
+
+/// A [`BufRead`] adaptor that yields the UTF-8 encoding of its input.
+///
+/// The encoding is detected from the first buffer the inner reader returns (see the
+/// [module documentation](self)), unless one was given with
+/// [`with_encoding`](Self::with_encoding). Invalid UTF-16 is reported as an
+/// [`io::ErrorKind::InvalidData`] error.
+pub struct DecodingReader<R> {
+    inner: R,
+    encoding: Option<XmlEncoding>,
+    started: bool,
+    /// Transcoded bytes not yet consumed, starting at `pos`.
+    out: Vec<u8>,
+    pos: usize,
+    /// Trailing input bytes that did not form a complete code unit or surrogate pair.
+    carry: Vec<u8>,
+}
+
+impl<R: BufRead> DecodingReader<R> {
+    /// Creates a reader that detects the encoding of `inner`.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            encoding: None,
+            started: false,
+            out: Vec::new(),
+            pos: 0,
+            carry: Vec::new(),
+        }
+    }
+
+    /// Creates a reader that decodes `inner` as `encoding`. A byte order mark matching the
+    /// encoding is still skipped.
+    pub fn with_encoding(inner: R, encoding: XmlEncoding) -> Self {
+        let mut reader = Self::new(inner);
+        reader.encoding = Some(encoding);
+        reader
+    }
+
+    /// Returns the encoding in use, once it has been detected.
+    pub fn encoding(&self) -> Option<XmlEncoding> {
+        self.encoding
+    }
+
+    /// Returns the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Detects the encoding and skips the byte order mark of a transcoded input. A UTF-8
+    /// BOM is left for the XML reader, which skips it itself.
+    fn start(&mut self) -> io::Result<XmlEncoding> {
+        let prefix = self.inner.fill_buf()?;
+        let (detected, bom) = match self.encoding {
+            // A declaration naming another encoding doesn't matter when one was given.
+            Some(_) => XmlEncoding::detect(prefix).unwrap_or((XmlEncoding::Utf8, 0)),
+            None => XmlEncoding::detect(prefix).map_err(|e| invalid_data(&e.to_string()))?,
+        };
+        let encoding = *self.encoding.get_or_insert(detected);
+        if encoding == detected && encoding != XmlEncoding::Utf8 {
+            self.inner.consume(bom);
+        }
+        self.started = true;
+        Ok(encoding)
+    }
+
+    /// Transcodes the next chunk of input into `out`. Leaves `out` empty at end of input.
+    fn refill(&mut self, encoding: XmlEncoding) -> io::Result<()> {
+        self.out.clear();
+        self.pos = 0;
+        while self.out.is_empty() {
+            let input = self.inner.fill_buf()?;
+            if input.is_empty() {
+                if !self.carry.is_empty() {
+                    return Err(invalid_data("Truncated character at end of input"));
+                }
+                return Ok(());
+            }
+            let len = input.len();
+            match encoding {
+                XmlEncoding::Utf16Le | XmlEncoding::Utf16Be => {
+                    self.carry.extend_from_slice(input);
+                    let used = decode_utf16(&self.carry, encoding, &mut self.out)?;
+                    self.carry.drain(..used);
+                }
+                XmlEncoding::Utf8 => unreachable!("UTF-8 input is passed through"),
+            }
+            self.inner.consume(len);
+        }
+        Ok(())
+    }
+}
+
+/// Appends the UTF-8 encoding of the complete UTF-16 code units in `input` to `out` and
+/// returns the number of input bytes used. A trailing odd byte or unpaired high surrogate
+/// is left for the next call.
+fn decode_utf16(input: &[u8], encoding: XmlEncoding, out: &mut Vec<u8>) -> io::Result<usize> {
+    let units: Vec<u16> = input
+        .chunks_exact(2)
+        .map(|pair| match encoding {
+            XmlEncoding::Utf16Be => u16::from_be_bytes([pair[0], pair[1]]),
+            _ => u16::from_le_bytes([pair[0], pair[1]]),
+        })
+        .collect();
+    let mut complete = units.len();
+    if units.last().is_some_and(|u| (0xD800..0xDC00).contains(u)) {
+        complete -= 1;
+    }
+    let mut buf = [0; 4];
+    for c in char::decode_utf16(units[..complete].iter().copied()) {
+        let c = c.map_err(|e| invalid_data(&format!("Invalid UTF-16: {}", e)))?;
+        out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+    }
+    Ok(complete * 2)
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+impl<R: BufRead> Read for DecodingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for DecodingReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let encoding = match self.encoding {
+            Some(encoding) if self.started => encoding,
+            _ => self.start()?,
+        };
+        if encoding == XmlEncoding::Utf8 {
+            return self.inner.fill_buf();
+        }
+        if self.pos >= self.out.len() {
+            self.refill(encoding)?;
+        }
+        Ok(&self.out[self.pos..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        if self.encoding == Some(XmlEncoding::Utf8) {
+            self.inner.consume(amount);
+        } else {
+            self.pos += amount;
+        }
+    }
+}
+
+/// Returns `data` as UTF-8, transcoding it if it is in another supported encoding.
+///
+/// UTF-8 input, with or without a byte order mark, is borrowed unchanged so that byte
+/// ranges found by the parser index both the input and the result.
+pub fn decode_xml(data: &[u8]) -> Result<Cow<'_, [u8]>> {
+    match XmlEncoding::detect(data)? {
+        (XmlEncoding::Utf8, _) => Ok(Cow::Borrowed(data)),
+        _ => {
+            let mut decoded = Vec::with_capacity(data.len());
+            DecodingReader::new(data).read_to_end(&mut decoded)?;
+            Ok(Cow::Owned(decoded))
+        }
+    }
+}
@@ -0,0 +1,536 @@
+//! Streaming extraction of single objects from large 3MF packages.
+//!
+//! [`extract_object`] copies one object, the resources it references (transitively, across
+//! model parts) and their attachments into a new package without building a
+//! [`Model`](crate::model::Model). It reads each needed model part twice:
+//!
+//! 1. [`index_resources`] records, for every resource, the IDs, parts and attachments it
+//!    references. Memory depends on the number of resources, not on mesh size.
+//! 2. [`copy_resources`] re-reads the part and copies the XML of the needed resources
+//!    verbatim, skipping everything else. Resource IDs are kept as they are.
+//!
+//! This makes it possible to pull a single part out of a multi-gigabyte build plate with
+//! bounded memory:
+//!
+//! ```no_run
+//! use lib3mf_core::archive::ZipArchiver;
+//! use lib3mf_core::model::ResourceId;
+//! use lib3mf_core::parser::extract::extract_object;
+//! use std::fs::File;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut archive = ZipArchiver::new(File::open("plate.3mf")?)?;
+//! let summary = extract_object(&mut archive, ResourceId(42), File::create("part.3mf")?)?;
+//! println!("Copied {} resource(s)", summary.resource_count());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::archive::opc::parse_relationships;
+use crate::archive::{ArchiveReader, ZipArchiver, find_model_path, part_rels_path};
+use crate::error::{Lib3mfError, Result};
+use crate::model::ResourceId;
+use crate::parser::xml_parser::XmlParser;
+use crate::writer::opc_writer::{
+    write_content_types, write_part_relationships, write_relationships,
+};
+use crate::writer::package_writer::{needs_zip64, resolve_target, zip_error};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, Writer};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::{BufRead, BufReader, Read, Seek, Write};
+use zip::ZipWriter;
+use zip::write::FileOptions;
+
+/// Attributes (by local name) holding one resource ID.
+const ID_REFERENCES: &[&[u8]] = &[
+    b"pid",
+    b"objectid",
+    b"texid",
+    b"matid",
+    b"slicestackid",
+    b"volumetricstackid",
+    b"dispid",
+    b"nid",
+    b"functionid",
+    b"image3did",
+    b"meshid",
+    b"volumeid",
+    b"basematerialid",
+];
+
+/// References held by a single resource.
+#[derive(Debug, Clone, Default)]
+struct IndexedResource {
+    /// Local name of the resource element (`object`, `basematerials`, ...).
+    element: String,
+    /// Referenced resources, with the part they live in if it isn't this one.
+    references: Vec<(Option<String>, ResourceId)>,
+    /// Paths of referenced attachments (textures, thumbnails).
+    attachments: Vec<String>,
+}
+
+/// The outgoing references of every resource in a model part, built by [`index_resources`].
+#[derive(Debug, Clone, Default)]
+pub struct ResourceIndex {
+    resources: HashMap<ResourceId, IndexedResource>,
+}
+
+impl ResourceIndex {
+    /// Returns the number of indexed resources.
+    pub fn len(&self) -> usize {
+        self.resources.len()
+    }
+
+    /// Returns true if the part has no resources.
+    pub fn is_empty(&self) -> bool {
+        self.resources.is_empty()
+    }
+
+    /// Returns the element name of resource `id` (e.g. `object`), if present.
+    pub fn element(&self, id: ResourceId) -> Option<&str> {
+        self.resources.get(&id).map(|r| r.element.as_str())
+    }
+}
+
+/// What [`extract_object`] copied.
+#[derive(Debug, Clone, Default)]
+pub struct ExtractSummary {
+    /// The extracted object.
+    pub object_id: ResourceId,
+    /// Copied resource IDs per model part (package-rooted, e.g. `/3D/3dmodel.model`).
+    pub parts: BTreeMap<String, BTreeSet<ResourceId>>,
+    /// Copied attachments (package-rooted).
+    pub attachments: Vec<String>,
+}
+
+impl ExtractSummary {
+    /// Returns the total number of copied resources across all parts.
+    pub fn resource_count(&self) -> usize {
+        self.parts.values().map(BTreeSet::len).sum()
+    }
+}
+
+/// Scans the `<resources>` of a model part and records what each resource references.
+///
+/// Only attributes are inspected, so meshes are tokenized but never stored. Parsing stops
+/// at the end of `<resources>`.
+pub fn index_resources<R: BufRead>(reader: R) -> Result<ResourceIndex> {
+    let mut parser = XmlParser::new(reader);
+    let mut index = ResourceIndex::default();
+    let mut in_resources = false;
+    let mut current: Option<(ResourceId, IndexedResource)> = None;
+    let mut depth = 0usize;
+
+    loop {
+        match parser.read_next_event()? {
+            Event::Start(e) => {
+                if let Some((_, resource)) = &mut current {
+                    depth += 1;
+                    record_references(&e, false, resource)?;
+                } else if in_resources {
+                    let Some(id) = resource_id(&e)? else {
+                        let qname = e.name().as_ref().to_vec();
+                        parser.read_to_end(&qname)?;
+                        continue;
+                    };
+                    let mut resource = IndexedResource {
+                        element: String::from_utf8_lossy(e.local_name().as_ref()).into_owned(),
+                        ..Default::default()
+                    };
+                    record_references(&e, true, &mut resource)?;
+                    current = Some((id, resource));
+                    depth = 1;
+                } else if e.local_name().as_ref() == b"resources" {
+                    in_resources = true;
+                }
+            }
+            Event::End(e) => {
+                if current.is_some() {
+                    depth -= 1;
+                    if depth == 0
+                        && let Some((id, resource)) = current.take()
+                    {
+                        index.resources.insert(id, resource);
+                    }
+                } else if in_resources && e.local_name().as_ref() == b"resources" {
+                    break;
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(index)
+}
+
+/// Returns the `id` attribute of a resource element, if it has one.
+fn resource_id(e: &BytesStart) -> Result<Option<ResourceId>> {
+    for attr in e.attributes().flatten() {
+        if attr.key.as_ref() == b"id" {
+            return parse_id(b"id", &attr.value).map(Some);
+        }
+    }
+    Ok(None)
+}
+
+fn parse_id(name: &[u8], value: &[u8]) -> Result<ResourceId> {
+    lexical_core::parse::<u32>(value)
+        .map(ResourceId)
+        .map_err(|_| {
+            Lib3mfError::Validation(format!(
+                "Invalid integer for attribute {}: {}",
+                String::from_utf8_lossy(name),
+                String::from_utf8_lossy(value)
+            ))
+        })
+}
+
+/// Adds the references held by the attributes of `e` to `resource`.
+///
+/// On the resource element itself and on 3D image sheets, `path` and `thumbnail` name
+/// attachments. On other nested elements, `path` (or `slicepath`) names the model part the
+/// element's references live in. The `value` of an implicit `constresourceid` node is a
+/// reference too.
+fn record_references(e: &BytesStart, is_root: bool, resource: &mut IndexedResource) -> Result<()> {
+    let mut ids = Vec::new();
+    let mut part = None;
+    let element = e.local_name();
+    let is_attachment_holder = is_root || element.as_ref() == b"imagesheet";
+    let is_resource_constant = element.as_ref() == b"constresourceid";
+    for attr in e.attributes().flatten() {
+        let name = attr.key.local_name();
+        let name = name.as_ref();
+        if ID_REFERENCES.contains(&name) || (is_resource_constant && name == b"value") {
+            ids.push(parse_id(name, &attr.value)?);
+        } else if name == b"pids" {
+            for value in attr.value.split(|b| b.is_ascii_whitespace()) {
+                if !value.is_empty() {
+                    ids.push(parse_id(name, value)?);
+                }
+            }
+        } else if is_attachment_holder && (name == b"path" || name == b"thumbnail") {
+            let value = attr
+                .unescape_value()
+                .map_err(|e| Lib3mfError::Validation(e.to_string()))?;
+            resource.attachments.push(value.into_owned());
+        } else if name == b"path" || name == b"slicepath" {
+            let value = attr
+                .unescape_value()
+                .map_err(|e| Lib3mfError::Validation(e.to_string()))?;
+            part = Some(value.into_owned());
+        }
+    }
+    resource
+        .references
+        .extend(ids.into_iter().map(|id| (part.clone(), id)));
+    Ok(())
+}
+
+/// Copies a model part to `writer`, keeping only the resources in `keep`.
+///
+/// Everything outside `<resources>` and `<build>` (XML declaration, the `<model>` element
+/// with its namespaces, metadata) is copied as is. With `build_object`, the build keeps only
+/// the items for that object, or gets a single untransformed item if there were none;
+/// without it, the build is left empty, as required for non-root model parts.
+pub fn copy_resources<R: BufRead, W: Write>(
+    reader: R,
+    keep: &BTreeSet<ResourceId>,
+    build_object: Option<ResourceId>,
+    writer: W,
+) -> Result<()> {
+    let mut reader = Reader::from_reader(reader);
+    reader.config_mut().trim_text(true);
+    let mut out = Writer::new_with_indent(writer, b' ', 2);
+    let mut buf = Vec::new();
+
+    loop {
+        buf.clear();
+        let event = reader.read_event_into(&mut buf).map_err(xml_error)?;
+        match event {
+            Event::Start(e) if e.local_name().as_ref() == b"resources" => {
+                out.write_event(Event::Start(e)).map_err(Lib3mfError::Io)?;
+                copy_kept_resources(&mut reader, &mut out, keep)?;
+            }
+            Event::Start(e) if e.local_name().as_ref() == b"build" => {
+                out.write_event(Event::Start(e.borrow()))
+                    .map_err(Lib3mfError::Io)?;
+                let copied = copy_build_items(&mut reader, &mut out, build_object)?;
+                if !copied && let Some(id) = build_object {
+                    write_item(&mut out, id)?;
+                }
+                out.write_event(Event::End(e.to_end()))
+                    .map_err(Lib3mfError::Io)?;
+            }
+            Event::Empty(e) if e.local_name().as_ref() == b"build" => match build_object {
+                Some(id) => {
+                    out.write_event(Event::Start(e.borrow()))
+                        .map_err(Lib3mfError::Io)?;
+                    write_item(&mut out, id)?;
+                    out.write_event(Event::End(e.to_end()))
+                        .map_err(Lib3mfError::Io)?;
+                }
+                None => out.write_event(Event::Empty(e)).map_err(Lib3mfError::Io)?,
+            },
+            Event::Eof => break,
+            event => out.write_event(event).map_err(Lib3mfError::Io)?,
+        }
+    }
+    Ok(())
+}
+
+/// Copies the kept children of `<resources>`, up to and including its end tag.
+fn copy_kept_resources<R: BufRead, W: Write>(
+    reader: &mut Reader<R>,
+    out: &mut Writer<W>,
+    keep: &BTreeSet<ResourceId>,
+) -> Result<()> {
+    let mut buf = Vec::new();
+    let mut skip = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_event_into(&mut buf).map_err(xml_error)? {
+            Event::Start(e) => {
+                if resource_id(&e)?.is_some_and(|id| keep.contains(&id)) {
+                    out.write_event(Event::Start(e.borrow()))
+                        .map_err(Lib3mfError::Io)?;
+                    copy_subtree(reader, out)?;
+                } else {
+                    skip.clear();
+                    reader
+                        .read_to_end_into(e.name(), &mut skip)
+                        .map_err(xml_error)?;
+                }
+            }
+            Event::Empty(e) => {
+                if resource_id(&e)?.is_some_and(|id| keep.contains(&id)) {
+                    out.write_event(Event::Empty(e)).map_err(Lib3mfError::Io)?;
+                }
+            }
+            Event::End(e) => {
+                out.write_event(Event::End(e)).map_err(Lib3mfError::Io)?;
+                return Ok(());
+            }
+            Event::Eof => {
+                return Err(Lib3mfError::Validation(
+                    "Unexpected EOF in resources".to_string(),
+                ));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Copies the build items for `object`, up to but excluding the `</build>` tag. Returns
+/// true if any item was copied.
+fn copy_build_items<R: BufRead, W: Write>(
+    reader: &mut Reader<R>,
+    out: &mut Writer<W>,
+    object: Option<ResourceId>,
+) -> Result<bool> {
+    let mut buf = Vec::new();
+    let mut skip = Vec::new();
+    let mut copied = false;
+    loop {
+        buf.clear();
+        match reader.read_event_into(&mut buf).map_err(xml_error)? {
+            Event::Start(e) => {
+                if is_item_for(&e, object)? {
+                    out.write_event(Event::Start(e.borrow()))
+                        .map_err(Lib3mfError::Io)?;
+                    copy_subtree(reader, out)?;
+                    copied = true;
+                } else {
+                    skip.clear();
+                    reader
+                        .read_to_end_into(e.name(), &mut skip)
+                        .map_err(xml_error)?;
+                }
+            }
+            Event::Empty(e) => {
+                if is_item_for(&e, object)? {
+                    out.write_event(Event::Empty(e)).map_err(Lib3mfError::Io)?;
+                    copied = true;
+                }
+            }
+            Event::End(_) => return Ok(copied),
+            Event::Eof => {
+                return Err(Lib3mfError::Validation(
+                    "Unexpected EOF in build".to_string(),
+                ));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns true if `e` is a build item placing `object` from this part.
+fn is_item_for(e: &BytesStart, object: Option<ResourceId>) -> Result<bool> {
+    let Some(object) = object else {
+        return Ok(false);
+    };
+    if e.local_name().as_ref() != b"item" {
+        return Ok(false);
+    }
+    let mut matches = false;
+    for attr in e.attributes().flatten() {
+        match attr.key.local_name().as_ref() {
+            b"objectid" => matches = parse_id(b"objectid", &attr.value)? == object,
+            b"path" => return Ok(false),
+            _ => {}
+        }
+    }
+    Ok(matches)
+}
+
+/// Copies events up to and including the end tag of the element just written.
+fn copy_subtree<R: BufRead, W: Write>(reader: &mut Reader<R>, out: &mut Writer<W>) -> Result<()> {
+    let mut buf = Vec::new();
+    let mut depth = 1usize;
+    loop {
+        buf.clear();
+        let event = reader.read_event_into(&mut buf).map_err(xml_error)?;
+        match &event {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth -= 1,
+            Event::Eof => {
+                return Err(Lib3mfError::Validation(
+                    "Unexpected EOF in resource".to_string(),
+                ));
+            }
+            _ => {}
+        }
+        out.write_event(event).map_err(Lib3mfError::Io)?;
+        if depth == 0 {
+            return Ok(());
+        }
+    }
+}
+
+fn write_item<W: Write>(out: &mut Writer<W>, object: ResourceId) -> Result<()> {
+    let id = object.0.to_string();
+    out.write_event(Event::Empty(
+        BytesStart::new("item").with_attributes([("objectid", id.as_str())]),
+    ))
+    .map_err(Lib3mfError::Io)?;
+    Ok(())
+}
+
+fn xml_error(e: quick_xml::Error) -> Lib3mfError {
+    Lib3mfError::Validation(e.to_string())
+}
+
+/// Writes a package to `writer` holding object `object_id` of `archive` and everything it
+/// references. See the [module docs](self).
+///
+/// Components and other references into further model parts are followed, and those parts
+/// are written with just the resources needed. Referenced textures and thumbnails are
+/// copied along with the part relationships that point at copied parts.
+///
+/// # Errors
+///
+/// - [`Lib3mfError::ResourceNotFound`] if `object_id` or a resource it references is missing
+/// - [`Lib3mfError::Validation`] if `object_id` is not an object or the XML is malformed
+pub fn extract_object<R: Read + Seek, W: Write + Seek>(
+    archive: &mut ZipArchiver<R>,
+    object_id: ResourceId,
+    writer: W,
+) -> Result<ExtractSummary> {
+    let root = format!("/{}", find_model_path(archive)?.trim_start_matches('/'));
+
+    // Pass 1: index each needed part and follow references to a fixed point.
+    let mut indices: HashMap<String, ResourceIndex> = HashMap::new();
+    let mut parts: BTreeMap<String, BTreeSet<ResourceId>> = BTreeMap::new();
+    let mut attachments = BTreeSet::new();
+    let mut pending = vec![(root.clone(), object_id)];
+    while let Some((part, id)) = pending.pop() {
+        if !parts.entry(part.clone()).or_default().insert(id) {
+            continue;
+        }
+        if !indices.contains_key(&part) {
+            let entry = archive.entry_reader(&part)?;
+            indices.insert(part.clone(), index_resources(BufReader::new(entry))?);
+        }
+        let resource = indices[&part]
+            .resources
+            .get(&id)
+            .ok_or(Lib3mfError::ResourceNotFound(id.0))?;
+        if id == object_id && part == root && resource.element != "object" {
+            return Err(Lib3mfError::Validation(format!(
+                "Resource {} is a {}, not an object",
+                id.0, resource.element
+            )));
+        }
+        for (other, referenced) in &resource.references {
+            let target = match other {
+                Some(path) => resolve_target(&part, path),
+                None => part.clone(),
+            };
+            pending.push((target, *referenced));
+        }
+        for path in &resource.attachments {
+            attachments.insert(resolve_target(&part, path));
+        }
+    }
+    attachments.retain(|path| archive.entry_exists(path.trim_start_matches('/')));
+
+    // Pass 2: copy the needed resources of each part, root first.
+    let options: FileOptions<'static, ()> = FileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .unix_permissions(0o644);
+    let mut zip = ZipWriter::new(writer);
+    let mut order: Vec<&String> = vec![&root];
+    order.extend(parts.keys().filter(|p| **p != root));
+    for part in order {
+        // The copy is no larger than the source part
+        let size = archive.entry_metadata(part)?.size;
+        zip.start_file(
+            part.trim_start_matches('/'),
+            options.large_file(needs_zip64(size)),
+        )
+        .map_err(zip_error)?;
+        let entry = archive.entry_reader(part)?;
+        let build_object = (*part == root).then_some(object_id);
+        copy_resources(BufReader::new(entry), &parts[part], build_object, &mut zip)?;
+    }
+
+    for path in &attachments {
+        let data = archive.read_entry(path.trim_start_matches('/'))?;
+        zip.start_file(
+            path.trim_start_matches('/'),
+            options.large_file(needs_zip64(data.len() as u64)),
+        )
+        .map_err(zip_error)?;
+        zip.write_all(&data).map_err(zip_error)?;
+    }
+
+    // Keep part relationships whose targets were copied.
+    for part in parts.keys() {
+        let rels_path = part_rels_path(part.trim_start_matches('/'));
+        if !archive.entry_exists(&rels_path) {
+            continue;
+        }
+        let mut rels = parse_relationships(&archive.read_entry(&rels_path)?)?;
+        rels.retain(|r| {
+            let target = resolve_target(part, &r.target);
+            parts.contains_key(&target) || attachments.contains(&target)
+        });
+        if !rels.is_empty() {
+            zip.start_file(rels_path, options).map_err(zip_error)?;
+            write_part_relationships(&mut zip, &rels)?;
+        }
+    }
+
+    zip.start_file("_rels/.rels", options).map_err(zip_error)?;
+    write_relationships(&mut zip, &root, None)?;
+    zip.start_file("[Content_Types].xml", options)
+        .map_err(zip_error)?;
+    write_content_types(&mut zip, attachments.iter().map(String::as_str))?;
+    zip.finish().map_err(zip_error)?;
+
+    Ok(ExtractSummary {
+        object_id,
+        parts,
+        attachments: attachments.into_iter().collect(),
+    })
+}
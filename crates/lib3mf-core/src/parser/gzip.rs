@@ -0,0 +1,76 @@
+//! Gzip-compressed model XML.
+//!
+//! Some pipelines pass a bare `3dmodel.model` document around gzip-compressed (`.model.gz`)
+//! instead of inside a 3MF package. [`parse_model`](crate::parser::parse_model), its
+//! option and budget variants and `parse_model_parallel` recognize the gzip magic bytes and
+//! decompress such input on the fly, so it needs no preprocessing. Lazy parsing does not:
+//! its mesh handles record offsets into the part as stored in the archive.
+//!
+//! ```
+//! use flate2::{Compression, write::GzEncoder};
+//! use lib3mf_core::parser::parse_model;
+//! use std::io::{Cursor, Write};
+//!
+//! let xml = r#"<model unit="inch" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+//!     <resources />
+//!     <build />
+//! </model>"#;
+//! let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+//! encoder.write_all(xml.as_bytes()).unwrap();
+//! let gz = encoder.finish().unwrap();
+//!
+//! let model = parse_model(Cursor::new(gz)).unwrap();
+//! assert_eq!(model.unit.as_str(), "inch");
+//! ```
+
+use crate::error::Result;
+use flate2::bufread::MultiGzDecoder;
+use std::borrow::Cow;
+use std::io::{BufRead, BufReader, Read};
+
+/// The first two bytes of every gzip stream.
+pub const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+
+/// Returns whether data starting with `prefix` is gzip-compressed.
+pub fn is_gzip(prefix: &[u8]) -> bool {
+    prefix.starts_with(&GZIP_MAGIC)
+}
+
+/// Decompresses `data` if it is gzip-compressed, or else returns it unchanged.
+///
+/// Streams of several concatenated gzip members are decompressed as one document.
+///
+/// # Errors
+///
+/// Returns [`Lib3mfError::Io`](crate::error::Lib3mfError::Io) if the gzip stream is
+/// corrupt or truncated.
+pub fn decompress(data: &[u8]) -> Result<Cow<'_, [u8]>> {
+    if !is_gzip(data) {
+        return Ok(Cow::Borrowed(data));
+    }
+    let mut decompressed = Vec::new();
+    MultiGzDecoder::new(data).read_to_end(&mut decompressed)?;
+    Ok(Cow::Owned(decompressed))
+}
+
+/// Returns up to `len` leading bytes of what `prefix` decompresses to, or of `prefix`
+/// itself if it is not gzip-compressed.
+///
+/// `prefix` may be a truncated stream, such as the first bytes of a file, so that formats
+/// can be recognized inside gzip without decompressing the whole file.
+pub fn peek(prefix: &[u8], len: usize) -> Cow<'_, [u8]> {
+    if !is_gzip(prefix) {
+        return Cow::Borrowed(&prefix[..len.min(prefix.len())]);
+    }
+    let mut head = Vec::new();
+    // A truncated stream fails once its input runs out, keeping what was decompressed
+    let _ = MultiGzDecoder::new(prefix)
+        .take(len as u64)
+        .read_to_end(&mut head);
+    Cow::Owned(head)
+}
+
+/// Decompresses the gzip stream read from `reader`.
+pub(crate) fn gunzip<R: BufRead>(reader: R) -> BufReader<MultiGzDecoder<R>> {
+    BufReader::new(MultiGzDecoder::new(reader))
+}
@@ -0,0 +1,86 @@
+use crate::error::{Lib3mfError, Result};
+use crate::model::Extension;
+use crate::parser::xml_parser::{XmlParser, get_attribute};
+use quick_xml::events::Event;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::BufRead;
+
+/// Namespace declarations and extension lists of a `<model>` element.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelHeader {
+    /// Namespace URIs by prefix. The default namespace has the empty prefix.
+    pub namespaces: BTreeMap<String, String>,
+    /// Prefixes listed in `requiredextensions`.
+    pub required_extensions: Vec<String>,
+    /// Prefixes listed in `recommendedextensions`.
+    pub recommended_extensions: Vec<String>,
+}
+
+impl ModelHeader {
+    /// Returns the namespace URI bound to `prefix`.
+    pub fn namespace(&self, prefix: &str) -> Option<&str> {
+        self.namespaces.get(prefix).map(String::as_str)
+    }
+
+    /// Returns the known extensions whose namespaces are declared.
+    pub fn declared_extensions(&self) -> BTreeSet<Extension> {
+        self.namespaces
+            .values()
+            .filter_map(|uri| Extension::from_namespace(uri))
+            .collect()
+    }
+}
+
+/// Reads the attributes of the root `<model>` element, stopping there.
+///
+/// This is cheap even for huge model parts, and keeps what [`parse_model`] does not:
+/// every namespace declaration and the `requiredextensions` and
+/// `recommendedextensions` lists.
+///
+/// [`parse_model`]: crate::parser::parse_model
+pub fn parse_model_header<R: BufRead>(reader: R) -> Result<ModelHeader> {
+    let mut parser = XmlParser::new(reader);
+    loop {
+        match parser.read_next_event()? {
+            Event::Start(e) if e.local_name().as_ref() == b"model" => {
+                let mut header = ModelHeader::default();
+                for attr in e.attributes().flatten() {
+                    let key = std::str::from_utf8(attr.key.as_ref()).unwrap_or("");
+                    let prefix = match key {
+                        "xmlns" => "",
+                        _ => match key.strip_prefix("xmlns:") {
+                            Some(prefix) => prefix,
+                            None => continue,
+                        },
+                    };
+                    let uri = attr
+                        .unescape_value()
+                        .map_err(|e| Lib3mfError::Validation(e.to_string()))?;
+                    header
+                        .namespaces
+                        .insert(prefix.to_string(), uri.into_owned());
+                }
+                let list = |name: &[u8]| -> Vec<String> {
+                    get_attribute(&e, name)
+                        .map(|v| v.split_whitespace().map(str::to_string).collect())
+                        .unwrap_or_default()
+                };
+                header.required_extensions = list(b"requiredextensions");
+                header.recommended_extensions = list(b"recommendedextensions");
+                return Ok(header);
+            }
+            Event::Start(e) => {
+                return Err(Lib3mfError::Validation(format!(
+                    "Expected <model> root element, found <{}>",
+                    String::from_utf8_lossy(e.name().as_ref())
+                )));
+            }
+            Event::Eof => {
+                return Err(Lib3mfError::Validation(
+                    "Missing <model> root element".to_string(),
+                ));
+            }
+            _ => {}
+        }
+    }
+}
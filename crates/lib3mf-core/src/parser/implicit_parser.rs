@@ -0,0 +1,138 @@
+use crate::error::{Lib3mfError, Result};
+use crate::model::{FunctionPort, ImplicitFunction, ImplicitNode, PortType, ResourceId};
+use crate::parser::xml_parser::{XmlParser, get_attribute, get_attribute_u32};
+use quick_xml::events::{BytesStart, Event};
+use std::io::BufRead;
+
+/// Parses an `<implicitfunction>` element whose start tag is `start` into an
+/// `ImplicitFunction`.
+///
+/// Child elements other than `<in>` and `<out>` are nodes and are kept by element name,
+/// so node types added by later revisions of the spec are preserved.
+pub fn parse_implicit_function<R: BufRead>(
+    parser: &mut XmlParser<R>,
+    start: &BytesStart,
+) -> Result<ImplicitFunction> {
+    let mut function = ImplicitFunction {
+        id: ResourceId(get_attribute_u32(start, b"id")?),
+        identifier: get_attribute(start, b"identifier").map(|s| s.into_owned()),
+        display_name: get_attribute(start, b"displayname").map(|s| s.into_owned()),
+        ..Default::default()
+    };
+
+    loop {
+        match parser.read_next_event()? {
+            Event::Start(e) => match e.local_name().as_ref() {
+                b"in" => function.inputs = parse_ports(parser)?,
+                b"out" => function.outputs = parse_ports(parser)?,
+                _ => {
+                    let start = e.into_owned();
+                    function.nodes.push(parse_node(parser, &start)?);
+                }
+            },
+            Event::End(e) if e.local_name().as_ref() == b"implicitfunction" => break,
+            Event::Eof => {
+                return Err(Lib3mfError::Validation(
+                    "Unexpected EOF in implicitfunction".to_string(),
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(function)
+}
+
+/// Parses a node element whose start tag is `start`, up to its end tag.
+fn parse_node<R: BufRead>(parser: &mut XmlParser<R>, start: &BytesStart) -> Result<ImplicitNode> {
+    let kind = String::from_utf8_lossy(start.local_name().as_ref()).into_owned();
+    let mut node = ImplicitNode {
+        identifier: get_attribute(start, b"identifier")
+            .ok_or_else(|| {
+                Lib3mfError::Validation(format!("{} node missing identifier attribute", kind))
+            })?
+            .into_owned(),
+        display_name: get_attribute(start, b"displayname").map(|s| s.into_owned()),
+        tag: get_attribute(start, b"tag").map(|s| s.into_owned()),
+        kind,
+        ..Default::default()
+    };
+    for attr in start.attributes().flatten() {
+        let key = String::from_utf8_lossy(attr.key.as_ref()).into_owned();
+        if matches!(key.as_str(), "identifier" | "displayname" | "tag") {
+            continue;
+        }
+        let value = attr
+            .unescape_value()
+            .map(|v| v.into_owned())
+            .unwrap_or_else(|_| String::from_utf8_lossy(&attr.value).into_owned());
+        node.attributes.push((key, value));
+    }
+
+    loop {
+        match parser.read_next_event()? {
+            Event::Start(e) => match e.local_name().as_ref() {
+                b"in" => node.inputs = parse_ports(parser)?,
+                b"out" => node.outputs = parse_ports(parser)?,
+                _ => {
+                    let end_tag = e.name().as_ref().to_vec();
+                    parser.read_to_end(&end_tag)?;
+                }
+            },
+            Event::End(e) if e.local_name().as_ref() == start.local_name().as_ref() => break,
+            Event::Eof => {
+                return Err(Lib3mfError::Validation(format!(
+                    "Unexpected EOF in {} node",
+                    node.kind
+                )));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(node)
+}
+
+/// Parses the port elements of an `<in>` or `<out>` list, up to its end tag.
+fn parse_ports<R: BufRead>(parser: &mut XmlParser<R>) -> Result<Vec<FunctionPort>> {
+    let mut ports = Vec::new();
+    loop {
+        match parser.read_next_event()? {
+            Event::Start(e) => {
+                let name = String::from_utf8_lossy(e.local_name().as_ref()).into_owned();
+                if let Some((port_type, is_ref)) = PortType::from_element_name(&name) {
+                    let reference = get_attribute(&e, b"ref").map(|s| s.into_owned());
+                    if is_ref && reference.is_none() {
+                        return Err(Lib3mfError::Validation(format!(
+                            "{} missing ref attribute",
+                            name
+                        )));
+                    }
+                    ports.push(FunctionPort {
+                        identifier: get_attribute(&e, b"identifier")
+                            .ok_or_else(|| {
+                                Lib3mfError::Validation(format!(
+                                    "{} missing identifier attribute",
+                                    name
+                                ))
+                            })?
+                            .into_owned(),
+                        display_name: get_attribute(&e, b"displayname").map(|s| s.into_owned()),
+                        port_type,
+                        reference,
+                    });
+                }
+                let end_tag = e.name().as_ref().to_vec();
+                parser.read_to_end(&end_tag)?;
+            }
+            Event::End(e) if matches!(e.local_name().as_ref(), b"in" | b"out") => break,
+            Event::Eof => {
+                return Err(Lib3mfError::Validation(
+                    "Unexpected EOF in function ports".to_string(),
+                ));
+            }
+            _ => {}
+        }
+    }
+    Ok(ports)
+}
@@ -1,14 +1,77 @@
 use crate::error::{Lib3mfError, Result};
 use crate::model::{
     BaseMaterial, BaseMaterialsGroup, BlendMethod, Color, ColorGroup, Composite,
-    CompositeMaterials, Multi, MultiProperties, ResourceId, Texture2DCoord, Texture2DGroup,
+    CompositeMaterials, FilterMode, Multi, MultiProperties, ResourceId, Texture2D, Texture2DCoord,
+    Texture2DGroup, TileStyle,
 };
-use crate::parser::xml_parser::{XmlParser, get_attribute};
-use quick_xml::events::Event;
+use crate::parser::xml_parser::{XmlParser, get_attribute, get_attribute_u32};
+use quick_xml::events::{BytesStart, Event};
 use std::io::BufRead;
 
 // ... existing code ...
 
+/// Parses a `<texture2d>` start tag into a `Texture2D`.
+///
+/// `contenttype` must be a PNG or JPEG type, and `tilestyleu`, `tilestylev` and `filter`
+/// one of the values the Materials Extension defines.
+pub fn parse_texture_2d(e: &BytesStart) -> Result<Texture2D> {
+    let id = ResourceId(get_attribute_u32(e, b"id")?);
+    let path = get_attribute(e, b"path")
+        .ok_or(Lib3mfError::Validation(
+            "texture2d missing required 'path' attribute".to_string(),
+        ))?
+        .into_owned();
+    let contenttype = get_attribute(e, b"contenttype")
+        .ok_or(Lib3mfError::Validation(
+            "texture2d missing required 'contenttype' attribute".to_string(),
+        ))?
+        .into_owned();
+
+    // Validate content type - must be a valid image MIME type
+    if contenttype.is_empty()
+        || (!contenttype.starts_with("image/png")
+            && !contenttype.starts_with("image/jpeg")
+            && !contenttype.starts_with("image/jpg"))
+    {
+        return Err(Lib3mfError::Validation(format!(
+            "Invalid contenttype '{}'. Must be 'image/png' or 'image/jpeg'",
+            contenttype
+        )));
+    }
+
+    let tile_style = |name: &[u8]| match get_attribute(e, name).as_deref() {
+        None | Some("wrap") => Ok(TileStyle::Wrap),
+        Some("mirror") => Ok(TileStyle::Mirror),
+        Some("clamp") => Ok(TileStyle::Clamp),
+        Some("none") => Ok(TileStyle::None),
+        Some(other) => Err(Lib3mfError::Validation(format!(
+            "Invalid texture2d {} '{}'. Must be wrap, mirror, clamp or none",
+            String::from_utf8_lossy(name),
+            other
+        ))),
+    };
+    let filter = match get_attribute(e, b"filter").as_deref() {
+        None | Some("auto") => None,
+        Some("linear") => Some(FilterMode::Linear),
+        Some("nearest") => Some(FilterMode::Nearest),
+        Some(other) => {
+            return Err(Lib3mfError::Validation(format!(
+                "Invalid texture2d filter '{}'. Must be auto, linear or nearest",
+                other
+            )));
+        }
+    };
+
+    Ok(Texture2D {
+        id,
+        path,
+        contenttype,
+        tile_style_u: tile_style(b"tilestyleu")?,
+        tile_style_v: tile_style(b"tilestylev")?,
+        filter,
+    })
+}
+
 /// Parses a `<texture2dgroup>` element into a `Texture2DGroup`.
 pub fn parse_texture_2d_group<R: BufRead>(
     parser: &mut XmlParser<R>,
@@ -150,7 +213,7 @@ pub fn parse_base_materials<R: BufRead>(
                         "base element missing 'displaycolor' attribute".to_string(),
                     )
                 })?;
-                let display_color = Color::from_hex(&color_hex).ok_or_else(|| {
+                let display_color = Color::parse(&color_hex).ok_or_else(|| {
                     Lib3mfError::Validation(format!("Invalid color format: {}", color_hex))
                 })?;
 
@@ -185,7 +248,7 @@ pub fn parse_color_group<R: BufRead>(
                 let color_hex = get_attribute(&e, b"color").ok_or_else(|| {
                     Lib3mfError::Validation("color element missing 'color' attribute".to_string())
                 })?;
-                let color = Color::from_hex(&color_hex).ok_or_else(|| {
+                let color = Color::parse(&color_hex).ok_or_else(|| {
                     Lib3mfError::Validation(format!("Invalid color format: {}", color_hex))
                 })?;
                 colors.push(color);
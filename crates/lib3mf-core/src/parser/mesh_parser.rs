@@ -1,19 +1,139 @@
 use crate::error::{Lib3mfError, Result};
-use crate::model::{ClippingMode, Mesh, Triangle, Vertex};
+use crate::model::{
+    BeamLattice, ClippingMode, Mesh, MeshStorage, PreciseMesh, ResourceId, Triangle,
+    TriangleAttributes, TriangleSet, Vertex,
+};
 use crate::parser::beamlattice_parser::parse_beam_lattice_content;
-use crate::parser::xml_parser::{XmlParser, get_attribute, get_attribute_f32, get_attribute_u32};
-use quick_xml::events::Event;
+use crate::parser::triangleset_parser::parse_triangle_sets;
+use crate::parser::xml_parser::{
+    XmlParser, get_attribute, get_attribute_f32, get_attribute_f64, get_attribute_u32,
+};
+use glam::DVec3;
+use quick_xml::events::{BytesStart, Event};
 use std::io::BufRead;
 
 /// Parses a `<mesh>` element (vertices and triangles) into a `Mesh`.
 pub fn parse_mesh<R: BufRead>(parser: &mut XmlParser<R>) -> Result<Mesh> {
-    let mut mesh = Mesh::default();
+    parse_mesh_into::<R, MeshBuilder>(parser).map(MeshBuilder::finish)
+}
+
+/// A [`Mesh`] being parsed. Vertices and triangles are appended to plain vectors and moved
+/// into the mesh's shared storage at the end, since every push to a
+/// [`SharedVec`](crate::model::SharedVec) checks whether it is shared.
+#[derive(Default)]
+struct MeshBuilder {
+    mesh: Mesh,
+    vertices: Vec<Vertex>,
+    triangles: Vec<Triangle>,
+}
+
+impl MeshBuilder {
+    fn finish(self) -> Mesh {
+        Mesh {
+            vertices: self.vertices.into(),
+            triangles: self.triangles.into(),
+            ..self.mesh
+        }
+    }
+}
+
+impl MeshStorage for MeshBuilder {
+    fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn triangle_count(&self) -> usize {
+        self.triangles.len()
+    }
+
+    fn vertex(&self, index: usize) -> Vertex {
+        self.vertices[index]
+    }
+
+    fn triangle(&self, index: usize) -> Triangle {
+        self.triangles[index]
+    }
+
+    fn push_vertex(&mut self, vertex: Vertex) {
+        self.vertices.push(vertex);
+    }
+
+    fn push_triangle(&mut self, triangle: Triangle) {
+        self.triangles.push(triangle);
+    }
+
+    fn beam_lattice(&self) -> Option<&BeamLattice> {
+        self.mesh.beam_lattice()
+    }
+
+    fn set_beam_lattice(&mut self, lattice: BeamLattice) {
+        self.mesh.set_beam_lattice(lattice);
+    }
+
+    fn triangle_sets(&self) -> &[TriangleSet] {
+        self.mesh.triangle_sets()
+    }
+
+    fn set_triangle_sets(&mut self, sets: Vec<TriangleSet>) {
+        self.mesh.set_triangle_sets(sets);
+    }
+
+    fn triangle_attributes(&self) -> &TriangleAttributes {
+        self.mesh.triangle_attributes()
+    }
+
+    fn triangle_attributes_mut(&mut self) -> &mut TriangleAttributes {
+        self.mesh.triangle_attributes_mut()
+    }
+
+    fn heap_bytes(&self) -> usize {
+        self.vertices.capacity() * size_of::<Vertex>()
+            + self.triangles.capacity() * size_of::<Triangle>()
+    }
+}
+
+/// Parses the `<mesh>` element whose start tag is `start`, including attributes of the
+/// start tag such as the Volumetric Extension `volumeid`.
+pub(crate) fn parse_mesh_element<R: BufRead>(
+    parser: &mut XmlParser<R>,
+    start: &BytesStart,
+) -> Result<Mesh> {
+    let mut mesh = parse_mesh(parser)?;
+    mesh.volume_id = volume_id(start);
+    Ok(mesh)
+}
+
+/// Like [`parse_mesh_element`], keeping vertex coordinates as `f64`.
+pub(crate) fn parse_precise_mesh_element<R: BufRead>(
+    parser: &mut XmlParser<R>,
+    start: &BytesStart,
+) -> Result<PreciseMesh> {
+    let mut mesh: PreciseMesh = parse_mesh_into(parser)?;
+    mesh.volume_id = volume_id(start);
+    Ok(mesh)
+}
+
+/// Reads the Volumetric Extension `volumeid` of a `<mesh>` start tag.
+fn volume_id(start: &BytesStart) -> Option<ResourceId> {
+    get_attribute_u32(start, b"vol:volumeid")
+        .or_else(|_| get_attribute_u32(start, b"volumeid"))
+        .map(ResourceId)
+        .ok()
+}
+
+/// Parses a `<mesh>` element into any [`MeshStorage`], e.g. a
+/// [`CompactMesh`](crate::model::CompactMesh) for very large meshes.
+pub fn parse_mesh_into<R: BufRead, S: MeshStorage + Default>(
+    parser: &mut XmlParser<R>,
+) -> Result<S> {
+    let mut mesh = S::default();
 
     loop {
         match parser.read_next_event()? {
             Event::Start(e) => match e.local_name().as_ref() {
                 b"vertices" => parse_vertices(parser, &mut mesh)?,
                 b"triangles" => parse_triangles(parser, &mut mesh)?,
+                b"trianglesets" => mesh.set_triangle_sets(parse_triangle_sets(parser)?),
                 b"beamlattice" => {
                     let radius = get_attribute_f32(&e, b"radius").ok();
                     let min_length = get_attribute_f32(&e, b"minlength").unwrap_or(0.0);
@@ -38,7 +158,7 @@ pub fn parse_mesh<R: BufRead>(parser: &mut XmlParser<R>) -> Result<Mesh> {
                         precision,
                         clipping_mode,
                     )?;
-                    mesh.beam_lattice = Some(lattice);
+                    mesh.set_beam_lattice(lattice);
                 }
                 _ => {} // Ignore headers/metadata inside mesh for now
             },
@@ -55,14 +175,26 @@ pub fn parse_mesh<R: BufRead>(parser: &mut XmlParser<R>) -> Result<Mesh> {
     Ok(mesh)
 }
 
-fn parse_vertices<R: BufRead>(parser: &mut XmlParser<R>, mesh: &mut Mesh) -> Result<()> {
+fn parse_vertices<R: BufRead, S: MeshStorage>(
+    parser: &mut XmlParser<R>,
+    mesh: &mut S,
+) -> Result<()> {
     loop {
         match parser.read_next_event()? {
             Event::Start(e) | Event::Empty(e) if e.name().as_ref() == b"vertex" => {
-                let x = get_attribute_f32(&e, b"x")?;
-                let y = get_attribute_f32(&e, b"y")?;
-                let z = get_attribute_f32(&e, b"z")?;
-                mesh.vertices.push(Vertex { x, y, z });
+                if mesh.has_f64_coordinates() {
+                    let x = get_attribute_f64(&e, b"x")?;
+                    let y = get_attribute_f64(&e, b"y")?;
+                    let z = get_attribute_f64(&e, b"z")?;
+                    parser.charge_mesh_bytes(size_of::<DVec3>())?;
+                    mesh.push_vertex_f64(DVec3::new(x, y, z));
+                } else {
+                    let x = get_attribute_f32(&e, b"x")?;
+                    let y = get_attribute_f32(&e, b"y")?;
+                    let z = get_attribute_f32(&e, b"z")?;
+                    parser.charge_mesh_bytes(size_of::<Vertex>())?;
+                    mesh.push_vertex(Vertex { x, y, z });
+                }
             }
             Event::End(e) if e.name().as_ref() == b"vertices" => break,
             Event::Eof => {
@@ -76,7 +208,10 @@ fn parse_vertices<R: BufRead>(parser: &mut XmlParser<R>, mesh: &mut Mesh) -> Res
     Ok(())
 }
 
-fn parse_triangles<R: BufRead>(parser: &mut XmlParser<R>, mesh: &mut Mesh) -> Result<()> {
+fn parse_triangles<R: BufRead, S: MeshStorage>(
+    parser: &mut XmlParser<R>,
+    mesh: &mut S,
+) -> Result<()> {
     loop {
         match parser.read_next_event()? {
             Event::Start(e) | Event::Empty(e) if e.name().as_ref() == b"triangle" => {
@@ -88,7 +223,24 @@ fn parse_triangles<R: BufRead>(parser: &mut XmlParser<R>, mesh: &mut Mesh) -> Re
                 let p3 = get_attribute_u32(&e, b"p3").ok();
                 let pid = get_attribute_u32(&e, b"pid").ok();
 
-                mesh.triangles.push(Triangle {
+                // Keep other attributes, e.g. slicer paint data
+                let index = mesh.triangle_count() as u32;
+                for attr in e.attributes().flatten() {
+                    let key = attr.key.as_ref();
+                    if !matches!(key, b"v1" | b"v2" | b"v3" | b"p1" | b"p2" | b"p3" | b"pid") {
+                        let value = attr
+                            .unescape_value()
+                            .map_err(|e| Lib3mfError::Validation(e.to_string()))?;
+                        mesh.triangle_attributes_mut().set(
+                            String::from_utf8_lossy(key),
+                            index,
+                            value,
+                        );
+                    }
+                }
+
+                parser.charge_mesh_bytes(size_of::<Triangle>())?;
+                mesh.push_triangle(Triangle {
                     v1,
                     v2,
                     v3,
@@ -39,6 +39,34 @@
 //!
 //! See [`streaming`] module documentation for details.
 //!
+//! ### Parallel DOM Mode (`parallel` feature)
+//!
+//! [`parse_model_parallel`] takes the whole document as a byte slice, indexes the byte range of
+//! every `<object>` in a first pass, then parses the objects on the rayon thread pool. Resources
+//! are inserted in document order, so the result is identical to [`parse_model`]. Use it for
+//! build plates with many objects; single-object files gain nothing.
+//!
+//! ### Lazy Mode
+//!
+//! [`parse_model_lazy`] parses everything except mesh bodies: each `<mesh>` becomes a
+//! [`LazyMesh`](crate::model::LazyMesh) handle with its byte range and vertex/triangle counts,
+//! loaded later from the archive on demand. Listing objects or printing the component tree of
+//! a huge file then costs a tokenizing pass instead of building every triangle.
+//!
+//! ### Memory Budget
+//!
+//! [`parse_model_with_budget`] parses like [`parse_model`] but fails with a `FileTooLarge`
+//! I/O error as soon as the vertices, triangles and beams read so far would take more memory
+//! than the given cap. Servers loading untrusted uploads use it to bound each request; see
+//! [`Model::memory_usage`](crate::model::Model::memory_usage) for what a loaded model takes.
+//!
+//! ### Streaming Extraction
+//!
+//! [`extract::extract_object`] copies one object and the resources it references into a
+//! new package by indexing references in a first pass and copying the needed XML verbatim
+//! in a second, so a single part can be pulled out of a multi-gigabyte plate without
+//! loading it.
+//!
 //! ## Parser Architecture
 //!
 //! The parser is organized into specialized modules:
@@ -50,16 +78,21 @@
 //! - [`material_parser`]: Parses material resources (base materials, colors, textures, composites)
 //! - [`build_parser`]: Parses `<build>` section (what to print and where)
 //! - [`component_parser`]: Parses `<components>` (object references and transformations)
+//! - [`encoding`]: Detects UTF-16 input (BOM, byte pattern) and transcodes it to UTF-8
+//! - [`gzip`]: Detects gzip-compressed model XML and decompresses it
 //!
 //! ### Extension Parsers
 //!
 //! - [`beamlattice_parser`]: Beam Lattice Extension (structural lattices)
+//! - [`triangleset_parser`]: Triangle Sets Extension (named triangle groups)
 //! - [`slice_parser`]: Slice Extension (2D layer-based geometry for DLP/SLA)
-//! - [`volumetric_parser`]: Volumetric Extension (voxel data)
+//! - [`volumetric_parser`]: Volumetric Extension (voxel data, 3D images, volume data, level sets)
+//! - [`implicit_parser`]: Implicit Extension (function node graphs)
 //! - [`boolean_parser`]: Boolean Operations Extension (CSG operations)
 //! - [`displacement_parser`]: Displacement Extension (texture-driven surface modification)
 //! - [`crypto_parser`]: Digital signature metadata (always available, parses XML only)
-//! - `secure_content_parser`: Secure Content Extension (encryption/decryption, requires `crypto` feature)
+//! - `secure_content_parser`: Secure Content Extension (encryption/decryption, requires `crypto` feature).
+//!   `parse_encrypted_model` decrypts an encrypted model part with a consumer's private key
 //!
 //! ### Vendor Extensions
 //!
@@ -81,11 +114,15 @@
 //! - **InvalidStructure**: Malformed XML, missing required attributes, spec violations
 //! - **Io**: File reading errors, ZIP errors
 //! - **ResourceNotFound**: References to non-existent resource IDs
+//! - **EncryptedContent**: The model part is encrypted; see
+//!   [`check_model_not_encrypted`](crate::archive::check_model_not_encrypted)
 //! - **FeatureNotEnabled**: Trying to use `secure_content_parser` without `crypto` feature
 //!
 //! The parser never panics on invalid input.
 
-/// Bambu Studio project file parsers (plate info, filament config, print settings).
+/// Bambu Studio project file parsers (plate info, filament config, print settings; requires
+/// `vendor` feature).
+#[cfg(feature = "vendor")]
 pub mod bambu_config;
 /// Beam Lattice Extension parser.
 pub mod beamlattice_parser;
@@ -95,10 +132,23 @@ pub mod boolean_parser;
 pub mod build_parser;
 /// Component and components element parser.
 pub mod component_parser;
+/// OPC core properties parser (`docProps/core.xml`).
+pub mod core_properties_parser;
 /// XML-DSIG crypto structure parser (always available; no crypto operations).
 pub mod crypto_parser;
 /// Displacement Extension parser.
 pub mod displacement_parser;
+/// Encoding detection and transcoding of UTF-16 input to UTF-8.
+pub mod encoding;
+/// Streaming extraction of single objects (and what they reference) into a new package
+/// (requires `writer` feature).
+#[cfg(feature = "writer")]
+pub mod extract;
+/// Transparent decompression of gzip-compressed model XML.
+pub mod gzip;
+pub mod header_parser;
+/// Implicit Extension function parser.
+pub mod implicit_parser;
 /// Materials and Properties Extension parser.
 pub mod material_parser;
 /// Mesh geometry parser (`<vertices>` and `<triangles>`).
@@ -112,17 +162,24 @@ pub mod secure_content_parser;
 pub mod slice_parser;
 /// SAX/streaming parser for memory-efficient processing of large 3MF files.
 pub mod streaming;
+/// Triangle Sets Extension parser.
+pub mod triangleset_parser;
 /// `ModelVisitor` trait for streaming parser callbacks.
 pub mod visitor;
+/// Detection of elements and attributes the 3MF schemas don't define.
+pub(crate) mod vocabulary;
 /// Volumetric Extension parser.
 pub mod volumetric_parser;
 /// Low-level XML parser primitives used by all parser modules.
 pub mod xml_parser;
 
+#[cfg(feature = "vendor")]
 pub use bambu_config::{
     parse_model_settings, parse_profile_config, parse_project_settings, parse_slice_info,
 };
+pub use core_properties_parser::parse_core_properties;
 pub use crypto_parser::parse_signature;
+pub use header_parser::{ModelHeader, parse_model_header};
 /// Primary entry point for parsing 3MF model XML.
 ///
 /// Converts XML content into an in-memory [`Model`](crate::model::Model) structure with all resources,
@@ -160,4 +217,11 @@ pub use crypto_parser::parse_signature;
 /// # }
 /// ```
 pub use model_parser::parse_model;
+pub use model_parser::parse_model_lazy;
+#[cfg(feature = "parallel")]
+pub use model_parser::parse_model_parallel;
+pub use model_parser::parse_model_with_budget;
+pub use model_parser::{ParseOptions, RequiredExtensionPolicy, parse_model_with_options};
+#[cfg(feature = "crypto")]
+pub use secure_content_parser::parse_encrypted_model;
 pub use xml_parser::XmlParser;
@@ -1,26 +1,258 @@
 use crate::error::{Lib3mfError, Result};
 use crate::model::{
-    BaseMaterialsGroup, ColorGroup, CompositeMaterials, Geometry, Model, MultiProperties, Object,
-    Texture2DGroup, Unit,
+    BaseMaterialsGroup, ColorGroup, CompositeMaterials, Extension, Function, Geometry, LazyMesh,
+    MeshInterner, MetadataEntry, Model, MultiProperties, Object, Texture2DGroup, Unit,
 };
 use crate::parser::boolean_parser::parse_boolean_shape;
 use crate::parser::build_parser::parse_build;
 use crate::parser::component_parser::parse_components;
 use crate::parser::displacement_parser::{parse_displacement_2d, parse_displacement_mesh};
+use crate::parser::gzip;
+use crate::parser::implicit_parser::parse_implicit_function;
 use crate::parser::material_parser::{
     parse_base_materials, parse_color_group, parse_composite_materials, parse_multi_properties,
-    parse_texture_2d_group,
+    parse_texture_2d, parse_texture_2d_group,
 };
-use crate::parser::mesh_parser::parse_mesh;
+use crate::parser::mesh_parser::{parse_mesh_element, parse_precise_mesh_element};
 use crate::parser::slice_parser::parse_slice_stack_content;
-use crate::parser::volumetric_parser::parse_volumetric_stack_content;
+use crate::parser::volumetric_parser::{
+    parse_function_from_image_3d, parse_image_3d, parse_level_set, parse_volume_data,
+    parse_volumetric_stack_content,
+};
 use crate::parser::xml_parser::{XmlParser, get_attribute, get_attribute_f32, get_attribute_u32};
-use quick_xml::events::Event;
+use crate::trace::{phase, span};
+use quick_xml::events::{BytesStart, Event};
+use std::collections::HashMap;
 use std::io::BufRead;
+use std::ops::Range;
 
 /// Parses a complete 3MF model XML document from the given reader into a `Model`.
 pub fn parse_model<R: BufRead>(reader: R) -> Result<Model> {
+    parse_model_with(reader, ParseMode::default())
+}
+
+/// Options for [`parse_model_with_options`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Record elements and attributes the 3MF schemas don't define, with their element
+    /// paths, in [`Model::unknown_names`]. Strict validation reports them as warnings.
+    ///
+    /// Off by default, since checking every name costs parse time.
+    pub track_unknown_names: bool,
+    /// Fail once mesh data would take more than this many bytes; see
+    /// [`parse_model_with_budget`].
+    pub max_mesh_bytes: Option<u64>,
+    /// What to do when `requiredextensions` lists an extension this library doesn't
+    /// support.
+    pub unsupported_extensions: RequiredExtensionPolicy,
+    /// Share identical vertex and triangle arrays between the meshes parsed with this
+    /// interner; see [`MeshInterner`].
+    ///
+    /// Off by default, since hashing every mesh costs parse time. Reuse one interner for
+    /// every model part of a package to share arrays across parts.
+    pub intern_meshes: Option<MeshInterner>,
+    /// Keep vertex coordinates as `f64`, parsing meshes into
+    /// [`Geometry::PreciseMesh`] instead of [`Geometry::Mesh`].
+    ///
+    /// Off by default, since `f64` coordinates take twice the memory. Statistics, repair and
+    /// [`Model::write`] use the `f64` coordinates; geometry validation checks their `f32`
+    /// view, and most editing operations only handle `Geometry::Mesh`.
+    pub precise_coordinates: bool,
+}
+
+/// How the parser treats required extensions it doesn't support.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RequiredExtensionPolicy {
+    /// Fail with [`Lib3mfError::UnsupportedExtension`], as the core specification requires.
+    #[default]
+    Fail,
+    /// Parse the model anyway, ignoring that extension's content. The entries stay in
+    /// [`Model::required_extensions`], and validation reports each as a warning.
+    Warn,
+}
+
+/// Like [`parse_model`], configured by `options`.
+pub fn parse_model_with_options<R: BufRead>(reader: R, options: &ParseOptions) -> Result<Model> {
+    parse_model_with(
+        reader,
+        ParseMode {
+            mesh_budget: options.max_mesh_bytes,
+            track_unknown_names: options.track_unknown_names,
+            unsupported_extensions: options.unsupported_extensions,
+            interner: options.intern_meshes.as_ref(),
+            precise: options.precise_coordinates,
+            ..Default::default()
+        },
+    )
+}
+
+/// Like [`parse_model`], but fails once the model's mesh data would take more than
+/// `max_mesh_bytes` of memory.
+///
+/// Vertices, triangles, beams and displacement mesh data are charged at their in-memory size
+/// as they are read, across all objects in the part, so an oversized model is rejected
+/// before it is allocated. Intended for servers that load untrusted files side by side.
+///
+/// # Errors
+///
+/// Returns [`Lib3mfError::Io`] with [`std::io::ErrorKind::FileTooLarge`] when the budget is
+/// exceeded, and the same errors as [`parse_model`] otherwise.
+pub fn parse_model_with_budget<R: BufRead>(reader: R, max_mesh_bytes: u64) -> Result<Model> {
+    parse_model_with(
+        reader,
+        ParseMode {
+            mesh_budget: Some(max_mesh_bytes),
+            ..Default::default()
+        },
+    )
+}
+
+/// Parses a model part without building its meshes.
+///
+/// Every object whose geometry is a `<mesh>` gets a [`Geometry::LazyMesh`] recording the
+/// element's byte range in the part together with its vertex and triangle counts. All other
+/// content (materials, components, build, metadata) is parsed as usual. `part` is the
+/// archive path of the part being read; it is stored in each handle so the mesh can later
+/// be loaded with [`LazyMesh::load`](crate::model::LazyMesh::load).
+///
+/// `reader` must yield the decompressed part from its first byte, since the recorded ranges
+/// are offsets into it.
+pub fn parse_model_lazy<R: BufRead>(reader: R, part: &str) -> Result<Model> {
+    parse_model_with(
+        reader,
+        ParseMode {
+            lazy_part: Some(part),
+            ..Default::default()
+        },
+    )
+}
+
+/// Parses a complete 3MF model XML document held in memory, parsing objects in parallel.
+///
+/// The document is parsed in two phases. The first pass handles everything except
+/// `<object>` elements, whose byte ranges are recorded and skipped. The second pass parses
+/// those ranges on the rayon thread pool. Objects are then added in document order, so the
+/// resulting model and the first reported error are the same on every run.
+///
+/// Produces the same `Model` as [`parse_model`], which remains the better choice for
+/// documents with only a handful of objects.
+#[cfg(feature = "parallel")]
+pub fn parse_model_parallel(data: &[u8]) -> Result<Model> {
+    use rayon::prelude::*;
+
+    // Spans index the UTF-8 document, so decompress and transcode other encodings up front.
+    let data = &*crate::parser::gzip::decompress(data)?;
+    let data = &*crate::parser::encoding::decode_xml(data)?;
+    let mut spans = Vec::new();
+    let mode = ParseMode {
+        deferred_objects: Some(&mut spans),
+        ..Default::default()
+    };
+    let mut model = parse_model_with(data, mode)?;
+
+    let _phase = phase!(span::PARSE_OBJECTS_PARALLEL, objects = spans.len());
+    let objects: Vec<Result<Object>> = spans
+        .into_par_iter()
+        .map(|span| parse_object_fragment(&data[span]))
+        .collect();
+    for object in objects {
+        model.resources.add_object(object?)?;
+    }
+    Ok(model)
+}
+
+/// Parses a standalone `<object>...</object>` fragment recorded by the first phase.
+#[cfg(feature = "parallel")]
+fn parse_object_fragment(fragment: &[u8]) -> Result<Object> {
+    let mut parser = XmlParser::new(fragment);
+    loop {
+        match parser.read_next_event()? {
+            Event::Start(e) if e.local_name().as_ref() == b"object" => {
+                let start = e.into_owned();
+                return parse_object(&mut parser, &start, None, false);
+            }
+            Event::Eof => {
+                return Err(Lib3mfError::Validation(
+                    "Unexpected EOF in object".to_string(),
+                ));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// How the shared driver treats `<object>` and `<mesh>` elements.
+#[derive(Default)]
+struct ParseMode<'a> {
+    /// Skip `<object>` elements and append their byte ranges here (parallel phase one).
+    deferred_objects: Option<&'a mut Vec<Range<usize>>>,
+    /// Skip `<mesh>` elements and record them as lazy meshes of this part.
+    lazy_part: Option<&'a str>,
+    /// Fail once parsed mesh data passes this many bytes.
+    mesh_budget: Option<u64>,
+    /// Record unknown element and attribute names in the model.
+    track_unknown_names: bool,
+    /// Whether unsupported required extensions fail the parse.
+    unsupported_extensions: RequiredExtensionPolicy,
+    /// Intern the arrays of each parsed mesh.
+    interner: Option<&'a MeshInterner>,
+    /// Parse meshes into `PreciseMesh`.
+    precise: bool,
+}
+
+/// Reads the attributes of a `<metadata>` element into an entry with an empty value.
+pub(crate) fn parse_metadata_entry(e: &BytesStart) -> Result<MetadataEntry> {
+    let name = get_attribute(e, b"name")
+        .ok_or(Lib3mfError::Validation("Metadata missing name".to_string()))?;
+    let mut entry = MetadataEntry::new(name, String::new());
+    entry.value_type = get_attribute(e, b"type").map(|s| s.into_owned());
+    entry.preserve =
+        get_attribute(e, b"preserve").is_some_and(|s| matches!(s.as_ref(), "1" | "true"));
+    entry.lang = get_attribute(e, b"xml:lang").map(|s| s.into_owned());
+    Ok(entry)
+}
+
+/// Adds a metadata entry, rejecting a second entry with the same name and language.
+fn push_metadata(model: &mut Model, entry: MetadataEntry) -> Result<()> {
+    if model
+        .metadata
+        .get_all(&entry.name)
+        .any(|e| e.lang == entry.lang)
+    {
+        return Err(Lib3mfError::Validation(format!(
+            "Duplicate metadata name '{}'. Each metadata name must be unique",
+            entry.name
+        )));
+    }
+    model.metadata.push(entry);
+    Ok(())
+}
+
+/// Shared driver for [`parse_model`], [`parse_model_with_options`],
+/// [`parse_model_with_budget`], [`parse_model_lazy`], and `parse_model_parallel`.
+///
+/// Gzip-compressed input is decompressed first, except in lazy mode; see
+/// [`crate::parser::gzip`].
+fn parse_model_with<R: BufRead>(mut reader: R, mode: ParseMode) -> Result<Model> {
+    if mode.lazy_part.is_none() && gzip::is_gzip(reader.fill_buf()?) {
+        return parse_model_xml(gzip::gunzip(reader), mode);
+    }
+    parse_model_xml(reader, mode)
+}
+
+fn parse_model_xml<R: BufRead>(reader: R, mut mode: ParseMode) -> Result<Model> {
+    let phase = phase!(
+        span::PARSE_MODEL,
+        objects = tracing::field::Empty,
+        build_items = tracing::field::Empty
+    );
     let mut parser = XmlParser::new(reader);
+    if let Some(max_bytes) = mode.mesh_budget {
+        parser.set_mesh_budget(max_bytes);
+    }
+    if mode.track_unknown_names {
+        parser.track_unknown_names();
+    }
     let mut model = Model::default();
     let mut seen_model_element = false;
     let mut seen_build_element = false;
@@ -51,62 +283,69 @@ pub fn parse_model<R: BufRead>(reader: R) -> Result<Model> {
                     }
 
                     if let Some(unit_str) = get_attribute(&e, b"unit") {
-                        model.unit = match unit_str.as_ref() {
-                            "micron" => Unit::Micron,
-                            "millimeter" => Unit::Millimeter,
-                            "centimeter" => Unit::Centimeter,
-                            "inch" => Unit::Inch,
-                            "foot" => Unit::Foot,
-                            "meter" => Unit::Meter,
-                            _ => Unit::Millimeter, // Default or warn?
-                        };
+                        model.unit = unit_str.parse::<Unit>()?;
                     }
                     model.language = get_attribute(&e, b"xml:lang").map(|s| s.into_owned());
 
                     // Extract extra namespace declarations (e.g., xmlns:BambuStudio)
+                    let mut namespaces = HashMap::new();
                     for attr in e.attributes().flatten() {
                         let key = std::str::from_utf8(attr.key.as_ref()).unwrap_or("");
                         if let Some(prefix) = key.strip_prefix("xmlns:") {
+                            let uri = String::from_utf8_lossy(&attr.value).to_string();
                             // Skip known namespaces that we already emit
-                            let known = ["m", "p", "b", "d", "s", "v", "sec", "bl"];
+                            let known =
+                                ["m", "p", "b", "d", "s", "v", "vol", "i", "t", "sec", "bl"];
                             if !known.contains(&prefix) {
-                                let uri = String::from_utf8_lossy(&attr.value).to_string();
-                                model.extra_namespaces.insert(prefix.to_string(), uri);
+                                model
+                                    .extra_namespaces
+                                    .insert(prefix.to_string(), uri.clone());
                             }
+                            if Extension::from_namespace(&uri) == Some(Extension::Production) {
+                                model.production_namespace = Some(uri.clone());
+                            }
+                            namespaces.insert(prefix.to_string(), uri);
+                        }
+                    }
+
+                    if let Some(required) = get_attribute(&e, b"requiredextensions") {
+                        for prefix in required.split_whitespace() {
+                            let uri = namespaces.get(prefix);
+                            if uri.and_then(|uri| Extension::from_namespace(uri)).is_none()
+                                && mode.unsupported_extensions == RequiredExtensionPolicy::Fail
+                            {
+                                return Err(Lib3mfError::UnsupportedExtension(match uri {
+                                    Some(uri) => format!("'{}' ({})", prefix, uri),
+                                    None => format!("'{}' has no namespace declaration", prefix),
+                                }));
+                            }
+                            model
+                                .required_extensions
+                                .push(uri.map_or(prefix, String::as_str).to_string());
                         }
                     }
                 }
                 b"metadata" => {
-                    let name = get_attribute(&e, b"name")
-                        .ok_or(Lib3mfError::Validation("Metadata missing name".to_string()))?
-                        .into_owned();
-                    if model.metadata.contains_key(&name) {
-                        return Err(Lib3mfError::Validation(format!(
-                            "Duplicate metadata name '{}'. Each metadata name must be unique",
-                            name
-                        )));
-                    }
-                    let content = parser.read_text_content()?;
-                    model.metadata.insert(name, content);
+                    let mut entry = parse_metadata_entry(&e)?;
+                    entry.value = parser.read_text_content()?;
+                    push_metadata(&mut model, entry)?;
+                }
+                b"resources" => {
+                    let _phase = phase!(span::PARSE_RESOURCES);
+                    parse_resources(&mut parser, &mut model, &mut mode)?
                 }
-                b"resources" => parse_resources(&mut parser, &mut model)?,
                 b"build" => {
+                    let _phase = phase!(span::PARSE_BUILD);
                     seen_build_element = true;
+                    let uuid = crate::parser::xml_parser::get_attribute_uuid(&e)?;
                     model.build = parse_build(&mut parser)?;
+                    model.build.uuid = uuid;
                 }
                 _ => {}
             },
             Event::Empty(e) => {
                 if e.name().as_ref() == b"metadata" {
-                    let name = get_attribute(&e, b"name")
-                        .ok_or(Lib3mfError::Validation("Metadata missing name".to_string()))?;
-                    if model.metadata.contains_key(name.as_ref()) {
-                        return Err(Lib3mfError::Validation(format!(
-                            "Duplicate metadata name '{}'. Each metadata name must be unique",
-                            name
-                        )));
-                    }
-                    model.metadata.insert(name.into_owned(), String::new());
+                    push_metadata(&mut model, parse_metadata_entry(&e)?)?;
                 }
             }
             Event::End(e) if e.name().as_ref() == b"model" => {
@@ -124,91 +363,43 @@ pub fn parse_model<R: BufRead>(reader: R) -> Result<Model> {
         ));
     }
 
+    model.unknown_names = parser.take_unknown_names();
+    phase.record("objects", model.resources.iter_objects().count());
+    phase.record("build_items", model.build.items.len());
     Ok(model)
 }
 
-fn parse_resources<R: BufRead>(parser: &mut XmlParser<R>, model: &mut Model) -> Result<()> {
+fn parse_resources<R: BufRead>(
+    parser: &mut XmlParser<R>,
+    model: &mut Model,
+    mode: &mut ParseMode,
+) -> Result<()> {
     loop {
+        // Offset of the next event, including any whitespace before its tag.
+        let event_start = parser.reader.buffer_position() as usize;
         match parser.read_next_event()? {
             Event::Start(e) => {
                 let local_name = e.local_name();
                 match local_name.as_ref() {
-                    b"object" => {
-                        let id = crate::model::ResourceId(get_attribute_u32(&e, b"id")?);
-                        let name = get_attribute(&e, b"name").map(|s| s.into_owned());
-                        let part_number = get_attribute(&e, b"partnumber").map(|s| s.into_owned());
-                        let pid = get_attribute_u32(&e, b"pid")
-                            .map(crate::model::ResourceId)
-                            .ok();
-                        let pindex = get_attribute_u32(&e, b"pindex").ok();
-                        let uuid = crate::parser::xml_parser::get_attribute_uuid(&e)?;
-
-                        // Check for slicestackid (default or prefixed)
-                        let slice_stack_id = get_attribute_u32(&e, b"slicestackid")
-                            .or_else(|_| get_attribute_u32(&e, b"s:slicestackid"))
-                            .map(crate::model::ResourceId)
-                            .ok();
-
-                        // Check for volumetricstackid (hypothetical prefix v:)
-                        let vol_stack_id = get_attribute_u32(&e, b"volumetricstackid")
-                            .or_else(|_| get_attribute_u32(&e, b"v:volumetricstackid"))
-                            .map(crate::model::ResourceId)
-                            .ok();
-
-                        let object_type = match get_attribute(&e, b"type") {
-                            Some(type_str) => match type_str.as_ref() {
-                                "model" => crate::model::ObjectType::Model,
-                                "support" => crate::model::ObjectType::Support,
-                                "solidsupport" => crate::model::ObjectType::SolidSupport,
-                                "surface" => crate::model::ObjectType::Surface,
-                                "other" => crate::model::ObjectType::Other,
-                                unknown => {
-                                    return Err(Lib3mfError::Validation(format!(
-                                        "Invalid object type '{}'. Valid types are: model, support, solidsupport, surface, other",
-                                        unknown
-                                    )));
-                                }
-                            },
-                            None => crate::model::ObjectType::Model,
-                        };
-
-                        let thumbnail = get_attribute(&e, b"thumbnail").map(|s| s.into_owned());
-
-                        let geometry_content = parse_object_geometry(parser)?;
-
-                        let geometry = if let Some(ssid) = slice_stack_id {
-                            if geometry_content.has_content() {
-                                eprintln!(
-                                    "Warning: Object {} has slicestackid but also contains geometry content; geometry will be ignored",
-                                    id.0
-                                );
-                            }
-                            crate::model::Geometry::SliceStack(ssid)
-                        } else if let Some(vsid) = vol_stack_id {
-                            if geometry_content.has_content() {
-                                eprintln!(
-                                    "Warning: Object {} has volumetricstackid but also contains geometry content; geometry will be ignored",
-                                    id.0
-                                );
+                    b"object" => match mode.deferred_objects.as_deref_mut() {
+                        Some(spans) => {
+                            let name = e.name().as_ref().to_vec();
+                            parser.read_to_end(&name)?;
+                            spans.push(event_start..parser.reader.buffer_position() as usize);
+                        }
+                        None => {
+                            // Own the start tag so the parser can be borrowed for the body.
+                            let start = e.clone().into_owned();
+                            let mut object =
+                                parse_object(parser, &start, mode.lazy_part, mode.precise)?;
+                            if let (Some(interner), Geometry::Mesh(mesh)) =
+                                (mode.interner, &mut object.geometry)
+                            {
+                                interner.intern(mesh);
                             }
-                            crate::model::Geometry::VolumetricStack(vsid)
-                        } else {
-                            geometry_content
-                        };
-
-                        let object = Object {
-                            id,
-                            object_type,
-                            name,
-                            part_number,
-                            uuid,
-                            pid,
-                            pindex,
-                            thumbnail,
-                            geometry,
-                        };
-                        model.resources.add_object(object)?;
-                    }
+                            model.resources.add_object(object)?;
+                        }
+                    },
                     b"basematerials" => {
                         let id = crate::model::ResourceId(get_attribute_u32(&e, b"id")?);
                         let group = parse_base_materials(parser, id)?;
@@ -220,35 +411,7 @@ fn parse_resources<R: BufRead>(parser: &mut XmlParser<R>, model: &mut Model) ->
                         model.resources.add_color_group(group)?;
                     }
                     b"texture2d" => {
-                        let id = crate::model::ResourceId(get_attribute_u32(&e, b"id")?);
-                        let path = get_attribute(&e, b"path")
-                            .ok_or(Lib3mfError::Validation(
-                                "texture2d missing required 'path' attribute".to_string(),
-                            ))?
-                            .into_owned();
-                        let contenttype = get_attribute(&e, b"contenttype")
-                            .ok_or(Lib3mfError::Validation(
-                                "texture2d missing required 'contenttype' attribute".to_string(),
-                            ))?
-                            .into_owned();
-
-                        // Validate content type - must be a valid image MIME type
-                        if contenttype.is_empty()
-                            || (!contenttype.starts_with("image/png")
-                                && !contenttype.starts_with("image/jpeg")
-                                && !contenttype.starts_with("image/jpg"))
-                        {
-                            return Err(Lib3mfError::Validation(format!(
-                                "Invalid contenttype '{}'. Must be 'image/png' or 'image/jpeg'",
-                                contenttype
-                            )));
-                        }
-
-                        let texture = crate::model::Texture2D {
-                            id,
-                            path,
-                            contenttype,
-                        };
+                        let texture = parse_texture_2d(&e)?;
                         model.resources.add_texture_2d(texture)?;
                     }
                     b"texture2dgroup" => {
@@ -324,6 +487,29 @@ fn parse_resources<R: BufRead>(parser: &mut XmlParser<R>, model: &mut Model) ->
                         let stack = parse_volumetric_stack_content(parser, id, 0.0)?;
                         model.resources.add_volumetric_stack(stack)?;
                     }
+                    b"image3d" => {
+                        let start = e.clone().into_owned();
+                        let image = parse_image_3d(parser, &start)?;
+                        model.resources.add_image_3d(image)?;
+                    }
+                    b"functionfromimage3d" => {
+                        let function = parse_function_from_image_3d(&e)?;
+                        let end_tag = e.name().as_ref().to_vec();
+                        parser.read_to_end(&end_tag)?;
+                        model
+                            .resources
+                            .add_function(Function::FromImage3D(function))?;
+                    }
+                    b"implicitfunction" => {
+                        let start = e.clone().into_owned();
+                        let function = parse_implicit_function(parser, &start)?;
+                        model.resources.add_function(Function::Implicit(function))?;
+                    }
+                    b"volumedata" => {
+                        let id = crate::model::ResourceId(get_attribute_u32(&e, b"id")?);
+                        let data = parse_volume_data(parser, id)?;
+                        model.resources.add_volume_data(data)?;
+                    }
                     b"booleanshape" => {
                         let id = crate::model::ResourceId(get_attribute_u32(&e, b"id")?);
                         let base_object_id =
@@ -521,7 +707,84 @@ fn parse_resources<R: BufRead>(parser: &mut XmlParser<R>, model: &mut Model) ->
     Ok(())
 }
 
-fn parse_object_geometry<R: BufRead>(parser: &mut XmlParser<R>) -> Result<Geometry> {
+/// Parses an `<object>` element whose start tag is `e`, consuming events through `</object>`.
+///
+/// With `lazy_part` set, a `<mesh>` child is indexed as a lazy mesh of that part. Otherwise
+/// it is parsed into a `PreciseMesh` if `precise` is set.
+fn parse_object<R: BufRead>(
+    parser: &mut XmlParser<R>,
+    e: &BytesStart,
+    lazy_part: Option<&str>,
+    precise: bool,
+) -> Result<Object> {
+    let id = crate::model::ResourceId(get_attribute_u32(e, b"id")?);
+    let name = get_attribute(e, b"name").map(|s| s.into_owned());
+    let part_number = get_attribute(e, b"partnumber").map(|s| s.into_owned());
+    let pid = get_attribute_u32(e, b"pid")
+        .map(crate::model::ResourceId)
+        .ok();
+    let pindex = get_attribute_u32(e, b"pindex").ok();
+    let uuid = crate::parser::xml_parser::get_attribute_uuid(e)?;
+
+    // Check for slicestackid (default or prefixed)
+    let slice_stack_id = get_attribute_u32(e, b"slicestackid")
+        .or_else(|_| get_attribute_u32(e, b"s:slicestackid"))
+        .map(crate::model::ResourceId)
+        .ok();
+
+    // Check for volumetricstackid (hypothetical prefix v:)
+    let vol_stack_id = get_attribute_u32(e, b"volumetricstackid")
+        .or_else(|_| get_attribute_u32(e, b"v:volumetricstackid"))
+        .map(crate::model::ResourceId)
+        .ok();
+
+    let object_type = match get_attribute(e, b"type") {
+        Some(type_str) => type_str.parse::<crate::model::ObjectType>()?,
+        None => crate::model::ObjectType::Model,
+    };
+
+    let thumbnail = get_attribute(e, b"thumbnail").map(|s| s.into_owned());
+
+    let geometry_content = parse_object_geometry(parser, lazy_part, precise)?;
+
+    let geometry = if let Some(ssid) = slice_stack_id {
+        if geometry_content.has_content() {
+            eprintln!(
+                "Warning: Object {} has slicestackid but also contains geometry content; geometry will be ignored",
+                id.0
+            );
+        }
+        crate::model::Geometry::SliceStack(ssid)
+    } else if let Some(vsid) = vol_stack_id {
+        if geometry_content.has_content() {
+            eprintln!(
+                "Warning: Object {} has volumetricstackid but also contains geometry content; geometry will be ignored",
+                id.0
+            );
+        }
+        crate::model::Geometry::VolumetricStack(vsid)
+    } else {
+        geometry_content
+    };
+
+    Ok(Object {
+        id,
+        object_type,
+        name,
+        part_number,
+        uuid,
+        pid,
+        pindex,
+        thumbnail,
+        geometry,
+    })
+}
+
+fn parse_object_geometry<R: BufRead>(
+    parser: &mut XmlParser<R>,
+    lazy_part: Option<&str>,
+    precise: bool,
+) -> Result<Geometry> {
     // We are inside <object> tag. We expect either <mesh> or <components> next.
     // NOTE: object is open. We read until </object>.
 
@@ -532,19 +795,36 @@ fn parse_object_geometry<R: BufRead>(parser: &mut XmlParser<R>) -> Result<Geomet
     let mut geometry = Geometry::Mesh(crate::model::Mesh::default()); // Default fallback? Or Option/Result?
 
     loop {
+        let event_start = parser.reader.buffer_position() as usize;
         match parser.read_next_event()? {
             Event::Start(e) => {
                 let local_name = e.local_name();
                 match local_name.as_ref() {
-                    b"mesh" => {
-                        geometry = Geometry::Mesh(parse_mesh(parser)?);
-                    }
+                    b"mesh" => match lazy_part {
+                        Some(part) => {
+                            geometry = Geometry::LazyMesh(skip_mesh(parser, part, event_start)?);
+                        }
+                        None if precise => {
+                            let start = e.clone().into_owned();
+                            geometry =
+                                Geometry::PreciseMesh(parse_precise_mesh_element(parser, &start)?);
+                        }
+                        None => {
+                            let start = e.clone().into_owned();
+                            geometry = Geometry::Mesh(parse_mesh_element(parser, &start)?);
+                        }
+                    },
                     b"components" => {
                         geometry = Geometry::Components(parse_components(parser)?);
                     }
                     b"displacementmesh" => {
                         geometry = Geometry::DisplacementMesh(parse_displacement_mesh(parser)?);
                     }
+                    b"levelset" => {
+                        geometry = Geometry::LevelSet(parse_level_set(&e)?);
+                        let end_tag = e.name().as_ref().to_vec();
+                        parser.read_to_end(&end_tag)?;
+                    }
                     _ => {}
                 }
             }
@@ -559,3 +839,37 @@ fn parse_object_geometry<R: BufRead>(parser: &mut XmlParser<R>) -> Result<Geomet
     }
     Ok(geometry)
 }
+
+/// Skips a `<mesh>` element whose start tag has just been read, counting its vertices and
+/// triangles. `start` is the input offset at which the start tag was read.
+fn skip_mesh<R: BufRead>(parser: &mut XmlParser<R>, part: &str, start: usize) -> Result<LazyMesh> {
+    let mut vertex_count = 0;
+    let mut triangle_count = 0;
+    let mut depth = 0usize;
+    loop {
+        match parser.read_next_event()? {
+            Event::Start(e) => {
+                match e.local_name().as_ref() {
+                    b"vertex" => vertex_count += 1,
+                    b"triangle" => triangle_count += 1,
+                    _ => {}
+                }
+                depth += 1;
+            }
+            Event::End(_) if depth > 0 => depth -= 1,
+            Event::End(_) => break,
+            Event::Eof => {
+                return Err(Lib3mfError::Validation(
+                    "Unexpected EOF in mesh".to_string(),
+                ));
+            }
+            _ => {}
+        }
+    }
+    Ok(LazyMesh {
+        part: part.to_string(),
+        span: start..parser.reader.buffer_position() as usize,
+        vertex_count,
+        triangle_count,
+    })
+}
@@ -1,11 +1,110 @@
+use crate::archive::{ArchiveReader, find_keystore_path, find_model_path};
+use crate::crypto::context::SecureContext;
 use crate::error::{Lib3mfError, Result};
-use crate::model::{AccessRight, Consumer, KeyStore, ResourceDataGroup};
+use crate::model::{AccessRight, Consumer, KeyStore, Model, ResourceDataGroup};
+use crate::parser::model_parser::parse_model;
 use crate::parser::xml_parser::{XmlParser, get_attribute};
 use base64::prelude::*;
-use quick_xml::events::Event;
+use quick_xml::events::{BytesStart, Event};
+use rsa::RsaPrivateKey;
 use std::borrow::Cow;
-use std::io::BufRead;
+use std::io::{BufRead, Cursor};
 
+/// Parses the root model of a package, decrypting the model part if it is encrypted.
+///
+/// The key store is found through the package relationships. If it lists the model part
+/// in a resource data group, the content key is unwrapped from whichever access right
+/// `private_key` matches and the part is decrypted before parsing. Packages without a key
+/// store, or whose model part is not encrypted, are parsed as usual. The key store is kept
+/// in `model.resources.key_store`.
+///
+/// # Errors
+///
+/// Returns [`Lib3mfError::EncryptionError`] if `private_key` matches no access right or
+/// decryption fails, besides the usual parse errors.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lib3mf_core::archive::ZipArchiver;
+/// use lib3mf_core::crypto::keys::KeyManager;
+/// use lib3mf_core::parser::parse_encrypted_model;
+/// use std::fs::File;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut archiver = ZipArchiver::new(File::open("secure.3mf")?)?;
+/// let key = KeyManager::load_private_key("consumer.pem")?;
+/// let model = parse_encrypted_model(&mut archiver, &key)?;
+/// println!("Objects: {}", model.resources.iter_objects().count());
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_encrypted_model<A: ArchiveReader>(
+    archiver: &mut A,
+    private_key: &RsaPrivateKey,
+) -> Result<Model> {
+    let model_path = find_model_path(archiver)?;
+    let keystore = match find_keystore_path(archiver)? {
+        Some(path) => Some(parse_keystore(Cursor::new(archiver.read_entry(&path)?))?),
+        None => None,
+    };
+
+    let data = match &keystore {
+        Some(store) if store.group_for_path(&model_path).is_some() => {
+            let mut context = SecureContext::for_private_key(store.clone(), private_key.clone())?;
+            context
+                .decrypt_entry(archiver, &model_path)?
+                .ok_or_else(|| {
+                    Lib3mfError::EncryptionError(format!(
+                        "Model part {} is not covered by the KeyStore",
+                        model_path
+                    ))
+                })?
+        }
+        _ => archiver.read_entry(&model_path)?,
+    };
+
+    let mut model = parse_model(Cursor::new(data))?;
+    if let Some(store) = keystore {
+        model.resources.set_key_store(store);
+    }
+    Ok(model)
+}
+
+/// Parses a standalone key store part (e.g. `Secure/keystore.xml`).
+///
+/// The store's UUID is taken from the `UUID` attribute of `<keystore>`, or generated if
+/// absent.
+pub fn parse_keystore<R: BufRead>(reader: R) -> Result<KeyStore> {
+    let mut parser = XmlParser::new(reader);
+    loop {
+        match parser.read_next_event()? {
+            Event::Start(e) if e.local_name().as_ref() == b"keystore" => {
+                let uuid = keystore_uuid(&e)?;
+                return parse_keystore_content(&mut parser, uuid);
+            }
+            Event::Empty(e) if e.local_name().as_ref() == b"keystore" => {
+                return Ok(KeyStore::new(keystore_uuid(&e)?));
+            }
+            Event::Eof => {
+                return Err(Lib3mfError::Validation(
+                    "No keystore element found".to_string(),
+                ));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn keystore_uuid(e: &BytesStart) -> Result<uuid::Uuid> {
+    match get_attribute(e, b"UUID") {
+        Some(s) => uuid::Uuid::parse_str(&s)
+            .map_err(|_| Lib3mfError::Validation(format!("Invalid keystore UUID: {}", s))),
+        None => Ok(uuid::Uuid::new_v4()),
+    }
+}
+
+/// Parses the children of a `<keystore>` element whose start tag has been consumed.
 pub fn parse_keystore_content<R: BufRead>(
     parser: &mut XmlParser<R>,
     uuid: uuid::Uuid,
@@ -70,7 +169,7 @@ fn parse_resource_data_group<R: BufRead>(
 ) -> Result<ResourceDataGroup> {
     let mut group = ResourceDataGroup {
         key_uuid,
-        access_rights: Vec::new(),
+        ..Default::default()
     };
 
     loop {
@@ -90,12 +189,24 @@ fn parse_resource_data_group<R: BufRead>(
                             wrapped_key,
                         });
                     }
+                    b"resourcedata" => {
+                        if let Some(path) = get_attribute(&e, b"path") {
+                            group.resource_paths.push(path.into_owned());
+                        }
+                        let end_tag = e.name().as_ref().to_vec();
+                        parser.read_to_end(&end_tag)?;
+                    }
                     _ => {
                         let end_tag = e.name().as_ref().to_vec();
                         parser.read_to_end(&end_tag)?;
                     }
                 }
             }
+            Event::Empty(e) if e.local_name().as_ref() == b"resourcedata" => {
+                if let Some(path) = get_attribute(&e, b"path") {
+                    group.resource_paths.push(path.into_owned());
+                }
+            }
             Event::End(e) if e.local_name().as_ref() == b"resourcedatagroup" => break,
             Event::Eof => {
                 return Err(Lib3mfError::Validation(
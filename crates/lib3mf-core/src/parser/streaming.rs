@@ -1,5 +1,6 @@
 use crate::error::{Lib3mfError, Result};
-use crate::model::{Beam, BuildItem, CapMode, DisplacementTriangle, ResourceId};
+use crate::model::{Beam, BuildItem, CapMode, DisplacementTriangle, ObjectType, ResourceId, Unit};
+use crate::parser::component_parser::{parse_components, parse_transform};
 use crate::parser::material_parser::{parse_base_materials, parse_color_group};
 use crate::parser::visitor::ModelVisitor;
 use crate::parser::xml_parser::{XmlParser, get_attribute, get_attribute_f32, get_attribute_u32};
@@ -19,36 +20,38 @@ pub fn parse_model_streaming<R: BufRead, V: ModelVisitor>(
 
     loop {
         match parser.read_next_event()? {
-            Event::Start(e) => {
-                match e.name().as_ref() {
-                    b"model" => {
-                        // Attributes like unit/lang handled here if needed?
-                    }
-                    b"metadata" => {
-                        let name = get_attribute(&e, b"name")
-                            .ok_or(Lib3mfError::Validation("Metadata missing name".to_string()))?
-                            .into_owned();
-                        let content = parser.read_text_content()?;
-                        visitor.on_metadata(&name, &content)?;
-                    }
-                    b"resources" => {
-                        visitor.on_start_resources()?;
-                        parse_resources_streaming(&mut parser, visitor)?;
-                        visitor.on_end_resources()?;
-                    }
-                    b"build" => {
-                        visitor.on_start_build()?;
-                        parse_build_streaming(&mut parser, visitor)?;
-                        visitor.on_end_build()?;
+            Event::Start(e) => match e.name().as_ref() {
+                b"model" => {
+                    if let Some(unit) = get_attribute(&e, b"unit") {
+                        visitor.on_unit(unit.parse::<Unit>()?)?;
                     }
-                    _ => {}
                 }
-            }
+                b"metadata" => {
+                    let name = get_attribute(&e, b"name")
+                        .ok_or(Lib3mfError::Validation("Metadata missing name".to_string()))?
+                        .into_owned();
+                    let lang = get_attribute(&e, b"xml:lang").map(|s| s.into_owned());
+                    let content = parser.read_text_content()?;
+                    visitor.on_metadata(&name, &content, lang.as_deref())?;
+                }
+                b"resources" => {
+                    visitor.on_start_resources()?;
+                    parse_resources_streaming(&mut parser, visitor)?;
+                    visitor.on_end_resources()?;
+                }
+                b"build" => {
+                    visitor.on_start_build()?;
+                    parse_build_streaming(&mut parser, visitor)?;
+                    visitor.on_end_build()?;
+                }
+                _ => {}
+            },
             Event::Empty(e) => {
                 if e.name().as_ref() == b"metadata" {
                     let name = get_attribute(&e, b"name")
                         .ok_or(Lib3mfError::Validation("Metadata missing name".to_string()))?;
-                    visitor.on_metadata(name.as_ref(), "")?;
+                    let lang = get_attribute(&e, b"xml:lang");
+                    visitor.on_metadata(name.as_ref(), "", lang.as_deref())?;
                 }
             }
             Event::End(e) if e.name().as_ref() == b"model" => break,
@@ -72,7 +75,9 @@ fn parse_resources_streaming<R: BufRead, V: ModelVisitor>(
                 match local_name.as_ref() {
                     b"object" => {
                         let id = ResourceId(get_attribute_u32(&e, b"id")?);
+                        visitor.on_start_object(id, object_type_attr(&e)?)?;
                         parse_object_content_streaming(parser, visitor, id)?;
+                        visitor.on_end_object()?;
                     }
                     b"basematerials" => {
                         let id = ResourceId(get_attribute_u32(&e, b"id")?);
@@ -84,7 +89,30 @@ fn parse_resources_streaming<R: BufRead, V: ModelVisitor>(
                         let group = parse_color_group(parser, id)?;
                         visitor.on_color_group(id, &group)?;
                     }
-                    _ => {}
+                    other => {
+                        let element = String::from_utf8_lossy(other).into_owned();
+                        let qname = e.name().as_ref().to_vec();
+                        if let Ok(id) = get_attribute_u32(&e, b"id") {
+                            visitor.on_other_resource(ResourceId(id), &element)?;
+                        }
+                        parser.read_to_end(&qname)?;
+                    }
+                }
+            }
+            Event::Empty(e) => {
+                let local_name = e.local_name();
+                let Ok(id) = get_attribute_u32(&e, b"id") else {
+                    continue;
+                };
+                match local_name.as_ref() {
+                    b"object" => {
+                        visitor.on_start_object(ResourceId(id), object_type_attr(&e)?)?;
+                        visitor.on_end_object()?;
+                    }
+                    other => {
+                        let element = String::from_utf8_lossy(other).into_owned();
+                        visitor.on_other_resource(ResourceId(id), &element)?;
+                    }
                 }
             }
             Event::End(e) if e.name().as_ref() == b"resources" => break,
@@ -99,6 +127,13 @@ fn parse_resources_streaming<R: BufRead, V: ModelVisitor>(
     Ok(())
 }
 
+fn object_type_attr(e: &quick_xml::events::BytesStart) -> Result<ObjectType> {
+    match get_attribute(e, b"type") {
+        Some(t) => t.parse(),
+        None => Ok(ObjectType::Model),
+    }
+}
+
 fn parse_object_content_streaming<R: BufRead, V: ModelVisitor>(
     parser: &mut XmlParser<R>,
     visitor: &mut V,
@@ -113,7 +148,10 @@ fn parse_object_content_streaming<R: BufRead, V: ModelVisitor>(
                     visitor.on_end_mesh()?;
                 }
                 b"components" => {
-                    // Skipping components for now
+                    // Components are small (IDs and transforms), so parse them whole.
+                    for component in parse_components(parser)?.components {
+                        visitor.on_component(&component)?;
+                    }
                 }
                 b"displacementmesh" => {
                     visitor.on_start_displacement_mesh(object_id)?;
@@ -410,13 +448,21 @@ fn parse_build_streaming<R: BufRead, V: ModelVisitor>(
             Event::Start(e) | Event::Empty(e) => {
                 if e.name().as_ref() == b"item" {
                     let object_id = ResourceId(get_attribute_u32(&e, b"objectid")?);
+                    let transform = match get_attribute(&e, b"transform") {
+                        Some(s) => parse_transform(&s)?,
+                        None => Mat4::IDENTITY,
+                    };
+                    let path = get_attribute(&e, b"path")
+                        .or_else(|| get_attribute(&e, b"p:path"))
+                        .map(|s| s.into_owned());
                     let item = BuildItem {
                         object_id,
-                        transform: Mat4::IDENTITY,
+                        transform,
                         part_number: None,
                         uuid: None,
-                        path: None,
+                        path,
                         printable: None,
+                        metadata: Default::default(),
                     };
                     visitor.on_build_item(&item)?;
                 }
@@ -0,0 +1,74 @@
+use crate::error::{Lib3mfError, Result};
+use crate::model::TriangleSet;
+use crate::parser::xml_parser::{XmlParser, get_attribute, get_attribute_u32};
+use quick_xml::events::Event;
+use std::io::BufRead;
+
+/// Parses the content of a `<trianglesets>` element into a list of `TriangleSet`s.
+///
+/// `<refrange>` elements are expanded into the individual triangle indices they cover.
+pub fn parse_triangle_sets<R: BufRead>(parser: &mut XmlParser<R>) -> Result<Vec<TriangleSet>> {
+    let mut sets = Vec::new();
+    loop {
+        match parser.read_next_event()? {
+            Event::Start(e) if e.local_name().as_ref() == b"triangleset" => {
+                let name = get_attribute(&e, b"name")
+                    .ok_or_else(|| {
+                        Lib3mfError::Validation("triangleset missing name attribute".to_string())
+                    })?
+                    .into_owned();
+                let identifier = get_attribute(&e, b"identifier")
+                    .ok_or_else(|| {
+                        Lib3mfError::Validation(
+                            "triangleset missing identifier attribute".to_string(),
+                        )
+                    })?
+                    .into_owned();
+                sets.push(TriangleSet {
+                    name,
+                    identifier,
+                    triangles: parse_triangle_refs(parser)?,
+                });
+            }
+            Event::End(e) if e.local_name().as_ref() == b"trianglesets" => break,
+            Event::Eof => {
+                return Err(Lib3mfError::Validation(
+                    "Unexpected EOF in trianglesets".to_string(),
+                ));
+            }
+            _ => {}
+        }
+    }
+    Ok(sets)
+}
+
+fn parse_triangle_refs<R: BufRead>(parser: &mut XmlParser<R>) -> Result<Vec<u32>> {
+    let mut triangles = Vec::new();
+    loop {
+        match parser.read_next_event()? {
+            Event::Start(e) => match e.local_name().as_ref() {
+                b"ref" => triangles.push(get_attribute_u32(&e, b"index")?),
+                b"refrange" => {
+                    let start = get_attribute_u32(&e, b"startindex")?;
+                    let end = get_attribute_u32(&e, b"endindex")?;
+                    if end < start {
+                        return Err(Lib3mfError::Validation(format!(
+                            "refrange endindex {} is less than startindex {}",
+                            end, start
+                        )));
+                    }
+                    triangles.extend(start..=end);
+                }
+                _ => {}
+            },
+            Event::End(e) if e.local_name().as_ref() == b"triangleset" => break,
+            Event::Eof => {
+                return Err(Lib3mfError::Validation(
+                    "Unexpected EOF in triangleset".to_string(),
+                ));
+            }
+            _ => {}
+        }
+    }
+    Ok(triangles)
+}
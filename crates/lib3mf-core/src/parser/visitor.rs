@@ -1,6 +1,7 @@
 use crate::error::Result;
 use crate::model::{
-    BaseMaterialsGroup, Beam, BuildItem, ColorGroup, DisplacementTriangle, ResourceId,
+    BaseMaterialsGroup, Beam, BuildItem, ColorGroup, Component, DisplacementTriangle, ObjectType,
+    ResourceId, Unit,
 };
 
 /// Trait for receiving callback events during streaming parsing of a 3MF model.
@@ -16,8 +17,13 @@ pub trait ModelVisitor {
         Ok(())
     }
 
-    /// Called upon encountering metadata.
-    fn on_metadata(&mut self, _name: &str, _value: &str) -> Result<()> {
+    /// Called with the model's `unit` attribute, if present.
+    fn on_unit(&mut self, _unit: Unit) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called upon encountering metadata, with its `xml:lang` tag if given.
+    fn on_metadata(&mut self, _name: &str, _value: &str, _lang: Option<&str>) -> Result<()> {
         Ok(())
     }
 
@@ -44,6 +50,30 @@ pub trait ModelVisitor {
         Ok(())
     }
 
+    /// Called for resources the streaming parser does not decode (textures,
+    /// composites, slice stacks, ...). Only the ID and element name are reported
+    /// so that references to them can still be checked.
+    fn on_other_resource(&mut self, _id: ResourceId, _element: &str) -> Result<()> {
+        Ok(())
+    }
+
+    // --- Objects ---
+
+    /// Called when an object resource starts, before any of its geometry events.
+    fn on_start_object(&mut self, _id: ResourceId, _object_type: ObjectType) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called for each component of a components object.
+    fn on_component(&mut self, _component: &Component) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when an object resource ends.
+    fn on_end_object(&mut self) -> Result<()> {
+        Ok(())
+    }
+
     // --- Mesh (Streaming) ---
 
     /// Called when a Mesh object starts.
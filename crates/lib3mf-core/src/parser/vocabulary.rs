@@ -0,0 +1,350 @@
+//! Detection of elements and attributes that the 3MF schemas don't define.
+//!
+//! The DOM parser reads only the names it understands and skips everything else, so a typo
+//! such as `transfrom=` silently loses data. When enabled on an [`XmlParser`], a
+//! [`VocabularyTracker`] sees every start tag, keeps the element path and in-scope namespace
+//! declarations, and records names missing from the tables below as [`UnknownName`]s.
+//!
+//! Only namespaces with a table are checked: the core specification and the Materials,
+//! Production, Slice, Beam Lattice, Triangle Sets and Boolean Operations extensions.
+//! Content in other namespaces, including vendor namespaces and the Volumetric, Implicit,
+//! Displacement and Secure Content extensions, is not checked, and neither is content the
+//! parser skips without reading (functions from 3D images and lazily loaded meshes).
+//!
+//! [`XmlParser`]: crate::parser::XmlParser
+
+use crate::model::{CORE_NAMESPACE, Extension, UnknownName, UnknownNameKind};
+use quick_xml::events::BytesStart;
+use std::collections::HashMap;
+
+/// Element name and unprefixed attributes of each element in a namespace.
+type ElementTable = &'static [(&'static str, &'static [&'static str])];
+
+const CORE_ELEMENTS: ElementTable = &[
+    (
+        "model",
+        &["unit", "requiredextensions", "recommendedextensions"],
+    ),
+    ("metadata", &["name", "preserve", "type"]),
+    ("metadatagroup", &[]),
+    ("resources", &[]),
+    (
+        "object",
+        &[
+            "id",
+            "type",
+            "thumbnail",
+            "partnumber",
+            "name",
+            "pid",
+            "pindex",
+        ],
+    ),
+    ("mesh", &[]),
+    ("vertices", &[]),
+    ("vertex", &["x", "y", "z"]),
+    ("triangles", &[]),
+    ("triangle", &["v1", "v2", "v3", "p1", "p2", "p3", "pid"]),
+    ("components", &[]),
+    ("component", &["objectid", "transform"]),
+    ("build", &[]),
+    // `printable` is written by slicers and read by this library
+    (
+        "item",
+        &["objectid", "transform", "partnumber", "printable"],
+    ),
+    ("basematerials", &["id", "displaypropertiesid"]),
+    ("base", &["name", "displaycolor"]),
+];
+
+const MATERIALS_ELEMENTS: ElementTable = &[
+    ("basematerials", &["id", "displaypropertiesid"]),
+    ("base", &["name", "displaycolor"]),
+    ("colorgroup", &["id", "displaypropertiesid"]),
+    ("color", &["color"]),
+    (
+        "texture2d",
+        &[
+            "id",
+            "path",
+            "contenttype",
+            "tilestyleu",
+            "tilestylev",
+            "filter",
+        ],
+    ),
+    ("texture2dgroup", &["id", "texid", "displaypropertiesid"]),
+    ("tex2coord", &["u", "v"]),
+    (
+        "compositematerials",
+        &["id", "matid", "matindices", "displaypropertiesid"],
+    ),
+    ("composite", &["values"]),
+    ("multiproperties", &["id", "pids", "blendmethods"]),
+    ("multi", &["pindices"]),
+    ("pbspeculardisplayproperties", &["id"]),
+    ("pbspecular", &["name", "specularcolor", "glossiness"]),
+    ("pbmetallicdisplayproperties", &["id"]),
+    ("pbmetallic", &["name", "metallicness", "roughness"]),
+    (
+        "pbspeculartexturedisplayproperties",
+        &[
+            "id",
+            "name",
+            "speculartextureid",
+            "glossinesstextureid",
+            "diffusefactor",
+            "specularfactor",
+            "glossinessfactor",
+        ],
+    ),
+    (
+        "pbmetallictexturedisplayproperties",
+        &[
+            "id",
+            "name",
+            "metallictextureid",
+            "roughnesstextureid",
+            "metallicfactor",
+            "roughnessfactor",
+        ],
+    ),
+    ("translucentdisplayproperties", &["id"]),
+    (
+        "translucent",
+        &["name", "attenuation", "refractiveindex", "roughness"],
+    ),
+];
+
+const SLICE_ELEMENTS: ElementTable = &[
+    ("slicestack", &["id", "zbottom"]),
+    ("slice", &["ztop"]),
+    ("vertices", &[]),
+    ("vertex", &["x", "y"]),
+    ("polygon", &["startv", "start"]),
+    ("segment", &["v2", "p1", "p2", "pid"]),
+    ("sliceref", &["slicestackid", "slicepath"]),
+];
+
+const BEAM_LATTICE_ELEMENTS: ElementTable = &[
+    (
+        "beamlattice",
+        &[
+            "minlength",
+            "radius",
+            "ballmode",
+            "ballradius",
+            "clippingmode",
+            "clippingmesh",
+            "representationmesh",
+            "cap",
+            "pid",
+            "pindex",
+            "clipping",
+            "precision",
+        ],
+    ),
+    ("beams", &[]),
+    (
+        "beam",
+        &["v1", "v2", "r1", "r2", "p1", "p2", "pid", "cap1", "cap2"],
+    ),
+    ("beamsets", &[]),
+    ("beamset", &["name", "identifier"]),
+    ("ref", &["index"]),
+    ("balls", &[]),
+    ("ball", &["vindex", "r", "p", "pid"]),
+    ("ballref", &["index"]),
+];
+
+const TRIANGLE_SETS_ELEMENTS: ElementTable = &[
+    ("trianglesets", &[]),
+    ("triangleset", &["name", "identifier"]),
+    ("ref", &["index"]),
+    ("refrange", &["startindex", "endindex"]),
+];
+
+const BOOLEAN_ELEMENTS: ElementTable = &[
+    ("booleanshape", &["id", "objectid", "transform", "path"]),
+    ("boolean", &["objectid", "transform", "path"]),
+];
+
+/// The elements of a namespace, and the attributes it adds to elements of other
+/// namespaces (written with the namespace's prefix, like `p:UUID`).
+struct Schema {
+    elements: ElementTable,
+    attributes: &'static [&'static str],
+}
+
+/// Returns the schema for a namespace URI, or `None` if the namespace isn't checked.
+fn schema(namespace: &str) -> Option<Schema> {
+    if namespace == CORE_NAMESPACE {
+        return Some(Schema {
+            elements: CORE_ELEMENTS,
+            attributes: &[],
+        });
+    }
+    let (elements, attributes): (ElementTable, &'static [&'static str]) =
+        match Extension::from_namespace(namespace)? {
+            Extension::Materials => (MATERIALS_ELEMENTS, &[]),
+            Extension::Production => (&[], &["UUID", "path"]),
+            Extension::Slice => (SLICE_ELEMENTS, &["slicestackid", "meshresolution"]),
+            Extension::BeamLattice => (BEAM_LATTICE_ELEMENTS, &["ballmode", "ballradius"]),
+            Extension::TriangleSets => (TRIANGLE_SETS_ELEMENTS, &[]),
+            Extension::BooleanOperations => (BOOLEAN_ELEMENTS, &[]),
+            Extension::Volumetric
+            | Extension::Implicit
+            | Extension::SecureContent
+            | Extension::Displacement => return None,
+        };
+    Some(Schema {
+        elements,
+        attributes,
+    })
+}
+
+/// An open element.
+struct Frame {
+    /// Path of the element, e.g. `/model/build/item[2]`.
+    path: String,
+    /// Namespace declarations on the element, as `(prefix, uri)`; `""` is the default.
+    namespaces: Vec<(String, String)>,
+    /// Number of children seen so far, by name.
+    children: HashMap<String, usize>,
+    /// Whether the element's children are checked. Off below unknown elements and in
+    /// namespaces without a schema.
+    checked: bool,
+}
+
+/// Records unknown names as the parser reads start and end tags.
+#[derive(Default)]
+pub(crate) struct VocabularyTracker {
+    frames: Vec<Frame>,
+    found: Vec<UnknownName>,
+}
+
+impl VocabularyTracker {
+    /// Checks a start tag and opens its element.
+    pub(crate) fn start(&mut self, e: &BytesStart) {
+        let namespaces: Vec<(String, String)> = e
+            .attributes()
+            .flatten()
+            .filter_map(|a| {
+                let key = std::str::from_utf8(a.key.as_ref()).ok()?;
+                let prefix = match key {
+                    "xmlns" => "",
+                    _ => key.strip_prefix("xmlns:")?,
+                };
+                Some((
+                    prefix.to_string(),
+                    String::from_utf8_lossy(&a.value).into_owned(),
+                ))
+            })
+            .collect();
+
+        let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+        let (path, parent_checked) = match self.frames.last_mut() {
+            Some(parent) => {
+                let count = parent.children.entry(name.clone()).or_default();
+                *count += 1;
+                let path = match *count {
+                    1 => format!("{}/{}", parent.path, name),
+                    n => format!("{}/{}[{}]", parent.path, name, n),
+                };
+                (path, parent.checked)
+            }
+            None => (format!("/{}", name), true),
+        };
+        let mut frame = Frame {
+            path,
+            namespaces,
+            children: HashMap::new(),
+            checked: false,
+        };
+        if parent_checked {
+            frame.checked = self.check(&frame, e, &name);
+        }
+        self.frames.push(frame);
+    }
+
+    /// Closes the innermost open element.
+    pub(crate) fn end(&mut self) {
+        self.frames.pop();
+    }
+
+    /// Returns the unknown names recorded so far.
+    pub(crate) fn take(&mut self) -> Vec<UnknownName> {
+        std::mem::take(&mut self.found)
+    }
+
+    /// Checks the element and its attributes, returning whether its children are checked.
+    fn check(&mut self, frame: &Frame, e: &BytesStart, name: &str) -> bool {
+        let (prefix, local) = split_name(name);
+        // Unqualified elements are read as core elements, as the parser does
+        let namespace = self
+            .resolve(frame, prefix)
+            .unwrap_or(CORE_NAMESPACE.to_string());
+        let Some(schema) = schema(&namespace) else {
+            return false;
+        };
+        let Some(&(_, allowed)) = schema.elements.iter().find(|(n, _)| *n == local) else {
+            self.record(&frame.path, name, UnknownNameKind::Element);
+            return false;
+        };
+
+        for attr in e.attributes().flatten() {
+            let Ok(key) = std::str::from_utf8(attr.key.as_ref()) else {
+                continue;
+            };
+            if key == "xmlns" || key.starts_with("xmlns:") {
+                continue;
+            }
+            let known = match split_name(key) {
+                (None, local) => allowed.contains(&local),
+                (Some("xml"), _) => true,
+                (Some(prefix), local) => match self.resolve(frame, Some(prefix)) {
+                    Some(ns) if ns == namespace => allowed.contains(&local),
+                    Some(ns) => schema_attributes(&ns).is_none_or(|a| a.contains(&local)),
+                    // An undeclared prefix is a namespace error, not a vocabulary one
+                    None => true,
+                },
+            };
+            if !known {
+                self.record(&frame.path, key, UnknownNameKind::Attribute);
+            }
+        }
+        true
+    }
+
+    /// Looks up the namespace bound to `prefix` (`None` for the default namespace) at
+    /// `frame`, whose declarations are not on the stack yet.
+    fn resolve(&self, frame: &Frame, prefix: Option<&str>) -> Option<String> {
+        let prefix = prefix.unwrap_or("");
+        std::iter::once(frame)
+            .chain(self.frames.iter().rev())
+            .flat_map(|f| f.namespaces.iter())
+            .find(|(p, _)| p == prefix)
+            .map(|(_, uri)| uri.clone())
+    }
+
+    fn record(&mut self, path: &str, name: &str, kind: UnknownNameKind) {
+        self.found.push(UnknownName {
+            path: path.to_string(),
+            name: name.to_string(),
+            kind,
+        });
+    }
+}
+
+/// Attributes a namespace adds to other elements, or `None` if it isn't checked.
+fn schema_attributes(namespace: &str) -> Option<&'static [&'static str]> {
+    schema(namespace).map(|s| s.attributes)
+}
+
+/// Splits `prefix:local` into its parts.
+fn split_name(name: &str) -> (Option<&str>, &str) {
+    match name.split_once(':') {
+        Some((prefix, local)) => (Some(prefix), local),
+        None => (None, name),
+    }
+}
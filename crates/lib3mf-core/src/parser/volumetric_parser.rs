@@ -1,7 +1,11 @@
 use crate::error::{Lib3mfError, Result};
-use crate::model::{ResourceId, VolumetricLayer, VolumetricRef, VolumetricStack};
+use crate::model::{
+    FilterMode, FunctionFromImage3D, Image3D, LevelSet, ResourceId, TileStyle, VolumeComposite,
+    VolumeData, VolumeFunctionRef, VolumeProperty, VolumetricLayer, VolumetricRef, VolumetricStack,
+};
+use crate::parser::component_parser::parse_transform;
 use crate::parser::xml_parser::{XmlParser, get_attribute, get_attribute_f32, get_attribute_u32};
-use quick_xml::events::Event;
+use quick_xml::events::{BytesStart, Event};
 use std::io::BufRead;
 
 /// Parses the content of a `<volumestack>` element into a `VolumetricStack`.
@@ -59,3 +63,181 @@ pub fn parse_volumetric_stack_content<R: BufRead>(
 
     Ok(stack)
 }
+
+/// Parses an `<image3d>` element whose start tag is `start` into an `Image3D`.
+pub fn parse_image_3d<R: BufRead>(
+    parser: &mut XmlParser<R>,
+    start: &BytesStart,
+) -> Result<Image3D> {
+    let mut image = Image3D {
+        id: ResourceId(get_attribute_u32(start, b"id")?),
+        name: get_attribute(start, b"name").map(|s| s.into_owned()),
+        ..Default::default()
+    };
+
+    loop {
+        match parser.read_next_event()? {
+            Event::Start(e) => {
+                match e.local_name().as_ref() {
+                    b"imagestack" => {
+                        image.stack.row_count = get_attribute_u32(&e, b"rowcount")?;
+                        image.stack.column_count = get_attribute_u32(&e, b"columncount")?;
+                        image.stack.sheet_count = get_attribute_u32(&e, b"sheetcount")?;
+                        // Sheets are children of the stack; keep reading
+                        continue;
+                    }
+                    b"imagesheet" => {
+                        let path = get_attribute(&e, b"path").ok_or_else(|| {
+                            Lib3mfError::Validation("imagesheet missing path attribute".to_string())
+                        })?;
+                        image.stack.sheets.push(path.into_owned());
+                    }
+                    _ => {}
+                }
+                let end_tag = e.name().as_ref().to_vec();
+                parser.read_to_end(&end_tag)?;
+            }
+            Event::End(e) if e.local_name().as_ref() == b"image3d" => break,
+            Event::Eof => {
+                return Err(Lib3mfError::Validation(
+                    "Unexpected EOF in image3d".to_string(),
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(image)
+}
+
+/// Parses a `<functionfromimage3d>` start tag into a `FunctionFromImage3D`.
+pub fn parse_function_from_image_3d(e: &BytesStart) -> Result<FunctionFromImage3D> {
+    let id = ResourceId(get_attribute_u32(e, b"id")?);
+    let image3d_id = ResourceId(get_attribute_u32(e, b"image3did")?);
+    let tile_style = |name: &[u8]| get_attribute(e, name).map_or(TileStyle::Wrap, parse_tile_style);
+    Ok(FunctionFromImage3D {
+        display_name: get_attribute(e, b"displayname").map(|s| s.into_owned()),
+        value_offset: get_attribute_f32(e, b"valueoffset").unwrap_or(0.0),
+        value_scale: get_attribute_f32(e, b"valuescale").unwrap_or(1.0),
+        filter: match get_attribute(e, b"filter").as_deref() {
+            Some("nearest") => FilterMode::Nearest,
+            _ => FilterMode::Linear,
+        },
+        tile_style_u: tile_style(b"tilestyleu"),
+        tile_style_v: tile_style(b"tilestylev"),
+        tile_style_w: tile_style(b"tilestylew"),
+        ..FunctionFromImage3D::new(id, image3d_id)
+    })
+}
+
+/// Parses a `<volumedata>` element whose start tag has been read into a `VolumeData`.
+pub fn parse_volume_data<R: BufRead>(
+    parser: &mut XmlParser<R>,
+    id: ResourceId,
+) -> Result<VolumeData> {
+    let mut data = VolumeData {
+        id,
+        ..Default::default()
+    };
+
+    loop {
+        match parser.read_next_event()? {
+            Event::Start(e) => {
+                match e.local_name().as_ref() {
+                    b"color" => data.color = Some(parse_function_ref(&e)?),
+                    b"composite" => {
+                        data.composite = Some(VolumeComposite {
+                            base_material_id: ResourceId(get_attribute_u32(&e, b"basematerialid")?),
+                            mappings: Vec::new(),
+                        });
+                        // Material mappings are children of the composite; keep reading
+                        continue;
+                    }
+                    b"materialmapping" => {
+                        let mapping = parse_function_ref(&e)?;
+                        data.composite
+                            .as_mut()
+                            .ok_or_else(|| {
+                                Lib3mfError::Validation(
+                                    "materialmapping outside of composite".to_string(),
+                                )
+                            })?
+                            .mappings
+                            .push(mapping);
+                    }
+                    b"property" => {
+                        let name = get_attribute(&e, b"name").ok_or_else(|| {
+                            Lib3mfError::Validation("property missing name attribute".to_string())
+                        })?;
+                        data.properties.push(VolumeProperty {
+                            name: name.into_owned(),
+                            required: get_attribute(&e, b"required")
+                                .is_some_and(|s| parse_bool(&s)),
+                            function: parse_function_ref(&e)?,
+                        });
+                    }
+                    _ => {}
+                }
+                let end_tag = e.name().as_ref().to_vec();
+                parser.read_to_end(&end_tag)?;
+            }
+            Event::End(e) if e.local_name().as_ref() == b"volumedata" => break,
+            Event::Eof => {
+                return Err(Lib3mfError::Validation(
+                    "Unexpected EOF in volumedata".to_string(),
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(data)
+}
+
+/// Parses a `<levelset>` start tag into a `LevelSet`.
+pub fn parse_level_set(e: &BytesStart) -> Result<LevelSet> {
+    let function = parse_function_ref(e)?;
+    Ok(LevelSet {
+        function_id: function.function_id,
+        channel: function.channel,
+        transform: function.transform,
+        min_feature_size: function.min_feature_size,
+        mesh_bbox_only: get_attribute(e, b"meshbboxonly").is_some_and(|s| parse_bool(&s)),
+        fallback_value: function.fallback_value,
+        mesh_id: ResourceId(get_attribute_u32(e, b"meshid")?),
+        volume_id: get_attribute_u32(e, b"volumeid").map(ResourceId).ok(),
+    })
+}
+
+/// Parses the attributes shared by elements that evaluate a function channel.
+fn parse_function_ref(e: &BytesStart) -> Result<VolumeFunctionRef> {
+    let channel = get_attribute(e, b"channel").ok_or_else(|| {
+        Lib3mfError::Validation(format!(
+            "{} missing channel attribute",
+            String::from_utf8_lossy(e.local_name().as_ref())
+        ))
+    })?;
+    let mut function_ref = VolumeFunctionRef::new(
+        ResourceId(get_attribute_u32(e, b"functionid")?),
+        channel.into_owned(),
+    );
+    if let Some(transform) = get_attribute(e, b"transform") {
+        function_ref.transform = parse_transform(&transform)?;
+    }
+    function_ref.min_feature_size = get_attribute_f32(e, b"minfeaturesize").unwrap_or(0.0);
+    function_ref.fallback_value = get_attribute_f32(e, b"fallbackvalue").unwrap_or(0.0);
+    Ok(function_ref)
+}
+
+fn parse_tile_style(s: std::borrow::Cow<'_, str>) -> TileStyle {
+    match s.as_ref() {
+        "mirror" => TileStyle::Mirror,
+        "clamp" => TileStyle::Clamp,
+        "none" => TileStyle::None,
+        _ => TileStyle::Wrap,
+    }
+}
+
+fn parse_bool(s: &str) -> bool {
+    matches!(s, "true" | "1")
+}
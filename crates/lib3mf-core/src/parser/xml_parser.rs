@@ -1,4 +1,7 @@
 use crate::error::{Lib3mfError, Result};
+use crate::model::UnknownName;
+use crate::parser::encoding::{DecodingReader, XmlEncoding};
+use crate::parser::vocabulary::VocabularyTracker;
 use lexical_core;
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::reader::Reader;
@@ -6,31 +9,99 @@ use std::borrow::Cow;
 use std::io::BufRead;
 
 /// A low-level XML parser wrapper providing event-based reading with a reusable buffer.
+///
+/// UTF-16 input is transcoded to UTF-8 first; see [`crate::parser::encoding`].
 pub struct XmlParser<R: BufRead> {
     /// The underlying quick-xml reader.
-    pub reader: Reader<R>,
+    pub reader: Reader<DecodingReader<R>>,
     /// Reusable internal buffer for XML event parsing.
     pub buf: Vec<u8>,
+    /// Mesh memory budget as `(bytes used, bytes allowed)`; see [`Self::set_mesh_budget`].
+    mesh_budget: Option<(u64, u64)>,
+    /// Unknown-name tracking; see [`Self::track_unknown_names`].
+    vocabulary: Option<VocabularyTracker>,
 }
 
 impl<R: BufRead> XmlParser<R> {
-    /// Creates a new `XmlParser` wrapping the given buffered reader.
+    /// Creates a new `XmlParser` wrapping the given buffered reader, detecting its encoding.
     pub fn new(reader: R) -> Self {
+        Self::from_decoder(DecodingReader::new(reader))
+    }
+
+    /// Creates a new `XmlParser` that reads its input as `encoding`, ignoring the encoding
+    /// named by the XML declaration.
+    pub fn with_encoding(reader: R, encoding: XmlEncoding) -> Self {
+        Self::from_decoder(DecodingReader::with_encoding(reader, encoding))
+    }
+
+    fn from_decoder(reader: DecodingReader<R>) -> Self {
         let mut reader = Reader::from_reader(reader);
         reader.config_mut().trim_text(true);
         reader.config_mut().expand_empty_elements = true;
         Self {
             reader,
             buf: Vec::new(),
+            mesh_budget: None,
+            vocabulary: None,
+        }
+    }
+
+    /// Caps the memory that mesh data parsed through this parser may take, in bytes.
+    ///
+    /// Every vertex, triangle and beam read is charged at its in-memory size. Once the
+    /// total would pass `max_bytes`, parsing fails with [`Lib3mfError::Io`] of kind
+    /// [`std::io::ErrorKind::FileTooLarge`], before the data is allocated.
+    pub fn set_mesh_budget(&mut self, max_bytes: u64) {
+        self.mesh_budget = Some((0, max_bytes));
+    }
+
+    /// Records elements and attributes the 3MF schemas don't define from here on; collect
+    /// them with [`Self::take_unknown_names`].
+    ///
+    /// Content skipped with [`Self::read_to_end`] is not checked.
+    pub fn track_unknown_names(&mut self) {
+        self.vocabulary = Some(VocabularyTracker::default());
+    }
+
+    /// Returns the unknown names recorded since tracking started, or since the last call.
+    pub fn take_unknown_names(&mut self) -> Vec<UnknownName> {
+        self.vocabulary
+            .as_mut()
+            .map(VocabularyTracker::take)
+            .unwrap_or_default()
+    }
+
+    /// Charges `bytes` of mesh data against the budget, if one is set.
+    pub(crate) fn charge_mesh_bytes(&mut self, bytes: usize) -> Result<()> {
+        if let Some((used, max)) = &mut self.mesh_budget {
+            *used += bytes as u64;
+            if *used > *max {
+                return Err(Lib3mfError::Io(std::io::Error::new(
+                    std::io::ErrorKind::FileTooLarge,
+                    format!(
+                        "Mesh data exceeds the memory budget of {max} bytes; the model is too large to load"
+                    ),
+                )));
+            }
         }
+        Ok(())
     }
 
     /// Reads the next XML event, clearing the internal buffer first.
     pub fn read_next_event(&mut self) -> Result<Event<'_>> {
         self.buf.clear();
-        self.reader
+        let event = self
+            .reader
             .read_event_into(&mut self.buf)
-            .map_err(|e| Lib3mfError::Validation(e.to_string()))
+            .map_err(|e| Lib3mfError::Validation(e.to_string()))?;
+        if let Some(vocabulary) = &mut self.vocabulary {
+            match &event {
+                Event::Start(e) => vocabulary.start(e),
+                Event::End(_) => vocabulary.end(),
+                _ => {}
+            }
+        }
+        Ok(event)
     }
 
     /// Reads and concatenates text content up to the closing tag, returning the accumulated string.
@@ -40,7 +111,12 @@ impl<R: BufRead> XmlParser<R> {
 
         loop {
             match self.read_next_event()? {
-                Event::Text(e) => text.push_str(&String::from_utf8_lossy(e.as_ref())),
+                // Text the writer escaped must read back unescaped; malformed references
+                // are kept as written
+                Event::Text(e) => text.push_str(
+                    &e.unescape()
+                        .unwrap_or_else(|_| String::from_utf8_lossy(e.as_ref())),
+                ),
                 Event::CData(e) => text.push_str(&String::from_utf8_lossy(e.into_inner().as_ref())),
                 Event::Start(_) => depth += 1,
                 Event::End(_) => {
@@ -66,6 +142,9 @@ impl<R: BufRead> XmlParser<R> {
         self.reader
             .read_to_end_into(quick_xml::name::QName(end), &mut self.buf)
             .map_err(|e| Lib3mfError::Validation(e.to_string()))?;
+        if let Some(vocabulary) = &mut self.vocabulary {
+            vocabulary.end();
+        }
         Ok(())
     }
 }
@@ -97,6 +176,23 @@ pub fn get_attribute_f32(e: &BytesStart, name: &[u8]) -> Result<f32> {
     })
 }
 
+/// Returns the named attribute parsed as an `f64`, or an error if absent or invalid.
+pub fn get_attribute_f64(e: &BytesStart, name: &[u8]) -> Result<f64> {
+    let attr = e.try_get_attribute(name).ok().flatten().ok_or_else(|| {
+        Lib3mfError::Validation(format!(
+            "Missing attribute: {}",
+            String::from_utf8_lossy(name)
+        ))
+    })?;
+    lexical_core::parse::<f64>(attr.value.as_ref()).map_err(|_| {
+        Lib3mfError::Validation(format!(
+            "Invalid float for attribute {}: {}",
+            String::from_utf8_lossy(name),
+            String::from_utf8_lossy(&attr.value)
+        ))
+    })
+}
+
 /// Returns the named attribute parsed as a `u32`, or an error if absent or invalid.
 pub fn get_attribute_u32(e: &BytesStart, name: &[u8]) -> Result<u32> {
     let attr = e.try_get_attribute(name).ok().flatten().ok_or_else(|| {
@@ -129,10 +225,13 @@ pub fn get_attribute_u32_opt(e: &BytesStart, name: &[u8]) -> Result<Option<u32>>
     }
 }
 
-/// Returns the `uuid` or `p:uuid` attribute parsed as a `Uuid`, or `None` if absent.
+/// Returns the `p:UUID` attribute parsed as a `Uuid`, or `None` if absent.
+///
+/// The unprefixed and lowercase spellings written by some producers are accepted too.
 pub fn get_attribute_uuid(e: &BytesStart) -> Result<Option<uuid::Uuid>> {
-    // Try "uuid" then "p:uuid"
-    let val = get_attribute(e, b"uuid").or_else(|| get_attribute(e, b"p:uuid"));
+    let val = [&b"p:UUID"[..], b"UUID", b"uuid", b"p:uuid"]
+        .into_iter()
+        .find_map(|name| get_attribute(e, name));
 
     match val {
         Some(s) => uuid::Uuid::parse_str(&s)
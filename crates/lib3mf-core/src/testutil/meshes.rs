@@ -0,0 +1,181 @@
+use crate::model::Mesh;
+use std::collections::HashMap;
+
+/// Closed tetrahedron with three edges of length `size` along the positive axes.
+pub fn tetrahedron(size: f32) -> Mesh {
+    let mut mesh = Mesh::new();
+    let o = mesh.add_vertex(0.0, 0.0, 0.0);
+    let x = mesh.add_vertex(size, 0.0, 0.0);
+    let y = mesh.add_vertex(0.0, size, 0.0);
+    let z = mesh.add_vertex(0.0, 0.0, size);
+    mesh.add_triangle(o, y, x);
+    mesh.add_triangle(o, x, z);
+    mesh.add_triangle(o, z, y);
+    mesh.add_triangle(x, y, z);
+    mesh
+}
+
+/// Closed box from the origin to `size`, each face split into `divisions`² quads.
+///
+/// Vertices on shared edges and corners are shared, so the mesh is manifold for any
+/// `divisions` (at least 1).
+pub fn cuboid(size: [f32; 3], divisions: u32) -> Mesh {
+    let n = divisions.max(1);
+    let mut mesh = Mesh::new();
+    let mut index: HashMap<[u32; 3], u32> = HashMap::new();
+    let mut vertex = |mesh: &mut Mesh, p: [u32; 3]| {
+        *index.entry(p).or_insert_with(|| {
+            mesh.add_vertex(
+                size[0] * p[0] as f32 / n as f32,
+                size[1] * p[1] as f32 / n as f32,
+                size[2] * p[2] as f32 / n as f32,
+            )
+        })
+    };
+
+    // For the face normal to axis `a`, axes `b` and `c` follow cyclically so that
+    // e_b × e_c = e_a; quads listed in (b, c) order then face +a.
+    for a in 0..3 {
+        let (b, c) = ((a + 1) % 3, (a + 2) % 3);
+        for side in [0, n] {
+            for i in 0..n {
+                for j in 0..n {
+                    let corner = |di: u32, dj: u32| {
+                        let mut p = [0; 3];
+                        p[a] = side;
+                        p[b] = i + di;
+                        p[c] = j + dj;
+                        p
+                    };
+                    let q = [
+                        vertex(&mut mesh, corner(0, 0)),
+                        vertex(&mut mesh, corner(1, 0)),
+                        vertex(&mut mesh, corner(1, 1)),
+                        vertex(&mut mesh, corner(0, 1)),
+                    ];
+                    if side == n {
+                        mesh.add_triangle(q[0], q[1], q[2]);
+                        mesh.add_triangle(q[0], q[2], q[3]);
+                    } else {
+                        mesh.add_triangle(q[0], q[2], q[1]);
+                        mesh.add_triangle(q[0], q[3], q[2]);
+                    }
+                }
+            }
+        }
+    }
+    mesh
+}
+
+/// Closed UV sphere centred on the origin with `bands` latitude bands (at least 2) and
+/// `2 * bands` segments, for `4 * bands * (bands - 1)` triangles.
+pub fn uv_sphere(radius: f32, bands: u32) -> Mesh {
+    let bands = bands.max(2);
+    let segments = bands * 2;
+    let mut mesh = Mesh::new();
+
+    let north = mesh.add_vertex(0.0, 0.0, radius);
+    for band in 1..bands {
+        let theta = std::f32::consts::PI * band as f32 / bands as f32;
+        for seg in 0..segments {
+            let phi = std::f32::consts::TAU * seg as f32 / segments as f32;
+            mesh.add_vertex(
+                radius * theta.sin() * phi.cos(),
+                radius * theta.sin() * phi.sin(),
+                radius * theta.cos(),
+            );
+        }
+    }
+    let south = mesh.add_vertex(0.0, 0.0, -radius);
+
+    let ring = |band: u32, seg: u32| 1 + (band - 1) * segments + seg % segments;
+    for seg in 0..segments {
+        mesh.add_triangle(north, ring(1, seg), ring(1, seg + 1));
+    }
+    for band in 1..bands - 1 {
+        for seg in 0..segments {
+            let (a, b) = (ring(band, seg), ring(band, seg + 1));
+            let (c, d) = (ring(band + 1, seg), ring(band + 1, seg + 1));
+            mesh.add_triangle(a, c, d);
+            mesh.add_triangle(a, d, b);
+        }
+    }
+    for seg in 0..segments {
+        mesh.add_triangle(south, ring(bands - 1, seg + 1), ring(bands - 1, seg));
+    }
+    mesh
+}
+
+/// A defect that can be injected into a closed mesh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MeshDefect {
+    /// One triangle removed, leaving boundary edges.
+    Hole,
+    /// A small closed tetrahedron pushed through one face.
+    SelfIntersection,
+    /// An extra triangle repeating one vertex.
+    Degenerate,
+}
+
+impl MeshDefect {
+    /// All defects, for iterating in tests.
+    pub const ALL: [MeshDefect; 3] = [Self::Hole, Self::SelfIntersection, Self::Degenerate];
+
+    /// Validation code Paranoid validation reports for this defect.
+    pub fn validation_code(&self) -> u32 {
+        match self {
+            Self::Hole => 4002,
+            Self::SelfIntersection => 4008,
+            Self::Degenerate => 4001,
+        }
+    }
+
+    /// Injects the defect at triangle `triangle` (modulo the triangle count).
+    ///
+    /// Does nothing to a mesh without triangles.
+    pub fn apply(&self, mesh: &mut Mesh, triangle: usize) {
+        if mesh.triangles.is_empty() {
+            return;
+        }
+        let index = triangle % mesh.triangles.len();
+        match self {
+            Self::Hole => {
+                mesh.triangles.remove(index);
+            }
+            Self::SelfIntersection => pierce(mesh, index),
+            Self::Degenerate => {
+                let t = mesh.triangles[index];
+                mesh.add_triangle(t.v1, t.v2, t.v1);
+            }
+        }
+    }
+}
+
+/// Adds a tetrahedron straddling triangle `index`: its apex sits above the triangle and
+/// its base below, both within the triangle's outline, so its side faces cross it.
+fn pierce(mesh: &mut Mesh, index: usize) {
+    let t = mesh.triangles[index];
+    let p = |i: u32| {
+        let v = mesh.vertices[i as usize];
+        glam::Vec3::new(v.x, v.y, v.z)
+    };
+    let (a, b, c) = (p(t.v1), p(t.v2), p(t.v3));
+    let centroid = (a + b + c) / 3.0;
+    let normal = (b - a).cross(c - a).normalize_or_zero();
+    let reach = (a - centroid)
+        .length()
+        .min((b - centroid).length())
+        .min((c - centroid).length());
+    let depth = reach * 0.5;
+
+    // Base triangle shrunk towards the centroid, offset below the face
+    let shrink = |v: glam::Vec3| centroid + (v - centroid) * 0.25 - normal * depth;
+    let mut add = |v: glam::Vec3| mesh.add_vertex(v.x, v.y, v.z);
+    let base = [add(shrink(a)), add(shrink(b)), add(shrink(c))];
+    let apex = add(centroid + normal * depth);
+
+    mesh.add_triangle(base[0], base[2], base[1]);
+    mesh.add_triangle(base[0], base[1], apex);
+    mesh.add_triangle(base[1], base[2], apex);
+    mesh.add_triangle(base[2], base[0], apex);
+}
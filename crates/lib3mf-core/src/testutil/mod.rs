@@ -0,0 +1,58 @@
+//! Mesh and model generators for tests (feature `testutil`).
+//!
+//! Crates building on lib3mf-core need realistic input for their own tests: closed solids
+//! that pass Paranoid validation, and meshes with one known defect to check that repair and
+//! validation code notices it. This module provides both as plain constructors
+//! ([`meshes`]) and as [proptest](https://docs.rs/proptest) strategies ([`strategies`]),
+//! for use in `proptest!` tests or with a test runner directly:
+//!
+//! ```
+//! use lib3mf_core::testutil::proptest::prelude::*;
+//! use lib3mf_core::testutil::proptest::test_runner::TestRunner;
+//! use lib3mf_core::testutil::{MeshDefect, manifold_mesh, mesh_with_defect};
+//!
+//! let mut runner = TestRunner::default();
+//! runner
+//!     .run(&manifold_mesh(), |mesh| {
+//!         let (_, volume) = mesh.compute_area_and_volume();
+//!         prop_assert!(volume > 0.0);
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! runner
+//!     .run(&mesh_with_defect(MeshDefect::Hole), |mesh| {
+//!         prop_assert!(!mesh.triangles.is_empty());
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+//!
+//! Every generated solid is edge- and vertex-manifold, consistently oriented with outward
+//! normals, and free of degenerate or self-intersecting triangles. Defective meshes contain
+//! at least the requested [`MeshDefect`], reported by Paranoid validation under the code
+//! given by [`MeshDefect::validation_code`].
+//!
+//! For writer output, [`snapshot`] canonicalizes written packages and compares them to
+//! golden files, which `LIB3MF_BLESS=1` regenerates.
+//!
+//! Enable with:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! lib3mf-core = { version = "0.4", features = ["testutil"] }
+//! ```
+
+/// Deterministic solid meshes and defect injection.
+pub mod meshes;
+/// Golden-file snapshots of canonicalized writer output.
+pub mod snapshot;
+/// Proptest strategies for meshes and models.
+pub mod strategies;
+
+pub use meshes::{MeshDefect, cuboid, tetrahedron, uv_sphere};
+pub use strategies::{
+    ModelOptions, defective_mesh, manifold_mesh, mesh_with_defect, model, model_with,
+};
+
+/// The proptest version the strategies are built with.
+pub use proptest;
@@ -0,0 +1,208 @@
+use crate::archive::{ArchiveReader, ZipArchiver};
+use crate::error::{Lib3mfError, Result};
+use crate::model::Model;
+use quick_xml::events::{BytesStart, Event};
+use std::io::Cursor;
+use std::path::Path;
+
+/// Environment variable that makes [`assert_snapshot`] rewrite stored snapshots instead of
+/// comparing against them.
+pub const BLESS_ENV: &str = "LIB3MF_BLESS";
+
+/// Canonical, line-oriented form of an XML document for snapshot comparison.
+///
+/// One element per line, indented by depth, with namespace declarations first and all
+/// other attributes sorted by name. The XML declaration, comments and processing
+/// instructions are dropped and whitespace-only text is ignored, so only changes to
+/// elements, attributes and text content show up in a diff.
+pub fn canonicalize_xml(xml: &str) -> Result<String> {
+    let mut reader = quick_xml::Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    // Elements still open, with whether each has had any content yet
+    let mut stack: Vec<(String, bool)> = Vec::new();
+    let mut out = String::new();
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| Lib3mfError::InvalidStructure(e.to_string()))?;
+        match event {
+            Event::Start(e) => {
+                open_parent(&mut stack, &mut out);
+                out.push_str(&indent(stack.len()));
+                out.push_str(&start_tag(&e)?);
+                stack.push((name(&e), false));
+            }
+            Event::Empty(e) => {
+                open_parent(&mut stack, &mut out);
+                out.push_str(&indent(stack.len()));
+                let tag = start_tag(&e)?;
+                out.push_str(&tag[..tag.len() - 1]);
+                out.push_str("/>\n");
+            }
+            Event::Text(e) => {
+                mark_content(&mut stack);
+                out.push_str(&String::from_utf8_lossy(&e.into_inner()));
+            }
+            Event::CData(e) => {
+                mark_content(&mut stack);
+                out.push_str("<![CDATA[");
+                out.push_str(&String::from_utf8_lossy(&e.into_inner()));
+                out.push_str("]]>");
+            }
+            Event::End(_) => {
+                let (tag, has_content) = stack.pop().ok_or_else(|| {
+                    Lib3mfError::InvalidStructure("Unbalanced end tag".to_string())
+                })?;
+                if !has_content {
+                    // `<a></a>` is written like `<a/>`
+                    out.pop();
+                    out.push_str("/>\n");
+                } else if out.ends_with('\n') {
+                    out.push_str(&indent(stack.len()));
+                    out.push_str(&format!("</{tag}>\n"));
+                } else {
+                    out.push_str(&format!("</{tag}>\n"));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(out)
+}
+
+/// Starts the first child line of the innermost open element.
+fn open_parent(stack: &mut [(String, bool)], out: &mut String) {
+    if !out.ends_with('\n') && !out.is_empty() {
+        out.push('\n');
+    }
+    mark_content(stack);
+}
+
+fn mark_content(stack: &mut [(String, bool)]) {
+    if let Some((_, has_content)) = stack.last_mut() {
+        *has_content = true;
+    }
+}
+
+fn indent(depth: usize) -> String {
+    "  ".repeat(depth)
+}
+
+fn name(e: &BytesStart) -> String {
+    String::from_utf8_lossy(e.name().as_ref()).into_owned()
+}
+
+fn start_tag(e: &BytesStart) -> Result<String> {
+    let mut attrs = e
+        .attributes()
+        .map(|attr| {
+            let attr = attr.map_err(|e| Lib3mfError::InvalidStructure(e.to_string()))?;
+            Ok((
+                String::from_utf8_lossy(attr.key.as_ref()).into_owned(),
+                String::from_utf8_lossy(&attr.value).into_owned(),
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    attrs.sort_by(|(a, _), (b, _)| {
+        let is_ns = |k: &str| k == "xmlns" || k.starts_with("xmlns:");
+        is_ns(b).cmp(&is_ns(a)).then_with(|| a.cmp(b))
+    });
+
+    let mut tag = format!("<{}", name(e));
+    for (key, value) in attrs {
+        tag.push_str(&format!(" {key}=\"{value}\""));
+    }
+    tag.push('>');
+    Ok(tag)
+}
+
+/// Canonical form of every part of a 3MF package, in part name order.
+///
+/// XML parts (`.model`, `.rels`, `.xml`) are canonicalized with [`canonicalize_xml`];
+/// other parts are listed with their size only.
+pub fn canonicalize_package(package: &[u8]) -> Result<String> {
+    let mut archive = ZipArchiver::new(Cursor::new(package))?;
+    let mut entries = archive.list_entries()?;
+    entries.retain(|e| !e.ends_with('/'));
+    entries.sort();
+
+    let mut out = String::new();
+    for entry in entries {
+        let data = archive.read_entry(&entry)?;
+        out.push_str(&format!("==> {entry} <==\n"));
+        let is_xml = [".model", ".rels", ".xml"]
+            .iter()
+            .any(|ext| entry.to_ascii_lowercase().ends_with(ext));
+        match std::str::from_utf8(&data) {
+            Ok(xml) if is_xml => out.push_str(&canonicalize_xml(xml)?),
+            _ => out.push_str(&format!("({} bytes)\n", data.len())),
+        }
+    }
+    Ok(out)
+}
+
+/// Writes `model` as a package and returns its [`canonicalize_package`] form.
+pub fn model_snapshot(model: &Model) -> Result<String> {
+    let mut buffer = Cursor::new(Vec::new());
+    model.write(&mut buffer)?;
+    canonicalize_package(buffer.get_ref())
+}
+
+/// Compares `actual` to the snapshot stored at `path`, panicking with the first
+/// differences on a mismatch.
+///
+/// With the [`BLESS_ENV`] environment variable set (`LIB3MF_BLESS=1 cargo test`), the
+/// snapshot is written instead. A missing snapshot fails, so new snapshots are always
+/// blessed deliberately.
+pub fn assert_snapshot(path: impl AsRef<Path>, actual: &str) {
+    let path = path.as_ref();
+    if std::env::var_os(BLESS_ENV).is_some() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("create snapshot directory");
+        }
+        std::fs::write(path, actual).expect("write snapshot");
+        return;
+    }
+
+    let expected = match std::fs::read_to_string(path) {
+        Ok(expected) => expected,
+        Err(e) => panic!(
+            "Snapshot {} cannot be read ({e}); run with {BLESS_ENV}=1 to create it",
+            path.display()
+        ),
+    };
+    if expected == actual {
+        return;
+    }
+
+    let expected_lines: Vec<_> = expected.lines().collect();
+    let actual_lines: Vec<_> = actual.lines().collect();
+    let mut diff = String::new();
+    let mut shown = 0;
+    for i in 0..expected_lines.len().max(actual_lines.len()) {
+        let (old, new) = (expected_lines.get(i), actual_lines.get(i));
+        if old == new {
+            continue;
+        }
+        if let Some(old) = old {
+            diff.push_str(&format!("{:>5} - {old}\n", i + 1));
+        }
+        if let Some(new) = new {
+            diff.push_str(&format!("{:>5} + {new}\n", i + 1));
+        }
+        shown += 1;
+        if shown == 20 {
+            diff.push_str("      ...\n");
+            break;
+        }
+    }
+    panic!(
+        "Snapshot {} does not match ({} lines stored, {} produced):\n{diff}\
+         Run with {BLESS_ENV}=1 to accept the new output",
+        path.display(),
+        expected_lines.len(),
+        actual_lines.len()
+    );
+}
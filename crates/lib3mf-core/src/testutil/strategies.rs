@@ -0,0 +1,244 @@
+use super::meshes::{MeshDefect, cuboid, tetrahedron, uv_sphere};
+use crate::model::{
+    BaseMaterial, BaseMaterialsGroup, BuildItem, Color, ColorGroup, Component, Components,
+    Geometry, Mesh, Model, Object, ObjectType, ResourceId, TriangleSet,
+};
+use glam::{Mat4, Vec3};
+use proptest::prelude::*;
+use uuid::Uuid;
+
+/// Closed, consistently oriented solid: a tetrahedron, subdivided box or UV sphere of
+/// random size (0.5–100 units) at a random position.
+pub fn manifold_mesh() -> impl Strategy<Value = Mesh> {
+    let shape = prop_oneof![
+        (0.5f32..100.0).prop_map(tetrahedron),
+        ((0.5f32..100.0, 0.5f32..100.0, 0.5f32..100.0), 1u32..4)
+            .prop_map(|((x, y, z), divisions)| cuboid([x, y, z], divisions)),
+        (0.5f32..100.0, 2u32..8).prop_map(|(radius, bands)| uv_sphere(radius, bands)),
+    ];
+    let offset = (-100.0f32..100.0, -100.0f32..100.0, -100.0f32..100.0);
+    (shape, offset).prop_map(|(mut mesh, (x, y, z))| {
+        for v in &mut mesh.vertices {
+            v.x += x;
+            v.y += y;
+            v.z += z;
+        }
+        mesh
+    })
+}
+
+/// A [`manifold_mesh`] with `defect` injected at a random triangle.
+pub fn mesh_with_defect(defect: MeshDefect) -> impl Strategy<Value = Mesh> {
+    (manifold_mesh(), any::<prop::sample::Index>()).prop_map(move |(mut mesh, at)| {
+        let triangle = at.index(mesh.triangles.len());
+        defect.apply(&mut mesh, triangle);
+        mesh
+    })
+}
+
+/// A mesh with a random [`MeshDefect`], together with the defect.
+pub fn defective_mesh() -> impl Strategy<Value = (Mesh, MeshDefect)> {
+    prop::sample::select(MeshDefect::ALL.to_vec())
+        .prop_flat_map(|defect| mesh_with_defect(defect).prop_map(move |mesh| (mesh, defect)))
+}
+
+/// Which extensions [`model_with`] may use; each enabled one appears in about half of
+/// the generated models.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelOptions {
+    /// Maximum number of mesh objects (at least 1, default: 4).
+    pub max_objects: usize,
+    /// Base materials as object defaults and color groups on triangles (default: true).
+    pub materials: bool,
+    /// Production Extension UUIDs on the build, items, objects and components (default: true).
+    pub production: bool,
+    /// A Triangle Sets extension group on each mesh (default: true).
+    pub triangle_sets: bool,
+    /// One assembly object placing every mesh object as a component (default: true).
+    pub components: bool,
+}
+
+impl Default for ModelOptions {
+    fn default() -> Self {
+        Self {
+            max_objects: 4,
+            materials: true,
+            production: true,
+            triangle_sets: true,
+            components: true,
+        }
+    }
+}
+
+/// Valid model with [`ModelOptions::default`].
+pub fn model() -> impl Strategy<Value = Model> {
+    model_with(ModelOptions::default())
+}
+
+/// Valid model of [`manifold_mesh`] objects using the extensions allowed by `options`.
+///
+/// Generated models have no errors at any validation level and survive a write/parse
+/// round trip.
+pub fn model_with(options: ModelOptions) -> impl Strategy<Value = Model> {
+    let meshes = prop::collection::vec(manifold_mesh(), 1..=options.max_objects.max(1));
+    let colors = prop::collection::vec(any::<(u8, u8, u8)>(), 1..=4);
+    let placements = prop::collection::vec(
+        (-100.0f32..100.0, -100.0f32..100.0, 0.0f32..100.0),
+        options.max_objects.max(1),
+    );
+    (
+        meshes,
+        colors,
+        placements,
+        any::<[bool; 4]>(),
+        any::<u128>(),
+    )
+        .prop_map(move |(meshes, colors, placements, coin, uuid_seed)| {
+            let features = ModelOptions {
+                max_objects: options.max_objects,
+                materials: options.materials && coin[0],
+                production: options.production && coin[1],
+                triangle_sets: options.triangle_sets && coin[2],
+                components: options.components && coin[3],
+            };
+            build_model(meshes, &colors, &placements, features, uuid_seed)
+        })
+}
+
+fn build_model(
+    meshes: Vec<Mesh>,
+    colors: &[(u8, u8, u8)],
+    placements: &[(f32, f32, f32)],
+    features: ModelOptions,
+    uuid_seed: u128,
+) -> Model {
+    let mut model = Model::default();
+    let mut next_id = 1;
+    let mut next_uuid = {
+        let mut counter = uuid_seed;
+        move || {
+            counter = counter.wrapping_add(1);
+            features.production.then(|| Uuid::from_u128(counter))
+        }
+    };
+    let translation = |i: usize| {
+        let (x, y, z) = placements[i % placements.len()];
+        Mat4::from_translation(Vec3::new(x, y, z))
+    };
+
+    let mut materials = None;
+    if features.materials {
+        let colors: Vec<_> = colors
+            .iter()
+            .map(|&(r, g, b)| Color::new(r, g, b, 255))
+            .collect();
+        let (base_id, color_id) = (ResourceId(next_id), ResourceId(next_id + 1));
+        next_id += 2;
+        model
+            .resources
+            .add_base_materials(BaseMaterialsGroup {
+                id: base_id,
+                materials: colors
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &display_color)| BaseMaterial {
+                        name: format!("Material {}", i + 1),
+                        display_color,
+                    })
+                    .collect(),
+            })
+            .expect("fresh resource id");
+        model
+            .resources
+            .add_color_group(ColorGroup {
+                id: color_id,
+                colors: colors.clone(),
+            })
+            .expect("fresh resource id");
+        materials = Some((base_id, color_id, colors.len() as u32));
+    }
+
+    let mut object_ids = Vec::with_capacity(meshes.len());
+    for (i, mut mesh) in meshes.into_iter().enumerate() {
+        let id = ResourceId(next_id);
+        next_id += 1;
+        if let Some((_, color_id, count)) = materials {
+            // Every other triangle gets a color; the rest use the object's base material
+            for (j, triangle) in mesh.triangles.iter_mut().enumerate().step_by(2) {
+                let index = j as u32 % count;
+                triangle.pid = Some(color_id.0);
+                triangle.p1 = Some(index);
+                triangle.p2 = Some(index);
+                triangle.p3 = Some(index);
+            }
+        }
+        if features.triangle_sets {
+            let mut set = TriangleSet::new("First half", "first-half");
+            set.triangles = (0..mesh.triangles.len() as u32 / 2).collect();
+            mesh.triangle_sets.push(set);
+        }
+        model
+            .resources
+            .add_object(Object {
+                id,
+                object_type: ObjectType::Model,
+                name: Some(format!("Object {}", i + 1)),
+                part_number: None,
+                uuid: next_uuid(),
+                pid: materials.map(|(base_id, _, _)| base_id),
+                pindex: materials.map(|(_, _, count)| i as u32 % count),
+                thumbnail: None,
+                geometry: Geometry::Mesh(mesh),
+            })
+            .expect("fresh resource id");
+        object_ids.push(id);
+    }
+
+    let build_ids = if features.components {
+        let id = ResourceId(next_id);
+        let components = object_ids
+            .iter()
+            .enumerate()
+            .map(|(i, &object_id)| Component {
+                object_id,
+                path: None,
+                uuid: next_uuid(),
+                transform: translation(i),
+            })
+            .collect();
+        model
+            .resources
+            .add_object(Object {
+                id,
+                object_type: ObjectType::Model,
+                name: Some("Assembly".to_string()),
+                part_number: None,
+                uuid: next_uuid(),
+                pid: None,
+                pindex: None,
+                thumbnail: None,
+                geometry: Geometry::Components(Components { components }),
+            })
+            .expect("fresh resource id");
+        vec![id]
+    } else {
+        object_ids
+    };
+
+    model.build.uuid = next_uuid();
+    for (i, object_id) in build_ids.into_iter().enumerate() {
+        model.build.items.push(BuildItem {
+            object_id,
+            uuid: next_uuid(),
+            path: None,
+            part_number: None,
+            transform: translation(i + 1),
+            printable: None,
+            metadata: Default::default(),
+        });
+    }
+    model
+        .metadata
+        .insert("Title", "Generated by lib3mf-core testutil");
+    model
+}
@@ -0,0 +1,256 @@
+//! Timing instrumentation with [`tracing`](https://docs.rs/tracing) spans.
+//!
+//! With the `tracing` feature, the library opens an `INFO` span with target [`TARGET`]
+//! around each stage of loading, validating and writing a model. The span names are the
+//! constants in [`span`]. When a span closes, its duration in milliseconds is recorded in
+//! its [`ELAPSED_FIELD`] field, and a `DEBUG` event repeating it is emitted inside the span,
+//! so a plain log subscriber prints the timings too. Some spans record further fields,
+//! such as the number of objects parsed or issues found.
+//!
+//! Spans nest: `parse_resources` runs inside `parse_model`, each validation check inside
+//! `validate`, and each writer stage inside `write_package`. Without a subscriber, or
+//! without the feature, the instrumentation costs nothing.
+//!
+//! Any subscriber can consume the spans, for example `tracing_subscriber::fmt` with the
+//! filter `lib3mf=debug`. [`TimingCollector`] is a minimal subscriber that only collects
+//! the durations:
+//!
+//! ```
+//! # #[cfg(feature = "tracing")] {
+//! use lib3mf_core::parser::parse_model;
+//! use lib3mf_core::trace::{TimingCollector, span};
+//!
+//! let collector = TimingCollector::new();
+//! let xml = r#"<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+//!     <resources />
+//!     <build />
+//! </model>"#;
+//! tracing::subscriber::with_default(collector.clone(), || parse_model(xml.as_bytes())).unwrap();
+//!
+//! assert_eq!(collector.count(span::PARSE_MODEL), 1);
+//! println!("parsed in {:.3} ms", collector.total_ms(span::PARSE_MODEL));
+//! # }
+//! ```
+
+/// Target of every span and event emitted by the library.
+pub const TARGET: &str = "lib3mf";
+
+/// Field holding a span's duration in milliseconds, recorded when it closes.
+pub const ELAPSED_FIELD: &str = "elapsed_ms";
+
+/// Names of the spans opened by the library.
+pub mod span {
+    /// `ZipArchiver::new` reading the archive's central directory. Records `entries`.
+    pub const ARCHIVE_OPEN: &str = "archive_open";
+    /// Parsing one model part, by any `parse_model*` function. Records `objects` and
+    /// `build_items`.
+    pub const PARSE_MODEL: &str = "parse_model";
+    /// The `<resources>` element, within `parse_model`.
+    pub const PARSE_RESOURCES: &str = "parse_resources";
+    /// The `<build>` element, within `parse_model`.
+    pub const PARSE_BUILD: &str = "parse_build";
+    /// Parsing deferred objects on the thread pool, within `parse_model_parallel`.
+    pub const PARSE_OBJECTS_PARALLEL: &str = "parse_objects_parallel";
+    /// `Model::compute_stats`.
+    pub const COMPUTE_STATS: &str = "compute_stats";
+    /// `MeshRepair::repair` on one mesh. Records `vertices_removed` and
+    /// `triangles_removed`.
+    pub const REPAIR_MESH: &str = "repair_mesh";
+    /// `Model::validate`. Records `level` and `issues`.
+    pub const VALIDATE: &str = "validate";
+    /// One validation check within `validate`, named by its `check` field. Records
+    /// `issues`, the number of issues it added.
+    pub const VALIDATION_CHECK: &str = "validation_check";
+    /// Writing a package with `PackageWriter::write` or `Model::write`. Records `parts`.
+    pub const WRITE_PACKAGE: &str = "write_package";
+    /// Writing the attachments, within `write_package`.
+    pub const WRITE_ATTACHMENTS: &str = "write_attachments";
+    /// Writing one model part and its relationships, within `write_package`. Records
+    /// `part`.
+    pub const WRITE_MODEL_PART: &str = "write_model_part";
+    /// Computing and writing the statistics cache, within `write_package`.
+    pub const WRITE_STATS_CACHE: &str = "write_stats_cache";
+    /// Writing the package relationships, within `write_package`.
+    pub const WRITE_RELATIONSHIPS: &str = "write_relationships";
+    /// Writing the content types and finishing the ZIP archive, within `write_package`.
+    pub const WRITE_CONTENT_TYPES: &str = "write_content_types";
+}
+
+/// Guard for an instrumented stage, created by [`phase!`]. Records the stage's duration
+/// when dropped.
+pub(crate) struct Phase {
+    #[cfg(feature = "tracing")]
+    span: tracing::span::EnteredSpan,
+    #[cfg(feature = "tracing")]
+    start: std::time::Instant,
+}
+
+impl Phase {
+    #[cfg(feature = "tracing")]
+    pub(crate) fn enter(span: tracing::Span) -> Self {
+        Self {
+            span: span.entered(),
+            start: std::time::Instant::now(),
+        }
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn disabled(_name: &'static str) -> Self {
+        Self {}
+    }
+
+    /// Records a field declared when the span was opened.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn record(&self, field: &str, value: impl tracing::Value) {
+        self.span.record(field, value);
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn record<T>(&self, _field: &str, _value: T) {}
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for Phase {
+    fn drop(&mut self) {
+        let elapsed_ms = self.start.elapsed().as_secs_f64() * 1e3;
+        self.span.record(ELAPSED_FIELD, elapsed_ms);
+        tracing::debug!(target: TARGET, elapsed_ms, "finished");
+    }
+}
+
+/// Opens an instrumented stage: `phase!(span::NAME, field = value, ...)` returns a
+/// [`Phase`] guard covering the rest of the enclosing scope.
+///
+/// Fields are given as in `tracing::info_span!`; declare fields recorded later with
+/// `tracing::field::Empty`. Without the `tracing` feature the fields are not evaluated.
+macro_rules! phase {
+    ($name:expr $(, $($fields:tt)*)?) => {{
+        #[cfg(feature = "tracing")]
+        let phase = $crate::trace::Phase::enter(tracing::info_span!(
+            target: "lib3mf",
+            $name,
+            $($($fields)*,)?
+            elapsed_ms = tracing::field::Empty
+        ));
+        #[cfg(not(feature = "tracing"))]
+        let phase = $crate::trace::Phase::disabled($name);
+        phase
+    }};
+}
+
+pub(crate) use phase;
+
+/// Duration of one closed span, as collected by [`TimingCollector`].
+#[cfg(feature = "tracing")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanTiming {
+    /// Span name, one of the constants in [`span`].
+    pub name: &'static str,
+    /// Duration in milliseconds.
+    pub elapsed_ms: f64,
+}
+
+/// A `tracing` subscriber that collects the duration of every library span.
+///
+/// Clones share the collected timings, so keep a clone when handing one to
+/// `tracing::subscriber::with_default` or `set_global_default`. Events and spans from
+/// other targets are disabled.
+#[cfg(feature = "tracing")]
+#[derive(Debug, Clone, Default)]
+pub struct TimingCollector {
+    inner: std::sync::Arc<std::sync::Mutex<Collected>>,
+}
+
+#[cfg(feature = "tracing")]
+#[derive(Debug, Default)]
+struct Collected {
+    next_id: u64,
+    open: std::collections::HashMap<u64, &'static str>,
+    timings: Vec<SpanTiming>,
+}
+
+#[cfg(feature = "tracing")]
+impl TimingCollector {
+    /// Creates a collector with no timings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the timings collected so far, in the order the spans closed.
+    pub fn timings(&self) -> Vec<SpanTiming> {
+        self.lock().timings.clone()
+    }
+
+    /// Returns how many spans named `name` have closed.
+    pub fn count(&self, name: &str) -> usize {
+        self.lock()
+            .timings
+            .iter()
+            .filter(|t| t.name == name)
+            .count()
+    }
+
+    /// Returns the summed duration of the spans named `name`, in milliseconds.
+    pub fn total_ms(&self, name: &str) -> f64 {
+        self.lock()
+            .timings
+            .iter()
+            .filter(|t| t.name == name)
+            .map(|t| t.elapsed_ms)
+            .sum()
+    }
+
+    /// Discards the collected timings.
+    pub fn clear(&self) {
+        self.lock().timings.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Collected> {
+        // A panic while holding the lock leaves the timings consistent
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl tracing::Subscriber for TimingCollector {
+    fn enabled(&self, metadata: &tracing::Metadata<'_>) -> bool {
+        metadata.is_span() && metadata.target() == TARGET
+    }
+
+    fn new_span(&self, attrs: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+        let mut collected = self.lock();
+        collected.next_id += 1;
+        let id = collected.next_id;
+        collected.open.insert(id, attrs.metadata().name());
+        tracing::span::Id::from_u64(id)
+    }
+
+    fn record(&self, span: &tracing::span::Id, values: &tracing::span::Record<'_>) {
+        struct Elapsed(Option<f64>);
+        impl tracing::field::Visit for Elapsed {
+            fn record_f64(&mut self, field: &tracing::field::Field, value: f64) {
+                if field.name() == ELAPSED_FIELD {
+                    self.0 = Some(value);
+                }
+            }
+            fn record_debug(&mut self, _: &tracing::field::Field, _: &dyn std::fmt::Debug) {}
+        }
+
+        let mut elapsed = Elapsed(None);
+        values.record(&mut elapsed);
+        if let Some(elapsed_ms) = elapsed.0 {
+            let mut collected = self.lock();
+            if let Some(name) = collected.open.remove(&span.into_u64()) {
+                collected.timings.push(SpanTiming { name, elapsed_ms });
+            }
+        }
+    }
+
+    fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+    fn event(&self, _: &tracing::Event<'_>) {}
+
+    fn enter(&self, _: &tracing::span::Id) {}
+
+    fn exit(&self, _: &tracing::span::Id) {}
+}
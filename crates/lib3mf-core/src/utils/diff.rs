@@ -1,12 +1,15 @@
-use crate::model::Model;
+use crate::error::{Lib3mfError, Result};
+use crate::model::{Mesh, MetadataEntry, Model, ResourceRef, SpatialIndex, Triangle};
+use glam::Vec3;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 
 /// The computed diff between two 3MF models.
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ModelDiff {
     /// Differences in metadata key-value pairs.
     pub metadata_diffs: Vec<MetadataDiff>,
-    /// Differences in resource objects (added, removed, or changed).
+    /// Differences in resources (added, removed, or changed).
     pub resource_diffs: Vec<ResourceDiff>,
     /// Differences in build item lists.
     pub build_diffs: Vec<BuildDiff>,
@@ -30,7 +33,7 @@ pub enum ResourceDiff {
     Added {
         /// Resource ID.
         id: u32,
-        /// Type name of the resource (e.g., `"Mesh"`, `"Components"`).
+        /// Type name of the resource (e.g., `"Mesh"`, `"Components"`, `"colorgroup"`).
         type_name: String,
     },
     /// A resource was removed from model A.
@@ -85,19 +88,25 @@ pub fn compare_models(model_a: &Model, model_b: &Model) -> ModelDiff {
     let mut diff = ModelDiff::default();
 
     // 1. Compare Metadata
-    // Combine keys
-    let mut all_keys: Vec<_> = model_a.metadata.keys().collect();
-    for k in model_b.metadata.keys() {
-        if !all_keys.contains(&k) {
-            all_keys.push(k);
-        }
-    }
-    all_keys.sort();
-    all_keys.dedup();
+    // Entries are keyed by name and language; translations show as `name[lang]`
+    let key = |e: &MetadataEntry| match &e.lang {
+        Some(lang) => format!("{}[{}]", e.name, lang),
+        None => e.name.clone(),
+    };
+    let values = |model: &Model| -> BTreeMap<String, String> {
+        model
+            .metadata
+            .iter()
+            .map(|e| (key(e), e.value.clone()))
+            .collect()
+    };
+    let (values_a, values_b) = (values(model_a), values(model_b));
+    let mut all_keys: BTreeSet<&String> = values_a.keys().collect();
+    all_keys.extend(values_b.keys());
 
     for key in all_keys {
-        let val_a = model_a.metadata.get(key);
-        let val_b = model_b.metadata.get(key);
+        let val_a = values_a.get(key);
+        let val_b = values_b.get(key);
 
         if val_a != val_b {
             diff.metadata_diffs.push(MetadataDiff {
@@ -121,15 +130,16 @@ pub fn compare_models(model_a: &Model, model_b: &Model) -> ModelDiff {
     let resources_b = &model_b.resources;
 
     // Check Removed or Changed
-    for res_a in resources_a.iter_objects() {
-        match resources_b.get_object(res_a.id) {
-            Some(res_b) => {
-                let type_a = get_geometry_type_name(&res_a.geometry);
-                let type_b = get_geometry_type_name(&res_b.geometry);
+    for res_a in resources_a.iter_all() {
+        let id = res_a.id();
+        match (res_a, resources_b.get(id)) {
+            (ResourceRef::Object(obj_a), Some(ResourceRef::Object(obj_b))) => {
+                let type_a = get_geometry_type_name(&obj_a.geometry);
+                let type_b = get_geometry_type_name(&obj_b.geometry);
 
                 if type_a != type_b {
                     diff.resource_diffs.push(ResourceDiff::Changed {
-                        id: res_a.id.0,
+                        id: id.0,
                         details: vec![format!("Type changed: {} -> {}", type_a, type_b)],
                     });
                 } else {
@@ -137,7 +147,7 @@ pub fn compare_models(model_a: &Model, model_b: &Model) -> ModelDiff {
                     if let (
                         crate::model::Geometry::Mesh(mesh_a),
                         crate::model::Geometry::Mesh(mesh_b),
-                    ) = (&res_a.geometry, &res_b.geometry)
+                    ) = (&obj_a.geometry, &obj_b.geometry)
                     {
                         let mut details = Vec::new();
                         if mesh_a.vertices.len() != mesh_b.vertices.len() {
@@ -157,29 +167,38 @@ pub fn compare_models(model_a: &Model, model_b: &Model) -> ModelDiff {
                         // TODO: Implement deeper hash comparison
 
                         if !details.is_empty() {
-                            diff.resource_diffs.push(ResourceDiff::Changed {
-                                id: res_a.id.0,
-                                details,
-                            });
+                            diff.resource_diffs
+                                .push(ResourceDiff::Changed { id: id.0, details });
                         }
                     }
                 }
             }
-            None => {
+            (_, Some(res_b)) if res_b.kind() != res_a.kind() => {
+                diff.resource_diffs.push(ResourceDiff::Changed {
+                    id: id.0,
+                    details: vec![format!(
+                        "Type changed: {} -> {}",
+                        resource_type_name(res_a),
+                        resource_type_name(res_b)
+                    )],
+                });
+            }
+            (_, Some(_)) => {}
+            (_, None) => {
                 diff.resource_diffs.push(ResourceDiff::Removed {
-                    id: res_a.id.0,
-                    type_name: get_geometry_type_name(&res_a.geometry).to_string(),
+                    id: id.0,
+                    type_name: resource_type_name(res_a).to_string(),
                 });
             }
         }
     }
 
     // Check Added
-    for res_b in resources_b.iter_objects() {
-        if !resources_a.exists(res_b.id) {
+    for res_b in resources_b.iter_all() {
+        if !resources_a.exists(res_b.id()) {
             diff.resource_diffs.push(ResourceDiff::Added {
-                id: res_b.id.0,
-                type_name: get_geometry_type_name(&res_b.geometry).to_string(),
+                id: res_b.id().0,
+                type_name: resource_type_name(res_b).to_string(),
             });
         }
     }
@@ -206,6 +225,161 @@ pub fn compare_models(model_a: &Model, model_b: &Model) -> ModelDiff {
     diff
 }
 
+/// Geometric deviation between two mesh surfaces, computed by [`compare_meshes`].
+///
+/// Distances are in the meshes' units, measured from points sampled on one surface to the
+/// closest point on the other.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct MeshDeviation {
+    /// Hausdorff distance: the larger of the two one-sided maxima.
+    pub hausdorff: f32,
+    /// Largest distance from mesh A to mesh B.
+    pub max_a_to_b: f32,
+    /// Largest distance from mesh B to mesh A.
+    pub max_b_to_a: f32,
+    /// Area-weighted mean distance over both surfaces.
+    pub mean: f32,
+    /// Area-weighted root-mean-square distance over both surfaces.
+    pub rms: f32,
+    /// Sample point where the Hausdorff distance is reached, on whichever mesh it was
+    /// measured from.
+    pub max_location: Vec3,
+    /// Number of surface points sampled on both meshes.
+    pub samples: usize,
+}
+
+/// Options for [`compare_meshes_with`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DeviationOptions {
+    /// Largest gap between surface samples. Default: `None`, 1/128 of the larger bounding
+    /// box diagonal.
+    pub spacing: Option<f32>,
+}
+
+/// Compares two mesh surfaces with default sampling. See [`compare_meshes_with`].
+pub fn compare_meshes(a: &Mesh, b: &Mesh) -> Result<MeshDeviation> {
+    compare_meshes_with(a, b, &DeviationOptions::default())
+}
+
+/// Computes Hausdorff, mean and RMS distances between the surfaces of `a` and `b`.
+///
+/// Each triangle is sampled on a barycentric grid no coarser than the spacing, including
+/// its vertices and edges, and every sample is weighted by its share of the triangle's
+/// area. Closest points are found with a [`SpatialIndex`] on the other mesh. The maxima
+/// are exact up to the spacing; both meshes are taken in their own coordinates.
+///
+/// # Errors
+///
+/// Returns [`Lib3mfError::Validation`] if either mesh has no triangles or the spacing is
+/// not positive.
+pub fn compare_meshes_with(
+    a: &Mesh,
+    b: &Mesh,
+    options: &DeviationOptions,
+) -> Result<MeshDeviation> {
+    let (index_a, index_b) = (SpatialIndex::new(a), SpatialIndex::new(b));
+    let (Some(bounds_a), Some(bounds_b)) = (index_a.bounds(), index_b.bounds()) else {
+        return Err(Lib3mfError::Validation(
+            "Cannot compare a mesh without triangles".into(),
+        ));
+    };
+    let diagonal = (bounds_a.max - bounds_a.min)
+        .length()
+        .max((bounds_b.max - bounds_b.min).length());
+    let spacing = options.spacing.unwrap_or(diagonal / 128.0);
+    if !(spacing > 0.0 && spacing.is_finite()) {
+        return Err(Lib3mfError::Validation(format!(
+            "Sample spacing must be positive, got {spacing}"
+        )));
+    }
+
+    let (a_to_b, max_a) = one_sided(a, &index_b, spacing);
+    let (b_to_a, max_b) = one_sided(b, &index_a, spacing);
+    let weight = a_to_b.weight + b_to_a.weight;
+    let (hausdorff, max_location) = if max_a.0 >= max_b.0 { max_a } else { max_b };
+    Ok(MeshDeviation {
+        hausdorff,
+        max_a_to_b: max_a.0,
+        max_b_to_a: max_b.0,
+        mean: ((a_to_b.sum + b_to_a.sum) / weight.max(f64::MIN_POSITIVE)) as f32,
+        rms: ((a_to_b.sum_squares + b_to_a.sum_squares) / weight.max(f64::MIN_POSITIVE)).sqrt()
+            as f32,
+        max_location,
+        samples: a_to_b.samples + b_to_a.samples,
+    })
+}
+
+/// Area-weighted distance sums from one surface to another.
+#[derive(Default)]
+struct Sums {
+    weight: f64,
+    sum: f64,
+    sum_squares: f64,
+    samples: usize,
+}
+
+/// Distances from samples on `mesh` to the mesh behind `other`, with the largest one and
+/// where it was measured.
+fn one_sided(mesh: &Mesh, other: &SpatialIndex, spacing: f32) -> (Sums, (f32, Vec3)) {
+    let measure = |t: &Triangle| {
+        let [a, b, c] = [t.v1, t.v2, t.v3].map(|v| {
+            let v = mesh.vertices[v as usize];
+            Vec3::new(v.x, v.y, v.z)
+        });
+        let longest = a.distance(b).max(b.distance(c)).max(c.distance(a));
+        let n = (longest / spacing).ceil().clamp(1.0, 4096.0) as usize;
+        let count = (n + 1) * (n + 2) / 2;
+        let weight = f64::from((b - a).cross(c - a).length() / 2.0) / count as f64;
+        let mut sums = Sums {
+            samples: count,
+            ..Default::default()
+        };
+        let mut max = (0.0f32, a);
+        for i in 0..=n {
+            for j in 0..=n - i {
+                let p = a + (b - a) * (i as f32 / n as f32) + (c - a) * (j as f32 / n as f32);
+                let d = other.closest_point(p).map_or(f32::INFINITY, |c| c.distance);
+                sums.weight += weight;
+                sums.sum += weight * f64::from(d);
+                sums.sum_squares += weight * f64::from(d) * f64::from(d);
+                if d > max.0 {
+                    max = (d, p);
+                }
+            }
+        }
+        (sums, max)
+    };
+    let merge = |(x, mx): (Sums, (f32, Vec3)), (y, my): (Sums, (f32, Vec3))| {
+        (
+            Sums {
+                weight: x.weight + y.weight,
+                sum: x.sum + y.sum,
+                sum_squares: x.sum_squares + y.sum_squares,
+                samples: x.samples + y.samples,
+            },
+            if my.0 > mx.0 { my } else { mx },
+        )
+    };
+    let empty = || (Sums::default(), (0.0f32, Vec3::ZERO));
+
+    #[cfg(feature = "parallel")]
+    let result = {
+        use rayon::prelude::*;
+        mesh.triangles.par_iter().map(measure).reduce(empty, merge)
+    };
+    #[cfg(not(feature = "parallel"))]
+    let result = mesh.triangles.iter().map(measure).fold(empty(), merge);
+    result
+}
+
+/// Geometry type name for objects, element name (e.g. `"colorgroup"`) for other resources.
+fn resource_type_name(resource: ResourceRef<'_>) -> &'static str {
+    match resource {
+        ResourceRef::Object(object) => get_geometry_type_name(&object.geometry),
+        other => other.kind().name(),
+    }
+}
+
 fn get_geometry_type_name(g: &crate::model::Geometry) -> &'static str {
     match g {
         crate::model::Geometry::Mesh(_) => "Mesh",
@@ -214,5 +388,8 @@ fn get_geometry_type_name(g: &crate::model::Geometry) -> &'static str {
         crate::model::Geometry::VolumetricStack(_) => "VolumetricStack",
         crate::model::Geometry::BooleanShape(_) => "BooleanShape",
         crate::model::Geometry::DisplacementMesh(_) => "DisplacementMesh",
+        crate::model::Geometry::LazyMesh(_) => "LazyMesh",
+        crate::model::Geometry::LevelSet(_) => "LevelSet",
+        crate::model::Geometry::PreciseMesh(_) => "PreciseMesh",
     }
 }
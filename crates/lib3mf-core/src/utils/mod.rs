@@ -4,3 +4,6 @@ pub mod c14n;
 pub mod diff;
 /// Hardware capability detection (CPU architecture, SIMD features).
 pub mod hardware;
+/// Runtime-dispatched SIMD kernels for mesh statistics and BVH construction.
+#[cfg(feature = "simd")]
+pub mod simd;
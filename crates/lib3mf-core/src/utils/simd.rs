@@ -0,0 +1,413 @@
+//! Runtime-dispatched SIMD kernels for mesh statistics and BVH construction.
+//!
+//! Each entry point returns `None` when no accelerated implementation is available for the
+//! running CPU, in which case callers fall back to their scalar code. On x86_64 the AVX
+//! kernels are used when [`detect_capabilities`] reports `avx`; other architectures always
+//! fall back. With the `parallel` feature the kernels run per chunk on the Rayon pool.
+//!
+//! Set the `LIB3MF_DISABLE_SIMD` environment variable to force the scalar paths, e.g. for
+//! A/B benchmarking without rebuilding.
+//!
+//! Bounding boxes match the scalar paths exactly for finite input. Area and volume use the
+//! same per-triangle arithmetic but a different summation order, so totals may differ in
+//! the last bits.
+
+use crate::model::{Mesh, MeshStorage, Vertex};
+use crate::utils::hardware::detect_capabilities;
+use crate::validation::bvh::AABB;
+use std::sync::OnceLock;
+
+/// Returns `true` if the accelerated kernels are active in this process.
+pub fn enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| {
+        cfg!(target_arch = "x86_64")
+            && std::env::var_os("LIB3MF_DISABLE_SIMD").is_none()
+            && detect_capabilities()
+                .simd_features
+                .iter()
+                .any(|f| f == "avx")
+    })
+}
+
+/// Name of the active backend (`"avx"` or `"scalar"`), for diagnostics.
+pub fn backend() -> &'static str {
+    if enabled() { "avx" } else { "scalar" }
+}
+
+/// Min/max corners of `vertices`, or `None` if unavailable or `vertices` is empty.
+///
+/// NaN coordinates are ignored, as with [`f32::min`].
+pub fn bounds(vertices: &[Vertex]) -> Option<([f32; 3], [f32; 3])> {
+    if !enabled() || vertices.is_empty() {
+        return None;
+    }
+    #[cfg(target_arch = "x86_64")]
+    {
+        let empty = ([f32::INFINITY; 3], [f32::NEG_INFINITY; 3]);
+        Some(map_reduce(
+            vertices,
+            empty,
+            // SAFETY: `enabled()` verified AVX support at runtime.
+            |chunk| unsafe { avx::bounds(chunk) },
+            |a, b| {
+                (
+                    std::array::from_fn(|i| a.0[i].min(b.0[i])),
+                    std::array::from_fn(|i| a.1[i].max(b.1[i])),
+                )
+            },
+        ))
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    None
+}
+
+/// Total `(surface_area, signed_volume)` of `mesh`, or `None` if unavailable.
+///
+/// # Panics
+///
+/// Panics if a triangle references a vertex index out of bounds, like the scalar path.
+pub fn area_and_volume(mesh: &Mesh) -> Option<(f64, f64)> {
+    if !enabled() {
+        return None;
+    }
+    #[cfg(target_arch = "x86_64")]
+    {
+        let vertices = &mesh.vertices;
+        Some(map_reduce(
+            &mesh.triangles,
+            (0.0, 0.0),
+            // SAFETY: `enabled()` verified AVX support at runtime.
+            |chunk| unsafe { avx::area_and_volume(vertices, chunk) },
+            |a, b| (a.0 + b.0, a.1 + b.1),
+        ))
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        let _ = mesh;
+        None
+    }
+}
+
+/// Bounding box of each triangle in `indices`, in the same order, or `None` if unavailable.
+///
+/// # Panics
+///
+/// Panics if an index or a vertex reference is out of bounds.
+pub fn triangle_bounds<S: MeshStorage>(mesh: &S, indices: &[usize]) -> Option<Vec<AABB>> {
+    if !enabled() {
+        return None;
+    }
+    #[cfg(target_arch = "x86_64")]
+    {
+        let mut out = Vec::with_capacity(indices.len());
+        // SAFETY: `enabled()` verified AVX support at runtime.
+        unsafe { avx::triangle_bounds(mesh, indices, &mut out) };
+        Some(out)
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        let _ = (mesh, indices);
+        None
+    }
+}
+
+/// Applies `kernel` to `items` (in parallel chunks with the `parallel` feature) and merges.
+#[cfg(target_arch = "x86_64")]
+fn map_reduce<T, R>(
+    items: &[T],
+    identity: R,
+    kernel: impl Fn(&[T]) -> R + Sync + Send,
+    merge: impl Fn(R, R) -> R + Sync + Send,
+) -> R
+where
+    T: Sync,
+    R: Copy + Send + Sync,
+{
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        const CHUNK: usize = 1 << 16;
+        items
+            .par_chunks(CHUNK)
+            .map(kernel)
+            .reduce(|| identity, merge)
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        merge(identity, kernel(items))
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod avx {
+    use crate::model::{MeshStorage, Triangle, Vertex};
+    use crate::validation::bvh::AABB;
+    use glam::Vec3;
+    use std::arch::x86_64::*;
+
+    /// Lane-wise `[x, y, z]` of eight points.
+    type Points = [__m256; 3];
+
+    #[target_feature(enable = "avx")]
+    fn sub(a: Points, b: Points) -> Points {
+        [
+            _mm256_sub_ps(a[0], b[0]),
+            _mm256_sub_ps(a[1], b[1]),
+            _mm256_sub_ps(a[2], b[2]),
+        ]
+    }
+
+    /// Same operand order as `glam::Vec3::cross`, so results are bit-identical.
+    #[target_feature(enable = "avx")]
+    fn cross(a: Points, b: Points) -> Points {
+        let [ax, ay, az] = a;
+        let [bx, by, bz] = b;
+        [
+            _mm256_sub_ps(_mm256_mul_ps(ay, bz), _mm256_mul_ps(by, az)),
+            _mm256_sub_ps(_mm256_mul_ps(az, bx), _mm256_mul_ps(bz, ax)),
+            _mm256_sub_ps(_mm256_mul_ps(ax, by), _mm256_mul_ps(bx, ay)),
+        ]
+    }
+
+    #[target_feature(enable = "avx")]
+    fn dot(a: Points, b: Points) -> __m256 {
+        _mm256_add_ps(
+            _mm256_add_ps(_mm256_mul_ps(a[0], b[0]), _mm256_mul_ps(a[1], b[1])),
+            _mm256_mul_ps(a[2], b[2]),
+        )
+    }
+
+    #[target_feature(enable = "avx")]
+    fn to_array(v: __m256) -> [f32; 8] {
+        let mut out = [0.0f32; 8];
+        // SAFETY: `out` holds exactly eight f32 values.
+        unsafe { _mm256_storeu_ps(out.as_mut_ptr(), v) };
+        out
+    }
+
+    #[target_feature(enable = "avx")]
+    fn hsum_pd(v: __m256d) -> f64 {
+        let mut lanes = [0.0f64; 4];
+        // SAFETY: `lanes` holds exactly four f64 values.
+        unsafe { _mm256_storeu_pd(lanes.as_mut_ptr(), v) };
+        lanes.iter().sum()
+    }
+
+    /// Reads `[v1, v2, v3]` for eight triangles, transposed to lane-wise coordinates.
+    #[target_feature(enable = "avx")]
+    fn corners(
+        vertex: impl Fn(usize) -> Vertex,
+        tris: impl Iterator<Item = Triangle>,
+    ) -> [Points; 3] {
+        let mut soa = [[0.0f32; 8]; 9];
+        for (lane, t) in tris.take(8).enumerate() {
+            for (corner, index) in [t.v1, t.v2, t.v3].into_iter().enumerate() {
+                let v = vertex(index as usize);
+                soa[3 * corner][lane] = v.x;
+                soa[3 * corner + 1][lane] = v.y;
+                soa[3 * corner + 2][lane] = v.z;
+            }
+        }
+        // SAFETY: each row of `soa` holds exactly eight f32 values.
+        let load = |row: &[f32; 8]| unsafe { _mm256_loadu_ps(row.as_ptr()) };
+        [
+            [load(&soa[0]), load(&soa[1]), load(&soa[2])],
+            [load(&soa[3]), load(&soa[4]), load(&soa[5])],
+            [load(&soa[6]), load(&soa[7]), load(&soa[8])],
+        ]
+    }
+
+    #[target_feature(enable = "avx")]
+    pub(super) fn bounds(vertices: &[Vertex]) -> ([f32; 3], [f32; 3]) {
+        // SAFETY: `Vertex` is `#[repr(C)]` with three `f32` fields, so the slice is a
+        // contiguous xyz stream of `3 * len` floats.
+        let flat: &[f32] = unsafe {
+            std::slice::from_raw_parts(vertices.as_ptr().cast::<f32>(), vertices.len() * 3)
+        };
+
+        // 24 floats = 8 vertices = 3 registers; lane `i` of register `k` holds axis (8k+i)%3.
+        let chunks = flat.chunks_exact(24);
+        let tail = chunks.remainder();
+        let mut mins = [_mm256_set1_ps(f32::INFINITY); 3];
+        let mut maxs = [_mm256_set1_ps(f32::NEG_INFINITY); 3];
+        for chunk in chunks {
+            for k in 0..3 {
+                // SAFETY: `chunk` has 24 elements, so `8k..8k+8` is in bounds.
+                let v = unsafe { _mm256_loadu_ps(chunk.as_ptr().add(8 * k)) };
+                // The second operand is returned when either is NaN, so the accumulator
+                // goes second to skip NaN input like `f32::min`.
+                mins[k] = _mm256_min_ps(v, mins[k]);
+                maxs[k] = _mm256_max_ps(v, maxs[k]);
+            }
+        }
+
+        let mut min = [f32::INFINITY; 3];
+        let mut max = [f32::NEG_INFINITY; 3];
+        for k in 0..3 {
+            let (lo, hi) = (to_array(mins[k]), to_array(maxs[k]));
+            for lane in 0..8 {
+                let axis = (8 * k + lane) % 3;
+                min[axis] = min[axis].min(lo[lane]);
+                max[axis] = max[axis].max(hi[lane]);
+            }
+        }
+        for v in tail.chunks_exact(3) {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(v[axis]);
+                max[axis] = max[axis].max(v[axis]);
+            }
+        }
+        (min, max)
+    }
+
+    #[target_feature(enable = "avx")]
+    pub(super) fn area_and_volume(vertices: &[Vertex], triangles: &[Triangle]) -> (f64, f64) {
+        let chunks = triangles.chunks_exact(8);
+        let tail = chunks.remainder();
+        let sixth = _mm256_set1_ps(6.0);
+        // Per-triangle terms are f32 like the scalar path; sums are kept in f64 lanes.
+        let mut area_acc = [_mm256_setzero_pd(); 2];
+        let mut volume_acc = [_mm256_setzero_pd(); 2];
+
+        for chunk in chunks {
+            let [p1, p2, p3] = corners(|i| vertices[i], chunk.iter().copied());
+            let c = cross(sub(p2, p1), sub(p3, p1));
+            let len = _mm256_sqrt_ps(dot(c, c));
+            let vol = _mm256_div_ps(dot(p1, cross(p2, p3)), sixth);
+            for (half, (len4, vol4)) in [
+                (_mm256_castps256_ps128(len), _mm256_castps256_ps128(vol)),
+                (_mm256_extractf128_ps(len, 1), _mm256_extractf128_ps(vol, 1)),
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                area_acc[half] = _mm256_add_pd(area_acc[half], _mm256_cvtps_pd(len4));
+                volume_acc[half] = _mm256_add_pd(volume_acc[half], _mm256_cvtps_pd(vol4));
+            }
+        }
+
+        let mut area = 0.5 * hsum_pd(_mm256_add_pd(area_acc[0], area_acc[1]));
+        let mut volume = hsum_pd(_mm256_add_pd(volume_acc[0], volume_acc[1]));
+
+        for t in tail {
+            let v1 = Vec3::from(vertices[t.v1 as usize]);
+            let v2 = Vec3::from(vertices[t.v2 as usize]);
+            let v3 = Vec3::from(vertices[t.v3 as usize]);
+            area += 0.5 * (v2 - v1).cross(v3 - v1).length() as f64;
+            volume += (v1.dot(v2.cross(v3)) / 6.0) as f64;
+        }
+        (area, volume)
+    }
+
+    #[target_feature(enable = "avx")]
+    pub(super) fn triangle_bounds<S: MeshStorage>(
+        mesh: &S,
+        indices: &[usize],
+        out: &mut Vec<AABB>,
+    ) {
+        let chunks = indices.chunks_exact(8);
+        let tail = chunks.remainder();
+
+        for chunk in chunks {
+            let [p1, p2, p3] = corners(|i| mesh.vertex(i), chunk.iter().map(|&i| mesh.triangle(i)));
+            let mut lo = [[0.0f32; 8]; 3];
+            let mut hi = [[0.0f32; 8]; 3];
+            for axis in 0..3 {
+                lo[axis] = to_array(_mm256_min_ps(_mm256_min_ps(p1[axis], p2[axis]), p3[axis]));
+                hi[axis] = to_array(_mm256_max_ps(_mm256_max_ps(p1[axis], p2[axis]), p3[axis]));
+            }
+            out.extend((0..8).map(|lane| AABB {
+                min: Vec3::new(lo[0][lane], lo[1][lane], lo[2][lane]),
+                max: Vec3::new(hi[0][lane], hi[1][lane], hi[2][lane]),
+            }));
+        }
+
+        out.extend(
+            tail.iter()
+                .map(|&i| AABB::from_triangle(mesh, &mesh.triangle(i))),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Mesh;
+
+    /// Irregular mesh whose vertex and triangle counts are not multiples of 8.
+    fn sample_mesh() -> Mesh {
+        let mut mesh = Mesh::new();
+        for i in 0..103u32 {
+            let t = i as f32 * 0.37;
+            mesh.add_vertex(t.sin() * 10.0 - 3.0, t.cos() * 7.0 + 1.0, t * 0.25 - 5.0);
+        }
+        for i in 0..157u32 {
+            mesh.add_triangle(i % 103, (i * 7 + 1) % 103, (i * 13 + 5) % 103);
+        }
+        mesh
+    }
+
+    fn scalar_bounds(vertices: &[Vertex]) -> ([f32; 3], [f32; 3]) {
+        vertices.iter().fold(
+            ([f32::INFINITY; 3], [f32::NEG_INFINITY; 3]),
+            |(min, max), v| {
+                (
+                    [min[0].min(v.x), min[1].min(v.y), min[2].min(v.z)],
+                    [max[0].max(v.x), max[1].max(v.y), max[2].max(v.z)],
+                )
+            },
+        )
+    }
+
+    #[test]
+    fn test_bounds_matches_scalar() {
+        let mesh = sample_mesh();
+        let Some(fast) = bounds(&mesh.vertices) else {
+            return; // No accelerated backend on this CPU.
+        };
+        assert_eq!(fast, scalar_bounds(&mesh.vertices));
+
+        let mut with_nan = mesh.vertices.clone();
+        with_nan[5].x = f32::NAN;
+        with_nan[40].z = f32::NAN;
+        assert_eq!(bounds(&with_nan), Some(scalar_bounds(&with_nan)));
+    }
+
+    #[test]
+    fn test_area_and_volume_matches_scalar() {
+        let mesh = sample_mesh();
+        let Some((area, volume)) = area_and_volume(&mesh) else {
+            return;
+        };
+        let (expected_area, expected_volume) = mesh
+            .triangles
+            .iter()
+            .map(|t| {
+                let v = |i: u32| glam::Vec3::from(mesh.vertices[i as usize]);
+                let (v1, v2, v3) = (v(t.v1), v(t.v2), v(t.v3));
+                (
+                    0.5 * (v2 - v1).cross(v3 - v1).length() as f64,
+                    (v1.dot(v2.cross(v3)) / 6.0) as f64,
+                )
+            })
+            .fold((0.0, 0.0), |a, b| (a.0 + b.0, a.1 + b.1));
+        assert!((area - expected_area).abs() <= 1e-9 * expected_area.abs());
+        assert!((volume - expected_volume).abs() <= 1e-9 * expected_volume.abs().max(1.0));
+    }
+
+    #[test]
+    fn test_triangle_bounds_matches_scalar() {
+        let mesh = sample_mesh();
+        let indices: Vec<usize> = (0..mesh.triangles.len()).rev().step_by(2).collect();
+        let Some(fast) = triangle_bounds(&mesh, &indices) else {
+            return;
+        };
+        assert_eq!(fast.len(), indices.len());
+        for (aabb, &i) in fast.iter().zip(&indices) {
+            let expected = AABB::from_triangle(&mesh, &mesh.triangles[i]);
+            assert_eq!(aabb.min, expected.min);
+            assert_eq!(aabb.max, expected.max);
+        }
+    }
+}
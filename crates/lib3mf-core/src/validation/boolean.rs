@@ -0,0 +1,316 @@
+//! Boolean shape graph validation — cycles, nesting depth and references to other model
+//! parts.
+//!
+//! A boolean shape's base object may itself be a boolean shape, so shapes form a graph
+//! that must be acyclic. [`validate_boolean_graph`] walks the shapes of a single model part,
+//! as [`Model::validate`] does; references with a `p:path` to another part are left to
+//! [`validate_boolean_parts`], which loads those parts from the package with a
+//! [`PartResolver`] and follows the graph across them.
+//!
+//! Both report:
+//! - a cycle through the shapes (2100)
+//! - shapes nested deeper than [`ValidationOptions::max_boolean_depth`] (2107)
+//!
+//! [`validate_boolean_parts`] also checks the objects it finds in other parts as
+//! [`validate_semantic`](crate::validation::semantic::validate_semantic) checks those of the
+//! model itself (2101 to 2104), and reports references to parts that can't be loaded (2108).
+//!
+//! [`Model::validate`]: crate::model::Model::validate
+
+use crate::archive::ArchiveReader;
+use crate::model::resolver::{PartResolver, ROOT_PATH, canonical_path};
+use crate::model::{BooleanShape, Geometry, Model, ResourceId};
+use crate::validation::{ValidationOptions, ValidationReport};
+use std::collections::HashMap;
+
+/// Validates the boolean shapes of `model` that reference objects of the same part.
+pub fn validate_boolean_graph(
+    model: &Model,
+    options: &ValidationOptions,
+    report: &mut ValidationReport,
+) {
+    let lookup = |id: ResourceId, part: &str| {
+        if part != ROOT_PATH {
+            return Lookup::Skipped;
+        }
+        Lookup::of(model.resources.get_object(id).map(|o| &o.geometry))
+    };
+    BooleanWalk::new(lookup, options, report).run(model);
+}
+
+/// Validates the boolean shapes of `model`, following references to other model parts of
+/// the package in `archive`.
+///
+/// Problems [`validate_boolean_graph`] already reported aren't reported again.
+pub fn validate_boolean_parts<A: ArchiveReader>(
+    archive: &mut A,
+    model: &Model,
+    options: &ValidationOptions,
+    report: &mut ValidationReport,
+) {
+    let references_parts = model.resources.iter_objects().any(|object| {
+        matches!(&object.geometry, Geometry::BooleanShape(shape)
+            if references(shape, ROOT_PATH).any(|r| r.part != ROOT_PATH))
+    });
+    if !references_parts {
+        return;
+    }
+
+    let mut resolver = PartResolver::new(archive, model.clone());
+    let lookup = |id: ResourceId, part: &str| match resolver.resolve_object(id, Some(part)) {
+        Ok(object) => Lookup::of(object.map(|(_, o)| &o.geometry)),
+        Err(e) => Lookup::Unavailable(e.to_string()),
+    };
+    BooleanWalk::new(lookup, options, report).run(model);
+}
+
+/// An object referenced by a boolean shape, as far as the walk is concerned.
+enum Lookup {
+    /// A boolean shape, with its references
+    Boolean(Vec<Reference>),
+    /// A mesh
+    Mesh,
+    /// A components object
+    Components,
+    /// Geometry of another extension
+    Other,
+    /// No object with the ID in the part
+    Missing,
+    /// The part can't be loaded
+    Unavailable(String),
+    /// A part the walk doesn't look into
+    Skipped,
+}
+
+impl Lookup {
+    fn of(geometry: Option<&Geometry>) -> Self {
+        match geometry {
+            None => Self::Missing,
+            Some(Geometry::BooleanShape(shape)) => {
+                // References without a path stay in the part of the shape, filled in by the walk
+                Self::Boolean(references(shape, "").collect())
+            }
+            Some(Geometry::Mesh(_) | Geometry::LazyMesh(_)) => Self::Mesh,
+            Some(Geometry::Components(_)) => Self::Components,
+            Some(_) => Self::Other,
+        }
+    }
+}
+
+/// A reference from a boolean shape to its base object or an operation object.
+struct Reference {
+    id: ResourceId,
+    /// Canonical part of the object, empty if it's the part of the shape
+    part: String,
+    /// Index of the operation, `None` for the base object
+    operation: Option<usize>,
+}
+
+/// Returns the references of `shape`, resolving those without a path to `part`.
+fn references<'a>(shape: &'a BooleanShape, part: &'a str) -> impl Iterator<Item = Reference> + 'a {
+    let reference = move |id: ResourceId, path: Option<&str>, operation| Reference {
+        id,
+        part: path.map_or_else(|| part.to_string(), |p| canonical_path(Some(p))),
+        operation,
+    };
+    std::iter::once(reference(
+        shape.base_object_id,
+        shape.base_path.as_deref(),
+        None,
+    ))
+    .chain(
+        shape
+            .operations
+            .iter()
+            .enumerate()
+            .map(move |(i, op)| reference(op.object_id, op.path.as_deref(), Some(i))),
+    )
+}
+
+enum Visit {
+    /// On the current path from a root shape
+    Active,
+    /// Walked, with the depth of the deepest shape below it (1 for a shape without any)
+    Done(u32),
+}
+
+/// Depth-first walk of the boolean shapes reachable from those of the root model part.
+struct BooleanWalk<'r, L> {
+    lookup: L,
+    max_depth: u32,
+    visits: HashMap<(u32, String), Visit>,
+    report: &'r mut ValidationReport,
+}
+
+impl<'r, L: FnMut(ResourceId, &str) -> Lookup> BooleanWalk<'r, L> {
+    fn new(lookup: L, options: &ValidationOptions, report: &'r mut ValidationReport) -> Self {
+        Self {
+            lookup,
+            max_depth: options.max_boolean_depth,
+            visits: HashMap::new(),
+            report,
+        }
+    }
+
+    fn run(mut self, model: &Model) {
+        for object in model.resources.iter_objects() {
+            if let Geometry::BooleanShape(shape) = &object.geometry {
+                let references = references(shape, ROOT_PATH).collect();
+                if self.visit(object.id, ROOT_PATH, references, 1).is_none() {
+                    // The depth error is reported once
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Walks the shape `id` of `part` at `depth`, returning the depth of the deepest shape
+    /// below it, or `None` once the maximum depth is exceeded.
+    fn visit(
+        &mut self,
+        id: ResourceId,
+        part: &str,
+        references: Vec<Reference>,
+        depth: u32,
+    ) -> Option<u32> {
+        let key = (id.0, part.to_string());
+        match self.visits.get(&key) {
+            Some(Visit::Active) => {
+                self.add_error(
+                    2100,
+                    format!(
+                        "Cycle detected in boolean operation graph involving object {}{}",
+                        id.0,
+                        in_part(part)
+                    ),
+                );
+                return Some(0);
+            }
+            Some(Visit::Done(height)) => {
+                let height = *height;
+                self.check_depth(id, part, depth + height - 1)?;
+                return Some(height);
+            }
+            None => {}
+        }
+        self.check_depth(id, part, depth)?;
+
+        self.visits.insert(key.clone(), Visit::Active);
+        let mut height = 1;
+        for reference in references {
+            let child_part = if reference.part.is_empty() {
+                part
+            } else {
+                reference.part.as_str()
+            };
+            // The model's own references are checked by validate_semantic
+            let check = child_part != ROOT_PATH;
+            let what = match reference.operation {
+                None => "base object".to_string(),
+                Some(i) => format!("operation {} object", i),
+            };
+            match (self.lookup)(reference.id, child_part) {
+                Lookup::Boolean(refs) => {
+                    if check && let Some(i) = reference.operation {
+                        self.add_error(
+                            2103,
+                            format!(
+                                "BooleanShape {} operation {} references non-mesh object {}{} (type must be mesh)",
+                                id.0,
+                                i,
+                                reference.id.0,
+                                in_part(child_part)
+                            ),
+                        );
+                    }
+                    let below = self.visit(reference.id, child_part, refs, depth + 1)?;
+                    height = height.max(below + 1);
+                }
+                Lookup::Components if check => {
+                    let code = if reference.operation.is_some() {
+                        2103
+                    } else {
+                        2101
+                    };
+                    self.add_error(
+                        code,
+                        format!(
+                            "BooleanShape {} {} {}{} cannot be Components type",
+                            id.0,
+                            what,
+                            reference.id.0,
+                            in_part(child_part)
+                        ),
+                    );
+                }
+                Lookup::Missing if check => {
+                    let code = if reference.operation.is_some() {
+                        2104
+                    } else {
+                        2102
+                    };
+                    self.add_error(
+                        code,
+                        format!(
+                            "BooleanShape {} references non-existent {} {}{}",
+                            id.0,
+                            what,
+                            reference.id.0,
+                            in_part(child_part)
+                        ),
+                    );
+                }
+                Lookup::Unavailable(error) => {
+                    self.add_error(
+                        2108,
+                        format!(
+                            "BooleanShape {} {} {} is in model part '{}', which can't be loaded: {}",
+                            id.0, what, reference.id.0, child_part, error
+                        ),
+                    );
+                }
+                _ => {}
+            }
+        }
+        self.visits.insert(key, Visit::Done(height));
+        Some(height)
+    }
+
+    /// Reports a shape nested deeper than the maximum depth.
+    fn check_depth(&mut self, id: ResourceId, part: &str, depth: u32) -> Option<()> {
+        if depth <= self.max_depth {
+            return Some(());
+        }
+        self.add_error(
+            2107,
+            format!(
+                "Boolean shapes are nested more than {} deep at object {}{}",
+                self.max_depth,
+                id.0,
+                in_part(part)
+            ),
+        );
+        None
+    }
+
+    /// Adds an error unless an identical one was reported.
+    fn add_error(&mut self, code: u32, message: String) {
+        if !self
+            .report
+            .items
+            .iter()
+            .any(|item| item.code == code && item.message == message)
+        {
+            self.report.add_error(code, message);
+        }
+    }
+}
+
+/// Names a model part in a message; the root part goes unnamed.
+fn in_part(part: &str) -> String {
+    if part == ROOT_PATH {
+        String::new()
+    } else {
+        format!(" in '{}'", part)
+    }
+}
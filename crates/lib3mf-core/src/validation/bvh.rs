@@ -1,4 +1,4 @@
-use crate::model::{Mesh, Triangle};
+use crate::model::{MeshStorage, Triangle};
 use glam::Vec3;
 
 /// Axis-Aligned Bounding Box used for BVH acceleration in collision/intersection tests.
@@ -12,10 +12,10 @@ pub struct AABB {
 
 impl AABB {
     /// Constructs an `AABB` that tightly bounds the given triangle in the mesh.
-    pub fn from_triangle(mesh: &Mesh, tri: &Triangle) -> Self {
-        let v1 = mesh.vertices[tri.v1 as usize];
-        let v2 = mesh.vertices[tri.v2 as usize];
-        let v3 = mesh.vertices[tri.v3 as usize];
+    pub fn from_triangle<S: MeshStorage>(mesh: &S, tri: &Triangle) -> Self {
+        let v1 = mesh.vertex(tri.v1 as usize);
+        let v2 = mesh.vertex(tri.v2 as usize);
+        let v3 = mesh.vertex(tri.v3 as usize);
 
         let min = Vec3::new(
             v1.x.min(v2.x).min(v3.x),
@@ -39,6 +39,38 @@ impl AABB {
             && self.min.z <= other.max.z
             && self.max.z >= other.min.z
     }
+
+    /// Squared distance from `point` to the box; zero inside it.
+    pub fn distance_squared(&self, point: Vec3) -> f32 {
+        (self.min - point)
+            .max(point - self.max)
+            .max(Vec3::ZERO)
+            .length_squared()
+    }
+
+    /// Distance along the ray `origin + t * dir` at which it enters the box (zero if it
+    /// starts inside), or `None` if it misses. `inv_dir` is the component-wise reciprocal
+    /// of the direction.
+    pub fn ray_entry(&self, origin: Vec3, inv_dir: Vec3) -> Option<f32> {
+        let t1 = (self.min - origin) * inv_dir;
+        let t2 = (self.max - origin) * inv_dir;
+        // A ray parallel to an axis and lying exactly in a face plane may count as a miss
+        let near = t1.min(t2).max_element().max(0.0);
+        let far = t1.max(t2).min_element();
+        (near <= far).then_some(near)
+    }
+}
+
+/// Bounding boxes of the triangles at `tri_indices`, in the same order.
+fn triangle_aabbs<S: MeshStorage>(mesh: &S, tri_indices: &[usize]) -> Vec<AABB> {
+    #[cfg(feature = "simd")]
+    if let Some(aabbs) = crate::utils::simd::triangle_bounds(mesh, tri_indices) {
+        return aabbs;
+    }
+    tri_indices
+        .iter()
+        .map(|&i| AABB::from_triangle(mesh, &mesh.triangle(i)))
+        .collect()
 }
 
 /// A node in a Bounding Volume Hierarchy tree for O(n log n) triangle intersection queries.
@@ -59,25 +91,26 @@ pub enum BvhContent {
 
 impl BvhNode {
     /// Builds a BVH tree over the given set of triangle indices from `mesh`.
-    pub fn build(mesh: &Mesh, tri_indices: Vec<usize>) -> Self {
-        let aabbs: Vec<AABB> = tri_indices
-            .iter()
-            .map(|&i| AABB::from_triangle(mesh, &mesh.triangles[i]))
-            .collect();
+    pub fn build<S: MeshStorage>(mesh: &S, tri_indices: Vec<usize>) -> Self {
+        // Triangle bounds are computed once up front rather than at every level.
+        let aabbs = triangle_aabbs(mesh, &tri_indices);
+        Self::build_from(tri_indices.into_iter().zip(aabbs).collect())
+    }
 
+    fn build_from(items: Vec<(usize, AABB)>) -> Self {
         let mut min = Vec3::splat(f32::INFINITY);
         let mut max = Vec3::splat(f32::NEG_INFINITY);
-        for aabb in &aabbs {
+        for (_, aabb) in &items {
             min = min.min(aabb.min);
             max = max.max(aabb.max);
         }
 
         let node_aabb = AABB { min, max };
 
-        if tri_indices.len() <= 8 {
+        if items.len() <= 8 {
             return BvhNode {
                 aabb: node_aabb,
-                content: BvhContent::Leaf(tri_indices),
+                content: BvhContent::Leaf(items.into_iter().map(|(i, _)| i).collect()),
             };
         }
 
@@ -93,39 +126,32 @@ impl BvhNode {
 
         let mid = (min[axis] + max[axis]) / 2.0;
 
-        let mut left_indices = Vec::new();
-        let mut right_indices = Vec::new();
-
-        for (i, &tri_idx) in tri_indices.iter().enumerate() {
-            let center = (aabbs[i].min[axis] + aabbs[i].max[axis]) / 2.0;
-            if center < mid {
-                left_indices.push(tri_idx);
-            } else {
-                right_indices.push(tri_idx);
-            }
-        }
+        let (left, right): (Vec<_>, Vec<_>) = items
+            .into_iter()
+            .partition(|(_, aabb)| (aabb.min[axis] + aabb.max[axis]) / 2.0 < mid);
 
         // Fallback if split failed to partition
-        if left_indices.is_empty() || right_indices.is_empty() {
+        if left.is_empty() || right.is_empty() {
+            let indices = left.into_iter().chain(right).map(|(i, _)| i).collect();
             return BvhNode {
                 aabb: node_aabb,
-                content: BvhContent::Leaf(tri_indices),
+                content: BvhContent::Leaf(indices),
             };
         }
 
         BvhNode {
             aabb: node_aabb,
             content: BvhContent::Branch(
-                Box::new(BvhNode::build(mesh, left_indices)),
-                Box::new(BvhNode::build(mesh, right_indices)),
+                Box::new(BvhNode::build_from(left)),
+                Box::new(BvhNode::build_from(right)),
             ),
         }
     }
 
     /// Finds all triangles in this BVH subtree that intersect the given triangle.
-    pub fn find_intersections(
+    pub fn find_intersections<S: MeshStorage>(
         &self,
-        mesh: &Mesh,
+        mesh: &S,
         tri_idx: usize,
         tri_aabb: &AABB,
         results: &mut Vec<usize>,
@@ -139,7 +165,7 @@ impl BvhNode {
                 for &idx in indices {
                     if idx > tri_idx {
                         // Avoid double-counting and self-check
-                        if tri_aabb.intersects(&AABB::from_triangle(mesh, &mesh.triangles[idx])) {
+                        if tri_aabb.intersects(&AABB::from_triangle(mesh, &mesh.triangle(idx))) {
                             // Precise check:
                             if intersect_triangles(mesh, tri_idx, idx) {
                                 results.push(idx);
@@ -157,9 +183,9 @@ impl BvhNode {
 }
 
 /// Robust triangle-triangle intersection (simplified Moller-Trumbore)
-fn intersect_triangles(mesh: &Mesh, i1: usize, i2: usize) -> bool {
-    let t1 = &mesh.triangles[i1];
-    let t2 = &mesh.triangles[i2];
+fn intersect_triangles<S: MeshStorage>(mesh: &S, i1: usize, i2: usize) -> bool {
+    let t1 = &mesh.triangle(i1);
+    let t2 = &mesh.triangle(i2);
 
     // Shared vertices: check if they share 1, 2 or 3 vertices.
     // If they share 2 vertices, they share an edge.
@@ -170,14 +196,17 @@ fn intersect_triangles(mesh: &Mesh, i1: usize, i2: usize) -> bool {
     if shared >= 2 {
         return false;
     }
+    if shared == 1 {
+        return fan_overlap(mesh, t1, t2);
+    }
 
-    let p1 = to_vec3(mesh.vertices[t1.v1 as usize]);
-    let p2 = to_vec3(mesh.vertices[t1.v2 as usize]);
-    let p3 = to_vec3(mesh.vertices[t1.v3 as usize]);
+    let p1 = to_vec3(mesh.vertex(t1.v1 as usize));
+    let p2 = to_vec3(mesh.vertex(t1.v2 as usize));
+    let p3 = to_vec3(mesh.vertex(t1.v3 as usize));
 
-    let q1 = to_vec3(mesh.vertices[t2.v1 as usize]);
-    let q2 = to_vec3(mesh.vertices[t2.v2 as usize]);
-    let q3 = to_vec3(mesh.vertices[t2.v3 as usize]);
+    let q1 = to_vec3(mesh.vertex(t2.v1 as usize));
+    let q2 = to_vec3(mesh.vertex(t2.v2 as usize));
+    let q3 = to_vec3(mesh.vertex(t2.v3 as usize));
 
     tri_tri_intersect(p1, p2, p3, q1, q2, q3)
 }
@@ -200,6 +229,74 @@ fn count_shared_vertices(t1: &Triangle, t2: &Triangle) -> usize {
     count
 }
 
+/// Tolerance, as the sine of an angle, for the shared-vertex test.
+const FAN_EPSILON: f32 = 1e-5;
+
+/// Intersection test for triangles sharing exactly one vertex `v`.
+///
+/// Both triangles contain `v`, so they overlap elsewhere only if they overlap right next
+/// to it. That reduces to comparing the angular wedges the triangles span at `v`, which
+/// (unlike the general test) is independent of the mesh's scale.
+fn fan_overlap<S: MeshStorage>(mesh: &S, t1: &Triangle, t2: &Triangle) -> bool {
+    let Some((a1, b1)) = fan_edges(mesh, t1, t2) else {
+        return false;
+    };
+    let Some((a2, b2)) = fan_edges(mesh, t2, t1) else {
+        return false;
+    };
+    let n1 = a1.cross(b1).normalize_or_zero();
+    let n2 = a2.cross(b2).normalize_or_zero();
+    if n1 == Vec3::ZERO || n2 == Vec3::ZERO {
+        return false; // Degenerate triangles are reported separately
+    }
+
+    let line = n1.cross(n2);
+    if line.length() < FAN_EPSILON {
+        // Coplanar: the wedges overlap if an edge of one lies inside the other, or they
+        // coincide
+        return wedge_contains(a1, b1, n1, a2) > FAN_EPSILON
+            || wedge_contains(a1, b1, n1, b2) > FAN_EPSILON
+            || wedge_contains(a2, b2, n2, a1) > FAN_EPSILON
+            || wedge_contains(a2, b2, n2, b1) > FAN_EPSILON
+            || (a1.dot(a2) > 1.0 - FAN_EPSILON && b1.dot(b2) > 1.0 - FAN_EPSILON);
+    }
+
+    // Otherwise each triangle meets the other's plane along the line through `v`; they
+    // overlap if both extend along it in the same direction. Touching along an edge only
+    // counts when the other triangle strictly contains that direction.
+    let line = line.normalize();
+    [line, -line].into_iter().any(|u| {
+        let in1 = wedge_contains(a1, b1, n1, u);
+        let in2 = wedge_contains(a2, b2, n2, u);
+        in1 > -FAN_EPSILON && in2 > -FAN_EPSILON && (in1 > FAN_EPSILON || in2 > FAN_EPSILON)
+    })
+}
+
+/// Unit edge directions from the vertex `t` shares with `other` to `t`'s other two
+/// vertices, in winding order.
+fn fan_edges<S: MeshStorage>(mesh: &S, t: &Triangle, other: &Triangle) -> Option<(Vec3, Vec3)> {
+    let vs = [t.v1, t.v2, t.v3];
+    let others = [other.v1, other.v2, other.v3];
+    let k = vs.iter().position(|v| others.contains(v))?;
+    let p = |i: usize| to_vec3(mesh.vertex(vs[(k + i) % 3] as usize));
+    let v = p(0);
+    Some((
+        (p(1) - v).normalize_or_zero(),
+        (p(2) - v).normalize_or_zero(),
+    ))
+}
+
+/// How far unit direction `u` lies inside the wedge spanned by unit edges `a` and `b` with
+/// normal `n`: positive inside, negative outside, near zero on an edge.
+fn wedge_contains(a: Vec3, b: Vec3, n: Vec3, u: Vec3) -> f32 {
+    // Drop any component out of the wedge's plane
+    let u = (u - n * n.dot(u)).normalize_or_zero();
+    if u == Vec3::ZERO {
+        return -1.0;
+    }
+    a.cross(u).dot(n).min(u.cross(b).dot(n))
+}
+
 // ----------------------------------------------------------------------------
 // Simplified Triangle-Triangle Intersection logic (adapted)
 // ----------------------------------------------------------------------------
@@ -241,6 +338,13 @@ fn tri_tri_intersect(p1: Vec3, p2: Vec3, p3: Vec3, q1: Vec3, q2: Vec3, q3: Vec3)
         return false; // Tri 2 entirely on one side of plane 1
     }
 
+    // Coplanar triangles have no line of intersection; compare them within the plane
+    let (u1, u2) = (n1.normalize(), n2.normalize());
+    let scale = (p2 - p1).length().max((q2 - q1).length());
+    if u1.cross(u2).length() < FAN_EPSILON && u1.dot(q1 - p1).abs() < FAN_EPSILON * scale {
+        return coplanar_overlap([p1, p2, p3], [q1, q2, q3], u1, scale);
+    }
+
     // 3. Line of intersection L
     let ld = n1.cross(n2);
     let index = if ld.x.abs() > ld.y.abs() && ld.x.abs() > ld.z.abs() {
@@ -294,3 +398,25 @@ fn tri_tri_intersect(p1: Vec3, p2: Vec3, p3: Vec3, q1: Vec3, q2: Vec3, q3: Vec3)
         _ => false,
     }
 }
+
+/// Separating axis test for triangles in the plane with unit normal `n`. Triangles that
+/// only touch along an edge or at a vertex do not overlap.
+fn coplanar_overlap(t1: [Vec3; 3], t2: [Vec3; 3], n: Vec3, scale: f32) -> bool {
+    let project = |t: &[Vec3; 3], axis: Vec3| {
+        t.iter()
+            .map(|p| p.dot(axis))
+            .fold((f32::MAX, f32::MIN), |(lo, hi), d| (lo.min(d), hi.max(d)))
+    };
+    let margin = FAN_EPSILON * scale;
+    [t1, t2].iter().all(|t| {
+        (0..3).all(|i| {
+            let axis = (t[(i + 1) % 3] - t[i]).cross(n).normalize_or_zero();
+            if axis == Vec3::ZERO {
+                return true;
+            }
+            let (lo1, hi1) = project(&t1, axis);
+            let (lo2, hi2) = project(&t2, axis);
+            lo1 + margin < hi2 && lo2 + margin < hi1
+        })
+    })
+}
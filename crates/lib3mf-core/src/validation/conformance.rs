@@ -0,0 +1,346 @@
+//! Conformance suite runner.
+//!
+//! Runs a directory of 3MF samples through the same pipeline the library uses to load a
+//! package (open archive, locate model part, parse, validate) and records a pass/fail
+//! matrix. Downstream implementers can point it at their own files, or at the 3MF
+//! Consortium samples, to check them against this implementation:
+//!
+//! ```no_run
+//! use lib3mf_core::validation::conformance::{ConformanceOptions, run_conformance_suite};
+//!
+//! # fn main() -> lib3mf_core::Result<()> {
+//! let report = run_conformance_suite("3mf-samples/", &ConformanceOptions::default())?;
+//! println!("{} of {} samples behaved as expected", report.passed(), report.results.len());
+//! std::fs::write("junit.xml", report.to_junit_xml())?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! What each sample should do is taken from its path, following the naming of the
+//! official suites: `MUSTPASS`/`MUSTFAIL` in the file or a parent directory name, or a
+//! `P_`/`N_` file name prefix. Other samples are expected to pass.
+
+use crate::archive::{ArchiveReader, ZipArchiver, check_model_not_encrypted, find_model_path};
+use crate::error::Result;
+use crate::parser::parse_model;
+use crate::validation::ValidationLevel;
+use crate::validation::report::{ValidationItem, ValidationSeverity};
+use quick_xml::escape::escape;
+use serde::Serialize;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// What a sample is expected to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Expectation {
+    /// The sample is valid and must load without validation errors.
+    MustPass,
+    /// The sample is invalid and must be rejected, either while loading or by validation.
+    MustFail,
+}
+
+impl Expectation {
+    /// Derives the expectation from a sample path.
+    ///
+    /// A `MUSTFAIL` or `MUSTPASS` component (case-insensitive, nearest to the file wins)
+    /// decides first, then an `N_` or `P_` file name prefix. Defaults to
+    /// [`Expectation::MustPass`].
+    pub fn from_path(path: &Path) -> Self {
+        for component in path.iter().rev() {
+            let name = component.to_string_lossy().to_ascii_uppercase();
+            if name.contains("MUSTFAIL") {
+                return Self::MustFail;
+            }
+            if name.contains("MUSTPASS") {
+                return Self::MustPass;
+            }
+        }
+        match path.file_name().map(|n| n.to_string_lossy()) {
+            Some(name) if name.starts_with("N_") => Self::MustFail,
+            _ => Self::MustPass,
+        }
+    }
+}
+
+/// Loading step at which a sample was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SampleStage {
+    /// Opening the file or reading the ZIP container.
+    Archive,
+    /// Locating or reading the model part (relationships, content types, encryption).
+    Package,
+    /// Parsing the model XML.
+    Parse,
+    /// Model validation reported errors.
+    Validation,
+}
+
+impl std::fmt::Display for SampleStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Archive => "archive",
+            Self::Package => "package",
+            Self::Parse => "parse",
+            Self::Validation => "validation",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Why a sample was rejected.
+#[derive(Debug, Clone, Serialize)]
+pub struct SampleRejection {
+    /// Step that rejected the sample.
+    pub stage: SampleStage,
+    /// Error message, or the first validation error.
+    pub message: String,
+    /// All validation errors, when `stage` is [`SampleStage::Validation`].
+    pub errors: Vec<ValidationItem>,
+}
+
+/// Outcome of running one sample.
+#[derive(Debug, Clone, Serialize)]
+pub struct SampleResult {
+    /// Path of the sample, relative to the suite directory.
+    pub path: PathBuf,
+    /// What the sample was expected to do.
+    pub expectation: Expectation,
+    /// Validation level the model was checked at.
+    pub level: ValidationLevel,
+    /// Why the sample was rejected, or `None` if it loaded and validated cleanly.
+    pub rejection: Option<SampleRejection>,
+    /// Time taken to load and validate the sample.
+    pub duration: Duration,
+}
+
+impl SampleResult {
+    /// Returns `true` if the sample loaded and validated without errors.
+    pub fn accepted(&self) -> bool {
+        self.rejection.is_none()
+    }
+
+    /// Returns `true` if the sample behaved as expected.
+    pub fn passed(&self) -> bool {
+        match self.expectation {
+            Expectation::MustPass => self.accepted(),
+            Expectation::MustFail => !self.accepted(),
+        }
+    }
+}
+
+/// Settings for [`run_conformance_suite`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConformanceOptions {
+    /// Validation level for samples expected to pass (default: Standard).
+    pub must_pass_level: ValidationLevel,
+    /// Validation level for samples expected to fail (default: Strict).
+    pub must_fail_level: ValidationLevel,
+}
+
+impl Default for ConformanceOptions {
+    fn default() -> Self {
+        Self {
+            must_pass_level: ValidationLevel::Standard,
+            must_fail_level: ValidationLevel::Strict,
+        }
+    }
+}
+
+impl ConformanceOptions {
+    /// Uses `level` for every sample.
+    pub fn with_level(level: ValidationLevel) -> Self {
+        Self {
+            must_pass_level: level,
+            must_fail_level: level,
+        }
+    }
+
+    /// Returns the level samples with the given expectation are validated at.
+    pub fn level_for(&self, expectation: Expectation) -> ValidationLevel {
+        match expectation {
+            Expectation::MustPass => self.must_pass_level,
+            Expectation::MustFail => self.must_fail_level,
+        }
+    }
+}
+
+/// Pass/fail matrix of a conformance run.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConformanceReport {
+    /// Suite directory the samples were read from.
+    pub suite: PathBuf,
+    /// One result per sample, sorted by path.
+    pub results: Vec<SampleResult>,
+}
+
+impl ConformanceReport {
+    /// Number of samples that behaved as expected.
+    pub fn passed(&self) -> usize {
+        self.results.iter().filter(|r| r.passed()).count()
+    }
+
+    /// Samples that did not behave as expected.
+    pub fn failures(&self) -> impl Iterator<Item = &SampleResult> {
+        self.results.iter().filter(|r| !r.passed())
+    }
+
+    /// Returns `true` if every sample behaved as expected.
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(SampleResult::passed)
+    }
+
+    /// Renders the report as JUnit XML, one `<testcase>` per sample.
+    ///
+    /// Samples are grouped by expectation (`classname` is `MustPass` or `MustFail`), so CI
+    /// systems show both halves of the matrix.
+    pub fn to_junit_xml(&self) -> String {
+        let total: Duration = self.results.iter().map(|r| r.duration).sum();
+        let failures = self.results.len() - self.passed();
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"lib3mf conformance\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">\n",
+            self.results.len(),
+            failures,
+            total.as_secs_f64()
+        ));
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" time=\"{:.3}\">\n",
+            escape(self.suite.display().to_string().as_str()),
+            self.results.len(),
+            failures,
+            total.as_secs_f64()
+        ));
+        for result in &self.results {
+            let name = result.path.display().to_string();
+            xml.push_str(&format!(
+                "    <testcase classname=\"{:?}\" name=\"{}\" time=\"{:.3}\"",
+                result.expectation,
+                escape(name.as_str()),
+                result.duration.as_secs_f64()
+            ));
+            if result.passed() {
+                xml.push_str("/>\n");
+                continue;
+            }
+            // Only MustPass samples can be rejected here, and only MustFail ones accepted
+            let message = match &result.rejection {
+                Some(rejection) => format!(
+                    "rejected at {} stage: {}",
+                    rejection.stage, rejection.message
+                ),
+                None => format!(
+                    "expected rejection, but accepted at {:?} level",
+                    result.level
+                ),
+            };
+            xml.push_str(&format!(
+                ">\n      <failure message=\"{}\" type=\"{:?}\"/>\n    </testcase>\n",
+                escape(message.as_str()),
+                result.expectation
+            ));
+        }
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+}
+
+/// Loads and validates one sample at `level`.
+///
+/// The sample's [`Expectation`] is derived from `path`; the returned result records whether
+/// it was accepted, and if not, at which [`SampleStage`].
+pub fn check_sample(path: impl AsRef<Path>, level: ValidationLevel) -> SampleResult {
+    let path = path.as_ref();
+    let start = Instant::now();
+    let rejection = load_and_validate(path, level).err();
+    SampleResult {
+        path: path.to_path_buf(),
+        expectation: Expectation::from_path(path),
+        level,
+        rejection,
+        duration: start.elapsed(),
+    }
+}
+
+/// Runs every `.3mf` file below `suite` (recursively) through [`check_sample`].
+///
+/// Fails only if the directory can't be read; problems with individual samples are
+/// recorded in the report.
+pub fn run_conformance_suite(
+    suite: impl AsRef<Path>,
+    options: &ConformanceOptions,
+) -> Result<ConformanceReport> {
+    let suite = suite.as_ref();
+    let mut samples = Vec::new();
+    collect_samples(suite, &mut samples)?;
+    samples.sort();
+
+    let results = samples
+        .into_iter()
+        .map(|path| {
+            let level = options.level_for(Expectation::from_path(&path));
+            let mut result = check_sample(&path, level);
+            result.path = path.strip_prefix(suite).unwrap_or(&path).to_path_buf();
+            result
+        })
+        .collect();
+    Ok(ConformanceReport {
+        suite: suite.to_path_buf(),
+        results,
+    })
+}
+
+fn collect_samples(dir: &Path, samples: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_samples(&path, samples)?;
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("3mf"))
+        {
+            samples.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn load_and_validate(
+    path: &Path,
+    level: ValidationLevel,
+) -> std::result::Result<(), SampleRejection> {
+    let reject = |stage: SampleStage| {
+        move |e: crate::error::Lib3mfError| SampleRejection {
+            stage,
+            message: e.to_string(),
+            errors: Vec::new(),
+        }
+    };
+
+    let file = File::open(path)
+        .map_err(Into::into)
+        .map_err(reject(SampleStage::Archive))?;
+    let mut archiver = ZipArchiver::new(file).map_err(reject(SampleStage::Archive))?;
+    let model_path = find_model_path(&mut archiver).map_err(reject(SampleStage::Package))?;
+    check_model_not_encrypted(&mut archiver, &model_path).map_err(reject(SampleStage::Package))?;
+    let data = archiver
+        .read_entry(&model_path)
+        .map_err(reject(SampleStage::Package))?;
+    let model = parse_model(std::io::Cursor::new(data)).map_err(reject(SampleStage::Parse))?;
+
+    let mut report = model.validate(level);
+    crate::validation::validate_package(&mut archiver, &model_path, &model, level, &mut report);
+    let errors: Vec<_> = report
+        .items
+        .into_iter()
+        .filter(|i| i.severity == ValidationSeverity::Error)
+        .collect();
+    match errors.first() {
+        None => Ok(()),
+        Some(first) => Err(SampleRejection {
+            stage: SampleStage::Validation,
+            message: format!("[{}] {}", first.code, first.message),
+            errors,
+        }),
+    }
+}
@@ -1,11 +1,12 @@
+use crate::archive::part_names_match;
 use crate::model::{DisplacementMesh, Geometry, Model, ObjectType, ResourceId, Unit};
 use crate::validation::{ValidationLevel, ValidationReport};
 
 /// Validate displacement-specific resources and geometry.
 ///
 /// This function validates:
-/// - Displacement2D texture resources (path, height, offset)
-/// - DisplacementMesh geometry (normals, gradients, texture coordinates)
+/// - Displacement2D texture resources (path, texture part, height, offset)
+/// - DisplacementMesh geometry (normals, gradients, displacement coordinate indices)
 ///
 /// Validation is progressive based on level:
 /// - Standard: Reference integrity, count matching
@@ -17,7 +18,7 @@ pub fn validate_displacement(model: &Model, level: ValidationLevel, report: &mut
     // Validate DisplacementMesh geometry
     for object in model.resources.iter_objects() {
         if let Geometry::DisplacementMesh(dmesh) = &object.geometry {
-            validate_displacement_mesh(dmesh, object.id, level, report, &model.resources);
+            validate_displacement_mesh(dmesh, object.id, level, report);
         }
     }
 }
@@ -154,8 +155,9 @@ fn validate_displacement_resources(
                 );
             }
 
-            // Check if path references existing attachment (warning, not error)
-            if !res.path.is_empty() && !model.attachments.contains_key(&res.path) {
+            // Check if path references existing attachment (warning, not error).
+            // Attachment keys may or may not carry the leading slash of the part name.
+            if !res.path.is_empty() && find_attachment(model, &res.path).is_none() {
                 report.add_warning(
                     5002,
                     format!(
@@ -188,15 +190,32 @@ fn validate_displacement_resources(
                 );
             }
 
-            // PNG validation (not yet implemented)
-            // TODO: Add PNG validation when png-validation feature is added
-            let _ = model; // Suppress unused variable warning
+            // Displacement textures must be PNG images; only the signature is checked
+            if let Some(data) = find_attachment(model, &res.path)
+                && !data.starts_with(PNG_SIGNATURE)
+            {
+                report.add_error(
+                    5005,
+                    format!(
+                        "Displacement2D resource {} texture '{}' is not a PNG image",
+                        res.id.0, res.path
+                    ),
+                );
+            }
         }
     }
 }
 
-// PNG validation function removed - requires png-validation feature to be added to Cargo.toml
-// TODO: Add back when png dependency is added
+pub(crate) const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Looks up the attachment for a part name such as `/3D/Textures/height.png`.
+pub(crate) fn find_attachment<'a>(model: &'a Model, path: &str) -> Option<&'a Vec<u8>> {
+    model
+        .attachments
+        .iter()
+        .find(|(k, _)| part_names_match(k, path))
+        .map(|(_, data)| data)
+}
 
 /// Validate DisplacementMesh geometry.
 fn validate_displacement_mesh(
@@ -204,7 +223,6 @@ fn validate_displacement_mesh(
     oid: ResourceId,
     level: ValidationLevel,
     report: &mut ValidationReport,
-    resources: &crate::model::ResourceCollection,
 ) {
     // Minimal level: Basic structural validation (always run)
     if mesh.vertices.is_empty() {
@@ -253,16 +271,11 @@ fn validate_displacement_mesh(
 
     // Standard level: Reference integrity
     if level >= ValidationLevel::Standard {
-        // Validate displacement texture coordinate indices
+        // Validate displacement coordinate indices against the disp2dgroup entries
+        let coordinate_count = mesh.gradients.as_ref().map_or(0, Vec::len);
         for (i, tri) in mesh.triangles.iter().enumerate() {
-            if let Some(d1) = tri.d1 {
-                validate_displacement_index(oid, i, d1, resources, report);
-            }
-            if let Some(d2) = tri.d2 {
-                validate_displacement_index(oid, i, d2, resources, report);
-            }
-            if let Some(d3) = tri.d3 {
-                validate_displacement_index(oid, i, d3, resources, report);
+            for d in [tri.d1, tri.d2, tri.d3].into_iter().flatten() {
+                validate_displacement_index(oid, i, d, coordinate_count, report);
             }
         }
 
@@ -357,23 +370,20 @@ fn validate_displacement_mesh(
     }
 }
 
-/// Helper: Validate displacement texture coordinate index references.
+/// Helper: Validate a displacement coordinate index against the mesh's coordinate count.
 fn validate_displacement_index(
     oid: ResourceId,
     tri_idx: usize,
     d_index: u32,
-    resources: &crate::model::ResourceCollection,
+    coordinate_count: usize,
     report: &mut ValidationReport,
 ) {
-    // Displacement indices are stored as ResourceId in triangle
-    // They should reference Displacement2D resources
-    let rid = ResourceId(d_index);
-    if resources.get_displacement_2d(rid).is_none() {
+    if d_index as usize >= coordinate_count {
         report.add_error(
             5014,
             format!(
-                "Triangle {} in object {} references non-existent displacement texture {}",
-                tri_idx, oid.0, d_index
+                "Triangle {} in object {} has displacement coordinate index {} but the mesh has {} displacement coordinates",
+                tri_idx, oid.0, d_index, coordinate_count
             ),
         );
     }
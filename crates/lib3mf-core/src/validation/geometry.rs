@@ -1,6 +1,13 @@
-use crate::model::{DisplacementMesh, Geometry, Mesh, Model, ObjectType, ResourceId};
+use crate::model::placement::{
+    SAFE_COORDINATE_LIMIT_MM, item_bounds, max_abs_coordinate, vertex_bounds,
+};
+use crate::model::{
+    BoundingBox, DisplacementMesh, Geometry, MeshStorage, Model, ObjectType, ResourceId, Triangle,
+    Unit,
+};
 use crate::validation::{ValidationLevel, ValidationReport};
-use std::collections::HashMap;
+use glam::Vec3;
+use std::collections::{HashMap, HashSet};
 
 /// Validates the geometry of all mesh objects in the model at the given validation level.
 pub fn validate_geometry(model: &Model, level: ValidationLevel, report: &mut ValidationReport) {
@@ -18,6 +25,16 @@ pub fn validate_geometry(model: &Model, level: ValidationLevel, report: &mut Val
                     model.unit,
                 );
             }
+            Geometry::PreciseMesh(mesh) => {
+                validate_mesh(
+                    mesh,
+                    object.id,
+                    object.object_type,
+                    level,
+                    report,
+                    model.unit,
+                );
+            }
             Geometry::DisplacementMesh(dmesh) => {
                 validate_displacement_mesh_geometry(
                     dmesh,
@@ -33,8 +50,78 @@ pub fn validate_geometry(model: &Model, level: ValidationLevel, report: &mut Val
     }
 }
 
-fn validate_mesh(
-    mesh: &Mesh,
+/// Checks that Model and SolidSupport mesh objects enclose positive volume (Strict and above).
+///
+/// Every closed mesh gets an info finding with its signed volume (4012). Meshes wound
+/// consistently but inward are flagged as inverted (4009); negative volume with mixed winding
+/// (4010) and closed meshes that enclose no volume (4011) are flagged too. Open meshes are
+/// left to the Paranoid manifold checks, as their signed volume depends on the origin.
+pub fn validate_solid_orientation(model: &Model, report: &mut ValidationReport) {
+    for object in model.resources.iter_objects() {
+        if let Geometry::Mesh(mesh) = &object.geometry
+            && object.object_type.requires_manifold()
+        {
+            check_solid_orientation(mesh, object.id, object.object_type, model.unit, report);
+        }
+    }
+}
+
+/// Warns about geometry beyond [`SAFE_COORDINATE_LIMIT_MM`] from the origin (Standard and
+/// above), where `f32` coordinates are more than a micrometre apart.
+///
+/// Meshes with vertices that far out have lost that precision in their own coordinates
+/// (4013). Build items placing geometry that far out lose it in every transform applied
+/// downstream (4014); [`Model::recenter`] moves the build back to the origin.
+pub fn validate_coordinate_range(model: &Model, report: &mut ValidationReport) {
+    let unit = model.unit;
+    let beyond_limit = |bounds: &BoundingBox| {
+        let distance = max_abs_coordinate(bounds);
+        (unit.convert(distance as f64, Unit::Millimeter) > SAFE_COORDINATE_LIMIT_MM)
+            .then(|| format!("{} {}", distance, unit.symbol()))
+    };
+    let limit = format!("{} mm", SAFE_COORDINATE_LIMIT_MM);
+
+    for object in model.resources.iter_objects() {
+        let bounds = match &object.geometry {
+            Geometry::Mesh(mesh) => mesh.compute_aabb(),
+            Geometry::DisplacementMesh(dmesh) => vertex_bounds(&dmesh.vertices),
+            _ => None,
+        };
+        if let Some(distance) = bounds.as_ref().and_then(beyond_limit) {
+            report.add_warning_with_suggestion(
+                4013,
+                format!(
+                    "Object {} ({}) has vertices {} from the origin, beyond the {} within which coordinates keep micrometre precision",
+                    object.id.0, object.object_type, distance, limit
+                ),
+                format!("Object {}", object.id.0),
+                "Model the object around its own origin and place it with the build item transform",
+            );
+        }
+    }
+
+    let item_bounds = item_bounds(model);
+    for (index, (item, bounds)) in model.build.items.iter().zip(&item_bounds).enumerate() {
+        if let Some(distance) = bounds.as_ref().and_then(beyond_limit) {
+            report.add_warning_with_suggestion(
+                4014,
+                format!(
+                    "Build item {} places object {} {} from the origin, beyond the {} within which coordinates keep micrometre precision",
+                    index, item.object_id.0, distance, limit
+                ),
+                format!("Build item {}", index),
+                "Move the build back to the origin with `Model::recenter`",
+            );
+        }
+    }
+}
+
+/// Validates one mesh object's geometry at the given level.
+///
+/// Generic over [`MeshStorage`] so [`CompactMesh`](crate::model::CompactMesh) meshes can be
+/// checked without expanding them.
+pub fn validate_mesh<S: MeshStorage>(
+    mesh: &S,
     oid: ResourceId,
     object_type: ObjectType,
     level: ValidationLevel,
@@ -42,7 +129,7 @@ fn validate_mesh(
     unit: crate::model::Unit,
 ) {
     // Basic checks for ALL object types (degenerate triangles)
-    for (i, tri) in mesh.triangles.iter().enumerate() {
+    for (i, tri) in mesh.iter_triangles().enumerate() {
         if tri.v1 == tri.v2 || tri.v2 == tri.v3 || tri.v1 == tri.v3 {
             report.add_warning(
                 4001,
@@ -81,20 +168,118 @@ fn validate_mesh(
     }
 }
 
-fn check_self_intersections(mesh: &Mesh, oid: ResourceId, report: &mut ValidationReport) {
-    if mesh.triangles.len() < 2 {
+fn check_solid_orientation<S: MeshStorage>(
+    mesh: &S,
+    oid: ResourceId,
+    object_type: ObjectType,
+    unit: Unit,
+    report: &mut ValidationReport,
+) {
+    let vertex_count = mesh.vertex_count();
+    if mesh.triangle_count() == 0
+        || mesh.iter_triangles().any(|t| {
+            [t.v1, t.v2, t.v3]
+                .iter()
+                .any(|&v| v as usize >= vertex_count)
+        })
+    {
+        return;
+    }
+
+    // Closed means every edge is shared by exactly two triangles; consistent means no
+    // directed edge appears twice
+    let mut edge_counts: HashMap<(u32, u32), usize> = HashMap::new();
+    let mut directed_edges = HashSet::new();
+    let mut consistent = true;
+    for tri in mesh.iter_triangles() {
+        for (a, b) in [(tri.v1, tri.v2), (tri.v2, tri.v3), (tri.v3, tri.v1)] {
+            *edge_counts.entry(sort_edge(a, b)).or_insert(0) += 1;
+            consistent &= directed_edges.insert((a, b));
+        }
+    }
+    if edge_counts.values().any(|&count| count != 2) {
+        return;
+    }
+
+    let (mut min, mut max) = (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN));
+    for i in 0..vertex_count {
+        let v = Vec3::from(mesh.vertex(i));
+        min = min.min(v);
+        max = max.max(v);
+    }
+    // Tetrahedra are measured from the bounding box centre to keep precision away from the origin
+    let center = ((min + max) * 0.5).as_dvec3();
+    let volume: f64 = mesh
+        .iter_triangles()
+        .map(|t| {
+            let [a, b, c] =
+                [t.v1, t.v2, t.v3].map(|v| Vec3::from(mesh.vertex(v as usize)).as_dvec3() - center);
+            a.dot(b.cross(c)) / 6.0
+        })
+        .sum();
+    let diagonal = (max - min).length() as f64;
+
+    let context = format!("Object {}", oid.0);
+    let volume_text = format!("{:.6} {}³", volume, unit.symbol());
+    report.add_info(
+        4012,
+        format!(
+            "Object {} ({}) encloses a signed volume of {}",
+            oid.0, object_type, volume_text
+        ),
+    );
+
+    if volume.abs() <= diagonal.powi(3) * 1e-9 {
+        report.add_warning_with_suggestion(
+            4011,
+            format!(
+                "Object {} ({}) is closed but encloses no volume",
+                oid.0, object_type
+            ),
+            context,
+            "If the object is a sheet without an interior, declare it as a surface object",
+        );
+    } else if volume < 0.0 && consistent {
+        report.add_warning_with_suggestion(
+            4009,
+            format!(
+                "Object {} ({}) is inside out: all triangles face inward (signed volume {})",
+                oid.0, object_type, volume_text
+            ),
+            context,
+            "Reverse the winding of every triangle, e.g. `3mf repair in.3mf out.3mf --fix harmonize`",
+        );
+    } else if volume < 0.0 {
+        report.add_warning_with_suggestion(
+            4010,
+            format!(
+                "Object {} ({}) encloses negative volume ({}) and its triangles are not consistently oriented",
+                oid.0, object_type, volume_text
+            ),
+            context,
+            "Harmonize triangle orientations, e.g. `3mf repair in.3mf out.3mf --fix harmonize`",
+        );
+    }
+}
+
+fn check_self_intersections<S: MeshStorage>(
+    mesh: &S,
+    oid: ResourceId,
+    report: &mut ValidationReport,
+) {
+    if mesh.triangle_count() < 2 {
         return;
     }
 
     use crate::validation::bvh::{AABB, BvhNode};
 
-    let tri_indices: Vec<usize> = (0..mesh.triangles.len()).collect();
+    let tri_indices: Vec<usize> = (0..mesh.triangle_count()).collect();
     let bvh = BvhNode::build(mesh, tri_indices);
 
     let mut intersections = Vec::new();
 
-    for i in 0..mesh.triangles.len() {
-        let tri_aabb = AABB::from_triangle(mesh, &mesh.triangles[i]);
+    for i in 0..mesh.triangle_count() {
+        let tri_aabb = AABB::from_triangle(mesh, &mesh.triangle(i));
         let mut results = Vec::new();
         bvh.find_intersections(mesh, i, &tri_aabb, &mut results);
         for &j in &results {
@@ -114,14 +299,14 @@ fn check_self_intersections(mesh: &Mesh, oid: ResourceId, report: &mut Validatio
     }
 }
 
-fn check_islands(mesh: &Mesh, oid: ResourceId, report: &mut ValidationReport) {
-    if mesh.triangles.is_empty() {
+fn check_islands<S: MeshStorage>(mesh: &S, oid: ResourceId, report: &mut ValidationReport) {
+    if mesh.triangle_count() == 0 {
         return;
     }
 
     // 1. Adjacency list: tri -> neighbors
     let mut edge_to_tris: HashMap<(u32, u32), Vec<usize>> = HashMap::new();
-    for (i, tri) in mesh.triangles.iter().enumerate() {
+    for (i, tri) in mesh.iter_triangles().enumerate() {
         let edges = [
             sort_edge(tri.v1, tri.v2),
             sort_edge(tri.v2, tri.v3),
@@ -132,10 +317,10 @@ fn check_islands(mesh: &Mesh, oid: ResourceId, report: &mut ValidationReport) {
         }
     }
 
-    let mut visited = vec![false; mesh.triangles.len()];
+    let mut visited = vec![false; mesh.triangle_count()];
     let mut component_count = 0;
 
-    for start_idx in 0..mesh.triangles.len() {
+    for start_idx in 0..mesh.triangle_count() {
         if visited[start_idx] {
             continue;
         }
@@ -145,7 +330,7 @@ fn check_islands(mesh: &Mesh, oid: ResourceId, report: &mut ValidationReport) {
         visited[start_idx] = true;
 
         while let Some(curr_idx) = stack.pop() {
-            let tri = &mesh.triangles[curr_idx];
+            let tri = mesh.triangle(curr_idx);
             let edges = [
                 sort_edge(tri.v1, tri.v2),
                 sort_edge(tri.v2, tri.v3),
@@ -176,14 +361,18 @@ fn check_islands(mesh: &Mesh, oid: ResourceId, report: &mut ValidationReport) {
     }
 }
 
-fn check_vertex_manifoldness(mesh: &Mesh, oid: ResourceId, report: &mut ValidationReport) {
-    if mesh.vertices.is_empty() || mesh.triangles.is_empty() {
+fn check_vertex_manifoldness<S: MeshStorage>(
+    mesh: &S,
+    oid: ResourceId,
+    report: &mut ValidationReport,
+) {
+    if mesh.vertex_count() == 0 || mesh.triangle_count() == 0 {
         return;
     }
 
     // 1. Group triangles by vertex
-    let mut vertex_to_triangles = vec![Vec::new(); mesh.vertices.len()];
-    for (i, tri) in mesh.triangles.iter().enumerate() {
+    let mut vertex_to_triangles = vec![Vec::new(); mesh.vertex_count()];
+    for (i, tri) in mesh.iter_triangles().enumerate() {
         vertex_to_triangles[tri.v1 as usize].push(i);
         vertex_to_triangles[tri.v2 as usize].push(i);
         vertex_to_triangles[tri.v3 as usize].push(i);
@@ -211,7 +400,7 @@ fn check_vertex_manifoldness(mesh: &Mesh, oid: ResourceId, report: &mut Validati
 
             while let Some(current_idx) = stack.pop() {
                 let current_tri_idx = tri_indices[current_idx];
-                let current_tri = &mesh.triangles[current_tri_idx];
+                let current_tri = mesh.triangle(current_tri_idx);
 
                 // Find neighbor triangles in the local neighbor list that share an edge with current_tri
                 // AND that edge must contain the vertex v_idx.
@@ -220,12 +409,12 @@ fn check_vertex_manifoldness(mesh: &Mesh, oid: ResourceId, report: &mut Validati
                         continue;
                     }
 
-                    let other_tri = &mesh.triangles[other_tri_idx];
+                    let other_tri = mesh.triangle(other_tri_idx);
 
                     // Do they share an edge containing v_idx?
                     // An edge is shared if they share 2 vertices.
                     // Since both share v_idx, they just need to share ONE MORE vertex.
-                    let shared_verts = count_shared_vertices(current_tri, other_tri);
+                    let shared_verts = count_shared_vertices(&current_tri, &other_tri);
                     if shared_verts >= 2 {
                         visited[other_idx] = true;
                         stack.push(other_idx);
@@ -260,10 +449,10 @@ fn count_shared_vertices(t1: &crate::model::Triangle, t2: &crate::model::Triangl
     count
 }
 
-fn check_manifoldness(mesh: &Mesh, oid: ResourceId, report: &mut ValidationReport) {
+fn check_manifoldness<S: MeshStorage>(mesh: &S, oid: ResourceId, report: &mut ValidationReport) {
     let mut edge_counts = HashMap::new();
 
-    for tri in &mesh.triangles {
+    for tri in mesh.iter_triangles() {
         let edges = [
             sort_edge(tri.v1, tri.v2),
             sort_edge(tri.v2, tri.v3),
@@ -296,12 +485,12 @@ fn check_manifoldness(mesh: &Mesh, oid: ResourceId, report: &mut ValidationRepor
     }
 }
 
-fn check_orientation(mesh: &Mesh, oid: ResourceId, report: &mut ValidationReport) {
+fn check_orientation<S: MeshStorage>(mesh: &S, oid: ResourceId, report: &mut ValidationReport) {
     // Count occurrences of directed edges.
     // If any directed edge count > 1, then two faces have edges in same direction -> Orientation Mismatch.
 
     let mut directed_edge_counts = HashMap::new();
-    for tri in &mesh.triangles {
+    for tri in mesh.iter_triangles() {
         let edges = [(tri.v1, tri.v2), (tri.v2, tri.v3), (tri.v3, tri.v1)];
         for edge in edges {
             *directed_edge_counts.entry(edge).or_insert(0) += 1;
@@ -321,8 +510,8 @@ fn check_orientation(mesh: &Mesh, oid: ResourceId, report: &mut ValidationReport
     }
 }
 
-fn check_degenerate_faces(
-    mesh: &Mesh,
+fn check_degenerate_faces<S: MeshStorage>(
+    mesh: &S,
     oid: ResourceId,
     report: &mut ValidationReport,
     unit: crate::model::Unit,
@@ -333,8 +522,8 @@ fn check_degenerate_faces(
     let scale = unit.scale_factor();
     let epsilon = 1e-12 / (scale * scale);
 
-    for (i, tri) in mesh.triangles.iter().enumerate() {
-        if mesh.compute_triangle_area(tri) < epsilon {
+    for (i, tri) in mesh.iter_triangles().enumerate() {
+        if triangle_area(mesh, &tri) < epsilon {
             report.add_warning(
                 4005,
                 format!(
@@ -346,14 +535,21 @@ fn check_degenerate_faces(
     }
 }
 
+fn triangle_area<S: MeshStorage>(mesh: &S, tri: &Triangle) -> f64 {
+    let v1 = glam::Vec3::from(mesh.vertex(tri.v1 as usize));
+    let v2 = glam::Vec3::from(mesh.vertex(tri.v2 as usize));
+    let v3 = glam::Vec3::from(mesh.vertex(tri.v3 as usize));
+    0.5 * (v2 - v1).cross(v3 - v1).length() as f64
+}
+
 fn sort_edge(v1: u32, v2: u32) -> (u32, u32) {
     if v1 < v2 { (v1, v2) } else { (v2, v1) }
 }
 
-fn count_non_manifold_edges(mesh: &Mesh) -> usize {
+fn count_non_manifold_edges<S: MeshStorage>(mesh: &S) -> usize {
     let mut edge_counts: HashMap<(u32, u32), usize> = HashMap::new();
 
-    for tri in &mesh.triangles {
+    for tri in mesh.iter_triangles() {
         let edges = [
             sort_edge(tri.v1, tri.v2),
             sort_edge(tri.v2, tri.v3),
@@ -23,6 +23,7 @@
 //! - All resource IDs are unique
 //! - Build items reference valid objects
 //! - Material references point to existing materials
+//! - Property indices within the referenced property group
 //! - Component references form valid DAG (no cycles)
 //! - Vertex indices within mesh bounds
 //!
@@ -36,6 +37,8 @@
 //! - Metadata requirements enforced
 //! - No unknown attributes or elements
 //! - Extension namespaces correctly declared
+//! - Production Extension UUIDs on the build, items, objects and components
+//! - Closed Model and SolidSupport meshes enclose positive volume (not inside out)
 //!
 //! **Performance**: Moderate
 //! **Use case**: Spec conformance testing, quality assurance
@@ -109,20 +112,34 @@
 //! - **Use Standard for production**: Catches 99% of issues in < 10ms
 //! - **Defer Paranoid to background**: Run geometry checks asynchronously
 //! - **Cache results**: Validation reports are cloneable and serializable
-//! - **Progressive checking**: Validate incrementally during parsing (not yet implemented)
+//! - **Stream huge files**: [`validate_streaming`] checks references and index bounds during
+//!   parsing with memory bounded by resource count, not mesh size;
+//!   [`validate_streaming_package`] also streams the model parts the root part references
 
+/// Boolean shape graph validation — cycles, nesting depth and references to other parts.
+pub mod boolean;
 /// Bounding Volume Hierarchy for accelerated spatial queries and intersection tests.
 pub mod bvh;
+/// Conformance suite runner — pass/fail matrix and JUnit report for a directory of samples.
+pub mod conformance;
 /// Displacement mesh validation helpers.
 pub mod displacement;
 /// Mesh geometry validation algorithms (manifoldness, self-intersection, orientation).
 pub mod geometry;
+/// Package-level validation — thumbnail parts, their content types and image data.
+pub mod package;
+/// Production Extension validation — `p:UUID` presence and uniqueness.
+pub mod production;
+/// Property index validation — object `pindex`, triangle `p1`/`p2`/`p3` and multiproperties layers.
+pub mod properties;
 /// Validation report types (`ValidationReport`, `ValidationItem`, `ValidationSeverity`).
 pub mod report;
 /// Schema-level validation against the 3MF core specification structure.
 pub mod schema;
 /// Semantic validation — resource references, ID uniqueness, and cross-reference integrity.
 pub mod semantic;
+/// Bounded-memory validation on top of the streaming parser, for very large model parts.
+pub mod streaming;
 
 use serde::{Deserialize, Serialize};
 
@@ -150,6 +167,7 @@ pub enum ValidationLevel {
     /// - Resource IDs are unique
     /// - Build items reference valid objects
     /// - Material/property references are valid
+    /// - Property indices within the referenced property group
     /// - Component references form valid DAG (no cycles)
     /// - Vertex indices within mesh bounds
     Standard,
@@ -162,6 +180,8 @@ pub enum ValidationLevel {
     /// - Metadata requirements enforced
     /// - No unknown attributes or elements
     /// - Extension namespaces correctly declared
+    /// - Production Extension UUIDs on the build, items, objects and components
+    /// - Closed Model and SolidSupport meshes enclose positive volume
     /// - Proper content type and relationship registration
     Strict,
 
@@ -179,7 +199,36 @@ pub enum ValidationLevel {
     Paranoid,
 }
 
+/// Maximum nesting depth of boolean shapes in [`ValidationOptions::default`].
+pub const DEFAULT_MAX_BOOLEAN_DEPTH: u32 = 16;
+
+/// Limits applied by validation, beyond those the [`ValidationLevel`] selects.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationOptions {
+    /// Maximum number of boolean shapes nested in one another, each the base object of the
+    /// next, before validation reports an error (2107). Default:
+    /// [`DEFAULT_MAX_BOOLEAN_DEPTH`].
+    ///
+    /// Consumers evaluate boolean shapes recursively, so deep nesting is a resource risk
+    /// even without a cycle.
+    pub max_boolean_depth: u32,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        Self {
+            max_boolean_depth: DEFAULT_MAX_BOOLEAN_DEPTH,
+        }
+    }
+}
+
 // Re-exports
+pub use boolean::{validate_boolean_graph, validate_boolean_parts};
+pub use conformance::{ConformanceOptions, ConformanceReport, run_conformance_suite};
 pub use displacement::validate_displacement;
 pub use geometry::validate_geometry;
+pub use package::{validate_package, validate_package_with_options};
+pub use production::validate_production;
+pub use properties::validate_properties;
 pub use report::{ValidationReport, ValidationSeverity};
+pub use streaming::{StreamingValidation, validate_streaming, validate_streaming_package};
@@ -0,0 +1,197 @@
+//! Package-level validation — checks on OPC parts outside the model XML.
+//!
+//! [`Model::validate`] sees only the parsed model. The checks here read the package the
+//! model was loaded from, so they take its archive as well.
+
+use crate::archive::opc::{
+    THUMBNAIL_REL_TYPE, content_type_of, parse_content_types, parse_relationships,
+};
+use crate::archive::{
+    ArchiveReader, normalize_part_name, part_names_match, part_rels_path, resolve_part_target,
+};
+use crate::model::Model;
+use crate::validation::boolean::validate_boolean_parts;
+use crate::validation::displacement::PNG_SIGNATURE;
+use crate::validation::properties::JPEG_SIGNATURE;
+use crate::validation::{ValidationLevel, ValidationOptions, ValidationReport};
+
+/// Validates the parts of the package that `model` was parsed from.
+///
+/// `model_path` is the archive entry name of the model part. At Strict level, every
+/// thumbnail the package references — through a thumbnail relationship of the package or
+/// of the model part, or an object's `thumbnail` attribute — must:
+/// - exist in the package (1010)
+/// - have the content type `image/png` or `image/jpeg` (1011)
+/// - start with the PNG or JPEG signature its content type calls for (1012)
+/// - with the `png-validation` feature, decode as a PNG image when it is one (1013)
+///
+/// At Standard level and above, boolean shapes referencing objects in other model parts
+/// are followed into those parts (see [`validate_boolean_parts`]).
+pub fn validate_package<A: ArchiveReader>(
+    archive: &mut A,
+    model_path: &str,
+    model: &Model,
+    level: ValidationLevel,
+    report: &mut ValidationReport,
+) {
+    validate_package_with_options(
+        archive,
+        model_path,
+        model,
+        level,
+        &ValidationOptions::default(),
+        report,
+    );
+}
+
+/// Validates the parts of the package that `model` was parsed from, with the limits of
+/// `options`.
+///
+/// Like [`validate_package`], which uses the default [`ValidationOptions`].
+pub fn validate_package_with_options<A: ArchiveReader>(
+    archive: &mut A,
+    model_path: &str,
+    model: &Model,
+    level: ValidationLevel,
+    options: &ValidationOptions,
+    report: &mut ValidationReport,
+) {
+    if level >= ValidationLevel::Standard {
+        validate_boolean_parts(archive, model, options, report);
+    }
+    if level >= ValidationLevel::Strict {
+        validate_thumbnails(archive, model_path, model, report);
+    }
+}
+
+fn validate_thumbnails<A: ArchiveReader>(
+    archive: &mut A,
+    model_path: &str,
+    model: &Model,
+    report: &mut ValidationReport,
+) {
+    // Referenced thumbnail parts, each with what references it first
+    let mut thumbnails: Vec<(String, String)> = Vec::new();
+    let mut add = |part: String, referrer: String| {
+        if !thumbnails.iter().any(|(p, _)| part_names_match(p, &part)) {
+            thumbnails.push((part, referrer));
+        }
+    };
+
+    let package_rels = read_relationships(archive, "_rels/.rels");
+    let model_rels = read_relationships(archive, &part_rels_path(model_path));
+    for (source, rels) in [("", &package_rels), (model_path, &model_rels)] {
+        for rel in rels.iter().filter(|r| r.rel_type == THUMBNAIL_REL_TYPE) {
+            let owner = if source.is_empty() {
+                "the package".to_string()
+            } else {
+                format!("'{}'", source)
+            };
+            add(
+                resolve_part_target(source, &rel.target),
+                format!("relationship '{}' of {}", rel.id, owner),
+            );
+        }
+    }
+    for object in model.resources.iter_objects() {
+        let Some(reference) = &object.thumbnail else {
+            continue;
+        };
+        // Older files name the relationship instead of the part
+        let part = model_rels
+            .iter()
+            .find(|r| &r.id == reference)
+            .map(|r| resolve_part_target(model_path, &r.target))
+            .unwrap_or_else(|| normalize_part_name(reference));
+        add(part, format!("object {}", object.id.0));
+    }
+    if thumbnails.is_empty() {
+        return;
+    }
+
+    let entries = archive.list_entries().unwrap_or_default();
+    let types = archive
+        .read_entry("[Content_Types].xml")
+        .ok()
+        .and_then(|data| parse_content_types(&data).ok())
+        .unwrap_or_default();
+    for (part, referrer) in thumbnails {
+        let data = entries
+            .iter()
+            .find(|e| part_names_match(e, &part))
+            .and_then(|entry| archive.read_entry(entry).ok());
+        let Some(data) = data else {
+            report.add_error(
+                1010,
+                format!(
+                    "Thumbnail '{}' referenced by {} does not exist in the package",
+                    part, referrer
+                ),
+            );
+            continue;
+        };
+
+        let content_type = content_type_of(&types, &part).unwrap_or("");
+        let signature = if content_type.eq_ignore_ascii_case("image/png") {
+            PNG_SIGNATURE
+        } else if content_type.eq_ignore_ascii_case("image/jpeg") {
+            JPEG_SIGNATURE
+        } else {
+            report.add_error(
+                1011,
+                format!(
+                    "Thumbnail '{}' has content type '{}', expected image/png or image/jpeg",
+                    part, content_type
+                ),
+            );
+            continue;
+        };
+        if !data.starts_with(signature) {
+            report.add_error(
+                1012,
+                format!(
+                    "Thumbnail '{}' does not match its content type {}",
+                    part, content_type
+                ),
+            );
+            continue;
+        }
+
+        #[cfg(feature = "png-validation")]
+        if signature == PNG_SIGNATURE
+            && let Err(e) = decode_png(&data)
+        {
+            report.add_error(
+                1013,
+                format!("Thumbnail '{}' is not a valid PNG image: {}", part, e),
+            );
+        }
+    }
+}
+
+/// Relationships in `rels_path`, or none if the part is missing or malformed.
+fn read_relationships<A: ArchiveReader>(
+    archive: &mut A,
+    rels_path: &str,
+) -> Vec<crate::archive::opc::Relationship> {
+    archive
+        .read_entry(rels_path)
+        .ok()
+        .and_then(|data| parse_relationships(&data).ok())
+        .unwrap_or_default()
+}
+
+/// Decodes the first frame of a PNG image, which checks its chunks and compressed data.
+///
+/// Images whose decoded size exceeds 64 MiB only have their header checked.
+#[cfg(feature = "png-validation")]
+fn decode_png(data: &[u8]) -> std::result::Result<(), png::DecodingError> {
+    const MAX_DECODED_BYTES: usize = 64 * 1024 * 1024;
+    let mut reader = png::Decoder::new(std::io::Cursor::new(data)).read_info()?;
+    if reader.output_buffer_size() > MAX_DECODED_BYTES {
+        return Ok(());
+    }
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    reader.next_frame(&mut buffer)?;
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use crate::model::{Geometry, Model};
+use crate::validation::{ValidationLevel, ValidationReport};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Validates Production Extension UUIDs.
+///
+/// Validation is progressive based on level:
+/// - Standard: UUIDs are unique across the build, build items, objects and components
+/// - Strict: When the model uses the Production Extension, each of those carries a UUID
+///   (see [`Model::uses_production_extension`])
+pub fn validate_production(model: &Model, level: ValidationLevel, report: &mut ValidationReport) {
+    let mut owners: Vec<(String, Option<Uuid>)> = vec![("Build".to_string(), model.build.uuid)];
+    for (i, item) in model.build.items.iter().enumerate() {
+        owners.push((format!("Build item {}", i), item.uuid));
+    }
+    for object in model.resources.iter_objects() {
+        owners.push((format!("Object {}", object.id.0), object.uuid));
+        if let Geometry::Components(comps) = &object.geometry {
+            for (i, component) in comps.components.iter().enumerate() {
+                owners.push((
+                    format!("Component {} of Object {}", i, object.id.0),
+                    component.uuid,
+                ));
+            }
+        }
+    }
+
+    let mut seen: HashMap<Uuid, &str> = HashMap::new();
+    for (owner, uuid) in &owners {
+        let Some(uuid) = uuid else {
+            continue;
+        };
+        if let Some(first) = seen.get(uuid) {
+            report.add_error(5102, format!("{} reuses UUID {} of {}", owner, uuid, first));
+        } else {
+            seen.insert(*uuid, owner);
+        }
+    }
+
+    if level >= ValidationLevel::Strict && model.uses_production_extension() {
+        for (owner, uuid) in &owners {
+            if uuid.is_none() {
+                report.add_error(
+                    5101,
+                    format!(
+                        "{} has no p:UUID (required when the Production Extension is used)",
+                        owner
+                    ),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use crate::model::{Geometry, Model, Object, ResourceCollection, ResourceId};
+use crate::validation::displacement::{PNG_SIGNATURE, find_attachment};
+use crate::validation::{ValidationLevel, ValidationReport};
+
+/// Validates property index usage: object `pindex`, triangle `p1`/`p2`/`p3` and the
+/// layers of multiproperties groups.
+///
+/// Validation is progressive based on level:
+/// - Standard: Indices within the referenced property group, a property group to resolve
+///   triangle indices against, multiproperties layers matching `pids`
+/// - Strict: `p2`/`p3` only together with `p1`, every multiproperties layer given,
+///   `texture2d` images present and matching their content type, no fully transparent
+///   colors
+pub fn validate_properties(model: &Model, level: ValidationLevel, report: &mut ValidationReport) {
+    let resources = &model.resources;
+
+    for object in resources.iter_objects() {
+        validate_object_pindex(object, resources, report);
+
+        let triangles: Box<dyn Iterator<Item = _>> = match &object.geometry {
+            Geometry::Mesh(mesh) => {
+                Box::new(mesh.triangles.iter().map(|t| (t.pid, [t.p1, t.p2, t.p3])))
+            }
+            Geometry::DisplacementMesh(mesh) => {
+                Box::new(mesh.triangles.iter().map(|t| (t.pid, [t.p1, t.p2, t.p3])))
+            }
+            _ => continue,
+        };
+        for (i, (pid, indices)) in triangles.enumerate() {
+            validate_triangle(object, i, pid, indices, level, resources, report);
+        }
+    }
+
+    validate_multi_properties(resources, level, report);
+
+    if level >= ValidationLevel::Strict {
+        validate_textures(model, report);
+        validate_color_alpha(resources, report);
+    }
+}
+
+/// Warns about fully transparent base material and color group colors, which make the
+/// surfaces using them invisible. Usually the alpha byte was left at `00` by an exporter
+/// that meant to write an opaque `#RRGGBB` color.
+fn validate_color_alpha(resources: &ResourceCollection, report: &mut ValidationReport) {
+    for group in resources.iter_base_materials() {
+        for (i, material) in group.materials.iter().enumerate() {
+            if material.display_color.is_transparent() {
+                report.add_warning(
+                    2113,
+                    format!(
+                        "Base material {} of group {} ('{}') has a fully transparent displaycolor {}",
+                        i,
+                        group.id.0,
+                        material.name,
+                        material.display_color.to_hex()
+                    ),
+                );
+            }
+        }
+    }
+    for group in resources.iter_color_groups() {
+        for (i, color) in group.colors.iter().enumerate() {
+            if color.is_transparent() {
+                report.add_warning(
+                    2113,
+                    format!(
+                        "Color {} of color group {} is fully transparent ({})",
+                        i,
+                        group.id.0,
+                        color.to_hex()
+                    ),
+                );
+            }
+        }
+    }
+}
+
+pub(crate) const JPEG_SIGNATURE: &[u8] = b"\xFF\xD8\xFF";
+
+/// Checks that every `texture2d` names a PNG or JPEG part that is in the package and
+/// whose image signature matches the declared content type.
+fn validate_textures(model: &Model, report: &mut ValidationReport) {
+    for texture in model.resources.iter_texture_2d() {
+        let signature = match texture.contenttype.as_str() {
+            "image/png" => PNG_SIGNATURE,
+            "image/jpeg" => JPEG_SIGNATURE,
+            other => {
+                report.add_error(
+                    2110,
+                    format!(
+                        "Texture2D {} has content type '{}', expected image/png or image/jpeg",
+                        texture.id.0, other
+                    ),
+                );
+                continue;
+            }
+        };
+        match find_attachment(model, &texture.path) {
+            None => report.add_warning(
+                2111,
+                format!(
+                    "Texture2D {} references non-existent attachment '{}'",
+                    texture.id.0, texture.path
+                ),
+            ),
+            Some(data) if !data.starts_with(signature) => report.add_error(
+                2112,
+                format!(
+                    "Texture2D {} image '{}' does not match its content type {}",
+                    texture.id.0, texture.path, texture.contenttype
+                ),
+            ),
+            Some(_) => {}
+        }
+    }
+}
+
+/// Number of entries in the property group `id`, or `None` if `id` isn't a property group.
+fn property_group_len(resources: &ResourceCollection, id: ResourceId) -> Option<usize> {
+    if let Some(group) = resources.get_base_materials(id) {
+        Some(group.materials.len())
+    } else if let Some(group) = resources.get_color_group(id) {
+        Some(group.colors.len())
+    } else if let Some(group) = resources.get_texture_2d_group(id) {
+        Some(group.coords.len())
+    } else if let Some(group) = resources.get_composite_materials(id) {
+        Some(group.composites.len())
+    } else {
+        resources
+            .get_multi_properties(id)
+            .map(|group| group.multis.len())
+    }
+}
+
+fn validate_object_pindex(
+    object: &Object,
+    resources: &ResourceCollection,
+    report: &mut ValidationReport,
+) {
+    let Some(pid) = object.pid else {
+        return;
+    };
+    if !resources.exists(pid) {
+        // Reported as 2001
+        return;
+    }
+    match property_group_len(resources, pid) {
+        None => report.add_error(
+            2014,
+            format!(
+                "Object {} pid {} does not reference a property group",
+                object.id.0, pid.0
+            ),
+        ),
+        Some(len) => {
+            if let Some(pindex) = object.pindex
+                && pindex as usize >= len
+            {
+                report.add_error(
+                    2011,
+                    format!(
+                        "Object {} pindex {} is out of range for property group {} ({} entries)",
+                        object.id.0, pindex, pid.0, len
+                    ),
+                );
+            }
+        }
+    }
+}
+
+fn validate_triangle(
+    object: &Object,
+    index: usize,
+    pid: Option<u32>,
+    indices: [Option<u32>; 3],
+    level: ValidationLevel,
+    resources: &ResourceCollection,
+    report: &mut ValidationReport,
+) {
+    let [p1, p2, p3] = indices;
+    if level >= ValidationLevel::Strict && p1.is_none() && (p2.is_some() || p3.is_some()) {
+        report.add_error(
+            2015,
+            format!(
+                "Triangle {} in Object {} specifies p2 or p3 without p1",
+                index, object.id.0
+            ),
+        );
+    }
+    if indices.iter().all(Option::is_none) {
+        return;
+    }
+
+    // Triangles without a pid use the object's property group
+    let Some(group) = pid.map(ResourceId).or(object.pid) else {
+        report.add_error(
+            2012,
+            format!(
+                "Triangle {} in Object {} has property indices but neither it nor the object specifies a pid",
+                index, object.id.0
+            ),
+        );
+        return;
+    };
+    if !resources.exists(group) {
+        // Reported as 2001 or 2002
+        return;
+    }
+    let Some(len) = property_group_len(resources, group) else {
+        // The object pid case is reported once per object
+        if pid.is_some() {
+            report.add_error(
+                2014,
+                format!(
+                    "Triangle {} in Object {} pid {} does not reference a property group",
+                    index, object.id.0, group.0
+                ),
+            );
+        }
+        return;
+    };
+    for (name, p) in ["p1", "p2", "p3"].into_iter().zip(indices) {
+        if let Some(p) = p
+            && p as usize >= len
+        {
+            report.add_error(
+                2013,
+                format!(
+                    "Triangle {} in Object {} {} {} is out of range for property group {} ({} entries)",
+                    index, object.id.0, name, p, group.0, len
+                ),
+            );
+        }
+    }
+}
+
+fn validate_multi_properties(
+    resources: &ResourceCollection,
+    level: ValidationLevel,
+    report: &mut ValidationReport,
+) {
+    for multi_prop in resources.iter_multi_properties() {
+        let layer_lens: Vec<Option<usize>> = multi_prop
+            .pids
+            .iter()
+            .map(|&pid| property_group_len(resources, pid))
+            .collect();
+
+        for (i, multi) in multi_prop.multis.iter().enumerate() {
+            if multi.pindices.len() > multi_prop.pids.len() {
+                report.add_error(
+                    2026,
+                    format!(
+                        "MultiProperties {} multi {} has {} pindices but only {} pids",
+                        multi_prop.id.0,
+                        i,
+                        multi.pindices.len(),
+                        multi_prop.pids.len()
+                    ),
+                );
+            } else if level >= ValidationLevel::Strict
+                && multi.pindices.len() < multi_prop.pids.len()
+            {
+                report.add_warning(
+                    2028,
+                    format!(
+                        "MultiProperties {} multi {} has {} pindices for {} pids (missing layers use index 0)",
+                        multi_prop.id.0,
+                        i,
+                        multi.pindices.len(),
+                        multi_prop.pids.len()
+                    ),
+                );
+            }
+
+            for (layer, (&pindex, len)) in multi.pindices.iter().zip(&layer_lens).enumerate() {
+                if let Some(len) = *len
+                    && pindex as usize >= len
+                {
+                    report.add_error(
+                        2027,
+                        format!(
+                            "MultiProperties {} multi {} layer {} index {} is out of range for property group {} ({} entries)",
+                            multi_prop.id.0, i, layer, pindex, multi_prop.pids[layer].0, len
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
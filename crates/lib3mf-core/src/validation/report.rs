@@ -61,6 +61,23 @@ impl ValidationReport {
         });
     }
 
+    /// Adds a warning-severity finding naming the affected resource and a suggested fix.
+    pub fn add_warning_with_suggestion(
+        &mut self,
+        code: u32,
+        msg: impl Into<String>,
+        context: impl Into<String>,
+        suggestion: impl Into<String>,
+    ) {
+        self.items.push(ValidationItem {
+            severity: ValidationSeverity::Warning,
+            code,
+            message: msg.into(),
+            suggestion: Some(suggestion.into()),
+            context: Some(context.into()),
+        });
+    }
+
     /// Adds an info-severity finding to the report.
     pub fn add_info(&mut self, code: u32, msg: impl Into<String>) {
         self.items.push(ValidationItem {
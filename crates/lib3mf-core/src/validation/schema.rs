@@ -1,7 +1,47 @@
-use crate::model::Model;
+use crate::model::{Extension, Model, UnknownNameKind};
+use crate::validation::ValidationLevel;
 use crate::validation::report::ValidationReport;
 
 /// Performs basic schema checks not caught by the XML parser.
-pub fn validate_schema(_model: &Model, _report: &mut ValidationReport) {
-    // Basic schema checks that aren't caught by parser
+///
+/// Required extensions this library doesn't support are reported as warnings (1003); the
+/// parser only lets them through with
+/// [`RequiredExtensionPolicy::Warn`](crate::parser::RequiredExtensionPolicy::Warn).
+///
+/// At Strict level, elements (1001) and attributes (1002) that the 3MF schemas don't
+/// define are reported as warnings. The parser records them only when asked to with
+/// [`ParseOptions::track_unknown_names`](crate::parser::ParseOptions::track_unknown_names).
+pub fn validate_schema(model: &Model, level: ValidationLevel, report: &mut ValidationReport) {
+    for required in &model.required_extensions {
+        if Extension::from_namespace(required).is_none() {
+            report.add_warning(
+                1003,
+                format!(
+                    "Required extension '{}' is not supported; its content was ignored",
+                    required
+                ),
+            );
+        }
+    }
+
+    if level >= ValidationLevel::Strict {
+        for unknown in &model.unknown_names {
+            let (code, message) = match unknown.kind {
+                UnknownNameKind::Element => (
+                    1001,
+                    format!("Unknown element <{}> at {}", unknown.name, unknown.path),
+                ),
+                UnknownNameKind::Attribute => (
+                    1002,
+                    format!("Unknown attribute '{}' on {}", unknown.name, unknown.path),
+                ),
+            };
+            report.add_warning_with_suggestion(
+                code,
+                message,
+                unknown.path.clone(),
+                "Check the spelling; vendor-specific names belong in their own namespace",
+            );
+        }
+    }
 }
@@ -1,21 +1,39 @@
-use crate::model::{Geometry, Model, ResourceId};
+use crate::model::resolver::{ROOT_PATH, canonical_path};
+use crate::model::{ChecksumFailure, Function, Geometry, Model, ResourceId, Triangle, TriangleSet};
+use crate::validation::ValidationOptions;
+use crate::validation::boolean::validate_boolean_graph;
 use crate::validation::report::ValidationReport;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
 /// Validates semantic correctness of the model (build references, material constraints, metadata).
 pub fn validate_semantic(model: &Model, report: &mut ValidationReport) {
+    validate_semantic_with_options(model, &ValidationOptions::default(), report);
+}
+
+/// Validates semantic correctness of the model, with the limits of `options`.
+pub fn validate_semantic_with_options(
+    model: &Model,
+    options: &ValidationOptions,
+    report: &mut ValidationReport,
+) {
     // Validate build items
     validate_build_references(model, report);
 
-    // Validate boolean operation cycles
-    validate_boolean_cycles(model, report);
+    // Validate boolean operation cycles and nesting depth
+    validate_boolean_graph(model, options, report);
 
     // Validate material references and constraints
     validate_material_constraints(model, report);
 
+    // Validate level sets, functions and volume data
+    validate_volumetric_references(model, report);
+
     // Validate metadata
     validate_metadata(model, report);
 
+    // Verify embedded geometry checksums
+    validate_geometry_checksums(model, report);
+
     // Check Resources
     for object in model.resources.iter_objects() {
         // Check PID validity
@@ -33,30 +51,22 @@ pub fn validate_semantic(model: &Model, report: &mut ValidationReport) {
         }
 
         match &object.geometry {
-            Geometry::Mesh(mesh) => {
-                for (i, tri) in mesh.triangles.iter().enumerate() {
-                    // Check indices bounds
-                    if tri.v1 as usize >= mesh.vertices.len()
-                        || tri.v2 as usize >= mesh.vertices.len()
-                        || tri.v3 as usize >= mesh.vertices.len()
-                    {
-                        report.add_error(
-                            3001,
-                            format!(
-                                "Triangle {} in Object {} references out-of-bounds vertex",
-                                i, object.id.0
-                            ),
-                        );
-                    }
-
-                    // Check PID
-                    if let Some(pid) = tri.pid.map(crate::model::ResourceId)
-                        && !model.resources.exists(pid)
-                    {
-                        report.add_error(2002, format!("Triangle {} in Object {} references non-existent property group {}", i, object.id.0, pid.0));
-                    }
-                }
-            }
+            Geometry::Mesh(mesh) => validate_mesh_references(
+                model,
+                object.id,
+                &mesh.triangles,
+                mesh.vertices.len(),
+                &mesh.triangle_sets,
+                report,
+            ),
+            Geometry::PreciseMesh(mesh) => validate_mesh_references(
+                model,
+                object.id,
+                &mesh.triangles,
+                mesh.vertices.len(),
+                &mesh.triangle_sets,
+                report,
+            ),
             Geometry::Components(comps) => {
                 for comp in &comps.components {
                     // Only validate internal references (components without external path)
@@ -94,11 +104,14 @@ pub fn validate_semantic(model: &Model, report: &mut ValidationReport) {
                 }
             }
             Geometry::BooleanShape(bs) => {
-                // Validate base object exists and is valid type
-                if let Some(base_obj) = model.resources.get_object(bs.base_object_id) {
+                if in_other_part(bs.base_path.as_deref()) {
+                    // Objects in other model parts are checked by validate_boolean_parts
+                } else if let Some(base_obj) = model.resources.get_object(bs.base_object_id) {
                     // Base can be Mesh or another BooleanShape (per spec)
                     match &base_obj.geometry {
-                        Geometry::Mesh(_) | Geometry::BooleanShape(_) => {
+                        Geometry::Mesh(_)
+                        | Geometry::PreciseMesh(_)
+                        | Geometry::BooleanShape(_) => {
                             // Valid base types
                         }
                         Geometry::Components(_) => {
@@ -126,10 +139,15 @@ pub fn validate_semantic(model: &Model, report: &mut ValidationReport) {
 
                 // Validate each operation object
                 for (idx, op) in bs.operations.iter().enumerate() {
+                    if in_other_part(op.path.as_deref()) {
+                        continue;
+                    }
                     if let Some(op_obj) = model.resources.get_object(op.object_id) {
                         // Operation objects MUST be triangle meshes (not Components, not BooleanShape)
                         match &op_obj.geometry {
-                            Geometry::Mesh(_) => {
+                            Geometry::Mesh(_)
+                            | Geometry::LazyMesh(_)
+                            | Geometry::PreciseMesh(_) => {
                                 // Valid - mesh object
                             }
                             _ => {
@@ -181,6 +199,12 @@ pub fn validate_semantic(model: &Model, report: &mut ValidationReport) {
                 // Displacement mesh validation will be implemented in displacement.rs
                 // For now, just allow it to pass semantic checks
             }
+            Geometry::LazyMesh(_) => {
+                // Triangles are not loaded; materialize the model to check them
+            }
+            Geometry::LevelSet(_) => {
+                // Checked by validate_volumetric_references
+            }
         }
     }
 }
@@ -212,65 +236,9 @@ fn validate_build_references(model: &Model, report: &mut ValidationReport) {
     }
 }
 
-/// Detects cycles in boolean operation graphs using DFS with recursion stack.
-fn validate_boolean_cycles(model: &Model, report: &mut ValidationReport) {
-    // Build adjacency list: BooleanShape -> referenced objects
-    let mut graph: HashMap<ResourceId, Vec<ResourceId>> = HashMap::new();
-
-    for obj in model.resources.iter_objects() {
-        if let Geometry::BooleanShape(bs) = &obj.geometry {
-            let mut refs = vec![bs.base_object_id];
-            refs.extend(bs.operations.iter().map(|op| op.object_id));
-            graph.insert(obj.id, refs);
-        }
-    }
-
-    // DFS for cycle detection
-    let mut visited = HashSet::new();
-    let mut rec_stack = HashSet::new();
-
-    for &start_id in graph.keys() {
-        if !visited.contains(&start_id)
-            && has_cycle_dfs(start_id, &graph, &mut visited, &mut rec_stack)
-        {
-            report.add_error(
-                2100,
-                format!(
-                    "Cycle detected in boolean operation graph involving object {}",
-                    start_id.0
-                ),
-            );
-        }
-    }
-}
-
-fn has_cycle_dfs(
-    node: ResourceId,
-    graph: &HashMap<ResourceId, Vec<ResourceId>>,
-    visited: &mut HashSet<ResourceId>,
-    rec_stack: &mut HashSet<ResourceId>,
-) -> bool {
-    visited.insert(node);
-    rec_stack.insert(node);
-
-    if let Some(neighbors) = graph.get(&node) {
-        for &neighbor in neighbors {
-            // Only follow edges to other BooleanShape objects (those in the graph)
-            if graph.contains_key(&neighbor) {
-                if !visited.contains(&neighbor) {
-                    if has_cycle_dfs(neighbor, graph, visited, rec_stack) {
-                        return true;
-                    }
-                } else if rec_stack.contains(&neighbor) {
-                    // Back edge found = cycle
-                    return true;
-                }
-            }
-        }
-    }
-
-    rec_stack.remove(&node);
-    false
+/// Returns whether a boolean shape reference with `path` names another model part.
+fn in_other_part(path: Option<&str>) -> bool {
+    path.is_some_and(|p| canonical_path(Some(p)) != ROOT_PATH)
 }
 
 /// Validates that a transformation matrix contains only finite values.
@@ -420,10 +388,179 @@ fn validate_material_constraints(model: &Model, report: &mut ValidationReport) {
 }
 
 /// Validates metadata constraints.
+fn validate_volumetric_references(model: &Model, report: &mut ValidationReport) {
+    let res = &model.resources;
+    let check_function = |report: &mut ValidationReport, owner: String, id, channel: &str| match res
+        .get_function(id)
+    {
+        None => report.add_error(
+            2006,
+            format!("{} references non-existent function {}", owner, id.0),
+        ),
+        Some(function) if !function.has_output(channel) => report.add_error(
+            2006,
+            format!(
+                "{} uses channel '{}', which function {} does not output",
+                owner, channel, id.0
+            ),
+        ),
+        Some(_) => {}
+    };
+    let check_volume = |report: &mut ValidationReport, owner: String, id: Option<ResourceId>| {
+        if let Some(id) = id
+            && res.get_volume_data(id).is_none()
+        {
+            report.add_error(
+                2008,
+                format!("{} references non-existent volumedata {}", owner, id.0),
+            );
+        }
+    };
+
+    for object in res.iter_objects() {
+        match &object.geometry {
+            Geometry::Mesh(mesh) => {
+                check_volume(report, format!("Object {}", object.id.0), mesh.volume_id);
+            }
+            Geometry::PreciseMesh(mesh) => {
+                check_volume(report, format!("Object {}", object.id.0), mesh.volume_id);
+            }
+            Geometry::LevelSet(level_set) => {
+                let owner = format!("Level set {}", object.id.0);
+                check_function(
+                    report,
+                    owner.clone(),
+                    level_set.function_id,
+                    &level_set.channel,
+                );
+                check_volume(report, owner, level_set.volume_id);
+                let is_mesh = res.get_object(level_set.mesh_id).is_some_and(|o| {
+                    matches!(
+                        o.geometry,
+                        Geometry::Mesh(_) | Geometry::LazyMesh(_) | Geometry::PreciseMesh(_)
+                    )
+                });
+                if !is_mesh {
+                    report.add_error(
+                        2007,
+                        format!(
+                            "Level set {} meshid {} is not a mesh object",
+                            object.id.0, level_set.mesh_id.0
+                        ),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for function in res.iter_functions() {
+        if let Function::FromImage3D(f) = function
+            && res.get_image_3d(f.image3d_id).is_none()
+        {
+            report.add_error(
+                2009,
+                format!(
+                    "Function {} references non-existent image3d {}",
+                    f.id.0, f.image3d_id.0
+                ),
+            );
+        }
+    }
+
+    for data in res.iter_volume_data() {
+        for function_ref in data.function_refs() {
+            check_function(
+                report,
+                format!("Volumedata {}", data.id.0),
+                function_ref.function_id,
+                &function_ref.channel,
+            );
+        }
+        if let Some(composite) = &data.composite
+            && res.get_base_materials(composite.base_material_id).is_none()
+        {
+            report.add_error(
+                2009,
+                format!(
+                    "Volumedata {} references non-existent basematerials {}",
+                    data.id.0, composite.base_material_id.0
+                ),
+            );
+        }
+    }
+}
+
+/// Checks the vertex indices, property references and triangle sets of a mesh object.
+fn validate_mesh_references(
+    model: &Model,
+    object_id: ResourceId,
+    triangles: &[Triangle],
+    vertex_count: usize,
+    triangle_sets: &[TriangleSet],
+    report: &mut ValidationReport,
+) {
+    for (i, tri) in triangles.iter().enumerate() {
+        // Check indices bounds
+        if tri.v1 as usize >= vertex_count
+            || tri.v2 as usize >= vertex_count
+            || tri.v3 as usize >= vertex_count
+        {
+            report.add_error(
+                3001,
+                format!(
+                    "Triangle {} in Object {} references out-of-bounds vertex",
+                    i, object_id.0
+                ),
+            );
+        }
+
+        // Check PID
+        if let Some(pid) = tri.pid.map(crate::model::ResourceId)
+            && !model.resources.exists(pid)
+        {
+            report.add_error(
+                2002,
+                format!(
+                    "Triangle {} in Object {} references non-existent property group {}",
+                    i, object_id.0, pid.0
+                ),
+            );
+        }
+    }
+
+    let mut identifiers = HashSet::new();
+    for set in triangle_sets {
+        if !identifiers.insert(set.identifier.as_str()) {
+            report.add_error(
+                3005,
+                format!(
+                    "Object {} has more than one triangle set with identifier '{}'",
+                    object_id.0, set.identifier
+                ),
+            );
+        }
+        if let Some(index) = set
+            .triangles
+            .iter()
+            .find(|&&t| t as usize >= triangles.len())
+        {
+            report.add_error(
+                3004,
+                format!(
+                    "Triangle set '{}' in Object {} references out-of-bounds triangle {}",
+                    set.identifier, object_id.0, index
+                ),
+            );
+        }
+    }
+}
+
 fn validate_metadata(model: &Model, report: &mut ValidationReport) {
-    let mut seen_names = HashSet::new();
+    let mut seen = HashSet::new();
 
-    for name in model.metadata.keys() {
+    for entry in &model.metadata {
+        let name = &entry.name;
         // Check for empty names
         if name.is_empty() {
             report.add_error(
@@ -432,8 +569,8 @@ fn validate_metadata(model: &Model, report: &mut ValidationReport) {
             );
         }
 
-        // Check for duplicate names
-        if !seen_names.insert(name.clone()) {
+        // Check for duplicate names; translations of the same name are distinct
+        if !seen.insert((name, entry.lang.as_deref())) {
             report.add_error(
                 2041,
                 format!(
@@ -444,3 +581,40 @@ fn validate_metadata(model: &Model, report: &mut ValidationReport) {
         }
     }
 }
+
+/// Recomputes embedded geometry checksums and reports entries that don't match.
+fn validate_geometry_checksums(model: &Model, report: &mut ValidationReport) {
+    for error in model.verify_geometry_checksums() {
+        match error.failure {
+            ChecksumFailure::Mismatch { expected, actual } => report.add_error(
+                2060,
+                format!(
+                    "Geometry checksum mismatch for object {}: metadata '{}' records {}, geometry hashes to {}",
+                    error.object_id.map_or(0, |id| id.0),
+                    error.name,
+                    expected,
+                    actual
+                ),
+            ),
+            ChecksumFailure::MissingObject => report.add_warning(
+                2061,
+                format!(
+                    "Geometry checksum '{}' refers to non-existent object",
+                    error.name
+                ),
+            ),
+            ChecksumFailure::Unverifiable => report.add_info(
+                2063,
+                format!(
+                    "Geometry checksum '{}' not verified: object {} references model parts or textures that are not loaded",
+                    error.name,
+                    error.object_id.map_or(0, |id| id.0)
+                ),
+            ),
+            ChecksumFailure::Malformed => report.add_warning(
+                2062,
+                format!("Geometry checksum '{}' is malformed", error.name),
+            ),
+        }
+    }
+}
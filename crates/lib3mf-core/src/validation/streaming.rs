@@ -0,0 +1,528 @@
+//! Bounded-memory validation built on the streaming parser.
+//!
+//! [`validate_streaming`] drives [`parse_model_streaming`] with a [`StreamingValidator`]
+//! visitor. Instead of building a [`Model`](crate::model::Model), the validator keeps only
+//! resource IDs, per-object counts and a content hash per mesh, so memory use depends on
+//! the number of resources rather than on the number of vertices and triangles. This makes
+//! it suitable for model parts well beyond 1GB.
+//!
+//! [`validate_streaming_package`] does the same for a whole package: it follows the
+//! Production extension `path` attributes of build items and components into the other
+//! model parts, streams each of them, and checks that the referenced objects exist.
+//!
+//! Checks that need mesh adjacency (manifoldness, orientation, self-intersection) cannot be
+//! done in a single pass with bounded memory and are not performed; use
+//! [`Model::validate`](crate::model::Model::validate) for those.
+
+use crate::archive::{ZipArchiver, normalize_part_name};
+use crate::error::Result;
+use crate::model::{Beam, BuildItem, Component, ObjectType, ResourceId, Unit};
+use crate::parser::streaming::parse_model_streaming;
+use crate::parser::visitor::ModelVisitor;
+use crate::validation::ValidationLevel;
+use crate::validation::report::ValidationReport;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::{BufRead, BufReader, Read, Seek};
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Counts and content digest for a single mesh seen by the streaming validator.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MeshDigest {
+    /// ID of the object that owns the mesh.
+    pub object_id: u32,
+    /// Number of vertices in the mesh.
+    pub vertex_count: u64,
+    /// Number of triangles in the mesh.
+    pub triangle_count: u64,
+    /// FNV-1a hash over vertex coordinates and triangle indices, in document order.
+    pub hash: u64,
+}
+
+/// A reference to an object in another model part, through a Production extension `path`
+/// attribute.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartReference {
+    /// Model part holding the object, normalized to an archive entry name.
+    pub path: String,
+    /// ID of the object within that part.
+    pub object_id: u32,
+    /// Component object making the reference, or `None` for a build item.
+    pub owner: Option<u32>,
+}
+
+/// Aggregate counts collected while streaming through a model.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StreamingSummary {
+    /// Unit declared on the `<model>` element (default if absent).
+    pub unit: Unit,
+    /// Number of resources of any kind.
+    pub resource_count: usize,
+    /// Number of object resources.
+    pub object_count: usize,
+    /// Total vertices across all meshes.
+    pub vertex_count: u64,
+    /// Total triangles across all meshes.
+    pub triangle_count: u64,
+    /// Total beams across all beam lattices.
+    pub beam_count: u64,
+    /// Total components across all component objects.
+    pub component_count: usize,
+    /// Number of build items.
+    pub build_item_count: usize,
+    /// One digest per mesh, in document order.
+    pub meshes: Vec<MeshDigest>,
+    /// References to objects in other model parts, in document order.
+    pub part_references: Vec<PartReference>,
+}
+
+/// Result of a streaming validation run.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StreamingValidation {
+    /// Findings, using the same codes as [`Model::validate`](crate::model::Model::validate)
+    /// where the checks overlap.
+    pub report: ValidationReport,
+    /// Counts and hashes collected along the way.
+    pub summary: StreamingSummary,
+}
+
+#[derive(Debug, Default)]
+struct CurrentMesh {
+    digest: MeshDigest,
+    beam_vertex_errors: u64,
+    index_errors: u64,
+    degenerate: u64,
+}
+
+/// [`ModelVisitor`] that checks reference integrity and index bounds without materializing geometry.
+///
+/// Most callers should use [`validate_streaming`]; the visitor is public so it can be combined
+/// with a custom reader pipeline.
+#[derive(Debug)]
+pub struct StreamingValidator {
+    level: ValidationLevel,
+    report: ValidationReport,
+    summary: StreamingSummary,
+    resource_ids: HashSet<u32>,
+    object_types: HashMap<u32, ObjectType>,
+    // (name, xml:lang) pairs; translations of the same name are distinct.
+    metadata_names: HashSet<(String, Option<String>)>,
+    current_object: Option<u32>,
+    current_mesh: Option<CurrentMesh>,
+    // (owner object, referenced object) pairs, checked once all resources are known.
+    component_refs: Vec<(u32, u32)>,
+    build_refs: Vec<u32>,
+}
+
+impl StreamingValidator {
+    /// Creates a validator applying the checks for `level`.
+    pub fn new(level: ValidationLevel) -> Self {
+        Self {
+            level,
+            report: ValidationReport::new(),
+            summary: StreamingSummary::default(),
+            resource_ids: HashSet::new(),
+            object_types: HashMap::new(),
+            metadata_names: HashSet::new(),
+            current_object: None,
+            current_mesh: None,
+            component_refs: Vec::new(),
+            build_refs: Vec::new(),
+        }
+    }
+
+    /// Runs the deferred reference checks and returns the findings.
+    pub fn finish(mut self) -> StreamingValidation {
+        if self.level >= ValidationLevel::Standard {
+            for &(owner, target) in &self.component_refs {
+                if !self.object_types.contains_key(&target) {
+                    self.report.add_error(
+                        2003,
+                        format!(
+                            "Component in Object {} references non-existent object {}",
+                            owner, target
+                        ),
+                    );
+                }
+            }
+            for (i, &target) in self.build_refs.iter().enumerate() {
+                match self.object_types.get(&target) {
+                    None => self.report.add_error(
+                        3002,
+                        format!("Build item {} references non-existent object {}", i, target),
+                    ),
+                    Some(t) if !t.can_be_in_build() => self.report.add_error(
+                        3010,
+                        format!(
+                            "Build item {} references object {} with type '{}' which cannot be in build",
+                            i, target, t
+                        ),
+                    ),
+                    Some(_) => {}
+                }
+            }
+        }
+        StreamingValidation {
+            report: self.report,
+            summary: self.summary,
+        }
+    }
+
+    fn register_resource(&mut self, id: ResourceId, kind: &str) {
+        self.summary.resource_count += 1;
+        if !self.resource_ids.insert(id.0) {
+            self.report
+                .add_error(2050, format!("Duplicate resource ID {} ({})", id.0, kind));
+        }
+    }
+}
+
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(hash, |h, b| (h ^ u64::from(*b)).wrapping_mul(FNV_PRIME))
+}
+
+impl ModelVisitor for StreamingValidator {
+    fn on_unit(&mut self, unit: Unit) -> Result<()> {
+        self.summary.unit = unit;
+        Ok(())
+    }
+
+    fn on_metadata(&mut self, name: &str, _value: &str, lang: Option<&str>) -> Result<()> {
+        if self.level < ValidationLevel::Standard {
+            return Ok(());
+        }
+        if name.is_empty() {
+            self.report.add_error(
+                2040,
+                "Metadata entry has empty name (name attribute is required)",
+            );
+        } else if !self
+            .metadata_names
+            .insert((name.to_string(), lang.map(str::to_string)))
+        {
+            self.report.add_error(
+                2041,
+                format!(
+                    "Metadata name '{}' is duplicated (names must be unique)",
+                    name
+                ),
+            );
+        }
+        Ok(())
+    }
+
+    fn on_base_materials(
+        &mut self,
+        id: ResourceId,
+        _group: &crate::model::BaseMaterialsGroup,
+    ) -> Result<()> {
+        self.register_resource(id, "basematerials");
+        Ok(())
+    }
+
+    fn on_color_group(&mut self, id: ResourceId, _group: &crate::model::ColorGroup) -> Result<()> {
+        self.register_resource(id, "colorgroup");
+        Ok(())
+    }
+
+    fn on_other_resource(&mut self, id: ResourceId, element: &str) -> Result<()> {
+        self.register_resource(id, element);
+        Ok(())
+    }
+
+    fn on_start_object(&mut self, id: ResourceId, object_type: ObjectType) -> Result<()> {
+        self.register_resource(id, "object");
+        self.summary.object_count += 1;
+        self.object_types.insert(id.0, object_type);
+        self.current_object = Some(id.0);
+        Ok(())
+    }
+
+    fn on_component(&mut self, component: &Component) -> Result<()> {
+        self.summary.component_count += 1;
+        // Components with a path live in another part and are checked across parts by
+        // validate_streaming_package.
+        match &component.path {
+            Some(path) => self.summary.part_references.push(PartReference {
+                path: normalize_part_name(path),
+                object_id: component.object_id.0,
+                owner: self.current_object,
+            }),
+            None => {
+                if let Some(owner) = self.current_object {
+                    self.component_refs.push((owner, component.object_id.0));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn on_end_object(&mut self) -> Result<()> {
+        self.current_object = None;
+        Ok(())
+    }
+
+    fn on_start_mesh(&mut self, id: ResourceId) -> Result<()> {
+        self.current_mesh = Some(CurrentMesh {
+            digest: MeshDigest {
+                object_id: id.0,
+                hash: FNV_OFFSET,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        Ok(())
+    }
+
+    fn on_vertex(&mut self, x: f32, y: f32, z: f32) -> Result<()> {
+        if let Some(mesh) = self.current_mesh.as_mut() {
+            let d = &mut mesh.digest;
+            d.vertex_count += 1;
+            for c in [x, y, z] {
+                d.hash = fnv1a(d.hash, &c.to_le_bytes());
+            }
+        }
+        Ok(())
+    }
+
+    fn on_triangle(&mut self, v1: u32, v2: u32, v3: u32) -> Result<()> {
+        if let Some(mesh) = self.current_mesh.as_mut() {
+            let d = &mut mesh.digest;
+            // Vertices always precede triangles, so the final vertex count is known here.
+            if [v1, v2, v3].iter().any(|&v| u64::from(v) >= d.vertex_count) {
+                mesh.index_errors += 1;
+            } else if v1 == v2 || v2 == v3 || v1 == v3 {
+                mesh.degenerate += 1;
+            }
+            for v in [v1, v2, v3] {
+                d.hash = fnv1a(d.hash, &v.to_le_bytes());
+            }
+            d.triangle_count += 1;
+        }
+        Ok(())
+    }
+
+    fn on_beam(&mut self, beam: &Beam) -> Result<()> {
+        self.summary.beam_count += 1;
+        if let Some(mesh) = self.current_mesh.as_mut() {
+            let count = mesh.digest.vertex_count;
+            if u64::from(beam.v1) >= count || u64::from(beam.v2) >= count {
+                mesh.beam_vertex_errors += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn on_end_mesh(&mut self) -> Result<()> {
+        let Some(mesh) = self.current_mesh.take() else {
+            return Ok(());
+        };
+        let id = mesh.digest.object_id;
+        if self.level >= ValidationLevel::Standard {
+            if mesh.index_errors > 0 {
+                self.report.add_error(
+                    3001,
+                    format!(
+                        "{} triangle(s) in Object {} reference out-of-bounds vertices",
+                        mesh.index_errors, id
+                    ),
+                );
+            }
+            if mesh.beam_vertex_errors > 0 {
+                self.report.add_error(
+                    3003,
+                    format!(
+                        "{} beam(s) in Object {} reference out-of-bounds vertices",
+                        mesh.beam_vertex_errors, id
+                    ),
+                );
+            }
+        }
+        if self.level >= ValidationLevel::Paranoid && mesh.degenerate > 0 {
+            self.report.add_error(
+                4001,
+                format!(
+                    "{} triangle(s) in Object {} are degenerate (duplicate vertices)",
+                    mesh.degenerate, id
+                ),
+            );
+        }
+        self.summary.vertex_count += mesh.digest.vertex_count;
+        self.summary.triangle_count += mesh.digest.triangle_count;
+        self.summary.meshes.push(mesh.digest);
+        Ok(())
+    }
+
+    fn on_build_item(&mut self, item: &BuildItem) -> Result<()> {
+        self.summary.build_item_count += 1;
+        match &item.path {
+            Some(path) => self.summary.part_references.push(PartReference {
+                path: normalize_part_name(path),
+                object_id: item.object_id.0,
+                owner: None,
+            }),
+            None => self.build_refs.push(item.object_id.0),
+        }
+        Ok(())
+    }
+}
+
+/// Validates a model XML stream without building an in-memory [`Model`](crate::model::Model).
+///
+/// Memory use is proportional to the number of resources, not to mesh size. Parse errors
+/// (malformed XML, invalid attributes) are returned as `Err`; spec findings are collected
+/// in the returned report.
+///
+/// # Examples
+///
+/// ```
+/// use lib3mf_core::validation::{ValidationLevel, streaming::validate_streaming};
+///
+/// let xml = r#"<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+///   <resources>
+///     <object id="1"><mesh>
+///       <vertices><vertex x="0" y="0" z="0"/><vertex x="1" y="0" z="0"/><vertex x="0" y="1" z="0"/></vertices>
+///       <triangles><triangle v1="0" v2="1" v3="5"/></triangles>
+///     </mesh></object>
+///   </resources>
+///   <build><item objectid="2"/></build>
+/// </model>"#;
+///
+/// let result = validate_streaming(xml.as_bytes(), ValidationLevel::Standard).unwrap();
+/// assert_eq!(result.summary.triangle_count, 1);
+/// let codes: Vec<u32> = result.report.items.iter().map(|i| i.code).collect();
+/// assert!(codes.contains(&3001)); // out-of-bounds vertex index
+/// assert!(codes.contains(&3002)); // build item references a missing object
+/// ```
+pub fn validate_streaming<R: BufRead>(
+    reader: R,
+    level: ValidationLevel,
+) -> Result<StreamingValidation> {
+    let mut validator = StreamingValidator::new(level);
+    parse_model_streaming(reader, &mut validator)?;
+    Ok(validator.finish())
+}
+
+/// Validates the model part `root` of a package and every model part it references,
+/// streaming each part.
+///
+/// Parts are found by following the `path` attributes of build items and components, and
+/// each is read once however often it is referenced. Findings from parts other than `root`
+/// carry the part name as their context. The summary adds up the counts of all parts; its
+/// unit is the unit of `root`. References to missing parts or to objects a part does not
+/// define are reported with the codes of the same checks within a part (2003 for
+/// components, 3002 and 3010 for build items).
+///
+/// # Errors
+///
+/// Returns an error if `root` is not in the package or a part cannot be parsed.
+pub fn validate_streaming_package<R: Read + Seek>(
+    archiver: &mut ZipArchiver<R>,
+    root: &str,
+    level: ValidationLevel,
+) -> Result<StreamingValidation> {
+    let root = normalize_part_name(root);
+    let mut result = StreamingValidation::default();
+    // Object types per part, keyed on the lowercased part name; `None` for missing parts.
+    let mut parts: HashMap<String, Option<HashMap<u32, ObjectType>>> = HashMap::new();
+    let mut references: Vec<(String, PartReference)> = Vec::new();
+    let mut queue = VecDeque::from([root.clone()]);
+
+    while let Some(part) = queue.pop_front() {
+        let key = part.to_ascii_lowercase();
+        if parts.contains_key(&key) {
+            continue;
+        }
+        let mut validator = StreamingValidator::new(level);
+        match archiver.entry_reader(&part) {
+            Ok(entry) => parse_model_streaming(BufReader::new(entry), &mut validator)?,
+            Err(e) if part == root => return Err(e),
+            Err(_) => {
+                parts.insert(key, None);
+                continue;
+            }
+        }
+        parts.insert(key, Some(validator.object_types.clone()));
+
+        let found = validator.finish();
+        for reference in &found.summary.part_references {
+            queue.push_back(reference.path.clone());
+            references.push((part.clone(), reference.clone()));
+        }
+        let summary = &mut result.summary;
+        if part == root {
+            summary.unit = found.summary.unit;
+        }
+        summary.resource_count += found.summary.resource_count;
+        summary.object_count += found.summary.object_count;
+        summary.vertex_count += found.summary.vertex_count;
+        summary.triangle_count += found.summary.triangle_count;
+        summary.beam_count += found.summary.beam_count;
+        summary.component_count += found.summary.component_count;
+        summary.build_item_count += found.summary.build_item_count;
+        summary.meshes.extend(found.summary.meshes);
+        summary
+            .part_references
+            .extend(found.summary.part_references);
+        for mut item in found.report.items {
+            if part != root && item.context.is_none() {
+                item.context = Some(part.clone());
+            }
+            result.report.items.push(item);
+        }
+    }
+
+    if level >= ValidationLevel::Standard {
+        for (source, reference) in references {
+            let types = &parts[&reference.path.to_ascii_lowercase()];
+            let target = reference.object_id;
+            let path = &reference.path;
+            let (code, referrer) = match reference.owner {
+                Some(owner) => (2003, format!("Component in Object {}", owner)),
+                None => (3002, "Build item".to_string()),
+            };
+            let finding = match types.as_ref().map(|types| types.get(&target)) {
+                None => Some((
+                    code,
+                    format!(
+                        "{} references object {} in missing part '{}'",
+                        referrer, target, path
+                    ),
+                )),
+                Some(None) => Some((
+                    code,
+                    format!(
+                        "{} references non-existent object {} in part '{}'",
+                        referrer, target, path
+                    ),
+                )),
+                Some(Some(t)) if reference.owner.is_none() && !t.can_be_in_build() => Some((
+                    3010,
+                    format!(
+                        "Build item references object {} in part '{}' with type '{}' which cannot be in build",
+                        target, path, t
+                    ),
+                )),
+                Some(Some(_)) => None,
+            };
+            if let Some((code, message)) = finding {
+                push_error(&mut result.report, code, message, &source, &root);
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Adds an error found in `part`, naming the part as context unless it is the root part.
+fn push_error(report: &mut ValidationReport, code: u32, message: String, part: &str, root: &str) {
+    report.add_error(code, message);
+    if part != root
+        && let Some(item) = report.items.last_mut()
+    {
+        item.context = Some(part.to_string());
+    }
+}
@@ -19,12 +19,12 @@ pub fn write_beam_lattice<W: Write>(
 
     // Write radius attribute when present (default radius for beams)
     if let Some(radius) = lattice.radius {
-        bl_elem = bl_elem.attr("radius", &radius.to_string());
+        bl_elem = bl_elem.float_attr("radius", radius);
     }
 
     bl_elem = bl_elem
-        .attr("minlength", &lattice.min_length.to_string())
-        .attr("precision", &lattice.precision.to_string());
+        .float_attr("minlength", lattice.min_length)
+        .float_attr("precision", lattice.precision);
 
     // clippingmode: omit when None (default) to match spec conventions
     if lattice.clipping_mode != ClippingMode::None {
@@ -40,8 +40,8 @@ pub fn write_beam_lattice<W: Write>(
             .start_element("beam")
             .attr("v1", &beam.v1.to_string())
             .attr("v2", &beam.v2.to_string())
-            .attr("r1", &beam.r1.to_string())
-            .attr("r2", &beam.r2.to_string());
+            .float_attr("r1", beam.r1)
+            .float_attr("r2", beam.r2);
 
         // cap: omit for default (Sphere) to match spec conventions
         if beam.cap_mode != CapMode::Sphere {
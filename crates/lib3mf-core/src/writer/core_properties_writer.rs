@@ -0,0 +1,38 @@
+use crate::error::Result;
+use crate::model::CoreProperties;
+use crate::writer::xml_writer::XmlWriter;
+use std::io::Write;
+
+/// Namespace of the core properties element and its `cp:` properties.
+pub const CORE_PROPERTIES_NS: &str =
+    "http://schemas.openxmlformats.org/package/2006/metadata/core-properties";
+
+/// Serializes [`CoreProperties`] as a core properties part (`docProps/core.xml`).
+///
+/// Only the properties that are set are written. The dates get the `dcterms:W3CDTF` type
+/// the OPC schema requires. The output is read back by
+/// [`parse_core_properties`](crate::parser::core_properties_parser::parse_core_properties).
+pub fn write_core_properties<W: Write>(writer: W, properties: &CoreProperties) -> Result<()> {
+    let mut xml = XmlWriter::new(writer);
+    xml.write_declaration()?;
+
+    xml.start_element("cp:coreProperties")
+        .attr("xmlns:cp", CORE_PROPERTIES_NS)
+        .attr("xmlns:dc", "http://purl.org/dc/elements/1.1/")
+        .attr("xmlns:dcterms", "http://purl.org/dc/terms/")
+        .attr("xmlns:dcmitype", "http://purl.org/dc/dcmitype/")
+        .attr("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance")
+        .write_start()?;
+
+    for (name, value) in properties.entries() {
+        let is_date = matches!(name, "dcterms:created" | "dcterms:modified");
+        xml.start_element(name)
+            .optional_attr("xsi:type", is_date.then_some("dcterms:W3CDTF"))
+            .write_start()?;
+        xml.write_text(value)?;
+        xml.end_element(name)?;
+    }
+
+    xml.end_element("cp:coreProperties")?;
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use crate::error::Result;
 use crate::model::{Channel, Displacement2D, DisplacementMesh, FilterMode, TileStyle};
+use crate::writer::model_writer::WriteOptions;
 use crate::writer::xml_writer::XmlWriter;
 use std::io::Write;
 
@@ -7,17 +8,27 @@ use std::io::Write;
 pub fn write_displacement_mesh<W: Write>(
     writer: &mut XmlWriter<W>,
     mesh: &DisplacementMesh,
+) -> Result<()> {
+    write_displacement_mesh_with_options(writer, mesh, &WriteOptions::default())
+}
+
+/// Like [`write_displacement_mesh`], formatting vertex coordinates as `options` specify.
+pub fn write_displacement_mesh_with_options<W: Write>(
+    writer: &mut XmlWriter<W>,
+    mesh: &DisplacementMesh,
+    options: &WriteOptions,
 ) -> Result<()> {
     writer.start_element("d:displacementmesh").write_start()?;
 
     // Write vertices section
     writer.start_element("d:vertices").write_start()?;
     for v in &mesh.vertices {
+        let v = options.written_vertex(*v)?;
         writer
             .start_element("d:vertex")
-            .attr("x", &v.x.to_string())
-            .attr("y", &v.y.to_string())
-            .attr("z", &v.z.to_string())
+            .float_attr("x", v.x)
+            .float_attr("y", v.y)
+            .float_attr("z", v.z)
             .write_empty()?;
     }
     writer.end_element("d:vertices")?;
@@ -65,9 +76,9 @@ pub fn write_displacement_mesh<W: Write>(
     for n in &mesh.normals {
         writer
             .start_element("d:normvector")
-            .attr("nx", &n.nx.to_string())
-            .attr("ny", &n.ny.to_string())
-            .attr("nz", &n.nz.to_string())
+            .float_attr("nx", n.nx)
+            .float_attr("ny", n.ny)
+            .float_attr("nz", n.nz)
             .write_empty()?;
     }
     writer.end_element("d:normvectors")?;
@@ -78,9 +89,9 @@ pub fn write_displacement_mesh<W: Write>(
         writer.start_element("d:disp2dgroup").write_start()?;
         for g in gradients {
             writer
-                .start_element("d:tex2dcoord")
-                .attr("gu", &g.gu.to_string())
-                .attr("gv", &g.gv.to_string())
+                .start_element("d:gradient")
+                .float_attr("gu", g.gu)
+                .float_attr("gv", g.gv)
                 .write_empty()?;
         }
         writer.end_element("d:disp2dgroup")?;
@@ -117,11 +128,11 @@ pub fn write_displacement_2d<W: Write>(
     }
 
     // Height: always required
-    builder = builder.attr("height", &res.height.to_string());
+    builder = builder.float_attr("height", res.height);
 
     // Offset: write only if non-zero
     if res.offset != 0.0 {
-        builder = builder.attr("offset", &res.offset.to_string());
+        builder = builder.float_attr("offset", res.offset);
     }
 
     builder.write_empty()?;
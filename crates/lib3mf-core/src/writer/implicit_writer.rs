@@ -0,0 +1,89 @@
+use crate::error::Result;
+use crate::model::{FunctionPort, ImplicitFunction, ImplicitNode};
+use crate::writer::xml_writer::XmlWriter;
+use std::io::Write;
+
+/// Writes an `<i:implicitfunction>` element with its inputs, nodes and outputs.
+///
+/// Elements use the `i` prefix declared on the root model element.
+pub fn write_implicit_function<W: Write>(
+    writer: &mut XmlWriter<W>,
+    function: &ImplicitFunction,
+) -> Result<()> {
+    let mut element = writer
+        .start_element("i:implicitfunction")
+        .attr("id", &function.id.0.to_string());
+    if let Some(identifier) = &function.identifier {
+        element = element.attr("identifier", identifier);
+    }
+    if let Some(display_name) = &function.display_name {
+        element = element.attr("displayname", display_name);
+    }
+    element.write_start()?;
+
+    write_ports(writer, "i:in", &function.inputs)?;
+    for node in &function.nodes {
+        write_node(writer, node)?;
+    }
+    write_ports(writer, "i:out", &function.outputs)?;
+
+    writer.end_element("i:implicitfunction")?;
+    Ok(())
+}
+
+/// Writes a node element named after its kind, keeping its attributes in order.
+fn write_node<W: Write>(writer: &mut XmlWriter<W>, node: &ImplicitNode) -> Result<()> {
+    let name = format!("i:{}", node.kind);
+    let mut element = writer
+        .start_element(&name)
+        .attr("identifier", &node.identifier);
+    if let Some(display_name) = &node.display_name {
+        element = element.attr("displayname", display_name);
+    }
+    if let Some(tag) = &node.tag {
+        element = element.attr("tag", tag);
+    }
+    for (key, value) in &node.attributes {
+        element = element.attr(key, value);
+    }
+
+    if node.inputs.is_empty() && node.outputs.is_empty() {
+        element.write_empty()?;
+        return Ok(());
+    }
+    element.write_start()?;
+    write_ports(writer, "i:in", &node.inputs)?;
+    write_ports(writer, "i:out", &node.outputs)?;
+    writer.end_element(&name)?;
+    Ok(())
+}
+
+/// Writes an `<i:in>` or `<i:out>` list; nothing is written for an empty list.
+fn write_ports<W: Write>(
+    writer: &mut XmlWriter<W>,
+    list: &str,
+    ports: &[FunctionPort],
+) -> Result<()> {
+    if ports.is_empty() {
+        return Ok(());
+    }
+    writer.start_element(list).write_start()?;
+    for port in ports {
+        let name = match port.reference {
+            Some(_) => port.port_type.reference_element_name(),
+            None => port.port_type.element_name(),
+        };
+        let mut element = writer
+            .start_element(&format!("i:{}", name))
+            .attr("identifier", &port.identifier);
+        if let Some(display_name) = &port.display_name {
+            element = element.attr("displayname", display_name);
+        }
+        if let Some(reference) = &port.reference {
+            element = element.attr("ref", reference);
+        }
+        element.write_empty()?;
+    }
+    writer.end_element(list)?;
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use crate::error::Result;
+use crate::model::KeyStore;
+use crate::writer::xml_writer::XmlWriter;
+use base64::prelude::*;
+use std::io::Write;
+
+/// Namespace of the Secure Content key store part.
+pub const KEYSTORE_NS: &str =
+    "http://schemas.microsoft.com/3dmanufacturing/secure_content/2019/04/keystore";
+
+/// Serializes a [`KeyStore`] as a standalone `<keystore>` document.
+///
+/// The package writer does not emit key stores, since it writes every part unencrypted;
+/// use this to produce the key store part of a package assembled by the caller.
+///
+/// Wrapped keys are base64-encoded; the output is read back by
+/// [`parse_keystore`](crate::parser::secure_content_parser::parse_keystore).
+pub fn write_keystore<W: Write>(writer: W, store: &KeyStore) -> Result<()> {
+    let mut xml = XmlWriter::new(writer);
+    xml.write_declaration()?;
+
+    xml.start_element("keystore")
+        .attr("xmlns", KEYSTORE_NS)
+        .attr("UUID", &store.uuid.to_string())
+        .write_start()?;
+
+    for consumer in &store.consumers {
+        xml.start_element("consumer")
+            .attr("consumerid", &consumer.id)
+            .optional_attr("keyid", consumer.key_id.as_deref())
+            .optional_attr("keyvalue", consumer.key_value.as_deref())
+            .write_empty()?;
+    }
+
+    for group in &store.resource_data_groups {
+        xml.start_element("resourcedatagroup")
+            .attr("keyuuid", &group.key_uuid.to_string())
+            .write_start()?;
+        for right in &group.access_rights {
+            xml.start_element("accessright")
+                .attr("consumerid", &right.consumer_id)
+                .write_start()?;
+            xml.start_element("wrappedkey")
+                .attr("encryptionalgorithm", &right.algorithm)
+                .write_start()?;
+            xml.write_text(&BASE64_STANDARD.encode(&right.wrapped_key))?;
+            xml.end_element("wrappedkey")?;
+            xml.end_element("accessright")?;
+        }
+        for path in &group.resource_paths {
+            xml.start_element("resourcedata")
+                .attr("path", path)
+                .write_empty()?;
+        }
+        xml.end_element("resourcedatagroup")?;
+    }
+
+    xml.end_element("keystore")?;
+    Ok(())
+}
@@ -1,28 +1,53 @@
 use crate::error::Result;
-use crate::model::mesh::Mesh;
+use crate::model::MeshStorage;
 use crate::writer::beamlattice_writer::write_beam_lattice;
+use crate::writer::model_writer::WriteOptions;
+use crate::writer::triangleset_writer::write_triangle_sets;
+use crate::writer::xml_value::XmlFloat;
 use crate::writer::xml_writer::XmlWriter;
 use std::io::Write;
 
-/// Serializes a mesh (vertices and triangles) to XML inside a `<mesh>` element.
-pub fn write_mesh<W: Write>(writer: &mut XmlWriter<W>, mesh: &Mesh) -> Result<()> {
-    writer.start_element("mesh").write_start()?;
+/// Serializes a mesh (vertices, triangles and triangle sets) to XML inside a `<mesh>` element.
+///
+/// Accepts any [`MeshStorage`], so a [`CompactMesh`](crate::model::CompactMesh) can be
+/// written without first expanding it into a `Mesh`, and a
+/// [`PreciseMesh`](crate::model::PreciseMesh) without rounding its coordinates to `f32`.
+pub fn write_mesh<W: Write, S: MeshStorage>(writer: &mut XmlWriter<W>, mesh: &S) -> Result<()> {
+    write_mesh_with_options(writer, mesh, &WriteOptions::default())
+}
+
+/// Like [`write_mesh`], formatting vertex coordinates as `options` specify.
+pub fn write_mesh_with_options<W: Write, S: MeshStorage>(
+    writer: &mut XmlWriter<W>,
+    mesh: &S,
+    options: &WriteOptions,
+) -> Result<()> {
+    let mut element = writer.start_element("mesh");
+    if let Some(volume_id) = mesh.volume_id() {
+        element = element.attr("vol:volumeid", &volume_id.0.to_string());
+    }
+    element.write_start()?;
 
     // Vertices
     writer.start_element("vertices").write_start()?;
-    for v in &mesh.vertices {
-        writer
-            .start_element("vertex")
-            .attr("x", &v.x.to_string())
-            .attr("y", &v.y.to_string())
-            .attr("z", &v.z.to_string())
-            .write_empty()?;
+    if mesh.has_f64_coordinates() {
+        for i in 0..mesh.vertex_count() {
+            let v = mesh.vertex_f64(i);
+            let [x, y, z] = [v.x, v.y, v.z].map(|c| options.written_coordinate_f64(c));
+            write_vertex(writer, [x?, y?, z?])?;
+        }
+    } else {
+        for v in mesh.iter_vertices() {
+            let v = options.written_vertex(v)?;
+            write_vertex(writer, [v.x, v.y, v.z])?;
+        }
     }
     writer.end_element("vertices")?;
 
     // Triangles
     writer.start_element("triangles").write_start()?;
-    for t in &mesh.triangles {
+    let attributes = mesh.triangle_attributes();
+    for (i, t) in mesh.iter_triangles().enumerate() {
         let mut builder = writer
             .start_element("triangle")
             .attr("v1", &t.v1.to_string())
@@ -41,16 +66,32 @@ pub fn write_mesh<W: Write>(writer: &mut XmlWriter<W>, mesh: &Mesh) -> Result<()
         if let Some(pid) = t.pid {
             builder = builder.attr("pid", &pid.to_string());
         }
+        if !attributes.is_empty() {
+            for (name, value) in attributes.of_triangle(i as u32) {
+                builder = builder.attr(name, value);
+            }
+        }
 
         builder.write_empty()?;
     }
     writer.end_element("triangles")?;
 
+    write_triangle_sets(writer, mesh.triangle_sets())?;
+
     // Write beam lattice if present
-    if let Some(lattice) = &mesh.beam_lattice {
+    if let Some(lattice) = mesh.beam_lattice() {
         write_beam_lattice(writer, lattice)?;
     }
 
     writer.end_element("mesh")?;
     Ok(())
 }
+
+fn write_vertex<W: Write, F: XmlFloat>(writer: &mut XmlWriter<W>, [x, y, z]: [F; 3]) -> Result<()> {
+    writer
+        .start_element("vertex")
+        .float_attr("x", x)
+        .float_attr("y", y)
+        .float_attr("z", z)
+        .write_empty()
+}
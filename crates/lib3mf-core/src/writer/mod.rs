@@ -47,13 +47,17 @@
 //! - [`opc_writer`]: Writes OPC metadata (`_rels/.rels`, `[Content_Types].xml`)
 //! - [`package_writer`]: Orchestrates writing of complete 3MF package
 //! - [`xml_writer`]: Low-level XML writing utilities
+//! - [`xml_value`]: Escaping of strings and round-trip formatting of numbers, applied to
+//!   every value the writers emit
 //!
 //! ### Extension Writers
 //!
 //! - [`beamlattice_writer`]: Writes Beam Lattice Extension data
+//! - [`triangleset_writer`]: Writes Triangle Sets Extension data
 //! - [`displacement_writer`]: Writes Displacement Extension data
 //! - [`slice_writer`]: Writes Slice Extension data
 //! - [`volumetric_writer`]: Writes Volumetric Extension data
+//! - [`implicit_writer`]: Writes Implicit Extension functions
 //! - **Boolean Operations**: Fully supported in `model_writer`
 //!
 //! ## Known Limitations
@@ -97,8 +101,15 @@
 
 /// Beam lattice extension writer.
 pub mod beamlattice_writer;
+/// OPC core properties writer (`docProps/core.xml`).
+pub mod core_properties_writer;
 /// Displacement extension writer.
 pub mod displacement_writer;
+/// Implicit extension function writer.
+pub mod implicit_writer;
+#[cfg(feature = "crypto")]
+/// Secure Content key store writer (requires `crypto` feature).
+pub mod keystore_writer;
 /// Mesh geometry writer.
 pub mod mesh_writer;
 /// `Model::write()` convenience method implementation.
@@ -111,7 +122,13 @@ pub mod opc_writer;
 pub mod package_writer;
 /// Slice extension writer.
 pub mod slice_writer;
+/// Triangle sets extension writer.
+pub mod triangleset_writer;
 /// Volumetric extension writer.
 pub mod volumetric_writer;
+/// Escaping and number formatting of XML attribute values and text.
+pub mod xml_value;
 /// Low-level XML writer primitives.
 pub mod xml_writer;
+
+pub use model_writer::{FloatPrecision, VertexQuantization, WriteOptions};
@@ -1,5 +1,6 @@
 use crate::error::Result;
 use crate::model::{Model, Package};
+use crate::writer::model_writer::WriteOptions;
 use std::io::{Seek, Write};
 
 impl Model {
@@ -9,4 +10,16 @@ impl Model {
         let package = Package::new(self.clone());
         package_writer.write(&package)
     }
+
+    /// Like [`write`](Self::write), formatting vertex coordinates as `options` specify.
+    pub fn write_with_options<W: Write + Seek>(
+        &self,
+        writer: W,
+        options: &WriteOptions,
+    ) -> Result<()> {
+        let package_writer = crate::writer::package_writer::PackageWriter::new(writer)
+            .with_write_options(options.clone());
+        let package = Package::new(self.clone());
+        package_writer.write(&package)
+    }
 }
@@ -1,46 +1,219 @@
-use crate::error::Result;
-use crate::model::{BooleanOperationType, Geometry, Model, Unit};
-use crate::writer::displacement_writer::{write_displacement_2d, write_displacement_mesh};
-use crate::writer::mesh_writer::write_mesh;
+use crate::error::{Lib3mfError, Result};
+use crate::model::{
+    BlendMethod, BooleanOperationType, Extension, FilterMode, Function, Geometry, Metadata, Model,
+    TileStyle, Vertex,
+};
+use crate::writer::displacement_writer::{
+    write_displacement_2d, write_displacement_mesh_with_options,
+};
+use crate::writer::implicit_writer::write_implicit_function;
+use crate::writer::mesh_writer::write_mesh_with_options;
 use crate::writer::slice_writer;
 use crate::writer::volumetric_writer;
+use crate::writer::xml_value::format_floats;
 use crate::writer::xml_writer::XmlWriter;
+use std::fmt::Display;
 use std::io::Write;
 
 use std::collections::HashMap;
 
-/// Formats a transformation matrix into the 3MF format (12 space-separated values in column-major order)
-fn format_transform_matrix(mat: &glam::Mat4) -> String {
-    format!(
-        "{} {} {} {} {} {} {} {} {} {} {} {}",
-        mat.x_axis.x,
-        mat.x_axis.y,
-        mat.x_axis.z,
-        mat.y_axis.x,
-        mat.y_axis.y,
-        mat.y_axis.z,
-        mat.z_axis.x,
-        mat.z_axis.y,
-        mat.z_axis.z,
-        mat.w_axis.x,
-        mat.w_axis.y,
-        mat.w_axis.z
-    )
+/// How the writer formats vertex coordinates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FloatPrecision {
+    /// The shortest decimal that reads back as the same value, so nothing is lost.
+    #[default]
+    Shortest,
+    /// Rounded to at most this many decimal places, with trailing zeros dropped.
+    Decimals(u8),
+}
+
+/// Snapping of vertex coordinates to a grid before they are written.
+///
+/// Scan data carries noise far below any printer's resolution in its low digits; snapping
+/// it to a grid of, say, a micron shortens every coordinate written.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VertexQuantization {
+    /// Grid spacing in model units; must be positive.
+    pub step: f64,
+    /// The furthest a written coordinate may be from the original, in model units,
+    /// counting [`FloatPrecision`] rounding and the precision of `f32`. Writing fails
+    /// beyond it.
+    pub tolerance: f64,
+}
+
+/// Options for [`Model::write_xml_with_options`] and
+/// [`PackageWriter::with_write_options`](crate::writer::package_writer::PackageWriter::with_write_options).
+///
+/// They apply to the vertex coordinates of meshes and displacement meshes. Transforms,
+/// property values and other numbers are always written exactly.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WriteOptions {
+    /// How vertex coordinates are formatted. Default: [`FloatPrecision::Shortest`].
+    pub float_precision: FloatPrecision,
+    /// Grid vertex coordinates are snapped to, if any.
+    pub vertex_quantization: Option<VertexQuantization>,
+}
+
+impl VertexQuantization {
+    fn checked_step(&self) -> Result<f64> {
+        if !(self.step > 0.0 && self.step.is_finite()) {
+            return Err(Lib3mfError::Validation(format!(
+                "Vertex quantization step must be positive, not {}",
+                self.step
+            )));
+        }
+        Ok(self.step)
+    }
+
+    fn check_error(&self, value: impl Display, written: impl Display, error: f64) -> Result<()> {
+        if error > self.tolerance {
+            return Err(Lib3mfError::Validation(format!(
+                "Writing vertex coordinate {} as {} is off by {}, more than the tolerance of {}",
+                value, written, error, self.tolerance
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl WriteOptions {
+    /// Returns true if vertex coordinates are written exactly.
+    pub fn is_lossless(&self) -> bool {
+        self.float_precision == FloatPrecision::Shortest && self.vertex_quantization.is_none()
+    }
+
+    /// Returns the value a vertex coordinate reads back as once written.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Lib3mfError::Validation`] if the quantization step isn't positive, or the
+    /// coordinate moves further than the quantization tolerance.
+    pub fn written_coordinate(&self, value: f32) -> Result<f32> {
+        if self.is_lossless() {
+            return Ok(value);
+        }
+        let mut written = value;
+        if let Some(quantization) = &self.vertex_quantization {
+            written = ((f64::from(value) / quantization.checked_step()?).round()
+                * quantization.step) as f32;
+        }
+        if let FloatPrecision::Decimals(places) = self.float_precision {
+            written = format!("{:.*}", usize::from(places), written)
+                .parse()
+                .unwrap_or(written);
+        }
+        if written == 0.0 {
+            // Drops the sign of a coordinate rounded to zero
+            written = 0.0;
+        }
+        if let Some(quantization) = &self.vertex_quantization {
+            let error = (f64::from(written) - f64::from(value)).abs();
+            quantization.check_error(value, written, error)?;
+        }
+        Ok(written)
+    }
+
+    /// Like [`written_coordinate`](Self::written_coordinate), for coordinates stored as
+    /// `f64` (see [`PreciseMesh`](crate::model::PreciseMesh)).
+    ///
+    /// # Errors
+    ///
+    /// As for [`written_coordinate`](Self::written_coordinate).
+    pub fn written_coordinate_f64(&self, value: f64) -> Result<f64> {
+        if self.is_lossless() {
+            return Ok(value);
+        }
+        let mut written = value;
+        if let Some(quantization) = &self.vertex_quantization {
+            written = (value / quantization.checked_step()?).round() * quantization.step;
+        }
+        if let FloatPrecision::Decimals(places) = self.float_precision {
+            written = format!("{:.*}", usize::from(places), written)
+                .parse()
+                .unwrap_or(written);
+        }
+        if written == 0.0 {
+            // Drops the sign of a coordinate rounded to zero
+            written = 0.0;
+        }
+        if let Some(quantization) = &self.vertex_quantization {
+            quantization.check_error(value, written, (written - value).abs())?;
+        }
+        Ok(written)
+    }
+
+    /// Returns the vertex as it reads back once written; see
+    /// [`written_coordinate`](Self::written_coordinate).
+    pub fn written_vertex(&self, vertex: Vertex) -> Result<Vertex> {
+        Ok(Vertex {
+            x: self.written_coordinate(vertex.x)?,
+            y: self.written_coordinate(vertex.y)?,
+            z: self.written_coordinate(vertex.z)?,
+        })
+    }
 }
 
 impl Model {
     /// Serializes the model to XML, writing the `<model>` document to the given writer.
+    ///
+    /// `thumbnail_paths` maps object `thumbnail` values to the part names written in their
+    /// place; values without an entry are written unchanged.
     pub fn write_xml<W: Write>(
         &self,
         writer: W,
-        thumbnail_relationships: Option<&HashMap<String, String>>,
+        thumbnail_paths: Option<&HashMap<String, String>>,
+    ) -> Result<()> {
+        self.write_xml_with_options(writer, thumbnail_paths, &WriteOptions::default())
+    }
+
+    /// Like [`write_xml`](Self::write_xml), formatting vertex coordinates as `options`
+    /// specify.
+    ///
+    /// ```
+    /// use lib3mf_core::model::{Geometry, Mesh, Model, Object, ObjectType, ResourceId};
+    /// use lib3mf_core::writer::{FloatPrecision, WriteOptions};
+    ///
+    /// let mut mesh = Mesh::new();
+    /// mesh.add_vertex(1.23456, 0.5, 2.0);
+    /// let mut model = Model::default();
+    /// model.resources.add_object(Object {
+    ///     id: ResourceId(1),
+    ///     object_type: ObjectType::Model,
+    ///     name: None,
+    ///     part_number: None,
+    ///     uuid: None,
+    ///     pid: None,
+    ///     pindex: None,
+    ///     thumbnail: None,
+    ///     geometry: Geometry::Mesh(mesh),
+    /// })?;
+    ///
+    /// let options = WriteOptions {
+    ///     float_precision: FloatPrecision::Decimals(2),
+    ///     ..Default::default()
+    /// };
+    /// let mut xml = Vec::new();
+    /// model.write_xml_with_options(&mut xml, None, &options)?;
+    /// assert!(String::from_utf8(xml).unwrap().contains(r#"x="1.23" y="0.5" z="2""#));
+    /// # Ok::<(), lib3mf_core::error::Lib3mfError>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails as [`WriteOptions::written_coordinate`] does, besides the errors of
+    /// [`write_xml`](Self::write_xml).
+    pub fn write_xml_with_options<W: Write>(
+        &self,
+        writer: W,
+        thumbnail_paths: Option<&HashMap<String, String>>,
+        options: &WriteOptions,
     ) -> Result<()> {
         let mut xml = XmlWriter::new(writer);
         xml.write_declaration()?;
 
         let mut root = xml
             .start_element("model")
-            .attr("unit", self.unit_str())
+            .attr("unit", self.unit.as_str())
             .attr("xml:lang", self.language.as_deref().unwrap_or("en-US"))
             .attr(
                 "xmlns",
@@ -49,11 +222,17 @@ impl Model {
             .attr(
                 "xmlns:m",
                 "http://schemas.microsoft.com/3dmanufacturing/material/2015/02",
-            )
-            .attr(
-                "xmlns:p",
-                "http://schemas.microsoft.com/3dmanufacturing/production/2015/06",
-            )
+            );
+        // Declaring the Production namespace makes UUIDs mandatory, so it is only declared
+        // for models that use the extension
+        if self.uses_production_extension() {
+            let namespace = self
+                .production_namespace
+                .as_deref()
+                .unwrap_or(Extension::Production.namespace());
+            root = root.attr("xmlns:p", namespace);
+        }
+        root = root
             .attr(
                 "xmlns:b",
                 "http://schemas.3mf.io/3dmanufacturing/booleanoperations/2023/07",
@@ -62,6 +241,10 @@ impl Model {
                 "xmlns:d",
                 "http://schemas.microsoft.com/3dmanufacturing/displacement/2024/01",
             )
+            .attr(
+                "xmlns:t",
+                "http://schemas.microsoft.com/3dmanufacturing/trianglesets/2021/07",
+            )
             .attr(
                 "xmlns:bl",
                 "http://schemas.microsoft.com/3dmanufacturing/beamlattice/2017/02",
@@ -73,6 +256,14 @@ impl Model {
             .attr(
                 "xmlns:v",
                 "http://schemas.microsoft.com/3dmanufacturing/volumetric/2018/11",
+            )
+            .attr(
+                "xmlns:vol",
+                "http://schemas.3mf.io/3dmanufacturing/volumetric/2022/01",
+            )
+            .attr(
+                "xmlns:i",
+                "http://schemas.3mf.io/3dmanufacturing/implicit/2023/12",
             );
 
         // Emit extra namespaces (e.g., BambuStudio vendor namespace)
@@ -87,32 +278,44 @@ impl Model {
             root = root.attr(attr_name, uri.as_str());
         }
 
-        // Add typical namespaces if needed (e.g. production, slice) - strictly core for now
+        // Require the extensions the content depends on, and keep unsupported requirements
+        // whose namespace is still declared
+        let mut required: Vec<&str> = self
+            .extensions_to_require()
+            .into_iter()
+            .filter_map(extension_prefix)
+            .collect();
+        for uri in &self.required_extensions {
+            if Extension::from_namespace(uri).is_none()
+                && let Some((prefix, _)) = extra_ns_owned.iter().find(|(_, v)| v == uri)
+            {
+                required.push(prefix.trim_start_matches("xmlns:"));
+            }
+        }
+        let required = required.join(" ");
+        if !required.is_empty() {
+            root = root.attr("requiredextensions", &required);
+        }
+
         root.write_start()?;
 
         // Metadata
-        for (key, value) in &self.metadata {
-            xml.start_element("metadata")
-                .attr("name", key)
-                .write_start()?;
-            xml.write_text(value)?;
-            xml.end_element("metadata")?;
-        }
+        write_metadata(&mut xml, &self.metadata)?;
 
         // Resources
         xml.start_element("resources").write_start()?;
 
         // Write material resources first (colorgroups, basematerials, textures, etc.)
         for color_group in self.resources.iter_color_groups() {
-            xml.start_element("colorgroup")
+            xml.start_element("m:colorgroup")
                 .attr("id", &color_group.id.0.to_string())
                 .write_start()?;
             for color in &color_group.colors {
-                xml.start_element("color")
+                xml.start_element("m:color")
                     .attr("color", &color.to_hex())
                     .write_empty()?;
             }
-            xml.end_element("colorgroup")?;
+            xml.end_element("m:colorgroup")?;
         }
 
         for base_materials in self.resources.iter_base_materials() {
@@ -128,6 +331,23 @@ impl Model {
             xml.end_element("m:basematerials")?;
         }
 
+        for texture in self.resources.iter_texture_2d() {
+            let mut element = xml
+                .start_element("m:texture2d")
+                .attr("id", &texture.id.0.to_string())
+                .attr("path", &texture.path)
+                .attr("contenttype", &texture.contenttype);
+            if texture.tile_style_u != TileStyle::Wrap {
+                element = element.attr("tilestyleu", tile_style_to_str(texture.tile_style_u));
+            }
+            if texture.tile_style_v != TileStyle::Wrap {
+                element = element.attr("tilestylev", tile_style_to_str(texture.tile_style_v));
+            }
+            element
+                .optional_attr("filter", texture.filter.map(filter_mode_to_str))
+                .write_empty()?;
+        }
+
         for texture_group in self.resources.iter_textures() {
             xml.start_element("m:texture2dgroup")
                 .attr("id", &texture_group.id.0.to_string())
@@ -135,8 +355,8 @@ impl Model {
                 .write_start()?;
             for coord in &texture_group.coords {
                 xml.start_element("m:tex2coord")
-                    .attr("u", &coord.u.to_string())
-                    .attr("v", &coord.v.to_string())
+                    .float_attr("u", coord.u)
+                    .float_attr("v", coord.v)
                     .write_empty()?;
             }
             xml.end_element("m:texture2dgroup")?;
@@ -146,25 +366,27 @@ impl Model {
             xml.start_element("m:compositematerials")
                 .attr("id", &composite.id.0.to_string())
                 .attr("matid", &composite.base_material_id.0.to_string())
+                .attr(
+                    "matindices",
+                    &composite
+                        .indices
+                        .iter()
+                        .map(|idx| idx.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                )
                 .write_start()?;
             for comp in &composite.composites {
                 xml.start_element("m:composite")
-                    .attr(
-                        "values",
-                        &comp
-                            .values
-                            .iter()
-                            .map(|v| v.to_string())
-                            .collect::<Vec<_>>()
-                            .join(" "),
-                    )
+                    .attr("values", &format_floats(comp.values.iter().copied())?)
                     .write_empty()?;
             }
             xml.end_element("m:compositematerials")?;
         }
 
         for multi_props in self.resources.iter_multi_properties() {
-            xml.start_element("m:multiproperties")
+            let mut element = xml
+                .start_element("m:multiproperties")
                 .attr("id", &multi_props.id.0.to_string())
                 .attr(
                     "pids",
@@ -174,8 +396,17 @@ impl Model {
                         .map(|id| id.0.to_string())
                         .collect::<Vec<_>>()
                         .join(" "),
-                )
-                .write_start()?;
+                );
+            let blend_methods = multi_props
+                .blend_methods
+                .iter()
+                .map(|&method| blend_method_to_str(method))
+                .collect::<Vec<_>>()
+                .join(" ");
+            if !blend_methods.is_empty() {
+                element = element.attr("blendmethods", &blend_methods);
+            }
+            element.write_start()?;
             for multi in &multi_props.multis {
                 xml.start_element("m:multi")
                     .attr(
@@ -208,6 +439,22 @@ impl Model {
             volumetric_writer::write_volumetric_stack(&mut xml, stack)?;
         }
 
+        // Write 3D images, then the functions sampling them, then the volume data using them
+        for image in self.resources.iter_images_3d() {
+            volumetric_writer::write_image_3d(&mut xml, image)?;
+        }
+        for function in self.resources.iter_functions() {
+            match function {
+                Function::Implicit(f) => write_implicit_function(&mut xml, f)?,
+                Function::FromImage3D(f) => {
+                    volumetric_writer::write_function_from_image_3d(&mut xml, f)?
+                }
+            }
+        }
+        for data in self.resources.iter_volume_data() {
+            volumetric_writer::write_volume_data(&mut xml, data)?;
+        }
+
         // Write objects
         for obj in self.resources.iter_objects() {
             match &obj.geometry {
@@ -219,8 +466,7 @@ impl Model {
                         .attr("objectid", &bs.base_object_id.0.to_string());
 
                     if bs.base_transform != glam::Mat4::IDENTITY {
-                        bool_elem = bool_elem
-                            .attr("transform", &format_transform_matrix(&bs.base_transform));
+                        bool_elem = bool_elem.transform_attr("transform", &bs.base_transform);
                     }
                     if let Some(path) = &bs.base_path {
                         bool_elem = bool_elem.attr("p:path", path);
@@ -242,8 +488,7 @@ impl Model {
                             .attr("operation", op_type_str);
 
                         if op.transform != glam::Mat4::IDENTITY {
-                            op_elem =
-                                op_elem.attr("transform", &format_transform_matrix(&op.transform));
+                            op_elem = op_elem.transform_attr("transform", &op.transform);
                         }
                         if let Some(path) = &op.path {
                             op_elem = op_elem.attr("p:path", path);
@@ -285,24 +530,20 @@ impl Model {
                     if let Some(name) = obj.name.as_ref() {
                         obj_elem = obj_elem.attr("name", name);
                     }
-                    if let Some(thumb_path) = obj.thumbnail.as_ref()
-                        && let Some(rels) = thumbnail_relationships
-                    {
-                        let lookup_key = if thumb_path.starts_with('/') {
-                            thumb_path.clone()
-                        } else {
-                            format!("/{}", thumb_path)
-                        };
-
-                        if let Some(rel_id) = rels.get(&lookup_key) {
-                            obj_elem = obj_elem.attr("thumbnail", rel_id);
-                        }
+                    if let Some(reference) = obj.thumbnail.as_ref() {
+                        let path = thumbnail_paths
+                            .and_then(|paths| paths.get(reference))
+                            .unwrap_or(reference);
+                        obj_elem = obj_elem.attr("thumbnail", path);
                     }
 
                     obj_elem.write_start()?;
 
                     match &obj.geometry {
-                        Geometry::Mesh(mesh) => write_mesh(&mut xml, mesh)?,
+                        Geometry::Mesh(mesh) => write_mesh_with_options(&mut xml, mesh, options)?,
+                        Geometry::PreciseMesh(mesh) => {
+                            write_mesh_with_options(&mut xml, mesh, options)?
+                        }
                         Geometry::Components(comps) => {
                             xml.start_element("components").write_start()?;
                             for c in &comps.components {
@@ -318,8 +559,7 @@ impl Model {
                                 }
 
                                 if c.transform != glam::Mat4::IDENTITY {
-                                    comp = comp
-                                        .attr("transform", &format_transform_matrix(&c.transform));
+                                    comp = comp.transform_attr("transform", &c.transform);
                                 }
                                 comp.write_empty()?;
                             }
@@ -335,7 +575,16 @@ impl Model {
                             unreachable!("BooleanShape handled in outer match")
                         }
                         Geometry::DisplacementMesh(mesh) => {
-                            write_displacement_mesh(&mut xml, mesh)?;
+                            write_displacement_mesh_with_options(&mut xml, mesh, options)?;
+                        }
+                        Geometry::LevelSet(level_set) => {
+                            volumetric_writer::write_level_set(&mut xml, level_set)?;
+                        }
+                        Geometry::LazyMesh(_) => {
+                            return Err(Lib3mfError::InvalidStructure(format!(
+                                "Object {} has a lazy mesh; call Model::materialize_meshes before writing",
+                                obj.id.0
+                            )));
                         }
                     }
 
@@ -346,15 +595,25 @@ impl Model {
         xml.end_element("resources")?;
 
         // Build
-        xml.start_element("build").write_start()?;
+        let mut build = xml.start_element("build");
+        if let Some(uuid) = self.build.uuid.as_ref() {
+            build = build.attr("p:UUID", &uuid.to_string());
+        }
+        build.write_start()?;
         for item in &self.build.items {
             let mut build_item = xml
                 .start_element("item")
                 .attr("objectid", &item.object_id.0.to_string());
 
+            if let Some(uuid) = item.uuid.as_ref() {
+                build_item = build_item.attr("p:UUID", &uuid.to_string());
+            }
+            if let Some(path) = item.path.as_deref() {
+                build_item = build_item.attr("p:path", path);
+            }
+
             if item.transform != glam::Mat4::IDENTITY {
-                build_item =
-                    build_item.attr("transform", &format_transform_matrix(&item.transform));
+                build_item = build_item.transform_attr("transform", &item.transform);
             }
             if let Some(ref pn) = item.part_number {
                 build_item = build_item.attr("partnumber", pn);
@@ -362,22 +621,84 @@ impl Model {
             if let Some(printable) = item.printable {
                 build_item = build_item.attr("printable", if printable { "1" } else { "0" });
             }
-            build_item.write_empty()?;
+            if item.metadata.is_empty() {
+                build_item.write_empty()?;
+            } else {
+                build_item.write_start()?;
+                xml.start_element("metadatagroup").write_start()?;
+                write_metadata(&mut xml, &item.metadata)?;
+                xml.end_element("metadatagroup")?;
+                xml.end_element("item")?;
+            }
         }
         xml.end_element("build")?;
 
         xml.end_element("model")?;
         Ok(())
     }
+}
+
+/// Returns the prefix the writer declares for an extension's namespace.
+fn extension_prefix(extension: Extension) -> Option<&'static str> {
+    match extension {
+        Extension::Materials => Some("m"),
+        Extension::Production => Some("p"),
+        Extension::BeamLattice => Some("bl"),
+        Extension::TriangleSets => Some("t"),
+        Extension::Slice => Some("s"),
+        Extension::Volumetric => Some("vol"),
+        Extension::Implicit => Some("i"),
+        Extension::BooleanOperations => Some("b"),
+        Extension::Displacement => Some("d"),
+        // Secure Content is not declared in model parts
+        Extension::SecureContent => None,
+    }
+}
 
-    fn unit_str(&self) -> &'static str {
-        match self.unit {
-            Unit::Micron => "micron",
-            Unit::Millimeter => "millimeter",
-            Unit::Centimeter => "centimeter",
-            Unit::Inch => "inch",
-            Unit::Foot => "foot",
-            Unit::Meter => "meter",
+/// Converts a BlendMethod to its `blendmethods` token.
+///
+/// The Materials Extension has no "no blend" token, so `NoBlend` is written as `mix`.
+fn blend_method_to_str(method: BlendMethod) -> &'static str {
+    match method {
+        BlendMethod::Mix | BlendMethod::NoBlend => "mix",
+        BlendMethod::Multiply => "multiply",
+    }
+}
+
+/// Converts a TileStyle to its `tilestyleu`/`tilestylev` token.
+fn tile_style_to_str(style: TileStyle) -> &'static str {
+    match style {
+        TileStyle::Wrap => "wrap",
+        TileStyle::Mirror => "mirror",
+        TileStyle::Clamp => "clamp",
+        TileStyle::None => "none",
+    }
+}
+
+/// Converts a FilterMode to its `filter` token.
+fn filter_mode_to_str(mode: FilterMode) -> &'static str {
+    match mode {
+        FilterMode::Linear => "linear",
+        FilterMode::Nearest => "nearest",
+    }
+}
+
+/// Writes one `<metadata>` element per entry.
+fn write_metadata<W: Write>(xml: &mut XmlWriter<W>, metadata: &Metadata) -> Result<()> {
+    for entry in metadata {
+        let mut element = xml.start_element("metadata").attr("name", &entry.name);
+        if let Some(value_type) = entry.value_type.as_deref() {
+            element = element.attr("type", value_type);
+        }
+        if entry.preserve {
+            element = element.attr("preserve", "1");
+        }
+        if let Some(lang) = entry.lang.as_deref() {
+            element = element.attr("xml:lang", lang);
         }
+        element.write_start()?;
+        xml.write_text(&entry.value)?;
+        xml.end_element("metadata")?;
     }
+    Ok(())
 }
@@ -1,9 +1,35 @@
+use crate::archive::opc::Relationship;
 use crate::error::Result;
+use crate::model::core_properties::{
+    CORE_PROPERTIES_CONTENT_TYPE, CORE_PROPERTIES_PATH, CORE_PROPERTIES_REL_TYPE,
+};
 use crate::writer::xml_writer::XmlWriter;
+use std::collections::BTreeSet;
 use std::io::Write;
 
+pub use crate::archive::opc::THUMBNAIL_REL_TYPE;
+
 /// Writes the `[Content_Types].xml` file for a 3MF package.
-pub fn write_content_types<W: Write>(writer: W) -> Result<()> {
+///
+/// `part_names` are the other parts in the package; a `Default` entry is emitted for each
+/// JPEG or JSON extension among them so JPEG thumbnails and textures, and the
+/// [stats cache](crate::model::stats_cache), have a content type. An `Override` entry is
+/// emitted for a [core properties](crate::model::core_properties) part at
+/// [`CORE_PROPERTIES_PATH`].
+pub fn write_content_types<'a, W: Write>(
+    writer: W,
+    part_names: impl IntoIterator<Item = &'a str>,
+) -> Result<()> {
+    write_content_types_with_overrides(writer, part_names, &[])
+}
+
+/// Writes the `[Content_Types].xml` file like [`write_content_types`], with an `Override`
+/// entry for each part name and content type in `overrides`, such as a print ticket's.
+pub fn write_content_types_with_overrides<'a, W: Write>(
+    writer: W,
+    part_names: impl IntoIterator<Item = &'a str>,
+    overrides: &[(&str, &str)],
+) -> Result<()> {
     let mut xml = XmlWriter::new(writer);
     xml.write_declaration()?;
 
@@ -34,6 +60,45 @@ pub fn write_content_types<W: Write>(writer: W) -> Result<()> {
         .attr("ContentType", "image/png")
         .write_empty()?;
 
+    let part_names: Vec<&str> = part_names.into_iter().collect();
+    let extensions: BTreeSet<String> = part_names
+        .iter()
+        .filter_map(|name| {
+            name.rsplit_once('.')
+                .map(|(_, ext)| ext.to_ascii_lowercase())
+        })
+        .collect();
+    for ext in &extensions {
+        let content_type = match ext.as_str() {
+            "jpg" | "jpeg" => "image/jpeg",
+            "json" => "application/json",
+            _ => continue,
+        };
+        xml.start_element("Default")
+            .attr("Extension", ext)
+            .attr("ContentType", content_type)
+            .write_empty()?;
+    }
+
+    if part_names
+        .iter()
+        .any(|name| name.trim_start_matches('/') == CORE_PROPERTIES_PATH)
+    {
+        xml.start_element("Override")
+            .attr("PartName", &format!("/{CORE_PROPERTIES_PATH}"))
+            .attr("ContentType", CORE_PROPERTIES_CONTENT_TYPE)
+            .write_empty()?;
+    }
+    for (part_name, content_type) in overrides {
+        xml.start_element("Override")
+            .attr(
+                "PartName",
+                &format!("/{}", part_name.trim_start_matches('/')),
+            )
+            .attr("ContentType", content_type)
+            .write_empty()?;
+    }
+
     // Don't enforce Override for 3D/3dmodel.model if extension match works,
     // but spec usually recommends explicit override for parts.
     // For now, minimal valid set.
@@ -48,6 +113,41 @@ pub fn write_relationships<W: Write>(
     model_part: &str,
     thumbnail_part: Option<&str>,
 ) -> Result<()> {
+    write_part_relationships(
+        writer,
+        &package_relationships(model_part, thumbnail_part, None),
+    )
+}
+
+/// Returns the package relationships (`_rels/.rels`) of a 3MF package: the model part,
+/// and the thumbnail and core properties parts if given. Part names start with `/`.
+pub fn package_relationships(
+    model_part: &str,
+    thumbnail_part: Option<&str>,
+    core_properties_part: Option<&str>,
+) -> Vec<Relationship> {
+    let internal = |id: &str, rel_type: &str, target: &str| Relationship {
+        id: id.to_string(),
+        rel_type: rel_type.to_string(),
+        target: target.to_string(),
+        target_mode: "Internal".to_string(),
+    };
+    let mut rels = vec![internal(
+        "rel0",
+        "http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel",
+        model_part,
+    )];
+    if let Some(thumb) = thumbnail_part {
+        rels.push(internal("rel1", THUMBNAIL_REL_TYPE, thumb));
+    }
+    if let Some(core) = core_properties_part {
+        rels.push(internal("rel2", CORE_PROPERTIES_REL_TYPE, core));
+    }
+    rels
+}
+
+/// Writes the relationships file of a model part (e.g. `3D/_rels/3dmodel.model.rels`).
+pub fn write_part_relationships<W: Write>(writer: W, rels: &[Relationship]) -> Result<()> {
     let mut xml = XmlWriter::new(writer);
     xml.write_declaration()?;
 
@@ -58,24 +158,16 @@ pub fn write_relationships<W: Write>(
         )
         .write_start()?;
 
-    xml.start_element("Relationship")
-        .attr("Target", model_part)
-        .attr("Id", "rel0")
-        .attr(
-            "Type",
-            "http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel",
-        )
-        .write_empty()?;
-
-    if let Some(thumb) = thumbnail_part {
-        xml.start_element("Relationship")
-            .attr("Target", thumb)
-            .attr("Id", "rel1")
-            .attr(
-                "Type",
-                "http://schemas.openxmlformats.org/package/2006/relationships/metadata/thumbnail",
-            )
-            .write_empty()?;
+    for rel in rels {
+        let mut elem = xml
+            .start_element("Relationship")
+            .attr("Target", &rel.target)
+            .attr("Id", &rel.id)
+            .attr("Type", &rel.rel_type);
+        if !rel.target_mode.eq_ignore_ascii_case("internal") {
+            elem = elem.attr("TargetMode", &rel.target_mode);
+        }
+        elem.write_empty()?;
     }
 
     xml.end_element("Relationships")?;
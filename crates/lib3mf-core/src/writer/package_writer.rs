@@ -1,14 +1,47 @@
+use crate::archive::opc::Relationship;
+use crate::archive::{
+    ArchiveReader, EntryMetadata, MODEL_REL_TYPE, part_rels_path, rels_source_part,
+    resolve_part_target,
+};
 use crate::error::{Lib3mfError, Result};
-use crate::model::Package;
-use crate::writer::opc_writer::{write_content_types, write_relationships};
-use std::io::{Seek, Write};
+use crate::model::core_properties::{
+    CORE_PROPERTIES_PATH, CORE_PROPERTIES_REL_TYPE, LEGACY_CORE_PROPERTIES_REL_TYPE,
+};
+use crate::model::stats_cache::{STATS_CACHE_PATH, package_fingerprint, stats_cache_json};
+use crate::model::{Geometry, Model, Package};
+use crate::model::{PRINT_TICKET_CONTENT_TYPE, PRINT_TICKET_REL_TYPE};
+use crate::trace::{phase, span};
+use crate::writer::core_properties_writer::write_core_properties;
+use crate::writer::model_writer::WriteOptions;
+use crate::writer::opc_writer::{
+    THUMBNAIL_REL_TYPE, package_relationships, write_content_types_with_overrides,
+    write_part_relationships,
+};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::{self, Read, Seek, Write};
 use zip::ZipWriter;
 use zip::write::FileOptions;
 
 /// A writer that orchestrates the creation of a 3MF package (ZIP archive).
+///
+/// Packages with more than 65,535 entries or larger than 4 GiB get Zip64 records
+/// automatically. A single entry of 4 GiB or more must be marked as Zip64 before it is
+/// written: attachments are marked by their size and model parts by an estimate of their
+/// XML size. [`with_zip64`](Self::with_zip64) marks every entry, for models the estimate
+/// could miss.
 pub struct PackageWriter<W: Write + Seek> {
     zip: ZipWriter<W>,
     options: FileOptions<'static, ()>,
+    geometry_checksums: bool,
+    production_uuids: bool,
+    stats_cache: bool,
+    zip64: bool,
+    write_options: WriteOptions,
+    /// Entries written so far, with their sizes and checksums, for the stats cache.
+    written: Vec<EntryMetadata>,
+    /// The entry being written and the checksum of its data so far.
+    current: Option<(EntryMetadata, crc32fast::Hasher)>,
 }
 
 impl<W: Write + Seek> PackageWriter<W> {
@@ -21,97 +54,140 @@ impl<W: Write + Seek> PackageWriter<W> {
         Self {
             zip: ZipWriter::new(writer),
             options,
+            geometry_checksums: false,
+            production_uuids: false,
+            stats_cache: false,
+            zip64: false,
+            write_options: WriteOptions::default(),
+            written: Vec::new(),
+            current: None,
         }
     }
 
+    /// Writes every entry with a Zip64 extra field, so any of them may exceed 4 GiB.
+    /// Readers without Zip64 support can't open the result.
+    pub fn with_zip64(mut self, enabled: bool) -> Self {
+        self.zip64 = enabled;
+        self
+    }
+
+    /// Compresses every entry at Deflate `level`, from 0 (fastest) to 9 (smallest),
+    /// instead of the default of 6. `None` restores the default.
+    pub fn with_compression_level(mut self, level: Option<i64>) -> Self {
+        self.options = self.options.compression_level(level);
+        self
+    }
+
+    /// Formats the vertex coordinates of every model part as `options` specify.
+    ///
+    /// Geometry checksums and the stats cache describe the coordinates as written.
+    pub fn with_write_options(mut self, options: WriteOptions) -> Self {
+        self.write_options = options;
+        self
+    }
+
+    /// Returns the entry options for an entry of about `size` bytes.
+    fn entry_options(&self, size: u64) -> FileOptions<'static, ()> {
+        self.options.large_file(self.zip64 || needs_zip64(size))
+    }
+
+    /// Embeds a geometry checksum for every object of each model part as metadata, so
+    /// corruption can be detected on load. See [`integrity`](crate::model::integrity).
+    pub fn with_geometry_checksums(mut self, enabled: bool) -> Self {
+        self.geometry_checksums = enabled;
+        self
+    }
+
+    /// Gives the build, build items, objects and components of each model part a UUID
+    /// where missing. See [`Model::assign_missing_uuids`].
+    pub fn with_production_uuids(mut self, enabled: bool) -> Self {
+        self.production_uuids = enabled;
+        self
+    }
+
+    /// Computes the package's statistics and stores them in the package, so they can be
+    /// read back without parsing. See [`stats_cache`](crate::model::stats_cache).
+    ///
+    /// A cache among the main model's attachments is never copied, with or without this
+    /// option, as it would describe the package before it was re-serialized.
+    pub fn with_stats_cache(mut self, enabled: bool) -> Self {
+        self.stats_cache = enabled;
+        self
+    }
+
     /// Writes all parts of the package to the ZIP archive and finalizes it.
+    ///
+    /// The main model's attachments are written with the relationships loaded for them
+    /// (see [`attachments`](crate::model::attachments)). Loaded package relationships are
+    /// kept unless the writer generates their type itself or their target isn't written.
     pub fn write(mut self, package: &Package) -> Result<()> {
+        let _phase = phase!(span::WRITE_PACKAGE, parts = package.parts.len() + 1);
+        let main_path = package.main_model_path.trim_start_matches('/');
+        let is_model_part = |part: &str| {
+            part == main_path
+                || package
+                    .parts
+                    .keys()
+                    .any(|path| path.trim_start_matches('/') == part)
+        };
+
         // 1. Write Attachments (Textures, Thumbnails) from the main model
         // (In a true multi-part, attachments might be shared or part-specific,
         // but for now we aggregate them in the main model or handle them simply).
-        for (path, data) in &package.main_model.attachments {
+        // Core properties set on the model replace a copy among the attachments.
+        let attachments = phase!(span::WRITE_ATTACHMENTS);
+        let core_properties = package.main_model.core_properties.as_ref();
+        // Sorted, so the same model always produces the same archive
+        let mut entries: Vec<_> = package.main_model.attachments.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        for (path, data) in entries {
             let zip_path = path.trim_start_matches('/');
-            self.zip
-                .start_file(zip_path, self.options)
-                .map_err(|e| Lib3mfError::Io(e.into()))?;
-            self.zip.write_all(data).map_err(Lib3mfError::Io)?;
-        }
-
-        // 2. Prepare Relationships (Textures, Thumbnails) for 3D Model
-        // We do this BEFORE writing XML because objects need the Relationship ID for the 'thumbnail' attribute.
-        let mut model_rels = Vec::new();
-        let mut path_to_rel_id = std::collections::HashMap::new();
-
-        // A. Collect Textures from Attachments
-        for path in package.main_model.attachments.keys() {
-            if path.starts_with("3D/Textures/") || path.starts_with("/3D/Textures/") {
-                let target = if path.starts_with('/') {
-                    path.to_string()
-                } else {
-                    format!("/{}", path)
-                };
-
-                // Deduplicate? For now, we assume 1:1 path to rel or just create distinct rels per path
-                path_to_rel_id.entry(target.clone()).or_insert_with(|| {
-                    let id = format!("rel_tex_{}", model_rels.len());
-                    model_rels.push(crate::archive::opc::Relationship {
-                        id: id.clone(),
-                        rel_type: "http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel/relationship/texture".to_string(),
-                        target: target.clone(),
-                        target_mode: "Internal".to_string(),
-                    });
-                    id
-                });
+            if zip_path == STATS_CACHE_PATH
+                || (core_properties.is_some() && zip_path == CORE_PROPERTIES_PATH)
+            {
+                continue;
             }
+            self.start_entry(zip_path, self.entry_options(data.len() as u64))?;
+            self.entry().write_all(data).map_err(zip_error)?;
         }
-
-        // B. Collect Object Thumbnails
-        for obj in package.main_model.resources.iter_objects() {
-            if let Some(thumb_path) = &obj.thumbnail {
-                let target = if thumb_path.starts_with('/') {
-                    thumb_path.clone()
-                } else {
-                    format!("/{}", thumb_path)
-                };
-
-                path_to_rel_id.entry(target.clone()).or_insert_with(|| {
-                    let id = format!("rel_thumb_{}", model_rels.len());
-                    model_rels.push(crate::archive::opc::Relationship {
-                        id: id.clone(),
-                        rel_type: "http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel/relationship/thumbnail".to_string(),
-                        target: target.clone(),
-                        target_mode: "Internal".to_string(),
-                    });
-                    id
-                });
+        if let Some(properties) = core_properties {
+            self.start_entry(CORE_PROPERTIES_PATH, self.options)?;
+            write_core_properties(self.entry(), properties)?;
+        }
+        // Relationships of attached parts; model parts get theirs below
+        let loaded_rels = &package.main_model.existing_relationships;
+        let mut rels_paths: Vec<&String> = loaded_rels.keys().collect();
+        rels_paths.sort();
+        for rels_path in rels_paths {
+            let source = rels_source_part(rels_path);
+            if source.is_empty()
+                || is_model_part(&source)
+                || package.main_model.attachment(&source).is_none()
+                || loaded_rels[rels_path].is_empty()
+            {
+                continue;
             }
+            self.start_entry(rels_path.trim_start_matches('/'), self.options)?;
+            write_part_relationships(self.entry(), &loaded_rels[rels_path])?;
         }
+        drop(attachments);
 
-        // 3. Write 3D Model parts
-        let main_path = "3D/3dmodel.model";
-        self.zip
-            .start_file(main_path, self.options)
-            .map_err(|e| Lib3mfError::Io(e.into()))?;
-
-        // Pass the relationship map to write_xml so it can write attributes
-        package
-            .main_model
-            .write_xml(&mut self.zip, Some(&path_to_rel_id))?;
+        // 2. Write 3D Model parts, each with its own relationships file. Textures are
+        // shared attachments, so they are linked from the main model only.
+        self.write_model_part(
+            main_path,
+            &package.main_model,
+            Some(&package.main_model.attachments),
+        )?;
 
-        for (path, model) in &package.parts {
-            self.zip
-                .start_file(path.trim_start_matches('/'), self.options)
-                .map_err(|e| Lib3mfError::Io(e.into()))?;
-            // TODO: Support relationships for other parts if they have their own thumbnails
-            model.write_xml(&mut self.zip, None)?;
+        let mut parts: Vec<_> = package.parts.iter().collect();
+        parts.sort_by(|a, b| a.0.cmp(b.0));
+        for (path, model) in parts {
+            self.write_model_part(path.trim_start_matches('/'), model, None)?;
         }
 
-        // 4. Write Relationships (_rels/.rels and model relationships)
-        // Global Relationships
-        self.zip
-            .start_file("_rels/.rels", self.options)
-            .map_err(|e| Lib3mfError::Io(e.into()))?;
-
+        // 3. Write Global Relationships
+        let relationships = phase!(span::WRITE_RELATIONSHIPS);
         let package_thumb = package
             .main_model
             .attachments
@@ -125,63 +201,462 @@ impl<W: Write + Seek> PackageWriter<W> {
                 }
             });
 
-        write_relationships(
-            &mut self.zip,
+        let core_properties_part = format!("/{CORE_PROPERTIES_PATH}");
+        let generated = package_relationships(
             &format!("/{}", main_path),
             package_thumb.as_deref(),
+            core_properties.map(|_| core_properties_part.as_str()),
+        );
+        let loaded = loaded_rels
+            .get("_rels/.rels")
+            .map_or(&[][..], Vec::as_slice);
+        let is_written = |part: &str| {
+            is_model_part(part)
+                || (part != STATS_CACHE_PATH && package.main_model.attachment(part).is_some())
+        };
+        let mut root_rels = Vec::new();
+        write_part_relationships(
+            &mut root_rels,
+            &with_loaded_relationships(generated, loaded, is_written),
         )?;
+        self.start_entry("_rels/.rels", self.options)?;
+        self.entry().write_all(&root_rels).map_err(zip_error)?;
+        drop(relationships);
 
-        // Model Relationships (e.g. 3D/_rels/3dmodel.model.rels)
-        // Merge existing relationships with new texture/thumbnail relationships
-        let model_rels_path = "3D/_rels/3dmodel.model.rels";
+        // 4. Write the statistics of everything written so far
+        if self.stats_cache {
+            let _phase = phase!(span::WRITE_STATS_CACHE);
+            self.finish_entry();
+            let model = self.prepare(&package.main_model)?;
+            let mut written = WrittenPackage {
+                package,
+                main_path,
+                root_rels,
+                write_options: &self.write_options,
+            };
+            let stats = model.compute_stats(&mut written)?;
+            let json = stats_cache_json(&stats, package_fingerprint(&self.written))?;
+            self.start_entry(STATS_CACHE_PATH, self.options)?;
+            self.entry().write_all(&json).map_err(zip_error)?;
+        }
 
-        // Start with existing relationships if available
-        let mut all_model_rels = package
-            .main_model
+        // 5. Write Content Types
+        let _phase = phase!(span::WRITE_CONTENT_TYPES);
+        self.start_entry("[Content_Types].xml", self.options)?;
+        let cache = self.stats_cache.then_some(STATS_CACHE_PATH);
+        let core = core_properties.map(|_| CORE_PROPERTIES_PATH);
+        let registry = package.main_model.attachment_registry();
+        let print_tickets: Vec<(&str, &str)> = registry
+            .of_type(PRINT_TICKET_REL_TYPE)
+            .iter()
+            .filter(|ticket| ticket.data.is_some())
+            .map(|ticket| (ticket.path.as_str(), PRINT_TICKET_CONTENT_TYPE))
+            .collect();
+        write_content_types_with_overrides(
+            self.entry(),
+            package
+                .main_model
+                .attachments
+                .keys()
+                .map(String::as_str)
+                .chain(cache)
+                .chain(core),
+            &print_tickets,
+        )?;
+
+        self.zip.finish().map_err(zip_error)?;
+        Ok(())
+    }
+
+    /// Starts a ZIP entry, recording the previous one.
+    fn start_entry(&mut self, name: &str, options: FileOptions<'static, ()>) -> Result<()> {
+        self.finish_entry();
+        self.zip.start_file(name, options).map_err(zip_error)?;
+        let entry = EntryMetadata {
+            name: name.to_string(),
+            size: 0,
+            compressed_size: 0,
+            compressed: true,
+            crc32: 0,
+        };
+        self.current = Some((entry, crc32fast::Hasher::new()));
+        Ok(())
+    }
+
+    /// Records the entry being written, if any, with its size and checksum.
+    fn finish_entry(&mut self) {
+        if let Some((mut entry, crc)) = self.current.take() {
+            entry.crc32 = crc.finalize();
+            self.written.push(entry);
+        }
+    }
+
+    /// Returns a writer for the data of the current entry.
+    fn entry(&mut self) -> EntryWriter<'_, W> {
+        EntryWriter {
+            zip: &mut self.zip,
+            current: &mut self.current,
+        }
+    }
+
+    /// Returns the model as written: with checksums and UUIDs added if enabled.
+    fn prepare<'m>(&self, model: &'m Model) -> Result<Cow<'m, Model>> {
+        let lossless = self.write_options.is_lossless();
+        if !self.geometry_checksums && !self.production_uuids && lossless {
+            return Ok(Cow::Borrowed(model));
+        }
+        let mut copy = model.clone();
+        if !lossless {
+            round_vertices(&mut copy, &self.write_options)?;
+        }
+        if self.production_uuids {
+            copy.assign_missing_uuids();
+        }
+        if self.geometry_checksums {
+            copy.embed_geometry_checksums();
+        }
+        Ok(Cow::Owned(copy))
+    }
+
+    /// Writes a model part and its relationships file.
+    ///
+    /// Relationships loaded with the model are kept. A thumbnail relationship is added for
+    /// every object thumbnail, and the `thumbnail` attribute is written as the image's part
+    /// name. Attributes holding a relationship ID (written by older versions of this crate)
+    /// are resolved to the relationship's target. When `textures` is given, a texture
+    /// relationship is added for each attachment under `3D/Textures/` and for each
+    /// displacement texture of the model.
+    fn write_model_part(
+        &mut self,
+        path: &str,
+        model: &Model,
+        textures: Option<&HashMap<String, Vec<u8>>>,
+    ) -> Result<()> {
+        let _phase = phase!(span::WRITE_MODEL_PART, part = path);
+        let model = &*self.prepare(model)?;
+
+        let rels_path = part_rels_path(path);
+        let mut rels = model
             .existing_relationships
-            .get(model_rels_path)
+            .get(&rels_path)
             .cloned()
             .unwrap_or_default();
 
-        // Add new texture/thumbnail relationships
-        // Use a HashSet to track existing IDs to avoid duplicates
-        let existing_ids: std::collections::HashSet<String> =
-            all_model_rels.iter().map(|r| r.id.clone()).collect();
+        let mut thumbnail_paths = HashMap::new();
+        for obj in model.resources.iter_objects() {
+            if let Some(reference) = &obj.thumbnail {
+                let target = rels
+                    .iter()
+                    .find(|r| &r.id == reference)
+                    .map(|r| resolve_target(path, &r.target))
+                    .unwrap_or_else(|| absolute(reference));
+                thumbnail_paths.insert(reference.clone(), target);
+            }
+        }
+        // Replaced by standard thumbnail relationships below.
+        rels.retain(|r| r.rel_type != LEGACY_THUMBNAIL_REL_TYPE);
+
+        let mut targets: Vec<&String> = thumbnail_paths.values().collect();
+        targets.sort();
+        targets.dedup();
+        for target in targets {
+            add_relationship(&mut rels, THUMBNAIL_REL_TYPE, target, "rel_thumb");
+        }
 
-        for rel in model_rels {
-            if !existing_ids.contains(&rel.id) {
-                all_model_rels.push(rel);
+        if let Some(attachments) = textures {
+            let displacement_paths: Vec<String> = model
+                .resources
+                .iter_displacement_2d()
+                .map(|d| absolute(&d.path))
+                .collect();
+            let mut paths: Vec<String> = attachments
+                .keys()
+                .map(|p| absolute(p))
+                .filter(|p| p.starts_with("/3D/Textures/") || displacement_paths.contains(p))
+                .filter(|p| !thumbnail_paths.values().any(|t| t == p))
+                .collect();
+            paths.sort();
+            for target in &paths {
+                add_relationship(&mut rels, TEXTURE_REL_TYPE, target, "rel_tex");
             }
         }
 
-        // Write merged relationships if any exist
-        if !all_model_rels.is_empty() {
-            self.zip
-                .start_file(model_rels_path, self.options)
-                .map_err(|e| Lib3mfError::Io(e.into()))?;
+        self.start_entry(path, self.entry_options(estimated_xml_size(model)))?;
+        model
+            .write_xml(self.entry(), Some(&thumbnail_paths))
+            .map_err(|e| match e {
+                Lib3mfError::Io(e) => zip_error(e),
+                e => e,
+            })?;
+
+        if !rels.is_empty() {
+            self.start_entry(&rels_path, self.options)?;
+            write_part_relationships(self.entry(), &rels)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes to the current ZIP entry, checksumming the data as it goes.
+struct EntryWriter<'a, W: Write + Seek> {
+    zip: &'a mut ZipWriter<W>,
+    current: &'a mut Option<(EntryMetadata, crc32fast::Hasher)>,
+}
+
+impl<W: Write + Seek> Write for EntryWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.zip.write(buf)?;
+        if let Some((entry, crc)) = self.current.as_mut() {
+            entry.size += n as u64;
+            crc.update(&buf[..n]);
+        }
+        Ok(n)
+    }
 
-            let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
-            xml.push_str("<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\n");
-            for rel in all_model_rels {
-                xml.push_str(&format!(
-                    "  <Relationship Target=\"{}\" Id=\"{}\" Type=\"{}\" />\n",
-                    rel.target, rel.id, rel.rel_type
-                ));
+    fn flush(&mut self) -> io::Result<()> {
+        self.zip.flush()
+    }
+}
+
+/// The package being written, read back from memory to compute its statistics.
+///
+/// Model parts are serialized again on request; attachments and the root relationships
+/// are served as written.
+struct WrittenPackage<'a> {
+    package: &'a Package,
+    main_path: &'a str,
+    root_rels: Vec<u8>,
+    write_options: &'a WriteOptions,
+}
+
+impl WrittenPackage<'_> {
+    fn part(&self, name: &str) -> Option<&Model> {
+        let name = name.trim_start_matches('/');
+        if name == self.main_path {
+            return Some(&self.package.main_model);
+        }
+        self.package
+            .parts
+            .iter()
+            .find(|(path, _)| path.trim_start_matches('/') == name)
+            .map(|(_, model)| model)
+    }
+
+    fn attachment(&self, name: &str) -> Option<&Vec<u8>> {
+        let name = name.trim_start_matches('/');
+        self.package
+            .main_model
+            .attachments
+            .iter()
+            .find(|(path, _)| path.trim_start_matches('/') == name)
+            .map(|(_, data)| data)
+    }
+}
+
+impl ArchiveReader for WrittenPackage<'_> {
+    fn read_entry(&mut self, name: &str) -> Result<Vec<u8>> {
+        if name.trim_start_matches('/') == "_rels/.rels" {
+            return Ok(self.root_rels.clone());
+        }
+        if let Some(model) = self.part(name) {
+            let mut xml = Vec::new();
+            model.write_xml_with_options(&mut xml, None, self.write_options)?;
+            return Ok(xml);
+        }
+        self.attachment(name).cloned().ok_or_else(|| {
+            Lib3mfError::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("Entry not found: {name}"),
+            ))
+        })
+    }
+
+    fn entry_exists(&mut self, name: &str) -> bool {
+        name.trim_start_matches('/') == "_rels/.rels"
+            || self.part(name).is_some()
+            || self.attachment(name).is_some()
+    }
+
+    fn list_entries(&mut self) -> Result<Vec<String>> {
+        let mut entries = vec![self.main_path.to_string(), "_rels/.rels".to_string()];
+        entries.extend(self.package.parts.keys().cloned());
+        entries.extend(self.package.main_model.attachments.keys().cloned());
+        Ok(entries)
+    }
+}
+
+impl Read for WrittenPackage<'_> {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        Ok(0)
+    }
+}
+
+impl Seek for WrittenPackage<'_> {
+    fn seek(&mut self, _: io::SeekFrom) -> io::Result<u64> {
+        Ok(0)
+    }
+}
+
+/// Replaces the vertex coordinates of `model` with the values they read back as once
+/// written with `options`, so that checksums and statistics describe the written geometry.
+fn round_vertices(model: &mut Model, options: &WriteOptions) -> Result<()> {
+    for object in model.resources.iter_objects_mut() {
+        match &mut object.geometry {
+            Geometry::Mesh(mesh) => {
+                for vertex in mesh.vertices.iter_mut() {
+                    *vertex = options.written_vertex(*vertex)?;
+                }
             }
-            xml.push_str("</Relationships>");
+            Geometry::PreciseMesh(mesh) => {
+                for vertex in &mut mesh.vertices {
+                    for c in vertex.as_mut() {
+                        *c = options.written_coordinate_f64(*c)?;
+                    }
+                }
+            }
+            Geometry::DisplacementMesh(mesh) => {
+                for vertex in &mut mesh.vertices {
+                    *vertex = options.written_vertex(*vertex)?;
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Returns true if an entry of `size` bytes must be written as Zip64. Sizes within an
+/// eighth of the 4 GiB limit count, as the compressed size of incompressible data can
+/// exceed its uncompressed size.
+pub(crate) fn needs_zip64(size: u64) -> bool {
+    size >= u64::from(u32::MAX) / 8 * 7
+}
 
-            self.zip
-                .write_all(xml.as_bytes())
-                .map_err(Lib3mfError::Io)?;
+/// Converts a ZIP write error, explaining the one raised when a non-Zip64 entry grows past
+/// 4 GiB.
+pub(crate) fn zip_error(e: impl Into<io::Error>) -> Lib3mfError {
+    let e = e.into();
+    if e.to_string().contains("Large file option has not been set") {
+        return Lib3mfError::Io(io::Error::new(
+            io::ErrorKind::FileTooLarge,
+            "A package entry exceeds 4 GiB but was not started as a Zip64 entry; \
+             write the package with PackageWriter::with_zip64(true)",
+        ));
+    }
+    Lib3mfError::Io(e)
+}
+
+/// Returns a generous estimate of the XML size of a model part, from its element counts.
+fn estimated_xml_size(model: &Model) -> u64 {
+    // Upper bounds for typical elements, e.g. a vertex with full-precision coordinates
+    const VERTEX: usize = 96;
+    const VERTEX_F64: usize = 160;
+    const TRIANGLE: usize = 128;
+    const BEAM: usize = 128;
+    const SLICE_VERTEX: usize = 64;
+
+    let mut elements = 0;
+    for object in model.resources.iter_objects() {
+        elements += match &object.geometry {
+            Geometry::Mesh(mesh) => {
+                mesh.vertices.len() * VERTEX
+                    + mesh.triangles.len() * TRIANGLE
+                    + mesh
+                        .beam_lattice
+                        .as_ref()
+                        .map_or(0, |lattice| lattice.beams.len() * BEAM)
+            }
+            Geometry::DisplacementMesh(mesh) => {
+                (mesh.vertices.len() + mesh.normals.len()) * VERTEX
+                    + mesh.triangles.len() * TRIANGLE
+            }
+            Geometry::PreciseMesh(mesh) => {
+                mesh.vertices.len() * VERTEX_F64
+                    + mesh.triangles.len() * TRIANGLE
+                    + mesh
+                        .beam_lattice
+                        .as_ref()
+                        .map_or(0, |lattice| lattice.beams.len() * BEAM)
+            }
+            Geometry::LazyMesh(mesh) => mesh.span.len(),
+            _ => 0,
+        };
+    }
+    for stack in model.resources.iter_slice_stacks() {
+        for slice in &stack.slices {
+            elements += slice.vertices.len() * SLICE_VERTEX;
         }
+    }
+    elements as u64
+}
 
-        // 4. Write Content Types
-        self.zip
-            .start_file("[Content_Types].xml", self.options)
-            .map_err(|e| Lib3mfError::Io(e.into()))?;
-        write_content_types(&mut self.zip)?;
+const TEXTURE_REL_TYPE: &str =
+    "http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel/relationship/texture";
 
-        self.zip.finish().map_err(|e| Lib3mfError::Io(e.into()))?;
-        Ok(())
+/// Non-standard object thumbnail relationship type written by older versions of this crate.
+const LEGACY_THUMBNAIL_REL_TYPE: &str =
+    "http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel/relationship/thumbnail";
+
+/// Returns a package-rooted part name (`/3D/...`).
+fn absolute(path: &str) -> String {
+    format!("/{}", path.trim_start_matches('/'))
+}
+
+/// Resolves a relationship target of `part` to a package-rooted part name.
+pub(crate) fn resolve_target(part: &str, target: &str) -> String {
+    absolute(&resolve_part_target(part, target))
+}
+
+/// Returns the package relationships to write: the `generated` ones, then the `loaded` ones
+/// of other types whose target is external or a part `is_written` accepts. A loaded
+/// relationship whose ID is taken gets a new one.
+fn with_loaded_relationships(
+    mut rels: Vec<Relationship>,
+    loaded: &[Relationship],
+    is_written: impl Fn(&str) -> bool,
+) -> Vec<Relationship> {
+    let generated: Vec<String> = rels.iter().map(|r| r.rel_type.clone()).collect();
+    let regenerated = |rel_type: &str| {
+        rel_type == MODEL_REL_TYPE
+            || generated.iter().any(|t| t == rel_type)
+            || (rel_type == LEGACY_CORE_PROPERTIES_REL_TYPE
+                && generated.iter().any(|t| t == CORE_PROPERTIES_REL_TYPE))
+    };
+    for rel in loaded {
+        let external = rel.target_mode.eq_ignore_ascii_case("external");
+        if regenerated(&rel.rel_type)
+            || (!external && !is_written(&resolve_part_target("", &rel.target)))
+        {
+            continue;
+        }
+        let mut rel = rel.clone();
+        if rels.iter().any(|r| r.id == rel.id) {
+            rel.id = (0..)
+                .map(|n| format!("rel{}", n))
+                .find(|id| !rels.iter().chain(loaded).any(|r| &r.id == id))
+                .unwrap_or_default();
+        }
+        rels.push(rel);
+    }
+    rels
+}
+
+/// Adds a relationship of `rel_type` to `target` unless one exists, picking an unused ID.
+fn add_relationship(rels: &mut Vec<Relationship>, rel_type: &str, target: &str, prefix: &str) {
+    if rels
+        .iter()
+        .any(|r| r.rel_type == rel_type && r.target == target)
+    {
+        return;
     }
+    let id = (0..)
+        .map(|n| format!("{}_{}", prefix, n))
+        .find(|id| !rels.iter().any(|r| &r.id == id))
+        .unwrap_or_default();
+    rels.push(Relationship {
+        id,
+        rel_type: rel_type.to_string(),
+        target: target.to_string(),
+        target_mode: "Internal".to_string(),
+    });
 }
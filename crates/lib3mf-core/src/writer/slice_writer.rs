@@ -89,7 +89,7 @@ pub fn write_slice_stack<W: Write>(
     writer
         .start_element("slicestack")
         .attr("id", &stack.id.0.to_string())
-        .attr("zbottom", &stack.z_bottom.to_string())
+        .float_attr("zbottom", stack.z_bottom)
         .write_start()?;
 
     // Write inline slices first
@@ -123,7 +123,7 @@ fn write_slice<W: Write>(writer: &mut XmlWriter<W>, slice: &Slice) -> Result<()>
 
     writer
         .start_element("slice")
-        .attr("ztop", &slice.z_top.to_string())
+        .float_attr("ztop", slice.z_top)
         .write_start()?;
 
     // Write vertices section (only if there are vertices)
@@ -148,8 +148,8 @@ fn write_slice<W: Write>(writer: &mut XmlWriter<W>, slice: &Slice) -> Result<()>
 fn write_vertex<W: Write>(writer: &mut XmlWriter<W>, v: &Vertex2D) -> Result<()> {
     writer
         .start_element("vertex")
-        .attr("x", &v.x.to_string())
-        .attr("y", &v.y.to_string())
+        .float_attr("x", v.x)
+        .float_attr("y", v.y)
         .write_empty()?;
     Ok(())
 }
@@ -0,0 +1,52 @@
+use crate::error::Result;
+use crate::model::TriangleSet;
+use crate::writer::xml_writer::XmlWriter;
+use std::io::Write;
+
+/// Writes a `<t:trianglesets>` element. Nothing is written for an empty list.
+///
+/// Runs of consecutive triangle indices are written as a single `<t:refrange>`.
+pub fn write_triangle_sets<W: Write>(
+    writer: &mut XmlWriter<W>,
+    sets: &[TriangleSet],
+) -> Result<()> {
+    if sets.is_empty() {
+        return Ok(());
+    }
+
+    writer.start_element("t:trianglesets").write_start()?;
+    for set in sets {
+        writer
+            .start_element("t:triangleset")
+            .attr("name", &set.name)
+            .attr("identifier", &set.identifier)
+            .write_start()?;
+
+        let mut i = 0;
+        while i < set.triangles.len() {
+            let start = set.triangles[i];
+            let mut end = start;
+            while i + 1 < set.triangles.len() && Some(set.triangles[i + 1]) == end.checked_add(1) {
+                end += 1;
+                i += 1;
+            }
+            if end == start {
+                writer
+                    .start_element("t:ref")
+                    .attr("index", &start.to_string())
+                    .write_empty()?;
+            } else {
+                writer
+                    .start_element("t:refrange")
+                    .attr("startindex", &start.to_string())
+                    .attr("endindex", &end.to_string())
+                    .write_empty()?;
+            }
+            i += 1;
+        }
+
+        writer.end_element("t:triangleset")?;
+    }
+    writer.end_element("t:trianglesets")?;
+    Ok(())
+}
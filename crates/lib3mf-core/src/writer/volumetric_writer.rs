@@ -1,6 +1,10 @@
 use crate::error::Result;
-use crate::model::{VolumetricLayer, VolumetricRef, VolumetricStack};
-use crate::writer::xml_writer::XmlWriter;
+use crate::model::{
+    FilterMode, FunctionFromImage3D, Image3D, LevelSet, TileStyle, VolumeData, VolumeFunctionRef,
+    VolumetricLayer, VolumetricRef, VolumetricStack,
+};
+use crate::writer::xml_writer::{ElementBuilder, XmlWriter};
+use glam::Mat4;
 use std::io::Write;
 
 /// Writes a `<volumetricstack>` element with its child layers and volumetricrefs.
@@ -36,7 +40,7 @@ pub fn write_volumetric_stack<W: Write>(
 fn write_layer<W: Write>(writer: &mut XmlWriter<W>, layer: &VolumetricLayer) -> Result<()> {
     writer
         .start_element("layer")
-        .attr("z", &layer.z_height.to_string())
+        .float_attr("z", layer.z_height)
         .attr("path", &layer.content_path)
         .write_empty()?;
     Ok(())
@@ -51,3 +55,150 @@ fn write_volumetricref<W: Write>(writer: &mut XmlWriter<W>, vref: &VolumetricRef
         .write_empty()?;
     Ok(())
 }
+
+/// Writes a `<vol:image3d>` element with its image stack and sheets.
+pub fn write_image_3d<W: Write>(writer: &mut XmlWriter<W>, image: &Image3D) -> Result<()> {
+    let mut element = writer
+        .start_element("vol:image3d")
+        .attr("id", &image.id.0.to_string());
+    if let Some(name) = &image.name {
+        element = element.attr("name", name);
+    }
+    element.write_start()?;
+
+    writer
+        .start_element("vol:imagestack")
+        .attr("rowcount", &image.stack.row_count.to_string())
+        .attr("columncount", &image.stack.column_count.to_string())
+        .attr("sheetcount", &image.stack.sheet_count.to_string())
+        .write_start()?;
+    for path in &image.stack.sheets {
+        writer
+            .start_element("vol:imagesheet")
+            .attr("path", path)
+            .write_empty()?;
+    }
+    writer.end_element("vol:imagestack")?;
+
+    writer.end_element("vol:image3d")?;
+    Ok(())
+}
+
+/// Writes a `<vol:functionfromimage3d>` element. Attributes at their defaults are omitted.
+pub fn write_function_from_image_3d<W: Write>(
+    writer: &mut XmlWriter<W>,
+    function: &FunctionFromImage3D,
+) -> Result<()> {
+    let mut element = writer
+        .start_element("vol:functionfromimage3d")
+        .attr("id", &function.id.0.to_string())
+        .attr("image3did", &function.image3d_id.0.to_string());
+    if let Some(display_name) = &function.display_name {
+        element = element.attr("displayname", display_name);
+    }
+    if function.value_offset != 0.0 {
+        element = element.float_attr("valueoffset", function.value_offset);
+    }
+    if function.value_scale != 1.0 {
+        element = element.float_attr("valuescale", function.value_scale);
+    }
+    if function.filter == FilterMode::Nearest {
+        element = element.attr("filter", "nearest");
+    }
+    for (name, style) in [
+        ("tilestyleu", function.tile_style_u),
+        ("tilestylev", function.tile_style_v),
+        ("tilestylew", function.tile_style_w),
+    ] {
+        if style != TileStyle::Wrap {
+            element = element.attr(name, tile_style_to_str(style));
+        }
+    }
+    element.write_empty()?;
+    Ok(())
+}
+
+/// Writes a `<vol:volumedata>` element with its color, composite and properties.
+pub fn write_volume_data<W: Write>(writer: &mut XmlWriter<W>, data: &VolumeData) -> Result<()> {
+    writer
+        .start_element("vol:volumedata")
+        .attr("id", &data.id.0.to_string())
+        .write_start()?;
+
+    if let Some(color) = &data.color {
+        function_ref_attrs(writer.start_element("vol:color"), color).write_empty()?;
+    }
+    if let Some(composite) = &data.composite {
+        writer
+            .start_element("vol:composite")
+            .attr("basematerialid", &composite.base_material_id.0.to_string())
+            .write_start()?;
+        for mapping in &composite.mappings {
+            function_ref_attrs(writer.start_element("vol:materialmapping"), mapping)
+                .write_empty()?;
+        }
+        writer.end_element("vol:composite")?;
+    }
+    for property in &data.properties {
+        let mut element = writer
+            .start_element("vol:property")
+            .attr("name", &property.name);
+        if property.required {
+            element = element.attr("required", "true");
+        }
+        function_ref_attrs(element, &property.function).write_empty()?;
+    }
+
+    writer.end_element("vol:volumedata")?;
+    Ok(())
+}
+
+/// Writes a `<vol:levelset>` element (the geometry of a level set object).
+pub fn write_level_set<W: Write>(writer: &mut XmlWriter<W>, level_set: &LevelSet) -> Result<()> {
+    let function = VolumeFunctionRef {
+        function_id: level_set.function_id,
+        channel: level_set.channel.clone(),
+        transform: level_set.transform,
+        min_feature_size: level_set.min_feature_size,
+        fallback_value: level_set.fallback_value,
+    };
+    let mut element = function_ref_attrs(writer.start_element("vol:levelset"), &function)
+        .attr("meshid", &level_set.mesh_id.0.to_string());
+    if level_set.mesh_bbox_only {
+        element = element.attr("meshbboxonly", "true");
+    }
+    if let Some(volume_id) = level_set.volume_id {
+        element = element.attr("volumeid", &volume_id.0.to_string());
+    }
+    element.write_empty()?;
+    Ok(())
+}
+
+/// Adds the attributes of a function channel reference, omitting defaults.
+fn function_ref_attrs<'a, W: Write>(
+    element: ElementBuilder<'a, W>,
+    function: &VolumeFunctionRef,
+) -> ElementBuilder<'a, W> {
+    let mut element = element
+        .attr("functionid", &function.function_id.0.to_string())
+        .attr("channel", &function.channel);
+    if function.transform != Mat4::IDENTITY {
+        element = element.transform_attr("transform", &function.transform);
+    }
+    if function.min_feature_size != 0.0 {
+        element = element.float_attr("minfeaturesize", function.min_feature_size);
+    }
+    if function.fallback_value != 0.0 {
+        element = element.float_attr("fallbackvalue", function.fallback_value);
+    }
+    element
+}
+
+fn tile_style_to_str(style: TileStyle) -> &'static str {
+    match style {
+        TileStyle::Wrap => "wrap",
+        TileStyle::Mirror => "mirror",
+        TileStyle::Clamp => "clamp",
+        TileStyle::None => "none",
+    }
+}
@@ -0,0 +1,167 @@
+//! Formatting of values written into 3MF XML.
+//!
+//! Every attribute value and text node the writers emit goes through this module, so that
+//! the parser reads back exactly what was written:
+//!
+//! - Numbers are written in the shortest form that parses back to the same value, in
+//!   positional notation: `0.1`, `1000000`, never `1e6`. Rust's `Display` for floats
+//!   produces this form. NaN and infinities can't be written; the 3MF number type has no
+//!   spelling for them.
+//! - Strings escape the five XML special characters. In attributes, tabs and line breaks
+//!   are written as character references, which attribute value normalization would
+//!   otherwise turn into spaces. In text, whitespace at either end is written as character
+//!   references too, as the parser trims text content, and so are carriage returns.
+//! - Characters XML 1.0 doesn't allow at all (most control characters, U+FFFE and U+FFFF)
+//!   are an error rather than a document no parser accepts.
+
+use crate::error::{Lib3mfError, Result};
+use std::borrow::Cow;
+use std::fmt::{Display, Write};
+
+/// A floating-point type the writers format with [`format_float`].
+pub trait XmlFloat: Copy + Display {
+    /// Returns whether the value is neither NaN nor infinite.
+    fn is_finite(self) -> bool;
+}
+
+impl XmlFloat for f32 {
+    fn is_finite(self) -> bool {
+        f32::is_finite(self)
+    }
+}
+
+impl XmlFloat for f64 {
+    fn is_finite(self) -> bool {
+        f64::is_finite(self)
+    }
+}
+
+/// Formats a number as the shortest decimal that parses back to the same value, without
+/// an exponent.
+///
+/// # Errors
+///
+/// Returns [`Lib3mfError::InvalidStructure`] for NaN and infinities.
+///
+/// ```
+/// use lib3mf_core::writer::xml_value::format_float;
+///
+/// assert_eq!(format_float(0.1f32)?, "0.1");
+/// assert_eq!(format_float(1.5e6f32)?, "1500000");
+/// assert_eq!(format_float(-2.5e-5f64)?, "-0.000025");
+/// assert!(format_float(f32::NAN).is_err());
+/// # Ok::<(), lib3mf_core::error::Lib3mfError>(())
+/// ```
+pub fn format_float<F: XmlFloat>(value: F) -> Result<String> {
+    if !value.is_finite() {
+        return Err(Lib3mfError::InvalidStructure(format!(
+            "Cannot write {} as a 3MF number",
+            value
+        )));
+    }
+    Ok(value.to_string())
+}
+
+/// Formats numbers with [`format_float`], separated by spaces, as in a transform matrix.
+///
+/// # Errors
+///
+/// Returns [`Lib3mfError::InvalidStructure`] if any value is NaN or infinite.
+pub fn format_floats<F: XmlFloat>(values: impl IntoIterator<Item = F>) -> Result<String> {
+    let mut formatted = String::new();
+    for value in values {
+        if !formatted.is_empty() {
+            formatted.push(' ');
+        }
+        formatted.push_str(&format_float(value)?);
+    }
+    Ok(formatted)
+}
+
+/// Escapes a string for use as an attribute value.
+///
+/// # Errors
+///
+/// Returns [`Lib3mfError::InvalidStructure`] if the string contains a character XML 1.0
+/// doesn't allow.
+///
+/// ```
+/// use lib3mf_core::writer::xml_value::escape_attribute;
+///
+/// assert_eq!(escape_attribute("plain")?, "plain");
+/// assert_eq!(escape_attribute("a \"b\" & <c>")?, "a &quot;b&quot; &amp; &lt;c&gt;");
+/// assert_eq!(escape_attribute("line\nbreak")?, "line&#10;break");
+/// # Ok::<(), lib3mf_core::error::Lib3mfError>(())
+/// ```
+pub fn escape_attribute(value: &str) -> Result<Cow<'_, str>> {
+    escape(value, |c, _| matches!(c, '\t' | '\n' | '\r'))
+}
+
+/// Escapes a string for use as text content.
+///
+/// # Errors
+///
+/// Returns [`Lib3mfError::InvalidStructure`] if the string contains a character XML 1.0
+/// doesn't allow.
+///
+/// ```
+/// use lib3mf_core::writer::xml_value::escape_text;
+///
+/// assert_eq!(escape_text("a < b")?, "a &lt; b");
+/// assert_eq!(escape_text(" padded\n")?, "&#32;padded&#10;");
+/// assert_eq!(escape_text("two\nlines")?, "two\nlines");
+/// # Ok::<(), lib3mf_core::error::Lib3mfError>(())
+/// ```
+pub fn escape_text(value: &str) -> Result<Cow<'_, str>> {
+    let start = value.len() - value.trim_start_matches(is_xml_whitespace).len();
+    let end = value.trim_end_matches(is_xml_whitespace).len();
+    escape(value, |c, i| {
+        c == '\r' || (is_xml_whitespace(c) && (i < start || i >= end))
+    })
+}
+
+/// Escapes the XML special characters of `value`, and writes the characters `as_reference`
+/// returns true for (given each character and its byte offset) as character references.
+fn escape(value: &str, as_reference: impl Fn(char, usize) -> bool) -> Result<Cow<'_, str>> {
+    let needs_escape = |(i, c): (usize, char)| {
+        matches!(c, '&' | '<' | '>' | '"' | '\'') || as_reference(c, i) || !is_xml_char(c)
+    };
+    if !value.char_indices().any(needs_escape) {
+        return Ok(Cow::Borrowed(value));
+    }
+
+    let mut escaped = String::with_capacity(value.len() + 8);
+    for (i, c) in value.char_indices() {
+        if !needs_escape((i, c)) {
+            escaped.push(c);
+            continue;
+        }
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c if !is_xml_char(c) => {
+                return Err(Lib3mfError::InvalidStructure(format!(
+                    "Cannot write character U+{:04X} to XML: XML 1.0 doesn't allow it",
+                    c as u32
+                )));
+            }
+            c => {
+                let _ = write!(escaped, "&#{};", c as u32);
+            }
+        }
+    }
+    Ok(Cow::Owned(escaped))
+}
+
+/// Whitespace as XML defines it, which the parser trims from text content.
+fn is_xml_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\n' | '\r')
+}
+
+/// Characters XML 1.0 allows in a document (the `Char` production).
+fn is_xml_char(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\r' | '\u{20}'..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}' | '\u{10000}'..)
+}
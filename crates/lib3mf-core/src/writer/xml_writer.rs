@@ -1,6 +1,12 @@
 use crate::error::{Lib3mfError, Result};
+use crate::writer::xml_value::{
+    XmlFloat, escape_attribute, escape_text, format_float, format_floats,
+};
 use quick_xml::Writer;
+use quick_xml::events::attributes::Attribute;
 use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::name::QName;
+use std::borrow::Cow;
 use std::io::Write;
 
 /// A low-level XML writer providing indented output for 3MF XML serialization.
@@ -30,6 +36,7 @@ impl<W: Write> XmlWriter<W> {
             writer: self,
             name: name.to_string(),
             attributes: Vec::new(),
+            error: None,
         }
     }
 
@@ -40,10 +47,12 @@ impl<W: Write> XmlWriter<W> {
             .map_err(|e| Lib3mfError::Validation(e.to_string()))
     }
 
-    /// Writes a text node with the given content.
+    /// Writes a text node with the given content, escaped with
+    /// [`escape_text`](crate::writer::xml_value::escape_text).
     pub fn write_text(&mut self, text: &str) -> Result<()> {
+        let text = escape_text(text)?;
         self.writer
-            .write_event(Event::Text(BytesText::new(text)))
+            .write_event(Event::Text(BytesText::from_escaped(text)))
             .map_err(|e| Lib3mfError::Validation(e.to_string()))
     }
 }
@@ -53,6 +62,8 @@ pub struct ElementBuilder<'a, W: Write> {
     writer: &'a mut XmlWriter<W>,
     name: String,
     attributes: Vec<(String, String)>,
+    /// First value that couldn't be formatted, returned when the element is written
+    error: Option<Lib3mfError>,
 }
 
 impl<'a, W: Write> ElementBuilder<'a, W> {
@@ -62,6 +73,42 @@ impl<'a, W: Write> ElementBuilder<'a, W> {
         self
     }
 
+    /// Adds a number attribute, formatted with
+    /// [`format_float`](crate::writer::xml_value::format_float).
+    ///
+    /// A NaN or infinite value fails the write of the element.
+    pub fn float_attr<F: XmlFloat>(mut self, key: &str, value: F) -> Self {
+        match format_float(value) {
+            Ok(formatted) => self.attributes.push((key.to_string(), formatted)),
+            Err(e) => {
+                if self.error.is_none() {
+                    self.error = Some(in_attribute(e, key, &self.name));
+                }
+            }
+        }
+        self
+    }
+
+    /// Adds a transform attribute: the 12 values of the matrix's first three rows, column by
+    /// column, each formatted as by [`float_attr`](Self::float_attr).
+    pub fn transform_attr(mut self, key: &str, matrix: &glam::Mat4) -> Self {
+        let values = [
+            matrix.x_axis.truncate(),
+            matrix.y_axis.truncate(),
+            matrix.z_axis.truncate(),
+            matrix.w_axis.truncate(),
+        ];
+        match format_floats(values.iter().flat_map(|column| column.to_array())) {
+            Ok(formatted) => self.attributes.push((key.to_string(), formatted)),
+            Err(e) => {
+                if self.error.is_none() {
+                    self.error = Some(in_attribute(e, key, &self.name));
+                }
+            }
+        }
+        self
+    }
+
     /// Adds an optional attribute to the element, only if `value` is `Some`.
     pub fn optional_attr(mut self, key: &str, value: Option<&str>) -> Self {
         if let Some(v) = value {
@@ -71,11 +118,11 @@ impl<'a, W: Write> ElementBuilder<'a, W> {
     }
 
     /// Writes the element as a self-closing empty element (e.g., `<vertex x="1" />`).
-    pub fn write_empty(self) -> Result<()> {
-        let mut elem = BytesStart::new(&self.name);
-        for (k, v) in &self.attributes {
-            elem.push_attribute((k.as_str(), v.as_str()));
+    pub fn write_empty(mut self) -> Result<()> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
         }
+        let elem = element(&self.name, &self.attributes)?;
         self.writer
             .writer
             .write_event(Event::Empty(elem))
@@ -83,14 +130,42 @@ impl<'a, W: Write> ElementBuilder<'a, W> {
     }
 
     /// Writes the element as an opening tag (e.g., `<model xmlns="...">`) with child content to follow.
-    pub fn write_start(self) -> Result<()> {
-        let mut elem = BytesStart::new(&self.name);
-        for (k, v) in &self.attributes {
-            elem.push_attribute((k.as_str(), v.as_str()));
+    pub fn write_start(mut self) -> Result<()> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
         }
+        let elem = element(&self.name, &self.attributes)?;
         self.writer
             .writer
             .write_event(Event::Start(elem))
             .map_err(|e| Lib3mfError::Validation(e.to_string()))
     }
 }
+
+/// Builds an element, escaping attribute values with
+/// [`escape_attribute`](crate::writer::xml_value::escape_attribute).
+fn element<'b>(name: &'b str, attributes: &'b [(String, String)]) -> Result<BytesStart<'b>> {
+    let mut elem = BytesStart::new(name);
+    for (k, v) in attributes {
+        let value = escape_attribute(v).map_err(|e| in_attribute(e, k, name))?;
+        elem.push_attribute(Attribute {
+            key: QName(k.as_bytes()),
+            value: match value {
+                Cow::Borrowed(value) => Cow::Borrowed(value.as_bytes()),
+                Cow::Owned(value) => Cow::Owned(value.into_bytes()),
+            },
+        });
+    }
+    Ok(elem)
+}
+
+/// Names the attribute a formatting error occurred in.
+fn in_attribute(error: Lib3mfError, key: &str, element: &str) -> Lib3mfError {
+    match error {
+        Lib3mfError::InvalidStructure(message) => Lib3mfError::InvalidStructure(format!(
+            "{} (attribute {} of <{}>)",
+            message, key, element
+        )),
+        e => e,
+    }
+}
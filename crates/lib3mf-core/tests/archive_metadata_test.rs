@@ -0,0 +1,53 @@
+//! Per-entry size and compression metadata of archives.
+
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver};
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+
+fn archive() -> ZipArchiver<Cursor<Vec<u8>>> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let deflated =
+        SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    let stored = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+
+    writer.add_directory("3D/", stored).unwrap();
+    writer.start_file("3D/3dmodel.model", deflated).unwrap();
+    writer.write_all(&[b'a'; 10_000]).unwrap();
+    writer.start_file("3D/Textures/raw.bin", stored).unwrap();
+    writer.write_all(&[7; 4_000]).unwrap();
+
+    let buffer = writer.finish().unwrap().into_inner();
+    ZipArchiver::new(Cursor::new(buffer)).unwrap()
+}
+
+#[test]
+fn test_entries_metadata_reports_sizes() {
+    let mut archiver = archive();
+    let entries = archiver.entries_metadata().unwrap();
+
+    // Directories are not entries
+    let names: Vec<_> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, ["3D/3dmodel.model", "3D/Textures/raw.bin"]);
+
+    let model = &entries[0];
+    assert_eq!(model.size, 10_000);
+    assert!(model.compressed);
+    assert!(model.compressed_size < model.size);
+    assert!(model.compression_ratio() < 0.1);
+
+    let texture = &entries[1];
+    assert_eq!(texture.size, 4_000);
+    assert!(!texture.compressed);
+    assert_eq!(texture.compressed_size, 4_000);
+    assert_eq!(texture.compression_ratio(), 1.0);
+    assert_eq!(texture.crc32, crc32fast::hash(&[7; 4_000]));
+}
+
+#[test]
+fn test_entry_metadata_by_name() {
+    let mut archiver = archive();
+    let entry = archiver.entry_metadata("/3D/3dmodel.model").unwrap();
+    assert_eq!(entry.name, "3D/3dmodel.model");
+    assert_eq!(entry.size, 10_000);
+    assert!(archiver.entry_metadata("missing.bin").is_err());
+}
@@ -0,0 +1,132 @@
+#![cfg(feature = "writer")]
+//! Attachments bound by relationships, enumerated by type and preserved on write.
+
+use lib3mf_core::archive::opc::{Relationship, bambu_rel_types, parse_relationships};
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver};
+use lib3mf_core::model::{Model, PRINT_TICKET_REL_TYPE};
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+
+const MODEL: &str = r#"<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    <resources />
+    <build />
+</model>"#;
+
+/// Writes `model` and loads the result back, attachments and all.
+fn round_trip(model: &Model) -> (Model, ZipArchiver<Cursor<Vec<u8>>>) {
+    let mut buffer = Cursor::new(Vec::new());
+    model.write(&mut buffer).unwrap();
+    let mut archive = ZipArchiver::new(Cursor::new(buffer.into_inner())).unwrap();
+    let mut loaded = Model::default();
+    loaded
+        .load_attachments(&mut archive, |path| path == "3D/3dmodel.model")
+        .unwrap();
+    (loaded, archive)
+}
+
+fn rels(archive: &mut ZipArchiver<Cursor<Vec<u8>>>, path: &str) -> Vec<Relationship> {
+    parse_relationships(&archive.read_entry(path).unwrap()).unwrap()
+}
+
+#[test]
+fn test_print_ticket_round_trip() {
+    let mut model = Model::default();
+    let id = model.add_attachment(
+        "3D/3dmodel.model",
+        PRINT_TICKET_REL_TYPE,
+        "/Metadata/print_ticket.xml",
+        b"<PrintTicket />".to_vec(),
+    );
+    // Adding it again replaces the part and keeps the relationship
+    let again = model.add_attachment(
+        "3D/3dmodel.model",
+        PRINT_TICKET_REL_TYPE,
+        "Metadata/print_ticket.xml",
+        b"<PrintTicket version=\"2\" />".to_vec(),
+    );
+    assert_eq!(id, again);
+    assert_eq!(model.attachments.len(), 1);
+
+    let (loaded, mut archive) = round_trip(&model);
+    let registry = loaded.attachment_registry();
+    assert_eq!(registry.len(), 1);
+    assert_eq!(
+        registry.rel_types().collect::<Vec<_>>(),
+        [PRINT_TICKET_REL_TYPE]
+    );
+    let ticket = &registry.of_type(PRINT_TICKET_REL_TYPE)[0];
+    assert_eq!(ticket.source, "3D/3dmodel.model");
+    assert_eq!(ticket.path, "Metadata/print_ticket.xml");
+    assert_eq!(ticket.relationship.id, id);
+    assert_eq!(ticket.data, Some(&b"<PrintTicket version=\"2\" />"[..]));
+
+    let content_types =
+        String::from_utf8(archive.read_entry("[Content_Types].xml").unwrap()).unwrap();
+    assert!(content_types.contains(
+        r#"PartName="/Metadata/print_ticket.xml" ContentType="application/vnd.ms-printing.printticket+xml""#
+    ));
+}
+
+#[test]
+fn test_package_relationships_are_preserved() {
+    let root_rels = format!(
+        r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="model" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel" Target="/3D/3dmodel.model" />
+  <Relationship Id="rel0" Type="{}" Target="/Metadata/plate_1.gcode" />
+  <Relationship Id="web" Type="urn:example:homepage" Target="https://example.com" TargetMode="External" />
+  <Relationship Id="gone" Type="urn:example:missing" Target="/Metadata/missing.bin" />
+</Relationships>"#,
+        bambu_rel_types::GCODE
+    );
+    let gcode_rels = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="md5" Type="urn:example:checksum" Target="plate_1.gcode.md5" />
+</Relationships>"#;
+
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, content) in [
+        ("_rels/.rels", root_rels.as_str()),
+        ("3D/3dmodel.model", MODEL),
+        ("Metadata/plate_1.gcode", "G28"),
+        ("Metadata/plate_1.gcode.md5", "0"),
+        ("Metadata/_rels/plate_1.gcode.rels", gcode_rels),
+    ] {
+        writer
+            .start_file(name, SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(content.as_bytes()).unwrap();
+    }
+    let package = writer.finish().unwrap().into_inner();
+    let mut archive = ZipArchiver::new(Cursor::new(package)).unwrap();
+    let mut model = Model::default();
+    model
+        .load_attachments(&mut archive, |path| path == "3D/3dmodel.model")
+        .unwrap();
+
+    let registry = model.attachment_registry();
+    let gcode = &registry.of_type(bambu_rel_types::GCODE)[0];
+    assert_eq!((gcode.source.as_str(), gcode.data), ("", Some(&b"G28"[..])));
+    assert_eq!(
+        registry.of_type("urn:example:checksum")[0].path,
+        "Metadata/plate_1.gcode.md5"
+    );
+    assert_eq!(registry.of_type("urn:example:missing")[0].data, None);
+    assert!(registry.of_type("urn:example:homepage").is_empty());
+
+    let (_, mut written) = round_trip(&model);
+    let root = rels(&mut written, "_rels/.rels");
+    let types: Vec<&str> = root.iter().map(|r| r.rel_type.as_str()).collect();
+    assert_eq!(
+        types,
+        [
+            "http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel",
+            bambu_rel_types::GCODE,
+            "urn:example:homepage",
+        ]
+    );
+    // The generated model relationship took the G-code relationship's ID
+    let ids: Vec<&str> = root.iter().map(|r| r.id.as_str()).collect();
+    assert_eq!(ids, ["rel0", "rel1", "web"]);
+
+    let checksum = rels(&mut written, "Metadata/_rels/plate_1.gcode.rels");
+    assert_eq!(checksum[0].target, "plate_1.gcode.md5");
+}
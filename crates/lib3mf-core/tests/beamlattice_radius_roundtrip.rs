@@ -5,6 +5,8 @@
 //!   - `radius: Some(f32)` — attribute must be emitted and survive parse
 //!   - `radius: None`     — attribute must be omitted and stay None after parse
 
+#![cfg(feature = "writer")]
+
 use lib3mf_core::model::*;
 use lib3mf_core::parser::parse_model;
 use std::io::Cursor;
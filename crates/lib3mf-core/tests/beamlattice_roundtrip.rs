@@ -2,6 +2,8 @@
 //!
 //! Covers requirements BLW-01 through BLW-08.
 
+#![cfg(feature = "writer")]
+
 use lib3mf_core::model::*;
 use lib3mf_core::parser::parse_model;
 use std::io::Cursor;
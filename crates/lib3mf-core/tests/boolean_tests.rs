@@ -1,9 +1,15 @@
 //! Tests for Boolean Operations Extension parsing, writing, and validation.
 
+#![cfg(feature = "writer")]
+
+use lib3mf_core::archive::ZipArchiver;
 use lib3mf_core::model::{BooleanOperationType, Geometry, ResourceId};
 use lib3mf_core::parser::parse_model;
-use lib3mf_core::validation::ValidationLevel;
-use std::io::Cursor;
+use lib3mf_core::validation::{
+    ValidationLevel, ValidationOptions, ValidationReport, validate_package,
+};
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
 
 /// Test parsing a simple boolean union operation
 #[test]
@@ -170,18 +176,18 @@ fn test_parse_with_transform() -> anyhow::Result<()> {
 
     let model = parse_model(Cursor::new(xml))?;
 
-    if let Some(obj) = model.resources.get_object(ResourceId(10)) {
-        if let Geometry::BooleanShape(bs) = &obj.geometry {
-            // Base transform: identity rotation + translation (10, 20, 30)
-            assert_eq!(bs.base_transform.w_axis.x, 10.0);
-            assert_eq!(bs.base_transform.w_axis.y, 20.0);
-            assert_eq!(bs.base_transform.w_axis.z, 30.0);
-
-            // Operation transform: scale 2x + translation (5, 5, 5)
-            let op = &bs.operations[0];
-            assert_eq!(op.transform.x_axis.x, 2.0);
-            assert_eq!(op.transform.w_axis.x, 5.0);
-        }
+    if let Some(obj) = model.resources.get_object(ResourceId(10))
+        && let Geometry::BooleanShape(bs) = &obj.geometry
+    {
+        // Base transform: identity rotation + translation (10, 20, 30)
+        assert_eq!(bs.base_transform.w_axis.x, 10.0);
+        assert_eq!(bs.base_transform.w_axis.y, 20.0);
+        assert_eq!(bs.base_transform.w_axis.z, 30.0);
+
+        // Operation transform: scale 2x + translation (5, 5, 5)
+        let op = &bs.operations[0];
+        assert_eq!(op.transform.x_axis.x, 2.0);
+        assert_eq!(op.transform.w_axis.x, 5.0);
     }
 
     Ok(())
@@ -262,6 +268,141 @@ fn test_validation_cycle_detection() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Test validation caps the nesting depth of boolean shapes
+#[test]
+fn test_validation_depth_limit() -> anyhow::Result<()> {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+       xmlns:b="http://schemas.3mf.io/3dmanufacturing/booleanoperations/2023/07">
+    <resources>
+        <object id="1" type="model"><mesh><vertices><vertex x="0" y="0" z="0"/></vertices><triangles/></mesh></object>
+        <b:booleanshape id="2" objectid="1"><b:boolean objectid="1"/></b:booleanshape>
+        <b:booleanshape id="3" objectid="2"><b:boolean objectid="1"/></b:booleanshape>
+        <b:booleanshape id="4" objectid="3"><b:boolean objectid="1"/></b:booleanshape>
+    </resources>
+    <build/>
+</model>"#;
+
+    let model = parse_model(Cursor::new(xml))?;
+    let report = model.validate(ValidationLevel::Standard);
+    assert!(!report.items.iter().any(|e| e.code == 2107));
+
+    let options = ValidationOptions {
+        max_boolean_depth: 2,
+    };
+    let report = model.validate_with_options(ValidationLevel::Standard, &options);
+    let depth_errors: Vec<_> = report.items.iter().filter(|e| e.code == 2107).collect();
+    assert_eq!(depth_errors.len(), 1);
+    assert!(depth_errors[0].message.contains("more than 2 deep"));
+
+    Ok(())
+}
+
+const ROOT_WITH_PART_REFERENCE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+       xmlns:b="http://schemas.3mf.io/3dmanufacturing/booleanoperations/2023/07"
+       xmlns:p="http://schemas.microsoft.com/3dmanufacturing/production/2015/06">
+    <resources>
+        <object id="1" type="model"><mesh><vertices><vertex x="0" y="0" z="0"/></vertices><triangles/></mesh></object>
+        <b:booleanshape id="10" objectid="20" p:path="/3D/part.model">
+            <b:boolean objectid="1"/>
+        </b:booleanshape>
+    </resources>
+    <build/>
+</model>"#;
+
+/// Builds a package of the root model above and, if given, the part it references.
+fn package_with_part(part: Option<&str>) -> anyhow::Result<ZipArchiver<Cursor<Vec<u8>>>> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    writer.start_file("3D/3dmodel.model", SimpleFileOptions::default())?;
+    writer.write_all(ROOT_WITH_PART_REFERENCE.as_bytes())?;
+    if let Some(part) = part {
+        writer.start_file("3D/part.model", SimpleFileOptions::default())?;
+        writer.write_all(part.as_bytes())?;
+    }
+    Ok(ZipArchiver::new(Cursor::new(
+        writer.finish()?.into_inner(),
+    ))?)
+}
+
+fn validate_with_part(part: Option<&str>) -> anyhow::Result<ValidationReport> {
+    let model = parse_model(Cursor::new(ROOT_WITH_PART_REFERENCE))?;
+    let mut report = model.validate(ValidationLevel::Standard);
+    let mut archive = package_with_part(part)?;
+    validate_package(
+        &mut archive,
+        "3D/3dmodel.model",
+        &model,
+        ValidationLevel::Standard,
+        &mut report,
+    );
+    Ok(report)
+}
+
+/// Test references to objects in other model parts aren't reported missing from the model
+#[test]
+fn test_validation_part_reference_not_missing() -> anyhow::Result<()> {
+    let model = parse_model(Cursor::new(ROOT_WITH_PART_REFERENCE))?;
+    let report = model.validate(ValidationLevel::Standard);
+    assert!(!report.has_errors(), "{:?}", report.items);
+    Ok(())
+}
+
+/// Test validation follows boolean shapes into other model parts and finds cycles through them
+#[test]
+fn test_validation_cross_part_cycle() -> anyhow::Result<()> {
+    let part = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+       xmlns:b="http://schemas.3mf.io/3dmanufacturing/booleanoperations/2023/07"
+       xmlns:p="http://schemas.microsoft.com/3dmanufacturing/production/2015/06">
+    <resources>
+        <object id="1" type="model"><mesh><vertices><vertex x="0" y="0" z="0"/></vertices><triangles/></mesh></object>
+        <b:booleanshape id="20" objectid="10" p:path="/3D/3dmodel.model">
+            <b:boolean objectid="1"/>
+        </b:booleanshape>
+    </resources>
+    <build/>
+</model>"#;
+
+    let report = validate_with_part(Some(part))?;
+    assert!(
+        report.items.iter().any(|e| e.code == 2100),
+        "{:?}",
+        report.items
+    );
+    Ok(())
+}
+
+/// Test validation checks the objects it finds in other model parts
+#[test]
+fn test_validation_cross_part_missing_object() -> anyhow::Result<()> {
+    let part = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    <resources>
+        <object id="1" type="model"><mesh><vertices><vertex x="0" y="0" z="0"/></vertices><triangles/></mesh></object>
+    </resources>
+    <build/>
+</model>"#;
+
+    let report = validate_with_part(Some(part))?;
+    let missing: Vec<_> = report.items.iter().filter(|e| e.code == 2102).collect();
+    assert_eq!(missing.len(), 1);
+    assert!(missing[0].message.contains("in '3D/part.model'"));
+    Ok(())
+}
+
+/// Test validation reports references to model parts that can't be loaded
+#[test]
+fn test_validation_missing_part() -> anyhow::Result<()> {
+    let report = validate_with_part(None)?;
+    assert!(
+        report.items.iter().any(|e| e.code == 2108),
+        "{:?}",
+        report.items
+    );
+    Ok(())
+}
+
 /// Test round-trip: parse -> write -> parse produces equivalent model
 #[test]
 fn test_round_trip() -> anyhow::Result<()> {
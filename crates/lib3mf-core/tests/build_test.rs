@@ -0,0 +1,95 @@
+use glam::{Mat4, Vec3};
+use lib3mf_core::error::Lib3mfError;
+use lib3mf_core::model::{Geometry, Mesh, Model, Object, ObjectType, ResourceId};
+use lib3mf_core::validation::ValidationLevel;
+use uuid::Uuid;
+
+fn model_with(types: &[ObjectType]) -> Model {
+    let mut model = Model::default();
+    for (i, object_type) in types.iter().enumerate() {
+        model
+            .resources
+            .add_object(Object {
+                id: ResourceId(i as u32 + 1),
+                object_type: *object_type,
+                name: None,
+                part_number: None,
+                uuid: None,
+                pid: None,
+                pindex: None,
+                thumbnail: None,
+                geometry: Geometry::Mesh(Mesh::new()),
+            })
+            .unwrap();
+    }
+    model
+}
+
+#[test]
+fn test_add_build_item_checks_reference() {
+    let mut model = model_with(&[ObjectType::Model, ObjectType::Other]);
+    assert_eq!(
+        model.add_build_item(ResourceId(1), Mat4::IDENTITY).unwrap(),
+        0
+    );
+
+    assert!(matches!(
+        model.add_build_item(ResourceId(9), Mat4::IDENTITY),
+        Err(Lib3mfError::ResourceNotFound(9))
+    ));
+    let err = model
+        .add_build_item(ResourceId(2), Mat4::IDENTITY)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Build item 1 references object 2"), "{}", err);
+    assert_eq!(model.build.len(), 1);
+
+    // The same mistake made directly is reported by the validator
+    model.build.items.push(model.build.items[0].clone());
+    model.build.items[1].object_id = ResourceId(2);
+    let report = model.validate(ValidationLevel::Standard);
+    assert!(
+        report
+            .items
+            .iter()
+            .any(|i| i.code == 3010 && err.ends_with(&i.message))
+    );
+}
+
+#[test]
+fn test_duplicate_remove_and_move_items() {
+    let mut model = model_with(&[ObjectType::Model, ObjectType::Support]);
+    model.add_build_item(ResourceId(1), Mat4::IDENTITY).unwrap();
+    model.add_build_item(ResourceId(2), Mat4::IDENTITY).unwrap();
+    model.build.items[0].uuid = Some(Uuid::new_v4());
+
+    let copy = model
+        .build
+        .duplicate_item(0, Vec3::new(10.0, 0.0, 0.0))
+        .unwrap();
+    assert_eq!(copy, 2);
+    let item = model.build.get(copy).unwrap();
+    assert_eq!(item.object_id, ResourceId(1));
+    assert!(item.uuid.is_none());
+    assert_eq!(item.transform.w_axis.x, 10.0);
+
+    model.build.move_item(2, 0).unwrap();
+    let ids: Vec<u32> = model.build.iter().map(|i| i.object_id.0).collect();
+    assert_eq!(ids, [1, 1, 2]);
+    assert!(model.build.iter().next().unwrap().uuid.is_none());
+
+    let removed = model.build.remove_item(2).unwrap();
+    assert_eq!(removed.object_id, ResourceId(2));
+    assert!(model.build.remove_item(2).is_err());
+    assert!(model.build.move_item(0, 5).is_err());
+    assert!(model.build.duplicate_item(3, Vec3::ZERO).is_err());
+    assert_eq!(model.build.len(), 2);
+}
+
+#[test]
+fn test_editor_rejects_other_objects_in_build() {
+    let model = model_with(&[ObjectType::Other]);
+    let mut editor = model.edit();
+    assert!(editor.add_item(ResourceId(1)).is_err());
+    assert!(editor.model().build.is_empty());
+}
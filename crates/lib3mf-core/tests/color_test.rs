@@ -0,0 +1,94 @@
+#![cfg(feature = "writer")]
+
+use lib3mf_core::model::{Color, ResourceId, linear_to_srgb, srgb_to_linear};
+use lib3mf_core::parser::parse_model;
+use std::io::Cursor;
+
+#[test]
+fn test_hex_forms() {
+    assert_eq!(
+        Color::from_hex("#FF8000"),
+        Some(Color::new(255, 128, 0, 255))
+    );
+    assert_eq!(
+        Color::from_hex("#FF800080"),
+        Some(Color::new(255, 128, 0, 128))
+    );
+    assert_eq!(Color::from_hex("#F80"), Some(Color::new(255, 136, 0, 255)));
+    assert_eq!(Color::from_hex("#f808"), Some(Color::new(255, 136, 0, 136)));
+
+    for invalid in ["#", "#FF", "#FF800", "#GG0000", "#+F0000", "#FF8000801"] {
+        assert_eq!(Color::from_hex(invalid), None, "{invalid}");
+    }
+}
+
+#[test]
+fn test_named_colors() {
+    assert_eq!(Color::from_name("Red"), Some(Color::new(255, 0, 0, 255)));
+    assert_eq!(Color::from_name("grey"), Color::from_name("GRAY"));
+    assert_eq!(
+        Color::from_name("transparent"),
+        Some(Color::new(0, 0, 0, 0))
+    );
+    assert_eq!(Color::from_name("rebeccapurple"), None);
+
+    assert_eq!(Color::parse(" orange "), Some(Color::new(255, 165, 0, 255)));
+    assert_eq!(Color::parse("#00F"), Some(Color::new(0, 0, 255, 255)));
+    // Hex digits without the leading '#' are still accepted
+    assert_eq!(Color::parse("00FF00"), Some(Color::new(0, 255, 0, 255)));
+    assert_eq!(Color::parse("#red"), None);
+}
+
+#[test]
+fn test_linear_conversion() {
+    assert_eq!(srgb_to_linear(0.0), 0.0);
+    assert!((srgb_to_linear(1.0) - 1.0).abs() < 1e-6);
+    assert!((srgb_to_linear(0.5) - 0.214_041).abs() < 1e-5);
+    assert!((linear_to_srgb(0.214_041) - 0.5).abs() < 1e-5);
+
+    let color = Color::new(200, 100, 30, 128);
+    let [r, g, b, a] = color.to_linear();
+    assert!(r > g && g > b);
+    assert!((a - 128.0 / 255.0).abs() < 1e-6);
+    assert_eq!(Color::from_linear(color.to_linear()), color);
+    assert_eq!(
+        Color::from_linear([2.0, -1.0, 0.0, 1.0]),
+        Color::new(255, 0, 0, 255)
+    );
+}
+
+#[test]
+fn test_parser_accepts_short_and_named_colors() {
+    let xml = r##"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+       xmlns:m="http://schemas.microsoft.com/3dmanufacturing/material/2015/02">
+    <resources>
+        <basematerials id="1">
+            <base name="A" displaycolor="#F00" />
+            <base name="B" displaycolor="navy" />
+        </basematerials>
+        <m:colorgroup id="2">
+            <m:color color="#0F08" />
+            <m:color color="White" />
+        </m:colorgroup>
+    </resources>
+    <build />
+</model>"##;
+    let model = parse_model(Cursor::new(xml)).unwrap();
+
+    let base = model.resources.get_base_materials(ResourceId(1)).unwrap();
+    assert_eq!(base.materials[0].display_color, Color::new(255, 0, 0, 255));
+    assert_eq!(base.materials[1].display_color, Color::new(0, 0, 128, 255));
+    let group = model.resources.get_color_group(ResourceId(2)).unwrap();
+    assert_eq!(
+        group.colors,
+        [Color::new(0, 255, 0, 136), Color::new(255, 255, 255, 255)]
+    );
+
+    // Written back in the canonical #RRGGBBAA form
+    let mut buffer = Vec::new();
+    model.write_xml(&mut buffer, None).unwrap();
+    let written = String::from_utf8(buffer).unwrap();
+    assert!(written.contains(r##"displaycolor="#000080FF""##));
+    assert!(written.contains(r##"color="#00FF0088""##));
+}
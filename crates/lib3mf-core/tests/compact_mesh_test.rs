@@ -0,0 +1,74 @@
+#![cfg(feature = "writer")]
+
+use lib3mf_core::model::{CompactMesh, Mesh, MeshStorage, ObjectType, ResourceId, Unit};
+use lib3mf_core::parser::mesh_parser::{parse_mesh, parse_mesh_into};
+use lib3mf_core::parser::xml_parser::XmlParser;
+use lib3mf_core::validation::geometry::validate_mesh;
+use lib3mf_core::validation::{ValidationLevel, ValidationReport};
+use lib3mf_core::writer::mesh_writer::write_mesh;
+use lib3mf_core::writer::xml_writer::XmlWriter;
+use std::io::Cursor;
+
+// Open tetrahedron (one face missing) with per-vertex properties on one triangle.
+const MESH_XML: &str = r#"<mesh>
+    <vertices>
+        <vertex x="0" y="0" z="0"/><vertex x="10" y="0" z="0"/>
+        <vertex x="0" y="10" z="0"/><vertex x="0" y="0" z="10"/>
+    </vertices>
+    <triangles>
+        <triangle v1="0" v2="2" v3="1"/>
+        <triangle v1="0" v2="1" v3="3" pid="5" p1="0" p2="1" p3="2"/>
+        <triangle v1="1" v2="2" v3="3"/>
+    </triangles>
+</mesh>"#;
+
+fn parse<S: MeshStorage + Default>(xml: &[u8]) -> S {
+    let mut parser = XmlParser::new(Cursor::new(xml));
+    // Skip the opening <mesh> tag; parse_mesh_into starts inside it.
+    parser.read_next_event().unwrap();
+    parse_mesh_into(&mut parser).unwrap()
+}
+
+fn write<S: MeshStorage>(mesh: &S) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_mesh(&mut XmlWriter::new(&mut out), mesh).unwrap();
+    out
+}
+
+fn codes<S: MeshStorage>(mesh: &S) -> Vec<u32> {
+    let mut report = ValidationReport::new();
+    validate_mesh(
+        mesh,
+        ResourceId(1),
+        ObjectType::Model,
+        ValidationLevel::Paranoid,
+        &mut report,
+        Unit::Millimeter,
+    );
+    let mut codes: Vec<u32> = report.items.iter().map(|i| i.code).collect();
+    codes.sort_unstable();
+    codes
+}
+
+#[test]
+fn test_compact_mesh_parse_write_validate_match_mesh() {
+    let mesh: Mesh = parse(MESH_XML.as_bytes());
+    let compact: CompactMesh = parse(MESH_XML.as_bytes());
+
+    assert_eq!(compact.vertex_count(), 4);
+    assert_eq!(compact.triangle_count(), 3);
+    assert!(compact.has_properties());
+    assert_eq!(Mesh::from(&compact), mesh);
+
+    // Identical XML from either storage, and it parses back to the same mesh.
+    let written = write(&compact);
+    assert_eq!(written, write(&mesh));
+    let mut parser = XmlParser::new(Cursor::new(written.as_slice()));
+    parser.read_next_event().unwrap();
+    assert_eq!(parse_mesh(&mut parser).unwrap(), mesh);
+
+    // Same findings (open mesh -> boundary edges) from either storage.
+    let found = codes(&compact);
+    assert!(found.contains(&4002), "{:?}", found);
+    assert_eq!(found, codes(&mesh));
+}
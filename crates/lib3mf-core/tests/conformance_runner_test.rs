@@ -0,0 +1,135 @@
+//! Conformance suite runner: expectations, pass/fail matrix and JUnit output.
+
+use lib3mf_core::validation::ValidationLevel;
+use lib3mf_core::validation::conformance::{
+    ConformanceOptions, Expectation, SampleStage, check_sample, run_conformance_suite,
+};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+
+const VALID: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+  <resources>
+    <object id="1" type="model">
+      <mesh>
+        <vertices>
+          <vertex x="0" y="0" z="0"/><vertex x="1" y="0" z="0"/><vertex x="0" y="1" z="0"/>
+        </vertices>
+        <triangles><triangle v1="0" v2="1" v3="2"/></triangles>
+      </mesh>
+    </object>
+  </resources>
+  <build><item objectid="1"/></build>
+</model>"#;
+
+const RELS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rel0" Target="/3D/3dmodel.model" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel"/>
+</Relationships>"#;
+
+fn write_sample(path: &Path, model: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let mut writer = zip::ZipWriter::new(std::fs::File::create(path).unwrap());
+    let options = SimpleFileOptions::default();
+    writer.start_file("_rels/.rels", options).unwrap();
+    writer.write_all(RELS.as_bytes()).unwrap();
+    writer.start_file("3D/3dmodel.model", options).unwrap();
+    writer.write_all(model.as_bytes()).unwrap();
+    writer.finish().unwrap();
+}
+
+/// Builds a suite with one sample per outcome; the last one misbehaves.
+fn suite(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("lib3mf_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    write_sample(&dir.join("MUSTPASS/valid.3mf"), VALID);
+    write_sample(&dir.join("MUSTFAIL/truncated.3mf"), &VALID[..200]);
+    write_sample(
+        &dir.join("MUSTFAIL/missing_object.3mf"),
+        &VALID.replace("objectid=\"1\"", "objectid=\"9\""),
+    );
+    write_sample(&dir.join("N_accepted.3mf"), VALID);
+    std::fs::write(dir.join("README.txt"), "not a sample").unwrap();
+    dir
+}
+
+#[test]
+fn test_expectation_from_path() {
+    for (path, expected) in [
+        ("MUSTPASS/MUSTPASS_Chapter2.1.3mf", Expectation::MustPass),
+        ("suite/MUSTFAIL/x.3mf", Expectation::MustFail),
+        ("mustfail/MUSTPASS_override.3mf", Expectation::MustPass),
+        ("core/N_XXX_0101_01.3mf", Expectation::MustFail),
+        ("core/P_XXX_0101_01.3mf", Expectation::MustPass),
+        ("plain.3mf", Expectation::MustPass),
+    ] {
+        assert_eq!(Expectation::from_path(Path::new(path)), expected, "{path}");
+    }
+}
+
+#[test]
+fn test_suite_matrix() {
+    let dir = suite("conformance_matrix");
+    let report = run_conformance_suite(&dir, &ConformanceOptions::default()).unwrap();
+
+    let paths: Vec<_> = report.results.iter().map(|r| r.path.clone()).collect();
+    assert_eq!(
+        paths,
+        [
+            PathBuf::from("MUSTFAIL/missing_object.3mf"),
+            PathBuf::from("MUSTFAIL/truncated.3mf"),
+            PathBuf::from("MUSTPASS/valid.3mf"),
+            PathBuf::from("N_accepted.3mf"),
+        ]
+    );
+    let stages: Vec<_> = report
+        .results
+        .iter()
+        .map(|r| r.rejection.as_ref().map(|r| r.stage))
+        .collect();
+    assert_eq!(stages[0], Some(SampleStage::Validation));
+    assert_eq!(stages[1], Some(SampleStage::Parse));
+    assert_eq!(stages[2], None);
+    assert_eq!(report.results[0].level, ValidationLevel::Strict);
+    assert_eq!(report.results[2].level, ValidationLevel::Standard);
+
+    assert_eq!(report.passed(), 3);
+    assert!(!report.is_success());
+    let failures: Vec<_> = report.failures().map(|r| r.path.clone()).collect();
+    assert_eq!(failures, [PathBuf::from("N_accepted.3mf")]);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_junit_report() {
+    let dir = suite("conformance_junit");
+    let report = run_conformance_suite(&dir, &ConformanceOptions::default()).unwrap();
+    let xml = report.to_junit_xml();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(xml.starts_with("<?xml"));
+    assert!(xml.contains("tests=\"4\" failures=\"1\""));
+    assert_eq!(xml.matches("<testcase ").count(), 4);
+    assert_eq!(xml.matches("<failure ").count(), 1);
+    assert!(xml.contains("<testcase classname=\"MustFail\" name=\"N_accepted.3mf\""));
+    assert!(xml.contains("expected rejection, but accepted at Strict level"));
+
+    // The report is well-formed XML
+    let mut reader = quick_xml::Reader::from_str(&xml);
+    loop {
+        match reader.read_event().unwrap() {
+            quick_xml::events::Event::Eof => break,
+            _ => continue,
+        }
+    }
+}
+
+#[test]
+fn test_check_sample_reports_unreadable_file() {
+    let result = check_sample("/nonexistent/MUSTPASS/x.3mf", ValidationLevel::Standard);
+    assert_eq!(result.expectation, Expectation::MustPass);
+    assert!(!result.passed());
+    assert_eq!(result.rejection.unwrap().stage, SampleStage::Archive);
+}
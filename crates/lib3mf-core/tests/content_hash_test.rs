@@ -0,0 +1,250 @@
+#![cfg(feature = "writer")]
+
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::model::{Geometry, Model, ResourceId};
+use lib3mf_core::parser::{parse_model, parse_model_lazy};
+use std::io::Cursor;
+
+const MODEL_A: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xml:lang="en-US"
+    xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:m="http://schemas.microsoft.com/3dmanufacturing/material/2015/02">
+    <metadata name="Title">Bracket</metadata>
+    <resources>
+        <m:colorgroup id="1">
+            <m:color color="#FF0000" />
+            <m:color color="#0000FF" />
+        </m:colorgroup>
+        <object id="2" type="model" pid="1" pindex="0" name="Bracket">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="10" y="0" z="0" />
+                    <vertex x="0" y="10" z="0" />
+                    <vertex x="0" y="0" z="10" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="2" v3="1" />
+                    <triangle v1="0" v2="1" v3="3" pid="1" p1="1" />
+                </triangles>
+            </mesh>
+        </object>
+        <object id="3" type="model">
+            <components>
+                <component objectid="2" transform="1 0 0 0 1 0 0 0 1 20 0 0" />
+                <component objectid="2" />
+            </components>
+        </object>
+    </resources>
+    <build>
+        <item objectid="3" />
+    </build>
+</model>"##;
+
+/// The same part saved by "another tool": centimetres, renumbered resources, reordered
+/// attributes and components, different number formatting and metadata.
+const MODEL_B: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<model xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02" unit="centimeter"
+    xmlns:m="http://schemas.microsoft.com/3dmanufacturing/material/2015/02">
+  <metadata name="Application">OtherSlicer 2.1</metadata>
+  <resources>
+    <m:colorgroup id="10"><m:color color="#ff0000ff" /><m:color color="#0000ffff" /></m:colorgroup>
+    <object pindex="0" pid="10" type="model" id="20">
+      <mesh>
+        <vertices>
+          <vertex z="0.0" y="0.0" x="0.0" /><vertex z="0" y="0" x="1.000000" />
+          <vertex z="0" y="1.0" x="0" /><vertex z="1" y="0" x="0" />
+        </vertices>
+        <triangles>
+          <triangle v3="1" v2="2" v1="0" />
+          <triangle p1="1" pid="10" v3="3" v2="1" v1="0" />
+        </triangles>
+      </mesh>
+    </object>
+    <object type="model" id="30">
+      <components>
+        <component objectid="20" />
+        <component transform="1 0 0 0 1 0 0 0 1 2.0 0 0" objectid="20" />
+      </components>
+    </object>
+  </resources>
+  <build><item objectid="30" /></build>
+</model>"##;
+
+fn parse(xml: &str) -> Model {
+    parse_model(Cursor::new(xml)).unwrap()
+}
+
+#[test]
+fn test_content_hash_ignores_serialization_differences() {
+    let a = parse(MODEL_A);
+    let b = parse(MODEL_B);
+    assert_eq!(a.content_hash(), b.content_hash());
+    assert_eq!(a.content_hash().to_hex().len(), 64);
+}
+
+#[test]
+fn test_content_hash_detects_changes() {
+    let base = parse(MODEL_A).content_hash();
+
+    let moved = MODEL_A.replace(
+        r#"<vertex x="10" y="0" z="0" />"#,
+        r#"<vertex x="10.01" y="0" z="0" />"#,
+    );
+    assert_ne!(parse(&moved).content_hash(), base);
+
+    let recolored = MODEL_A.replace("#0000FF", "#00FF00");
+    assert_ne!(parse(&recolored).content_hash(), base);
+
+    let placed = MODEL_A.replace(
+        r#"<item objectid="3" />"#,
+        r#"<item objectid="3" transform="1 0 0 0 1 0 0 0 1 0 0 5" />"#,
+    );
+    assert_ne!(parse(&placed).content_hash(), base);
+
+    // Metadata is not content.
+    let retitled = MODEL_A.replace(">Bracket<", ">Bracket v2<");
+    assert_eq!(parse(&retitled).content_hash(), base);
+}
+
+#[test]
+fn test_content_hash_survives_package_roundtrip() {
+    let model = parse(MODEL_A);
+    let mut buf = Cursor::new(Vec::new());
+    model.write(&mut buf).unwrap();
+
+    let mut archive = ZipArchiver::new(Cursor::new(buf.into_inner())).unwrap();
+    let path = find_model_path(&mut archive).unwrap();
+    let reparsed = parse(&String::from_utf8(archive.read_entry(&path).unwrap()).unwrap());
+    assert_eq!(reparsed.content_hash(), model.content_hash());
+}
+
+#[test]
+fn test_content_hash_follows_external_parts() {
+    let root = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:p="http://schemas.microsoft.com/3dmanufacturing/production/2015/06">
+    <resources>
+        <object id="1" type="model">
+            <components><component p:path="/3D/Objects/part.model" objectid="2" /></components>
+        </object>
+    </resources>
+    <build><item objectid="1" /></build>
+</model>"#;
+    let mut model = parse(root);
+    model.attachments.insert(
+        "3D/Objects/part.model".to_string(),
+        MODEL_A.as_bytes().to_vec(),
+    );
+    let with_part = model.content_hash();
+
+    let moved = MODEL_A.replace(r#"x="10" y="0""#, r#"x="11" y="0""#);
+    model
+        .attachments
+        .insert("3D/Objects/part.model".to_string(), moved.into_bytes());
+    assert_ne!(model.content_hash(), with_part);
+
+    model.attachments.clear();
+    assert_ne!(model.content_hash(), with_part);
+}
+
+/// Beam lattice, displacement mesh and slice stack geometry with lengths written as
+/// `size` in `unit` and resource IDs offset by `base`.
+fn extension_model(unit: &str, size: &str, base: u32) -> Model {
+    let xml = format!(
+        r##"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="{unit}" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:b="http://schemas.microsoft.com/3dmanufacturing/beamlattice/2017/02"
+    xmlns:s="http://schemas.microsoft.com/3dmanufacturing/slice/2015/07"
+    xmlns:d="http://schemas.microsoft.com/3dmanufacturing/displacement/2024/01">
+    <resources>
+        <s:slicestack id="{slices}" zbottom="0">
+            <s:slice ztop="{size}">
+                <s:vertices>
+                    <s:vertex x="0" y="0" /><s:vertex x="{size}" y="0" /><s:vertex x="0" y="{size}" />
+                </s:vertices>
+                <s:polygon start="0"><s:segment v2="1" /><s:segment v2="2" /><s:segment v2="0" /></s:polygon>
+            </s:slice>
+        </s:slicestack>
+        <object id="{lattice}" type="model">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" /><vertex x="{size}" y="0" z="0" />
+                    <vertex x="0" y="{size}" z="0" />
+                </vertices>
+                <triangles><triangle v1="0" v2="1" v3="2" /></triangles>
+                <b:beamlattice radius="{size}" minlength="{size}">
+                    <b:beams><b:beam v1="0" v2="1" r1="{size}" /></b:beams>
+                </b:beamlattice>
+            </mesh>
+        </object>
+        <object id="{displaced}" type="model">
+            <d:displacementmesh>
+                <d:vertices>
+                    <d:vertex x="0" y="0" z="0" /><d:vertex x="{size}" y="0" z="0" />
+                    <d:vertex x="0" y="{size}" z="0" />
+                </d:vertices>
+                <d:triangles><d:triangle v1="0" v2="1" v3="2" /></d:triangles>
+                <d:normvectors><d:normvector nx="0" ny="0" nz="1" /></d:normvectors>
+            </d:displacementmesh>
+        </object>
+        <object id="{sliced}" type="model" s:slicestackid="{slices}" />
+    </resources>
+    <build>
+        <item objectid="{lattice}" /><item objectid="{displaced}" /><item objectid="{sliced}" />
+    </build>
+</model>"##,
+        slices = base,
+        lattice = base + 1,
+        displaced = base + 2,
+        sliced = base + 3,
+    );
+    parse(&xml)
+}
+
+#[test]
+fn test_content_hash_canonicalizes_extension_geometry() {
+    let a = extension_model("millimeter", "10", 1);
+    let b = extension_model("centimeter", "1.0", 50);
+    let lattice = &a.resources.get_object(ResourceId(2)).unwrap().geometry;
+    assert!(matches!(lattice, Geometry::Mesh(m) if m.beam_lattice.is_some()));
+    let displaced = &a.resources.get_object(ResourceId(3)).unwrap().geometry;
+    assert!(matches!(displaced, Geometry::DisplacementMesh(_)));
+    assert_eq!(
+        a.resources
+            .get_slice_stack(ResourceId(1))
+            .unwrap()
+            .slices
+            .len(),
+        1
+    );
+    assert_eq!(a.content_hash(), b.content_hash());
+    for id in 1..4 {
+        assert_eq!(
+            a.object_hash(ResourceId(id + 1)),
+            b.object_hash(ResourceId(id + 50))
+        );
+    }
+    assert_ne!(
+        a.content_hash(),
+        extension_model("millimeter", "12", 1).content_hash()
+    );
+}
+
+#[test]
+fn test_content_hash_of_lazy_meshes() {
+    let eager = parse(MODEL_A);
+    let mut lazy = parse_model_lazy(Cursor::new(MODEL_A), "3D/3dmodel.model").unwrap();
+
+    // Without the part's bytes the mesh can't be hashed.
+    assert!(lazy.object_hash(ResourceId(2)).is_none());
+    assert_ne!(lazy.content_hash(), eager.content_hash());
+
+    lazy.attachments
+        .insert("3D/3dmodel.model".to_string(), MODEL_A.as_bytes().to_vec());
+    assert_eq!(lazy.content_hash(), eager.content_hash());
+    assert_eq!(
+        lazy.object_hash(ResourceId(2)),
+        eager.object_hash(ResourceId(2))
+    );
+}
@@ -0,0 +1,156 @@
+//! Warnings for geometry far from the origin, and recentering of the build.
+#![cfg(feature = "testutil")]
+
+use glam::{Mat4, Vec3};
+use lib3mf_core::model::{
+    BuildItem, Component, Components, Geometry, Mesh, Model, Object, ObjectType, ResourceId, Unit,
+};
+use lib3mf_core::testutil::meshes::cuboid;
+use lib3mf_core::validation::{ValidationLevel, ValidationReport};
+
+/// 10-unit cube with its corner at `origin`.
+fn cube(origin: f32) -> Mesh {
+    let mut mesh = cuboid([10.0; 3], 1);
+    for v in &mut mesh.vertices {
+        (v.x, v.y, v.z) = (v.x + origin, v.y + origin, v.z + origin);
+    }
+    mesh
+}
+
+fn object(id: u32, geometry: Geometry) -> Object {
+    Object {
+        id: ResourceId(id),
+        object_type: ObjectType::Model,
+        name: None,
+        part_number: None,
+        uuid: None,
+        pid: None,
+        pindex: None,
+        thumbnail: None,
+        geometry,
+    }
+}
+
+/// A model placing a cube with its corner at `origin` by `transform`.
+fn model_with(origin: f32, transform: Mat4) -> Model {
+    let mut model = Model::default();
+    model
+        .resources
+        .add_object(object(1, Geometry::Mesh(cube(origin))))
+        .unwrap();
+    model
+        .build
+        .items
+        .push(BuildItem::new(ResourceId(1), transform));
+    model
+}
+
+fn codes(report: &ValidationReport) -> Vec<u32> {
+    report
+        .items
+        .iter()
+        .map(|item| item.code)
+        .filter(|code| matches!(code, 4013 | 4014))
+        .collect()
+}
+
+fn far() -> Mat4 {
+    Mat4::from_translation(Vec3::new(3.0e6, -2.0e6, 100.0))
+}
+
+#[test]
+fn test_far_build_item_is_recentered() {
+    let mut model = model_with(0.0, far());
+    assert_eq!(codes(&model.validate(ValidationLevel::Standard)), [4014]);
+
+    let offset = model.recenter();
+    assert_eq!(offset, Vec3::new(-3_000_005.0, 1_999_995.0, -105.0));
+    let bounds = model.build_bounds().unwrap();
+    assert_eq!((bounds.min, bounds.max), ([-5.0; 3], [5.0; 3]));
+    assert!(codes(&model.validate(ValidationLevel::Standard)).is_empty());
+    assert_eq!(model.recenter_offset(), Some(offset));
+
+    // Recentering again moves nothing and records nothing new.
+    assert_eq!(model.recenter(), Vec3::ZERO);
+    assert_eq!(model.recenter_offset(), Some(offset));
+}
+
+#[test]
+fn test_recenter_offsets_accumulate() {
+    let mut model = model_with(0.0, far());
+    let first = model.recenter();
+    model.build.items[0].transform = Mat4::from_translation(Vec3::new(1000.0, 0.0, 0.0));
+    let second = model.recenter();
+    assert_eq!(second, Vec3::new(-1005.0, -5.0, -5.0));
+    assert_eq!(model.recenter_offset(), Some(first + second));
+}
+
+#[test]
+fn test_far_vertices_are_flagged() {
+    // The mesh itself lies far out: moving the build item can't restore its precision.
+    let model = model_with(1.0e7, Mat4::IDENTITY);
+    assert_eq!(
+        codes(&model.validate(ValidationLevel::Standard)),
+        [4013, 4014]
+    );
+
+    // Minimal validation doesn't check coordinates.
+    assert!(codes(&model.validate(ValidationLevel::Minimal)).is_empty());
+}
+
+#[test]
+fn test_limit_is_in_millimetres() {
+    // 10 m from the origin is beyond the limit; 2 m in microns is not.
+    let mut model = model_with(10.0, Mat4::IDENTITY);
+    model.unit = Unit::Meter;
+    assert_eq!(
+        codes(&model.validate(ValidationLevel::Standard)),
+        [4013, 4014]
+    );
+
+    let mut model = model_with(2.0e6, Mat4::IDENTITY);
+    model.unit = Unit::Micron;
+    assert!(codes(&model.validate(ValidationLevel::Standard)).is_empty());
+}
+
+#[test]
+fn test_components_are_bounded() {
+    let mut model = model_with(0.0, Mat4::IDENTITY);
+    model.build.items.clear();
+    model
+        .resources
+        .add_object(object(
+            2,
+            Geometry::Components(Components {
+                components: vec![Component {
+                    object_id: ResourceId(1),
+                    path: None,
+                    uuid: None,
+                    transform: far(),
+                }],
+            }),
+        ))
+        .unwrap();
+    model
+        .build
+        .items
+        .push(BuildItem::new(ResourceId(2), Mat4::IDENTITY));
+    assert_eq!(codes(&model.validate(ValidationLevel::Standard)), [4014]);
+
+    model.recenter();
+    let bounds = model.build_item_bounds(0).unwrap();
+    assert_eq!((bounds.min, bounds.max), ([-5.0; 3], [5.0; 3]));
+}
+
+#[cfg(feature = "writer")]
+#[test]
+fn test_recenter_offset_is_written() {
+    let mut model = model_with(0.0, far());
+    let offset = model.recenter();
+
+    let mut xml = Vec::new();
+    model.write_xml(&mut xml, None).unwrap();
+    let parsed = lib3mf_core::parser::parse_model(std::io::Cursor::new(xml)).unwrap();
+    assert_eq!(parsed.recenter_offset(), Some(offset));
+    assert!(codes(&parsed.validate(ValidationLevel::Standard)).is_empty());
+}
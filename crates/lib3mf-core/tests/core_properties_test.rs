@@ -0,0 +1,107 @@
+#![cfg(feature = "writer")]
+//! OPC core properties read from and written to `docProps/core.xml`.
+
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, opc};
+use lib3mf_core::model::{
+    CORE_PROPERTIES_PATH, CORE_PROPERTIES_REL_TYPE, CoreProperties, Model, read_core_properties,
+};
+use lib3mf_core::parser::parse_core_properties;
+use std::io::Cursor;
+
+const CORE_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<coreProperties xmlns="http://schemas.openxmlformats.org/package/2006/metadata/core-properties"
+    xmlns:d="http://purl.org/dc/elements/1.1/" xmlns:t="http://purl.org/dc/terms/"
+    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+  <d:title> Bracket </d:title>
+  <d:creator>Jane Doe</d:creator>
+  <d:subject></d:subject>
+  <t:created xsi:type="t:W3CDTF">2024-05-01T09:30:00Z</t:created>
+  <t:modified xsi:type="t:W3CDTF">2024-05-02T10:00:00Z</t:modified>
+  <x:custom xmlns:x="urn:example">ignored</x:custom>
+</coreProperties>"#;
+
+fn written(model: &Model) -> ZipArchiver<Cursor<Vec<u8>>> {
+    let mut buffer = Cursor::new(Vec::new());
+    model.write(&mut buffer).unwrap();
+    ZipArchiver::new(Cursor::new(buffer.into_inner())).unwrap()
+}
+
+#[test]
+fn test_parse_core_properties() {
+    let properties = parse_core_properties(CORE_XML.as_bytes()).unwrap();
+    assert_eq!(
+        properties,
+        CoreProperties {
+            title: Some("Bracket".to_string()),
+            creator: Some("Jane Doe".to_string()),
+            created: Some("2024-05-01T09:30:00Z".to_string()),
+            modified: Some("2024-05-02T10:00:00Z".to_string()),
+            ..Default::default()
+        }
+    );
+    assert!(parse_core_properties(b"<Types />").is_err());
+}
+
+#[test]
+fn test_core_properties_round_trip() {
+    let mut model = Model::default();
+    assert!(
+        read_core_properties(&mut written(&model))
+            .unwrap()
+            .is_none()
+    );
+
+    let properties = CoreProperties {
+        title: Some("Bracket & clip".to_string()),
+        creator: Some("Jane Doe".to_string()),
+        keywords: Some("bracket, clip".to_string()),
+        created: Some("2024-05-01T09:30:00Z".to_string()),
+        ..Default::default()
+    };
+    model.core_properties = Some(properties.clone());
+    let mut archive = written(&model);
+
+    assert_eq!(
+        read_core_properties(&mut archive).unwrap(),
+        Some(properties)
+    );
+
+    let rels = opc::parse_relationships(&archive.read_entry("_rels/.rels").unwrap()).unwrap();
+    let rel = rels
+        .iter()
+        .find(|rel| rel.rel_type == CORE_PROPERTIES_REL_TYPE)
+        .expect("core properties relationship");
+    assert_eq!(rel.target, "/docProps/core.xml");
+
+    let content_types =
+        String::from_utf8(archive.read_entry("[Content_Types].xml").unwrap()).unwrap();
+    assert!(content_types.contains(
+        r#"PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml""#
+    ));
+}
+
+#[test]
+fn test_core_properties_replace_attachment() {
+    let mut model = Model::default();
+    model.attachments.insert(
+        CORE_PROPERTIES_PATH.to_string(),
+        CORE_XML.as_bytes().to_vec(),
+    );
+    model.core_properties = Some(CoreProperties {
+        title: Some("Edited".to_string()),
+        ..Default::default()
+    });
+    let mut archive = written(&model);
+
+    let entries = archive.list_entries().unwrap();
+    assert_eq!(
+        entries
+            .iter()
+            .filter(|e| *e == CORE_PROPERTIES_PATH)
+            .count(),
+        1
+    );
+    let properties = read_core_properties(&mut archive).unwrap().unwrap();
+    assert_eq!(properties.title.as_deref(), Some("Edited"));
+    assert_eq!(properties.creator, None);
+}
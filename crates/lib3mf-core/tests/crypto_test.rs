@@ -416,6 +416,7 @@ fn test_keystore_add_resource_group() {
                 wrapped_key: vec![0x05, 0x06, 0x07, 0x08],
             },
         ],
+        resource_paths: Vec::new(),
     });
 
     assert_eq!(
@@ -455,6 +456,7 @@ fn test_keystore_structure() {
                 algorithm: "RSA-OAEP".to_string(),
                 wrapped_key: vec![0xDE, 0xAD, 0xBE, 0xEF],
             }],
+            resource_paths: vec!["/3D/3dmodel.model".to_string()],
         }],
     };
 
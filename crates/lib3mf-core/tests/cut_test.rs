@@ -0,0 +1,228 @@
+//! Plane cuts with capped faces and alignment pins.
+#![cfg(feature = "testutil")]
+
+use lib3mf_core::Lib3mfError;
+use lib3mf_core::model::{
+    BuildItem, CutOptions, Geometry, Mesh, Model, Object, ObjectType, PinOptions, Plane,
+    ResourceId, Side, TriangleSet,
+};
+use lib3mf_core::testutil::meshes::cuboid;
+use std::collections::HashMap;
+
+/// Appends an axis-aligned box; `inward` flips its winding, as for a cavity surface.
+fn add_box(mesh: &mut Mesh, min: f32, size: f32, inward: bool) {
+    let base = mesh.vertices.len() as u32;
+    for i in 0..8 {
+        let bit = |b: u32| if i >> b & 1 == 1 { min + size } else { min };
+        mesh.add_vertex(bit(0), bit(1), bit(2));
+    }
+    for [a, b, c, d] in [
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+    ] {
+        let [a, b, c, d] = [a, b, c, d].map(|v| base + v);
+        if inward {
+            mesh.add_triangle(a, c, b);
+            mesh.add_triangle(a, d, c);
+        } else {
+            mesh.add_triangle(a, b, c);
+            mesh.add_triangle(a, c, d);
+        }
+    }
+}
+
+/// Every directed edge is matched by exactly one opposite edge.
+fn assert_closed(mesh: &Mesh) {
+    let mut edges: HashMap<(u32, u32), i32> = HashMap::new();
+    for t in &mesh.triangles {
+        for (a, b) in [(t.v1, t.v2), (t.v2, t.v3), (t.v3, t.v1)] {
+            *edges.entry((a, b)).or_default() += 1;
+        }
+    }
+    for (&(a, b), &n) in &edges {
+        assert_eq!(n, 1, "edge {a}-{b} used {n} times");
+        assert_eq!(edges.get(&(b, a)), Some(&1), "edge {a}-{b} is open");
+    }
+}
+
+fn volume(mesh: &Mesh) -> f64 {
+    mesh.compute_area_and_volume().1
+}
+
+#[test]
+fn test_cut_caps_both_halves() {
+    let mesh = cuboid([10.0; 3], 1);
+    let cut = mesh.cut(&"z=4".parse().unwrap()).unwrap();
+    assert_closed(&cut.above);
+    assert_closed(&cut.below);
+    assert!((volume(&cut.above) - 600.0).abs() < 1e-3);
+    assert!((volume(&cut.below) - 400.0).abs() < 1e-3);
+    assert!(cut.above.vertices.iter().all(|v| v.z >= 4.0 - 1e-5));
+
+    // Oblique plane through three corners' neighbourhood
+    let plane: Plane = "1,1,1,12".parse().unwrap();
+    let cut = mesh.cut(&plane).unwrap();
+    assert_closed(&cut.above);
+    assert_closed(&cut.below);
+    assert!((volume(&cut.above) + volume(&cut.below) - 1000.0).abs() < 1e-2);
+
+    // Plane along a face: everything on one side, unchanged
+    let cut = mesh.cut(&"z=0".parse().unwrap()).unwrap();
+    assert_eq!(cut.above, mesh);
+    assert!(cut.below.triangles.is_empty());
+
+    let only_below = CutOptions {
+        keep: Side::Below,
+        ..Default::default()
+    };
+    let cut = mesh
+        .cut_with(&"x=2.5".parse().unwrap(), &only_below)
+        .unwrap();
+    assert!(cut.above.triangles.is_empty());
+    assert!((volume(&cut.below) - 250.0).abs() < 1e-3);
+}
+
+#[test]
+fn test_cut_hollow_mesh_caps_rings_and_keeps_data() {
+    let mut mesh = Mesh::new();
+    add_box(&mut mesh, 0.0, 10.0, false);
+    add_box(&mut mesh, 2.0, 6.0, true);
+    let mut set = TriangleSet::new("Sides", "sides");
+    // Side faces of the outer box cross the plane and are split in two
+    set.triangles = vec![4, 5];
+    mesh.triangle_sets.push(set);
+    mesh.triangle_attributes.set("paint_color", 4, "8");
+
+    let cut = mesh.cut(&"z=5".parse().unwrap()).unwrap();
+    // Triangle 4 rises from the bottom edge, so its larger part is below
+    for (half, painted) in [(&cut.above, 1), (&cut.below, 2)] {
+        assert_closed(half);
+        assert!(
+            (volume(half) - (500.0 - 108.0)).abs() < 1e-3,
+            "{}",
+            volume(half)
+        );
+        assert_eq!(half.triangle_sets[0].triangles.len(), 3);
+        let paint = half.triangle_attributes.channel("paint_color").unwrap();
+        assert_eq!(paint.len(), painted);
+        assert!(paint.keys().all(|t| half.triangle_sets[0].contains(*t)));
+    }
+}
+
+#[test]
+fn test_cut_alignment_pins() {
+    let mesh = cuboid([40.0; 3], 1);
+    let pins = PinOptions {
+        diameter: 4.0,
+        length: 5.0,
+        clearance: 0.25,
+        ..Default::default()
+    };
+    let options = CutOptions {
+        pins: Some(pins.clone()),
+        ..Default::default()
+    };
+    let cut = mesh.cut_with(&"z=20".parse().unwrap(), &options).unwrap();
+    assert_eq!(cut.pins.len(), 2);
+    assert!(cut.pins[0].distance(cut.pins[1]) >= 8.0);
+    assert!(cut.pins.iter().all(|p| (p.z - 20.0).abs() < 1e-4));
+    assert_closed(&cut.above);
+    assert_closed(&cut.below);
+
+    // Regular 16-gon prisms
+    let section = |r: f64| 8.0 * r * r * (std::f64::consts::TAU / 16.0).sin();
+    let peg = section(2.0) * 5.0;
+    let socket = section(2.25) * 5.25;
+    assert!((volume(&cut.below) - (32000.0 + 2.0 * peg)).abs() < 1e-2);
+    assert!((volume(&cut.above) - (32000.0 - 2.0 * socket)).abs() < 1e-2);
+    let top = cut.below.vertices.iter().map(|v| v.z).fold(0.0, f32::max);
+    assert!((top - 25.0).abs() < 1e-4);
+
+    // No room on a thin slab
+    let cut = cuboid([6.0; 3], 1)
+        .cut_with(&"z=3".parse().unwrap(), &options)
+        .unwrap();
+    assert!(cut.pins.is_empty());
+
+    let one_side = CutOptions {
+        keep: Side::Above,
+        pins: Some(pins),
+        ..Default::default()
+    };
+    assert!(matches!(
+        mesh.cut_with(&"z=20".parse().unwrap(), &one_side),
+        Err(Lib3mfError::Validation(_))
+    ));
+}
+
+#[test]
+fn test_model_cut_in_build_coordinates() {
+    let mut model = Model::default();
+    model
+        .resources
+        .add_object(Object {
+            id: ResourceId(1),
+            object_type: ObjectType::Model,
+            name: Some("Tower".into()),
+            part_number: None,
+            uuid: None,
+            pid: None,
+            pindex: None,
+            thumbnail: None,
+            geometry: Geometry::Mesh(cuboid([10.0; 3], 1)),
+        })
+        .unwrap();
+    // Same object twice: on the plate, and lifted clear of the cut
+    model
+        .add_build_item(ResourceId(1), glam::Mat4::IDENTITY)
+        .unwrap();
+    model
+        .add_build_item(
+            ResourceId(1),
+            glam::Mat4::from_translation(glam::Vec3::new(20.0, 0.0, 4.0)),
+        )
+        .unwrap();
+    let mut lifted = BuildItem::new(
+        ResourceId(1),
+        glam::Mat4::from_translation(glam::Vec3::new(40.0, 0.0, 50.0)),
+    );
+    lifted.part_number = Some("lifted".into());
+    model.build.items.push(lifted);
+
+    let report = model.cut(&"z=8".parse().unwrap(), Side::Below).unwrap();
+    assert_eq!(report.items_cut, 2);
+    assert_eq!(report.items_removed, 1);
+    // The first two items get copies; the last one, cut away, takes the object with it
+    assert_eq!(report.new_objects, vec![ResourceId(2), ResourceId(3)]);
+    assert!(model.resources.get_object(ResourceId(1)).is_none());
+
+    let heights: Vec<(u32, f32)> = model
+        .build
+        .items
+        .iter()
+        .map(|item| {
+            let object = model.resources.get_object(item.object_id).unwrap();
+            let Geometry::Mesh(mesh) = &object.geometry else {
+                panic!("not a mesh");
+            };
+            let top = mesh.vertices.iter().map(|v| v.z).fold(f32::MIN, f32::max);
+            (item.object_id.0, top + item.transform.w_axis.z)
+        })
+        .collect();
+    assert_eq!(heights, vec![(2, 8.0), (3, 8.0)]);
+
+    let report = model.cut(&"x=5".parse().unwrap(), Side::Both).unwrap();
+    assert_eq!(report.items_cut, 1);
+    assert_eq!(model.build.items.len(), 3);
+    let below = model.resources.get_object(model.build.items[1].object_id);
+    assert_eq!(below.unwrap().name.as_deref(), Some("Tower (below)"));
+
+    assert!("w=3".parse::<Plane>().is_err());
+    assert!("0,0,0,1".parse::<Plane>().is_err());
+    assert!("1,2,3".parse::<Plane>().is_err());
+    assert_eq!("below".parse::<Side>().unwrap(), Side::Below);
+}
@@ -0,0 +1,165 @@
+//! Duplicate resource detection and merging.
+
+#![cfg(feature = "writer")]
+
+use lib3mf_core::model::{DuplicateKind, Geometry, Model, PackageFilter, ResourceId};
+use lib3mf_core::parser::parse_model;
+use std::io::Cursor;
+
+const TETRAHEDRON: &str = r#"<mesh>
+    <vertices>
+        <vertex x="0" y="0" z="0" />
+        <vertex x="10" y="0" z="0" />
+        <vertex x="0" y="10" z="0" />
+        <vertex x="0" y="0" z="10" />
+    </vertices>
+    <triangles>
+        <triangle v1="0" v2="2" v3="1" />
+        <triangle v1="0" v2="1" v3="3" />
+        <triangle v1="0" v2="3" v3="2" />
+        <triangle v1="1" v2="2" v3="3" PROPS />
+    </triangles>
+</mesh>"#;
+
+fn model() -> Model {
+    let mesh = |props: &str| TETRAHEDRON.replace("PROPS", props);
+    let xml = format!(
+        r##"<model unit="millimeter"
+    xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:m="http://schemas.microsoft.com/3dmanufacturing/material/2015/02">
+    <resources>
+        <m:basematerials id="1"><m:base name="PLA" displaycolor="#FFFFFF" /></m:basematerials>
+        <m:basematerials id="2"><m:base name="PLA" displaycolor="#FFFFFF" /></m:basematerials>
+        <m:basematerials id="3"><m:base name="PETG" displaycolor="#FFFFFF" /></m:basematerials>
+        <m:texture2d id="4" path="/3D/Textures/a.png" contenttype="image/png" />
+        <m:texture2d id="5" path="/3D/Textures/b.png" contenttype="image/png" />
+        <m:texture2dgroup id="6" texid="4">
+            <m:tex2coord u="0" v="0" /><m:tex2coord u="1" v="0" /><m:tex2coord u="0" v="1" />
+        </m:texture2dgroup>
+        <m:texture2dgroup id="7" texid="5">
+            <m:tex2coord u="0" v="0" /><m:tex2coord u="1" v="0" /><m:tex2coord u="0" v="1" />
+        </m:texture2dgroup>
+        <object id="8" name="first" pid="1" pindex="0">{plain}</object>
+        <object id="9" name="copy" pid="1" pindex="0">{plain}</object>
+        <object id="10" pid="2" pindex="0">{plain}</object>
+        <object id="11" pid="3" pindex="0">{plain}</object>
+        <object id="12" pid="1" pindex="0">{textured_a}</object>
+        <object id="13" pid="1" pindex="0">{textured_b}</object>
+        <object id="14"><components><component objectid="10" /></components></object>
+    </resources>
+    <build>
+        <item objectid="8" />
+        <item objectid="9" />
+        <item objectid="11" />
+        <item objectid="12" />
+        <item objectid="13" />
+        <item objectid="14" />
+    </build>
+</model>"##,
+        plain = mesh(""),
+        textured_a = mesh(r#"pid="6" p1="0" p2="1" p3="2""#),
+        textured_b = mesh(r#"pid="7" p1="0" p2="1" p3="2""#),
+    );
+    let mut model = parse_model(Cursor::new(xml)).unwrap();
+    model
+        .attachments
+        .insert("3D/Textures/a.png".to_string(), vec![7; 100]);
+    model
+        .attachments
+        .insert("3D/Textures/b.png".to_string(), vec![7; 100]);
+    model
+}
+
+#[test]
+fn test_find_duplicate_resources() {
+    let model = model();
+    let report = model.find_duplicate_resources();
+
+    let groups: Vec<_> = report
+        .groups
+        .iter()
+        .map(|g| {
+            (
+                g.kind,
+                g.kept.0,
+                g.duplicates.iter().map(|d| d.0).collect(),
+                g.exact,
+            )
+        })
+        .collect();
+    assert_eq!(
+        groups,
+        vec![
+            (DuplicateKind::BaseMaterials, 1, vec![2], true),
+            (DuplicateKind::Texture, 4, vec![5], false),
+            (DuplicateKind::TextureGroup, 6, vec![7], false),
+            // Names differ, but the copy points at material 1 too; object 10 uses material 2
+            (DuplicateKind::Object, 8, vec![9, 10], false),
+            (DuplicateKind::Object, 12, vec![13], false),
+        ]
+    );
+    assert_eq!(report.images.len(), 1);
+    assert_eq!(report.images[0].kept, "3D/Textures/a.png");
+    assert_eq!(report.images[0].duplicates, ["3D/Textures/b.png"]);
+    assert_eq!(report.bytes_saved, 100);
+    assert_eq!(report.vertices_saved, 12);
+    assert_eq!(report.triangles_saved, 12);
+    assert_eq!(report.duplicate_ids(), [2, 5, 7, 9, 10, 13].map(ResourceId));
+
+    // Finding changes nothing
+    assert_eq!(model.resources.iter_objects().count(), 7);
+}
+
+#[test]
+fn test_deduplicate_resources_keeps_content() {
+    let mut model = model();
+    let before = model.content_hash();
+
+    let report = model.deduplicate_resources().unwrap();
+    assert_eq!(report.duplicate_ids().len(), 6);
+    assert_eq!(model.content_hash(), before);
+
+    let ids: Vec<u32> = model.resources.iter_ids().map(|id| id.0).collect();
+    for id in [2, 5, 7, 9, 10, 13] {
+        assert!(!ids.contains(&id), "{id} still in {ids:?}");
+    }
+    let items: Vec<u32> = model.build.items.iter().map(|i| i.object_id.0).collect();
+    assert_eq!(items, [8, 8, 11, 12, 12, 14]);
+    match &model.resources.get_object(ResourceId(14)).unwrap().geometry {
+        Geometry::Components(c) => assert_eq!(c.components[0].object_id, ResourceId(8)),
+        other => panic!("expected components, got {other:?}"),
+    }
+    assert_eq!(
+        model
+            .resources
+            .get_object(ResourceId(8))
+            .unwrap()
+            .name
+            .as_deref(),
+        Some("first")
+    );
+    assert!(!model.attachments.contains_key("3D/Textures/b.png"));
+    assert!(model.attachments.contains_key("3D/Textures/a.png"));
+
+    // Merged models round-trip and have nothing left to merge
+    let mut buffer = Cursor::new(Vec::new());
+    model.write(&mut buffer).unwrap();
+    assert!(model.find_duplicate_resources().is_empty());
+    assert!(model.deduplicate_resources().unwrap().is_empty());
+}
+
+#[test]
+fn test_filter_merges_duplicates() {
+    let mut model = model();
+    let report = PackageFilter {
+        duplicates: true,
+        ..Default::default()
+    }
+    .apply(&mut model);
+    assert_eq!(
+        report.resources_removed,
+        [2, 5, 7, 9, 10, 13].map(ResourceId)
+    );
+    assert_eq!(report.attachments_removed, ["3D/Textures/b.png"]);
+    assert_eq!(report.bytes_removed, 100);
+}
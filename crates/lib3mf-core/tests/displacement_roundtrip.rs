@@ -1,5 +1,7 @@
 //! Integration tests for Displacement Extension
 
+#![cfg(feature = "writer")]
+
 use lib3mf_core::model::*;
 use lib3mf_core::parser::parse_model;
 use std::io::Cursor;
@@ -101,6 +103,168 @@ fn test_normal_validation() {
     assert!(!report.has_errors());
 }
 
+#[test]
+fn test_gradients_and_displacement_indices_roundtrip() {
+    let model = create_indexed_test_model(TEXTURE);
+
+    let mut buffer = Vec::new();
+    model.write_xml(&mut buffer, None).unwrap();
+    let parsed = parse_model(Cursor::new(&buffer)).unwrap();
+
+    assert_eq!(
+        parsed.resources.get_object(ResourceId(1)).unwrap().geometry,
+        model.resources.get_object(ResourceId(1)).unwrap().geometry
+    );
+}
+
+#[test]
+fn test_parse_legacy_gradient_elements() {
+    // Written by earlier versions of the writer
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:d="http://schemas.microsoft.com/3dmanufacturing/displacement/2024/01">
+    <resources>
+        <object id="1" type="model">
+            <d:displacementmesh>
+                <d:vertices>
+                    <d:vertex x="0" y="0" z="0" />
+                </d:vertices>
+                <d:triangles />
+                <d:normvectors>
+                    <d:normvector nx="0" ny="0" nz="1" />
+                </d:normvectors>
+                <d:disp2dgroups>
+                    <d:disp2dgroup>
+                        <d:tex2dcoord gu="0.5" gv="0.25" />
+                    </d:disp2dgroup>
+                </d:disp2dgroups>
+            </d:displacementmesh>
+        </object>
+    </resources>
+    <build />
+</model>"#;
+    let model = parse_model(Cursor::new(xml)).unwrap();
+    let Geometry::DisplacementMesh(dmesh) =
+        &model.resources.get_object(ResourceId(1)).unwrap().geometry
+    else {
+        panic!("Expected DisplacementMesh geometry");
+    };
+    assert_eq!(
+        dmesh.gradients.as_deref(),
+        Some(&[GradientVector { gu: 0.5, gv: 0.25 }][..])
+    );
+}
+
+#[test]
+fn test_displacement_index_validation() {
+    use lib3mf_core::validation::ValidationLevel;
+
+    let model = create_indexed_test_model(TEXTURE);
+    let report = model.validate(ValidationLevel::Standard);
+    assert!(!report.items.iter().any(|i| i.code == 5014));
+
+    let mut model = create_indexed_test_model(TEXTURE);
+    for obj in model.resources.iter_objects_mut() {
+        if let Geometry::DisplacementMesh(dmesh) = &mut obj.geometry {
+            dmesh.triangles[0].d3 = Some(3);
+        }
+    }
+    let report = model.validate(ValidationLevel::Standard);
+    assert!(report.items.iter().any(|i| i.code == 5014));
+
+    // Indices without any displacement coordinates are out of bounds too
+    let mut model = create_indexed_test_model(TEXTURE);
+    for obj in model.resources.iter_objects_mut() {
+        if let Geometry::DisplacementMesh(dmesh) = &mut obj.geometry {
+            dmesh.gradients = None;
+        }
+    }
+    let report = model.validate(ValidationLevel::Standard);
+    assert!(report.items.iter().any(|i| i.code == 5014));
+}
+
+#[test]
+fn test_displacement_texture_part_validation() {
+    use lib3mf_core::validation::ValidationLevel;
+
+    let mut model = create_indexed_test_model(TEXTURE);
+    let report = model.validate(ValidationLevel::Paranoid);
+    assert!(report.items.iter().any(|i| i.code == 5002));
+
+    // Attachment keys are package paths without the leading slash
+    model.attachments.insert(
+        "3D/Textures/height.png".to_string(),
+        b"\x89PNG\r\n\x1a\n....".to_vec(),
+    );
+    let report = model.validate(ValidationLevel::Paranoid);
+    assert!(
+        !report
+            .items
+            .iter()
+            .any(|i| i.code == 5002 || i.code == 5005)
+    );
+
+    model
+        .attachments
+        .insert("3D/Textures/height.png".to_string(), b"not a png".to_vec());
+    let report = model.validate(ValidationLevel::Paranoid);
+    assert!(report.items.iter().any(|i| i.code == 5005));
+}
+
+#[test]
+fn test_package_links_displacement_texture() {
+    use lib3mf_core::archive::{ArchiveReader, ZipArchiver, opc};
+
+    let mut model = create_indexed_test_model("/3D/Displacement/height.png");
+    model.attachments.insert(
+        "3D/Displacement/height.png".to_string(),
+        b"\x89PNG\r\n\x1a\n".to_vec(),
+    );
+
+    let mut buffer = Cursor::new(Vec::new());
+    model.write(&mut buffer).unwrap();
+    let mut archive = ZipArchiver::new(Cursor::new(buffer.into_inner())).unwrap();
+    let rels =
+        opc::parse_relationships(&archive.read_entry("3D/_rels/3dmodel.model.rels").unwrap())
+            .unwrap();
+    assert!(
+        rels.iter()
+            .any(|r| r.target == "/3D/Displacement/height.png" && r.rel_type.ends_with("/texture"))
+    );
+}
+
+const TEXTURE: &str = "/3D/Textures/height.png";
+
+/// The test model with a displacement texture at `path`, per-vertex gradients and
+/// d-indices.
+fn create_indexed_test_model(path: &str) -> Model {
+    let mut model = create_test_model();
+    model
+        .resources
+        .add_displacement_2d(Displacement2D {
+            id: ResourceId(2),
+            path: path.to_string(),
+            channel: Channel::G,
+            tile_style: TileStyle::Wrap,
+            filter: FilterMode::Linear,
+            height: 0.5,
+            offset: 0.0,
+        })
+        .unwrap();
+    for obj in model.resources.iter_objects_mut() {
+        if let Geometry::DisplacementMesh(dmesh) = &mut obj.geometry {
+            dmesh.gradients = Some(vec![
+                GradientVector { gu: 1.0, gv: 0.0 },
+                GradientVector { gu: 0.0, gv: 1.0 },
+                GradientVector { gu: 0.5, gv: 0.5 },
+            ]);
+            let tri = &mut dmesh.triangles[0];
+            (tri.d1, tri.d2, tri.d3) = (Some(0), Some(1), Some(2));
+        }
+    }
+    model
+}
+
 fn create_test_model() -> Model {
     let mut model = Model::default();
 
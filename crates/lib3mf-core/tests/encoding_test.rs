@@ -0,0 +1,148 @@
+use lib3mf_core::parser::encoding::{DecodingReader, XmlEncoding, decode_xml};
+use lib3mf_core::parser::parse_model;
+use std::io::{BufReader, Cursor, Read};
+
+/// A model whose object name needs a surrogate pair in UTF-16.
+fn model_xml(encoding: &str, name: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="{encoding}"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    <metadata name="Title">{name}</metadata>
+    <resources>
+        <object id="1" name="{name}" type="model">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="1" y="0" z="0" />
+                    <vertex x="0" y="1" z="0" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="1" v3="2" />
+                </triangles>
+            </mesh>
+        </object>
+    </resources>
+    <build>
+        <item objectid="1" />
+    </build>
+</model>"#
+    )
+}
+
+const NAME: &str = "Zahnrad Ø20 🔧";
+
+fn utf16(text: &str, big_endian: bool, bom: bool) -> Vec<u8> {
+    let mut data = Vec::new();
+    if bom {
+        data.extend_from_slice(if big_endian {
+            &[0xFE, 0xFF]
+        } else {
+            &[0xFF, 0xFE]
+        });
+    }
+    for unit in text.encode_utf16() {
+        data.extend(if big_endian {
+            unit.to_be_bytes()
+        } else {
+            unit.to_le_bytes()
+        });
+    }
+    data
+}
+
+fn assert_parses(data: Vec<u8>, expected: &str) {
+    let model = parse_model(Cursor::new(data)).unwrap();
+    assert_eq!(model.metadata.get("Title"), Some(expected));
+    let object = model
+        .resources
+        .get_object(lib3mf_core::model::ResourceId(1));
+    assert_eq!(object.unwrap().name.as_deref(), Some(expected));
+    assert_eq!(model.build.items.len(), 1);
+}
+
+#[test]
+fn test_utf8_with_and_without_bom() {
+    let xml = model_xml("UTF-8", NAME);
+    assert_parses(xml.clone().into_bytes(), NAME);
+
+    let mut data = vec![0xEF, 0xBB, 0xBF];
+    data.extend_from_slice(xml.as_bytes());
+    assert_parses(data, NAME);
+}
+
+#[test]
+fn test_utf16_both_byte_orders() {
+    let xml = model_xml("UTF-16", NAME);
+    for big_endian in [false, true] {
+        for bom in [true, false] {
+            assert_parses(utf16(&xml, big_endian, bom), NAME);
+        }
+    }
+}
+
+#[test]
+fn test_utf16_split_across_small_reads() {
+    // Tiny buffers split code units and surrogate pairs between reads
+    let data = utf16(&model_xml("UTF-16", NAME), false, true);
+    let model = parse_model(BufReader::with_capacity(3, Cursor::new(data))).unwrap();
+    assert_eq!(model.metadata.get("Title"), Some(NAME));
+}
+
+#[test]
+fn test_invalid_input_is_rejected() {
+    // 3MF allows only UTF-8 and UTF-16
+    for label in ["ISO-8859-1", "Shift_JIS"] {
+        let err = parse_model(Cursor::new(model_xml(label, "x"))).unwrap_err();
+        let expected = format!("Unsupported XML encoding '{}'", label);
+        assert!(err.to_string().contains(&expected), "{}", err);
+    }
+
+    // Lone low surrogate
+    let mut data = utf16(&model_xml("UTF-16", "x"), false, true);
+    let at = data.len() - 4;
+    data[at..at + 2].copy_from_slice(&0xDC00u16.to_le_bytes());
+    assert!(parse_model(Cursor::new(data)).is_err());
+}
+
+#[test]
+fn test_forced_encoding_and_decode_xml() {
+    let xml = model_xml("UTF-16", NAME);
+    let data = utf16(&xml, true, false);
+
+    let mut reader = DecodingReader::with_encoding(&data[..], XmlEncoding::Utf16Be);
+    let mut decoded = String::new();
+    reader.read_to_string(&mut decoded).unwrap();
+    assert_eq!(decoded, xml);
+
+    assert_eq!(&*decode_xml(&data).unwrap(), xml.as_bytes());
+    assert!(matches!(
+        decode_xml(xml.as_bytes()).unwrap(),
+        std::borrow::Cow::Borrowed(_)
+    ));
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallel_parse_of_utf16() {
+    let data = utf16(&model_xml("UTF-16", NAME), false, true);
+    let model = lib3mf_core::parser::parse_model_parallel(&data).unwrap();
+    let object = model
+        .resources
+        .get_object(lib3mf_core::model::ResourceId(1));
+    assert_eq!(object.unwrap().name.as_deref(), Some(NAME));
+}
+
+#[test]
+fn test_lazy_mesh_of_utf16_part() {
+    let data = utf16(&model_xml("UTF-16", NAME), true, true);
+    let model =
+        lib3mf_core::parser::parse_model_lazy(Cursor::new(&data), "3D/3dmodel.model").unwrap();
+    let object = model
+        .resources
+        .get_object(lib3mf_core::model::ResourceId(1));
+    let lib3mf_core::model::Geometry::LazyMesh(lazy) = &object.unwrap().geometry else {
+        panic!("expected a lazy mesh");
+    };
+    let mesh = lazy.load_from(&data).unwrap();
+    assert_eq!(mesh.triangles.len(), 1);
+}
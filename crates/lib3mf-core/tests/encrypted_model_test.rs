@@ -0,0 +1,161 @@
+//! Detection and decryption of packages whose model part is encrypted.
+
+use lib3mf_core::archive::{
+    ArchiveReader, ZipArchiver, check_model_not_encrypted, find_model_path, inspect_secure_content,
+};
+use lib3mf_core::error::Lib3mfError;
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+
+const MODEL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+  <resources>
+    <object id="1" type="model">
+      <mesh>
+        <vertices>
+          <vertex x="0" y="0" z="0"/><vertex x="1" y="0" z="0"/><vertex x="0" y="1" z="0"/>
+        </vertices>
+        <triangles><triangle v1="0" v2="1" v3="2"/></triangles>
+      </mesh>
+    </object>
+  </resources>
+  <build><item objectid="1"/></build>
+</model>"#;
+
+const RELS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rel0" Target="/3D/3dmodel.model" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel"/>
+  <Relationship Id="rel1" Target="/Secure/keystore.xml" Type="http://schemas.microsoft.com/3dmanufacturing/2019/04/keystore"/>
+</Relationships>"#;
+
+fn package(keystore: &[u8], model: &[u8]) -> ZipArchiver<Cursor<Vec<u8>>> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    writer.start_file("_rels/.rels", options).unwrap();
+    writer.write_all(RELS.as_bytes()).unwrap();
+    writer.start_file("Secure/keystore.xml", options).unwrap();
+    writer.write_all(keystore).unwrap();
+    writer.start_file("3D/3dmodel.model", options).unwrap();
+    writer.write_all(model).unwrap();
+    let buffer = writer.finish().unwrap().into_inner();
+    ZipArchiver::new(Cursor::new(buffer)).unwrap()
+}
+
+#[test]
+fn test_encrypted_model_is_reported_with_keystore_details() {
+    let keystore = br#"<keystore xmlns="http://schemas.microsoft.com/3dmanufacturing/securecontent/2019/04" UUID="2b1e7a4c-0d2f-4c59-9a4e-5b2b8e7f9c10">
+  <consumer consumerid="alice"/>
+  <consumer consumerid="bob"/>
+  <resourcedatagroup keyuuid="8f4c1a20-7d3b-4e0e-b2a6-1c9d5e3f7a21">
+    <resourcedata path="/3D/3dmodel.model"/>
+  </resourcedatagroup>
+</keystore>"#;
+    let mut archiver = package(keystore, &[0x8d, 0x13, 0x77, 0x02]);
+
+    let model_path = find_model_path(&mut archiver).unwrap();
+    let err = check_model_not_encrypted(&mut archiver, &model_path).unwrap_err();
+    let Lib3mfError::EncryptedContent(info) = err else {
+        panic!("expected EncryptedContent, got {err:?}");
+    };
+    assert_eq!(info.keystore_path, "Secure/keystore.xml");
+    assert_eq!(
+        info.keystore_uuid.as_deref(),
+        Some("2b1e7a4c-0d2f-4c59-9a4e-5b2b8e7f9c10")
+    );
+    assert_eq!(info.consumers, ["alice", "bob"]);
+    assert_eq!(info.encrypted_parts, ["3D/3dmodel.model"]);
+}
+
+#[test]
+fn test_keystore_without_encrypted_model_is_not_an_error() {
+    let keystore = br#"<keystore UUID="2b1e7a4c-0d2f-4c59-9a4e-5b2b8e7f9c10">
+  <resourcedatagroup keyuuid="8f4c1a20-7d3b-4e0e-b2a6-1c9d5e3f7a21">
+    <resourcedata path="/3D/Textures/secret.png"/>
+  </resourcedatagroup>
+</keystore>"#;
+    let mut archiver = package(keystore, MODEL.as_bytes());
+
+    check_model_not_encrypted(&mut archiver, "3D/3dmodel.model").unwrap();
+    let info = inspect_secure_content(&mut archiver).unwrap().unwrap();
+    assert_eq!(info.encrypted_parts, ["3D/Textures/secret.png"]);
+}
+
+#[test]
+fn test_package_without_keystore_has_no_secure_content() {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    writer
+        .start_file("3D/3dmodel.model", SimpleFileOptions::default())
+        .unwrap();
+    writer.write_all(MODEL.as_bytes()).unwrap();
+    let buffer = writer.finish().unwrap().into_inner();
+    let mut archiver = ZipArchiver::new(Cursor::new(buffer)).unwrap();
+
+    assert!(inspect_secure_content(&mut archiver).unwrap().is_none());
+    assert!(archiver.entry_exists("3D/3dmodel.model"));
+}
+
+#[cfg(all(feature = "crypto", feature = "writer"))]
+mod decryption {
+    use super::*;
+    use lib3mf_core::crypto::encryption::encrypt_aes256gcm;
+    use lib3mf_core::model::{Consumer, KeyStore};
+    use lib3mf_core::parser::parse_encrypted_model;
+    use lib3mf_core::writer::keystore_writer::write_keystore;
+    use rand::rngs::OsRng;
+    use rsa::{RsaPrivateKey, RsaPublicKey};
+    use uuid::Uuid;
+
+    /// Builds a package whose model part is encrypted for `alice`, returning it and alice's key.
+    fn encrypted_package() -> (ZipArchiver<Cursor<Vec<u8>>>, RsaPrivateKey) {
+        let alice = RsaPrivateKey::new(&mut OsRng, 1024).unwrap();
+        let mut store = KeyStore::new(Uuid::new_v4());
+        store
+            .add_consumer(Consumer {
+                id: "alice".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        let (key_uuid, cek) = store.create_resource_data_group().unwrap();
+        store
+            .grant_access(key_uuid, "alice", &cek, &RsaPublicKey::from(&alice))
+            .unwrap();
+        store
+            .resource_data_group_mut(key_uuid)
+            .unwrap()
+            .resource_paths
+            .push("/3D/3dmodel.model".to_string());
+
+        let (ciphertext, nonce) = encrypt_aes256gcm(&cek, MODEL.as_bytes()).unwrap();
+        let mut part = nonce;
+        part.extend_from_slice(&ciphertext);
+
+        let mut keystore = Vec::new();
+        write_keystore(&mut keystore, &store).unwrap();
+        (package(&keystore, &part), alice)
+    }
+
+    #[test]
+    fn test_parse_encrypted_model_decrypts_model_part() {
+        let (mut archiver, alice) = encrypted_package();
+
+        let err = check_model_not_encrypted(&mut archiver, "3D/3dmodel.model").unwrap_err();
+        assert!(
+            matches!(err, Lib3mfError::EncryptedContent(ref info) if info.consumers == ["alice"])
+        );
+
+        let model = parse_encrypted_model(&mut archiver, &alice).unwrap();
+        assert_eq!(model.resources.iter_objects().count(), 1);
+        assert_eq!(model.build.items.len(), 1);
+        let store = model.resources.key_store.as_ref().unwrap();
+        assert_eq!(store.consumers[0].id, "alice");
+    }
+
+    #[test]
+    fn test_parse_encrypted_model_rejects_unknown_key() {
+        let (mut archiver, _) = encrypted_package();
+        let mallory = RsaPrivateKey::new(&mut OsRng, 1024).unwrap();
+
+        let err = parse_encrypted_model(&mut archiver, &mallory).unwrap_err();
+        assert!(matches!(err, Lib3mfError::EncryptionError(_)));
+    }
+}
@@ -59,3 +59,20 @@ fn test_error_invalid_zip() {
     }
     let _ = std::fs::remove_file(path);
 }
+
+#[test]
+fn test_error_unknown_unit() {
+    let xml = r#"<model unit="MicroMeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"><resources/><build/></model>"#;
+    let result = lib3mf_core::parser::parse_model(std::io::Cursor::new(xml));
+    match result {
+        Err(Lib3mfError::Validation(msg)) => assert!(msg.contains("MicroMeter")),
+        other => panic!(
+            "Expected unit validation error, got {:?}",
+            other.map(|m| m.unit)
+        ),
+    }
+
+    let xml = r#"<model unit="micron" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"><resources/><build/></model>"#;
+    let model = lib3mf_core::parser::parse_model(std::io::Cursor::new(xml)).unwrap();
+    assert_eq!(model.unit, lib3mf_core::model::Unit::Micron);
+}
@@ -408,7 +408,8 @@ fn test_extension_roundtrip() -> anyhow::Result<()> {
                 y: 10.0,
                 z: 0.0,
             },
-        ],
+        ]
+        .into(),
         triangles: vec![lib3mf_core::model::Triangle {
             v1: 0,
             v2: 1,
@@ -417,8 +418,13 @@ fn test_extension_roundtrip() -> anyhow::Result<()> {
             p1: None,
             p2: None,
             p3: None,
-        }],
+        }]
+        .into(),
         beam_lattice: None,
+        volume_id: None,
+        triangle_sets: Vec::new(),
+        triangle_attributes: Default::default(),
+        normals: None,
     };
 
     let object = lib3mf_core::model::Object {
@@ -442,6 +448,7 @@ fn test_extension_roundtrip() -> anyhow::Result<()> {
         part_number: None,
         transform: glam::Mat4::IDENTITY,
         printable: None,
+        metadata: Default::default(),
     });
 
     // Verify programmatically created model structure
@@ -0,0 +1,176 @@
+//! Extension identification, model header parsing and extension use detection.
+
+#![cfg(feature = "writer")]
+
+use lib3mf_core::error::Lib3mfError;
+use lib3mf_core::model::*;
+use lib3mf_core::parser::{
+    ParseOptions, RequiredExtensionPolicy, parse_model, parse_model_header,
+    parse_model_with_options,
+};
+use lib3mf_core::validation::ValidationLevel;
+use std::io::Cursor;
+
+const MODEL: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:m="http://schemas.microsoft.com/3dmanufacturing/material/2015/02"
+    xmlns:t="http://schemas.microsoft.com/3dmanufacturing/trianglesets/2021/07"
+    xmlns:v="http://schemas.microsoft.com/3dmanufacturing/volumetric/2018/11"
+    xmlns:vendor="http://example.com/vendor"
+    requiredextensions="m t" recommendedextensions="vendor">
+    <resources>
+        <m:colorgroup id="1">
+            <m:color color="#FF0000" />
+        </m:colorgroup>
+        <object id="2" type="model">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="1" y="0" z="0" />
+                    <vertex x="0" y="1" z="0" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="1" v3="2" />
+                </triangles>
+            </mesh>
+        </object>
+    </resources>
+    <build>
+        <item objectid="2" />
+    </build>
+</model>"##;
+
+#[test]
+fn test_parse_model_header() {
+    let header = parse_model_header(Cursor::new(MODEL)).unwrap();
+    assert_eq!(header.namespace(""), Some(CORE_NAMESPACE));
+    assert_eq!(
+        header.namespace("vendor"),
+        Some("http://example.com/vendor")
+    );
+    assert_eq!(header.required_extensions, ["m", "t"]);
+    assert_eq!(header.recommended_extensions, ["vendor"]);
+
+    let declared: Vec<Extension> = header.declared_extensions().into_iter().collect();
+    assert_eq!(
+        declared,
+        [
+            Extension::Materials,
+            Extension::TriangleSets,
+            Extension::Volumetric
+        ]
+    );
+}
+
+#[test]
+fn test_parse_model_header_rejects_other_roots() {
+    assert!(parse_model_header(Cursor::new("<resources/>")).is_err());
+    assert!(parse_model_header(Cursor::new("")).is_err());
+}
+
+#[test]
+fn test_used_extensions() {
+    let mut model = parse_model(Cursor::new(MODEL)).unwrap();
+    let used: Vec<Extension> = model.used_extensions().into_iter().collect();
+    assert_eq!(used, [Extension::Materials]);
+
+    model.build.items[0].uuid = Some(uuid::Uuid::new_v4());
+    assert!(model.used_extensions().contains(&Extension::Production));
+}
+
+#[test]
+fn test_extension_namespaces() {
+    for ext in Extension::ALL {
+        assert_eq!(Extension::from_namespace(ext.namespace()), Some(ext));
+    }
+    assert_eq!(Extension::from_namespace(CORE_NAMESPACE), None);
+}
+
+fn warn_options() -> ParseOptions {
+    ParseOptions {
+        unsupported_extensions: RequiredExtensionPolicy::Warn,
+        ..Default::default()
+    }
+}
+
+fn write(model: &Model) -> String {
+    let mut buffer = Vec::new();
+    model.write_xml(&mut buffer, None).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+#[test]
+fn test_required_extensions_parsed() {
+    let model = parse_model(Cursor::new(MODEL)).unwrap();
+    assert_eq!(
+        model.required_extensions,
+        [
+            Extension::Materials.namespace(),
+            Extension::TriangleSets.namespace()
+        ]
+    );
+}
+
+#[test]
+fn test_unsupported_required_extension_policy() {
+    let xml = MODEL.replace(
+        r#"requiredextensions="m t""#,
+        r#"requiredextensions="m vendor""#,
+    );
+    let err = parse_model(Cursor::new(&xml)).unwrap_err();
+    assert!(
+        matches!(err, Lib3mfError::UnsupportedExtension(ref msg) if msg.contains("http://example.com/vendor")),
+        "{err}"
+    );
+
+    let model = parse_model_with_options(Cursor::new(&xml), &warn_options()).unwrap();
+    assert_eq!(model.required_extensions[1], "http://example.com/vendor");
+    let report = model.validate(ValidationLevel::Minimal);
+    assert!(report.items.iter().any(|i| i.code == 1003));
+
+    // An undeclared prefix is kept as is
+    let xml = MODEL.replace(r#"requiredextensions="m t""#, r#"requiredextensions="q""#);
+    let err = parse_model(Cursor::new(&xml)).unwrap_err();
+    assert!(err.to_string().contains("'q' has no namespace declaration"));
+    let model = parse_model_with_options(Cursor::new(&xml), &warn_options()).unwrap();
+    assert_eq!(model.required_extensions, ["q"]);
+}
+
+#[test]
+fn test_writer_requires_used_extensions() {
+    // Materials are safe to ignore, so nothing is required
+    let mut model = parse_model(Cursor::new(MODEL)).unwrap();
+    assert!(model.extensions_to_require().is_empty());
+    assert!(!write(&model).contains("requiredextensions"));
+
+    // Build items in other parts need the Production extension
+    model.build.items[0].uuid = Some(uuid::Uuid::new_v4());
+    assert!(model.extensions_to_require().is_empty());
+    model.build.items[0].path = Some("/3D/other.model".to_string());
+    assert_eq!(
+        model
+            .extensions_to_require()
+            .into_iter()
+            .collect::<Vec<_>>(),
+        [Extension::Production]
+    );
+    let xml = write(&model);
+    assert!(xml.contains(r#"requiredextensions="p""#));
+    let parsed = parse_model(Cursor::new(&xml)).unwrap();
+    assert_eq!(
+        parsed.required_extensions,
+        [Extension::Production.namespace()]
+    );
+}
+
+#[test]
+fn test_writer_keeps_unsupported_required_extensions() {
+    let xml = MODEL.replace(
+        r#"requiredextensions="m t""#,
+        r#"requiredextensions="vendor""#,
+    );
+    let model = parse_model_with_options(Cursor::new(&xml), &warn_options()).unwrap();
+    let written = write(&model);
+    assert!(written.contains(r#"requiredextensions="vendor""#));
+    assert!(parse_model(Cursor::new(&written)).is_err());
+}
@@ -0,0 +1,164 @@
+#![cfg(feature = "writer")]
+
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::error::Lib3mfError;
+use lib3mf_core::model::{Model, ResourceId};
+use lib3mf_core::parser::extract::extract_object;
+use lib3mf_core::parser::parse_model;
+use lib3mf_core::writer::opc_writer::{write_content_types, write_relationships};
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{Cursor, Write};
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+const MESH: &str = r#"<vertices>
+        <vertex x="0" y="0" z="0" />
+        <vertex x="1" y="0" z="0" />
+        <vertex x="0" y="1" z="0" />
+    </vertices>"#;
+
+/// A package with a textured mesh (10) used by an assembly (20), and an unrelated mesh
+/// (11) with its own color group.
+fn package() -> Vec<u8> {
+    let xml = format!(
+        r##"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:m="http://schemas.microsoft.com/3dmanufacturing/material/2015/02">
+    <resources>
+        <basematerials id="1">
+            <base name="PLA" displaycolor="#FF0000" />
+        </basematerials>
+        <m:colorgroup id="2">
+            <m:color color="#00FF00" />
+        </m:colorgroup>
+        <m:texture2d id="3" path="/3D/Textures/wood.png" contenttype="image/png" />
+        <m:texture2dgroup id="4" texid="3">
+            <m:tex2coord u="0" v="0" />
+            <m:tex2coord u="1" v="0" />
+            <m:tex2coord u="0" v="1" />
+        </m:texture2dgroup>
+        <object id="10" type="model" pid="1" pindex="0" name="Textured">
+            <mesh>{MESH}
+                <triangles>
+                    <triangle v1="0" v2="1" v3="2" pid="4" p1="0" p2="1" p3="2" />
+                </triangles>
+            </mesh>
+        </object>
+        <object id="11" type="model" pid="2" pindex="0">
+            <mesh>{MESH}
+                <triangles>
+                    <triangle v1="0" v2="1" v3="2" />
+                </triangles>
+            </mesh>
+        </object>
+        <object id="20" type="model">
+            <components>
+                <component objectid="10" />
+            </components>
+        </object>
+    </resources>
+    <build>
+        <item objectid="20" transform="1 0 0 0 1 0 0 0 1 5 0 0" />
+        <item objectid="11" />
+    </build>
+</model>"##
+    );
+    // Written by hand so the package holds exactly this XML
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    zip.start_file("3D/3dmodel.model", options).unwrap();
+    zip.write_all(xml.as_bytes()).unwrap();
+    zip.start_file("3D/Textures/wood.png", options).unwrap();
+    zip.write_all(b"not really a png").unwrap();
+    zip.start_file("_rels/.rels", options).unwrap();
+    write_relationships(&mut zip, "/3D/3dmodel.model", None).unwrap();
+    zip.start_file("[Content_Types].xml", options).unwrap();
+    write_content_types(&mut zip, ["3D/Textures/wood.png"]).unwrap();
+    zip.finish().unwrap().into_inner()
+}
+
+fn extract(data: Vec<u8>, id: u32) -> lib3mf_core::error::Result<(Vec<u8>, BTreeSet<u32>)> {
+    let mut archive = ZipArchiver::new(Cursor::new(data))?;
+    let mut out = Cursor::new(Vec::new());
+    let summary = extract_object(&mut archive, ResourceId(id), &mut out)?;
+    let ids = summary.parts.values().flatten().map(|id| id.0).collect();
+    Ok((out.into_inner(), ids))
+}
+
+fn root_model(archive: &mut ZipArchiver<Cursor<Vec<u8>>>) -> Model {
+    let path = find_model_path(archive).unwrap();
+    parse_model(Cursor::new(archive.read_entry(&path).unwrap())).unwrap()
+}
+
+#[test]
+fn test_extracts_object_with_references() {
+    let (out, ids) = extract(package(), 20).unwrap();
+    assert_eq!(ids, BTreeSet::from([1, 3, 4, 10, 20]));
+
+    let mut archive = ZipArchiver::new(Cursor::new(out)).unwrap();
+    let model = root_model(&mut archive);
+    let objects: BTreeSet<u32> = model.resources.iter_objects().map(|o| o.id.0).collect();
+    assert_eq!(objects, BTreeSet::from([10, 20]));
+    assert!(model.resources.get_base_materials(ResourceId(1)).is_some());
+    assert!(model.resources.get_color_group(ResourceId(2)).is_none());
+    assert!(
+        model
+            .resources
+            .get_texture_2d_group(ResourceId(4))
+            .is_some()
+    );
+    assert_eq!(
+        archive.read_entry("3D/Textures/wood.png").unwrap(),
+        b"not really a png"
+    );
+
+    // Only the extracted object's build item is kept, with its transform
+    assert_eq!(model.build.items.len(), 1);
+    assert_eq!(model.build.items[0].object_id, ResourceId(20));
+    assert_eq!(model.build.items[0].transform.w_axis.x, 5.0);
+}
+
+#[test]
+fn test_object_without_build_item_gets_one() {
+    let (out, ids) = extract(package(), 11).unwrap();
+    assert_eq!(ids, BTreeSet::from([2, 11]));
+
+    let mut archive = ZipArchiver::new(Cursor::new(out)).unwrap();
+    let model = root_model(&mut archive);
+    assert_eq!(model.build.items.len(), 1);
+    assert_eq!(model.build.items[0].object_id, ResourceId(11));
+
+    let (out, _) = extract(package(), 10).unwrap();
+    let model = root_model(&mut ZipArchiver::new(Cursor::new(out)).unwrap());
+    assert_eq!(model.build.items.len(), 1);
+    assert_eq!(model.build.items[0].object_id, ResourceId(10));
+    assert_eq!(model.build.items[0].transform, glam::Mat4::IDENTITY);
+}
+
+#[test]
+fn test_missing_or_non_object_id_is_rejected() {
+    assert!(matches!(
+        extract(package(), 99),
+        Err(Lib3mfError::ResourceNotFound(99))
+    ));
+    let err = extract(package(), 1).unwrap_err();
+    assert!(err.to_string().contains("not an object"));
+}
+
+#[test]
+fn test_follows_components_into_other_parts() {
+    let file = File::open("../../models/Benchy.3mf").unwrap();
+    let mut archive = ZipArchiver::new(file).unwrap();
+    let mut out = Cursor::new(Vec::new());
+    let summary = extract_object(&mut archive, ResourceId(8), &mut out).unwrap();
+
+    let part = &summary.parts["/3D/Objects/object_1.model"];
+    assert_eq!(part.len(), 7);
+    assert_eq!(summary.resource_count(), 8);
+
+    let mut extracted = ZipArchiver::new(Cursor::new(out.into_inner())).unwrap();
+    let model = root_model(&mut extracted);
+    let stats = model.compute_stats(&mut extracted).unwrap();
+    assert_eq!(stats.geometry.triangle_count, 226654);
+}
@@ -0,0 +1,187 @@
+#![cfg(feature = "writer")]
+
+use lib3mf_core::model::{Geometry, Model, PackageFilter, ResourceId};
+use lib3mf_core::parser::parse_model;
+use std::io::Cursor;
+
+const XML: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xml:lang="en-US"
+    xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:m="http://schemas.microsoft.com/3dmanufacturing/material/2015/02"
+    xmlns:Vendor="http://example.com/vendor">
+    <metadata name="Title">Part</metadata>
+    <metadata name="Vendor:Version">7</metadata>
+    <resources>
+        <m:basematerials id="1">
+            <m:base name="Red" displaycolor="#FF0000" />
+        </m:basematerials>
+        <m:basematerials id="2">
+            <m:base name="Unused" displaycolor="#00FF00" />
+        </m:basematerials>
+        <m:texture2d id="3" path="/3D/Textures/wood.png" contenttype="image/png" />
+        <m:texture2dgroup id="4" texid="3">
+            <m:tex2coord u="0" v="0" />
+            <m:tex2coord u="1" v="0" />
+            <m:tex2coord u="0" v="1" />
+        </m:texture2dgroup>
+        <object id="5" type="model" pid="1" pindex="0" thumbnail="/Metadata/part.png">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="1" y="0" z="0" />
+                    <vertex x="0" y="1" z="0" />
+                    <vertex x="0" y="0" z="1" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="2" v3="1" pid="4" p1="0" p2="1" p3="2" />
+                    <triangle v1="0" v2="1" v3="3" />
+                </triangles>
+            </mesh>
+        </object>
+        <object id="6" type="model">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="1" y="0" z="0" />
+                    <vertex x="0" y="1" z="0" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="1" v3="2" />
+                </triangles>
+            </mesh>
+        </object>
+    </resources>
+    <build>
+        <item objectid="5" />
+    </build>
+</model>"##;
+
+fn model() -> Model {
+    let mut model = parse_model(Cursor::new(XML)).unwrap();
+    for path in [
+        "Metadata/thumbnail.png",
+        "Metadata/part.png",
+        "Metadata/slicer.config",
+        "3D/Textures/wood.png",
+    ] {
+        model.attachments.insert(path.to_string(), vec![0; 10]);
+    }
+    model
+}
+
+fn triangles(model: &Model) -> Vec<lib3mf_core::model::Triangle> {
+    match &model.resources.get_object(ResourceId(5)).unwrap().geometry {
+        Geometry::Mesh(mesh) => mesh.triangles.to_vec(),
+        _ => panic!("expected mesh"),
+    }
+}
+
+#[test]
+fn test_filter_nothing() {
+    let mut model = model();
+    let report = PackageFilter::default().apply(&mut model);
+    assert!(report.is_empty());
+    assert_eq!(model.attachments.len(), 4);
+}
+
+#[test]
+fn test_filter_thumbnails() {
+    let mut model = model();
+    let filter = PackageFilter {
+        thumbnails: true,
+        ..Default::default()
+    };
+    let report = filter.apply(&mut model);
+    assert_eq!(
+        report.attachments_removed,
+        ["Metadata/part.png", "Metadata/thumbnail.png"]
+    );
+    assert_eq!(report.bytes_removed, 20);
+    assert!(model.attachments.contains_key("3D/Textures/wood.png"));
+    assert!(
+        model
+            .resources
+            .get_object(ResourceId(5))
+            .unwrap()
+            .thumbnail
+            .is_none()
+    );
+}
+
+#[test]
+fn test_filter_textures() {
+    let mut model = model();
+    let filter = PackageFilter {
+        textures: true,
+        ..Default::default()
+    };
+    let report = filter.apply(&mut model);
+    assert_eq!(report.resources_removed, [ResourceId(3), ResourceId(4)]);
+    assert_eq!(report.attachments_removed, ["3D/Textures/wood.png"]);
+
+    // The textured face falls back to the object's base material.
+    let tris = triangles(&model);
+    assert_eq!((tris[0].pid, tris[0].p1), (None, None));
+    assert_eq!(
+        model.resources.get_object(ResourceId(5)).unwrap().pid,
+        Some(ResourceId(1))
+    );
+    assert!(
+        !model
+            .validate(lib3mf_core::validation::ValidationLevel::Standard)
+            .has_errors()
+    );
+}
+
+#[test]
+fn test_filter_vendor_metadata() {
+    let mut model = model();
+    let filter = PackageFilter {
+        vendor_metadata: true,
+        ..Default::default()
+    };
+    let report = filter.apply(&mut model);
+    assert_eq!(report.metadata_removed, ["Vendor:Version"]);
+    assert_eq!(report.attachments_removed, ["Metadata/slicer.config"]);
+    assert!(model.metadata.contains_key("Title"));
+    assert!(model.extra_namespaces.is_empty());
+}
+
+#[test]
+fn test_filter_unused_resources() {
+    let mut model = model();
+    let filter = PackageFilter {
+        unused_resources: true,
+        ..Default::default()
+    };
+    let report = filter.apply(&mut model);
+    assert_eq!(report.resources_removed, [ResourceId(2), ResourceId(6)]);
+    assert!(report.attachments_removed.is_empty());
+    assert!(
+        model
+            .resources
+            .get_texture_2d_group(ResourceId(4))
+            .is_some()
+    );
+}
+
+#[test]
+fn test_filter_all_roundtrip() {
+    let mut model = model();
+    let report = PackageFilter::all().apply(&mut model);
+    assert_eq!(model.attachments.len(), 0);
+    assert_eq!(
+        report.resources_removed,
+        [ResourceId(2), ResourceId(3), ResourceId(4), ResourceId(6)]
+    );
+
+    let mut buf = Cursor::new(Vec::new());
+    model.write(&mut buf).unwrap();
+    let mut archiver =
+        lib3mf_core::archive::ZipArchiver::new(Cursor::new(buf.into_inner())).unwrap();
+    let path = lib3mf_core::archive::find_model_path(&mut archiver).unwrap();
+    let data = lib3mf_core::archive::ArchiveReader::read_entry(&mut archiver, &path).unwrap();
+    let reparsed = parse_model(Cursor::new(data)).unwrap();
+    assert_eq!(reparsed.resources.iter_objects().count(), 1);
+    assert!(!reparsed.metadata.contains_key("Vendor:Version"));
+}
@@ -0,0 +1,161 @@
+#![cfg(feature = "writer")]
+
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::model::{ChecksumFailure, Geometry, Model, Package, ResourceId};
+use lib3mf_core::parser::parse_model;
+use lib3mf_core::validation::ValidationLevel;
+use lib3mf_core::writer::package_writer::PackageWriter;
+use std::io::Cursor;
+
+fn two_objects() -> Model {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    <resources>
+        <object id="1" type="model">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="10" y="0" z="0" />
+                    <vertex x="0" y="10" z="0" />
+                    <vertex x="0" y="0" z="10" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="2" v3="1" />
+                    <triangle v1="0" v2="1" v3="3" />
+                    <triangle v1="1" v2="2" v3="3" />
+                    <triangle v1="0" v2="3" v3="2" />
+                </triangles>
+            </mesh>
+        </object>
+        <object id="2" type="model">
+            <components>
+                <component objectid="1" transform="1 0 0 0 1 0 0 0 1 20 0 0" />
+            </components>
+        </object>
+    </resources>
+    <build>
+        <item objectid="2" />
+    </build>
+</model>"#;
+    parse_model(Cursor::new(xml)).unwrap()
+}
+
+fn write_and_reload(model: &Model, checksums: bool) -> Model {
+    let mut buffer = Cursor::new(Vec::new());
+    PackageWriter::new(&mut buffer)
+        .with_geometry_checksums(checksums)
+        .write(&Package::new(model.clone()))
+        .unwrap();
+
+    let mut archiver = ZipArchiver::new(Cursor::new(buffer.into_inner())).unwrap();
+    let path = find_model_path(&mut archiver).unwrap();
+    let data = archiver.read_entry(&path).unwrap();
+    parse_model(Cursor::new(data)).unwrap()
+}
+
+fn checksum_codes(model: &Model) -> Vec<u32> {
+    model
+        .validate(ValidationLevel::Standard)
+        .items
+        .iter()
+        .map(|i| i.code)
+        .filter(|c| (2060..=2062).contains(c))
+        .collect()
+}
+
+#[test]
+fn test_checksums_survive_roundtrip() {
+    let loaded = write_and_reload(&two_objects(), true);
+
+    assert!(loaded.metadata.contains_key("lib3mf:sha256.1"));
+    assert!(loaded.metadata.contains_key("lib3mf:sha256.2"));
+    assert!(loaded.extra_namespaces.contains_key("lib3mf"));
+    assert!(loaded.verify_geometry_checksums().is_empty());
+    assert!(checksum_codes(&loaded).is_empty());
+
+    // Off by default
+    let plain = write_and_reload(&two_objects(), false);
+    assert!(!plain.has_geometry_checksums());
+}
+
+#[test]
+fn test_modified_geometry_fails_validation() {
+    let mut loaded = write_and_reload(&two_objects(), true);
+    for obj in loaded.resources.iter_objects_mut() {
+        if let Geometry::Mesh(mesh) = &mut obj.geometry {
+            mesh.vertices[3].z = 12.0;
+        }
+    }
+
+    // Object 2 references object 1, so both checksums fail.
+    let errors = loaded.verify_geometry_checksums();
+    let ids: Vec<_> = errors.iter().map(|e| e.object_id).collect();
+    assert_eq!(ids, vec![Some(ResourceId(1)), Some(ResourceId(2))]);
+    assert!(matches!(
+        errors[0].failure,
+        ChecksumFailure::Mismatch { .. }
+    ));
+    assert_eq!(checksum_codes(&loaded), vec![2060, 2060]);
+
+    // Re-embedding refreshes the stale entries.
+    loaded.embed_geometry_checksums();
+    assert!(loaded.verify_geometry_checksums().is_empty());
+}
+
+#[test]
+fn test_malformed_and_orphaned_checksums_warn() {
+    let mut model = two_objects();
+    model.embed_geometry_checksums();
+    model
+        .metadata
+        .insert("lib3mf:sha256.1".to_string(), "not-a-digest".to_string());
+    let digest = model.metadata["lib3mf:sha256.2"].clone();
+    model.metadata.insert("lib3mf:sha256.9".to_string(), digest);
+
+    let errors = model.verify_geometry_checksums();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].failure, ChecksumFailure::Malformed);
+    assert_eq!(errors[1].failure, ChecksumFailure::MissingObject);
+
+    let report = model.validate(ValidationLevel::Standard);
+    assert!(!report.has_errors());
+    assert_eq!(checksum_codes(&model), vec![2062, 2061]);
+}
+
+#[test]
+fn test_external_parts_need_attachments() {
+    let part = two_objects();
+    let mut part_xml = Vec::new();
+    part.write_xml(&mut part_xml, None).unwrap();
+
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:p="http://schemas.microsoft.com/3dmanufacturing/production/2015/06">
+    <resources>
+        <object id="5" type="model">
+            <components>
+                <component objectid="1" p:path="/3D/Objects/part.model" />
+            </components>
+        </object>
+    </resources>
+    <build>
+        <item objectid="5" />
+    </build>
+</model>"#;
+    let mut model = parse_model(Cursor::new(xml)).unwrap();
+
+    // Without the part loaded the checksum can't be computed.
+    assert_eq!(model.embed_geometry_checksums(), 0);
+
+    model
+        .attachments
+        .insert("3D/Objects/part.model".to_string(), part_xml);
+    assert_eq!(model.embed_geometry_checksums(), 1);
+    assert!(model.verify_geometry_checksums().is_empty());
+
+    model.attachments.clear();
+    let errors = model.verify_geometry_checksums();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].failure, ChecksumFailure::Unverifiable);
+    assert!(!model.validate(ValidationLevel::Standard).has_errors());
+}
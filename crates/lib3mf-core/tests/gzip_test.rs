@@ -0,0 +1,91 @@
+//! Parsing of gzip-compressed model XML.
+
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use lib3mf_core::parser::encoding::is_xml;
+use lib3mf_core::parser::{gzip, parse_model, parse_model_with_budget};
+use std::io::{Cursor, Write};
+
+const MODEL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="centimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    <resources>
+        <object id="1" name="Triangle" type="model">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="1" y="0" z="0" />
+                    <vertex x="0" y="1" z="0" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="1" v3="2" />
+                </triangles>
+            </mesh>
+        </object>
+    </resources>
+    <build>
+        <item objectid="1" />
+    </build>
+</model>"#;
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn test_parse_model_decompresses_gzip() {
+    let plain = parse_model(Cursor::new(MODEL)).unwrap();
+    let compressed = parse_model(Cursor::new(gzip(MODEL.as_bytes()))).unwrap();
+    assert_eq!(compressed.unit.as_str(), "centimeter");
+    assert_eq!(
+        compressed.resources.iter_objects().count(),
+        plain.resources.iter_objects().count()
+    );
+    assert_eq!(compressed.build.items.len(), 1);
+
+    let budgeted = parse_model_with_budget(Cursor::new(gzip(MODEL.as_bytes())), 1 << 20).unwrap();
+    assert_eq!(budgeted.resources.iter_objects().count(), 1);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parse_model_parallel_decompresses_gzip() {
+    let model = lib3mf_core::parser::parse_model_parallel(&gzip(MODEL.as_bytes())).unwrap();
+    assert_eq!(model.resources.iter_objects().count(), 1);
+}
+
+#[test]
+fn test_concatenated_members_form_one_document() {
+    let (head, tail) = MODEL.split_at(MODEL.len() / 2);
+    let mut data = gzip(head.as_bytes());
+    data.extend(gzip(tail.as_bytes()));
+    assert_eq!(&*gzip::decompress(&data).unwrap(), MODEL.as_bytes());
+    assert!(parse_model(Cursor::new(data)).is_ok());
+}
+
+#[test]
+fn test_corrupt_gzip_is_an_error() {
+    let mut data = gzip(MODEL.as_bytes());
+    data.truncate(data.len() / 2);
+    assert!(gzip::decompress(&data).is_err());
+    assert!(parse_model(Cursor::new(data)).is_err());
+}
+
+#[test]
+fn test_peek_reads_into_truncated_streams() {
+    let data = gzip(MODEL.as_bytes());
+    assert_eq!(&*gzip::peek(&data[..data.len() / 2], 5), b"<?xml");
+    assert_eq!(&*gzip::peek(b"PK\x03\x04rest", 4), b"PK\x03\x04");
+    assert_eq!(&*gzip::decompress(b"plain").unwrap(), b"plain");
+}
+
+#[test]
+fn test_is_xml() {
+    assert!(is_xml(b"<?xml version=\"1.0\"?>"));
+    assert!(is_xml(b"\xEF\xBB\xBF\r\n  <model>"));
+    assert!(is_xml(b"\xFF\xFE<\x00?\x00"));
+    assert!(!is_xml(b"solid cube"));
+    assert!(!is_xml(b"PK\x03\x04"));
+    assert!(!is_xml(b""));
+}
@@ -0,0 +1,299 @@
+//! Function-based Volumetric and Implicit Extension resources: parsing, roundtrip,
+//! validation and ID remapping.
+
+#![cfg(feature = "writer")]
+
+use lib3mf_core::model::*;
+use lib3mf_core::parser::parse_model;
+use lib3mf_core::validation::ValidationLevel;
+use std::collections::HashMap;
+use std::io::Cursor;
+
+/// A sphere level set (object 10) bounded by a box mesh (5) with volume data (9)
+/// coloring it from a 3D image (7, sampled by function 8).
+const MODEL: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:vol="http://schemas.3mf.io/3dmanufacturing/volumetric/2022/01"
+    xmlns:i="http://schemas.3mf.io/3dmanufacturing/implicit/2023/12">
+    <resources>
+        <basematerials id="1">
+            <base name="Hard" displaycolor="#FFFFFF" />
+            <base name="Soft" displaycolor="#000000" />
+        </basematerials>
+        <object id="5" type="model">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="10" y="0" z="0" />
+                    <vertex x="0" y="10" z="0" />
+                    <vertex x="0" y="0" z="10" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="2" v3="1" />
+                    <triangle v1="0" v2="1" v3="3" />
+                    <triangle v1="1" v2="2" v3="3" />
+                    <triangle v1="0" v2="3" v3="2" />
+                </triangles>
+            </mesh>
+        </object>
+        <i:implicitfunction id="6" identifier="sphere" displayname="Sphere">
+            <i:in>
+                <i:vector identifier="pos" displayname="position" />
+            </i:in>
+            <i:constant identifier="radius" value="4.5" tag="params">
+                <i:out>
+                    <i:scalar identifier="value" />
+                </i:out>
+            </i:constant>
+            <i:length identifier="len">
+                <i:in>
+                    <i:vectorref identifier="A" ref="inputs.pos" />
+                </i:in>
+                <i:out>
+                    <i:scalar identifier="result" />
+                </i:out>
+            </i:length>
+            <i:subtraction identifier="sdf">
+                <i:in>
+                    <i:scalarref identifier="A" ref="len.result" />
+                    <i:scalarref identifier="B" ref="radius.value" />
+                </i:in>
+                <i:out>
+                    <i:scalar identifier="result" />
+                </i:out>
+            </i:subtraction>
+            <i:constresourceid identifier="box" value="5">
+                <i:out>
+                    <i:resourceid identifier="value" />
+                </i:out>
+            </i:constresourceid>
+            <i:out>
+                <i:scalarref identifier="shape" ref="sdf.result" />
+            </i:out>
+        </i:implicitfunction>
+        <vol:image3d id="7" name="Density">
+            <vol:imagestack rowcount="16" columncount="16" sheetcount="2">
+                <vol:imagesheet path="/3D/Volume/sheet0.png" />
+                <vol:imagesheet path="/3D/Volume/sheet1.png" />
+            </vol:imagestack>
+        </vol:image3d>
+        <vol:functionfromimage3d id="8" image3did="7" valuescale="2" filter="nearest"
+            tilestyleu="clamp" tilestylew="mirror" />
+        <vol:volumedata id="9">
+            <vol:color functionid="8" channel="color" transform="0.1 0 0 0 0.1 0 0 0 0.1 0 0 0" />
+            <vol:composite basematerialid="1">
+                <vol:materialmapping functionid="8" channel="red" />
+                <vol:materialmapping functionid="8" channel="green" minfeaturesize="0.5" />
+            </vol:composite>
+            <vol:property name="density" functionid="6" channel="shape" required="true"
+                fallbackvalue="1" />
+        </vol:volumedata>
+        <object id="10" type="model" name="Ball">
+            <vol:levelset functionid="6" channel="shape" meshid="5" meshbboxonly="true"
+                minfeaturesize="0.1" volumeid="9" transform="1 0 0 0 1 0 0 0 1 -5 -5 -5" />
+        </object>
+    </resources>
+    <build>
+        <item objectid="10" />
+    </build>
+</model>"##;
+
+fn parse(xml: &str) -> Model {
+    parse_model(Cursor::new(xml.as_bytes())).unwrap()
+}
+
+fn roundtrip(model: &Model) -> Model {
+    let mut buffer = Vec::new();
+    model.write_xml(&mut buffer, None).unwrap();
+    parse_model(Cursor::new(buffer)).unwrap()
+}
+
+fn codes(model: &Model) -> Vec<u32> {
+    model
+        .validate(ValidationLevel::Standard)
+        .items
+        .iter()
+        .map(|i| i.code)
+        .collect()
+}
+
+#[test]
+fn test_parse_implicit_function() {
+    let model = parse(MODEL);
+    let Some(Function::Implicit(f)) = model.resources.get_function(ResourceId(6)) else {
+        panic!("function 6 is not an implicit function");
+    };
+    assert_eq!(f.identifier.as_deref(), Some("sphere"));
+    assert_eq!(f.inputs[0].identifier, "pos");
+    assert_eq!(f.inputs[0].port_type, PortType::Vector);
+    assert_eq!(f.outputs[0].reference.as_deref(), Some("sdf.result"));
+
+    let kinds: Vec<&str> = f.nodes.iter().map(|n| n.kind.as_str()).collect();
+    assert_eq!(
+        kinds,
+        ["constant", "length", "subtraction", "constresourceid"]
+    );
+    let radius = f.node("radius").unwrap();
+    assert_eq!(radius.attribute("value"), Some("4.5"));
+    assert_eq!(radius.tag.as_deref(), Some("params"));
+    assert_eq!(radius.outputs[0].port_type, PortType::Scalar);
+    let sdf = f.node("sdf").unwrap();
+    assert_eq!(sdf.inputs.len(), 2);
+    assert_eq!(sdf.inputs[1].reference.as_deref(), Some("radius.value"));
+}
+
+#[test]
+fn test_parse_volumetric_resources() {
+    let model = parse(MODEL);
+
+    let image = model.resources.get_image_3d(ResourceId(7)).unwrap();
+    assert_eq!(image.name.as_deref(), Some("Density"));
+    assert_eq!(image.stack.sheet_count, 2);
+    assert_eq!(image.stack.sheets[1], "/3D/Volume/sheet1.png");
+
+    let Some(Function::FromImage3D(f)) = model.resources.get_function(ResourceId(8)) else {
+        panic!("function 8 is not a function from image3d");
+    };
+    assert_eq!(f.image3d_id, ResourceId(7));
+    assert_eq!(f.value_scale, 2.0);
+    assert_eq!(f.filter, FilterMode::Nearest);
+    assert_eq!(f.tile_style_u, TileStyle::Clamp);
+    assert_eq!(f.tile_style_v, TileStyle::Wrap);
+    assert_eq!(f.tile_style_w, TileStyle::Mirror);
+
+    let data = model.resources.get_volume_data(ResourceId(9)).unwrap();
+    let color = data.color.as_ref().unwrap();
+    assert_eq!(color.channel, "color");
+    assert_eq!(color.transform.x_axis.x, 0.1);
+    let composite = data.composite.as_ref().unwrap();
+    assert_eq!(composite.base_material_id, ResourceId(1));
+    assert_eq!(composite.mappings[1].min_feature_size, 0.5);
+    assert!(data.properties[0].required);
+    assert_eq!(data.properties[0].function.fallback_value, 1.0);
+
+    let object = model.resources.get_object(ResourceId(10)).unwrap();
+    let Geometry::LevelSet(level_set) = &object.geometry else {
+        panic!("object 10 is not a level set");
+    };
+    assert_eq!(level_set.function_id, ResourceId(6));
+    assert_eq!(level_set.mesh_id, ResourceId(5));
+    assert_eq!(level_set.volume_id, Some(ResourceId(9)));
+    assert!(level_set.mesh_bbox_only);
+    assert_eq!(level_set.transform.w_axis.x, -5.0);
+}
+
+#[test]
+fn test_roundtrip_preserves_function_resources() {
+    let model = parse(MODEL);
+    let written = roundtrip(&model);
+
+    for id in [6, 8] {
+        assert_eq!(
+            written.resources.get_function(ResourceId(id)),
+            model.resources.get_function(ResourceId(id))
+        );
+    }
+    assert_eq!(
+        written.resources.get_image_3d(ResourceId(7)),
+        model.resources.get_image_3d(ResourceId(7))
+    );
+    assert_eq!(
+        written.resources.get_volume_data(ResourceId(9)),
+        model.resources.get_volume_data(ResourceId(9))
+    );
+    assert_eq!(
+        written
+            .resources
+            .get_object(ResourceId(10))
+            .unwrap()
+            .geometry,
+        model.resources.get_object(ResourceId(10)).unwrap().geometry
+    );
+}
+
+#[test]
+fn test_mesh_volume_id_roundtrip() {
+    let xml = MODEL.replace(
+        r#"<object id="5" type="model">
+            <mesh>"#,
+        r#"<object id="5" type="model">
+            <mesh vol:volumeid="9">"#,
+    );
+    let model = parse(&xml);
+    let Geometry::Mesh(mesh) = &model.resources.get_object(ResourceId(5)).unwrap().geometry else {
+        panic!("object 5 is not a mesh");
+    };
+    assert_eq!(mesh.volume_id, Some(ResourceId(9)));
+
+    let written = roundtrip(&model);
+    let Geometry::Mesh(mesh) = &written
+        .resources
+        .get_object(ResourceId(5))
+        .unwrap()
+        .geometry
+    else {
+        panic!("object 5 is not a mesh");
+    };
+    assert_eq!(mesh.volume_id, Some(ResourceId(9)));
+}
+
+#[test]
+fn test_valid_references_pass_validation() {
+    let model = parse(MODEL);
+    let codes = codes(&model);
+    assert!(
+        !codes.iter().any(|c| (2006..=2009).contains(c)),
+        "{:?}",
+        codes
+    );
+}
+
+#[test]
+fn test_broken_references_are_reported() {
+    // Missing function channel
+    let model = parse(&MODEL.replace(r#"channel="shape" meshid"#, r#"channel="nope" meshid"#));
+    assert!(codes(&model).contains(&2006));
+
+    // Level set bounded by a non-mesh
+    let model = parse(&MODEL.replace(r#"meshid="5""#, r#"meshid="1""#));
+    assert!(codes(&model).contains(&2007));
+
+    // Missing volume data
+    let model = parse(&MODEL.replace(r#"volumeid="9""#, r#"volumeid="99""#));
+    assert!(codes(&model).contains(&2008));
+
+    // Missing 3D image
+    let model = parse(&MODEL.replace(r#"image3did="7""#, r#"image3did="77""#));
+    assert!(codes(&model).contains(&2009));
+}
+
+#[test]
+fn test_remap_ids_updates_function_references() {
+    let mut model = parse(MODEL);
+    let mapping: HashMap<ResourceId, ResourceId> = [(5, 50), (6, 60), (7, 70), (9, 90)]
+        .into_iter()
+        .map(|(old, new)| (ResourceId(old), ResourceId(new)))
+        .collect();
+    model.remap_ids(&mapping).unwrap();
+
+    let Geometry::LevelSet(level_set) =
+        &model.resources.get_object(ResourceId(10)).unwrap().geometry
+    else {
+        panic!("object 10 is not a level set");
+    };
+    assert_eq!(level_set.function_id, ResourceId(60));
+    assert_eq!(level_set.mesh_id, ResourceId(50));
+    assert_eq!(level_set.volume_id, Some(ResourceId(90)));
+
+    let Some(Function::Implicit(f)) = model.resources.get_function(ResourceId(60)) else {
+        panic!("function 60 is not an implicit function");
+    };
+    assert_eq!(f.node("box").unwrap().attribute("value"), Some("50"));
+    let Some(Function::FromImage3D(f)) = model.resources.get_function(ResourceId(8)) else {
+        panic!("function 8 is not a function from image3d");
+    };
+    assert_eq!(f.image3d_id, ResourceId(70));
+    let data = model.resources.get_volume_data(ResourceId(90)).unwrap();
+    assert_eq!(data.properties[0].function.function_id, ResourceId(60));
+    assert!(codes(&model).iter().all(|c| !(2006..=2009).contains(c)));
+}
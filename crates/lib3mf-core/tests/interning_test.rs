@@ -0,0 +1,142 @@
+//! Identical vertex and triangle arrays shared at parse time with a `MeshInterner`.
+
+use lib3mf_core::archive::ZipArchiver;
+use lib3mf_core::model::{Geometry, Mesh, MeshInterner, Model, ResourceId, SharedVec, Triangle};
+use lib3mf_core::parser::{ParseOptions, parse_model, parse_model_with_options};
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+
+const TRIANGLE: &str = r#"<mesh><vertices><vertex x="0" y="0" z="0" /><vertex x="1" y="0" z="0" />
+    <vertex x="0" y="1" z="0" /></vertices>
+    <triangles><triangle v1="0" v2="1" v3="2" /></triangles></mesh>"#;
+
+const FLIPPED: &str = r#"<mesh><vertices><vertex x="0" y="0" z="0" /><vertex x="1" y="0" z="0" />
+    <vertex x="0" y="1" z="0" /></vertices>
+    <triangles><triangle v1="0" v2="2" v3="1" /></triangles></mesh>"#;
+
+fn model_xml(resources: &str, build: &str) -> String {
+    format!(
+        r#"<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:p="http://schemas.microsoft.com/3dmanufacturing/production/2015/06">
+    <resources>{resources}</resources>
+    <build>{build}</build>
+</model>"#
+    )
+}
+
+fn interning() -> ParseOptions {
+    ParseOptions {
+        intern_meshes: Some(MeshInterner::new()),
+        ..Default::default()
+    }
+}
+
+fn mesh(model: &Model, id: u32) -> &Mesh {
+    match &model.resources.get_object(ResourceId(id)).unwrap().geometry {
+        Geometry::Mesh(mesh) => mesh,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_identical_arrays_are_shared() {
+    let xml = model_xml(
+        &format!(
+            r#"<object id="1">{TRIANGLE}</object><object id="2">{TRIANGLE}</object>
+            <object id="3">{FLIPPED}</object>"#
+        ),
+        r#"<item objectid="1" /><item objectid="2" /><item objectid="3" />"#,
+    );
+    let options = interning();
+    let model = parse_model_with_options(xml.as_bytes(), &options).unwrap();
+
+    let (a, b, flipped) = (mesh(&model, 1), mesh(&model, 2), mesh(&model, 3));
+    assert!(SharedVec::ptr_eq(&a.vertices, &b.vertices));
+    assert!(SharedVec::ptr_eq(&a.triangles, &b.triangles));
+    // Same vertices, different triangles
+    assert!(SharedVec::ptr_eq(&a.vertices, &flipped.vertices));
+    assert!(!SharedVec::ptr_eq(&a.triangles, &flipped.triangles));
+
+    let stats = options.intern_meshes.as_ref().unwrap().stats();
+    assert_eq!(stats.buffers, 6);
+    assert_eq!(stats.shared_buffers, 3);
+    assert_eq!(
+        stats.deduplicated_bytes,
+        (2 * 3 * 12 + std::mem::size_of::<Triangle>()) as u64
+    );
+
+    // Interning changes where the arrays live, not what they hold
+    let plain = parse_model(xml.as_bytes()).unwrap();
+    for id in 1..=3 {
+        assert_eq!(mesh(&model, id), mesh(&plain, id));
+        assert!(!mesh(&plain, id).vertices.is_shared());
+    }
+}
+
+#[test]
+fn test_editing_an_interned_mesh_leaves_the_others() {
+    let xml = model_xml(
+        &format!(r#"<object id="1">{TRIANGLE}</object><object id="2">{TRIANGLE}</object>"#),
+        r#"<item objectid="1" />"#,
+    );
+    let mut model = parse_model_with_options(xml.as_bytes(), &interning()).unwrap();
+
+    let object = model.resources.iter_objects_mut().nth(1).unwrap();
+    let Geometry::Mesh(mesh_2) = &mut object.geometry else {
+        unreachable!()
+    };
+    mesh_2.vertices[1].x = 5.0;
+
+    assert_eq!(mesh(&model, 1).vertices[1].x, 1.0);
+    assert_eq!(mesh(&model, 2).vertices[1].x, 5.0);
+    assert!(SharedVec::ptr_eq(
+        &mesh(&model, 1).triangles,
+        &mesh(&model, 2).triangles
+    ));
+}
+
+#[test]
+fn test_stats_report_arrays_shared_across_parts() {
+    let root = model_xml(
+        &format!(
+            r#"<object id="1">{TRIANGLE}</object>
+            <object id="2"><components>
+                <component objectid="1" p:path="/3D/part.model" />
+            </components></object>"#
+        ),
+        r#"<item objectid="1" /><item objectid="2" />"#,
+    );
+    let part = model_xml(&format!(r#"<object id="1">{TRIANGLE}</object>"#), "");
+
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, content) in [("3D/3dmodel.model", &root), ("3D/part.model", &part)] {
+        writer
+            .start_file(name, SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(content.as_bytes()).unwrap();
+    }
+    let package = writer.finish().unwrap().into_inner();
+    let mut archiver = ZipArchiver::new(Cursor::new(package)).unwrap();
+
+    let options = interning();
+    let model = parse_model_with_options(root.as_bytes(), &options).unwrap();
+    let unique = model
+        .compute_stats_with(&mut archiver, &options)
+        .unwrap()
+        .geometry
+        .unique;
+    assert_eq!(unique.mesh_count, 2);
+    assert_eq!(unique.shared_buffers, 2);
+    assert_eq!(
+        unique.deduplicated_bytes,
+        (3 * 12 + std::mem::size_of::<Triangle>()) as u64
+    );
+    assert_eq!(options.intern_meshes.unwrap().stats().shared_buffers, 2);
+
+    // Parsed separately, the two meshes share nothing
+    let model = parse_model(root.as_bytes()).unwrap();
+    let unique = model.compute_stats(&mut archiver).unwrap().geometry.unique;
+    assert_eq!(unique.mesh_count, 2);
+    assert_eq!(unique.shared_buffers, 0);
+    assert_eq!(unique.deduplicated_bytes, 0);
+}
@@ -0,0 +1,165 @@
+#![cfg(feature = "writer")]
+
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::model::{Color, Geometry, JSON_SCHEMA_VERSION, Model, ResourceId};
+use lib3mf_core::parser::{parse_model, parse_model_lazy};
+use std::fs::File;
+use std::io::Cursor;
+
+const XML: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="inch" xml:lang="en-US" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    <metadata name="Title">Tetra</metadata>
+    <metadata name="Designer">Ada</metadata>
+    <resources>
+        <basematerials id="1">
+            <base name="Red" displaycolor="#FF0000" />
+        </basematerials>
+        <colorgroup id="2">
+            <color color="#0000FFFF" />
+        </colorgroup>
+        <object id="3" name="Tetra" type="model" pid="1" pindex="0">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="10" y="0" z="0" />
+                    <vertex x="0" y="10" z="0" />
+                    <vertex x="0" y="0" z="10.5" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="2" v3="1" />
+                    <triangle v1="0" v2="1" v3="3" pid="2" p1="0" />
+                    <triangle v1="1" v2="2" v3="3" />
+                    <triangle v1="0" v2="3" v3="2" />
+                </triangles>
+            </mesh>
+        </object>
+    </resources>
+    <build>
+        <item objectid="3" transform="1 0 0 0 1 0 0 0 1 20 5 0" />
+    </build>
+</model>"##;
+
+#[test]
+fn test_json_roundtrip_is_lossless() -> anyhow::Result<()> {
+    let model = parse_model(Cursor::new(XML))?;
+    let json = model.to_json()?;
+
+    let document: serde_json::Value = serde_json::from_str(&json)?;
+    assert_eq!(document["schema"], "lib3mf-model");
+    assert_eq!(document["version"], JSON_SCHEMA_VERSION);
+
+    let restored = Model::from_json(&json)?;
+    assert_eq!(restored.unit, model.unit);
+    assert_eq!(restored.metadata, model.metadata);
+    assert_eq!(
+        restored
+            .resources
+            .get_color_group(ResourceId(2))
+            .unwrap()
+            .colors,
+        vec![Color::new(0, 0, 255, 255)]
+    );
+    let object = restored.resources.get_object(ResourceId(3)).unwrap();
+    assert_eq!(object.name.as_deref(), Some("Tetra"));
+    let Geometry::Mesh(mesh) = &object.geometry else {
+        panic!("expected a mesh");
+    };
+    assert_eq!(mesh.vertices.len(), 4);
+    assert_eq!(mesh.vertices[3].z, 10.5);
+    assert_eq!(mesh.triangles[1].pid, Some(2));
+    assert_eq!(
+        restored.build.items[0].transform,
+        model.build.items[0].transform
+    );
+
+    // Serializing the restored model reproduces the document byte for byte.
+    assert_eq!(restored.to_json()?, json);
+
+    // And the restored model is still writable as a package.
+    let mut package = Cursor::new(Vec::new());
+    restored.write(&mut package)?;
+    Ok(())
+}
+
+#[test]
+fn test_json_output_is_deterministic() -> anyhow::Result<()> {
+    let mut model = Model::default();
+    for i in 0..32 {
+        model
+            .extra_namespaces
+            .insert(format!("Key{i:02}"), i.to_string());
+        // Metadata keeps document order rather than sorting
+        model
+            .metadata
+            .insert(format!("Meta{:02}", 31 - i), i.to_string());
+    }
+    let json = model.to_json()?;
+    let keys: Vec<_> = json
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix("\"Key"))
+        .collect();
+    let mut sorted = keys.clone();
+    sorted.sort();
+    assert_eq!(keys.len(), 32);
+    assert_eq!(keys, sorted);
+
+    let names: Vec<_> = json
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix("\"name\": \"Meta"))
+        .collect();
+    let mut reversed = names.clone();
+    reversed.sort();
+    reversed.reverse();
+    assert_eq!(names.len(), 32);
+    assert_eq!(names, reversed);
+    Ok(())
+}
+
+#[test]
+fn test_json_roundtrip_benchy() -> anyhow::Result<()> {
+    let mut archiver = ZipArchiver::new(File::open("../../models/Benchy.3mf")?)?;
+    let model_path = find_model_path(&mut archiver)?;
+    let model = parse_model(Cursor::new(archiver.read_entry(&model_path)?))?;
+
+    let mut buffer = Vec::new();
+    model.write_json(&mut buffer)?;
+    let restored = Model::read_json(buffer.as_slice())?;
+
+    assert_eq!(
+        restored.resources.iter_objects().count(),
+        model.resources.iter_objects().count()
+    );
+    assert_eq!(restored.build.items.len(), model.build.items.len());
+    assert_eq!(restored.to_json()?.as_bytes(), buffer.as_slice());
+    Ok(())
+}
+
+#[test]
+fn test_json_rejects_other_documents() {
+    let json = parse_model(Cursor::new(XML)).unwrap().to_json().unwrap();
+
+    let future = json.replacen(
+        &format!("\"version\": {JSON_SCHEMA_VERSION}"),
+        "\"version\": 999",
+        1,
+    );
+    let err = Model::from_json(&future).unwrap_err().to_string();
+    assert!(err.contains("version 999"), "{err}");
+
+    let foreign = json.replacen("lib3mf-model", "something-else", 1);
+    let err = Model::from_json(&foreign).unwrap_err().to_string();
+    assert!(err.contains("something-else"), "{err}");
+
+    let err = Model::from_json("{\"unit\": \"millimeter\"}")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Invalid model JSON"), "{err}");
+}
+
+#[test]
+fn test_json_rejects_lazy_meshes() -> anyhow::Result<()> {
+    let model = parse_model_lazy(Cursor::new(XML), "/3D/3dmodel.model")?;
+    let err = model.to_json().unwrap_err().to_string();
+    assert!(err.contains("materialize_meshes"), "{err}");
+    Ok(())
+}
@@ -0,0 +1,130 @@
+//! Lattice infill emitted as beam lattices.
+#![cfg(feature = "testutil")]
+
+use glam::Vec3;
+use lib3mf_core::Lib3mfError;
+use lib3mf_core::model::{LatticeOptions, LatticePattern, Mesh, OffsetOptions};
+use lib3mf_core::testutil::meshes::cuboid;
+use std::collections::HashMap;
+
+fn options(pattern: LatticePattern) -> LatticeOptions {
+    LatticeOptions {
+        pattern,
+        cell_size: 6.0,
+        beam_radius: 0.4,
+        grid: OffsetOptions {
+            voxel_size: Some(0.5),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn position(mesh: &Mesh, v: u32) -> Vec3 {
+    let v = mesh.vertices[v as usize];
+    Vec3::new(v.x, v.y, v.z)
+}
+
+#[test]
+fn test_patterns_have_expected_connectivity() {
+    let mesh = cuboid([40.0; 3], 1);
+    for (pattern, degree, length) in [
+        (LatticePattern::Grid, 6, 6.0),
+        (LatticePattern::Octet, 12, 6.0 / 2f32.sqrt()),
+        (LatticePattern::Gyroid, 3, 6.0 * 2f32.sqrt() / 4.0),
+    ] {
+        let solid = LatticeOptions {
+            shell: None,
+            ..options(pattern)
+        };
+        let filled = mesh.lattice_fill(&solid).unwrap();
+        let lattice = filled.beam_lattice.as_ref().unwrap();
+        assert_eq!(lattice.radius, Some(0.4));
+
+        let mut degrees: HashMap<u32, usize> = HashMap::new();
+        for beam in &lattice.beams {
+            let (a, b) = (position(&filled, beam.v1), position(&filled, beam.v2));
+            assert!((a.distance(b) - length).abs() < 1e-4, "{pattern}");
+            // Beams stay a radius inside the outer surface
+            for p in [a, b] {
+                assert!(
+                    p.min_element() >= 0.4 && p.max_element() <= 39.6,
+                    "{pattern}: {p}"
+                );
+            }
+            assert_eq!((beam.r1, beam.r2), (0.4, 0.4));
+            *degrees.entry(beam.v1).or_default() += 1;
+            *degrees.entry(beam.v2).or_default() += 1;
+        }
+        assert_eq!(degrees.values().max(), Some(&degree), "{pattern}");
+        // Nodes more than a cell from the surface are fully connected
+        for (&v, &d) in &degrees {
+            let p = position(&filled, v);
+            if p.min_element() > 7.0 && p.max_element() < 33.0 {
+                assert_eq!(d, degree, "{pattern}: {p}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_shell_and_density() {
+    let mesh = cuboid([30.0; 3], 1);
+
+    // Without a shell the input triangles are kept as they are
+    let kept = LatticeOptions {
+        shell: None,
+        ..options(LatticePattern::Grid)
+    };
+    let filled = mesh.lattice_fill(&kept).unwrap();
+    assert_eq!(filled.triangles, mesh.triangles);
+    assert_eq!(filled.vertices[..8], mesh.vertices[..]);
+    assert!(filled.vertices.len() > 8);
+
+    // The default shell adds an inner cavity surface
+    let shelled = mesh.lattice_fill(&options(LatticePattern::Grid)).unwrap();
+    let (_, volume) = shelled.compute_area_and_volume();
+    assert!((volume - (27000.0 - 26f64.powi(3))).abs() < 27000.0 * 0.02);
+
+    // Grid cells hold three beams of one cell length
+    let dense = LatticeOptions {
+        density: Some(0.1),
+        ..options(LatticePattern::Grid)
+    };
+    let expected = 6.0 * (0.1 / (3.0 * std::f32::consts::PI)).sqrt();
+    assert!((dense.effective_radius() - expected).abs() < 1e-6);
+    let filled = mesh.lattice_fill(&dense).unwrap();
+    assert_eq!(filled.beam_lattice.unwrap().radius, Some(expected));
+}
+
+#[test]
+fn test_lattice_fill_errors() {
+    let validation = |result: Result<Mesh, Lib3mfError>| match result {
+        Err(Lib3mfError::Validation(msg)) => msg,
+        other => panic!("expected a validation error, got {other:?}"),
+    };
+    let mesh = cuboid([30.0; 3], 1);
+
+    let filled = mesh.lattice_fill(&options(LatticePattern::Octet)).unwrap();
+    assert!(validation(filled.lattice_fill(&options(LatticePattern::Octet))).contains("already"));
+
+    let overfull = LatticeOptions {
+        density: Some(1.5),
+        ..options(LatticePattern::Grid)
+    };
+    assert!(validation(mesh.lattice_fill(&overfull)).contains("density"));
+
+    let tiny = LatticeOptions {
+        cell_size: 20.0,
+        beam_radius: 3.0,
+        shell: None,
+        ..options(LatticePattern::Grid)
+    };
+    assert!(validation(cuboid([8.0; 3], 1).lattice_fill(&tiny)).contains("No lattice beams"));
+
+    assert_eq!(
+        "gyroid".parse::<LatticePattern>().unwrap(),
+        LatticePattern::Gyroid
+    );
+    assert!("honeycomb".parse::<LatticePattern>().is_err());
+}
@@ -0,0 +1,159 @@
+#![cfg(feature = "testutil")]
+
+use lib3mf_core::Model;
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::model::{
+    BuildItem, Component, Components, Geometry, LazyMesh, Object, ObjectType, ResourceId,
+};
+use lib3mf_core::parser::{parse_model, parse_model_lazy};
+use lib3mf_core::testutil::meshes::tetrahedron;
+use lib3mf_core::validation::ValidationLevel;
+use std::io::{BufReader, Cursor};
+
+fn object(id: u32, geometry: Geometry) -> Object {
+    Object {
+        id: ResourceId(id),
+        object_type: ObjectType::Model,
+        name: Some(format!("Part {id}")),
+        part_number: None,
+        uuid: None,
+        pid: None,
+        pindex: None,
+        thumbnail: None,
+        geometry,
+    }
+}
+
+/// In-memory package with three mesh objects, one placed twice through an assembly.
+fn open_plate() -> (ZipArchiver<Cursor<Vec<u8>>>, String) {
+    let mut model = Model::default();
+    for id in 1..=3 {
+        let geometry = Geometry::Mesh(tetrahedron(id as f32 * 10.0));
+        model.resources.add_object(object(id, geometry)).unwrap();
+    }
+    let components = (1..=2)
+        .map(|id| Component {
+            object_id: ResourceId(id),
+            path: None,
+            uuid: None,
+            transform: glam::Mat4::from_translation(glam::Vec3::new(50.0 * id as f32, 0.0, 0.0)),
+        })
+        .collect();
+    let assembly = Geometry::Components(Components { components });
+    model.resources.add_object(object(4, assembly)).unwrap();
+    for id in [3, 4] {
+        model.build.items.push(BuildItem {
+            object_id: ResourceId(id),
+            uuid: None,
+            path: None,
+            part_number: None,
+            transform: glam::Mat4::IDENTITY,
+            printable: None,
+            metadata: Default::default(),
+        });
+    }
+
+    let mut out = Cursor::new(Vec::new());
+    model.write(&mut out).unwrap();
+    let mut archiver = ZipArchiver::new(Cursor::new(out.into_inner())).unwrap();
+    let model_path = find_model_path(&mut archiver).unwrap();
+    (archiver, model_path)
+}
+
+fn sorted_objects(model: &Model) -> Vec<String> {
+    let mut objects: Vec<_> = model.resources.iter_objects().collect();
+    objects.sort_by_key(|o| o.id.0);
+    objects.iter().map(|o| format!("{o:?}")).collect()
+}
+
+#[test]
+fn test_lazy_parse_records_mesh_counts() {
+    let (mut archiver, model_path) = open_plate();
+    let eager = parse_model(Cursor::new(archiver.read_entry(&model_path).unwrap())).unwrap();
+    let lazy = {
+        let part = BufReader::new(archiver.entry_reader(&model_path).unwrap());
+        parse_model_lazy(part, &model_path).unwrap()
+    };
+
+    assert!(lazy.has_lazy_meshes());
+    assert!(!eager.has_lazy_meshes());
+    assert_eq!(
+        lazy.resources.iter_objects().count(),
+        eager.resources.iter_objects().count()
+    );
+    for object in eager.resources.iter_objects() {
+        let lazy_object = lazy.resources.get_object(object.id).unwrap();
+        match (&object.geometry, &lazy_object.geometry) {
+            (Geometry::Mesh(mesh), Geometry::LazyMesh(handle)) => {
+                assert_eq!(handle.part, model_path);
+                assert_eq!(handle.vertex_count, mesh.vertices.len());
+                assert_eq!(handle.triangle_count, mesh.triangles.len());
+                assert_eq!(&handle.load(&mut archiver).unwrap(), mesh);
+            }
+            (eager_geometry, lazy_geometry) => assert_eq!(eager_geometry, lazy_geometry),
+        }
+    }
+}
+
+#[test]
+fn test_materialize_matches_eager_parse() {
+    let (mut archiver, model_path) = open_plate();
+    let data = archiver.read_entry(&model_path).unwrap();
+    let eager = parse_model(Cursor::new(&data)).unwrap();
+    let mut lazy = parse_model_lazy(Cursor::new(&data), &model_path).unwrap();
+
+    let mesh_count = eager
+        .resources
+        .iter_objects()
+        .filter(|o| matches!(o.geometry, Geometry::Mesh(_)))
+        .count();
+    assert_eq!(lazy.materialize_meshes(&mut archiver).unwrap(), mesh_count);
+    assert!(!lazy.has_lazy_meshes());
+    assert_eq!(sorted_objects(&lazy), sorted_objects(&eager));
+    assert_eq!(lazy.materialize_meshes(&mut archiver).unwrap(), 0);
+}
+
+#[test]
+fn test_stats_on_lazy_model_match_eager() {
+    let (mut archiver, model_path) = open_plate();
+    let data = archiver.read_entry(&model_path).unwrap();
+    let eager = parse_model(Cursor::new(&data)).unwrap();
+    let lazy = parse_model_lazy(Cursor::new(&data), &model_path).unwrap();
+
+    let expected = eager.compute_stats(&mut archiver).unwrap().geometry;
+    let actual = lazy.compute_stats(&mut archiver).unwrap().geometry;
+    assert_eq!(actual.object_count, expected.object_count);
+    assert_eq!(actual.vertex_count, expected.vertex_count);
+    assert_eq!(actual.triangle_count, expected.triangle_count);
+    assert_eq!(actual.surface_area, expected.surface_area);
+    assert_eq!(actual.volume, expected.volume);
+    assert_eq!(
+        format!("{:?}", actual.bounding_box),
+        format!("{:?}", expected.bounding_box)
+    );
+}
+
+#[test]
+fn test_lazy_model_cannot_be_written_but_validates() {
+    let (mut archiver, model_path) = open_plate();
+    let data = archiver.read_entry(&model_path).unwrap();
+    let lazy = parse_model_lazy(Cursor::new(&data), &model_path).unwrap();
+
+    let err = lazy.write(Cursor::new(Vec::new())).unwrap_err();
+    assert!(err.to_string().contains("materialize_meshes"), "{err}");
+
+    let report = lazy.validate(ValidationLevel::Standard);
+    assert!(!report.has_errors(), "{:?}", report.items);
+}
+
+#[test]
+fn test_lazy_mesh_rejects_bad_span() {
+    let handle = LazyMesh {
+        part: "3D/3dmodel.model".to_string(),
+        span: 10..100,
+        vertex_count: 0,
+        triangle_count: 0,
+    };
+    assert!(handle.load_from(b"<model/>").is_err());
+    assert!(handle.load_from(&[b' '; 200]).is_err());
+}
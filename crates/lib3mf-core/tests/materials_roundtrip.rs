@@ -0,0 +1,233 @@
+//! Write/parse round trips of Materials Extension composite materials, multiproperties
+//! and texture sampling attributes
+
+#![cfg(feature = "writer")]
+
+use lib3mf_core::model::*;
+use lib3mf_core::parser::parse_model;
+use std::io::Cursor;
+
+fn roundtrip(model: &Model) -> Model {
+    let mut buffer = Vec::new();
+    model.write_xml(&mut buffer, None).unwrap();
+    parse_model(Cursor::new(&buffer)).unwrap()
+}
+
+fn mixed_material_model() -> Model {
+    let mut model = Model::default();
+    model
+        .resources
+        .add_base_materials(BaseMaterialsGroup {
+            id: ResourceId(1),
+            materials: vec![
+                BaseMaterial {
+                    name: "PLA".to_string(),
+                    display_color: Color::new(255, 255, 255, 255),
+                },
+                BaseMaterial {
+                    name: "TPU".to_string(),
+                    display_color: Color::new(0, 0, 0, 255),
+                },
+                BaseMaterial {
+                    name: "PVA".to_string(),
+                    display_color: Color::new(200, 200, 100, 255),
+                },
+            ],
+        })
+        .unwrap();
+    model
+        .resources
+        .add_color_group(ColorGroup {
+            id: ResourceId(2),
+            colors: vec![Color::new(255, 0, 0, 255), Color::new(0, 0, 255, 128)],
+        })
+        .unwrap();
+    model
+        .resources
+        .add_composite_materials(CompositeMaterials {
+            id: ResourceId(3),
+            base_material_id: ResourceId(1),
+            indices: vec![0, 2],
+            composites: vec![
+                Composite {
+                    values: vec![0.25, 0.75],
+                },
+                Composite {
+                    values: vec![0.5, 0.5],
+                },
+            ],
+        })
+        .unwrap();
+    model
+        .resources
+        .add_multi_properties(MultiProperties {
+            id: ResourceId(4),
+            pids: vec![ResourceId(3), ResourceId(2)],
+            blend_methods: vec![BlendMethod::Multiply],
+            multis: vec![
+                Multi {
+                    pindices: vec![0, 1],
+                },
+                Multi {
+                    pindices: vec![1, 0],
+                },
+            ],
+        })
+        .unwrap();
+
+    let mut mesh = Mesh::new();
+    mesh.add_vertex(0.0, 0.0, 0.0);
+    mesh.add_vertex(10.0, 0.0, 0.0);
+    mesh.add_vertex(0.0, 10.0, 0.0);
+    mesh.add_triangle(0, 1, 2);
+    mesh.triangles[0].pid = Some(4);
+    mesh.triangles[0].p1 = Some(0);
+    mesh.triangles[0].p2 = Some(1);
+    mesh.triangles[0].p3 = Some(1);
+    model
+        .resources
+        .add_object(Object {
+            id: ResourceId(5),
+            object_type: ObjectType::Model,
+            name: None,
+            part_number: None,
+            uuid: None,
+            pid: Some(ResourceId(3)),
+            pindex: Some(1),
+            thumbnail: None,
+            geometry: Geometry::Mesh(mesh),
+        })
+        .unwrap();
+    model
+}
+
+#[test]
+fn test_composite_materials_roundtrip() {
+    let model = mixed_material_model();
+    let parsed = roundtrip(&model);
+
+    let composite = parsed
+        .resources
+        .get_composite_materials(ResourceId(3))
+        .expect("composite materials lost");
+    assert_eq!(
+        composite,
+        model
+            .resources
+            .get_composite_materials(ResourceId(3))
+            .unwrap()
+    );
+    let object = parsed.resources.get_object(ResourceId(5)).unwrap();
+    assert_eq!((object.pid, object.pindex), (Some(ResourceId(3)), Some(1)));
+}
+
+#[test]
+fn test_multi_properties_roundtrip() {
+    let model = mixed_material_model();
+    let parsed = roundtrip(&model);
+
+    let multi = parsed
+        .resources
+        .get_multi_properties(ResourceId(4))
+        .expect("multiproperties lost");
+    assert_eq!(
+        multi,
+        model.resources.get_multi_properties(ResourceId(4)).unwrap()
+    );
+    let Geometry::Mesh(mesh) = &parsed.resources.get_object(ResourceId(5)).unwrap().geometry else {
+        panic!("Expected mesh");
+    };
+    let tri = mesh.triangles[0];
+    assert_eq!(
+        (tri.pid, tri.p1, tri.p2, tri.p3),
+        (Some(4), Some(0), Some(1), Some(1))
+    );
+}
+
+#[test]
+fn test_parsed_materials_survive_rewrite() {
+    let xml = r##"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+       xmlns:m="http://schemas.microsoft.com/3dmanufacturing/material/2015/02">
+    <resources>
+        <m:basematerials id="1">
+            <m:base name="A" displaycolor="#FF0000" />
+            <m:base name="B" displaycolor="#00FF00" />
+        </m:basematerials>
+        <m:colorgroup id="2">
+            <m:color color="#0000FF" />
+        </m:colorgroup>
+        <m:compositematerials id="3" matid="1" matindices="1 0">
+            <m:composite values="0.3 0.7" />
+        </m:compositematerials>
+        <m:multiproperties id="4" pids="1 2" blendmethods="mix">
+            <m:multi pindices="0 0" />
+            <m:multi pindices="1 0" />
+        </m:multiproperties>
+    </resources>
+    <build />
+</model>"##;
+    let parsed = parse_model(Cursor::new(xml)).unwrap();
+    let rewritten = roundtrip(&parsed);
+
+    let composite = rewritten
+        .resources
+        .get_composite_materials(ResourceId(3))
+        .unwrap();
+    assert_eq!(composite.indices, [1, 0]);
+    assert_eq!(
+        Some(composite),
+        parsed.resources.get_composite_materials(ResourceId(3))
+    );
+    let multi = rewritten
+        .resources
+        .get_multi_properties(ResourceId(4))
+        .unwrap();
+    assert_eq!(multi.blend_methods, [BlendMethod::Mix]);
+    assert_eq!(
+        Some(multi),
+        parsed.resources.get_multi_properties(ResourceId(4))
+    );
+}
+
+#[test]
+fn test_texture_2d_sampling_roundtrip() {
+    let mut model = Model::default();
+    model
+        .resources
+        .add_texture_2d(Texture2D {
+            id: ResourceId(1),
+            path: "/3D/Textures/tiles.png".to_string(),
+            contenttype: "image/png".to_string(),
+            tile_style_u: TileStyle::Mirror,
+            tile_style_v: TileStyle::None,
+            filter: Some(FilterMode::Nearest),
+        })
+        .unwrap();
+    model
+        .resources
+        .add_texture_2d(Texture2D {
+            id: ResourceId(2),
+            path: "/3D/Textures/photo.jpg".to_string(),
+            contenttype: "image/jpeg".to_string(),
+            tile_style_u: TileStyle::Wrap,
+            tile_style_v: TileStyle::Wrap,
+            filter: None,
+        })
+        .unwrap();
+
+    let mut buffer = Vec::new();
+    model.write_xml(&mut buffer, None).unwrap();
+    let xml = String::from_utf8(buffer.clone()).unwrap();
+    assert!(xml.contains(r#"tilestyleu="mirror""#));
+    assert!(xml.contains(r#"tilestylev="none""#));
+    assert!(xml.contains(r#"filter="nearest""#));
+    // Defaults are left implicit
+    assert_eq!(xml.matches("tilestyle").count(), 2);
+    assert_eq!(xml.matches("filter=").count(), 1);
+
+    let parsed = parse_model(Cursor::new(&buffer)).unwrap();
+    let textures: Vec<_> = parsed.resources.iter_texture_2d().cloned().collect();
+    let expected: Vec<_> = model.resources.iter_texture_2d().cloned().collect();
+    assert_eq!(textures, expected);
+}
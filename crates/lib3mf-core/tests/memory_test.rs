@@ -0,0 +1,83 @@
+//! Memory usage estimates and the parse-time mesh memory budget.
+
+use lib3mf_core::error::Lib3mfError;
+use lib3mf_core::model::{Triangle, Vertex};
+use lib3mf_core::parser::{parse_model, parse_model_with_budget};
+use std::io::Cursor;
+
+const MODEL: &str = r##"<model unit="millimeter"
+    xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:m="http://schemas.microsoft.com/3dmanufacturing/material/2015/02">
+    <resources>
+        <m:colorgroup id="1"><m:color color="#FF0000" /><m:color color="#00FF00" /></m:colorgroup>
+        <object id="2">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="10" y="0" z="0" />
+                    <vertex x="0" y="10" z="0" />
+                    <vertex x="0" y="0" z="10" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="2" v3="1" />
+                    <triangle v1="0" v2="1" v3="3" />
+                    <triangle v1="0" v2="3" v3="2" />
+                    <triangle v1="1" v2="2" v3="3" paint_color="4" />
+                </triangles>
+            </mesh>
+        </object>
+        <object id="3"><components><component objectid="2" /></components></object>
+    </resources>
+    <build><item objectid="3" /></build>
+</model>"##;
+
+/// In-memory size of the tetrahedron's vertices and triangles.
+const MESH_BYTES: usize = 4 * size_of::<Vertex>() + 4 * size_of::<Triangle>();
+
+#[test]
+fn test_memory_usage_by_resource_kind() {
+    let mut model = parse_model(Cursor::new(MODEL)).unwrap();
+    let usage = model.memory_usage();
+    assert!(usage.meshes >= MESH_BYTES, "{usage:?}");
+    assert!(usage.mesh_annotations > 0, "paint data: {usage:?}");
+    assert!(usage.components > 0);
+    assert!(usage.materials > 0);
+    assert_eq!(
+        (usage.beam_lattices, usage.slices, usage.attachments),
+        (0, 0, 0)
+    );
+    assert_eq!(
+        usage.total(),
+        usage
+            .categories()
+            .iter()
+            .map(|(_, bytes)| bytes)
+            .sum::<usize>()
+    );
+
+    model
+        .attachments
+        .insert("Metadata/thumbnail.png".to_string(), vec![0; 1000]);
+    let with_thumbnail = model.memory_usage();
+    assert!(with_thumbnail.attachments >= 1000);
+    assert_eq!(with_thumbnail.meshes, usage.meshes);
+}
+
+#[test]
+fn test_parse_budget_rejects_large_meshes() {
+    // Exactly enough for the mesh parses like parse_model
+    let model = parse_model_with_budget(Cursor::new(MODEL), MESH_BYTES as u64).unwrap();
+    let full = parse_model(Cursor::new(MODEL)).unwrap();
+    assert_eq!(model.memory_usage(), full.memory_usage());
+
+    let err = parse_model_with_budget(Cursor::new(MODEL), MESH_BYTES as u64 - 1).unwrap_err();
+    match &err {
+        Lib3mfError::Io(e) => assert_eq!(e.kind(), std::io::ErrorKind::FileTooLarge),
+        other => panic!("expected FileTooLarge, got {other:?}"),
+    }
+    assert!(
+        err.to_string()
+            .contains(&format!("memory budget of {} bytes", MESH_BYTES - 1)),
+        "{err}"
+    );
+}
@@ -0,0 +1,67 @@
+//! Hausdorff and RMS deviation between mesh surfaces.
+#![cfg(feature = "testutil")]
+
+use lib3mf_core::Lib3mfError;
+use lib3mf_core::model::Mesh;
+use lib3mf_core::testutil::meshes::cuboid;
+use lib3mf_core::utils::diff::{DeviationOptions, compare_meshes, compare_meshes_with};
+
+fn cube(min: [f32; 3], size: f32) -> Mesh {
+    let mut mesh = cuboid([size; 3], 1);
+    for v in &mut mesh.vertices {
+        (v.x, v.y, v.z) = (v.x + min[0], v.y + min[1], v.z + min[2]);
+    }
+    mesh
+}
+
+#[test]
+fn test_identical_meshes_have_no_deviation() {
+    let mesh = cube([0.0; 3], 10.0);
+    let deviation = compare_meshes(&mesh, &mesh).unwrap();
+    assert!(deviation.hausdorff < 1e-5);
+    assert!(deviation.rms < 1e-5);
+    assert!(deviation.samples > 24);
+}
+
+#[test]
+fn test_shifted_and_scaled_meshes() {
+    let a = cube([0.0; 3], 10.0);
+    let b = cube([0.0, 0.0, 0.5], 10.0);
+    let deviation = compare_meshes(&a, &b).unwrap();
+    assert!((deviation.hausdorff - 0.5).abs() < 1e-4, "{deviation:?}");
+    assert!((deviation.max_a_to_b - deviation.max_b_to_a).abs() < 1e-4);
+    // Only the top and bottom faces move away from the other surface
+    assert!(
+        deviation.mean > 0.05 && deviation.mean < 0.5,
+        "{deviation:?}"
+    );
+    assert!(deviation.rms >= deviation.mean);
+
+    // A smaller cube inside: every point of it is 1 from the big one, but the big one's
+    // corners are sqrt(3) away from the small one
+    let inner = cube([1.0; 3], 8.0);
+    let deviation = compare_meshes(&inner, &a).unwrap();
+    assert!((deviation.max_a_to_b - 1.0).abs() < 1e-4, "{deviation:?}");
+    assert!((deviation.max_b_to_a - 3f32.sqrt()).abs() < 1e-4);
+    assert_eq!(deviation.hausdorff, deviation.max_b_to_a);
+    let corner = deviation.max_location;
+    assert!(
+        [corner.x, corner.y, corner.z]
+            .iter()
+            .all(|c| *c == 0.0 || *c == 10.0)
+    );
+}
+
+#[test]
+fn test_compare_meshes_errors() {
+    let mesh = cube([0.0; 3], 1.0);
+    assert!(matches!(
+        compare_meshes(&mesh, &Mesh::new()),
+        Err(Lib3mfError::Validation(_))
+    ));
+    let options = DeviationOptions { spacing: Some(0.0) };
+    assert!(matches!(
+        compare_meshes_with(&mesh, &mesh, &options),
+        Err(Lib3mfError::Validation(_))
+    ));
+}
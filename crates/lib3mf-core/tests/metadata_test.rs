@@ -0,0 +1,189 @@
+#![cfg(feature = "writer")]
+
+use lib3mf_core::model::Model;
+use lib3mf_core::parser::parse_model;
+use lib3mf_core::validation::ValidationLevel;
+use std::io::Cursor;
+
+fn with_metadata(entries: &str) -> lib3mf_core::error::Result<Model> {
+    let xml = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    {entries}
+    <resources />
+    <build />
+</model>"#
+    );
+    parse_model(Cursor::new(xml))
+}
+
+const ENTRIES: &str = r#"
+    <metadata name="Title" xml:lang="en">Bracket</metadata>
+    <metadata name="Designer">Ada</metadata>
+    <metadata name="Title" xml:lang="de">Halterung</metadata>
+    <metadata name="CreationDate" type="xs:date" preserve="1">2026-01-31</metadata>
+    <metadata name="Empty" />
+"#;
+
+#[test]
+fn test_attributes_and_translations_are_parsed() {
+    let model = with_metadata(ENTRIES).unwrap();
+    let metadata = &model.metadata;
+
+    let names: Vec<_> = metadata.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(
+        names,
+        ["Title", "Designer", "Title", "CreationDate", "Empty"]
+    );
+    assert_eq!(
+        metadata.names().collect::<Vec<_>>(),
+        ["Title", "Designer", "CreationDate", "Empty"]
+    );
+
+    assert_eq!(metadata.get_lang("Title", "de"), Some("Halterung"));
+    // Without an untagged entry, the first translation is used
+    assert_eq!(metadata.get("Title"), Some("Bracket"));
+    assert_eq!(metadata["Designer"], "Ada");
+    assert_eq!(metadata.get("Empty"), Some(""));
+
+    let date = metadata.get_entry("CreationDate").unwrap();
+    assert_eq!(date.value_type.as_deref(), Some("xs:date"));
+    assert!(date.preserve);
+    assert!(!metadata.get_entry("Designer").unwrap().preserve);
+
+    let report = model.validate(ValidationLevel::Strict);
+    assert!(!report.items.iter().any(|i| i.code == 2041));
+}
+
+#[test]
+fn test_duplicate_name_and_language_is_rejected() {
+    let err = with_metadata(
+        r#"<metadata name="Title" xml:lang="en">A</metadata>
+           <metadata name="Title" xml:lang="en">B</metadata>"#,
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("Duplicate metadata name 'Title'"));
+
+    assert!(with_metadata(r#"<metadata name="A" /><metadata name="A">x</metadata>"#).is_err());
+}
+
+#[test]
+fn test_metadata_roundtrips_through_xml() {
+    let model = with_metadata(ENTRIES).unwrap();
+    let mut xml = Vec::new();
+    model.write_xml(&mut xml, None).unwrap();
+    let reparsed = parse_model(Cursor::new(xml)).unwrap();
+    assert_eq!(reparsed.metadata, model.metadata);
+}
+
+#[test]
+fn test_escaped_values_roundtrip_through_xml() {
+    let model =
+        with_metadata(r#"<metadata name="Note">"a" &amp; &lt;b&gt; &quot;c&quot;</metadata>"#)
+            .unwrap();
+    assert_eq!(model.metadata.get("Note"), Some(r#""a" & <b> "c""#));
+
+    let mut xml = Vec::new();
+    model.write_xml(&mut xml, None).unwrap();
+    let reparsed = parse_model(Cursor::new(xml)).unwrap();
+    assert_eq!(reparsed.metadata, model.metadata);
+}
+
+#[test]
+fn test_insert_replaces_translations_in_place() {
+    let mut model = with_metadata(ENTRIES).unwrap();
+    let old = model.metadata.insert("Title", "Clip");
+    assert_eq!(old.as_deref(), Some("Bracket"));
+
+    let names: Vec<_> = model.metadata.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, ["Title", "Designer", "CreationDate", "Empty"]);
+    assert_eq!(model.metadata.get_lang("Title", "de"), None);
+
+    assert_eq!(model.metadata.remove("Designer").as_deref(), Some("Ada"));
+    assert!(!model.metadata.contains_key("Designer"));
+}
+
+#[test]
+fn test_editor_undo_restores_all_entries() {
+    let model = with_metadata(ENTRIES).unwrap();
+    let mut editor = model.edit();
+    editor.set_metadata("Title", "Clip");
+    editor.remove_metadata("CreationDate");
+    assert_eq!(editor.model().metadata.len(), 3);
+
+    editor.undo().unwrap();
+    editor.undo().unwrap();
+    assert_eq!(editor.model().metadata, model.metadata);
+
+    // Redo reapplies the original edits
+    editor.redo().unwrap();
+    assert_eq!(editor.model().metadata.get("Title"), Some("Clip"));
+    assert_eq!(editor.model().metadata.get_lang("Title", "de"), None);
+}
+
+const ITEM_MODEL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    <resources>
+        <object id="1" type="model">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="1" y="0" z="0" />
+                    <vertex x="0" y="1" z="0" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="1" v3="2" />
+                </triangles>
+            </mesh>
+        </object>
+    </resources>
+    <build>
+        <item objectid="1" partnumber="P-1">
+            <metadatagroup>
+                <metadata name="Tag" preserve="1">left</metadata>
+                <metadata name="Batch">7</metadata>
+            </metadatagroup>
+        </item>
+        <item objectid="1" />
+    </build>
+</model>"#;
+
+#[test]
+fn test_item_metadata_roundtrips_through_xml() {
+    let model = parse_model(Cursor::new(ITEM_MODEL)).unwrap();
+    let item = &model.build.items[0];
+    assert_eq!(item.part_number.as_deref(), Some("P-1"));
+    assert_eq!(item.metadata.get("Tag"), Some("left"));
+    assert!(item.metadata.get_entry("Tag").unwrap().preserve);
+    assert_eq!(item.metadata.get("Batch"), Some("7"));
+    assert!(model.build.items[1].metadata.is_empty());
+
+    let mut buf = Vec::new();
+    model.write_xml(&mut buf, None).unwrap();
+    let reparsed = parse_model(Cursor::new(buf)).unwrap();
+    assert_eq!(reparsed.build.items[0].metadata, item.metadata);
+    assert!(reparsed.build.items[1].metadata.is_empty());
+}
+
+#[test]
+fn test_editor_item_metadata_undo() {
+    let model = parse_model(Cursor::new(ITEM_MODEL)).unwrap();
+    let mut editor = model.edit();
+    editor.set_item_metadata(1, "Tag", "right").unwrap();
+    editor.remove_item_metadata(0, "Batch").unwrap();
+    assert!(editor.set_item_metadata(5, "Tag", "x").is_err());
+
+    assert_eq!(
+        editor.model().build.items[1].metadata.get("Tag"),
+        Some("right")
+    );
+    assert_eq!(editor.model().build.items[0].metadata.len(), 1);
+
+    editor.undo().unwrap();
+    editor.undo().unwrap();
+    assert_eq!(
+        editor.model().build.items[0].metadata,
+        model.build.items[0].metadata
+    );
+    assert!(editor.model().build.items[1].metadata.is_empty());
+}
@@ -0,0 +1,162 @@
+use glam::{Mat4, Vec3};
+use lib3mf_core::model::{
+    Component, Components, Edit, Geometry, Mesh, Model, Object, ObjectType, ResourceId,
+};
+
+fn object(id: u32, geometry: Geometry) -> Object {
+    Object {
+        id: ResourceId(id),
+        object_type: ObjectType::Model,
+        name: None,
+        part_number: None,
+        uuid: None,
+        pid: None,
+        pindex: None,
+        thumbnail: None,
+        geometry,
+    }
+}
+
+fn triangle() -> Geometry {
+    let mut mesh = Mesh::new();
+    mesh.add_vertex(0.0, 0.0, 0.0);
+    mesh.add_vertex(1.0, 0.0, 0.0);
+    mesh.add_vertex(0.0, 1.0, 0.0);
+    mesh.add_triangle(0, 1, 2);
+    Geometry::Mesh(mesh)
+}
+
+#[test]
+fn test_undo_redo_restores_each_state() {
+    let original = Model::default();
+    let mut editor = original.edit();
+
+    editor.add_object(object(1, triangle())).unwrap();
+    let item = editor.add_item(ResourceId(1)).unwrap();
+    let moved = Mat4::from_translation(Vec3::new(5.0, 0.0, 0.0));
+    editor.set_item_transform(item, moved).unwrap();
+    editor.set_metadata("Title", "Part");
+
+    let log: Vec<String> = editor.history().map(|e| e.to_string()).collect();
+    assert_eq!(
+        log,
+        [
+            "Add object 1",
+            "Add build item for object 1",
+            "Transform build item 0",
+            "Set metadata 'Title'"
+        ]
+    );
+
+    // Unwind everything
+    assert!(editor.undo().unwrap());
+    assert!(!editor.model().metadata.contains_key("Title"));
+    assert!(editor.undo().unwrap());
+    assert_eq!(editor.model().build.items[0].transform, Mat4::IDENTITY);
+    assert!(editor.undo().unwrap());
+    assert!(editor.model().build.items.is_empty());
+    assert!(editor.undo().unwrap());
+    assert!(editor.model().resources.get_object(ResourceId(1)).is_none());
+    assert!(!editor.undo().unwrap());
+
+    // And replay it
+    while editor.redo().unwrap() {}
+    assert!(!editor.can_redo());
+    let edited = editor.commit();
+    assert_eq!(edited.build.items[0].transform, moved);
+    assert_eq!(edited.metadata["Title"], "Part");
+
+    // The source model is untouched
+    assert!(original.build.items.is_empty());
+    assert_eq!(original.resources.iter_objects().count(), 0);
+}
+
+#[test]
+fn test_new_edit_clears_redo() {
+    let mut editor = Model::default().edit();
+    editor.set_metadata("A", "1");
+    editor.undo().unwrap();
+    assert!(editor.can_redo());
+    assert_eq!(editor.redo_edit().unwrap().to_string(), "Set metadata 'A'");
+
+    editor.set_metadata("B", "2");
+    assert!(!editor.can_redo());
+    assert!(!editor.redo().unwrap());
+}
+
+#[test]
+fn test_invalid_edits_leave_model_unchanged() {
+    let mut editor = Model::default().edit();
+    editor.add_object(object(1, triangle())).unwrap();
+    editor
+        .add_object(object(
+            2,
+            Geometry::Components(Components {
+                components: vec![Component {
+                    object_id: ResourceId(1),
+                    path: None,
+                    uuid: None,
+                    transform: Mat4::IDENTITY,
+                }],
+            }),
+        ))
+        .unwrap();
+    editor.add_item(ResourceId(2)).unwrap();
+
+    // Dangling or out-of-range references
+    assert!(editor.add_item(ResourceId(9)).is_err());
+    assert!(editor.add_object(object(1, triangle())).is_err());
+    assert!(editor.remove_item(3).is_err());
+    assert!(editor.set_item_transform(3, Mat4::IDENTITY).is_err());
+
+    // Objects in use can't be removed
+    let err = editor.remove_object(ResourceId(1)).unwrap_err();
+    assert!(err.to_string().contains("object 2"));
+    assert!(editor.remove_object(ResourceId(2)).is_err());
+
+    assert_eq!(editor.history().count(), 3);
+    assert_eq!(editor.next_resource_id(), ResourceId(3));
+
+    // Removing users first makes removal possible, and undo brings everything back
+    editor.remove_item(0).unwrap();
+    editor.remove_object(ResourceId(2)).unwrap();
+    editor.remove_object(ResourceId(1)).unwrap();
+    assert_eq!(editor.model().resources.iter_objects().count(), 0);
+    for _ in 0..3 {
+        editor.undo().unwrap();
+    }
+    assert_eq!(editor.model().resources.iter_objects().count(), 2);
+    assert_eq!(editor.model().build.items[0].object_id, ResourceId(2));
+}
+
+#[test]
+fn test_replace_object_is_reversible() {
+    let mut editor = Model::default().edit();
+    editor.add_object(object(1, triangle())).unwrap();
+
+    let mut bigger = object(1, triangle());
+    if let Geometry::Mesh(mesh) = &mut bigger.geometry {
+        mesh.add_vertex(0.0, 0.0, 1.0);
+    }
+    editor.replace_object(bigger).unwrap();
+    editor
+        .apply(Edit::ReplaceObject(object(1, triangle())))
+        .unwrap();
+    editor.undo().unwrap();
+
+    let vertex_count = |editor: &lib3mf_core::model::ModelEditor| match &editor
+        .model()
+        .resources
+        .get_object(ResourceId(1))
+        .unwrap()
+        .geometry
+    {
+        Geometry::Mesh(mesh) => mesh.vertices.len(),
+        _ => 0,
+    };
+    assert_eq!(vertex_count(&editor), 4);
+    editor.undo().unwrap();
+    assert_eq!(vertex_count(&editor), 3);
+
+    assert!(editor.replace_object(object(7, triangle())).is_err());
+}
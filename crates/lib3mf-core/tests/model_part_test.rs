@@ -0,0 +1,76 @@
+//! Enumeration and selection of root model parts in packages with several 3D model
+//! relationships.
+
+use lib3mf_core::archive::{ZipArchiver, find_model_path, find_model_paths, select_model_path};
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+
+const MODEL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    <resources />
+    <build />
+</model>"#;
+
+/// A package with three model relationships; the first target is missing.
+fn archive() -> ZipArchiver<Cursor<Vec<u8>>> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    let files: [(&str, &[u8]); 4] = [
+        (
+            "_rels/.rels",
+            br#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+    <Relationship Id="rel0" Target="/3D/plate_0.model" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel" />
+    <Relationship Id="rel1" Target="/3D/plate_1.model" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel" />
+    <Relationship Id="rel2" Target="/3D/plate_2.model" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel" />
+    <Relationship Id="ext" Target="https://example.com/remote.model" TargetMode="External" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel" />
+</Relationships>"#,
+        ),
+        ("3D/plate_1.model", MODEL.as_bytes()),
+        ("3D/plate_2.model", MODEL.as_bytes()),
+        ("[Content_Types].xml", b"<Types />"),
+    ];
+    for (name, data) in files {
+        writer.start_file(name, options).unwrap();
+        writer.write_all(data).unwrap();
+    }
+    let buffer = writer.finish().unwrap().into_inner();
+    ZipArchiver::new(Cursor::new(buffer)).unwrap()
+}
+
+#[test]
+fn test_find_model_paths_lists_candidates() {
+    let mut archiver = archive();
+    let candidates = find_model_paths(&mut archiver).unwrap();
+    let listed: Vec<_> = candidates
+        .iter()
+        .map(|c| (c.path.as_str(), c.relationship_id.as_str(), c.exists))
+        .collect();
+    assert_eq!(
+        listed,
+        [
+            ("3D/plate_0.model", "rel0", false),
+            ("3D/plate_1.model", "rel1", true),
+            ("3D/plate_2.model", "rel2", true),
+        ]
+    );
+
+    // The strict lookup still rejects the package
+    assert!(find_model_path(&mut archiver).is_err());
+}
+
+#[test]
+fn test_select_model_path_policy() {
+    let mut archiver = archive();
+    // Without a request, the first relationship whose target exists wins
+    assert_eq!(
+        select_model_path(&mut archiver, None).unwrap(),
+        "3D/plate_1.model"
+    );
+    assert_eq!(
+        select_model_path(&mut archiver, Some("/3D/plate_2.model")).unwrap(),
+        "3D/plate_2.model"
+    );
+    assert!(select_model_path(&mut archiver, Some("3D/plate_0.model")).is_err());
+    assert!(select_model_path(&mut archiver, Some("3D/plate_9.model")).is_err());
+}
@@ -1,4 +1,5 @@
 use lib3mf_core::archive::{ArchiveReader, ZipArchiver};
+use lib3mf_core::model::{Geometry, Package};
 use lib3mf_core::parser::model_parser::parse_model;
 use std::fs::File;
 use std::io::Cursor;
@@ -46,3 +47,42 @@ fn test_multipart_stats_recursive() {
         assert!((size_z - 54.62).abs() < 2.0, "Size Z mismatch: {}", size_z);
     }
 }
+
+#[test]
+fn test_package_resolves_referenced_parts() {
+    let mut archiver = ZipArchiver::new(File::open("../../models/Benchy.3mf").unwrap()).unwrap();
+    let root = parse_model(Cursor::new(
+        archiver.read_entry("3D/3dmodel.model").unwrap(),
+    ))
+    .unwrap();
+    let parts = root.referenced_parts();
+    assert_eq!(parts, ["3D/Objects/object_1.model"]);
+
+    let part = parse_model(Cursor::new(archiver.read_entry(&parts[0]).unwrap())).unwrap();
+    assert!(part.referenced_parts().is_empty());
+    let mut package = Package::new(root);
+    package.add_part(format!("/{}", parts[0]), part);
+
+    // Paths resolve as part names: leading slash and case don't matter.
+    assert!(package.part(Some("/3d/objects/OBJECT_1.model")).is_some());
+    assert!(std::ptr::eq(
+        package.part(Some("/3D/3dmodel.model")).unwrap(),
+        &package.main_model
+    ));
+    assert!(package.part(Some("/3D/Objects/object_2.model")).is_none());
+    for item in &package.main_model.build.items {
+        let (_, object) = package
+            .resolve_object(item.object_id, item.path.as_deref())
+            .unwrap();
+        let Geometry::Components(components) = &object.geometry else {
+            panic!("expected an assembly");
+        };
+        for c in &components.components {
+            assert!(
+                package
+                    .resolve_object(c.object_id, c.path.as_deref())
+                    .is_some()
+            );
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Face, vertex and crease-aware corner normals.
+#![cfg(feature = "testutil")]
+
+use glam::{Mat4, Vec3};
+use lib3mf_core::model::{Mesh, MeshRepair, RepairOptions};
+use lib3mf_core::testutil::meshes::cuboid;
+
+/// A shallow pyramid: four faces tilted 10° from the base plane, over a square base.
+fn roof() -> Mesh {
+    let mut mesh = Mesh::new();
+    let h = 10f32.to_radians().tan();
+    for [x, y] in [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]] {
+        mesh.add_vertex(x, y, 0.0);
+    }
+    let apex = mesh.add_vertex(0.0, 0.0, h);
+    for i in 0..4 {
+        mesh.add_triangle(i, (i + 1) % 4, apex);
+    }
+    mesh.add_triangle(0, 2, 1);
+    mesh.add_triangle(0, 3, 2);
+    mesh
+}
+
+#[test]
+fn test_face_and_vertex_normals() {
+    let mesh = cuboid([2.0; 3], 1);
+    let faces = mesh.face_normals();
+    assert_eq!(faces[0], Vec3::NEG_X);
+    assert_eq!(faces[2], Vec3::X);
+
+    // Each corner sees its three faces at right angles, so it points along the diagonal
+    let vertices = mesh.vertex_normals();
+    assert!(vertices[0].abs_diff_eq(Vec3::splat(-1.0).normalize(), 1e-6));
+    assert!(vertices[6].abs_diff_eq(Vec3::ONE.normalize(), 1e-6));
+
+    let mut degenerate = Mesh::new();
+    degenerate.add_vertex(0.0, 0.0, 0.0);
+    degenerate.add_vertex(1.0, 0.0, 0.0);
+    degenerate.add_triangle(0, 1, 1);
+    degenerate.add_triangle(0, 1, 9);
+    assert_eq!(degenerate.face_normals(), vec![Vec3::ZERO; 2]);
+    assert_eq!(degenerate.compute_normals(30.0).indices.len(), 2);
+}
+
+#[test]
+fn test_crease_angle() {
+    // Flat: one normal per face direction, shared by both triangles of each side
+    let flat = cuboid([1.0; 3], 1).compute_normals(30.0);
+    assert_eq!(flat.normals.len(), 6);
+    assert_eq!(flat.indices[0], [flat.indices[0][0]; 3]);
+    assert_eq!(flat.indices[0][0], flat.indices[1][0]);
+    assert_eq!(flat.corners(2), Some([Vec3::X; 3]));
+
+    let smooth = cuboid([1.0; 3], 1).compute_normals(180.0);
+    assert_eq!(smooth.normals.len(), 8);
+
+    // The 10° roof edges are smoothed, the 80° eaves stay sharp
+    let roof = roof();
+    let normals = roof.compute_normals(30.0);
+    let top = normals.corners(0).unwrap();
+    assert!(top[2].abs_diff_eq(Vec3::Z, 1e-6), "{top:?}");
+    assert!(top[0].z > 0.9 && top[0].z < 1.0);
+    assert_eq!(normals.corners(4), Some([Vec3::NEG_Z; 3]));
+    assert!(roof.compute_normals(0.0).normals.len() == 5);
+}
+
+#[test]
+fn test_stored_normals() {
+    let mut mesh = cuboid([1.0; 3], 1);
+    assert!(mesh.stored_normals().is_none());
+    mesh.update_normals(30.0);
+    assert!(mesh.stored_normals().is_some());
+
+    let mut mirrored = mesh.stored_normals().unwrap().clone();
+    mirrored.transform(Mat4::from_scale(Vec3::new(-2.0, 1.0, 1.0)));
+    let original = mesh.stored_normals().unwrap();
+    // The +X side of the cube faces -X, and corners follow the reversed winding
+    let [a, b, c] = original.indices[2];
+    assert_eq!(mirrored.indices[2], [a, c, b]);
+    assert_eq!(mirrored.normals[a as usize], Vec3::NEG_X);
+
+    // Edits that change the triangles leave stale normals unused or drop them
+    mesh.add_triangle(0, 1, 2);
+    assert!(mesh.stored_normals().is_none());
+    mesh.triangles.pop();
+    mesh.add_vertex(0.0, 0.0, 0.0);
+    mesh.repair(RepairOptions::default());
+    assert!(mesh.normals.is_none());
+}
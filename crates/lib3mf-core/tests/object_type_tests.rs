@@ -1,5 +1,7 @@
 //! Tests for ObjectType enum and type-specific behavior
 
+#![cfg(feature = "writer")]
+
 use lib3mf_core::model::{Geometry, Mesh, Object, ObjectType, ResourceId};
 use lib3mf_core::parser::parse_model;
 
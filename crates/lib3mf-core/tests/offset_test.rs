@@ -0,0 +1,184 @@
+//! Offsetting and hollowing meshes through signed distance fields.
+#![cfg(feature = "testutil")]
+
+use glam::Vec3;
+use lib3mf_core::Lib3mfError;
+use lib3mf_core::model::{DrainHole, Mesh, OffsetOptions, ShellOptions};
+use lib3mf_core::testutil::meshes::cuboid;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+/// Asserts every directed edge is matched by exactly one opposite edge.
+fn assert_closed(mesh: &Mesh) {
+    let mut edges: HashMap<(u32, u32), usize> = HashMap::new();
+    for t in &mesh.triangles {
+        for edge in [(t.v1, t.v2), (t.v2, t.v3), (t.v3, t.v1)] {
+            *edges.entry(edge).or_default() += 1;
+        }
+    }
+    for (&(a, b), &count) in &edges {
+        assert_eq!(count, 1, "edge {a}-{b} used {count} times");
+        assert_eq!(edges.get(&(b, a)), Some(&1), "edge {a}-{b} is a boundary");
+    }
+}
+
+/// Number of vertex-connected triangle groups.
+fn components(mesh: &Mesh) -> usize {
+    let mut parent: Vec<usize> = (0..mesh.vertices.len()).collect();
+    fn find(parent: &mut [usize], i: usize) -> usize {
+        let mut root = i;
+        while parent[root] != root {
+            root = parent[root];
+        }
+        parent[i] = root;
+        root
+    }
+    for t in &mesh.triangles {
+        let a = find(&mut parent, t.v1 as usize);
+        for v in [t.v2, t.v3] {
+            let b = find(&mut parent, v as usize);
+            parent[b] = a;
+        }
+    }
+    let mut roots: Vec<usize> = mesh
+        .triangles
+        .iter()
+        .map(|t| find(&mut parent, t.v1 as usize))
+        .collect();
+    roots.sort_unstable();
+    roots.dedup();
+    roots.len()
+}
+
+/// Half-millimetre voxels keep the tests fast without the default resolution.
+fn grid() -> OffsetOptions {
+    OffsetOptions {
+        voxel_size: Some(0.5),
+        ..Default::default()
+    }
+}
+
+fn volume(mesh: &Mesh) -> f64 {
+    mesh.compute_area_and_volume().1
+}
+
+fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+    assert!(
+        (actual - expected).abs() <= expected.abs() * tolerance,
+        "{actual} vs {expected}"
+    );
+}
+
+#[test]
+fn test_offset_cube_outward_and_inward() {
+    let mesh = cuboid([20.0; 3], 1);
+
+    let grown = mesh.offset_with(2.0, &grid()).unwrap();
+    assert_closed(&grown);
+    // Minkowski sum of a cube and a ball: faces, quarter-cylinder edges and corner spheres
+    let (a, d) = (20.0, 2.0);
+    let expected = a * a * a + 6.0 * a * a * d + 3.0 * PI * a * d * d + 4.0 / 3.0 * PI * d * d * d;
+    assert_close(volume(&grown), expected, 0.02);
+    let bounds = grown.compute_aabb().unwrap();
+    assert!((bounds.min[0] + 2.0).abs() < 0.05 && (bounds.max[2] - 22.0).abs() < 0.05);
+
+    let shrunk = mesh.offset_with(-3.0, &grid()).unwrap();
+    assert_closed(&shrunk);
+    assert_close(volume(&shrunk), 14.0f64.powi(3), 0.02);
+
+    // Offsetting by zero remeshes the original surface
+    let same = mesh.offset_with(0.0, &grid()).unwrap();
+    assert_closed(&same);
+    assert_close(volume(&same), 8000.0, 0.01);
+}
+
+#[test]
+fn test_shell_without_and_with_drain_hole() {
+    let mesh = cuboid([20.0; 3], 1);
+
+    let closed = mesh
+        .shell_with(&ShellOptions {
+            thickness: 2.0,
+            grid: grid(),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_closed(&closed);
+    // Outer surface plus an inward-facing cavity
+    assert_eq!(components(&closed), 2);
+    assert_close(volume(&closed), 8000.0 - 16.0f64.powi(3), 0.03);
+
+    let options = ShellOptions {
+        thickness: 2.0,
+        drain_holes: vec![DrainHole::new(4.0)],
+        grid: grid(),
+    };
+    let drained = mesh.shell_with(&options).unwrap();
+    assert_closed(&drained);
+    // The hole joins the cavity to the outside
+    assert_eq!(components(&drained), 1);
+    // Removes about a 4 mm diameter, 2 mm deep plug from the floor
+    let plug = PI * 4.0 * 2.0;
+    assert!(volume(&drained) < volume(&closed) - plug * 0.5);
+    assert!(volume(&drained) > volume(&closed) - plug * 2.0);
+
+    // The hole goes through the bottom, under the centre of the cube
+    let floor: Vec<Vec3> = drained
+        .vertices
+        .iter()
+        .map(|v| Vec3::new(v.x, v.y, v.z))
+        .filter(|v| v.z.abs() < 1e-3)
+        .collect();
+    assert!(
+        !floor
+            .iter()
+            .any(|v| v.distance(Vec3::new(10.0, 10.0, 0.0)) < 1.5)
+    );
+
+    // An explicit hole through the side
+    let side = ShellOptions {
+        drain_holes: vec![DrainHole {
+            diameter: 3.0,
+            origin: Some(Vec3::new(10.0, 10.0, 10.0)),
+            direction: Vec3::X,
+        }],
+        grid: grid(),
+        ..Default::default()
+    };
+    let drained = mesh.shell_with(&side).unwrap();
+    assert_closed(&drained);
+    assert_eq!(components(&drained), 1);
+}
+
+#[test]
+fn test_offset_and_shell_errors() {
+    let mesh = cuboid([20.0; 3], 1);
+    let validation = |result: Result<Mesh, Lib3mfError>| match result {
+        Err(Lib3mfError::Validation(msg)) => msg,
+        other => panic!("expected a validation error, got {other:?}"),
+    };
+
+    assert!(validation(mesh.offset_with(-11.0, &grid())).contains("no surface"));
+    assert!(validation(mesh.offset(f32::NAN)).contains("finite"));
+    assert!(validation(mesh.shell(0.0)).contains("positive"));
+    let thick = ShellOptions {
+        thickness: 10.5,
+        grid: grid(),
+        ..Default::default()
+    };
+    assert!(validation(mesh.shell_with(&thick)).contains("no cavity"));
+    assert!(validation(Mesh::new().offset(1.0)).contains("without triangles"));
+
+    let too_wide = ShellOptions {
+        thickness: 2.0,
+        drain_holes: vec![DrainHole::new(18.0)],
+        grid: grid(),
+    };
+    assert!(validation(mesh.shell_with(&too_wide)).contains("No room"));
+
+    let too_fine = OffsetOptions {
+        voxel_size: Some(0.01),
+        ..Default::default()
+    };
+    assert!(validation(mesh.offset_with(1.0, &too_fine)).contains("exceeds"));
+}
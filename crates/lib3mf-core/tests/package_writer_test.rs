@@ -1,6 +1,10 @@
+#![cfg(feature = "writer")]
+
 use lib3mf_core::archive::ArchiveReader;
 use lib3mf_core::archive::ZipArchiver;
-use lib3mf_core::model::Model;
+use lib3mf_core::archive::opc;
+use lib3mf_core::model::{Model, Package};
+use lib3mf_core::writer::package_writer::PackageWriter;
 use std::io::Cursor;
 
 #[test]
@@ -50,3 +54,131 @@ fn test_write_package_with_attachments() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+fn cube_model(thumbnail: &str) -> Model {
+    let xml = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    <resources>
+        <object id="1" type="model" thumbnail="{thumbnail}">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" /><vertex x="1" y="0" z="0" /><vertex x="0" y="1" z="0" />
+                </vertices>
+                <triangles><triangle v1="0" v2="1" v3="2" /></triangles>
+            </mesh>
+        </object>
+    </resources>
+    <build><item objectid="1" /></build>
+</model>"#
+    );
+    lib3mf_core::parser::parse_model(Cursor::new(xml)).unwrap()
+}
+
+fn write_and_open(model: &Model) -> ZipArchiver<Cursor<Vec<u8>>> {
+    let mut buffer = Cursor::new(Vec::new());
+    model.write(&mut buffer).unwrap();
+    ZipArchiver::new(Cursor::new(buffer.into_inner())).unwrap()
+}
+
+#[test]
+fn test_write_object_thumbnail() -> anyhow::Result<()> {
+    let mut model = cube_model("/3D/Thumbnails/object_1.jpg");
+    model.attachments.insert(
+        "3D/Thumbnails/object_1.jpg".to_string(),
+        vec![0xFF, 0xD8, 0xFF, 0xE0],
+    );
+
+    let mut archiver = write_and_open(&model);
+
+    // The attribute holds the part name, not a relationship ID.
+    let reparsed =
+        lib3mf_core::parser::parse_model(Cursor::new(archiver.read_entry("3D/3dmodel.model")?))?;
+    let obj = reparsed.resources.iter_objects().next().unwrap();
+    assert_eq!(
+        obj.thumbnail.as_deref(),
+        Some("/3D/Thumbnails/object_1.jpg")
+    );
+
+    let rels = opc::parse_relationships(&archiver.read_entry("3D/_rels/3dmodel.model.rels")?)?;
+    assert_eq!(rels.len(), 1);
+    assert_eq!(rels[0].target, "/3D/Thumbnails/object_1.jpg");
+    assert_eq!(
+        rels[0].rel_type,
+        "http://schemas.openxmlformats.org/package/2006/relationships/metadata/thumbnail"
+    );
+
+    let content_types = String::from_utf8(archiver.read_entry("[Content_Types].xml")?)?;
+    assert!(content_types.contains(r#"Extension="jpg" ContentType="image/jpeg""#));
+
+    let types = opc::parse_content_types(content_types.as_bytes())?;
+    assert_eq!(
+        opc::content_type_of(&types, "/3D/Thumbnails/object_1.JPG"),
+        Some("image/jpeg")
+    );
+    assert_eq!(
+        opc::content_type_of(&types, "3D/3dmodel.model"),
+        Some("application/vnd.ms-package.3dmanufacturing-3dmodel+xml")
+    );
+    assert_eq!(opc::content_type_of(&types, "3D/notes.txt"), None);
+    Ok(())
+}
+
+#[test]
+fn test_write_resolves_legacy_thumbnail_relationship_id() -> anyhow::Result<()> {
+    // Older versions wrote a relationship ID with a non-standard relationship type.
+    let mut model = cube_model("rel_thumb_0");
+    model.attachments.insert(
+        "3D/Textures/thumb_1.png".to_string(),
+        b"\x89PNG\r\n\x1a\n".to_vec(),
+    );
+    model.existing_relationships.insert(
+        "3D/_rels/3dmodel.model.rels".to_string(),
+        vec![opc::Relationship {
+            id: "rel_thumb_0".to_string(),
+            rel_type:
+                "http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel/relationship/thumbnail"
+                    .to_string(),
+            target: "/3D/Textures/thumb_1.png".to_string(),
+            target_mode: "Internal".to_string(),
+        }],
+    );
+
+    let mut archiver = write_and_open(&model);
+
+    let xml = String::from_utf8(archiver.read_entry("3D/3dmodel.model")?)?;
+    assert!(xml.contains(r#"thumbnail="/3D/Textures/thumb_1.png""#));
+
+    // One standard thumbnail relationship and no texture relationship for the image.
+    let rels = opc::parse_relationships(&archiver.read_entry("3D/_rels/3dmodel.model.rels")?)?;
+    assert_eq!(rels.len(), 1);
+    assert!(rels[0].rel_type.ends_with("/metadata/thumbnail"));
+    assert_eq!(rels[0].target, "/3D/Textures/thumb_1.png");
+    Ok(())
+}
+
+#[test]
+fn test_write_part_thumbnail_relationships() -> anyhow::Result<()> {
+    let mut main = cube_model("/3D/Thumbnails/main.png");
+    main.attachments
+        .insert("3D/Thumbnails/main.png".to_string(), vec![1]);
+    main.attachments
+        .insert("3D/Thumbnails/part.png".to_string(), vec![2]);
+    let mut package = Package::new(main);
+    package.add_part(
+        "/3D/Objects/part.model".to_string(),
+        cube_model("/3D/Thumbnails/part.png"),
+    );
+
+    let mut buffer = Cursor::new(Vec::new());
+    PackageWriter::new(&mut buffer).write(&package)?;
+    let mut archiver = ZipArchiver::new(Cursor::new(buffer.into_inner()))?;
+
+    let rels = opc::parse_relationships(&archiver.read_entry("3D/Objects/_rels/part.model.rels")?)?;
+    assert_eq!(rels.len(), 1);
+    assert_eq!(rels[0].target, "/3D/Thumbnails/part.png");
+    let rels = opc::parse_relationships(&archiver.read_entry("3D/_rels/3dmodel.model.rels")?)?;
+    assert_eq!(rels.len(), 1);
+    assert_eq!(rels[0].target, "/3D/Thumbnails/main.png");
+    Ok(())
+}
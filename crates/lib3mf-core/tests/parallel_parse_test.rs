@@ -0,0 +1,142 @@
+#![cfg(feature = "parallel")]
+
+use lib3mf_core::Model;
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::parser::{parse_model, parse_model_parallel};
+use std::fs::File;
+use std::io::Cursor;
+
+const HEADER: &str = r#"<model unit="millimeter" xml:lang="en-US" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02" xmlns:p="http://schemas.microsoft.com/3dmanufacturing/production/2015/06">"#;
+
+/// Objects sorted by ID, rendered with `Debug` so HashMap ordering does not matter.
+fn objects_debug(model: &Model) -> Vec<String> {
+    let mut objects: Vec<_> = model.resources.iter_objects().collect();
+    objects.sort_by_key(|o| o.id.0);
+    objects.iter().map(|o| format!("{o:?}")).collect()
+}
+
+fn assert_same(xml: &[u8]) {
+    let sequential = parse_model(Cursor::new(xml)).expect("sequential parse");
+    let parallel = parse_model_parallel(xml).expect("parallel parse");
+    assert_eq!(objects_debug(&parallel), objects_debug(&sequential));
+    assert_eq!(
+        parallel.resources.iter_base_materials().count(),
+        sequential.resources.iter_base_materials().count()
+    );
+    assert_eq!(
+        format!("{:?}", parallel.build),
+        format!("{:?}", sequential.build)
+    );
+    assert_eq!(parallel.metadata, sequential.metadata);
+    assert_eq!(parallel.unit, sequential.unit);
+    assert_eq!(parallel.language, sequential.language);
+}
+
+fn tetrahedron(id: u32, offset: f32) -> String {
+    format!(
+        r#"<object id="{id}" name="Part {id}" pid="1" pindex="0" p:UUID="00000000-0000-0000-0000-{id:012}">
+            <mesh>
+                <vertices>
+                    <vertex x="{offset}" y="0" z="0"/><vertex x="{x1}" y="0" z="0"/>
+                    <vertex x="{offset}" y="1" z="0"/><vertex x="{offset}" y="0" z="1"/>
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="2" v3="1"/><triangle v1="0" v2="1" v3="3" pid="1" p1="0"/>
+                    <triangle v1="1" v2="2" v3="3"/><triangle v1="0" v2="3" v3="2"/>
+                </triangles>
+            </mesh>
+        </object>"#,
+        x1 = offset + 1.0
+    )
+}
+
+#[test]
+fn test_parallel_parse_matches_sequential() {
+    let mut resources = String::from(
+        r##"<basematerials id="1"><base name="Red" displaycolor="#FF0000"/></basematerials>"##,
+    );
+    for id in 2..200 {
+        resources.push_str(&tetrahedron(id, id as f32 * 2.0));
+    }
+    resources.push_str(r#"<colorgroup id="200"/>"#);
+    resources.push_str(
+        r#"<object id="201" type="model"><components><component objectid="2"/><component objectid="3" transform="1 0 0 0 1 0 0 0 1 5 0 0"/></components></object>"#,
+    );
+    resources.push_str(r#"<object id="202"/>"#);
+
+    let xml = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+        {HEADER}
+        <metadata name="Title">Plate</metadata>
+        <resources>{resources}</resources>
+        <build><item objectid="201"/><item objectid="4" transform="1 0 0 0 1 0 0 0 1 0 10 0"/></build>
+    </model>"#
+    );
+
+    assert_same(xml.as_bytes());
+    let model = parse_model_parallel(xml.as_bytes()).unwrap();
+    assert_eq!(model.resources.iter_objects().count(), 200);
+}
+
+#[test]
+fn test_parallel_parse_benchy_matches_sequential() {
+    let file = File::open("../../models/Benchy.3mf").expect("Benchy.3mf fixture");
+    let mut archiver = ZipArchiver::new(file).unwrap();
+    let model_path = find_model_path(&mut archiver).unwrap();
+    let data = archiver.read_entry(&model_path).unwrap();
+    assert_same(&data);
+}
+
+#[test]
+fn test_parallel_parse_reports_errors_deterministically() {
+    // Object 3 is malformed and object 4 duplicates a material ID; the malformed
+    // object comes first in the document, so its error is reported on every run.
+    let xml = format!(
+        r##"{HEADER}
+        <resources>
+            <basematerials id="4"><base name="Red" displaycolor="#FF0000"/></basematerials>
+            {}
+            <object id="3" type="bogus"><mesh/></object>
+            <object id="4"><mesh/></object>
+        </resources>
+        <build/>
+    </model>"##,
+        tetrahedron(2, 0.0)
+    );
+
+    let sequential = parse_model(Cursor::new(xml.as_bytes()))
+        .unwrap_err()
+        .to_string();
+    for _ in 0..8 {
+        let parallel = parse_model_parallel(xml.as_bytes())
+            .unwrap_err()
+            .to_string();
+        assert_eq!(parallel, sequential);
+    }
+
+    // Duplicate IDs between objects and other resources are still rejected.
+    let xml = format!(
+        r##"{HEADER}
+        <resources>
+            <basematerials id="2"><base name="Red" displaycolor="#FF0000"/></basematerials>
+            {}
+        </resources>
+        <build/>
+    </model>"##,
+        tetrahedron(2, 0.0)
+    );
+    let err = parse_model_parallel(xml.as_bytes()).unwrap_err();
+    assert!(
+        err.to_string().contains("Duplicate resource ID: 2"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_parallel_parse_requires_build() {
+    let xml = format!(
+        "{HEADER}<resources>{}</resources></model>",
+        tetrahedron(1, 0.0)
+    );
+    assert!(parse_model_parallel(xml.as_bytes()).is_err());
+}
@@ -28,12 +28,9 @@ fn test_parse_benchy() {
     let model = parse_model(Cursor::new(model_data)).expect("Failed to parse model XML");
 
     // 1. Verify Metadata
+    assert_eq!(model.metadata.get("Title"), Some("Benchy Bambu Pla Basic"));
     assert_eq!(
-        model.metadata.get("Title").map(|s| s.as_str()),
-        Some("Benchy Bambu Pla Basic")
-    );
-    assert_eq!(
-        model.metadata.get("Application").map(|s| s.as_str()),
+        model.metadata.get("Application"),
         Some("BambuStudio-01.10.02.73")
     );
 
@@ -0,0 +1,141 @@
+//! OPC part name normalization and resolution of unusual relationship targets.
+
+use lib3mf_core::archive::{
+    ArchiveReader, ZipArchiver, find_model_path, normalize_part_name, part_names_match,
+    rels_source_part, resolve_part_target,
+};
+use lib3mf_core::model::ResourceId;
+use lib3mf_core::model::resolver::PartResolver;
+use lib3mf_core::parser::parse_model;
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+
+#[test]
+fn test_normalize_part_name() {
+    assert_eq!(normalize_part_name("/3D/3dmodel.model"), "3D/3dmodel.model");
+    assert_eq!(
+        normalize_part_name("./3D//./3dmodel.model"),
+        "3D/3dmodel.model"
+    );
+    assert_eq!(
+        normalize_part_name("3D\\Textures\\..\\part.model"),
+        "3D/part.model"
+    );
+    assert_eq!(normalize_part_name("/../3D/a.model"), "3D/a.model");
+    assert_eq!(
+        normalize_part_name("3D/K%C3%B6rper%201.model"),
+        "3D/Körper 1.model"
+    );
+    // Malformed escapes are kept as written
+    assert_eq!(normalize_part_name("3D/100%.model"), "3D/100%.model");
+    assert_eq!(normalize_part_name("3D/%ZZ.model"), "3D/%ZZ.model");
+}
+
+#[test]
+fn test_resolve_part_target() {
+    assert_eq!(
+        resolve_part_target("3D/3dmodel.model", "Textures/wood.png"),
+        "3D/Textures/wood.png"
+    );
+    assert_eq!(
+        resolve_part_target("3D/3dmodel.model", "/Metadata/thumbnail.png"),
+        "Metadata/thumbnail.png"
+    );
+    assert_eq!(
+        resolve_part_target("/3D/Objects/part.model", "..\\Textures\\a%20b.png"),
+        "3D/Textures/a b.png"
+    );
+    assert_eq!(
+        resolve_part_target("", "3D/3dmodel.model"),
+        "3D/3dmodel.model"
+    );
+
+    assert_eq!(rels_source_part("_rels/.rels"), "");
+    assert_eq!(
+        rels_source_part("3D/_rels/3dmodel.model.rels"),
+        "3D/3dmodel.model"
+    );
+    assert!(part_names_match("/3d/3DModel.model", "3D/./3dmodel.model"));
+    assert!(!part_names_match("3D/a.model", "3D/b.model"));
+}
+
+const ROOT_MODEL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+       xmlns:p="http://schemas.microsoft.com/3dmanufacturing/production/2015/06">
+    <resources>
+        <object id="1" type="model">
+            <components>
+                <component objectid="5" p:path="/3D/./Objects/../Objects/K%C3%B6rper.model" />
+            </components>
+        </object>
+    </resources>
+    <build>
+        <item objectid="1" />
+    </build>
+</model>"#;
+
+const PART_MODEL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    <resources>
+        <object id="5" type="model">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="1" y="0" z="0" />
+                    <vertex x="0" y="1" z="0" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="1" v3="2" />
+                </triangles>
+            </mesh>
+        </object>
+    </resources>
+    <build />
+</model>"#;
+
+/// A package whose relationships use `./`, backslashes and percent-escapes.
+fn archive() -> ZipArchiver<Cursor<Vec<u8>>> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    let files: [(&str, &[u8]); 5] = [
+        (
+            "_rels/.rels",
+            br#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+    <Relationship Id="rel0" Target="./3D\Main%20Model.model" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel" />
+    <Relationship Id="rel1" Target="Metadata/../Metadata/thumb%201.png" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/thumbnail" />
+</Relationships>"#,
+        ),
+        ("3D/Main Model.model", ROOT_MODEL.as_bytes()),
+        ("3D/Objects/Körper.model", PART_MODEL.as_bytes()),
+        ("Metadata/thumb 1.png", b"\x89PNG\r\n\x1a\n"),
+        ("[Content_Types].xml", b"<Types />"),
+    ];
+    for (name, data) in files {
+        writer.start_file(name, options).unwrap();
+        writer.write_all(data).unwrap();
+    }
+    let buffer = writer.finish().unwrap().into_inner();
+    ZipArchiver::new(Cursor::new(buffer)).unwrap()
+}
+
+#[test]
+fn test_unusual_targets_resolve_to_entries() {
+    let mut archiver = archive();
+    let model_path = find_model_path(&mut archiver).unwrap();
+    assert_eq!(model_path, "3D/Main Model.model");
+
+    // Lookups fall back to normalized, case-insensitive names
+    assert!(archiver.entry_exists("/3d/objects/K%C3%B6rper.model"));
+    assert!(archiver.entry_exists("Metadata\\thumb 1.png"));
+    assert!(!archiver.entry_exists("Metadata/thumb 2.png"));
+
+    let model = parse_model(Cursor::new(archiver.read_entry(&model_path).unwrap())).unwrap();
+    let mut resolver = PartResolver::new(&mut archiver, model);
+    let path = "/3D/./Objects/../Objects/K%C3%B6rper.model";
+    let (_, object) = resolver
+        .resolve_object(ResourceId(5), Some(path))
+        .unwrap()
+        .unwrap();
+    assert_eq!(object.id, ResourceId(5));
+}
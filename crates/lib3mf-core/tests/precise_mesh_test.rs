@@ -0,0 +1,198 @@
+#![cfg(feature = "writer")]
+//! Meshes with `f64` coordinates through parsing, statistics, repair and writing.
+
+use glam::DVec3;
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::model::{
+    Geometry, Mesh, MeshRepair, MeshStorage, Model, PreciseMesh, RepairOptions, ResourceId,
+    Triangle,
+};
+use lib3mf_core::parser::mesh_parser::parse_mesh_into;
+use lib3mf_core::parser::xml_parser::XmlParser;
+use lib3mf_core::parser::{ParseOptions, parse_model_with_options};
+use lib3mf_core::writer::mesh_writer::{write_mesh, write_mesh_with_options};
+use lib3mf_core::writer::xml_writer::XmlWriter;
+use lib3mf_core::writer::{VertexQuantization, WriteOptions};
+use std::io::Cursor;
+
+/// Surveyed points 5 km from the origin, in millimetres, resolved to 10 nm.
+const FAR: f64 = 5_000_000.0;
+
+const MESH_XML: &str = r#"<mesh>
+    <vertices>
+        <vertex x="5000000.00001" y="0.00002" z="0"/>
+        <vertex x="5000000.01003" y="0" z="0"/>
+        <vertex x="5000000" y="0.01007" z="0.00001"/>
+    </vertices>
+    <triangles>
+        <triangle v1="0" v2="1" v3="2"/>
+    </triangles>
+</mesh>"#;
+
+fn parse<S: MeshStorage + Default>(xml: &[u8]) -> S {
+    let mut parser = XmlParser::new(Cursor::new(xml));
+    parser.read_next_event().unwrap();
+    parse_mesh_into(&mut parser).unwrap()
+}
+
+fn write<S: MeshStorage>(mesh: &S, options: &WriteOptions) -> String {
+    let mut out = Vec::new();
+    write_mesh_with_options(&mut XmlWriter::new(&mut out), mesh, options).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+/// 10 µm cube with its corner at `origin`, every vertex listed twice.
+fn doubled_cube(origin: DVec3) -> PreciseMesh {
+    let mut mesh = PreciseMesh::new();
+    for _ in 0..2 {
+        for i in 0..8 {
+            let at = |b: usize| if i >> b & 1 == 1 { 0.01 } else { 0.0 };
+            mesh.push_vertex_f64(origin + DVec3::new(at(0), at(1), at(2)));
+        }
+    }
+    for (n, [a, b, c, d]) in [
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        // Alternate faces use the second copy of the vertices.
+        let o = if n % 2 == 0 { 0 } else { 8 };
+        for [v1, v2, v3] in [[a, b, c], [a, c, d]] {
+            mesh.push_triangle(Triangle {
+                v1: v1 + o,
+                v2: v2 + o,
+                v3: v3 + o,
+                ..Default::default()
+            });
+        }
+    }
+    mesh
+}
+
+#[test]
+fn test_coordinates_round_trip_in_f64() {
+    let precise: PreciseMesh = parse(MESH_XML.as_bytes());
+    assert_eq!(precise.vertices[1], DVec3::new(5_000_000.010_03, 0.0, 0.0));
+
+    // f32 rounds these coordinates to the nearest half millimetre.
+    let rounded: Mesh = parse(MESH_XML.as_bytes());
+    assert_eq!(rounded.vertices[1].x, 5_000_000.0);
+
+    let written = write(&precise, &WriteOptions::default());
+    assert!(
+        written.contains(r#"<vertex x="5000000.01003" y="0" z="0"/>"#),
+        "{}",
+        written
+    );
+    assert_eq!(parse::<PreciseMesh>(written.as_bytes()), precise);
+
+    // Converting from a Mesh widens exactly: the f64 text reads back as the same f32.
+    let widened = PreciseMesh::from(&rounded);
+    let written = write(&widened, &WriteOptions::default());
+    assert_eq!(parse::<Mesh>(written.as_bytes()), rounded);
+
+    // A Mesh written as f32 reads back as the shortest decimals instead.
+    let mut from_mesh = Vec::new();
+    write_mesh(&mut XmlWriter::new(&mut from_mesh), &rounded).unwrap();
+    let reread: PreciseMesh = parse(&from_mesh);
+    assert_eq!(reread.vertices[0].y, 0.00002);
+    assert_eq!(Mesh::from(&reread), rounded);
+}
+
+#[test]
+fn test_quantization_applies_to_f64_coordinates() {
+    let precise: PreciseMesh = parse(MESH_XML.as_bytes());
+    let options = WriteOptions {
+        vertex_quantization: Some(VertexQuantization {
+            step: 0.001,
+            tolerance: 0.0005,
+        }),
+        ..Default::default()
+    };
+    let written = write(&precise, &options);
+    assert!(
+        written.contains(r#"<vertex x="5000000.01" y="0" z="0"/>"#),
+        "{}",
+        written
+    );
+}
+
+#[test]
+fn test_statistics_far_from_origin() {
+    let mesh = doubled_cube(DVec3::splat(FAR));
+    let (area, volume) = mesh.compute_area_and_volume();
+    assert!((area / 6.0e-4 - 1.0).abs() < 1e-6, "{}", area);
+    assert!((volume / 1.0e-6 - 1.0).abs() < 1e-6, "{}", volume);
+
+    // The f32 box still contains every vertex.
+    let aabb = mesh.compute_aabb().unwrap();
+    for v in &mesh.vertices {
+        for (axis, c) in v.to_array().into_iter().enumerate() {
+            assert!(f64::from(aabb.min[axis]) <= c && c <= f64::from(aabb.max[axis]));
+        }
+    }
+
+    // As f32, all eight corners collapse into one point.
+    assert_eq!(Mesh::from(&mesh).compute_area_and_volume(), (0.0, 0.0));
+}
+
+#[test]
+fn test_repair_keeps_f64_coordinates() {
+    let origin = DVec3::splat(FAR) + 0.000_123;
+    let mut mesh = doubled_cube(origin);
+    let stats = mesh.repair(RepairOptions {
+        stitch_epsilon: 1e-6,
+        ..Default::default()
+    });
+    assert_eq!(stats.vertices_removed, 8);
+    assert_eq!(mesh.vertices, doubled_cube(origin).vertices[..8]);
+    assert_eq!(mesh.triangle_count(), 12);
+    let volume = mesh.compute_area_and_volume().1;
+    assert!((volume / 1.0e-6 - 1.0).abs() < 1e-6, "{}", volume);
+}
+
+#[test]
+fn test_model_keeps_f64_coordinates() {
+    let xml = format!(
+        r#"<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    <resources><object id="1" type="model">{}</object></resources>
+    <build><item objectid="1"/></build>
+</model>"#,
+        MESH_XML
+    );
+    let options = ParseOptions {
+        precise_coordinates: true,
+        ..Default::default()
+    };
+    let parse_precise =
+        |xml: &[u8]| -> Model { parse_model_with_options(Cursor::new(xml), &options).unwrap() };
+    let precise_mesh =
+        |model: &Model| match &model.resources.get_object(ResourceId(1)).unwrap().geometry {
+            Geometry::PreciseMesh(mesh) => mesh.clone(),
+            other => panic!("expected a PreciseMesh, got {:?}", other),
+        };
+    let expected: PreciseMesh = parse(MESH_XML.as_bytes());
+
+    let model = parse_precise(xml.as_bytes());
+    assert_eq!(precise_mesh(&model).vertices, expected.vertices);
+
+    let mut package = Cursor::new(Vec::new());
+    model.write(&mut package).unwrap();
+    let mut archive = ZipArchiver::new(Cursor::new(package.into_inner())).unwrap();
+    let path = find_model_path(&mut archive).unwrap();
+    let written = parse_precise(&archive.read_entry(&path).unwrap());
+    assert_eq!(precise_mesh(&written).vertices, expected.vertices);
+
+    // Measured from the f64 coordinates: as f32, x snaps to a 0.5 mm grid.
+    let stats = written.compute_stats_with(&mut archive, &options).unwrap();
+    assert_eq!(stats.geometry.vertex_count, 3);
+    let area = expected.compute_area_and_volume().0;
+    assert!(area > 0.0);
+    assert!((stats.geometry.surface_area / area - 1.0).abs() < 1e-6);
+}
@@ -1,5 +1,10 @@
-use lib3mf_core::model::Geometry;
+#![cfg(feature = "writer")]
+
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::model::{Geometry, Model, Package, ResourceId};
 use lib3mf_core::parser::parse_model;
+use lib3mf_core::validation::ValidationLevel;
+use lib3mf_core::writer::package_writer::PackageWriter;
 use std::io::Cursor;
 use uuid::Uuid;
 
@@ -86,3 +91,206 @@ fn test_parse_production_extension() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// An assembly (2) of a mesh (1) in the build; only the listed elements get a UUID. The
+/// Production namespace is declared if any of them does.
+fn assembly(build_uuid: &str, item_uuid: &str, object_uuid: &str) -> Model {
+    let uuids = [build_uuid, item_uuid, object_uuid];
+    let declarations = if uuids.iter().all(|uuid| uuid.is_empty()) {
+        ""
+    } else {
+        r#"xmlns:p="http://schemas.microsoft.com/3dmanufacturing/production/2015/06""#
+    };
+    production_assembly(declarations, build_uuid, item_uuid, object_uuid)
+}
+
+/// [`assembly`] with `declarations` (namespaces and `requiredextensions`) on `<model>`.
+fn production_assembly(
+    declarations: &str,
+    build_uuid: &str,
+    item_uuid: &str,
+    object_uuid: &str,
+) -> Model {
+    let attr = |uuid: &str| {
+        if uuid.is_empty() {
+            String::new()
+        } else {
+            format!(r#"p:UUID="{}""#, uuid)
+        }
+    };
+    let xml = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    {}>
+    <resources>
+        <object id="1" type="model">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="1" y="0" z="0" />
+                    <vertex x="0" y="1" z="0" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="1" v3="2" />
+                </triangles>
+            </mesh>
+        </object>
+        <object id="2" type="model" {}>
+            <components>
+                <component objectid="1" />
+            </components>
+        </object>
+    </resources>
+    <build {}>
+        <item objectid="2" {} />
+    </build>
+</model>"#,
+        declarations,
+        attr(object_uuid),
+        attr(build_uuid),
+        attr(item_uuid)
+    );
+    parse_model(Cursor::new(xml)).unwrap()
+}
+
+fn production_codes(model: &Model, level: ValidationLevel) -> Vec<u32> {
+    model
+        .validate(level)
+        .items
+        .iter()
+        .map(|i| i.code)
+        .filter(|c| (5101..=5102).contains(c))
+        .collect()
+}
+
+const UUID_A: &str = "3f1c7e52-0d4b-4c7e-9d55-8a1e2b3c4d5e";
+const UUID_B: &str = "9b2d4f60-1e2a-4b3c-8d4e-5f6a7b8c9d0e";
+
+#[test]
+fn test_spec_uuid_attribute_is_parsed() -> anyhow::Result<()> {
+    let model = assembly(UUID_A, UUID_B, "");
+    assert_eq!(model.build.uuid, Some(Uuid::parse_str(UUID_A)?));
+    assert_eq!(model.build.items[0].uuid, Some(Uuid::parse_str(UUID_B)?));
+    assert!(model.uses_production_extension());
+    assert!(!assembly("", "", "").uses_production_extension());
+    Ok(())
+}
+
+#[test]
+fn test_strict_requires_uuids_when_production_is_used() {
+    // Build, object 1 and the component lack UUIDs
+    let model = assembly("", UUID_A, UUID_B);
+    assert!(production_codes(&model, ValidationLevel::Standard).is_empty());
+    let report = model.validate(ValidationLevel::Strict);
+    let missing: Vec<_> = report
+        .items
+        .iter()
+        .filter(|i| i.code == 5101)
+        .map(|i| i.message.as_str())
+        .collect();
+    assert_eq!(missing.len(), 3, "{:?}", missing);
+    assert!(
+        missing
+            .iter()
+            .any(|m| m.starts_with("Component 0 of Object 2"))
+    );
+
+    // Files that don't use the extension aren't required to
+    assert!(production_codes(&assembly("", "", ""), ValidationLevel::Strict).is_empty());
+}
+
+#[test]
+fn test_strict_requires_uuids_when_production_is_declared() {
+    const NS: &str = "http://schemas.microsoft.com/3dmanufacturing/production/2015/06";
+
+    // Declaring the namespace is enough, even if no element carries a UUID
+    let declared = production_assembly(&format!(r#"xmlns:p="{}""#, NS), "", "", "");
+    assert_eq!(declared.production_namespace.as_deref(), Some(NS));
+    assert!(declared.uses_production_extension());
+    // The build, its item, both objects and the component
+    assert_eq!(
+        production_codes(&declared, ValidationLevel::Strict),
+        [5101; 5]
+    );
+
+    // Any prefix bound to the namespace counts, as does requiring it
+    let required = production_assembly(
+        &format!(r#"xmlns:prod="{}" requiredextensions="prod""#, NS),
+        "",
+        "",
+        "",
+    );
+    assert_eq!(required.production_namespace.as_deref(), Some(NS));
+    assert_eq!(
+        production_codes(&required, ValidationLevel::Strict).len(),
+        5
+    );
+}
+
+#[test]
+fn test_duplicate_uuids_are_rejected() {
+    let model = assembly(UUID_A, UUID_B, UUID_A);
+    assert_eq!(production_codes(&model, ValidationLevel::Standard), [5102]);
+}
+
+#[test]
+fn test_assign_missing_uuids() {
+    let mut model = assembly("", UUID_A, "");
+    assert_eq!(model.assign_missing_uuids(), 4);
+    assert_eq!(
+        model.build.items[0].uuid,
+        Some(Uuid::parse_str(UUID_A).unwrap())
+    );
+    assert!(production_codes(&model, ValidationLevel::Strict).is_empty());
+    assert_eq!(model.assign_missing_uuids(), 0);
+}
+
+#[test]
+fn test_writer_assigns_and_writes_uuids() {
+    let model = assembly("", "", "");
+    let mut buffer = Cursor::new(Vec::new());
+    PackageWriter::new(&mut buffer)
+        .with_production_uuids(true)
+        .write(&Package::new(model.clone()))
+        .unwrap();
+
+    let mut archiver = ZipArchiver::new(Cursor::new(buffer.into_inner())).unwrap();
+    let path = find_model_path(&mut archiver).unwrap();
+    let written = parse_model(Cursor::new(archiver.read_entry(&path).unwrap())).unwrap();
+
+    assert!(written.build.uuid.is_some());
+    assert!(written.build.items[0].uuid.is_some());
+    assert!(
+        written
+            .resources
+            .get_object(ResourceId(1))
+            .unwrap()
+            .uuid
+            .is_some()
+    );
+    assert!(written.uses_production_extension());
+    assert!(production_codes(&written, ValidationLevel::Strict).is_empty());
+
+    // The source model is left as it was
+    assert!(!model.uses_production_extension());
+}
+
+#[test]
+fn test_writer_declares_production_only_when_used() {
+    let write = |model: &Model| {
+        let mut buffer = Cursor::new(Vec::new());
+        PackageWriter::new(&mut buffer)
+            .write(&Package::new(model.clone()))
+            .unwrap();
+        let mut archiver = ZipArchiver::new(Cursor::new(buffer.into_inner())).unwrap();
+        let path = find_model_path(&mut archiver).unwrap();
+        String::from_utf8(archiver.read_entry(&path).unwrap()).unwrap()
+    };
+
+    let plain = write(&assembly("", "", ""));
+    assert!(!plain.contains("xmlns:p="));
+    let reparsed = parse_model(Cursor::new(plain)).unwrap();
+    assert!(production_codes(&reparsed, ValidationLevel::Strict).is_empty());
+
+    assert!(write(&assembly(UUID_A, "", "")).contains("xmlns:p="));
+}
@@ -0,0 +1,240 @@
+use lib3mf_core::model::Model;
+use lib3mf_core::parser::parse_model;
+use lib3mf_core::validation::{ValidationLevel, ValidationSeverity};
+use std::io::Cursor;
+
+/// Wraps `objects` in a model with a 2-entry base material group (1), a 3-entry color
+/// group (2), a texture (3) and a multiproperties group (4) layering 1 and 2.
+fn model(multis: &str, objects: &str) -> Model {
+    let xml = format!(
+        r##"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:m="http://schemas.microsoft.com/3dmanufacturing/material/2015/02">
+    <resources>
+        <basematerials id="1">
+            <base name="PLA" displaycolor="#FF0000" />
+            <base name="PETG" displaycolor="#0000FF" />
+        </basematerials>
+        <m:colorgroup id="2">
+            <m:color color="#00FF00" />
+            <m:color color="#00FF80" />
+            <m:color color="#00FFFF" />
+        </m:colorgroup>
+        <m:texture2d id="3" path="/3D/Textures/wood.png" contenttype="image/png" />
+        <m:multiproperties id="4" pids="1 2">
+            {multis}
+        </m:multiproperties>
+        {objects}
+    </resources>
+    <build />
+</model>"##
+    );
+    parse_model(Cursor::new(xml)).unwrap()
+}
+
+fn mesh_object(id: u32, attrs: &str, triangle_attrs: &str) -> String {
+    format!(
+        r#"<object id="{id}" type="model" {attrs}>
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="1" y="0" z="0" />
+                    <vertex x="0" y="1" z="0" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="1" v3="2" {triangle_attrs} />
+                </triangles>
+            </mesh>
+        </object>"#
+    )
+}
+
+fn codes(model: &Model, level: ValidationLevel) -> Vec<u32> {
+    let mut codes: Vec<u32> = model
+        .validate(level)
+        .items
+        .iter()
+        .map(|i| i.code)
+        .filter(|c| (2011..=2015).contains(c) || (2026..=2028).contains(c))
+        .collect();
+    codes.sort_unstable();
+    codes
+}
+
+const VALID_MULTI: &str = r#"<m:multi pindices="1 2" />"#;
+
+#[test]
+fn test_valid_property_indices() {
+    let objects = [
+        mesh_object(10, r#"pid="1" pindex="1""#, r#"p1="0" p2="1" p3="1""#),
+        mesh_object(11, "", r#"pid="2" p1="2""#),
+        mesh_object(12, r#"pid="4" pindex="0""#, r#"p1="0""#),
+        mesh_object(13, "", ""),
+    ]
+    .concat();
+    let model = model(VALID_MULTI, &objects);
+    assert!(codes(&model, ValidationLevel::Strict).is_empty());
+}
+
+#[test]
+fn test_out_of_range_indices() {
+    let objects = [
+        // Object pindex past the end of the base materials
+        mesh_object(10, r#"pid="1" pindex="2""#, ""),
+        // Triangle indices checked against the triangle's own pid
+        mesh_object(
+            11,
+            r#"pid="1" pindex="0""#,
+            r#"pid="2" p1="2" p2="3" p3="0""#,
+        ),
+        // Pid-less triangle indices fall back to the object's group
+        mesh_object(12, r#"pid="1" pindex="0""#, r#"p1="1" p2="5""#),
+    ]
+    .concat();
+    let model = model(VALID_MULTI, &objects);
+    assert_eq!(codes(&model, ValidationLevel::Standard), [2011, 2013, 2013]);
+
+    let report = model.validate(ValidationLevel::Standard);
+    let messages: Vec<_> = report
+        .items
+        .iter()
+        .filter(|i| i.code == 2013)
+        .map(|i| i.message.as_str())
+        .collect();
+    assert!(messages.iter().any(|m| m.contains("Object 11 p2 3")));
+    assert!(messages.iter().any(|m| m.contains("Object 12 p2 5")));
+}
+
+#[test]
+fn test_indices_without_property_group() {
+    let objects = [
+        // No pid on either the triangle or the object
+        mesh_object(10, "", r#"p1="0""#),
+        // Pids that aren't property groups
+        mesh_object(11, r#"pid="3" pindex="0""#, ""),
+        mesh_object(12, "", r#"pid="3" p1="0""#),
+    ]
+    .concat();
+    let model = model(VALID_MULTI, &objects);
+    assert_eq!(codes(&model, ValidationLevel::Standard), [2012, 2014, 2014]);
+
+    // Minimal doesn't check references
+    assert!(codes(&model, ValidationLevel::Minimal).is_empty());
+}
+
+#[test]
+fn test_multiproperties_layers() {
+    let multis = r#"
+        <m:multi pindices="1 2" />
+        <m:multi pindices="0 1 0" />
+        <m:multi pindices="2 0" />
+        <m:multi pindices="1" />
+    "#;
+    let model = model(multis, "");
+    assert_eq!(codes(&model, ValidationLevel::Standard), [2026, 2027]);
+
+    let report = model.validate(ValidationLevel::Strict);
+    let missing_layer = report.items.iter().find(|i| i.code == 2028).unwrap();
+    assert_eq!(missing_layer.severity, ValidationSeverity::Warning);
+    assert!(missing_layer.message.contains("multi 3"));
+}
+
+#[test]
+fn test_strict_requires_p1() {
+    let objects = mesh_object(10, r#"pid="2" pindex="0""#, r#"p2="1""#);
+    let model = model(VALID_MULTI, &objects);
+    assert!(codes(&model, ValidationLevel::Standard).is_empty());
+    assert_eq!(codes(&model, ValidationLevel::Strict), [2015]);
+}
+
+fn texture_codes(model: &Model, level: ValidationLevel) -> Vec<u32> {
+    let mut codes: Vec<u32> = model
+        .validate(level)
+        .items
+        .iter()
+        .map(|i| i.code)
+        .filter(|c| (2110..=2112).contains(c))
+        .collect();
+    codes.sort_unstable();
+    codes
+}
+
+#[test]
+fn test_strict_texture_checks() {
+    let mut model = model(VALID_MULTI, "");
+    // Texture checks are Strict only
+    assert!(texture_codes(&model, ValidationLevel::Standard).is_empty());
+
+    // Missing image part is a warning
+    let report = model.validate(ValidationLevel::Strict);
+    let missing = report.items.iter().find(|i| i.code == 2111).unwrap();
+    assert_eq!(missing.severity, ValidationSeverity::Warning);
+
+    // A JPEG stored under an image/png texture
+    model.attachments.insert(
+        "3D/Textures/wood.png".to_string(),
+        vec![0xFF, 0xD8, 0xFF, 0xE0, 0, 0x10],
+    );
+    assert_eq!(texture_codes(&model, ValidationLevel::Strict), [2112]);
+
+    model.attachments.insert(
+        "3D/Textures/wood.png".to_string(),
+        b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec(),
+    );
+    assert!(texture_codes(&model, ValidationLevel::Strict).is_empty());
+}
+
+#[test]
+fn test_texture_attributes_rejected_when_invalid() {
+    let xml = |attrs: &str| {
+        format!(
+            r#"<model xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:m="http://schemas.microsoft.com/3dmanufacturing/material/2015/02">
+    <resources>
+        <m:texture2d id="1" path="/3D/Textures/a.png" contenttype="image/png" {attrs} />
+    </resources>
+    <build />
+</model>"#
+        )
+    };
+    assert!(parse_model(Cursor::new(xml(r#"tilestyleu="clamp" filter="auto""#))).is_ok());
+    assert!(parse_model(Cursor::new(xml(r#"tilestylev="repeat""#))).is_err());
+    assert!(parse_model(Cursor::new(xml(r#"filter="bicubic""#))).is_err());
+}
+
+#[test]
+fn test_strict_warns_on_transparent_colors() {
+    let xml = r##"<model xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:m="http://schemas.microsoft.com/3dmanufacturing/material/2015/02">
+    <resources>
+        <basematerials id="1">
+            <base name="Clear" displaycolor="#FFFFFF00" />
+            <base name="Tinted" displaycolor="#FFFFFF80" />
+        </basematerials>
+        <m:colorgroup id="2">
+            <m:color color="transparent" />
+            <m:color color="#00FF00" />
+        </m:colorgroup>
+    </resources>
+    <build />
+</model>"##;
+    let model = parse_model(Cursor::new(xml)).unwrap();
+
+    let transparent = |level| {
+        model
+            .validate(level)
+            .items
+            .into_iter()
+            .filter(|i| i.code == 2113)
+            .collect::<Vec<_>>()
+    };
+    assert!(transparent(ValidationLevel::Standard).is_empty());
+    let warnings = transparent(ValidationLevel::Strict);
+    assert_eq!(warnings.len(), 2);
+    assert!(
+        warnings
+            .iter()
+            .all(|i| i.severity == ValidationSeverity::Warning)
+    );
+    assert!(warnings[0].message.contains("'Clear'"));
+}
@@ -0,0 +1,179 @@
+use glam::{Mat4, Vec3};
+use lib3mf_core::model::{
+    BuildItem, Component, Components, Geometry, Mesh, Model, Object, ObjectFilter, ObjectType,
+    ResourceId,
+};
+
+fn object(id: u32, name: &str, object_type: ObjectType, geometry: Geometry) -> Object {
+    Object {
+        id: ResourceId(id),
+        object_type,
+        name: Some(name.to_string()),
+        part_number: None,
+        uuid: None,
+        pid: None,
+        pindex: None,
+        thumbnail: None,
+        geometry,
+    }
+}
+
+/// A closed axis-aligned box with the given edge length (12 triangles, 8 vertices).
+fn cube(size: f32) -> Geometry {
+    let mut mesh = Mesh::new();
+    for i in 0..8 {
+        mesh.add_vertex(
+            if i & 1 != 0 { size } else { 0.0 },
+            if i & 2 != 0 { size } else { 0.0 },
+            if i & 4 != 0 { size } else { 0.0 },
+        );
+    }
+    for [a, b, c] in [
+        [0, 2, 1],
+        [1, 2, 3],
+        [4, 5, 6],
+        [5, 7, 6],
+        [0, 1, 4],
+        [1, 5, 4],
+        [2, 6, 3],
+        [3, 6, 7],
+        [0, 4, 2],
+        [2, 4, 6],
+        [1, 3, 5],
+        [3, 7, 5],
+    ] {
+        mesh.add_triangle(a, b, c);
+    }
+    Geometry::Mesh(mesh)
+}
+
+fn component(id: u32, transform: Mat4) -> Component {
+    Component {
+        object_id: ResourceId(id),
+        path: None,
+        uuid: None,
+        transform,
+    }
+}
+
+/// Objects: 1 small cube, 2 large cube, 3 support cube, 4 assembly of 1 (twice, one scaled).
+fn sample() -> Model {
+    let mut model = Model::default();
+    let resources = &mut model.resources;
+    resources
+        .add_object(object(1, "Bracket", ObjectType::Model, cube(1.0)))
+        .unwrap();
+    resources
+        .add_object(object(2, "Base plate", ObjectType::Model, cube(3.0)))
+        .unwrap();
+    resources
+        .add_object(object(3, "Tree support", ObjectType::Support, cube(2.0)))
+        .unwrap();
+    resources
+        .add_object(object(
+            4,
+            "Bracket pair",
+            ObjectType::Model,
+            Geometry::Components(Components {
+                components: vec![
+                    component(1, Mat4::IDENTITY),
+                    component(1, Mat4::from_scale(Vec3::splat(2.0))),
+                ],
+            }),
+        ))
+        .unwrap();
+    for id in [2, 4] {
+        model.build.items.push(BuildItem {
+            object_id: ResourceId(id),
+            uuid: None,
+            path: None,
+            part_number: None,
+            transform: Mat4::IDENTITY,
+            printable: None,
+            metadata: Default::default(),
+        });
+    }
+    model
+}
+
+fn ids(ids: Vec<ResourceId>) -> Vec<u32> {
+    ids.into_iter().map(|id| id.0).collect()
+}
+
+#[test]
+fn test_fluent_selection() {
+    let model = sample();
+    let query = model.query();
+
+    assert_eq!(ids(query.objects().ids()), [1, 2, 3, 4]);
+    assert_eq!(ids(query.objects().of_type(ObjectType::Support).ids()), [3]);
+    assert_eq!(ids(query.objects().name_contains("BRACKET").ids()), [1, 4]);
+    assert_eq!(ids(query.objects().in_build().ids()), [2, 4]);
+    assert_eq!(ids(query.objects().with_triangle_count_gt(12).ids()), [4]);
+    assert_eq!(
+        ids(query.objects().with_triangle_count_lt(13).ids()),
+        [1, 2, 3]
+    );
+    assert!(
+        query
+            .objects()
+            .filter(|o| o.part_number.is_some())
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_component_metrics_and_largest() {
+    let model = sample();
+    let metrics = model.query().objects().with_metrics();
+    let pair = metrics
+        .iter()
+        .find(|(o, _)| o.id == ResourceId(4))
+        .unwrap()
+        .1;
+    assert_eq!(pair.triangles, 24);
+    assert_eq!(pair.vertices, 16);
+    // 1 + 2³ for the scaled copy
+    assert!((pair.volume - 9.0).abs() < 1e-4);
+
+    let largest = model.query().objects().largest_by_volume(2);
+    assert_eq!(ids(largest.ids()), [2, 4]);
+    assert_eq!(model.query().objects().largest_by_volume(10).count(), 4);
+}
+
+#[test]
+fn test_filter_expressions() {
+    let model = sample();
+    let select = |expr: &str| {
+        let filter = ObjectFilter::parse(expr).unwrap();
+        ids(model.query().objects().matching(&filter).ids())
+    };
+
+    assert_eq!(select("type=support"), [3]);
+    assert_eq!(select("volume>5 && build=true"), [2, 4]);
+    assert_eq!(select("name~plate || id==3"), [2, 3]);
+    assert_eq!(select("triangles>=24"), [4]);
+    assert_eq!(select("vertices<10 && type!=support"), [1, 2]);
+    assert_eq!(select("volume > 1e3"), Vec::<u32>::new());
+}
+
+#[test]
+fn test_invalid_filter_expressions() {
+    for expr in [
+        "",
+        "triangles",
+        "color=red",
+        "triangles>many",
+        "type=widget",
+        "name>a",
+        "id=1 &&",
+    ] {
+        let err = ObjectFilter::parse(expr).unwrap_err();
+        assert!(
+            err.to_string().contains("Invalid query condition"),
+            "{}: {}",
+            expr,
+            err
+        );
+    }
+}
@@ -1,4 +1,11 @@
-use lib3mf_core::model::{Mesh, MeshRepair, RepairOptions};
+#![cfg(feature = "testutil")]
+
+use lib3mf_core::model::repair::flatten_instances;
+use lib3mf_core::model::{
+    DegenerateArea, Geometry, Mesh, MeshRepair, MeshSnapshot, Object, ObjectType, RepairCondition,
+    RepairOptions, RepairPass, RepairPipeline, ResolvedMesh, ResourceId, Unit,
+};
+use lib3mf_core::testutil::meshes::tetrahedron;
 
 #[test]
 fn test_vertex_stitching() {
@@ -82,6 +89,7 @@ fn test_orientation_harmonization() {
         harmonize_orientations: true,
         remove_islands: false,
         fill_holes: false,
+        ..Default::default()
     });
 
     assert_eq!(stats.triangles_flipped, 1);
@@ -118,6 +126,7 @@ fn test_island_removal() {
         harmonize_orientations: false,
         remove_islands: true,
         fill_holes: false,
+        ..Default::default()
     });
 
     assert_eq!(stats.triangles_removed, 1);
@@ -150,6 +159,7 @@ fn test_hole_filling() {
         harmonize_orientations: false,
         remove_islands: false,
         fill_holes: true,
+        ..Default::default()
     });
 
     // Should add 1 triangle to cap the (1,3,2) triangle's reverse side
@@ -158,3 +168,285 @@ fn test_hole_filling() {
     assert_eq!(stats.triangles_added, 1);
     assert_eq!(mesh.triangles.len(), 2);
 }
+
+/// A single open triangle: one boundary loop that hole filling would cap.
+fn open_object(object_type: ObjectType) -> Object {
+    let mut mesh = Mesh::new();
+    mesh.add_vertex(0.0, 0.0, 0.0);
+    mesh.add_vertex(1.0, 0.0, 0.0);
+    mesh.add_vertex(0.0, 1.0, 0.0);
+    mesh.add_triangle(0, 1, 2);
+    Object {
+        id: ResourceId(1),
+        object_type,
+        name: None,
+        part_number: None,
+        uuid: None,
+        pid: None,
+        pindex: None,
+        thumbnail: None,
+        geometry: Geometry::Mesh(mesh),
+    }
+}
+
+#[test]
+fn test_options_for_object_type() {
+    let options = RepairOptions {
+        remove_islands: true,
+        fill_holes: true,
+        ..Default::default()
+    };
+    for object_type in [ObjectType::Model, ObjectType::SolidSupport] {
+        assert_eq!(options.for_object_type(object_type), options);
+    }
+    for object_type in [ObjectType::Support, ObjectType::Surface, ObjectType::Other] {
+        let adjusted = options.for_object_type(object_type);
+        assert!(!adjusted.remove_islands);
+        assert!(!adjusted.fill_holes);
+        // Clean-up repairs still apply
+        assert!(adjusted.remove_degenerate);
+        assert!(adjusted.harmonize_orientations);
+    }
+}
+
+#[test]
+fn test_object_repair_is_type_aware() {
+    let options = RepairOptions {
+        fill_holes: true,
+        ..Default::default()
+    };
+
+    let mut model = open_object(ObjectType::Model);
+    assert_eq!(model.repair(options).triangles_added, 1);
+
+    let mut surface = open_object(ObjectType::Surface);
+    assert_eq!(surface.repair(options).triangles_added, 0);
+    match &surface.geometry {
+        Geometry::Mesh(mesh) => assert_eq!(mesh.triangles.len(), 1),
+        _ => unreachable!(),
+    }
+}
+
+fn instance(mesh: Mesh, transform: glam::Mat4) -> ResolvedMesh {
+    ResolvedMesh {
+        mesh,
+        transform,
+        object_type: ObjectType::Model,
+        name: None,
+        unit: Unit::Millimeter,
+    }
+}
+
+#[test]
+fn test_world_mesh_keeps_mirrored_instances_outward() {
+    let mirror = glam::Mat4::from_scale(glam::Vec3::new(-1.0, 1.0, 1.0));
+    let mirrored = instance(tetrahedron(1.0), mirror);
+    assert!(mirrored.is_mirrored());
+    assert!(!instance(tetrahedron(1.0), glam::Mat4::IDENTITY).is_mirrored());
+
+    let (_, volume) = mirrored.world_mesh().compute_area_and_volume();
+    assert!(volume > 0.0, "mirrored tetrahedron is inside out: {volume}");
+}
+
+#[test]
+fn test_flatten_instances_harmonizes_across_instances() {
+    // Two halves of a unit square as separate objects, wound inconsistently
+    let mut first = Mesh::new();
+    first.add_vertex(0.0, 0.0, 0.0);
+    first.add_vertex(1.0, 0.0, 0.0);
+    first.add_vertex(0.0, 1.0, 0.0);
+    first.add_triangle(0, 1, 2);
+    let mut second = Mesh::new();
+    second.add_vertex(1.0, 0.0, 0.0);
+    second.add_vertex(0.0, 1.0, 0.0);
+    second.add_vertex(1.0, 1.0, 0.0);
+    second.add_triangle(0, 1, 2);
+
+    let (flat, stats) = flatten_instances(
+        &[
+            instance(first, glam::Mat4::IDENTITY),
+            instance(second, glam::Mat4::IDENTITY),
+        ],
+        RepairOptions::default(),
+    );
+    assert_eq!(flat.vertices.len(), 4);
+    assert_eq!(stats.triangles_flipped, 1);
+    let normals: Vec<f32> = flat
+        .triangles
+        .iter()
+        .map(|t| {
+            let v = |i: u32| glam::Vec3::from(flat.vertices[i as usize]);
+            (v(t.v2) - v(t.v1)).cross(v(t.v3) - v(t.v1)).z
+        })
+        .collect();
+    assert!(normals[0].signum() == normals[1].signum());
+}
+
+#[test]
+fn test_flatten_instances_orients_closed_pieces_outward() {
+    let mut inside_out = tetrahedron(1.0);
+    for tri in &mut inside_out.triangles {
+        std::mem::swap(&mut tri.v2, &mut tri.v3);
+    }
+    let mirror = glam::Mat4::from_translation(glam::Vec3::new(5.0, 0.0, 0.0))
+        * glam::Mat4::from_scale(glam::Vec3::new(-1.0, 1.0, 1.0));
+
+    let (flat, stats) = flatten_instances(
+        &[
+            instance(inside_out, glam::Mat4::IDENTITY),
+            instance(tetrahedron(1.0), mirror),
+        ],
+        RepairOptions::default(),
+    );
+    assert_eq!(flat.triangles.len(), 8);
+    assert_eq!(stats.triangles_flipped, 4);
+    let (_, volume) = flat.compute_area_and_volume();
+    assert!((volume - 2.0 / 6.0).abs() < 1e-6, "volume {volume}");
+}
+
+/// Two triangles of a unit square, wound inconsistently, with a near-duplicate vertex.
+fn unstitched_square() -> Mesh {
+    let mut mesh = Mesh::new();
+    mesh.add_vertex(0.0, 0.0, 0.0);
+    mesh.add_vertex(1.0, 0.0, 0.0);
+    mesh.add_vertex(0.0, 1.0, 0.0);
+    mesh.add_vertex(1.0, 0.0, 0.00001);
+    mesh.add_vertex(1.0, 1.0, 0.0);
+    mesh.add_triangle(0, 1, 2);
+    mesh.add_triangle(3, 2, 4);
+    mesh
+}
+
+#[test]
+fn test_pipeline_from_options_matches_repair() {
+    let options = RepairOptions {
+        remove_islands: true,
+        ..Default::default()
+    };
+    let mut repaired = unstitched_square();
+    let stats = repaired.repair(options);
+    let mut piped = unstitched_square();
+    let report = RepairPipeline::from(options).run(&mut piped);
+
+    assert_eq!(report.stats, stats);
+    assert_eq!(piped, repaired);
+    let passes: Vec<RepairPass> = report.passes.iter().map(|p| p.step.pass).collect();
+    assert_eq!(
+        passes,
+        [
+            RepairPass::Stitch { epsilon: 1e-4 },
+            RepairPass::RemoveDegenerate {
+                area: DegenerateArea::default(),
+            },
+            RepairPass::RemoveDuplicateFaces,
+            RepairPass::RemoveUnusedVertices,
+            RepairPass::RemoveIslands,
+            RepairPass::HarmonizeOrientations,
+        ]
+    );
+}
+
+#[test]
+fn test_pipeline_snapshots_and_conditions() {
+    let mut mesh = unstitched_square();
+    let report = RepairPipeline::new()
+        .then_if(
+            RepairCondition::IfInconsistentOrientation,
+            RepairPass::HarmonizeOrientations,
+        )
+        .then(RepairPass::Stitch { epsilon: 1e-4 })
+        .then_if(
+            RepairCondition::IfInconsistentOrientation,
+            RepairPass::HarmonizeOrientations,
+        )
+        .then(RepairPass::Validate)
+        .then_if(RepairCondition::IfNotManifold, RepairPass::FillHoles)
+        .run(&mut mesh);
+
+    // Before stitching the triangles share no edge, so their winding cannot disagree
+    let first = &report.passes[0];
+    assert!(first.skipped);
+    assert_eq!(first.before.boundary_edges, 6);
+    assert_eq!(first.before, first.after);
+
+    let stitched = &report.passes[1];
+    assert_eq!(stitched.stats.vertices_removed, 1);
+    assert_eq!(stitched.after.boundary_edges, 4);
+    assert_eq!(stitched.after.inconsistent_edges, 1);
+
+    let harmonized = &report.passes[2];
+    assert!(!harmonized.skipped);
+    assert_eq!(harmonized.stats.triangles_flipped, 1);
+    assert!(harmonized.after.is_consistently_oriented());
+
+    let validated = &report.passes[3];
+    assert_eq!(validated.stats, Default::default());
+    assert!(!validated.after.is_manifold());
+    // Still open after validation, so the hole is filled
+    let filled = &report.passes[4];
+    assert!(!filled.skipped);
+    assert_eq!(filled.stats.triangles_added, 2);
+    assert!(filled.after.is_closed());
+
+    assert_eq!(report.initial().unwrap().vertices, 5);
+    assert_eq!(*report.last().unwrap(), MeshSnapshot::of(&mesh));
+    assert_eq!(report.stats.vertices_removed, 1);
+}
+
+#[test]
+fn test_pipeline_for_object_type() {
+    let pipeline = RepairPipeline::new()
+        .then(RepairPass::RemoveDegenerate {
+            area: DegenerateArea::default(),
+        })
+        .then(RepairPass::RemoveIslands)
+        .then_if(RepairCondition::IfOpen, RepairPass::FillHoles);
+    assert_eq!(
+        pipeline.clone().for_object_type(ObjectType::Model),
+        pipeline
+    );
+    let surface = pipeline.for_object_type(ObjectType::Surface);
+    assert_eq!(surface.steps().len(), 1);
+    assert!(matches!(
+        surface.steps()[0].pass,
+        RepairPass::RemoveDegenerate { .. }
+    ));
+}
+
+#[test]
+fn test_degenerate_area_follows_scale_and_unit() {
+    // A 20 mm part modeled in meters, with a real 10 µm triangle and a collapsed one
+    let mut mesh = Mesh::new();
+    mesh.add_vertex(0.0, 0.0, 0.0);
+    mesh.add_vertex(0.02, 0.0, 0.0);
+    mesh.add_vertex(0.0, 0.02, 0.0);
+    mesh.add_triangle(0, 1, 2);
+    mesh.add_vertex(0.0, 0.0, 0.01);
+    mesh.add_vertex(1e-5, 0.0, 0.01);
+    mesh.add_vertex(0.0, 1e-5, 0.01);
+    mesh.add_triangle(3, 4, 5);
+    mesh.add_triangle(3, 4, 4);
+    let options = RepairOptions {
+        stitch_epsilon: 0.0,
+        unit: Unit::Meter,
+        ..Default::default()
+    };
+
+    // Relative to the bounding box, only the collapsed triangle is degenerate
+    let mut relative = mesh.clone();
+    let stats = relative.repair(options);
+    assert_eq!(stats.triangles_removed, 1);
+    assert_eq!(stats.degenerate_areas.zero, 1);
+    assert_eq!(stats.degenerate_areas.count(), 1);
+
+    // In physical units, 1 mm² also takes the 5e-5 mm² triangle
+    let stats = mesh.repair(RepairOptions {
+        degenerate_area: DegenerateArea::SquareMillimeters(1.0),
+        ..options
+    });
+    assert_eq!(stats.triangles_removed, 2);
+    let areas = &stats.degenerate_areas;
+    assert_eq!(areas.decades.get(&-5), Some(&1));
+    assert!((areas.max.unwrap() - 5e-5).abs() < 1e-9, "{areas:?}");
+    assert_eq!(mesh.triangles.len(), 1);
+}
@@ -0,0 +1,306 @@
+use lib3mf_core::model::{Geometry, Model, ResourceId, ResourceKind, ResourceRef};
+use lib3mf_core::parser::parse_model;
+use lib3mf_core::utils::diff::{ResourceDiff, compare_models};
+use lib3mf_core::validation::ValidationLevel;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Cursor;
+
+/// A model using every resource type, with IDs spread out so remapping is visible.
+fn all_resources() -> Model {
+    let xml = r##"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:m="http://schemas.microsoft.com/3dmanufacturing/material/2015/02"
+    xmlns:b="http://schemas.3mf.io/3dmanufacturing/booleanoperations/2023/07"
+    xmlns:s="http://schemas.microsoft.com/3dmanufacturing/slice/2015/07"
+    xmlns:d="http://schemas.microsoft.com/3dmanufacturing/displacement/2024/01">
+    <resources>
+        <basematerials id="10">
+            <base name="PLA" displaycolor="#FF0000" />
+        </basematerials>
+        <m:colorgroup id="20">
+            <m:color color="#00FF00" />
+        </m:colorgroup>
+        <m:texture2d id="30" path="/3D/Textures/wood.png" contenttype="image/png" />
+        <m:texture2dgroup id="40" texid="30">
+            <m:tex2coord u="0" v="0" />
+        </m:texture2dgroup>
+        <m:compositematerials id="50" matid="10" matindices="0">
+            <m:composite values="1" />
+        </m:compositematerials>
+        <m:multiproperties id="60" pids="10 20">
+            <m:multi pindices="0 0" />
+        </m:multiproperties>
+        <d:displacement2d id="70" path="/3D/Textures/height.png" height="1" />
+        <slicestack id="80" zbottom="0">
+            <slice ztop="1">
+                <vertices>
+                    <vertex x="0" y="0" />
+                    <vertex x="1" y="0" />
+                    <vertex x="0" y="1" />
+                </vertices>
+                <polygon start="0">
+                    <segment v2="1" pid="20" p1="0" p2="0" />
+                    <segment v2="2" />
+                    <segment v2="0" />
+                </polygon>
+            </slice>
+        </slicestack>
+        <object id="100" type="model" pid="10" pindex="0">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="1" y="0" z="0" />
+                    <vertex x="0" y="1" z="0" />
+                    <vertex x="0" y="0" z="1" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="2" v3="1" pid="20" p1="0" />
+                    <triangle v1="0" v2="1" v3="3" pid="60" p1="0" />
+                    <triangle v1="1" v2="2" v3="3" />
+                    <triangle v1="0" v2="3" v3="2" />
+                </triangles>
+            </mesh>
+        </object>
+        <object id="110" type="model">
+            <components>
+                <component objectid="100" />
+                <component objectid="5" p:path="/3D/other.model"
+                    xmlns:p="http://schemas.microsoft.com/3dmanufacturing/production/2015/06" />
+            </components>
+        </object>
+        <b:booleanshape id="120" objectid="100">
+            <b:boolean operation="difference" objectid="110" />
+        </b:booleanshape>
+        <object id="130" type="model" s:slicestackid="80" />
+        <object id="140" type="model">
+            <d:displacementmesh>
+                <d:vertices>
+                    <d:vertex x="0" y="0" z="0" />
+                    <d:vertex x="1" y="0" z="0" />
+                    <d:vertex x="0" y="1" z="0" />
+                </d:vertices>
+                <d:triangles>
+                    <d:triangle v1="0" v2="1" v3="2" pid="40" p1="0" />
+                </d:triangles>
+                <d:normvectors>
+                    <d:normvector nx="0" ny="0" nz="1" />
+                </d:normvectors>
+            </d:displacementmesh>
+        </object>
+    </resources>
+    <build>
+        <item objectid="120" />
+        <item objectid="130" />
+    </build>
+</model>"##;
+    parse_model(Cursor::new(xml)).unwrap()
+}
+
+fn object(model: &Model, id: u32) -> &lib3mf_core::model::Object {
+    model
+        .resources
+        .get_object(ResourceId(id))
+        .unwrap_or_else(|| panic!("object {} missing", id))
+}
+
+fn issue_codes(model: &Model) -> Vec<u32> {
+    let mut codes: Vec<u32> = model
+        .validate(ValidationLevel::Standard)
+        .items
+        .iter()
+        .map(|i| i.code)
+        .collect();
+    codes.sort_unstable();
+    codes
+}
+
+#[test]
+fn test_allocate_id() {
+    let model = all_resources();
+    assert_eq!(model.resources.allocate_id(), ResourceId(141));
+    assert_eq!(Model::default().resources.allocate_id(), ResourceId(1));
+}
+
+#[test]
+fn test_normalize_ids_rewrites_all_references() {
+    let mut model = all_resources();
+    let mapping = model.normalize_ids().unwrap();
+    assert_eq!(mapping.len(), 13);
+    assert_eq!(mapping[&ResourceId(10)], ResourceId(1));
+    assert_eq!(mapping[&ResourceId(140)], ResourceId(13));
+
+    let mut ids: Vec<u32> = model.resources.iter_ids().map(|id| id.0).collect();
+    ids.sort_unstable();
+    assert_eq!(ids, (1..=13).collect::<Vec<_>>());
+
+    // Materials: 10→1, 20→2, 30→3, 40→4, 50→5, 60→6, 70→7, 80→8
+    let group = model.resources.get_texture_2d_group(ResourceId(4)).unwrap();
+    assert_eq!(group.texture_id, ResourceId(3));
+    let composite = model
+        .resources
+        .get_composite_materials(ResourceId(5))
+        .unwrap();
+    assert_eq!(composite.base_material_id, ResourceId(1));
+    let multi = model.resources.get_multi_properties(ResourceId(6)).unwrap();
+    assert_eq!(multi.pids, vec![ResourceId(1), ResourceId(2)]);
+    assert!(model.resources.get_displacement_2d(ResourceId(7)).is_some());
+    let stack = model.resources.get_slice_stack(ResourceId(8)).unwrap();
+    assert_eq!(
+        stack.slices[0].polygons[0].segments[0].pid,
+        Some(ResourceId(2))
+    );
+
+    // Objects: 100→9, 110→10, 120→11, 130→12, 140→13
+    let mesh_obj = object(&model, 9);
+    assert_eq!(mesh_obj.pid, Some(ResourceId(1)));
+    let Geometry::Mesh(mesh) = &mesh_obj.geometry else {
+        panic!("expected mesh");
+    };
+    let pids: Vec<_> = mesh.triangles.iter().map(|t| t.pid).collect();
+    assert_eq!(pids, vec![Some(2), Some(6), None, None]);
+
+    let Geometry::Components(comps) = &object(&model, 10).geometry else {
+        panic!("expected components");
+    };
+    assert_eq!(comps.components[0].object_id, ResourceId(9));
+    // References into other parts keep their IDs
+    assert_eq!(comps.components[1].object_id, ResourceId(5));
+
+    let Geometry::BooleanShape(shape) = &object(&model, 11).geometry else {
+        panic!("expected boolean shape");
+    };
+    assert_eq!(shape.base_object_id, ResourceId(9));
+    assert_eq!(shape.operations[0].object_id, ResourceId(10));
+
+    assert!(matches!(
+        object(&model, 12).geometry,
+        Geometry::SliceStack(ResourceId(8))
+    ));
+    let Geometry::DisplacementMesh(dmesh) = &object(&model, 13).geometry else {
+        panic!("expected displacement mesh");
+    };
+    assert_eq!(dmesh.triangles[0].pid, Some(4));
+
+    let items: Vec<_> = model.build.items.iter().map(|i| i.object_id).collect();
+    assert_eq!(items, vec![ResourceId(11), ResourceId(12)]);
+
+    // No dangling references: validation reports the same issues as before remapping
+    assert_eq!(issue_codes(&model), issue_codes(&all_resources()));
+}
+
+#[test]
+fn test_partial_remap_keeps_other_ids() {
+    let mut model = all_resources();
+    let mapping = HashMap::from([
+        (ResourceId(10), ResourceId(500)),
+        (ResourceId(100), ResourceId(600)),
+    ]);
+    model.remap_ids(&mapping).unwrap();
+
+    assert!(
+        model
+            .resources
+            .get_base_materials(ResourceId(500))
+            .is_some()
+    );
+    assert!(model.resources.get_color_group(ResourceId(20)).is_some());
+    assert_eq!(object(&model, 600).pid, Some(ResourceId(500)));
+    let multi = model
+        .resources
+        .get_multi_properties(ResourceId(60))
+        .unwrap();
+    assert_eq!(multi.pids, vec![ResourceId(500), ResourceId(20)]);
+}
+
+#[test]
+fn test_colliding_remap_is_rejected() {
+    let mut model = all_resources();
+    let before = model.resources.iter_ids().count();
+
+    // 10 → 20 collides with the existing color group
+    let mapping = HashMap::from([(ResourceId(10), ResourceId(20))]);
+    assert!(model.remap_ids(&mapping).is_err());
+
+    // Nothing changed
+    assert_eq!(model.resources.iter_ids().count(), before);
+    assert!(model.resources.get_base_materials(ResourceId(10)).is_some());
+    assert_eq!(object(&model, 100).pid, Some(ResourceId(10)));
+
+    // Swapping two IDs is fine
+    let swap = HashMap::from([
+        (ResourceId(10), ResourceId(20)),
+        (ResourceId(20), ResourceId(10)),
+    ]);
+    model.remap_ids(&swap).unwrap();
+    assert!(model.resources.get_base_materials(ResourceId(20)).is_some());
+    assert!(model.resources.get_color_group(ResourceId(10)).is_some());
+}
+
+#[test]
+fn test_iter_all_and_counts() {
+    let model = all_resources();
+    let res = &model.resources;
+
+    let ids: Vec<ResourceId> = res.iter_all().map(|r| r.id()).collect();
+    assert_eq!(ids, res.iter_ids().collect::<Vec<_>>());
+    for r in res.iter_all() {
+        assert_eq!(res.get(r.id()).map(|g| g.kind()), Some(r.kind()));
+    }
+    assert!(matches!(
+        res.get(ResourceId(20)),
+        Some(ResourceRef::ColorGroup(g)) if g.colors.len() == 1
+    ));
+    assert!(res.get(ResourceId(21)).is_none());
+
+    let expected = BTreeMap::from([
+        (ResourceKind::Object, 5),
+        (ResourceKind::BaseMaterials, 1),
+        (ResourceKind::ColorGroup, 1),
+        (ResourceKind::SliceStack, 1),
+        (ResourceKind::Texture2D, 1),
+        (ResourceKind::Texture2DGroup, 1),
+        (ResourceKind::CompositeMaterials, 1),
+        (ResourceKind::MultiProperties, 1),
+        (ResourceKind::Displacement2D, 1),
+    ]);
+    assert_eq!(res.counts(), expected);
+    assert_eq!(res.counts().values().sum::<usize>(), ids.len());
+}
+
+#[test]
+fn test_diff_covers_all_resource_kinds() {
+    let a = all_resources();
+    let mut b = all_resources();
+    b.resources.remove(ResourceId(50));
+    b.resources.remove(ResourceId(20));
+    b.resources
+        .add_color_group(lib3mf_core::model::ColorGroup {
+            id: ResourceId(50),
+            colors: vec![],
+        })
+        .unwrap();
+    b.resources
+        .add_color_group(lib3mf_core::model::ColorGroup {
+            id: ResourceId(150),
+            colors: vec![],
+        })
+        .unwrap();
+
+    let summary: Vec<String> = compare_models(&a, &b)
+        .resource_diffs
+        .iter()
+        .map(|d| match d {
+            ResourceDiff::Added { id, type_name } => format!("+{} {}", id, type_name),
+            ResourceDiff::Removed { id, type_name } => format!("-{} {}", id, type_name),
+            ResourceDiff::Changed { id, details } => format!("~{} {}", id, details.join("; ")),
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            "-20 colorgroup",
+            "~50 Type changed: compositematerials -> colorgroup",
+            "+150 colorgroup",
+        ]
+    );
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "writer")]
+
 use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
 use lib3mf_core::parser::parse_model;
 use std::fs::File;
@@ -0,0 +1,163 @@
+//! Signed distance fields sampled from meshes.
+
+use glam::Vec3;
+use lib3mf_core::Lib3mfError;
+use lib3mf_core::model::{Mesh, SdfGrid, SdfOptions, SpatialIndex};
+
+/// Closed, outward-facing box from `min` to `max`.
+fn cuboid(min: Vec3, max: Vec3) -> Mesh {
+    let mut mesh = Mesh::new();
+    for i in 0..8 {
+        let pick = |bit: usize, lo: f32, hi: f32| if i >> bit & 1 == 1 { hi } else { lo };
+        mesh.add_vertex(
+            pick(0, min.x, max.x),
+            pick(1, min.y, max.y),
+            pick(2, min.z, max.z),
+        );
+    }
+    for [a, b, c, d] in [
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+    ] {
+        mesh.add_triangle(a, b, c);
+        mesh.add_triangle(a, c, d);
+    }
+    mesh
+}
+
+/// Exact signed distance to the box from `min` to `max`.
+fn box_distance(p: Vec3, min: Vec3, max: Vec3) -> f32 {
+    let center = (min + max) * 0.5;
+    let q = (p - center).abs() - (max - min) * 0.5;
+    q.max(Vec3::ZERO).length() + q.max_element().min(0.0)
+}
+
+#[test]
+fn test_dense_matches_analytic_box() {
+    let (min, max) = (Vec3::new(0.0, 0.0, 0.0), Vec3::new(10.0, 6.0, 4.0));
+    let mesh = cuboid(min, max);
+    let options = SdfOptions {
+        voxel_size: 0.5,
+        ..Default::default()
+    };
+    let sdf = mesh.signed_distance_field(&options).unwrap();
+    assert_eq!(sdf.narrow_band(), None);
+
+    // Bounds plus two voxels of padding on each side
+    let [nx, ny, nz] = sdf.dims();
+    assert!(nx >= 25 && ny >= 17 && nz >= 13, "{:?}", sdf.dims());
+    assert_eq!(
+        sdf.dense_bricks(),
+        nx.div_ceil(8) * ny.div_ceil(8) * nz.div_ceil(8)
+    );
+
+    for z in 0..nz {
+        for y in 0..ny {
+            for x in 0..nx {
+                let p = sdf.position(x, y, z);
+                let expected = box_distance(p, min, max);
+                let actual = sdf.get(x, y, z);
+                assert!(
+                    (actual - expected).abs() < 1e-4,
+                    "({x}, {y}, {z}) at {p}: {actual} vs {expected}"
+                );
+            }
+        }
+    }
+    assert_eq!(sdf.to_dense().len(), nx * ny * nz);
+
+    // Interpolation between voxel centres
+    for p in [
+        Vec3::new(5.0, 3.0, 2.0),
+        Vec3::new(1.3, 0.7, 2.2),
+        Vec3::new(-0.6, 3.1, 1.9),
+    ] {
+        assert!(
+            (sdf.sample(p) - box_distance(p, min, max)).abs() < 0.05,
+            "{p}"
+        );
+    }
+}
+
+#[test]
+fn test_narrow_band_is_sparse_and_consistent() {
+    let (min, max) = (Vec3::ZERO, Vec3::splat(40.0));
+    let mesh = cuboid(min, max);
+    let index = SpatialIndex::new(&mesh);
+    let dense = SdfGrid::from_index(&index, &SdfOptions::default()).unwrap();
+    let sparse = SdfGrid::from_index(
+        &index,
+        &SdfOptions {
+            narrow_band: Some(3.0),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(sparse.dims(), dense.dims());
+    assert!(
+        sparse.dense_bricks() < dense.dense_bricks(),
+        "{} of {}",
+        sparse.dense_bricks(),
+        dense.dense_bricks()
+    );
+    for (a, b) in sparse.to_dense().iter().zip(dense.to_dense()) {
+        assert_eq!(*a, b.clamp(-3.0, 3.0));
+    }
+    // The middle of the box is far inside
+    assert_eq!(sparse.sample(Vec3::splat(20.0)), -3.0);
+}
+
+#[test]
+fn test_sign_survives_grid_aligned_edges() {
+    // Box faces and edges fall exactly on voxel centres and row rays
+    let mesh = cuboid(Vec3::splat(-2.0), Vec3::splat(2.0));
+    let options = SdfOptions {
+        voxel_size: 1.0,
+        padding: 0,
+        ..Default::default()
+    };
+    let sdf = mesh.signed_distance_field(&options).unwrap();
+    let [nx, ny, nz] = sdf.dims();
+    for z in 0..nz {
+        for y in 0..ny {
+            for x in 0..nx {
+                let p = sdf.position(x, y, z);
+                let expected = box_distance(p, Vec3::splat(-2.0), Vec3::splat(2.0));
+                if expected.abs() > 1e-6 {
+                    assert_eq!(sdf.get(x, y, z).signum(), expected.signum(), "{p}");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_invalid_options() {
+    let mesh = cuboid(Vec3::ZERO, Vec3::ONE);
+    let bad_voxel = SdfOptions {
+        voxel_size: 0.0,
+        ..Default::default()
+    };
+    assert!(matches!(
+        mesh.signed_distance_field(&bad_voxel),
+        Err(Lib3mfError::Validation(_))
+    ));
+    let too_fine = SdfOptions {
+        voxel_size: 1e-4,
+        ..Default::default()
+    };
+    assert!(matches!(
+        mesh.signed_distance_field(&too_fine),
+        Err(Lib3mfError::Validation(msg)) if msg.contains("exceeds")
+    ));
+    assert!(
+        Mesh::new()
+            .signed_distance_field(&SdfOptions::default())
+            .is_err()
+    );
+}
@@ -527,3 +527,110 @@ fn test_truncated_xml_eof_handling() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(feature = "writer")]
+fn test_key(bits: usize) -> (rsa::RsaPrivateKey, rsa::RsaPublicKey) {
+    let private = rsa::RsaPrivateKey::new(&mut rand::rngs::OsRng, bits).unwrap();
+    let public = rsa::RsaPublicKey::from(&private);
+    (private, public)
+}
+
+#[test]
+fn test_keystore_consumer_management() -> anyhow::Result<()> {
+    use lib3mf_core::model::{AccessRight, Consumer, KeyStore};
+
+    let mut ks = KeyStore::new(Uuid::new_v4());
+    ks.add_consumer(Consumer {
+        id: "alice".to_string(),
+        ..Default::default()
+    })?;
+    ks.add_consumer(Consumer {
+        id: "bob".to_string(),
+        ..Default::default()
+    })?;
+    assert!(
+        ks.add_consumer(Consumer {
+            id: "alice".to_string(),
+            ..Default::default()
+        })
+        .is_err()
+    );
+
+    let key_uuid = Uuid::new_v4();
+    let group = ks.add_resource_data_group(key_uuid)?;
+    for id in ["alice", "bob"] {
+        group.set_access_right(AccessRight {
+            consumer_id: id.to_string(),
+            algorithm: "RSA-OAEP".to_string(),
+            wrapped_key: vec![1, 2, 3],
+        });
+    }
+    assert!(ks.add_resource_data_group(key_uuid).is_err());
+    assert_eq!(ks.consumers_with_access(key_uuid), ["alice", "bob"]);
+
+    assert!(ks.revoke_access(key_uuid, "bob"));
+    assert!(!ks.revoke_access(key_uuid, "bob"));
+    assert_eq!(ks.consumers_with_access(key_uuid), ["alice"]);
+
+    // Removing a consumer drops its remaining access rights.
+    assert!(ks.remove_consumer("alice").is_some());
+    assert!(ks.consumers_with_access(key_uuid).is_empty());
+    assert_eq!(ks.consumers.len(), 1);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "writer")]
+fn test_keystore_share_access_and_roundtrip() -> anyhow::Result<()> {
+    use lib3mf_core::model::{Consumer, KeyStore};
+    use lib3mf_core::parser::secure_content_parser::parse_keystore;
+    use lib3mf_core::writer::keystore_writer::write_keystore;
+
+    let (alice_private, alice_public) = test_key(1024);
+    let (bob_private, bob_public) = test_key(1024);
+
+    let mut ks = KeyStore::new(Uuid::new_v4());
+    for id in ["alice", "bob"] {
+        ks.add_consumer(Consumer {
+            id: id.to_string(),
+            key_id: Some(format!("{id}-key")),
+            ..Default::default()
+        })?;
+    }
+    let (key_uuid, cek) = ks.create_resource_data_group()?;
+    ks.grant_access(key_uuid, "alice", &cek, &alice_public)?;
+    assert!(
+        ks.grant_access(key_uuid, "mallory", &cek, &alice_public)
+            .is_err()
+    );
+
+    // Alice re-wraps the content key for Bob.
+    ks.share_access(key_uuid, "alice", &alice_private, "bob", &bob_public)?;
+    assert_eq!(ks.unwrap_content_key(key_uuid, "bob", &bob_private)?, cek);
+    assert!(
+        ks.unwrap_content_key(key_uuid, "bob", &alice_private)
+            .is_err()
+    );
+
+    let mut xml = Vec::new();
+    write_keystore(&mut xml, &ks)?;
+    let parsed = parse_keystore(Cursor::new(xml))?;
+    assert_eq!(parsed.uuid, ks.uuid);
+    assert_eq!(parsed.consumers.len(), 2);
+    assert_eq!(parsed.consumers[1].key_id.as_deref(), Some("bob-key"));
+    assert_eq!(
+        parsed.unwrap_content_key(key_uuid, "alice", &alice_private)?,
+        cek
+    );
+    assert_eq!(
+        parsed.resource_data_group(key_uuid).unwrap().access_rights[1].wrapped_key,
+        ks.resource_data_group(key_uuid).unwrap().access_rights[1].wrapped_key
+    );
+
+    ks.revoke_access(key_uuid, "bob");
+    assert!(
+        ks.unwrap_content_key(key_uuid, "bob", &bob_private)
+            .is_err()
+    );
+    Ok(())
+}
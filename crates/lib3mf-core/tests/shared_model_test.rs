@@ -0,0 +1,172 @@
+#![cfg(feature = "writer")]
+//! Clones of a model share their mesh storage until modified.
+
+use lib3mf_core::archive::ZipArchiver;
+use lib3mf_core::model::{
+    BuildItem, Geometry, Mesh, Model, Object, ObjectType, ResourceId, SharedVec,
+};
+use lib3mf_core::validation::ValidationLevel;
+use std::io::Cursor;
+
+/// A closed grid-sided box with `n` by `n` quads on each face.
+fn box_mesh(n: u32) -> Mesh {
+    let mut mesh = Mesh::new();
+    let step = 10.0 / n as f32;
+    // Each face is its own grid of (n + 1)^2 vertices, outward facing
+    let faces: [(usize, f32, bool); 6] = [
+        (0, 0.0, false),
+        (0, 10.0, true),
+        (1, 0.0, true),
+        (1, 10.0, false),
+        (2, 0.0, false),
+        (2, 10.0, true),
+    ];
+    for (axis, offset, flip) in faces {
+        let base = mesh.vertices.len() as u32;
+        for i in 0..=n {
+            for j in 0..=n {
+                let mut p = [0.0; 3];
+                p[axis] = offset;
+                p[(axis + 1) % 3] = i as f32 * step;
+                p[(axis + 2) % 3] = j as f32 * step;
+                mesh.add_vertex(p[0], p[1], p[2]);
+            }
+        }
+        for i in 0..n {
+            for j in 0..n {
+                let a = base + i * (n + 1) + j;
+                let (b, c, d) = (a + 1, a + n + 1, a + n + 2);
+                if flip {
+                    mesh.add_triangle(a, c, b);
+                    mesh.add_triangle(b, c, d);
+                } else {
+                    mesh.add_triangle(a, b, c);
+                    mesh.add_triangle(b, d, c);
+                }
+            }
+        }
+    }
+    mesh
+}
+
+fn model_with_mesh(mesh: Mesh) -> Model {
+    let mut model = Model::default();
+    model
+        .resources
+        .add_object(Object {
+            id: ResourceId(1),
+            object_type: ObjectType::Model,
+            name: Some("Box".to_string()),
+            part_number: None,
+            uuid: None,
+            pid: None,
+            pindex: None,
+            thumbnail: None,
+            geometry: Geometry::Mesh(mesh),
+        })
+        .unwrap();
+    model.build.items.push(BuildItem {
+        object_id: ResourceId(1),
+        uuid: None,
+        path: None,
+        part_number: None,
+        transform: glam::Mat4::IDENTITY,
+        printable: None,
+        metadata: Default::default(),
+    });
+    model
+}
+
+fn mesh_of(model: &Model) -> &Mesh {
+    match &model.resources.get_object(ResourceId(1)).unwrap().geometry {
+        Geometry::Mesh(mesh) => mesh,
+        _ => unreachable!(),
+    }
+}
+
+fn shares_meshes(a: &Model, b: &Model) -> bool {
+    let (a, b) = (mesh_of(a), mesh_of(b));
+    SharedVec::ptr_eq(&a.vertices, &b.vertices) && SharedVec::ptr_eq(&a.triangles, &b.triangles)
+}
+
+#[test]
+fn test_model_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Model>();
+    assert_send_sync::<SharedVec<lib3mf_core::model::Vertex>>();
+}
+
+#[test]
+fn test_workers_share_one_loaded_model() {
+    let model = model_with_mesh(box_mesh(20));
+    let mut package = Cursor::new(Vec::new());
+    model.write(&mut package).unwrap();
+    let package = package.into_inner();
+
+    let workers: Vec<Model> = (0..3).map(|_| model.clone()).collect();
+    assert!(workers.iter().all(|w| shares_meshes(w, &model)));
+
+    let (report, stats, written) = std::thread::scope(|scope| {
+        let validate = scope.spawn(|| workers[0].validate(ValidationLevel::Paranoid));
+        let stats = scope.spawn(|| {
+            let mut archiver = ZipArchiver::new(Cursor::new(package.as_slice())).unwrap();
+            workers[1].compute_stats(&mut archiver).unwrap()
+        });
+        let export = scope.spawn(|| {
+            let mut buffer = Cursor::new(Vec::new());
+            workers[2].write(&mut buffer).unwrap();
+            buffer.into_inner()
+        });
+        (
+            validate.join().unwrap(),
+            stats.join().unwrap(),
+            export.join().unwrap(),
+        )
+    });
+    assert!(!report.has_errors(), "{:?}", report.items);
+    assert_eq!(stats.geometry.triangle_count, 6 * 2 * 20 * 20);
+    assert!(!written.is_empty());
+
+    // None of the workers copied the meshes
+    assert!(workers.iter().all(|w| shares_meshes(w, &model)));
+}
+
+#[test]
+fn test_modifying_a_clone_copies_only_its_mesh() {
+    let model = model_with_mesh(box_mesh(2));
+    let mut edited = model.clone();
+    let untouched = model.clone();
+
+    let Geometry::Mesh(mesh) = &mut edited.resources.iter_objects_mut().next().unwrap().geometry
+    else {
+        unreachable!()
+    };
+    mesh.vertices[0].x = -5.0;
+
+    assert_eq!(mesh_of(&model).vertices[0].x, 0.0);
+    assert_eq!(mesh_of(&edited).vertices[0].x, -5.0);
+    let (original, edited) = (mesh_of(&model), mesh_of(&edited));
+    assert!(!SharedVec::ptr_eq(&original.vertices, &edited.vertices));
+    // Only the modified array was copied
+    assert!(SharedVec::ptr_eq(&original.triangles, &edited.triangles));
+    assert!(shares_meshes(&model, &untouched));
+}
+
+#[test]
+fn test_shared_vec_behaves_like_vec() {
+    let mut shared: SharedVec<u32> = (1..=3).collect();
+    let copy = shared.clone();
+    shared.push(4);
+    assert_eq!(shared, vec![1, 2, 3, 4]);
+    assert_eq!(copy, vec![1, 2, 3]);
+    assert!(!shared.is_shared() && !copy.is_shared());
+
+    for value in &mut shared {
+        *value *= 2;
+    }
+    assert_eq!(shared.iter().sum::<u32>(), 20);
+    assert_eq!(serde_json::to_string(&shared).unwrap(), "[2,4,6,8]");
+    let parsed: SharedVec<u32> = serde_json::from_str("[2,4,6,8]").unwrap();
+    assert_eq!(parsed, shared);
+    assert_eq!(Vec::from(copy), vec![1, 2, 3]);
+}
@@ -0,0 +1,121 @@
+//! Signature verification with RSA, ECDSA and Ed25519 keys.
+#![cfg(feature = "crypto")]
+
+use base64::prelude::*;
+use lib3mf_core::crypto::keygen::{KeyAlgorithm, KeyPair};
+use lib3mf_core::crypto::verification::{
+    ECDSA_SHA256, ECDSA_SHA384, ED25519, RSA_SHA256, extract_key_from_signature,
+    extract_verification_key, verify_signature_extended, verify_signature_with_key,
+};
+use lib3mf_core::error::{Lib3mfError, Result};
+use lib3mf_core::model::crypto::*;
+use sha2::{Digest, Sha256};
+use x509_parser::pem::parse_x509_pem;
+
+const MODEL: &[u8] = b"<model/>";
+const SIGNED_INFO: &[u8] = b"<SignedInfo>canonical bytes</SignedInfo>";
+
+fn resolver(uri: &str) -> Result<Vec<u8>> {
+    match uri {
+        "/3D/3dmodel.model" => Ok(MODEL.to_vec()),
+        _ => Err(Lib3mfError::Validation(format!(
+            "Content not found: {}",
+            uri
+        ))),
+    }
+}
+
+/// Signs `SIGNED_INFO` with `key`, embedding its self-signed certificate in KeyInfo.
+fn sign(key: &KeyPair) -> Signature {
+    let cert_pem = key.self_signed_certificate("CN=Signer", 1).unwrap();
+    let (_, pem) = parse_x509_pem(cert_pem.as_bytes()).unwrap();
+
+    Signature {
+        signed_info: SignedInfo {
+            signature_method: SignatureMethod {
+                algorithm: key.signature_method().to_string(),
+            },
+            references: vec![Reference {
+                uri: "/3D/3dmodel.model".to_string(),
+                digest_method: DigestMethod {
+                    algorithm: "http://www.w3.org/2001/04/xmlenc#sha256".to_string(),
+                },
+                digest_value: DigestValue {
+                    value: BASE64_STANDARD.encode(Sha256::digest(MODEL)),
+                },
+                transforms: None,
+            }],
+            ..Default::default()
+        },
+        signature_value: SignatureValue {
+            value: BASE64_STANDARD.encode(key.sign(SIGNED_INFO).unwrap()),
+        },
+        key_info: Some(KeyInfo {
+            x509_data: Some(X509Data {
+                certificate: Some(BASE64_STANDARD.encode(&pem.contents)),
+            }),
+            ..Default::default()
+        }),
+    }
+}
+
+#[test]
+fn test_signature_method_follows_key_type() {
+    let cases = [
+        (KeyAlgorithm::Rsa(2048), RSA_SHA256),
+        (KeyAlgorithm::EcdsaP256, ECDSA_SHA256),
+        (KeyAlgorithm::EcdsaP384, ECDSA_SHA384),
+        (KeyAlgorithm::Ed25519, ED25519),
+    ];
+    for (algorithm, method) in cases {
+        let key = KeyPair::generate(algorithm).unwrap();
+        assert_eq!(key.signature_method(), method);
+
+        let signature = sign(&key);
+        assert_eq!(
+            extract_verification_key(&signature).unwrap(),
+            key.verification_key()
+        );
+        assert!(verify_signature_extended(&signature, resolver, SIGNED_INFO).unwrap());
+
+        // Tampered SignedInfo fails for every algorithm
+        assert!(verify_signature_extended(&signature, resolver, b"<SignedInfo/>").is_err());
+    }
+}
+
+#[test]
+fn test_ecdsa_signature_value_is_raw_r_s() {
+    let p256 = KeyPair::generate(KeyAlgorithm::EcdsaP256).unwrap();
+    assert_eq!(p256.sign(SIGNED_INFO).unwrap().len(), 64);
+    let p384 = KeyPair::generate(KeyAlgorithm::EcdsaP384).unwrap();
+    assert_eq!(p384.sign(SIGNED_INFO).unwrap().len(), 96);
+    let ed25519 = KeyPair::generate(KeyAlgorithm::Ed25519).unwrap();
+    assert_eq!(ed25519.sign(SIGNED_INFO).unwrap().len(), 64);
+}
+
+#[test]
+fn test_mismatched_signature_method_is_rejected() {
+    let key = KeyPair::generate(KeyAlgorithm::EcdsaP256).unwrap();
+    let mut signature = sign(&key);
+    signature.signed_info.signature_method.algorithm = RSA_SHA256.to_string();
+
+    let err = verify_signature_with_key(&signature, &key.verification_key(), resolver, SIGNED_INFO)
+        .unwrap_err();
+    assert!(err.to_string().contains("does not match"), "{err}");
+
+    // The RSA-only helper reports the key type instead of failing to parse
+    let err = extract_key_from_signature(&signature).unwrap_err();
+    assert!(err.to_string().contains("ECDSA P-256"), "{err}");
+}
+
+#[test]
+fn test_wrong_key_fails_verification() {
+    let signer = KeyPair::generate(KeyAlgorithm::Ed25519).unwrap();
+    let other = KeyPair::generate(KeyAlgorithm::Ed25519).unwrap();
+    let signature = sign(&signer);
+
+    assert!(
+        verify_signature_with_key(&signature, &other.verification_key(), resolver, SIGNED_INFO)
+            .is_err()
+    );
+}
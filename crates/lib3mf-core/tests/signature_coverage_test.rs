@@ -0,0 +1,84 @@
+use lib3mf_core::archive::ZipArchiver;
+use lib3mf_core::model::{Reference, Signature, SignatureCoverage};
+use std::io::{Cursor, Write};
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+const MODEL_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Target="../Images/wood.png" Id="tex0" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dtexture" />
+</Relationships>"#;
+
+fn package() -> ZipArchiver<Cursor<Vec<u8>>> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    for (name, data) in [
+        ("[Content_Types].xml", "<Types />"),
+        ("_rels/.rels", "<Relationships />"),
+        ("3D/3dmodel.model", "<model />"),
+        ("3D/_rels/3dmodel.model.rels", MODEL_RELS),
+        ("3D/Objects/part.model", "<model />"),
+        ("Images/wood.png", "png"),
+        ("Metadata/thumbnail.png", "png"),
+        ("package/services/digital-signature/origin.psdsor", ""),
+    ] {
+        zip.start_file(name, options).unwrap();
+        zip.write_all(data.as_bytes()).unwrap();
+    }
+    ZipArchiver::new(Cursor::new(zip.finish().unwrap().into_inner())).unwrap()
+}
+
+fn signature(uris: &[&str]) -> Signature {
+    let mut signature = Signature::default();
+    signature.signed_info.references = uris
+        .iter()
+        .map(|uri| Reference {
+            uri: uri.to_string(),
+            ..Default::default()
+        })
+        .collect();
+    signature
+}
+
+#[test]
+fn test_coverage_flags_unsigned_geometry() {
+    // Signs only the thumbnail: valid, but the geometry can be swapped freely.
+    let coverage = signature(&["/Metadata/thumbnail.png"])
+        .coverage(&mut package())
+        .unwrap();
+    assert!(!coverage.protects_geometry());
+    assert_eq!(coverage.covered, ["Metadata/thumbnail.png"]);
+    assert_eq!(
+        coverage.uncovered_critical,
+        [
+            "3D/3dmodel.model",
+            "3D/Objects/part.model",
+            "Images/wood.png"
+        ]
+    );
+    assert_eq!(
+        coverage.uncovered,
+        ["3D/_rels/3dmodel.model.rels", "_rels/.rels"]
+    );
+}
+
+#[test]
+fn test_coverage_complete() {
+    let coverage = signature(&[
+        "/3D/3dmodel.model?ContentType=application/vnd.ms-package.3dmanufacturing-3dmodel+xml",
+        "/3D/Objects/part.model",
+        "/3D/_rels/3dmodel.model.rels",
+        "/_rels/.rels",
+        "/Images/wood.png",
+        "/Metadata/thumbnail.png",
+        "#idPackageObject",
+        "/3D/removed.model",
+    ])
+    .coverage(&mut package())
+    .unwrap();
+    assert!(coverage.protects_geometry());
+    assert!(coverage.uncovered.is_empty());
+    assert_eq!(coverage.covered.len(), 6);
+    assert_eq!(coverage.missing, ["3D/removed.model"]);
+    assert_ne!(coverage, SignatureCoverage::default());
+}
@@ -2,6 +2,8 @@
 //!
 //! Covers requirements SLW-01 through SLW-10.
 
+#![cfg(feature = "writer")]
+
 use glam::Mat4;
 use lib3mf_core::model::*;
 use lib3mf_core::parser::parse_model;
@@ -16,6 +18,7 @@ fn build_item(object_id: ResourceId) -> BuildItem {
         part_number: None,
         transform: Mat4::IDENTITY,
         printable: None,
+        metadata: Default::default(),
     }
 }
 
@@ -0,0 +1,141 @@
+//! Strict validation of the volume sign and orientation of solid objects.
+#![cfg(feature = "testutil")]
+
+use lib3mf_core::model::{
+    Geometry, Mesh, MeshRepair, Model, Object, ObjectType, RepairOptions, ResourceId,
+};
+use lib3mf_core::testutil::meshes::cuboid;
+use lib3mf_core::validation::{ValidationLevel, ValidationReport, ValidationSeverity};
+
+/// 10 mm cube with outward-facing triangles.
+fn cube() -> Mesh {
+    cuboid([10.0; 3], 1)
+}
+
+fn inverted_cube() -> Mesh {
+    let mut mesh = cube();
+    for tri in &mut mesh.triangles {
+        std::mem::swap(&mut tri.v2, &mut tri.v3);
+    }
+    mesh
+}
+
+fn validate(mesh: Mesh, object_type: ObjectType, level: ValidationLevel) -> ValidationReport {
+    let mut model = Model::default();
+    model
+        .resources
+        .add_object(Object {
+            id: ResourceId(1),
+            object_type,
+            name: None,
+            part_number: None,
+            uuid: None,
+            pid: None,
+            pindex: None,
+            thumbnail: None,
+            geometry: Geometry::Mesh(mesh),
+        })
+        .unwrap();
+    model.validate(level)
+}
+
+fn codes(report: &ValidationReport) -> Vec<u32> {
+    report
+        .items
+        .iter()
+        .map(|i| i.code)
+        .filter(|code| (4009..=4012).contains(code))
+        .collect()
+}
+
+#[test]
+fn test_outward_cube_reports_positive_volume() {
+    let report = validate(cube(), ObjectType::Model, ValidationLevel::Strict);
+    assert_eq!(codes(&report), vec![4012]);
+    let info = report.items.iter().find(|i| i.code == 4012).unwrap();
+    assert_eq!(info.severity, ValidationSeverity::Info);
+    assert!(info.message.contains("1000.000000 mm³"), "{}", info.message);
+
+    // Not checked below Strict
+    let report = validate(cube(), ObjectType::Model, ValidationLevel::Standard);
+    assert!(codes(&report).is_empty());
+}
+
+#[test]
+fn test_inverted_cube_is_flagged_with_suggestion() {
+    let report = validate(
+        inverted_cube(),
+        ObjectType::SolidSupport,
+        ValidationLevel::Strict,
+    );
+    assert_eq!(codes(&report), vec![4012, 4009]);
+    let item = report.items.iter().find(|i| i.code == 4009).unwrap();
+    assert_eq!(item.severity, ValidationSeverity::Warning);
+    assert!(
+        item.message.contains("-1000.000000 mm³"),
+        "{}",
+        item.message
+    );
+    assert_eq!(item.context.as_deref(), Some("Object 1"));
+    assert!(item.suggestion.as_deref().unwrap().contains("harmonize"));
+    assert!(!report.has_errors());
+
+    // Inverted surfaces and open meshes are not solids to check
+    let report = validate(
+        inverted_cube(),
+        ObjectType::Surface,
+        ValidationLevel::Strict,
+    );
+    assert!(codes(&report).is_empty());
+    let mut open = inverted_cube();
+    open.triangles.pop();
+    let report = validate(open, ObjectType::Model, ValidationLevel::Paranoid);
+    assert!(codes(&report).is_empty());
+}
+
+#[test]
+fn test_mixed_orientation_and_empty_volume() {
+    let mut mixed = inverted_cube();
+    let tri = &mut mixed.triangles[0];
+    std::mem::swap(&mut tri.v2, &mut tri.v3);
+    let report = validate(mixed, ObjectType::Model, ValidationLevel::Strict);
+    assert_eq!(codes(&report), vec![4012, 4010]);
+
+    // A double-sided triangle is closed but has no inside
+    let mut sheet = Mesh::new();
+    let a = sheet.add_vertex(0.0, 0.0, 0.0);
+    let b = sheet.add_vertex(1.0, 0.0, 0.0);
+    let c = sheet.add_vertex(0.0, 1.0, 0.0);
+    sheet.add_triangle(a, b, c);
+    sheet.add_triangle(a, c, b);
+    let report = validate(sheet, ObjectType::Model, ValidationLevel::Strict);
+    assert_eq!(codes(&report), vec![4012, 4011]);
+}
+
+#[test]
+fn test_harmonize_turns_inverted_meshes_outward() {
+    let options = RepairOptions {
+        stitch_epsilon: 0.0,
+        remove_degenerate: false,
+        remove_duplicate_faces: false,
+        harmonize_orientations: true,
+        remove_islands: false,
+        fill_holes: false,
+        ..Default::default()
+    };
+
+    let mut mesh = inverted_cube();
+    let stats = mesh.repair(options);
+    assert_eq!(stats.triangles_flipped, 12);
+    assert_eq!(mesh, cube());
+
+    // One triangle already faced outward: it is flipped twice and ends where it started
+    let mut mesh = inverted_cube();
+    let tri = &mut mesh.triangles[0];
+    std::mem::swap(&mut tri.v2, &mut tri.v3);
+    let stats = mesh.repair(options);
+    assert_eq!(stats.triangles_flipped, 11);
+    assert!(mesh.compute_area_and_volume().1 > 0.0);
+    let report = validate(mesh, ObjectType::Model, ValidationLevel::Paranoid);
+    assert_eq!(codes(&report), vec![4012]);
+}
@@ -0,0 +1,176 @@
+//! Public spatial queries: ray casting, closest point, containment and box queries.
+#![cfg(feature = "testutil")]
+
+use glam::Vec3;
+use lib3mf_core::model::{Mesh, SpatialIndex};
+use lib3mf_core::testutil::meshes::cuboid;
+use lib3mf_core::validation::bvh::AABB;
+
+/// Closed unit-size box from `min` to `min + size`, outward-facing.
+fn cube(min: Vec3, size: f32) -> Mesh {
+    let mut mesh = cuboid([size; 3], 1);
+    for v in &mut mesh.vertices {
+        (v.x, v.y, v.z) = (v.x + min.x, v.y + min.y, v.z + min.z);
+    }
+    mesh
+}
+
+/// Many small triangles scattered pseudo-randomly, to exercise deep hierarchies.
+fn scattered(count: usize) -> Mesh {
+    let mut state = 0x2545_f491_u32;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state as f32 / u32::MAX as f32
+    };
+    let mut mesh = Mesh::new();
+    for _ in 0..count {
+        let center = Vec3::new(next(), next(), next()) * 100.0;
+        let base = mesh.vertices.len() as u32;
+        for _ in 0..3 {
+            let p = center + (Vec3::new(next(), next(), next()) - 0.5) * 6.0;
+            mesh.add_vertex(p.x, p.y, p.z);
+        }
+        mesh.add_triangle(base, base + 1, base + 2);
+    }
+    mesh
+}
+
+#[test]
+fn test_raycast_cube() {
+    let mesh = cube(Vec3::ZERO, 10.0);
+    let index = mesh.spatial_index();
+
+    let hit = index
+        .raycast(Vec3::new(2.0, 3.0, -5.0), Vec3::Z)
+        .expect("ray hits the bottom face");
+    assert!((hit.distance - 5.0).abs() < 1e-5);
+    assert!(hit.point.abs_diff_eq(Vec3::new(2.0, 3.0, 0.0), 1e-5));
+    assert!(hit.front_face);
+    assert!((hit.barycentric.element_sum() - 1.0).abs() < 1e-5);
+
+    // From inside, the far wall is hit from behind
+    let hit = index.raycast(Vec3::splat(5.0), Vec3::X).unwrap();
+    assert!((hit.distance - 5.0).abs() < 1e-5);
+    assert!(!hit.front_face);
+
+    // The direction's length scales the distance
+    let hit = index.raycast(Vec3::new(5.0, 5.0, 20.0), Vec3::new(0.0, 0.0, -2.0));
+    assert!((hit.unwrap().distance - 5.0).abs() < 1e-5);
+
+    assert!(index.raycast(Vec3::new(20.0, 5.0, 5.0), Vec3::X).is_none());
+    assert!(index.raycast(Vec3::new(5.0, 5.0, 20.0), Vec3::Z).is_none());
+
+    let hits = index.raycast_all(Vec3::new(2.0, 3.0, -5.0), Vec3::Z);
+    let distances: Vec<_> = hits.iter().map(|h| h.distance).collect();
+    assert_eq!(distances, [5.0, 15.0]);
+    assert!(hits[0].front_face && !hits[1].front_face);
+}
+
+#[test]
+fn test_closest_point_and_containment() {
+    let mesh = cube(Vec3::ZERO, 10.0);
+    let index = mesh.spatial_index();
+
+    let closest = index.closest_point(Vec3::new(5.0, 5.0, 13.0)).unwrap();
+    assert!(closest.point.abs_diff_eq(Vec3::new(5.0, 5.0, 10.0), 1e-5));
+    assert!((closest.distance - 3.0).abs() < 1e-5);
+
+    let corner = index.closest_point(Vec3::new(-1.0, -2.0, -2.0)).unwrap();
+    assert!(corner.point.abs_diff_eq(Vec3::ZERO, 1e-5));
+    assert!((corner.distance - 3.0).abs() < 1e-5);
+
+    let inside = index.closest_point(Vec3::new(5.0, 5.0, 1.0)).unwrap();
+    assert!((inside.distance - 1.0).abs() < 1e-5);
+
+    assert!((index.winding_number(Vec3::splat(5.0)) - 1.0).abs() < 1e-4);
+    assert!(index.winding_number(Vec3::splat(15.0)).abs() < 1e-4);
+    assert!(index.contains(Vec3::new(0.5, 9.5, 5.0)));
+    assert!(!index.contains(Vec3::new(10.5, 5.0, 5.0)));
+    assert!(!index.contains(Vec3::new(-100.0, 5.0, 5.0)));
+
+    // An open box still contains its center by winding number
+    let mut open = mesh.clone();
+    open.triangles.truncate(10);
+    assert!(open.spatial_index().contains(Vec3::splat(5.0)));
+}
+
+#[test]
+fn test_aabb_query() {
+    let mut mesh = cube(Vec3::ZERO, 1.0);
+    let far = cube(Vec3::splat(50.0), 1.0);
+    let offset = mesh.vertices.len() as u32;
+    mesh.vertices.extend(far.vertices);
+    for t in far.triangles {
+        mesh.add_triangle(t.v1 + offset, t.v2 + offset, t.v3 + offset);
+    }
+    let index = mesh.spatial_index();
+
+    let near = AABB {
+        min: Vec3::splat(-1.0),
+        max: Vec3::splat(2.0),
+    };
+    assert_eq!(index.triangles_in_aabb(&near), (0..12).collect::<Vec<_>>());
+    let around_far = AABB {
+        min: Vec3::splat(49.5),
+        max: Vec3::splat(60.0),
+    };
+    assert_eq!(
+        index.triangles_in_aabb(&around_far),
+        (12..24).collect::<Vec<_>>()
+    );
+    let bounds = index.bounds().unwrap();
+    assert_eq!((bounds.min, bounds.max), (Vec3::ZERO, Vec3::splat(51.0)));
+}
+
+#[test]
+fn test_queries_match_brute_force() {
+    let mesh = scattered(500);
+    let index = SpatialIndex::new(&mesh);
+
+    for i in 0..50 {
+        let t = i as f32;
+        let origin = Vec3::new(t * 2.0, -10.0, 50.0 + t);
+        let direction = Vec3::new(0.1 * (t - 25.0), 1.0, 0.03 * (25.0 - t));
+
+        let all = index.raycast_all(origin, direction);
+        let nearest = index.raycast(origin, direction);
+        assert_eq!(nearest.map(|h| h.distance), all.first().map(|h| h.distance));
+
+        let point = Vec3::new(t * 2.0, 100.0 - t * 2.0, t);
+        let closest = index.closest_point(point).unwrap();
+        let brute = mesh
+            .triangles
+            .iter()
+            .enumerate()
+            .map(|(j, _)| {
+                // Any single-triangle index gives the exact distance to that triangle
+                let mut single = Mesh::new();
+                let t = mesh.triangles[j];
+                for v in [t.v1, t.v2, t.v3] {
+                    let v = mesh.vertices[v as usize];
+                    single.add_vertex(v.x, v.y, v.z);
+                }
+                single.add_triangle(0, 1, 2);
+                single
+                    .spatial_index()
+                    .closest_point(point)
+                    .unwrap()
+                    .distance
+            })
+            .fold(f32::INFINITY, f32::min);
+        assert!((closest.distance - brute).abs() < 1e-4, "{i}");
+    }
+}
+
+#[test]
+fn test_empty_mesh() {
+    let mesh = Mesh::new();
+    let index = mesh.spatial_index();
+    assert!(index.bounds().is_none());
+    assert!(index.raycast(Vec3::ZERO, Vec3::X).is_none());
+    assert!(index.closest_point(Vec3::ZERO).is_none());
+    assert!(!index.contains(Vec3::ZERO));
+    assert_eq!(index.winding_number(Vec3::ZERO), 0.0);
+}
@@ -0,0 +1,217 @@
+//! Splitting meshes and mesh objects into connected shells.
+
+use lib3mf_core::Lib3mfError;
+use lib3mf_core::model::{
+    Beam, BeamLattice, BeamSet, BuildItem, Component, Components, Geometry, Mesh, Model, Object,
+    ObjectType, ResourceId, TriangleSet,
+};
+use uuid::Uuid;
+
+/// Appends an axis-aligned box; `inward` flips its winding, as for a cavity surface.
+fn add_box(mesh: &mut Mesh, min: f32, size: f32, inward: bool) {
+    let base = mesh.vertices.len() as u32;
+    for i in 0..8 {
+        let bit = |b: u32| if i >> b & 1 == 1 { min + size } else { min };
+        mesh.add_vertex(bit(0), bit(1), bit(2));
+    }
+    for [a, b, c, d] in [
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+    ] {
+        let [a, b, c, d] = [a, b, c, d].map(|v| base + v);
+        if inward {
+            mesh.add_triangle(a, c, b);
+            mesh.add_triangle(a, d, c);
+        } else {
+            mesh.add_triangle(a, b, c);
+            mesh.add_triangle(a, c, d);
+        }
+    }
+}
+
+fn object(id: u32, mesh: Mesh) -> Object {
+    Object {
+        id: ResourceId(id),
+        object_type: ObjectType::Model,
+        name: Some("Parts".into()),
+        part_number: Some("PN-1".into()),
+        uuid: Some(Uuid::new_v4()),
+        pid: Some(ResourceId(7)),
+        pindex: Some(1),
+        thumbnail: None,
+        geometry: Geometry::Mesh(mesh),
+    }
+}
+
+#[test]
+fn test_split_components_keeps_cavities_and_data() {
+    let mut mesh = Mesh::new();
+    add_box(&mut mesh, 0.0, 10.0, false);
+    add_box(&mut mesh, 20.0, 4.0, false);
+    // Cavity of the first box, listed after the second box
+    add_box(&mut mesh, 2.0, 6.0, true);
+    // Stray vertex used by nothing
+    mesh.add_vertex(100.0, 100.0, 100.0);
+    let mut set = TriangleSet::new("Top", "top");
+    set.triangles = vec![1, 12, 13, 30];
+    mesh.triangle_sets.push(set);
+    mesh.triangle_attributes.set("paint_color", 13, "8");
+    mesh.triangle_attributes.set("paint_color", 25, "4");
+
+    let parts = mesh.split_components();
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0].triangles.len(), 24);
+    assert_eq!(parts[0].vertices.len(), 16);
+    assert_eq!(parts[1].triangles.len(), 12);
+    assert_eq!(parts[1].vertices.len(), 8);
+
+    let (_, hollow) = parts[0].compute_area_and_volume();
+    assert!((hollow - (1000.0 - 216.0)).abs() < 1e-3, "{hollow}");
+    let (_, small) = parts[1].compute_area_and_volume();
+    assert!((small - 64.0).abs() < 1e-3, "{small}");
+
+    // Triangles keep their order within each part
+    assert_eq!(parts[0].triangle_sets[0].triangles, vec![1, 18]);
+    assert_eq!(parts[1].triangle_sets[0].triangles, vec![0, 1]);
+    assert_eq!(
+        parts[0].triangle_attributes.get("paint_color", 13),
+        Some("4")
+    );
+    assert_eq!(
+        parts[1].triangle_attributes.get("paint_color", 1),
+        Some("8")
+    );
+
+    let mut connected = Mesh::new();
+    add_box(&mut connected, 0.0, 1.0, false);
+    assert_eq!(connected.split_components(), vec![connected.clone()]);
+    assert!(Mesh::new().split_components().is_empty());
+}
+
+#[test]
+fn test_split_components_beam_lattice() {
+    let mut mesh = Mesh::new();
+    add_box(&mut mesh, 0.0, 10.0, false);
+    let a = mesh.add_vertex(1.0, 1.0, 1.0);
+    let b = mesh.add_vertex(5.0, 5.0, 5.0);
+    let c = mesh.add_vertex(30.0, 0.0, 0.0);
+    let d = mesh.add_vertex(40.0, 0.0, 0.0);
+    let beam = |v1, v2| Beam {
+        v1,
+        v2,
+        r1: 0.5,
+        r2: 0.5,
+        ..Default::default()
+    };
+    mesh.beam_lattice = Some(BeamLattice {
+        radius: Some(0.5),
+        // The first beam is tied to the box, the second stands alone
+        beams: vec![beam(a, 0), beam(c, d), beam(a, b)],
+        beam_sets: vec![BeamSet {
+            name: Some("All".into()),
+            identifier: None,
+            refs: vec![0, 1, 2],
+        }],
+        ..Default::default()
+    });
+
+    let parts = mesh.split_components();
+    assert_eq!(parts.len(), 2);
+    let boxed = parts[0].beam_lattice.as_ref().unwrap();
+    assert_eq!(boxed.beams.len(), 2);
+    assert_eq!(boxed.radius, Some(0.5));
+    assert_eq!(boxed.beam_sets[0].refs, vec![0, 1]);
+    assert_eq!(parts[0].vertices.len(), 10);
+
+    assert!(parts[1].triangles.is_empty());
+    assert_eq!(parts[1].vertices.len(), 2);
+    let loose = parts[1].beam_lattice.as_ref().unwrap();
+    assert_eq!((loose.beams[0].v1, loose.beams[0].v2), (0, 1));
+    assert_eq!(loose.beam_sets[0].refs, vec![0]);
+}
+
+#[test]
+fn test_split_object_components_updates_references() {
+    let mut mesh = Mesh::new();
+    for i in 0..3 {
+        add_box(&mut mesh, i as f32 * 10.0, 5.0, false);
+    }
+    let mut model = Model::default();
+    model.resources.add_object(object(1, mesh)).unwrap();
+    model
+        .resources
+        .add_object(Object {
+            id: ResourceId(2),
+            name: None,
+            uuid: None,
+            geometry: Geometry::Components(Components {
+                components: vec![Component {
+                    object_id: ResourceId(1),
+                    path: None,
+                    uuid: None,
+                    transform: glam::Mat4::from_translation(glam::Vec3::Z),
+                }],
+            }),
+            ..object(2, Mesh::new())
+        })
+        .unwrap();
+    let mut item = BuildItem::new(ResourceId(1), glam::Mat4::from_translation(glam::Vec3::X));
+    item.uuid = Some(Uuid::new_v4());
+    model.build.items.push(item);
+    model
+        .add_build_item(ResourceId(2), glam::Mat4::IDENTITY)
+        .unwrap();
+
+    let ids = model.split_object_components(ResourceId(1)).unwrap();
+    assert_eq!(ids, vec![ResourceId(1), ResourceId(3), ResourceId(4)]);
+
+    let original = model.resources.get_object(ResourceId(1)).unwrap();
+    let third = model.resources.get_object(ResourceId(4)).unwrap();
+    assert_eq!(third.name.as_deref(), Some("Parts (3)"));
+    assert_eq!(third.part_number.as_deref(), Some("PN-1"));
+    assert_eq!((third.pid, third.pindex), (Some(ResourceId(7)), Some(1)));
+    assert!(third.uuid.is_some() && third.uuid != original.uuid);
+    for (id, min) in [(1, 0.0), (3, 10.0), (4, 20.0)] {
+        match &model.resources.get_object(ResourceId(id)).unwrap().geometry {
+            Geometry::Mesh(mesh) => {
+                assert_eq!(mesh.triangles.len(), 12);
+                assert_eq!(mesh.vertices[0].x, min);
+            }
+            _ => panic!("object {id} is not a mesh"),
+        }
+    }
+
+    let built: Vec<u32> = model.build.items.iter().map(|i| i.object_id.0).collect();
+    assert_eq!(built, vec![1, 3, 4, 2]);
+    assert!(
+        model.build.items[..3]
+            .iter()
+            .all(|i| i.transform == glam::Mat4::from_translation(glam::Vec3::X))
+    );
+    assert_ne!(model.build.items[0].uuid, model.build.items[1].uuid);
+    match &model.resources.get_object(ResourceId(2)).unwrap().geometry {
+        Geometry::Components(c) => {
+            let ids: Vec<u32> = c.components.iter().map(|c| c.object_id.0).collect();
+            assert_eq!(ids, vec![1, 3, 4]);
+        }
+        _ => panic!("object 2 is not an assembly"),
+    }
+
+    // Already connected
+    assert_eq!(
+        model.split_object_components(ResourceId(3)).unwrap(),
+        vec![ResourceId(3)]
+    );
+    assert!(matches!(
+        model.split_object_components(ResourceId(2)),
+        Err(Lib3mfError::Validation(_))
+    ));
+    assert!(matches!(
+        model.split_object_components(ResourceId(9)),
+        Err(Lib3mfError::ResourceNotFound(9))
+    ));
+}
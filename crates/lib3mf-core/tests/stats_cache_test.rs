@@ -0,0 +1,137 @@
+//! Model statistics cached in the package by `PackageWriter::with_stats_cache`.
+
+#![cfg(feature = "writer")]
+
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::model::{Model, ModelStats, Package, STATS_CACHE_PATH, read_stats_cache};
+use lib3mf_core::parser::parse_model;
+use lib3mf_core::writer::package_writer::PackageWriter;
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+
+const MODEL: &str = r##"<model unit="millimeter"
+    xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    <metadata name="Title">Tetrahedron</metadata>
+    <resources>
+        <object id="1">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="10" y="0" z="0" />
+                    <vertex x="0" y="10" z="0" />
+                    <vertex x="0" y="0" z="10" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="2" v3="1" />
+                    <triangle v1="0" v2="1" v3="3" />
+                    <triangle v1="0" v2="3" v3="2" />
+                    <triangle v1="1" v2="2" v3="3" />
+                </triangles>
+            </mesh>
+        </object>
+    </resources>
+    <build><item objectid="1" /></build>
+</model>"##;
+
+fn write_package(model: Model, stats_cache: bool) -> Vec<u8> {
+    let mut buffer = Cursor::new(Vec::new());
+    PackageWriter::new(&mut buffer)
+        .with_stats_cache(stats_cache)
+        .write(&Package::new(model))
+        .unwrap();
+    buffer.into_inner()
+}
+
+fn tetrahedron() -> Model {
+    let mut model = parse_model(Cursor::new(MODEL)).unwrap();
+    model.attachments.insert(
+        "Metadata/thumbnail.png".to_string(),
+        b"fake thumbnail".to_vec(),
+    );
+    model
+}
+
+/// Statistics computed from the package, for comparison with the cached ones.
+fn computed_stats(data: &[u8]) -> ModelStats {
+    let mut archive = ZipArchiver::new(Cursor::new(data)).unwrap();
+    let path = find_model_path(&mut archive).unwrap();
+    let model = parse_model(Cursor::new(archive.read_entry(&path).unwrap())).unwrap();
+    model.compute_stats(&mut archive).unwrap()
+}
+
+#[test]
+fn test_cached_stats_match_computed_stats() {
+    let data = write_package(tetrahedron(), true);
+    let mut archive = ZipArchiver::new(Cursor::new(data.as_slice())).unwrap();
+    assert!(archive.entry_exists(STATS_CACHE_PATH));
+
+    let cached = read_stats_cache(&mut archive)
+        .unwrap()
+        .expect("valid cache");
+    let computed = computed_stats(&data);
+    assert_eq!(cached.geometry.triangle_count, 4);
+    assert_eq!(cached.generator, computed.generator);
+    assert_eq!(
+        serde_json::to_value(&cached).unwrap(),
+        serde_json::to_value(&computed).unwrap()
+    );
+
+    let content_types = archive.read_entry("[Content_Types].xml").unwrap();
+    assert!(
+        String::from_utf8(content_types)
+            .unwrap()
+            .contains("application/json")
+    );
+}
+
+#[test]
+fn test_no_cache_by_default() {
+    let data = write_package(tetrahedron(), false);
+    let mut archive = ZipArchiver::new(Cursor::new(data)).unwrap();
+    assert!(!archive.entry_exists(STATS_CACHE_PATH));
+    assert!(read_stats_cache(&mut archive).unwrap().is_none());
+}
+
+#[test]
+fn test_cache_ignored_after_package_changes() {
+    let data = write_package(tetrahedron(), true);
+
+    // Rewrite the package entry by entry, replacing the thumbnail
+    let mut source = zip::ZipArchive::new(Cursor::new(data)).unwrap();
+    let mut edited = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for i in 0..source.len() {
+        let mut file = source.by_index(i).unwrap();
+        let name = file.name().to_string();
+        let mut content = Vec::new();
+        std::io::Read::read_to_end(&mut file, &mut content).unwrap();
+        if name == "Metadata/thumbnail.png" {
+            content = b"another thumbnail".to_vec();
+        }
+        edited
+            .start_file(name, SimpleFileOptions::default())
+            .unwrap();
+        edited.write_all(&content).unwrap();
+    }
+    let edited = edited.finish().unwrap().into_inner();
+
+    let mut archive = ZipArchiver::new(Cursor::new(edited)).unwrap();
+    assert!(archive.entry_exists(STATS_CACHE_PATH));
+    assert!(read_stats_cache(&mut archive).unwrap().is_none());
+}
+
+#[test]
+fn test_stale_cache_not_copied() {
+    let data = write_package(tetrahedron(), true);
+    let mut archive = ZipArchiver::new(Cursor::new(data)).unwrap();
+
+    // A copy that keeps every entry as an attachment, like `copy` does
+    let mut model = tetrahedron();
+    let cache = archive.read_entry(STATS_CACHE_PATH).unwrap();
+    model
+        .attachments
+        .insert(STATS_CACHE_PATH.to_string(), cache);
+
+    let copy = write_package(model, false);
+    let mut archive = ZipArchiver::new(Cursor::new(copy)).unwrap();
+    assert!(!archive.entry_exists(STATS_CACHE_PATH));
+}
@@ -0,0 +1,165 @@
+//! Geometry statistics of meshes placed more than once, by build items and components.
+
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::model::ModelStats;
+use lib3mf_core::parser::parse_model;
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+
+const RELS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rel0" Target="/3D/3dmodel.model" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel"/>
+</Relationships>"#;
+
+/// A tetrahedron (4 vertices, 4 triangles, volume 1000/6) placed twice by build items and
+/// twice more by an assembly of two components.
+const MODEL: &str = r##"<model unit="millimeter"
+    xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    <metadata name="Application">BambuStudio-01.09.00.00</metadata>
+    <resources>
+        <object id="1">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="10" y="0" z="0" />
+                    <vertex x="0" y="10" z="0" />
+                    <vertex x="0" y="0" z="10" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="2" v3="1" />
+                    <triangle v1="0" v2="1" v3="3" />
+                    <triangle v1="0" v2="3" v3="2" />
+                    <triangle v1="1" v2="2" v3="3" />
+                </triangles>
+            </mesh>
+        </object>
+        <object id="2">
+            <components>
+                <component objectid="1" />
+                <component objectid="1" transform="1 0 0 0 1 0 0 0 1 20 0 0" />
+            </components>
+        </object>
+    </resources>
+    <build>
+        <item objectid="1" />
+        <item objectid="1" transform="2 0 0 0 2 0 0 0 2 0 40 0" />
+        <item objectid="2" transform="1 0 0 0 1 0 0 0 1 0 80 0" />
+    </build>
+</model>"##;
+
+/// Plate 1 holds both instances of object 1, plate 2 the assembly.
+const MODEL_SETTINGS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<config>
+  <plate>
+    <metadata key="plater_id" value="1"/>
+    <metadata key="plater_name" value="Singles"/>
+    <model_instance>
+      <metadata key="object_id" value="1"/>
+      <metadata key="instance_id" value="0"/>
+    </model_instance>
+    <model_instance>
+      <metadata key="object_id" value="1"/>
+      <metadata key="instance_id" value="1"/>
+    </model_instance>
+  </plate>
+  <plate>
+    <metadata key="plater_id" value="2"/>
+    <model_instance>
+      <metadata key="object_id" value="2"/>
+      <metadata key="instance_id" value="0"/>
+    </model_instance>
+  </plate>
+</config>"#;
+
+const TETRAHEDRON_VOLUME: f64 = 1000.0 / 6.0;
+
+fn stats() -> ModelStats {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    for (name, data) in [
+        ("_rels/.rels", RELS),
+        ("3D/3dmodel.model", MODEL),
+        ("Metadata/model_settings.config", MODEL_SETTINGS),
+    ] {
+        writer.start_file(name, options).unwrap();
+        writer.write_all(data.as_bytes()).unwrap();
+    }
+    let data = writer.finish().unwrap().into_inner();
+
+    let mut archive = ZipArchiver::new(Cursor::new(data)).unwrap();
+    let path = find_model_path(&mut archive).unwrap();
+    let model = parse_model(Cursor::new(archive.read_entry(&path).unwrap())).unwrap();
+    model.compute_stats(&mut archive).unwrap()
+}
+
+/// Compares measurements computed from f32 vertices.
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < expected * 1e-6,
+        "expected {expected}, got {actual}"
+    );
+}
+
+#[test]
+fn test_totals_count_every_placement() {
+    let geometry = stats().geometry;
+    assert_eq!(geometry.instance_count, 3);
+    assert_eq!(geometry.object_count, 4);
+    assert_eq!(geometry.vertex_count, 16);
+    assert_eq!(geometry.triangle_count, 16);
+    // The second item is scaled by 2, so it holds 8 times the volume
+    assert_close(geometry.volume, TETRAHEDRON_VOLUME * 11.0);
+}
+
+#[test]
+fn test_unique_totals_count_each_mesh_once() {
+    let unique = stats().geometry.unique;
+    assert_eq!(unique.mesh_count, 1);
+    assert_eq!(unique.vertex_count, 4);
+    assert_eq!(unique.triangle_count, 4);
+    assert_close(unique.volume, TETRAHEDRON_VOLUME);
+    assert_close(unique.surface_area, 50.0 * 3.0 + 50.0 * 3f64.sqrt());
+}
+
+#[cfg(feature = "vendor")]
+#[test]
+fn test_plate_totals_count_instances_on_each_plate() {
+    let plates = stats().plates;
+    assert_eq!(plates.len(), 2);
+
+    assert_eq!(plates[0].plate_id, 1);
+    assert_eq!(plates[0].name.as_deref(), Some("Singles"));
+    assert_eq!(plates[0].instance_count, 2);
+    assert_eq!(plates[0].triangle_count, 8);
+    assert_close(plates[0].volume, TETRAHEDRON_VOLUME * 9.0);
+
+    assert_eq!(plates[1].plate_id, 2);
+    assert_eq!(plates[1].instance_count, 1);
+    assert_eq!(plates[1].vertex_count, 8);
+    assert_close(plates[1].volume, TETRAHEDRON_VOLUME * 2.0);
+}
+
+#[cfg(feature = "vendor")]
+#[test]
+fn test_plates_group_build_items() {
+    let plates = stats().plates;
+
+    assert_eq!(plates[0].build_items, vec![1, 2]);
+    assert_eq!(plates[0].object_count, 1);
+    let bbox = plates[0].bounding_box.unwrap();
+    assert_eq!(bbox.min, [0.0, 0.0, 0.0]);
+    assert_eq!(bbox.max, [20.0, 60.0, 20.0]);
+
+    assert_eq!(plates[1].build_items, vec![3]);
+    assert_eq!(plates[1].object_count, 1);
+    let bbox = plates[1].bounding_box.unwrap();
+    assert_eq!(bbox.min, [0.0, 80.0, 0.0]);
+    assert_eq!(bbox.max, [30.0, 90.0, 10.0]);
+}
+
+#[test]
+fn test_json_labels_unique_totals() {
+    let json = serde_json::to_value(stats()).unwrap();
+    assert_eq!(json["geometry"]["triangle_count"], 16);
+    assert_eq!(json["geometry"]["unique"]["triangle_count"], 4);
+}
@@ -0,0 +1,230 @@
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::parser::parse_model;
+use lib3mf_core::validation::{ValidationLevel, validate_streaming, validate_streaming_package};
+use std::fs::File;
+use std::io::{BufReader, Cursor, Write};
+use zip::write::SimpleFileOptions;
+
+const HEADER: &str = r#"<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">"#;
+
+fn codes(xml: &str, level: ValidationLevel) -> Vec<u32> {
+    validate_streaming(Cursor::new(xml), level)
+        .expect("streaming validation should parse")
+        .report
+        .items
+        .iter()
+        .map(|i| i.code)
+        .collect()
+}
+
+#[test]
+fn test_streaming_validation_clean_model() {
+    let xml = format!(
+        r##"{HEADER}
+        <resources>
+            <basematerials id="1"><base name="Red" displaycolor="#FF0000"/></basematerials>
+            <object id="2" pid="1">
+                <mesh>
+                    <vertices>
+                        <vertex x="0" y="0" z="0"/><vertex x="1" y="0" z="0"/>
+                        <vertex x="0" y="1" z="0"/><vertex x="0" y="0" z="1"/>
+                    </vertices>
+                    <triangles>
+                        <triangle v1="0" v2="2" v3="1"/><triangle v1="0" v2="1" v3="3"/>
+                        <triangle v1="1" v2="2" v3="3"/><triangle v1="0" v2="3" v3="2"/>
+                    </triangles>
+                </mesh>
+            </object>
+            <object id="3"><components><component objectid="2"/></components></object>
+        </resources>
+        <build><item objectid="3"/></build>
+    </model>"##
+    );
+
+    let result = validate_streaming(Cursor::new(&xml), ValidationLevel::Paranoid).unwrap();
+    assert!(result.report.items.is_empty(), "{:?}", result.report.items);
+    assert_eq!(result.summary.resource_count, 3);
+    assert_eq!(result.summary.object_count, 2);
+    assert_eq!(result.summary.vertex_count, 4);
+    assert_eq!(result.summary.triangle_count, 4);
+    assert_eq!(result.summary.component_count, 1);
+    assert_eq!(result.summary.meshes.len(), 1);
+    assert_eq!(result.summary.meshes[0].object_id, 2);
+
+    // Hash is a pure function of the geometry.
+    let again = validate_streaming(Cursor::new(&xml), ValidationLevel::Minimal).unwrap();
+    assert_eq!(again.summary.meshes[0].hash, result.summary.meshes[0].hash);
+}
+
+#[test]
+fn test_streaming_validation_reference_errors() {
+    let xml = format!(
+        r#"{HEADER}
+        <resources>
+            <object id="1">
+                <mesh>
+                    <vertices><vertex x="0" y="0" z="0"/><vertex x="1" y="0" z="0"/><vertex x="0" y="1" z="0"/></vertices>
+                    <triangles><triangle v1="0" v2="1" v3="3"/><triangle v1="0" v2="0" v3="1"/></triangles>
+                </mesh>
+            </object>
+            <object id="1" type="other"><components><component objectid="9"/></components></object>
+            <object id="4" type="other"><components><component objectid="1"/></components></object>
+        </resources>
+        <build><item objectid="7"/><item objectid="4"/></build>
+    </model>"#
+    );
+
+    let found = codes(&xml, ValidationLevel::Paranoid);
+    assert!(found.contains(&2050), "duplicate ID: {:?}", found);
+    assert!(found.contains(&3001), "index bounds: {:?}", found);
+    assert!(found.contains(&4001), "degenerate: {:?}", found);
+    assert!(found.contains(&2003), "component ref: {:?}", found);
+    assert!(found.contains(&3002), "build ref: {:?}", found);
+    assert!(found.contains(&3010), "build type: {:?}", found);
+
+    // Degenerate triangles are only reported at Paranoid, reference checks from Standard.
+    let found = codes(&xml, ValidationLevel::Standard);
+    assert!(!found.contains(&4001));
+    let found = codes(&xml, ValidationLevel::Minimal);
+    assert_eq!(found, vec![2050]);
+}
+
+#[test]
+fn test_streaming_validation_matches_dom_counts() {
+    let file = File::open("../../models/Benchy.3mf").expect("Benchy.3mf fixture");
+    let mut archiver = ZipArchiver::new(file).unwrap();
+    let model_path = find_model_path(&mut archiver).unwrap();
+
+    let streamed = {
+        let entry = archiver.entry_reader(&model_path).unwrap();
+        validate_streaming(BufReader::new(entry), ValidationLevel::Standard).unwrap()
+    };
+    let model = parse_model(Cursor::new(archiver.read_entry(&model_path).unwrap())).unwrap();
+
+    let dom_vertices: u64 = model
+        .resources
+        .iter_objects()
+        .filter_map(|o| match &o.geometry {
+            lib3mf_core::model::Geometry::Mesh(m) => Some(m.vertices.len() as u64),
+            _ => None,
+        })
+        .sum();
+    assert_eq!(streamed.summary.vertex_count, dom_vertices);
+    assert_eq!(
+        streamed.summary.object_count,
+        model.resources.iter_objects().count()
+    );
+    assert_eq!(streamed.summary.build_item_count, model.build.items.len());
+    assert!(!streamed.report.has_errors(), "{:?}", streamed.report.items);
+}
+
+#[test]
+fn test_streaming_validation_metadata_per_language() {
+    let xml = format!(
+        r#"{HEADER}
+        <metadata name="Title">Bracket</metadata>
+        <metadata name="Title" xml:lang="de">Halterung</metadata>
+        <metadata name="Designer" xml:lang="de"/>
+        <resources/>
+        <build/>
+    </model>"#
+    );
+    assert!(!codes(&xml, ValidationLevel::Standard).contains(&2041));
+
+    let duplicated = xml.replace(
+        r#"<metadata name="Designer" xml:lang="de"/>"#,
+        r#"<metadata name="Title" xml:lang="de"/>"#,
+    );
+    assert!(codes(&duplicated, ValidationLevel::Standard).contains(&2041));
+}
+
+#[test]
+fn test_streaming_package_validation_follows_part_references() {
+    let root = r#"<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02" xmlns:p="http://schemas.microsoft.com/3dmanufacturing/production/2015/06">
+        <resources>
+            <object id="1">
+                <components>
+                    <component objectid="2" p:path="/3D/Objects/part.model"/>
+                    <component objectid="9" p:path="/3D/Objects/part.model"/>
+                    <component objectid="2" p:path="/3D/Objects/missing.model"/>
+                </components>
+            </object>
+        </resources>
+        <build><item objectid="1"/><item objectid="2" p:path="/3D/Objects/Part.model"/></build>
+    </model>"#;
+    let part = format!(
+        r#"{HEADER}
+        <resources>
+            <object id="2">
+                <mesh>
+                    <vertices><vertex x="0" y="0" z="0"/><vertex x="1" y="0" z="0"/><vertex x="0" y="1" z="0"/></vertices>
+                    <triangles><triangle v1="0" v2="1" v3="3"/></triangles>
+                </mesh>
+            </object>
+        </resources>
+        <build/>
+    </model>"#
+    );
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, data) in [
+        ("3D/3dmodel.model", root.as_bytes()),
+        ("3D/Objects/part.model", part.as_bytes()),
+    ] {
+        writer
+            .start_file(name, SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(data).unwrap();
+    }
+    let buffer = writer.finish().unwrap().into_inner();
+    let mut archiver = ZipArchiver::new(Cursor::new(buffer)).unwrap();
+
+    let result = validate_streaming_package(
+        &mut archiver,
+        "/3D/3dmodel.model",
+        ValidationLevel::Standard,
+    )
+    .unwrap();
+    // The part is read once, though referenced three times with different spellings
+    assert_eq!(result.summary.object_count, 2);
+    assert_eq!(result.summary.triangle_count, 1);
+    assert_eq!(result.summary.part_references.len(), 4);
+
+    let found: Vec<(u32, Option<&str>, &str)> = result
+        .report
+        .items
+        .iter()
+        .map(|i| (i.code, i.context.as_deref(), i.message.as_str()))
+        .collect();
+    assert!(
+        found
+            .iter()
+            .any(|f| f.0 == 3001 && f.1 == Some("3D/Objects/part.model")),
+        "{found:?}"
+    );
+    assert!(
+        found
+            .iter()
+            .any(|f| f.0 == 2003 && f.2.contains("non-existent object 9")),
+        "{found:?}"
+    );
+    assert!(
+        found
+            .iter()
+            .any(|f| f.0 == 2003 && f.2.contains("missing part '3D/Objects/missing.model'")),
+        "{found:?}"
+    );
+    assert_eq!(found.iter().filter(|f| f.0 == 3002).count(), 0, "{found:?}");
+}
+
+#[test]
+fn test_streaming_package_validation_reads_benchy_parts() {
+    let file = File::open("../../models/Benchy.3mf").expect("Benchy.3mf fixture");
+    let mut archiver = ZipArchiver::new(file).unwrap();
+    let model_path = find_model_path(&mut archiver).unwrap();
+
+    let result =
+        validate_streaming_package(&mut archiver, &model_path, ValidationLevel::Standard).unwrap();
+    assert!(!result.summary.part_references.is_empty());
+    assert!(result.summary.triangle_count > 0);
+    assert!(!result.report.has_errors(), "{:?}", result.report.items);
+}
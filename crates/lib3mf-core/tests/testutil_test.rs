@@ -0,0 +1,101 @@
+//! The `testutil` generators produce what they promise.
+#![cfg(feature = "testutil")]
+
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::model::{Geometry, Mesh, Model, Object, ObjectType, ResourceId};
+use lib3mf_core::parser::parse_model;
+use lib3mf_core::testutil::proptest::prelude::*;
+use lib3mf_core::testutil::{
+    MeshDefect, cuboid, defective_mesh, manifold_mesh, model, tetrahedron, uv_sphere,
+};
+use lib3mf_core::validation::{ValidationLevel, ValidationReport};
+use std::io::Cursor;
+
+/// Paranoid validation report of a model holding only `mesh`.
+fn paranoid_report(mesh: Mesh) -> ValidationReport {
+    let mut model = Model::default();
+    model
+        .resources
+        .add_object(Object {
+            id: ResourceId(1),
+            object_type: ObjectType::Model,
+            name: None,
+            part_number: None,
+            uuid: None,
+            pid: None,
+            pindex: None,
+            thumbnail: None,
+            geometry: Geometry::Mesh(mesh),
+        })
+        .unwrap();
+    model.validate(ValidationLevel::Paranoid)
+}
+
+fn geometry_codes(report: &ValidationReport) -> Vec<u32> {
+    report
+        .items
+        .iter()
+        .map(|i| i.code)
+        .filter(|code| (4001..=4011).contains(code))
+        .collect()
+}
+
+#[test]
+fn test_fixed_solids() {
+    assert_eq!(tetrahedron(1.0).triangles.len(), 4);
+    assert_eq!(cuboid([1.0, 2.0, 3.0], 3).triangles.len(), 12 * 9);
+    assert_eq!(uv_sphere(5.0, 4).triangles.len(), 4 * 4 * 3);
+
+    let (_, volume) = cuboid([1.0, 2.0, 3.0], 2).compute_area_and_volume();
+    assert!((volume - 6.0).abs() < 1e-4, "{volume}");
+    for mesh in [
+        tetrahedron(2.0),
+        cuboid([1.0, 1.0, 1.0], 1),
+        uv_sphere(1.0, 2),
+    ] {
+        assert_eq!(geometry_codes(&paranoid_report(mesh)), Vec::<u32>::new());
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn prop_manifold_mesh_is_clean(mesh in manifold_mesh()) {
+        let (_, volume) = mesh.compute_area_and_volume();
+        prop_assert!(volume > 0.0, "volume {}", volume);
+        let report = paranoid_report(mesh);
+        prop_assert_eq!(geometry_codes(&report), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn prop_defect_is_reported((mesh, defect) in defective_mesh()) {
+        let report = paranoid_report(mesh);
+        let codes = geometry_codes(&report);
+        prop_assert!(codes.contains(&defect.validation_code()), "{:?}: {:?}", defect, codes);
+        if defect == MeshDefect::Hole {
+            // Removing a triangle leaves the rest of the surface intact
+            prop_assert!(!codes.contains(&4008), "{:?}", codes);
+        }
+    }
+
+    #[test]
+    fn prop_model_is_valid_and_roundtrips(model in model()) {
+        let report = model.validate(ValidationLevel::Paranoid);
+        prop_assert!(!report.has_errors(), "{:?}", report.items);
+
+        let mut buffer = Cursor::new(Vec::new());
+        model.write(&mut buffer).unwrap();
+        let mut archive = ZipArchiver::new(Cursor::new(buffer.into_inner())).unwrap();
+        let path = find_model_path(&mut archive).unwrap();
+        let parsed = parse_model(Cursor::new(archive.read_entry(&path).unwrap())).unwrap();
+
+        prop_assert_eq!(
+            parsed.resources.iter_objects().count(),
+            model.resources.iter_objects().count()
+        );
+        prop_assert_eq!(parsed.build.items.len(), model.build.items.len());
+        prop_assert_eq!(parsed.build.uuid, model.build.uuid);
+        prop_assert!(!parsed.validate(ValidationLevel::Strict).has_errors());
+    }
+}
@@ -0,0 +1,112 @@
+//! Strict package validation of package and object thumbnails.
+
+#![cfg(feature = "writer")]
+
+use lib3mf_core::archive::ZipArchiver;
+use lib3mf_core::model::Model;
+use lib3mf_core::parser::parse_model;
+use lib3mf_core::validation::{ValidationLevel, ValidationReport, validate_package};
+use std::io::Cursor;
+
+/// A 1x1 grayscale PNG.
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR\x00\x00\x00\x01\x00\x00\x00\x01\x08\x00\x00\x00\x00\x3a\x7e\x9b\x55\x00\x00\x00\x0aIDAT\x78\x9c\x63\x68\x00\x00\x00\x82\x00\x81\x77\xcd\x72\xb6\x00\x00\x00\x00IEND\xae\x42\x60\x82";
+const JPEG: &[u8] = b"\xff\xd8\xff\xe0fake jpeg";
+
+fn model_with_thumbnail(thumbnail: &str) -> Model {
+    let xml = format!(
+        r#"<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    <resources>
+        <object id="1" type="model" thumbnail="{thumbnail}">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" /><vertex x="1" y="0" z="0" /><vertex x="0" y="1" z="0" />
+                </vertices>
+                <triangles><triangle v1="0" v2="1" v3="2" /></triangles>
+            </mesh>
+        </object>
+    </resources>
+    <build><item objectid="1" /></build>
+</model>"#
+    );
+    parse_model(Cursor::new(xml)).unwrap()
+}
+
+/// Writes `model` and validates the resulting package at `level`.
+fn validate(model: &Model, level: ValidationLevel) -> ValidationReport {
+    let mut buffer = Cursor::new(Vec::new());
+    model.write(&mut buffer).unwrap();
+    let mut archive = ZipArchiver::new(Cursor::new(buffer.into_inner())).unwrap();
+    let mut report = ValidationReport::new();
+    validate_package(&mut archive, "3D/3dmodel.model", model, level, &mut report);
+    report
+}
+
+fn codes(report: &ValidationReport) -> Vec<u32> {
+    report.items.iter().map(|i| i.code).collect()
+}
+
+#[test]
+fn test_valid_thumbnails_pass() {
+    let mut model = model_with_thumbnail("/3D/Thumbnails/object_1.jpg");
+    model
+        .attachments
+        .insert("3D/Thumbnails/object_1.jpg".to_string(), JPEG.to_vec());
+    model
+        .attachments
+        .insert("Metadata/thumbnail.png".to_string(), PNG.to_vec());
+
+    let report = validate(&model, ValidationLevel::Strict);
+    assert!(report.items.is_empty(), "{:?}", report.items);
+}
+
+#[test]
+fn test_missing_object_thumbnail() {
+    let model = model_with_thumbnail("/3D/Thumbnails/missing.png");
+
+    let report = validate(&model, ValidationLevel::Strict);
+    assert_eq!(codes(&report), [1010]);
+    assert!(
+        report.items[0]
+            .message
+            .contains("3D/Thumbnails/missing.png")
+    );
+
+    // Thumbnails are only checked at Strict level
+    assert!(validate(&model, ValidationLevel::Standard).items.is_empty());
+}
+
+#[test]
+fn test_thumbnail_content_type_and_signature() {
+    // JPEG data in a part the package declares as PNG
+    let mut model = model_with_thumbnail("/3D/Thumbnails/object_1.png");
+    model
+        .attachments
+        .insert("3D/Thumbnails/object_1.png".to_string(), JPEG.to_vec());
+    let report = validate(&model, ValidationLevel::Strict);
+    assert_eq!(codes(&report), [1012]);
+    assert!(report.items[0].message.contains("image/png"));
+
+    // A format thumbnails can't have
+    let mut model = model_with_thumbnail("/3D/Thumbnails/object_1.gif");
+    model
+        .attachments
+        .insert("3D/Thumbnails/object_1.gif".to_string(), b"GIF89a".to_vec());
+    let report = validate(&model, ValidationLevel::Strict);
+    assert_eq!(codes(&report), [1011]);
+}
+
+#[cfg(feature = "png-validation")]
+#[test]
+fn test_corrupt_png_thumbnail() {
+    let mut model = model_with_thumbnail("/3D/Thumbnails/object_1.png");
+    model
+        .attachments
+        .insert("3D/Thumbnails/object_1.png".to_string(), PNG[..40].to_vec());
+    model
+        .attachments
+        .insert("Metadata/thumbnail.png".to_string(), PNG.to_vec());
+
+    let report = validate(&model, ValidationLevel::Strict);
+    assert_eq!(codes(&report), [1013]);
+    assert!(report.items[0].message.contains("object_1.png"));
+}
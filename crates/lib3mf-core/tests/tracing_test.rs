@@ -0,0 +1,119 @@
+#![cfg(all(feature = "tracing", feature = "writer"))]
+//! Timing spans emitted with the `tracing` feature.
+
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::model::{
+    BuildItem, Geometry, Mesh, MeshRepair, Model, Object, ObjectType, RepairOptions, ResourceId,
+};
+use lib3mf_core::parser::parse_model;
+use lib3mf_core::trace::{TimingCollector, span};
+use lib3mf_core::validation::ValidationLevel;
+use std::io::Cursor;
+
+fn tetrahedron_model() -> Model {
+    let mut mesh = Mesh::new();
+    let v0 = mesh.add_vertex(0.0, 0.0, 0.0);
+    let v1 = mesh.add_vertex(10.0, 0.0, 0.0);
+    let v2 = mesh.add_vertex(0.0, 10.0, 0.0);
+    let v3 = mesh.add_vertex(0.0, 0.0, 10.0);
+    mesh.add_triangle(v0, v2, v1);
+    mesh.add_triangle(v0, v1, v3);
+    mesh.add_triangle(v0, v3, v2);
+    mesh.add_triangle(v1, v2, v3);
+
+    let mut model = Model::default();
+    model
+        .resources
+        .add_object(Object {
+            id: ResourceId(1),
+            object_type: ObjectType::Model,
+            name: None,
+            part_number: None,
+            uuid: None,
+            pid: None,
+            pindex: None,
+            thumbnail: None,
+            geometry: Geometry::Mesh(mesh),
+        })
+        .unwrap();
+    model.build.items.push(BuildItem {
+        object_id: ResourceId(1),
+        uuid: None,
+        path: None,
+        part_number: None,
+        transform: glam::Mat4::IDENTITY,
+        printable: None,
+        metadata: Default::default(),
+    });
+    model
+}
+
+fn names(collector: &TimingCollector) -> Vec<&'static str> {
+    collector.timings().iter().map(|t| t.name).collect()
+}
+
+#[test]
+fn test_spans_cover_the_pipeline() {
+    let collector = TimingCollector::new();
+    tracing::subscriber::with_default(collector.clone(), || {
+        let mut buffer = Cursor::new(Vec::new());
+        tetrahedron_model().write(&mut buffer).unwrap();
+        assert_eq!(
+            names(&collector),
+            [
+                span::WRITE_ATTACHMENTS,
+                span::WRITE_MODEL_PART,
+                span::WRITE_RELATIONSHIPS,
+                span::WRITE_CONTENT_TYPES,
+                span::WRITE_PACKAGE,
+            ]
+        );
+        collector.clear();
+
+        let mut archiver = ZipArchiver::new(Cursor::new(buffer.into_inner())).unwrap();
+        let path = find_model_path(&mut archiver).unwrap();
+        let data = archiver.read_entry(&path).unwrap();
+        let mut model = parse_model(Cursor::new(data)).unwrap();
+        assert_eq!(
+            names(&collector),
+            [
+                span::ARCHIVE_OPEN,
+                span::PARSE_RESOURCES,
+                span::PARSE_BUILD,
+                span::PARSE_MODEL,
+            ]
+        );
+        collector.clear();
+
+        model.compute_stats(&mut archiver).unwrap();
+        assert_eq!(collector.count(span::COMPUTE_STATS), 1);
+
+        let report = model.validate(ValidationLevel::Paranoid);
+        assert!(!report.has_errors());
+        assert_eq!(collector.count(span::VALIDATION_CHECK), 8);
+        assert_eq!(collector.count(span::VALIDATE), 1);
+
+        if let Geometry::Mesh(mesh) =
+            &mut model.resources.iter_objects_mut().next().unwrap().geometry
+        {
+            mesh.repair(RepairOptions::default());
+        }
+        assert_eq!(collector.count(span::REPAIR_MESH), 1);
+    });
+
+    // The enclosing span lasts at least as long as the checks inside it
+    let checks = collector.total_ms(span::VALIDATION_CHECK);
+    assert!(collector.total_ms(span::VALIDATE) >= checks);
+    assert!(collector.timings().iter().all(|t| t.elapsed_ms >= 0.0));
+}
+
+#[test]
+fn test_failed_stages_still_close_their_spans() {
+    let collector = TimingCollector::new();
+    tracing::subscriber::with_default(collector.clone(), || {
+        assert!(parse_model(Cursor::new("<model><resources></model>")).is_err());
+        assert!(ZipArchiver::new(Cursor::new(b"not a zip".to_vec())).is_err());
+    });
+    assert_eq!(collector.count(span::PARSE_MODEL), 1);
+    assert_eq!(collector.count(span::ARCHIVE_OPEN), 1);
+}
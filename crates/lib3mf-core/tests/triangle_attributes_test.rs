@@ -0,0 +1,150 @@
+//! Per-triangle attributes: slicer paint data round trips and vendor adapters.
+
+#![cfg(feature = "writer")]
+
+use lib3mf_core::model::repair::{MeshRepair, RepairOptions};
+use lib3mf_core::model::*;
+use lib3mf_core::parser::parse_model;
+use std::io::Cursor;
+
+/// Two triangles as PrusaSlicer writes them: the first a support enforcer, the second
+/// painted with extruder 3 and partly subdivided for seam painting.
+const PRUSA: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:slic3rpe="http://schemas.slic3r.org/3mf/2017/06">
+    <resources>
+        <object id="1" type="model">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="1" y="0" z="0" />
+                    <vertex x="0" y="1" z="0" />
+                    <vertex x="1" y="1" z="0" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="1" v3="2" slic3rpe:custom_supports="4" />
+                    <triangle v1="1" v2="3" v3="2" slic3rpe:mmu_segmentation="0C"
+                        slic3rpe:custom_seam="8C1" />
+                </triangles>
+            </mesh>
+        </object>
+    </resources>
+    <build>
+        <item objectid="1" />
+    </build>
+</model>"#;
+
+fn mesh(model: &Model) -> &Mesh {
+    match &model.resources.get_object(ResourceId(1)).unwrap().geometry {
+        Geometry::Mesh(mesh) => mesh,
+        _ => panic!("object 1 is not a mesh"),
+    }
+}
+
+fn roundtrip(model: &Model) -> Model {
+    let mut buffer = Vec::new();
+    model.write_xml(&mut buffer, None).unwrap();
+    parse_model(Cursor::new(buffer)).unwrap()
+}
+
+#[test]
+fn test_parse_keeps_paint_attributes() {
+    let model = parse_model(Cursor::new(PRUSA)).unwrap();
+    let attributes = &mesh(&model).triangle_attributes;
+    assert_eq!(attributes.get("slic3rpe:custom_supports", 0), Some("4"));
+    assert_eq!(attributes.get("slic3rpe:custom_supports", 1), None);
+    assert_eq!(
+        attributes.of_triangle(1).collect::<Vec<_>>(),
+        [
+            ("slic3rpe:custom_seam", "8C1"),
+            ("slic3rpe:mmu_segmentation", "0C")
+        ]
+    );
+
+    let paint = |channel, t| attributes.paint_state(PaintVendor::Prusa, channel, t);
+    assert_eq!(paint(PaintChannel::Supports, 0), Some(1));
+    assert_eq!(paint(PaintChannel::MultiMaterial, 1), Some(3));
+    // Subdivided triangles have no single state
+    assert_eq!(paint(PaintChannel::Seam, 1), None);
+}
+
+#[test]
+fn test_paint_attributes_roundtrip() {
+    let model = parse_model(Cursor::new(PRUSA)).unwrap();
+    let written = roundtrip(&model);
+    assert_eq!(
+        mesh(&written).triangle_attributes,
+        mesh(&model).triangle_attributes
+    );
+    assert_eq!(
+        written.extra_namespaces.get("slic3rpe").map(String::as_str),
+        Some("http://schemas.slic3r.org/3mf/2017/06")
+    );
+}
+
+#[test]
+fn test_convert_prusa_paint_to_bambu() {
+    let mut model = parse_model(Cursor::new(PRUSA)).unwrap();
+    for object in model.resources.iter_objects_mut() {
+        if let Geometry::Mesh(mesh) = &mut object.geometry {
+            mesh.triangle_attributes
+                .convert_paint(PaintVendor::Prusa, PaintVendor::Bambu);
+        }
+    }
+
+    let written = roundtrip(&model);
+    let attributes = &mesh(&written).triangle_attributes;
+    assert_eq!(attributes.get("paint_supports", 0), Some("4"));
+    assert_eq!(attributes.get("paint_color", 1), Some("0C"));
+    assert_eq!(attributes.get("paint_seam", 1), Some("8C1"));
+    assert!(attributes.channel("slic3rpe:custom_supports").is_none());
+    assert_eq!(
+        attributes.paint_state(PaintVendor::Bambu, PaintChannel::MultiMaterial, 1),
+        Some(3)
+    );
+}
+
+#[test]
+fn test_paint_state_encoding() {
+    assert_eq!(encode_paint_state(1), "4");
+    assert_eq!(encode_paint_state(2), "8");
+    assert_eq!(encode_paint_state(3), "0C");
+    assert_eq!(encode_paint_state(4), "1C");
+    for state in 0..=MAX_PAINT_STATE {
+        assert_eq!(decode_paint_state(&encode_paint_state(state)), Some(state));
+    }
+    assert_eq!(decode_paint_state(""), None);
+    assert_eq!(decode_paint_state("xyz"), None);
+    // Trailing bits after a leaf are not padding
+    assert_eq!(decode_paint_state("14"), None);
+}
+
+#[test]
+fn test_set_paint_state() {
+    let mut attributes = TriangleAttributes::new();
+    attributes.set_paint_state(PaintVendor::Bambu, PaintChannel::Seam, 2, 2);
+    assert_eq!(attributes.get("paint_seam", 2), Some("8"));
+    attributes.set_paint_state(PaintVendor::Bambu, PaintChannel::Seam, 2, 0);
+    assert!(attributes.is_empty());
+    // Bambu has no fuzzy skin attribute
+    attributes.set_paint_state(PaintVendor::Bambu, PaintChannel::FuzzySkin, 0, 1);
+    assert!(attributes.is_empty());
+}
+
+#[test]
+fn test_repair_keeps_attributes_on_their_triangles() {
+    let model = parse_model(Cursor::new(PRUSA)).unwrap();
+    let mut mesh = mesh(&model).clone();
+    // Make the first triangle degenerate so repair removes it
+    mesh.triangles[0].v3 = mesh.triangles[0].v1;
+    mesh.repair(RepairOptions {
+        stitch_epsilon: 0.0,
+        harmonize_orientations: false,
+        ..Default::default()
+    });
+
+    assert_eq!(mesh.triangles.len(), 1);
+    let attributes = &mesh.triangle_attributes;
+    assert!(attributes.channel("slic3rpe:custom_supports").is_none());
+    assert_eq!(attributes.get("slic3rpe:mmu_segmentation", 0), Some("0C"));
+}
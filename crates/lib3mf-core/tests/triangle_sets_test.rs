@@ -0,0 +1,154 @@
+//! Triangle Sets extension: parsing, writing, queries, validation and repair.
+
+#![cfg(feature = "writer")]
+
+use lib3mf_core::model::repair::{MeshRepair, RepairOptions};
+use lib3mf_core::model::*;
+use lib3mf_core::parser::parse_model;
+use lib3mf_core::validation::ValidationLevel;
+use std::io::Cursor;
+
+/// A strip of five triangles with a painted seam (0-2, 4) and support region (1).
+const MODEL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:t="http://schemas.microsoft.com/3dmanufacturing/trianglesets/2021/07">
+    <resources>
+        <object id="1" type="model">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="0" y="1" z="0" />
+                    <vertex x="1" y="0" z="0" />
+                    <vertex x="1" y="1" z="0" />
+                    <vertex x="2" y="0" z="0" />
+                    <vertex x="2" y="1" z="0" />
+                    <vertex x="3" y="0" z="0" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="2" v3="1" />
+                    <triangle v1="1" v2="2" v3="3" />
+                    <triangle v1="2" v2="4" v3="3" />
+                    <triangle v1="3" v2="4" v3="5" />
+                    <triangle v1="4" v2="6" v3="5" />
+                </triangles>
+                <t:trianglesets>
+                    <t:triangleset name="Seam" identifier="seam">
+                        <t:refrange startindex="0" endindex="2" />
+                        <t:ref index="4" />
+                    </t:triangleset>
+                    <t:triangleset name="Support enforcer" identifier="support">
+                        <t:ref index="1" />
+                    </t:triangleset>
+                </t:trianglesets>
+            </mesh>
+        </object>
+    </resources>
+    <build>
+        <item objectid="1" />
+    </build>
+</model>"#;
+
+fn mesh(model: &Model) -> &Mesh {
+    match &model.resources.get_object(ResourceId(1)).unwrap().geometry {
+        Geometry::Mesh(mesh) => mesh,
+        _ => panic!("object 1 is not a mesh"),
+    }
+}
+
+fn codes(model: &Model) -> Vec<u32> {
+    model
+        .validate(ValidationLevel::Standard)
+        .items
+        .iter()
+        .map(|i| i.code)
+        .collect()
+}
+
+#[test]
+fn test_parse_triangle_sets() {
+    let model = parse_model(Cursor::new(MODEL)).unwrap();
+    let mesh = mesh(&model);
+    assert_eq!(mesh.triangle_sets.len(), 2);
+
+    let seam = mesh.triangle_set("seam").unwrap();
+    assert_eq!(seam.name, "Seam");
+    assert_eq!(seam.triangles, [0, 1, 2, 4]);
+    assert!(mesh.triangle_set("infill").is_none());
+
+    let of_1: Vec<&str> = mesh
+        .triangle_sets_of(1)
+        .map(|s| s.identifier.as_str())
+        .collect();
+    assert_eq!(of_1, ["seam", "support"]);
+    assert_eq!(mesh.triangle_sets_of(3).count(), 0);
+}
+
+#[test]
+fn test_write_compresses_ranges_and_roundtrips() {
+    let model = parse_model(Cursor::new(MODEL)).unwrap();
+    let mut buffer = Vec::new();
+    model.write_xml(&mut buffer, None).unwrap();
+    let xml = String::from_utf8(buffer.clone()).unwrap();
+    assert!(xml.contains(r#"<t:refrange startindex="0" endindex="2"/>"#));
+    assert!(xml.contains(r#"<t:ref index="4"/>"#));
+
+    let written = parse_model(Cursor::new(buffer)).unwrap();
+    assert_eq!(mesh(&written).triangle_sets, mesh(&model).triangle_sets);
+}
+
+#[test]
+fn test_compact_mesh_keeps_triangle_sets() {
+    let model = parse_model(Cursor::new(MODEL)).unwrap();
+    let compact = CompactMesh::from(mesh(&model));
+    assert_eq!(
+        compact.triangle_sets(),
+        mesh(&model).triangle_sets.as_slice()
+    );
+    assert_eq!(&Mesh::from(&compact), mesh(&model));
+}
+
+#[test]
+fn test_validation_reports_bad_triangle_sets() {
+    let model = parse_model(Cursor::new(MODEL)).unwrap();
+    let found = codes(&model);
+    assert!(
+        !found.contains(&3004) && !found.contains(&3005),
+        "{:?}",
+        found
+    );
+
+    let model = parse_model(Cursor::new(MODEL.replace(r#"index="4""#, r#"index="9""#))).unwrap();
+    assert!(codes(&model).contains(&3004));
+
+    let model = parse_model(Cursor::new(
+        MODEL.replace(r#"identifier="support""#, r#"identifier="seam""#),
+    ))
+    .unwrap();
+    assert!(codes(&model).contains(&3005));
+}
+
+#[test]
+fn test_invalid_refrange_is_rejected() {
+    let xml = MODEL.replace(
+        r#"startindex="0" endindex="2""#,
+        r#"startindex="2" endindex="0""#,
+    );
+    assert!(parse_model(Cursor::new(xml)).is_err());
+}
+
+#[test]
+fn test_repair_updates_triangle_sets() {
+    let model = parse_model(Cursor::new(MODEL)).unwrap();
+    let mut mesh = mesh(&model).clone();
+    // Make triangle 1 degenerate so repair removes it
+    mesh.triangles[1].v3 = mesh.triangles[1].v1;
+    mesh.repair(RepairOptions {
+        stitch_epsilon: 0.0,
+        harmonize_orientations: false,
+        ..Default::default()
+    });
+
+    assert_eq!(mesh.triangles.len(), 4);
+    assert_eq!(mesh.triangle_set("seam").unwrap().triangles, [0, 1, 3]);
+    assert!(mesh.triangle_set("support").unwrap().triangles.is_empty());
+}
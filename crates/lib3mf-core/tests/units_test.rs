@@ -0,0 +1,93 @@
+//! Every unit from the 3MF Core Specification through parse, write and statistics.
+
+#![cfg(feature = "writer")]
+
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::model::Unit;
+use lib3mf_core::parser::parse_model;
+use std::io::Cursor;
+
+/// A tetrahedron with 10-unit legs in the given unit.
+fn tetrahedron(unit: &str) -> String {
+    format!(
+        r##"<model unit="{unit}" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    <resources>
+        <object id="1">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="10" y="0" z="0" />
+                    <vertex x="0" y="10" z="0" />
+                    <vertex x="0" y="0" z="10" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="2" v3="1" />
+                    <triangle v1="0" v2="1" v3="3" />
+                    <triangle v1="0" v2="3" v3="2" />
+                    <triangle v1="1" v2="2" v3="3" />
+                </triangles>
+            </mesh>
+        </object>
+    </resources>
+    <build><item objectid="1" /></build>
+</model>"##
+    )
+}
+
+#[test]
+fn test_units_parse_and_write_spec_tokens() {
+    for (token, unit) in [
+        ("micron", Unit::Micron),
+        ("millimeter", Unit::Millimeter),
+        ("centimeter", Unit::Centimeter),
+        ("inch", Unit::Inch),
+        ("foot", Unit::Foot),
+        ("meter", Unit::Meter),
+    ] {
+        let model = parse_model(Cursor::new(tetrahedron(token))).unwrap();
+        assert_eq!(model.unit, unit);
+
+        let mut xml = Vec::new();
+        model.write_xml(&mut xml, None).unwrap();
+        let xml = String::from_utf8(xml).unwrap();
+        assert!(xml.contains(&format!(r#"unit="{token}""#)), "{xml}");
+        assert_eq!(parse_model(Cursor::new(xml)).unwrap().unit, unit);
+    }
+
+    for spelling in ["MicroMeter", "micrometer", "Micron", "um", "mm", ""] {
+        assert!(
+            parse_model(Cursor::new(tetrahedron(spelling))).is_err(),
+            "{spelling:?} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn test_stats_scale_with_unit() {
+    for unit in Unit::ALL {
+        let model = parse_model(Cursor::new(tetrahedron(unit.as_str()))).unwrap();
+        let mut package = Cursor::new(Vec::new());
+        model.write(&mut package).unwrap();
+
+        let mut archive = ZipArchiver::new(Cursor::new(package.into_inner())).unwrap();
+        let path = find_model_path(&mut archive).unwrap();
+        let model = parse_model(Cursor::new(archive.read_entry(&path).unwrap())).unwrap();
+        let stats = model.compute_stats(&mut archive).unwrap();
+        assert_eq!(stats.unit, unit);
+
+        let edge_mm = unit.convert(10.0, Unit::Millimeter);
+        let size = stats.size_in(Unit::Millimeter).unwrap();
+        for length in size {
+            assert_eq!(length.unit, Unit::Millimeter);
+            assert!((length.value - edge_mm).abs() < edge_mm * 1e-6, "{unit}");
+        }
+
+        // Native volume is 1000/6 cubic units
+        let volume_m3 = stats.volume_in(Unit::Meter);
+        let expected = (10.0 * unit.scale_factor()).powi(3) / 6.0;
+        assert!((volume_m3 - expected).abs() < expected * 1e-6, "{unit}");
+
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["unit"], unit.as_str());
+    }
+}
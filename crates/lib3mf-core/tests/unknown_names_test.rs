@@ -0,0 +1,164 @@
+#![cfg(feature = "writer")]
+
+use lib3mf_core::model::{Model, UnknownNameKind};
+use lib3mf_core::parser::{ParseOptions, parse_model, parse_model_with_options};
+use lib3mf_core::validation::{ValidationLevel, ValidationSeverity};
+use std::io::Cursor;
+
+const TYPOS: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:m="http://schemas.microsoft.com/3dmanufacturing/material/2015/02"
+    xmlns:p="http://schemas.microsoft.com/3dmanufacturing/production/2015/06"
+    xmlns:v="http://example.com/vendor">
+    <metadata name="Title">Typos</metadata>
+    <resources>
+        <m:colorgroup id="1" v:palette="warm">
+            <m:color color="#FF0000" />
+        </m:colorgroup>
+        <object id="2" type="model" v:origin="scan">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="1" y="0" z="0" />
+                    <vertex x="0" y="1" z="0" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="1" v3="2" pid="1" p1="0" />
+                </triangles>
+                <normals>
+                    <normal x="0" y="0" z="1" />
+                </normals>
+            </mesh>
+            <v:notes>kept by the vendor</v:notes>
+        </object>
+    </resources>
+    <build p:UIID="f3b5f4a6-2d6f-4ab1-a2a4-6a2f7c1f1f4e">
+        <item objectid="2" />
+        <item objectid="2" transfrom="1 0 0 0 1 0 0 0 1 10 0 0" />
+    </build>
+</model>"##;
+
+fn parse_tracked(xml: &str) -> Model {
+    let options = ParseOptions {
+        track_unknown_names: true,
+        ..Default::default()
+    };
+    parse_model_with_options(Cursor::new(xml), &options).unwrap()
+}
+
+#[test]
+fn test_unknown_names_with_paths() {
+    let model = parse_tracked(TYPOS);
+    let found: Vec<(UnknownNameKind, &str, &str)> = model
+        .unknown_names
+        .iter()
+        .map(|u| (u.kind, u.path.as_str(), u.name.as_str()))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            (
+                UnknownNameKind::Element,
+                "/model/resources/object/mesh/normals",
+                "normals"
+            ),
+            (UnknownNameKind::Attribute, "/model/build", "p:UIID"),
+            (
+                UnknownNameKind::Attribute,
+                "/model/build/item[2]",
+                "transfrom"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_unknown_names_tracked_only_on_request() {
+    assert!(
+        parse_model(Cursor::new(TYPOS))
+            .unwrap()
+            .unknown_names
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_strict_reports_unknown_names() {
+    let model = parse_tracked(TYPOS);
+    let unknown = |level| {
+        model
+            .validate(level)
+            .items
+            .into_iter()
+            .filter(|i| matches!(i.code, 1001 | 1002))
+            .collect::<Vec<_>>()
+    };
+    assert!(unknown(ValidationLevel::Standard).is_empty());
+
+    let items = unknown(ValidationLevel::Strict);
+    assert_eq!(
+        items.iter().map(|i| i.code).collect::<Vec<_>>(),
+        [1001, 1002, 1002]
+    );
+    assert!(
+        items
+            .iter()
+            .all(|i| i.severity == ValidationSeverity::Warning)
+    );
+    assert_eq!(items[2].context.as_deref(), Some("/model/build/item[2]"));
+    assert!(items[2].message.contains("'transfrom'"));
+}
+
+#[test]
+fn test_written_models_have_no_unknown_names() {
+    let xml = r##"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xml:lang="en-US"
+    xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:m="http://schemas.microsoft.com/3dmanufacturing/material/2015/02"
+    xmlns:p="http://schemas.microsoft.com/3dmanufacturing/production/2015/06">
+    <metadata name="Title" preserve="1">Clean</metadata>
+    <resources>
+        <basematerials id="1">
+            <base name="PLA" displaycolor="#FF0000" />
+        </basematerials>
+        <m:colorgroup id="2">
+            <m:color color="#00FF00" />
+        </m:colorgroup>
+        <m:texture2d id="3" path="/3D/Textures/a.png" contenttype="image/png" tilestyleu="clamp" />
+        <m:texture2dgroup id="4" texid="3">
+            <m:tex2coord u="0" v="1" />
+        </m:texture2dgroup>
+        <m:multiproperties id="5" pids="1 4" blendmethods="multiply">
+            <m:multi pindices="0 0" />
+        </m:multiproperties>
+        <object id="10" type="model" name="part" partnumber="A-1" pid="1" pindex="0"
+            p:UUID="4f5d2f8e-7c51-4c8c-9a4f-3f0f4b7ab001">
+            <mesh>
+                <vertices>
+                    <vertex x="0" y="0" z="0" />
+                    <vertex x="1" y="0" z="0" />
+                    <vertex x="0" y="1" z="0" />
+                </vertices>
+                <triangles>
+                    <triangle v1="0" v2="1" v3="2" pid="5" p1="0" />
+                </triangles>
+            </mesh>
+        </object>
+        <object id="11" type="model">
+            <components>
+                <component objectid="10" transform="1 0 0 0 1 0 0 0 1 5 0 0" />
+            </components>
+        </object>
+    </resources>
+    <build p:UUID="4f5d2f8e-7c51-4c8c-9a4f-3f0f4b7ab002">
+        <item objectid="11" partnumber="B-2" />
+    </build>
+</model>"##;
+    let model = parse_tracked(xml);
+    assert_eq!(model.unknown_names, []);
+
+    let mut written = Vec::new();
+    model.write_xml(&mut written, None).unwrap();
+    let reparsed = parse_tracked(std::str::from_utf8(&written).unwrap());
+    assert_eq!(reparsed.unknown_names, []);
+}
@@ -239,3 +239,52 @@ fn test_self_intersection() {
         report.items
     );
 }
+
+#[test]
+fn test_self_intersection_coplanar_and_shared_vertex() {
+    let intersects = |mesh: Mesh| {
+        let mut model = Model::default();
+        model.resources.add_object(make_object(mesh)).unwrap();
+        let mut report = ValidationReport::default();
+        validate_geometry(&model, ValidationLevel::Paranoid, &mut report);
+        report.items.iter().any(|i| i.code == 4008)
+    };
+
+    // Overlapping triangles in the same plane
+    let mut mesh = Mesh::new();
+    mesh.add_vertex(0.0, 0.0, 0.0);
+    mesh.add_vertex(2.0, 0.0, 0.0);
+    mesh.add_vertex(0.0, 2.0, 0.0);
+    mesh.add_vertex(0.5, 0.5, 0.0);
+    mesh.add_vertex(3.0, 0.5, 0.0);
+    mesh.add_vertex(0.5, 3.0, 0.0);
+    mesh.add_triangle(0, 1, 2);
+    mesh.add_triangle(3, 4, 5);
+    assert!(intersects(mesh));
+
+    // Coplanar triangles sharing one vertex, with overlapping and disjoint wedges
+    let fan = |far: (f32, f32)| {
+        let mut mesh = Mesh::new();
+        mesh.add_vertex(0.0, 0.0, 0.0);
+        mesh.add_vertex(1.0, 0.0, 0.0);
+        mesh.add_vertex(0.0, 1.0, 0.0);
+        mesh.add_vertex(far.0, far.1, 0.0);
+        mesh.add_vertex(-1.0, 1.0, 0.0);
+        mesh.add_triangle(0, 1, 2);
+        mesh.add_triangle(0, 3, 4);
+        mesh
+    };
+    assert!(intersects(fan((1.0, 0.5))));
+    assert!(!intersects(fan((0.0, 1.5))));
+
+    // Triangle sharing one vertex and cutting through the other
+    let mut mesh = Mesh::new();
+    mesh.add_vertex(0.0, 0.0, 0.0);
+    mesh.add_vertex(1.0, 0.0, 0.0);
+    mesh.add_vertex(0.0, 1.0, 0.0);
+    mesh.add_vertex(0.5, 0.5, -0.5);
+    mesh.add_vertex(0.5, 0.5, 0.5);
+    mesh.add_triangle(0, 1, 2);
+    mesh.add_triangle(0, 3, 4);
+    assert!(intersects(mesh));
+}
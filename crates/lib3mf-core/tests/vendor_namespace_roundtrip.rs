@@ -6,6 +6,8 @@
 //!
 //! This is a pure synthetic test — no dependency on external .3mf files.
 
+#![cfg(feature = "writer")]
+
 use lib3mf_core::model::Model;
 use lib3mf_core::parser::parse_model;
 use std::io::Cursor;
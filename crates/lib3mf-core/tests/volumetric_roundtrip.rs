@@ -2,6 +2,8 @@
 //!
 //! Covers requirements VLW-01 through VLW-07 and edge cases.
 
+#![cfg(feature = "writer")]
+
 use glam::Mat4;
 use lib3mf_core::model::*;
 use lib3mf_core::parser::parse_model;
@@ -27,6 +29,7 @@ fn build_item(object_id: ResourceId) -> BuildItem {
         part_number: None,
         transform: Mat4::IDENTITY,
         printable: None,
+        metadata: Default::default(),
     }
 }
 
@@ -0,0 +1,175 @@
+#![cfg(feature = "writer")]
+//! Vertex coordinates written with reduced precision or snapped to a grid.
+
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::model::{Geometry, Mesh, Model, Object, ObjectType, ResourceId};
+use lib3mf_core::parser::parse_model;
+use lib3mf_core::writer::package_writer::PackageWriter;
+use lib3mf_core::writer::{FloatPrecision, VertexQuantization, WriteOptions};
+use lib3mf_core::{Lib3mfError, Package};
+use std::io::Cursor;
+
+fn model_with(vertices: &[[f32; 3]]) -> Model {
+    let mut mesh = Mesh::new();
+    for [x, y, z] in vertices {
+        mesh.add_vertex(*x, *y, *z);
+    }
+    for i in 0..vertices.len() as u32 / 3 {
+        mesh.add_triangle(3 * i, 3 * i + 1, 3 * i + 2);
+    }
+    let mut model = Model::default();
+    model
+        .resources
+        .add_object(Object {
+            id: ResourceId(1),
+            object_type: ObjectType::Model,
+            name: None,
+            part_number: None,
+            uuid: None,
+            pid: None,
+            pindex: None,
+            thumbnail: None,
+            geometry: Geometry::Mesh(mesh),
+        })
+        .unwrap();
+    model
+}
+
+/// Points of a noisy surface, as a 3D scanner produces them.
+fn scan_vertices(count: usize) -> Vec<[f32; 3]> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut noise = || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (state >> 40) as f32 / (1u64 << 24) as f32
+    };
+    (0..count)
+        .map(|i| {
+            let t = i as f32 * 0.01;
+            [
+                40.0 * t.cos() + noise() * 0.05,
+                40.0 * t.sin() + noise() * 0.05,
+                t + noise() * 0.05,
+            ]
+        })
+        .collect()
+}
+
+fn xml(model: &Model, options: &WriteOptions) -> Result<String, Lib3mfError> {
+    let mut xml = Vec::new();
+    model.write_xml_with_options(&mut xml, None, options)?;
+    Ok(String::from_utf8(xml).unwrap())
+}
+
+fn vertices(model: &Model) -> Vec<[f32; 3]> {
+    let Geometry::Mesh(mesh) = &model.resources.get_object(ResourceId(1)).unwrap().geometry else {
+        panic!("expected a mesh");
+    };
+    mesh.vertices.iter().map(|v| [v.x, v.y, v.z]).collect()
+}
+
+/// Size of the `<vertices>` element of the document.
+fn vertices_size(xml: &str) -> usize {
+    xml.find("</vertices>").unwrap() - xml.find("<vertices>").unwrap()
+}
+
+#[test]
+fn test_decimal_places() {
+    let model = model_with(&[[1.23456, -0.0004, 100.0], [0.5, 2.0, 3.0], [0.0, 0.0, 0.0]]);
+    let options = WriteOptions {
+        float_precision: FloatPrecision::Decimals(3),
+        ..Default::default()
+    };
+    let xml = xml(&model, &options).unwrap();
+    assert!(
+        xml.contains(r#"<vertex x="1.235" y="0" z="100"/>"#),
+        "{}",
+        xml
+    );
+    assert!(xml.contains(r#"<vertex x="0.5" y="2" z="3"/>"#), "{}", xml);
+}
+
+#[test]
+fn test_quantization_shrinks_scan_data_within_tolerance() {
+    let original = scan_vertices(3000);
+    let model = model_with(&original);
+    let options = WriteOptions {
+        vertex_quantization: Some(VertexQuantization {
+            step: 0.01,
+            tolerance: 0.006,
+        }),
+        ..Default::default()
+    };
+
+    let exact = xml(&model, &WriteOptions::default()).unwrap();
+    let quantized = xml(&model, &options).unwrap();
+    let (exact_size, quantized_size) = (vertices_size(&exact), vertices_size(&quantized));
+    assert!(
+        quantized_size * 100 < exact_size * 85,
+        "{} bytes of vertices quantized, {} exact",
+        quantized_size,
+        exact_size
+    );
+
+    let parsed = parse_model(Cursor::new(quantized)).unwrap();
+    for (written, original) in vertices(&parsed).iter().zip(&original) {
+        for (w, o) in written.iter().zip(original) {
+            assert!(
+                (f64::from(*w) - f64::from(*o)).abs() <= 0.006,
+                "{} -> {}",
+                o,
+                w
+            );
+        }
+    }
+}
+
+#[test]
+fn test_tolerance_is_enforced() {
+    let model = model_with(&[[0.04, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]);
+    let options = WriteOptions {
+        vertex_quantization: Some(VertexQuantization {
+            step: 0.1,
+            tolerance: 0.01,
+        }),
+        ..Default::default()
+    };
+    let error = xml(&model, &options).unwrap_err();
+    assert!(matches!(error, Lib3mfError::Validation(_)), "{}", error);
+    assert!(error.to_string().contains("more than the tolerance"));
+
+    let options = WriteOptions {
+        vertex_quantization: Some(VertexQuantization {
+            step: 0.0,
+            tolerance: 1.0,
+        }),
+        ..Default::default()
+    };
+    assert!(xml(&model, &options).is_err());
+}
+
+#[test]
+fn test_checksums_describe_written_coordinates() {
+    let model = model_with(&scan_vertices(30));
+    let options = WriteOptions {
+        float_precision: FloatPrecision::Decimals(2),
+        vertex_quantization: Some(VertexQuantization {
+            step: 0.01,
+            tolerance: 0.01,
+        }),
+    };
+
+    let mut buffer = Cursor::new(Vec::new());
+    PackageWriter::new(&mut buffer)
+        .with_geometry_checksums(true)
+        .with_write_options(options)
+        .write(&Package::new(model))
+        .unwrap();
+
+    let mut archive = ZipArchiver::new(Cursor::new(buffer.into_inner())).unwrap();
+    let model_path = find_model_path(&mut archive).unwrap();
+    let parsed = parse_model(Cursor::new(archive.read_entry(&model_path).unwrap())).unwrap();
+    assert!(parsed.has_geometry_checksums());
+    assert_eq!(parsed.verify_geometry_checksums(), []);
+}
@@ -0,0 +1,264 @@
+//! Golden-file snapshots of writer output.
+//!
+//! Each representative model is written as a package and compared, part by part in
+//! canonical form, to `tests/snapshots/writer/<name>.snap`. After an intended writer
+//! change, update the snapshots with:
+//!
+//! ```text
+//! LIB3MF_BLESS=1 cargo test -p lib3mf-core --features testutil --test writer_snapshots
+//! ```
+#![cfg(feature = "testutil")]
+
+use glam::{Mat4, Vec3};
+use lib3mf_core::model::*;
+use lib3mf_core::testutil::snapshot::{assert_snapshot, canonicalize_xml, model_snapshot};
+use lib3mf_core::testutil::{cuboid, tetrahedron};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+fn check(name: &str, model: &Model) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots/writer")
+        .join(format!("{name}.snap"));
+    assert_snapshot(path, &model_snapshot(model).unwrap());
+}
+
+fn object(id: u32, name: &str, geometry: Geometry) -> Object {
+    Object {
+        id: ResourceId(id),
+        object_type: ObjectType::Model,
+        name: Some(name.to_string()),
+        part_number: None,
+        uuid: None,
+        pid: None,
+        pindex: None,
+        thumbnail: None,
+        geometry,
+    }
+}
+
+fn item(object_id: u32, transform: Mat4) -> BuildItem {
+    BuildItem {
+        object_id: ResourceId(object_id),
+        uuid: None,
+        path: None,
+        part_number: None,
+        transform,
+        printable: None,
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn snapshot_core_mesh() {
+    let mut model = Model {
+        unit: Unit::Inch,
+        language: Some("de-DE".to_string()),
+        ..Default::default()
+    };
+    model.metadata.insert("Title", "Snapshot & <core>");
+    model.metadata.insert("Designer", "lib3mf-rs");
+    let mut cube = object(1, "Cube", Geometry::Mesh(cuboid([10.0, 20.0, 30.0], 1)));
+    cube.part_number = Some("CUBE-01".to_string());
+    model.resources.add_object(cube).unwrap();
+    model.build.items.push(item(1, Mat4::IDENTITY));
+    let mut rotated = item(1, Mat4::from_rotation_z(std::f32::consts::FRAC_PI_2));
+    rotated.printable = Some(false);
+    model.build.items.push(rotated);
+
+    check("core_mesh", &model);
+}
+
+#[test]
+fn snapshot_materials() {
+    let mut model = Model::default();
+    model
+        .resources
+        .add_base_materials(BaseMaterialsGroup {
+            id: ResourceId(1),
+            materials: vec![
+                BaseMaterial {
+                    name: "PLA Red".to_string(),
+                    display_color: Color::new(255, 0, 0, 255),
+                },
+                BaseMaterial {
+                    name: "PLA Clear".to_string(),
+                    display_color: Color::new(255, 255, 255, 128),
+                },
+            ],
+        })
+        .unwrap();
+    model
+        .resources
+        .add_color_group(ColorGroup {
+            id: ResourceId(2),
+            colors: vec![Color::new(0, 128, 255, 255), Color::new(16, 32, 48, 255)],
+        })
+        .unwrap();
+    model
+        .resources
+        .add_texture_2d(Texture2D {
+            id: ResourceId(3),
+            path: "/3D/Textures/checker.png".to_string(),
+            contenttype: "image/png".to_string(),
+            tile_style_u: TileStyle::Clamp,
+            tile_style_v: TileStyle::Wrap,
+            filter: Some(FilterMode::Nearest),
+        })
+        .unwrap();
+    model
+        .resources
+        .add_texture_2d_group(Texture2DGroup {
+            id: ResourceId(4),
+            texture_id: ResourceId(3),
+            coords: vec![
+                Texture2DCoord { u: 0.0, v: 0.0 },
+                Texture2DCoord { u: 1.0, v: 0.0 },
+                Texture2DCoord { u: 0.5, v: 1.0 },
+            ],
+        })
+        .unwrap();
+    model
+        .resources
+        .add_composite_materials(CompositeMaterials {
+            id: ResourceId(6),
+            base_material_id: ResourceId(1),
+            indices: vec![0, 1],
+            composites: vec![Composite {
+                values: vec![0.25, 0.75],
+            }],
+        })
+        .unwrap();
+    model
+        .resources
+        .add_multi_properties(MultiProperties {
+            id: ResourceId(7),
+            pids: vec![ResourceId(6), ResourceId(2)],
+            blend_methods: vec![BlendMethod::Multiply],
+            multis: vec![Multi {
+                pindices: vec![0, 1],
+            }],
+        })
+        .unwrap();
+    model.attachments.insert(
+        "3D/Textures/checker.png".to_string(),
+        vec![0x89, b'P', b'N', b'G'],
+    );
+
+    let mut mesh = tetrahedron(10.0);
+    mesh.triangles[0].pid = Some(2);
+    mesh.triangles[0].p1 = Some(0);
+    mesh.triangles[0].p2 = Some(1);
+    mesh.triangles[0].p3 = Some(0);
+    mesh.triangles[1].pid = Some(4);
+    mesh.triangles[1].p1 = Some(0);
+    mesh.triangles[1].p2 = Some(1);
+    mesh.triangles[1].p3 = Some(2);
+    mesh.triangles[2].pid = Some(7);
+    mesh.triangles[2].p1 = Some(0);
+    let mut tetra = object(5, "Painted", Geometry::Mesh(mesh));
+    tetra.pid = Some(ResourceId(1));
+    tetra.pindex = Some(1);
+    model.resources.add_object(tetra).unwrap();
+    model.build.items.push(item(5, Mat4::IDENTITY));
+
+    check("materials", &model);
+}
+
+#[test]
+fn snapshot_components_production() {
+    let uuid = |n: u128| {
+        Some(Uuid::from_u128(
+            0x6d7c_0000_0000_4000_8000_0000_0000_0000 + n,
+        ))
+    };
+    let mut model = Model::default();
+    let mut part = object(1, "Part", Geometry::Mesh(cuboid([5.0, 5.0, 5.0], 1)));
+    part.uuid = uuid(1);
+    model.resources.add_object(part).unwrap();
+    let mut assembly = object(
+        2,
+        "Assembly",
+        Geometry::Components(Components {
+            components: (0..3)
+                .map(|i| Component {
+                    object_id: ResourceId(1),
+                    path: None,
+                    uuid: uuid(10 + i),
+                    transform: Mat4::from_translation(Vec3::new(i as f32 * 6.0, 0.0, 0.0)),
+                })
+                .collect(),
+        }),
+    );
+    assembly.uuid = uuid(2);
+    model.resources.add_object(assembly).unwrap();
+    model.build.uuid = uuid(100);
+    let mut placed = item(2, Mat4::from_translation(Vec3::new(50.0, 50.0, 0.0)));
+    placed.uuid = uuid(101);
+    placed.part_number = Some("ASM-7".to_string());
+    model.build.items.push(placed);
+
+    check("components_production", &model);
+}
+
+#[test]
+fn snapshot_beam_lattice_and_triangle_sets() {
+    let mut model = Model::default();
+    let mut mesh = cuboid([10.0, 10.0, 10.0], 1);
+    let mut top = TriangleSet::new("Top face", "top");
+    top.triangles = vec![2, 3];
+    mesh.triangle_sets.push(top);
+    mesh.beam_lattice = Some(BeamLattice {
+        radius: Some(0.5),
+        min_length: 0.1,
+        precision: 0.01,
+        clipping_mode: ClippingMode::Inside,
+        beams: vec![
+            Beam {
+                v1: 0,
+                v2: 6,
+                r1: 0.5,
+                r2: 0.25,
+                p1: None,
+                p2: None,
+                cap_mode: CapMode::Sphere,
+            },
+            Beam {
+                v1: 1,
+                v2: 7,
+                r1: 0.5,
+                r2: 0.5,
+                p1: None,
+                p2: None,
+                cap_mode: CapMode::Butt,
+            },
+        ],
+        beam_sets: vec![],
+    });
+    model
+        .resources
+        .add_object(object(1, "Lattice", Geometry::Mesh(mesh)))
+        .unwrap();
+    model.build.items.push(item(1, Mat4::IDENTITY));
+
+    check("beam_lattice_triangle_sets", &model);
+}
+
+#[test]
+fn test_canonical_form_ignores_formatting() {
+    let a = r#"<?xml version="1.0"?>
+<!-- comment -->
+<model xmlns:m="urn:m" unit="millimeter" xmlns="urn:core"><resources>
+    <object type="model" id="1"/>
+  </resources><m:note>text</m:note></model>"#;
+    let b = r#"<model xmlns="urn:core" xmlns:m="urn:m" unit="millimeter">
+  <resources><object id="1" type="model"></object></resources>
+  <m:note>text</m:note>
+</model>"#;
+    let canonical = canonicalize_xml(a).unwrap();
+    assert_eq!(
+        canonical,
+        "<model xmlns=\"urn:core\" xmlns:m=\"urn:m\" unit=\"millimeter\">\n  <resources>\n    <object id=\"1\" type=\"model\"/>\n  </resources>\n  <m:note>text</m:note>\n</model>\n"
+    );
+    assert_eq!(canonical, canonicalize_xml(b).unwrap());
+}
@@ -0,0 +1,149 @@
+//! Values written to XML read back exactly: numbers without precision loss, strings with
+//! every character XML allows.
+#![cfg(feature = "writer")]
+
+use lib3mf_core::model::{
+    BuildItem, Geometry, Mesh, Metadata, Model, Object, ObjectType, ResourceId,
+};
+use lib3mf_core::parser::parse_model;
+use lib3mf_core::writer::xml_value::{escape_attribute, escape_text, format_float};
+use proptest::prelude::*;
+use std::io::Cursor;
+
+const METADATA_NAMES: [&str; 3] = ["Title", "Designer", "Description"];
+
+/// Finite `f32` values of every magnitude, subnormals included.
+fn finite_f32() -> impl Strategy<Value = f32> {
+    any::<f32>().prop_filter("finite", |v| v.is_finite())
+}
+
+/// Strings of characters XML allows, weighted towards those that need escaping.
+fn xml_string() -> impl Strategy<Value = String> {
+    let special = prop::sample::select(vec![' ', '\t', '\n', '\r', '&', '<', '>', '"', '\'']);
+    let any_char = any::<char>().prop_filter(
+        "XML character",
+        |c| matches!(c, '\u{20}'..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}' | '\u{10000}'..),
+    );
+    prop::collection::vec(prop_oneof![special, any_char], 0..24)
+        .prop_map(|chars| chars.into_iter().collect())
+}
+
+fn model_of(vertices: &[[f32; 3]], transform: &[f32; 12], name: &str, values: &[String]) -> Model {
+    let mut mesh = Mesh::new();
+    for [x, y, z] in vertices {
+        mesh.add_vertex(*x, *y, *z);
+    }
+    mesh.add_triangle(0, 1, 2);
+
+    let mut model = Model::default();
+    model
+        .resources
+        .add_object(Object {
+            id: ResourceId(1),
+            object_type: ObjectType::Model,
+            name: Some(name.to_string()),
+            part_number: None,
+            uuid: None,
+            pid: None,
+            pindex: None,
+            thumbnail: None,
+            geometry: Geometry::Mesh(mesh),
+        })
+        .unwrap();
+
+    let mut columns = [0.0; 16];
+    for column in 0..4 {
+        columns[column * 4..column * 4 + 3].copy_from_slice(&transform[column * 3..column * 3 + 3]);
+    }
+    columns[15] = 1.0;
+    model.build.items.push(BuildItem {
+        object_id: ResourceId(1),
+        uuid: None,
+        path: None,
+        part_number: None,
+        transform: glam::Mat4::from_cols_array(&columns),
+        printable: None,
+        metadata: Metadata::new(),
+    });
+
+    for (name, value) in METADATA_NAMES.iter().zip(values) {
+        model.metadata.insert(*name, value.clone());
+    }
+    model
+}
+
+fn round_trip(model: &Model) -> Model {
+    let mut xml = Vec::new();
+    model.write_xml(&mut xml, None).unwrap();
+    parse_model(Cursor::new(xml)).unwrap()
+}
+
+fn bits(values: impl IntoIterator<Item = f32>) -> Vec<u32> {
+    values.into_iter().map(f32::to_bits).collect()
+}
+
+proptest! {
+    #[test]
+    fn test_values_round_trip(
+        vertices in prop::collection::vec(prop::array::uniform3(finite_f32()), 3..8),
+        transform in prop::array::uniform12(finite_f32()),
+        name in xml_string(),
+        values in prop::collection::vec(xml_string(), 3),
+    ) {
+        let model = model_of(&vertices, &transform, &name, &values);
+        let parsed = round_trip(&model);
+
+        let object = parsed.resources.get_object(ResourceId(1)).unwrap();
+        prop_assert_eq!(object.name.as_deref(), Some(name.as_str()));
+        let Geometry::Mesh(mesh) = &object.geometry else {
+            panic!("expected a mesh");
+        };
+        prop_assert_eq!(
+            bits(mesh.vertices.iter().flat_map(|v| [v.x, v.y, v.z])),
+            bits(vertices.iter().flatten().copied())
+        );
+
+        let written = parsed.build.items[0].transform;
+        let columns = [written.x_axis, written.y_axis, written.z_axis, written.w_axis];
+        prop_assert_eq!(
+            bits(columns.iter().flat_map(|c| c.truncate().to_array())),
+            bits(transform)
+        );
+
+        prop_assert_eq!(&parsed.metadata, &model.metadata);
+    }
+
+    #[test]
+    fn test_float_format_is_positional(value in finite_f32()) {
+        let formatted = format_float(value).unwrap();
+        prop_assert!(!formatted.contains(['e', 'E']), "{}", formatted);
+        prop_assert_eq!(formatted.parse::<f32>().unwrap().to_bits(), value.to_bits());
+    }
+}
+
+#[test]
+fn test_non_finite_values_are_not_written() {
+    let model = model_of(
+        &[[0.0, 0.0, 0.0], [f32::NAN, 0.0, 0.0], [0.0, 1.0, 0.0]],
+        &[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0],
+        "cube",
+        &[],
+    );
+    let error = model.write_xml(Vec::new(), None).unwrap_err().to_string();
+    assert!(error.contains("attribute x of <vertex>"), "{}", error);
+}
+
+#[test]
+fn test_characters_xml_disallows_are_not_written() {
+    assert!(escape_attribute("bell\u{7}").is_err());
+    assert!(escape_text("\u{FFFE}").is_err());
+
+    let model = model_of(
+        &[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+        &[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0],
+        "nul\u{0}",
+        &[],
+    );
+    let error = model.write_xml(Vec::new(), None).unwrap_err().to_string();
+    assert!(error.contains("U+0000"), "{}", error);
+}
@@ -0,0 +1,74 @@
+//! Zip64 support: packages with more than 65,535 entries and entries of 4 GiB or more.
+
+#![cfg(feature = "writer")]
+
+use lib3mf_core::Model;
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::model::Package;
+use lib3mf_core::parser::parse_model;
+use lib3mf_core::writer::package_writer::PackageWriter;
+use std::fs::File;
+use std::io::{BufWriter, Cursor};
+
+#[test]
+fn test_more_than_65535_entries() {
+    let mut model = Model::default();
+    for i in 0..70_000 {
+        model
+            .attachments
+            .insert(format!("Metadata/part_{i}.txt"), i.to_string().into_bytes());
+    }
+
+    let mut buffer = Cursor::new(Vec::new());
+    model.write(&mut buffer).unwrap();
+
+    let mut archiver = ZipArchiver::new(Cursor::new(buffer.into_inner())).unwrap();
+    assert!(archiver.list_entries().unwrap().len() > 70_000);
+    assert_eq!(
+        archiver.read_entry("Metadata/part_69999.txt").unwrap(),
+        b"69999"
+    );
+    let model_path = find_model_path(&mut archiver).unwrap();
+    parse_model(Cursor::new(archiver.read_entry(&model_path).unwrap())).unwrap();
+}
+
+#[test]
+fn test_forced_zip64_package_reads_back() {
+    let mut model = Model::default();
+    model
+        .attachments
+        .insert("Metadata/notes.txt".to_string(), b"notes".to_vec());
+
+    let mut buffer = Cursor::new(Vec::new());
+    PackageWriter::new(&mut buffer)
+        .with_zip64(true)
+        .write(&Package::new(model))
+        .unwrap();
+
+    let mut archiver = ZipArchiver::new(Cursor::new(buffer.into_inner())).unwrap();
+    assert_eq!(archiver.read_entry("Metadata/notes.txt").unwrap(), b"notes");
+    let model_path = find_model_path(&mut archiver).unwrap();
+    let model = parse_model(Cursor::new(archiver.read_entry(&model_path).unwrap())).unwrap();
+    assert_eq!(model.unit.as_str(), "millimeter");
+}
+
+#[test]
+#[ignore = "writes a 4 GiB entry; needs about 5 GiB of memory and disk"]
+fn test_entry_larger_than_4gib() {
+    let size = u64::from(u32::MAX) + 1024;
+    let mut model = Model::default();
+    model
+        .attachments
+        .insert("3D/Textures/volume.bin".to_string(), vec![0; size as usize]);
+
+    let path = std::env::temp_dir().join("lib3mf_zip64_test.3mf");
+    PackageWriter::new(BufWriter::new(File::create(&path).unwrap()))
+        .write(&Package::new(model))
+        .unwrap();
+
+    let mut archiver = ZipArchiver::new(File::open(&path).unwrap()).unwrap();
+    let metadata = archiver.entry_metadata("3D/Textures/volume.bin").unwrap();
+    assert_eq!(metadata.size, size);
+    assert!(find_model_path(&mut archiver).is_ok());
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,45 @@
+"""Print mesh statistics for a 3MF file, repair it, and save the result.
+
+    maturin develop -m crates/lib3mf-py/Cargo.toml
+    python crates/lib3mf-py/examples/inspect.py input.3mf fixed.3mf
+"""
+
+import sys
+
+import lib3mf
+
+try:
+    import numpy as np
+except ImportError:
+    np = None
+
+
+def main(src: str, dst: str) -> None:
+    model = lib3mf.Model.open(src)
+    print(model)
+
+    for obj in model.objects:
+        mesh = obj.mesh
+        if mesh is None:
+            print(f"  {obj.id}: {obj.kind}")
+            continue
+        area, volume = mesh.area_and_volume()
+        print(f"  {obj.id}: {obj.name or '(unnamed)'} {mesh.triangle_count} triangles, "
+              f"area {area:.2f}, volume {volume:.2f}")
+        if np is not None and mesh.vertex_count:
+            vertices = np.asarray(mesh.vertices)  # shares memory with the model
+            print(f"      centroid {vertices.mean(axis=0)}")
+            del vertices  # release the view so the model can be repaired
+
+    report = model.validate("paranoid")
+    for item in report:
+        print(f"  [{item.severity}] {item.code}: {item.message}")
+
+    print(model.repair())
+    model.write(dst)
+
+
+if __name__ == "__main__":
+    if len(sys.argv) != 3:
+        sys.exit(f"usage: {sys.argv[0]} <input.3mf> <output.3mf>")
+    main(sys.argv[1], sys.argv[2])
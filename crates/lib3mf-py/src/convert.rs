@@ -0,0 +1,45 @@
+//! STL and OBJ import/export through `lib3mf-converters`.
+
+use crate::model::PyModel;
+use crate::to_py_err;
+use lib3mf_converters::obj::{ObjExporter, ObjImporter};
+use lib3mf_converters::stl::{StlExportOptions, StlExporter, StlFormat, StlImporter};
+use lib3mf_core::Model;
+use pyo3::prelude::*;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+/// Reads an STL file (ASCII or binary, detected automatically) into a new model.
+#[pyfunction]
+pub fn import_stl(py: Python<'_>, path: PathBuf) -> PyResult<PyModel> {
+    py.detach(|| StlImporter::read(BufReader::new(File::open(&path)?)))
+        .map(PyModel::wrap)
+        .map_err(to_py_err)
+}
+
+/// Reads a Wavefront OBJ file (and any referenced `.mtl` materials) into a new model.
+#[pyfunction]
+pub fn import_obj(py: Python<'_>, path: PathBuf) -> PyResult<PyModel> {
+    py.detach(|| ObjImporter::read_from_path(&path))
+        .map(PyModel::wrap)
+        .map_err(to_py_err)
+}
+
+pub(crate) fn export_stl(model: &Model, path: &Path, ascii: bool) -> PyResult<()> {
+    let run = || {
+        let format = if ascii {
+            StlFormat::Ascii
+        } else {
+            StlFormat::Binary
+        };
+        StlExporter::new(StlExportOptions { format })
+            .write(model, BufWriter::new(File::create(path)?))
+    };
+    run().map_err(to_py_err)
+}
+
+pub(crate) fn export_obj(model: &Model, path: &Path) -> PyResult<()> {
+    let run = || ObjExporter::write(model, BufWriter::new(File::create(path)?));
+    run().map_err(to_py_err)
+}
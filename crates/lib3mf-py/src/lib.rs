@@ -0,0 +1,82 @@
+//! # lib3mf-py
+//!
+//! Python bindings for lib3mf-rs, built with [PyO3](https://pyo3.rs) and packaged with
+//! [maturin](https://www.maturin.rs) as the `lib3mf` module.
+//!
+//! ## Python Usage
+//!
+//! ```python
+//! import lib3mf
+//! import numpy as np
+//!
+//! model = lib3mf.Model.open("Benchy.3mf")
+//! print(model.unit, len(model.objects))
+//!
+//! for obj in model.objects:
+//!     if obj.kind == "mesh":
+//!         vertices = np.asarray(obj.mesh.vertices)    # (n, 3) float32, no copy
+//!         triangles = np.asarray(obj.mesh.triangles)  # (m, 3) uint32
+//!         print(obj.id, obj.name, vertices.shape, triangles.shape)
+//!
+//! report = model.validate("paranoid")
+//! if report.has_errors:
+//!     stats = model.repair(fill_holes=True)
+//!     print(stats)
+//! model.write("fixed.3mf")
+//! ```
+//!
+//! ## Zero-Copy Arrays
+//!
+//! [`Mesh::vertices`](mesh::PyMesh) returns an object implementing the Python buffer
+//! protocol over the model's own vertex storage, so `numpy.asarray` and `memoryview` see
+//! it without copying. While such a view is alive, operations that could reallocate the
+//! storage (`repair`, `add_mesh`) raise `BufferError`, just as resizing a `bytearray`
+//! with live exports does. Triangle indices are copied once, since the in-memory triangle
+//! also carries optional property indices.
+//!
+//! NumPy is optional; without it, `memoryview(mesh.vertices).tolist()` gives nested lists.
+
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyIOError};
+use pyo3::prelude::*;
+
+mod convert;
+mod mesh;
+mod model;
+mod validation;
+
+pub use mesh::{PyArray, PyMesh};
+pub use model::{PyModel, PyObject};
+pub use validation::{PyRepairStats, PyValidationItem, PyValidationReport};
+
+create_exception!(
+    lib3mf,
+    Lib3mfError,
+    PyException,
+    "Raised when a 3MF file cannot be parsed, written or converted."
+);
+
+/// Converts a core error into a Python exception: `OSError` for I/O, `Lib3mfError` otherwise.
+pub(crate) fn to_py_err(err: lib3mf_core::error::Lib3mfError) -> PyErr {
+    match err {
+        lib3mf_core::error::Lib3mfError::Io(e) => PyIOError::new_err(e.to_string()),
+        other => Lib3mfError::new_err(other.to_string()),
+    }
+}
+
+/// The `lib3mf` Python module.
+#[pymodule]
+pub fn lib3mf(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    m.add("Lib3mfError", m.py().get_type::<Lib3mfError>())?;
+    m.add_class::<PyModel>()?;
+    m.add_class::<PyObject>()?;
+    m.add_class::<PyMesh>()?;
+    m.add_class::<PyArray>()?;
+    m.add_class::<PyValidationReport>()?;
+    m.add_class::<PyValidationItem>()?;
+    m.add_class::<PyRepairStats>()?;
+    m.add_function(wrap_pyfunction!(convert::import_stl, m)?)?;
+    m.add_function(wrap_pyfunction!(convert::import_obj, m)?)?;
+    Ok(())
+}
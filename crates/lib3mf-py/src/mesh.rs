@@ -0,0 +1,224 @@
+//! `lib3mf.Mesh` and the buffer-protocol `lib3mf.Array`.
+
+use crate::model::PyModel;
+use lib3mf_core::model::{Geometry, Mesh};
+use pyo3::exceptions::{PyBufferError, PyValueError};
+use pyo3::ffi;
+use pyo3::prelude::*;
+use std::ffi::{c_int, c_void};
+use std::sync::atomic::Ordering;
+
+/// Geometry of one mesh object. Holds a reference to its model, not a copy.
+#[pyclass(name = "Mesh", module = "lib3mf", frozen)]
+pub struct PyMesh {
+    model: Py<PyModel>,
+    /// Resource ID of the object this mesh belongs to.
+    #[pyo3(get)]
+    object_id: u32,
+}
+
+impl PyMesh {
+    pub(crate) fn new(model: Py<PyModel>, object_id: u32) -> Self {
+        Self { model, object_id }
+    }
+}
+
+fn mesh_of(model: &PyModel, object_id: u32) -> PyResult<&Mesh> {
+    match &model.object(object_id)?.geometry {
+        Geometry::Mesh(mesh) => Ok(mesh),
+        _ => Err(PyValueError::new_err(format!(
+            "Object {object_id} no longer holds a triangle mesh"
+        ))),
+    }
+}
+
+#[pymethods]
+impl PyMesh {
+    /// Number of vertices.
+    #[getter]
+    fn vertex_count(&self, py: Python<'_>) -> PyResult<usize> {
+        Ok(mesh_of(&*self.model.try_borrow(py)?, self.object_id)?
+            .vertices
+            .len())
+    }
+
+    /// Number of triangles.
+    #[getter]
+    fn triangle_count(&self, py: Python<'_>) -> PyResult<usize> {
+        Ok(mesh_of(&*self.model.try_borrow(py)?, self.object_id)?
+            .triangles
+            .len())
+    }
+
+    /// Vertex positions as an `(n, 3)` float32 array that shares the model's memory.
+    #[getter]
+    fn vertices(&self, py: Python<'_>) -> PyResult<PyArray> {
+        mesh_of(&*self.model.try_borrow(py)?, self.object_id)?;
+        Ok(PyArray {
+            data: ArrayData::Vertices {
+                model: self.model.clone_ref(py),
+                object_id: self.object_id,
+            },
+        })
+    }
+
+    /// Triangle vertex indices as an `(m, 3)` uint32 array (a copy).
+    #[getter]
+    fn triangles(&self, py: Python<'_>) -> PyResult<PyArray> {
+        let model = self.model.try_borrow(py)?;
+        let indices = mesh_of(&model, self.object_id)?
+            .triangles
+            .iter()
+            .flat_map(|t| [t.v1, t.v2, t.v3])
+            .collect();
+        Ok(PyArray {
+            data: ArrayData::Indices(indices),
+        })
+    }
+
+    /// Surface area and signed volume in model units, as `(area, volume)`.
+    fn area_and_volume(&self, py: Python<'_>) -> PyResult<(f64, f64)> {
+        Ok(mesh_of(&*self.model.try_borrow(py)?, self.object_id)?.compute_area_and_volume())
+    }
+
+    /// Axis-aligned bounds as `((min_x, min_y, min_z), (max_x, max_y, max_z))`, or `None`
+    /// for an empty mesh.
+    fn bounds(&self, py: Python<'_>) -> PyResult<Option<(Point, Point)>> {
+        let model = self.model.try_borrow(py)?;
+        let mesh = mesh_of(&model, self.object_id)?;
+        Ok(mesh.compute_aabb().map(|b| (b.min.into(), b.max.into())))
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        let model = self.model.try_borrow(py)?;
+        let mesh = mesh_of(&model, self.object_id)?;
+        Ok(format!(
+            "<lib3mf.Mesh object_id={} vertices={} triangles={}>",
+            self.object_id,
+            mesh.vertices.len(),
+            mesh.triangles.len()
+        ))
+    }
+}
+
+/// `(x, y, z)` tuple on the Python side.
+type Point = (f32, f32, f32);
+
+enum ArrayData {
+    /// Borrowed from the model's vertex storage; rows of `x, y, z` float32.
+    Vertices { model: Py<PyModel>, object_id: u32 },
+    /// Owned rows of `v1, v2, v3` uint32.
+    Indices(Vec<u32>),
+}
+
+/// A read-only `(rows, 3)` array exposed through the Python buffer protocol.
+///
+/// Use `numpy.asarray(array)` or `memoryview(array)` to read it.
+#[pyclass(name = "Array", module = "lib3mf", frozen)]
+pub struct PyArray {
+    data: ArrayData,
+}
+
+/// Shape and strides handed to the consumer; freed in `__releasebuffer__`.
+struct Layout {
+    shape: [ffi::Py_ssize_t; 2],
+    strides: [ffi::Py_ssize_t; 2],
+}
+
+#[pymethods]
+impl PyArray {
+    fn __len__(&self, py: Python<'_>) -> PyResult<usize> {
+        match &self.data {
+            ArrayData::Vertices { model, object_id } => {
+                Ok(mesh_of(&*model.try_borrow(py)?, *object_id)?.vertices.len())
+            }
+            ArrayData::Indices(indices) => Ok(indices.len() / 3),
+        }
+    }
+
+    /// Buffer-protocol export.
+    ///
+    /// # Safety
+    ///
+    /// Called by the interpreter with a valid `view`.
+    unsafe fn __getbuffer__(
+        slf: Bound<'_, Self>,
+        view: *mut ffi::Py_buffer,
+        flags: c_int,
+    ) -> PyResult<()> {
+        if view.is_null() {
+            return Err(PyBufferError::new_err("View is null"));
+        }
+        if flags & ffi::PyBUF_WRITABLE == ffi::PyBUF_WRITABLE {
+            return Err(PyBufferError::new_err("lib3mf arrays are read-only"));
+        }
+
+        let array = slf.get();
+        let (buf, rows, format): (*const c_void, usize, &'static [u8]) = match &array.data {
+            ArrayData::Vertices { model, object_id } => {
+                let model = model.try_borrow(slf.py())?;
+                let vertices = &mesh_of(&model, *object_id)?.vertices;
+                // Counted until `__releasebuffer__`, so the model refuses to reallocate the
+                // vertex storage while the consumer holds this pointer.
+                model.exports.fetch_add(1, Ordering::AcqRel);
+                (vertices.as_ptr().cast(), vertices.len(), b"f\0")
+            }
+            ArrayData::Indices(indices) => (indices.as_ptr().cast(), indices.len() / 3, b"I\0"),
+        };
+
+        const ITEMSIZE: usize = 4;
+        let layout = Box::new(Layout {
+            shape: [rows as ffi::Py_ssize_t, 3],
+            strides: [
+                (3 * ITEMSIZE) as ffi::Py_ssize_t,
+                ITEMSIZE as ffi::Py_ssize_t,
+            ],
+        });
+
+        // SAFETY: `view` is non-null and owned by the caller for the duration of the export.
+        // `buf` points into memory kept alive by `obj` (this array, which holds the model
+        // or the owned indices) and is not reallocated while `exports` is non-zero.
+        unsafe {
+            (*view).obj = slf.into_any().into_ptr();
+            (*view).buf = buf as *mut c_void;
+            (*view).len = (rows * 3 * ITEMSIZE) as ffi::Py_ssize_t;
+            (*view).readonly = 1;
+            (*view).itemsize = ITEMSIZE as ffi::Py_ssize_t;
+            (*view).format = if flags & ffi::PyBUF_FORMAT == ffi::PyBUF_FORMAT {
+                format.as_ptr() as *mut _
+            } else {
+                std::ptr::null_mut()
+            };
+            let layout = Box::into_raw(layout);
+            (*view).internal = layout.cast();
+            if flags & ffi::PyBUF_ND == ffi::PyBUF_ND {
+                (*view).ndim = 2;
+                (*view).shape = (*layout).shape.as_mut_ptr();
+            } else {
+                (*view).ndim = 1;
+                (*view).shape = std::ptr::null_mut();
+            }
+            (*view).strides = if flags & ffi::PyBUF_STRIDES == ffi::PyBUF_STRIDES {
+                (*layout).strides.as_mut_ptr()
+            } else {
+                std::ptr::null_mut()
+            };
+            (*view).suboffsets = std::ptr::null_mut();
+        }
+        Ok(())
+    }
+
+    /// Buffer-protocol release.
+    ///
+    /// # Safety
+    ///
+    /// Called by the interpreter with a view previously filled by `__getbuffer__`.
+    unsafe fn __releasebuffer__(&self, py: Python<'_>, view: *mut ffi::Py_buffer) -> PyResult<()> {
+        // SAFETY: `internal` was set from `Box::into_raw` in `__getbuffer__`.
+        drop(unsafe { Box::from_raw((*view).internal.cast::<Layout>()) });
+        if let ArrayData::Vertices { model, .. } = &self.data {
+            model.try_borrow(py)?.exports.fetch_sub(1, Ordering::AcqRel);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,371 @@
+//! `lib3mf.Model` and `lib3mf.Object`.
+
+use crate::mesh::PyMesh;
+use crate::to_py_err;
+use crate::validation::{PyRepairStats, PyValidationReport};
+use lib3mf_core::Model;
+use lib3mf_core::archive::{
+    ArchiveReader, ZipArchiver, check_model_not_encrypted, find_model_path,
+};
+use lib3mf_core::model::repair::{DegenerateArea, MeshRepair, RepairOptions};
+use lib3mf_core::model::{BuildItem, Geometry, Mesh, Object, ObjectType, ResourceId, Triangle};
+use lib3mf_core::parser::parse_model;
+use lib3mf_core::validation::ValidationLevel;
+use pyo3::exceptions::{PyBufferError, PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use std::fs::File;
+use std::io::{BufWriter, Cursor, Read, Seek};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A 3MF model: resources, build items and metadata.
+#[pyclass(name = "Model", module = "lib3mf")]
+pub struct PyModel {
+    pub(crate) inner: Model,
+    /// Number of live buffer exports that point into mesh storage.
+    pub(crate) exports: AtomicUsize,
+}
+
+impl PyModel {
+    pub(crate) fn wrap(inner: Model) -> Self {
+        Self {
+            inner,
+            exports: AtomicUsize::new(0),
+        }
+    }
+
+    pub(crate) fn object(&self, id: u32) -> PyResult<&Object> {
+        self.inner
+            .resources
+            .get_object(ResourceId(id))
+            .ok_or_else(|| PyKeyError::new_err(format!("No object with ID {id}")))
+    }
+
+    /// Fails if a zero-copy view is alive, since the operation may reallocate mesh storage.
+    fn ensure_unexported(&self) -> PyResult<()> {
+        match self.exports.load(Ordering::Acquire) {
+            0 => Ok(()),
+            n => Err(PyBufferError::new_err(format!(
+                "Model has {n} exported vertex buffer(s); release them before modifying meshes"
+            ))),
+        }
+    }
+
+    fn load<R: Read + Seek>(reader: R) -> lib3mf_core::error::Result<Model> {
+        let mut archiver = ZipArchiver::new(reader)?;
+        let model_path = find_model_path(&mut archiver)?;
+        check_model_not_encrypted(&mut archiver, &model_path)?;
+        let data = archiver.read_entry(&model_path)?;
+        parse_model(Cursor::new(data))
+    }
+}
+
+fn geometry_kind(geometry: &Geometry) -> &'static str {
+    match geometry {
+        Geometry::Mesh(_) | Geometry::LazyMesh(_) | Geometry::PreciseMesh(_) => "mesh",
+        Geometry::Components(_) => "components",
+        Geometry::SliceStack(_) => "slicestack",
+        Geometry::VolumetricStack(_) => "volumetricstack",
+        Geometry::BooleanShape(_) => "booleanshape",
+        Geometry::DisplacementMesh(_) => "displacementmesh",
+        Geometry::LevelSet(_) => "levelset",
+    }
+}
+
+#[pymethods]
+impl PyModel {
+    /// Creates an empty model (millimeters, no objects, empty build).
+    #[new]
+    fn new() -> Self {
+        Self::wrap(Model::default())
+    }
+
+    /// Opens and parses a `.3mf` package.
+    #[staticmethod]
+    fn open(py: Python<'_>, path: std::path::PathBuf) -> PyResult<Self> {
+        let model = py.detach(|| {
+            let file = File::open(&path)?;
+            Self::load(file)
+        });
+        model.map(Self::wrap).map_err(to_py_err)
+    }
+
+    /// Parses a `.3mf` package from bytes.
+    #[staticmethod]
+    fn from_bytes(py: Python<'_>, data: Vec<u8>) -> PyResult<Self> {
+        py.detach(|| Self::load(Cursor::new(data)))
+            .map(Self::wrap)
+            .map_err(to_py_err)
+    }
+
+    /// Unit of measurement, e.g. `"millimeter"`.
+    #[getter]
+    fn unit(&self) -> String {
+        self.inner.unit.to_string()
+    }
+
+    /// Objects in the model's resources, in ascending ID order.
+    #[getter]
+    fn objects(slf: Bound<'_, Self>) -> Vec<PyObject> {
+        let model = slf.borrow();
+        let mut objects: Vec<_> = model
+            .inner
+            .resources
+            .iter_objects()
+            .map(|o| PyObject::new(slf.clone().unbind(), o))
+            .collect();
+        objects.sort_by_key(|o| o.id);
+        objects
+    }
+
+    /// Returns the object with the given resource ID.
+    fn object_by_id(slf: Bound<'_, Self>, id: u32) -> PyResult<PyObject> {
+        let object = PyObject::new(slf.clone().unbind(), slf.borrow().object(id)?);
+        Ok(object)
+    }
+
+    /// Number of build items (placed instances) on the build plate.
+    #[getter]
+    fn build_item_count(&self) -> usize {
+        self.inner.build.items.len()
+    }
+
+    /// Model-level metadata as a `{name: value}` dict. Names given in several languages
+    /// map to their untagged value.
+    #[getter]
+    fn metadata(&self) -> std::collections::HashMap<String, String> {
+        let metadata = &self.inner.metadata;
+        metadata
+            .names()
+            .filter_map(|name| Some((name.to_string(), metadata.get(name)?.to_string())))
+            .collect()
+    }
+
+    /// Adds a mesh object and returns its resource ID.
+    ///
+    /// `vertices` is a sequence of `(x, y, z)` rows and `triangles` a sequence of
+    /// `(v1, v2, v3)` index rows; NumPy arrays of shape `(n, 3)` work directly. When
+    /// `build` is true (the default), the object is also placed on the build plate.
+    #[pyo3(signature = (vertices, triangles, name = None, build = true))]
+    fn add_mesh(
+        &mut self,
+        vertices: Vec<[f32; 3]>,
+        triangles: Vec<[u32; 3]>,
+        name: Option<String>,
+        build: bool,
+    ) -> PyResult<u32> {
+        self.ensure_unexported()?;
+        if let Some(bad) = triangles
+            .iter()
+            .flatten()
+            .find(|&&i| i as usize >= vertices.len())
+        {
+            return Err(PyValueError::new_err(format!(
+                "Triangle index {bad} out of range ({} vertices)",
+                vertices.len()
+            )));
+        }
+
+        let mut mesh = Mesh::new();
+        for [x, y, z] in vertices {
+            mesh.add_vertex(x, y, z);
+        }
+        mesh.triangles = triangles
+            .into_iter()
+            .map(|[v1, v2, v3]| Triangle {
+                v1,
+                v2,
+                v3,
+                ..Default::default()
+            })
+            .collect();
+
+        let resources = &mut self.inner.resources;
+        let id = (1..=u32::MAX)
+            .map(ResourceId)
+            .find(|id| !resources.exists(*id))
+            .ok_or_else(|| PyValueError::new_err("No free resource ID"))?;
+        resources
+            .add_object(Object {
+                id,
+                object_type: ObjectType::Model,
+                name,
+                part_number: None,
+                uuid: None,
+                pid: None,
+                pindex: None,
+                thumbnail: None,
+                geometry: Geometry::Mesh(mesh),
+            })
+            .map_err(to_py_err)?;
+        if build {
+            self.inner.build.items.push(BuildItem {
+                object_id: id,
+                uuid: None,
+                path: None,
+                part_number: None,
+                transform: glam::Mat4::IDENTITY,
+                printable: None,
+                metadata: Default::default(),
+            });
+        }
+        Ok(id.0)
+    }
+
+    /// Validates the model; `level` is `"minimal"`, `"standard"`, `"strict"` or `"paranoid"`.
+    #[pyo3(signature = (level = "standard"))]
+    fn validate(&self, py: Python<'_>, level: &str) -> PyResult<PyValidationReport> {
+        let level = match level {
+            "minimal" => ValidationLevel::Minimal,
+            "standard" => ValidationLevel::Standard,
+            "strict" => ValidationLevel::Strict,
+            "paranoid" => ValidationLevel::Paranoid,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown validation level '{other}'; expected minimal, standard, strict or paranoid"
+                )));
+            }
+        };
+        let report = py.detach(|| self.inner.validate(level));
+        PyValidationReport::new(py, report)
+    }
+
+    /// Repairs every mesh in place and returns the combined statistics.
+    ///
+    /// Keyword arguments mirror the core `RepairOptions` and default to the same values.
+    /// `degenerate_area` is in square millimeters; by default the threshold is relative to
+    /// each mesh's bounding box.
+    #[pyo3(signature = (
+        stitch_epsilon = 1e-4,
+        remove_degenerate = true,
+        remove_duplicate_faces = true,
+        harmonize_orientations = true,
+        remove_islands = false,
+        fill_holes = false,
+        degenerate_area = None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn repair(
+        &mut self,
+        py: Python<'_>,
+        stitch_epsilon: f32,
+        remove_degenerate: bool,
+        remove_duplicate_faces: bool,
+        harmonize_orientations: bool,
+        remove_islands: bool,
+        fill_holes: bool,
+        degenerate_area: Option<f64>,
+    ) -> PyResult<PyRepairStats> {
+        self.ensure_unexported()?;
+        let options = RepairOptions {
+            stitch_epsilon,
+            remove_degenerate,
+            remove_duplicate_faces,
+            harmonize_orientations,
+            remove_islands,
+            fill_holes,
+            degenerate_area: degenerate_area
+                .map(DegenerateArea::SquareMillimeters)
+                .unwrap_or_default(),
+            unit: self.inner.unit,
+        };
+        let inner = &mut self.inner;
+        let stats = py.detach(move || {
+            let mut total = PyRepairStats::default();
+            for object in inner.resources.iter_objects_mut() {
+                if let Geometry::Mesh(mesh) = &mut object.geometry {
+                    total.add(&mesh.repair(options));
+                }
+            }
+            total
+        });
+        Ok(stats)
+    }
+
+    /// Writes the model as a `.3mf` package.
+    fn write(&self, py: Python<'_>, path: std::path::PathBuf) -> PyResult<()> {
+        py.detach(|| {
+            let file = File::create(&path)?;
+            self.inner.write(BufWriter::new(file))
+        })
+        .map_err(to_py_err)
+    }
+
+    /// Serializes the model as a `.3mf` package and returns the bytes.
+    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        let mut buffer = Cursor::new(Vec::new());
+        py.detach(|| self.inner.write(&mut buffer))
+            .map_err(to_py_err)?;
+        Ok(PyBytes::new(py, buffer.get_ref()))
+    }
+
+    /// Exports all build items as an STL file (binary unless `ascii` is true).
+    #[pyo3(signature = (path, ascii = false))]
+    fn export_stl(&self, py: Python<'_>, path: std::path::PathBuf, ascii: bool) -> PyResult<()> {
+        py.detach(|| crate::convert::export_stl(&self.inner, &path, ascii))
+    }
+
+    /// Exports all build items as a Wavefront OBJ file.
+    fn export_obj(&self, py: Python<'_>, path: std::path::PathBuf) -> PyResult<()> {
+        py.detach(|| crate::convert::export_obj(&self.inner, &path))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<lib3mf.Model unit={} objects={} build_items={}>",
+            self.inner.unit,
+            self.inner.resources.iter_objects().count(),
+            self.inner.build.items.len()
+        )
+    }
+}
+
+/// Summary of one object; `mesh` gives access to its geometry.
+#[pyclass(name = "Object", module = "lib3mf", frozen)]
+pub struct PyObject {
+    model: Py<PyModel>,
+    /// Resource ID of the object.
+    #[pyo3(get)]
+    id: u32,
+    /// Value of the `name` attribute, if any.
+    #[pyo3(get)]
+    name: Option<String>,
+    /// Object type: `"model"`, `"support"`, `"solidsupport"`, `"surface"` or `"other"`.
+    #[pyo3(get, name = "type")]
+    object_type: String,
+    /// Geometry kind: `"mesh"`, `"components"`, `"slicestack"`, `"volumetricstack"`,
+    /// `"booleanshape"` or `"displacementmesh"`.
+    #[pyo3(get)]
+    kind: &'static str,
+}
+
+impl PyObject {
+    fn new(model: Py<PyModel>, object: &Object) -> Self {
+        Self {
+            model,
+            id: object.id.0,
+            name: object.name.clone(),
+            object_type: object.object_type.to_string(),
+            kind: geometry_kind(&object.geometry),
+        }
+    }
+}
+
+#[pymethods]
+impl PyObject {
+    /// The object's triangle mesh, or `None` if it holds other geometry.
+    #[getter]
+    fn mesh(&self, py: Python<'_>) -> Option<PyMesh> {
+        (self.kind == "mesh").then(|| PyMesh::new(self.model.clone_ref(py), self.id))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<lib3mf.Object id={} name={:?} type={} kind={}>",
+            self.id,
+            self.name.as_deref().unwrap_or(""),
+            self.object_type,
+            self.kind
+        )
+    }
+}
@@ -0,0 +1,143 @@
+//! `lib3mf.ValidationReport`, `lib3mf.ValidationItem` and `lib3mf.RepairStats`.
+
+use lib3mf_core::model::repair::RepairStats;
+use lib3mf_core::validation::{ValidationReport, ValidationSeverity};
+use pyo3::prelude::*;
+
+/// One validation finding.
+#[pyclass(name = "ValidationItem", module = "lib3mf", frozen, get_all)]
+pub struct PyValidationItem {
+    /// `"error"`, `"warning"` or `"info"`.
+    severity: &'static str,
+    /// Numeric code identifying the kind of finding.
+    code: u32,
+    /// Human-readable description.
+    message: String,
+    /// Suggested fix, if any.
+    suggestion: Option<String>,
+    /// Affected object or resource (e.g. `"Object 5"`), if known.
+    context: Option<String>,
+}
+
+#[pymethods]
+impl PyValidationItem {
+    fn __repr__(&self) -> String {
+        format!(
+            "<lib3mf.ValidationItem {} {}: {}>",
+            self.severity, self.code, self.message
+        )
+    }
+}
+
+/// Result of `Model.validate()`. Iterating yields `ValidationItem`s.
+#[pyclass(name = "ValidationReport", module = "lib3mf", frozen)]
+pub struct PyValidationReport {
+    items: Vec<Py<PyValidationItem>>,
+    errors: usize,
+}
+
+impl PyValidationReport {
+    pub(crate) fn new(py: Python<'_>, report: ValidationReport) -> PyResult<Self> {
+        let errors = report
+            .items
+            .iter()
+            .filter(|i| i.severity == ValidationSeverity::Error)
+            .count();
+        let items = report
+            .items
+            .into_iter()
+            .map(|item| {
+                let severity = match item.severity {
+                    ValidationSeverity::Error => "error",
+                    ValidationSeverity::Warning => "warning",
+                    ValidationSeverity::Info => "info",
+                };
+                Py::new(
+                    py,
+                    PyValidationItem {
+                        severity,
+                        code: item.code,
+                        message: item.message,
+                        suggestion: item.suggestion,
+                        context: item.context,
+                    },
+                )
+            })
+            .collect::<PyResult<_>>()?;
+        Ok(Self { items, errors })
+    }
+}
+
+#[pymethods]
+impl PyValidationReport {
+    /// All findings, in the order they were reported.
+    #[getter]
+    fn items(&self, py: Python<'_>) -> Vec<Py<PyValidationItem>> {
+        self.items.iter().map(|i| i.clone_ref(py)).collect()
+    }
+
+    /// True if any finding has error severity.
+    #[getter]
+    fn has_errors(&self) -> bool {
+        self.errors > 0
+    }
+
+    /// Number of error-severity findings.
+    #[getter]
+    fn error_count(&self) -> usize {
+        self.errors
+    }
+
+    fn __len__(&self) -> usize {
+        self.items.len()
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let items = pyo3::types::PyList::new(py, self.items(py))?;
+        Ok(items.try_iter()?.into_any().unbind())
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<lib3mf.ValidationReport items={} errors={}>",
+            self.items.len(),
+            self.errors
+        )
+    }
+}
+
+/// Totals from `Model.repair()`, summed over every mesh.
+#[pyclass(name = "RepairStats", module = "lib3mf", frozen, get_all)]
+#[derive(Default)]
+pub struct PyRepairStats {
+    /// Vertices removed (merged duplicates or unused).
+    vertices_removed: usize,
+    /// Triangles removed (degenerate, duplicates, or from islands).
+    triangles_removed: usize,
+    /// Triangles flipped for consistent orientation.
+    triangles_flipped: usize,
+    /// Triangles added by hole filling.
+    triangles_added: usize,
+}
+
+impl PyRepairStats {
+    pub(crate) fn add(&mut self, stats: &RepairStats) {
+        self.vertices_removed += stats.vertices_removed;
+        self.triangles_removed += stats.triangles_removed;
+        self.triangles_flipped += stats.triangles_flipped;
+        self.triangles_added += stats.triangles_added;
+    }
+}
+
+#[pymethods]
+impl PyRepairStats {
+    fn __repr__(&self) -> String {
+        format!(
+            "<lib3mf.RepairStats vertices_removed={} triangles_removed={} triangles_flipped={} triangles_added={}>",
+            self.vertices_removed,
+            self.triangles_removed,
+            self.triangles_flipped,
+            self.triangles_added
+        )
+    }
+}
@@ -0,0 +1,168 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::ffi::CString;
+
+/// Runs a Python snippet with `lib3mf` importable and `tmp` bound to a scratch directory.
+fn run(code: &str) {
+    let dir = std::env::temp_dir().join(format!("lib3mf-py-{}-{}", std::process::id(), code.len()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    Python::attach(|py| {
+        let modules = py.import("sys").unwrap().getattr("modules").unwrap();
+        if !modules.contains("lib3mf").unwrap() {
+            let module = pyo3::wrap_pymodule!(lib3mf::lib3mf)(py);
+            modules.set_item("lib3mf", module).unwrap();
+        }
+        let globals = PyDict::new(py);
+        globals.set_item("tmp", dir.to_str().unwrap()).unwrap();
+        let code = CString::new(format!("import lib3mf\n{code}")).unwrap();
+        if let Err(err) = py.run(&code, Some(&globals), None) {
+            err.display(py);
+            panic!("Python snippet failed: {err}");
+        }
+    });
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+const TETRAHEDRON: &str = r#"
+VERTICES = [(0, 0, 0), (10, 0, 0), (0, 10, 0), (0, 0, 10)]
+TRIANGLES = [(0, 2, 1), (0, 1, 3), (1, 2, 3), (0, 3, 2)]
+model = lib3mf.Model()
+oid = model.add_mesh(VERTICES, TRIANGLES, name="Tetra")
+"#;
+
+#[test]
+fn test_build_roundtrip_and_read_arrays() {
+    run(&format!(
+        "{TETRAHEDRON}{}",
+        r#"
+assert oid == 1
+assert model.build_item_count == 1
+loaded = lib3mf.Model.from_bytes(model.to_bytes())
+assert loaded.unit == "millimeter"
+[obj] = loaded.objects
+assert (obj.id, obj.name, obj.type, obj.kind) == (1, "Tetra", "model", "mesh")
+
+mesh = obj.mesh
+assert (mesh.vertex_count, mesh.triangle_count) == (4, 4)
+vertices = memoryview(mesh.vertices)
+assert vertices.format == "f" and vertices.shape == (4, 3) and vertices.readonly
+assert vertices.tolist()[1] == [10.0, 0.0, 0.0]
+triangles = memoryview(mesh.triangles)
+assert triangles.format == "I" and triangles.shape == (4, 3)
+assert [tuple(t) for t in triangles.tolist()] == TRIANGLES
+assert len(mesh.vertices) == 4
+
+area, volume = mesh.area_and_volume()
+assert abs(volume - 1000 / 6) < 1e-3, volume
+assert mesh.bounds() == ((0, 0, 0), (10, 10, 10))
+"#
+    ));
+}
+
+#[test]
+fn test_exported_vertices_block_mutation() {
+    run(&format!(
+        "{TETRAHEDRON}{}",
+        r#"
+view = memoryview(model.object_by_id(oid).mesh.vertices)
+try:
+    model.repair()
+    raise AssertionError("repair should fail while a view is alive")
+except BufferError:
+    pass
+try:
+    model.add_mesh(VERTICES, TRIANGLES)
+    raise AssertionError("add_mesh should fail while a view is alive")
+except BufferError:
+    pass
+assert view[3, 2] == 10.0
+view.release()
+model.repair()
+assert model.add_mesh(VERTICES, TRIANGLES, build=False) == 2
+assert model.build_item_count == 1
+"#
+    ));
+}
+
+#[test]
+fn test_validate_and_repair() {
+    run(r#"
+model = lib3mf.Model()
+# Two copies of the same triangle plus a degenerate one.
+model.add_mesh([(0, 0, 0), (1, 0, 0), (0, 1, 0)], [(0, 1, 2), (0, 1, 2), (0, 0, 1)])
+
+report = model.validate("paranoid")
+assert any("degenerate" in item.message for item in report)
+assert report.has_errors == (report.error_count > 0)
+assert len(report) == len(report.items) == len(list(report))
+assert all(item.severity in ("error", "warning", "info") for item in report)
+assert all(item.message for item in report)
+
+stats = model.repair()
+assert stats.triangles_removed == 2, stats
+assert model.objects[0].mesh.triangle_count == 1
+
+try:
+    model.validate("pedantic")
+    raise AssertionError("unknown level should fail")
+except ValueError:
+    pass
+"#);
+}
+
+#[test]
+fn test_converters_and_files() {
+    run(&format!(
+        "{TETRAHEDRON}{}",
+        r#"
+import os
+path_3mf = os.path.join(tmp, "tetra.3mf")
+model.write(path_3mf)
+assert lib3mf.Model.open(path_3mf).objects[0].name == "Tetra"
+
+for ascii in (False, True):
+    path = os.path.join(tmp, f"tetra-{ascii}.stl")
+    model.export_stl(path, ascii=ascii)
+    stl = lib3mf.import_stl(path)
+    assert sum(o.mesh.triangle_count for o in stl.objects) == 4
+
+path_obj = os.path.join(tmp, "tetra.obj")
+model.export_obj(path_obj)
+obj = lib3mf.import_obj(path_obj)
+assert sum(o.mesh.vertex_count for o in obj.objects if o.mesh) == 4
+"#
+    ));
+}
+
+#[test]
+fn test_benchy_and_errors() {
+    run(r#"
+model = lib3mf.Model.open("../../models/Benchy.3mf")
+assert len(model.objects) >= 1
+assert "objects=" in repr(model)
+components = [o for o in model.objects if o.kind == "components"]
+assert components and components[0].mesh is None
+
+try:
+    lib3mf.Model.open("/nonexistent/model.3mf")
+    raise AssertionError("missing file should fail")
+except OSError:
+    pass
+
+try:
+    model.object_by_id(123456)
+    raise AssertionError("unknown ID should fail")
+except KeyError:
+    pass
+
+try:
+    lib3mf.Model().add_mesh([(0, 0, 0)], [(0, 1, 2)])
+    raise AssertionError("out-of-range index should fail")
+except ValueError:
+    pass
+
+assert issubclass(lib3mf.Lib3mfError, Exception)
+assert lib3mf.__version__
+"#);
+}
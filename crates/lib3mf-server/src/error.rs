@@ -0,0 +1,65 @@
+//! JSON error responses.
+
+use axum::Json;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use lib3mf_core::error::Lib3mfError;
+use serde_json::json;
+
+/// An error returned to the client as `{"error": "<message>"}` with an HTTP status.
+#[derive(Debug)]
+pub struct ApiError {
+    /// HTTP status code of the response.
+    pub status: StatusCode,
+    /// Human-readable description of what went wrong.
+    pub message: String,
+}
+
+impl ApiError {
+    /// Creates an error with the given status and message.
+    pub fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    /// `400 Bad Request`, for malformed query parameters.
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, message)
+    }
+
+    /// `413 Payload Too Large`, for uploads or archive parts over the configured limits.
+    pub fn too_large(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::PAYLOAD_TOO_LARGE, message)
+    }
+
+    /// `500 Internal Server Error`, for failures on the server side (temp files, tasks).
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
+
+impl From<Lib3mfError> for ApiError {
+    /// Maps library errors to statuses. Anything wrong with the uploaded file itself is
+    /// `422 Unprocessable Entity`; decompressed parts and mesh data over the limits are `413`.
+    fn from(err: Lib3mfError) -> Self {
+        let status = match &err {
+            Lib3mfError::Io(e) if e.kind() == std::io::ErrorKind::FileTooLarge => {
+                StatusCode::PAYLOAD_TOO_LARGE
+            }
+            Lib3mfError::FeatureNotEnabled(..) => StatusCode::NOT_IMPLEMENTED,
+            _ => StatusCode::UNPROCESSABLE_ENTITY,
+        };
+        Self::new(status, err.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(json!({ "error": self.message }))).into_response()
+    }
+}
+
+/// Result type for request handlers.
+pub type ApiResult<T> = Result<T, ApiError>;
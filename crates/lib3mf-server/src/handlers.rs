@@ -0,0 +1,207 @@
+//! Request handlers.
+
+use crate::ServerConfig;
+use crate::error::{ApiError, ApiResult};
+use crate::upload::Upload;
+use axum::Json;
+use axum::body::Body;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, HeaderValue, header};
+use axum::response::{IntoResponse, Response};
+use lib3mf_converters::obj::ObjExporter;
+use lib3mf_converters::stl::{StlExportOptions, StlExporter, StlFormat, StlImporter};
+use lib3mf_core::model::Model;
+use lib3mf_core::model::resolver::PartResolver;
+use lib3mf_core::validation::{ValidationLevel, ValidationSeverity};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::io::{BufReader, Cursor};
+use std::sync::Arc;
+
+type AppState = State<Arc<ServerConfig>>;
+
+/// Runs CPU-bound work on the blocking thread pool.
+async fn blocking<T: Send + 'static>(
+    work: impl FnOnce() -> ApiResult<T> + Send + 'static,
+) -> ApiResult<T> {
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| ApiError::internal(format!("Worker task failed: {e}")))?
+}
+
+/// Loads the root model of an uploaded 3MF package.
+async fn load_3mf(upload: &Upload, config: &ServerConfig) -> ApiResult<Model> {
+    if !upload.is_zip {
+        return Err(ApiError::new(
+            axum::http::StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "Expected a 3MF package (ZIP archive)",
+        ));
+    }
+    Ok(lib3mf_async::loader::load_model_async_with_budget(
+        upload.path(),
+        config.max_part_bytes,
+        config.max_mesh_bytes,
+    )
+    .await?)
+}
+
+fn parse_level(level: &str) -> ApiResult<ValidationLevel> {
+    match level.to_lowercase().as_str() {
+        "minimal" => Ok(ValidationLevel::Minimal),
+        "standard" => Ok(ValidationLevel::Standard),
+        "strict" => Ok(ValidationLevel::Strict),
+        "paranoid" => Ok(ValidationLevel::Paranoid),
+        other => Err(ApiError::bad_request(format!(
+            "Unknown validation level '{other}'; expected minimal, standard, strict or paranoid"
+        ))),
+    }
+}
+
+/// `GET /health`
+pub(crate) async fn health() -> Json<Value> {
+    Json(json!({ "status": "ok", "version": env!("CARGO_PKG_VERSION") }))
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ValidateParams {
+    level: Option<String>,
+}
+
+/// `POST /validate?level=<level>`
+pub(crate) async fn validate(
+    State(config): AppState,
+    Query(params): Query<ValidateParams>,
+    headers: HeaderMap,
+    body: Body,
+) -> ApiResult<Json<Value>> {
+    let level_name = params.level.as_deref().unwrap_or("standard").to_lowercase();
+    let level = parse_level(&level_name)?;
+    let upload = Upload::receive(&headers, body, config.max_upload_bytes).await?;
+    let model = load_3mf(&upload, &config).await?;
+
+    let report = blocking(move || Ok(model.validate(level))).await?;
+    let count = |severity| {
+        report
+            .items
+            .iter()
+            .filter(|i| i.severity == severity)
+            .count()
+    };
+    let errors = count(ValidationSeverity::Error);
+    let warnings = count(ValidationSeverity::Warning);
+    Ok(Json(json!({
+        "valid": errors == 0,
+        "level": level_name,
+        "errors": errors,
+        "warnings": warnings,
+        "items": report.items,
+    })))
+}
+
+/// `POST /stats`
+pub(crate) async fn stats(
+    State(config): AppState,
+    headers: HeaderMap,
+    body: Body,
+) -> ApiResult<Json<Value>> {
+    let upload = Upload::receive(&headers, body, config.max_upload_bytes).await?;
+    let model = load_3mf(&upload, &config).await?;
+
+    let max_part_bytes = config.max_part_bytes;
+    let stats = blocking(move || {
+        let mut archive = upload.open_archive(max_part_bytes)?;
+        Ok(model.compute_stats(&mut archive)?)
+    })
+    .await?;
+    serde_json::to_value(stats)
+        .map(Json)
+        .map_err(|e| ApiError::internal(format!("Failed to encode statistics: {e}")))
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ConvertParams {
+    to: Option<String>,
+    #[serde(default)]
+    ascii: bool,
+}
+
+/// `POST /convert?to=<stl|obj|json|3mf>[&ascii=true]`
+///
+/// Accepts a 3MF package, or an STL file (binary or ASCII) for conversion to 3MF or JSON.
+pub(crate) async fn convert(
+    State(config): AppState,
+    Query(params): Query<ConvertParams>,
+    headers: HeaderMap,
+    body: Body,
+) -> ApiResult<Response> {
+    let target = params
+        .to
+        .ok_or_else(|| ApiError::bad_request("Missing 'to' parameter (stl, obj, json or 3mf)"))?
+        .to_lowercase();
+    let content_type = match target.as_str() {
+        "stl" => "model/stl",
+        "obj" => "model/obj",
+        "json" => "application/json",
+        "3mf" => "model/3mf",
+        other => {
+            return Err(ApiError::bad_request(format!(
+                "Unsupported target format '{other}'; expected stl, obj, json or 3mf"
+            )));
+        }
+    };
+
+    let upload = Upload::receive(&headers, body, config.max_upload_bytes).await?;
+    let model = if upload.is_zip {
+        load_3mf(&upload, &config).await?
+    } else {
+        let file = upload
+            .reopen()
+            .map_err(|e| ApiError::internal(format!("Failed to reopen upload: {e}")))?;
+        blocking(move || Ok(StlImporter::read(BufReader::new(file))?)).await?
+    };
+
+    let stl = StlExporter::new(StlExportOptions {
+        format: if params.ascii {
+            StlFormat::Ascii
+        } else {
+            StlFormat::Binary
+        },
+    });
+    let max_part_bytes = config.max_part_bytes;
+    let target_ext = target.clone();
+    let output = blocking(move || {
+        let mut out = Vec::new();
+        match target_ext.as_str() {
+            // Mesh exports from a package resolve components across model parts.
+            "stl" | "obj" if upload.is_zip => {
+                let mut archive = upload.open_archive(max_part_bytes)?;
+                let root = model.clone();
+                let resolver = PartResolver::new(&mut archive, model);
+                if target_ext == "obj" {
+                    ObjExporter::write_with_resolver(&root, resolver, &mut out)?;
+                } else {
+                    stl.write_with_resolver(&root, resolver, &mut out)?;
+                }
+            }
+            "stl" => stl.write(&model, &mut out)?,
+            "obj" => ObjExporter::write(&model, &mut out)?,
+            "json" => model.write_json(&mut out)?,
+            _ => {
+                let mut cursor = Cursor::new(out);
+                model.write(&mut cursor)?;
+                out = cursor.into_inner();
+            }
+        }
+        Ok(out)
+    })
+    .await?;
+
+    let disposition = format!("attachment; filename=\"model.{target}\"");
+    let mut response = output.into_response();
+    let headers = response.headers_mut();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    if let Ok(value) = HeaderValue::from_str(&disposition) {
+        headers.insert(header::CONTENT_DISPOSITION, value);
+    }
+    Ok(response)
+}
@@ -0,0 +1,116 @@
+//! # lib3mf-server
+//!
+//! A small HTTP service for validating, inspecting and converting 3MF files, built on
+//! [axum](https://docs.rs/axum), tokio and [`lib3mf_async`].
+//!
+//! ## Endpoints
+//!
+//! | Method | Path | Body | Response |
+//! |--------|------|------|----------|
+//! | `GET` | `/health` | — | `{"status": "ok", "version": "..."}` |
+//! | `POST` | `/validate?level=standard` | 3MF package | Validation summary and findings as JSON |
+//! | `POST` | `/stats` | 3MF package | `ModelStats` as JSON |
+//! | `POST` | `/convert?to=stl` | 3MF package or STL | Converted file (`stl`, `obj`, `json` or `3mf`; add `ascii=true` for ASCII STL) |
+//!
+//! Errors are returned as `{"error": "<message>"}` with `400` for bad parameters, `413`
+//! for uploads, archive parts or mesh data over the limits, `415` for non-3MF uploads where a package
+//! is required, `422` for files that cannot be parsed, and `408` when a request times out.
+//!
+//! ## Limits
+//!
+//! Uploads are streamed to a temporary file rather than buffered in memory, and rejected
+//! once they pass [`ServerConfig::max_upload_bytes`]. Every archive part is decompressed
+//! with a cap of [`ServerConfig::max_part_bytes`], so a small ZIP cannot expand without
+//! bound, and the parsed mesh data of a model may take at most
+//! [`ServerConfig::max_mesh_bytes`] of memory. A global concurrency limit and a per-request timeout protect the CPU-bound
+//! parsing and validation work, which runs on tokio's blocking pool. A timed-out request
+//! gets its `408` immediately, but work already on the blocking pool runs to completion.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use lib3mf_server::{ServerConfig, router};
+//!
+//! #[tokio::main]
+//! async fn main() -> std::io::Result<()> {
+//!     let listener = tokio::net::TcpListener::bind("127.0.0.1:8080").await?;
+//!     axum::serve(listener, router(ServerConfig::default())).await
+//! }
+//! ```
+//!
+//! ```bash
+//! curl --data-binary @model.3mf 'http://127.0.0.1:8080/validate?level=strict'
+//! curl --data-binary @model.3mf -o model.stl 'http://127.0.0.1:8080/convert?to=stl'
+//! ```
+
+use axum::Router;
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use std::sync::Arc;
+use std::time::Duration;
+use tower::ServiceBuilder;
+use tower::limit::GlobalConcurrencyLimitLayer;
+use tower_http::timeout::TimeoutLayer;
+use tower_http::trace::TraceLayer;
+
+mod error;
+mod handlers;
+mod upload;
+
+pub use error::{ApiError, ApiResult};
+
+/// Limits applied to every request.
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// Largest accepted request body, in bytes. Default: 64 MiB.
+    pub max_upload_bytes: u64,
+    /// Largest size any archive part may decompress to, in bytes. Default: 512 MiB.
+    pub max_part_bytes: u64,
+    /// Largest amount of memory the meshes of an uploaded model may take once parsed, in
+    /// bytes. Default: 1 GiB.
+    pub max_mesh_bytes: u64,
+    /// Time allowed for a whole request, including the upload. Default: 60 seconds.
+    pub request_timeout: Duration,
+    /// Requests processed at once; further requests wait for a slot. Default: the number
+    /// of CPUs.
+    pub max_concurrent_requests: usize,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            max_upload_bytes: 64 << 20,
+            max_part_bytes: 512 << 20,
+            max_mesh_bytes: 1 << 30,
+            request_timeout: Duration::from_secs(60),
+            max_concurrent_requests: std::thread::available_parallelism().map_or(4, |n| n.get()),
+        }
+    }
+}
+
+/// Builds the service's router.
+///
+/// `/health` bypasses the concurrency limit and timeout so load balancers can probe a busy
+/// server.
+pub fn router(config: ServerConfig) -> Router {
+    let limits = ServiceBuilder::new()
+        .layer(TimeoutLayer::with_status_code(
+            StatusCode::REQUEST_TIMEOUT,
+            config.request_timeout,
+        ))
+        .layer(GlobalConcurrencyLimitLayer::new(
+            config.max_concurrent_requests.max(1),
+        ));
+
+    let api = Router::new()
+        .route("/validate", post(handlers::validate))
+        .route("/stats", post(handlers::stats))
+        .route("/convert", post(handlers::convert))
+        .layer(limits)
+        .with_state(Arc::new(config));
+
+    Router::new()
+        .route("/health", get(handlers::health))
+        .merge(api)
+        .layer(TraceLayer::new_for_http())
+}
@@ -0,0 +1,62 @@
+use clap::Parser;
+use lib3mf_server::{ServerConfig, router};
+use std::net::SocketAddr;
+use std::time::Duration;
+use tracing_subscriber::EnvFilter;
+
+/// HTTP service for validating, inspecting and converting 3MF files.
+///
+/// Endpoints: GET /health, POST /validate?level=<level>, POST /stats,
+/// POST /convert?to=<stl|obj|json|3mf>. Set RUST_LOG to control request logging.
+#[derive(Parser)]
+#[command(name = "lib3mf-server", version)]
+struct Args {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    listen: SocketAddr,
+    /// Largest accepted upload, in MiB
+    #[arg(long, default_value_t = 64)]
+    max_upload_mb: u64,
+    /// Largest size an archive part may decompress to, in MiB
+    #[arg(long, default_value_t = 512)]
+    max_part_mb: u64,
+    /// Largest amount of memory a model's parsed meshes may take, in MiB
+    #[arg(long, default_value_t = 1024)]
+    max_mesh_mb: u64,
+    /// Seconds allowed per request, including the upload
+    #[arg(long, default_value_t = 60)]
+    timeout_secs: u64,
+    /// Requests processed at once (defaults to the number of CPUs)
+    #[arg(long)]
+    max_concurrent: Option<usize>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+        )
+        .init();
+
+    let args = Args::parse();
+    let defaults = ServerConfig::default();
+    let config = ServerConfig {
+        max_upload_bytes: args.max_upload_mb << 20,
+        max_part_bytes: args.max_part_mb << 20,
+        max_mesh_bytes: args.max_mesh_mb << 20,
+        request_timeout: Duration::from_secs(args.timeout_secs),
+        max_concurrent_requests: args
+            .max_concurrent
+            .unwrap_or(defaults.max_concurrent_requests),
+    };
+
+    let listener = tokio::net::TcpListener::bind(args.listen).await?;
+    tracing::info!("lib3mf-server listening on {}", listener.local_addr()?);
+    axum::serve(listener, router(config))
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,136 @@
+//! Streaming request bodies to disk, and bounded archive access for uploaded files.
+
+use crate::error::{ApiError, ApiResult};
+use axum::body::Body;
+use axum::http::{HeaderMap, header};
+use futures_util::StreamExt;
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver};
+use lib3mf_core::error::{Lib3mfError, Result};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use tempfile::NamedTempFile;
+use tokio::io::AsyncWriteExt;
+
+/// An uploaded file, spooled to a temporary file that is deleted on drop.
+pub(crate) struct Upload {
+    file: NamedTempFile,
+    /// Whether the upload starts with the ZIP local file header (`PK\x03\x04`).
+    pub is_zip: bool,
+}
+
+impl Upload {
+    /// Streams `body` into a temporary file, failing with `413` once it passes `max_bytes`.
+    ///
+    /// The body is never held in memory as a whole, and a `Content-Length` over the limit
+    /// is rejected before any of it is read.
+    pub async fn receive(headers: &HeaderMap, body: Body, max_bytes: u64) -> ApiResult<Self> {
+        let too_large =
+            || ApiError::too_large(format!("Upload exceeds the {max_bytes} byte limit"));
+        let declared = headers
+            .get(header::CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok());
+        if declared.is_some_and(|len| len > max_bytes) {
+            return Err(too_large());
+        }
+
+        let file = NamedTempFile::new()
+            .map_err(|e| ApiError::internal(format!("Failed to create temporary file: {e}")))?;
+        let write_error =
+            |e: std::io::Error| ApiError::internal(format!("Failed to spool upload: {e}"));
+        let mut out = tokio::fs::File::from_std(file.reopen().map_err(write_error)?);
+
+        let mut stream = body.into_data_stream();
+        let mut received = 0u64;
+        let mut head: Vec<u8> = Vec::with_capacity(4);
+        while let Some(chunk) = stream.next().await {
+            let chunk =
+                chunk.map_err(|e| ApiError::bad_request(format!("Failed to read upload: {e}")))?;
+            received += chunk.len() as u64;
+            if received > max_bytes {
+                return Err(too_large());
+            }
+            if head.len() < 4 {
+                head.extend(chunk.iter().take(4 - head.len()));
+            }
+            out.write_all(&chunk).await.map_err(write_error)?;
+        }
+        out.flush().await.map_err(write_error)?;
+
+        if received == 0 {
+            return Err(ApiError::bad_request("Request body is empty"));
+        }
+        Ok(Self {
+            file,
+            is_zip: head == b"PK\x03\x04",
+        })
+    }
+
+    /// Path of the spooled file.
+    pub fn path(&self) -> &Path {
+        self.file.path()
+    }
+
+    /// Opens the upload as a ZIP archive whose entries are capped at `max_part_bytes`.
+    pub fn open_archive(&self, max_part_bytes: u64) -> Result<BoundedArchive> {
+        Ok(BoundedArchive {
+            inner: ZipArchiver::new(self.file.reopen()?)?,
+            max_part_bytes,
+        })
+    }
+
+    /// Reopens the spooled file for reading from the start.
+    pub fn reopen(&self) -> std::io::Result<std::fs::File> {
+        self.file.reopen()
+    }
+}
+
+/// An [`ArchiveReader`] over an uploaded ZIP that refuses entries decompressing past a limit.
+///
+/// Used wherever the core library reads further parts (statistics, component resolution),
+/// so a ZIP bomb in a secondary part is caught just like one in the root model.
+pub(crate) struct BoundedArchive {
+    inner: ZipArchiver<std::fs::File>,
+    max_part_bytes: u64,
+}
+
+impl ArchiveReader for BoundedArchive {
+    fn read_entry(&mut self, name: &str) -> Result<Vec<u8>> {
+        let max = self.max_part_bytes;
+        let mut data = Vec::new();
+        // One byte past the limit tells a full entry apart from an oversized one.
+        self.inner
+            .entry_reader(name)?
+            .take(max.saturating_add(1))
+            .read_to_end(&mut data)?;
+        if data.len() as u64 > max {
+            return Err(Lib3mfError::Io(std::io::Error::new(
+                std::io::ErrorKind::FileTooLarge,
+                format!("Entry '{name}' decompresses to more than {max} bytes"),
+            )));
+        }
+        Ok(data)
+    }
+
+    fn entry_exists(&mut self, name: &str) -> bool {
+        self.inner.entry_exists(name)
+    }
+
+    fn list_entries(&mut self) -> Result<Vec<String>> {
+        self.inner.list_entries()
+    }
+}
+
+// `ArchiveReader` requires `Read + Seek`; like `ZipArchiver`, the archive as a whole is
+// never read as a stream, so these are no-ops.
+impl Read for BoundedArchive {
+    fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+        Ok(0)
+    }
+}
+
+impl Seek for BoundedArchive {
+    fn seek(&mut self, _pos: SeekFrom) -> std::io::Result<u64> {
+        Ok(0)
+    }
+}
@@ -0,0 +1,197 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use http_body_util::BodyExt;
+use lib3mf_core::parser::parse_model;
+use lib3mf_server::{ServerConfig, router};
+use serde_json::Value;
+use std::io::Cursor;
+use tower::ServiceExt;
+
+const BENCHY: &str = "../../models/Benchy.3mf";
+const BENCHY_STL: &str = "../../models/Benchy.stl";
+
+async fn post(config: ServerConfig, uri: &str, body: Vec<u8>) -> (StatusCode, Vec<u8>) {
+    let request = Request::post(uri).body(Body::from(body)).unwrap();
+    let response = router(config).oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, bytes.to_vec())
+}
+
+fn json(bytes: &[u8]) -> Value {
+    serde_json::from_slice(bytes).expect("response must be JSON")
+}
+
+#[tokio::test]
+async fn test_health() {
+    let request = Request::get("/health").body(Body::empty()).unwrap();
+    let response = router(ServerConfig::default())
+        .oneshot(request)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(json(&body)["status"], "ok");
+}
+
+#[tokio::test]
+async fn test_validate_benchy() {
+    let data = std::fs::read(BENCHY).unwrap();
+    let (status, body) = post(ServerConfig::default(), "/validate?level=strict", data).await;
+    assert_eq!(status, StatusCode::OK, "{}", String::from_utf8_lossy(&body));
+
+    let report = json(&body);
+    assert_eq!(report["level"], "strict");
+    assert!(report["valid"].is_boolean());
+    assert_eq!(
+        report["valid"].as_bool().unwrap(),
+        report["errors"].as_u64() == Some(0)
+    );
+    assert!(report["items"].is_array());
+}
+
+#[tokio::test]
+async fn test_stats_benchy() {
+    let data = std::fs::read(BENCHY).unwrap();
+    let (status, body) = post(ServerConfig::default(), "/stats", data).await;
+    assert_eq!(status, StatusCode::OK, "{}", String::from_utf8_lossy(&body));
+    let stats = json(&body);
+    assert!(stats["geometry"]["triangle_count"].as_u64().unwrap() > 0);
+}
+
+#[tokio::test]
+async fn test_convert_benchy_to_stl_resolves_components() {
+    let data = std::fs::read(BENCHY).unwrap();
+    let request = Request::post("/convert?to=stl")
+        .body(Body::from(data))
+        .unwrap();
+    let response = router(ServerConfig::default())
+        .oneshot(request)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "model/stl");
+    let stl = response.into_body().collect().await.unwrap().to_bytes();
+    // Binary STL: 80-byte header, triangle count, 50 bytes per triangle.
+    let triangles = u32::from_le_bytes(stl[80..84].try_into().unwrap());
+    assert!(triangles > 0);
+    assert_eq!(stl.len(), 84 + 50 * triangles as usize);
+}
+
+#[tokio::test]
+async fn test_convert_stl_to_3mf_and_json() {
+    let data = std::fs::read(BENCHY_STL).unwrap();
+    let (status, package) = post(ServerConfig::default(), "/convert?to=3mf", data.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(&package[..4], b"PK\x03\x04");
+
+    let (status, body) = post(ServerConfig::default(), "/validate", package).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json(&body)["level"], "standard");
+
+    let (status, body) = post(ServerConfig::default(), "/convert?to=json", data).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json(&body)["schema"], "lib3mf-model");
+}
+
+/// A package whose root model holds one mesh of `triangles` disjoint triangles.
+fn mesh_package(triangles: u32) -> Vec<u8> {
+    let mut vertices = String::new();
+    let mut tris = String::new();
+    for i in 0..triangles {
+        vertices.push_str(&format!(
+            r#"<vertex x="{i}" y="0" z="0" /><vertex x="{i}.5" y="0" z="0" /><vertex x="{i}" y="1" z="0" />"#
+        ));
+        let v = i * 3;
+        tris.push_str(&format!(
+            r#"<triangle v1="{v}" v2="{}" v3="{}" />"#,
+            v + 1,
+            v + 2
+        ));
+    }
+    let xml = format!(
+        r#"<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+    <resources>
+        <object id="1"><mesh><vertices>{vertices}</vertices><triangles>{tris}</triangles></mesh></object>
+    </resources>
+    <build><item objectid="1" /></build>
+</model>"#
+    );
+    let model = parse_model(Cursor::new(xml)).unwrap();
+    let mut package = Cursor::new(Vec::new());
+    model.write(&mut package).unwrap();
+    package.into_inner()
+}
+
+#[tokio::test]
+async fn test_mesh_budget() {
+    let package = mesh_package(20_000);
+
+    let small_meshes = ServerConfig {
+        max_mesh_bytes: 1 << 20,
+        ..Default::default()
+    };
+    let (status, body) = post(small_meshes, "/stats", package.clone()).await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert!(
+        json(&body)["error"]
+            .as_str()
+            .unwrap()
+            .contains("memory budget of 1048576 bytes")
+    );
+
+    let (status, body) = post(ServerConfig::default(), "/stats", package).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json(&body)["geometry"]["triangle_count"], 20_000);
+}
+
+#[tokio::test]
+async fn test_limits_and_bad_requests() {
+    let data = std::fs::read(BENCHY).unwrap();
+
+    let small_upload = ServerConfig {
+        max_upload_bytes: 1024,
+        ..Default::default()
+    };
+    let (status, body) = post(small_upload, "/validate", data.clone()).await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert!(json(&body)["error"].as_str().unwrap().contains("1024"));
+
+    let small_parts = ServerConfig {
+        max_part_bytes: 64,
+        ..Default::default()
+    };
+    let (status, _) = post(small_parts, "/validate", data.clone()).await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+
+    let (status, body) = post(
+        ServerConfig::default(),
+        "/validate?level=pedantic",
+        data.clone(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(json(&body)["error"].as_str().unwrap().contains("pedantic"));
+
+    let (status, _) = post(ServerConfig::default(), "/convert?to=step", data.clone()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = post(ServerConfig::default(), "/convert", data).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = post(
+        ServerConfig::default(),
+        "/stats",
+        b"solid x\nendsolid x\n".to_vec(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    let mut broken = b"PK\x03\x04".to_vec();
+    broken.extend_from_slice(&[0; 64]);
+    let (status, body) = post(ServerConfig::default(), "/validate", broken).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(json(&body)["error"].is_string());
+
+    let (status, _) = post(ServerConfig::default(), "/validate", Vec::new()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
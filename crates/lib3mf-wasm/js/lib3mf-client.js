@@ -0,0 +1,85 @@
+// Main-thread side of the lib3mf-wasm worker wrapper.
+//
+//     import { Lib3mfWorker } from './lib3mf-client.js';
+//
+//     const lib3mf = new Lib3mfWorker();
+//     const model = await lib3mf.parse(await file.arrayBuffer());
+//     console.log(`${model.objectCount} objects in ${model.unit}`);
+//     const bytes = await lib3mf.toArrayBuffer(model.handle);
+//     await lib3mf.free(model.handle);
+//
+// Parsing runs in lib3mf-worker.js, so large files don't block the page. Buffers passed
+// to `parse` are transferred to the worker and become detached (zero length) here; pass
+// `buffer.slice(0)` to keep a copy.
+
+export class Lib3mfWorker {
+    /**
+     * Starts the worker. `url` defaults to lib3mf-worker.js next to this module.
+     */
+    constructor(url = new URL('./lib3mf-worker.js', import.meta.url)) {
+        this.worker = new Worker(url, { type: 'module' });
+        this.pending = new Map();
+        this.nextId = 1;
+        this.worker.onmessage = (event) => {
+            const { id, result, error } = event.data;
+            const request = this.pending.get(id);
+            if (!request) {
+                return;
+            }
+            this.pending.delete(id);
+            if (error !== undefined) {
+                request.reject(new Error(error));
+            } else {
+                request.resolve(result);
+            }
+        };
+    }
+
+    /**
+     * Parses a .3mf package. Resolves to `{ handle, unit, objectCount }`.
+     */
+    parse(buffer) {
+        return this.call({ op: 'parse', buffer }, [buffer]);
+    }
+
+    /**
+     * Resolves to `{ handle, unit, objectCount }` for a parsed model.
+     */
+    summary(handle) {
+        return this.call({ op: 'summary', handle });
+    }
+
+    /**
+     * Writes a parsed model as a .3mf package. Resolves to a transferred ArrayBuffer.
+     * Rejects with a viewer build, which cannot write packages.
+     */
+    toArrayBuffer(handle) {
+        return this.call({ op: 'toArrayBuffer', handle });
+    }
+
+    /**
+     * Releases a parsed model in the worker.
+     */
+    free(handle) {
+        return this.call({ op: 'free', handle });
+    }
+
+    /**
+     * Stops the worker. Pending requests are rejected.
+     */
+    terminate() {
+        this.worker.terminate();
+        for (const request of this.pending.values()) {
+            request.reject(new Error('Worker terminated'));
+        }
+        this.pending.clear();
+    }
+
+    call(message, transfer = []) {
+        const id = this.nextId++;
+        return new Promise((resolve, reject) => {
+            this.pending.set(id, { resolve, reject });
+            this.worker.postMessage({ id, ...message }, transfer);
+        });
+    }
+}
@@ -0,0 +1,63 @@
+// Worker side of the lib3mf-wasm worker wrapper.
+//
+// Runs as a module worker started by `Lib3mfWorker` (lib3mf-client.js). Parsed models stay
+// in the worker and are referred to by numeric handles; package bytes move between the
+// threads as transferred ArrayBuffers, so they are never copied.
+//
+// Expects the wasm-pack output (`wasm-pack build --target web`) in ../pkg. A viewer build
+// (`--no-default-features`) works too, except for `toArrayBuffer`.
+
+import init, { WasmModel } from '../pkg/lib3mf_wasm.js';
+
+const ready = init();
+const models = new Map();
+let nextHandle = 1;
+
+function model(handle) {
+    const found = models.get(handle);
+    if (!found) {
+        throw new Error(`No model with handle ${handle}`);
+    }
+    return found;
+}
+
+function summary(handle, parsed) {
+    return { handle, unit: parsed.unit(), objectCount: parsed.object_count() };
+}
+
+self.onmessage = async (event) => {
+    const { id, op, buffer, handle } = event.data;
+    try {
+        await ready;
+        switch (op) {
+            case 'parse': {
+                const parsed = await WasmModel.from_bytes_async(new Uint8Array(buffer));
+                const newHandle = nextHandle++;
+                models.set(newHandle, parsed);
+                self.postMessage({ id, result: summary(newHandle, parsed) });
+                break;
+            }
+            case 'summary':
+                self.postMessage({ id, result: summary(handle, model(handle)) });
+                break;
+            case 'toArrayBuffer': {
+                const parsed = model(handle);
+                if (typeof parsed.to_array_buffer !== 'function') {
+                    throw new Error('This build of lib3mf-wasm cannot write packages (viewer profile)');
+                }
+                const bytes = parsed.to_array_buffer();
+                self.postMessage({ id, result: bytes }, [bytes]);
+                break;
+            }
+            case 'free':
+                model(handle).free();
+                models.delete(handle);
+                self.postMessage({ id, result: null });
+                break;
+            default:
+                throw new Error(`Unknown operation '${op}'`);
+        }
+    } catch (error) {
+        self.postMessage({ id, error: String(error) });
+    }
+};
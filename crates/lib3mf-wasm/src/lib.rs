@@ -52,35 +52,102 @@
 //! });
 //! ```
 //!
+//! ## Large Files and Web Workers
+//!
+//! Parsing a large file with [`WasmModel::from_bytes()`] blocks the thread it runs on until it
+//! is done. Two ways to keep a page responsive:
+//!
+//! - [`WasmModel::from_bytes_async()`] yields to the event loop between reading the archive,
+//!   decompressing the model part and parsing it, so the page can repaint in between.
+//! - The worker wrapper in `js/` runs parsing in a module worker. `lib3mf-client.js` exports
+//!   `Lib3mfWorker`, whose `parse(buffer)` transfers the `ArrayBuffer` to the worker rather
+//!   than copying it, and `toArrayBuffer(handle)` transfers the written package back.
+//!
+//! ```javascript
+//! import { Lib3mfWorker } from './js/lib3mf-client.js';
+//!
+//! const lib3mf = new Lib3mfWorker();
+//! const model = await lib3mf.parse(await file.arrayBuffer());
+//! console.log(`${model.objectCount} objects`);
+//! ```
+//!
+//! ## Bundle Size
+//!
+//! Three Cargo features of this crate pull in code a read-only viewer does not need:
+//!
+//! - `writer`: package writing, for [`WasmModel::to_array_buffer()`]
+//! - `vendor`: Bambu Studio project data parsing
+//! - `converters`: STL and OBJ import and export, through [`lib3mf_converters`]
+//!
+//! All are on by default. Building without them gives the "viewer" profile, which can parse
+//! and inspect models but not write or convert them:
+//!
+//! ```bash
+//! wasm-pack build crates/lib3mf-wasm --release --target web -- --no-default-features
+//! ```
+//!
+//! Digital signatures and encryption are never compiled into this crate. [`build_profile()`]
+//! and [`capabilities()`] tell JavaScript which build it loaded.
+//!
 //! ## Module Structure
 //!
 //! This crate exposes a single primary API surface:
 //!
 //! - [`WasmModel`]: The main wrapper around [`lib3mf_core::Model`], providing JavaScript-accessible methods
-//!   for parsing 3MF files and accessing model data.
+//!   for parsing and writing 3MF files and accessing model data.
 //! - [`set_panic_hook()`]: Optional panic handler for better error messages in browser console.
+//! - [`build_profile()`] and [`capabilities()`]: Which optional features this build includes.
 //!
 //! ## Current Limitations
 //!
 //! This is an early-stage binding layer with limited API surface. Currently supported:
 //!
-//! - Parsing 3MF files from byte arrays
+//! - Parsing 3MF files from byte arrays or `ArrayBuffer`s, synchronously or asynchronously
 //! - Accessing basic model metadata (unit, object count)
+//! - Writing the parsed model back to a 3MF package (`writer` feature)
+//! - Converting between 3MF and STL or OBJ (`converters` feature)
 //!
 //! **Not yet exposed:**
 //!
 //! - Validation
 //! - Geometry access (vertices, triangles)
-//! - Materials and textures
-//! - Writing/serialization
+//! - Materials and textures (attachments are not loaded, so they are not written either)
 //!
 //! For the full Rust API, see [`lib3mf_core`].
 
+use js_sys::{ArrayBuffer, Promise, Uint8Array};
+#[cfg(feature = "converters")]
+use lib3mf_converters::obj::{ObjExporter, ObjImporter};
+#[cfg(feature = "converters")]
+use lib3mf_converters::stl::{StlExporter, StlImporter};
+use lib3mf_core::archive::{ArchiveReader, ZipArchiver, find_model_path};
+use lib3mf_core::parser::parse_model;
+use std::io::Cursor;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
 
 #[wasm_bindgen]
 extern "C" {
     fn alert(s: &str);
+
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(handler: &js_sys::Function, timeout: i32);
+}
+
+fn js_error(e: lib3mf_core::error::Lib3mfError) -> JsError {
+    JsError::new(&e.to_string())
+}
+
+/// Returns control to the event loop until the next macrotask.
+///
+/// Resolves through `setTimeout`, which windows and workers both provide, so the browser can
+/// repaint and handle input before the caller continues.
+async fn yield_now() -> Result<(), JsError> {
+    let promise = Promise::new(&mut |resolve, _| set_timeout(&resolve, 0));
+    JsFuture::from(promise)
+        .await
+        .map(|_| ())
+        .map_err(|e| JsError::new(&format!("Failed to yield to the event loop: {:?}", e)))
 }
 
 /// Set up better panic messages for debugging in browser console.
@@ -115,6 +182,52 @@ pub fn set_panic_hook() {
     console_error_panic_hook::set_once();
 }
 
+/// Name of the feature set this module was built with.
+///
+/// Returns `"full"` with the `writer`, `vendor` and `converters` features (the default
+/// build), `"viewer"` with none of them, and `"custom"` otherwise.
+///
+/// # JavaScript Usage
+///
+/// ```javascript
+/// if (build_profile() === 'viewer') {
+///     saveButton.disabled = true;
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn build_profile() -> String {
+    let features = capabilities();
+    match features.len() {
+        3 => "full",
+        0 => "viewer",
+        _ => "custom",
+    }
+    .to_string()
+}
+
+/// Optional features compiled into this module: any of `"writer"`, `"vendor"` and
+/// `"converters"`.
+///
+/// # JavaScript Usage
+///
+/// ```javascript
+/// const canSave = capabilities().includes('writer');
+/// ```
+#[wasm_bindgen]
+pub fn capabilities() -> Vec<String> {
+    let mut features = Vec::new();
+    if cfg!(feature = "writer") {
+        features.push("writer".to_string());
+    }
+    if cfg!(feature = "vendor") {
+        features.push("vendor".to_string());
+    }
+    if cfg!(feature = "converters") {
+        features.push("converters".to_string());
+    }
+    features
+}
+
 /// WebAssembly wrapper around the core 3MF Model.
 ///
 /// This struct provides JavaScript-accessible methods for working with 3MF files
@@ -246,40 +359,174 @@ impl WasmModel {
     /// ```
     #[wasm_bindgen]
     pub fn from_bytes(data: &[u8]) -> Result<WasmModel, JsError> {
-        use lib3mf_core::{
-            archive::{ArchiveReader, ZipArchiver, find_model_path},
-            parser::parse_model,
-        };
-        use std::io::Cursor;
-
         let cursor = Cursor::new(data.to_vec());
-        let mut archiver = ZipArchiver::new(cursor).map_err(|e| JsError::new(&e.to_string()))?;
+        let mut archiver = ZipArchiver::new(cursor).map_err(js_error)?;
+        let model_path = find_model_path(&mut archiver).map_err(js_error)?;
+        let model_data = archiver.read_entry(&model_path).map_err(js_error)?;
+        let model = parse_model(Cursor::new(model_data)).map_err(js_error)?;
 
-        let model_path =
-            find_model_path(&mut archiver).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(WasmModel { inner: model })
+    }
 
-        let model_data = archiver
-            .read_entry(&model_path)
-            .map_err(|e| JsError::new(&e.to_string()))?;
+    /// Parse a 3MF file from an `ArrayBuffer`, such as one transferred from another thread
+    /// with `postMessage`.
+    ///
+    /// Equivalent to `WasmModel.from_bytes(new Uint8Array(buffer))`.
+    ///
+    /// # JavaScript Usage
+    ///
+    /// ```javascript
+    /// // In a worker
+    /// self.onmessage = (event) => {
+    ///     const model = WasmModel.from_array_buffer(event.data);
+    ///     self.postMessage(model.object_count());
+    /// };
+    /// ```
+    #[wasm_bindgen]
+    pub fn from_array_buffer(buffer: &ArrayBuffer) -> Result<WasmModel, JsError> {
+        Self::from_bytes(&Uint8Array::new(buffer).to_vec())
+    }
 
-        let cursor_xml = Cursor::new(model_data);
-        let model = parse_model(cursor_xml).map_err(|e| JsError::new(&e.to_string()))?;
+    /// Parse a 3MF file without blocking the event loop for the whole parse.
+    ///
+    /// Does the same work as [`WasmModel::from_bytes()`], but yields to the event loop after
+    /// opening the archive and after decompressing the model part, so the page can repaint
+    /// between phases. The XML parse itself still runs in one piece; for very large files,
+    /// parse in a worker (see the crate documentation).
+    ///
+    /// # JavaScript Usage
+    ///
+    /// ```javascript
+    /// const buffer = await file.arrayBuffer();
+    /// const model = await WasmModel.from_bytes_async(new Uint8Array(buffer));
+    /// ```
+    #[wasm_bindgen]
+    pub async fn from_bytes_async(data: Vec<u8>) -> Result<WasmModel, JsError> {
+        let mut archiver = ZipArchiver::new(Cursor::new(data)).map_err(js_error)?;
+        let model_path = find_model_path(&mut archiver).map_err(js_error)?;
+        yield_now().await?;
+
+        let model_data = archiver.read_entry(&model_path).map_err(js_error)?;
+        drop(archiver);
+        yield_now().await?;
+
+        let model = parse_model(Cursor::new(model_data)).map_err(js_error)?;
+        Ok(WasmModel { inner: model })
+    }
+
+    /// Write the model as a 3MF package into a new `ArrayBuffer`.
+    ///
+    /// The buffer can be transferred to another thread with `postMessage` without copying.
+    /// Textures, thumbnails and other attachments are not loaded when parsing, so only the
+    /// model part and the parts the writer generates are included.
+    ///
+    /// # JavaScript Usage
+    ///
+    /// ```javascript
+    /// const bytes = model.to_array_buffer();
+    /// const blob = new Blob([bytes], { type: 'model/3mf' });
+    /// ```
+    ///
+    /// Only available with the `writer` feature; `capabilities()` includes `"writer"` when
+    /// it is.
+    #[cfg(feature = "writer")]
+    #[wasm_bindgen]
+    pub fn to_array_buffer(&self) -> Result<ArrayBuffer, JsError> {
+        let mut package = Cursor::new(Vec::new());
+        self.inner.write(&mut package).map_err(js_error)?;
+        Ok(Uint8Array::from(package.get_ref().as_slice()).buffer())
+    }
 
+    /// Import an STL file (binary or ASCII, detected from the content).
+    ///
+    /// A binary STL becomes one mesh object, an ASCII STL one per solid. STL has no unit;
+    /// the model uses millimeters.
+    ///
+    /// Only available with the `converters` feature.
+    ///
+    /// # JavaScript Usage
+    ///
+    /// ```javascript
+    /// const model = WasmModel.from_stl(new Uint8Array(await file.arrayBuffer()));
+    /// const package = new Blob([model.to_array_buffer()], { type: 'model/3mf' });
+    /// ```
+    #[cfg(feature = "converters")]
+    #[wasm_bindgen]
+    pub fn from_stl(data: &[u8]) -> Result<WasmModel, JsError> {
+        let model = StlImporter::read(Cursor::new(data)).map_err(js_error)?;
         Ok(WasmModel { inner: model })
     }
 
+    /// Export the meshes placed by the build as a binary STL file.
+    ///
+    /// Build item transforms are applied and everything is merged into one solid. Geometry
+    /// without a mesh, and colors and materials, are not exported.
+    ///
+    /// Only available with the `converters` feature.
+    ///
+    /// # JavaScript Usage
+    ///
+    /// ```javascript
+    /// const stl = model.to_stl();
+    /// const blob = new Blob([stl], { type: 'model/stl' });
+    /// ```
+    #[cfg(feature = "converters")]
+    #[wasm_bindgen]
+    pub fn to_stl(&self) -> Result<Uint8Array, JsError> {
+        let mut stl = Vec::new();
+        StlExporter::default()
+            .write(&self.inner, &mut stl)
+            .map_err(js_error)?;
+        Ok(Uint8Array::from(stl.as_slice()))
+    }
+
+    /// Import an OBJ file. Materials from `.mtl` files are not loaded, as the browser has
+    /// only the one file.
+    ///
+    /// Only available with the `converters` feature.
+    ///
+    /// # JavaScript Usage
+    ///
+    /// ```javascript
+    /// const model = WasmModel.from_obj(new Uint8Array(await file.arrayBuffer()));
+    /// ```
+    #[cfg(feature = "converters")]
+    #[wasm_bindgen]
+    pub fn from_obj(data: &[u8]) -> Result<WasmModel, JsError> {
+        let model = ObjImporter::read(data).map_err(js_error)?;
+        Ok(WasmModel { inner: model })
+    }
+
+    /// Export the meshes placed by the build as OBJ text.
+    ///
+    /// Only available with the `converters` feature.
+    ///
+    /// # JavaScript Usage
+    ///
+    /// ```javascript
+    /// const obj = model.to_obj();
+    /// const blob = new Blob([obj], { type: 'model/obj' });
+    /// ```
+    #[cfg(feature = "converters")]
+    #[wasm_bindgen]
+    pub fn to_obj(&self) -> Result<Uint8Array, JsError> {
+        let mut obj = Vec::new();
+        ObjExporter::write(&self.inner, &mut obj).map_err(js_error)?;
+        Ok(Uint8Array::from(obj.as_slice()))
+    }
+
     /// Get the unit of measurement used in the model.
     ///
-    /// Returns the unit as a string for display in JavaScript.
+    /// Returns the unit as spelled in the 3MF `unit` attribute.
     ///
     /// # Possible Return Values
     ///
-    /// - `"Millimeter"` (default, most common)
-    /// - `"Centimeter"`
-    /// - `"Inch"`
-    /// - `"Foot"`
-    /// - `"Meter"`
-    /// - `"MicroMeter"`
+    /// - `"millimeter"` (default, most common)
+    /// - `"centimeter"`
+    /// - `"inch"`
+    /// - `"foot"`
+    /// - `"meter"`
+    /// - `"micron"`
     ///
     /// # JavaScript Usage
     ///
@@ -289,7 +536,7 @@ impl WasmModel {
     /// ```
     #[wasm_bindgen]
     pub fn unit(&self) -> String {
-        format!("{:?}", self.inner.unit)
+        self.inner.unit.to_string()
     }
 
     /// Get the total number of objects in the model resources.